@@ -30,6 +30,10 @@ pub enum FileFormat {
     Orc,
     /// Excel (.xls, .xlsx, .xlsm, .xlsb)
     Excel,
+    /// SQLite database (.db, .sqlite, .sqlite3)
+    Sqlite,
+    /// Length-delimited protobuf records (.pb); needs --proto
+    Protobuf,
 }
 
 impl FileFormat {
@@ -53,9 +57,39 @@ impl FileFormat {
             "avro" => Some(Self::Avro),
             "orc" => Some(Self::Orc),
             "xls" | "xlsx" | "xlsm" | "xlsb" => Some(Self::Excel),
+            "db" | "sqlite" | "sqlite3" => Some(Self::Sqlite),
+            "pb" => Some(Self::Protobuf),
             _ => None,
         }
     }
+
+    /// File extension for this format (the one detection maps back to it).
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Parquet => "parquet",
+            Self::Csv => "csv",
+            Self::Tsv => "tsv",
+            Self::Psv => "psv",
+            Self::Json => "json",
+            Self::Jsonl => "jsonl",
+            Self::Arrow => "arrow",
+            Self::Avro => "avro",
+            Self::Orc => "orc",
+            Self::Excel => "xlsx",
+            Self::Sqlite => "sqlite",
+            Self::Protobuf => "pb",
+        }
+    }
+}
+
+/// How multiple input files are combined into one table.
+#[derive(Debug, Clone, Copy, Default, ValueEnum, PartialEq, Eq)]
+pub enum ConcatMode {
+    /// Stack files with the same format and schema (default)
+    #[default]
+    Vertical,
+    /// Union the schemas: columns missing from a file are filled with nulls. Each file may have its own format
+    Diagonal,
 }
 
 /// Compression format for data files
@@ -107,8 +141,8 @@ impl CompressionFormat {
     long_about = include_str!("../long_about.txt")
 )]
 pub struct Args {
-    /// Path(s) to the data file(s) to open.
-    /// Multiple files of the same format are concatenated into one table (not required with --generate-config, --clear-cache, or --remove-templates)
+    /// Path(s) to the data file(s) to open, or `-` to read from standard input (requires --format).
+    /// Multiple files of the same format are concatenated into one table (not required with --generate-config, --clear-cache, or --remove-templates, or when data is piped in)
     #[arg(required_unless_present_any = ["generate_config", "clear_cache", "remove_templates"], num_args = 1.., value_name = "PATH")]
     pub paths: Vec<std::path::PathBuf>,
 
@@ -132,6 +166,18 @@ pub struct Args {
     #[arg(long = "delimiter")]
     pub delimiter: Option<u8>,
 
+    /// Quote character of a delimited text file (default: "). Use "none" to read quotes as ordinary characters
+    #[arg(long = "quote-char", value_name = "CHAR", value_parser = parse_quote_char)]
+    pub quote_char: Option<QuoteChar>,
+
+    /// Escape character before a quote inside a quoted field (e.g. \ for \"). Default: quotes are escaped by doubling them
+    #[arg(long = "escape-char", value_name = "CHAR", value_parser = parse_ascii_char)]
+    pub escape_char: Option<u8>,
+
+    /// Skip lines of a delimited text file starting with this prefix (e.g. #)
+    #[arg(long = "comment-prefix", value_name = "PREFIX")]
+    pub comment_prefix: Option<String>,
+
     /// Number of rows to use when inferring CSV schema (default: 1000). Larger values reduce risk of wrong type (e.g. int then N/A).
     #[arg(long = "infer-schema-length", value_name = "N")]
     pub infer_schema_length: Option<usize>,
@@ -140,10 +186,22 @@ pub struct Args {
     #[arg(long = "ignore-errors", value_name = "BOOL", value_parser = clap::value_parser!(bool))]
     pub ignore_errors: Option<bool>,
 
+    /// Leave out CSV and JSON Lines rows that don't parse (wrong number of fields, a value of the wrong type) instead of failing the load, and list them in the Load report panel (default: false)
+    #[arg(long = "skip-bad-rows", default_missing_value = "true", num_args = 0..=1, value_parser = clap::value_parser!(bool))]
+    pub skip_bad_rows: Option<bool>,
+
     /// Treat these values as null when reading CSV. Use once per value; no "=" means all columns, COL=VAL means column COL only (first "=" separates column from value). Example: --null-value NA --null-value amount=
     #[arg(long = "null-value", value_name = "VAL")]
     pub null_value: Vec<String>,
 
+    /// Read these CSV columns as the given types instead of inferring them, as COL:TYPE pairs separated by commas (or repeat the option). Types: str, i64, i32, u64, u32, f64, f32, bool, date, datetime, time. Example: --dtypes zip:str,amount:f64
+    #[arg(long = "dtypes", value_name = "COL:TYPE", value_delimiter = ',')]
+    pub dtypes: Vec<String>,
+
+    /// Before reading a CSV, list its columns with their inferred types to change them (the same as pressing K after loading)
+    #[arg(long = "adjust-schema", action)]
+    pub adjust_schema: bool,
+
     /// Specify the compression format explicitly (gzip, zstd, bzip2, xz)
     /// If not specified, compression is auto-detected from file extension.
     #[arg(long = "compression", value_enum)]
@@ -158,6 +216,11 @@ pub struct Args {
     #[arg(long = "debug", action)]
     pub debug: bool,
 
+    /// Start with built-in defaults: skip loading config, templates, cache and theme, and use minimal rendering.
+    /// Use this to check whether a crash or misrender comes from your configuration or from datui itself.
+    #[arg(long = "safe-mode", action)]
+    pub safe_mode: bool,
+
     /// Enable Hive-style partitioning for directory or glob paths; ignored for a single file
     #[arg(long = "hive", action)]
     pub hive: bool,
@@ -190,6 +253,30 @@ pub struct Args {
     #[arg(long = "sheet", value_name = "SHEET")]
     pub excel_sheet: Option<String>,
 
+    /// Excel cells to load: a range (e.g. A3:F200, A3 for everything from A3 on, or Sales!A3:F200) or a name defined in the workbook
+    #[arg(long = "excel-range", value_name = "RANGE")]
+    pub excel_range: Option<String>,
+
+    /// Excel sheet row (as numbered in Excel, from 1) holding the column names. Rows above it are skipped
+    #[arg(long = "excel-header-row", value_name = "ROW", value_parser = clap::value_parser!(u32).range(1..))]
+    pub excel_header_row: Option<u32>,
+
+    /// Database table (or view) to load. Without it, a SQLite database with several tables shows a table picker
+    #[arg(long = "table", value_name = "TABLE")]
+    pub table: Option<String>,
+
+    /// SQL query whose result to load from a SQLite, Postgres or MySQL database instead of a table, e.g. "SELECT * FROM orders WHERE total > 100"
+    #[arg(long = "sql", value_name = "QUERY", conflicts_with = "table")]
+    pub sql_query: Option<String>,
+
+    /// .proto schema of a file of length-delimited protobuf records. Implies --format protobuf
+    #[arg(long = "proto", value_name = "FILE")]
+    pub proto: Option<std::path::PathBuf>,
+
+    /// Protobuf message type of each record (e.g. MyMsg or my.pkg.MyMsg). Needed when the schema has several messages
+    #[arg(long = "message", value_name = "NAME", requires = "proto")]
+    pub proto_message: Option<String>,
+
     /// Clear all cache data and exit
     #[arg(long = "clear-cache", action)]
     pub clear_cache: bool,
@@ -198,10 +285,113 @@ pub struct Args {
     #[arg(long = "template")]
     pub template: Option<String>,
 
+    /// Run a Rhai script on the data once it is loaded: the script gets the data as `df` and
+    /// returns the frame to show (the same as picking it from the Scripts menu, `$`)
+    #[arg(long = "script", value_name = "PATH")]
+    pub script: Option<std::path::PathBuf>,
+
+    /// Open the data with a query, e.g. "select name, price where price > 10" (replaces the
+    /// --template's query)
+    #[arg(long = "query", value_name = "QUERY")]
+    pub query: Option<String>,
+
+    /// Keep the rows passing a filter written as `column operator value`, e.g. "price > 10" or
+    /// "name contains Ann"; repeat for more (rows must pass all of them)
+    #[arg(long = "filter", value_name = "FILTER")]
+    pub filter: Vec<String>,
+
+    /// Sort by column(s); comma-separated or repeated
+    #[arg(long = "sort", value_name = "COL", value_delimiter = ',')]
+    pub sort: Vec<String>,
+
+    /// Sort in descending order (with --sort)
+    #[arg(long = "descending", action, requires = "sort")]
+    pub descending: bool,
+
+    /// Load the data, apply --template, --query, --filter, --sort and --script, write the result
+    /// to --export and exit, without starting the terminal UI
+    #[arg(long = "headless", action, requires = "export")]
+    pub headless: bool,
+
+    /// File to write with --headless. Its extension picks the format (csv, parquet, json,
+    /// ndjson, arrow, avro, xlsx, db); csv, json and ndjson may add .gz, .zst, .bz2 or .xz
+    #[arg(long = "export", value_name = "PATH", requires = "headless")]
+    pub export: Option<std::path::PathBuf>,
+
+    /// Draw the table (or with --chart, a chart) as the app would, write it to PATH and exit,
+    /// without starting the terminal UI. The extension picks the format: txt, svg or png
+    #[arg(long = "render", value_name = "PATH", conflicts_with = "headless")]
+    pub render: Option<std::path::PathBuf>,
+
+    /// Rows of data to draw with --render
+    #[arg(
+        long = "rows",
+        value_name = "N",
+        default_value_t = 25,
+        requires = "render"
+    )]
+    pub rows: u16,
+
+    /// Width of the drawing with --render, in terminal columns
+    #[arg(
+        long = "width",
+        value_name = "COLS",
+        default_value_t = 120,
+        requires = "render"
+    )]
+    pub width: u16,
+
+    /// With --render, draw a chart instead of the table: the x column, then the y column(s),
+    /// comma-separated
+    #[arg(
+        long = "chart",
+        value_name = "COLS",
+        value_delimiter = ',',
+        requires = "render"
+    )]
+    pub chart: Vec<String>,
+
+    /// Write a profiling report of the data (schema, nulls, distributions, top values,
+    /// correlations and warnings) to PATH and exit, without starting the terminal UI. The
+    /// extension picks the format: html or md
+    #[arg(
+        long = "profile",
+        value_name = "PATH",
+        conflicts_with_all = ["headless", "render"]
+    )]
+    pub profile: Option<std::path::PathBuf>,
+
     /// Remove all templates and exit
     #[arg(long = "remove-templates", action)]
     pub remove_templates: bool,
 
+    /// Print a columns × files matrix of column presence and dtype for PATH(s) and exit.
+    /// Accepts files, directories, or a quoted pattern such as 'data/*.parquet'
+    #[arg(long = "schema-diff", action)]
+    pub schema_diff: bool,
+
+    /// Compare two files and show the rows added, removed and changed from the first to the
+    /// second (`--diff OLD NEW`). Rows are matched by --diff-key, or by position without it
+    #[arg(long = "diff", action)]
+    pub diff: bool,
+
+    /// Column(s) identifying a row in both files for --diff; comma-separated or repeated
+    #[arg(
+        long = "diff-key",
+        value_name = "COL",
+        value_delimiter = ',',
+        requires = "diff"
+    )]
+    pub diff_key: Vec<String>,
+
+    /// How to combine multiple paths: vertical (same schema, default) or diagonal (union of columns, missing ones null)
+    #[arg(long = "concat", value_name = "MODE")]
+    pub concat: Option<ConcatMode>,
+
+    /// Add a __source_file__ column with the path of the file each row came from, when opening multiple paths or a Hive dataset
+    #[arg(long = "source-column", action)]
+    pub source_column: bool,
+
     /// When set, datasets with this many or more rows are sampled for analysis (faster, less memory).
     /// Overrides config [performance] sampling_threshold. Use 0 to disable sampling (full dataset) for this run.
     /// When omitted, config or full-dataset mode is used.
@@ -238,6 +428,10 @@ pub struct Args {
     #[arg(long = "column-colors", value_name = "BOOL", value_parser = clap::value_parser!(bool))]
     pub column_colors: Option<bool>,
 
+    /// Locale for displaying dates and numbers (e.g. en-US, de-DE, iso). Display only; data is unchanged. Overrides config [display] locale.
+    #[arg(long = "locale", value_name = "LOCALE")]
+    pub locale: Option<String>,
+
     /// Generate default configuration file at ~/.config/datui/config.toml
     #[arg(long = "generate-config", action)]
     pub generate_config: bool,
@@ -261,6 +455,35 @@ pub struct Args {
     /// S3 region (overrides config and AWS_REGION). Example: us-east-1
     #[arg(long = "s3-region", value_name = "REGION")]
     pub s3_region: Option<String>,
+
+    /// Azure storage account for az:// and abfss:// URLs (overrides config and AZURE_STORAGE_ACCOUNT_NAME)
+    #[arg(long = "azure-account", value_name = "ACCOUNT")]
+    pub azure_account: Option<String>,
+
+    /// Azure storage account key (overrides config and AZURE_STORAGE_ACCOUNT_KEY)
+    #[arg(long = "azure-key", value_name = "KEY")]
+    pub azure_key: Option<String>,
+}
+
+/// One ASCII character, e.g. for `--escape-char`.
+fn parse_ascii_char(s: &str) -> Result<u8, String> {
+    match s.as_bytes() {
+        [c] if c.is_ascii() => Ok(*c),
+        _ => Err(format!("expected a single ASCII character, got '{s}'")),
+    }
+}
+
+/// `--quote-char`: a quote character, or None for no quoting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuoteChar(pub Option<u8>);
+
+/// One ASCII character, or "none".
+fn parse_quote_char(s: &str) -> Result<QuoteChar, String> {
+    if s.eq_ignore_ascii_case("none") {
+        Ok(QuoteChar(None))
+    } else {
+        parse_ascii_char(s).map(|c| QuoteChar(Some(c)))
+    }
 }
 
 /// Escape `|` and newlines for use in markdown table cells.
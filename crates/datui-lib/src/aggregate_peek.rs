@@ -0,0 +1,229 @@
+//! Quick aggregates (count, sum, mean, min, max) for a single column of the current view.
+//!
+//! Computed with one lazy aggregation over the filtered/queried LazyFrame. When the view has at
+//! least `sampling_threshold` rows, the aggregates are computed on a sample instead and the
+//! result says so.
+
+use crate::locale::DisplayLocale;
+use crate::sampling::{sample_lazy, SamplingStrategy};
+use crate::statistics::collect_lazy;
+use color_eyre::Result;
+use polars::prelude::*;
+
+/// Aggregates for one column. Values that don't apply to the column's type are None.
+#[derive(Debug, Clone)]
+pub struct AggregatePeek {
+    pub column: String,
+    pub dtype: DataType,
+    /// Non-null values.
+    pub count: usize,
+    pub null_count: usize,
+    pub sum: Option<AnyValue<'static>>,
+    pub mean: Option<f64>,
+    pub min: Option<AnyValue<'static>>,
+    pub max: Option<AnyValue<'static>>,
+    /// Rows in the current view, or in the selected rows.
+    pub total_rows: usize,
+    /// (offset, len) of the selected rows the aggregates cover; None for the whole view.
+    pub rows: Option<(usize, usize)>,
+    /// Rows the aggregates were computed from, when sampled.
+    pub sample_size: Option<usize>,
+}
+
+pub(crate) fn has_sum(dtype: &DataType) -> bool {
+    dtype.is_numeric() || dtype.is_bool()
+}
+
+pub(crate) fn has_min_max(dtype: &DataType) -> bool {
+    has_sum(dtype) || dtype.is_temporal() || matches!(dtype, DataType::String)
+}
+
+pub(crate) fn first_value(df: &DataFrame, name: &str) -> Option<AnyValue<'static>> {
+    let value = df.column(name).ok()?.get(0).ok()?.into_static();
+    (!value.is_null()).then_some(value)
+}
+
+impl AggregatePeek {
+    /// Aggregate `column` of `lf`, which has `total_rows` rows. Samples when
+    /// `sampling_threshold` is set and `total_rows` reaches it.
+    pub fn compute(
+        lf: &LazyFrame,
+        column: &str,
+        total_rows: usize,
+        sampling_threshold: Option<usize>,
+        sampling: &SamplingStrategy,
+        polars_streaming: bool,
+    ) -> Result<Self> {
+        let selected = lf.clone().select([col(column)]);
+        let dtype = selected
+            .clone()
+            .collect_schema()?
+            .get(column)
+            .cloned()
+            .unwrap_or(DataType::Null);
+
+        let (source, sample_size) = match sampling_threshold {
+            Some(threshold) if total_rows >= threshold => {
+                let sample = sample_lazy(lf, total_rows, threshold, sampling, polars_streaming)?;
+                let df = collect_lazy(sample.select([col(column)]), polars_streaming)?;
+                let n = df.height();
+                (df.lazy(), Some(n))
+            }
+            _ => (selected, None),
+        };
+
+        let mut exprs = vec![
+            col(column).count().alias("count"),
+            col(column).null_count().alias("null_count"),
+        ];
+        if has_sum(&dtype) {
+            exprs.push(col(column).sum().alias("sum"));
+            exprs.push(col(column).cast(DataType::Float64).mean().alias("mean"));
+        }
+        if has_min_max(&dtype) {
+            exprs.push(col(column).min().alias("min"));
+            exprs.push(col(column).max().alias("max"));
+        }
+        let df = collect_lazy(source.select(exprs), polars_streaming)?;
+
+        let count_of = |name: &str| {
+            first_value(&df, name)
+                .and_then(|v| v.extract::<usize>())
+                .unwrap_or(0)
+        };
+        Ok(Self {
+            column: column.to_string(),
+            count: count_of("count"),
+            null_count: count_of("null_count"),
+            sum: first_value(&df, "sum"),
+            mean: first_value(&df, "mean").and_then(|v| v.extract::<f64>()),
+            min: first_value(&df, "min"),
+            max: first_value(&df, "max"),
+            total_rows,
+            rows: None,
+            sample_size,
+            dtype,
+        })
+    }
+
+    /// Mark the aggregates as covering the selected rows `rows` ((offset, len) in the view).
+    pub fn for_rows(mut self, rows: Option<(usize, usize)>) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// (label, value) lines for the popup, formatted with `locale`. "—" marks values that
+    /// don't apply to the column's type or are all null.
+    pub fn lines(&self, locale: &DisplayLocale) -> Vec<(&'static str, String)> {
+        let value = |v: &Option<AnyValue<'static>>| match v {
+            Some(v) => locale.format_value(v).into_owned(),
+            None => "—".to_string(),
+        };
+        let integer = |n: usize| {
+            locale
+                .format_number(&n.to_string())
+                .unwrap_or_else(|| n.to_string())
+        };
+        let mean = match self.mean {
+            Some(m) if m.is_finite() => {
+                let text = format!("{m:.4}");
+                locale.format_number(&text).unwrap_or(text)
+            }
+            Some(m) => m.to_string(),
+            None => "—".to_string(),
+        };
+        vec![
+            ("Count", integer(self.count)),
+            ("Nulls", integer(self.null_count)),
+            ("Sum", value(&self.sum)),
+            ("Mean", mean),
+            ("Min", value(&self.min)),
+            ("Max", value(&self.max)),
+        ]
+    }
+
+    /// Footer describing what the aggregates cover, e.g. "sample of 1,000 / 2,000,000 rows" or
+    /// "rows 11–20".
+    pub fn coverage(&self, locale: &DisplayLocale) -> String {
+        let integer = |n: usize| {
+            locale
+                .format_number(&n.to_string())
+                .unwrap_or_else(|| n.to_string())
+        };
+        match (self.sample_size, self.rows) {
+            (Some(n), _) => format!(
+                "sample of {} / {} rows",
+                integer(n),
+                integer(self.total_rows)
+            ),
+            (None, Some((offset, len))) => {
+                format!("rows {}–{}", integer(offset + 1), integer(offset + len))
+            }
+            (None, None) => format!("{} rows", integer(self.total_rows)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> LazyFrame {
+        df!(
+            "n" => [Some(1i64), Some(2), None, Some(5)],
+            "s" => [Some("b"), Some("a"), Some("c"), None],
+        )
+        .unwrap()
+        .lazy()
+    }
+
+    #[test]
+    fn test_numeric_column() {
+        let peek =
+            AggregatePeek::compute(&frame(), "n", 4, None, &SamplingStrategy::default(), false)
+                .unwrap();
+        assert_eq!(peek.count, 3);
+        assert_eq!(peek.null_count, 1);
+        assert_eq!(peek.sum, Some(AnyValue::Int64(8)));
+        assert_eq!(peek.min, Some(AnyValue::Int64(1)));
+        assert_eq!(peek.max, Some(AnyValue::Int64(5)));
+        assert!((peek.mean.unwrap() - 8.0 / 3.0).abs() < 1e-9);
+        assert_eq!(peek.sample_size, None);
+        assert_eq!(peek.coverage(&DisplayLocale::ISO), "4 rows");
+        let peek = peek.for_rows(Some((10, 4)));
+        assert_eq!(peek.coverage(&DisplayLocale::ISO), "rows 11–14");
+    }
+
+    #[test]
+    fn test_string_column_has_no_sum() {
+        let peek =
+            AggregatePeek::compute(&frame(), "s", 4, None, &SamplingStrategy::default(), false)
+                .unwrap();
+        assert_eq!(peek.count, 3);
+        assert!(peek.sum.is_none() && peek.mean.is_none());
+        let lines = peek.lines(&DisplayLocale::ISO);
+        assert_eq!(lines[2], ("Sum", "—".to_string()));
+        assert_eq!(lines[4], ("Min", "a".to_string()));
+        assert_eq!(lines[5], ("Max", "c".to_string()));
+    }
+
+    #[test]
+    fn test_samples_at_threshold() {
+        let lf = df!("x" => (0..10_000i64).collect::<Vec<_>>())
+            .unwrap()
+            .lazy();
+        let peek = AggregatePeek::compute(
+            &lf,
+            "x",
+            10_000,
+            Some(100),
+            &SamplingStrategy::default(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(peek.sample_size, Some(100));
+        assert_eq!(peek.count, 100);
+        let locale = DisplayLocale::from_name("en-US").unwrap();
+        assert_eq!(peek.coverage(&locale), "sample of 100 / 10,000 rows");
+    }
+}
@@ -0,0 +1,192 @@
+//! Analysis results as tables for the export pipeline (`E` in the analysis modal): the
+//! Describe statistics, the distribution fits and the correlation matrix. Columns follow the
+//! headers on screen, but values keep full precision; statistics that do not apply are null.
+
+use crate::analysis_modal::AnalysisTool;
+use crate::statistics::{AnalysisResults, CorrelationMatrix};
+use polars::prelude::*;
+
+/// The results table of `tool`, or None when the tool has no exportable table (or the
+/// correlation matrix was not computed).
+pub fn results_frame(
+    tool: AnalysisTool,
+    results: &AnalysisResults,
+) -> Option<PolarsResult<DataFrame>> {
+    match tool {
+        AnalysisTool::Describe => Some(describe_frame(results)),
+        AnalysisTool::DistributionAnalysis => Some(distribution_frame(results)),
+        AnalysisTool::CorrelationMatrix => {
+            results.correlation_matrix.as_ref().map(correlation_frame)
+        }
+        AnalysisTool::TimeSeriesGaps
+        | AnalysisTool::ColumnCardinality
+        | AnalysisTool::MissingData
+        | AnalysisTool::DuplicateRows
+        | AnalysisTool::Crosstab
+        | AnalysisTool::Plugin(_) => None,
+    }
+}
+
+/// One row per column. Min and Max are text because string columns have them too.
+pub fn describe_frame(results: &AnalysisResults) -> PolarsResult<DataFrame> {
+    let stats = &results.column_statistics;
+    let numeric = |f: fn(&crate::statistics::NumericStatistics) -> f64| {
+        stats
+            .iter()
+            .map(|s| s.numeric_stats.as_ref().map(f))
+            .collect::<Vec<_>>()
+    };
+    let bound =
+        |value: fn(&crate::statistics::NumericStatistics) -> f64,
+         text: fn(&crate::statistics::CategoricalStatistics) -> Option<String>| {
+            stats
+                .iter()
+                .map(|s| match (&s.numeric_stats, &s.categorical_stats) {
+                    (Some(n), _) => Some(value(n).to_string()),
+                    (None, Some(c)) => text(c),
+                    (None, None) => None,
+                })
+                .collect::<Vec<_>>()
+        };
+    df!(
+        "Column" => stats.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+        "Type" => stats.iter().map(|s| s.dtype.to_string()).collect::<Vec<_>>(),
+        "Count" => stats.iter().map(|s| s.count as u64).collect::<Vec<_>>(),
+        "Nulls" => stats.iter().map(|s| s.null_count as u64).collect::<Vec<_>>(),
+        "Mean" => numeric(|n| n.mean),
+        "Std" => numeric(|n| n.std),
+        "Min" => bound(|n| n.min, |c| c.min.clone()),
+        "25%" => numeric(|n| n.q25),
+        "50%" => numeric(|n| n.median),
+        "75%" => numeric(|n| n.q75),
+        "Max" => bound(|n| n.max, |c| c.max.clone()),
+    )
+}
+
+/// One row per numeric column with its best-fitting distribution and shape statistics.
+pub fn distribution_frame(results: &AnalysisResults) -> PolarsResult<DataFrame> {
+    let analyses = &results.distribution_analyses;
+    let values = |f: fn(&crate::statistics::DistributionAnalysis) -> f64| {
+        analyses.iter().map(f).collect::<Vec<_>>()
+    };
+    df!(
+        "Column" => analyses.iter().map(|a| a.column_name.as_str()).collect::<Vec<_>>(),
+        "Distribution" => analyses
+            .iter()
+            .map(|a| a.distribution_type.to_string())
+            .collect::<Vec<_>>(),
+        "P-value" => values(|a| a.confidence),
+        "Shapiro-Wilk" => analyses
+            .iter()
+            .map(|a| a.characteristics.shapiro_wilk_stat)
+            .collect::<Vec<_>>(),
+        "SW p-value" => analyses
+            .iter()
+            .map(|a| a.characteristics.shapiro_wilk_pvalue)
+            .collect::<Vec<_>>(),
+        "CV" => values(|a| a.characteristics.coefficient_of_variation),
+        "Outliers" => analyses
+            .iter()
+            .map(|a| a.outliers.total_count as u64)
+            .collect::<Vec<_>>(),
+        "Outliers %" => values(|a| a.outliers.percentage),
+        "Skewness" => values(|a| a.characteristics.skewness),
+        "Kurtosis" => values(|a| a.characteristics.kurtosis),
+    )
+}
+
+/// The square matrix: a `Column` column of names, then one column of coefficients per name.
+pub fn correlation_frame(matrix: &CorrelationMatrix) -> PolarsResult<DataFrame> {
+    let mut columns = vec![Column::new("Column".into(), &matrix.columns)];
+    columns.extend(matrix.columns.iter().enumerate().map(|(j, name)| {
+        let values: Vec<f64> = matrix.correlations.iter().map(|row| row[j]).collect();
+        Column::new(name.as_str().into(), values)
+    }));
+    DataFrame::new(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::{CategoricalStatistics, ColumnStatistics, NumericStatistics};
+    use std::collections::HashMap;
+
+    fn results() -> AnalysisResults {
+        let numeric = NumericStatistics {
+            mean: 2.5,
+            std: 1.0,
+            min: 1.0,
+            max: 4.0,
+            median: 2.5,
+            q25: 1.75,
+            q75: 3.25,
+            percentiles: HashMap::new(),
+            skewness: 0.0,
+            kurtosis: 0.0,
+            outliers_iqr: 0,
+            outliers_zscore: 0,
+        };
+        let categorical = CategoricalStatistics {
+            unique_count: 2,
+            mode: Some("a".to_string()),
+            top_values: Vec::new(),
+            min: Some("a".to_string()),
+            max: Some("b".to_string()),
+        };
+        AnalysisResults {
+            column_statistics: vec![
+                ColumnStatistics {
+                    name: "x".to_string(),
+                    dtype: DataType::Float64,
+                    count: 4,
+                    null_count: 0,
+                    numeric_stats: Some(numeric),
+                    categorical_stats: None,
+                    distribution_info: None,
+                },
+                ColumnStatistics {
+                    name: "s".to_string(),
+                    dtype: DataType::String,
+                    count: 4,
+                    null_count: 1,
+                    numeric_stats: None,
+                    categorical_stats: Some(categorical),
+                    distribution_info: None,
+                },
+            ],
+            total_rows: 4,
+            sample_size: None,
+            sample_seed: 0,
+            correlation_matrix: Some(CorrelationMatrix {
+                columns: vec!["x".to_string(), "y".to_string()],
+                correlations: vec![vec![1.0, -0.5], vec![-0.5, 1.0]],
+                p_values: None,
+                sample_sizes: vec![vec![4, 4], vec![4, 4]],
+            }),
+            distribution_analyses: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn describe_keeps_numbers_and_text_bounds() {
+        let df = describe_frame(&results()).unwrap();
+        assert_eq!(df.height(), 2);
+        assert_eq!(df.column("Mean").unwrap().f64().unwrap().get(0), Some(2.5));
+        assert_eq!(df.column("Mean").unwrap().f64().unwrap().get(1), None);
+        let min = df.column("Min").unwrap();
+        assert_eq!(min.str().unwrap().get(0), Some("1"));
+        assert_eq!(min.str().unwrap().get(1), Some("a"));
+        assert_eq!(df.column("Nulls").unwrap().u64().unwrap().get(1), Some(1));
+    }
+
+    #[test]
+    fn correlation_is_a_square_table() {
+        let results = results();
+        let df = results_frame(AnalysisTool::CorrelationMatrix, &results)
+            .unwrap()
+            .unwrap();
+        assert_eq!(df.get_column_names(), ["Column", "x", "y"]);
+        assert_eq!(df.column("y").unwrap().f64().unwrap().get(0), Some(-0.5));
+        assert!(results_frame(AnalysisTool::MissingData, &results).is_none());
+    }
+}
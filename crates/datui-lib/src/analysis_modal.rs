@@ -1,5 +1,15 @@
+use crate::analysis_plugin;
+use crate::cardinality::CardinalityReport;
+use crate::crosstab::{Crosstab, CrosstabSpec};
+use crate::duplicates::DuplicateReport;
+use crate::missing_data::MissingDataReport;
 use crate::statistics::{AnalysisResults, DistributionType};
+use crate::stats_compare::{self, StatsBaseline};
+use crate::time_gaps::GapReport;
+use crate::widgets::text_input::TextInput;
+use polars::prelude::DataFrame;
 use ratatui::widgets::TableState;
+use std::sync::Arc;
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum AnalysisView {
@@ -15,8 +25,20 @@ pub enum AnalysisTool {
     Describe, // Column describe table
     DistributionAnalysis, // Distribution analysis table
     CorrelationMatrix,    // Correlation matrix
+    TimeSeriesGaps,       // Missing timestamps and null runs along a time column
+    ColumnCardinality,    // Columns ranked by distinct ratio and entropy
+    MissingData,          // Null counts per column and a null-pattern heatmap
+    DuplicateRows,        // Rows that repeat, in full or on key columns
+    Crosstab,             // Counts (or an aggregate) over the value pairs of two columns
+    Plugin(usize),        // A registered analysis plugin, by its index in `plugins`
 }
 
+/// Number of built-in tools in the sidebar; plugins are listed after them.
+pub const TOOL_COUNT: usize = 8;
+
+/// Starting |r| above which correlation cells are highlighted.
+pub const CORRELATION_THRESHOLD_DEFAULT: f64 = 0.7;
+
 /// Progress state for the analysis progress overlay (display only).
 #[derive(Debug, Clone)]
 pub struct AnalysisProgress {
@@ -59,10 +81,55 @@ pub struct AnalysisModal {
     pub selected_tool: Option<AnalysisTool>,
     pub selected_distribution: Option<usize>, // Selected row in distribution table
     pub selected_correlation: Option<(usize, usize)>, // Selected cell in correlation matrix (row, col)
+    /// Shade correlation cells on a diverging palette (negative to positive).
+    pub correlation_heatmap: bool,
+    /// Off-diagonal cells with |r| at or above this are highlighted.
+    pub correlation_threshold: f64,
     pub detail_section: usize, // Current section in detail view (0=Characteristics, 1=Outliers, 2=Percentiles)
     pub selected_theoretical_distribution: DistributionType, // Selected theoretical distribution for Q-Q plot
     pub distribution_selector_state: TableState,             // For distribution selector list
     pub histogram_scale: HistogramScale,                     // Scale for histogram (linear or log)
+    /// Overlay the best-fit curve on the detail histogram alongside the selected distribution.
+    pub histogram_best_fit: bool,
+    /// Time series gaps result, or the error that prevented it (e.g. no time column).
+    pub gap_results: Option<Result<GapReport, String>>,
+    pub gap_table_state: TableState, // For the gap list
+    /// Time column for gap detection; None = first date/datetime column.
+    pub gap_time_column: Option<String>,
+    /// Expected frequency in nanoseconds; None = infer from the data.
+    pub gap_frequency: Option<i64>,
+    pub gap_frequency_input: TextInput,
+    pub gap_frequency_editing: bool,
+    /// Column cardinality ranking, or the error that prevented it.
+    pub cardinality_results: Option<Result<CardinalityReport, String>>,
+    pub cardinality_table_state: TableState, // For the cardinality ranking
+    /// Null counts and pattern, or the error that prevented them.
+    pub missing_results: Option<Result<MissingDataReport, String>>,
+    pub missing_table_state: TableState, // For the per-column null list
+    /// Duplicated row groups, or the error that prevented finding them.
+    pub duplicate_results: Option<Result<DuplicateReport, String>>,
+    pub duplicate_table_state: TableState, // For the duplicate group list
+    /// Columns rows are compared on; empty = the whole row.
+    pub duplicate_keys: Vec<String>,
+    pub duplicate_keys_input: TextInput,
+    pub duplicate_keys_editing: bool,
+    /// Crosstab table, or the error that prevented it.
+    pub crosstab_results: Option<Result<Crosstab, String>>,
+    pub crosstab_table_state: TableState, // For the crosstab rows
+    pub crosstab_column_offset: usize,    // For horizontal scrolling in the crosstab
+    /// Columns of the crosstab; None until chosen (or defaulted when the tool opens).
+    pub crosstab_spec: Option<CrosstabSpec>,
+    pub crosstab_input: TextInput,
+    pub crosstab_editing: bool,
+    /// Describe results saved for comparison; kept across open/close so filters can change.
+    pub describe_baseline: Option<StatsBaseline>,
+    /// Show the Describe results side by side with the baseline.
+    pub describe_compare: bool,
+    /// Registered analysis plugins, as of opening.
+    pub plugins: Vec<Arc<dyn analysis_plugin::AnalysisTool>>,
+    /// Result of each plugin, or the error that prevented it; None until computed.
+    pub plugin_results: Vec<Option<Result<DataFrame, String>>>,
+    pub plugin_table_state: TableState, // For the selected plugin's rows
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -93,11 +160,32 @@ impl AnalysisModal {
         self.selected_tool = None; // No tool until user selects from sidebar
         self.selected_distribution = Some(0);
         self.selected_correlation = Some((0, 0));
+        self.correlation_heatmap = true;
+        self.correlation_threshold = CORRELATION_THRESHOLD_DEFAULT;
+        self.histogram_best_fit = true;
         self.detail_section = 0;
         self.computing = None;
         self.describe_results = None;
         self.distribution_results = None;
         self.correlation_results = None;
+        self.gap_results = None;
+        self.gap_table_state.select(Some(0));
+        self.gap_frequency_editing = false;
+        self.cardinality_results = None;
+        self.cardinality_table_state.select(Some(0));
+        self.missing_results = None;
+        self.missing_table_state.select(Some(0));
+        self.duplicate_results = None;
+        self.duplicate_table_state.select(Some(0));
+        self.duplicate_keys_editing = false;
+        self.crosstab_results = None;
+        self.crosstab_table_state.select(Some(0));
+        self.crosstab_column_offset = 0;
+        self.crosstab_spec = None;
+        self.crosstab_editing = false;
+        self.plugins = analysis_plugin::analysis_tools();
+        self.plugin_results = vec![None; self.plugins.len()];
+        self.plugin_table_state.select(Some(0));
         // Generate initial random seed (use 0 if system time is before UNIX_EPOCH)
         self.random_seed = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -122,6 +210,54 @@ impl AnalysisModal {
         self.describe_results = None;
         self.distribution_results = None;
         self.correlation_results = None;
+        self.gap_results = None;
+        self.gap_frequency_editing = false;
+        self.cardinality_results = None;
+        self.missing_results = None;
+        self.duplicate_results = None;
+        self.duplicate_keys_editing = false;
+        self.crosstab_results = None;
+        self.crosstab_editing = false;
+        self.plugin_results.clear();
+    }
+
+    /// Number of tools in the sidebar: the built-in ones, then the plugins.
+    pub fn tool_count(&self) -> usize {
+        TOOL_COUNT + self.plugins.len()
+    }
+
+    /// Result of the plugin at `index`, once computed.
+    pub fn plugin_result(&self, index: usize) -> Option<&Result<DataFrame, String>> {
+        self.plugin_results.get(index)?.as_ref()
+    }
+
+    /// Rows of the selected plugin's result; 0 until it is computed.
+    pub fn plugin_rows(&self) -> usize {
+        match self.selected_tool {
+            Some(AnalysisTool::Plugin(index)) => match self.plugin_result(index) {
+                Some(Ok(df)) => df.height(),
+                _ => 0,
+            },
+            _ => 0,
+        }
+    }
+
+    /// Rows in the Describe table: one per column, plus baseline-only columns when comparing.
+    pub fn describe_row_count(&self, schema_len: usize) -> usize {
+        match self.compare_rows() {
+            Some(rows) => rows.len(),
+            None => schema_len,
+        }
+    }
+
+    /// Describe results paired with the baseline, when the compare view is on.
+    pub fn compare_rows(&self) -> Option<Vec<stats_compare::CompareRow>> {
+        if !self.describe_compare {
+            return None;
+        }
+        let baseline = self.describe_baseline.as_ref()?;
+        let current = self.describe_results.as_ref()?;
+        Some(stats_compare::compare(&baseline.results, current))
     }
 
     /// Returns the cached results for the currently selected tool, if any.
@@ -130,7 +266,13 @@ impl AnalysisModal {
             Some(AnalysisTool::Describe) => self.describe_results.as_ref(),
             Some(AnalysisTool::DistributionAnalysis) => self.distribution_results.as_ref(),
             Some(AnalysisTool::CorrelationMatrix) => self.correlation_results.as_ref(),
-            None => None,
+            Some(AnalysisTool::TimeSeriesGaps)
+            | Some(AnalysisTool::ColumnCardinality)
+            | Some(AnalysisTool::MissingData)
+            | Some(AnalysisTool::DuplicateRows)
+            | Some(AnalysisTool::Crosstab)
+            | Some(AnalysisTool::Plugin(_))
+            | None => None,
         }
     }
 
@@ -156,6 +298,12 @@ impl AnalysisModal {
                 0 => AnalysisTool::Describe,
                 1 => AnalysisTool::DistributionAnalysis,
                 2 => AnalysisTool::CorrelationMatrix,
+                3 => AnalysisTool::TimeSeriesGaps,
+                4 => AnalysisTool::ColumnCardinality,
+                5 => AnalysisTool::MissingData,
+                6 => AnalysisTool::DuplicateRows,
+                7 => AnalysisTool::Crosstab,
+                idx if idx < self.tool_count() => AnalysisTool::Plugin(idx - TOOL_COUNT),
                 _ => AnalysisTool::Describe,
             });
             self.focus = AnalysisFocus::Main;
@@ -164,7 +312,7 @@ impl AnalysisModal {
 
     pub fn next_tool(&mut self) {
         if let Some(current) = self.sidebar_state.selected() {
-            let next = (current + 1).min(2);
+            let next = (current + 1).min(self.tool_count() - 1);
             self.sidebar_state.select(Some(next));
         }
     }
@@ -210,6 +358,33 @@ impl AnalysisModal {
         }
     }
 
+    /// Row of the selected gap in the current view, for jumping to it in the table.
+    pub fn selected_gap_row(&self) -> Option<usize> {
+        let report = self.gap_results.as_ref()?.as_ref().ok()?;
+        report
+            .gaps
+            .get(self.gap_table_state.selected()?)
+            .map(|g| g.row)
+    }
+
+    /// Column highlighted in the Missing Data list.
+    pub fn selected_missing_column(&self) -> Option<&str> {
+        let report = self.missing_results.as_ref()?.as_ref().ok()?;
+        report
+            .columns
+            .get(self.missing_table_state.selected()?)
+            .map(|c| c.column.as_str())
+    }
+
+    /// First row of the duplicate group highlighted in the Duplicate Rows list.
+    pub fn selected_duplicate_row(&self) -> Option<usize> {
+        let report = self.duplicate_results.as_ref()?.as_ref().ok()?;
+        report
+            .groups
+            .get(self.duplicate_table_state.selected()?)
+            .map(|g| g.first_row)
+    }
+
     pub fn close_detail(&mut self) {
         self.view = AnalysisView::Main;
         self.detail_section = 0;
@@ -236,6 +411,9 @@ impl AnalysisModal {
             Some(AnalysisTool::DistributionAnalysis) if self.distribution_column_offset > 0 => {
                 self.distribution_column_offset -= 1;
             }
+            Some(AnalysisTool::Crosstab) if self.crosstab_column_offset > 0 => {
+                self.crosstab_column_offset -= 1;
+            }
             _ => {}
         }
     }
@@ -258,6 +436,14 @@ impl AnalysisModal {
                     *offset += 1;
                 }
             }
+            Some(AnalysisTool::Crosstab) => {
+                let offset = &mut self.crosstab_column_offset;
+                if *offset + visible_columns < max_columns
+                    && *offset < max_columns.saturating_sub(1)
+                {
+                    *offset += 1;
+                }
+            }
             _ => {}
         }
     }
@@ -300,6 +486,48 @@ impl AnalysisModal {
                     self.correlation_table_state.select(Some(next_row));
                 }
             }
+            Some(AnalysisTool::TimeSeriesGaps) => {
+                let next = self
+                    .gap_table_state
+                    .selected()
+                    .map_or(0, |current| (current + 1).min(max_rows.saturating_sub(1)));
+                self.gap_table_state.select(Some(next));
+            }
+            Some(AnalysisTool::ColumnCardinality) => {
+                let next = self
+                    .cardinality_table_state
+                    .selected()
+                    .map_or(0, |current| (current + 1).min(max_rows.saturating_sub(1)));
+                self.cardinality_table_state.select(Some(next));
+            }
+            Some(AnalysisTool::MissingData) => {
+                let next = self
+                    .missing_table_state
+                    .selected()
+                    .map_or(0, |current| (current + 1).min(max_rows.saturating_sub(1)));
+                self.missing_table_state.select(Some(next));
+            }
+            Some(AnalysisTool::DuplicateRows) => {
+                let next = self
+                    .duplicate_table_state
+                    .selected()
+                    .map_or(0, |current| (current + 1).min(max_rows.saturating_sub(1)));
+                self.duplicate_table_state.select(Some(next));
+            }
+            Some(AnalysisTool::Crosstab) => {
+                let next = self
+                    .crosstab_table_state
+                    .selected()
+                    .map_or(0, |current| (current + 1).min(max_rows.saturating_sub(1)));
+                self.crosstab_table_state.select(Some(next));
+            }
+            Some(AnalysisTool::Plugin(_)) => {
+                let next = self
+                    .plugin_table_state
+                    .selected()
+                    .map_or(0, |current| (current + 1).min(max_rows.saturating_sub(1)));
+                self.plugin_table_state.select(Some(next));
+            }
             None => {}
         }
     }
@@ -335,6 +563,41 @@ impl AnalysisModal {
                     }
                 }
             }
+            Some(AnalysisTool::TimeSeriesGaps) => {
+                if let Some(current) = self.gap_table_state.selected() {
+                    self.gap_table_state.select(Some(current.saturating_sub(1)));
+                }
+            }
+            Some(AnalysisTool::ColumnCardinality) => {
+                if let Some(current) = self.cardinality_table_state.selected() {
+                    self.cardinality_table_state
+                        .select(Some(current.saturating_sub(1)));
+                }
+            }
+            Some(AnalysisTool::MissingData) => {
+                if let Some(current) = self.missing_table_state.selected() {
+                    self.missing_table_state
+                        .select(Some(current.saturating_sub(1)));
+                }
+            }
+            Some(AnalysisTool::DuplicateRows) => {
+                if let Some(current) = self.duplicate_table_state.selected() {
+                    self.duplicate_table_state
+                        .select(Some(current.saturating_sub(1)));
+                }
+            }
+            Some(AnalysisTool::Crosstab) => {
+                if let Some(current) = self.crosstab_table_state.selected() {
+                    self.crosstab_table_state
+                        .select(Some(current.saturating_sub(1)));
+                }
+            }
+            Some(AnalysisTool::Plugin(_)) => {
+                if let Some(current) = self.plugin_table_state.selected() {
+                    self.plugin_table_state
+                        .select(Some(current.saturating_sub(1)));
+                }
+            }
             None => {}
         }
     }
@@ -365,6 +628,42 @@ impl AnalysisModal {
                     self.correlation_table_state.select(Some(next_row));
                 }
             }
+            Some(AnalysisTool::TimeSeriesGaps) => {
+                if let Some(current) = self.gap_table_state.selected() {
+                    let next = (current + page_size).min(max_rows.saturating_sub(1));
+                    self.gap_table_state.select(Some(next));
+                }
+            }
+            Some(AnalysisTool::ColumnCardinality) => {
+                if let Some(current) = self.cardinality_table_state.selected() {
+                    let next = (current + page_size).min(max_rows.saturating_sub(1));
+                    self.cardinality_table_state.select(Some(next));
+                }
+            }
+            Some(AnalysisTool::MissingData) => {
+                if let Some(current) = self.missing_table_state.selected() {
+                    let next = (current + page_size).min(max_rows.saturating_sub(1));
+                    self.missing_table_state.select(Some(next));
+                }
+            }
+            Some(AnalysisTool::DuplicateRows) => {
+                if let Some(current) = self.duplicate_table_state.selected() {
+                    let next = (current + page_size).min(max_rows.saturating_sub(1));
+                    self.duplicate_table_state.select(Some(next));
+                }
+            }
+            Some(AnalysisTool::Crosstab) => {
+                if let Some(current) = self.crosstab_table_state.selected() {
+                    let next = (current + page_size).min(max_rows.saturating_sub(1));
+                    self.crosstab_table_state.select(Some(next));
+                }
+            }
+            Some(AnalysisTool::Plugin(_)) => {
+                if let Some(current) = self.plugin_table_state.selected() {
+                    let next = (current + page_size).min(max_rows.saturating_sub(1));
+                    self.plugin_table_state.select(Some(next));
+                }
+            }
             None => {}
         }
     }
@@ -395,6 +694,42 @@ impl AnalysisModal {
                     self.correlation_table_state.select(Some(prev_row));
                 }
             }
+            Some(AnalysisTool::TimeSeriesGaps) => {
+                if let Some(current) = self.gap_table_state.selected() {
+                    self.gap_table_state
+                        .select(Some(current.saturating_sub(page_size)));
+                }
+            }
+            Some(AnalysisTool::ColumnCardinality) => {
+                if let Some(current) = self.cardinality_table_state.selected() {
+                    self.cardinality_table_state
+                        .select(Some(current.saturating_sub(page_size)));
+                }
+            }
+            Some(AnalysisTool::MissingData) => {
+                if let Some(current) = self.missing_table_state.selected() {
+                    self.missing_table_state
+                        .select(Some(current.saturating_sub(page_size)));
+                }
+            }
+            Some(AnalysisTool::DuplicateRows) => {
+                if let Some(current) = self.duplicate_table_state.selected() {
+                    self.duplicate_table_state
+                        .select(Some(current.saturating_sub(page_size)));
+                }
+            }
+            Some(AnalysisTool::Crosstab) => {
+                if let Some(current) = self.crosstab_table_state.selected() {
+                    self.crosstab_table_state
+                        .select(Some(current.saturating_sub(page_size)));
+                }
+            }
+            Some(AnalysisTool::Plugin(_)) => {
+                if let Some(current) = self.plugin_table_state.selected() {
+                    self.plugin_table_state
+                        .select(Some(current.saturating_sub(page_size)));
+                }
+            }
             None => {}
         }
     }
@@ -429,6 +764,13 @@ impl AnalysisModal {
         }
     }
 
+    /// Raise or lower the correlation highlight threshold by `delta`, within 0 to 1.
+    pub fn adjust_correlation_threshold(&mut self, delta: f64) {
+        let threshold = self.correlation_threshold + delta;
+        // Round to the step so repeated presses land on 0.05 multiples
+        self.correlation_threshold = ((threshold * 20.0).round() / 20.0).clamp(0.0, 1.0);
+    }
+
     pub fn next_distribution(&mut self) {
         let max_idx = 13;
 
@@ -0,0 +1,143 @@
+//! Analysis plugins: tools from other crates, listed in the analysis sidebar after the built-in
+//! ones. A tool computes a frame from the current view on a background thread, then draws it;
+//! unless it draws its own way, the frame is shown as a table.
+//!
+//! Register tools before starting the app, e.g. from `main` or from a plugin's init function:
+//!
+//! ```no_run
+//! use datui_lib::analysis_plugin::{register_analysis_tool, AnalysisTool};
+//! use polars::prelude::*;
+//!
+//! struct RowCount;
+//!
+//! impl AnalysisTool for RowCount {
+//!     fn name(&self) -> &str {
+//!         "Row Count"
+//!     }
+//!
+//!     fn compute(&self, lf: LazyFrame) -> Result<DataFrame, String> {
+//!         lf.select([len().alias("rows")])
+//!             .collect()
+//!             .map_err(|e| e.to_string())
+//!     }
+//! }
+//!
+//! register_analysis_tool(RowCount);
+//! ```
+
+use crate::config::Theme;
+use polars::prelude::{DataFrame, LazyFrame};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Cell, Row, StatefulWidget, Table, TableState};
+use std::sync::{Arc, RwLock};
+
+/// A tool of the analysis sidebar.
+pub trait AnalysisTool: Send + Sync {
+    /// Name in the sidebar and the breadcrumb.
+    fn name(&self) -> &str;
+
+    /// Text of the help overlay (`?`) while the tool is shown.
+    fn help(&self) -> &str {
+        "No help for this tool."
+    }
+
+    /// Result of the tool on the current view (its query, filters and sort applied). Called on
+    /// a background thread; an error is shown in place of the result.
+    fn compute(&self, lf: LazyFrame) -> Result<DataFrame, String>;
+
+    /// Draw `result` in `area`. Up/Down, PageUp/PageDown and Home/End move `state` over the
+    /// result's rows. The default draws the rows as a table.
+    fn render(
+        &self,
+        result: &DataFrame,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut TableState,
+        theme: &Theme,
+    ) {
+        render_frame(result, area, buf, state, theme);
+    }
+}
+
+static TOOLS: RwLock<Vec<Arc<dyn AnalysisTool>>> = RwLock::new(Vec::new());
+
+/// Add `tool` to the analysis sidebar of every app opened from now on, after the tools
+/// registered before it.
+pub fn register_analysis_tool(tool: impl AnalysisTool + 'static) {
+    TOOLS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(Arc::new(tool));
+}
+
+/// The registered tools, in order.
+pub fn analysis_tools() -> Vec<Arc<dyn AnalysisTool>> {
+    TOOLS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Draw `df` as a table: its column names over its rows, nulls as `null`.
+pub fn render_frame(
+    df: &DataFrame,
+    area: Rect,
+    buf: &mut Buffer,
+    state: &mut TableState,
+    theme: &Theme,
+) {
+    let columns = df.get_columns();
+    let rows: Vec<Row> = (0..df.height())
+        .map(|i| {
+            Row::new(columns.iter().map(|c| {
+                let value = c.get(i).map(|v| {
+                    if v.is_null() {
+                        "null".to_string()
+                    } else {
+                        v.str_value().into_owned()
+                    }
+                });
+                Cell::from(value.unwrap_or_default())
+            }))
+        })
+        .collect();
+    let header = Row::new(columns.iter().map(|c| Cell::from(c.name().to_string()))).style(
+        Style::default()
+            .fg(theme.get("table_header"))
+            .add_modifier(Modifier::UNDERLINED),
+    );
+    let widths = vec![Constraint::Fill(1); columns.len().max(1)];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .style(Style::default().fg(theme.get("text_primary")))
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(table, area, buf, state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_render_frame_shows_headers_values_and_nulls() {
+        let df = df!("name" => ["a", "b"], "qty" => [Some(3i64), None]).unwrap();
+        let area = Rect::new(0, 0, 20, 3);
+        let mut buf = Buffer::empty(area);
+        render_frame(
+            &df,
+            area,
+            &mut buf,
+            &mut TableState::default(),
+            &Theme::from_config(&crate::config::AppConfig::default().theme).unwrap(),
+        );
+        let line = |y: u16| -> String {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect()
+        };
+        assert!(line(0).starts_with("name"));
+        assert!(line(0).contains("qty"));
+        assert!(line(1).contains('3'));
+        assert!(line(2).contains("null"));
+    }
+}
@@ -0,0 +1,225 @@
+//! Column cardinality ranking (the "Column Cardinality" analysis tool).
+//!
+//! For every column of the current view: distinct values, distinct ratio and Shannon entropy,
+//! ranked so likely keys come first and constant columns last. Helps find identifiers,
+//! categories and dead columns in an unfamiliar wide dataset. Sampled like the other tools
+//! when a sampling threshold is configured.
+
+use crate::sampling::SamplingStrategy;
+use crate::statistics::{collect_lazy, sample_dataframe};
+use color_eyre::Result;
+use polars::prelude::*;
+
+/// What a column's cardinality suggests about it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardinalityFlag {
+    /// Every value is null.
+    AllNull,
+    /// A single distinct value (nulls aside).
+    Constant,
+    /// Every row has a distinct, non-null value.
+    LikelyKey,
+}
+
+impl CardinalityFlag {
+    pub fn label(self) -> &'static str {
+        match self {
+            CardinalityFlag::AllNull => "all null",
+            CardinalityFlag::Constant => "constant",
+            CardinalityFlag::LikelyKey => "likely key",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ColumnCardinality {
+    pub column: String,
+    pub dtype: DataType,
+    /// Distinct non-null values; None for types that can't be counted (nested, binary).
+    pub distinct: Option<usize>,
+    pub null_count: usize,
+    /// Distinct values / non-null rows, in [0, 1].
+    pub distinct_ratio: Option<f64>,
+    /// Shannon entropy of the non-null values, in bits.
+    pub entropy: Option<f64>,
+    pub flag: Option<CardinalityFlag>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CardinalityReport {
+    /// Rows the report was computed from (the sample size when sampled).
+    pub rows: usize,
+    /// Rows in the view, when the report was computed on a sample.
+    pub sampled_from: Option<usize>,
+    /// Highest distinct ratio first; ties by entropy, constant and all-null columns last.
+    pub columns: Vec<ColumnCardinality>,
+}
+
+pub(crate) fn is_countable(dtype: &DataType) -> bool {
+    !matches!(
+        dtype,
+        DataType::List(_) | DataType::Binary | DataType::BinaryOffset | DataType::Struct(_)
+    )
+}
+
+/// Shannon entropy (bits) of a distribution given by its counts.
+fn entropy_bits(counts: &[u64]) -> f64 {
+    let total: u64 = counts.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+    let total = total as f64;
+    counts
+        .iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f64 / total;
+            -p * p.log2()
+        })
+        .sum::<f64>()
+        .max(0.0)
+}
+
+fn column_cardinality(column: &Column) -> Result<ColumnCardinality> {
+    let series = column.as_materialized_series();
+    let dtype = series.dtype().clone();
+    let rows = series.len();
+    let null_count = series.null_count();
+    let non_null = rows - null_count;
+    let mut result = ColumnCardinality {
+        column: series.name().to_string(),
+        dtype: dtype.clone(),
+        distinct: None,
+        null_count,
+        distinct_ratio: None,
+        entropy: None,
+        flag: (rows > 0 && non_null == 0).then_some(CardinalityFlag::AllNull),
+    };
+    if non_null == 0 || !is_countable(&dtype) {
+        return Ok(result);
+    }
+
+    let counts_df = series
+        .drop_nulls()
+        .value_counts(false, false, "counts".into(), false)?;
+    let counts: Vec<u64> = counts_df
+        .column("counts")?
+        .as_materialized_series()
+        .cast(&DataType::UInt64)?
+        .u64()?
+        .into_no_null_iter()
+        .collect();
+    let distinct = counts.len();
+    result.distinct = Some(distinct);
+    result.distinct_ratio = Some(distinct as f64 / non_null as f64);
+    result.entropy = Some(entropy_bits(&counts));
+    result.flag = if distinct == 1 {
+        Some(CardinalityFlag::Constant)
+    } else if distinct == rows {
+        Some(CardinalityFlag::LikelyKey)
+    } else {
+        None
+    };
+    Ok(result)
+}
+
+/// Rank the columns of `lf` (`total_rows` rows) by cardinality. Samples when
+/// `sampling_threshold` is set and `total_rows` reaches it.
+pub fn compute(
+    lf: &LazyFrame,
+    total_rows: usize,
+    sampling_threshold: Option<usize>,
+    sampling: &SamplingStrategy,
+    polars_streaming: bool,
+) -> Result<CardinalityReport> {
+    let (df, sampled_from) = match sampling_threshold {
+        Some(threshold) if total_rows >= threshold => (
+            sample_dataframe(lf, total_rows, threshold, sampling, polars_streaming)?,
+            Some(total_rows),
+        ),
+        _ => (collect_lazy(lf.clone(), polars_streaming)?, None),
+    };
+
+    let mut columns: Vec<ColumnCardinality> = df
+        .get_columns()
+        .iter()
+        .map(column_cardinality)
+        .collect::<Result<_>>()?;
+    let rank = |c: &ColumnCardinality| match c.flag {
+        Some(CardinalityFlag::AllNull) => 2,
+        Some(CardinalityFlag::Constant) => 1,
+        _ => 0,
+    };
+    columns.sort_by(|a, b| {
+        rank(a)
+            .cmp(&rank(b))
+            .then_with(|| {
+                b.distinct_ratio
+                    .unwrap_or(-1.0)
+                    .total_cmp(&a.distinct_ratio.unwrap_or(-1.0))
+            })
+            .then_with(|| {
+                b.entropy
+                    .unwrap_or(-1.0)
+                    .total_cmp(&a.entropy.unwrap_or(-1.0))
+            })
+    });
+
+    Ok(CardinalityReport {
+        rows: df.height(),
+        sampled_from,
+        columns,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> LazyFrame {
+        df!(
+            "id" => [1i64, 2, 3, 4],
+            "category" => ["a", "a", "b", "b"],
+            "constant" => [Some(7i32), Some(7), None, Some(7)],
+            "empty" => [None::<f64>, None, None, None],
+        )
+        .unwrap()
+        .lazy()
+    }
+
+    #[test]
+    fn test_ranking_and_flags() {
+        let report = compute(&frame(), 4, None, &SamplingStrategy::default(), false).unwrap();
+        let names: Vec<&str> = report.columns.iter().map(|c| c.column.as_str()).collect();
+        assert_eq!(names, ["id", "category", "constant", "empty"]);
+
+        let id = &report.columns[0];
+        assert_eq!(id.flag, Some(CardinalityFlag::LikelyKey));
+        assert_eq!(id.distinct, Some(4));
+        assert!((id.entropy.unwrap() - 2.0).abs() < 1e-9);
+
+        let category = &report.columns[1];
+        assert_eq!(category.flag, None);
+        assert_eq!(category.distinct_ratio, Some(0.5));
+        assert!((category.entropy.unwrap() - 1.0).abs() < 1e-9);
+
+        let constant = &report.columns[2];
+        assert_eq!(constant.flag, Some(CardinalityFlag::Constant));
+        assert_eq!(constant.null_count, 1);
+        assert_eq!(constant.entropy, Some(0.0));
+
+        assert_eq!(report.columns[3].flag, Some(CardinalityFlag::AllNull));
+        assert_eq!(report.columns[3].distinct, None);
+    }
+
+    #[test]
+    fn test_samples_at_threshold() {
+        let lf = df!("x" => (0..5_000i64).collect::<Vec<_>>())
+            .unwrap()
+            .lazy();
+        let report = compute(&lf, 5_000, Some(200), &SamplingStrategy::default(), false).unwrap();
+        assert_eq!(report.rows, 200);
+        assert_eq!(report.sampled_from, Some(5_000));
+        assert_eq!(report.columns[0].flag, Some(CardinalityFlag::LikelyKey));
+    }
+}
@@ -0,0 +1,222 @@
+//! Cast modal: read the leftmost scrolled column as another type, with a format for text read as
+//! dates or times and a choice of failing or nulling values that can't be converted. The first
+//! values are shown before and after. Opened with `~`; Enter applies, Esc discards.
+
+use crate::column_cast::{cast_dtype, is_temporal, CastStrategy, ColumnCast, CAST_TYPES};
+use crate::computed_column_modal::PREVIEW_ROWS;
+use crate::widgets::text_input::TextInput;
+use polars::prelude::*;
+
+/// Formats offered as examples in the modal.
+pub const CAST_FORMAT_EXAMPLES: &str = "%Y-%m-%d, %d/%m/%Y, %Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CastFocus {
+    #[default]
+    Type,
+    Format,
+    Strategy,
+}
+
+/// First values before and after the cast, and how many of them can't be converted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastPreview {
+    pub values: Vec<(String, String)>,
+    pub failed: usize,
+}
+
+#[derive(Default)]
+pub struct CastModal {
+    pub active: bool,
+    pub focus: CastFocus,
+    pub column: String,
+    /// Current type of the column.
+    pub from: Option<DataType>,
+    /// Index of the target type in [`CAST_TYPES`].
+    pub target: usize,
+    pub format_input: TextInput,
+    pub strategy: CastStrategy,
+    /// Source the preview is evaluated on.
+    source: Option<LazyFrame>,
+    pub preview: Option<Result<CastPreview, String>>,
+}
+
+impl CastModal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open for `column` of `source`, whose type is `dtype`. The target starts at the type after
+    /// the column's own.
+    pub fn open(
+        &mut self,
+        column: &str,
+        dtype: &DataType,
+        source: LazyFrame,
+        theme: &crate::config::Theme,
+    ) {
+        self.active = true;
+        self.focus = CastFocus::Type;
+        self.column = column.to_string();
+        self.from = Some(dtype.clone());
+        let current = CAST_TYPES.iter().position(|name| {
+            ColumnCast {
+                column: String::new(),
+                to: name.to_string(),
+                format: None,
+                strategy: CastStrategy::Strict,
+            }
+            .is_noop(dtype)
+        });
+        self.target = current.map_or(0, |i| (i + 1) % CAST_TYPES.len());
+        self.format_input = TextInput::new().with_theme(theme);
+        self.strategy = CastStrategy::Strict;
+        self.source = Some(source);
+        self.refresh_preview();
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+        self.source = None;
+        self.preview = None;
+    }
+
+    /// Whether the format field applies: text read as a date, datetime or time.
+    pub fn takes_format(&self) -> bool {
+        self.from.as_ref() == Some(&DataType::String)
+            && cast_dtype(CAST_TYPES[self.target]).is_some_and(|dtype| is_temporal(&dtype))
+    }
+
+    /// The cast as edited.
+    pub fn cast(&self) -> ColumnCast {
+        let format = self.format_input.value().trim();
+        ColumnCast {
+            column: self.column.clone(),
+            to: CAST_TYPES[self.target].to_string(),
+            format: (self.takes_format() && !format.is_empty()).then(|| format.to_string()),
+            strategy: self.strategy,
+        }
+    }
+
+    /// Evaluate the cast on the first rows of the source. Call after each edit.
+    pub fn refresh_preview(&mut self) {
+        let (Some(source), Some(from)) = (self.source.as_ref(), self.from.as_ref()) else {
+            self.preview = None;
+            return;
+        };
+        self.preview = Some(preview(source, &self.cast(), from));
+    }
+
+    pub fn next_focus(&mut self) {
+        self.focus = match self.focus {
+            CastFocus::Type if self.takes_format() => CastFocus::Format,
+            CastFocus::Type | CastFocus::Format => CastFocus::Strategy,
+            CastFocus::Strategy => CastFocus::Type,
+        };
+    }
+
+    pub fn prev_focus(&mut self) {
+        self.focus = match self.focus {
+            CastFocus::Type => CastFocus::Strategy,
+            CastFocus::Strategy if self.takes_format() => CastFocus::Format,
+            CastFocus::Strategy | CastFocus::Format => CastFocus::Type,
+        };
+    }
+
+    /// Change the focused selector forwards (`delta` > 0) or backwards.
+    pub fn cycle(&mut self, delta: i32) {
+        match self.focus {
+            CastFocus::Type => {
+                let len = CAST_TYPES.len();
+                self.target = if delta < 0 {
+                    (self.target + len - 1) % len
+                } else {
+                    (self.target + 1) % len
+                };
+            }
+            CastFocus::Strategy => self.strategy = self.strategy.toggle(),
+            CastFocus::Format => {}
+        }
+    }
+
+    pub fn focused_input(&mut self) -> Option<&mut TextInput> {
+        match self.focus {
+            CastFocus::Format => Some(&mut self.format_input),
+            CastFocus::Type | CastFocus::Strategy => None,
+        }
+    }
+}
+
+fn preview(source: &LazyFrame, cast: &ColumnCast, from: &DataType) -> Result<CastPreview, String> {
+    let df = source
+        .clone()
+        .limit(PREVIEW_ROWS as IdxSize)
+        .select([
+            col(cast.column.as_str()).alias("before"),
+            cast.expr(from, CastStrategy::NullOnError)?.alias("after"),
+        ])
+        .collect()
+        .map_err(|e| crate::error_display::user_message_from_polars(&e))?;
+    let values = |name: &str| -> Vec<Option<String>> {
+        df.column(name)
+            .map(|c| {
+                c.as_materialized_series()
+                    .iter()
+                    .map(|v| (!v.is_null()).then(|| v.str_value().into_owned()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let pairs: Vec<(Option<String>, Option<String>)> =
+        values("before").into_iter().zip(values("after")).collect();
+    let failed = pairs
+        .iter()
+        .filter(|(before, after)| before.is_some() && after.is_none())
+        .count();
+    let text = |v: Option<String>| v.unwrap_or_else(|| "null".to_string());
+    Ok(CastPreview {
+        values: pairs
+            .into_iter()
+            .map(|(before, after)| (text(before), text(after)))
+            .collect(),
+        failed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modal_previews_cast_and_counts_failures() {
+        let config = crate::config::AppConfig::default();
+        let theme = crate::config::Theme::from_config(&config.theme).unwrap();
+        let source = df!("day" => ["03/01/2024", "n/a"]).unwrap().lazy();
+        let mut modal = CastModal::new();
+        modal.open("day", &DataType::String, source, &theme);
+        assert_eq!(
+            CAST_TYPES[modal.target], "i64",
+            "starts after the column's type"
+        );
+        while CAST_TYPES[modal.target] != "date" {
+            modal.cycle(1);
+        }
+        assert!(modal.takes_format());
+        modal.next_focus();
+        modal.focused_input().unwrap().set_value("%d/%m/%Y".into());
+        modal.refresh_preview();
+        let preview = modal.preview.clone().unwrap().unwrap();
+        assert_eq!(
+            preview.values[0],
+            ("03/01/2024".into(), "2024-01-03".into())
+        );
+        assert_eq!(preview.values[1].1, "null");
+        assert_eq!(preview.failed, 1);
+
+        modal.next_focus();
+        modal.cycle(1);
+        let cast = modal.cast();
+        assert_eq!(cast.format.as_deref(), Some("%d/%m/%Y"));
+        assert_eq!(cast.strategy, CastStrategy::NullOnError);
+    }
+}
@@ -1,6 +1,7 @@
 //! Prepare chart data from LazyFrame: select x/y columns, collect, and convert to (f64, f64) points.
 //! All prepare_* and collect_* functions take a `row_limit` to cap materialized rows (default from config).
 
+use crate::chart_modal::{BandSource, CategoryAgg};
 use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use color_eyre::Result;
 use polars::datatypes::{DataType, TimeUnit};
@@ -84,6 +85,117 @@ pub fn format_x_axis_label(v: f64, kind: XAxisTemporalKind) -> String {
     }
 }
 
+/// How axis tick values are written. Set per axis in the chart's Labels dialog.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TickFormat {
+    /// Dates and times on temporal axes, otherwise two decimals (scientific when very large or small).
+    #[default]
+    Auto,
+    /// SI prefixes: 1.5k, 2.3M, 40m.
+    Si,
+    /// Fractions as percentages: 0.25 is 25%.
+    Percent,
+    /// Date only (temporal axes; numeric axes use Auto).
+    Date,
+    /// Time of day only (temporal axes; numeric axes use Auto).
+    Time,
+}
+
+impl TickFormat {
+    pub const ALL: [Self; 5] = [Self::Auto, Self::Si, Self::Percent, Self::Date, Self::Time];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "Auto",
+            Self::Si => "SI units",
+            Self::Percent => "Percent",
+            Self::Date => "Date",
+            Self::Time => "Time",
+        }
+    }
+}
+
+/// Number with an SI prefix and up to three significant digits, e.g. 1234 -> "1.23k".
+pub fn format_si(v: f64) -> String {
+    const PREFIXES: [(f64, &str); 8] = [
+        (1e12, "T"),
+        (1e9, "G"),
+        (1e6, "M"),
+        (1e3, "k"),
+        (1.0, ""),
+        (1e-3, "m"),
+        (1e-6, "µ"),
+        (1e-9, "n"),
+    ];
+    if v == 0.0 || !v.is_finite() {
+        return format!("{}", v);
+    }
+    let (scale, prefix) = PREFIXES
+        .iter()
+        .find(|(scale, _)| v.abs() >= *scale * 0.9995)
+        .unwrap_or(&PREFIXES[PREFIXES.len() - 1]);
+    let scaled = v / scale;
+    let digits = if scaled.abs() >= 99.95 {
+        0
+    } else if scaled.abs() >= 9.995 {
+        1
+    } else {
+        2
+    };
+    let mut out = format!("{:.*}", digits, scaled);
+    if out.contains('.') {
+        out = out.trim_end_matches('0').trim_end_matches('.').to_string();
+    }
+    out.push_str(prefix);
+    out
+}
+
+/// Date and time of a temporal axis value; None for numeric and time-of-day axes.
+fn axis_datetime(v: f64, kind: XAxisTemporalKind) -> Option<chrono::NaiveDateTime> {
+    const UNIX_EPOCH_CE_DAYS: i32 = 719_163;
+    match kind {
+        XAxisTemporalKind::Numeric | XAxisTemporalKind::Time => None,
+        XAxisTemporalKind::Date => NaiveDate::from_num_days_from_ce_opt(
+            UNIX_EPOCH_CE_DAYS.saturating_add(v.trunc() as i32),
+        )
+        .and_then(|d| d.and_hms_opt(0, 0, 0)),
+        XAxisTemporalKind::DatetimeUs => {
+            DateTime::from_timestamp_micros(v.trunc() as i64).map(|dt| dt.naive_utc())
+        }
+        XAxisTemporalKind::DatetimeMs => {
+            DateTime::from_timestamp_millis(v.trunc() as i64).map(|dt| dt.naive_utc())
+        }
+        XAxisTemporalKind::DatetimeNs => {
+            Some(DateTime::from_timestamp_nanos(v.trunc() as i64).naive_utc())
+        }
+    }
+}
+
+/// Format an axis tick with `format`. `kind` is the axis' temporal kind (Numeric for value axes).
+pub fn format_tick(v: f64, kind: XAxisTemporalKind, format: TickFormat) -> String {
+    let temporal = kind != XAxisTemporalKind::Numeric;
+    match format {
+        TickFormat::Si if !temporal => format_si(v),
+        TickFormat::Percent if !temporal => {
+            let pct = v * 100.0;
+            if pct.abs() >= 10.0 || pct == 0.0 {
+                format!("{:.0}%", pct)
+            } else {
+                format!("{:.1}%", pct)
+            }
+        }
+        TickFormat::Date => match axis_datetime(v, kind) {
+            Some(dt) => dt.format("%Y-%m-%d").to_string(),
+            None => format_x_axis_label(v, kind),
+        },
+        TickFormat::Time => match axis_datetime(v, kind) {
+            Some(dt) => dt.format("%H:%M:%S").to_string(),
+            None => format_x_axis_label(v, kind),
+        },
+        _ => format_x_axis_label(v, kind),
+    }
+}
+
 /// Result of loading only the x column: min/max for axis bounds and temporal kind.
 pub struct ChartXRangeResult {
     pub x_min: f64,
@@ -161,6 +273,8 @@ pub fn prepare_chart_x_range(
 /// Result of preparing chart data: series points and x-axis kind for label formatting.
 pub struct ChartDataResult {
     pub series: Vec<Vec<(f64, f64)>>,
+    /// Legend name of each series: its y column, or its group's value.
+    pub names: Vec<String>,
     pub x_axis_kind: XAxisTemporalKind,
 }
 
@@ -229,6 +343,48 @@ pub struct HeatmapData {
     pub max_count: f64,
 }
 
+/// Aggregated value of each of the top categories, for a category chart.
+#[derive(Clone)]
+pub struct CategoryBarData {
+    pub column: String,
+    /// Category (nulls as `null`) and value, largest value first.
+    pub bars: Vec<(String, f64)>,
+    /// Number of categories, including those past the cutoff.
+    pub categories: usize,
+    /// Value range, including 0 (bars start at 0).
+    pub y_min: f64,
+    pub y_max: f64,
+}
+
+/// Least-squares line through a scatter chart's points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrendLine {
+    pub slope: f64,
+    pub intercept: f64,
+    /// Coefficient of determination, 0 (no fit) to 1 (every point on the line).
+    pub r_squared: f64,
+}
+
+impl TrendLine {
+    pub fn at(&self, x: f64) -> f64 {
+        self.slope * x + self.intercept
+    }
+}
+
+/// Points of a scatter chart of two numeric columns.
+#[derive(Clone)]
+pub struct ScatterData {
+    pub x_column: String,
+    pub y_column: String,
+    pub points: Vec<(f64, f64)>,
+    pub x_min: f64,
+    pub x_max: f64,
+    pub y_min: f64,
+    pub y_max: f64,
+    /// None when there are fewer than two points or x doesn't vary.
+    pub trend: Option<TrendLine>,
+}
+
 /// Prepares chart data from the current LazyFrame.
 /// Returns series data and x-axis kind. X is cast to f64 (temporal types as ordinal).
 /// Drops nulls and limits to `row_limit` rows.
@@ -242,6 +398,7 @@ pub fn prepare_chart_data(
     if y_columns.is_empty() {
         return Ok(ChartDataResult {
             series: Vec::new(),
+            names: Vec::new(),
             x_axis_kind: XAxisTemporalKind::Numeric,
         });
     }
@@ -276,6 +433,7 @@ pub fn prepare_chart_data(
     if n_rows == 0 {
         return Ok(ChartDataResult {
             series: vec![vec![]; y_columns.len()],
+            names: y_columns.to_vec(),
             x_axis_kind,
         });
     }
@@ -302,10 +460,166 @@ pub fn prepare_chart_data(
 
     Ok(ChartDataResult {
         series: series_per_y,
+        names: y_columns.to_vec(),
+        x_axis_kind,
+    })
+}
+
+/// Prepares the XY chart's series: those of [`prepare_chart_data`], or with `group_column`
+/// those of [`prepare_grouped_chart_data`] for the first y column.
+pub fn prepare_xy_chart_data(
+    lf: &LazyFrame,
+    schema: &Schema,
+    x_column: &str,
+    y_columns: &[String],
+    group_column: Option<&str>,
+    row_limit: usize,
+) -> Result<ChartDataResult> {
+    match (group_column, y_columns.first()) {
+        (Some(group), Some(y)) => prepare_grouped_chart_data(
+            lf,
+            schema,
+            x_column,
+            y,
+            group,
+            crate::chart_modal::Y_SERIES_MAX,
+            row_limit,
+        ),
+        _ => prepare_chart_data(lf, schema, x_column, y_columns, row_limit),
+    }
+}
+
+/// Prepares one series of `y_column` per value of `group_column` (nulls as `null`), for the
+/// `max_groups` values with the most rows, in order of value. X is handled as in
+/// [`prepare_chart_data`]; rows where x or y is null are dropped before limiting to
+/// `row_limit` rows.
+pub fn prepare_grouped_chart_data(
+    lf: &LazyFrame,
+    schema: &Schema,
+    x_column: &str,
+    y_column: &str,
+    group_column: &str,
+    max_groups: usize,
+    row_limit: usize,
+) -> Result<ChartDataResult> {
+    let x_dtype = schema
+        .get(x_column)
+        .ok_or_else(|| color_eyre::eyre::eyre!("x column '{}' not in schema", x_column))?;
+    let x_axis_kind = x_axis_temporal_kind(x_dtype);
+    let x_expr: Expr = match x_dtype {
+        DataType::Datetime(_, _) | DataType::Date | DataType::Time => {
+            col(x_column).cast(DataType::Int64).cast(DataType::Float64)
+        }
+        _ => col(x_column).cast(DataType::Float64),
+    };
+    const X: &str = "__chart_x";
+    const Y: &str = "__chart_y";
+    const GROUP: &str = "__chart_group";
+    let df = lf
+        .clone()
+        .select([
+            x_expr.alias(X),
+            col(y_column).cast(DataType::Float64).alias(Y),
+            col(group_column)
+                .cast(DataType::String)
+                .fill_null(lit("null"))
+                .alias(GROUP),
+        ])
+        .drop_nulls(None)
+        .slice(0, row_limit as u32)
+        .collect()?;
+
+    let xs = df.column(X)?.f64()?;
+    let ys = df.column(Y)?.f64()?;
+    let groups = df.column(GROUP)?.str()?;
+    let mut counts: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for group in groups.into_no_null_iter() {
+        *counts.entry(group).or_default() += 1;
+    }
+    let mut names: Vec<&str> = counts.keys().copied().collect();
+    names.sort_by(|a, b| counts[b].cmp(&counts[a]).then(a.cmp(b)));
+    names.truncate(max_groups);
+    names.sort_unstable();
+
+    let mut series = vec![Vec::new(); names.len()];
+    for ((x, y), group) in xs.iter().zip(ys.iter()).zip(groups.into_no_null_iter()) {
+        if let (Some(x), Some(y), Ok(i)) = (x, y, names.binary_search(&group)) {
+            if x.is_finite() && y.is_finite() {
+                series[i].push((x, y));
+            }
+        }
+    }
+    Ok(ChartDataResult {
+        series,
+        names: names.into_iter().map(str::to_string).collect(),
         x_axis_kind,
     })
 }
 
+/// Prepares the band around a series as (x, lower, upper) points, in the same x units as
+/// [`prepare_chart_data`]. Rows where x, the series (for a std-dev band) or a bound is null
+/// are dropped before limiting to `row_limit` rows.
+pub fn prepare_band_data(
+    lf: &LazyFrame,
+    schema: &Schema,
+    x_column: &str,
+    y_column: &str,
+    source: &BandSource,
+    row_limit: usize,
+) -> Result<Vec<(f64, f64, f64)>> {
+    const X: &str = "__datui_band_x";
+    const A: &str = "__datui_band_a";
+    const B: &str = "__datui_band_b";
+
+    let x_dtype = schema
+        .get(x_column)
+        .ok_or_else(|| color_eyre::eyre::eyre!("x column '{}' not in schema", x_column))?;
+    let x_expr: Expr = match x_dtype {
+        DataType::Datetime(_, _) | DataType::Date | DataType::Time => {
+            col(x_column).cast(DataType::Int64)
+        }
+        _ => col(x_column).cast(DataType::Float64),
+    };
+    // Lower/upper bounds, or the series and its standard deviation.
+    let (a, b) = match source {
+        BandSource::Bounds { lower, upper } => (lower.as_str(), upper.as_str()),
+        BandSource::StdDev { column, .. } => (y_column, column.as_str()),
+    };
+
+    let df = lf
+        .clone()
+        .select([
+            x_expr.alias(X),
+            col(a).cast(DataType::Float64).alias(A),
+            col(b).cast(DataType::Float64).alias(B),
+        ])
+        .drop_nulls(None)
+        .slice(0, row_limit as u32)
+        .collect()?;
+
+    let x_series = df.column(X)?.cast(&DataType::Float64)?;
+    let x_f64 = x_series.f64()?;
+    let a_f64 = df.column(A)?.f64()?;
+    let b_f64 = df.column(B)?.f64()?;
+    let mut points = Vec::with_capacity(df.height());
+    for i in 0..df.height() {
+        let (Some(x), Some(a), Some(b)) = (x_f64.get(i), a_f64.get(i), b_f64.get(i)) else {
+            continue;
+        };
+        let (lower, upper) = match source {
+            BandSource::Bounds { .. } => (a.min(b), a.max(b)),
+            BandSource::StdDev { multiplier, .. } => {
+                let spread = (b * multiplier).abs();
+                (a - spread, a + spread)
+            }
+        };
+        if x.is_finite() && lower.is_finite() && upper.is_finite() {
+            points.push((x, lower, upper));
+        }
+    }
+    Ok(points)
+}
+
 fn collect_numeric_values(lf: &LazyFrame, column: &str, row_limit: usize) -> Result<Vec<f64>> {
     let df = lf
         .clone()
@@ -713,9 +1027,203 @@ pub fn prepare_heatmap_data(
     })
 }
 
+/// Prepare a scatter chart of two numeric columns: the (x, y) pairs without nulls in the first
+/// `row_limit` rows, their bounds and the least-squares trend line.
+pub fn prepare_scatter_data(
+    lf: &LazyFrame,
+    x_column: &str,
+    y_column: &str,
+    row_limit: usize,
+) -> Result<ScatterData> {
+    let points = collect_numeric_pairs(lf, x_column, y_column, row_limit)?;
+    let (mut x_min, mut x_max) = (f64::INFINITY, f64::NEG_INFINITY);
+    let (mut y_min, mut y_max) = (f64::INFINITY, f64::NEG_INFINITY);
+    for &(x, y) in &points {
+        x_min = x_min.min(x);
+        x_max = x_max.max(x);
+        y_min = y_min.min(y);
+        y_max = y_max.max(y);
+    }
+    if points.is_empty() {
+        (x_min, x_max, y_min, y_max) = (0.0, 1.0, 0.0, 1.0);
+    }
+    if x_max <= x_min {
+        x_min -= 0.5;
+        x_max = x_min + 1.0;
+    }
+    if y_max <= y_min {
+        y_min -= 0.5;
+        y_max = y_min + 1.0;
+    }
+    let trend = linear_fit(&points);
+    Ok(ScatterData {
+        x_column: x_column.to_string(),
+        y_column: y_column.to_string(),
+        points,
+        x_min,
+        x_max,
+        y_min,
+        y_max,
+        trend,
+    })
+}
+
+/// Ordinary least-squares fit of y on x.
+pub fn linear_fit(points: &[(f64, f64)]) -> Option<TrendLine> {
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let x_mean = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let y_mean = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+    for &(x, y) in points {
+        let (dx, dy) = (x - x_mean, y - y_mean);
+        sxx += dx * dx;
+        sxy += dx * dy;
+        syy += dy * dy;
+    }
+    if sxx <= 0.0 {
+        return None;
+    }
+    let slope = sxy / sxx;
+    // Every y the same: the flat line fits exactly.
+    let r_squared = if syy > 0.0 {
+        (sxy * sxy / (sxx * syy)).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+    Some(TrendLine {
+        slope,
+        intercept: y_mean - slope * x_mean,
+        r_squared,
+    })
+}
+
+/// Prepare a category chart: `agg` of `value_column` (rows, for Count) per value of
+/// `category_column` in the first `row_limit` rows, for the `top` categories with the largest
+/// values. Grouping, sorting and the cutoff run in Polars, so only the bars are collected.
+pub fn prepare_category_bar_data(
+    lf: &LazyFrame,
+    category_column: &str,
+    agg: CategoryAgg,
+    value_column: Option<&str>,
+    top: usize,
+    row_limit: usize,
+) -> Result<CategoryBarData> {
+    const CATEGORY: &str = "__chart_category";
+    const VALUE: &str = "__chart_value";
+    let value = match (agg, value_column) {
+        (CategoryAgg::Count, _) => len(),
+        (CategoryAgg::Sum, Some(v)) => col(v).cast(DataType::Float64).sum(),
+        (CategoryAgg::Mean, Some(v)) => col(v).cast(DataType::Float64).mean(),
+        (_, None) => {
+            return Err(color_eyre::eyre::eyre!(
+                "{} needs a value column",
+                agg.as_str()
+            ))
+        }
+    };
+    let grouped = lf
+        .clone()
+        .slice(0, row_limit as u32)
+        .group_by([col(category_column)
+            .cast(DataType::String)
+            .fill_null(lit("null"))
+            .alias(CATEGORY)])
+        .agg([value.cast(DataType::Float64).alias(VALUE)]);
+    let categories = grouped
+        .clone()
+        .select([len()])
+        .collect()?
+        .get_columns()
+        .first()
+        .and_then(|c| c.get(0).ok())
+        .and_then(|v| v.extract::<usize>())
+        .unwrap_or(0);
+    let df = grouped
+        .filter(col(VALUE).is_not_null())
+        .sort(
+            [VALUE, CATEGORY],
+            SortMultipleOptions::default().with_order_descending_multi([true, false]),
+        )
+        .limit(top as IdxSize)
+        .collect()?;
+    let names = df.column(CATEGORY)?.str()?;
+    let values = df.column(VALUE)?.f64()?;
+    let bars: Vec<(String, f64)> = names
+        .into_no_null_iter()
+        .zip(values.into_no_null_iter())
+        .filter(|(_, v)| v.is_finite())
+        .map(|(name, v)| (name.to_string(), v))
+        .collect();
+    let y_min = bars.iter().map(|(_, v)| *v).fold(0.0_f64, f64::min);
+    let y_max = bars.iter().map(|(_, v)| *v).fold(0.0_f64, f64::max);
+    Ok(CategoryBarData {
+        column: category_column.to_string(),
+        bars,
+        categories,
+        y_min,
+        y_max: if y_max > y_min { y_max } else { y_min + 1.0 },
+    })
+}
+
+/// Summary statistics of one charted column (the table of a report-card export).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnSummary {
+    pub column: String,
+    pub count: usize,
+    pub min: f64,
+    pub mean: f64,
+    pub median: f64,
+    /// Sample standard deviation; 0 for a single value.
+    pub std: f64,
+    pub max: f64,
+}
+
+/// Summarize each numeric column over the same rows a chart uses (first `row_limit` non-null,
+/// finite values). Columns without values are left out.
+pub fn prepare_chart_summary<T: AsRef<str>>(
+    lf: &LazyFrame,
+    columns: &[T],
+    row_limit: usize,
+) -> Result<Vec<ColumnSummary>> {
+    let mut out = Vec::with_capacity(columns.len());
+    for column in columns {
+        let column = column.as_ref();
+        let mut values = collect_numeric_values(lf, column, row_limit)?;
+        if values.is_empty() {
+            continue;
+        }
+        values.sort_by(|a, b| a.total_cmp(b));
+        let n = values.len();
+        let mean = values.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        out.push(ColumnSummary {
+            column: column.to_string(),
+            count: n,
+            min: values[0],
+            mean,
+            median: quantile(&values, 0.5),
+            std: variance.sqrt(),
+            max: values[n - 1],
+        });
+    }
+    Ok(out)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{prepare_chart_data, XAxisTemporalKind};
+    use super::{
+        format_si, format_tick, linear_fit, prepare_band_data, prepare_category_bar_data,
+        prepare_chart_data, prepare_chart_summary, prepare_grouped_chart_data,
+        prepare_scatter_data, TickFormat, XAxisTemporalKind,
+    };
+    use crate::chart_modal::{BandSource, CategoryAgg};
     use polars::prelude::*;
 
     #[test]
@@ -768,6 +1276,101 @@ mod tests {
         assert_eq!(result.series[0], vec![(1.0, 10.0), (3.0, 30.0)]);
     }
 
+    #[test]
+    fn prepare_grouped_keeps_the_largest_groups() {
+        let lf = df!(
+            "day" => &[1.0_f64, 1.0, 2.0, 2.0, 3.0, 3.0],
+            "price" => &[Some(10.0_f64), Some(5.0), Some(11.0), Some(6.0), None, Some(7.0)],
+            "ticker" => &[Some("B"), Some("A"), Some("B"), Some("A"), Some("B"), None]
+        )
+        .unwrap()
+        .lazy();
+        let schema = lf.clone().collect_schema().unwrap();
+        let result =
+            prepare_grouped_chart_data(&lf, schema.as_ref(), "day", "price", "ticker", 3, 10_000)
+                .unwrap();
+        assert_eq!(result.names, ["A", "B", "null"]);
+        assert_eq!(result.series[0], vec![(1.0, 5.0), (2.0, 6.0)]);
+        assert_eq!(result.series[1], vec![(1.0, 10.0), (2.0, 11.0)]);
+        assert_eq!(result.series[2], vec![(3.0, 7.0)]);
+
+        let result =
+            prepare_grouped_chart_data(&lf, schema.as_ref(), "day", "price", "ticker", 2, 10_000)
+                .unwrap();
+        assert_eq!(result.names, ["A", "B"]);
+    }
+
+    #[test]
+    fn prepare_category_bars_keeps_the_top_categories() {
+        let lf = df!(
+            "region" => &[Some("east"), Some("west"), Some("east"), None, Some("north")],
+            "sales" => &[Some(1.0_f64), Some(10.0), Some(2.0), Some(4.0), None]
+        )
+        .unwrap()
+        .lazy();
+        let data =
+            prepare_category_bar_data(&lf, "region", CategoryAgg::Count, None, 2, 10_000).unwrap();
+        assert_eq!(data.categories, 4);
+        assert_eq!(data.bars, vec![("east".into(), 2.0), ("north".into(), 1.0)]);
+
+        let data =
+            prepare_category_bar_data(&lf, "region", CategoryAgg::Sum, Some("sales"), 10, 10_000)
+                .unwrap();
+        assert_eq!(data.bars[0], ("west".into(), 10.0));
+        assert_eq!(data.bars[1], ("null".into(), 4.0));
+        assert_eq!((data.y_min, data.y_max), (0.0, 10.0));
+
+        // A category without values has no mean
+        let data =
+            prepare_category_bar_data(&lf, "region", CategoryAgg::Mean, Some("sales"), 10, 10_000)
+                .unwrap();
+        assert_eq!(data.bars.len(), 3);
+        assert_eq!(data.bars[2], ("east".into(), 1.5));
+        assert!(
+            prepare_category_bar_data(&lf, "region", CategoryAgg::Sum, None, 10, 10_000).is_err()
+        );
+    }
+
+    #[test]
+    fn prepare_scatter_fits_a_trend_line() {
+        let lf = df!(
+            "x" => &[Some(1.0_f64), Some(2.0), Some(3.0), None, Some(4.0)],
+            "y" => &[Some(3.0_f64), Some(5.0), Some(7.0), Some(1.0), Some(9.0)]
+        )
+        .unwrap()
+        .lazy();
+        let data = prepare_scatter_data(&lf, "x", "y", 10_000).unwrap();
+        assert_eq!(data.points.len(), 4);
+        assert_eq!((data.x_min, data.x_max), (1.0, 4.0));
+        assert_eq!((data.y_min, data.y_max), (3.0, 9.0));
+        let trend = data.trend.unwrap();
+        assert!((trend.slope - 2.0).abs() < 1e-12);
+        assert!((trend.intercept - 1.0).abs() < 1e-12);
+        assert!((trend.r_squared - 1.0).abs() < 1e-12);
+        assert!((trend.at(10.0) - 21.0).abs() < 1e-9);
+
+        // No line through points that all share one x
+        assert_eq!(linear_fit(&[(1.0, 2.0), (1.0, 5.0)]), None);
+        let noisy = linear_fit(&[(0.0, 0.0), (1.0, 2.0), (2.0, 1.0), (3.0, 3.0)]).unwrap();
+        assert!(noisy.r_squared > 0.0 && noisy.r_squared < 1.0);
+    }
+
+    #[test]
+    fn chart_summary_stats() {
+        let lf = df!(
+            "a" => &[Some(4.0_f64), Some(1.0), None, Some(7.0)],
+            "b" => &[None::<f64>, None, None, None]
+        )
+        .unwrap()
+        .lazy();
+        let summary = prepare_chart_summary(&lf, &["a", "b"], 10_000).unwrap();
+        assert_eq!(summary.len(), 1);
+        let a = &summary[0];
+        assert_eq!((a.count, a.min, a.median, a.max), (3, 1.0, 4.0, 7.0));
+        assert_eq!(a.mean, 4.0);
+        assert_eq!(a.std, 3.0);
+    }
+
     #[test]
     fn prepare_missing_x_column_errors() {
         let lf = df!("x" => &[1.0_f64], "y" => &[2.0_f64]).unwrap().lazy();
@@ -775,6 +1378,68 @@ mod tests {
         let result = prepare_chart_data(&lf, schema.as_ref(), "missing", &["y".into()], 10_000);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn band_from_bounds_and_std_dev() {
+        let lf = df!(
+            "x" => &[1.0_f64, 2.0, 3.0],
+            "y" => &[Some(10.0_f64), Some(20.0), None],
+            "lo" => &[8.0_f64, 21.0, 25.0],
+            "hi" => &[12.0_f64, 19.0, 35.0],
+            "sd" => &[1.0_f64, -2.0, 3.0],
+        )
+        .unwrap()
+        .lazy();
+        let schema = lf.clone().collect_schema().unwrap();
+
+        let bounds = BandSource::Bounds {
+            lower: "lo".into(),
+            upper: "hi".into(),
+        };
+        let points = prepare_band_data(&lf, schema.as_ref(), "x", "y", &bounds, 10_000).unwrap();
+        // Swapped bounds are put in order; a null series does not matter for explicit bounds.
+        assert_eq!(
+            points,
+            vec![(1.0, 8.0, 12.0), (2.0, 19.0, 21.0), (3.0, 25.0, 35.0)]
+        );
+
+        let std_dev = BandSource::StdDev {
+            column: "sd".into(),
+            multiplier: 2.0,
+        };
+        let points = prepare_band_data(&lf, schema.as_ref(), "x", "y", &std_dev, 1).unwrap();
+        assert_eq!(points, vec![(1.0, 8.0, 12.0)]);
+        let points = prepare_band_data(&lf, schema.as_ref(), "x", "y", &std_dev, 10).unwrap();
+        assert_eq!(points, vec![(1.0, 8.0, 12.0), (2.0, 16.0, 24.0)]);
+    }
+
+    #[test]
+    fn tick_formats() {
+        assert_eq!(format_si(1234.0), "1.23k");
+        assert_eq!(format_si(-25_000_000.0), "-25M");
+        assert_eq!(format_si(999_999.0), "1M");
+        assert_eq!(format_si(0.04), "40m");
+        assert_eq!(format_si(12.0), "12");
+        assert_eq!(format_si(0.0), "0");
+
+        let numeric = XAxisTemporalKind::Numeric;
+        assert_eq!(format_tick(0.25, numeric, TickFormat::Percent), "25%");
+        assert_eq!(format_tick(0.005, numeric, TickFormat::Percent), "0.5%");
+        assert_eq!(format_tick(2.5, numeric, TickFormat::Auto), "2.50");
+        // Date/time formats fall back to Auto on value axes, and SI/percent on time axes.
+        assert_eq!(format_tick(2.5, numeric, TickFormat::Date), "2.50");
+
+        // 2024-01-02 03:04:05 UTC in milliseconds.
+        let ms = 1_704_164_645_000.0;
+        let kind = XAxisTemporalKind::DatetimeMs;
+        assert_eq!(format_tick(ms, kind, TickFormat::Date), "2024-01-02");
+        assert_eq!(format_tick(ms, kind, TickFormat::Time), "03:04:05");
+        assert_eq!(format_tick(ms, kind, TickFormat::Si), "2024-01-02 03:04");
+        assert_eq!(
+            format_tick(19_724.0, XAxisTemporalKind::Date, TickFormat::Date),
+            "2024-01-02"
+        );
+    }
 }
 
 #[cfg(test)]
@@ -6,15 +6,33 @@ use std::io::Write;
 use std::path::Path;
 
 use crate::chart_data::{
-    format_axis_label, format_x_axis_label, BoxPlotData, HeatmapData, XAxisTemporalKind,
+    format_axis_label, format_tick, BoxPlotData, CategoryBarData, ColumnSummary, HeatmapData,
+    TickFormat, XAxisTemporalKind,
 };
-use crate::chart_modal::ChartType;
-
-/// Escape a string for PostScript ( and ) and \.
+use crate::chart_modal::{BandStyle, ChartType};
+use crate::statistics::CorrelationMatrix;
+
+/// Drawing area of a PNG export.
+type BitMapArea<'a> =
+    plotters::drawing::DrawingArea<plotters::prelude::BitMapBackend<'a>, plotters::coord::Shift>;
+
+/// Chart with value axes, drawn on a [`BitMapArea`].
+type BitMapChart<'a, 'b> = plotters::chart::ChartContext<
+    'a,
+    plotters::prelude::BitMapBackend<'b>,
+    plotters::coord::cartesian::Cartesian2d<
+        plotters::coord::types::RangedCoordf64,
+        plotters::coord::types::RangedCoordf64,
+    >,
+>;
+
+/// Escape a string for PostScript ( and ) and \. The micro sign (SI ticks) becomes `u`, as the
+/// standard font encoding has no glyph for it.
 fn ps_escape(s: &str) -> String {
     s.replace('\\', "\\\\")
         .replace('(', "\\(")
         .replace(')', "\\)")
+        .replace('µ', "u")
 }
 
 /// Generate "nice" tick values in [min, max] with roughly max_ticks steps.
@@ -54,11 +72,6 @@ fn nice_ticks(min: f64, max: f64, max_ticks: usize) -> Vec<f64> {
     ticks
 }
 
-/// Format a numeric tick for display (used for y when not log scale).
-fn format_tick(v: f64) -> String {
-    format_axis_label(v)
-}
-
 /// Bounds and options for rendering the chart to a file.
 pub struct ChartExportBounds {
     pub x_min: f64,
@@ -75,6 +88,44 @@ pub struct ChartExportBounds {
     pub log_scale: bool,
     /// Optional chart title shown on export. None or empty = no title.
     pub chart_title: Option<String>,
+    /// Tick label formats chosen in the chart labels dialog.
+    pub x_ticks: TickFormat,
+    pub y_ticks: TickFormat,
+    /// Right-hand y axis for series with their own scale; None draws only the left axis.
+    pub right_axis: Option<ChartExportRightAxis>,
+}
+
+/// Right-hand y axis of an XY export. Its series' points are already scaled onto the left axis
+/// (`to_left`); its ticks are labelled in its own range, log-transformed like the left one.
+pub struct ChartExportRightAxis {
+    pub y_min: f64,
+    pub y_max: f64,
+    /// Column name(s) of the right-hand series (for the axis title).
+    pub label: String,
+}
+
+impl ChartExportRightAxis {
+    /// Position of right-axis value `v` on a left axis spanning `left_min..left_max`.
+    pub fn to_left(&self, v: f64, left_min: f64, left_max: f64) -> f64 {
+        let span = self.y_max - self.y_min;
+        if span > 0.0 {
+            left_min + (v - self.y_min) / span * (left_max - left_min)
+        } else {
+            v
+        }
+    }
+}
+
+impl ChartExportBounds {
+    fn x_tick(&self, v: f64) -> String {
+        format_tick(v, self.x_axis_kind, self.x_ticks)
+    }
+
+    /// Y tick label in linear space (undoing ln(1+y) on log scale).
+    fn y_tick(&self, v: f64) -> String {
+        let v = if self.log_scale { v.exp_m1() } else { v };
+        format_tick(v, XAxisTemporalKind::Numeric, self.y_ticks)
+    }
 }
 
 /// Bounds and options for rendering a box plot export.
@@ -85,12 +136,75 @@ pub struct BoxPlotExportBounds {
     pub x_label: String,
     pub y_label: String,
     pub chart_title: Option<String>,
+    pub y_ticks: TickFormat,
+}
+
+/// Options for rendering a correlation matrix export.
+pub struct CorrelationExportBounds {
+    /// Off-diagonal cells with |r| at or above this are outlined.
+    pub threshold: f64,
+    pub chart_title: Option<String>,
+}
+
+/// RGB (0 to 1) of correlation `r` on a diverging palette: blue at -1, white at 0, red at +1.
+/// NaN (a constant column) is light gray.
+fn diverging_rgb(r: f64) -> (f64, f64, f64) {
+    const NEGATIVE: (f64, f64, f64) = (0.13, 0.40, 0.67);
+    const POSITIVE: (f64, f64, f64) = (0.70, 0.09, 0.17);
+    if r.is_nan() {
+        return (0.85, 0.85, 0.85);
+    }
+    let (end, t) = if r < 0.0 {
+        (NEGATIVE, (-r).min(1.0))
+    } else {
+        (POSITIVE, r.min(1.0))
+    };
+    let mix = |c: f64| 1.0 + (c - 1.0) * t;
+    (mix(end.0), mix(end.1), mix(end.2))
+}
+
+/// Cell text of correlation `r`: two decimals, or "n/a" for NaN.
+fn correlation_label(r: f64) -> String {
+    if r.is_nan() {
+        "n/a".to_string()
+    } else {
+        format!("{:.2}", r)
+    }
+}
+
+/// `name` cut to `max` characters, ending in `...` when cut.
+fn truncate_label(name: &str, max: usize) -> String {
+    if name.chars().count() <= max {
+        name.to_string()
+    } else {
+        let kept: String = name.chars().take(max.saturating_sub(3)).collect();
+        format!("{kept}...")
+    }
 }
 
 /// One series: name and (x, y) points (y already log-transformed if log scale).
 pub struct ChartExportSeries {
     pub name: String,
     pub points: Vec<(f64, f64)>,
+    /// Confidence band or error bars drawn behind the series.
+    pub band: Option<ChartExportBand>,
+}
+
+/// Band around a series: (x, lower, upper) points, transformed like the series points.
+pub struct ChartExportBand {
+    pub points: Vec<(f64, f64, f64)>,
+    pub style: BandStyle,
+}
+
+impl ChartExportBand {
+    /// Outline of a shaded band: the upper bound left to right, then the lower bound back.
+    fn outline(&self) -> Vec<(f64, f64)> {
+        let mut sorted = self.points.clone();
+        sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let upper = sorted.iter().map(|&(x, _, upper)| (x, upper));
+        let lower = sorted.iter().rev().map(|&(x, lower, _)| (x, lower));
+        upper.chain(lower).collect()
+    }
 }
 
 /// Export format for chart: PNG or EPS.
@@ -118,24 +232,71 @@ impl ChartExportFormat {
     }
 }
 
+/// Size in points of the chart part of an EPS export.
+const EPS_WIDTH: f64 = 400.0;
+const EPS_HEIGHT: f64 = 300.0;
+
+/// Write an EPS file of `EPS_WIDTH` x `height` points whose content is drawn by `body`.
+/// The body is rendered first so a failure (e.g. no data) leaves no file behind.
+fn write_eps(
+    path: &Path,
+    height: f64,
+    body: impl FnOnce(&mut Vec<u8>) -> Result<()>,
+) -> Result<()> {
+    let mut content = Vec::new();
+    body(&mut content)?;
+    let mut f = File::create(path)?;
+    writeln!(f, "%!PS-Adobe-3.0 EPSF-3.0")?;
+    writeln!(
+        f,
+        "%%BoundingBox: 0 0 {} {}",
+        EPS_WIDTH.ceil() as i32,
+        height.ceil() as i32
+    )?;
+    writeln!(f, "%%Creator: datui")?;
+    writeln!(f, "%%EndComments")?;
+    f.write_all(&content)?;
+    writeln!(f, "%%EOF")?;
+    f.sync_all()?;
+    Ok(())
+}
+
 /// Write chart to EPS (Encapsulated PostScript). No external dependencies.
 pub fn write_chart_eps(
     path: &Path,
     series: &[ChartExportSeries],
     chart_type: ChartType,
     bounds: &ChartExportBounds,
+) -> Result<()> {
+    write_eps(path, EPS_HEIGHT, |f| {
+        chart_eps_body(f, series, chart_type, bounds)
+    })
+}
+
+fn chart_eps_body(
+    f: &mut impl Write,
+    series: &[ChartExportSeries],
+    chart_type: ChartType,
+    bounds: &ChartExportBounds,
 ) -> Result<()> {
     if series.is_empty() || series.iter().all(|s| s.points.is_empty()) {
         return Err(color_eyre::eyre::eyre!("No data to export"));
     }
 
-    const W: f64 = 400.0;
-    const H: f64 = 300.0;
+    const W: f64 = EPS_WIDTH;
+    const H: f64 = EPS_HEIGHT;
     const MARGIN_LEFT: f64 = 50.0;
     const MARGIN_BOTTOM: f64 = 40.0;
-    const PLOT_W: f64 = W - MARGIN_LEFT - 40.0;
     const PLOT_H: f64 = H - MARGIN_BOTTOM - 30.0;
 
+    // A right-hand axis needs room for its tick labels and title.
+    let margin_right = if bounds.right_axis.is_some() {
+        70.0
+    } else {
+        40.0
+    };
+    let plot_w = W - MARGIN_LEFT - margin_right;
+
     let x_min = bounds.x_min;
     let x_max = bounds.x_max;
     let y_min = bounds.y_min;
@@ -143,20 +304,9 @@ pub fn write_chart_eps(
     let x_range = if x_max > x_min { x_max - x_min } else { 1.0 };
     let y_range = if y_max > y_min { y_max - y_min } else { 1.0 };
 
-    let to_x = |x: f64| MARGIN_LEFT + (x - x_min) / x_range * PLOT_W;
+    let to_x = |x: f64| MARGIN_LEFT + (x - x_min) / x_range * plot_w;
     let to_y = |y: f64| MARGIN_BOTTOM + (y - y_min) / y_range * PLOT_H;
 
-    let mut f = File::create(path)?;
-
-    writeln!(f, "%!PS-Adobe-3.0 EPSF-3.0")?;
-    writeln!(
-        f,
-        "%%BoundingBox: 0 0 {} {}",
-        W.ceil() as i32,
-        H.ceil() as i32
-    )?;
-    writeln!(f, "%%Creator: datui")?;
-    writeln!(f, "%%EndComments")?;
     writeln!(f, "gsave")?;
     writeln!(f, "1 setlinewidth")?;
 
@@ -182,7 +332,7 @@ pub fn write_chart_eps(
     writeln!(f, "0.5 setlinewidth")?;
     for &v in &x_ticks {
         let px = to_x(v);
-        if (MARGIN_LEFT..=MARGIN_LEFT + PLOT_W).contains(&px) {
+        if (MARGIN_LEFT..=MARGIN_LEFT + plot_w).contains(&px) {
             writeln!(
                 f,
                 "{} {} moveto 0 {} rlineto stroke",
@@ -196,7 +346,7 @@ pub fn write_chart_eps(
             writeln!(
                 f,
                 "{} {} moveto {} 0 rlineto stroke",
-                MARGIN_LEFT, py, PLOT_W
+                MARGIN_LEFT, py, plot_w
             )?;
         }
     }
@@ -205,16 +355,16 @@ pub fn write_chart_eps(
 
     // Axis box
     writeln!(f, "{} {} moveto", MARGIN_LEFT, MARGIN_BOTTOM)?;
-    writeln!(f, "{} 0 rlineto", PLOT_W)?;
+    writeln!(f, "{} 0 rlineto", plot_w)?;
     writeln!(f, "0 {} rlineto", PLOT_H)?;
-    writeln!(f, "{} 0 rlineto", -PLOT_W)?;
+    writeln!(f, "{} 0 rlineto", -plot_w)?;
     writeln!(f, "closepath stroke")?;
 
     // Tick marks (short lines on axes)
     const TICK_LEN: f64 = 4.0;
     for &v in &x_ticks {
         let px = to_x(v);
-        if (MARGIN_LEFT..=MARGIN_LEFT + PLOT_W).contains(&px) {
+        if (MARGIN_LEFT..=MARGIN_LEFT + plot_w).contains(&px) {
             writeln!(
                 f,
                 "{} {} moveto 0 {} rlineto stroke",
@@ -236,15 +386,14 @@ pub fn write_chart_eps(
     // Tick labels and axis titles (text)
     writeln!(f, "/Helvetica findfont 9 scalefont setfont")?;
     let char_w: f64 = 5.0;
-    let format_x_tick = |v: f64| format_x_axis_label(v, bounds.x_axis_kind);
     for &v in &x_ticks {
         let px = to_x(v);
-        if (MARGIN_LEFT..=MARGIN_LEFT + PLOT_W).contains(&px) {
-            let s = format_x_tick(v);
+        if (MARGIN_LEFT..=MARGIN_LEFT + plot_w).contains(&px) {
+            let s = bounds.x_tick(v);
             let label_w = s.len() as f64 * char_w;
             let tx = (px - label_w / 2.0)
                 .max(MARGIN_LEFT)
-                .min(MARGIN_LEFT + PLOT_W - label_w);
+                .min(MARGIN_LEFT + plot_w - label_w);
             writeln!(
                 f,
                 "{} {} moveto ({}) show",
@@ -254,17 +403,10 @@ pub fn write_chart_eps(
             )?;
         }
     }
-    let format_y_tick = |v: f64| {
-        if bounds.log_scale {
-            format_axis_label(v.exp_m1())
-        } else {
-            format_tick(v)
-        }
-    };
     for &v in &y_ticks {
         let py = to_y(v);
         if (MARGIN_BOTTOM..=MARGIN_BOTTOM + PLOT_H).contains(&py) {
-            let s = format_y_tick(v);
+            let s = bounds.y_tick(v);
             let label_w = s.len() as f64 * char_w;
             let tx = (MARGIN_LEFT - label_w - 4.0).max(2.0);
             writeln!(f, "{} {} moveto ({}) show", tx, py - 3.0, ps_escape(&s))?;
@@ -276,7 +418,7 @@ pub fn write_chart_eps(
     let x_label = &bounds.x_label;
     let y_label = &bounds.y_label;
     if !x_label.is_empty() {
-        let x_center = MARGIN_LEFT + PLOT_W / 2.0;
+        let x_center = MARGIN_LEFT + plot_w / 2.0;
         let x_str_approx_len = x_label.len() as f64 * char_w;
         writeln!(
             f,
@@ -303,6 +445,43 @@ pub fn write_chart_eps(
         writeln!(f, "grestore")?;
     }
 
+    // Right-hand axis: ticks at the positions of its values on the left axis, labels outside
+    if let Some(right) = &bounds.right_axis {
+        writeln!(f, "/Helvetica findfont 9 scalefont setfont")?;
+        let right_x = MARGIN_LEFT + plot_w;
+        for v in nice_ticks(right.y_min, right.y_max, MAX_TICKS) {
+            let py = to_y(right.to_left(v, y_min, y_max));
+            if (MARGIN_BOTTOM..=MARGIN_BOTTOM + PLOT_H).contains(&py) {
+                writeln!(f, "{} {} moveto {} 0 rlineto stroke", right_x, py, TICK_LEN)?;
+                writeln!(
+                    f,
+                    "{} {} moveto ({}) show",
+                    right_x + TICK_LEN + 2.0,
+                    py - 3.0,
+                    ps_escape(&bounds.y_tick(v))
+                )?;
+            }
+        }
+        if !right.label.is_empty() {
+            writeln!(f, "/Helvetica findfont 10 scalefont setfont")?;
+            writeln!(f, "gsave")?;
+            writeln!(
+                f,
+                "{} {} translate 90 rotate",
+                W - 6.0,
+                MARGIN_BOTTOM + PLOT_H / 2.0
+            )?;
+            let label_w = right.label.len() as f64 * char_w;
+            writeln!(
+                f,
+                "{} 0 moveto ({}) show",
+                -label_w / 2.0,
+                ps_escape(&right.label)
+            )?;
+            writeln!(f, "grestore")?;
+        }
+    }
+
     // Fixed palette (RGB 0–1)
     let palette: [(f64, f64, f64); 7] = [
         (0.0, 0.7, 0.9), // cyan
@@ -314,6 +493,40 @@ pub fn write_chart_eps(
         (0.5, 0.9, 0.9), // light cyan
     ];
 
+    for (idx, s) in series.iter().enumerate() {
+        let Some(band) = s.band.as_ref().filter(|b| !b.points.is_empty()) else {
+            continue;
+        };
+        let (r, g, b) = palette[idx % palette.len()];
+        match band.style {
+            BandStyle::Shaded => {
+                // Tint of the series color, since plain EPS has no transparency.
+                let tint = |c: f64| c + (1.0 - c) * 0.75;
+                writeln!(f, "{} {} {} setrgbcolor", tint(r), tint(g), tint(b))?;
+                let outline = band.outline();
+                let (px, py) = outline[0];
+                writeln!(f, "{} {} moveto", to_x(px), to_y(py))?;
+                for &(px, py) in &outline[1..] {
+                    writeln!(f, "{} {} lineto", to_x(px), to_y(py))?;
+                }
+                writeln!(f, "closepath fill")?;
+            }
+            BandStyle::ErrorBars => {
+                const CAP: f64 = 2.5;
+                writeln!(f, "{} {} {} setrgbcolor", r, g, b)?;
+                writeln!(f, "0.75 setlinewidth")?;
+                for &(px, lower, upper) in &band.points {
+                    let (x, y0, y1) = (to_x(px), to_y(lower), to_y(upper));
+                    writeln!(f, "{} {} moveto {} {} lineto stroke", x, y0, x, y1)?;
+                    for y in [y0, y1] {
+                        writeln!(f, "{} {} moveto {} 0 rlineto stroke", x - CAP, y, 2.0 * CAP)?;
+                    }
+                }
+                writeln!(f, "1 setlinewidth")?;
+            }
+        }
+    }
+
     for (idx, s) in series.iter().enumerate() {
         if s.points.is_empty() {
             continue;
@@ -338,7 +551,7 @@ pub fn write_chart_eps(
             }
             ChartType::Bar => {
                 let n = s.points.len() as f64;
-                let bar_w = (PLOT_W / n).clamp(1.0, 20.0) * 0.7;
+                let bar_w = (plot_w / n).clamp(1.0, 20.0) * 0.7;
                 for &(px, py) in &s.points {
                     let cx = to_x(px) - bar_w / 2.0;
                     let cy = to_y(0.0_f64.max(y_min));
@@ -350,8 +563,6 @@ pub fn write_chart_eps(
     }
 
     writeln!(f, "grestore")?;
-    writeln!(f, "%%EOF")?;
-    f.sync_all()?;
     Ok(())
 }
 
@@ -365,40 +576,53 @@ pub fn write_chart_png(
 ) -> Result<()> {
     use plotters::prelude::*;
 
+    let root = BitMapBackend::new(path, (width, height)).into_drawing_area();
+    root.fill(&WHITE)?;
+    draw_chart_png(&root, series, chart_type, bounds)?;
+    root.present()?;
+    Ok(())
+}
+
+/// Draw an XY chart (line, scatter or bar series) onto `root`.
+fn draw_chart_png(
+    root: &BitMapArea<'_>,
+    series: &[ChartExportSeries],
+    chart_type: ChartType,
+    bounds: &ChartExportBounds,
+) -> Result<()> {
+    use plotters::prelude::*;
+
     if series.is_empty() || series.iter().all(|s| s.points.is_empty()) {
         return Err(color_eyre::eyre::eyre!("No data to export"));
     }
 
-    let root = BitMapBackend::new(path, (width, height)).into_drawing_area();
-    root.fill(&WHITE)?;
-
     let x_min = bounds.x_min;
     let x_max = bounds.x_max;
     let y_min = bounds.y_min;
     let y_max = bounds.y_max;
 
-    let mut binding = ChartBuilder::on(&root);
+    let mut binding = ChartBuilder::on(root);
     let builder = binding.margin(30);
     let builder = if let Some(t) = bounds.chart_title.as_ref().filter(|s| !s.is_empty()) {
         builder.caption(t.as_str(), ("sans-serif", 20))
     } else {
         builder
     };
+    let right = bounds.right_axis.as_ref();
+    // Series are drawn on the left coordinates (right-axis ones already scaled onto them); the
+    // secondary coordinates only label the right-hand axis.
     let mut chart = builder
         .x_label_area_size(40)
         .y_label_area_size(50)
-        .build_cartesian_2d(x_min..x_max, y_min..y_max)?;
-
-    let x_axis_kind = bounds.x_axis_kind;
-    let log_scale = bounds.log_scale;
-    let x_formatter = move |v: &f64| format_x_axis_label(*v, x_axis_kind);
-    let y_formatter = move |v: &f64| {
-        if log_scale {
-            format_axis_label(v.exp_m1())
-        } else {
-            format_axis_label(*v)
-        }
-    };
+        .right_y_label_area_size(if right.is_some() { 60 } else { 0 })
+        .build_cartesian_2d(x_min..x_max, y_min..y_max)?
+        .set_secondary_coord(
+            x_min..x_max,
+            right.map_or(y_min..y_max, |r| r.y_min..r.y_max),
+        );
+
+    let x_formatter = |v: &f64| bounds.x_tick(*v);
+    let y_formatter = |v: &f64| bounds.y_tick(*v);
     chart
         .configure_mesh()
         .x_desc(bounds.x_label.as_str())
@@ -406,6 +630,13 @@ pub fn write_chart_png(
         .x_label_formatter(&x_formatter)
         .y_label_formatter(&y_formatter)
         .draw()?;
+    if let Some(right) = right {
+        chart
+            .configure_secondary_axes()
+            .y_desc(right.label.as_str())
+            .y_label_formatter(&y_formatter)
+            .draw()?;
+    }
 
     let colors = [
         CYAN,
@@ -417,6 +648,32 @@ pub fn write_chart_png(
         RGBColor(128, 255, 255),
     ];
 
+    for (idx, s) in series.iter().enumerate() {
+        let Some(band) = s.band.as_ref().filter(|b| !b.points.is_empty()) else {
+            continue;
+        };
+        let color = colors[idx % colors.len()];
+        match band.style {
+            BandStyle::Shaded => {
+                chart.draw_series(std::iter::once(Polygon::new(
+                    band.outline(),
+                    color.mix(0.25).filled(),
+                )))?;
+            }
+            BandStyle::ErrorBars => {
+                let cap = (x_max - x_min) * 0.005;
+                let style = color.stroke_width(2);
+                chart.draw_series(band.points.iter().flat_map(|&(x, lower, upper)| {
+                    [
+                        PathElement::new(vec![(x, lower), (x, upper)], style),
+                        PathElement::new(vec![(x - cap, lower), (x + cap, lower)], style),
+                        PathElement::new(vec![(x - cap, upper), (x + cap, upper)], style),
+                    ]
+                }))?;
+            }
+        }
+    }
+
     for (idx, s) in series.iter().enumerate() {
         if s.points.is_empty() {
             continue;
@@ -453,7 +710,6 @@ pub fn write_chart_png(
         .border_style(BLACK)
         .draw()?;
 
-    root.present()?;
     Ok(())
 }
 
@@ -466,16 +722,25 @@ pub fn write_box_plot_png(
 ) -> Result<()> {
     use plotters::prelude::*;
 
-    if data.stats.is_empty() {
-        return Err(color_eyre::eyre::eyre!("No data to export"));
-    }
-
     let root = BitMapBackend::new(path, (width, height)).into_drawing_area();
     root.fill(&WHITE)?;
+    draw_box_plot_png(&root, data, bounds)?;
+    root.present()?;
+    Ok(())
+}
+
+/// Chart for `count` categories (box plots, bars) at x = 0, 1, ..., with the axes drawn and the
+/// x ticks labelled with `bounds.x_labels`.
+fn category_chart_png<'a, 'b>(
+    root: &'a BitMapArea<'b>,
+    count: usize,
+    bounds: &BoxPlotExportBounds,
+) -> Result<BitMapChart<'a, 'b>> {
+    use plotters::prelude::*;
 
     let x_min = -0.5;
-    let x_max = (data.stats.len() as f64 - 1.0).max(0.0) + 0.5;
-    let mut binding = ChartBuilder::on(&root);
+    let x_max = (count as f64 - 1.0).max(0.0) + 0.5;
+    let mut binding = ChartBuilder::on(root);
     let builder = binding.margin(30);
     let builder = if let Some(t) = bounds.chart_title.as_ref().filter(|s| !s.is_empty()) {
         builder.caption(t.as_str(), ("sans-serif", 20))
@@ -507,7 +772,24 @@ pub fn write_box_plot_png(
                 String::new()
             }
         })
+        .y_label_formatter(&|v| format_tick(*v, XAxisTemporalKind::Numeric, bounds.y_ticks))
         .draw()?;
+    Ok(chart)
+}
+
+/// Draw a box plot onto `root`.
+fn draw_box_plot_png(
+    root: &BitMapArea<'_>,
+    data: &BoxPlotData,
+    bounds: &BoxPlotExportBounds,
+) -> Result<()> {
+    use plotters::prelude::*;
+
+    if data.stats.is_empty() {
+        return Err(color_eyre::eyre::eyre!("No data to export"));
+    }
+
+    let mut chart = category_chart_png(root, data.stats.len(), bounds)?;
 
     let colors = [
         CYAN,
@@ -551,10 +833,44 @@ pub fn write_box_plot_png(
         )))?;
     }
 
+    Ok(())
+}
+
+/// Write category bars to PNG using plotters bitmap backend. Size is (width, height) in pixels.
+pub fn write_category_bars_png(
+    path: &Path,
+    data: &CategoryBarData,
+    bounds: &BoxPlotExportBounds,
+    (width, height): (u32, u32),
+) -> Result<()> {
+    use plotters::prelude::*;
+
+    let root = BitMapBackend::new(path, (width, height)).into_drawing_area();
+    root.fill(&WHITE)?;
+    draw_category_bars_png(&root, data, bounds)?;
     root.present()?;
     Ok(())
 }
 
+/// Draw category bars onto `root`.
+fn draw_category_bars_png(
+    root: &BitMapArea<'_>,
+    data: &CategoryBarData,
+    bounds: &BoxPlotExportBounds,
+) -> Result<()> {
+    use plotters::prelude::*;
+
+    if data.bars.is_empty() {
+        return Err(color_eyre::eyre::eyre!("No data to export"));
+    }
+    let mut chart = category_chart_png(root, data.bars.len(), bounds)?;
+    chart.draw_series(data.bars.iter().enumerate().map(|(idx, &(_, value))| {
+        let x = idx as f64;
+        Rectangle::new([(x - 0.3, 0.0), (x + 0.3, value)], CYAN.filled())
+    }))?;
+    Ok(())
+}
+
 /// Write heatmap to PNG using plotters bitmap backend. Size is (width, height) in pixels.
 pub fn write_heatmap_png(
     path: &Path,
@@ -564,14 +880,26 @@ pub fn write_heatmap_png(
 ) -> Result<()> {
     use plotters::prelude::*;
 
+    let root = BitMapBackend::new(path, (width, height)).into_drawing_area();
+    root.fill(&WHITE)?;
+    draw_heatmap_png(&root, data, bounds)?;
+    root.present()?;
+    Ok(())
+}
+
+/// Draw a heatmap onto `root`.
+fn draw_heatmap_png(
+    root: &BitMapArea<'_>,
+    data: &HeatmapData,
+    bounds: &ChartExportBounds,
+) -> Result<()> {
+    use plotters::prelude::*;
+
     if data.counts.is_empty() || data.max_count <= 0.0 {
         return Err(color_eyre::eyre::eyre!("No data to export"));
     }
 
-    let root = BitMapBackend::new(path, (width, height)).into_drawing_area();
-    root.fill(&WHITE)?;
-
-    let mut binding = ChartBuilder::on(&root);
+    let mut binding = ChartBuilder::on(root);
     let builder = binding.margin(30);
     let builder = if let Some(t) = bounds.chart_title.as_ref().filter(|s| !s.is_empty()) {
         builder.caption(t.as_str(), ("sans-serif", 20))
@@ -606,11 +934,10 @@ pub fn write_heatmap_png(
         .configure_mesh()
         .x_desc(bounds.x_label.as_str())
         .y_desc(bounds.y_label.as_str())
-        .x_label_formatter(&|v| format_x_axis_label(*v, bounds.x_axis_kind))
-        .y_label_formatter(&|v| format_axis_label(*v))
+        .x_label_formatter(&|v| bounds.x_tick(*v))
+        .y_label_formatter(&|v| bounds.y_tick(*v))
         .draw()?;
 
-    root.present()?;
     Ok(())
 }
 
@@ -620,37 +947,62 @@ pub fn write_box_plot_eps(
     data: &BoxPlotData,
     bounds: &BoxPlotExportBounds,
 ) -> Result<()> {
-    if data.stats.is_empty() {
-        return Err(color_eyre::eyre::eyre!("No data to export"));
-    }
+    write_eps(path, EPS_HEIGHT, |f| box_plot_eps_body(f, data, bounds))
+}
+
+/// Plot area of an EPS chart for categories at x = 0, 1, ...: maps values to points.
+struct CategoryScale {
+    x_min: f64,
+    x_range: f64,
+    y_min: f64,
+    y_range: f64,
+}
 
-    const W: f64 = 400.0;
-    const H: f64 = 300.0;
+impl CategoryScale {
     const MARGIN_LEFT: f64 = 50.0;
     const MARGIN_BOTTOM: f64 = 40.0;
-    const PLOT_W: f64 = W - MARGIN_LEFT - 40.0;
-    const PLOT_H: f64 = H - MARGIN_BOTTOM - 30.0;
+    const PLOT_W: f64 = EPS_WIDTH - Self::MARGIN_LEFT - 40.0;
+    const PLOT_H: f64 = EPS_HEIGHT - Self::MARGIN_BOTTOM - 30.0;
+
+    fn x(&self, x: f64) -> f64 {
+        Self::MARGIN_LEFT + (x - self.x_min) / self.x_range * Self::PLOT_W
+    }
+
+    fn y(&self, y: f64) -> f64 {
+        Self::MARGIN_BOTTOM + (y - self.y_min) / self.y_range * Self::PLOT_H
+    }
+}
+
+/// Start an EPS chart for `count` categories (box plots, bars): the title, grid, axes, ticks
+/// labelled with `bounds.x_labels`, and axis titles. Leaves a `gsave` for the caller to close.
+fn category_axes_eps(
+    f: &mut impl Write,
+    count: usize,
+    bounds: &BoxPlotExportBounds,
+) -> Result<CategoryScale> {
+    const W: f64 = EPS_WIDTH;
+    const H: f64 = EPS_HEIGHT;
+    const MARGIN_LEFT: f64 = CategoryScale::MARGIN_LEFT;
+    const MARGIN_BOTTOM: f64 = CategoryScale::MARGIN_BOTTOM;
+    const PLOT_W: f64 = CategoryScale::PLOT_W;
+    const PLOT_H: f64 = CategoryScale::PLOT_H;
 
     let x_min = -0.5;
-    let x_max = (data.stats.len() as f64 - 1.0).max(0.0) + 0.5;
+    let x_max = (count as f64 - 1.0).max(0.0) + 0.5;
     let y_min = bounds.y_min;
     let y_max = bounds.y_max;
     let x_range = if x_max > x_min { x_max - x_min } else { 1.0 };
     let y_range = if y_max > y_min { y_max - y_min } else { 1.0 };
 
-    let to_x = |x: f64| MARGIN_LEFT + (x - x_min) / x_range * PLOT_W;
-    let to_y = |y: f64| MARGIN_BOTTOM + (y - y_min) / y_range * PLOT_H;
+    let scale = CategoryScale {
+        x_min,
+        x_range,
+        y_min,
+        y_range,
+    };
+    let to_x = |x: f64| scale.x(x);
+    let to_y = |y: f64| scale.y(y);
 
-    let mut f = File::create(path)?;
-    writeln!(f, "%!PS-Adobe-3.0 EPSF-3.0")?;
-    writeln!(
-        f,
-        "%%BoundingBox: 0 0 {} {}",
-        W.ceil() as i32,
-        H.ceil() as i32
-    )?;
-    writeln!(f, "%%Creator: datui")?;
-    writeln!(f, "%%EndComments")?;
     writeln!(f, "gsave")?;
     writeln!(f, "1 setlinewidth")?;
 
@@ -667,7 +1019,7 @@ pub fn write_box_plot_eps(
 
     const MAX_TICKS: usize = 8;
     let y_ticks = nice_ticks(y_min, y_max, MAX_TICKS);
-    let x_ticks: Vec<f64> = (0..data.stats.len()).map(|i| i as f64).collect();
+    let x_ticks: Vec<f64> = (0..count).map(|i| i as f64).collect();
 
     writeln!(f, "0.9 setgray")?;
     writeln!(f, "0.5 setlinewidth")?;
@@ -744,7 +1096,7 @@ pub fn write_box_plot_eps(
     for &v in &y_ticks {
         let py = to_y(v);
         if (MARGIN_BOTTOM..=MARGIN_BOTTOM + PLOT_H).contains(&py) {
-            let s = format_axis_label(v);
+            let s = format_tick(v, XAxisTemporalKind::Numeric, bounds.y_ticks);
             let label_w = s.len() as f64 * char_w;
             let tx = (MARGIN_LEFT - label_w - 4.0).max(2.0);
             writeln!(f, "{} {} moveto ({}) show", tx, py - 3.0, ps_escape(&s))?;
@@ -779,6 +1131,21 @@ pub fn write_box_plot_eps(
         )?;
         writeln!(f, "grestore")?;
     }
+    Ok(scale)
+}
+
+fn box_plot_eps_body(
+    f: &mut impl Write,
+    data: &BoxPlotData,
+    bounds: &BoxPlotExportBounds,
+) -> Result<()> {
+    if data.stats.is_empty() {
+        return Err(color_eyre::eyre::eyre!("No data to export"));
+    }
+
+    let scale = category_axes_eps(f, data.stats.len(), bounds)?;
+    let to_x = |x: f64| scale.x(x);
+    let to_y = |y: f64| scale.y(y);
 
     let palette: [(f64, f64, f64); 7] = [
         (0.0, 0.7, 0.9),
@@ -818,8 +1185,47 @@ pub fn write_box_plot_eps(
     }
 
     writeln!(f, "grestore")?;
-    writeln!(f, "%%EOF")?;
-    f.sync_all()?;
+    Ok(())
+}
+
+/// Write category bars to EPS (Encapsulated PostScript). No external dependencies.
+pub fn write_category_bars_eps(
+    path: &Path,
+    data: &CategoryBarData,
+    bounds: &BoxPlotExportBounds,
+) -> Result<()> {
+    write_eps(path, EPS_HEIGHT, |f| {
+        category_bars_eps_body(f, data, bounds)
+    })
+}
+
+fn category_bars_eps_body(
+    f: &mut impl Write,
+    data: &CategoryBarData,
+    bounds: &BoxPlotExportBounds,
+) -> Result<()> {
+    if data.bars.is_empty() {
+        return Err(color_eyre::eyre::eyre!("No data to export"));
+    }
+
+    let scale = category_axes_eps(f, data.bars.len(), bounds)?;
+    writeln!(f, "0.0 0.7 0.9 setrgbcolor")?;
+    for (idx, (_, value)) in data.bars.iter().enumerate() {
+        let x = idx as f64;
+        let left = scale.x(x - 0.3);
+        let base = scale.y(0.0);
+        let w = scale.x(x + 0.3) - left;
+        writeln!(
+            f,
+            "{} {} {} {} rectfill",
+            left,
+            base,
+            w,
+            scale.y(*value) - base
+        )?;
+    }
+
+    writeln!(f, "grestore")?;
     Ok(())
 }
 
@@ -828,13 +1234,21 @@ pub fn write_heatmap_eps(
     path: &Path,
     data: &HeatmapData,
     bounds: &ChartExportBounds,
+) -> Result<()> {
+    write_eps(path, EPS_HEIGHT, |f| heatmap_eps_body(f, data, bounds))
+}
+
+fn heatmap_eps_body(
+    f: &mut impl Write,
+    data: &HeatmapData,
+    bounds: &ChartExportBounds,
 ) -> Result<()> {
     if data.counts.is_empty() || data.max_count <= 0.0 {
         return Err(color_eyre::eyre::eyre!("No data to export"));
     }
 
-    const W: f64 = 400.0;
-    const H: f64 = 300.0;
+    const W: f64 = EPS_WIDTH;
+    const H: f64 = EPS_HEIGHT;
     const MARGIN_LEFT: f64 = 50.0;
     const MARGIN_BOTTOM: f64 = 40.0;
     const PLOT_W: f64 = W - MARGIN_LEFT - 40.0;
@@ -849,16 +1263,6 @@ pub fn write_heatmap_eps(
     let to_x = |x: f64| MARGIN_LEFT + (x - x_min) / x_range * PLOT_W;
     let to_y = |y: f64| MARGIN_BOTTOM + (y - y_min) / y_range * PLOT_H;
 
-    let mut f = File::create(path)?;
-    writeln!(f, "%!PS-Adobe-3.0 EPSF-3.0")?;
-    writeln!(
-        f,
-        "%%BoundingBox: 0 0 {} {}",
-        W.ceil() as i32,
-        H.ceil() as i32
-    )?;
-    writeln!(f, "%%Creator: datui")?;
-    writeln!(f, "%%EndComments")?;
     writeln!(f, "gsave")?;
     writeln!(f, "1 setlinewidth")?;
 
@@ -952,7 +1356,7 @@ pub fn write_heatmap_eps(
     for &v in &x_ticks {
         let px = to_x(v);
         if (MARGIN_LEFT..=MARGIN_LEFT + PLOT_W).contains(&px) {
-            let s = format_x_axis_label(v, bounds.x_axis_kind);
+            let s = bounds.x_tick(v);
             let label_w = s.len() as f64 * char_w;
             let tx = (px - label_w / 2.0)
                 .max(MARGIN_LEFT)
@@ -969,7 +1373,7 @@ pub fn write_heatmap_eps(
     for &v in &y_ticks {
         let py = to_y(v);
         if (MARGIN_BOTTOM..=MARGIN_BOTTOM + PLOT_H).contains(&py) {
-            let s = format_axis_label(v);
+            let s = bounds.y_tick(v);
             let label_w = s.len() as f64 * char_w;
             let tx = (MARGIN_LEFT - label_w - 4.0).max(2.0);
             writeln!(f, "{} {} moveto ({}) show", tx, py - 3.0, ps_escape(&s))?;
@@ -1006,11 +1410,545 @@ pub fn write_heatmap_eps(
     }
 
     writeln!(f, "grestore")?;
-    writeln!(f, "%%EOF")?;
-    f.sync_all()?;
     Ok(())
 }
 
+/// Write a correlation matrix to PNG using plotters bitmap backend. Size is (width, height) in
+/// pixels.
+pub fn write_correlation_png(
+    path: &Path,
+    matrix: &CorrelationMatrix,
+    bounds: &CorrelationExportBounds,
+    (width, height): (u32, u32),
+) -> Result<()> {
+    use plotters::prelude::*;
+
+    let root = BitMapBackend::new(path, (width, height)).into_drawing_area();
+    root.fill(&WHITE)?;
+    draw_correlation_png(&root, matrix, bounds)?;
+    root.present()?;
+    Ok(())
+}
+
+/// Draw a correlation matrix onto `root`: a square grid of shaded cells with their values,
+/// column names along the left and bottom, and a color scale on the right.
+fn draw_correlation_png(
+    root: &BitMapArea<'_>,
+    matrix: &CorrelationMatrix,
+    bounds: &CorrelationExportBounds,
+) -> Result<()> {
+    use plotters::prelude::*;
+    use plotters::style::text_anchor::{HPos, Pos, VPos};
+
+    let n = matrix.columns.len();
+    if n == 0 {
+        return Err(color_eyre::eyre::eyre!("No data to export"));
+    }
+
+    const MARGIN: i32 = 30;
+    const LABEL_CHARS: usize = 16;
+    const LABEL_PX: i32 = 8 * LABEL_CHARS as i32;
+    const SCALE_W: i32 = 70;
+    let (width, height) = root.dim_in_pixel();
+    let title = bounds.chart_title.as_ref().filter(|s| !s.is_empty());
+    let top = MARGIN + if title.is_some() { 30 } else { 0 };
+    let grid_w = width as i32 - 2 * MARGIN - LABEL_PX - SCALE_W;
+    let grid_h = height as i32 - top - MARGIN - LABEL_PX;
+    let cell = (grid_w.min(grid_h) / n as i32).max(1);
+    let left = MARGIN + LABEL_PX;
+
+    if let Some(t) = title {
+        root.draw(&Text::new(
+            t.as_str(),
+            (width as i32 / 2, MARGIN),
+            TextStyle::from(("sans-serif", 20)).pos(Pos::new(HPos::Center, VPos::Center)),
+        ))?;
+    }
+
+    let value_size = (cell / 3).clamp(8, 16);
+    let label_font = TextStyle::from(("sans-serif", 15));
+    for (i, row) in matrix.correlations.iter().enumerate() {
+        let y0 = top + i as i32 * cell;
+        for (j, &r) in row.iter().enumerate() {
+            let x0 = left + j as i32 * cell;
+            let (red, green, blue) = diverging_rgb(r);
+            let fill = RGBColor(
+                (red * 255.0) as u8,
+                (green * 255.0) as u8,
+                (blue * 255.0) as u8,
+            );
+            root.draw(&Rectangle::new(
+                [(x0, y0), (x0 + cell, y0 + cell)],
+                fill.filled(),
+            ))?;
+            if cell >= 24 {
+                // Light text on the darkest shades
+                let text = if r.abs() > 0.6 { WHITE } else { BLACK };
+                root.draw(&Text::new(
+                    correlation_label(r),
+                    (x0 + cell / 2, y0 + cell / 2),
+                    ("sans-serif", value_size)
+                        .into_font()
+                        .color(&text)
+                        .pos(Pos::new(HPos::Center, VPos::Center)),
+                ))?;
+            }
+        }
+    }
+    // Outline strong correlations after all fills so neighbours do not cover the border
+    for (i, row) in matrix.correlations.iter().enumerate() {
+        for (j, &r) in row.iter().enumerate() {
+            if i != j && r.abs() >= bounds.threshold {
+                let (x0, y0) = (left + j as i32 * cell, top + i as i32 * cell);
+                root.draw(&Rectangle::new(
+                    [(x0 + 1, y0 + 1), (x0 + cell - 1, y0 + cell - 1)],
+                    BLACK.stroke_width(2),
+                ))?;
+            }
+        }
+    }
+
+    for (i, name) in matrix.columns.iter().enumerate() {
+        let label = truncate_label(name, LABEL_CHARS);
+        let center = i as i32 * cell + cell / 2;
+        root.draw(&Text::new(
+            label.clone(),
+            (left - 6, top + center),
+            label_font.clone().pos(Pos::new(HPos::Right, VPos::Center)),
+        ))?;
+        root.draw(&Text::new(
+            label,
+            (left + center, top + n as i32 * cell + 6),
+            label_font
+                .transform(FontTransform::Rotate90)
+                .pos(Pos::new(HPos::Left, VPos::Center)),
+        ))?;
+    }
+
+    // Color scale: -1 at the bottom to +1 at the top, with the threshold marked
+    let scale_x = left + n as i32 * cell + 20;
+    let scale_h = n as i32 * cell;
+    for py in 0..scale_h {
+        let r = 1.0 - 2.0 * py as f64 / scale_h.max(1) as f64;
+        let (red, green, blue) = diverging_rgb(r);
+        root.draw(&Rectangle::new(
+            [(scale_x, top + py), (scale_x + 16, top + py + 1)],
+            RGBColor(
+                (red * 255.0) as u8,
+                (green * 255.0) as u8,
+                (blue * 255.0) as u8,
+            )
+            .filled(),
+        ))?;
+    }
+    root.draw(&Rectangle::new(
+        [(scale_x, top), (scale_x + 16, top + scale_h)],
+        BLACK.stroke_width(1),
+    ))?;
+    let to_y = |r: f64| top + ((1.0 - r) / 2.0 * scale_h as f64) as i32;
+    for (r, label) in [(1.0, "+1"), (0.0, "0"), (-1.0, "-1")] {
+        root.draw(&Text::new(
+            label,
+            (scale_x + 22, to_y(r)),
+            label_font.clone().pos(Pos::new(HPos::Left, VPos::Center)),
+        ))?;
+    }
+    for r in [bounds.threshold, -bounds.threshold] {
+        let y = to_y(r);
+        root.draw(&PathElement::new(
+            vec![(scale_x - 4, y), (scale_x + 20, y)],
+            BLACK.stroke_width(2),
+        ))?;
+    }
+    Ok(())
+}
+
+/// Write a correlation matrix to EPS (Encapsulated PostScript). No external dependencies.
+pub fn write_correlation_eps(
+    path: &Path,
+    matrix: &CorrelationMatrix,
+    bounds: &CorrelationExportBounds,
+) -> Result<()> {
+    write_eps(path, EPS_HEIGHT, |f| {
+        correlation_eps_body(f, matrix, bounds)
+    })
+}
+
+fn correlation_eps_body(
+    f: &mut impl Write,
+    matrix: &CorrelationMatrix,
+    bounds: &CorrelationExportBounds,
+) -> Result<()> {
+    let n = matrix.columns.len();
+    if n == 0 {
+        return Err(color_eyre::eyre::eyre!("No data to export"));
+    }
+
+    const W: f64 = EPS_WIDTH;
+    const H: f64 = EPS_HEIGHT;
+    const MARGIN: f64 = 10.0;
+    const LABEL_CHARS: usize = 14;
+    const LABEL_W: f64 = 5.0 * LABEL_CHARS as f64;
+    const SCALE_W: f64 = 40.0;
+    let title = bounds.chart_title.as_ref().filter(|s| !s.is_empty());
+    let top = H - MARGIN - if title.is_some() { 20.0 } else { 0.0 };
+    let grid_w = W - 2.0 * MARGIN - LABEL_W - SCALE_W;
+    let grid_h = top - MARGIN - LABEL_W;
+    let cell = grid_w.min(grid_h) / n as f64;
+    let left = MARGIN + LABEL_W;
+    // Row i runs top to bottom; PostScript y grows upwards.
+    let row_y = |i: usize| top - (i + 1) as f64 * cell;
+
+    writeln!(f, "gsave")?;
+    if let Some(title) = title {
+        const CHAR_W: f64 = 6.0;
+        writeln!(f, "/Helvetica findfont 12 scalefont setfont")?;
+        let title_w = title.len() as f64 * CHAR_W;
+        let tx = (W / 2.0 - title_w / 2.0).max(4.0).min(W - title_w - 4.0);
+        writeln!(f, "{} {} moveto ({}) show", tx, H - 15.0, ps_escape(title))?;
+    }
+
+    let value_size = (cell / 3.0).clamp(4.0, 9.0);
+    writeln!(f, "/Helvetica findfont {} scalefont setfont", value_size)?;
+    for (i, row) in matrix.correlations.iter().enumerate() {
+        for (j, &r) in row.iter().enumerate() {
+            let (x0, y0) = (left + j as f64 * cell, row_y(i));
+            let (red, green, blue) = diverging_rgb(r);
+            writeln!(f, "{} {} {} setrgbcolor", red, green, blue)?;
+            writeln!(f, "{} {} {} {} rectfill", x0, y0, cell, cell)?;
+            if cell >= 14.0 {
+                let s = correlation_label(r);
+                let gray = if r.abs() > 0.6 { 1 } else { 0 };
+                let text_w = s.len() as f64 * value_size * 0.55;
+                writeln!(f, "{} setgray", gray)?;
+                writeln!(
+                    f,
+                    "{} {} moveto ({}) show",
+                    x0 + (cell - text_w) / 2.0,
+                    y0 + (cell - value_size * 0.7) / 2.0,
+                    s
+                )?;
+            }
+        }
+    }
+    writeln!(f, "0 setgray")?;
+    writeln!(f, "1.5 setlinewidth")?;
+    for (i, row) in matrix.correlations.iter().enumerate() {
+        for (j, &r) in row.iter().enumerate() {
+            if i != j && r.abs() >= bounds.threshold {
+                writeln!(
+                    f,
+                    "{} {} {} {} rectstroke",
+                    left + j as f64 * cell + 0.75,
+                    row_y(i) + 0.75,
+                    cell - 1.5,
+                    cell - 1.5
+                )?;
+            }
+        }
+    }
+
+    writeln!(f, "/Helvetica findfont 8 scalefont setfont")?;
+    let char_w: f64 = 4.5;
+    let grid_bottom = row_y(n - 1);
+    for (i, name) in matrix.columns.iter().enumerate() {
+        let label = ps_escape(&truncate_label(name, LABEL_CHARS));
+        let label_w = label.len() as f64 * char_w;
+        let center = i as f64 * cell + cell / 2.0;
+        writeln!(
+            f,
+            "{} {} moveto ({}) show",
+            left - label_w - 4.0,
+            top - center - 3.0,
+            label
+        )?;
+        writeln!(f, "gsave")?;
+        writeln!(
+            f,
+            "{} {} translate -90 rotate",
+            left + center + 3.0,
+            grid_bottom - 4.0
+        )?;
+        writeln!(f, "0 0 moveto ({}) show", label)?;
+        writeln!(f, "grestore")?;
+    }
+
+    // Color scale: -1 at the bottom to +1 at the top, with the threshold marked
+    let scale_x = left + n as f64 * cell + 12.0;
+    let scale_h = n as f64 * cell;
+    const STEPS: usize = 50;
+    for step in 0..STEPS {
+        let r = -1.0 + 2.0 * (step as f64 + 0.5) / STEPS as f64;
+        let (red, green, blue) = diverging_rgb(r);
+        writeln!(f, "{} {} {} setrgbcolor", red, green, blue)?;
+        writeln!(
+            f,
+            "{} {} 8 {} rectfill",
+            scale_x,
+            grid_bottom + step as f64 * scale_h / STEPS as f64,
+            scale_h / STEPS as f64
+        )?;
+    }
+    writeln!(f, "0 setgray 0.5 setlinewidth")?;
+    writeln!(f, "{} {} 8 {} rectstroke", scale_x, grid_bottom, scale_h)?;
+    let to_y = |r: f64| grid_bottom + (r + 1.0) / 2.0 * scale_h;
+    for (r, label) in [(1.0, "+1"), (0.0, "0"), (-1.0, "-1")] {
+        writeln!(
+            f,
+            "{} {} moveto ({}) show",
+            scale_x + 11.0,
+            to_y(r) - 3.0,
+            label
+        )?;
+    }
+    writeln!(f, "1.5 setlinewidth")?;
+    for r in [bounds.threshold, -bounds.threshold] {
+        writeln!(
+            f,
+            "{} {} moveto 12 0 rlineto stroke",
+            scale_x - 2.0,
+            to_y(r)
+        )?;
+    }
+
+    writeln!(f, "grestore")?;
+    Ok(())
+}
+
+/// The chart part of an export: what `write_chart_export` draws above an optional summary table.
+pub enum ExportPlot<'a> {
+    Xy {
+        series: &'a [ChartExportSeries],
+        chart_type: ChartType,
+        bounds: &'a ChartExportBounds,
+    },
+    BoxPlot {
+        data: &'a BoxPlotData,
+        bounds: &'a BoxPlotExportBounds,
+    },
+    Heatmap {
+        data: &'a HeatmapData,
+        bounds: &'a ChartExportBounds,
+    },
+    CategoryBars {
+        data: &'a CategoryBarData,
+        bounds: &'a BoxPlotExportBounds,
+    },
+    Correlation {
+        matrix: &'a CorrelationMatrix,
+        bounds: &'a CorrelationExportBounds,
+    },
+}
+
+/// Write `plot` to `path`. With a `summary`, the file is a "report card": the chart with a table
+/// of the columns' summary statistics below it. The table adds to the height, so the chart keeps
+/// the requested (width, height) in pixels for PNG and its fixed logical size for EPS.
+pub fn write_chart_export(
+    path: &Path,
+    format: ChartExportFormat,
+    plot: &ExportPlot,
+    summary: Option<&[ColumnSummary]>,
+    (width, height): (u32, u32),
+) -> Result<()> {
+    match (format, summary) {
+        (ChartExportFormat::Png, None) => match *plot {
+            ExportPlot::Xy {
+                series,
+                chart_type,
+                bounds,
+            } => write_chart_png(path, series, chart_type, bounds, (width, height)),
+            ExportPlot::BoxPlot { data, bounds } => {
+                write_box_plot_png(path, data, bounds, (width, height))
+            }
+            ExportPlot::Heatmap { data, bounds } => {
+                write_heatmap_png(path, data, bounds, (width, height))
+            }
+            ExportPlot::CategoryBars { data, bounds } => {
+                write_category_bars_png(path, data, bounds, (width, height))
+            }
+            ExportPlot::Correlation { matrix, bounds } => {
+                write_correlation_png(path, matrix, bounds, (width, height))
+            }
+        },
+        (ChartExportFormat::Eps, None) => match *plot {
+            ExportPlot::Xy {
+                series,
+                chart_type,
+                bounds,
+            } => write_chart_eps(path, series, chart_type, bounds),
+            ExportPlot::BoxPlot { data, bounds } => write_box_plot_eps(path, data, bounds),
+            ExportPlot::Heatmap { data, bounds } => write_heatmap_eps(path, data, bounds),
+            ExportPlot::CategoryBars { data, bounds } => {
+                write_category_bars_eps(path, data, bounds)
+            }
+            ExportPlot::Correlation { matrix, bounds } => {
+                write_correlation_eps(path, matrix, bounds)
+            }
+        },
+        (ChartExportFormat::Png, Some(summary)) => {
+            write_report_card_png(path, plot, summary, (width, height))
+        }
+        (ChartExportFormat::Eps, Some(summary)) => write_report_card_eps(path, plot, summary),
+    }
+}
+
+/// Longest column name shown in the summary table; longer names are cut with `...` (ASCII, so
+/// EPS fonts can show it).
+const SUMMARY_NAME_MAX: usize = 24;
+
+/// Summary table cells, header row first.
+fn summary_table_rows(summary: &[ColumnSummary]) -> Vec<Vec<String>> {
+    let mut rows = vec![["Column", "Count", "Min", "Mean", "Median", "Std", "Max"]
+        .iter()
+        .map(|h| h.to_string())
+        .collect::<Vec<_>>()];
+    for s in summary {
+        let name = if s.column.chars().count() > SUMMARY_NAME_MAX {
+            let mut cut: String = s.column.chars().take(SUMMARY_NAME_MAX - 3).collect();
+            cut.push_str("...");
+            cut
+        } else {
+            s.column.clone()
+        };
+        rows.push(vec![
+            name,
+            s.count.to_string(),
+            format_axis_label(s.min),
+            format_axis_label(s.mean),
+            format_axis_label(s.median),
+            format_axis_label(s.std),
+            format_axis_label(s.max),
+        ]);
+    }
+    rows
+}
+
+/// Left edge of each table column, in characters, given `gap` characters between columns.
+fn summary_column_offsets(rows: &[Vec<String>], gap: usize) -> Vec<usize> {
+    let cols = rows.first().map_or(0, |r| r.len());
+    let mut offsets = Vec::with_capacity(cols);
+    let mut x = 0;
+    for c in 0..cols {
+        offsets.push(x);
+        let width = rows.iter().map(|r| r[c].chars().count()).max().unwrap_or(0);
+        x += width + gap;
+    }
+    offsets
+}
+
+fn write_report_card_png(
+    path: &Path,
+    plot: &ExportPlot,
+    summary: &[ColumnSummary],
+    (width, height): (u32, u32),
+) -> Result<()> {
+    use plotters::prelude::*;
+    use plotters::style::FontStyle;
+
+    const ROW_PX: u32 = 24;
+    const MARGIN_PX: i32 = 30;
+    const CHAR_PX: usize = 9;
+    let rows = summary_table_rows(summary);
+    let table_height = rows.len() as u32 * ROW_PX + 2 * MARGIN_PX as u32;
+
+    let root = BitMapBackend::new(path, (width, height + table_height)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let (chart_area, table_area) = root.split_vertically(height);
+    match *plot {
+        ExportPlot::Xy {
+            series,
+            chart_type,
+            bounds,
+        } => draw_chart_png(&chart_area, series, chart_type, bounds)?,
+        ExportPlot::BoxPlot { data, bounds } => draw_box_plot_png(&chart_area, data, bounds)?,
+        ExportPlot::Heatmap { data, bounds } => draw_heatmap_png(&chart_area, data, bounds)?,
+        ExportPlot::CategoryBars { data, bounds } => {
+            draw_category_bars_png(&chart_area, data, bounds)?
+        }
+        ExportPlot::Correlation { matrix, bounds } => {
+            draw_correlation_png(&chart_area, matrix, bounds)?
+        }
+    }
+
+    let offsets = summary_column_offsets(&rows, 2);
+    for (i, row) in rows.iter().enumerate() {
+        let y = MARGIN_PX + (i as u32 * ROW_PX) as i32;
+        let font = if i == 0 {
+            ("sans-serif", 16, FontStyle::Bold).into_font()
+        } else {
+            ("sans-serif", 16).into_font()
+        };
+        for (cell, &offset) in row.iter().zip(&offsets) {
+            let x = MARGIN_PX + (offset * CHAR_PX) as i32;
+            table_area.draw(&Text::new(cell.as_str(), (x, y), font.clone()))?;
+        }
+        if i == 0 {
+            let rule_y = y + ROW_PX as i32 - 4;
+            table_area.draw(&PathElement::new(
+                vec![(MARGIN_PX, rule_y), (width as i32 - MARGIN_PX, rule_y)],
+                BLACK,
+            ))?;
+        }
+    }
+
+    root.present()?;
+    Ok(())
+}
+
+fn write_report_card_eps(path: &Path, plot: &ExportPlot, summary: &[ColumnSummary]) -> Result<()> {
+    const ROW_H: f64 = 12.0;
+    const MARGIN: f64 = 20.0;
+    const CHAR_W: f64 = 5.0;
+    let rows = summary_table_rows(summary);
+    let table_height = rows.len() as f64 * ROW_H + 2.0 * MARGIN;
+
+    write_eps(path, EPS_HEIGHT + table_height, |f| {
+        writeln!(f, "gsave")?;
+        writeln!(f, "0 setgray")?;
+        let offsets = summary_column_offsets(&rows, 2);
+        for (i, row) in rows.iter().enumerate() {
+            let font = if i == 0 {
+                "Helvetica-Bold"
+            } else {
+                "Helvetica"
+            };
+            writeln!(f, "/{} findfont 9 scalefont setfont", font)?;
+            // Rows run top to bottom; PostScript y grows upwards.
+            let y = table_height - MARGIN - (i as f64 + 1.0) * ROW_H;
+            for (cell, &offset) in row.iter().zip(&offsets) {
+                let x = MARGIN + offset as f64 * CHAR_W;
+                writeln!(f, "{} {} moveto ({}) show", x, y, ps_escape(cell))?;
+            }
+            if i == 0 {
+                writeln!(
+                    f,
+                    "0.5 setlinewidth {} {} moveto {} 0 rlineto stroke",
+                    MARGIN,
+                    y - 3.0,
+                    EPS_WIDTH - 2.0 * MARGIN
+                )?;
+            }
+        }
+        writeln!(f, "grestore")?;
+
+        writeln!(f, "gsave")?;
+        writeln!(f, "0 {} translate", table_height)?;
+        match *plot {
+            ExportPlot::Xy {
+                series,
+                chart_type,
+                bounds,
+            } => chart_eps_body(f, series, chart_type, bounds)?,
+            ExportPlot::BoxPlot { data, bounds } => box_plot_eps_body(f, data, bounds)?,
+            ExportPlot::Heatmap { data, bounds } => heatmap_eps_body(f, data, bounds)?,
+            ExportPlot::CategoryBars { data, bounds } => category_bars_eps_body(f, data, bounds)?,
+            ExportPlot::Correlation { matrix, bounds } => correlation_eps_body(f, matrix, bounds)?,
+        }
+        writeln!(f, "grestore")?;
+        Ok(())
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1024,6 +1962,7 @@ mod tests {
         let series = vec![ChartExportSeries {
             name: "s1".to_string(),
             points: vec![(0.0, 1.0), (1.0, 2.0), (2.0, 1.5)],
+            band: None,
         }];
         let bounds = ChartExportBounds {
             x_min: 0.0,
@@ -1035,6 +1974,9 @@ mod tests {
             x_axis_kind: XAxisTemporalKind::Numeric,
             log_scale: false,
             chart_title: None,
+            x_ticks: TickFormat::Auto,
+            y_ticks: TickFormat::Auto,
+            right_axis: None,
         };
 
         let dir = tempfile::tempdir().expect("temp dir");
@@ -1077,4 +2019,224 @@ mod tests {
         assert!(content.contains("setrgbcolor"), "series color");
         assert!(content.contains("lineto"), "line series");
     }
+
+    #[test]
+    fn report_card_eps_appends_summary_table() {
+        let series = vec![ChartExportSeries {
+            name: "y_col".to_string(),
+            points: vec![(0.0, 1.0), (1.0, 3.0)],
+            band: None,
+        }];
+        let bounds = ChartExportBounds {
+            x_min: 0.0,
+            x_max: 1.0,
+            y_min: 0.0,
+            y_max: 3.0,
+            x_label: "x_col".to_string(),
+            y_label: "y_col".to_string(),
+            x_axis_kind: XAxisTemporalKind::Numeric,
+            log_scale: false,
+            chart_title: None,
+            x_ticks: TickFormat::Auto,
+            y_ticks: TickFormat::Auto,
+            right_axis: None,
+        };
+        let summary = vec![ColumnSummary {
+            column: "y_col".to_string(),
+            count: 2,
+            min: 1.0,
+            mean: 2.0,
+            median: 2.0,
+            std: 1.41,
+            max: 3.0,
+        }];
+        let plot = ExportPlot::Xy {
+            series: &series,
+            chart_type: ChartType::Line,
+            bounds: &bounds,
+        };
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("card.eps");
+        write_chart_export(
+            &path,
+            ChartExportFormat::Eps,
+            &plot,
+            Some(&summary),
+            (1024, 768),
+        )
+        .expect("write report card");
+        let content = std::fs::read_to_string(&path).expect("read");
+
+        // Taller than a plain chart: the table sits below it and the chart is shifted up.
+        assert!(content.contains("%%BoundingBox: 0 0 400 364"), "{content}");
+        assert!(content.contains("0 64 translate"));
+        assert!(content.contains("(Median) show"));
+        assert!(content.contains("(1.41) show"));
+        assert!(content.contains("(x_col) show"), "chart still drawn");
+        assert_eq!(content.matches("%%EOF").count(), 1);
+    }
+
+    #[test]
+    fn eps_labels_the_right_axis_in_its_own_range() {
+        let axis = ChartExportRightAxis {
+            y_min: 0.0,
+            y_max: 1000.0,
+            label: "volume".to_string(),
+        };
+        assert_eq!(axis.to_left(500.0, 0.0, 10.0), 5.0);
+        let series = vec![
+            ChartExportSeries {
+                name: "price".to_string(),
+                points: vec![(0.0, 2.0), (1.0, 8.0)],
+                band: None,
+            },
+            ChartExportSeries {
+                name: "volume (right)".to_string(),
+                points: vec![(0.0, axis.to_left(1000.0, 0.0, 10.0)), (1.0, 0.0)],
+                band: None,
+            },
+        ];
+        let bounds = ChartExportBounds {
+            x_min: 0.0,
+            x_max: 1.0,
+            y_min: 0.0,
+            y_max: 10.0,
+            x_label: "x".to_string(),
+            y_label: "price".to_string(),
+            x_axis_kind: XAxisTemporalKind::Numeric,
+            log_scale: false,
+            chart_title: None,
+            x_ticks: TickFormat::Auto,
+            y_ticks: TickFormat::Auto,
+            right_axis: Some(axis),
+        };
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("right.eps");
+        write_chart_eps(&path, &series, ChartType::Line, &bounds).expect("write_chart_eps");
+        let content = std::fs::read_to_string(&path).expect("read");
+
+        // The plot narrows to make room; the top right tick is level with the left axis's top
+        let right_x = EPS_WIDTH - 70.0;
+        assert!(content.contains(&format!("{right_x} 270 moveto 4 0 rlineto stroke")));
+        assert!(content.contains(&format!("({}) show", bounds.y_tick(1000.0))));
+        assert!(content.contains("90 rotate") && content.contains("(volume) show"));
+    }
+
+    #[test]
+    fn eps_draws_band_behind_series() {
+        let band_series = |style| {
+            vec![ChartExportSeries {
+                name: "y".to_string(),
+                points: vec![(0.0, 1.0), (1.0, 2.0)],
+                band: Some(ChartExportBand {
+                    points: vec![(1.0, 1.5, 2.5), (0.0, 0.5, 1.5)],
+                    style,
+                }),
+            }]
+        };
+        let bounds = ChartExportBounds {
+            x_min: 0.0,
+            x_max: 1.0,
+            y_min: 0.5,
+            y_max: 2.5,
+            x_label: "x".to_string(),
+            y_label: "y".to_string(),
+            x_axis_kind: XAxisTemporalKind::Numeric,
+            log_scale: false,
+            chart_title: None,
+            x_ticks: TickFormat::Auto,
+            y_ticks: TickFormat::Auto,
+            right_axis: None,
+        };
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("band.eps");
+
+        write_chart_eps(
+            &path,
+            &band_series(BandStyle::Shaded),
+            ChartType::Line,
+            &bounds,
+        )
+        .expect("write shaded band");
+        let content = std::fs::read_to_string(&path).expect("read");
+        let fill = content.find("closepath fill").expect("band polygon");
+        assert!(fill < content.rfind("stroke").unwrap(), "series drawn last");
+        // Outline runs along the upper bound (sorted by x) and back along the lower bound.
+        assert!(content.contains("50 155 moveto\n360 270 lineto\n360 155 lineto\n50 40 lineto"));
+
+        write_chart_eps(
+            &path,
+            &band_series(BandStyle::ErrorBars),
+            ChartType::Line,
+            &bounds,
+        )
+        .expect("write error bars");
+        let content = std::fs::read_to_string(&path).expect("read");
+        assert!(!content.contains("closepath fill"));
+        assert!(
+            content.contains("360 155 moveto 360 270 lineto stroke"),
+            "{content}"
+        );
+    }
+
+    #[test]
+    fn eps_draws_category_bars_with_their_labels() {
+        let data = CategoryBarData {
+            column: "region".to_string(),
+            bars: vec![("east".to_string(), 10.0), ("west (2)".to_string(), 4.0)],
+            categories: 3,
+            y_min: 0.0,
+            y_max: 10.0,
+        };
+        let bounds = BoxPlotExportBounds {
+            y_min: data.y_min,
+            y_max: data.y_max,
+            x_labels: data.bars.iter().map(|(name, _)| name.clone()).collect(),
+            x_label: "region".to_string(),
+            y_label: "Count".to_string(),
+            chart_title: None,
+            y_ticks: TickFormat::Auto,
+        };
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("bars.eps");
+        write_category_bars_eps(&path, &data, &bounds).expect("write bars");
+        let content = std::fs::read_to_string(&path).expect("read");
+        assert_eq!(content.matches("rectfill").count(), 2);
+        assert!(content.contains("(east) show"));
+        assert!(content.contains("(west \\(2\\)) show"));
+        // The tallest bar reaches the top of the plot
+        assert!(content.contains(" 40 93 230 rectfill"), "{content}");
+    }
+
+    #[test]
+    fn eps_outlines_correlations_above_the_threshold() {
+        let matrix = CorrelationMatrix {
+            columns: vec!["price".to_string(), "qty".to_string(), "flat".to_string()],
+            correlations: vec![
+                vec![1.0, -0.9, f64::NAN],
+                vec![-0.9, 1.0, f64::NAN],
+                vec![f64::NAN, f64::NAN, 1.0],
+            ],
+            p_values: None,
+            sample_sizes: vec![vec![10; 3]; 3],
+        };
+        let bounds = CorrelationExportBounds {
+            threshold: 0.7,
+            chart_title: Some("Correlations".to_string()),
+        };
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("corr.eps");
+        write_correlation_eps(&path, &matrix, &bounds).expect("write matrix");
+        let content = std::fs::read_to_string(&path).expect("read");
+        // Both cells of the strong pair are outlined, plus the color scale's border
+        assert_eq!(content.matches("rectstroke").count(), 3, "{content}");
+        assert!(content.contains("(-0.90) show"));
+        assert!(content.contains("(n/a) show"));
+        assert!(content.contains("(Correlations) show"));
+        assert!(content.contains("(price) show"));
+        // -1 is the palette's blue end, +1 its red end
+        assert_eq!(diverging_rgb(-1.0), (0.13, 0.40, 0.67));
+        assert_eq!(diverging_rgb(0.0), (1.0, 1.0, 1.0));
+    }
 }
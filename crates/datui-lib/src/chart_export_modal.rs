@@ -1,6 +1,8 @@
-//! Chart export modal: format (PNG/EPS), optional chart title, and file path. Used from Chart view only.
+//! Chart export modal: format (PNG/EPS), optional chart title, file path and whether to append a
+//! summary table (report card). Used from Chart view only.
 
 use crate::chart_export::ChartExportFormat;
+use crate::mouse::ButtonAreas;
 use crate::widgets::text_input::TextInput;
 use std::path::Path;
 
@@ -12,6 +14,7 @@ pub enum ChartExportFocus {
     TitleInput,
     WidthInput,
     HeightInput,
+    ReportCardToggle,
     ExportButton,
     CancelButton,
 }
@@ -19,11 +22,16 @@ pub enum ChartExportFocus {
 pub struct ChartExportModal {
     pub active: bool,
     pub focus: ChartExportFocus,
+    /// Where the buttons were drawn in the last frame, for mouse clicks.
+    pub buttons: ButtonAreas<ChartExportFocus>,
     pub selected_format: ChartExportFormat,
     pub title_input: TextInput,
     pub path_input: TextInput,
     pub width_input: TextInput,
     pub height_input: TextInput,
+    /// Append a table of the charted columns' summary statistics below the chart. Kept between
+    /// exports in a session.
+    pub report_card: bool,
 }
 
 impl ChartExportModal {
@@ -68,7 +76,8 @@ impl ChartExportModal {
             ChartExportFocus::PathInput => ChartExportFocus::TitleInput,
             ChartExportFocus::TitleInput => ChartExportFocus::WidthInput,
             ChartExportFocus::WidthInput => ChartExportFocus::HeightInput,
-            ChartExportFocus::HeightInput => ChartExportFocus::ExportButton,
+            ChartExportFocus::HeightInput => ChartExportFocus::ReportCardToggle,
+            ChartExportFocus::ReportCardToggle => ChartExportFocus::ExportButton,
             ChartExportFocus::ExportButton => ChartExportFocus::CancelButton,
             ChartExportFocus::CancelButton => ChartExportFocus::FormatSelector,
         };
@@ -81,7 +90,8 @@ impl ChartExportModal {
             ChartExportFocus::TitleInput => ChartExportFocus::PathInput,
             ChartExportFocus::WidthInput => ChartExportFocus::TitleInput,
             ChartExportFocus::HeightInput => ChartExportFocus::WidthInput,
-            ChartExportFocus::ExportButton => ChartExportFocus::HeightInput,
+            ChartExportFocus::ReportCardToggle => ChartExportFocus::HeightInput,
+            ChartExportFocus::ExportButton => ChartExportFocus::ReportCardToggle,
             ChartExportFocus::CancelButton => ChartExportFocus::ExportButton,
         };
     }
@@ -117,11 +127,13 @@ impl Default for ChartExportModal {
         Self {
             active: false,
             focus: ChartExportFocus::FormatSelector,
+            buttons: ButtonAreas::default(),
             selected_format: ChartExportFormat::Png,
             title_input: TextInput::new(),
             path_input: TextInput::new(),
             width_input: TextInput::new(),
             height_input: TextInput::new(),
+            report_card: false,
         }
     }
 }
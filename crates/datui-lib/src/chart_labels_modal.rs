@@ -0,0 +1,150 @@
+//! Chart labels modal: chart title, axis titles and tick formats. Opened with `t` from Chart
+//! view; Enter applies the values to the chart, Esc discards them.
+
+use crate::chart_data::TickFormat;
+use crate::chart_modal::ChartLabels;
+use crate::widgets::text_input::TextInput;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ChartLabelsFocus {
+    #[default]
+    TitleInput,
+    XTitleInput,
+    YTitleInput,
+    XTicks,
+    YTicks,
+}
+
+impl ChartLabelsFocus {
+    const ORDER: [Self; 5] = [
+        Self::TitleInput,
+        Self::XTitleInput,
+        Self::YTitleInput,
+        Self::XTicks,
+        Self::YTicks,
+    ];
+}
+
+#[derive(Default)]
+pub struct ChartLabelsModal {
+    pub active: bool,
+    pub focus: ChartLabelsFocus,
+    pub title_input: TextInput,
+    pub x_title_input: TextInput,
+    pub y_title_input: TextInput,
+    pub x_ticks: TickFormat,
+    pub y_ticks: TickFormat,
+}
+
+impl ChartLabelsModal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open with the chart's current labels.
+    pub fn open(&mut self, labels: &ChartLabels, theme: &crate::config::Theme) {
+        self.active = true;
+        self.focus = ChartLabelsFocus::TitleInput;
+        self.title_input = TextInput::new().with_theme(theme);
+        self.title_input.set_value(labels.title.clone());
+        self.x_title_input = TextInput::new().with_theme(theme);
+        self.x_title_input.set_value(labels.x_title.clone());
+        self.y_title_input = TextInput::new().with_theme(theme);
+        self.y_title_input.set_value(labels.y_title.clone());
+        self.x_ticks = labels.x_ticks;
+        self.y_ticks = labels.y_ticks;
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+        self.focus = ChartLabelsFocus::TitleInput;
+    }
+
+    /// The labels as edited.
+    pub fn labels(&self) -> ChartLabels {
+        ChartLabels {
+            title: self.title_input.value().trim().to_string(),
+            x_title: self.x_title_input.value().trim().to_string(),
+            y_title: self.y_title_input.value().trim().to_string(),
+            x_ticks: self.x_ticks,
+            y_ticks: self.y_ticks,
+        }
+    }
+
+    pub fn next_focus(&mut self) {
+        self.move_focus(1);
+    }
+
+    pub fn prev_focus(&mut self) {
+        self.move_focus(ChartLabelsFocus::ORDER.len() - 1);
+    }
+
+    fn move_focus(&mut self, step: usize) {
+        let order = ChartLabelsFocus::ORDER;
+        let pos = order.iter().position(|&f| f == self.focus).unwrap_or(0);
+        self.focus = order[(pos + step) % order.len()];
+    }
+
+    /// Step the focused tick format forwards (`delta` > 0) or backwards. No-op on text inputs.
+    pub fn cycle_tick_format(&mut self, delta: i32) {
+        let format = match self.focus {
+            ChartLabelsFocus::XTicks => &mut self.x_ticks,
+            ChartLabelsFocus::YTicks => &mut self.y_ticks,
+            _ => return,
+        };
+        let all = TickFormat::ALL;
+        let idx = all.iter().position(|f| f == format).unwrap_or(0);
+        let next = if delta < 0 {
+            (idx + all.len() - 1) % all.len()
+        } else {
+            (idx + 1) % all.len()
+        };
+        *format = all[next];
+    }
+
+    pub fn is_text_input_focused(&self) -> bool {
+        matches!(
+            self.focus,
+            ChartLabelsFocus::TitleInput
+                | ChartLabelsFocus::XTitleInput
+                | ChartLabelsFocus::YTitleInput
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_round_trip_and_tick_formats_cycle() {
+        let labels = ChartLabels {
+            title: "Sales".into(),
+            y_ticks: TickFormat::Percent,
+            ..Default::default()
+        };
+        let config = crate::config::AppConfig::default();
+        let theme = crate::config::Theme::from_config(&config.theme).unwrap();
+        let mut modal = ChartLabelsModal::new();
+        modal.open(&labels, &theme);
+        assert_eq!(modal.labels(), labels);
+
+        modal.x_title_input.set_value("  Day ".into());
+        modal.cycle_tick_format(1);
+        assert_eq!(modal.labels().x_ticks, TickFormat::Auto, "title focused");
+        modal.next_focus();
+        modal.next_focus();
+        modal.next_focus();
+        assert_eq!(modal.focus, ChartLabelsFocus::XTicks);
+        modal.cycle_tick_format(-1);
+        modal.prev_focus();
+        modal.prev_focus();
+        assert_eq!(modal.focus, ChartLabelsFocus::XTitleInput);
+
+        let edited = modal.labels();
+        assert_eq!(edited.x_title, "Day");
+        assert_eq!(edited.x_ticks, TickFormat::Time);
+        assert_eq!(edited.x_title("date"), "Day");
+        assert_eq!(edited.y_title("value"), "value");
+    }
+}
@@ -2,6 +2,7 @@
 
 use ratatui::widgets::ListState;
 
+use crate::chart_data::{format_tick, TickFormat, XAxisTemporalKind};
 use crate::widgets::text_input::TextInput;
 
 /// Chart kind: full chart category shown as tabs.
@@ -13,15 +14,19 @@ pub enum ChartKind {
     BoxPlot,
     Kde,
     Heatmap,
+    Category,
+    Scatter,
 }
 
 impl ChartKind {
-    pub const ALL: [Self; 5] = [
+    pub const ALL: [Self; 7] = [
         Self::XY,
         Self::Histogram,
         Self::BoxPlot,
         Self::Kde,
         Self::Heatmap,
+        Self::Category,
+        Self::Scatter,
     ];
 
     pub fn as_str(self) -> &'static str {
@@ -31,6 +36,8 @@ impl ChartKind {
             Self::BoxPlot => "Box Plot",
             Self::Kde => "KDE",
             Self::Heatmap => "Heatmap",
+            Self::Category => "Category",
+            Self::Scatter => "Scatter",
         }
     }
 }
@@ -56,6 +63,148 @@ impl ChartType {
     }
 }
 
+/// Uncertainty drawn around the first Y series of an XY chart.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BandMode {
+    #[default]
+    Off,
+    /// Lower and upper bound columns.
+    Bounds,
+    /// The series ± one standard-deviation column.
+    StdDev1,
+    /// The series ± twice a standard-deviation column (about 95% for normal errors).
+    StdDev2,
+}
+
+impl BandMode {
+    pub const ALL: [Self; 4] = [Self::Off, Self::Bounds, Self::StdDev1, Self::StdDev2];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Bounds => "Lower/upper",
+            Self::StdDev1 => "± 1 std dev",
+            Self::StdDev2 => "± 2 std dev",
+        }
+    }
+}
+
+/// What a category chart's bars show for the rows of each category.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CategoryAgg {
+    #[default]
+    Count,
+    Sum,
+    Mean,
+}
+
+impl CategoryAgg {
+    pub const ALL: [Self; 3] = [Self::Count, Self::Sum, Self::Mean];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Count => "Count",
+            Self::Sum => "Sum",
+            Self::Mean => "Mean",
+        }
+    }
+}
+
+/// How a band is drawn: a shaded region or an error bar at each point.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BandStyle {
+    #[default]
+    Shaded,
+    ErrorBars,
+}
+
+impl BandStyle {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Shaded => "Shaded",
+            Self::ErrorBars => "Error bars",
+        }
+    }
+}
+
+/// Columns that give a band's bounds, resolved from the chart options.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BandSource {
+    Bounds { lower: String, upper: String },
+    StdDev { column: String, multiplier: f64 },
+}
+
+/// Crosshair on the XY or Scatter chart: the data point it is on (as drawn, so log-scaled on a
+/// log-scale chart) and the index of that point's series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Crosshair {
+    pub series: usize,
+    pub point: (f64, f64),
+}
+
+impl Crosshair {
+    /// The point of `points` nearest `target`: the closest in x, then in y.
+    pub fn snap(points: &[(f64, f64)], target: (f64, f64)) -> Option<(f64, f64)> {
+        let distance = |p: &(f64, f64)| ((p.0 - target.0).abs(), (p.1 - target.1).abs());
+        points.iter().copied().min_by(|a, b| {
+            let (a, b) = (distance(a), distance(b));
+            a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1))
+        })
+    }
+}
+
+/// Order of points for stepping a crosshair: by x, then y.
+fn point_order(a: &(f64, f64), b: &(f64, f64)) -> std::cmp::Ordering {
+    a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1))
+}
+
+/// Smallest and largest x of all `series`.
+fn x_extent(series: &[&[(f64, f64)]]) -> Option<(f64, f64)> {
+    let mut points = series.iter().flat_map(|s| s.iter());
+    let first = points.next()?.0;
+    Some(points.fold((first, first), |(min, max), &(x, _)| {
+        (min.min(x), max.max(x))
+    }))
+}
+
+/// Chart title, axis titles and tick formats chosen in the Labels dialog. Empty titles use the
+/// defaults (column names, "Count", ...). Applies to every chart kind, on screen and in exports.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ChartLabels {
+    pub title: String,
+    pub x_title: String,
+    pub y_title: String,
+    pub x_ticks: TickFormat,
+    pub y_ticks: TickFormat,
+}
+
+impl ChartLabels {
+    pub fn title(&self) -> Option<&str> {
+        Some(self.title.trim()).filter(|t| !t.is_empty())
+    }
+
+    pub fn x_title<'a>(&'a self, default: &'a str) -> &'a str {
+        Some(self.x_title.trim())
+            .filter(|t| !t.is_empty())
+            .unwrap_or(default)
+    }
+
+    pub fn y_title<'a>(&'a self, default: &'a str) -> &'a str {
+        Some(self.y_title.trim())
+            .filter(|t| !t.is_empty())
+            .unwrap_or(default)
+    }
+
+    /// X tick label; `kind` is the x column's temporal kind (Numeric for value axes).
+    pub fn format_x(&self, v: f64, kind: XAxisTemporalKind) -> String {
+        format_tick(v, kind, self.x_ticks)
+    }
+
+    pub fn format_y(&self, v: f64) -> String {
+        format_tick(v, XAxisTemporalKind::Numeric, self.y_ticks)
+    }
+}
+
 /// Focus area in the chart sidebar.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum ChartFocus {
@@ -66,9 +215,16 @@ pub enum ChartFocus {
     XList,
     YInput,
     YList,
+    GroupBy,
+    RightAxis,
     YStartsAtZero,
     LogScale,
     ShowLegend,
+    BandMode,
+    /// Lower bound column (Lower/upper band) or standard-deviation column.
+    BandColumn,
+    BandUpper,
+    BandStyle,
     HistInput,
     HistList,
     HistBins,
@@ -82,8 +238,17 @@ pub enum ChartFocus {
     HeatmapYInput,
     HeatmapYList,
     HeatmapBins,
+    CategoryColumn,
+    CategoryAgg,
+    CategoryValue,
+    CategoryTop,
+    ScatterX,
+    ScatterY,
+    ScatterTrend,
     /// Limit Rows (shared across all chart types; at bottom of options).
     LimitRows,
+    /// The chart itself (XY and Scatter): zoom and pan the x axis.
+    Plot,
 }
 
 /// Maximum number of y-axis series that can be selected (remembered).
@@ -99,6 +264,16 @@ pub const HEATMAP_DEFAULT_BINS: usize = 20;
 pub const HEATMAP_MIN_BINS: usize = 5;
 pub const HEATMAP_MAX_BINS: usize = 60;
 
+/// Default number of bars in a category chart (the categories with the largest values).
+pub const CATEGORY_DEFAULT_TOP: usize = 10;
+pub const CATEGORY_MIN_TOP: usize = 1;
+pub const CATEGORY_MAX_TOP: usize = 50;
+
+/// Deepest x-axis zoom: the visible window is at least this fraction of the data's range.
+pub const ZOOM_MIN_WINDOW: f64 = 1.0 / 1024.0;
+/// How far ←/→ move a zoomed chart, in widths of the visible window.
+pub const PAN_STEP: f64 = 0.25;
+
 /// KDE bandwidth multiplier bounds and step.
 pub const KDE_BANDWIDTH_MIN: f64 = 0.2;
 pub const KDE_BANDWIDTH_MAX: f64 = 5.0;
@@ -188,6 +363,38 @@ pub struct ChartModal {
     pub heatmap_y_candidates: Vec<String>,
     /// Maximum rows for chart data. None = unlimited (display "Unlimited"); Some(n) = cap at n.
     pub row_limit: Option<usize>,
+    /// XY: uncertainty band around the first Y series.
+    pub band_mode: BandMode,
+    pub band_style: BandStyle,
+    /// Lower bound column (Lower/upper) or standard-deviation column (± std dev).
+    pub band_column: Option<String>,
+    pub band_upper: Option<String>,
+    /// XY: column whose values split the first Y column into one series each (up to
+    /// `Y_SERIES_MAX` values, the most frequent).
+    pub group_column: Option<String>,
+    /// Columns to group by: strings, categoricals and booleans.
+    pub group_candidates: Vec<String>,
+    /// XY: how many of the last Y columns are drawn against a right-hand axis with its own scale
+    /// (see `right_axis_count`).
+    pub right_axis_series: usize,
+    /// Category chart: column whose values are the bars (one of `group_candidates`).
+    pub category_column: Option<String>,
+    pub category_agg: CategoryAgg,
+    /// Numeric column summed or averaged (not used for Count).
+    pub category_value: Option<String>,
+    /// Number of bars: the categories with the largest values.
+    pub category_top: usize,
+    /// Scatter chart: x and y columns (numeric) and whether the least-squares trend line is drawn.
+    pub scatter_x_column: Option<String>,
+    pub scatter_y_column: Option<String>,
+    pub scatter_trend: bool,
+    /// Zoomed x window of the XY and Scatter charts, as fractions (0 to 1) of the data's x
+    /// range; None shows the whole range.
+    pub x_window: Option<(f64, f64)>,
+    /// Crosshair on the XY or Scatter chart (Enter on the chart); None when off.
+    pub crosshair: Option<Crosshair>,
+    /// Title, axis titles and tick formats. Kept when the chart view is closed and reopened.
+    pub labels: ChartLabels,
 }
 
 impl ChartModal {
@@ -243,6 +450,22 @@ impl ChartModal {
         self.heatmap_x_column = None;
         self.heatmap_y_column = None;
         self.heatmap_bins = HEATMAP_DEFAULT_BINS;
+        self.band_mode = BandMode::Off;
+        self.band_style = BandStyle::Shaded;
+        self.band_column = None;
+        self.band_upper = None;
+        self.group_column = None;
+        self.group_candidates.clear();
+        self.right_axis_series = 0;
+        self.category_column = None;
+        self.category_agg = CategoryAgg::Count;
+        self.category_value = None;
+        self.category_top = CATEGORY_DEFAULT_TOP;
+        self.scatter_x_column = None;
+        self.scatter_y_column = None;
+        self.scatter_trend = false;
+        self.x_window = None;
+        self.crosshair = None;
 
         self.x_input.set_value(String::new());
         self.y_input.set_value(String::new());
@@ -292,6 +515,48 @@ impl ChartModal {
             });
     }
 
+    /// Pre-populate the lists from columns selected in the main table (in selection order).
+    /// X is the first selected date/time column, or else the first selected numeric column when
+    /// more than one is selected; the remaining numeric columns become Y series. The first
+    /// numeric column is also remembered for the single-column charts (the first two for the
+    /// heatmap and scatter chart), and the first text column as the category chart's category. Unchartable columns are ignored. Call after
+    /// [`ChartModal::open`] and after setting `group_candidates`.
+    pub fn preselect(&mut self, selected: &[String]) {
+        let numeric: Vec<&String> = selected
+            .iter()
+            .filter(|c| self.y_candidates.contains(c))
+            .collect();
+        let temporal = selected
+            .iter()
+            .find(|c| self.x_candidates.contains(c) && !self.y_candidates.contains(c));
+        let x = match temporal {
+            Some(c) => Some(c),
+            None if numeric.len() > 1 => Some(numeric[0]),
+            None => None,
+        };
+        self.x_column = x.cloned();
+        self.y_columns = numeric
+            .iter()
+            .filter(|c| Some(**c) != x)
+            .take(Y_SERIES_MAX)
+            .map(|c| (*c).clone())
+            .collect();
+        if let Some(first) = numeric.first() {
+            self.hist_column = Some((*first).clone());
+            self.box_column = Some((*first).clone());
+            self.kde_column = Some((*first).clone());
+            self.heatmap_x_column = Some((*first).clone());
+            self.heatmap_y_column = numeric.get(1).map(|c| (*c).clone());
+            self.scatter_x_column = Some((*first).clone());
+            self.scatter_y_column = numeric.get(1).map(|c| (*c).clone());
+            self.category_value = Some((*first).clone());
+        }
+        self.category_column = selected
+            .iter()
+            .find(|c| self.group_candidates.contains(c))
+            .cloned();
+    }
+
     /// X-axis candidates filtered by current x search string (case-insensitive substring).
     pub fn x_filtered(&self) -> Vec<String> {
         let q = self.x_input.value().trim().to_lowercase();
@@ -629,6 +894,208 @@ impl ChartModal {
         self.show_legend = !self.show_legend;
     }
 
+    /// Step the band mode forwards (`delta` > 0) or backwards.
+    pub fn cycle_band_mode(&mut self, delta: i32) {
+        let idx = BandMode::ALL
+            .iter()
+            .position(|&m| m == self.band_mode)
+            .unwrap_or(0);
+        let len = BandMode::ALL.len();
+        let next = if delta < 0 {
+            (idx + len - 1) % len
+        } else {
+            (idx + 1) % len
+        };
+        self.band_mode = BandMode::ALL[next];
+    }
+
+    pub fn toggle_band_style(&mut self) {
+        self.band_style = match self.band_style {
+            BandStyle::Shaded => BandStyle::ErrorBars,
+            BandStyle::ErrorBars => BandStyle::Shaded,
+        };
+    }
+
+    /// Step a band column through the numeric columns; from unset, starts at the first (or last).
+    fn step_column(candidates: &[String], current: &Option<String>, delta: i32) -> Option<String> {
+        if candidates.is_empty() {
+            return None;
+        }
+        let len = candidates.len();
+        let pos = current
+            .as_ref()
+            .and_then(|c| candidates.iter().position(|x| x == c));
+        let next = match (pos, delta < 0) {
+            (None, false) => 0,
+            (None, true) => len - 1,
+            (Some(i), false) => (i + 1) % len,
+            (Some(i), true) => (i + len - 1) % len,
+        };
+        Some(candidates[next].clone())
+    }
+
+    pub fn cycle_band_column(&mut self, delta: i32) {
+        self.band_column = Self::step_column(&self.y_candidates, &self.band_column, delta);
+    }
+
+    /// Name of the y axis for `y_columns`: the columns, or with grouping the first one and the
+    /// group-by column.
+    pub fn y_axis_name(&self, y_columns: &[String]) -> String {
+        match (&self.group_column, y_columns.first()) {
+            (Some(group), Some(y)) => format!("{y} by {group}"),
+            _ => y_columns.join(", "),
+        }
+    }
+
+    /// Step the group-by column through the candidates and back to none.
+    pub fn cycle_group_column(&mut self, delta: i32) {
+        let last = self.group_candidates.last();
+        let first = self.group_candidates.first();
+        self.group_column = if delta < 0 && self.group_column.as_ref() == first
+            || delta >= 0 && self.group_column.as_ref() == last
+        {
+            None
+        } else {
+            Self::step_column(&self.group_candidates, &self.group_column, delta)
+        };
+    }
+
+    /// Step how many of the last Y columns use the right-hand axis: none, the last one, the last
+    /// two, and so on up to all but the first, then back to none.
+    pub fn cycle_right_axis(&mut self, delta: i32) {
+        let max = self.y_columns.len().saturating_sub(1);
+        let current = self.right_axis_series.min(max);
+        self.right_axis_series = match (delta < 0, current) {
+            (true, 0) => max,
+            (true, n) => n - 1,
+            (false, n) if n >= max => 0,
+            (false, n) => n + 1,
+        };
+    }
+
+    /// How many of `series` XY series (in Y column order) are drawn against the right-hand axis:
+    /// the last `right_axis_series`, keeping at least one on the left. None while grouping, since
+    /// the groups share one column's scale.
+    pub fn right_axis_count(&self, series: usize) -> usize {
+        if self.group_column.is_some() {
+            0
+        } else {
+            self.right_axis_series.min(series.saturating_sub(1))
+        }
+    }
+
+    /// Sidebar value of the Right axis row: the columns on the right-hand axis.
+    pub fn right_axis_display(&self) -> String {
+        let n = self.right_axis_count(self.y_columns.len());
+        if n == 0 {
+            "(none)".to_string()
+        } else {
+            self.y_columns[self.y_columns.len() - n..].join(", ")
+        }
+    }
+
+    pub fn cycle_category_column(&mut self, delta: i32) {
+        self.category_column =
+            Self::step_column(&self.group_candidates, &self.category_column, delta);
+    }
+
+    pub fn cycle_category_agg(&mut self, delta: i32) {
+        let idx = CategoryAgg::ALL
+            .iter()
+            .position(|&a| a == self.category_agg)
+            .unwrap_or(0);
+        let len = CategoryAgg::ALL.len();
+        let next = if delta < 0 {
+            (idx + len - 1) % len
+        } else {
+            (idx + 1) % len
+        };
+        self.category_agg = CategoryAgg::ALL[next];
+    }
+
+    /// Only meaningful for Sum and Mean.
+    pub fn cycle_category_value(&mut self, delta: i32) {
+        if self.category_agg != CategoryAgg::Count {
+            self.category_value =
+                Self::step_column(&self.y_candidates, &self.category_value, delta);
+        }
+    }
+
+    pub fn adjust_category_top(&mut self, delta: i32) {
+        let next = (self.category_top as i32 + delta)
+            .clamp(CATEGORY_MIN_TOP as i32, CATEGORY_MAX_TOP as i32);
+        self.category_top = next as usize;
+    }
+
+    /// The value column the category chart aggregates; None for Count, which needs none.
+    pub fn effective_category_value(&self) -> Option<&str> {
+        match self.category_agg {
+            CategoryAgg::Count => None,
+            CategoryAgg::Sum | CategoryAgg::Mean => self.category_value.as_deref(),
+        }
+    }
+
+    /// Whether the category chart has its columns: a category, and a value unless counting.
+    pub fn category_ready(&self) -> bool {
+        self.category_column.is_some()
+            && (self.category_agg == CategoryAgg::Count || self.category_value.is_some())
+    }
+
+    /// Y axis title of the category chart, e.g. "Count" or "Mean of price".
+    pub fn category_axis_name(&self) -> String {
+        match (self.category_agg, &self.category_value) {
+            (CategoryAgg::Sum | CategoryAgg::Mean, Some(value)) => {
+                format!("{} of {}", self.category_agg.as_str(), value)
+            }
+            _ => self.category_agg.as_str().to_string(),
+        }
+    }
+
+    pub fn cycle_scatter_x_column(&mut self, delta: i32) {
+        self.scatter_x_column =
+            Self::step_column(&self.y_candidates, &self.scatter_x_column, delta);
+    }
+
+    pub fn cycle_scatter_y_column(&mut self, delta: i32) {
+        self.scatter_y_column =
+            Self::step_column(&self.y_candidates, &self.scatter_y_column, delta);
+    }
+
+    pub fn toggle_scatter_trend(&mut self) {
+        self.scatter_trend = !self.scatter_trend;
+    }
+
+    /// Only meaningful for the Lower/upper band.
+    pub fn cycle_band_upper(&mut self, delta: i32) {
+        if self.band_mode == BandMode::Bounds {
+            self.band_upper = Self::step_column(&self.y_candidates, &self.band_upper, delta);
+        }
+    }
+
+    /// The band to draw, when one is switched on and its columns are chosen and the series
+    /// aren't grouped.
+    pub fn band_source(&self) -> Option<BandSource> {
+        if self.group_column.is_some() {
+            return None;
+        }
+        let column = self.band_column.clone()?;
+        match self.band_mode {
+            BandMode::Off => None,
+            BandMode::Bounds => Some(BandSource::Bounds {
+                lower: column,
+                upper: self.band_upper.clone()?,
+            }),
+            BandMode::StdDev1 => Some(BandSource::StdDev {
+                column,
+                multiplier: 1.0,
+            }),
+            BandMode::StdDev2 => Some(BandSource::StdDev {
+                column,
+                multiplier: 2.0,
+            }),
+        }
+    }
+
     /// Cycle chart type: Line -> Scatter -> Bar -> Line.
     pub fn next_chart_type(&mut self) {
         self.chart_type = match self.chart_type {
@@ -653,6 +1120,8 @@ impl ChartModal {
             .unwrap_or(0);
         self.chart_kind = ChartKind::ALL[(idx + 1) % ChartKind::ALL.len()];
         self.focus = ChartFocus::TabBar;
+        self.x_window = None;
+        self.crosshair = None;
     }
 
     pub fn prev_chart_kind(&mut self) {
@@ -667,6 +1136,129 @@ impl ChartModal {
         };
         self.chart_kind = ChartKind::ALL[prev];
         self.focus = ChartFocus::TabBar;
+        self.x_window = None;
+        self.crosshair = None;
+    }
+
+    /// Zoom the x axis in (`factor` > 1) or out around the middle of the visible window.
+    pub fn zoom_x(&mut self, factor: f64) {
+        let (lo, hi) = self.x_window.unwrap_or((0.0, 1.0));
+        let width = ((hi - lo) / factor).clamp(ZOOM_MIN_WINDOW, 1.0);
+        let mid = (lo + hi) / 2.0;
+        self.set_x_window(mid - width / 2.0, width);
+    }
+
+    /// Move the visible x window by `delta` window widths (negative = left), stopping at the
+    /// ends of the data.
+    pub fn pan_x(&mut self, delta: f64) {
+        if let Some((lo, hi)) = self.x_window {
+            let width = hi - lo;
+            self.set_x_window(lo + delta * width, width);
+        }
+    }
+
+    pub fn reset_zoom(&mut self) {
+        self.x_window = None;
+    }
+
+    fn set_x_window(&mut self, lo: f64, width: f64) {
+        let lo = lo.clamp(0.0, 1.0 - width);
+        self.x_window = (width < 1.0).then_some((lo, lo + width));
+    }
+
+    /// How far the x axis is zoomed in: 1 for the whole range.
+    pub fn zoom_factor(&self) -> f64 {
+        self.x_window.map_or(1.0, |(lo, hi)| 1.0 / (hi - lo))
+    }
+
+    /// The visible part of the x range `min`..`max`.
+    pub fn visible_x_range(&self, min: f64, max: f64) -> (f64, f64) {
+        match self.x_window {
+            Some((lo, hi)) => (min + lo * (max - min), min + hi * (max - min)),
+            None => (min, max),
+        }
+    }
+
+    /// Turn the crosshair off, or on at the point of the first series nearest the middle of the
+    /// visible x range. `series` are the chart's points as drawn.
+    pub fn toggle_crosshair(&mut self, series: &[&[(f64, f64)]]) {
+        if self.crosshair.take().is_some() {
+            return;
+        }
+        let Some((min, max)) = x_extent(series) else {
+            return;
+        };
+        let (lo, hi) = self.visible_x_range(min, max);
+        let mid = (lo + hi) / 2.0;
+        self.crosshair = series.iter().position(|s| !s.is_empty()).and_then(|i| {
+            let point = series[i]
+                .iter()
+                .copied()
+                .min_by(|a, b| (a.0 - mid).abs().total_cmp(&(b.0 - mid).abs()))?;
+            Some(Crosshair { series: i, point })
+        });
+    }
+
+    /// Move the crosshair to the next (`delta` > 0) or previous point of its series in x order,
+    /// panning a zoomed chart to keep it in view.
+    pub fn step_crosshair(&mut self, series: &[&[(f64, f64)]], delta: i32) {
+        let Some(current) = self.crosshair else {
+            return;
+        };
+        let Some(points) = series.get(current.series) else {
+            return;
+        };
+        let at = current.point;
+        let next = if delta > 0 {
+            points
+                .iter()
+                .filter(|p| point_order(p, &at).is_gt())
+                .min_by(|a, b| point_order(a, b))
+        } else {
+            points
+                .iter()
+                .filter(|p| point_order(p, &at).is_lt())
+                .max_by(|a, b| point_order(a, b))
+        };
+        if let Some(&point) = next {
+            self.crosshair = Some(Crosshair { point, ..current });
+            self.follow_crosshair(series);
+        }
+    }
+
+    /// Move the crosshair to the next (`delta` > 0) or previous series that has points, onto
+    /// its point nearest the current one.
+    pub fn cycle_crosshair_series(&mut self, series: &[&[(f64, f64)]], delta: i32) {
+        let Some(current) = self.crosshair else {
+            return;
+        };
+        let len = series.len();
+        for step in 1..len {
+            let i = if delta < 0 {
+                (current.series + len * step - step) % len
+            } else {
+                (current.series + step) % len
+            };
+            if let Some(point) = Crosshair::snap(series[i], current.point) {
+                self.crosshair = Some(Crosshair { series: i, point });
+                self.follow_crosshair(series);
+                return;
+            }
+        }
+    }
+
+    /// Pan a zoomed chart so the crosshair is in view.
+    fn follow_crosshair(&mut self, series: &[&[(f64, f64)]]) {
+        let (Some(crosshair), Some((lo, hi)), Some((min, max))) =
+            (self.crosshair, self.x_window, x_extent(series))
+        else {
+            return;
+        };
+        let at = (crosshair.point.0 - min) / (max - min);
+        if at < lo || at > hi {
+            let width = hi - lo;
+            self.set_x_window(at - width / 2.0, width);
+        }
     }
 
     /// Effective row limit to pass to prepare_* (unlimited = CHART_ROW_LIMIT_MAX).
@@ -1032,6 +1624,10 @@ impl ChartModal {
                 self.effective_heatmap_x_column().is_some()
                     && self.effective_heatmap_y_column().is_some()
             }
+            ChartKind::Category => self.category_ready(),
+            ChartKind::Scatter => {
+                self.scatter_x_column.is_some() && self.scatter_y_column.is_some()
+            }
         }
     }
 
@@ -1044,10 +1640,17 @@ impl ChartModal {
                 ChartFocus::XList,
                 ChartFocus::YInput,
                 ChartFocus::YList,
+                ChartFocus::GroupBy,
+                ChartFocus::RightAxis,
                 ChartFocus::YStartsAtZero,
                 ChartFocus::LogScale,
                 ChartFocus::ShowLegend,
+                ChartFocus::BandMode,
+                ChartFocus::BandColumn,
+                ChartFocus::BandUpper,
+                ChartFocus::BandStyle,
                 ChartFocus::LimitRows,
+                ChartFocus::Plot,
             ],
             ChartKind::Histogram => &[
                 ChartFocus::TabBar,
@@ -1078,13 +1681,32 @@ impl ChartModal {
                 ChartFocus::HeatmapBins,
                 ChartFocus::LimitRows,
             ],
+            ChartKind::Category => &[
+                ChartFocus::TabBar,
+                ChartFocus::CategoryColumn,
+                ChartFocus::CategoryAgg,
+                ChartFocus::CategoryValue,
+                ChartFocus::CategoryTop,
+                ChartFocus::LimitRows,
+            ],
+            ChartKind::Scatter => &[
+                ChartFocus::TabBar,
+                ChartFocus::ScatterX,
+                ChartFocus::ScatterY,
+                ChartFocus::ScatterTrend,
+                ChartFocus::LimitRows,
+                ChartFocus::Plot,
+            ],
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{ChartFocus, ChartKind, ChartModal, ChartType, Y_SERIES_MAX};
+    use super::{
+        BandMode, BandSource, CategoryAgg, ChartFocus, ChartKind, ChartModal, ChartType,
+        Y_SERIES_MAX,
+    };
 
     #[test]
     fn open_no_default_columns() {
@@ -1165,4 +1787,204 @@ mod tests {
         modal.y_list_toggle(); // should not add
         assert_eq!(modal.y_columns.len(), Y_SERIES_MAX);
     }
+
+    #[test]
+    fn band_source_needs_mode_and_columns() {
+        let mut modal = ChartModal::new();
+        modal.open(&["y".into(), "lo".into(), "hi".into()], &[], Some(10_000));
+        modal.cycle_band_column(1);
+        assert_eq!(modal.band_column.as_deref(), Some("y"));
+        assert_eq!(modal.band_source(), None, "band is off");
+
+        modal.cycle_band_mode(1);
+        assert_eq!(modal.band_mode, BandMode::Bounds);
+        modal.cycle_band_column(1);
+        assert_eq!(modal.band_source(), None, "no upper bound yet");
+        modal.cycle_band_upper(-1);
+        assert_eq!(
+            modal.band_source(),
+            Some(BandSource::Bounds {
+                lower: "lo".into(),
+                upper: "hi".into()
+            })
+        );
+
+        modal.cycle_band_mode(1);
+        assert_eq!(
+            modal.band_source(),
+            Some(BandSource::StdDev {
+                column: "lo".into(),
+                multiplier: 1.0
+            })
+        );
+        modal.cycle_band_mode(-1);
+        modal.cycle_band_mode(-1);
+        assert_eq!(modal.band_mode, BandMode::Off);
+    }
+
+    #[test]
+    fn right_axis_keeps_one_series_on_the_left() {
+        let mut modal = ChartModal::new();
+        modal.open(
+            &["price".into(), "volume".into(), "trades".into()],
+            &[],
+            None,
+        );
+        modal.y_columns = vec!["price".into(), "volume".into(), "trades".into()];
+        assert_eq!(modal.right_axis_display(), "(none)");
+        modal.cycle_right_axis(1);
+        assert_eq!(modal.right_axis_display(), "trades");
+        modal.cycle_right_axis(1);
+        assert_eq!(modal.right_axis_display(), "volume, trades");
+        modal.cycle_right_axis(1);
+        assert_eq!(modal.right_axis_count(3), 0);
+        modal.cycle_right_axis(-1);
+        assert_eq!(modal.right_axis_count(3), 2);
+
+        // Fewer series than the setting: the first still stays on the left
+        assert_eq!(modal.right_axis_count(2), 1);
+        assert_eq!(modal.right_axis_count(1), 0);
+        modal.group_column = Some("ticker".into());
+        assert_eq!(modal.right_axis_count(3), 0);
+    }
+
+    #[test]
+    fn group_column_cycles_through_none() {
+        let mut modal = ChartModal::new();
+        modal.open(&["y".into(), "sd".into()], &[], Some(10_000));
+        modal.group_candidates = vec!["ticker".into(), "venue".into()];
+        modal.cycle_group_column(1);
+        assert_eq!(modal.group_column.as_deref(), Some("ticker"));
+        modal.cycle_group_column(1);
+        assert_eq!(modal.group_column.as_deref(), Some("venue"));
+        modal.cycle_group_column(1);
+        assert_eq!(modal.group_column, None);
+        modal.cycle_group_column(-1);
+        assert_eq!(modal.group_column.as_deref(), Some("venue"));
+
+        // A band belongs to one series, so grouping turns it off
+        modal.cycle_band_mode(1);
+        modal.cycle_band_mode(1);
+        modal.cycle_band_column(1);
+        assert_eq!(modal.band_source(), None);
+        modal.group_column = None;
+        assert!(modal.band_source().is_some());
+    }
+
+    #[test]
+    fn category_chart_needs_a_value_unless_counting() {
+        let mut modal = ChartModal::new();
+        modal.open(&["price".into(), "qty".into()], &[], Some(10_000));
+        modal.group_candidates = vec!["region".into()];
+        modal.chart_kind = ChartKind::Category;
+        assert!(!modal.can_export());
+        modal.cycle_category_column(1);
+        assert!(modal.can_export());
+        assert_eq!(modal.category_axis_name(), "Count");
+
+        // Sum and Mean need a value column; Count ignores it
+        modal.cycle_category_agg(1);
+        assert_eq!(modal.category_agg, CategoryAgg::Sum);
+        assert!(!modal.category_ready());
+        modal.cycle_category_value(-1);
+        assert_eq!(modal.effective_category_value(), Some("qty"));
+        assert_eq!(modal.category_axis_name(), "Sum of qty");
+        modal.cycle_category_agg(-1);
+        assert_eq!(modal.effective_category_value(), None);
+        modal.cycle_category_value(1);
+        assert_eq!(modal.category_value.as_deref(), Some("qty"));
+
+        modal.adjust_category_top(100);
+        assert_eq!(modal.category_top, super::CATEGORY_MAX_TOP);
+    }
+
+    #[test]
+    fn scatter_chart_takes_the_first_two_marked_numeric_columns() {
+        let mut modal = ChartModal::new();
+        modal.open(&["a".into(), "b".into(), "c".into()], &[], Some(10_000));
+        modal.chart_kind = ChartKind::Scatter;
+        modal.preselect(&["c".into()]);
+        assert!(!modal.can_export());
+        modal.preselect(&["c".into(), "a".into()]);
+        assert_eq!(modal.scatter_x_column.as_deref(), Some("c"));
+        assert_eq!(modal.scatter_y_column.as_deref(), Some("a"));
+        assert!(modal.can_export());
+        modal.cycle_scatter_y_column(1);
+        assert_eq!(modal.scatter_y_column.as_deref(), Some("b"));
+        assert!(!modal.scatter_trend);
+    }
+
+    #[test]
+    fn zoom_and_pan_keep_the_x_window_inside_the_data() {
+        let mut modal = ChartModal::new();
+        modal.open(&["a".into()], &[], Some(10_000));
+        modal.pan_x(1.0);
+        assert_eq!(modal.x_window, None);
+
+        modal.zoom_x(4.0);
+        assert_eq!(modal.x_window, Some((0.375, 0.625)));
+        assert_eq!(modal.zoom_factor(), 4.0);
+        assert_eq!(modal.visible_x_range(100.0, 200.0), (137.5, 162.5));
+        modal.pan_x(-0.5);
+        assert_eq!(modal.x_window, Some((0.25, 0.5)));
+        modal.pan_x(-10.0);
+        assert_eq!(modal.x_window, Some((0.0, 0.25)));
+
+        // Zooming out keeps the window inside the data, and all the way out shows everything
+        modal.zoom_x(0.5);
+        assert_eq!(modal.x_window, Some((0.0, 0.5)));
+        modal.zoom_x(0.5);
+        assert_eq!(modal.x_window, None);
+
+        modal.zoom_x(1e9);
+        assert!((modal.zoom_factor() - 1.0 / super::ZOOM_MIN_WINDOW).abs() < 1e-6);
+        modal.next_chart_kind();
+        assert_eq!(modal.x_window, None);
+    }
+
+    #[test]
+    fn crosshair_steps_through_points_in_x_order() {
+        let mut modal = ChartModal::new();
+        modal.open(&["a".into()], &[], Some(10_000));
+        let first: &[(f64, f64)] = &[(3.0, 30.0), (0.0, 0.0), (2.0, 20.0), (1.0, 10.0)];
+        let second: &[(f64, f64)] = &[(0.5, 5.0), (2.5, 25.0)];
+        let series = [first, &[], second];
+
+        modal.toggle_crosshair(&series);
+        assert_eq!(modal.crosshair.map(|c| c.point), Some((2.0, 20.0)));
+        modal.step_crosshair(&series, 1);
+        assert_eq!(modal.crosshair.map(|c| c.point), Some((3.0, 30.0)));
+        // Stays on the last point
+        modal.step_crosshair(&series, 1);
+        assert_eq!(modal.crosshair.map(|c| c.point), Some((3.0, 30.0)));
+        modal.step_crosshair(&series, -1);
+        modal.step_crosshair(&series, -1);
+        assert_eq!(modal.crosshair.map(|c| c.point), Some((1.0, 10.0)));
+
+        // Empty series are skipped; the crosshair lands on the nearest point
+        modal.cycle_crosshair_series(&series, 1);
+        assert_eq!(
+            modal.crosshair,
+            Some(super::Crosshair {
+                series: 2,
+                point: (0.5, 5.0)
+            })
+        );
+        modal.cycle_crosshair_series(&series, 1);
+        assert_eq!(modal.crosshair.map(|c| c.series), Some(0));
+
+        // A zoomed chart pans to keep the crosshair in view
+        modal.crosshair = Some(super::Crosshair {
+            series: 0,
+            point: (1.0, 10.0),
+        });
+        modal.zoom_x(4.0);
+        modal.step_crosshair(&series, 1);
+        assert_eq!(modal.crosshair.map(|c| c.point), Some((2.0, 20.0)));
+        let (lo, hi) = modal.visible_x_range(0.0, 3.0);
+        assert!(lo <= 2.0 && 2.0 <= hi && lo > 1.0);
+
+        modal.toggle_crosshair(&series);
+        assert_eq!(modal.crosshair, None);
+    }
 }
@@ -1,3 +1,3 @@
 //! Re-export CLI definitions from the shared datui-cli crate.
 
-pub use datui_cli::{Args, CompressionFormat, FileFormat};
+pub use datui_cli::{Args, CompressionFormat, ConcatMode, FileFormat, QuoteChar};
@@ -0,0 +1,101 @@
+//! Copy text to the system clipboard.
+//!
+//! Local sessions write the clipboard directly (with the `clipboard` feature). Over SSH, or when
+//! no clipboard is reachable (e.g. no display server), the text goes through the terminal with
+//! OSC 52, which asks the terminal emulator to set the clipboard; this also works inside tmux
+//! (with `set-clipboard on`). Terminals that don't support it silently ignore the sequence.
+
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use std::io::{IsTerminal, Write};
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// The escape sequence that sets the clipboard to `text`.
+pub fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
+/// Put `text` on the clipboard: directly when the session is local, otherwise through the
+/// terminal. Fails when neither works (stdout is not a terminal).
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    #[cfg(feature = "clipboard")]
+    if !is_remote_session() && system::set_text(text) {
+        return Ok(());
+    }
+    copy_with_osc52(text)
+}
+
+/// Over SSH the system clipboard belongs to the remote host, not the user's machine.
+#[cfg(feature = "clipboard")]
+fn is_remote_session() -> bool {
+    std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+}
+
+fn copy_with_osc52(text: &str) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    if !stdout.is_terminal() {
+        return Err(eyre!("Clipboard copy needs a terminal"));
+    }
+    stdout.write_all(osc52_sequence(text).as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+#[cfg(feature = "clipboard")]
+mod system {
+    use std::sync::{Mutex, OnceLock};
+
+    /// Opened once and kept for the whole session: on X11 the copied text is served by this
+    /// process and is lost when the handle is dropped. None when no clipboard is available.
+    static CLIPBOARD: OnceLock<Option<Mutex<arboard::Clipboard>>> = OnceLock::new();
+
+    /// Set the system clipboard; false when it is unavailable or refused the text.
+    pub fn set_text(text: &str) -> bool {
+        let clipboard = CLIPBOARD.get_or_init(|| arboard::Clipboard::new().ok().map(Mutex::new));
+        clipboard
+            .as_ref()
+            .and_then(|c| c.lock().ok())
+            .is_some_and(|mut c| c.set_text(text).is_ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode_padding() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("a,b\n"), "\x1b]52;c;YSxiCg==\x07");
+    }
+}
@@ -0,0 +1,275 @@
+//! Column casts: a column of the view's source read as another type, e.g. text as integers,
+//! text as dates with a `strftime` format, or integers as categories. A value that can't be
+//! converted either stops the cast (strict) or becomes null, as chosen.
+//!
+//! Casts replace the column in the view's source, so queries, filters, sorts and analysis see
+//! the new type. They're made with the Cast modal (`~`) and saved in templates.
+
+use crate::schema_override;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Types a column can be cast to, in the order the Cast modal cycles through them.
+pub const CAST_TYPES: [&str; 10] = [
+    "str",
+    "i64",
+    "i32",
+    "f64",
+    "f32",
+    "bool",
+    "date",
+    "datetime",
+    "time",
+    "categorical",
+];
+
+/// What happens to a value that can't be converted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CastStrategy {
+    /// The cast fails and the column keeps its type.
+    #[default]
+    Strict,
+    /// The value becomes null.
+    NullOnError,
+}
+
+impl CastStrategy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Strict => "strict (fail on bad values)",
+            Self::NullOnError => "null on error",
+        }
+    }
+
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Strict => Self::NullOnError,
+            Self::NullOnError => Self::Strict,
+        }
+    }
+}
+
+/// The type named `name`: one of [`CAST_TYPES`] or a name `--dtypes` takes.
+pub fn cast_dtype(name: &str) -> Option<DataType> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "cat" | "categorical" => Some(DataType::from_categories(Categories::global())),
+        name => schema_override::parse_dtype(name),
+    }
+}
+
+/// Whether casting text to `dtype` parses dates or times, so a format applies.
+pub fn is_temporal(dtype: &DataType) -> bool {
+    matches!(
+        dtype,
+        DataType::Date | DataType::Datetime(_, _) | DataType::Time
+    )
+}
+
+/// A column read as another type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnCast {
+    pub column: String,
+    /// Target type, one of [`CAST_TYPES`].
+    pub to: String,
+    /// `strftime` format for text read as a date, datetime or time; inferred when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub strategy: CastStrategy,
+}
+
+impl ColumnCast {
+    pub fn target(&self) -> Result<DataType, String> {
+        cast_dtype(&self.to).ok_or_else(|| {
+            format!(
+                "Unknown type \"{}\" (use one of {})",
+                self.to,
+                CAST_TYPES.join(", ")
+            )
+        })
+    }
+
+    /// The column converted, keeping its name. `from` is its current type. With `strategy`
+    /// Strict, evaluating it fails on a value that can't be converted.
+    pub fn expr(&self, from: &DataType, strategy: CastStrategy) -> Result<Expr, String> {
+        let to = self.target()?;
+        let column = col(self.column.as_str());
+        let strict = strategy == CastStrategy::Strict;
+        let expr = if *from == DataType::String && is_temporal(&to) {
+            let options = StrptimeOptions {
+                format: self
+                    .format
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|f| !f.is_empty())
+                    .map(Into::into),
+                strict,
+                exact: true,
+                cache: true,
+            };
+            match to {
+                DataType::Date => column.str().to_date(options),
+                DataType::Time => column.str().to_time(options),
+                _ => column
+                    .str()
+                    .to_datetime(None, None, options, lit("earliest")),
+            }
+        } else {
+            // Categories are built from text, so numbers are written out first
+            let column = if matches!(to, DataType::Categorical(_, _)) && *from != DataType::String {
+                column.cast(DataType::String)
+            } else {
+                column
+            };
+            if strict {
+                column.strict_cast(to)
+            } else {
+                column.cast(to)
+            }
+        };
+        Ok(expr.alias(self.column.as_str()))
+    }
+
+    /// Whether the column of type `from` already has the target type.
+    pub fn is_noop(&self, from: &DataType) -> bool {
+        match (self.target(), from) {
+            (Ok(DataType::Datetime(_, _)), DataType::Datetime(_, _)) => true,
+            (Ok(DataType::Categorical(_, _)), DataType::Categorical(_, _)) => true,
+            (Ok(to), from) => to == *from,
+            (Err(_), _) => false,
+        }
+    }
+}
+
+/// Number of non-null values of `lf`'s column that `cast` can't convert (they'd become null).
+pub fn failed_values(lf: &LazyFrame, cast: &ColumnCast, from: &DataType) -> Result<usize, String> {
+    let converted = cast.expr(from, CastStrategy::NullOnError)?;
+    let df = lf
+        .clone()
+        .select([(col(cast.column.as_str())
+            .is_not_null()
+            .and(converted.is_null()))
+        .sum()
+        .cast(DataType::UInt64)
+        .alias("failed")])
+        .collect()
+        .map_err(|e| crate::error_display::user_message_from_polars(&e))?;
+    Ok(df
+        .column("failed")
+        .ok()
+        .and_then(|c| c.u64().ok()?.get(0))
+        .unwrap_or(0) as usize)
+}
+
+/// `lf` with `casts` applied in order, or a message when one is invalid or, when strict, meets a
+/// value it can't convert. A cast to the type the column already has changes nothing.
+pub fn apply_casts(lf: LazyFrame, casts: &[ColumnCast]) -> Result<LazyFrame, String> {
+    let mut lf = lf;
+    for cast in casts {
+        let schema = lf
+            .clone()
+            .collect_schema()
+            .map_err(|e| crate::error_display::user_message_from_polars(&e))?;
+        let from = schema
+            .get(cast.column.as_str())
+            .cloned()
+            .ok_or_else(|| format!("Column '{}' not found", cast.column))?;
+        if cast.is_noop(&from) {
+            continue;
+        }
+        if cast.strategy == CastStrategy::Strict {
+            let failed = failed_values(&lf, cast, &from)?;
+            if failed > 0 {
+                return Err(format!(
+                    "{} value{} of '{}' can't be read as {}: fix the format or cast with null on error",
+                    failed,
+                    if failed == 1 { "" } else { "s" },
+                    cast.column,
+                    cast.to
+                ));
+            }
+        }
+        lf = lf.with_column(cast.expr(&from, cast.strategy)?);
+    }
+    lf.clone()
+        .collect_schema()
+        .map_err(|e| crate::error_display::user_message_from_polars(&e))?;
+    Ok(lf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cast(column: &str, to: &str, format: Option<&str>, strategy: CastStrategy) -> ColumnCast {
+        ColumnCast {
+            column: column.into(),
+            to: to.into(),
+            format: format.map(Into::into),
+            strategy,
+        }
+    }
+
+    #[test]
+    fn casts_text_to_numbers_and_dates() {
+        let lf = df!(
+            "id" => ["007", "42", "x"],
+            "day" => ["01/02/2024", "15/03/2024", "31/12/2023"],
+            "code" => [1i64, 2, 1]
+        )
+        .unwrap()
+        .lazy();
+
+        let err = apply_casts(lf.clone(), &[cast("id", "i64", None, CastStrategy::Strict)])
+            .err()
+            .unwrap();
+        assert!(
+            err.contains("1 value of 'id' can't be read as i64"),
+            "{err}"
+        );
+
+        let casts = [
+            cast("id", "i64", None, CastStrategy::NullOnError),
+            cast("day", "date", Some("%d/%m/%Y"), CastStrategy::Strict),
+            cast("code", "categorical", None, CastStrategy::Strict),
+        ];
+        let df = apply_casts(lf.clone(), &casts).unwrap().collect().unwrap();
+        let ids: Vec<Option<i64>> = df
+            .column("id")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(ids, [Some(7), Some(42), None]);
+        assert_eq!(df.column("day").unwrap().dtype(), &DataType::Date);
+        assert!(matches!(
+            df.column("code").unwrap().dtype(),
+            DataType::Categorical(_, _)
+        ));
+
+        // A wrong format fails strictly; the same cast again changes nothing
+        let wrong = [cast("day", "date", Some("%Y-%m-%d"), CastStrategy::Strict)];
+        assert!(apply_casts(lf.clone(), &wrong).is_err());
+        let cast_lf = apply_casts(lf, &casts).unwrap();
+        assert!(apply_casts(cast_lf, &casts).is_ok());
+    }
+
+    #[test]
+    fn casts_round_trip_through_json() {
+        let original = cast(
+            "day",
+            "datetime",
+            Some("%Y%m%d %H%M"),
+            CastStrategy::NullOnError,
+        );
+        let json = serde_json::to_string(&original).unwrap();
+        assert!(json.contains("\"strategy\":\"null_on_error\""), "{json}");
+        assert_eq!(serde_json::from_str::<ColumnCast>(&json).unwrap(), original);
+        let minimal: ColumnCast = serde_json::from_str(r#"{"column":"a","to":"f64"}"#).unwrap();
+        assert_eq!(minimal.strategy, CastStrategy::Strict);
+        assert_eq!(minimal.format, None);
+    }
+}
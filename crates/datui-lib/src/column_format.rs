@@ -0,0 +1,312 @@
+//! Per-column display formats: decimal places, thousands separators, percent and currency
+//! rendering for numbers, and strftime patterns for dates and times.
+//!
+//! Like the display locale, formats only change the rendered text. Queries, filters and exports
+//! keep working on the underlying values. Formats are set with the Format modal (`f`), saved in
+//! templates, and can be given per column name in `[display.column_formats]`.
+
+use crate::locale::DisplayLocale;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, NaiveDate, NaiveTime};
+use polars::prelude::{AnyValue, DataType, TimeUnit};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How a numeric value is written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NumberStyle {
+    #[default]
+    Number,
+    /// Value × 100 followed by `%` (0.25 → 25%).
+    Percent,
+    /// Value preceded by the currency symbol.
+    Currency,
+}
+
+impl NumberStyle {
+    pub const ALL: [NumberStyle; 3] = [Self::Number, Self::Percent, Self::Currency];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Number => "Number",
+            Self::Percent => "Percent",
+            Self::Currency => "Currency",
+        }
+    }
+}
+
+/// Symbol used for the currency style when none is set.
+pub const DEFAULT_CURRENCY_SYMBOL: &str = "$";
+
+/// Display format of one column. The default leaves values as they are.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnFormat {
+    #[serde(skip_serializing_if = "is_default_style")]
+    pub style: NumberStyle,
+    /// Digits after the decimal separator; None keeps the value's own digits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<usize>,
+    /// Group digits of the integer part (with the locale's separator, "," when it has none).
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub thousands: bool,
+    /// Symbol for the currency style; None uses [`DEFAULT_CURRENCY_SYMBOL`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency_symbol: Option<String>,
+    /// strftime pattern for date, datetime and time columns (e.g. "%d %b %Y").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+}
+
+fn is_default_style(style: &NumberStyle) -> bool {
+    *style == NumberStyle::Number
+}
+
+/// Formats by column name.
+pub type ColumnFormats = HashMap<String, ColumnFormat>;
+
+/// Whether the Format modal offers number settings (true) or a date pattern (false) for `dtype`.
+/// Other types can't be formatted.
+pub fn formattable_kind(dtype: &DataType) -> Option<bool> {
+    if dtype.is_primitive_numeric() {
+        Some(true)
+    } else if matches!(
+        dtype,
+        DataType::Date | DataType::Datetime(_, _) | DataType::Time
+    ) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Check a strftime pattern, returning a message for patterns chrono can't render.
+pub fn validate_date_format(pattern: &str) -> Result<(), String> {
+    if StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error)) {
+        Err(format!("Invalid date format: {}", pattern))
+    } else {
+        Ok(())
+    }
+}
+
+impl ColumnFormat {
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check the settings, returning a message for the first invalid one.
+    pub fn validate(&self) -> Result<(), String> {
+        match self.decimals {
+            Some(d) if d > 20 => return Err("Decimals must be between 0 and 20".to_string()),
+            _ => {}
+        }
+        match &self.date_format {
+            Some(pattern) => validate_date_format(pattern),
+            None => Ok(()),
+        }
+    }
+
+    /// Cell text for `value`, or None when this format doesn't apply to it (the caller then uses
+    /// the locale's text).
+    pub fn format_value(&self, value: &AnyValue, locale: &DisplayLocale) -> Option<String> {
+        match value {
+            AnyValue::Date(_)
+            | AnyValue::Datetime(..)
+            | AnyValue::DatetimeOwned(..)
+            | AnyValue::Time(_) => self.format_temporal(value),
+            v if v.dtype().is_primitive_numeric() => self.format_number(v, locale),
+            _ => None,
+        }
+    }
+
+    fn format_number(&self, value: &AnyValue, locale: &DisplayLocale) -> Option<String> {
+        if self.style == NumberStyle::Number && self.decimals.is_none() && !self.thousands {
+            return None;
+        }
+        let x: f64 = value.extract()?;
+        if !x.is_finite() {
+            return None;
+        }
+        let scaled = if self.style == NumberStyle::Percent {
+            x * 100.0
+        } else {
+            x
+        };
+        let text = match self.decimals {
+            Some(d) => format!("{:.*}", d, scaled),
+            None if value.dtype().is_integer() && self.style != NumberStyle::Percent => {
+                value.str_value().into_owned()
+            }
+            None => format!("{}", scaled),
+        };
+        let separators = DisplayLocale {
+            group_separator: if self.thousands {
+                Some(
+                    locale
+                        .group_separator
+                        .unwrap_or(if locale.decimal_separator == ',' {
+                            '.'
+                        } else {
+                            ','
+                        }),
+                )
+            } else {
+                None
+            },
+            ..*locale
+        };
+        let number = separators.format_number(&text)?;
+        Some(match self.style {
+            NumberStyle::Number => number,
+            NumberStyle::Percent => format!("{}%", number),
+            NumberStyle::Currency => {
+                let symbol = self
+                    .currency_symbol
+                    .as_deref()
+                    .unwrap_or(DEFAULT_CURRENCY_SYMBOL);
+                match number.strip_prefix('-') {
+                    Some(unsigned) => format!("-{}{}", symbol, unsigned),
+                    None => format!("{}{}", symbol, number),
+                }
+            }
+        })
+    }
+
+    /// Apply the date pattern. Time-zone-aware datetimes are left as-is, since the pattern
+    /// would otherwise show UTC wall-clock time.
+    fn format_temporal(&self, value: &AnyValue) -> Option<String> {
+        let pattern = self.date_format.as_deref().filter(|p| !p.is_empty())?;
+        validate_date_format(pattern).ok()?;
+        let text = match value {
+            AnyValue::Date(days) => {
+                NaiveDate::from_num_days_from_ce_opt(days + EPOCH_DAYS_FROM_CE)?
+                    .format(pattern)
+                    .to_string()
+            }
+            AnyValue::Datetime(v, unit, None) | AnyValue::DatetimeOwned(v, unit, None) => {
+                let dt = match unit {
+                    TimeUnit::Nanoseconds => DateTime::from_timestamp_nanos(*v),
+                    TimeUnit::Microseconds => DateTime::from_timestamp_micros(*v)?,
+                    TimeUnit::Milliseconds => DateTime::from_timestamp_millis(*v)?,
+                };
+                dt.naive_utc().format(pattern).to_string()
+            }
+            AnyValue::Time(ns) => {
+                let secs = (ns / 1_000_000_000) as u32;
+                let nanos = (ns % 1_000_000_000) as u32;
+                NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos)?
+                    .format(pattern)
+                    .to_string()
+            }
+            _ => return None,
+        };
+        Some(text)
+    }
+}
+
+/// Days from 0001-01-01 (chrono's day 1) to 1970-01-01 (Polars' day 0), plus one.
+const EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_styles_use_locale_separators() {
+        let iso = DisplayLocale::ISO;
+        let de = DisplayLocale::from_name("de-DE").unwrap();
+        let money = ColumnFormat {
+            style: NumberStyle::Currency,
+            decimals: Some(2),
+            thousands: true,
+            currency_symbol: Some("€".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            money
+                .format_value(&AnyValue::Float64(-1234567.891), &iso)
+                .as_deref(),
+            Some("-€1,234,567.89")
+        );
+        assert_eq!(
+            money.format_value(&AnyValue::Int64(1234), &de).as_deref(),
+            Some("€1.234,00")
+        );
+
+        let percent = ColumnFormat {
+            style: NumberStyle::Percent,
+            decimals: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            percent
+                .format_value(&AnyValue::Float64(0.256), &iso)
+                .as_deref(),
+            Some("25.6%")
+        );
+
+        let grouped = ColumnFormat {
+            thousands: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            grouped
+                .format_value(&AnyValue::Int32(-1000000), &iso)
+                .as_deref(),
+            Some("-1,000,000")
+        );
+        assert_eq!(
+            grouped.format_value(&AnyValue::Float64(f64::NAN), &iso),
+            None
+        );
+        assert_eq!(
+            ColumnFormat::default().format_value(&AnyValue::Int64(5), &iso),
+            None
+        );
+    }
+
+    #[test]
+    fn date_patterns_apply_to_temporal_values() {
+        let format = ColumnFormat {
+            date_format: Some("%d %b %Y".into()),
+            ..Default::default()
+        };
+        // 2024-01-31 is day 19753 since the epoch
+        assert_eq!(
+            format
+                .format_value(&AnyValue::Date(19753), &DisplayLocale::ISO)
+                .as_deref(),
+            Some("31 Jan 2024")
+        );
+        let ms = 19753_i64 * 86_400_000 + 8 * 3_600_000;
+        let hours = ColumnFormat {
+            date_format: Some("%H:%M".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            hours
+                .format_value(
+                    &AnyValue::Datetime(ms, TimeUnit::Milliseconds, None),
+                    &DisplayLocale::ISO
+                )
+                .as_deref(),
+            Some("08:00")
+        );
+        assert_eq!(
+            format.format_value(&AnyValue::Int64(3), &DisplayLocale::ISO),
+            None
+        );
+        assert!(validate_date_format("%Y-%m-%d").is_ok());
+        assert!(validate_date_format("%Q").is_err());
+    }
+
+    #[test]
+    fn plain_format_serializes_empty() {
+        let json = serde_json::to_string(&ColumnFormat::default()).unwrap();
+        assert_eq!(json, "{}");
+        let parsed: ColumnFormat = toml::from_str("style = \"percent\"\ndecimals = 1").unwrap();
+        assert_eq!(parsed.style, NumberStyle::Percent);
+        assert_eq!(parsed.decimals, Some(1));
+    }
+}
@@ -0,0 +1,144 @@
+//! Column ruler and go-to-column: an optional row above the headers numbering the columns
+//! (`1`, `2`, ...) or lettering them like a spreadsheet (`A` ... `Z`, `AA`, ...), and the lookup
+//! behind `|`, which finds a column by name, number, letters or a fuzzy match on its name.
+
+use serde::{Deserialize, Serialize};
+
+/// What the ruler above the column headers shows (`display.column_ruler`, cycled with `zr`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnRuler {
+    /// No ruler.
+    #[default]
+    Off,
+    /// Column numbers, from 1.
+    Index,
+    /// Spreadsheet-style letters: A ... Z, AA, AB, ...
+    Letters,
+}
+
+impl ColumnRuler {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Index,
+            Self::Index => Self::Letters,
+            Self::Letters => Self::Off,
+        }
+    }
+
+    /// Label of the column at `index` (0-based, in display order); empty when off.
+    pub fn label(self, index: usize) -> String {
+        match self {
+            Self::Off => String::new(),
+            Self::Index => (index + 1).to_string(),
+            Self::Letters => column_letters(index),
+        }
+    }
+}
+
+/// Spreadsheet letters of the column at `index` (0-based): 0 is `A`, 25 `Z`, 26 `AA`.
+pub fn column_letters(index: usize) -> String {
+    let mut letters = Vec::new();
+    let mut n = index + 1;
+    while n > 0 {
+        n -= 1;
+        letters.push(b'A' + (n % 26) as u8);
+        n /= 26;
+    }
+    letters.iter().rev().map(|&b| b as char).collect()
+}
+
+/// Index (0-based) of the column spreadsheet letters name: upper case, at most three letters.
+pub fn parse_column_letters(text: &str) -> Option<usize> {
+    if text.is_empty() || text.len() > 3 || !text.bytes().all(|b| b.is_ascii_uppercase()) {
+        return None;
+    }
+    let n = text
+        .bytes()
+        .fold(0usize, |n, b| n * 26 + (b - b'A') as usize + 1);
+    Some(n - 1)
+}
+
+/// How well `name` matches `query`, ignoring case: a prefix beats a substring, which beats the
+/// query's characters appearing in order (lower is better). None when they don't appear.
+pub fn fuzzy_score(name: &str, query: &str) -> Option<u8> {
+    let name = name.to_lowercase();
+    let query = query.to_lowercase();
+    if name.starts_with(&query) {
+        return Some(0);
+    }
+    if name.contains(&query) {
+        return Some(1);
+    }
+    let mut chars = name.chars();
+    query
+        .chars()
+        .all(|q| chars.by_ref().any(|c| c == q))
+        .then_some(2)
+}
+
+/// Indexes of `columns` matching `query`, best first; ties go to the shorter name, then to the
+/// column further left.
+pub fn fuzzy_matches(columns: &[String], query: &str) -> Vec<usize> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let mut matches: Vec<(u8, usize, usize)> = columns
+        .iter()
+        .enumerate()
+        .filter_map(|(i, name)| Some((fuzzy_score(name, query)?, name.chars().count(), i)))
+        .collect();
+    matches.sort_unstable();
+    matches.into_iter().map(|(_, _, i)| i).collect()
+}
+
+/// Index of the column `query` names: a column of that name (ignoring case), a column number
+/// from 1, upper-case spreadsheet letters, or else the best fuzzy match.
+pub fn find_column(columns: &[String], query: &str) -> Option<usize> {
+    let query = query.trim();
+    if query.is_empty() {
+        return None;
+    }
+    if let Some(i) = columns.iter().position(|c| c.eq_ignore_ascii_case(query)) {
+        return Some(i);
+    }
+    if let Ok(number) = query.parse::<usize>() {
+        return (1..=columns.len()).contains(&number).then(|| number - 1);
+    }
+    if let Some(i) = parse_column_letters(query).filter(|&i| i < columns.len()) {
+        return Some(i);
+    }
+    fuzzy_matches(columns, query).first().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_round_trip() {
+        for (index, letters) in [(0, "A"), (25, "Z"), (26, "AA"), (51, "AZ"), (702, "AAA")] {
+            assert_eq!(column_letters(index), letters);
+            assert_eq!(parse_column_letters(letters), Some(index));
+        }
+        assert_eq!(parse_column_letters("ab"), None);
+        assert_eq!(ColumnRuler::Index.label(4), "5");
+    }
+
+    #[test]
+    fn finds_columns_by_name_number_letters_and_fuzzy_match() {
+        let columns: Vec<String> = ["id", "unit_price", "price", "quantity", "AB"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(find_column(&columns, "PRICE"), Some(2));
+        assert_eq!(find_column(&columns, "4"), Some(3));
+        assert_eq!(find_column(&columns, "9"), None);
+        assert_eq!(find_column(&columns, "B"), Some(1));
+        assert_eq!(find_column(&columns, "AB"), Some(4), "a name beats letters");
+        assert_eq!(find_column(&columns, "qty"), Some(3));
+        assert_eq!(fuzzy_matches(&columns, "pri"), [2, 1]);
+        assert_eq!(find_column(&columns, "zzz"), None);
+    }
+}
@@ -0,0 +1,263 @@
+//! Column statistics sidebar (`I`): min, max, mean, nulls and an estimate of distinct values for
+//! the column under the horizontal cursor.
+//!
+//! Statistics are computed in the background one column at a time and cached per column for the
+//! current view (identified by its `len_generation`), so moving back and forth across columns
+//! only computes each column once. Views with at least `sampling_threshold` rows are sampled.
+
+use crate::aggregate_peek::{first_value, has_min_max, has_sum};
+use crate::cardinality::is_countable;
+use crate::locale::DisplayLocale;
+use crate::sampling::{sample_lazy, SamplingStrategy};
+use crate::statistics::collect_lazy;
+use color_eyre::Result;
+use polars::prelude::*;
+use std::collections::HashMap;
+
+/// Statistics of one column. Values that don't apply to the column's type are None.
+#[derive(Debug, Clone)]
+pub struct ColumnStats {
+    pub column: String,
+    pub dtype: DataType,
+    /// Rows the statistics cover (the sample size when sampled).
+    pub rows: usize,
+    pub null_count: usize,
+    pub min: Option<AnyValue<'static>>,
+    pub max: Option<AnyValue<'static>>,
+    pub mean: Option<f64>,
+    /// Estimated distinct values (HyperLogLog), nulls counted as one.
+    pub distinct: Option<usize>,
+    /// Rows in the view, when the statistics were computed on a sample.
+    pub sampled_from: Option<usize>,
+}
+
+impl ColumnStats {
+    /// Statistics of `column` in `lf`. `total_rows` is the view's row count when known; views
+    /// with at least `sampling_threshold` rows are sampled.
+    pub fn compute(
+        lf: &LazyFrame,
+        column: &str,
+        total_rows: Option<usize>,
+        sampling_threshold: Option<usize>,
+        sampling: &SamplingStrategy,
+        polars_streaming: bool,
+    ) -> Result<Self> {
+        let selected = lf.clone().select([col(column)]);
+        let dtype = selected
+            .clone()
+            .collect_schema()?
+            .get(column)
+            .cloned()
+            .unwrap_or(DataType::Null);
+
+        let (source, sampled_from) = match (sampling_threshold, total_rows) {
+            (Some(threshold), Some(total)) if total >= threshold => {
+                let sample = sample_lazy(lf, total, threshold, sampling, polars_streaming)?;
+                (sample.select([col(column)]), Some(total))
+            }
+            _ => (selected, None),
+        };
+
+        let mut exprs = vec![
+            len().alias("rows"),
+            col(column).null_count().alias("null_count"),
+        ];
+        if has_sum(&dtype) {
+            exprs.push(col(column).cast(DataType::Float64).mean().alias("mean"));
+        }
+        if has_min_max(&dtype) {
+            exprs.push(col(column).min().alias("min"));
+            exprs.push(col(column).max().alias("max"));
+        }
+        if is_countable(&dtype) {
+            exprs.push(col(column).approx_n_unique().alias("distinct"));
+        }
+        let df = collect_lazy(source.select(exprs), polars_streaming)?;
+
+        let count_of = |name: &str| first_value(&df, name).and_then(|v| v.extract::<usize>());
+        Ok(Self {
+            column: column.to_string(),
+            rows: count_of("rows").unwrap_or(0),
+            null_count: count_of("null_count").unwrap_or(0),
+            min: first_value(&df, "min"),
+            max: first_value(&df, "max"),
+            mean: first_value(&df, "mean").and_then(|v| v.extract::<f64>()),
+            distinct: count_of("distinct"),
+            sampled_from,
+            dtype,
+        })
+    }
+
+    /// (label, value) lines for the sidebar, formatted with `locale`. "—" marks values that
+    /// don't apply to the column's type or are all null.
+    pub fn lines(&self, locale: &DisplayLocale) -> Vec<(&'static str, String)> {
+        let value = |v: &Option<AnyValue<'static>>| match v {
+            Some(v) => locale.format_value(v).into_owned(),
+            None => "—".to_string(),
+        };
+        let integer = |n: usize| {
+            locale
+                .format_number(&n.to_string())
+                .unwrap_or_else(|| n.to_string())
+        };
+        let mean = match self.mean {
+            Some(m) if m.is_finite() => {
+                let text = format!("{m:.4}");
+                locale.format_number(&text).unwrap_or(text)
+            }
+            Some(m) => m.to_string(),
+            None => "—".to_string(),
+        };
+        let nulls = if self.rows > 0 {
+            format!(
+                "{} ({:.1}%)",
+                integer(self.null_count),
+                self.null_count as f64 * 100.0 / self.rows as f64
+            )
+        } else {
+            integer(self.null_count)
+        };
+        vec![
+            ("Type", self.dtype.to_string()),
+            ("Rows", integer(self.rows)),
+            ("Nulls", nulls),
+            (
+                "Distinct",
+                self.distinct
+                    .map(|n| format!("~{}", integer(n)))
+                    .unwrap_or_else(|| "—".to_string()),
+            ),
+            ("Min", value(&self.min)),
+            ("Max", value(&self.max)),
+            ("Mean", mean),
+        ]
+    }
+}
+
+/// The sidebar: whether it is shown, and statistics cached for the current view.
+#[derive(Debug, Default)]
+pub struct ColumnStatsSidebar {
+    pub active: bool,
+    /// `len_generation` of the view the cached statistics belong to.
+    generation: u64,
+    cache: HashMap<String, std::result::Result<ColumnStats, String>>,
+    /// (generation, column) being computed.
+    inflight: Option<(u64, String)>,
+}
+
+impl ColumnStatsSidebar {
+    /// Cached statistics (or why they failed) of `column` in the view with `generation`.
+    pub fn get(
+        &self,
+        generation: u64,
+        column: &str,
+    ) -> Option<&std::result::Result<ColumnStats, String>> {
+        if generation != self.generation {
+            return None;
+        }
+        self.cache.get(column)
+    }
+
+    /// Whether `column` of the view with `generation` should be computed now: the sidebar is
+    /// shown, nothing is cached for it and no column is being computed.
+    pub fn should_compute(&self, generation: u64, column: &str) -> bool {
+        self.active && self.inflight.is_none() && self.get(generation, column).is_none()
+    }
+
+    /// Note that `column` of the view with `generation` is being computed. A new view drops the
+    /// statistics cached for the old one.
+    pub fn start(&mut self, generation: u64, column: &str) {
+        if generation != self.generation {
+            self.generation = generation;
+            self.cache.clear();
+        }
+        self.inflight = Some((generation, column.to_string()));
+    }
+
+    /// Store the statistics computed for `column` of the view with `generation`.
+    pub fn finish(
+        &mut self,
+        generation: u64,
+        column: &str,
+        stats: std::result::Result<ColumnStats, String>,
+    ) {
+        if self
+            .inflight
+            .as_ref()
+            .is_some_and(|(g, c)| *g == generation && c == column)
+        {
+            self.inflight = None;
+        }
+        if generation == self.generation {
+            self.cache.insert(column.to_string(), stats);
+        }
+    }
+
+    pub fn is_computing(&self, generation: u64, column: &str) -> bool {
+        self.inflight
+            .as_ref()
+            .is_some_and(|(g, c)| *g == generation && c == column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_stats_numeric_and_string() {
+        let lf = df!(
+            "n" => [Some(1i64), Some(4), None, Some(4)],
+            "s" => [Some("b"), Some("a"), Some("b"), None],
+        )
+        .unwrap()
+        .lazy();
+        let sampling = SamplingStrategy::default();
+        let n = ColumnStats::compute(&lf, "n", Some(4), None, &sampling, false).unwrap();
+        assert_eq!(n.rows, 4);
+        assert_eq!(n.null_count, 1);
+        assert_eq!(n.min, Some(AnyValue::Int64(1)));
+        assert_eq!(n.max, Some(AnyValue::Int64(4)));
+        assert_eq!(n.mean, Some(3.0));
+        assert_eq!(n.distinct, Some(3), "1, 4 and null");
+        assert_eq!(n.sampled_from, None);
+
+        let s = ColumnStats::compute(&lf, "s", None, Some(2), &sampling, false).unwrap();
+        assert_eq!(s.mean, None);
+        assert_eq!(s.min.map(|v| v.to_string()), Some("\"a\"".to_string()));
+        assert_eq!(s.sampled_from, None, "no sampling without a row count");
+
+        let sampled = ColumnStats::compute(&lf, "n", Some(4), Some(2), &sampling, false).unwrap();
+        assert_eq!(sampled.sampled_from, Some(4));
+        assert_eq!(sampled.rows, 2);
+    }
+
+    #[test]
+    fn test_sidebar_caches_per_view() {
+        let lf = df!("n" => [1i64, 2]).unwrap().lazy();
+        let stats = ColumnStats::compute(&lf, "n", None, None, &SamplingStrategy::default(), false)
+            .unwrap();
+        let mut sidebar = ColumnStatsSidebar::default();
+        assert!(
+            !sidebar.should_compute(7, "n"),
+            "hidden sidebar computes nothing"
+        );
+        sidebar.active = true;
+        assert!(sidebar.should_compute(7, "n"));
+        sidebar.start(7, "n");
+        assert!(sidebar.is_computing(7, "n"));
+        assert!(!sidebar.should_compute(7, "m"), "one column at a time");
+        sidebar.finish(7, "n", Ok(stats));
+        assert!(sidebar.get(7, "n").is_some());
+        assert!(!sidebar.should_compute(7, "n"));
+        assert!(sidebar.should_compute(7, "m"));
+
+        sidebar.start(8, "m");
+        assert!(sidebar.get(8, "n").is_none(), "a new view drops the cache");
+        sidebar.finish(7, "n", Err("stale".to_string()));
+        assert!(
+            sidebar.get(8, "n").is_none(),
+            "results for an old view are ignored"
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! Columns modal: every column of the view in display order, to rename, hide, duplicate and
+//! reorder. Renames and duplicates change the view's source (duplicates are computed columns
+//! copying the original); the order and hidden columns are the view's column order. All of it
+//! is saved in templates. Opened with `#`; Enter applies, Esc discards.
+
+use crate::computed_column::ComputedColumn;
+use crate::widgets::text_input::TextInput;
+use ratatui::widgets::TableState;
+use serde::{Deserialize, Serialize};
+
+/// A column of the source given another name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnRename {
+    pub from: String,
+    pub to: String,
+}
+
+/// A row of the modal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnEntry {
+    /// Name in the data; None for a duplicate not made yet.
+    pub source: Option<String>,
+    /// Name after the changes.
+    pub name: String,
+    pub visible: bool,
+    /// Whether the column is computed (including a new duplicate), so it can be removed.
+    pub computed: bool,
+    /// Column a new duplicate copies, by its name in the data.
+    pub duplicate_of: Option<String>,
+}
+
+impl ColumnEntry {
+    pub fn renamed(&self) -> bool {
+        self.source
+            .as_deref()
+            .is_some_and(|source| source != self.name)
+    }
+}
+
+/// What applying the modal does to the view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnChanges {
+    /// Renames of columns read from the data.
+    pub renames: Vec<ColumnRename>,
+    /// The computed columns after the changes: renamed, removed or added as duplicates.
+    pub computed_columns: Vec<ComputedColumn>,
+    /// Visible columns in display order.
+    pub order: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct ColumnsModal {
+    pub entries: Vec<ColumnEntry>,
+    pub table_state: TableState,
+    /// Name being typed for the selected column (`r`); None when not renaming.
+    pub rename_input: Option<TextInput>,
+    /// Computed columns of the view when opened.
+    computed_columns: Vec<ComputedColumn>,
+}
+
+impl ColumnsModal {
+    /// The modal for a view showing `order` of `columns` (the others are hidden), with
+    /// `computed_columns` among them.
+    pub fn new(columns: &[String], order: &[String], computed_columns: &[ComputedColumn]) -> Self {
+        let hidden = columns.iter().filter(|c| !order.contains(c));
+        let entries = order
+            .iter()
+            .filter(|c| columns.contains(c))
+            .map(|c| (c, true))
+            .chain(hidden.map(|c| (c, false)))
+            .map(|(name, visible)| ColumnEntry {
+                source: Some(name.clone()),
+                name: name.clone(),
+                visible,
+                computed: computed_columns.iter().any(|c| &c.name == name),
+                duplicate_of: None,
+            })
+            .collect();
+        let mut table_state = TableState::default();
+        table_state.select(Some(0));
+        Self {
+            entries,
+            table_state,
+            rename_input: None,
+            computed_columns: computed_columns.to_vec(),
+        }
+    }
+
+    pub fn selected(&self) -> usize {
+        self.table_state.selected().unwrap_or(0)
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        let last = self.entries.len().saturating_sub(1);
+        let selected = self.selected().saturating_add_signed(delta).min(last);
+        self.table_state.select(Some(selected));
+    }
+
+    /// Move the selected column up (`delta` < 0) or down in the order, keeping it selected.
+    pub fn shift(&mut self, delta: isize) {
+        let from = self.selected();
+        let to = from
+            .saturating_add_signed(delta)
+            .min(self.entries.len().saturating_sub(1));
+        if from < self.entries.len() && from != to {
+            let entry = self.entries.remove(from);
+            self.entries.insert(to, entry);
+            self.table_state.select(Some(to));
+        }
+    }
+
+    pub fn toggle_visible(&mut self) {
+        let selected = self.selected();
+        if let Some(entry) = self.entries.get_mut(selected) {
+            entry.visible = !entry.visible;
+        }
+    }
+
+    /// Add a copy of the selected column after it, named `<name>_copy` (or `_copy2`, ...).
+    pub fn duplicate(&mut self) {
+        let selected = self.selected();
+        let Some(entry) = self.entries.get(selected) else {
+            return;
+        };
+        let Some(original) = entry.source.clone().or_else(|| entry.duplicate_of.clone()) else {
+            return;
+        };
+        let base = format!("{}_copy", entry.name);
+        let name = (1..)
+            .map(|n| match n {
+                1 => base.clone(),
+                n => format!("{base}{n}"),
+            })
+            .find(|name| !self.entries.iter().any(|e| &e.name == name))
+            .unwrap_or(base);
+        self.entries.insert(
+            selected + 1,
+            ColumnEntry {
+                source: None,
+                name,
+                visible: true,
+                computed: true,
+                duplicate_of: Some(original),
+            },
+        );
+        self.table_state.select(Some(selected + 1));
+    }
+
+    /// Remove the selected column when it is computed. Returns whether it was removed.
+    pub fn remove(&mut self) -> bool {
+        let selected = self.selected();
+        if !self.entries.get(selected).is_some_and(|e| e.computed) {
+            return false;
+        }
+        self.entries.remove(selected);
+        self.move_by(0);
+        true
+    }
+
+    /// Start typing a new name for the selected column.
+    pub fn start_rename(&mut self, theme: &crate::config::Theme) {
+        let Some(entry) = self.entries.get(self.selected()) else {
+            return;
+        };
+        let mut input = TextInput::new().with_theme(theme);
+        input.set_value(entry.name.clone());
+        input.set_cursor(entry.name.chars().count());
+        input.set_focused(true);
+        self.rename_input = Some(input);
+    }
+
+    /// Give the selected column the typed name.
+    pub fn finish_rename(&mut self) -> Result<(), String> {
+        let Some(input) = self.rename_input.as_ref() else {
+            return Ok(());
+        };
+        let name = input.value().trim().to_string();
+        let selected = self.selected();
+        if name.is_empty() {
+            return Err("Column name is empty".to_string());
+        }
+        if self
+            .entries
+            .iter()
+            .enumerate()
+            .any(|(i, e)| i != selected && e.name == name)
+        {
+            return Err(format!("Column '{}' already exists", name));
+        }
+        if let Some(entry) = self.entries.get_mut(selected) {
+            entry.name = name;
+        }
+        self.rename_input = None;
+        Ok(())
+    }
+
+    /// The changes as edited, or a message when no column would be shown.
+    pub fn changes(&self) -> Result<ColumnChanges, String> {
+        let new_name = |source: &str| {
+            self.entries
+                .iter()
+                .find(|e| e.source.as_deref() == Some(source))
+                .map_or_else(|| source.to_string(), |e| e.name.clone())
+        };
+        let renames = self
+            .entries
+            .iter()
+            .filter(|e| !e.computed && e.renamed())
+            .map(|e| ColumnRename {
+                from: e.source.clone().unwrap_or_default(),
+                to: e.name.clone(),
+            })
+            .collect();
+        // Computed columns keep their order; duplicates go after them, copying the original
+        // under its new name
+        let mut computed_columns: Vec<ComputedColumn> = self
+            .computed_columns
+            .iter()
+            .filter_map(|c| {
+                let entry = self
+                    .entries
+                    .iter()
+                    .find(|e| e.source.as_deref() == Some(c.name.as_str()))?;
+                Some(ComputedColumn::new(
+                    entry.name.clone(),
+                    c.expression.clone(),
+                ))
+            })
+            .collect();
+        computed_columns.extend(self.entries.iter().filter_map(|e| {
+            let original = e.duplicate_of.as_deref()?;
+            let expression = format!("col(\"{}\")", new_name(original).replace('"', "\\\""));
+            Some(ComputedColumn::new(e.name.clone(), expression))
+        }));
+        let order: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|e| e.visible)
+            .map(|e| e.name.clone())
+            .collect();
+        if order.is_empty() {
+            return Err("Keep at least one column visible".to_string());
+        }
+        Ok(ColumnChanges {
+            renames,
+            computed_columns,
+            order,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn edits_become_renames_computed_columns_and_order() {
+        let computed = [ComputedColumn::new("total", "price * qty")];
+        let mut modal = ColumnsModal::new(
+            &names(&["id", "price", "qty", "total"]),
+            &names(&["id", "price", "total"]),
+            &computed,
+        );
+        assert_eq!(modal.entries[3].name, "qty");
+        assert!(!modal.entries[3].visible);
+
+        // Rename price, copy it, show qty and move it to the front, rename total
+        modal.move_by(1);
+        let config = crate::config::AppConfig::default();
+        let theme = crate::config::Theme::from_config(&config.theme).unwrap();
+        modal.start_rename(&theme);
+        modal
+            .rename_input
+            .as_mut()
+            .unwrap()
+            .set_value("unit price".into());
+        modal.finish_rename().unwrap();
+        modal.duplicate();
+        assert_eq!(modal.entries[2].name, "unit price_copy");
+        modal.move_by(10);
+        modal.toggle_visible();
+        modal.shift(-10);
+        modal.move_by(4);
+        assert_eq!(modal.entries[modal.selected()].name, "total");
+        modal.start_rename(&theme);
+        modal.rename_input.as_mut().unwrap().set_value("id".into());
+        assert!(modal.finish_rename().is_err(), "names stay unique");
+        modal
+            .rename_input
+            .as_mut()
+            .unwrap()
+            .set_value("amount".into());
+        modal.finish_rename().unwrap();
+
+        let changes = modal.changes().unwrap();
+        assert_eq!(
+            changes.renames,
+            [ColumnRename {
+                from: "price".into(),
+                to: "unit price".into()
+            }]
+        );
+        assert_eq!(
+            changes.computed_columns,
+            [
+                ComputedColumn::new("amount", "price * qty"),
+                ComputedColumn::new("unit price_copy", "col(\"unit price\")"),
+            ]
+        );
+        assert_eq!(
+            changes.order,
+            names(&["qty", "id", "unit price", "unit price_copy", "amount"])
+        );
+
+        // Only computed columns can be removed
+        modal.move_by(-10);
+        assert!(!modal.remove());
+        modal.move_by(3);
+        assert!(modal.remove());
+        assert_eq!(modal.changes().unwrap().computed_columns.len(), 1);
+    }
+}
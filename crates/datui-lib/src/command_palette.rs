@@ -0,0 +1,129 @@
+//! Command palette (`Ctrl+P`): one fuzzy finder over the view's columns and the main-view
+//! actions. Picking a column scrolls the table to it; picking an action runs it as if its key
+//! had been pressed.
+
+use crate::column_ruler::fuzzy_score;
+use crate::keymap::Action;
+use crate::widgets::text_input::TextInput;
+use ratatui::widgets::TableState;
+
+/// An entry of the palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteItem {
+    /// Column at this index of the view's column order.
+    Column(usize),
+    Action(Action),
+}
+
+/// Name an action is listed and matched under, e.g. `open pivot melt`.
+pub fn action_label(action: Action) -> String {
+    action.name().replace('_', " ")
+}
+
+pub struct CommandPalette {
+    pub input: TextInput,
+    /// Columns of the view in display order.
+    pub columns: Vec<String>,
+    /// Entries matching the input, best first.
+    pub items: Vec<PaletteItem>,
+    pub table_state: TableState,
+}
+
+impl CommandPalette {
+    pub fn new(columns: Vec<String>, theme: &crate::config::Theme) -> Self {
+        let mut input = TextInput::new().with_theme(theme);
+        input.set_focused(true);
+        let mut palette = Self {
+            input,
+            columns,
+            items: Vec::new(),
+            table_state: TableState::default(),
+        };
+        palette.refresh();
+        palette
+    }
+
+    /// Label of `item`: the column name or the action's name.
+    pub fn label(&self, item: PaletteItem) -> String {
+        match item {
+            PaletteItem::Column(i) => self.columns[i].clone(),
+            PaletteItem::Action(action) => action_label(action),
+        }
+    }
+
+    /// Match the entries against the input. Without input every column is listed, then every
+    /// action; otherwise better matches come first, a column before an action on a tie.
+    pub fn refresh(&mut self) {
+        let all = (0..self.columns.len()).map(PaletteItem::Column).chain(
+            Action::ALL
+                .into_iter()
+                .filter(|a| *a != Action::CommandPalette)
+                .map(PaletteItem::Action),
+        );
+        let query = self.input.value().trim().to_string();
+        self.items = if query.is_empty() {
+            all.collect()
+        } else {
+            let mut scored: Vec<(u8, usize, usize, PaletteItem)> = all
+                .enumerate()
+                .filter_map(|(order, item)| {
+                    let label = self.label(item);
+                    let score = fuzzy_score(&label, &query)?;
+                    Some((score, label.chars().count(), order, item))
+                })
+                .collect();
+            scored.sort_unstable_by_key(|&(score, len, order, _)| (score, len, order));
+            scored.into_iter().map(|(_, _, _, item)| item).collect()
+        };
+        self.table_state
+            .select((!self.items.is_empty()).then_some(0));
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        if self.items.is_empty() {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0);
+        let last = self.items.len() - 1;
+        self.table_state
+            .select(Some(selected.saturating_add_signed(delta).min(last)));
+    }
+
+    pub fn selected(&self) -> Option<PaletteItem> {
+        self.items.get(self.table_state.selected()?).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_columns_and_actions() {
+        let config = crate::config::AppConfig::default();
+        let theme = crate::config::Theme::from_config(&config.theme).unwrap();
+        let columns = vec!["id".to_string(), "export_date".to_string()];
+        let mut palette = CommandPalette::new(columns, &theme);
+        assert_eq!(palette.items.len(), 2 + Action::ALL.len() - 1);
+
+        palette.input.set_value("export".into());
+        palette.refresh();
+        assert_eq!(
+            palette.selected(),
+            Some(PaletteItem::Action(Action::Export))
+        );
+        assert!(palette.items.contains(&PaletteItem::Column(1)));
+
+        palette.input.set_value("pivot".into());
+        palette.refresh();
+        assert_eq!(
+            palette.selected(),
+            Some(PaletteItem::Action(Action::OpenPivotMelt))
+        );
+        assert_eq!(palette.label(palette.items[0]), "open pivot melt");
+
+        palette.input.set_value("zzzz".into());
+        palette.refresh();
+        assert_eq!(palette.selected(), None);
+    }
+}
@@ -0,0 +1,180 @@
+//! New Column modal: name and expression of a computed column, with a preview of its first
+//! values. Opened with `+` (prefilled when the leftmost scrolled column is computed); Enter adds
+//! the column, Esc discards. Enter with an empty expression removes the computed column.
+
+use crate::computed_column::ComputedColumn;
+use crate::widgets::text_input::TextInput;
+use polars::prelude::*;
+
+/// Rows evaluated for the preview.
+pub const PREVIEW_ROWS: usize = 5;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ComputedColumnFocus {
+    #[default]
+    Name,
+    Expression,
+}
+
+/// Type and first values of the edited column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnPreview {
+    pub dtype: DataType,
+    pub values: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct ComputedColumnModal {
+    pub active: bool,
+    pub focus: ComputedColumnFocus,
+    pub name_input: TextInput,
+    pub expression_input: TextInput,
+    /// Source the preview is evaluated on (the view's source, with its computed columns).
+    source: Option<LazyFrame>,
+    /// Preview of the edited expression; None while it's empty.
+    pub preview: Option<Result<ColumnPreview, String>>,
+}
+
+impl ComputedColumnModal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open with `column` (a computed column being edited) or empty fields. The preview is
+    /// evaluated on the first rows of `source`.
+    pub fn open(
+        &mut self,
+        column: Option<&ComputedColumn>,
+        source: LazyFrame,
+        theme: &crate::config::Theme,
+    ) {
+        self.active = true;
+        self.name_input = TextInput::new().with_theme(theme);
+        self.expression_input = TextInput::new().with_theme(theme);
+        match column {
+            Some(column) => {
+                self.name_input.set_value(column.name.clone());
+                self.expression_input.set_value(column.expression.clone());
+                self.focus = ComputedColumnFocus::Expression;
+            }
+            None => self.focus = ComputedColumnFocus::Name,
+        }
+        self.source = Some(source);
+        self.refresh_preview();
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+        self.source = None;
+        self.preview = None;
+    }
+
+    /// The column as edited, or a message when the name is empty.
+    pub fn column(&self) -> Result<ComputedColumn, String> {
+        let name = self.name_input.value().trim();
+        if name.is_empty() {
+            return Err("Column name is empty".to_string());
+        }
+        Ok(ComputedColumn::new(
+            name,
+            self.expression_input.value().trim(),
+        ))
+    }
+
+    /// Evaluate the expression on the first rows of the source. Call after each edit.
+    pub fn refresh_preview(&mut self) {
+        let expression = self.expression_input.value().trim();
+        if expression.is_empty() {
+            self.preview = None;
+            return;
+        }
+        let name = match self.name_input.value().trim() {
+            "" => "preview",
+            name => name,
+        };
+        let column = ComputedColumn::new(name, expression);
+        self.preview = self.source.as_ref().map(|source| preview(source, &column));
+    }
+
+    pub fn next_focus(&mut self) {
+        self.focus = match self.focus {
+            ComputedColumnFocus::Name => ComputedColumnFocus::Expression,
+            ComputedColumnFocus::Expression => ComputedColumnFocus::Name,
+        };
+    }
+
+    pub fn prev_focus(&mut self) {
+        self.next_focus();
+    }
+
+    pub fn focused_input(&mut self) -> &mut TextInput {
+        match self.focus {
+            ComputedColumnFocus::Name => &mut self.name_input,
+            ComputedColumnFocus::Expression => &mut self.expression_input,
+        }
+    }
+}
+
+pub(crate) fn preview(
+    source: &LazyFrame,
+    column: &ComputedColumn,
+) -> Result<ColumnPreview, String> {
+    let expr = column.expr()?;
+    let df = source
+        .clone()
+        .limit(PREVIEW_ROWS as IdxSize)
+        .select([expr])
+        .collect()
+        .map_err(|e| crate::error_display::user_message_from_polars(&e))?;
+    let series = df.get_columns()[0].as_materialized_series();
+    let values = series
+        .iter()
+        .map(|v| {
+            if v.is_null() {
+                "null".to_string()
+            } else {
+                v.str_value().into_owned()
+            }
+        })
+        .collect();
+    Ok(ColumnPreview {
+        dtype: series.dtype().clone(),
+        values,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme() -> crate::config::Theme {
+        let config = crate::config::AppConfig::default();
+        crate::config::Theme::from_config(&config.theme).unwrap()
+    }
+
+    #[test]
+    fn preview_shows_first_values() {
+        let source = df!("price" => [1.5, 2.0, 4.0], "qty" => [2i64, 3, 1])
+            .unwrap()
+            .lazy();
+        let mut modal = ComputedColumnModal::new();
+        modal.open(None, source, &theme());
+        assert_eq!(modal.focus, ComputedColumnFocus::Name);
+        assert!(modal.column().is_err());
+        modal.focused_input().set_value("total".into());
+        modal.next_focus();
+        modal.focused_input().set_value("price * qty".into());
+        modal.refresh_preview();
+        let preview = modal.preview.clone().unwrap().unwrap();
+        assert_eq!(preview.dtype, DataType::Float64);
+        assert_eq!(preview.values, ["3.0", "6.0", "4.0"]);
+        assert_eq!(
+            modal.column(),
+            Ok(ComputedColumn::new("total", "price * qty"))
+        );
+
+        modal.focused_input().set_value("price * missing".into());
+        modal.refresh_preview();
+        assert!(modal.preview.clone().unwrap().is_err());
+    }
+}
@@ -0,0 +1,214 @@
+//! Diagonal concatenation (`--concat diagonal`): combine files whose schemas differ by taking
+//! the union of their columns, filling columns a file lacks with nulls, and remember which file
+//! contributed which columns for the info panel. Also adds the per-row source file column
+//! (`--source-column`) when concatenating.
+
+use std::path::{Path, PathBuf};
+
+use color_eyre::Result;
+use polars::prelude::*;
+
+use crate::schema_diff;
+use crate::widgets::datatable::DataTableState;
+use crate::OpenOptions;
+
+/// Column added by `--source-column` holding the path of the file each row came from.
+pub const SOURCE_FILE_COLUMN: &str = "__source_file__";
+
+/// Which files contributed each column of a diagonally concatenated table.
+#[derive(Debug, Clone, Default)]
+pub struct ConcatSources {
+    /// File names, in load order.
+    pub files: Vec<String>,
+    /// Per column (in the combined table's order): indexes into `files` of the files that have it.
+    pub columns: Vec<(String, Vec<usize>)>,
+}
+
+impl ConcatSources {
+    pub fn from_schemas(files: Vec<String>, schemas: &[SchemaRef]) -> Self {
+        let mut columns: Vec<(String, Vec<usize>)> = Vec::new();
+        for (file_idx, schema) in schemas.iter().enumerate() {
+            for name in schema.iter_names() {
+                match columns
+                    .iter_mut()
+                    .find(|(c, _)| c.as_str() == name.as_str())
+                {
+                    Some((_, sources)) => sources.push(file_idx),
+                    None => columns.push((name.to_string(), vec![file_idx])),
+                }
+            }
+        }
+        Self { files, columns }
+    }
+
+    /// Files that have `column`: "all" when every file has it, else their names. None for
+    /// columns no file has (e.g. added after load).
+    pub fn label(&self, column: &str) -> Option<String> {
+        let (_, sources) = self.columns.iter().find(|(c, _)| c == column)?;
+        if sources.len() == self.files.len() {
+            return Some("all".to_string());
+        }
+        Some(
+            sources
+                .iter()
+                .map(|&f| self.files[f].as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+
+    /// Number of columns not every file has.
+    pub fn partial_count(&self) -> usize {
+        self.columns
+            .iter()
+            .filter(|(_, sources)| sources.len() < self.files.len())
+            .count()
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Open each path with its own format, adding [`SOURCE_FILE_COLUMN`] when
+/// `options.source_column` is set. Returns the frames and each file's own schema.
+fn open_frames(
+    paths: &[PathBuf],
+    options: &OpenOptions,
+) -> Result<(Vec<LazyFrame>, Vec<SchemaRef>)> {
+    let mut frames = Vec::with_capacity(paths.len());
+    let mut schemas = Vec::with_capacity(paths.len());
+    for path in paths {
+        let state = schema_diff::open_file(path, options)
+            .map_err(|e| color_eyre::eyre::eyre!("{}: {}", path.display(), e))?;
+        schemas.push(state.schema.clone());
+        frames.push(if options.source_column {
+            state
+                .lf
+                .with_column(lit(path.display().to_string()).alias(SOURCE_FILE_COLUMN))
+        } else {
+            state.lf
+        });
+    }
+    Ok((frames, schemas))
+}
+
+/// Open each path with its own format and stack them (same schema assumed). Used when
+/// `--source-column` is set, since each file then needs its own path column.
+pub fn concat_vertical(paths: &[PathBuf], options: &OpenOptions) -> Result<LazyFrame> {
+    let (frames, _) = open_frames(paths, options)?;
+    Ok(concat(frames, UnionArgs::default())?)
+}
+
+/// Open each path with its own format and concatenate them diagonally. Columns with different
+/// types across files are cast to a common supertype.
+pub fn concat_diagonal(
+    paths: &[PathBuf],
+    options: &OpenOptions,
+) -> Result<(LazyFrame, ConcatSources)> {
+    let (frames, schemas) = open_frames(paths, options)?;
+    let lf = concat_lf_diagonal(
+        frames,
+        UnionArgs {
+            to_supertypes: true,
+            ..Default::default()
+        },
+    )?;
+    let files = paths.iter().map(|p| file_name(p)).collect();
+    let mut sources = ConcatSources::from_schemas(files, &schemas);
+    if options.source_column {
+        sources
+            .columns
+            .push((SOURCE_FILE_COLUMN.to_string(), (0..paths.len()).collect()));
+    }
+    Ok((lf, sources))
+}
+
+/// Build a [`DataTableState`] from diagonally concatenated paths.
+pub fn open_diagonal(
+    paths: &[PathBuf],
+    options: &OpenOptions,
+) -> Result<(DataTableState, ConcatSources)> {
+    let (lf, sources) = concat_diagonal(paths, options)?;
+    Ok((DataTableState::from_lazyframe(lf, options)?, sources))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(names: &[&str]) -> SchemaRef {
+        Arc::new(Schema::from_iter(
+            names
+                .iter()
+                .map(|n| Field::new((*n).into(), DataType::Int64)),
+        ))
+    }
+
+    #[test]
+    fn test_sources_label_columns_by_file() {
+        let sources = ConcatSources::from_schemas(
+            vec!["a.csv".into(), "b.csv".into(), "c.csv".into()],
+            &[
+                schema(&["id", "x"]),
+                schema(&["id", "y"]),
+                schema(&["id", "x", "z"]),
+            ],
+        );
+        let names: Vec<&str> = sources.columns.iter().map(|(c, _)| c.as_str()).collect();
+        assert_eq!(names, vec!["id", "x", "y", "z"]);
+        assert_eq!(sources.label("id").as_deref(), Some("all"));
+        assert_eq!(sources.label("x").as_deref(), Some("a.csv, c.csv"));
+        assert_eq!(sources.label("z").as_deref(), Some("c.csv"));
+        assert_eq!(sources.label("missing"), None);
+        assert_eq!(sources.partial_count(), 3);
+    }
+
+    #[test]
+    fn test_concat_diagonal_fills_missing_columns_with_nulls() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.csv");
+        let b = dir.path().join("b.csv");
+        std::fs::write(&a, "id,x\n1,10\n2,20\n").unwrap();
+        std::fs::write(&b, "id,y\n3,c\n").unwrap();
+        let (lf, sources) = concat_diagonal(&[a, b], &OpenOptions::default()).unwrap();
+        let df = lf.collect().unwrap();
+        assert_eq!(df.height(), 3);
+        let names: Vec<&str> = df.get_column_names().iter().map(|c| c.as_str()).collect();
+        assert_eq!(names, vec!["id", "x", "y"]);
+        assert_eq!(df.column("x").unwrap().null_count(), 1);
+        assert_eq!(df.column("y").unwrap().null_count(), 2);
+        assert_eq!(sources.label("y").as_deref(), Some("b.csv"));
+    }
+
+    #[test]
+    fn test_source_column_names_each_rows_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.csv");
+        let b = dir.path().join("b.csv");
+        std::fs::write(&a, "id\n1\n2\n").unwrap();
+        std::fs::write(&b, "id\n3\n").unwrap();
+        let options = OpenOptions {
+            source_column: true,
+            ..Default::default()
+        };
+        let df = concat_vertical(&[a.clone(), b.clone()], &options)
+            .unwrap()
+            .collect()
+            .unwrap();
+        let sources: Vec<Option<&str>> = df
+            .column(SOURCE_FILE_COLUMN)
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .collect();
+        let (a, b) = (a.display().to_string(), b.display().to_string());
+        assert_eq!(
+            sources,
+            vec![Some(a.as_str()), Some(a.as_str()), Some(b.as_str())]
+        );
+    }
+}
@@ -6,6 +6,17 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use supports_color::Stream;
 
+use crate::migration::{self, Migration};
+use crate::terminal_background::TerminalBackground;
+
+/// Current config file format (`version` in config.toml). Bump it and add a step to
+/// `CONFIG_MIGRATIONS` when a change to `AppConfig` would stop older files from loading.
+pub const CONFIG_VERSION: &str = "0.2";
+
+/// Upgrade steps for config files, applied to the parsed document so comments and layout
+/// survive. Files without a `version` count as "0.2", the format before upgrades existed.
+const CONFIG_MIGRATIONS: &[Migration<&str, toml_edit::DocumentMut>] = &[];
+
 /// Manages config directory and config file operations
 #[derive(Clone)]
 pub struct ConfigManager {
@@ -113,6 +124,11 @@ impl ConfigManager {
             comments.insert(format!("theme.colors.{}", field), comment.to_string());
         }
 
+        // UI fields
+        for (field, comment) in UI_COMMENTS {
+            comments.insert(format!("ui.{}", field), comment.to_string());
+        }
+
         // Controls fields
         for (field, comment) in CONTROLS_COMMENTS {
             comments.insert(format!("ui.controls.{}", field), comment.to_string());
@@ -123,6 +139,16 @@ impl ConfigManager {
             comments.insert(format!("query.{}", field), comment.to_string());
         }
 
+        // Export fields
+        for (field, comment) in EXPORT_COMMENTS {
+            comments.insert(format!("export.{}", field), comment.to_string());
+        }
+
+        // Notification fields
+        for (field, comment) in NOTIFICATION_COMMENTS {
+            comments.insert(format!("notifications.{}", field), comment.to_string());
+        }
+
         // Template fields
         for (field, comment) in TEMPLATE_COMMENTS {
             comments.insert(format!("templates.{}", field), comment.to_string());
@@ -219,6 +245,8 @@ impl ConfigManager {
             "cloud.s3_access_key_id",
             "cloud.s3_secret_access_key",
             "cloud.s3_region",
+            "cloud.azure_account",
+            "cloud.azure_key",
             "file_loading.delimiter",
             "file_loading.has_header",
             "file_loading.skip_lines",
@@ -344,8 +372,13 @@ pub struct AppConfig {
     pub theme: ThemeConfig,
     pub ui: UiConfig,
     pub query: QueryConfig,
+    pub export: ExportConfig,
+    pub notifications: NotificationConfig,
     pub templates: TemplateConfig,
     pub debug: DebugConfig,
+    /// Keys of main-view actions, by action name (e.g. `open_filter = "ctrl+s"`).
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub keys: crate::keymap::KeysConfig,
 }
 
 // Field comments for AppConfig (top-level fields)
@@ -393,6 +426,14 @@ const SECTION_HEADERS: &[(&str, &str)] = &[
         "query",
         "# ============================================================================\n# Query System\n# ============================================================================",
     ),
+    (
+        "export",
+        "# ============================================================================\n# Export and Copy\n# ============================================================================",
+    ),
+    (
+        "notifications",
+        "# ============================================================================\n# Notifications\n# ============================================================================\n# Signal when a long load, export or analysis finishes while the terminal is in the\n# background (or when the terminal does not report focus).",
+    ),
     (
         "templates",
         "# ============================================================================\n# Template Settings\n# ============================================================================",
@@ -414,6 +455,10 @@ pub struct CloudConfig {
     pub s3_secret_access_key: Option<String>,
     /// Region (e.g. us-east-1). Often required when using a custom endpoint (MinIO uses us-east-1).
     pub s3_region: Option<String>,
+    /// Azure storage account name for az:// and abfss:// URLs when not using env
+    pub azure_account: Option<String>,
+    /// Azure storage account access key when not using env
+    pub azure_key: Option<String>,
 }
 
 const CLOUD_COMMENTS: &[(&str, &str)] = &[
@@ -433,6 +478,14 @@ const CLOUD_COMMENTS: &[(&str, &str)] = &[
         "s3_region",
         "Region (e.g. us-east-1). Required for custom endpoints; MinIO often uses us-east-1.",
     ),
+    (
+        "azure_account",
+        "Azure storage account for az:// and abfss:// URLs (or set AZURE_STORAGE_ACCOUNT_NAME).",
+    ),
+    (
+        "azure_key",
+        "Azure storage account key (or set AZURE_STORAGE_ACCOUNT_KEY). Unset = other AZURE_* credentials.",
+    ),
 ];
 
 impl CloudConfig {
@@ -449,6 +502,12 @@ impl CloudConfig {
         if other.s3_region.is_some() {
             self.s3_region = other.s3_region;
         }
+        if other.azure_account.is_some() {
+            self.azure_account = other.azure_account;
+        }
+        if other.azure_key.is_some() {
+            self.azure_key = other.azure_key;
+        }
     }
 }
 
@@ -479,6 +538,10 @@ pub struct FileLoadingConfig {
     pub infer_schema_length: Option<usize>,
     /// When true, CSV reader ignores parse errors and continues with the next batch. Default false.
     pub ignore_errors: Option<bool>,
+    /// When true, CSV and JSON Lines rows that don't parse are left out of the load and listed in the Load report panel. Default false.
+    pub skip_bad_rows: Option<bool>,
+    /// CSV column types: list of "COL:TYPE" (str, i64, i32, u64, u32, f64, f32, bool, date, datetime, time), read as given instead of inferred. Example: ["zip:str", "amount:f64"].
+    pub dtypes: Option<Vec<String>>,
 }
 
 // Field comments for FileLoadingConfig
@@ -534,6 +597,14 @@ const FILE_LOADING_COMMENTS: &[(&str, &str)] = &[
         "ignore_errors",
         "When true, CSV reader ignores parse errors and continues with the next batch (default false).",
     ),
+    (
+        "skip_bad_rows",
+        "When true, CSV and JSON Lines rows that don't parse are left out and listed in the Load report panel (default false).",
+    ),
+    (
+        "dtypes",
+        "CSV column types, read as given instead of inferred: list of \"COL:TYPE\" (str, i64, i32, u64, u32, f64, f32, bool, date, datetime, time). Example: [\"zip:str\", \"amount:f64\"]",
+    ),
 ];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -553,6 +624,18 @@ pub struct DisplayConfig {
     /// Optional fixed width for all sidebars (Info, Sort & Filter, Template, Pivot & Melt). When None, use built-in defaults per sidebar.
     #[serde(default)]
     pub sidebar_width: Option<u16>,
+    /// Widest a column is drawn; longer values end in `…`. None = as wide as the content.
+    #[serde(default)]
+    pub max_column_width: Option<u16>,
+    /// Ruler above the column headers: off, column numbers (index) or spreadsheet letters.
+    #[serde(default)]
+    pub column_ruler: crate::column_ruler::ColumnRuler,
+    /// Locale for displaying dates and numbers (e.g. "en-US", "de-DE"). None = ISO dates and plain numbers.
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Display formats by column name, applied to any file with a column of that name.
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub column_formats: crate::column_format::ColumnFormats,
 }
 
 // Field comments for DisplayConfig
@@ -587,6 +670,18 @@ const DISPLAY_COMMENTS: &[(&str, &str)] = &[
         "sidebar_width",
         "Optional: fixed width in characters for all sidebars (Info, Sort & Filter, Templates, Pivot & Melt). When unset, each sidebar uses its default width. Example: sidebar_width = 70",
     ),
+    (
+        "max_column_width",
+        "Optional: widest a table column is drawn, in characters; longer values are cut with …\nza shows the full value; <, > and = change the selected column's width. Example: max_column_width = 40",
+    ),
+    (
+        "column_ruler",
+        "Ruler above the column headers: \"off\", \"index\" (column numbers from 1) or \"letters\" (A, B, ... AA)\nzr cycles it; | jumps to a column by name, number or letters. Default \"off\"",
+    ),
+    (
+        "locale",
+        "Optional: locale for displaying dates and numbers in the table (display only; data is unchanged)\nOne of: iso, en-US, en-GB, de-DE, fr-FR, es-ES, it-IT, nl-NL, pt-BR, sv-SE, ja-JP. Example: locale = \"de-DE\"",
+    ),
 ];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -597,6 +692,12 @@ pub struct PerformanceConfig {
     pub event_poll_interval_ms: u64,
     /// When true (default), use Polars streaming engine for LazyFrame collect when the streaming feature is enabled (lower memory, batch processing).
     pub polars_streaming: bool,
+    /// When true (default), pick a load profile from the input's size on open (see `load_profile`).
+    pub load_profiles: bool,
+    /// Inputs up to this size (MiB) get the Small profile: read into memory, no sampling.
+    pub small_file_mb: u64,
+    /// Inputs of at least this size (MiB) get the Large profile: streaming, sampled analysis, small buffer.
+    pub large_file_mb: u64,
 }
 
 // Field comments for PerformanceConfig
@@ -613,6 +714,18 @@ const PERFORMANCE_COMMENTS: &[(&str, &str)] = &[
         "polars_streaming",
         "Use Polars streaming engine for LazyFrame collect when available (default: true). Reduces memory and can improve performance on large or partitioned data.",
     ),
+    (
+        "load_profiles",
+        "Pick defaults from the input's size when opening a file (default: true). Small files are read into memory\nand analyzed in full; large files stream, sample for analysis and buffer fewer pages. The Info panel shows\nthe profile and can change it. Explicit --sampling-threshold, --pages-lookahead/--pages-lookback or\n--polars-streaming flags turn this off for that run.",
+    ),
+    (
+        "small_file_mb",
+        "Inputs up to this size in MiB use the Small profile (default: 16)",
+    ),
+    (
+        "large_file_mb",
+        "Inputs of at least this size in MiB use the Large profile (default: 1024)",
+    ),
 ];
 
 /// Default maximum rows used for chart data when not overridden by config or UI.
@@ -651,14 +764,28 @@ impl ChartConfig {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ThemeConfig {
+    /// "auto" (detect terminal background), "dark", or "light".
+    pub mode: String,
     pub colors: ColorConfig,
 }
 
 // Field comments for ThemeConfig
-const THEME_COMMENTS: &[(&str, &str)] = &[];
+const THEME_COMMENTS: &[(&str, &str)] = &[(
+    "mode",
+    "Theme variant: \"auto\" (default) detects the terminal background, \"dark\" or \"light\" forces a variant.\nIn the light variant, colors left at their defaults switch to light-background defaults; colors you set are kept.",
+)];
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            mode: "auto".to_string(),
+            colors: ColorConfig::default(),
+        }
+    }
+}
 
 fn default_row_numbers_color() -> String {
     "dark_gray".to_string()
@@ -755,6 +882,9 @@ pub struct ColorConfig {
     pub temporal_col: String,
     /// Main data table: placeholder color for binary columns (the `‹binary›` stub)
     pub binary_col: String,
+    /// Main data table, heatmap mode (`H`): background at a column's minimum and maximum
+    pub heatmap_low: String,
+    pub heatmap_high: String,
     /// Chart view: series colors 1–7 (line/scatter/bar series)
     pub chart_series_color_1: String,
     pub chart_series_color_2: String,
@@ -821,6 +951,14 @@ const COLOR_COMMENTS: &[(&str, &str)] = &[
     ("bool_col", "Main table: boolean column text color"),
     ("temporal_col", "Main table: date/datetime/time column text color"),
     ("binary_col", "Main table: binary column placeholder color"),
+    (
+        "heatmap_low",
+        "Main table, heatmap mode: background of a column's smallest value\nCorrelation matrix: background of -1",
+    ),
+    (
+        "heatmap_high",
+        "Main table, heatmap mode: background of a column's largest value\nCorrelation matrix: background of +1",
+    ),
     ("chart_series_color_1", "Chart view: first series color"),
     ("chart_series_color_2", "Chart view: second series color"),
     ("chart_series_color_3", "Chart view: third series color"),
@@ -830,12 +968,19 @@ const COLOR_COMMENTS: &[(&str, &str)] = &[
     ("chart_series_color_7", "Chart view: seventh series color"),
 ];
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct UiConfig {
+    pub mouse: bool,
     pub controls: ControlsConfig,
 }
 
+// Field comments for UiConfig
+const UI_COMMENTS: &[(&str, &str)] = &[(
+    "mouse",
+    "Capture the mouse: click cells, headers and modal buttons, scroll with the wheel or by dragging\nWhile on, most terminals select text with Shift held down",
+)];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ControlsConfig {
@@ -868,6 +1013,49 @@ const QUERY_COMMENTS: &[(&str, &str)] = &[
     ("enable_history", "Enable query history caching"),
 ];
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportConfig {
+    pub context_rows: usize,
+}
+
+// Field comments for ExportConfig
+const EXPORT_COMMENTS: &[(&str, &str)] = &[(
+    "context_rows",
+    "Rows before and after the selected row included when copying (C) or exporting (E) row context",
+)];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationConfig {
+    pub enabled: bool,
+    pub threshold_secs: u64,
+    pub desktop: bool,
+    pub bell: bool,
+    pub title: bool,
+}
+
+// Field comments for NotificationConfig
+const NOTIFICATION_COMMENTS: &[(&str, &str)] = &[
+    (
+        "enabled",
+        "Notify when a long operation finishes while the terminal is unfocused",
+    ),
+    (
+        "threshold_secs",
+        "Only operations that take at least this many seconds notify",
+    ),
+    (
+        "desktop",
+        "Show a desktop notification (needs the notify build feature, on by default)",
+    ),
+    ("bell", "Ring the terminal bell"),
+    (
+        "title",
+        "Show the result in the terminal title until the terminal is focused again",
+    ),
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
 pub struct TemplateConfig {
@@ -907,7 +1095,7 @@ const DEBUG_COMMENTS: &[(&str, &str)] = &[
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
-            version: "0.2".to_string(),
+            version: CONFIG_VERSION.to_string(),
             cloud: CloudConfig::default(),
             file_loading: FileLoadingConfig::default(),
             display: DisplayConfig::default(),
@@ -916,8 +1104,11 @@ impl Default for AppConfig {
             theme: ThemeConfig::default(),
             ui: UiConfig::default(),
             query: QueryConfig::default(),
+            export: ExportConfig::default(),
+            notifications: NotificationConfig::default(),
             templates: TemplateConfig::default(),
             debug: DebugConfig::default(),
+            keys: Default::default(),
         }
     }
 }
@@ -934,6 +1125,10 @@ impl Default for DisplayConfig {
             table_cell_padding: 2,
             column_colors: true,
             sidebar_width: None,
+            max_column_width: None,
+            column_ruler: Default::default(),
+            locale: None,
+            column_formats: Default::default(),
         }
     }
 }
@@ -944,6 +1139,9 @@ impl Default for PerformanceConfig {
             sampling_threshold: None,
             event_poll_interval_ms: 25,
             polars_streaming: true,
+            load_profiles: true,
+            small_file_mb: 16,
+            large_file_mb: 1024,
         }
     }
 }
@@ -987,6 +1185,8 @@ impl Default for ColorConfig {
             bool_col: "yellow".to_string(),
             temporal_col: "magenta".to_string(),
             binary_col: "dark_gray".to_string(),
+            heatmap_low: "#1e3a8a".to_string(),
+            heatmap_high: "#991b1b".to_string(),
             chart_series_color_1: "cyan".to_string(),
             chart_series_color_2: "magenta".to_string(),
             chart_series_color_3: "green".to_string(),
@@ -998,6 +1198,115 @@ impl Default for ColorConfig {
     }
 }
 
+impl ColorConfig {
+    /// Defaults for light terminal backgrounds. Replaces colors that are unreadable on a light
+    /// background (near-white text, near-black bars, yellow and cyan text).
+    pub fn light() -> Self {
+        Self {
+            keybind_hints: "indexed(30)".to_string(),
+            keybind_labels: "indexed(238)".to_string(),
+            throbber: "indexed(30)".to_string(),
+            primary_chart_series_color: "indexed(30)".to_string(),
+            warning: "indexed(130)".to_string(),
+            controls_bg: "indexed(254)".to_string(),
+            text_inverse: "white".to_string(),
+            table_header: "black".to_string(),
+            table_header_bg: "indexed(254)".to_string(),
+            column_separator: "indexed(30)".to_string(),
+            sidebar_border: "indexed(250)".to_string(),
+            modal_border_active: "indexed(130)".to_string(),
+            distribution_skewed: "indexed(130)".to_string(),
+            distribution_other: "black".to_string(),
+            alternate_row_color: "indexed(255)".to_string(),
+            int_col: "indexed(30)".to_string(),
+            bool_col: "indexed(130)".to_string(),
+            heatmap_low: "#bfdbfe".to_string(),
+            heatmap_high: "#fca5a5".to_string(),
+            chart_series_color_1: "indexed(30)".to_string(),
+            chart_series_color_4: "indexed(130)".to_string(),
+            chart_series_color_7: "indexed(31)".to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Colors for the given background. For Light, every color still at its dark default is
+    /// replaced by the light default; colors the user changed are kept.
+    pub fn for_background(&self, background: TerminalBackground) -> Self {
+        if background == TerminalBackground::Dark {
+            return self.clone();
+        }
+        let dark = ColorConfig::default();
+        let light = ColorConfig::light();
+        let mut out = self.clone();
+        macro_rules! pick_light {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    if self.$field == dark.$field {
+                        out.$field = light.$field.clone();
+                    }
+                )*
+            };
+        }
+        pick_light!(
+            keybind_hints,
+            keybind_labels,
+            throbber,
+            primary_chart_series_color,
+            secondary_chart_series_color,
+            success,
+            error,
+            warning,
+            dimmed,
+            background,
+            surface,
+            controls_bg,
+            text_primary,
+            text_secondary,
+            text_inverse,
+            table_header,
+            table_header_bg,
+            row_numbers,
+            column_separator,
+            table_selected,
+            sidebar_border,
+            modal_border_active,
+            modal_border_error,
+            distribution_normal,
+            distribution_skewed,
+            distribution_other,
+            outlier_marker,
+            cursor_focused,
+            cursor_dimmed,
+            alternate_row_color,
+            str_col,
+            int_col,
+            float_col,
+            bool_col,
+            temporal_col,
+            binary_col,
+            heatmap_low,
+            heatmap_high,
+            chart_series_color_1,
+            chart_series_color_2,
+            chart_series_color_3,
+            chart_series_color_4,
+            chart_series_color_5,
+            chart_series_color_6,
+            chart_series_color_7,
+        );
+        out
+    }
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            mouse: true,
+            controls: ControlsConfig::default(),
+        }
+    }
+}
+
 impl Default for ControlsConfig {
     fn default() -> Self {
         Self {
@@ -1016,6 +1325,24 @@ impl Default for QueryConfig {
     }
 }
 
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self { context_rows: 5 }
+    }
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold_secs: 10,
+            desktop: true,
+            bell: true,
+            title: true,
+        }
+    }
+}
+
 impl Default for DebugConfig {
     fn default() -> Self {
         Self {
@@ -1031,13 +1358,22 @@ impl Default for DebugConfig {
 impl AppConfig {
     /// Load configuration from all layers (default → user)
     pub fn load(app_name: &str) -> Result<Self> {
-        let mut config = AppConfig::default();
+        match ConfigManager::new(app_name) {
+            Ok(manager) => Self::load_from(&manager),
+            Err(_) => Self::load_layers(None, None),
+        }
+    }
+
+    /// Load configuration with the user config read from `manager`'s directory
+    pub fn load_from(manager: &ConfigManager) -> Result<Self> {
+        let config_path = manager.config_path("config.toml");
+        let user_config = Self::load_user_config(&config_path).ok();
+        Self::load_layers(user_config, Some(config_path))
+    }
 
-        // Try to load user config (if exists)
-        let config_path = ConfigManager::new(app_name)
-            .ok()
-            .map(|m| m.config_path("config.toml"));
-        if let Ok(user_config) = Self::load_user_config(app_name) {
+    fn load_layers(user_config: Option<AppConfig>, config_path: Option<PathBuf>) -> Result<Self> {
+        let mut config = AppConfig::default();
+        if let Some(user_config) = user_config {
             config.merge(user_config);
         }
 
@@ -1053,22 +1389,29 @@ impl AppConfig {
         Ok(config)
     }
 
-    /// Load user configuration from ~/.config/datui/config.toml
-    fn load_user_config(app_name: &str) -> Result<AppConfig> {
-        let config_manager = ConfigManager::new(app_name)?;
-        let config_path = config_manager.config_path("config.toml");
+    /// Configuration for `--safe-mode`: built-in defaults without reading the user config,
+    /// the dark theme without terminal detection, and no per-type column colors.
+    pub fn safe_mode() -> Self {
+        let mut config = AppConfig::default();
+        config.theme.mode = "dark".to_string();
+        config.display.column_colors = false;
+        config
+    }
 
+    /// Load user configuration from ~/.config/datui/config.toml, upgrading an older format
+    fn load_user_config(config_path: &Path) -> Result<AppConfig> {
         if !config_path.exists() {
             return Ok(AppConfig::default());
         }
 
-        let content = std::fs::read_to_string(&config_path).map_err(|e| {
+        let content = std::fs::read_to_string(config_path).map_err(|e| {
             eyre!(
                 "Failed to read config file at {}: {}",
                 config_path.display(),
                 e
             )
         })?;
+        let content = Self::migrate_user_config(config_path, content);
 
         toml::from_str(&content).map_err(|e| {
             eyre!(
@@ -1079,6 +1422,28 @@ impl AppConfig {
         })
     }
 
+    /// Upgrade config file contents to `CONFIG_VERSION`. An upgraded file is written back (the
+    /// original kept as a backup) on a best-effort basis. Contents that don't parse, or that
+    /// have no upgrade path, are returned as-is for parsing and validation to report.
+    fn migrate_user_config(config_path: &Path, content: String) -> String {
+        let Ok(mut doc) = content.parse::<toml_edit::DocumentMut>() else {
+            return content;
+        };
+        let version = doc
+            .get("version")
+            .and_then(|v| v.as_str())
+            .unwrap_or(CONFIG_VERSION)
+            .to_string();
+        let reached = migration::migrate(&mut doc, version.as_str(), CONFIG_MIGRATIONS);
+        if reached == version {
+            return content;
+        }
+        doc["version"] = toml_edit::value(reached);
+        let migrated = doc.to_string();
+        let _ = migration::write_migrated(config_path, &version, &migrated);
+        migrated
+    }
+
     /// Merge another config into this one (other takes precedence)
     pub fn merge(&mut self, other: AppConfig) {
         // Version: take other's version if present and different from default
@@ -1095,17 +1460,21 @@ impl AppConfig {
         self.theme.merge(other.theme);
         self.ui.merge(other.ui);
         self.query.merge(other.query);
+        self.export.merge(other.export);
+        self.notifications.merge(other.notifications);
         self.templates.merge(other.templates);
         self.debug.merge(other.debug);
+        self.keys.extend(other.keys);
     }
 
     /// Validate configuration values
     pub fn validate(&self) -> Result<()> {
         // Validate version compatibility
-        if !self.version.starts_with("0.2") {
+        if !self.version.starts_with(CONFIG_VERSION) {
             return Err(eyre!(
-                "Unsupported config version: {}. Expected 0.2.x",
-                self.version
+                "Unsupported config version: {}. Expected {}.x",
+                self.version,
+                CONFIG_VERSION
             ));
         }
 
@@ -1120,6 +1489,20 @@ impl AppConfig {
             return Err(eyre!("event_poll_interval_ms must be greater than 0"));
         }
 
+        if self.performance.large_file_mb <= self.performance.small_file_mb {
+            return Err(eyre!(
+                "performance.large_file_mb ({}) must be greater than small_file_mb ({})",
+                self.performance.large_file_mb,
+                self.performance.small_file_mb
+            ));
+        }
+
+        if self.display.max_column_width == Some(0) {
+            return Err(eyre!(
+                "display.max_column_width must be greater than 0 when set"
+            ));
+        }
+
         if let Some(n) = self.chart.row_limit {
             if n == 0 || n > MAX_CHART_ROW_LIMIT {
                 return Err(eyre!(
@@ -1130,10 +1513,39 @@ impl AppConfig {
             }
         }
 
+        match self.theme.mode.as_str() {
+            "auto" | "dark" | "light" => {}
+            other => {
+                return Err(eyre!(
+                    "theme.mode must be one of: auto, dark, light, got \"{}\"",
+                    other
+                ))
+            }
+        }
+
+        if let Some(ref locale) = self.display.locale {
+            if crate::locale::DisplayLocale::from_name(locale).is_none() {
+                return Err(eyre!(
+                    "display.locale must be one of: {}, got \"{}\"",
+                    crate::locale::SUPPORTED_LOCALES.join(", "),
+                    locale
+                ));
+            }
+        }
+
+        for (column, format) in &self.display.column_formats {
+            format
+                .validate()
+                .map_err(|e| eyre!("display.column_formats.\"{}\": {}", column, e))?;
+        }
+
         // Validate all colors can be parsed
         let parser = ColorParser::new();
         self.theme.colors.validate(&parser)?;
 
+        // Key bindings: unknown actions or keys, reserved keys and conflicts
+        crate::keymap::KeyBindings::from_config(&self.keys).map_err(|e| eyre!("keys: {}", e))?;
+
         Ok(())
     }
 }
@@ -1183,6 +1595,12 @@ impl FileLoadingConfig {
         if other.ignore_errors.is_some() {
             self.ignore_errors = other.ignore_errors;
         }
+        if other.skip_bad_rows.is_some() {
+            self.skip_bad_rows = other.skip_bad_rows;
+        }
+        if other.dtypes.is_some() {
+            self.dtypes = other.dtypes;
+        }
     }
 }
 
@@ -1216,6 +1634,16 @@ impl DisplayConfig {
         if other.sidebar_width != default.sidebar_width {
             self.sidebar_width = other.sidebar_width;
         }
+        if other.max_column_width != default.max_column_width {
+            self.max_column_width = other.max_column_width;
+        }
+        if other.column_ruler != default.column_ruler {
+            self.column_ruler = other.column_ruler;
+        }
+        if other.locale.is_some() {
+            self.locale = other.locale;
+        }
+        self.column_formats.extend(other.column_formats);
     }
 }
 
@@ -1231,13 +1659,36 @@ impl PerformanceConfig {
         if other.polars_streaming != default.polars_streaming {
             self.polars_streaming = other.polars_streaming;
         }
+        if other.load_profiles != default.load_profiles {
+            self.load_profiles = other.load_profiles;
+        }
+        if other.small_file_mb != default.small_file_mb {
+            self.small_file_mb = other.small_file_mb;
+        }
+        if other.large_file_mb != default.large_file_mb {
+            self.large_file_mb = other.large_file_mb;
+        }
     }
 }
 
 impl ThemeConfig {
     pub fn merge(&mut self, other: Self) {
+        let default = ThemeConfig::default();
+        if other.mode != default.mode {
+            self.mode = other.mode;
+        }
         self.colors.merge(other.colors);
     }
+
+    /// Resolve the background variant to use. `detected` is only consulted in "auto" mode;
+    /// when detection fails the dark variant is used.
+    pub fn resolve_background(&self, detected: Option<TerminalBackground>) -> TerminalBackground {
+        match self.mode.as_str() {
+            "light" => TerminalBackground::Light,
+            "dark" => TerminalBackground::Dark,
+            _ => detected.unwrap_or(TerminalBackground::Dark),
+        }
+    }
 }
 
 impl ColorConfig {
@@ -1300,6 +1751,8 @@ impl ColorConfig {
         validate_color!(&self.float_col, "float_col");
         validate_color!(&self.bool_col, "bool_col");
         validate_color!(&self.temporal_col, "temporal_col");
+        validate_color!(&self.heatmap_low, "heatmap_low");
+        validate_color!(&self.heatmap_high, "heatmap_high");
         validate_color!(&self.chart_series_color_1, "chart_series_color_1");
         validate_color!(&self.chart_series_color_2, "chart_series_color_2");
         validate_color!(&self.chart_series_color_3, "chart_series_color_3");
@@ -1423,6 +1876,12 @@ impl ColorConfig {
         if other.binary_col != default.binary_col {
             self.binary_col = other.binary_col;
         }
+        if other.heatmap_low != default.heatmap_low {
+            self.heatmap_low = other.heatmap_low;
+        }
+        if other.heatmap_high != default.heatmap_high {
+            self.heatmap_high = other.heatmap_high;
+        }
         if other.chart_series_color_1 != default.chart_series_color_1 {
             self.chart_series_color_1 = other.chart_series_color_1;
         }
@@ -1449,6 +1908,10 @@ impl ColorConfig {
 
 impl UiConfig {
     pub fn merge(&mut self, other: Self) {
+        let default = UiConfig::default();
+        if other.mouse != default.mouse {
+            self.mouse = other.mouse;
+        }
         self.controls.merge(other.controls);
     }
 }
@@ -1477,6 +1940,36 @@ impl QueryConfig {
     }
 }
 
+impl ExportConfig {
+    pub fn merge(&mut self, other: Self) {
+        let default = ExportConfig::default();
+        if other.context_rows != default.context_rows {
+            self.context_rows = other.context_rows;
+        }
+    }
+}
+
+impl NotificationConfig {
+    pub fn merge(&mut self, other: Self) {
+        let default = NotificationConfig::default();
+        if other.enabled != default.enabled {
+            self.enabled = other.enabled;
+        }
+        if other.threshold_secs != default.threshold_secs {
+            self.threshold_secs = other.threshold_secs;
+        }
+        if other.desktop != default.desktop {
+            self.desktop = other.desktop;
+        }
+        if other.bell != default.bell {
+            self.bell = other.bell;
+        }
+        if other.title != default.title {
+            self.title = other.title;
+        }
+    }
+}
+
 impl TemplateConfig {
     pub fn merge(&mut self, other: Self) {
         let default = TemplateConfig::default();
@@ -1685,164 +2178,173 @@ pub struct Theme {
 }
 
 impl Theme {
-    /// Create a Theme from a ThemeConfig by parsing all color strings
+    /// Create a Theme from a ThemeConfig by parsing all color strings.
+    /// "auto" mode uses the dark variant; use `from_config_with_background` to pass a detected background.
     pub fn from_config(config: &ThemeConfig) -> Result<Self> {
+        Self::from_config_with_background(config, None)
+    }
+
+    /// Create a Theme for the variant resolved from `config.mode` and the detected terminal background.
+    pub fn from_config_with_background(
+        config: &ThemeConfig,
+        detected: Option<TerminalBackground>,
+    ) -> Result<Self> {
+        let colors = config
+            .colors
+            .for_background(config.resolve_background(detected));
+        Self::from_colors(&colors)
+    }
+
+    fn from_colors(config: &ColorConfig) -> Result<Self> {
         let parser = ColorParser::new();
         let mut colors = HashMap::new();
 
         // Parse all colors from config
         colors.insert(
             "keybind_hints".to_string(),
-            parser.parse(&config.colors.keybind_hints)?,
+            parser.parse(&config.keybind_hints)?,
         );
         colors.insert(
             "keybind_labels".to_string(),
-            parser.parse(&config.colors.keybind_labels)?,
-        );
-        colors.insert(
-            "throbber".to_string(),
-            parser.parse(&config.colors.throbber)?,
+            parser.parse(&config.keybind_labels)?,
         );
+        colors.insert("throbber".to_string(), parser.parse(&config.throbber)?);
         colors.insert(
             "primary_chart_series_color".to_string(),
-            parser.parse(&config.colors.primary_chart_series_color)?,
+            parser.parse(&config.primary_chart_series_color)?,
         );
         colors.insert(
             "secondary_chart_series_color".to_string(),
-            parser.parse(&config.colors.secondary_chart_series_color)?,
+            parser.parse(&config.secondary_chart_series_color)?,
         );
-        colors.insert("success".to_string(), parser.parse(&config.colors.success)?);
-        colors.insert("error".to_string(), parser.parse(&config.colors.error)?);
-        colors.insert("warning".to_string(), parser.parse(&config.colors.warning)?);
-        colors.insert("dimmed".to_string(), parser.parse(&config.colors.dimmed)?);
-        colors.insert(
-            "background".to_string(),
-            parser.parse(&config.colors.background)?,
-        );
-        colors.insert("surface".to_string(), parser.parse(&config.colors.surface)?);
+        colors.insert("success".to_string(), parser.parse(&config.success)?);
+        colors.insert("error".to_string(), parser.parse(&config.error)?);
+        colors.insert("warning".to_string(), parser.parse(&config.warning)?);
+        colors.insert("dimmed".to_string(), parser.parse(&config.dimmed)?);
+        colors.insert("background".to_string(), parser.parse(&config.background)?);
+        colors.insert("surface".to_string(), parser.parse(&config.surface)?);
         colors.insert(
             "controls_bg".to_string(),
-            parser.parse(&config.colors.controls_bg)?,
+            parser.parse(&config.controls_bg)?,
         );
         colors.insert(
             "text_primary".to_string(),
-            parser.parse(&config.colors.text_primary)?,
+            parser.parse(&config.text_primary)?,
         );
         colors.insert(
             "text_secondary".to_string(),
-            parser.parse(&config.colors.text_secondary)?,
+            parser.parse(&config.text_secondary)?,
         );
         colors.insert(
             "text_inverse".to_string(),
-            parser.parse(&config.colors.text_inverse)?,
+            parser.parse(&config.text_inverse)?,
         );
         colors.insert(
             "table_header".to_string(),
-            parser.parse(&config.colors.table_header)?,
+            parser.parse(&config.table_header)?,
         );
         colors.insert(
             "table_header_bg".to_string(),
-            parser.parse(&config.colors.table_header_bg)?,
+            parser.parse(&config.table_header_bg)?,
         );
         colors.insert(
             "row_numbers".to_string(),
-            parser.parse(&config.colors.row_numbers)?,
+            parser.parse(&config.row_numbers)?,
         );
         colors.insert(
             "column_separator".to_string(),
-            parser.parse(&config.colors.column_separator)?,
+            parser.parse(&config.column_separator)?,
         );
         colors.insert(
             "table_selected".to_string(),
-            parser.parse(&config.colors.table_selected)?,
+            parser.parse(&config.table_selected)?,
         );
         colors.insert(
             "sidebar_border".to_string(),
-            parser.parse(&config.colors.sidebar_border)?,
+            parser.parse(&config.sidebar_border)?,
         );
         colors.insert(
             "modal_border_active".to_string(),
-            parser.parse(&config.colors.modal_border_active)?,
+            parser.parse(&config.modal_border_active)?,
         );
         colors.insert(
             "modal_border_error".to_string(),
-            parser.parse(&config.colors.modal_border_error)?,
+            parser.parse(&config.modal_border_error)?,
         );
         colors.insert(
             "distribution_normal".to_string(),
-            parser.parse(&config.colors.distribution_normal)?,
+            parser.parse(&config.distribution_normal)?,
         );
         colors.insert(
             "distribution_skewed".to_string(),
-            parser.parse(&config.colors.distribution_skewed)?,
+            parser.parse(&config.distribution_skewed)?,
         );
         colors.insert(
             "distribution_other".to_string(),
-            parser.parse(&config.colors.distribution_other)?,
+            parser.parse(&config.distribution_other)?,
         );
         colors.insert(
             "outlier_marker".to_string(),
-            parser.parse(&config.colors.outlier_marker)?,
+            parser.parse(&config.outlier_marker)?,
         );
         colors.insert(
             "cursor_focused".to_string(),
-            parser.parse(&config.colors.cursor_focused)?,
+            parser.parse(&config.cursor_focused)?,
         );
         colors.insert(
             "cursor_dimmed".to_string(),
-            parser.parse(&config.colors.cursor_dimmed)?,
+            parser.parse(&config.cursor_dimmed)?,
         );
-        if config.colors.alternate_row_color != "default" {
+        if config.alternate_row_color != "default" {
             colors.insert(
                 "alternate_row_color".to_string(),
-                parser.parse(&config.colors.alternate_row_color)?,
+                parser.parse(&config.alternate_row_color)?,
             );
         }
-        colors.insert("str_col".to_string(), parser.parse(&config.colors.str_col)?);
-        colors.insert("int_col".to_string(), parser.parse(&config.colors.int_col)?);
-        colors.insert(
-            "float_col".to_string(),
-            parser.parse(&config.colors.float_col)?,
-        );
+        colors.insert("str_col".to_string(), parser.parse(&config.str_col)?);
+        colors.insert("int_col".to_string(), parser.parse(&config.int_col)?);
+        colors.insert("float_col".to_string(), parser.parse(&config.float_col)?);
+        colors.insert("bool_col".to_string(), parser.parse(&config.bool_col)?);
         colors.insert(
-            "bool_col".to_string(),
-            parser.parse(&config.colors.bool_col)?,
+            "temporal_col".to_string(),
+            parser.parse(&config.temporal_col)?,
         );
+        colors.insert("binary_col".to_string(), parser.parse(&config.binary_col)?);
         colors.insert(
-            "temporal_col".to_string(),
-            parser.parse(&config.colors.temporal_col)?,
+            "heatmap_low".to_string(),
+            parser.parse(&config.heatmap_low)?,
         );
         colors.insert(
-            "binary_col".to_string(),
-            parser.parse(&config.colors.binary_col)?,
+            "heatmap_high".to_string(),
+            parser.parse(&config.heatmap_high)?,
         );
         colors.insert(
             "chart_series_color_1".to_string(),
-            parser.parse(&config.colors.chart_series_color_1)?,
+            parser.parse(&config.chart_series_color_1)?,
         );
         colors.insert(
             "chart_series_color_2".to_string(),
-            parser.parse(&config.colors.chart_series_color_2)?,
+            parser.parse(&config.chart_series_color_2)?,
         );
         colors.insert(
             "chart_series_color_3".to_string(),
-            parser.parse(&config.colors.chart_series_color_3)?,
+            parser.parse(&config.chart_series_color_3)?,
         );
         colors.insert(
             "chart_series_color_4".to_string(),
-            parser.parse(&config.colors.chart_series_color_4)?,
+            parser.parse(&config.chart_series_color_4)?,
         );
         colors.insert(
             "chart_series_color_5".to_string(),
-            parser.parse(&config.colors.chart_series_color_5)?,
+            parser.parse(&config.chart_series_color_5)?,
         );
         colors.insert(
             "chart_series_color_6".to_string(),
-            parser.parse(&config.colors.chart_series_color_6)?,
+            parser.parse(&config.chart_series_color_6)?,
         );
         colors.insert(
             "chart_series_color_7".to_string(),
-            parser.parse(&config.colors.chart_series_color_7)?,
+            parser.parse(&config.chart_series_color_7)?,
         );
 
         Ok(Self { colors })
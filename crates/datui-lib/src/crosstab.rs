@@ -0,0 +1,522 @@
+//! Crosstab (the "Crosstab" analysis tool): a contingency table of two columns.
+//!
+//! Each cell counts the rows with that pair of values, or aggregates a third column over them
+//! (sum, mean, min or max), with row and column totals. A chi-square test of independence runs
+//! on the counts whatever the cells show. Nulls are a value of their own. Counts are only right
+//! on the full view, so this tool never samples.
+
+use crate::cardinality::is_countable;
+use crate::statistics::{chi_squared_cdf, collect_lazy};
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use polars::prelude::*;
+use std::collections::HashMap;
+
+/// Distinct values a row or column may have; more would not make a readable table.
+pub const MAX_LABELS: usize = 200;
+
+const COUNT_COLUMN: &str = "__datui_count";
+const VALUE_COLUMN: &str = "__datui_value";
+
+/// What a cell shows for the rows with its pair of values.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CrosstabAgg {
+    #[default]
+    Count,
+    Sum,
+    Mean,
+    Min,
+    Max,
+}
+
+impl CrosstabAgg {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Count => "count",
+            Self::Sum => "sum",
+            Self::Mean => "mean",
+            Self::Min => "min",
+            Self::Max => "max",
+        }
+    }
+
+    /// The aggregation after this one, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Count => Self::Sum,
+            Self::Sum => Self::Mean,
+            Self::Mean => Self::Min,
+            Self::Min => Self::Max,
+            Self::Max => Self::Count,
+        }
+    }
+
+    fn expr(self, value: &str) -> Expr {
+        let value = col(value).cast(DataType::Float64);
+        match self {
+            Self::Count => len().cast(DataType::Float64),
+            Self::Sum => value.sum(),
+            Self::Mean => value.mean(),
+            Self::Min => value.min(),
+            Self::Max => value.max(),
+        }
+    }
+}
+
+/// The columns of a crosstab: one down the side, one across the top, and optionally a column
+/// whose values the cells aggregate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrosstabSpec {
+    pub rows: String,
+    pub columns: String,
+    pub value: Option<String>,
+    /// Count when there is no value column.
+    pub agg: CrosstabAgg,
+}
+
+impl CrosstabSpec {
+    /// Parse the columns typed in the tool: `rows, columns` or `rows, columns, value`. A value
+    /// column given with `agg` Count sums it instead.
+    pub fn parse(text: &str, agg: CrosstabAgg) -> Result<Self> {
+        let names: Vec<&str> = text
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect();
+        let (rows, columns, value) = match names[..] {
+            [rows, columns] => (rows, columns, None),
+            [rows, columns, value] => (rows, columns, Some(value.to_string())),
+            _ => return Err(eyre!("Type two or three columns: rows, columns[, values]")),
+        };
+        let agg = match (&value, agg) {
+            (None, _) => CrosstabAgg::Count,
+            (Some(_), CrosstabAgg::Count) => CrosstabAgg::Sum,
+            (Some(_), agg) => agg,
+        };
+        Ok(Self {
+            rows: rows.to_string(),
+            columns: columns.to_string(),
+            value,
+            agg,
+        })
+    }
+
+    /// The spec as typed in the tool, e.g. "region, product, sales".
+    pub fn to_text(&self) -> String {
+        let mut names = vec![self.rows.as_str(), self.columns.as_str()];
+        names.extend(self.value.as_deref());
+        names.join(", ")
+    }
+
+    /// What the cells show, e.g. "count" or "sum of sales".
+    pub fn cells_label(&self) -> String {
+        match &self.value {
+            Some(value) if !self.counts_only() => format!("{} of {}", self.agg.label(), value),
+            _ => CrosstabAgg::Count.label().to_string(),
+        }
+    }
+
+    /// Whether the cells count rows (no value column, or the Count aggregation).
+    pub fn counts_only(&self) -> bool {
+        self.value.is_none() || self.agg == CrosstabAgg::Count
+    }
+
+    /// The next aggregation; Count and back when there is no value column.
+    pub fn cycle_agg(&mut self) {
+        if self.value.is_some() {
+            self.agg = self.agg.next();
+        }
+    }
+
+    fn cell_expr(&self) -> Expr {
+        match &self.value {
+            Some(value) => self.agg.expr(value),
+            None => CrosstabAgg::Count.expr(""),
+        }
+    }
+}
+
+/// Chi-square test of independence of the two columns, on the counts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChiSquare {
+    pub statistic: f64,
+    pub dof: usize,
+    pub p_value: f64,
+    /// Strength of the association, 0 (none) to 1.
+    pub cramers_v: f64,
+    /// Share of cells whose expected count is below 5, where the test is unreliable.
+    pub low_expected: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Crosstab {
+    pub spec: CrosstabSpec,
+    /// Values of the rows column, sorted ("null" last).
+    pub row_labels: Vec<String>,
+    /// Values of the columns column, sorted ("null" last).
+    pub column_labels: Vec<String>,
+    /// `cells[row][column]`; None where no rows have the pair (or all values are null).
+    pub cells: Vec<Vec<Option<f64>>>,
+    /// Rows with each pair, whatever the cells show.
+    pub counts: Vec<Vec<usize>>,
+    /// Each row's aggregate over all columns.
+    pub row_totals: Vec<Option<f64>>,
+    /// Each column's aggregate over all rows.
+    pub column_totals: Vec<Option<f64>>,
+    pub total: Option<f64>,
+    /// Rows in the view.
+    pub rows: usize,
+    /// None with fewer than two rows or two columns.
+    pub chi_square: Option<ChiSquare>,
+}
+
+impl Crosstab {
+    /// The table as a frame for export: the row values, one column per column value, then the
+    /// totals, with a last row of column totals.
+    pub fn to_frame(&self) -> PolarsResult<DataFrame> {
+        let total = "Total".to_string();
+        let mut labels = self.row_labels.clone();
+        labels.push(total.clone());
+        let mut columns = vec![Column::new(self.spec.rows.as_str().into(), labels)];
+        let column = |name: &str, values: Vec<Option<f64>>| {
+            let column = Column::new(name.into(), values);
+            if self.spec.counts_only() {
+                column.cast(&DataType::UInt64)
+            } else {
+                Ok(column)
+            }
+        };
+        for (j, name) in self.column_labels.iter().enumerate() {
+            let mut values: Vec<Option<f64>> = self.cells.iter().map(|row| row[j]).collect();
+            values.push(self.column_totals[j]);
+            columns.push(column(name, values)?);
+        }
+        let mut totals = self.row_totals.clone();
+        totals.push(self.total);
+        columns.push(column(&total, totals)?);
+        DataFrame::new(columns)
+    }
+}
+
+/// The rows column, columns column and value column a crosstab starts with: the first marked
+/// columns (`marked`), or else the first two text, categorical or boolean columns of `schema`.
+pub fn default_spec(schema: &Schema, marked: &[String]) -> Option<CrosstabSpec> {
+    let text = if marked.len() >= 2 {
+        marked
+            .iter()
+            .take(3)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ")
+    } else {
+        let categorical: Vec<&str> = schema
+            .iter()
+            .filter(|(_, dtype)| {
+                matches!(
+                    dtype,
+                    DataType::String | DataType::Boolean | DataType::Categorical(..)
+                )
+            })
+            .map(|(name, _)| name.as_str())
+            .take(2)
+            .collect();
+        categorical.join(", ")
+    };
+    CrosstabSpec::parse(&text, CrosstabAgg::Count).ok()
+}
+
+fn label(value: AnyValue) -> String {
+    match value {
+        AnyValue::Null => "null".to_string(),
+        v => v.str_value().into_owned(),
+    }
+}
+
+/// Labels of `name` in `df`, in the frame's order.
+fn labels(df: &DataFrame, name: &str) -> Result<Vec<String>> {
+    let column = df.column(name)?;
+    Ok((0..df.height())
+        .map(|i| column.get(i).map_or_else(|_| "null".to_string(), label))
+        .collect())
+}
+
+fn floats(df: &DataFrame, name: &str) -> Result<Vec<Option<f64>>> {
+    Ok(df
+        .column(name)?
+        .cast(&DataType::Float64)?
+        .f64()?
+        .into_iter()
+        .collect())
+}
+
+/// `expr` of `lf` grouped by `key`, as label to value.
+fn margin(
+    lf: &LazyFrame,
+    key: &str,
+    expr: Expr,
+    polars_streaming: bool,
+) -> Result<HashMap<String, Option<f64>>> {
+    let df = collect_lazy(
+        lf.clone()
+            .group_by([col(key)])
+            .agg([expr.alias(VALUE_COLUMN)]),
+        polars_streaming,
+    )?;
+    Ok(labels(&df, key)?
+        .into_iter()
+        .zip(floats(&df, VALUE_COLUMN)?)
+        .collect())
+}
+
+/// Chi-square test on `counts`, None unless it has at least two rows and two columns.
+pub fn chi_square(counts: &[Vec<usize>]) -> Option<ChiSquare> {
+    let rows = counts.len();
+    let columns = counts.first()?.len();
+    if rows < 2 || columns < 2 {
+        return None;
+    }
+    let row_sums: Vec<f64> = counts
+        .iter()
+        .map(|row| row.iter().sum::<usize>() as f64)
+        .collect();
+    let column_sums: Vec<f64> = (0..columns)
+        .map(|j| counts.iter().map(|row| row[j]).sum::<usize>() as f64)
+        .collect();
+    let n: f64 = row_sums.iter().sum();
+    if n == 0.0 {
+        return None;
+    }
+    let mut statistic = 0.0;
+    let mut low = 0usize;
+    for (i, row) in counts.iter().enumerate() {
+        for (j, &observed) in row.iter().enumerate() {
+            let expected = row_sums[i] * column_sums[j] / n;
+            if expected < 5.0 {
+                low += 1;
+            }
+            if expected > 0.0 {
+                statistic += (observed as f64 - expected).powi(2) / expected;
+            }
+        }
+    }
+    let dof = (rows - 1) * (columns - 1);
+    let p_value = (1.0 - chi_squared_cdf(statistic, dof as f64)).clamp(0.0, 1.0);
+    let cramers_v = (statistic / (n * (rows.min(columns) - 1) as f64)).sqrt();
+    Some(ChiSquare {
+        statistic,
+        dof,
+        p_value,
+        cramers_v,
+        low_expected: low as f64 / (rows * columns) as f64,
+    })
+}
+
+/// Build the crosstab of `lf` described by `spec`.
+pub fn compute(lf: &LazyFrame, spec: &CrosstabSpec, polars_streaming: bool) -> Result<Crosstab> {
+    let schema = lf.clone().collect_schema()?;
+    for name in [&spec.rows, &spec.columns].into_iter().chain(&spec.value) {
+        if !schema.contains(name) {
+            return Err(eyre!("Column '{}' is not in the current view", name));
+        }
+    }
+    for name in [&spec.rows, &spec.columns] {
+        if !schema.get(name).is_some_and(is_countable) {
+            return Err(eyre!("Column '{}' can't be cross-tabulated", name));
+        }
+    }
+    if let Some(value) = &spec.value {
+        if !schema
+            .get(value)
+            .is_some_and(|dtype| dtype.is_primitive_numeric())
+        {
+            return Err(eyre!("Column '{}' isn't numeric", value));
+        }
+    }
+
+    let distinct = collect_lazy(
+        lf.clone().select([
+            len().alias(COUNT_COLUMN),
+            col(spec.rows.as_str()).n_unique().alias("rows"),
+            col(spec.columns.as_str()).n_unique().alias("columns"),
+        ]),
+        polars_streaming,
+    )?;
+    let get = |name: &str| -> Result<usize> {
+        Ok(distinct
+            .column(name)?
+            .get(0)?
+            .extract::<usize>()
+            .unwrap_or(0))
+    };
+    let rows = get(COUNT_COLUMN)?;
+    for (name, key) in [(&spec.rows, "rows"), (&spec.columns, "columns")] {
+        let n = get(key)?;
+        if n > MAX_LABELS {
+            return Err(eyre!(
+                "'{}' has {} distinct values; a crosstab shows up to {}",
+                name,
+                n,
+                MAX_LABELS
+            ));
+        }
+    }
+
+    let cell_expr = spec.cell_expr();
+    let pairs = collect_lazy(
+        lf.clone()
+            .group_by([col(spec.rows.as_str()), col(spec.columns.as_str())])
+            .agg([
+                len().alias(COUNT_COLUMN),
+                cell_expr.clone().alias(VALUE_COLUMN),
+            ]),
+        polars_streaming,
+    )?;
+    let sorted_labels = |name: &str| -> Result<Vec<String>> {
+        let unique = pairs
+            .column(name)?
+            .as_materialized_series()
+            .unique()?
+            .sort(SortOptions::default().with_nulls_last(true))?;
+        Ok((0..unique.len())
+            .map(|i| unique.get(i).map_or_else(|_| "null".to_string(), label))
+            .collect())
+    };
+    let row_labels = sorted_labels(&spec.rows)?;
+    let column_labels = sorted_labels(&spec.columns)?;
+    let row_index: HashMap<&str, usize> = row_labels
+        .iter()
+        .enumerate()
+        .map(|(i, l)| (l.as_str(), i))
+        .collect();
+    let column_index: HashMap<&str, usize> = column_labels
+        .iter()
+        .enumerate()
+        .map(|(j, l)| (l.as_str(), j))
+        .collect();
+
+    let mut cells = vec![vec![None; column_labels.len()]; row_labels.len()];
+    let mut counts = vec![vec![0usize; column_labels.len()]; row_labels.len()];
+    let pair_rows = labels(&pairs, &spec.rows)?;
+    let pair_columns = labels(&pairs, &spec.columns)?;
+    let pair_counts = floats(&pairs, COUNT_COLUMN)?;
+    let pair_values = floats(&pairs, VALUE_COLUMN)?;
+    for k in 0..pairs.height() {
+        let (i, j) = (
+            row_index[pair_rows[k].as_str()],
+            column_index[pair_columns[k].as_str()],
+        );
+        counts[i][j] = pair_counts[k].unwrap_or(0.0) as usize;
+        cells[i][j] = pair_values[k];
+    }
+
+    let (row_totals, column_totals, total) = if spec.counts_only() {
+        let count = |n: usize| Some(n as f64);
+        (
+            counts.iter().map(|row| count(row.iter().sum())).collect(),
+            (0..column_labels.len())
+                .map(|j| count(counts.iter().map(|row| row[j]).sum()))
+                .collect(),
+            count(rows),
+        )
+    } else {
+        let by_row = margin(lf, &spec.rows, cell_expr.clone(), polars_streaming)?;
+        let by_column = margin(lf, &spec.columns, cell_expr.clone(), polars_streaming)?;
+        let total = collect_lazy(
+            lf.clone().select([cell_expr.alias(VALUE_COLUMN)]),
+            polars_streaming,
+        )?;
+        (
+            row_labels.iter().map(|l| by_row[l]).collect(),
+            column_labels.iter().map(|l| by_column[l]).collect(),
+            floats(&total, VALUE_COLUMN)?.first().copied().flatten(),
+        )
+    };
+
+    Ok(Crosstab {
+        spec: spec.clone(),
+        chi_square: chi_square(&counts),
+        row_labels,
+        column_labels,
+        cells,
+        counts,
+        row_totals,
+        column_totals,
+        total,
+        rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> LazyFrame {
+        df!(
+            "region" => [Some("eu"), Some("us"), Some("eu"), Some("us"), Some("eu"), None],
+            "product" => ["a", "a", "b", "b", "a", "a"],
+            "sales" => [10.0, 20.0, 5.0, 7.0, 30.0, 1.0],
+        )
+        .unwrap()
+        .lazy()
+    }
+
+    #[test]
+    fn counts_pairs_with_totals() {
+        let spec = CrosstabSpec::parse("region, product", CrosstabAgg::Sum).unwrap();
+        assert_eq!(spec.agg, CrosstabAgg::Count, "no value column: counts");
+        let table = compute(&frame(), &spec, false).unwrap();
+        assert_eq!(table.row_labels, ["eu", "us", "null"]);
+        assert_eq!(table.column_labels, ["a", "b"]);
+        assert_eq!(table.counts, [[2, 1], [1, 1], [1, 0]]);
+        assert_eq!(table.cells[2], [Some(1.0), None]);
+        assert_eq!(table.row_totals, [Some(3.0), Some(2.0), Some(1.0)]);
+        assert_eq!(table.column_totals, [Some(4.0), Some(2.0)]);
+        assert_eq!(table.total, Some(6.0));
+        assert_eq!(table.chi_square.as_ref().unwrap().dof, 2);
+
+        let df = table.to_frame().unwrap();
+        assert_eq!(df.get_column_names(), ["region", "a", "b", "Total"]);
+        assert_eq!(df.column("Total").unwrap().u64().unwrap().get(3), Some(6));
+    }
+
+    #[test]
+    fn aggregates_a_value_column() {
+        let mut spec = CrosstabSpec::parse("region, product, sales", CrosstabAgg::Count).unwrap();
+        assert_eq!(spec.agg, CrosstabAgg::Sum);
+        assert_eq!(spec.cells_label(), "sum of sales");
+        let table = compute(&frame(), &spec, false).unwrap();
+        assert_eq!(table.cells[0], [Some(40.0), Some(5.0)]);
+        assert_eq!(table.row_totals[0], Some(45.0));
+        assert_eq!(table.column_totals[1], Some(12.0));
+        assert_eq!(table.total, Some(73.0));
+
+        spec.cycle_agg();
+        assert_eq!(spec.agg, CrosstabAgg::Mean);
+        let table = compute(&frame(), &spec, false).unwrap();
+        assert_eq!(table.cells[0][0], Some(20.0));
+        assert_eq!(table.row_totals[0], Some(15.0));
+
+        let spec = CrosstabSpec::parse("region, product, nope", CrosstabAgg::Sum).unwrap();
+        assert!(compute(&frame(), &spec, false)
+            .unwrap_err()
+            .to_string()
+            .contains("nope"));
+        assert!(CrosstabSpec::parse("region", CrosstabAgg::Count).is_err());
+    }
+
+    #[test]
+    fn chi_square_of_independent_and_dependent_tables() {
+        let independent = chi_square(&[vec![10, 20], vec![20, 40]]).unwrap();
+        assert!(independent.statistic.abs() < 1e-9);
+        assert!((independent.p_value - 1.0).abs() < 1e-6);
+        assert_eq!(independent.cramers_v, 0.0);
+
+        let dependent = chi_square(&[vec![50, 0], vec![0, 50]]).unwrap();
+        assert!((dependent.statistic - 100.0).abs() < 1e-9);
+        assert!(dependent.p_value < 0.001);
+        assert!((dependent.cramers_v - 1.0).abs() < 1e-9);
+        assert_eq!(dependent.low_expected, 0.0);
+
+        assert!(chi_square(&[vec![1, 2]]).is_none());
+    }
+}
@@ -0,0 +1,548 @@
+//! CSV dialect: delimiter, quote character, escape character, comment prefix and header row of
+//! a delimited text file. When a CSV loads as a single column or fails to parse, the dialect
+//! modal opens with a dialect sniffed from the file's first lines; `D` opens it at any time.
+//! It shows the raw lines and re-parses them with every change, and Enter reloads the file.
+
+use crate::widgets::datatable::DataTableState;
+use crate::{CompressionFormat, FileFormat, OpenOptions};
+use color_eyre::Result;
+use polars::prelude::*;
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Bytes of the file read for sniffing and the live preview.
+const SAMPLE_BYTES: usize = 64 * 1024;
+/// Raw lines listed in the modal.
+const RAW_LINES: usize = 20;
+/// Parsed rows shown under the raw lines.
+const PREVIEW_ROWS: usize = 10;
+
+const DELIMITERS: [u8; 6] = [b',', b';', b'\t', b'|', b':', b' '];
+/// Delimiters sniffing tries: not space or colon, common inside values ("New York", "12:30").
+const SNIFFED_DELIMITERS: [u8; 4] = [b',', b';', b'\t', b'|'];
+const QUOTES: [Option<u8>; 3] = [Some(b'"'), Some(b'\''), None];
+const ESCAPES: [Option<u8>; 3] = [None, Some(b'\\'), Some(b'"')];
+const COMMENTS: [Option<&str>; 4] = [None, Some("#"), Some("//"), Some("--")];
+
+/// How a delimited text file is written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvDialect {
+    pub delimiter: u8,
+    /// None reads quotes as ordinary characters.
+    pub quote_char: Option<u8>,
+    /// Character before a quote inside a quoted field (e.g. `\"`); None for doubled quotes only.
+    pub escape_char: Option<u8>,
+    pub comment_prefix: Option<String>,
+    /// Line of the file (from 1) holding the column names; 0 when there is none.
+    pub header_row: usize,
+}
+
+impl CsvDialect {
+    /// The dialect `options` read `path` with.
+    pub fn from_options(path: &Path, options: &OpenOptions) -> Self {
+        let delimiter = options
+            .delimiter
+            .unwrap_or(match delimited_format(path, options) {
+                Some(FileFormat::Tsv) => b'\t',
+                Some(FileFormat::Psv) => b'|',
+                _ => b',',
+            });
+        let header_row = if options.has_header == Some(false) {
+            0
+        } else {
+            options.skip_lines.unwrap_or(0) + options.skip_rows.unwrap_or(0) + 1
+        };
+        Self {
+            delimiter,
+            quote_char: options.quote_char,
+            escape_char: options.escape_char,
+            comment_prefix: options.comment_prefix.clone(),
+            header_row,
+        }
+    }
+
+    /// Set `options` to read with this dialect.
+    pub fn apply(&self, options: &mut OpenOptions) {
+        options.delimiter = Some(self.delimiter);
+        options.quote_char = self.quote_char;
+        options.escape_char = self.escape_char;
+        options.comment_prefix = self.comment_prefix.clone();
+        options.has_header = Some(self.header_row > 0);
+        options.skip_lines = Some(self.header_row.saturating_sub(1));
+        options.skip_rows = None;
+    }
+
+    /// Guess the dialect of the first `lines` of a file: the delimiter splitting the most lines
+    /// into the same number of fields, and the first line with that many fields as the header.
+    pub fn sniff(lines: &[String]) -> Self {
+        let comment_prefix = COMMENTS.iter().flatten().find(|prefix| {
+            let commented = lines.iter().filter(|l| l.starts_with(**prefix)).count();
+            commented > 0 && commented < lines.len()
+        });
+        let data: Vec<(usize, &String)> = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| {
+                !l.trim().is_empty() && !comment_prefix.is_some_and(|p| l.starts_with(*p))
+            })
+            .collect();
+        let quote_char = Some(b'"');
+        let escape_char = data
+            .iter()
+            .any(|(_, l)| l.contains("\\\""))
+            .then_some(b'\\');
+        let mut best: Option<(usize, usize, u8)> = None;
+        for delimiter in SNIFFED_DELIMITERS {
+            let counts: Vec<usize> = data
+                .iter()
+                .map(|(_, l)| field_count(l, delimiter, quote_char))
+                .collect();
+            let Some(fields) = most_common(&counts).filter(|&n| n > 1) else {
+                continue;
+            };
+            let agreeing = counts.iter().filter(|&&n| n == fields).count();
+            if best.is_none_or(|(a, f, _)| (agreeing, fields) > (a, f)) {
+                best = Some((agreeing, fields, delimiter));
+            }
+        }
+        let (delimiter, header_row) = match best {
+            Some((_, fields, delimiter)) => {
+                let first = data
+                    .iter()
+                    .find(|(_, l)| field_count(l, delimiter, quote_char) == fields)
+                    .map_or(0, |(i, _)| *i);
+                (delimiter, first + 1)
+            }
+            None => (b',', data.first().map_or(1, |(i, _)| i + 1)),
+        };
+        Self {
+            delimiter,
+            quote_char,
+            escape_char,
+            comment_prefix: comment_prefix.map(|p| p.to_string()),
+            header_row,
+        }
+    }
+
+    /// Read `rows` rows (all when None) of `sample` with the dialect, inferring column types
+    /// from `infer_schema_length` rows (0 reads every column as a string).
+    pub fn read(
+        &self,
+        sample: &[u8],
+        rows: Option<usize>,
+        infer_schema_length: Option<usize>,
+        try_parse_dates: bool,
+    ) -> std::result::Result<DataFrame, String> {
+        let bytes = match (self.escape_char, self.quote_char) {
+            (Some(escape), Some(quote)) if escape != quote => {
+                let mut out = Vec::with_capacity(sample.len());
+                unescape(sample, &mut out, quote, escape).map_err(|e| e.to_string())?;
+                out
+            }
+            _ => sample.to_vec(),
+        };
+        let mut read_options = CsvReadOptions::default()
+            .with_has_header(self.header_row > 0)
+            .with_skip_lines(self.header_row.saturating_sub(1))
+            .with_n_rows(rows);
+        if infer_schema_length.is_some() {
+            read_options = read_options.with_infer_schema_length(infer_schema_length);
+        }
+        read_options
+            .map_parse_options(|parse| {
+                parse
+                    .with_separator(self.delimiter)
+                    .with_quote_char(self.quote_char)
+                    .with_comment_prefix(self.comment_prefix.as_deref())
+                    .with_try_parse_dates(try_parse_dates)
+            })
+            .into_reader_with_file_handle(std::io::Cursor::new(bytes))
+            .finish()
+            .map_err(|e| crate::error_display::user_message_from_polars(&e))
+    }
+}
+
+/// Fields of `line` split at `delimiter`, not counting delimiters inside quotes.
+fn field_count(line: &str, delimiter: u8, quote: Option<u8>) -> usize {
+    let mut in_quotes = false;
+    let mut fields = 1;
+    for b in line.bytes() {
+        if Some(b) == quote {
+            in_quotes = !in_quotes;
+        } else if b == delimiter && !in_quotes {
+            fields += 1;
+        }
+    }
+    fields
+}
+
+fn most_common(counts: &[usize]) -> Option<usize> {
+    let mut sorted = counts.to_vec();
+    sorted.sort_unstable();
+    sorted
+        .chunk_by(|a, b| a == b)
+        .max_by_key(|run| (run.len(), run[0]))
+        .map(|run| run[0])
+}
+
+/// CSV, TSV or PSV: the format `path` loads as with `options`, when it is one of those.
+pub fn delimited_format(path: &Path, options: &OpenOptions) -> Option<FileFormat> {
+    options
+        .format
+        .or_else(|| FileFormat::from_path(path))
+        .or_else(|| {
+            CompressionFormat::from_extension(path)?;
+            FileFormat::from_path(Path::new(path.file_stem()?))
+        })
+        .filter(|f| matches!(f, FileFormat::Csv | FileFormat::Tsv | FileFormat::Psv))
+}
+
+/// The file behind opening `paths` with `options` when it has a dialect to adjust: a single
+/// local CSV, TSV or PSV file.
+pub fn target(paths: &[PathBuf], options: &OpenOptions) -> Option<PathBuf> {
+    let [path] = paths else {
+        return None;
+    };
+    let local = matches!(
+        crate::source::input_source(path),
+        crate::source::InputSource::Local(_)
+    );
+    (local && path.is_file() && delimited_format(path, options).is_some()).then(|| path.clone())
+}
+
+/// Copy CSV from `reader` to `writer`, rewriting `escape` before a quote inside a quoted field
+/// as a doubled quote and a doubled `escape` as one. Other escapes are kept as they are.
+pub fn unescape(reader: impl Read, mut writer: impl Write, quote: u8, escape: u8) -> Result<()> {
+    let mut bytes = BufReader::new(reader).bytes();
+    let mut in_quotes = false;
+    while let Some(b) = bytes.next() {
+        let b = b?;
+        if in_quotes && b == escape {
+            match bytes.next().transpose()? {
+                Some(next) if next == quote => writer.write_all(&[quote, quote])?,
+                Some(next) if next == escape => writer.write_all(&[escape])?,
+                Some(next) => writer.write_all(&[escape, next])?,
+                None => writer.write_all(&[escape])?,
+            }
+            continue;
+        }
+        if b == quote {
+            in_quotes = !in_quotes;
+        }
+        writer.write_all(&[b])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// The start of the file at `path` (decompressed), up to [`SAMPLE_BYTES`] in whole lines.
+pub fn read_sample(path: &Path, options: &OpenOptions) -> Result<Vec<u8>> {
+    let compression = options
+        .compression
+        .or_else(|| CompressionFormat::from_extension(path));
+    let mut sample = Vec::new();
+    DataTableState::open_decompressed(path, compression)?
+        .take(SAMPLE_BYTES as u64 + 1)
+        .read_to_end(&mut sample)?;
+    // Keep whole lines only, so a cut-off last line doesn't show up as a parse error
+    if sample.len() > SAMPLE_BYTES {
+        let end = sample
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        sample.truncate(end);
+    }
+    Ok(sample)
+}
+
+/// Parse the first rows of `sample` with `dialect`.
+fn parse_sample(sample: &[u8], dialect: &CsvDialect) -> std::result::Result<DataFrame, String> {
+    dialect.read(sample, Some(PREVIEW_ROWS), None, false)
+}
+
+/// Label of a delimiter, quote or escape character: "Tab", "Space" or the character itself.
+pub fn char_label(c: Option<u8>) -> String {
+    match c {
+        None => "none".to_string(),
+        Some(b'\t') => "Tab".to_string(),
+        Some(b' ') => "Space".to_string(),
+        Some(c) => (c as char).to_string(),
+    }
+}
+
+/// A setting of the dialect modal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DialectField {
+    Delimiter,
+    Quote,
+    Escape,
+    Comment,
+    HeaderRow,
+}
+
+impl DialectField {
+    pub const ALL: [Self; 5] = [
+        Self::Delimiter,
+        Self::Quote,
+        Self::Escape,
+        Self::Comment,
+        Self::HeaderRow,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Delimiter => "Delimiter",
+            Self::Quote => "Quote",
+            Self::Escape => "Escape",
+            Self::Comment => "Comment",
+            Self::HeaderRow => "Header row",
+        }
+    }
+}
+
+/// The dialect modal: the file's first lines, the dialect being edited and the rows it parses.
+pub struct CsvDialectModal {
+    pub path: PathBuf,
+    /// Options the file was opened with; Enter reloads it with these and the dialect.
+    pub options: OpenOptions,
+    /// Why the modal opened on its own (one column or a parse error).
+    pub reason: Option<String>,
+    /// First lines of the file as they are written.
+    pub lines: Vec<String>,
+    sample: Vec<u8>,
+    pub dialect: CsvDialect,
+    pub field: DialectField,
+    /// The first rows of the sample parsed with `dialect`, or why they don't parse.
+    pub preview: std::result::Result<DataFrame, String>,
+}
+
+impl CsvDialectModal {
+    /// Open on `path` with the dialect `options` read it with.
+    pub fn open(path: PathBuf, options: OpenOptions) -> Result<Self> {
+        let dialect = CsvDialect::from_options(&path, &options);
+        Self::with_dialect(path, options, None, |_| dialect)
+    }
+
+    /// Open on `path` with a dialect sniffed from its first lines, saying why.
+    pub fn sniffed(path: PathBuf, options: OpenOptions, reason: String) -> Result<Self> {
+        Self::with_dialect(path, options, Some(reason), CsvDialect::sniff)
+    }
+
+    fn with_dialect(
+        path: PathBuf,
+        options: OpenOptions,
+        reason: Option<String>,
+        dialect: impl FnOnce(&[String]) -> CsvDialect,
+    ) -> Result<Self> {
+        let sample = read_sample(&path, &options)?;
+        let lines: Vec<String> = String::from_utf8_lossy(&sample)
+            .lines()
+            .take(RAW_LINES)
+            .map(|l| l.to_string())
+            .collect();
+        let dialect = dialect(&lines);
+        let preview = parse_sample(&sample, &dialect);
+        Ok(Self {
+            path,
+            options,
+            reason,
+            lines,
+            sample,
+            dialect,
+            field: DialectField::Delimiter,
+            preview,
+        })
+    }
+
+    /// Columns the dialect finds, 0 when the sample doesn't parse.
+    pub fn preview_columns(&self) -> usize {
+        self.preview.as_ref().map_or(0, |df| df.width())
+    }
+
+    pub fn move_field(&mut self, delta: isize) {
+        let fields = DialectField::ALL;
+        let index = fields.iter().position(|f| *f == self.field).unwrap_or(0);
+        self.field = fields[index.saturating_add_signed(delta).min(fields.len() - 1)];
+    }
+
+    /// Step the focused setting through its usual values (the header row by one line).
+    pub fn cycle(&mut self, delta: isize) {
+        fn step<T: PartialEq + Copy>(values: &[T], current: T, delta: isize) -> T {
+            let index = values.iter().position(|v| *v == current);
+            let next = match index {
+                Some(i) => (i as isize + delta).rem_euclid(values.len() as isize) as usize,
+                None => 0,
+            };
+            values[next]
+        }
+        let d = &mut self.dialect;
+        match self.field {
+            DialectField::Delimiter => d.delimiter = step(&DELIMITERS, d.delimiter, delta),
+            DialectField::Quote => d.quote_char = step(&QUOTES, d.quote_char, delta),
+            DialectField::Escape => d.escape_char = step(&ESCAPES, d.escape_char, delta),
+            DialectField::Comment => {
+                d.comment_prefix =
+                    step(&COMMENTS, d.comment_prefix.as_deref(), delta).map(str::to_string)
+            }
+            DialectField::HeaderRow => {
+                d.header_row = d
+                    .header_row
+                    .saturating_add_signed(delta)
+                    .min(self.lines.len());
+            }
+        }
+        self.reparse();
+    }
+
+    /// A typed character: the delimiter, quote or escape itself, the next character of the
+    /// comment prefix, or a digit of the header row.
+    pub fn type_char(&mut self, c: char) {
+        let Ok(byte) = u8::try_from(c) else {
+            return;
+        };
+        let d = &mut self.dialect;
+        match self.field {
+            DialectField::Delimiter => d.delimiter = byte,
+            DialectField::Quote => d.quote_char = Some(byte),
+            DialectField::Escape => d.escape_char = Some(byte),
+            DialectField::Comment => d.comment_prefix.get_or_insert_default().push(c),
+            DialectField::HeaderRow => {
+                let Some(digit) = c.to_digit(10) else {
+                    return;
+                };
+                d.header_row = (d.header_row * 10 + digit as usize).min(self.lines.len());
+            }
+        }
+        self.reparse();
+    }
+
+    /// Backspace: no quote or escape, one character less of the comment prefix, one digit less
+    /// of the header row.
+    pub fn backspace(&mut self) {
+        let d = &mut self.dialect;
+        match self.field {
+            DialectField::Delimiter => return,
+            DialectField::Quote => d.quote_char = None,
+            DialectField::Escape => d.escape_char = None,
+            DialectField::Comment => {
+                if let Some(prefix) = d.comment_prefix.as_mut() {
+                    prefix.pop();
+                    if prefix.is_empty() {
+                        d.comment_prefix = None;
+                    }
+                }
+            }
+            DialectField::HeaderRow => d.header_row /= 10,
+        }
+        self.reparse();
+    }
+
+    fn reparse(&mut self) {
+        self.preview = parse_sample(&self.sample, &self.dialect);
+    }
+
+    /// Value of `field` as shown in the modal.
+    pub fn value(&self, field: DialectField) -> String {
+        let d = &self.dialect;
+        match field {
+            DialectField::Delimiter => char_label(Some(d.delimiter)),
+            DialectField::Quote => char_label(d.quote_char),
+            DialectField::Escape => char_label(d.escape_char),
+            DialectField::Comment => d.comment_prefix.clone().unwrap_or_else(|| "none".into()),
+            DialectField::HeaderRow if d.header_row == 0 => "none".to_string(),
+            DialectField::HeaderRow => d.header_row.to_string(),
+        }
+    }
+
+    /// Paths and options that reload the file with the dialect. TSV and PSV files load as CSV,
+    /// so the delimiter applies.
+    pub fn open_selected(&self) -> (Vec<PathBuf>, OpenOptions) {
+        let mut options = self.options.clone();
+        self.dialect.apply(&mut options);
+        if delimited_format(&self.path, &options) != Some(FileFormat::Csv) {
+            options.format = Some(FileFormat::Csv);
+        }
+        (vec![self.path.clone()], options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn sniffs_delimiter_header_row_comments_and_escapes() {
+        let dialect = CsvDialect::sniff(&lines(
+            "Exported 2024-01-02\n\nid;name;note\n1;\"a;b\";x\n2;c;\"say \\\"hi\\\"\"\n",
+        ));
+        assert_eq!(dialect.delimiter, b';');
+        assert_eq!(dialect.header_row, 3);
+        assert_eq!(dialect.escape_char, Some(b'\\'));
+        assert_eq!(dialect.comment_prefix, None);
+
+        let dialect = CsvDialect::sniff(&lines("# generated\nid\tname\n1\tann\n2\tbob\n"));
+        assert_eq!(dialect.delimiter, b'\t');
+        assert_eq!(dialect.comment_prefix.as_deref(), Some("#"));
+        assert_eq!(dialect.header_row, 2);
+
+        let dialect = CsvDialect::sniff(&lines("name\nann\nbob\n"));
+        assert_eq!((dialect.delimiter, dialect.header_row), (b',', 1));
+    }
+
+    #[test]
+    fn unescapes_quotes_inside_quoted_fields() {
+        let mut out = Vec::new();
+        unescape(
+            &b"a,\"x \\\"y\\\" \\\\ \\n\"\nb\\\"c\n"[..],
+            &mut out,
+            b'"',
+            b'\\',
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "a,\"x \"\"y\"\" \\ \\n\"\nb\\\"c\n"
+        );
+    }
+
+    #[test]
+    fn modal_reparses_as_settings_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.csv");
+        std::fs::write(&path, "Report\nid;name\n1;\"a\\\"b\"\n2;c\n").unwrap();
+
+        let mut modal = CsvDialectModal::open(path.clone(), OpenOptions::default()).unwrap();
+        assert_eq!(modal.lines.len(), 4);
+        assert!(modal.preview_columns() <= 1);
+
+        modal.type_char(';');
+        modal.field = DialectField::HeaderRow;
+        modal.cycle(1);
+        assert_eq!(modal.value(DialectField::HeaderRow), "2");
+        modal.field = DialectField::Escape;
+        modal.cycle(1);
+        assert_eq!(modal.value(DialectField::Escape), "\\");
+        let df = modal.preview.as_ref().unwrap();
+        assert_eq!(df.get_column_names(), vec!["id", "name"]);
+        assert_eq!(
+            df.column("name").unwrap().str().unwrap().get(0),
+            Some("a\"b")
+        );
+
+        let sniffed =
+            CsvDialectModal::sniffed(path.clone(), OpenOptions::default(), "1 column".into())
+                .unwrap();
+        assert_eq!(sniffed.dialect, modal.dialect);
+
+        let (paths, options) = modal.open_selected();
+        assert_eq!(paths, vec![path]);
+        assert_eq!(options.delimiter, Some(b';'));
+        assert_eq!(options.skip_lines, Some(1));
+        assert_eq!(options.escape_char, Some(b'\\'));
+        assert_eq!(options.format, None);
+    }
+}
@@ -0,0 +1,558 @@
+//! Data comparison of two files (`datui --diff OLD NEW`): rows are aligned by key columns, or by
+//! position when no keys are given, and each row is added (only in NEW), removed (only in OLD),
+//! changed (some cell differs) or unchanged. Columns are compared when both files have them;
+//! columns present in one file only, or stored with different types, are reported from the
+//! schema comparison.
+//!
+//! Counts cover every row; the rows themselves are kept for the first [`MAX_DIFF_ROWS`]
+//! differences in key order.
+
+use crate::schema_diff::{self, SchemaDiff};
+use crate::statistics::collect_lazy;
+use crate::OpenOptions;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use polars::prelude::*;
+use ratatui::widgets::TableState;
+use std::path::PathBuf;
+
+/// Differing rows kept for display.
+pub const MAX_DIFF_ROWS: usize = 10_000;
+
+/// Key column added when rows are aligned by position.
+const ROW_KEY: &str = "__datui_diff_row";
+const IN_OLD: &str = "__datui_diff_in_old";
+const IN_NEW: &str = "__datui_diff_in_new";
+const STATUS: &str = "__datui_diff_status";
+
+/// Files to compare and the columns that identify a row in both.
+#[derive(Debug, Clone)]
+pub struct DiffRequest {
+    pub old: PathBuf,
+    pub new: PathBuf,
+    /// Key columns; empty aligns rows by position.
+    pub keys: Vec<String>,
+}
+
+/// How a row differs between the files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowChange {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl RowChange {
+    pub const ALL: [RowChange; 3] = [Self::Added, Self::Removed, Self::Changed];
+
+    /// Marker shown before the row.
+    pub fn marker(self) -> &'static str {
+        match self {
+            Self::Added => "+",
+            Self::Removed => "-",
+            Self::Changed => "~",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Changed => "changed",
+        }
+    }
+}
+
+/// A row that differs between the files.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffRow {
+    pub change: RowChange,
+    /// Key values (the 1-based row number when aligned by position).
+    pub key: Vec<String>,
+    /// Value of each compared column: the new value for added and unchanged cells, the old one
+    /// for removed rows, and `old → new` for changed cells.
+    pub values: Vec<String>,
+    /// Per compared column: whether the cell changed.
+    pub changed: Vec<bool>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DataDiff {
+    pub old: PathBuf,
+    pub new: PathBuf,
+    /// Key column names; `["row"]` when aligned by position.
+    pub keys: Vec<String>,
+    /// Non-key columns in both files, in OLD's order.
+    pub columns: Vec<String>,
+    /// Schema comparison of the two files.
+    pub schema: SchemaDiff,
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+    pub unchanged: usize,
+    /// The first [`MAX_DIFF_ROWS`] differing rows, by key.
+    pub rows: Vec<DiffRow>,
+}
+
+impl DataDiff {
+    /// Open both files of `request` (as the table loads them) and compare them.
+    pub fn compute_files(request: &DiffRequest, options: &OpenOptions) -> Result<Self> {
+        let old = schema_diff::open_file(&request.old, options)?;
+        let new = schema_diff::open_file(&request.new, options)?;
+        Self::compute(
+            old.lf,
+            new.lf,
+            request,
+            MAX_DIFF_ROWS,
+            options.polars_streaming,
+        )
+    }
+
+    /// Compare `old` and `new` (the data of `request`'s files), keeping at most `max_rows`
+    /// differing rows.
+    pub fn compute(
+        old: LazyFrame,
+        new: LazyFrame,
+        request: &DiffRequest,
+        max_rows: usize,
+        polars_streaming: bool,
+    ) -> Result<Self> {
+        let old_schema = old.clone().collect_schema()?;
+        let new_schema = new.clone().collect_schema()?;
+        let schema = SchemaDiff::from_schemas(
+            vec![request.old.clone(), request.new.clone()],
+            vec![
+                Ok(old_schema.as_ref().clone()),
+                Ok(new_schema.as_ref().clone()),
+            ],
+        );
+        let file_name = |i: usize| schema.file_name(i);
+
+        let by_position = request.keys.is_empty();
+        let (old, new, keys) = if by_position {
+            (
+                old.with_row_index(ROW_KEY, Some(1)),
+                new.with_row_index(ROW_KEY, Some(1)),
+                vec![ROW_KEY.to_string()],
+            )
+        } else {
+            for key in &request.keys {
+                for (i, schema) in [&old_schema, &new_schema].into_iter().enumerate() {
+                    if schema.get(key).is_none() {
+                        return Err(eyre!("Key column '{}' is not in {}", key, file_name(i)));
+                    }
+                }
+            }
+            for (i, lf) in [&old, &new].into_iter().enumerate() {
+                let repeated = repeated_keys(lf, &request.keys, polars_streaming)?;
+                if repeated > 0 {
+                    return Err(eyre!(
+                        "Key columns ({}) don't identify rows in {}: {} keys repeat",
+                        request.keys.join(", "),
+                        file_name(i),
+                        repeated
+                    ));
+                }
+            }
+            (old, new, request.keys.clone())
+        };
+
+        let columns: Vec<String> = old_schema
+            .iter_names()
+            .filter(|name| new_schema.get(name).is_some() && !keys.iter().any(|k| k == *name))
+            .map(|name| name.to_string())
+            .collect();
+        // Keys and columns stored with different types are compared as floats when both are
+        // numbers, otherwise as text
+        let common = |name: &str| match (old_schema.get(name), new_schema.get(name)) {
+            (Some(a), Some(b)) if a != b => {
+                Some(if a.is_primitive_numeric() && b.is_primitive_numeric() {
+                    DataType::Float64
+                } else {
+                    DataType::String
+                })
+            }
+            _ => None,
+        };
+        let value = |name: &str| match common(name) {
+            Some(dtype) => col(name).cast(dtype),
+            None => col(name),
+        };
+        let side = |lf: LazyFrame, suffix: &str, marker: &str| {
+            let mut exprs: Vec<Expr> = keys.iter().map(|k| value(k)).collect();
+            exprs.extend(
+                columns
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| value(c).alias(format!("{i}{suffix}"))),
+            );
+            exprs.push(lit(true).alias(marker));
+            lf.select(exprs)
+        };
+        let key_exprs: Vec<Expr> = keys.iter().map(|k| col(k.as_str())).collect();
+        let joined = side(old, "_old", IN_OLD).join(
+            side(new, "_new", IN_NEW),
+            key_exprs.clone(),
+            key_exprs,
+            JoinArgs::new(JoinType::Full).with_coalesce(JoinCoalesce::CoalesceColumns),
+        );
+        let changed: Vec<Expr> = (0..columns.len())
+            .map(|i| {
+                col(format!("{i}_old"))
+                    .neq_missing(col(format!("{i}_new")))
+                    .alias(format!("{i}_changed"))
+            })
+            .collect();
+        let any_changed = if changed.is_empty() {
+            lit(false)
+        } else {
+            any_horizontal(
+                (0..columns.len())
+                    .map(|i| col(format!("{i}_changed")))
+                    .collect::<Vec<_>>(),
+            )?
+        };
+        let joined = joined.with_columns(changed).with_column(
+            when(col(IN_OLD).is_null())
+                .then(lit(RowChange::Added.label()))
+                .when(col(IN_NEW).is_null())
+                .then(lit(RowChange::Removed.label()))
+                .when(any_changed)
+                .then(lit(RowChange::Changed.label()))
+                .otherwise(lit("unchanged"))
+                .alias(STATUS),
+        );
+
+        let counts = collect_lazy(
+            joined
+                .clone()
+                .group_by([col(STATUS)])
+                .agg([len().alias("count")]),
+            polars_streaming,
+        )?;
+        let count_of = |label: &str| -> Result<usize> {
+            let statuses = counts.column(STATUS)?.str()?;
+            let totals = counts.column("count")?;
+            Ok(statuses
+                .iter()
+                .position(|s| s == Some(label))
+                .map(|row| totals.get(row))
+                .transpose()?
+                .and_then(|n| n.extract::<usize>())
+                .unwrap_or(0))
+        };
+
+        let differing = collect_lazy(
+            joined
+                .filter(col(STATUS).neq(lit("unchanged")))
+                .sort(keys.clone(), SortMultipleOptions::default())
+                .limit(max_rows as IdxSize),
+            polars_streaming,
+        )?;
+        let rows = diff_rows(&differing, &keys, columns.len())?;
+
+        Ok(Self {
+            old: request.old.clone(),
+            new: request.new.clone(),
+            keys: if by_position {
+                vec!["row".to_string()]
+            } else {
+                keys
+            },
+            columns,
+            schema,
+            added: count_of(RowChange::Added.label())?,
+            removed: count_of(RowChange::Removed.label())?,
+            changed: count_of(RowChange::Changed.label())?,
+            unchanged: count_of("unchanged")?,
+            rows,
+        })
+    }
+
+    /// Rows that differ, including those beyond [`DataDiff::rows`].
+    pub fn differing(&self) -> usize {
+        self.added + self.removed + self.changed
+    }
+
+    pub fn count(&self, change: RowChange) -> usize {
+        match change {
+            RowChange::Added => self.added,
+            RowChange::Removed => self.removed,
+            RowChange::Changed => self.changed,
+        }
+    }
+
+    /// One line per schema difference: columns in one file only, and columns whose type
+    /// changed.
+    pub fn schema_changes(&self) -> Vec<String> {
+        let only_in = |file: usize| -> Vec<&str> {
+            self.schema
+                .columns
+                .iter()
+                .filter(|c| c.dtypes[1 - file].is_none())
+                .map(|c| c.name.as_str())
+                .collect()
+        };
+        let mut lines = Vec::new();
+        for file in 0..2 {
+            let names = only_in(file);
+            if !names.is_empty() {
+                lines.push(format!(
+                    "Only in {}: {}",
+                    self.schema.file_name(file),
+                    names.join(", ")
+                ));
+            }
+        }
+        for column in &self.schema.columns {
+            if let [Some(old), Some(new)] = column.dtypes.as_slice() {
+                if old != new {
+                    lines.push(format!("{}: {} → {}", column.name, old, new));
+                }
+            }
+        }
+        lines
+    }
+}
+
+/// Number of key values that appear on more than one row of `lf`.
+fn repeated_keys(lf: &LazyFrame, keys: &[String], polars_streaming: bool) -> Result<usize> {
+    let key_exprs: Vec<Expr> = keys.iter().map(|k| col(k.as_str())).collect();
+    let df = collect_lazy(
+        lf.clone()
+            .group_by(key_exprs)
+            .agg([len().alias("count")])
+            .filter(col("count").gt(lit(1)))
+            .select([len().alias("repeated")]),
+        polars_streaming,
+    )?;
+    Ok(df
+        .column("repeated")?
+        .get(0)?
+        .extract::<usize>()
+        .unwrap_or(0))
+}
+
+/// The rows of the compared frame `df`, with `columns` compared columns.
+fn diff_rows(df: &DataFrame, keys: &[String], columns: usize) -> Result<Vec<DiffRow>> {
+    let text = |v: AnyValue| {
+        if v.is_null() {
+            "null".to_string()
+        } else {
+            v.str_value().into_owned()
+        }
+    };
+    let statuses = df.column(STATUS)?.str()?.clone();
+    let key_columns = keys
+        .iter()
+        .map(|k| df.column(k))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let mut rows = Vec::with_capacity(df.height());
+    for (row, status) in statuses.iter().enumerate() {
+        let change = match status {
+            Some("added") => RowChange::Added,
+            Some("removed") => RowChange::Removed,
+            _ => RowChange::Changed,
+        };
+        let key = key_columns
+            .iter()
+            .map(|c| c.get(row).map(text))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let mut values = Vec::with_capacity(columns);
+        let mut changed = Vec::with_capacity(columns);
+        for i in 0..columns {
+            let old = df.column(&format!("{i}_old"))?.get(row)?;
+            let new = df.column(&format!("{i}_new"))?.get(row)?;
+            let differs = change == RowChange::Changed
+                && df.column(&format!("{i}_changed"))?.get(row)? == AnyValue::Boolean(true);
+            values.push(match change {
+                RowChange::Removed => text(old),
+                _ if differs => format!("{} → {}", text(old), text(new)),
+                _ => text(new),
+            });
+            changed.push(differs);
+        }
+        rows.push(DiffRow {
+            change,
+            key,
+            values,
+            changed,
+        });
+    }
+    Ok(rows)
+}
+
+/// The data comparison shown over the table, computed in the background.
+pub struct DataDiffView {
+    pub request: DiffRequest,
+    /// None while comparing; the error message when the comparison failed.
+    pub diff: Option<std::result::Result<DataDiff, String>>,
+    pub table_state: TableState,
+    /// First compared column shown (scrolled with Left/Right).
+    pub column_offset: usize,
+    /// Show only rows with this change.
+    pub filter: Option<RowChange>,
+    /// Rows that fit in the table, as of the last render.
+    pub visible: usize,
+}
+
+impl DataDiffView {
+    pub fn new(request: DiffRequest) -> Self {
+        Self {
+            request,
+            diff: None,
+            table_state: TableState::default(),
+            column_offset: 0,
+            filter: None,
+            visible: 1,
+        }
+    }
+
+    pub fn set_result(&mut self, diff: std::result::Result<DataDiff, String>) {
+        self.diff = Some(diff);
+        self.column_offset = 0;
+        self.select_first();
+    }
+
+    /// Indices into `diff.rows` of the rows currently shown.
+    pub fn visible_rows(&self) -> Vec<usize> {
+        let Some(Ok(diff)) = &self.diff else {
+            return Vec::new();
+        };
+        (0..diff.rows.len())
+            .filter(|&i| self.filter.is_none_or(|f| diff.rows[i].change == f))
+            .collect()
+    }
+
+    /// Show all rows, then only added, removed and changed rows in turn.
+    pub fn cycle_filter(&mut self) {
+        self.filter = match self.filter {
+            None => Some(RowChange::Added),
+            Some(RowChange::Added) => Some(RowChange::Removed),
+            Some(RowChange::Removed) => Some(RowChange::Changed),
+            Some(RowChange::Changed) => None,
+        };
+        self.select_first();
+    }
+
+    fn select_first(&mut self) {
+        let any = !self.visible_rows().is_empty();
+        self.table_state.select(any.then_some(0));
+        *self.table_state.offset_mut() = 0;
+    }
+
+    /// Move the selection by `delta` rows, stopping at the first and last row.
+    pub fn select_by(&mut self, delta: isize) {
+        let len = self.visible_rows().len();
+        if let Some(i) = self.table_state.selected() {
+            let last = len.saturating_sub(1);
+            self.table_state
+                .select(Some(i.saturating_add_signed(delta).min(last)));
+        }
+    }
+
+    pub fn scroll_columns_right(&mut self) {
+        if let Some(Ok(diff)) = &self.diff {
+            if self.column_offset + 1 < diff.columns.len() {
+                self.column_offset += 1;
+            }
+        }
+    }
+
+    pub fn scroll_columns_left(&mut self) {
+        self.column_offset = self.column_offset.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(keys: &[&str]) -> DiffRequest {
+        DiffRequest {
+            old: PathBuf::from("old.csv"),
+            new: PathBuf::from("new.csv"),
+            keys: keys.iter().map(|k| k.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn rows_are_aligned_by_key() {
+        let old = df!(
+            "id" => [1i64, 2, 3],
+            "name" => ["a", "b", "c"],
+            "qty" => [10i64, 20, 30],
+            "gone" => [true, false, true]
+        )
+        .unwrap()
+        .lazy();
+        let new = df!(
+            "id" => [4i64, 3, 1],
+            "name" => ["d", "c", "a"],
+            "qty" => [40.0f64, 31.0, 10.0],
+            "extra" => ["x", "y", "z"]
+        )
+        .unwrap()
+        .lazy();
+        let diff = DataDiff::compute(old, new, &request(&["id"]), 100, false).unwrap();
+        assert_eq!(diff.columns, ["name", "qty"]);
+        assert_eq!(
+            (diff.added, diff.removed, diff.changed, diff.unchanged),
+            (1, 1, 1, 1)
+        );
+        let rows: Vec<(RowChange, &str, Vec<&str>)> = diff
+            .rows
+            .iter()
+            .map(|r| {
+                (
+                    r.change,
+                    r.key[0].as_str(),
+                    r.values.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (RowChange::Removed, "2", vec!["b", "20.0"]),
+                (RowChange::Changed, "3", vec!["c", "30.0 → 31.0"]),
+                (RowChange::Added, "4", vec!["d", "40.0"]),
+            ]
+        );
+        assert_eq!(diff.rows[1].changed, [false, true]);
+        assert_eq!(
+            diff.schema_changes(),
+            [
+                "Only in old.csv: gone",
+                "Only in new.csv: extra",
+                "qty: i64 → f64"
+            ]
+        );
+    }
+
+    #[test]
+    fn rows_are_aligned_by_position_without_keys() {
+        let old = df!("v" => [1i32, 2, 3]).unwrap().lazy();
+        let new = df!("v" => [1i32, 5]).unwrap().lazy();
+        let diff = DataDiff::compute(old, new, &request(&[]), 100, false).unwrap();
+        assert_eq!(diff.keys, ["row"]);
+        assert_eq!((diff.added, diff.removed, diff.changed), (0, 1, 1));
+        assert_eq!(diff.rows[0].key, ["2"]);
+        assert_eq!(diff.rows[0].values, ["2 → 5"]);
+        assert_eq!(diff.rows[1].change, RowChange::Removed);
+    }
+
+    #[test]
+    fn keys_must_exist_and_be_unique() {
+        let old = df!("id" => [1i64, 1], "v" => [1, 2]).unwrap().lazy();
+        let new = df!("id" => [1i64], "v" => [1]).unwrap().lazy();
+        let err =
+            DataDiff::compute(old.clone(), new.clone(), &request(&["id"]), 100, false).unwrap_err();
+        assert!(err.to_string().contains("old.csv: 1 keys repeat"), "{err}");
+        let err = DataDiff::compute(old, new, &request(&["v", "nope"]), 100, false).unwrap_err();
+        assert!(
+            err.to_string().contains("'nope' is not in old.csv"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//! Duplicate row detection (the "Duplicate Rows" analysis tool).
+//!
+//! Groups the rows of the current view by key columns (every comparable column when none are
+//! chosen) and reports the groups that occur more than once, largest first, with the row where
+//! each group first appears. Duplicates can only be found on the full view, so this tool never
+//! samples.
+
+use crate::cardinality::is_countable;
+use crate::statistics::collect_lazy;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use polars::prelude::*;
+
+/// Groups listed in the report; the totals count every group.
+pub const MAX_GROUPS: usize = 1_000;
+
+const ROW_COLUMN: &str = "__datui_row";
+const COUNT_COLUMN: &str = "__datui_count";
+const FIRST_COLUMN: &str = "__datui_first";
+
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    /// Key values, in key column order ("null" for nulls).
+    pub values: Vec<String>,
+    /// Rows sharing these values (at least 2).
+    pub count: usize,
+    /// Row (in the view) where the group first appears.
+    pub first_row: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct DuplicateReport {
+    /// Columns the rows were compared on.
+    pub key_columns: Vec<String>,
+    /// Whether `key_columns` is the whole row (no keys were chosen).
+    pub full_row: bool,
+    /// Columns left out of a full-row comparison because their type can't be compared.
+    pub skipped: Vec<String>,
+    pub rows: usize,
+    /// Rows that belong to a duplicated group, first occurrences included.
+    pub duplicate_rows: usize,
+    /// Duplicated groups in total.
+    pub group_count: usize,
+    /// Largest groups first, then by first row; at most `MAX_GROUPS`.
+    pub groups: Vec<DuplicateGroup>,
+}
+
+impl DuplicateReport {
+    /// Rows beyond the first of each group: what dropping duplicates would remove.
+    pub fn extra_rows(&self) -> usize {
+        self.duplicate_rows - self.group_count
+    }
+}
+
+/// Columns to compare: `keys` when given (each must be in `schema`), else every comparable
+/// column. Returns (columns, skipped).
+pub fn resolve_keys(schema: &Schema, keys: &[String]) -> Result<(Vec<String>, Vec<String>)> {
+    if !keys.is_empty() {
+        if let Some(missing) = keys.iter().find(|k| !schema.contains(k.as_str())) {
+            return Err(eyre!("Column '{}' is not in the current view", missing));
+        }
+        return Ok((keys.to_vec(), Vec::new()));
+    }
+    let (columns, skipped): (Vec<_>, Vec<_>) =
+        schema.iter().partition(|(_, dtype)| is_countable(dtype));
+    let names = |fields: Vec<(&PlSmallStr, &DataType)>| {
+        fields
+            .into_iter()
+            .map(|(name, _)| name.to_string())
+            .collect::<Vec<_>>()
+    };
+    Ok((names(columns), names(skipped)))
+}
+
+/// Parse the key columns typed in the tool: comma-separated names, empty for the whole row.
+pub fn parse_keys(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Rows whose values in `keys` occur more than once.
+pub fn duplicated_expr(keys: &[String]) -> Expr {
+    len()
+        .over(keys.iter().map(|k| col(k.as_str())).collect::<Vec<_>>())
+        .gt(lit(1))
+}
+
+/// Find the duplicated rows of `lf` compared on `keys` (the whole row when empty).
+pub fn compute(lf: &LazyFrame, keys: &[String], polars_streaming: bool) -> Result<DuplicateReport> {
+    let schema = lf.clone().collect_schema()?;
+    let (key_columns, skipped) = resolve_keys(&schema, keys)?;
+    if key_columns.is_empty() {
+        return Err(eyre!("No columns can be compared for duplicates"));
+    }
+    let key_exprs: Vec<Expr> = key_columns.iter().map(|k| col(k.as_str())).collect();
+
+    let rows = collect_lazy(lf.clone().select([len()]), polars_streaming)?
+        .get(0)
+        .and_then(|row| row.first().and_then(|v| v.extract::<usize>()))
+        .unwrap_or(0);
+    let groups_lf = lf
+        .clone()
+        .with_row_index(ROW_COLUMN, None)
+        .group_by(key_exprs)
+        .agg([
+            len().alias(COUNT_COLUMN),
+            col(ROW_COLUMN).min().alias(FIRST_COLUMN),
+        ])
+        .filter(col(COUNT_COLUMN).gt(lit(1)))
+        .sort(
+            [COUNT_COLUMN, FIRST_COLUMN],
+            SortMultipleOptions::default().with_order_descending_multi([true, false]),
+        );
+    let df = collect_lazy(groups_lf, polars_streaming)?;
+
+    let counts: Vec<usize> = df
+        .column(COUNT_COLUMN)?
+        .cast(&DataType::UInt64)?
+        .u64()?
+        .into_no_null_iter()
+        .map(|n| n as usize)
+        .collect();
+    let first_rows: Vec<usize> = df
+        .column(FIRST_COLUMN)?
+        .cast(&DataType::UInt64)?
+        .u64()?
+        .into_no_null_iter()
+        .map(|n| n as usize)
+        .collect();
+    let key_values: Vec<&Column> = key_columns
+        .iter()
+        .map(|k| df.column(k))
+        .collect::<PolarsResult<_>>()?;
+    let groups = (0..df.height().min(MAX_GROUPS))
+        .map(|i| {
+            let values = key_values
+                .iter()
+                .map(|c| match c.get(i) {
+                    Ok(AnyValue::Null) | Err(_) => "null".to_string(),
+                    Ok(v) => v.str_value().into_owned(),
+                })
+                .collect();
+            DuplicateGroup {
+                values,
+                count: counts[i],
+                first_row: first_rows[i],
+            }
+        })
+        .collect();
+
+    Ok(DuplicateReport {
+        full_row: keys.is_empty(),
+        key_columns,
+        skipped,
+        rows,
+        duplicate_rows: counts.iter().sum(),
+        group_count: counts.len(),
+        groups,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> LazyFrame {
+        df!(
+            "id" => [1i64, 2, 3, 4, 5, 6],
+            "name" => [Some("a"), Some("b"), Some("a"), None, Some("a"), None],
+            "city" => ["x", "y", "x", "z", "w", "z"],
+        )
+        .unwrap()
+        .lazy()
+    }
+
+    #[test]
+    fn test_duplicates_by_keys() {
+        let report = compute(&frame(), &["name".to_string()], false).unwrap();
+        assert!(!report.full_row);
+        assert_eq!(report.rows, 6);
+        assert_eq!(report.group_count, 2);
+        assert_eq!(report.duplicate_rows, 5);
+        assert_eq!(report.extra_rows(), 3);
+        assert_eq!(report.groups[0].values, ["a"]);
+        assert_eq!(report.groups[0].count, 3);
+        assert_eq!(report.groups[0].first_row, 0);
+        assert_eq!(report.groups[1].values, ["null"], "nulls group together");
+        assert_eq!(report.groups[1].first_row, 3);
+
+        let report = compute(&frame(), &["name".to_string(), "city".to_string()], false).unwrap();
+        assert_eq!(report.group_count, 2);
+        assert_eq!(report.groups[0].values, ["a", "x"]);
+
+        let err = compute(&frame(), &["nope".to_string()], false).unwrap_err();
+        assert!(err.to_string().contains("nope"));
+    }
+
+    #[test]
+    fn test_full_row_and_filter_expr() {
+        let lf = df!(
+            "a" => [1i64, 1, 2, 1],
+            "b" => ["x", "x", "x", "y"],
+        )
+        .unwrap()
+        .lazy();
+        let report = compute(&lf, &[], false).unwrap();
+        assert!(report.full_row);
+        assert_eq!(report.key_columns, ["a", "b"]);
+        assert_eq!(report.group_count, 1);
+        assert_eq!(report.groups[0].first_row, 0);
+
+        let kept = lf
+            .filter(duplicated_expr(&report.key_columns))
+            .collect()
+            .unwrap();
+        assert_eq!(kept.height(), 2);
+    }
+
+    #[test]
+    fn test_parse_keys() {
+        assert_eq!(parse_keys(" a, b ,,c "), ["a", "b", "c"]);
+        assert!(parse_keys("  ").is_empty());
+    }
+}
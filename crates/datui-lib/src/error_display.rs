@@ -93,6 +93,8 @@ pub fn user_message_from_io(err: &io::Error, context: Option<&str>) -> String {
 pub enum ErrorKindForPython {
     FileNotFound,
     PermissionDenied,
+    /// An argument the caller gave is invalid (e.g. [`crate::initial_view::InvalidView`]).
+    InvalidArgument,
     Other,
 }
 
@@ -101,6 +103,9 @@ pub enum ErrorKindForPython {
 pub fn error_for_python(report: &color_eyre::eyre::Report) -> (ErrorKindForPython, String) {
     use std::io::ErrorKind;
     for cause in report.chain() {
+        if let Some(invalid) = cause.downcast_ref::<crate::initial_view::InvalidView>() {
+            return (ErrorKindForPython::InvalidArgument, invalid.to_string());
+        }
         if let Some(io_err) = cause.downcast_ref::<io::Error>() {
             let kind = match io_err.kind() {
                 ErrorKind::NotFound => ErrorKindForPython::FileNotFound,
@@ -0,0 +1,358 @@
+//! Part of an Excel sheet to load: `--excel-range` takes a cell range (`A3:F200`, `A3` for
+//! everything from A3 on, or `Sales!A3:F200`) or a name defined in the workbook, and
+//! `--excel-header-row` the sheet row holding the column names, for sheets that start with
+//! title rows. The [sheet preview](SheetPreview) shows a sheet's first rows so the header row
+//! can be chosen before loading it.
+
+use crate::OpenOptions;
+use calamine::{open_workbook_auto, Data, Range, Reader, Sheets};
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use ratatui::widgets::TableState;
+use std::io::{Read, Seek};
+use std::path::{Path, PathBuf};
+
+/// Rows the sheet preview reads.
+const PREVIEW_ROWS: usize = 50;
+
+/// A block of cells, 0-based (row, column) like calamine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellRange {
+    /// Sheet named before `!`, if any.
+    pub sheet: Option<String>,
+    pub start: (u32, u32),
+    /// Bottom-right cell; None runs to the end of the sheet's used cells.
+    pub end: Option<(u32, u32)>,
+}
+
+impl CellRange {
+    /// Parse A1 notation: `A3:F200`, `A3`, `$A$3:$F$200`, `Sales!A3:F200` or `'Q1 Sales'!A3`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().trim_start_matches('=');
+        let (sheet, cells) = match text.rsplit_once('!') {
+            Some((sheet, cells)) => (Some(unquote_sheet(sheet)?), cells),
+            None => (None, text),
+        };
+        let (start, end) = match cells.split_once(':') {
+            Some((start, end)) => (parse_cell(start)?, Some(parse_cell(end)?)),
+            None => (parse_cell(cells)?, None),
+        };
+        if let Some(end) = end {
+            if end.0 < start.0 || end.1 < start.1 {
+                return None;
+            }
+        }
+        Some(Self { sheet, start, end })
+    }
+
+    /// `text` as a range, or the range a name defined in `workbook` refers to.
+    pub fn resolve<RS: Read + Seek>(text: &str, workbook: &Sheets<RS>) -> Result<Self> {
+        if let Some(range) = Self::parse(text) {
+            return Ok(range);
+        }
+        let (name, formula) = workbook
+            .defined_names()
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(text.trim()))
+            .ok_or_else(|| {
+                eyre!(
+                    "Excel range '{}' is neither a cell range like A3:F200 nor a name defined in the workbook",
+                    text
+                )
+            })?;
+        Self::parse(formula)
+            .filter(|range| range.sheet.is_some())
+            .ok_or_else(|| {
+                eyre!(
+                    "Excel name '{}' refers to '{}', not a cell range",
+                    name,
+                    formula
+                )
+            })
+    }
+}
+
+fn unquote_sheet(sheet: &str) -> Option<String> {
+    match sheet.strip_prefix('\'') {
+        Some(rest) => Some(rest.strip_suffix('\'')?.replace("''", "'")),
+        None if sheet.is_empty() => None,
+        None => Some(sheet.to_string()),
+    }
+}
+
+/// `B12` (or `$B$12`) as 0-based (row, column).
+fn parse_cell(cell: &str) -> Option<(u32, u32)> {
+    let cell = cell.trim().replace('$', "");
+    let digits = cell.find(|c: char| c.is_ascii_digit())?;
+    let (letters, number) = cell.split_at(digits);
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let column = letters.chars().try_fold(0u32, |acc, c| {
+        acc.checked_mul(26)?
+            .checked_add(c.to_ascii_uppercase() as u32 - 'A' as u32 + 1)
+    })?;
+    let row: u32 = number.parse().ok()?;
+    (row >= 1).then(|| (row - 1, column - 1))
+}
+
+/// Spreadsheet name of a 0-based column: 0 is A, 26 is AA.
+pub fn column_name(mut column: u32) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (column % 26) as u8);
+        if column < 26 {
+            break;
+        }
+        column = column / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// Cells read from a sheet.
+#[derive(Debug, Clone)]
+pub struct SheetRows {
+    pub sheet: String,
+    /// 0-based sheet row of `rows[0]`.
+    pub first_row: u32,
+    /// 0-based sheet column of each row's first cell.
+    pub first_column: u32,
+    pub rows: Vec<Vec<Data>>,
+}
+
+impl SheetRows {
+    /// Range of the cells, e.g. "A3:F200"; empty when there are none.
+    pub fn range_label(&self) -> String {
+        let width = self.rows.iter().map(Vec::len).max().unwrap_or(0) as u32;
+        if self.rows.is_empty() || width == 0 {
+            return String::new();
+        }
+        format!(
+            "{}{}:{}{}",
+            column_name(self.first_column),
+            self.first_row + 1,
+            column_name(self.first_column + width - 1),
+            self.first_row + self.rows.len() as u32
+        )
+    }
+}
+
+/// The cells `options` selects from the workbook at `path`: the sheet (`excel_sheet`, or the
+/// sheet of `excel_range`), cut to `excel_range`, starting at `excel_header_row`.
+pub fn read_rows(path: &Path, options: &OpenOptions) -> Result<SheetRows> {
+    let mut workbook = open_workbook_auto(path).map_err(|e| eyre!("Excel: {}", e))?;
+    let sheet_names = workbook.sheet_names();
+    if sheet_names.is_empty() {
+        return Err(eyre!("Excel file has no worksheets"));
+    }
+    let cell_range = options
+        .excel_range
+        .as_deref()
+        .map(|text| CellRange::resolve(text, &workbook))
+        .transpose()?;
+    let sheet = match cell_range.as_ref().and_then(|r| r.sheet.clone()) {
+        Some(sheet) => sheet,
+        None => match options.excel_sheet.as_deref() {
+            None => sheet_names[0].clone(),
+            Some(sel) => match sel.parse::<usize>() {
+                Ok(idx) => sheet_names
+                    .get(idx)
+                    .cloned()
+                    .ok_or_else(|| eyre!("Excel: no sheet at index {}", idx))?,
+                Err(_) => sel.to_string(),
+            },
+        },
+    };
+    let used = workbook
+        .worksheet_range(&sheet)
+        .map_err(|e| eyre!("Excel: {}", e))?;
+    let (cells, first_row, first_column) = match cell_range {
+        Some(CellRange { start, end, .. }) => {
+            let end = end.unwrap_or_else(|| {
+                let (last_row, last_column) = used.end().unwrap_or(start);
+                (last_row.max(start.0), last_column.max(start.1))
+            });
+            (used.range(start, end), start.0, start.1)
+        }
+        None => {
+            let (row, column) = used.start().unwrap_or((0, 0));
+            (used, row, column)
+        }
+    };
+    let mut rows = SheetRows {
+        sheet,
+        first_row,
+        first_column,
+        rows: rows_of(&cells),
+    };
+    if let Some(header_row) = options.excel_header_row {
+        let skip = (header_row as u32)
+            .checked_sub(1 + rows.first_row)
+            .map(|skip| skip as usize)
+            .filter(|&skip| skip < rows.rows.len())
+            .ok_or_else(|| {
+                eyre!(
+                    "Excel header row {} is outside the rows read ({}-{})",
+                    header_row,
+                    rows.first_row + 1,
+                    rows.first_row as usize + rows.rows.len()
+                )
+            })?;
+        rows.rows.drain(..skip);
+        rows.first_row += skip as u32;
+    }
+    Ok(rows)
+}
+
+fn rows_of(range: &Range<Data>) -> Vec<Vec<Data>> {
+    range.rows().map(|r| r.to_vec()).collect()
+}
+
+/// The first rows of a sheet, to choose its header row before loading it.
+pub struct SheetPreview {
+    pub path: PathBuf,
+    /// Options the sheet loads with; Enter adds the chosen header row.
+    pub options: OpenOptions,
+    pub sheet: String,
+    /// Range of the previewed cells, e.g. "A1:F50".
+    pub range: String,
+    /// 0-based sheet row of `rows[0]`.
+    pub first_row: u32,
+    /// 0-based sheet column of each row's first cell.
+    pub first_column: u32,
+    pub rows: Vec<Vec<String>>,
+    /// Position of the header row in `rows`.
+    pub header: usize,
+    /// Scroll position of the popup, which keeps the header row in view.
+    pub table_state: TableState,
+}
+
+impl SheetPreview {
+    /// Preview the sheet `options` selects. Starts on `options.excel_header_row` when it is
+    /// among the first rows, else on the first of the fullest rows, past any title rows.
+    pub fn open(path: PathBuf, mut options: OpenOptions) -> Result<Self> {
+        let header_row = options.excel_header_row.take();
+        let mut sheet_rows = read_rows(&path, &options)?;
+        sheet_rows.rows.truncate(PREVIEW_ROWS);
+        let range = sheet_rows.range_label();
+        let rows: Vec<Vec<String>> = sheet_rows
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        let header = header_row
+            .and_then(|row| row.checked_sub(1 + sheet_rows.first_row as usize))
+            .filter(|&i| i < rows.len())
+            .unwrap_or_else(|| fullest_row(&rows));
+        Ok(Self {
+            path,
+            options,
+            sheet: sheet_rows.sheet,
+            range,
+            first_row: sheet_rows.first_row,
+            first_column: sheet_rows.first_column,
+            rows,
+            header,
+            table_state: TableState::default(),
+        })
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        let last = self.rows.len().saturating_sub(1);
+        self.header = self.header.saturating_add_signed(delta).min(last);
+    }
+
+    /// Sheet row number of the header row, as Excel shows it.
+    pub fn header_row(&self) -> usize {
+        self.first_row as usize + self.header + 1
+    }
+
+    /// Paths and options that load the sheet with the chosen header row.
+    pub fn open_selected(&self) -> (Vec<PathBuf>, OpenOptions) {
+        let mut options = self.options.clone();
+        options.excel_header_row = Some(self.header_row());
+        (vec![self.path.clone()], options)
+    }
+}
+
+/// The first row with the most filled cells: title rows above a table fill fewer.
+fn fullest_row(rows: &[Vec<String>]) -> usize {
+    let filled = |row: &Vec<String>| row.iter().filter(|c| !c.is_empty()).count();
+    let most = rows.iter().map(filled).max().unwrap_or(0);
+    rows.iter().position(|r| filled(r) == most).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cell_ranges() {
+        let range = CellRange::parse("A3:F200").unwrap();
+        assert_eq!(range.sheet, None);
+        assert_eq!(range.start, (2, 0));
+        assert_eq!(range.end, Some((199, 5)));
+        let range = CellRange::parse("='Q1 ''24'!$B$2:$AA$10").unwrap();
+        assert_eq!(range.sheet.as_deref(), Some("Q1 '24"));
+        assert_eq!(range.start, (1, 1));
+        assert_eq!(range.end, Some((9, 26)));
+        assert_eq!(CellRange::parse("c7").unwrap().end, None);
+        assert!(CellRange::parse("F200:A3").is_none());
+        assert!(CellRange::parse("Totals").is_none());
+        assert!(CellRange::parse("A0").is_none());
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(701), "ZZ");
+    }
+
+    #[test]
+    fn reads_ranges_names_and_header_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let sheet = workbook.add_worksheet();
+        sheet.set_name("Report").unwrap();
+        sheet.write(0, 0, "Quarterly report").unwrap();
+        sheet.write(2, 0, "id").unwrap();
+        sheet.write(2, 1, "amount").unwrap();
+        for row in 3..6u32 {
+            sheet.write(row, 0, row).unwrap();
+            sheet.write(row, 1, row * 10).unwrap();
+        }
+        sheet.write(7, 0, "Total").unwrap();
+        workbook
+            .define_name("Amounts", "=Report!$A$3:$B$6")
+            .unwrap();
+        workbook.save(&path).unwrap();
+
+        let mut options = OpenOptions {
+            excel_range: Some("A3:B6".to_string()),
+            ..Default::default()
+        };
+        let rows = read_rows(&path, &options).unwrap();
+        assert_eq!(rows.range_label(), "A3:B6");
+        assert_eq!(rows.rows[0][1], Data::String("amount".to_string()));
+
+        options.excel_range = Some("amounts".to_string());
+        let rows = read_rows(&path, &options).unwrap();
+        assert_eq!((rows.sheet.as_str(), rows.rows.len()), ("Report", 4));
+
+        options.excel_range = None;
+        options.excel_header_row = Some(3);
+        let rows = read_rows(&path, &options).unwrap();
+        assert_eq!(rows.range_label(), "A3:B8");
+        options.excel_header_row = Some(9);
+        let err = read_rows(&path, &options).unwrap_err().to_string();
+        assert!(err.contains("outside the rows read (1-8)"), "{err}");
+
+        options.excel_range = Some("Missing".to_string());
+        assert!(read_rows(&path, &options).is_err());
+
+        let mut preview = SheetPreview::open(path.clone(), OpenOptions::default()).unwrap();
+        assert_eq!(preview.header_row(), 3);
+        preview.move_by(1);
+        let (_, chosen) = preview.open_selected();
+        assert_eq!(chosen.excel_header_row, Some(4));
+    }
+}
@@ -1,5 +1,8 @@
 //! Export modal state and focus management.
 
+use crate::locale::format_count;
+use crate::mouse::ButtonAreas;
+use crate::sqlite_export::IfExists;
 use crate::widgets::text_input::TextInput;
 use crate::CompressionFormat;
 
@@ -13,18 +16,29 @@ pub enum ExportFormat {
     /// Arrow IPC / Feather v2
     Ipc,
     Avro,
+    /// Excel workbook
+    Xlsx,
+    /// Table in a SQLite database
+    Sqlite,
+    /// Markdown table; offered for analysis results only
+    Markdown,
 }
 
 impl ExportFormat {
-    pub const ALL: [Self; 6] = [
+    pub const ALL: [Self; 8] = [
         Self::Csv,
         Self::Parquet,
         Self::Json,
         Self::Ndjson,
         Self::Ipc,
         Self::Avro,
+        Self::Xlsx,
+        Self::Sqlite,
     ];
 
+    /// Formats offered for analysis results tables.
+    pub const ANALYSIS: [Self; 3] = [Self::Csv, Self::Json, Self::Markdown];
+
     pub fn as_str(self) -> &'static str {
         match self {
             Self::Csv => "CSV",
@@ -33,6 +47,9 @@ impl ExportFormat {
             Self::Ndjson => "NDJSON",
             Self::Ipc => "Arrow",
             Self::Avro => "Avro",
+            Self::Xlsx => "Excel",
+            Self::Sqlite => "SQLite",
+            Self::Markdown => "Markdown",
         }
     }
 
@@ -44,6 +61,9 @@ impl ExportFormat {
             Self::Ndjson => "jsonl",
             Self::Ipc => "arrow",
             Self::Avro => "avro",
+            Self::Xlsx => "xlsx",
+            Self::Sqlite => "db",
+            Self::Markdown => "md",
         }
     }
 
@@ -55,6 +75,8 @@ impl ExportFormat {
             "ndjson" | "jsonl" => Some(Self::Ndjson),
             "arrow" | "ipc" | "feather" => Some(Self::Ipc),
             "avro" => Some(Self::Avro),
+            "xlsx" => Some(Self::Xlsx),
+            "db" | "sqlite" | "sqlite3" => Some(Self::Sqlite),
             _ => None,
         }
     }
@@ -64,10 +86,41 @@ impl ExportFormat {
     }
 }
 
+/// Which rows are exported.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExportScope {
+    /// The loaded data (with computed columns), ignoring queries, filters, sorts and reshapes.
+    Dataset,
+    /// Every row of the current view.
+    #[default]
+    View,
+    /// The rows on screen.
+    Page,
+    /// Rows selected with `m`, or the selected row and its neighbors (`E`).
+    Selection,
+    /// The results table of the analysis tool the modal was opened from.
+    Analysis,
+}
+
+impl ExportScope {
+    pub const ALL: [Self; 4] = [Self::Dataset, Self::View, Self::Page, Self::Selection];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Dataset => "Entire dataset",
+            Self::View => "Filtered view",
+            Self::Page => "Current page",
+            Self::Selection => "Selected rows",
+            Self::Analysis => "Analysis results",
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum ExportFocus {
     #[default]
     FormatSelector,
+    ScopeSelector,
     PathInput,
     // CSV options
     CsvDelimiter,
@@ -77,6 +130,14 @@ pub enum ExportFocus {
     JsonCompression,
     // NDJSON options
     NdjsonCompression,
+    // Parquet options
+    ParquetPartitions,
+    // Excel options
+    XlsxSheetName,
+    XlsxAutofit,
+    // SQLite options
+    SqliteTable,
+    SqliteIfExists,
     // Footer buttons
     ExportButton,
     CancelButton,
@@ -85,6 +146,8 @@ pub enum ExportFocus {
 pub struct ExportModal {
     pub active: bool,
     pub focus: ExportFocus,
+    /// Where the buttons were drawn in the last frame, for mouse clicks.
+    pub buttons: ButtonAreas<ExportFocus>,
     pub selected_format: ExportFormat,
     pub path_input: TextInput,
     // CSV options
@@ -95,9 +158,34 @@ pub struct ExportModal {
     pub json_compression: Option<CompressionFormat>,
     // NDJSON options
     pub ndjson_compression: Option<CompressionFormat>,
+    // Parquet options
+    /// Columns of the view, for the partition list.
+    pub columns: Vec<String>,
+    /// Hive partition columns, in the order they were chosen; empty writes a single file.
+    pub parquet_partition_by: Vec<String>,
+    pub partition_cursor: usize,
+    // Excel options
+    pub xlsx_sheet_name_input: TextInput,
+    /// Size columns to their contents.
+    pub xlsx_autofit: bool,
+    // SQLite options
+    pub sqlite_table_input: TextInput,
+    pub sqlite_if_exists: IfExists,
     // Compression selection index (for horizontal radio buttons)
     pub compression_selection_idx: usize,
     pub history_limit: usize,
+    pub scope: ExportScope,
+    /// Row count of the current view, when known, for the scope list.
+    pub view_rows: Option<usize>,
+    /// (offset, len) of the rows on screen, when the table has been drawn.
+    pub page_range: Option<(usize, usize)>,
+    /// (offset, len) of the selected rows, if any.
+    pub selection_range: Option<(usize, usize)>,
+    /// (offset, len) of the rows to export within the current view; None = all rows. Follows
+    /// `scope`.
+    pub row_range: Option<(usize, usize)>,
+    /// Name of the analysis tool whose results are exported; None exports the data.
+    pub analysis: Option<String>,
 }
 
 impl ExportModal {
@@ -135,7 +223,139 @@ impl ExportModal {
         self.csv_compression = None;
         self.json_compression = None;
         self.ndjson_compression = None;
+        self.columns.clear();
+        self.parquet_partition_by.clear();
+        self.partition_cursor = 0;
+        self.xlsx_sheet_name_input = TextInput::new()
+            .with_history_limit(history_limit)
+            .with_theme(theme);
+        self.xlsx_sheet_name_input
+            .set_value(crate::xlsx_export::DEFAULT_SHEET_NAME.to_string());
+        self.xlsx_autofit = true;
+        self.sqlite_table_input = TextInput::new()
+            .with_history_limit(history_limit)
+            .with_theme(theme);
+        self.sqlite_table_input
+            .set_value(crate::sqlite_export::DEFAULT_TABLE_NAME.to_string());
+        self.sqlite_if_exists = IfExists::Fail;
         self.compression_selection_idx = 0;
+        self.view_rows = None;
+        self.page_range = None;
+        self.selection_range = None;
+        self.analysis = None;
+        self.set_scope(ExportScope::View);
+    }
+
+    /// Switch to exporting the results table of the analysis tool named `tool`: CSV, JSON or
+    /// Markdown, starting on CSV unless one of those is already selected.
+    pub fn set_analysis(&mut self, tool: &str) {
+        self.analysis = Some(tool.to_string());
+        if !ExportFormat::ANALYSIS.contains(&self.selected_format) {
+            self.selected_format = ExportFormat::Csv;
+        }
+        self.set_scope(ExportScope::Analysis);
+    }
+
+    /// Formats that can be chosen: all of them for data, a few for analysis results.
+    pub fn formats(&self) -> &'static [ExportFormat] {
+        if self.analysis.is_some() {
+            &ExportFormat::ANALYSIS
+        } else {
+            &ExportFormat::ALL
+        }
+    }
+
+    /// Move to the next (`delta` > 0) or previous available format, wrapping around.
+    pub fn cycle_format(&mut self, delta: i32) {
+        let formats = self.formats();
+        let idx = formats
+            .iter()
+            .position(|&f| f == self.selected_format)
+            .unwrap_or(0);
+        let next = if delta < 0 {
+            (idx + formats.len() - 1) % formats.len()
+        } else {
+            (idx + 1) % formats.len()
+        };
+        self.selected_format = formats[next];
+    }
+
+    /// Set the ranges the page and selection scopes export, starting on the selection when
+    /// there is one.
+    pub fn set_ranges(
+        &mut self,
+        view_rows: Option<usize>,
+        page_range: Option<(usize, usize)>,
+        selection_range: Option<(usize, usize)>,
+    ) {
+        self.view_rows = view_rows;
+        self.page_range = page_range;
+        self.selection_range = selection_range;
+        if selection_range.is_some() {
+            self.set_scope(ExportScope::Selection);
+        }
+    }
+
+    /// Scopes that can be chosen: the page needs a drawn table and the selection selected rows.
+    /// Analysis results have only their own scope.
+    pub fn available_scopes(&self) -> Vec<ExportScope> {
+        if self.analysis.is_some() {
+            return vec![ExportScope::Analysis];
+        }
+        ExportScope::ALL
+            .into_iter()
+            .filter(|scope| match scope {
+                ExportScope::Page => self.page_range.is_some(),
+                ExportScope::Selection => self.selection_range.is_some(),
+                ExportScope::Dataset | ExportScope::View => true,
+                ExportScope::Analysis => false,
+            })
+            .collect()
+    }
+
+    pub fn set_scope(&mut self, scope: ExportScope) {
+        self.scope = scope;
+        self.row_range = match scope {
+            ExportScope::Page => self.page_range,
+            ExportScope::Selection => self.selection_range,
+            ExportScope::Dataset | ExportScope::View | ExportScope::Analysis => None,
+        };
+    }
+
+    /// Move to the next (`delta` > 0) or previous available scope, wrapping around.
+    pub fn cycle_scope(&mut self, delta: i32) {
+        let scopes = self.available_scopes();
+        let idx = scopes.iter().position(|&s| s == self.scope).unwrap_or(0);
+        let next = if delta < 0 {
+            (idx + scopes.len() - 1) % scopes.len()
+        } else {
+            (idx + 1) % scopes.len()
+        };
+        self.set_scope(scopes[next]);
+    }
+
+    /// What `scope` covers, e.g. "1,234 rows" or "rows 11-20", when known.
+    pub fn scope_detail(&self, scope: ExportScope) -> Option<String> {
+        let range =
+            |(offset, len): (usize, usize)| format!("rows {}-{}", offset + 1, offset + len.max(1));
+        match scope {
+            ExportScope::Dataset | ExportScope::Analysis => None,
+            ExportScope::View => self.view_rows.map(|n| format!("{} rows", format_count(n))),
+            ExportScope::Page => self.page_range.map(range),
+            ExportScope::Selection => self.selection_range.map(range),
+        }
+    }
+
+    /// Modal title, naming the scope's rows, e.g. "Export Data (rows 11-20)".
+    pub fn title(&self) -> String {
+        if let Some(tool) = &self.analysis {
+            return format!("Export {} Results", tool);
+        }
+        match (self.scope, self.scope_detail(self.scope)) {
+            (ExportScope::Dataset, _) => "Export Data (entire dataset)".to_string(),
+            (_, Some(detail)) => format!("Export Data ({})", detail),
+            (_, None) => "Export Data".to_string(),
+        }
     }
 
     pub fn close(&mut self) {
@@ -146,12 +366,16 @@ impl ExportModal {
 
     pub fn next_focus(&mut self) {
         let new_focus = match self.focus {
-            ExportFocus::FormatSelector => ExportFocus::PathInput,
+            ExportFocus::FormatSelector => ExportFocus::ScopeSelector,
+            ExportFocus::ScopeSelector => ExportFocus::PathInput,
             ExportFocus::PathInput => match self.selected_format {
                 ExportFormat::Csv => ExportFocus::CsvDelimiter,
                 ExportFormat::Json => ExportFocus::JsonCompression,
                 ExportFormat::Ndjson => ExportFocus::NdjsonCompression,
-                ExportFormat::Parquet | ExportFormat::Ipc | ExportFormat::Avro => {
+                ExportFormat::Xlsx => ExportFocus::XlsxSheetName,
+                ExportFormat::Sqlite => ExportFocus::SqliteTable,
+                ExportFormat::Parquet => ExportFocus::ParquetPartitions,
+                ExportFormat::Ipc | ExportFormat::Avro | ExportFormat::Markdown => {
                     ExportFocus::ExportButton
                 }
             },
@@ -160,6 +384,11 @@ impl ExportModal {
             ExportFocus::CsvCompression => ExportFocus::ExportButton,
             ExportFocus::JsonCompression => ExportFocus::ExportButton,
             ExportFocus::NdjsonCompression => ExportFocus::ExportButton,
+            ExportFocus::ParquetPartitions => ExportFocus::ExportButton,
+            ExportFocus::XlsxSheetName => ExportFocus::XlsxAutofit,
+            ExportFocus::XlsxAutofit => ExportFocus::ExportButton,
+            ExportFocus::SqliteTable => ExportFocus::SqliteIfExists,
+            ExportFocus::SqliteIfExists => ExportFocus::ExportButton,
             ExportFocus::ExportButton => ExportFocus::CancelButton,
             ExportFocus::CancelButton => ExportFocus::FormatSelector,
         };
@@ -178,17 +407,26 @@ impl ExportModal {
     pub fn prev_focus(&mut self) {
         let new_focus = match self.focus {
             ExportFocus::FormatSelector => ExportFocus::CancelButton,
-            ExportFocus::PathInput => ExportFocus::FormatSelector,
+            ExportFocus::ScopeSelector => ExportFocus::FormatSelector,
+            ExportFocus::PathInput => ExportFocus::ScopeSelector,
             ExportFocus::CsvDelimiter => ExportFocus::PathInput,
             ExportFocus::CsvIncludeHeader => ExportFocus::CsvDelimiter,
             ExportFocus::CsvCompression => ExportFocus::CsvIncludeHeader,
             ExportFocus::JsonCompression => ExportFocus::PathInput,
             ExportFocus::NdjsonCompression => ExportFocus::PathInput,
+            ExportFocus::ParquetPartitions => ExportFocus::PathInput,
+            ExportFocus::XlsxSheetName => ExportFocus::PathInput,
+            ExportFocus::XlsxAutofit => ExportFocus::XlsxSheetName,
+            ExportFocus::SqliteTable => ExportFocus::PathInput,
+            ExportFocus::SqliteIfExists => ExportFocus::SqliteTable,
             ExportFocus::ExportButton => match self.selected_format {
                 ExportFormat::Csv => ExportFocus::CsvCompression,
                 ExportFormat::Json => ExportFocus::JsonCompression,
                 ExportFormat::Ndjson => ExportFocus::NdjsonCompression,
-                ExportFormat::Parquet | ExportFormat::Ipc | ExportFormat::Avro => {
+                ExportFormat::Xlsx => ExportFocus::XlsxAutofit,
+                ExportFormat::Sqlite => ExportFocus::SqliteIfExists,
+                ExportFormat::Parquet => ExportFocus::ParquetPartitions,
+                ExportFormat::Ipc | ExportFormat::Avro | ExportFormat::Markdown => {
                     ExportFocus::PathInput
                 }
             },
@@ -276,6 +514,51 @@ impl ExportModal {
         *compression = COMPRESSION_OPTIONS[self.compression_selection_idx];
     }
 
+    /// Whether the export is a hive-partitioned Parquet directory rather than a file.
+    pub fn is_partitioned(&self) -> bool {
+        self.selected_format == ExportFormat::Parquet && !self.parquet_partition_by.is_empty()
+    }
+
+    /// Move the partition list cursor down (`delta` > 0) or up, wrapping around.
+    pub fn move_partition_cursor(&mut self, delta: i32) {
+        let len = self.columns.len();
+        if len == 0 {
+            self.partition_cursor = 0;
+        } else if delta < 0 {
+            self.partition_cursor = (self.partition_cursor + len - 1) % len;
+        } else {
+            self.partition_cursor = (self.partition_cursor + 1) % len;
+        }
+    }
+
+    /// Add or remove the column under the cursor as a partition column.
+    pub fn toggle_partition(&mut self) {
+        let Some(column) = self.columns.get(self.partition_cursor) else {
+            return;
+        };
+        match self.parquet_partition_by.iter().position(|c| c == column) {
+            Some(i) => {
+                self.parquet_partition_by.remove(i);
+            }
+            None => self.parquet_partition_by.push(column.clone()),
+        }
+    }
+
+    /// Move to the next (`delta` > 0) or previous if-exists behavior, wrapping around.
+    pub fn cycle_if_exists(&mut self, delta: i32) {
+        let all = IfExists::ALL;
+        let idx = all
+            .iter()
+            .position(|&b| b == self.sqlite_if_exists)
+            .unwrap_or(0);
+        let next = if delta < 0 {
+            (idx + all.len() - 1) % all.len()
+        } else {
+            (idx + 1) % all.len()
+        };
+        self.sqlite_if_exists = all[next];
+    }
+
     pub fn select_compression(&mut self, compression: Option<CompressionFormat>) {
         match self.focus {
             ExportFocus::CsvCompression => {
@@ -297,6 +580,7 @@ impl Default for ExportModal {
         Self {
             active: false,
             focus: ExportFocus::FormatSelector,
+            buttons: ButtonAreas::default(),
             selected_format: ExportFormat::Csv,
             path_input: TextInput::new(),
             csv_delimiter_input: TextInput::new(),
@@ -304,8 +588,21 @@ impl Default for ExportModal {
             csv_compression: None,
             json_compression: None,
             ndjson_compression: None,
+            columns: Vec::new(),
+            parquet_partition_by: Vec::new(),
+            partition_cursor: 0,
+            xlsx_sheet_name_input: TextInput::new(),
+            xlsx_autofit: true,
+            sqlite_table_input: TextInput::new(),
+            sqlite_if_exists: IfExists::Fail,
             compression_selection_idx: 0,
             history_limit: 1000,
+            scope: ExportScope::View,
+            view_rows: None,
+            page_range: None,
+            selection_range: None,
+            row_range: None,
+            analysis: None,
         }
     }
 }
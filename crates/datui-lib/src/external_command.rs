@@ -0,0 +1,82 @@
+//! Running shell commands from the TUI (`!`) and suspending to the shell (Ctrl-Z). Both hand the
+//! terminal back to the shell; the run loop restores the TUI afterwards.
+
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::process::{Command, ExitStatus};
+
+/// Placeholder replaced by the current file's path (shell-quoted).
+pub const FILE_PLACEHOLDER: &str = "{file}";
+
+/// Replace `{file}` in `command` with the quoted path of the open file.
+pub fn expand(command: &str, file: Option<&Path>) -> Result<String, String> {
+    if !command.contains(FILE_PLACEHOLDER) {
+        return Ok(command.to_string());
+    }
+    let file =
+        file.ok_or_else(|| format!("No file is open to substitute for {}", FILE_PLACEHOLDER))?;
+    Ok(command.replace(FILE_PLACEHOLDER, &shell_quote(&file.to_string_lossy())))
+}
+
+/// Quote `s` as a single shell word.
+#[cfg(unix)]
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+#[cfg(not(unix))]
+fn shell_quote(s: &str) -> String {
+    format!("\"{}\"", s)
+}
+
+/// Run `command` through the platform shell on the normal terminal screen, then wait for Enter
+/// so its output can be read before the TUI redraws over it.
+pub fn run(command: &str) -> std::io::Result<ExitStatus> {
+    #[cfg(unix)]
+    let status = Command::new("sh").arg("-c").arg(command).status()?;
+    #[cfg(not(unix))]
+    let status = Command::new("cmd").arg("/C").arg(command).status()?;
+
+    let mut stdout = std::io::stdout();
+    match status.code() {
+        Some(0) => write!(stdout, "\n[datui] Press Enter to return")?,
+        Some(code) => write!(
+            stdout,
+            "\n[datui] Exited with status {code}. Press Enter to return"
+        )?,
+        None => write!(
+            stdout,
+            "\n[datui] Terminated by a signal. Press Enter to return"
+        )?,
+    }
+    stdout.flush()?;
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line)?;
+    Ok(status)
+}
+
+/// Stop the process as the shell's Ctrl-Z would; returns once it is resumed (`fg`).
+#[cfg(unix)]
+pub fn suspend() {
+    // SAFETY: raise() only delivers a signal to the calling process.
+    unsafe {
+        libc::raise(libc::SIGTSTP);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_quotes_the_current_file() {
+        let file = Path::new("/data/it's here.csv");
+        assert_eq!(expand("wc -l", None).unwrap(), "wc -l");
+        #[cfg(unix)]
+        assert_eq!(
+            expand("vd {file}", Some(file)).unwrap(),
+            r"vd '/data/it'\''s here.csv'"
+        );
+        assert!(expand("vd {file}", None).unwrap_err().contains("No file"));
+    }
+}
@@ -0,0 +1,273 @@
+//! File picker opened with `o`: browse local directories and open a data file in a new tab.
+//!
+//! Lists subdirectories first, then files, filtered by extension (supported data files, one
+//! format, or everything). Hidden entries are toggled with `.`. The highlighted file's format,
+//! size and estimated rows are shown as a preview.
+
+use crate::workspace::{detect_format, estimate_rows, format_name, RowEstimate};
+use crate::{CompressionFormat, FileFormat};
+use clap::ValueEnum;
+use ratatui::widgets::TableState;
+use std::path::{Path, PathBuf};
+
+/// Which files are listed. Directories are always listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionFilter {
+    /// Files datui can open.
+    Supported,
+    /// Files of one format.
+    Format(FileFormat),
+    All,
+}
+
+impl ExtensionFilter {
+    /// Next filter in the cycle: supported, each format, all, back to supported.
+    pub fn next(self) -> Self {
+        let formats = FileFormat::value_variants();
+        match self {
+            Self::Supported => Self::Format(formats[0]),
+            Self::Format(f) => match formats.iter().position(|&x| x == f) {
+                Some(i) if i + 1 < formats.len() => Self::Format(formats[i + 1]),
+                _ => Self::All,
+            },
+            Self::All => Self::Supported,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Supported => "data files",
+            Self::Format(f) => format_name(f),
+            Self::All => "all files",
+        }
+    }
+
+    fn accepts(self, format: Option<(FileFormat, Option<CompressionFormat>)>) -> bool {
+        match self {
+            Self::Supported => format.is_some(),
+            Self::Format(f) => format.is_some_and(|(g, _)| g == f),
+            Self::All => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FileBrowserEntry {
+    pub path: PathBuf,
+    pub name: String,
+    pub is_dir: bool,
+    pub size: u64,
+    /// Format detected from the extension; None for directories and unsupported files.
+    pub format: Option<(FileFormat, Option<CompressionFormat>)>,
+}
+
+impl FileBrowserEntry {
+    /// Format column text, e.g. "Parquet" or "CSV (gz)"; empty when not a data file.
+    pub fn format_label(&self) -> String {
+        match self.format {
+            Some((format, Some(c))) => format!("{} ({})", format_name(format), c.extension()),
+            Some((format, None)) => format_name(format).to_string(),
+            None => String::new(),
+        }
+    }
+}
+
+/// List the entries of `dir` that pass the filters: subdirectories first, then files, each by
+/// name.
+pub fn list_directory(
+    dir: &Path,
+    filter: ExtensionFilter,
+    show_hidden: bool,
+) -> std::io::Result<Vec<FileBrowserEntry>> {
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !show_hidden && name.starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        // Follow symlinks so linked directories can be entered
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        let is_dir = metadata.is_dir();
+        let format = if is_dir { None } else { detect_format(&path) };
+        if !is_dir && !filter.accepts(format) {
+            continue;
+        }
+        entries.push(FileBrowserEntry {
+            path,
+            name,
+            is_dir,
+            size: metadata.len(),
+            format,
+        });
+    }
+    entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+    Ok(entries)
+}
+
+/// The file picker popup.
+pub struct FileBrowser {
+    pub dir: PathBuf,
+    pub entries: Vec<FileBrowserEntry>,
+    pub table_state: TableState,
+    pub filter: ExtensionFilter,
+    pub show_hidden: bool,
+    /// Row estimate of the highlighted data file, refreshed as the highlight moves.
+    pub preview_rows: Option<RowEstimate>,
+    /// Rows that fit in the popup, as of the last render.
+    pub visible: usize,
+}
+
+impl FileBrowser {
+    /// Open the picker on `dir`, listing supported data files.
+    pub fn open(dir: PathBuf) -> std::io::Result<Self> {
+        let mut browser = Self {
+            dir,
+            entries: Vec::new(),
+            table_state: TableState::default(),
+            filter: ExtensionFilter::Supported,
+            show_hidden: false,
+            preview_rows: None,
+            visible: 1,
+        };
+        browser.reload(None)?;
+        Ok(browser)
+    }
+
+    /// List `dir` again with the current filters, highlighting `select` when it is listed.
+    fn reload(&mut self, select: Option<&Path>) -> std::io::Result<()> {
+        self.entries = list_directory(&self.dir, self.filter, self.show_hidden)?;
+        let index = select
+            .and_then(|p| self.entries.iter().position(|e| e.path == p))
+            .unwrap_or(0);
+        self.table_state
+            .select((!self.entries.is_empty()).then_some(index));
+        self.refresh_preview();
+        Ok(())
+    }
+
+    fn refresh_preview(&mut self) {
+        self.preview_rows = self.selected_entry().and_then(|e| {
+            let (format, compression) = e.format?;
+            Some(estimate_rows(&e.path, format, compression, e.size))
+        });
+    }
+
+    pub fn selected_entry(&self) -> Option<&FileBrowserEntry> {
+        self.table_state
+            .selected()
+            .and_then(|i| self.entries.get(i))
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let current = self.table_state.selected().unwrap_or(0);
+        let last = self.entries.len() - 1;
+        self.table_state
+            .select(Some(current.saturating_add_signed(delta).min(last)));
+        self.refresh_preview();
+    }
+
+    /// Enter the highlighted directory. Returns the highlighted file instead when it is one.
+    pub fn activate(&mut self) -> std::io::Result<Option<PathBuf>> {
+        let Some(entry) = self.selected_entry().cloned() else {
+            return Ok(None);
+        };
+        if !entry.is_dir {
+            return Ok(Some(entry.path));
+        }
+        let previous = std::mem::replace(&mut self.dir, entry.path);
+        if let Err(e) = self.reload(None) {
+            self.dir = previous;
+            return Err(e);
+        }
+        Ok(None)
+    }
+
+    /// Go to the parent directory, highlighting the one we came from.
+    pub fn go_up(&mut self) -> std::io::Result<()> {
+        let Some(parent) = self.dir.parent().map(Path::to_path_buf) else {
+            return Ok(());
+        };
+        let child = std::mem::replace(&mut self.dir, parent);
+        if let Err(e) = self.reload(Some(&child)) {
+            self.dir = child;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn toggle_hidden(&mut self) -> std::io::Result<()> {
+        self.show_hidden = !self.show_hidden;
+        let selected = self.selected_entry().map(|e| e.path.clone());
+        self.reload(selected.as_deref())
+    }
+
+    pub fn cycle_filter(&mut self) -> std::io::Result<()> {
+        self.filter = self.filter.next();
+        let selected = self.selected_entry().map(|e| e.path.clone());
+        self.reload(selected.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_browser_filters_and_navigates() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("sub/inner.parquet"), b"").unwrap();
+        std::fs::write(dir.path().join("b.csv"), b"a\n1\n2\n").unwrap();
+        std::fs::write(dir.path().join("a.parquet"), b"").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), b"").unwrap();
+        std::fs::write(dir.path().join(".hidden.csv"), b"").unwrap();
+
+        let mut browser = FileBrowser::open(dir.path().to_path_buf()).unwrap();
+        let names = |b: &FileBrowser| b.entries.iter().map(|e| e.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&browser), ["sub", "a.parquet", "b.csv"]);
+
+        browser.toggle_hidden().unwrap();
+        assert_eq!(
+            names(&browser),
+            ["sub", ".hidden.csv", "a.parquet", "b.csv"]
+        );
+        browser.toggle_hidden().unwrap();
+
+        browser.cycle_filter().unwrap();
+        assert_eq!(browser.filter, ExtensionFilter::Format(FileFormat::Parquet));
+        assert_eq!(names(&browser), ["sub", "a.parquet"]);
+        browser.filter = ExtensionFilter::Format(FileFormat::Protobuf);
+        browser.cycle_filter().unwrap();
+        assert_eq!(browser.filter, ExtensionFilter::All);
+        assert_eq!(names(&browser), ["sub", "a.parquet", "b.csv", "notes.txt"]);
+        browser.cycle_filter().unwrap();
+        assert_eq!(browser.filter, ExtensionFilter::Supported);
+
+        browser.move_by(2);
+        assert_eq!(browser.preview_rows, Some(RowEstimate::Exact(2)));
+        assert_eq!(
+            browser.activate().unwrap(),
+            Some(dir.path().join("b.csv")),
+            "a file is returned to open"
+        );
+
+        browser.move_by(isize::MIN);
+        assert_eq!(browser.activate().unwrap(), None);
+        assert_eq!(browser.dir, dir.path().join("sub"));
+        assert_eq!(names(&browser), ["inner.parquet"]);
+        browser.go_up().unwrap();
+        assert_eq!(browser.dir, dir.path());
+        assert_eq!(
+            browser.selected_entry().map(|e| e.name.as_str()),
+            Some("sub"),
+            "the directory we left stays highlighted"
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::value_counts::ValueCounts;
+use polars::prelude::*;
 use ratatui::widgets::ListState;
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy, serde::Serialize, serde::Deserialize)]
@@ -8,8 +10,14 @@ pub enum FilterOperator {
     Lt,
     GtEq,
     LtEq,
+    /// Value is `low, high`, both included.
+    Between,
+    /// Value is a comma-separated list.
+    In,
     Contains,
     NotContains,
+    IsNull,
+    IsNotNull,
 }
 
 impl FilterOperator {
@@ -21,11 +29,20 @@ impl FilterOperator {
             FilterOperator::Lt => "<",
             FilterOperator::GtEq => ">=",
             FilterOperator::LtEq => "<=",
+            FilterOperator::Between => "between",
+            FilterOperator::In => "in",
             FilterOperator::Contains => "contains",
             FilterOperator::NotContains => "!contains",
+            FilterOperator::IsNull => "is null",
+            FilterOperator::IsNotNull => "not null",
         }
     }
 
+    /// Whether the operator compares against a value; null checks ignore it.
+    pub fn takes_value(&self) -> bool {
+        !matches!(self, FilterOperator::IsNull | FilterOperator::IsNotNull)
+    }
+
     pub fn iterator() -> impl Iterator<Item = FilterOperator> {
         [
             FilterOperator::Eq,
@@ -34,8 +51,12 @@ impl FilterOperator {
             FilterOperator::Lt,
             FilterOperator::GtEq,
             FilterOperator::LtEq,
+            FilterOperator::Between,
+            FilterOperator::In,
             FilterOperator::Contains,
             FilterOperator::NotContains,
+            FilterOperator::IsNull,
+            FilterOperator::IsNotNull,
         ]
         .iter()
         .copied()
@@ -69,6 +90,13 @@ pub struct FilterStatement {
     pub logical_op: LogicalOperator,
 }
 
+impl FilterStatement {
+    /// The comma-separated values of `between` and `in`, trimmed.
+    pub fn values(&self) -> Vec<&str> {
+        self.value.split(',').map(str::trim).collect()
+    }
+}
+
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
 pub enum FilterFocus {
     #[default]
@@ -95,6 +123,159 @@ pub struct FilterModal {
 
     pub focus: FilterFocus,
     pub list_state: ListState,
+    /// Values of the column to pick from (Enter on the value field); None when closed.
+    pub value_picker: Option<ValuePicker>,
+    /// Start and end of a date column's range (Enter on the value field); None when closed.
+    pub date_range: Option<DateRange>,
+}
+
+/// Start and end dates typed for a `between` filter on a date or datetime column. They start
+/// out as the column's first and last values, looked up in the background.
+#[derive(Debug, Clone, Default)]
+pub struct DateRange {
+    pub column: String,
+    pub start: String,
+    pub end: String,
+    /// Typing into the end rather than the start.
+    pub editing_end: bool,
+    /// The column's first and last values; None while looking them up.
+    pub bounds: Option<(String, String)>,
+}
+
+impl DateRange {
+    pub fn new(column: String) -> Self {
+        Self {
+            column,
+            ..Self::default()
+        }
+    }
+
+    /// Fill in the column's first and last values, as defaults for fields left empty.
+    pub fn set_bounds(&mut self, min: String, max: String) {
+        if self.start.is_empty() {
+            self.start = min.clone();
+        }
+        if self.end.is_empty() {
+            self.end = max.clone();
+        }
+        self.bounds = Some((min, max));
+    }
+
+    /// First and last values of `column` in `lf`, written the way they're typed back in.
+    pub fn column_bounds(
+        lf: &LazyFrame,
+        column: &str,
+        polars_streaming: bool,
+    ) -> PolarsResult<(String, String)> {
+        let df = crate::statistics::collect_lazy(
+            lf.clone().select([
+                col(column).min().cast(DataType::String).alias("min"),
+                col(column).max().cast(DataType::String).alias("max"),
+            ]),
+            polars_streaming,
+        )?;
+        let bound = |name: &str| -> PolarsResult<Option<String>> {
+            Ok(df.column(name)?.str()?.get(0).map(str::to_string))
+        };
+        match (bound("min")?, bound("max")?) {
+            (Some(min), Some(max)) => Ok((min, max)),
+            _ => Err(PolarsError::ComputeError(
+                format!("{} has no values", column).into(),
+            )),
+        }
+    }
+
+    /// The field being typed into.
+    pub fn field_mut(&mut self) -> &mut String {
+        if self.editing_end {
+            &mut self.end
+        } else {
+            &mut self.start
+        }
+    }
+}
+
+/// The most frequent values of a column, counted in the background, to pick one or several as
+/// the filter's value instead of typing them.
+#[derive(Debug, Clone, Default)]
+pub struct ValuePicker {
+    pub column: String,
+    /// Values with their counts, most frequent first; None while counting.
+    pub values: Option<Vec<(String, usize)>>,
+    /// Whether each value is ticked (Space).
+    pub picked: Vec<bool>,
+    pub list_state: ListState,
+}
+
+impl ValuePicker {
+    pub fn new(column: String) -> Self {
+        Self {
+            column,
+            ..Self::default()
+        }
+    }
+
+    /// Fill in the counted values. Null can't be compared against, so it isn't listed.
+    pub fn set_counts(&mut self, counts: &ValueCounts) {
+        let values: Vec<(String, usize)> = counts
+            .values
+            .iter()
+            .filter(|(value, _)| !value.is_null())
+            .map(|(value, count)| (value_text(value), *count))
+            .collect();
+        self.picked = vec![false; values.len()];
+        self.list_state.select((!values.is_empty()).then_some(0));
+        self.values = Some(values);
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        let len = self.values.as_ref().map_or(0, Vec::len);
+        if len > 0 {
+            let i = self.list_state.selected().unwrap_or(0);
+            self.list_state
+                .select(Some(i.saturating_add_signed(delta).min(len - 1)));
+        }
+    }
+
+    /// Tick or untick the selected value.
+    pub fn toggle(&mut self) {
+        if let Some(picked) = self
+            .list_state
+            .selected()
+            .and_then(|i| self.picked.get_mut(i))
+        {
+            *picked = !*picked;
+        }
+    }
+
+    /// The ticked values, or the selected one when none are ticked.
+    pub fn chosen(&self) -> Vec<&str> {
+        let Some(values) = self.values.as_ref() else {
+            return Vec::new();
+        };
+        let ticked: Vec<&str> = values
+            .iter()
+            .zip(&self.picked)
+            .filter(|(_, picked)| **picked)
+            .map(|((value, _), _)| value.as_str())
+            .collect();
+        if !ticked.is_empty() {
+            return ticked;
+        }
+        self.list_state
+            .selected()
+            .and_then(|i| values.get(i))
+            .map(|(value, _)| vec![value.as_str()])
+            .unwrap_or_default()
+    }
+}
+
+/// A value as typed in the filter's value field: strings without quotes.
+pub fn value_text(value: &AnyValue) -> String {
+    value
+        .get_str()
+        .map(str::to_string)
+        .unwrap_or_else(|| value.to_string())
 }
 
 impl FilterModal {
@@ -125,6 +306,49 @@ impl FilterModal {
         self.focus = FilterFocus::Column;
     }
 
+    /// Use the values chosen in the value picker as the new filter's value: several become an
+    /// `in` list, one replaces the value and keeps the operator when it compares against one.
+    pub fn pick_values(&mut self) {
+        let Some(picker) = self.value_picker.take() else {
+            return;
+        };
+        let chosen = picker.chosen();
+        let op = FilterOperator::iterator()
+            .nth(self.new_operator_idx)
+            .unwrap_or(FilterOperator::Eq);
+        let op = match chosen.len() {
+            0 => return,
+            1 if op.takes_value()
+                && !matches!(op, FilterOperator::Between | FilterOperator::In) =>
+            {
+                op
+            }
+            1 => FilterOperator::Eq,
+            _ => FilterOperator::In,
+        };
+        self.new_operator_idx = FilterOperator::iterator()
+            .position(|o| o == op)
+            .unwrap_or(0);
+        self.new_value = chosen.join(", ");
+    }
+
+    /// Use the date range as the new filter's value, as a `between` filter. Nothing happens
+    /// while a bound is empty.
+    pub fn use_date_range(&mut self) {
+        let Some(range) = self.date_range.as_ref() else {
+            return;
+        };
+        let (start, end) = (range.start.trim(), range.end.trim());
+        if start.is_empty() || end.is_empty() {
+            return;
+        }
+        self.new_value = format!("{}, {}", start, end);
+        self.new_operator_idx = FilterOperator::iterator()
+            .position(|o| o == FilterOperator::Between)
+            .unwrap_or(0);
+        self.date_range = None;
+    }
+
     /// Advance focus within body only (Column → ... → Statements). Returns true if we were on
     /// Statements and caller should move to footer (Apply).
     pub fn next_body_focus(&mut self) -> bool {
@@ -172,6 +396,69 @@ mod tests {
         assert_eq!(modal.focus, FilterFocus::Column);
     }
 
+    #[test]
+    fn test_value_picker_fills_value_and_operator() {
+        let counts = ValueCounts {
+            column: "region".to_string(),
+            dtype: polars::prelude::DataType::String,
+            values: vec![
+                (AnyValue::StringOwned("East".into()), 3),
+                (AnyValue::Null, 2),
+                (AnyValue::StringOwned("West".into()), 1),
+            ],
+            distinct: 3,
+            rows: 6,
+            total_rows: 6,
+            sampled: false,
+        };
+        let mut modal = FilterModal::new();
+        modal.new_operator_idx = 1; // !=
+        let mut picker = ValuePicker::new("region".to_string());
+        picker.set_counts(&counts);
+        assert_eq!(
+            picker.values.as_ref().unwrap().len(),
+            2,
+            "null isn't listed"
+        );
+        modal.value_picker = Some(picker.clone());
+        modal.pick_values();
+        assert!(modal.value_picker.is_none());
+        assert_eq!(modal.new_value, "East");
+        assert_eq!(modal.new_operator_idx, 1);
+
+        picker.toggle();
+        picker.move_by(1);
+        picker.toggle();
+        modal.value_picker = Some(picker);
+        modal.pick_values();
+        assert_eq!(modal.new_value, "East, West");
+        assert_eq!(
+            FilterOperator::iterator().nth(modal.new_operator_idx),
+            Some(FilterOperator::In)
+        );
+    }
+
+    #[test]
+    fn test_date_range_defaults_to_bounds_and_makes_between() {
+        let mut modal = FilterModal::new();
+        let mut range = DateRange::new("day".to_string());
+        range.start = "2024-02-01".to_string();
+        range.set_bounds("2024-01-01".to_string(), "2024-12-31".to_string());
+        assert_eq!(range.start, "2024-02-01", "typed value kept");
+        assert_eq!(range.end, "2024-12-31");
+        range.editing_end = true;
+        range.field_mut().truncate(5);
+        range.field_mut().push_str("06-30");
+        modal.date_range = Some(range);
+        modal.use_date_range();
+        assert!(modal.date_range.is_none());
+        assert_eq!(modal.new_value, "2024-02-01, 2024-06-30");
+        assert_eq!(
+            FilterOperator::iterator().nth(modal.new_operator_idx),
+            Some(FilterOperator::Between)
+        );
+    }
+
     #[test]
     fn test_filter_modal_add_statement() {
         let mut modal = FilterModal::new();
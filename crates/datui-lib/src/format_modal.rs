@@ -0,0 +1,220 @@
+//! Format modal: display format of one column (number style, decimals, thousands separators,
+//! currency symbol, or a date pattern). Opened with `f` on the leftmost scrolled column; Enter
+//! applies, Esc discards.
+
+use crate::column_format::{ColumnFormat, NumberStyle};
+use crate::widgets::text_input::TextInput;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FormatFocus {
+    #[default]
+    Style,
+    Decimals,
+    Thousands,
+    CurrencySymbol,
+    DateFormat,
+}
+
+impl FormatFocus {
+    const NUMBER_ORDER: [Self; 4] = [
+        Self::Style,
+        Self::Decimals,
+        Self::Thousands,
+        Self::CurrencySymbol,
+    ];
+}
+
+#[derive(Default)]
+pub struct FormatModal {
+    pub active: bool,
+    pub focus: FormatFocus,
+    /// Column being formatted.
+    pub column: String,
+    /// True for numeric columns; false for date/datetime/time columns (date pattern only).
+    pub numeric: bool,
+    /// Selected cell value, shown with the edited format applied as a preview.
+    pub sample: Option<polars::prelude::AnyValue<'static>>,
+    pub style: NumberStyle,
+    pub decimals_input: TextInput,
+    pub thousands: bool,
+    pub currency_input: TextInput,
+    pub date_format_input: TextInput,
+}
+
+impl FormatModal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open for `column` with its current format.
+    pub fn open(
+        &mut self,
+        column: String,
+        numeric: bool,
+        format: &ColumnFormat,
+        sample: Option<polars::prelude::AnyValue<'static>>,
+        theme: &crate::config::Theme,
+    ) {
+        self.active = true;
+        self.column = column;
+        self.numeric = numeric;
+        self.sample = sample;
+        self.focus = if numeric {
+            FormatFocus::Style
+        } else {
+            FormatFocus::DateFormat
+        };
+        self.style = format.style;
+        self.thousands = format.thousands;
+        self.decimals_input = TextInput::new().with_theme(theme);
+        self.decimals_input
+            .set_value(format.decimals.map(|d| d.to_string()).unwrap_or_default());
+        self.currency_input = TextInput::new().with_theme(theme);
+        self.currency_input
+            .set_value(format.currency_symbol.clone().unwrap_or_default());
+        self.date_format_input = TextInput::new().with_theme(theme);
+        self.date_format_input
+            .set_value(format.date_format.clone().unwrap_or_default());
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+        self.sample = None;
+    }
+
+    /// The format as edited, or a message when a field is invalid.
+    pub fn format(&self) -> Result<ColumnFormat, String> {
+        let format = if self.numeric {
+            let decimals = match self.decimals_input.value().trim() {
+                "" => None,
+                text => Some(
+                    text.parse::<usize>()
+                        .map_err(|_| format!("Decimals must be a whole number: {}", text))?,
+                ),
+            };
+            let symbol = self.currency_input.value().trim();
+            ColumnFormat {
+                style: self.style,
+                decimals,
+                thousands: self.thousands,
+                currency_symbol: (!symbol.is_empty()).then(|| symbol.to_string()),
+                date_format: None,
+            }
+        } else {
+            let pattern = self.date_format_input.value().trim();
+            ColumnFormat {
+                date_format: (!pattern.is_empty()).then(|| pattern.to_string()),
+                ..Default::default()
+            }
+        };
+        format.validate()?;
+        Ok(format)
+    }
+
+    pub fn next_focus(&mut self) {
+        self.move_focus(1);
+    }
+
+    pub fn prev_focus(&mut self) {
+        self.move_focus(FormatFocus::NUMBER_ORDER.len() - 1);
+    }
+
+    fn move_focus(&mut self, step: usize) {
+        if !self.numeric {
+            return;
+        }
+        let order = FormatFocus::NUMBER_ORDER;
+        let pos = order.iter().position(|&f| f == self.focus).unwrap_or(0);
+        self.focus = order[(pos + step) % order.len()];
+    }
+
+    /// Change the focused selector (style or thousands) forwards (`delta` > 0) or backwards.
+    /// No-op on text inputs.
+    pub fn cycle(&mut self, delta: i32) {
+        match self.focus {
+            FormatFocus::Style => {
+                let all = NumberStyle::ALL;
+                let idx = all.iter().position(|s| *s == self.style).unwrap_or(0);
+                let next = if delta < 0 {
+                    (idx + all.len() - 1) % all.len()
+                } else {
+                    (idx + 1) % all.len()
+                };
+                self.style = all[next];
+            }
+            FormatFocus::Thousands => self.thousands = !self.thousands,
+            _ => {}
+        }
+    }
+
+    pub fn focused_input(&mut self) -> Option<&mut TextInput> {
+        match self.focus {
+            FormatFocus::Decimals => Some(&mut self.decimals_input),
+            FormatFocus::CurrencySymbol => Some(&mut self.currency_input),
+            FormatFocus::DateFormat => Some(&mut self.date_format_input),
+            FormatFocus::Style | FormatFocus::Thousands => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme() -> crate::config::Theme {
+        let config = crate::config::AppConfig::default();
+        crate::config::Theme::from_config(&config.theme).unwrap()
+    }
+
+    #[test]
+    fn number_fields_round_trip() {
+        let format = ColumnFormat {
+            style: NumberStyle::Currency,
+            decimals: Some(2),
+            thousands: true,
+            currency_symbol: Some("€".into()),
+            date_format: None,
+        };
+        let mut modal = FormatModal::new();
+        modal.open("price".into(), true, &format, None, &theme());
+        assert_eq!(modal.format(), Ok(format));
+
+        modal.cycle(1);
+        assert_eq!(modal.style, NumberStyle::Number);
+        modal.next_focus();
+        modal.focused_input().unwrap().set_value("x".into());
+        assert!(modal.format().unwrap_err().contains("whole number"));
+        modal.focused_input().unwrap().set_value(String::new());
+        modal.next_focus();
+        modal.cycle(1);
+        modal.prev_focus();
+        modal.prev_focus();
+        assert_eq!(modal.focus, FormatFocus::Style);
+        let edited = modal.format().unwrap();
+        assert_eq!(edited.decimals, None);
+        assert!(!edited.thousands);
+    }
+
+    #[test]
+    fn temporal_columns_only_edit_the_pattern() {
+        let mut modal = FormatModal::new();
+        modal.open(
+            "day".into(),
+            false,
+            &ColumnFormat::default(),
+            None,
+            &theme(),
+        );
+        assert_eq!(modal.focus, FormatFocus::DateFormat);
+        modal.next_focus();
+        assert_eq!(modal.focus, FormatFocus::DateFormat);
+        assert!(modal.format().unwrap().is_plain());
+        modal.date_format_input.set_value("%d/%m".into());
+        assert_eq!(
+            modal.format().unwrap().date_format.as_deref(),
+            Some("%d/%m")
+        );
+        modal.date_format_input.set_value("%Q".into());
+        assert!(modal.format().is_err());
+    }
+}
@@ -0,0 +1,350 @@
+//! GroupBy modal: group the current view by one or more columns and aggregate other columns
+//! (sum, mean, count, ...). The aggregated result replaces the view; `R` returns to the raw
+//! data. Opened with `g`; Space toggles the item under the cursor, Enter applies, Esc discards.
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Aggregation applied to each value column. `Count` is the number of rows in the group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupAggregation {
+    Sum,
+    Mean,
+    Count,
+    Min,
+    Max,
+    Median,
+    NUnique,
+    First,
+    Last,
+}
+
+impl GroupAggregation {
+    pub const ALL: [GroupAggregation; 9] = [
+        Self::Sum,
+        Self::Mean,
+        Self::Count,
+        Self::Min,
+        Self::Max,
+        Self::Median,
+        Self::NUnique,
+        Self::First,
+        Self::Last,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sum => "sum",
+            Self::Mean => "mean",
+            Self::Count => "count",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Median => "median",
+            Self::NUnique => "n_unique",
+            Self::First => "first",
+            Self::Last => "last",
+        }
+    }
+
+    /// The aggregation of `column`, keeping the column's name.
+    pub fn of(self, column: &str) -> Expr {
+        let c = col(column);
+        match self {
+            Self::Sum => c.sum(),
+            Self::Mean => c.mean(),
+            Self::Count => c.count(),
+            Self::Min => c.min(),
+            Self::Max => c.max(),
+            Self::Median => c.median(),
+            Self::NUnique => c.n_unique(),
+            Self::First => c.first(),
+            Self::Last => c.last(),
+        }
+    }
+
+    fn expr(self, column: &str) -> Expr {
+        self.of(column)
+            .alias(format!("{}_{}", column, self.as_str()))
+    }
+}
+
+/// Spec for a group-by: each aggregation is applied to each value column, giving a column named
+/// `{column}_{aggregation}`. `count` without value columns gives the group size as `count`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupBySpec {
+    pub group_columns: Vec<String>,
+    #[serde(default)]
+    pub value_columns: Vec<String>,
+    pub aggregations: Vec<GroupAggregation>,
+}
+
+impl GroupBySpec {
+    /// Check the spec, returning a message for the first problem.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.group_columns.is_empty() {
+            return Err("Choose at least one column to group by".to_string());
+        }
+        if self.aggregations.is_empty() {
+            return Err("Choose at least one aggregation".to_string());
+        }
+        if self.value_columns.is_empty() && self.aggregations != [GroupAggregation::Count] {
+            return Err("Choose the columns to aggregate (only count works without)".to_string());
+        }
+        Ok(())
+    }
+
+    /// Aggregation expressions, in value column order.
+    pub fn agg_exprs(&self) -> Vec<Expr> {
+        if self.value_columns.is_empty() {
+            return vec![len().alias("count")];
+        }
+        self.value_columns
+            .iter()
+            .flat_map(|column| self.aggregations.iter().map(move |a| a.expr(column)))
+            .collect()
+    }
+
+    /// `lf` grouped and aggregated, sorted by the group columns.
+    pub fn apply(&self, lf: LazyFrame) -> LazyFrame {
+        let keys: Vec<Expr> = self.group_columns.iter().map(col).collect();
+        lf.group_by(keys.clone())
+            .agg(self.agg_exprs())
+            .sort_by_exprs(keys, Default::default())
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GroupByFocus {
+    #[default]
+    GroupColumns,
+    ValueColumns,
+    Aggregations,
+}
+
+#[derive(Default)]
+pub struct GroupByModal {
+    pub active: bool,
+    pub focus: GroupByFocus,
+    /// Columns of the view.
+    pub columns: Vec<String>,
+    /// Group columns, in the order they were chosen.
+    pub group_columns: Vec<String>,
+    pub value_columns: Vec<String>,
+    pub aggregations: Vec<GroupAggregation>,
+    /// Cursor in each list.
+    pub group_cursor: usize,
+    pub value_cursor: usize,
+    pub aggregation_cursor: usize,
+}
+
+impl GroupByModal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open for a view with `columns`, starting from `spec` (the last group-by) when its columns
+    /// are still there.
+    pub fn open(&mut self, columns: Vec<String>, spec: Option<&GroupBySpec>) {
+        self.active = true;
+        self.focus = GroupByFocus::GroupColumns;
+        self.group_cursor = 0;
+        self.value_cursor = 0;
+        self.aggregation_cursor = 0;
+        let keep = |names: &[String]| -> Vec<String> {
+            names
+                .iter()
+                .filter(|n| columns.contains(n))
+                .cloned()
+                .collect()
+        };
+        match spec {
+            Some(spec) => {
+                self.group_columns = keep(&spec.group_columns);
+                self.value_columns = keep(&spec.value_columns);
+                self.aggregations = spec.aggregations.clone();
+            }
+            None => {
+                self.group_columns.clear();
+                self.value_columns.clear();
+                self.aggregations = vec![GroupAggregation::Count];
+            }
+        }
+        self.columns = columns;
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+    }
+
+    /// Columns that can be aggregated: those not grouped by.
+    pub fn value_pool(&self) -> Vec<&String> {
+        self.columns
+            .iter()
+            .filter(|c| !self.group_columns.contains(c))
+            .collect()
+    }
+
+    /// The spec as edited, or a message when it isn't complete.
+    pub fn spec(&self) -> Result<GroupBySpec, String> {
+        let spec = GroupBySpec {
+            group_columns: self.group_columns.clone(),
+            value_columns: self
+                .value_pool()
+                .into_iter()
+                .filter(|c| self.value_columns.contains(c))
+                .cloned()
+                .collect(),
+            aggregations: GroupAggregation::ALL
+                .into_iter()
+                .filter(|a| self.aggregations.contains(a))
+                .collect(),
+        };
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    pub fn next_focus(&mut self) {
+        self.focus = match self.focus {
+            GroupByFocus::GroupColumns => GroupByFocus::ValueColumns,
+            GroupByFocus::ValueColumns => GroupByFocus::Aggregations,
+            GroupByFocus::Aggregations => GroupByFocus::GroupColumns,
+        };
+    }
+
+    pub fn prev_focus(&mut self) {
+        self.focus = match self.focus {
+            GroupByFocus::GroupColumns => GroupByFocus::Aggregations,
+            GroupByFocus::ValueColumns => GroupByFocus::GroupColumns,
+            GroupByFocus::Aggregations => GroupByFocus::ValueColumns,
+        };
+    }
+
+    /// Move the cursor of the focused list down (`delta` > 0) or up, wrapping around.
+    pub fn move_cursor(&mut self, delta: i32) {
+        let len = match self.focus {
+            GroupByFocus::GroupColumns => self.columns.len(),
+            GroupByFocus::ValueColumns => self.value_pool().len(),
+            GroupByFocus::Aggregations => GroupAggregation::ALL.len(),
+        };
+        let cursor = match self.focus {
+            GroupByFocus::GroupColumns => &mut self.group_cursor,
+            GroupByFocus::ValueColumns => &mut self.value_cursor,
+            GroupByFocus::Aggregations => &mut self.aggregation_cursor,
+        };
+        if len == 0 {
+            *cursor = 0;
+        } else if delta < 0 {
+            *cursor = (*cursor + len - 1) % len;
+        } else {
+            *cursor = (*cursor + 1) % len;
+        }
+    }
+
+    /// Toggle the item under the cursor of the focused list.
+    pub fn toggle(&mut self) {
+        fn toggle_in<T: PartialEq + Clone>(list: &mut Vec<T>, item: &T) {
+            match list.iter().position(|x| x == item) {
+                Some(i) => {
+                    list.remove(i);
+                }
+                None => list.push(item.clone()),
+            }
+        }
+        match self.focus {
+            GroupByFocus::GroupColumns => {
+                if let Some(column) = self.columns.get(self.group_cursor).cloned() {
+                    toggle_in(&mut self.group_columns, &column);
+                    self.value_cursor = 0;
+                }
+            }
+            GroupByFocus::ValueColumns => {
+                if let Some(column) = self
+                    .value_pool()
+                    .get(self.value_cursor)
+                    .map(|c| (*c).clone())
+                {
+                    toggle_in(&mut self.value_columns, &column);
+                }
+            }
+            GroupByFocus::Aggregations => {
+                let aggregation = GroupAggregation::ALL[self.aggregation_cursor];
+                toggle_in(&mut self.aggregations, &aggregation);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spec_aggregates_each_value_column() {
+        let lf = df!(
+            "region" => ["b", "a", "b", "a", "b"],
+            "sales" => [1i64, 2, 3, 4, 5],
+        )
+        .unwrap()
+        .lazy();
+        let spec = GroupBySpec {
+            group_columns: vec!["region".into()],
+            value_columns: vec!["sales".into()],
+            aggregations: vec![GroupAggregation::Sum, GroupAggregation::NUnique],
+        };
+        let df = spec.apply(lf.clone()).collect().unwrap();
+        let names: Vec<&str> = df.get_column_names().iter().map(|n| n.as_str()).collect();
+        assert_eq!(names, ["region", "sales_sum", "sales_n_unique"]);
+        let sums: Vec<i64> = df
+            .column("sales_sum")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(sums, [6, 9]);
+
+        let count_only = GroupBySpec {
+            value_columns: Vec::new(),
+            aggregations: vec![GroupAggregation::Count],
+            ..spec.clone()
+        };
+        let df = count_only.apply(lf).collect().unwrap();
+        assert_eq!(df.column("count").unwrap().len(), 2);
+
+        let no_values = GroupBySpec {
+            value_columns: Vec::new(),
+            ..spec
+        };
+        assert!(no_values.validate().is_err());
+    }
+
+    #[test]
+    fn modal_builds_spec_from_toggles() {
+        let mut modal = GroupByModal::new();
+        let columns = vec!["region".to_string(), "sales".to_string(), "qty".to_string()];
+        modal.open(columns, None);
+        assert!(modal.spec().unwrap_err().contains("group by"));
+        modal.toggle();
+        // Count alone works without value columns
+        assert_eq!(modal.spec().unwrap().value_columns, Vec::<String>::new());
+
+        // The value list leaves out group columns: sales, qty
+        modal.next_focus();
+        modal.move_cursor(1);
+        modal.toggle();
+        modal.next_focus();
+        modal.toggle(); // sum on
+        modal.move_cursor(1);
+        modal.move_cursor(1);
+        modal.toggle(); // count off
+        let spec = modal.spec().unwrap();
+        assert_eq!(spec.group_columns, ["region"]);
+        assert_eq!(spec.value_columns, ["qty"]);
+        assert_eq!(spec.aggregations, [GroupAggregation::Sum]);
+
+        // Reopening starts from the last spec
+        modal.open(vec!["region".into(), "qty".into()], Some(&spec));
+        assert_eq!(modal.spec(), Ok(spec));
+    }
+}
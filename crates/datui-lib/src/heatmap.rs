@@ -0,0 +1,206 @@
+//! Heatmap mode (`H`): numeric cells are shaded on a gradient from the theme's `heatmap_low`
+//! color at their column's minimum to `heatmap_high` at its maximum, so patterns show without
+//! opening a chart.
+//!
+//! Each column's range covers the whole view, not just the rows on screen, so a value keeps its
+//! shade while scrolling. Ranges are computed in the background for the current view (identified
+//! by its `len_generation`); views with at least `sampling_threshold` rows are sampled.
+
+use crate::config::rgb_to_256_color;
+use crate::sampling::{sample_lazy, SamplingStrategy};
+use crate::statistics::collect_lazy;
+use color_eyre::Result;
+use polars::prelude::*;
+use ratatui::style::Color;
+use std::collections::HashMap;
+
+/// Minimum and maximum of each numeric column, by column name.
+pub type ColumnRanges = HashMap<String, (f64, f64)>;
+
+/// Minimum and maximum of every numeric column of `lf`. Columns without a finite value are
+/// left out. `total_rows` is the view's row count when known.
+pub fn column_ranges(
+    lf: &LazyFrame,
+    total_rows: Option<usize>,
+    sampling_threshold: Option<usize>,
+    sampling: &SamplingStrategy,
+    polars_streaming: bool,
+) -> Result<ColumnRanges> {
+    let schema = lf.clone().collect_schema()?;
+    let numeric: Vec<String> = schema
+        .iter()
+        .filter(|(_, dtype)| dtype.is_primitive_numeric())
+        .map(|(name, _)| name.to_string())
+        .collect();
+    if numeric.is_empty() {
+        return Ok(ColumnRanges::new());
+    }
+    let source = match (sampling_threshold, total_rows) {
+        (Some(threshold), Some(total)) if total >= threshold => {
+            sample_lazy(lf, total, threshold, sampling, polars_streaming)?
+        }
+        _ => lf.clone(),
+    };
+    let exprs: Vec<Expr> = numeric
+        .iter()
+        .enumerate()
+        .flat_map(|(i, name)| {
+            let values = col(name.as_str()).cast(DataType::Float64);
+            [
+                values.clone().min().alias(format!("min_{i}")),
+                values.max().alias(format!("max_{i}")),
+            ]
+        })
+        .collect();
+    let df = collect_lazy(source.select(exprs), polars_streaming)?;
+    let value = |name: String| {
+        df.column(&name)
+            .ok()
+            .and_then(|c| c.f64().ok()?.get(0))
+            .filter(|v| v.is_finite())
+    };
+    Ok(numeric
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, name)| {
+            Some((
+                name,
+                (value(format!("min_{i}"))?, value(format!("max_{i}"))?),
+            ))
+        })
+        .collect())
+}
+
+/// Where `value` falls in `range`, from 0 (minimum) to 1 (maximum); 0.5 when every value is the
+/// same.
+pub fn position(value: f64, (min, max): (f64, f64)) -> f64 {
+    if max > min {
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    } else {
+        0.5
+    }
+}
+
+/// The color a fraction `t` (0 to 1) of the way from `low` to `high`. RGB and 256-palette colors
+/// are blended (a blend of palette colors is the nearest palette color); other colors (the 16
+/// basic ones) can't be, so the lower half takes `low` and the upper half `high`.
+pub fn shade(low: Color, high: Color, t: f64) -> Color {
+    let (Some(from), Some(to)) = (rgb(low), rgb(high)) else {
+        return if t < 0.5 { low } else { high };
+    };
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    let (r, g, b) = (mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2));
+    if matches!((low, high), (Color::Rgb(..), _) | (_, Color::Rgb(..))) {
+        Color::Rgb(r, g, b)
+    } else {
+        Color::Indexed(rgb_to_256_color(r, g, b))
+    }
+}
+
+/// RGB of an RGB color or of a 256-palette entry past the 16 basic colors (the 6×6×6 cube and
+/// the gray ramp, whose values are fixed).
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    match color {
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(i @ 16..=231) => {
+            let i = i - 16;
+            Some((
+                LEVELS[(i / 36) as usize],
+                LEVELS[(i / 6 % 6) as usize],
+                LEVELS[(i % 6) as usize],
+            ))
+        }
+        Color::Indexed(i @ 232..=255) => {
+            let v = 8 + 10 * (i - 232);
+            Some((v, v, v))
+        }
+        _ => None,
+    }
+}
+
+/// Heatmap mode and the column ranges of the current view.
+#[derive(Debug, Default)]
+pub struct Heatmap {
+    pub active: bool,
+    /// `len_generation` of the view the ranges belong to.
+    generation: u64,
+    ranges: Option<std::result::Result<ColumnRanges, String>>,
+    /// Generation being computed.
+    inflight: Option<u64>,
+}
+
+impl Heatmap {
+    /// Column ranges of the view with `generation`, when shown and computed.
+    pub fn ranges(&self, generation: u64) -> Option<&ColumnRanges> {
+        if !self.active || generation != self.generation {
+            return None;
+        }
+        self.ranges.as_ref()?.as_ref().ok()
+    }
+
+    /// Whether the ranges of the view with `generation` should be computed now: the heatmap is
+    /// on, they aren't known and nothing is being computed.
+    pub fn should_compute(&self, generation: u64) -> bool {
+        self.active
+            && self.inflight.is_none()
+            && (generation != self.generation || self.ranges.is_none())
+    }
+
+    /// Note that the ranges of the view with `generation` are being computed.
+    pub fn start(&mut self, generation: u64) {
+        self.generation = generation;
+        self.ranges = None;
+        self.inflight = Some(generation);
+    }
+
+    /// Store the ranges computed for the view with `generation` (or why they failed).
+    pub fn finish(&mut self, generation: u64, ranges: std::result::Result<ColumnRanges, String>) {
+        if self.inflight == Some(generation) {
+            self.inflight = None;
+        }
+        if generation == self.generation {
+            self.ranges = Some(ranges);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_cover_numeric_columns_only() {
+        let lf = df!(
+            "n" => [3i64, -1, 7],
+            "x" => [Some(0.5f64), None, Some(2.5)],
+            "s" => ["a", "b", "c"],
+            "empty" => [None::<i32>, None, None]
+        )
+        .unwrap()
+        .lazy();
+        let ranges =
+            column_ranges(&lf, Some(3), None, &SamplingStrategy::default(), false).unwrap();
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(ranges["n"], (-1.0, 7.0));
+        assert_eq!(ranges["x"], (0.5, 2.5));
+    }
+
+    #[test]
+    fn shades_blend_rgb_and_palette_colors() {
+        let (low, high) = (Color::Rgb(0, 0, 0), Color::Rgb(200, 100, 50));
+        assert_eq!(shade(low, high, 0.0), low);
+        assert_eq!(shade(low, high, 1.0), high);
+        assert_eq!(shade(low, high, 0.5), Color::Rgb(100, 50, 25));
+        // Palette colors stay in the palette: halfway between cube black and white is a gray.
+        assert_eq!(
+            shade(Color::Indexed(16), Color::Indexed(231), 0.5),
+            Color::Indexed(rgb_to_256_color(128, 128, 128))
+        );
+        // Basic colors can't be blended.
+        assert_eq!(shade(Color::Blue, Color::Red, 0.3), Color::Blue);
+        assert_eq!(shade(Color::Blue, Color::Red, 0.7), Color::Red);
+        assert_eq!(position(5.0, (0.0, 10.0)), 0.5);
+        assert_eq!(position(4.0, (4.0, 4.0)), 0.5);
+    }
+}
@@ -12,6 +12,7 @@ macro_rules! include_help {
     };
 }
 
+/// Template with `{action}` placeholders; render with `KeyBindings::render_help`.
 pub fn main_view() -> &'static str {
     include_help!("main_view")
 }
@@ -48,6 +49,30 @@ pub fn template() -> &'static str {
     include_help!("template")
 }
 
+pub fn workspace() -> &'static str {
+    include_help!("workspace")
+}
+
+pub fn analysis_time_gaps() -> &'static str {
+    include_help!("analysis_time_gaps")
+}
+
+pub fn analysis_cardinality() -> &'static str {
+    include_help!("analysis_cardinality")
+}
+
+pub fn analysis_missing_data() -> &'static str {
+    include_help!("analysis_missing_data")
+}
+
+pub fn analysis_duplicates() -> &'static str {
+    include_help!("analysis_duplicates")
+}
+
+pub fn analysis_crosstab() -> &'static str {
+    include_help!("analysis_crosstab")
+}
+
 pub fn analysis_distribution_detail() -> &'static str {
     include_help!("analysis_distribution_detail")
 }
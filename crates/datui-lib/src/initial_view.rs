@@ -0,0 +1,278 @@
+//! A view to open the data with, given by the caller instead of set up in the app (the Python
+//! module's `datui.show(lf, filters=..., template=...)`, or `--query`, `--filter` and friends):
+//! a saved template, then a query, filters, sort and column order over it.
+
+use crate::filter_modal::{FilterOperator, FilterStatement, LogicalOperator};
+use crate::query::{apply_query, parse_query};
+use crate::template::TemplateManager;
+use polars::prelude::LazyFrame;
+use std::fmt;
+
+/// Why a view can't be applied to the data, e.g. a filter on a column that doesn't exist.
+/// Bindings report it as an invalid argument (see [`crate::error_for_python`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidView(pub String);
+
+impl fmt::Display for InvalidView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for InvalidView {}
+
+#[derive(Debug, Clone, Default)]
+pub struct InitialView {
+    /// Name or id of a saved template, applied first.
+    pub template: Option<String>,
+    /// Query in datui's query syntax; replaces the template's query.
+    pub query: Option<String>,
+    /// Filters, all of which a row must pass; replace the template's filters.
+    pub filters: Vec<FilterStatement>,
+    /// Columns to sort by; replace the template's sort.
+    pub sort: Vec<String>,
+    pub descending: bool,
+    /// Columns shown first, in this order; the others follow.
+    pub columns: Vec<String>,
+}
+
+impl InitialView {
+    pub fn is_empty(&self) -> bool {
+        self.template.is_none()
+            && self.query.is_none()
+            && self.filters.is_empty()
+            && self.sort.is_empty()
+            && self.columns.is_empty()
+    }
+
+    /// The view given by `--template`, `--query`, `--filter` and `--sort`, if any.
+    pub fn from_args(args: &crate::cli::Args) -> Result<Option<Self>, InvalidView> {
+        let view = InitialView {
+            template: args.template.clone(),
+            query: args.query.clone(),
+            filters: args
+                .filter
+                .iter()
+                .map(|f| parse_filter(f))
+                .collect::<Result<_, _>>()?,
+            sort: args.sort.clone(),
+            descending: args.descending,
+            columns: Vec::new(),
+        };
+        Ok((!view.is_empty()).then_some(view))
+    }
+
+    /// Check the view against `lf` without reading any rows: the template must exist, the
+    /// query must run on the data and, when no template is named, the filter, sort and column
+    /// names must be columns of the result. (A template can rename and add columns, so with one
+    /// the names are checked when the view is applied.)
+    pub fn validate(&self, lf: LazyFrame, templates: &TemplateManager) -> Result<(), InvalidView> {
+        if let Some(name) = &self.template {
+            if find_template(templates, name).is_none() {
+                return Err(InvalidView(format!("Template not found: {name}")));
+            }
+        }
+        let query = self.query.as_deref().unwrap_or("");
+        let (cols, filter, group_by_cols, group_by_col_names) =
+            parse_query(query).map_err(|e| InvalidView(format!("Invalid query: {e}")))?;
+        let (_, schema) = apply_query(lf, cols, filter, group_by_cols, &group_by_col_names)
+            .map_err(|e| {
+                InvalidView(format!(
+                    "Invalid query: {}",
+                    crate::error_display::user_message_from_polars(&e)
+                ))
+            })?;
+        if self.template.is_some() {
+            return Ok(());
+        }
+        let names = self
+            .filters
+            .iter()
+            .map(|f| ("filter", &f.column))
+            .chain(self.sort.iter().map(|c| ("sort", c)))
+            .chain(self.columns.iter().map(|c| ("columns", c)));
+        for (what, name) in names {
+            if schema.get(name).is_none() {
+                return Err(InvalidView(format!("Unknown column in {what}: {name}")));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The saved template called `name`, or else the one whose id is `name`.
+pub fn find_template<'a>(
+    templates: &'a TemplateManager,
+    name: &str,
+) -> Option<&'a crate::template::Template> {
+    templates
+        .get_template_by_name(name)
+        .or_else(|| templates.get_template_by_id(name))
+}
+
+/// Parse a filter written as `column operator value`, e.g. `price > 10`, `name contains Ann`
+/// or `note is null`. Operators are those of the filter dialog (`=`, `!=`, `>`, `<`, `>=`,
+/// `<=`, `between`, `in`, `contains`, `!contains`, `is null`, `not null`), plus `==` and
+/// `is not null`; `between` takes `low and high` and `in` a list like `(a, b)`. Quotes
+/// around the value are dropped.
+pub fn parse_filter(text: &str) -> Result<FilterStatement, InvalidView> {
+    let text = text.trim();
+    let statement = |column: &str, operator, value: &str| {
+        let column = column.trim();
+        if column.is_empty() {
+            return Err(InvalidView(format!("Filter has no column: {text}")));
+        }
+        Ok(FilterStatement {
+            column: column.to_string(),
+            operator,
+            value: unquote(value.trim()).to_string(),
+            logical_op: LogicalOperator::And,
+        })
+    };
+    for (suffix, operator) in [
+        (" is not null", FilterOperator::IsNotNull),
+        (" not null", FilterOperator::IsNotNull),
+        (" is null", FilterOperator::IsNull),
+    ] {
+        if let Some(column) = text.strip_suffix(suffix) {
+            return statement(column, operator, "");
+        }
+    }
+    if let Some((column, range)) = text.split_once(" between ") {
+        let Some((low, high)) = range.split_once(" and ") else {
+            return Err(InvalidView(format!(
+                "Expected 'between low and high': {text}"
+            )));
+        };
+        let value = format!("{}, {}", unquote(low.trim()), unquote(high.trim()));
+        return statement(column, FilterOperator::Between, &value);
+    }
+    if let Some((column, list)) = text.split_once(" in (") {
+        let Some(list) = list.trim_end().strip_suffix(')') else {
+            return Err(InvalidView(format!("Expected 'in (a, b, ...)': {text}")));
+        };
+        let value = list
+            .split(',')
+            .map(|v| unquote(v.trim()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return statement(column, FilterOperator::In, &value);
+    }
+    for (word, operator) in [
+        (" !contains ", FilterOperator::NotContains),
+        (" contains ", FilterOperator::Contains),
+    ] {
+        if let Some((column, value)) = text.split_once(word) {
+            return statement(column, operator, value);
+        }
+    }
+    // Earliest operator wins; at the same position the longer one (`>=` before `>`)
+    let symbols = [
+        (">=", FilterOperator::GtEq),
+        ("<=", FilterOperator::LtEq),
+        ("!=", FilterOperator::NotEq),
+        ("==", FilterOperator::Eq),
+        ("=", FilterOperator::Eq),
+        (">", FilterOperator::Gt),
+        ("<", FilterOperator::Lt),
+    ];
+    symbols
+        .iter()
+        .filter_map(|(symbol, operator)| text.find(symbol).map(|at| (at, *symbol, *operator)))
+        .min_by_key(|(at, symbol, _)| (*at, usize::MAX - symbol.len()))
+        .map(|(at, symbol, operator)| statement(&text[..at], operator, &text[at + symbol.len()..]))
+        .unwrap_or_else(|| {
+            Err(InvalidView(format!(
+                "Filter needs an operator (=, !=, >, <, >=, <=, contains, is null, ...): {text}"
+            )))
+        })
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_parse_filter_operators() {
+        let f = parse_filter("price >= 10").unwrap();
+        assert_eq!(
+            (f.column.as_str(), f.operator, f.value.as_str()),
+            ("price", FilterOperator::GtEq, "10")
+        );
+        let f = parse_filter("unit price == 'a b'").unwrap();
+        assert_eq!(
+            (f.column.as_str(), f.operator, f.value.as_str()),
+            ("unit price", FilterOperator::Eq, "a b")
+        );
+        let f = parse_filter("name !contains Ann").unwrap();
+        assert_eq!(f.operator, FilterOperator::NotContains);
+        assert_eq!(
+            parse_filter("note is not null").unwrap().operator,
+            FilterOperator::IsNotNull
+        );
+        assert_eq!(parse_filter("note is null").unwrap().column, "note");
+        let f = parse_filter("price between 10 and 20").unwrap();
+        assert_eq!(
+            (f.operator, f.value.as_str()),
+            (FilterOperator::Between, "10, 20")
+        );
+        let f = parse_filter("city in ('a', \"b c\")").unwrap();
+        assert_eq!(
+            (f.operator, f.value.as_str()),
+            (FilterOperator::In, "a, b c")
+        );
+        assert!(parse_filter("price between 10").is_err());
+        assert!(parse_filter("price").is_err());
+        assert!(parse_filter("> 3").is_err());
+    }
+
+    #[test]
+    fn test_validate_checks_query_and_columns() {
+        let lf = df!("id" => [1i64, 2], "qty" => [3i64, 4]).unwrap().lazy();
+        let templates = TemplateManager::empty(&crate::config::ConfigManager::with_dir(
+            std::env::temp_dir().join("datui-initial-view-test"),
+        ));
+        let view = InitialView {
+            query: Some("select total: qty * 2, id".into()),
+            filters: vec![parse_filter("total > 4").unwrap()],
+            sort: vec!["id".into()],
+            ..Default::default()
+        };
+        assert!(view.validate(lf.clone(), &templates).is_ok());
+
+        let view = InitialView {
+            filters: vec![parse_filter("total > 4").unwrap()],
+            ..Default::default()
+        };
+        assert_eq!(
+            view.validate(lf.clone(), &templates).unwrap_err().0,
+            "Unknown column in filter: total"
+        );
+        let view = InitialView {
+            query: Some("select nope".into()),
+            ..Default::default()
+        };
+        assert!(view.validate(lf.clone(), &templates).is_err());
+        let view = InitialView {
+            template: Some("missing".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            view.validate(lf, &templates).unwrap_err().0,
+            "Template not found: missing"
+        );
+    }
+}
@@ -0,0 +1,697 @@
+//! Main-view key bindings. Each action (page_down, open_filter, open_chart, export, ...) has
+//! default keys; the `[keys]` config section gives an action other keys, e.g.
+//! `open_filter = "ctrl+s"` or `page_down = ["pagedown", "space"]`. Rebinding an action frees
+//! its default keys. Two actions on one key, or an action on a reserved key, is an error at
+//! startup. The main view help is rendered from the active bindings.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// The `[keys]` config section: action name to its keys.
+pub type KeysConfig = BTreeMap<String, KeySpec>;
+
+/// One key or a list of keys (an empty list unbinds the action).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeySpec {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeySpec {
+    pub fn keys(&self) -> &[String] {
+        match self {
+            Self::One(key) => std::slice::from_ref(key),
+            Self::Many(keys) => keys,
+        }
+    }
+}
+
+/// Keys the main view handles itself; they can't be given to an action. `z` and `f` start
+/// two-key commands (`za`, the `f=` quick filters).
+const RESERVED_KEYS: [&str; 10] = [
+    "?", "f1", "esc", "enter", "tab", "backtab", "ctrl+c", "ctrl+z", "z", "f",
+];
+
+/// A key with its Ctrl/Alt modifiers, e.g. `ctrl+f`, `G`, `pagedown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    /// Parse a key such as `s`, `S`, `ctrl+f`, `alt+x`, `shift+tab`, `pagedown`, `space` or `f5`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut rest = text.trim();
+        let mut modifiers = KeyModifiers::NONE;
+        'prefixes: loop {
+            for (prefix, modifier) in [
+                ("ctrl+", KeyModifiers::CONTROL),
+                ("control+", KeyModifiers::CONTROL),
+                ("alt+", KeyModifiers::ALT),
+                ("shift+", KeyModifiers::SHIFT),
+            ] {
+                let matches = rest
+                    .get(..prefix.len())
+                    .is_some_and(|p| p.eq_ignore_ascii_case(prefix));
+                if matches && rest.len() > prefix.len() {
+                    rest = &rest[prefix.len()..];
+                    modifiers |= modifier;
+                    continue 'prefixes;
+                }
+            }
+            break;
+        }
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => {
+                let c = if modifiers.contains(KeyModifiers::SHIFT) {
+                    c.to_ascii_uppercase()
+                } else if modifiers.contains(KeyModifiers::CONTROL) {
+                    c.to_ascii_lowercase()
+                } else {
+                    c
+                };
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c)
+            }
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "pagedown" | "pgdn" | "pgdown" => KeyCode::PageDown,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => {
+                    modifiers.remove(KeyModifiers::SHIFT);
+                    KeyCode::BackTab
+                }
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key \"{}\"", text)),
+                },
+            },
+        };
+        Ok(Self { code, modifiers })
+    }
+
+    /// The key of a terminal event. Shift is dropped for characters, which carry their case.
+    pub fn from_event(event: &KeyEvent) -> Self {
+        let mut modifiers = event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if event.modifiers.contains(KeyModifiers::SHIFT)
+            && !matches!(event.code, KeyCode::Char(_) | KeyCode::BackTab)
+        {
+            modifiers |= KeyModifiers::SHIFT;
+        }
+        Self {
+            code: event.code,
+            modifiers,
+        }
+    }
+
+    /// Name shown in help, e.g. `Ctrl+F`, `PgDown`, `s`.
+    pub fn label(&self) -> String {
+        let mut label = String::new();
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                label.push_str(name);
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => label.push_str("Space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                label.push(c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => label.push(c),
+            KeyCode::PageDown => label.push_str("PgDown"),
+            KeyCode::PageUp => label.push_str("PgUp"),
+            KeyCode::BackTab => label.push_str("Shift+Tab"),
+            KeyCode::Delete => label.push_str("Del"),
+            KeyCode::F(n) => label.push_str(&format!("F{}", n)),
+            code => label.push_str(&format!("{:?}", code)),
+        }
+        label
+    }
+}
+
+/// Main-view actions that can be bound to keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Action {
+    Quit,
+    ScrollDown,
+    ScrollUp,
+    ScrollLeft,
+    ScrollRight,
+    PageDown,
+    PageUp,
+    HalfPageDown,
+    HalfPageUp,
+    FirstRow,
+    LastRow,
+    GoToLine,
+    GoToColumn,
+    OpenQuery,
+    OpenSql,
+    NextMatch,
+    PrevMatch,
+    OpenChart,
+    MarkColumn,
+    ClearMarks,
+    OpenFilter,
+    OpenPivotMelt,
+    GroupBy,
+    Resample,
+    Analysis,
+    Aggregates,
+    ValueCounts,
+    ComputedColumn,
+    TimeZone,
+    CastColumn,
+    Columns,
+    Rolling,
+    ExpandColumn,
+    Sampling,
+    Export,
+    CopyCell,
+    CopyRow,
+    VisualBlock,
+    SelectRows,
+    PinRow,
+    CopyRowContext,
+    ExportRowContext,
+    CopyRowJson,
+    ReverseSort,
+    Reset,
+    ApplyTemplate,
+    Templates,
+    Info,
+    ColumnStats,
+    Heatmap,
+    RowNumbers,
+    FormatColumn,
+    WidenColumn,
+    NarrowColumn,
+    AutofitColumn,
+    Workspace,
+    OpenFile,
+    SwitchTable,
+    CsvDialect,
+    LoadReport,
+    AdjustSchema,
+    CloseTab,
+    PrevTab,
+    NextTab,
+    Shell,
+    CommandPalette,
+    Scripts,
+    SavedQueries,
+    Profile,
+}
+
+impl Action {
+    pub const ALL: [Action; 69] = [
+        Self::Quit,
+        Self::ScrollDown,
+        Self::ScrollUp,
+        Self::ScrollLeft,
+        Self::ScrollRight,
+        Self::PageDown,
+        Self::PageUp,
+        Self::HalfPageDown,
+        Self::HalfPageUp,
+        Self::FirstRow,
+        Self::LastRow,
+        Self::GoToLine,
+        Self::GoToColumn,
+        Self::OpenQuery,
+        Self::OpenSql,
+        Self::NextMatch,
+        Self::PrevMatch,
+        Self::OpenChart,
+        Self::MarkColumn,
+        Self::ClearMarks,
+        Self::OpenFilter,
+        Self::OpenPivotMelt,
+        Self::GroupBy,
+        Self::Resample,
+        Self::Analysis,
+        Self::Aggregates,
+        Self::ValueCounts,
+        Self::ComputedColumn,
+        Self::TimeZone,
+        Self::CastColumn,
+        Self::Columns,
+        Self::Rolling,
+        Self::ExpandColumn,
+        Self::Sampling,
+        Self::Export,
+        Self::CopyCell,
+        Self::CopyRow,
+        Self::VisualBlock,
+        Self::SelectRows,
+        Self::PinRow,
+        Self::CopyRowContext,
+        Self::ExportRowContext,
+        Self::CopyRowJson,
+        Self::ReverseSort,
+        Self::Reset,
+        Self::ApplyTemplate,
+        Self::Templates,
+        Self::Info,
+        Self::ColumnStats,
+        Self::Heatmap,
+        Self::RowNumbers,
+        Self::FormatColumn,
+        Self::WidenColumn,
+        Self::NarrowColumn,
+        Self::AutofitColumn,
+        Self::Workspace,
+        Self::OpenFile,
+        Self::SwitchTable,
+        Self::CsvDialect,
+        Self::LoadReport,
+        Self::AdjustSchema,
+        Self::CloseTab,
+        Self::PrevTab,
+        Self::NextTab,
+        Self::Shell,
+        Self::CommandPalette,
+        Self::Scripts,
+        Self::SavedQueries,
+        Self::Profile,
+    ];
+
+    /// Name in the `[keys]` config section and in help placeholders.
+    pub fn name(self) -> &'static str {
+        self.spec().0
+    }
+
+    /// Keys bound when the config doesn't set the action. The first is the one the main view
+    /// handles; the others are aliases of it.
+    pub fn default_keys(self) -> &'static [&'static str] {
+        self.spec().1
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.name() == name)
+    }
+
+    fn spec(self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            Self::Quit => ("quit", &["q", "Q"]),
+            Self::ScrollDown => ("scroll_down", &["j", "down"]),
+            Self::ScrollUp => ("scroll_up", &["k", "up"]),
+            Self::ScrollLeft => ("scroll_left", &["h", "left"]),
+            Self::ScrollRight => ("scroll_right", &["l", "right"]),
+            Self::PageDown => ("page_down", &["pagedown", "ctrl+f"]),
+            Self::PageUp => ("page_up", &["pageup", "ctrl+b"]),
+            Self::HalfPageDown => ("half_page_down", &["ctrl+d"]),
+            Self::HalfPageUp => ("half_page_up", &["ctrl+u"]),
+            Self::FirstRow => ("first_row", &["home"]),
+            Self::LastRow => ("last_row", &["end", "G"]),
+            Self::GoToLine => ("go_to_line", &[":"]),
+            Self::GoToColumn => ("go_to_column", &["|"]),
+            Self::OpenQuery => ("open_query", &["/"]),
+            Self::OpenSql => ("open_sql", &["S"]),
+            Self::NextMatch => ("next_match", &["n"]),
+            Self::PrevMatch => ("prev_match", &["alt+n"]),
+            Self::OpenChart => ("open_chart", &["c"]),
+            Self::MarkColumn => ("mark_column", &["v"]),
+            Self::ClearMarks => ("clear_marks", &["V"]),
+            Self::OpenFilter => ("open_filter", &["s"]),
+            Self::OpenPivotMelt => ("open_pivot_melt", &["p"]),
+            Self::GroupBy => ("group_by", &["g"]),
+            Self::Resample => ("resample", &["W"]),
+            Self::Analysis => ("analysis", &["a"]),
+            Self::Aggregates => ("aggregates", &["A"]),
+            Self::ValueCounts => ("value_counts", &["F"]),
+            Self::ComputedColumn => ("computed_column", &["+"]),
+            Self::TimeZone => ("time_zone", &["Z"]),
+            Self::CastColumn => ("cast_column", &["~"]),
+            Self::Columns => ("columns", &["#"]),
+            Self::Rolling => ("rolling", &["L"]),
+            Self::ExpandColumn => ("expand_column", &["u"]),
+            Self::Sampling => ("sampling", &["M"]),
+            Self::Export => ("export", &["e"]),
+            Self::CopyCell => ("copy_cell", &["y"]),
+            Self::CopyRow => ("copy_row", &["Y"]),
+            Self::VisualBlock => ("visual_block", &["ctrl+v"]),
+            Self::SelectRows => ("select_rows", &["m"]),
+            Self::PinRow => ("pin_row", &["P"]),
+            Self::CopyRowContext => ("copy_row_context", &["C"]),
+            Self::ExportRowContext => ("export_row_context", &["E"]),
+            Self::CopyRowJson => ("copy_row_json", &["J"]),
+            Self::ReverseSort => ("reverse_sort", &["r"]),
+            Self::Reset => ("reset", &["R"]),
+            Self::ApplyTemplate => ("apply_template", &["T"]),
+            Self::Templates => ("templates", &["t"]),
+            Self::Info => ("info", &["i"]),
+            Self::ColumnStats => ("column_stats", &["I"]),
+            Self::Heatmap => ("heatmap", &["H"]),
+            Self::RowNumbers => ("row_numbers", &["N"]),
+            Self::FormatColumn => ("format_column", &["%"]),
+            Self::WidenColumn => ("widen_column", &[">"]),
+            Self::NarrowColumn => ("narrow_column", &["<"]),
+            Self::AutofitColumn => ("autofit_column", &["="]),
+            Self::Workspace => ("workspace", &["w"]),
+            Self::OpenFile => ("open_file", &["o"]),
+            Self::SwitchTable => ("switch_table", &["O"]),
+            Self::CsvDialect => ("csv_dialect", &["D"]),
+            Self::LoadReport => ("load_report", &["B"]),
+            Self::AdjustSchema => ("adjust_schema", &["K"]),
+            Self::CloseTab => ("close_tab", &["X"]),
+            Self::PrevTab => ("prev_tab", &["["]),
+            Self::NextTab => ("next_tab", &["]"]),
+            Self::Shell => ("shell", &["!"]),
+            Self::CommandPalette => ("command_palette", &["ctrl+p"]),
+            Self::Scripts => ("scripts", &["$"]),
+            Self::SavedQueries => ("saved_queries", &["b"]),
+            Self::Profile => ("profile", &["U"]),
+        }
+    }
+
+    fn parsed_default_keys(self) -> Vec<Key> {
+        self.default_keys()
+            .iter()
+            .map(|k| Key::parse(k).expect("default keys parse"))
+            .collect()
+    }
+}
+
+/// The active keys of each action.
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    keys: BTreeMap<Action, Vec<Key>>,
+    actions: HashMap<Key, Action>,
+    /// Default keys of all actions, so those freed by a rebinding can be ignored.
+    defaults: HashSet<Key>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::from_config(&KeysConfig::new()).expect("default key bindings don't conflict")
+    }
+}
+
+impl KeyBindings {
+    /// Bindings from the `[keys]` section: the configured keys of each action it sets and the
+    /// default keys of the others. Fails on an unknown action or key, a reserved key, or a key
+    /// bound to two actions.
+    pub fn from_config(config: &KeysConfig) -> Result<Self, String> {
+        for name in config.keys() {
+            if Action::from_name(name).is_none() {
+                return Err(format!(
+                    "unknown action \"{}\"; actions are: {}",
+                    name,
+                    Action::ALL.map(Action::name).join(", ")
+                ));
+            }
+        }
+        let reserved: Vec<Key> = RESERVED_KEYS
+            .iter()
+            .map(|k| Key::parse(k).expect("reserved keys parse"))
+            .collect();
+        let mut keys = BTreeMap::new();
+        let mut actions: HashMap<Key, Action> = HashMap::new();
+        let mut defaults = HashSet::new();
+        for action in Action::ALL {
+            defaults.extend(action.parsed_default_keys());
+            let bound = match config.get(action.name()) {
+                Some(spec) => spec
+                    .keys()
+                    .iter()
+                    .map(|text| {
+                        let key =
+                            Key::parse(text).map_err(|e| format!("{}: {}", action.name(), e))?;
+                        if reserved.contains(&key) {
+                            return Err(format!(
+                                "{}: \"{}\" is reserved and can't be rebound",
+                                action.name(),
+                                text
+                            ));
+                        }
+                        Ok(key)
+                    })
+                    .collect::<Result<Vec<_>, String>>()?,
+                None => action.parsed_default_keys(),
+            };
+            for key in &bound {
+                if let Some(other) = actions.insert(*key, action) {
+                    if other != action {
+                        return Err(format!(
+                            "\"{}\" is bound to both {} and {}",
+                            key.label(),
+                            other.name(),
+                            action.name()
+                        ));
+                    }
+                }
+            }
+            keys.insert(action, bound);
+        }
+        Ok(Self {
+            keys,
+            actions,
+            defaults,
+        })
+    }
+
+    /// Active keys of `action`.
+    pub fn keys(&self, action: Action) -> &[Key] {
+        self.keys.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Action bound to the key of `event`.
+    pub fn action(&self, event: &KeyEvent) -> Option<Action> {
+        self.actions.get(&Key::from_event(event)).copied()
+    }
+
+    /// The event the main view should handle for `event`: the action's built-in key when `event`
+    /// is bound to an action, `event` itself for keys no action uses, and None for a default key
+    /// freed by a rebinding.
+    pub fn remap(&self, event: &KeyEvent) -> Option<KeyEvent> {
+        let key = Key::from_event(event);
+        match self.actions.get(&key) {
+            Some(action) => {
+                let defaults = action.parsed_default_keys();
+                if defaults.contains(&key) {
+                    return Some(*event);
+                }
+                let builtin = defaults[0];
+                Some(KeyEvent {
+                    code: builtin.code,
+                    modifiers: builtin.modifiers,
+                    kind: event.kind,
+                    state: event.state,
+                })
+            }
+            None if self.defaults.contains(&key) => None,
+            None => Some(*event),
+        }
+    }
+
+    /// Keys of `action` for help, e.g. `PgDown/Ctrl+F`; `(unbound)` without keys.
+    pub fn label(&self, action: Action) -> String {
+        let keys = self.keys(action);
+        if keys.is_empty() {
+            return "(unbound)".to_string();
+        }
+        keys.iter().map(Key::label).collect::<Vec<_>>().join("/")
+    }
+
+    /// Fill a help template: each `{action}` placeholder becomes the action's keys, and lines
+    /// whose key column starts with a placeholder are realigned. Other braces are left as-is.
+    pub fn render_help(&self, template: &str) -> String {
+        const KEY_COLUMN: usize = 18;
+        let fill = |text: &str| -> String {
+            let mut out = String::new();
+            let mut rest = text;
+            while let Some(start) = rest.find('{') {
+                out.push_str(&rest[..start]);
+                let after = &rest[start + 1..];
+                let action = after
+                    .find('}')
+                    .and_then(|end| Action::from_name(&after[..end]).map(|a| (a, end)));
+                match action {
+                    Some((action, end)) => {
+                        out.push_str(&self.label(action));
+                        rest = &after[end + 1..];
+                    }
+                    None => {
+                        out.push('{');
+                        rest = after;
+                    }
+                }
+            }
+            out.push_str(rest);
+            out
+        };
+        template
+            .lines()
+            .map(|line| {
+                let key_column = line
+                    .strip_prefix("  {")
+                    .and_then(|_| line.find("}:").map(|end| end + 2));
+                match key_column {
+                    Some(end) => {
+                        let keys = fill(&line[..end]);
+                        let description = line[end..].trim_start();
+                        // Indent plus key column, with at least one space before the description.
+                        let width = (KEY_COLUMN + 2).max(keys.chars().count() + 1);
+                        format!("{:<width$}{}", keys, description)
+                    }
+                    None => fill(line),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(entries: &[(&str, &[&str])]) -> KeysConfig {
+        entries
+            .iter()
+            .map(|(action, keys)| {
+                (
+                    action.to_string(),
+                    KeySpec::Many(keys.iter().map(|k| k.to_string()).collect()),
+                )
+            })
+            .collect()
+    }
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_keys_and_chords() {
+        let key = |text| Key::parse(text).unwrap();
+        assert_eq!(key("s").code, KeyCode::Char('s'));
+        assert_eq!(key("shift+s"), key("S"));
+        assert_eq!(
+            key("Ctrl+F"),
+            Key {
+                code: KeyCode::Char('f'),
+                modifiers: KeyModifiers::CONTROL
+            }
+        );
+        assert_eq!(key("shift+tab").code, KeyCode::BackTab);
+        assert_eq!(key("PgDn").code, KeyCode::PageDown);
+        assert_eq!(key("+").code, KeyCode::Char('+'));
+        assert_eq!(key("ctrl++").code, KeyCode::Char('+'));
+        assert_eq!(key("f5").code, KeyCode::F(5));
+        assert_eq!(key("space").code, KeyCode::Char(' '));
+        assert!(Key::parse("hyper+x").is_err());
+        assert!(Key::parse("f13").is_err());
+        assert_eq!(key("ctrl+f").label(), "Ctrl+F");
+        assert_eq!(key("pagedown").label(), "PgDown");
+        assert_eq!(
+            Key::from_event(&press(KeyCode::Char('S'), KeyModifiers::SHIFT)),
+            key("S")
+        );
+    }
+
+    #[test]
+    fn defaults_cover_every_action_without_conflicts() {
+        let bindings = KeyBindings::default();
+        for action in Action::ALL {
+            assert!(!bindings.keys(action).is_empty(), "{}", action.name());
+            assert_eq!(Action::from_name(action.name()), Some(action));
+        }
+        let event = press(KeyCode::Char('f'), KeyModifiers::CONTROL);
+        assert_eq!(bindings.action(&event), Some(Action::PageDown));
+        assert_eq!(bindings.remap(&event), Some(event), "aliases pass through");
+        let other = press(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(bindings.remap(&other), Some(other));
+    }
+
+    #[test]
+    fn rebinding_maps_to_builtin_key_and_frees_defaults() {
+        let bindings = KeyBindings::from_config(&config(&[
+            ("open_filter", &["ctrl+s"]),
+            ("page_down", &["space"]),
+        ]))
+        .unwrap();
+        let remapped = bindings
+            .remap(&press(KeyCode::Char('s'), KeyModifiers::CONTROL))
+            .unwrap();
+        assert_eq!(remapped.code, KeyCode::Char('s'));
+        assert_eq!(remapped.modifiers, KeyModifiers::NONE);
+        assert_eq!(
+            bindings.remap(&press(KeyCode::Char('s'), KeyModifiers::NONE)),
+            None,
+            "s no longer opens the filter"
+        );
+        assert_eq!(
+            bindings
+                .remap(&press(KeyCode::Char(' '), KeyModifiers::NONE))
+                .unwrap()
+                .code,
+            KeyCode::PageDown
+        );
+        assert_eq!(
+            bindings.remap(&press(KeyCode::PageDown, KeyModifiers::NONE)),
+            None
+        );
+        assert_eq!(bindings.label(Action::OpenFilter), "Ctrl+S");
+    }
+
+    #[test]
+    fn reports_conflicts_and_bad_entries() {
+        let err = KeyBindings::from_config(&config(&[("open_chart", &["s"])])).unwrap_err();
+        assert!(
+            err.contains("open_chart") && err.contains("open_filter"),
+            "{}",
+            err
+        );
+        // Moving the other action away resolves the conflict.
+        assert!(KeyBindings::from_config(&config(&[
+            ("open_chart", &["s"]),
+            ("open_filter", &["c"]),
+        ]))
+        .is_ok());
+        let err = KeyBindings::from_config(&config(&[("open_chart", &["?"])])).unwrap_err();
+        assert!(err.contains("reserved"), "{}", err);
+        let err = KeyBindings::from_config(&config(&[("export", &["f"])])).unwrap_err();
+        assert!(err.contains("\"f\" is reserved"), "{}", err);
+        let err = KeyBindings::from_config(&config(&[("open_chrt", &["x"])])).unwrap_err();
+        assert!(err.contains("unknown action \"open_chrt\""), "{}", err);
+        let err = KeyBindings::from_config(&config(&[("export", &["ctrl+nope"])])).unwrap_err();
+        assert!(err.starts_with("export: unknown key"), "{}", err);
+    }
+
+    #[test]
+    fn help_is_rendered_from_active_bindings() {
+        let bindings =
+            KeyBindings::from_config(&config(&[("open_chart", &["ctrl+g"]), ("export", &[])]))
+                .unwrap();
+        let help = bindings.render_help(
+            "Data Operations:\n  {open_chart}:     Open charts\n  {page_down}:  Page down\n  {export}:  Export\n  !:                Run ({file} = current file); {copy_cell} copies",
+        );
+        assert_eq!(
+            help,
+            "Data Operations:\n  Ctrl+G:           Open charts\n  PgDown/Ctrl+F:    Page down\n  (unbound):        Export\n  !:                Run ({file} = current file); y copies"
+        );
+    }
+}
@@ -1,10 +1,10 @@
 use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use polars::datatypes::AnyValue;
 use polars::datatypes::DataType;
 #[cfg(feature = "cloud")]
-use polars::io::cloud::{AmazonS3ConfigKey, CloudOptions};
-use polars::prelude::{col, len, DataFrame, LazyFrame, Schema};
+use polars::io::cloud::{AmazonS3ConfigKey, AzureConfigKey, CloudOptions};
+use polars::prelude::{col, len, CsvWriter, DataFrame, IdxSize, LazyFrame, Schema, SerWriter};
 #[cfg(feature = "cloud")]
 use polars::prelude::{PlPathRef, ScanArgsParquet};
 use std::path::{Path, PathBuf};
@@ -16,29 +16,94 @@ use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
 
 use ratatui::widgets::{Block, Clear};
 
+pub mod aggregate_peek;
+pub mod analysis_export;
 pub mod analysis_modal;
+pub mod analysis_plugin;
 pub mod cache;
+pub mod cardinality;
+pub mod cast_modal;
 pub mod chart_data;
 pub mod chart_export;
 pub mod chart_export_modal;
+pub mod chart_labels_modal;
 pub mod chart_modal;
 pub mod cli;
+mod clipboard;
 #[cfg(feature = "cloud")]
 mod cloud_hive;
+pub mod column_cast;
+pub mod column_format;
+pub mod column_ruler;
+pub mod column_stats;
+pub mod columns_modal;
+pub mod command_palette;
+pub mod computed_column;
+pub mod computed_column_modal;
+pub mod concat;
 pub mod config;
+pub mod crosstab;
+pub mod csv_dialect;
+pub mod data_diff;
+#[cfg(feature = "database")]
+pub mod db_import;
+pub mod duplicates;
 pub mod error_display;
+pub mod excel_range;
 pub mod export_modal;
+mod external_command;
+pub mod file_browser;
 pub mod filter_modal;
+pub mod format_modal;
+pub mod group_by_modal;
+pub mod heatmap;
 pub(crate) mod help_strings;
+pub mod hive_export;
+pub mod initial_view;
+pub mod keymap;
+pub mod load_profile;
+pub mod load_report;
+pub mod locale;
+pub mod migration;
+pub mod missing_data;
+pub mod mouse;
+mod notifications;
 pub mod pivot_melt_modal;
+pub mod profile_report;
+pub mod protobuf;
 mod query;
+pub mod record_json;
+pub mod record_view;
+pub mod regex_search;
 mod render;
+pub mod resample_modal;
+pub mod rolling_modal;
+#[cfg(feature = "cloud")]
+mod s3_browser;
+pub mod sampling;
+pub mod sampling_modal;
+pub mod saved_queries;
+pub mod schema_diff;
+pub mod schema_override;
+pub mod script;
+pub mod snapshot;
 pub mod sort_filter_modal;
 pub mod sort_modal;
 mod source;
+pub mod sqlite_export;
+pub mod sqlite_import;
 pub mod statistics;
+pub mod stats_compare;
+pub mod table_picker;
 pub mod template;
+pub mod terminal_background;
+pub mod time_gaps;
+pub mod time_zone_modal;
+pub mod value_counts;
 pub mod widgets;
+pub mod workspace;
+pub mod xlsx_export;
+pub mod yank;
 
 pub use cache::CacheManager;
 pub use cli::Args;
@@ -47,22 +112,33 @@ pub use config::{
 };
 
 use analysis_modal::{AnalysisModal, AnalysisProgress};
+use cast_modal::CastModal;
 use chart_export::{
-    write_box_plot_eps, write_box_plot_png, write_chart_eps, write_chart_png, write_heatmap_eps,
-    write_heatmap_png, BoxPlotExportBounds, ChartExportBounds, ChartExportFormat,
-    ChartExportSeries,
+    write_chart_export, BoxPlotExportBounds, ChartExportBand, ChartExportBounds, ChartExportFormat,
+    ChartExportRightAxis, ChartExportSeries, CorrelationExportBounds, ExportPlot,
 };
 use chart_export_modal::{ChartExportFocus, ChartExportModal};
+use chart_labels_modal::{ChartLabelsFocus, ChartLabelsModal};
 use chart_modal::{ChartFocus, ChartKind, ChartModal, ChartType};
+use computed_column_modal::ComputedColumnModal;
 pub use error_display::{error_for_python, ErrorKindForPython};
-use export_modal::{ExportFocus, ExportFormat, ExportModal};
+use export_modal::{ExportFocus, ExportFormat, ExportModal, ExportScope};
 use filter_modal::{FilterFocus, FilterOperator, FilterStatement, LogicalOperator};
+use format_modal::FormatModal;
+use group_by_modal::GroupByModal;
 use pivot_melt_modal::{MeltSpec, PivotMeltFocus, PivotMeltModal, PivotMeltTab, PivotSpec};
+pub use query::QueryEngine;
+use resample_modal::ResampleModal;
+use rolling_modal::RollingModal;
+use sampling_modal::SamplingModal;
 use sort_filter_modal::{SortFilterFocus, SortFilterModal, SortFilterTab};
 use sort_modal::{SortColumn, SortFocus};
+pub use source::STDIN_PATH;
+use sqlite_export::IfExists;
 pub use template::{Template, TemplateManager};
+use time_zone_modal::TimeZoneModal;
 use widgets::controls::Controls;
-use widgets::datatable::DataTableState;
+use widgets::datatable::{DataTableState, SourceFiles, TableHit, COLUMN_WIDTH_STEP};
 use widgets::debug::DebugState;
 use widgets::template_modal::{CreateFocus, TemplateFocus, TemplateModal, TemplateModalMode};
 use widgets::text_input::{TextInput, TextInputEvent};
@@ -71,9 +147,9 @@ use widgets::text_input::{TextInput, TextInputEvent};
 pub const APP_NAME: &str = "datui";
 
 /// Re-export compression format and file format from CLI module
-pub use cli::{CompressionFormat, FileFormat};
+pub use cli::{CompressionFormat, ConcatMode, FileFormat};
 
-/// Map FileFormat to ExportFormat for default export. Tsv/Psv map to Csv; Orc/Excel have no export variant.
+/// Map FileFormat to ExportFormat for default export. Tsv/Psv map to Csv; Orc and Protobuf have no export variant.
 fn file_format_to_export_format(f: FileFormat) -> Option<ExportFormat> {
     match f {
         FileFormat::Parquet => Some(ExportFormat::Parquet),
@@ -82,7 +158,9 @@ fn file_format_to_export_format(f: FileFormat) -> Option<ExportFormat> {
         FileFormat::Jsonl => Some(ExportFormat::Ndjson),
         FileFormat::Arrow => Some(ExportFormat::Ipc),
         FileFormat::Avro => Some(ExportFormat::Avro),
-        FileFormat::Orc | FileFormat::Excel => None,
+        FileFormat::Excel => Some(ExportFormat::Xlsx),
+        FileFormat::Sqlite => Some(ExportFormat::Sqlite),
+        FileFormat::Orc | FileFormat::Protobuf => None,
     }
 }
 
@@ -225,6 +303,12 @@ pub enum ParseStringsTarget {
 #[derive(Clone)]
 pub struct OpenOptions {
     pub delimiter: Option<u8>,
+    /// Quote character of delimited text; None reads quotes as ordinary characters. Default `"`.
+    pub quote_char: Option<u8>,
+    /// Escape character before a quote inside a quoted field (e.g. `\`). None: doubled quotes only.
+    pub escape_char: Option<u8>,
+    /// Lines of delimited text starting with this prefix are skipped.
+    pub comment_prefix: Option<String>,
     pub has_header: Option<bool>,
     pub skip_lines: Option<usize>,
     pub skip_rows: Option<usize>,
@@ -241,6 +325,10 @@ pub struct OpenOptions {
     pub row_start_index: usize,
     /// When true, use hive load path for directory/glob; single file uses normal load.
     pub hive: bool,
+    /// How multiple paths are combined (vertical = same schema; diagonal = union of columns).
+    pub concat: ConcatMode,
+    /// When true, multiple paths and Hive datasets get a `__source_file__` column with each row's file.
+    pub source_column: bool,
     /// When true (default), infer Hive/partitioned Parquet schema from one file for faster "Caching schema". When false, use Polars collect_schema().
     pub single_spine_schema: bool,
     /// When true, CSV reader tries to parse string columns as dates (e.g. YYYY-MM-DD, ISO datetime).
@@ -255,11 +343,33 @@ pub struct OpenOptions {
     pub temp_dir: Option<std::path::PathBuf>,
     /// Excel sheet: 0-based index or sheet name (CLI only).
     pub excel_sheet: Option<String>,
+    /// Excel cell range or defined name to load (CLI only). None loads the sheet's used cells.
+    pub excel_range: Option<String>,
+    /// Excel sheet row (1-based) of the column names (CLI only). None uses the first row read.
+    pub excel_header_row: Option<usize>,
+    /// SQLite, Postgres or MySQL table or view to load (CLI only). None picks the only table, or
+    /// for SQLite shows the table picker when there are several.
+    pub table: Option<String>,
+    /// SQL query whose result is loaded from a SQLite, Postgres or MySQL database instead of a
+    /// table (CLI only).
+    pub sql_query: Option<String>,
+    /// `.proto` schema for protobuf record files (CLI only).
+    pub proto_schema: Option<std::path::PathBuf>,
+    /// Protobuf message of each record (CLI only). None uses the schema's only message.
+    pub proto_message: Option<String>,
+    /// Rhai script to run on the data once it is loaded (CLI only).
+    pub script: Option<std::path::PathBuf>,
+    /// Template, query, filters, sort and column order to open the data with (`--query`,
+    /// `--filter`, ... or the Python module).
+    pub initial_view: Option<initial_view::InitialView>,
     /// S3/compatible overrides (env + CLI). Take precedence over config when building CloudOptions.
     pub s3_endpoint_url_override: Option<String>,
     pub s3_access_key_id_override: Option<String>,
     pub s3_secret_access_key_override: Option<String>,
     pub s3_region_override: Option<String>,
+    /// Azure Blob overrides (env + CLI). Take precedence over config when building CloudOptions.
+    pub azure_account_override: Option<String>,
+    pub azure_key_override: Option<String>,
     /// When true, use Polars streaming engine for LazyFrame collect when the streaming feature is enabled.
     pub polars_streaming: bool,
     /// When true, cast Date/Datetime pivot index columns to Int32 before pivot to avoid Polars 0.52 panic.
@@ -270,14 +380,29 @@ pub struct OpenOptions {
     pub infer_schema_length: Option<usize>,
     /// When true, CSV reader ignores parse errors and continues with the next batch.
     pub ignore_errors: bool,
+    /// When true, CSV and JSON Lines rows that don't parse are left out of the load and reported.
+    pub skip_bad_rows: bool,
+    /// CSV column types as "COL:TYPE" specs, read as given instead of inferred.
+    pub dtypes: Option<Vec<String>>,
+    /// When true, a CSV opens the schema step with its inferred column types before it is read.
+    pub adjust_schema: bool,
     /// When true, show the debug overlay (session info, performance, query, etc.).
     pub debug: bool,
+    /// When true, start without user templates or cached history and skip terminal theme detection.
+    pub safe_mode: bool,
+    /// Load profile to use. None picks one from the input size when `auto_load_profile` is set.
+    pub load_profile: Option<load_profile::LoadProfile>,
+    /// When true, choose a load profile from the input size on open (`performance.load_profiles`).
+    pub auto_load_profile: bool,
 }
 
 impl OpenOptions {
     pub fn new() -> Self {
         Self {
             delimiter: None,
+            quote_char: Some(b'"'),
+            escape_char: None,
+            comment_prefix: None,
             has_header: None,
             skip_lines: None,
             skip_rows: None,
@@ -291,6 +416,8 @@ impl OpenOptions {
             row_numbers: false,
             row_start_index: 1,
             hive: false,
+            concat: ConcatMode::Vertical,
+            source_column: false,
             single_spine_schema: true,
             parse_dates: true,
             parse_strings: None,
@@ -298,16 +425,32 @@ impl OpenOptions {
             decompress_in_memory: false,
             temp_dir: None,
             excel_sheet: None,
+            excel_range: None,
+            excel_header_row: None,
+            table: None,
+            sql_query: None,
+            proto_schema: None,
+            proto_message: None,
+            script: None,
+            initial_view: None,
             s3_endpoint_url_override: None,
             s3_access_key_id_override: None,
             s3_secret_access_key_override: None,
             s3_region_override: None,
+            azure_account_override: None,
+            azure_key_override: None,
             polars_streaming: true,
             workaround_pivot_date_index: true,
             null_values: None,
             infer_schema_length: None,
             ignore_errors: false,
+            skip_bad_rows: false,
+            dtypes: None,
+            adjust_schema: false,
             debug: false,
+            safe_mode: false,
+            load_profile: None,
+            auto_load_profile: false,
         }
     }
 }
@@ -364,6 +507,11 @@ impl OpenOptions {
 
         // File loading options: CLI args override config
         opts.delimiter = args.delimiter.or(config.file_loading.delimiter);
+        if let Some(cli::QuoteChar(quote_char)) = args.quote_char {
+            opts.quote_char = quote_char;
+        }
+        opts.escape_char = args.escape_char;
+        opts.comment_prefix = args.comment_prefix.clone();
         opts.skip_lines = args.skip_lines.or(config.file_loading.skip_lines);
         opts.skip_rows = args.skip_rows.or(config.file_loading.skip_rows);
         opts.skip_tail_rows = args.skip_tail_rows.or(config.file_loading.skip_tail_rows);
@@ -379,7 +527,9 @@ impl OpenOptions {
         opts.compression = args.compression;
 
         // Format: CLI only (auto-detect from extension when not specified)
-        opts.format = args.format;
+        opts.format = args
+            .format
+            .or(args.proto.is_some().then_some(FileFormat::Protobuf));
 
         // Display options: CLI args override config
         opts.pages_lookahead = args
@@ -399,6 +549,10 @@ impl OpenOptions {
 
         // Hive partitioning: CLI only (no config option yet)
         opts.hive = args.hive;
+        if let Some(concat) = args.concat {
+            opts.concat = concat;
+        }
+        opts.source_column = args.source_column;
 
         // Single-spine schema: CLI overrides config; default true
         opts.single_spine_schema = args
@@ -457,6 +611,13 @@ impl OpenOptions {
 
         // Excel sheet (CLI only)
         opts.excel_sheet = args.excel_sheet.clone();
+        opts.excel_range = args.excel_range.clone();
+        opts.excel_header_row = args.excel_header_row.map(|row| row as usize);
+        opts.table = args.table.clone();
+        opts.sql_query = args.sql_query.clone();
+        opts.proto_schema = args.proto.clone();
+        opts.script = args.script.clone();
+        opts.proto_message = args.proto_message.clone();
 
         // S3/compatible overrides: env then CLI (CLI wins). Env vars match AWS SDK (AWS_ENDPOINT_URL, etc.)
         opts.s3_endpoint_url_override = args
@@ -478,13 +639,38 @@ impl OpenOptions {
             .or_else(|| std::env::var("AWS_REGION").ok())
             .or_else(|| std::env::var("AWS_DEFAULT_REGION").ok());
 
+        // Azure overrides: env then CLI (CLI wins). AZURE_STORAGE_ACCOUNT / AZURE_STORAGE_KEY are
+        // the az CLI's names. Other AZURE_* credentials (SAS, service principal) are read by the
+        // Azure client itself.
+        opts.azure_account_override = args
+            .azure_account
+            .clone()
+            .or_else(|| std::env::var("AZURE_STORAGE_ACCOUNT_NAME").ok())
+            .or_else(|| std::env::var("AZURE_STORAGE_ACCOUNT").ok());
+        opts.azure_key_override = args
+            .azure_key
+            .clone()
+            .or_else(|| std::env::var("AZURE_STORAGE_ACCOUNT_KEY").ok())
+            .or_else(|| std::env::var("AZURE_STORAGE_KEY").ok());
+
         opts.polars_streaming = config.performance.polars_streaming;
 
+        // Load profiles: config enables them unless a performance flag was given explicitly,
+        // in which case the user's settings are taken as-is.
+        opts.auto_load_profile = config.performance.load_profiles
+            && args.sampling_threshold.is_none()
+            && args.pages_lookahead.is_none()
+            && args.pages_lookback.is_none()
+            && args.polars_streaming.is_none();
+
         opts.workaround_pivot_date_index = args.workaround_pivot_date_index.unwrap_or(true);
 
         // Debug: CLI flag overrides config
         opts.debug = args.debug || config.debug.enabled;
 
+        // Safe mode: CLI only
+        opts.safe_mode = args.safe_mode;
+
         // Null values: merge config list with CLI list (CLI appended); if either is non-empty, set
         let config_nulls = config.file_loading.null_values.as_deref().unwrap_or(&[]);
         let cli_nulls = &args.null_value;
@@ -512,6 +698,26 @@ impl OpenOptions {
             .or(config.file_loading.ignore_errors)
             .unwrap_or(false);
 
+        // Skip malformed CSV / JSON Lines rows: CLI overrides config; default false
+        opts.skip_bad_rows = args
+            .skip_bad_rows
+            .or(config.file_loading.skip_bad_rows)
+            .unwrap_or(false);
+
+        // Column types: config list with CLI specs appended (later specs win)
+        let config_dtypes = config.file_loading.dtypes.as_deref().unwrap_or(&[]);
+        opts.dtypes = Some(
+            config_dtypes
+                .iter()
+                .chain(args.dtypes.iter())
+                .cloned()
+                .collect::<Vec<_>>(),
+        )
+        .filter(|specs| !specs.is_empty());
+
+        // Schema step before reading a CSV: CLI only
+        opts.adjust_schema = args.adjust_schema;
+
         opts
     }
 }
@@ -524,16 +730,28 @@ impl From<&cli::Args> for OpenOptions {
     }
 }
 
+/// What scanning an input gives besides its LazyFrame, for the state built from it.
+#[derive(Default)]
+struct ScanExtras {
+    concat_sources: Option<concat::ConcatSources>,
+    source_files: SourceFiles,
+}
+
+/// LazyFrame of a background scan, or the message of its error.
+type ScanResult = std::result::Result<(LazyFrame, ScanExtras), String>;
+
 pub enum AppEvent {
     Key(KeyEvent),
+    /// A click, drag or scroll wheel step; only sent when `ui.mouse` is on.
+    Mouse(MouseEvent),
     Open(Vec<PathBuf>, OpenOptions),
     /// Open with an existing LazyFrame (e.g. from Python binding); no file load.
     OpenLazyFrame(Box<LazyFrame>, OpenOptions),
+    /// Compare two files (`--diff`) in the background and show the result over the table.
+    OpenDiff(Box<data_diff::DiffRequest>, OpenOptions),
     DoLoad(Vec<PathBuf>, OpenOptions), // Internal event to actually perform loading after UI update
-    /// Scan paths and build LazyFrame; then emit DoLoadSchema (phased loading).
+    /// Scan paths into a LazyFrame on a worker; BackgroundScanReady then emits DoLoadSchema (phased loading).
     DoLoadScanPaths(Vec<PathBuf>, OpenOptions),
-    /// Build LazyFrame for CSV with --parse-strings (phase already set to "Scanning string columns" so UI shows it).
-    DoLoadCsvWithParseStrings(Vec<PathBuf>, OpenOptions),
     /// Perform HTTP download (next loop so "Downloading" can render first). Then emit DoLoadFromHttpTemp.
     #[cfg(feature = "http")]
     DoDownloadHttp(String, OpenOptions),
@@ -543,7 +761,10 @@ pub enum AppEvent {
     /// Perform GCS download to temp (next loop so "Downloading" can render first). Then emit DoLoadFromHttpTemp.
     #[cfg(feature = "cloud")]
     DoDownloadGcsToTemp(String, OpenOptions),
-    /// HTTP, S3, or GCS download finished; temp path is ready. Scan it and continue load.
+    /// Perform Azure Blob download to temp (next loop so "Downloading" can render first). Then emit DoLoadFromHttpTemp.
+    #[cfg(feature = "cloud")]
+    DoDownloadAzureToTemp(String, OpenOptions),
+    /// HTTP, S3, GCS or Azure download finished; temp path is ready. Scan it and continue load.
     #[cfg(any(feature = "http", feature = "cloud"))]
     DoLoadFromHttpTemp(PathBuf, OpenOptions),
     /// Update phase to "Caching schema" and emit DoLoadSchemaBlocking so UI can draw before blocking.
@@ -562,26 +783,32 @@ pub enum AppEvent {
     Search(String),
     SqlSearch(String),
     FuzzySearch(String),
+    /// Highlight cells matching a regex (in every column, or only the given one) and jump to the
+    /// first match; an empty pattern clears the search.
+    RegexSearch(String, Option<String>),
     Filter(Vec<FilterStatement>),
     Sort(Vec<String>, bool),         // Columns, Ascending
     ColumnOrder(Vec<String>, usize), // Column order, locked columns count
     Pivot(PivotSpec),
     Melt(MeltSpec),
     Export(PathBuf, ExportFormat, ExportOptions), // Path, format, options
+    /// Run a shell command with the TUI suspended; handled by the run loop, which owns the terminal.
+    RunExternalCommand(String),
     ChartExport(PathBuf, ChartExportFormat, String, u32, u32), // path, format, title, width, height
     DoChartExport(PathBuf, ChartExportFormat, String, u32, u32), // Deferred: run chart export
     Collect,
     Update,
     Reset,
-    Resize(u16, u16), // resized (width, height)
-    DoScrollDown,     // Deferred scroll: perform page_down after one frame (throbber)
-    DoScrollUp,       // Deferred scroll: perform page_up
-    DoScrollNext,     // Deferred scroll: perform select_next (one row down)
-    DoScrollPrev,     // Deferred scroll: perform select_previous (one row up)
-    DoScrollEnd,      // Deferred scroll: jump to last page (throbber)
-    DoScrollHalfDown, // Deferred scroll: half page down
-    DoScrollHalfUp,   // Deferred scroll: half page up
-    GoToLine(usize),  // Deferred: jump to line number (when collect needed)
+    Resize(u16, u16),  // resized (width, height)
+    DoScrollDown,      // Deferred scroll: perform page_down after one frame (throbber)
+    DoScrollUp,        // Deferred scroll: perform page_up
+    DoScrollNext,      // Deferred scroll: perform select_next (one row down)
+    DoScrollPrev,      // Deferred scroll: perform select_previous (one row up)
+    DoScrollEnd,       // Deferred scroll: jump to last page (throbber)
+    DoScrollHalfDown,  // Deferred scroll: half page down
+    DoScrollHalfUp,    // Deferred scroll: half page up
+    DoScrollRows(i64), // Deferred scroll: move the view by rows (mouse wheel or drag)
+    GoToLine(usize),   // Deferred: jump to line number (when collect needed)
     /// Run the next chunk of analysis (describe/distribution); drives per-column progress.
     AnalysisChunk,
     /// Run distribution analysis (deferred so progress overlay can show first).
@@ -621,6 +848,24 @@ pub enum AppEvent {
     BackgroundLenFailed {
         len_generation: u64,
     },
+    /// Background task completed: schema comparison of the workspace files.
+    BackgroundSchemaDiffReady {
+        generation: u64,
+        diff: Box<schema_diff::SchemaDiff>,
+    },
+    /// Phase of the load a worker is in, for the status line.
+    BackgroundLoadProgress {
+        generation: u64,
+        phase: String,
+        progress_percent: u16,
+    },
+    /// Background task completed: input scanned into a LazyFrame (or failed).
+    /// The result is stored in App::pending_scan_result, with the temp files it holds.
+    BackgroundScanReady {
+        generation: u64,
+        path: Option<PathBuf>,
+        options: OpenOptions,
+    },
     /// Background task completed: schema loaded and DataTableState constructed.
     /// The actual state is stored in App::pending_schema_result (to avoid cloning DataTableState).
     BackgroundSchemaReady {
@@ -629,6 +874,11 @@ pub enum AppEvent {
         options: OpenOptions,
         debug_label: Option<String>,
     },
+    /// Background task completed: a Small-profile source read into memory.
+    BackgroundInMemoryReady {
+        generation: u64,
+        df: Result<Box<DataFrame>, String>,
+    },
     /// Background task completed: export data collected.
     BackgroundExportCollected {
         generation: u64,
@@ -643,6 +893,18 @@ pub enum AppEvent {
         path: PathBuf,
         result: Result<(), String>,
     },
+    /// Background task completed: profiling report written to disk.
+    BackgroundProfileWritten {
+        generation: u64,
+        path: PathBuf,
+        result: Result<(), String>,
+    },
+    /// Background task completed: standard input copied to a temp file (or why it failed).
+    BackgroundStdinReady {
+        generation: u64,
+        temp_path: Result<PathBuf, String>,
+        options: OpenOptions,
+    },
     /// Background task completed: remote file downloaded to temp path.
     #[cfg(any(feature = "http", feature = "cloud"))]
     BackgroundDownloadReady {
@@ -650,6 +912,133 @@ pub enum AppEvent {
         temp_path: PathBuf,
         options: OpenOptions,
     },
+    /// Background task completed: objects listed under an S3 prefix, ready to browse.
+    #[cfg(feature = "cloud")]
+    BackgroundS3ListingReady {
+        generation: u64,
+        browser: Box<s3_browser::S3Browser>,
+    },
+    /// Background task completed: quick aggregates for the selected column.
+    BackgroundAggregatePeekReady {
+        generation: u64,
+        peek: Box<aggregate_peek::AggregatePeek>,
+    },
+    /// Background task completed: statistics of `column` for the column statistics sidebar (or
+    /// why they failed), for the view with `len_generation`.
+    BackgroundColumnStatsReady {
+        len_generation: u64,
+        column: String,
+        stats: Result<Box<column_stats::ColumnStats>, String>,
+    },
+    /// Background task completed: numeric column ranges for heatmap mode (or why they failed),
+    /// for the view with `len_generation`.
+    BackgroundHeatmapReady {
+        len_generation: u64,
+        ranges: Result<heatmap::ColumnRanges, String>,
+    },
+    /// Value counts moved on to phase `phase` (index into `value_counts::PHASES`).
+    BackgroundValueCountsPhase {
+        generation: u64,
+        phase: usize,
+    },
+    /// Background task completed: value counts for the selected column (or why they failed).
+    BackgroundValueCountsReady {
+        generation: u64,
+        counts: Result<Box<value_counts::ValueCounts>, String>,
+    },
+    /// Background task completed: values of a column for the filter's value picker (or why
+    /// they couldn't be counted).
+    BackgroundFilterValuesReady {
+        generation: u64,
+        counts: Result<Box<value_counts::ValueCounts>, String>,
+    },
+    /// Background task completed: first and last values of a date column for the filter's
+    /// date range (or why they couldn't be found).
+    BackgroundFilterDateRangeReady {
+        generation: u64,
+        bounds: Result<(String, String), String>,
+    },
+    /// Regex search scan progress: status text with the share of the view scanned and the
+    /// matches found so far.
+    BackgroundRegexScanProgress {
+        generation: u64,
+        status: String,
+    },
+    /// Background task completed: rows of the view matching the regex search `pattern` (or why
+    /// the scan failed); `forward` is the direction of the jump that started it.
+    BackgroundRegexMatchesReady {
+        generation: u64,
+        pattern: String,
+        plan: String,
+        rows: Result<Vec<usize>, String>,
+        forward: bool,
+    },
+    /// Background task completed: time series gaps (or why they could not be computed).
+    BackgroundTimeGapsReady {
+        generation: u64,
+        report: Result<Box<time_gaps::GapReport>, String>,
+    },
+    /// Background task completed: column cardinality ranking (or why it failed).
+    BackgroundCardinalityReady {
+        generation: u64,
+        report: Result<Box<cardinality::CardinalityReport>, String>,
+    },
+    /// Background task completed: null counts and pattern for Missing Data (or why it failed).
+    BackgroundMissingDataReady {
+        generation: u64,
+        report: Result<Box<missing_data::MissingDataReport>, String>,
+    },
+    /// Background task completed: duplicated row groups (or why they couldn't be found).
+    BackgroundDuplicatesReady {
+        generation: u64,
+        report: Result<Box<duplicates::DuplicateReport>, String>,
+    },
+    /// Background task completed: Crosstab table (or why it couldn't be built).
+    BackgroundCrosstabReady {
+        generation: u64,
+        table: Result<Box<crosstab::Crosstab>, String>,
+    },
+    /// Background task completed: result of the analysis plugin at `index`.
+    BackgroundPluginToolReady {
+        generation: u64,
+        index: usize,
+        result: Result<DataFrame, String>,
+    },
+    /// Background task completed: selected row and its context rows as CSV, ready to copy.
+    BackgroundRowContextReady {
+        generation: u64,
+        csv: String,
+        offset: usize,
+        rows: usize,
+    },
+    /// Selected row as pretty-printed JSON, ready to copy.
+    BackgroundRowJsonReady {
+        generation: u64,
+        json: String,
+        row: usize,
+    },
+    /// Background task completed: the selected row for the record view (or why it failed).
+    BackgroundRecordReady {
+        generation: u64,
+        record: Result<Box<record_view::RecordView>, String>,
+    },
+    /// Background task completed: the frame a script returned (or why it failed).
+    BackgroundScriptReady {
+        generation: u64,
+        result: Result<Box<LazyFrame>, String>,
+    },
+    /// Background task completed: the comparison of the `--diff` files (or why it failed).
+    BackgroundDataDiffReady {
+        diff: Result<Box<data_diff::DataDiff>, String>,
+    },
+    /// Cells copied with `y`, `Y` or from a visual block as tab-separated text, or the visible
+    /// page as a Markdown table or CSV.
+    BackgroundCellsReady {
+        generation: u64,
+        text: String,
+        /// What was copied, for the confirmation, e.g. "row 12".
+        description: String,
+    },
     /// Background task failed.
     BackgroundError {
         generation: u64,
@@ -662,6 +1051,16 @@ pub enum AppEvent {
 pub enum RunInput {
     Paths(Vec<PathBuf>, OpenOptions),
     LazyFrame(Box<LazyFrame>, OpenOptions),
+    /// Open the old file of a comparison and show its differences from the new one (`--diff`).
+    Diff(Box<data_diff::DiffRequest>, OpenOptions),
+}
+
+impl RunInput {
+    pub fn options(&self) -> &OpenOptions {
+        match self {
+            RunInput::Paths(_, o) | RunInput::LazyFrame(_, o) | RunInput::Diff(_, o) => o,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -672,6 +1071,13 @@ pub struct ExportOptions {
     pub json_compression: Option<CompressionFormat>,
     pub ndjson_compression: Option<CompressionFormat>,
     pub parquet_compression: Option<CompressionFormat>, // Not used in UI, but kept for API compatibility
+    pub parquet_partition_by: Vec<String>, // Non-empty: path is a hive-partitioned directory
+    pub row_range: Option<(usize, usize)>, // (offset, len) within the current view; None = all rows
+    pub scope: ExportScope,                // Dataset ignores the view (and row_range)
+    pub xlsx_sheet_name: String,
+    pub xlsx_autofit: bool,
+    pub sqlite_table: String,
+    pub sqlite_if_exists: IfExists,
 }
 
 #[derive(Debug, Default, PartialEq, Eq)]
@@ -691,15 +1097,23 @@ pub enum InputType {
     Search,
     Filter,
     GoToLine,
+    GoToColumn,
+    TemplateParameter,
+    ExternalCommand,
+    OpenFile,
+    Profile,
 }
 
-/// Query dialog tab: SQL-Like (current parser), Fuzzy, or SQL (future).
+/// Query dialog tab: SQL-Like (current parser), Fuzzy, SQL (Polars SQL context), Regex
+/// (highlight matching cells without filtering), or Column (Regex limited to the selected column).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum QueryTab {
     #[default]
     SqlLike,
     Fuzzy,
     Sql,
+    Regex,
+    Column,
 }
 
 impl QueryTab {
@@ -707,14 +1121,18 @@ impl QueryTab {
         match self {
             QueryTab::SqlLike => QueryTab::Fuzzy,
             QueryTab::Fuzzy => QueryTab::Sql,
-            QueryTab::Sql => QueryTab::SqlLike,
+            QueryTab::Sql => QueryTab::Regex,
+            QueryTab::Regex => QueryTab::Column,
+            QueryTab::Column => QueryTab::SqlLike,
         }
     }
     fn prev(self) -> Self {
         match self {
-            QueryTab::SqlLike => QueryTab::Sql,
+            QueryTab::SqlLike => QueryTab::Column,
             QueryTab::Fuzzy => QueryTab::SqlLike,
             QueryTab::Sql => QueryTab::Fuzzy,
+            QueryTab::Regex => QueryTab::Sql,
+            QueryTab::Column => QueryTab::Regex,
         }
     }
     fn index(self) -> usize {
@@ -722,8 +1140,15 @@ impl QueryTab {
             QueryTab::SqlLike => 0,
             QueryTab::Fuzzy => 1,
             QueryTab::Sql => 2,
+            QueryTab::Regex => 3,
+            QueryTab::Column => 4,
         }
     }
+
+    /// Regex and Column tabs: both edit the regex search.
+    fn is_regex(self) -> bool {
+        matches!(self, QueryTab::Regex | QueryTab::Column)
+    }
 }
 
 /// Focus within the query dialog: tab bar or input (SQL-Like only).
@@ -738,6 +1163,8 @@ pub enum QueryFocus {
 pub struct ErrorModal {
     pub active: bool,
     pub message: String,
+    /// Where OK was drawn in the last frame.
+    pub buttons: mouse::ButtonAreas<()>,
 }
 
 impl ErrorModal {
@@ -760,6 +1187,8 @@ impl ErrorModal {
 pub struct SuccessModal {
     pub active: bool,
     pub message: String,
+    /// Where OK was drawn in the last frame.
+    pub buttons: mouse::ButtonAreas<()>,
 }
 
 impl SuccessModal {
@@ -783,6 +1212,8 @@ pub struct ConfirmationModal {
     pub active: bool,
     pub message: String,
     pub focus_yes: bool, // true = Yes focused, false = No focused
+    /// Where Yes (true) and No (false) were drawn in the last frame.
+    pub buttons: mouse::ButtonAreas<bool>,
 }
 
 impl ConfirmationModal {
@@ -825,6 +1256,12 @@ pub enum PendingDownload {
         size: Option<u64>,
         options: OpenOptions,
     },
+    #[cfg(feature = "cloud")]
+    Azure {
+        url: String,
+        size: Option<u64>,
+        options: OpenOptions,
+    },
 }
 
 #[derive(Clone, Debug, Default)]
@@ -867,6 +1304,32 @@ struct AnalysisComputationState {
     sample_size: Option<usize>,
 }
 
+/// Control bar text while the regex search scans the view, e.g. "Searching name... (40%, 12
+/// matches)", or the rows scanned so far when the view's row count isn't known.
+fn regex_scan_status(column: Option<&str>, progress: Option<regex_search::ScanProgress>) -> String {
+    let target = column.map_or_else(String::new, |c| format!(" {}", c));
+    let matches = |n: usize| if n == 1 { "match" } else { "matches" };
+    match progress {
+        Some(p) => match p.percent() {
+            Some(percent) => format!(
+                "Searching{}... ({}%, {} {})",
+                target,
+                percent,
+                p.matches,
+                matches(p.matches)
+            ),
+            None => format!(
+                "Searching{}... ({} rows, {} {})",
+                target,
+                p.scanned,
+                p.matches,
+                matches(p.matches)
+            ),
+        },
+        None => format!("Searching{}...", target),
+    }
+}
+
 /// At most one query type can be active. Returns (query, sql_query, fuzzy_query) with only the
 /// active one set (SQL takes precedence over fuzzy over DSL query). Used when saving template settings.
 fn active_query_settings(
@@ -888,6 +1351,21 @@ fn active_query_settings(
     }
 }
 
+/// Row count of a view: `cached_rows` when known, else a `len()` query. For background tasks
+/// that need the total (e.g. to decide whether to sample).
+fn count_rows(lf: &LazyFrame, cached_rows: Option<usize>, streaming: bool) -> Result<usize> {
+    match cached_rows {
+        Some(n) => Ok(n),
+        None => crate::statistics::collect_lazy(lf.clone().select([len()]), streaming)
+            .map(|df| {
+                df.get(0)
+                    .and_then(|row| row.first().and_then(|v| v.extract::<usize>()))
+                    .unwrap_or(0)
+            })
+            .map_err(color_eyre::eyre::Report::from),
+    }
+}
+
 // Helper struct to save state before template application
 struct TemplateApplicationState {
     lf: LazyFrame,
@@ -900,33 +1378,83 @@ struct TemplateApplicationState {
     sort_ascending: bool,
     column_order: Vec<String>,
     locked_columns_count: usize,
+    computed_columns: Vec<computed_column::ComputedColumn>,
+    source: LazyFrame,
+    column_casts: Vec<column_cast::ColumnCast>,
+    column_renames: Vec<columns_modal::ColumnRename>,
+}
+
+/// Template waiting on parameter values; one is prompted for at a time.
+struct PendingTemplateParameters {
+    template: Template,
+    values: std::collections::HashMap<String, String>,
+    next: usize,
 }
 
 #[derive(Default)]
 pub(crate) struct ChartCache {
+    /// Frame the charts read and the row limit it was drawn for (see `App::chart_source`).
+    pub(crate) source: Option<(usize, LazyFrame)>,
     pub(crate) xy: Option<ChartCacheXY>,
+    pub(crate) band: Option<ChartCacheBand>,
     pub(crate) x_range: Option<ChartCacheXRange>,
     pub(crate) histogram: Option<ChartCacheHistogram>,
     pub(crate) box_plot: Option<ChartCacheBoxPlot>,
     pub(crate) kde: Option<ChartCacheKde>,
     pub(crate) heatmap: Option<ChartCacheHeatmap>,
+    pub(crate) category: Option<ChartCacheCategory>,
+    pub(crate) scatter: Option<ChartCacheScatter>,
 }
 
 impl ChartCache {
     fn clear(&mut self) {
         *self = Self::default();
     }
+
+    /// Points of the XY or Scatter chart as last drawn (log-scaled on a log-scale chart), one
+    /// slice per series; the crosshair moves over these.
+    fn plot_series(&self, modal: &ChartModal) -> Vec<&[(f64, f64)]> {
+        match modal.chart_kind {
+            ChartKind::XY => self
+                .xy
+                .as_ref()
+                .map(|c| {
+                    let series = match &c.series_log {
+                        Some(log) if modal.log_scale => log,
+                        _ => &c.series,
+                    };
+                    series.iter().map(Vec::as_slice).collect()
+                })
+                .unwrap_or_default(),
+            ChartKind::Scatter => self
+                .scatter
+                .iter()
+                .map(|c| c.data.points.as_slice())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 pub(crate) struct ChartCacheXY {
     pub(crate) x_column: String,
     pub(crate) y_columns: Vec<String>,
+    pub(crate) group_column: Option<String>,
     pub(crate) row_limit: Option<usize>,
     pub(crate) series: Vec<Vec<(f64, f64)>>,
+    pub(crate) names: Vec<String>,
     pub(crate) series_log: Option<Vec<Vec<(f64, f64)>>>,
     pub(crate) x_axis_kind: chart_data::XAxisTemporalKind,
 }
 
+pub(crate) struct ChartCacheBand {
+    pub(crate) x_column: String,
+    pub(crate) y_column: String,
+    pub(crate) source: chart_modal::BandSource,
+    pub(crate) row_limit: Option<usize>,
+    pub(crate) points: Vec<(f64, f64, f64)>,
+}
+
 pub(crate) struct ChartCacheXRange {
     pub(crate) x_column: String,
     pub(crate) row_limit: Option<usize>,
@@ -963,6 +1491,22 @@ pub(crate) struct ChartCacheHeatmap {
     pub(crate) data: chart_data::HeatmapData,
 }
 
+pub(crate) struct ChartCacheCategory {
+    pub(crate) column: String,
+    pub(crate) agg: chart_modal::CategoryAgg,
+    pub(crate) value: Option<String>,
+    pub(crate) top: usize,
+    pub(crate) row_limit: Option<usize>,
+    pub(crate) data: chart_data::CategoryBarData,
+}
+
+pub(crate) struct ChartCacheScatter {
+    pub(crate) x_column: String,
+    pub(crate) y_column: String,
+    pub(crate) row_limit: Option<usize>,
+    pub(crate) data: chart_data::ScatterData,
+}
+
 pub struct App {
     pub data_table_state: Option<DataTableState>,
     path: Option<PathBuf>,
@@ -976,16 +1520,28 @@ pub struct App {
     query_input: TextInput, // Query input widget with history support
     sql_input: TextInput,   // SQL tab input with its own history (id "sql")
     fuzzy_input: TextInput, // Fuzzy tab input with its own history (id "fuzzy")
+    regex_input: TextInput, // Regex tab input with its own history (id "regex")
     pub input_mode: InputMode,
     input_type: Option<InputType>,
     query_tab: QueryTab,
     query_focus: QueryFocus,
+    /// Candidates listed by Tab in the SQL-Like input, and the one filled in (Tab cycles them).
+    query_completion: Option<(query::Completion, Option<usize>)>,
     pub sort_filter_modal: SortFilterModal,
     pub pivot_melt_modal: PivotMeltModal,
     pub template_modal: TemplateModal,
     pub analysis_modal: AnalysisModal,
     pub chart_modal: ChartModal,
     pub chart_export_modal: ChartExportModal,
+    pub chart_labels_modal: ChartLabelsModal,
+    pub format_modal: FormatModal,
+    pub sampling_modal: SamplingModal,
+    pub computed_column_modal: ComputedColumnModal,
+    pub group_by_modal: GroupByModal,
+    pub resample_modal: ResampleModal,
+    pub rolling_modal: RollingModal,
+    pub time_zone_modal: TimeZoneModal,
+    pub cast_modal: CastModal,
     pub export_modal: ExportModal,
     pub(crate) chart_cache: ChartCache,
     error_modal: ErrorModal,
@@ -998,19 +1554,57 @@ pub struct App {
     /// Pending remote file download (HTTP/S3/GCS) while waiting for user confirmation. Size is from HEAD when available.
     #[cfg(any(feature = "http", feature = "cloud"))]
     pending_download: Option<PendingDownload>,
+    /// Object browser shown when an S3 bucket or prefix is opened instead of an object.
+    #[cfg(feature = "cloud")]
+    s3_browser: Option<s3_browser::S3Browser>,
     show_help: bool,
     help_scroll: usize, // Scroll position for help content
     cache: CacheManager,
     template_manager: TemplateManager,
     active_template_id: Option<String>, // ID of currently applied template
-    loading_state: LoadingState,        // Current loading state for progress indication
-    theme: Theme,                       // Color theme for UI rendering
+    pending_template: Option<PendingTemplateParameters>, // Template awaiting parameter values
+    workspace: Option<workspace::Workspace>, // File list and tabs when a directory was opened
+    aggregate_peek: Option<aggregate_peek::AggregatePeek>, // Transient aggregates popup; closed by the next key
+    cell_peek: Option<(String, String)>, // Transient (column, full value) popup from `za`; closed by the next key
+    value_counts: Option<value_counts::ValueCountsPopup>, // Value counts popup from `F`; Esc closes
+    record_view: Option<record_view::RecordView>, // Selected row as field: value lines from Enter; Esc closes
+    data_diff: Option<data_diff::DataDiffView>,   // Comparison of the `--diff` files; Esc closes
+    file_browser: Option<file_browser::FileBrowser>, // File picker from `o`; opens a file in a new tab
+    table_picker: Option<table_picker::TablePicker>, // Sheet or table picker (`O`, or a SQLite database with several tables)
+    sheet_preview: Option<excel_range::SheetPreview>, // First rows of a sheet, to choose its header row (`p` in the sheet picker)
+    csv_dialect: Option<csv_dialect::CsvDialectModal>, // CSV dialect modal (`D`, or a CSV that loads as one column or fails to parse)
+    load_report: Option<load_report::LoadReportPanel>, // Rows left out by skip_bad_rows (`B`, or after a load that skipped rows)
+    schema_modal: Option<schema_override::SchemaModal>, // Column types of a CSV to override (`K`, or --adjust-schema before it is read)
+    pub columns_modal: Option<columns_modal::ColumnsModal>, // Columns to rename, hide, duplicate and reorder (`#`)
+    pub pending_script: Option<PathBuf>,                    // --script, run once the data is loaded
+    pub pending_view: Option<initial_view::InitialView>, // OpenOptions::initial_view, applied once loaded
+    pub scripts_modal: Option<script::ScriptsModal>,     // Rhai scripts to run on the source (`$`)
+    pub saved_queries_modal: Option<saved_queries::SavedQueriesModal>, // Named queries to apply, save and keep in templates (`b`)
+    pub command_palette: Option<command_palette::CommandPalette>, // Fuzzy finder over columns and actions (Ctrl+P)
+    loading_csv: Option<(PathBuf, OpenOptions)>, // CSV file being loaded, until its first rows are in; a parse error offers the dialect modal
+    loading_source_files: SourceFiles, // Temp copy and load report of the file being loaded, for the state built from its LazyFrame
+    column_stats: column_stats::ColumnStatsSidebar, // Statistics sidebar for the selected column, toggled with `I`
+    heatmap: heatmap::Heatmap, // Heatmap mode for numeric columns, toggled with `H`
+    pending_z: bool,           // `z` pressed in the main table; the next key completes the command
+    pending_f: bool, // `f` pressed in the main table; `=` `!` `>` `<` next filter on the selected cell
+    pub column_ruler: column_ruler::ColumnRuler, // Ruler above the column headers (`zr` cycles it)
+    key_bindings: keymap::KeyBindings, // Main-view keys from the `[keys]` config section
+    mouse_drag_row: Option<u16>, // Screen row of the last left-button press or drag in the main table
+    external_command: String,    // Last command run with `!`; pre-fills the next prompt
+    loading_state: LoadingState, // Current loading state for progress indication
+    theme: Theme,                // Color theme for UI rendering
     sampling_threshold: Option<usize>, // None = no sampling (full data); Some(n) = sample when rows >= n
+    sampling_strategy: sampling::SamplingStrategy, // How rows are sampled when sampling_threshold applies
+    load_profile: Option<load_profile::LoadProfileInfo>, // Profile of the current view; None when profiles are off
+    concat_sources: Option<concat::ConcatSources>, // Files behind each column after `--concat diagonal`
+    source_tables: Option<table_picker::SourceTables>, // Sheets or tables of the loaded Excel or SQLite file
+    last_open: Option<(Vec<PathBuf>, OpenOptions)>, // Paths and options (before the profile) of the last Open, for reopening with another profile
     history_limit: usize, // History limit for all text inputs (from config.query.history_limit)
     table_cell_padding: u16, // Spaces between columns (from config.display.table_cell_padding)
     column_colors: bool, // When true, colorize table cells by column type (from config.display.column_colors)
-    runtime: tokio::runtime::Handle, // Tokio runtime handle for background tasks
-    task_generation: u64, // Incremented to invalidate stale background results
+    display_locale: locale::DisplayLocale, // Date/number display format (from config.display.locale)
+    runtime: tokio::runtime::Handle,       // Tokio runtime handle for background tasks
+    task_generation: u64,                  // Incremented to invalidate stale background results
     // `len_generation` of the in-flight background row-count, if any. Prevents re-spawning
     // the (potentially minutes-long) count on every scroll while it's still running.
     len_count_inflight: Option<u64>,
@@ -1019,8 +1613,13 @@ pub struct App {
     // misleading provisional total.
     len_count_failed: Option<u64>,
     pending_schema_result: std::sync::Arc<std::sync::Mutex<Option<(u64, DataTableState)>>>, // (generation, result) from background schema load
+    pending_scan_result: std::sync::Arc<std::sync::Mutex<Option<(u64, ScanResult)>>>, // (generation, result) from background scan
+    bg_workers: std::sync::Arc<std::sync::atomic::AtomicUsize>, // spawn_bg tasks still running; a load can be cancelled while one is
     pending_collect_result:
         std::sync::Arc<std::sync::Mutex<Option<(u64, crate::widgets::datatable::CollectResult)>>>, // (generation, result) from background buffer load
+    // Data rows that fit the main view as of the last render. A new view has visible_rows 0
+    // until it is first drawn; sizing its first buffer from this avoids a second collect.
+    viewport_rows_hint: usize,
     busy: bool,                     // When true, show throbber and ignore keys
     throbber_frame: u8,             // Spinner frame index (0..3) for control bar
     drain_keys_on_next_loop: bool,  // Main loop drains crossterm key buffer when true
@@ -1030,6 +1629,11 @@ pub struct App {
     /// Temp file path for HTTP-downloaded data; removed when user opens different data or exits.
     #[cfg(feature = "http")]
     http_temp_path: Option<PathBuf>,
+    /// Temp file holding data read from standard input; deleted when the app is dropped.
+    stdin_temp_path: Option<tempfile::TempPath>,
+    /// `--safe-mode` scratch directory standing in for the config and cache directories;
+    /// deleted when the app is dropped.
+    safe_mode_dir: Option<tempfile::TempDir>,
 }
 
 impl App {
@@ -1038,619 +1642,725 @@ impl App {
         self.busy
     }
 
-    /// Current background-task generation. Bumped each time work is spawned that should
-    /// invalidate prior in-flight tasks. Exposed for tests that need to construct
-    /// synthetic Background* events with a known-stale generation.
-    pub fn task_generation(&self) -> u64 {
-        self.task_generation
+    /// The aggregate peek popup currently shown, if any.
+    pub fn aggregate_peek(&self) -> Option<&aggregate_peek::AggregatePeek> {
+        self.aggregate_peek.as_ref()
     }
 
-    /// Returns true when the main loop should drain the crossterm key buffer after render.
-    pub fn should_drain_keys(&self) -> bool {
-        self.drain_keys_on_next_loop
+    pub fn value_counts(&self) -> Option<&value_counts::ValueCountsPopup> {
+        self.value_counts.as_ref()
     }
 
-    /// Clears the drain-keys request after the main loop has drained the buffer.
-    pub fn clear_drain_keys_request(&mut self) {
-        self.drain_keys_on_next_loop = false;
+    pub fn record_view(&self) -> Option<&record_view::RecordView> {
+        self.record_view.as_ref()
     }
 
-    pub fn send_event(&mut self, event: AppEvent) -> Result<()> {
-        self.events.send(event)?;
-        Ok(())
+    /// The data comparison shown with `--diff`, until closed.
+    pub fn data_diff(&self) -> Option<&data_diff::DataDiffView> {
+        self.data_diff.as_ref()
     }
 
-    /// Set loading state and phase so the progress dialog is visible. Used by run() to show
-    /// loading UI immediately when launching from LazyFrame (e.g. Python) before sending the open event.
-    pub fn set_loading_phase(&mut self, phase: impl Into<String>, progress_percent: u16) {
-        self.busy = true;
-        self.loading_state = LoadingState::Loading {
-            file_path: None,
-            file_size: 0,
-            current_phase: phase.into(),
-            progress_percent,
+    /// Load profile of the current view; None when profiles are off or the data came from a
+    /// LazyFrame.
+    pub fn load_profile(&self) -> Option<&load_profile::LoadProfileInfo> {
+        self.load_profile.as_ref()
+    }
+
+    /// The (column, full value) cell popup currently shown, if any.
+    pub fn cell_peek(&self) -> Option<(&str, &str)> {
+        self.cell_peek
+            .as_ref()
+            .map(|(column, value)| (column.as_str(), value.as_str()))
+    }
+
+    /// Show the full value of the selected row's truncated cell (or, when nothing is truncated,
+    /// the leftmost scrolled column) in a popup.
+    fn open_cell_peek(&mut self) {
+        let Some(state) = self.data_table_state.as_ref() else {
+            return;
         };
+        // The selected column when it's cut short, else the one cut at the right edge.
+        let selected = state.selected_column().map(str::to_string);
+        let column = selected
+            .clone()
+            .filter(|c| state.is_column_cut(c))
+            .or_else(|| state.truncated_column.clone())
+            .or(selected);
+        if let Some(column) = column {
+            // Structs and lists are shown field by field rather than on one line.
+            let nested = state
+                .selected_cell_series(&column)
+                .and_then(|s| record_view::nested_text(&s).ok().flatten());
+            if let Some(value) = nested.or_else(|| state.selected_cell_value(&column)) {
+                self.cell_peek = Some((column, value));
+            }
+        }
     }
 
-    /// Apply a successfully loaded DataTableState to the app. Shared by all schema load paths.
-    fn apply_schema_ready(
-        &mut self,
-        state: DataTableState,
-        path: Option<PathBuf>,
-        options: &OpenOptions,
-        debug_label: Option<String>,
-    ) {
-        self.debug.schema_load = debug_label;
-        self.parquet_metadata_cache = None;
-        self.export_df = None;
-        self.data_table_state = Some(state);
-        self.path = path.clone();
-        if let Some(ref p) = path {
-            self.original_file_format = p.extension().and_then(|e| e.to_str()).and_then(|ext| {
-                if ext.eq_ignore_ascii_case("parquet") {
-                    Some(ExportFormat::Parquet)
-                } else if ext.eq_ignore_ascii_case("csv") {
-                    Some(ExportFormat::Csv)
-                } else if ext.eq_ignore_ascii_case("json") {
-                    Some(ExportFormat::Json)
-                } else if ext.eq_ignore_ascii_case("jsonl") || ext.eq_ignore_ascii_case("ndjson") {
-                    Some(ExportFormat::Ndjson)
-                } else if ext.eq_ignore_ascii_case("arrow")
-                    || ext.eq_ignore_ascii_case("ipc")
-                    || ext.eq_ignore_ascii_case("feather")
-                {
-                    Some(ExportFormat::Ipc)
-                } else if ext.eq_ignore_ascii_case("avro") {
-                    Some(ExportFormat::Avro)
-                } else {
-                    None
-                }
-            });
-            self.original_file_delimiter = Some(options.delimiter.unwrap_or(b','));
-        } else {
-            self.original_file_format = None;
-            self.original_file_delimiter = None;
+    /// Display format of `column`: the one set for this view, else the one from config.
+    fn column_format(&self, column: &str) -> column_format::ColumnFormat {
+        self.data_table_state
+            .as_ref()
+            .and_then(|s| s.column_formats.get(column))
+            .or_else(|| self.app_config.display.column_formats.get(column))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Formats used to render the table: config formats overridden by this view's.
+    pub(crate) fn display_column_formats(&self) -> column_format::ColumnFormats {
+        let mut formats = self.app_config.display.column_formats.clone();
+        if let Some(state) = &self.data_table_state {
+            formats.extend(state.column_formats.clone());
         }
-        // Enable the cheap footer-sum row count for a local Parquet hive directory.
-        if options.hive {
-            if let Some(p) = path.as_ref().filter(|p| p.is_dir()) {
-                if let Some(state) = self.data_table_state.as_mut() {
-                    state.set_parquet_count_dir(p.clone());
-                }
+        formats
+    }
+
+    /// The sampling strategy for a computation, with the analysis seed filled in when no fixed
+    /// seed is set.
+    fn sampling(&self) -> sampling::SamplingStrategy {
+        self.sampling_strategy
+            .with_fallback_seed(self.analysis_modal.random_seed)
+    }
+
+    /// The frame charts read for `row_limit` rows: a sample of the view drawn with the sampling
+    /// strategy when sampling applies to the view and it has more rows than the limit, else the
+    /// view (which the charts cut to its first rows). Kept in the chart cache.
+    pub(crate) fn chart_source(&mut self, row_limit: usize) -> Option<LazyFrame> {
+        let state = self.data_table_state.as_ref()?;
+        if let Some((limit, lf)) = &self.chart_cache.source {
+            if *limit == row_limit {
+                return Some(lf.clone());
             }
         }
-        self.sort_filter_modal = SortFilterModal::new();
-        self.pivot_melt_modal = PivotMeltModal::new();
-        if let LoadingState::Loading {
-            file_path,
-            file_size,
-            ..
-        } = &self.loading_state
-        {
-            self.loading_state = LoadingState::Loading {
-                file_path: file_path.clone(),
-                file_size: *file_size,
-                current_phase: "Loading buffer".to_string(),
-                progress_percent: 70,
-            };
+        let sampled = match self.sampling_threshold {
+            Some(threshold) if self.sampling_strategy.method != sampling::SamplingMethod::Head => {
+                let streaming = state.polars_streaming;
+                count_rows(&state.lf, state.num_rows_if_valid(), streaming)
+                    .ok()
+                    .filter(|&total| total >= threshold && total > row_limit)
+                    .and_then(|total| {
+                        sampling::sample_lazy(
+                            &state.lf,
+                            total,
+                            row_limit,
+                            &self.sampling(),
+                            streaming,
+                        )
+                        .ok()
+                    })
+            }
+            _ => None,
+        };
+        let lf = sampled.unwrap_or_else(|| state.lf.clone());
+        self.chart_cache.source = Some((row_limit, lf.clone()));
+        Some(lf)
+    }
+
+    /// The frame `chart_source` drew for `row_limit` rows, or `view` when there is none.
+    fn cached_chart_source<'a>(&'a self, view: &'a LazyFrame, row_limit: usize) -> &'a LazyFrame {
+        match &self.chart_cache.source {
+            Some((limit, lf)) if *limit == row_limit => lf,
+            _ => view,
         }
-        self.status_message = Some("Loading buffer...".to_string());
     }
 
-    /// Ensures file path has an extension when user did not provide one; only adds
-    /// compression suffix (e.g. .gz) when compression is selected. If the user
-    /// provided a path with an extension (e.g. foo.feather), that extension is kept.
-    /// Spawn an async buffer collect if needed. Returns true if a background task was spawned.
-    /// Increments task_generation to invalidate any in-flight collect from a prior call.
-    ///
-    /// When the LazyFrame's row count is unknown (e.g. fresh load, or just after a
-    /// filter/sort/pivot/melt that invalidates the cache), the exact `len()` is computed
-    /// in the background and applied later via `BackgroundLenReady` — it never gates the
-    /// buffer paint. `prepare_async_collect` plans a top-of-data window when the count is
-    /// still unknown, so the first screen renders immediately. For large/partitioned/remote
-    /// datasets the count can take a long time; it runs silently and concurrently.
-    pub fn spawn_async_collect(&mut self, status: &str) -> bool {
-        let Some(state) = self.data_table_state.as_mut() else {
-            return false;
+    /// Open the chart view, with the selected columns (if any) charted.
+    fn open_chart(&mut self) {
+        let Some(state) = &self.data_table_state else {
+            return;
         };
+        let numeric_columns: Vec<String> = state
+            .schema
+            .iter()
+            .filter(|(_, dtype)| dtype.is_numeric())
+            .map(|(name, _)| name.to_string())
+            .collect();
+        let datetime_columns: Vec<String> = state
+            .schema
+            .iter()
+            .filter(|(_, dtype)| {
+                matches!(
+                    dtype,
+                    DataType::Datetime(_, _) | DataType::Date | DataType::Time
+                )
+            })
+            .map(|(name, _)| name.to_string())
+            .collect();
+        self.chart_modal.open(
+            &numeric_columns,
+            &datetime_columns,
+            self.app_config.chart.row_limit,
+        );
+        self.chart_modal.group_candidates = state
+            .schema
+            .iter()
+            .filter(|(_, dtype)| {
+                matches!(
+                    dtype,
+                    DataType::String
+                        | DataType::Categorical(..)
+                        | DataType::Enum(..)
+                        | DataType::Boolean
+                )
+            })
+            .map(|(name, _)| name.to_string())
+            .collect();
+        self.chart_modal.preselect(&state.selected_columns());
+        self.chart_modal.x_input =
+            std::mem::take(&mut self.chart_modal.x_input).with_theme(&self.theme);
+        self.chart_modal.y_input =
+            std::mem::take(&mut self.chart_modal.y_input).with_theme(&self.theme);
+        self.chart_modal.hist_input =
+            std::mem::take(&mut self.chart_modal.hist_input).with_theme(&self.theme);
+        self.chart_modal.box_input =
+            std::mem::take(&mut self.chart_modal.box_input).with_theme(&self.theme);
+        self.chart_modal.kde_input =
+            std::mem::take(&mut self.chart_modal.kde_input).with_theme(&self.theme);
+        self.chart_modal.heatmap_x_input =
+            std::mem::take(&mut self.chart_modal.heatmap_x_input).with_theme(&self.theme);
+        self.chart_modal.heatmap_y_input =
+            std::mem::take(&mut self.chart_modal.heatmap_y_input).with_theme(&self.theme);
+        self.chart_cache.clear();
+        self.input_mode = InputMode::Chart;
+    }
 
-        // Kick off the exact row count off-thread when it isn't known, unless one is
-        // already running for this data version. This task is independent of
-        // `task_generation` (a scroll must not restart it) and does not set `busy`.
-        if !state.is_num_rows_valid() {
-            let len_gen = state.len_generation();
-            if self.len_count_inflight != Some(len_gen) {
-                // Prefer the cheap footer-sum count for a pristine local Parquet hive
-                // directory; otherwise fall back to a `len()` data scan.
-                let count_dir = state.parquet_count_dir();
-                let lf = state.lf_clone();
-                let streaming = state.polars_streaming_enabled();
-                self.len_count_inflight = Some(len_gen);
-                // A fresh attempt for this generation clears any prior failure marker.
-                if self.len_count_failed == Some(len_gen) {
-                    self.len_count_failed = None;
-                }
-                let tx = self.events.clone();
-                self.runtime.spawn_blocking(move || {
-                    let counted = match count_dir {
-                        Some(dir) => {
-                            crate::widgets::datatable::DataTableState::count_rows_from_parquet_dir(
-                                &dir,
-                            )
-                            .map_err(|_| ())
-                        }
-                        None => {
-                            match crate::statistics::collect_lazy(lf.select([len()]), streaming) {
-                                Ok(df) => Ok(match df.get(0) {
-                                    Some(col) => match col.first() {
-                                        Some(AnyValue::UInt32(n)) => *n as usize,
-                                        _ => 0,
-                                    },
-                                    None => 0,
-                                }),
-                                Err(_) => Err(()),
-                            }
-                        }
-                    };
-                    match counted {
-                        Ok(num_rows) => {
-                            let _ = tx.send(AppEvent::BackgroundLenReady {
-                                len_generation: len_gen,
-                                num_rows,
-                            });
-                        }
-                        // Count failed: leave the total provisional and allow a retry on a
-                        // later interaction. The buffer paint below is unaffected.
-                        Err(()) => {
-                            let _ = tx.send(AppEvent::BackgroundLenFailed {
-                                len_generation: len_gen,
-                            });
-                        }
-                    }
-                });
-            }
-        }
-
-        // Plan and spawn the buffer collect. With the count unknown this is a top-of-data
-        // window (`slice(0, N)`) that touches only the first file(s) of a partitioned set.
-        let Some(state) = self.data_table_state.as_mut() else {
-            return false;
+    /// Open the Format modal for the leftmost scrolled column.
+    fn open_format_modal(&mut self) {
+        let Some(state) = self.data_table_state.as_ref() else {
+            return;
         };
-        let Some(request) = state.prepare_async_collect(None) else {
-            return false;
+        let Some(column) = state.selected_column().map(str::to_string) else {
+            return;
         };
-        self.task_generation = self.task_generation.wrapping_add(1);
-        let collect_slot = self.pending_collect_result.clone();
-        self.spawn_bg(status, move |gen, tx| {
-            match crate::statistics::collect_lazy(request.lf, request.polars_streaming) {
-                Ok(df) => {
-                    let mut slot = collect_slot.lock().unwrap_or_else(|e| e.into_inner());
-                    // Only write if no newer result is already stored.
-                    let dominated = slot.as_ref().is_some_and(|(g, _)| *g > gen);
-                    if !dominated {
-                        *slot = Some((
-                            gen,
-                            crate::widgets::datatable::CollectResult {
-                                df,
-                                buffer_start: request.buffer_start,
-                                buffer_end: request.buffer_end,
-                                num_rows: request.num_rows,
-                                count_known: request.count_known,
-                            },
-                        ));
-                    }
-                    drop(slot);
-                    let _ = tx.send(AppEvent::BackgroundCollectReady { generation: gen });
-                }
-                Err(e) => {
-                    let _ = tx.send(AppEvent::BackgroundError {
-                        generation: gen,
-                        message: crate::error_display::user_message_from_polars(&e),
-                    });
-                }
+        let Some(numeric) = state
+            .schema
+            .get(&column)
+            .and_then(column_format::formattable_kind)
+        else {
+            self.error_modal.show(format!(
+                "Only numeric, date, datetime and time columns can be formatted ({} isn't).",
+                column
+            ));
+            return;
+        };
+        let sample = state.selected_cell_any_value(&column);
+        let format = self.column_format(&column);
+        self.format_modal
+            .open(column, numeric, &format, sample, &self.theme);
+    }
+
+    /// Add a filter comparing the selected cell's column with its value (`f=`, `f!`, `f>`,
+    /// `f<`) to the view's filters. On a null cell `f=` / `f!` keep the null / non-null rows.
+    fn quick_filter(&mut self, operator: FilterOperator) -> Option<AppEvent> {
+        let state = self.data_table_state.as_ref()?;
+        let column = state.selected_column()?.to_string();
+        let value = state.selected_cell_any_value(&column)?;
+        let (operator, value) = match operator {
+            _ if !value.is_null() => (operator, filter_modal::value_text(&value)),
+            FilterOperator::Eq => (FilterOperator::IsNull, String::new()),
+            FilterOperator::NotEq => (FilterOperator::IsNotNull, String::new()),
+            _ => {
+                self.error_modal.show(format!(
+                    "The cell is null, so {} can't be compared with it",
+                    column
+                ));
+                return None;
             }
+        };
+        let mut statements = state.get_filters().to_vec();
+        statements.push(FilterStatement {
+            column,
+            operator,
+            value,
+            logical_op: LogicalOperator::And,
         });
-        true
+        self.sort_filter_modal.filter.statements = statements.clone();
+        Some(AppEvent::Filter(statements))
     }
 
-    /// Spawn a background task. Captures the current generation and event sender
-    /// for the closure, sets `busy` and `status_message`. The closure is responsible
-    /// for sending a follow-up event (typically `Background*Ready` or `BackgroundError`)
-    /// using the captured generation so stale results can be filtered out.
-    ///
-    /// Does not bump `task_generation`. Callers that need to invalidate prior in-flight
-    /// work should bump it explicitly before calling.
-    fn spawn_bg<F>(&mut self, status: &str, work: F)
-    where
-        F: FnOnce(u64, Sender<AppEvent>) + Send + 'static,
-    {
-        let gen = self.task_generation;
-        let tx = self.events.clone();
-        self.busy = true;
-        self.status_message = Some(status.to_string());
-        self.runtime.spawn_blocking(move || work(gen, tx));
+    /// Store the format edited in the Format modal for its column. A format equal to the
+    /// config's for that column is dropped rather than stored as an override.
+    fn apply_format_modal(&mut self) {
+        let format = match self.format_modal.format() {
+            Ok(format) => format,
+            Err(message) => {
+                self.error_modal.show(message);
+                return;
+            }
+        };
+        let column = self.format_modal.column.clone();
+        let configured = self
+            .app_config
+            .display
+            .column_formats
+            .get(&column)
+            .cloned()
+            .unwrap_or_default();
+        if let Some(state) = self.data_table_state.as_mut() {
+            if format == configured {
+                state.column_formats.remove(&column);
+            } else {
+                state.column_formats.insert(column, format);
+            }
+        }
+        self.format_modal.close();
     }
 
-    /// Run a scroll on `data_table_state` and resolve the busy/spawn cycle.
-    /// `scroll` returns true when its movement leaves the buffered window (caller must collect).
-    /// We clear `busy` ourselves when no collect is needed or the spawn no-ops, otherwise
-    /// the busy flag set by the key handler would gate further input forever.
-    fn handle_scroll<F>(&mut self, scroll: F) -> Option<AppEvent>
-    where
-        F: FnOnce(&mut crate::widgets::datatable::DataTableState) -> bool,
-    {
-        let needs = self.data_table_state.as_mut().is_some_and(scroll);
-        if !needs || !self.spawn_async_collect("Loading buffer...") {
-            self.busy = false;
-            self.status_message = None;
-        }
-        self.drain_keys_on_next_loop = true;
-        None
+    /// Use the sample size and strategy edited in the Sampling modal. Cached analysis results
+    /// are dropped so the tools recompute with them.
+    fn apply_sampling_modal(&mut self) {
+        let (threshold, strategy) = match self.sampling_modal.settings() {
+            Ok(settings) => settings,
+            Err(message) => {
+                self.error_modal.show(message);
+                return;
+            }
+        };
+        self.sampling_threshold = threshold;
+        self.sampling_strategy = strategy;
+        self.analysis_modal.describe_results = None;
+        self.analysis_modal.distribution_results = None;
+        self.analysis_modal.cardinality_results = None;
+        self.chart_cache.clear();
+        self.sampling_modal.close();
     }
 
-    fn ensure_file_extension(
-        path: &Path,
-        format: ExportFormat,
-        compression: Option<CompressionFormat>,
-    ) -> PathBuf {
-        let current_ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-        let mut new_path = path.to_path_buf();
+    /// Open the New Column modal, with the definition of the leftmost scrolled column when it
+    /// is a computed column.
+    fn open_computed_column_modal(&mut self) {
+        let Some(state) = self.data_table_state.as_ref() else {
+            return;
+        };
+        let column = state.selected_column().and_then(|name| {
+            state
+                .computed_columns()
+                .iter()
+                .find(|c| c.name == name)
+                .cloned()
+        });
+        self.computed_column_modal
+            .open(column.as_ref(), state.source_lf_clone(), &self.theme);
+    }
 
-        if current_ext.is_empty() {
-            // No extension: use default for format (and add compression if selected)
-            let desired_ext = if let Some(comp) = compression {
-                format!("{}.{}", format.extension(), comp.extension())
-            } else {
-                format.extension().to_string()
-            };
-            new_path.set_extension(&desired_ext);
+    /// Add the column edited in the New Column modal (replacing a computed column of the same
+    /// name), or remove that computed column when the expression is empty.
+    fn apply_computed_column_modal(&mut self) {
+        let column = match self.computed_column_modal.column() {
+            Ok(column) => column,
+            Err(message) => {
+                self.error_modal.show(message);
+                return;
+            }
+        };
+        let Some(state) = self.data_table_state.as_mut() else {
+            return;
+        };
+        state.defer_collect = true;
+        let result = if column.expression.is_empty()
+            && state
+                .computed_columns()
+                .iter()
+                .any(|c| c.name == column.name)
+        {
+            let columns = state
+                .computed_columns()
+                .iter()
+                .filter(|c| c.name != column.name)
+                .cloned()
+                .collect();
+            state.set_computed_columns(columns)
         } else {
-            // User provided an extension: keep it. Only add compression suffix when compression is selected.
-            let is_compression_only = matches!(
-                current_ext.to_lowercase().as_str(),
-                "gz" | "zst" | "bz2" | "xz"
-            ) && ExportFormat::from_extension(current_ext).is_none();
-
-            if is_compression_only {
-                // Path has only compression ext (e.g. file.gz); stem may have format (file.csv.gz)
-                let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
-                let stem_has_format = stem
-                    .split('.')
-                    .next_back()
-                    .and_then(ExportFormat::from_extension)
-                    .is_some();
-                if stem_has_format {
-                    if let Some(comp) = compression {
-                        if let Some(format_ext) = stem
-                            .split('.')
-                            .next_back()
-                            .and_then(ExportFormat::from_extension)
-                            .map(|f| f.extension())
-                        {
-                            new_path =
-                                PathBuf::from(stem.rsplit_once('.').map(|x| x.0).unwrap_or(stem));
-                            new_path.set_extension(format!("{}.{}", format_ext, comp.extension()));
-                        }
-                    }
-                } else if let Some(comp) = compression {
-                    new_path.set_extension(format!("{}.{}", format.extension(), comp.extension()));
-                } else {
-                    new_path.set_extension(format.extension());
-                }
-            } else if let Some(comp) = compression {
-                if format.supports_compression() {
-                    new_path.set_extension(format!("{}.{}", current_ext, comp.extension()));
-                }
-                // else: path stays as-is (e.g. foo.feather stays foo.feather)
+            state.add_computed_column(column)
+        };
+        state.defer_collect = false;
+        match result {
+            Ok(()) => {
+                self.computed_column_modal.close();
+                self.spawn_async_collect("Adding column...");
             }
-            // else: path with format extension stays as-is
+            Err(message) => self.error_modal.show(message),
         }
+    }
 
-        new_path
+    /// Open the Rolling modal with the marked columns checked, or the leftmost scrolled one.
+    fn open_rolling_modal(&mut self) {
+        let Some(state) = self.data_table_state.as_ref() else {
+            return;
+        };
+        let mut preselected = state.selected_columns();
+        if preselected.is_empty() {
+            preselected.extend(state.selected_column().map(str::to_string));
+        }
+        self.rolling_modal
+            .open(state.source_lf_clone(), &preselected, &self.theme);
     }
 
-    pub fn new(events: Sender<AppEvent>, runtime: tokio::runtime::Handle) -> App {
-        // Create default theme for backward compatibility
-        let theme = Theme::from_config(&AppConfig::default().theme).unwrap_or_else(|_| {
-            // Create a minimal fallback theme
-            Theme {
-                colors: std::collections::HashMap::new(),
+    /// Add the columns chosen in the Rolling modal as computed columns, replacing computed
+    /// columns of the same name.
+    fn apply_rolling_modal(&mut self) {
+        let new_columns = match self.rolling_modal.computed_columns() {
+            Ok(columns) => columns,
+            Err(message) => {
+                self.error_modal.show(message);
+                return;
             }
-        });
-
-        Self::new_with_config(events, runtime, theme, AppConfig::default())
+        };
+        let Some(state) = self.data_table_state.as_mut() else {
+            return;
+        };
+        let mut columns = state.computed_columns().to_vec();
+        for column in new_columns {
+            match columns.iter_mut().find(|c| c.name == column.name) {
+                Some(existing) => *existing = column,
+                None => columns.push(column),
+            }
+        }
+        state.defer_collect = true;
+        let result = state.set_computed_columns(columns);
+        state.defer_collect = false;
+        match result {
+            Ok(()) => {
+                self.rolling_modal.close();
+                self.spawn_async_collect("Adding rolling columns...");
+            }
+            Err(message) => self.error_modal.show(message),
+        }
     }
 
-    pub fn new_with_theme(
-        events: Sender<AppEvent>,
-        runtime: tokio::runtime::Handle,
-        theme: Theme,
-    ) -> App {
-        Self::new_with_config(events, runtime, theme, AppConfig::default())
+    /// Open the Time Zone modal for the leftmost scrolled column when it is a datetime column.
+    fn open_time_zone_modal(&mut self) {
+        let Some(state) = self.data_table_state.as_ref() else {
+            return;
+        };
+        let Some(column) = state.selected_column() else {
+            return;
+        };
+        let source = state.source_lf_clone();
+        let dtype = source
+            .clone()
+            .collect_schema()
+            .ok()
+            .and_then(|schema| schema.get(column).cloned());
+        match dtype {
+            Some(dtype @ DataType::Datetime(_, _)) => {
+                self.time_zone_modal
+                    .open(column, &dtype, source, &self.theme)
+            }
+            _ => self
+                .error_modal
+                .show(format!("'{}' is not a datetime column", column)),
+        }
     }
 
-    pub fn new_with_config(
-        events: Sender<AppEvent>,
-        runtime: tokio::runtime::Handle,
-        theme: Theme,
-        app_config: AppConfig,
-    ) -> App {
-        let cache = CacheManager::new(APP_NAME).unwrap_or_else(|_| CacheManager {
-            cache_dir: std::env::temp_dir().join(APP_NAME),
-        });
+    /// Replace the column of the Time Zone modal with the column in its new zone.
+    fn apply_time_zone_modal(&mut self) {
+        let change = self.time_zone_modal.change();
+        let Some(state) = self.data_table_state.as_mut() else {
+            return;
+        };
+        state.defer_collect = true;
+        let result = state.change_time_zone(&change);
+        state.defer_collect = false;
+        match result {
+            Ok(()) => {
+                self.time_zone_modal.close();
+                self.spawn_async_collect("Changing time zone...");
+            }
+            Err(message) => self.error_modal.show(message),
+        }
+    }
 
-        let config_manager = ConfigManager::new(APP_NAME).unwrap_or_else(|_| ConfigManager {
-            config_dir: std::env::temp_dir().join(APP_NAME).join("config"),
-        });
+    /// Open the Cast modal for the leftmost scrolled column.
+    fn open_cast_modal(&mut self) {
+        let Some(state) = self.data_table_state.as_ref() else {
+            return;
+        };
+        let Some(column) = state.selected_column() else {
+            return;
+        };
+        let source = state.source_lf_clone();
+        let dtype = source
+            .clone()
+            .collect_schema()
+            .ok()
+            .and_then(|schema| schema.get(column).cloned());
+        match dtype {
+            Some(dtype) => self.cast_modal.open(column, &dtype, source, &self.theme),
+            None => self
+                .error_modal
+                .show(format!("'{}' is not a column of the data", column)),
+        }
+    }
 
-        let template_manager = TemplateManager::new(&config_manager).unwrap_or_else(|_| {
-            let temp_config = ConfigManager::new("datui").unwrap_or_else(|_| ConfigManager {
-                config_dir: std::env::temp_dir().join("datui").join("config"),
-            });
-            TemplateManager::new(&temp_config).unwrap_or_else(|_| {
-                let last_resort = ConfigManager {
-                    config_dir: std::env::temp_dir().join("datui_config"),
-                };
-                TemplateManager::new(&last_resort)
-                    .unwrap_or_else(|_| TemplateManager::empty(&last_resort))
-            })
-        });
+    /// Replace the column of the Cast modal with the column read as the chosen type.
+    fn apply_cast_modal(&mut self) {
+        let cast = self.cast_modal.cast();
+        let Some(state) = self.data_table_state.as_mut() else {
+            return;
+        };
+        state.defer_collect = true;
+        let result = state.cast_columns(std::slice::from_ref(&cast));
+        state.defer_collect = false;
+        match result {
+            Ok(()) => {
+                self.cast_modal.close();
+                self.spawn_async_collect("Casting column...");
+            }
+            Err(message) => self.error_modal.show(message),
+        }
+    }
 
-        App {
-            path: None,
-            data_table_state: None,
-            original_file_format: None,
-            original_file_delimiter: None,
-            events,
-            focus: 0,
-            debug: DebugState::default(),
-            info_modal: InfoModal::new(),
-            parquet_metadata_cache: None,
-            query_input: TextInput::new()
-                .with_history_limit(app_config.query.history_limit)
-                .with_theme(&theme)
-                .with_history("query".to_string()),
-            sql_input: TextInput::new()
-                .with_history_limit(app_config.query.history_limit)
-                .with_theme(&theme)
-                .with_history("sql".to_string()),
-            fuzzy_input: TextInput::new()
-                .with_history_limit(app_config.query.history_limit)
-                .with_theme(&theme)
-                .with_history("fuzzy".to_string()),
-            input_mode: InputMode::Normal,
-            input_type: None,
-            query_tab: QueryTab::SqlLike,
-            query_focus: QueryFocus::Input,
-            sort_filter_modal: SortFilterModal::new(),
-            pivot_melt_modal: PivotMeltModal::new(),
-            template_modal: TemplateModal::new(),
-            analysis_modal: AnalysisModal::new(),
-            chart_modal: ChartModal::new(),
-            chart_export_modal: ChartExportModal::new(),
-            export_modal: ExportModal::new(),
-            chart_cache: ChartCache::default(),
-            error_modal: ErrorModal::new(),
-            success_modal: SuccessModal::new(),
-            confirmation_modal: ConfirmationModal::new(),
-            pending_export: None,
-            export_df: None,
-            pending_chart_export: None,
-            #[cfg(any(feature = "http", feature = "cloud"))]
-            pending_download: None,
-            show_help: false,
-            help_scroll: 0,
-            cache,
-            template_manager,
-            active_template_id: None,
-            loading_state: LoadingState::Idle,
-            theme,
-            sampling_threshold: app_config.performance.sampling_threshold,
-            history_limit: app_config.query.history_limit,
-            table_cell_padding: app_config.display.table_cell_padding.min(u16::MAX as usize) as u16,
-            column_colors: app_config.display.column_colors,
-            runtime,
-            task_generation: 0,
-            pending_schema_result: std::sync::Arc::new(std::sync::Mutex::new(None)),
-            len_count_inflight: None,
-            len_count_failed: None,
-            pending_collect_result: std::sync::Arc::new(std::sync::Mutex::new(None)),
-            busy: false,
-            throbber_frame: 0,
-            drain_keys_on_next_loop: false,
-            status_message: None,
-            analysis_computation: None,
-            app_config,
-            #[cfg(feature = "http")]
-            http_temp_path: None,
+    /// Unnest the selected struct column into its fields, or explode the selected list column
+    /// into one row per item.
+    fn expand_selected_column(&mut self) {
+        let Some(state) = self.data_table_state.as_mut() else {
+            return;
+        };
+        let Some(column) = state.selected_column().map(str::to_string) else {
+            return;
+        };
+        state.defer_collect = true;
+        let result = state.expand_column(&column);
+        state.defer_collect = false;
+        match result {
+            Ok(()) => {
+                self.spawn_async_collect("Expanding column...");
+            }
+            Err(message) => self.error_modal.show(message),
         }
     }
 
-    pub fn enable_debug(&mut self) {
-        self.debug.enabled = true;
+    /// Replace the view with the group-by edited in the GroupBy modal.
+    fn apply_group_by_modal(&mut self) {
+        let spec = match self.group_by_modal.spec() {
+            Ok(spec) => spec,
+            Err(message) => {
+                self.error_modal.show(message);
+                return;
+            }
+        };
+        let Some(state) = self.data_table_state.as_mut() else {
+            return;
+        };
+        self.busy = true;
+        state.defer_collect = true;
+        let result = state.group_by(&spec);
+        state.defer_collect = false;
+        match result {
+            Ok(()) => {
+                self.group_by_modal.close();
+                self.spawn_async_collect("Grouping...");
+            }
+            Err(e) => {
+                self.busy = false;
+                self.error_modal
+                    .show(crate::error_display::user_message_from_report(&e, None));
+            }
+        }
     }
 
-    /// Get a color from the theme by name
-    fn color(&self, name: &str) -> Color {
-        self.theme.get(name)
+    /// Replace the view with the resample edited in the Resample modal.
+    fn apply_resample_modal(&mut self) {
+        let spec = match self.resample_modal.spec() {
+            Ok(spec) => spec,
+            Err(message) => {
+                self.error_modal.show(message);
+                return;
+            }
+        };
+        let Some(state) = self.data_table_state.as_mut() else {
+            return;
+        };
+        self.busy = true;
+        state.defer_collect = true;
+        let result = state.resample(&spec);
+        state.defer_collect = false;
+        match result {
+            Ok(()) => {
+                self.resample_modal.close();
+                self.spawn_async_collect("Resampling...");
+            }
+            Err(e) => {
+                self.busy = false;
+                self.error_modal
+                    .show(crate::error_display::user_message_from_report(&e, None));
+            }
+        }
     }
 
-    fn load(&mut self, paths: &[PathBuf], options: &OpenOptions) -> Result<()> {
-        self.parquet_metadata_cache = None;
-        self.export_df = None;
-        let path = &paths[0]; // Primary path for format detection and single-path logic
-                              // Check for compressed CSV files (e.g., file.csv.gz, file.csv.zst, etc.) — only single-file
-        let compression = options
-            .compression
-            .or_else(|| CompressionFormat::from_extension(path));
-        let is_csv = options.format == Some(FileFormat::Csv)
-            || path
-                .file_stem()
-                .and_then(|stem| stem.to_str())
-                .map(|stem| {
-                    stem.ends_with(".csv")
-                        || path
-                            .extension()
-                            .and_then(|e| e.to_str())
-                            .map(|e| e.eq_ignore_ascii_case("csv"))
-                            .unwrap_or(false)
-                })
-                .unwrap_or(false);
-        let is_compressed_csv = paths.len() == 1 && compression.is_some() && is_csv;
+    /// Current background-task generation. Bumped each time work is spawned that should
+    /// invalidate prior in-flight tasks. Exposed for tests that need to construct
+    /// synthetic Background* events with a known-stale generation.
+    pub fn task_generation(&self) -> u64 {
+        self.task_generation
+    }
 
-        // For compressed files, decompression phase is already set in DoLoad handler
-        // Now actually perform decompression and CSV reading (this is the slow part)
-        if is_compressed_csv {
-            // Phase: Reading data or Scanning string columns (decompressing + parsing CSV; user may see "Decompressing" until we return)
-            if let LoadingState::Loading {
-                file_path,
-                file_size,
-                ..
-            } = &self.loading_state
-            {
-                self.loading_state = LoadingState::Loading {
-                    file_path: file_path.clone(),
-                    file_size: *file_size,
-                    current_phase: if options.parse_strings.is_some() {
-                        "Scanning string columns".to_string()
-                    } else {
-                        "Reading data".to_string()
-                    },
-                    progress_percent: if options.parse_strings.is_some() {
-                        55
-                    } else {
-                        50
-                    },
-                };
-            }
-            let lf = DataTableState::from_csv(path, options)?; // Already passes pages_lookahead/lookback via options
+    /// Returns true when the main loop should drain the crossterm key buffer after render.
+    pub fn should_drain_keys(&self) -> bool {
+        self.drain_keys_on_next_loop
+    }
 
-            // Phase: Building lazyframe (after decompression, before rendering)
-            if let LoadingState::Loading {
-                file_path,
-                file_size,
-                ..
-            } = &self.loading_state
-            {
-                self.loading_state = LoadingState::Loading {
-                    file_path: file_path.clone(),
-                    file_size: *file_size,
-                    current_phase: "Building lazyframe".to_string(),
-                    progress_percent: 60,
-                };
+    /// Clears the drain-keys request after the main loop has drained the buffer.
+    pub fn clear_drain_keys_request(&mut self) {
+        self.drain_keys_on_next_loop = false;
+    }
+
+    pub fn send_event(&mut self, event: AppEvent) -> Result<()> {
+        self.events.send(event)?;
+        Ok(())
+    }
+
+    /// Set loading state and phase so the progress dialog is visible. Used by run() to show
+    /// loading UI immediately when launching from LazyFrame (e.g. Python) before sending the open event.
+    pub fn set_loading_phase(&mut self, phase: impl Into<String>, progress_percent: u16) {
+        self.busy = true;
+        self.loading_state = LoadingState::Loading {
+            file_path: None,
+            file_size: 0,
+            current_phase: phase.into(),
+            progress_percent,
+        };
+    }
+
+    /// Open the query dialog on `tab`, with every input prefilled from the active queries.
+    fn open_query_dialog(&mut self, tab: QueryTab) {
+        self.input_mode = InputMode::Editing;
+        self.input_type = Some(InputType::Search);
+        self.query_tab = tab;
+        self.query_focus = QueryFocus::Input;
+        self.query_completion = None;
+        if let Some(state) = &mut self.data_table_state {
+            self.query_input.value = state.active_query.clone();
+            self.query_input.cursor = self.query_input.value.chars().count();
+            self.sql_input.value = state.get_active_sql_query().to_string();
+            self.fuzzy_input.value = state.get_active_fuzzy_query().to_string();
+            self.fuzzy_input.cursor = self.fuzzy_input.value.chars().count();
+            self.sql_input.cursor = self.sql_input.value.chars().count();
+            self.regex_input
+                .set_value(state.regex_search_pattern().to_string());
+            state.suppress_error_display = true;
+        } else {
+            self.query_input.clear();
+            self.sql_input.clear();
+            self.fuzzy_input.clear();
+            self.regex_input.clear();
+        }
+        self.query_input.set_focused(tab == QueryTab::SqlLike);
+        self.fuzzy_input.set_focused(tab == QueryTab::Fuzzy);
+        self.sql_input.set_focused(tab == QueryTab::Sql);
+        self.regex_input.set_focused(tab.is_regex());
+    }
+
+    /// What the app is busy with, for completion notifications.
+    fn busy_label(&self) -> Option<String> {
+        let file_name = |path: &Path| {
+            path.file_name().map_or_else(
+                || path.display().to_string(),
+                |n| n.to_string_lossy().to_string(),
+            )
+        };
+        match &self.loading_state {
+            LoadingState::Loading { file_path, .. } => Some(match file_path {
+                Some(path) => format!("Loading {}", file_name(path)),
+                None => "Loading".to_string(),
+            }),
+            LoadingState::Exporting { file_path, .. } => {
+                Some(format!("Exporting {}", file_name(file_path)))
             }
+            LoadingState::Idle => self
+                .analysis_modal
+                .computing
+                .as_ref()
+                .map(|progress| progress.phase.clone())
+                .or_else(|| {
+                    self.status_message
+                        .as_ref()
+                        .map(|status| status.trim_end_matches('.').to_string())
+                }),
+        }
+    }
 
-            // Phased loading: set "Loading buffer" so UI can show progress; caller (DoDecompress) will send DoLoadBuffer
-            if let LoadingState::Loading {
-                file_path,
-                file_size,
-                ..
-            } = &self.loading_state
+    /// Apply a successfully loaded DataTableState to the app. Shared by all schema load paths.
+    fn apply_schema_ready(
+        &mut self,
+        mut state: DataTableState,
+        path: Option<PathBuf>,
+        options: &OpenOptions,
+        debug_label: Option<String>,
+    ) {
+        self.debug.schema_load = debug_label;
+        self.parquet_metadata_cache = None;
+        self.export_df = None;
+        state.set_source_files(std::mem::take(&mut self.loading_source_files));
+        // A CSV read as one column without a chosen delimiter may use another one: offer the
+        // dialect modal when a dialect sniffed from its first lines finds more columns.
+        if state.schema.len() == 1 {
+            if let Some((path, options)) = self
+                .loading_csv
+                .as_ref()
+                .filter(|(_, options)| options.delimiter.is_none())
             {
-                self.loading_state = LoadingState::Loading {
-                    file_path: file_path.clone(),
-                    file_size: *file_size,
-                    current_phase: "Loading buffer".to_string(),
-                    progress_percent: 70,
-                };
+                if let Ok(modal) = csv_dialect::CsvDialectModal::sniffed(
+                    path.clone(),
+                    options.clone(),
+                    "Only one column was found".to_string(),
+                ) {
+                    if modal.preview_columns() > 1 {
+                        self.csv_dialect = Some(modal);
+                    }
+                }
             }
-
-            self.data_table_state = Some(lf);
-            self.path = Some(path.clone());
-            let original_format =
-                path.file_stem()
-                    .and_then(|stem| stem.to_str())
-                    .and_then(|stem| {
-                        if stem.ends_with(".csv") {
-                            Some(ExportFormat::Csv)
-                        } else {
-                            None
-                        }
-                    });
-            self.original_file_format = original_format;
-            self.original_file_delimiter = Some(options.delimiter.unwrap_or(b','));
-            self.sort_filter_modal = SortFilterModal::new();
-            self.pivot_melt_modal = PivotMeltModal::new();
-            return Ok(());
         }
-
-        // Hive path: when --hive and single path is directory or glob (not a single file), use hive load.
-        // Multiple paths or single file with --hive use the normal path below.
-        if paths.len() == 1 && options.hive {
-            let path_str = path.as_os_str().to_string_lossy();
-            let is_single_file = path.exists()
-                && path.is_file()
-                && !path_str.contains('*')
-                && !path_str.contains("**");
-            if !is_single_file {
-                // Directory or glob: only Parquet supported for hive in this implementation
-                let use_parquet_hive = path.is_dir()
-                    || path_str.contains(".parquet")
-                    || path_str.contains("*.parquet");
-                if use_parquet_hive {
-                    if let LoadingState::Loading {
-                        file_path,
-                        file_size,
-                        ..
-                    } = &self.loading_state
-                    {
-                        self.loading_state = LoadingState::Loading {
-                            file_path: file_path.clone(),
-                            file_size: *file_size,
-                            current_phase: "Scanning partitioned dataset".to_string(),
-                            progress_percent: 60,
-                        };
-                    }
-                    let lf = DataTableState::from_parquet_hive(
-                        path,
-                        options.pages_lookahead,
-                        options.pages_lookback,
-                        options.max_buffered_rows,
-                        options.max_buffered_mb,
-                        options.row_numbers,
-                        options.row_start_index,
-                    )?;
-                    if let LoadingState::Loading {
-                        file_path,
-                        file_size,
-                        ..
-                    } = &self.loading_state
-                    {
-                        self.loading_state = LoadingState::Loading {
-                            file_path: file_path.clone(),
-                            file_size: *file_size,
-                            current_phase: "Rendering data".to_string(),
-                            progress_percent: 90,
-                        };
-                    }
-                    self.loading_state = LoadingState::Idle;
-                    self.data_table_state = Some(lf);
-                    self.path = Some(path.clone());
-                    self.original_file_format = Some(ExportFormat::Parquet);
-                    self.original_file_delimiter = None;
-                    // Enable the cheap footer-sum row count for a local hive directory
-                    // (globs go through the same constructor but aren't a single dir).
-                    if path.is_dir() {
-                        if let Some(state) = self.data_table_state.as_mut() {
-                            state.set_parquet_count_dir(path.clone());
-                        }
-                    }
-                    self.sort_filter_modal = SortFilterModal::new();
-                    self.pivot_melt_modal = PivotMeltModal::new();
-                    return Ok(());
+        // Rows left out of the load: list them right away
+        self.load_report = state
+            .load_report
+            .clone()
+            .filter(|report| report.skipped > 0)
+            .map(load_report::LoadReportPanel::new);
+        self.data_table_state = Some(state);
+        self.path = path.clone();
+        if let Some(ref p) = path {
+            self.original_file_format = p.extension().and_then(|e| e.to_str()).and_then(|ext| {
+                if ext.eq_ignore_ascii_case("parquet") {
+                    Some(ExportFormat::Parquet)
+                } else if ext.eq_ignore_ascii_case("csv") {
+                    Some(ExportFormat::Csv)
+                } else if ext.eq_ignore_ascii_case("json") {
+                    Some(ExportFormat::Json)
+                } else if ext.eq_ignore_ascii_case("jsonl") || ext.eq_ignore_ascii_case("ndjson") {
+                    Some(ExportFormat::Ndjson)
+                } else if ext.eq_ignore_ascii_case("arrow")
+                    || ext.eq_ignore_ascii_case("ipc")
+                    || ext.eq_ignore_ascii_case("feather")
+                {
+                    Some(ExportFormat::Ipc)
+                } else if ext.eq_ignore_ascii_case("avro") {
+                    Some(ExportFormat::Avro)
+                } else if ext.eq_ignore_ascii_case("xlsx") {
+                    Some(ExportFormat::Xlsx)
+                } else {
+                    None
+                }
+            });
+            self.original_file_delimiter = Some(options.delimiter.unwrap_or(b','));
+        } else {
+            self.original_file_format = None;
+            self.original_file_delimiter = None;
+        }
+        // Enable the cheap footer-sum row count for a local Parquet hive directory.
+        if options.hive {
+            if let Some(p) = path.as_ref().filter(|p| {
+                p.is_dir()
+                    && DataTableState::hive_format(p, options.format) == Some(FileFormat::Parquet)
+            }) {
+                if let Some(state) = self.data_table_state.as_mut() {
+                    state.set_parquet_count_dir(p.clone());
                 }
-                self.loading_state = LoadingState::Idle;
-                return Err(color_eyre::eyre::eyre!(
-                    "With --hive use a directory or a glob pattern for Parquet (e.g. path/to/dir or path/**/*.parquet)"
-                ));
             }
         }
-
-        // For non-gzipped files, proceed with normal loading
-        // Phase 2: Building lazyframe (or Scanning string columns for CSV when --parse-strings)
-        let effective_format = options.format.or_else(|| FileFormat::from_path(path));
-        let csv_parse_strings =
-            effective_format == Some(FileFormat::Csv) && options.parse_strings.is_some();
+        self.sort_filter_modal = SortFilterModal::new();
+        self.pivot_melt_modal = PivotMeltModal::new();
         if let LoadingState::Loading {
             file_path,
             file_size,
@@ -1660,1348 +2370,5056 @@ impl App {
             self.loading_state = LoadingState::Loading {
                 file_path: file_path.clone(),
                 file_size: *file_size,
-                current_phase: if csv_parse_strings {
-                    "Scanning string columns".to_string()
-                } else {
-                    "Building lazyframe".to_string()
-                },
-                progress_percent: if csv_parse_strings { 55 } else { 60 },
+                current_phase: "Loading buffer".to_string(),
+                progress_percent: 70,
             };
         }
+        self.status_message = Some("Loading buffer...".to_string());
+    }
 
-        // Determine and store original file format (from explicit format or first path)
-        let original_format = effective_format
-            .and_then(file_format_to_export_format)
-            .or_else(|| {
-                path.extension().and_then(|e| e.to_str()).and_then(|ext| {
-                    if ext.eq_ignore_ascii_case("parquet") {
-                        Some(ExportFormat::Parquet)
-                    } else if ext.eq_ignore_ascii_case("csv") {
-                        Some(ExportFormat::Csv)
-                    } else if ext.eq_ignore_ascii_case("json") {
-                        Some(ExportFormat::Json)
-                    } else if ext.eq_ignore_ascii_case("jsonl")
-                        || ext.eq_ignore_ascii_case("ndjson")
-                    {
-                        Some(ExportFormat::Ndjson)
-                    } else if ext.eq_ignore_ascii_case("arrow")
-                        || ext.eq_ignore_ascii_case("ipc")
-                        || ext.eq_ignore_ascii_case("feather")
-                    {
-                        Some(ExportFormat::Ipc)
-                    } else if ext.eq_ignore_ascii_case("avro") {
-                        Some(ExportFormat::Avro)
-                    } else {
-                        None
-                    }
-                })
-            });
+    /// Enter workspace directory mode: list the supported files in `dir` instead of loading it.
+    fn open_workspace(&mut self, dir: &Path, options: &OpenOptions) -> Option<AppEvent> {
+        let entries = match workspace::scan_directory(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                return Some(AppEvent::Crash(format!(
+                    "Failed to read directory {}: {}",
+                    dir.display(),
+                    e
+                )));
+            }
+        };
+        if entries.is_empty() {
+            return Some(AppEvent::Crash(format!(
+                "No supported data files in {}. To open a partitioned dataset, use --hive.",
+                dir.display()
+            )));
+        }
+        self.workspace = Some(workspace::Workspace::new(
+            dir.to_path_buf(),
+            entries,
+            options.clone(),
+        ));
+        self.loading_state = LoadingState::Idle;
+        self.busy = false;
+        self.drain_keys_on_next_loop = true;
+        None
+    }
 
-        let lf = if paths.len() > 1 {
-            // Multiple files: same format assumed (from first path or --format), concatenated into one LazyFrame
-            match effective_format {
-                Some(FileFormat::Parquet) => DataTableState::from_parquet_paths(
-                    paths,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Csv) => DataTableState::from_csv_paths(paths, options)?,
-                Some(FileFormat::Json) => DataTableState::from_json_paths(
-                    paths,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Jsonl) => DataTableState::from_json_lines_paths(
-                    paths,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Arrow) => DataTableState::from_ipc_paths(
-                    paths,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Avro) => DataTableState::from_avro_paths(
-                    paths,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Orc) => DataTableState::from_orc_paths(
-                    paths,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Tsv) | Some(FileFormat::Psv) | Some(FileFormat::Excel) | None => {
-                    self.loading_state = LoadingState::Idle;
-                    if !paths.is_empty() && !path.exists() {
-                        return Err(std::io::Error::new(
-                            std::io::ErrorKind::NotFound,
-                            format!("File not found: {}", path.display()),
-                        )
-                        .into());
-                    }
-                    return Err(color_eyre::eyre::eyre!(
-                        "Unsupported file type for multiple files (parquet, csv, json, jsonl, ndjson, arrow/ipc/feather, avro, orc only)"
-                    ));
+    /// True while the workspace file list is shown in place of the table.
+    pub(crate) fn workspace_list_visible(&self) -> bool {
+        self.workspace.as_ref().is_some_and(|w| w.list_visible)
+    }
+
+    /// Move the shown dataset out of `App`, with the view state that belongs to its tab
+    /// (applied template, chart, load profile). Leaves the chart view if it was open.
+    fn take_tab_dataset(&mut self) -> Option<workspace::SavedDataset> {
+        let state = self.data_table_state.take()?;
+        let chart_open = self.input_mode == InputMode::Chart;
+        if chart_open {
+            self.input_mode = InputMode::Normal;
+        }
+        Some(workspace::SavedDataset {
+            state,
+            path: self.path.take(),
+            original_file_format: self.original_file_format.take(),
+            original_file_delimiter: self.original_file_delimiter.take(),
+            active_template_id: self.active_template_id.take(),
+            chart_modal: std::mem::take(&mut self.chart_modal),
+            chart_cache: std::mem::take(&mut self.chart_cache),
+            chart_open,
+            load_profile: self.load_profile.take(),
+            sampling_threshold: self.sampling_threshold,
+            sampling_strategy: std::mem::take(&mut self.sampling_strategy),
+            last_open: self.last_open.take(),
+        })
+    }
+
+    /// Park the current tab's dataset in the workspace so another tab can be shown.
+    fn stash_workspace_tab(&mut self) {
+        let Some(index) = self.workspace.as_mut().and_then(|ws| ws.current.take()) else {
+            return;
+        };
+        let saved = self.take_tab_dataset();
+        if let Some(ws) = self.workspace.as_mut() {
+            ws.tabs[index].saved = saved;
+        }
+    }
+
+    /// Show workspace tab `index`, loading its file if it has no dataset yet. Ignored while a
+    /// file is loading or exporting, since that work belongs to the current tab.
+    fn switch_workspace_tab(&mut self, index: usize) -> Option<AppEvent> {
+        if self.loading_state.is_loading() {
+            return None;
+        }
+        let ws = self.workspace.as_mut()?;
+        ws.list_visible = false;
+        if ws.current == Some(index) {
+            return None;
+        }
+        self.stash_workspace_tab();
+        let ws = self.workspace.as_mut()?;
+        ws.current = Some(index);
+        let tab = &mut ws.tabs[index];
+        let Some(saved) = tab.saved.take() else {
+            return Some(AppEvent::Open(vec![tab.path.clone()], ws.options.clone()));
+        };
+        // Drop results of background work started for the previous tab.
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.len_count_inflight = None;
+        self.len_count_failed = None;
+        self.parquet_metadata_cache = None;
+        self.export_df = None;
+        self.data_table_state = Some(saved.state);
+        self.path = saved.path;
+        self.original_file_format = saved.original_file_format;
+        self.original_file_delimiter = saved.original_file_delimiter;
+        self.active_template_id = saved.active_template_id;
+        self.chart_modal = saved.chart_modal;
+        self.chart_cache = saved.chart_cache;
+        if saved.chart_open {
+            self.input_mode = InputMode::Chart;
+        }
+        self.load_profile = saved.load_profile;
+        self.concat_sources = None;
+        self.sampling_threshold = saved.sampling_threshold;
+        self.sampling_strategy = saved.sampling_strategy;
+        self.last_open = saved.last_open;
+        self.source_tables = self
+            .last_open
+            .as_ref()
+            .and_then(|(paths, options)| table_picker::SourceTables::for_open(paths, options));
+        self.sort_filter_modal = SortFilterModal::new();
+        self.pivot_melt_modal = PivotMeltModal::new();
+        self.spawn_async_collect("Loading buffer...");
+        None
+    }
+
+    /// Switch to the tab `offset` positions from the current one, wrapping around.
+    fn cycle_workspace_tab(&mut self, offset: isize) -> Option<AppEvent> {
+        let target = self
+            .workspace
+            .as_ref()
+            .and_then(|w| w.relative_tab(offset))?;
+        self.switch_workspace_tab(target)
+    }
+
+    /// Open `path` in a new tab, or switch to its tab if it is already open. The first time,
+    /// the dataset already shown becomes the first tab.
+    fn open_file_in_tab(&mut self, path: PathBuf) -> Option<AppEvent> {
+        if self.loading_state.is_loading() {
+            return None;
+        }
+        match source::input_source(&path) {
+            source::InputSource::Local(_) if !path.is_file() => {
+                self.error_modal
+                    .show(format!("Not a file: {}", path.display()));
+                return None;
+            }
+            source::InputSource::Stdin => {
+                self.error_modal
+                    .show("Standard input can only be read when datui starts.".to_string());
+                return None;
+            }
+            _ => {}
+        }
+        self.open_files_in_tabs(vec![path])
+    }
+
+    /// Open each of `paths` in a tab (reusing tabs already open for them) and show the first.
+    /// The first time, the dataset already shown becomes the first tab.
+    fn open_files_in_tabs(&mut self, paths: Vec<PathBuf>) -> Option<AppEvent> {
+        if self.loading_state.is_loading() {
+            return None;
+        }
+        if self.workspace.is_none() {
+            // Format and compression were given for the first file; detect them for the others
+            let mut options = self
+                .last_open
+                .as_ref()
+                .map(|(_, options)| options.clone())
+                .unwrap_or_default();
+            options.format = None;
+            options.compression = None;
+            let mut ws = workspace::Workspace::without_dir(options);
+            if self.data_table_state.is_some() {
+                let current = self.path.clone().unwrap_or_else(|| PathBuf::from("data"));
+                ws.tabs.push(workspace::WorkspaceTab {
+                    path: current,
+                    saved: None,
+                });
+                ws.current = Some(0);
+            }
+            self.workspace = Some(ws);
+        }
+        let ws = self.workspace.as_mut()?;
+        let mut first = None;
+        for path in paths {
+            let index = match ws.tab_index(&path) {
+                Some(index) => index,
+                None => {
+                    ws.tabs.push(workspace::WorkspaceTab { path, saved: None });
+                    ws.tabs.len() - 1
                 }
+            };
+            first.get_or_insert(index);
+        }
+        self.switch_workspace_tab(first?)
+    }
+
+    /// Keys of the file picker. Enter enters the highlighted directory or opens the highlighted
+    /// file in a new tab; `e` switches to typing a path (or URL) instead.
+    fn file_browser_key(&mut self, event: &KeyEvent) -> Option<AppEvent> {
+        let browser = self.file_browser.as_mut()?;
+        let page = browser.visible.max(1) as isize;
+        let result = match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.file_browser = None;
+                Ok(())
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                browser.move_by(1);
+                Ok(())
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                browser.move_by(-1);
+                Ok(())
+            }
+            KeyCode::PageDown => {
+                browser.move_by(page);
+                Ok(())
+            }
+            KeyCode::PageUp => {
+                browser.move_by(-page);
+                Ok(())
+            }
+            KeyCode::Home => {
+                browser.move_by(isize::MIN);
+                Ok(())
+            }
+            KeyCode::End => {
+                browser.move_by(isize::MAX);
+                Ok(())
+            }
+            KeyCode::Backspace | KeyCode::Left | KeyCode::Char('h') => browser.go_up(),
+            KeyCode::Char('.') => browser.toggle_hidden(),
+            KeyCode::Char('f') => browser.cycle_filter(),
+            KeyCode::Char('e') => {
+                let dir = format!("{}{}", browser.dir.display(), std::path::MAIN_SEPARATOR);
+                self.file_browser = None;
+                self.input_mode = InputMode::Editing;
+                self.input_type = Some(InputType::OpenFile);
+                let cursor = dir.chars().count();
+                self.query_input.set_value(dir);
+                self.query_input.set_cursor(cursor);
+                self.query_input.set_focused(true);
+                Ok(())
             }
-        } else {
-            match effective_format {
-                Some(FileFormat::Parquet) => DataTableState::from_parquet(
-                    path,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Csv) => DataTableState::from_csv(path, options)?,
-                Some(FileFormat::Tsv) => DataTableState::from_delimited(path, b'\t', options)?,
-                Some(FileFormat::Psv) => DataTableState::from_delimited(path, b'|', options)?,
-                Some(FileFormat::Json) => DataTableState::from_json(
-                    path,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Jsonl) => DataTableState::from_json_lines(
-                    path,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Arrow) => DataTableState::from_ipc(
-                    path,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Avro) => DataTableState::from_avro(
-                    path,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Excel) => DataTableState::from_excel(
-                    path,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                    options.excel_sheet.as_deref(),
-                )?,
-                Some(FileFormat::Orc) => DataTableState::from_orc(
-                    path,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                None => {
-                    self.loading_state = LoadingState::Idle;
-                    if paths.len() == 1 && !path.exists() {
-                        return Err(std::io::Error::new(
-                            std::io::ErrorKind::NotFound,
-                            format!("File not found: {}", path.display()),
-                        )
-                        .into());
-                    }
-                    return Err(color_eyre::eyre::eyre!("Unsupported file type"));
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => match browser.activate() {
+                Ok(Some(path)) => {
+                    self.file_browser = None;
+                    return self.open_file_in_tab(path);
                 }
+                Ok(None) => Ok(()),
+                Err(e) => Err(e),
+            },
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(AppEvent::Exit);
             }
+            _ => Ok(()),
         };
-
-        // Phase 3: Rendering data
-        if let LoadingState::Loading {
-            file_path,
-            file_size,
-            ..
-        } = &self.loading_state
-        {
-            self.loading_state = LoadingState::Loading {
-                file_path: file_path.clone(),
-                file_size: *file_size,
-                current_phase: "Rendering data".to_string(),
-                progress_percent: 90,
-            };
+        if let Err(e) = result {
+            self.error_modal
+                .show(format!("Could not list the directory: {e}"));
         }
-
-        // Clear loading state after successful load
-        self.loading_state = LoadingState::Idle;
-        self.data_table_state = Some(lf);
-        self.path = Some(path.clone());
-        self.original_file_format = original_format;
-        // Store delimiter based on file type (use effective format when set)
-        self.original_file_delimiter = match effective_format {
-            Some(FileFormat::Csv) => Some(options.delimiter.unwrap_or(b',')),
-            Some(FileFormat::Tsv) => Some(b'\t'),
-            Some(FileFormat::Psv) => Some(b'|'),
-            _ => path.extension().and_then(|e| e.to_str()).and_then(|ext| {
-                if ext.eq_ignore_ascii_case("csv") {
-                    Some(options.delimiter.unwrap_or(b','))
-                } else if ext.eq_ignore_ascii_case("tsv") {
-                    Some(b'\t')
-                } else if ext.eq_ignore_ascii_case("psv") {
-                    Some(b'|')
-                } else {
-                    None
-                }
-            }),
-        };
-        self.sort_filter_modal = SortFilterModal::new();
-        self.pivot_melt_modal = PivotMeltModal::new();
-        Ok(())
+        None
     }
 
+    /// List the sub-prefixes and objects under `prefix` of `bucket` in the background, then show
+    /// them in the S3 object browser.
     #[cfg(feature = "cloud")]
-    fn build_s3_cloud_options(
-        cloud: &crate::config::CloudConfig,
-        options: &OpenOptions,
-    ) -> CloudOptions {
-        let mut opts = CloudOptions::default();
-        let mut configs: Vec<(AmazonS3ConfigKey, String)> = Vec::new();
-        let e = options
-            .s3_endpoint_url_override
-            .as_ref()
-            .or(cloud.s3_endpoint_url.as_ref());
-        let k = options
-            .s3_access_key_id_override
-            .as_ref()
-            .or(cloud.s3_access_key_id.as_ref());
-        let s = options
-            .s3_secret_access_key_override
-            .as_ref()
-            .or(cloud.s3_secret_access_key.as_ref());
-        let r = options
-            .s3_region_override
-            .as_ref()
-            .or(cloud.s3_region.as_ref());
-        if let Some(e) = e {
-            configs.push((AmazonS3ConfigKey::Endpoint, e.clone()));
-        }
-        if let Some(k) = k {
-            configs.push((AmazonS3ConfigKey::AccessKeyId, k.clone()));
-        }
-        if let Some(s) = s {
-            configs.push((AmazonS3ConfigKey::SecretAccessKey, s.clone()));
-        }
-        if let Some(r) = r {
-            configs.push((AmazonS3ConfigKey::Region, r.clone()));
-        }
-        if !configs.is_empty() {
-            opts = opts.with_aws(configs);
-        }
-        opts
+    fn spawn_s3_listing(&mut self, bucket: String, prefix: String, options: OpenOptions) {
+        let cloud = self.app_config.cloud.clone();
+        let rt = self.runtime.clone();
+        let status = format!("Listing s3://{bucket}/{prefix}...");
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg(&status, move |gen, tx| {
+            let listed = Self::build_s3_object_store(&format!("s3://{bucket}/"), &cloud, &options)
+                .and_then(|store| rt.block_on(s3_browser::list_prefix(store, &prefix)));
+            let _ = tx.send(match listed {
+                Ok(entries) => AppEvent::BackgroundS3ListingReady {
+                    generation: gen,
+                    browser: Box::new(s3_browser::S3Browser::new(bucket, prefix, entries, options)),
+                },
+                Err(e) => AppEvent::BackgroundError {
+                    generation: gen,
+                    message: crate::error_display::user_message_from_report(&e, None),
+                },
+            });
+        });
     }
 
+    /// Keys of the S3 object browser. Enter lists the highlighted sub-prefix or loads the chosen
+    /// objects: one replaces the view, several open in tabs.
     #[cfg(feature = "cloud")]
-    fn build_s3_object_store(
-        s3_url: &str,
-        cloud: &crate::config::CloudConfig,
-        options: &OpenOptions,
-    ) -> Result<Arc<dyn object_store::ObjectStore>> {
-        let (path_part, _ext) = source::url_path_extension(s3_url);
-        let (bucket, _key) = path_part
-            .split_once('/')
-            .ok_or_else(|| color_eyre::eyre::eyre!("S3 URL must be s3://bucket/key"))?;
-        let mut builder = object_store::aws::AmazonS3Builder::from_env()
-            .with_url(s3_url)
-            .with_bucket_name(bucket);
-        let e = options
-            .s3_endpoint_url_override
-            .as_ref()
-            .or(cloud.s3_endpoint_url.as_ref());
-        let k = options
-            .s3_access_key_id_override
-            .as_ref()
-            .or(cloud.s3_access_key_id.as_ref());
-        let s = options
-            .s3_secret_access_key_override
-            .as_ref()
-            .or(cloud.s3_secret_access_key.as_ref());
-        let r = options
-            .s3_region_override
-            .as_ref()
-            .or(cloud.s3_region.as_ref());
-        if let Some(e) = e {
-            builder = builder.with_endpoint(e);
+    fn s3_browser_key(&mut self, event: &KeyEvent) -> Option<AppEvent> {
+        let browser = self.s3_browser.as_mut()?;
+        let page = browser.visible.max(1) as isize;
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                let browser = self.s3_browser.take()?;
+                if self.data_table_state.is_none() {
+                    // Nothing loaded: leave like a declined download, or drop the tab it was for
+                    let message = format!("No object chosen under {}", browser.location());
+                    if !self.recover_workspace_load_error(&message) {
+                        return Some(AppEvent::Exit);
+                    }
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => browser.move_by(1),
+            KeyCode::Up | KeyCode::Char('k') => browser.move_by(-1),
+            KeyCode::PageDown => browser.move_by(page),
+            KeyCode::PageUp => browser.move_by(-page),
+            KeyCode::Home => browser.move_by(isize::MIN),
+            KeyCode::End => browser.move_by(isize::MAX),
+            KeyCode::Char(' ') => browser.toggle_mark(),
+            KeyCode::Backspace | KeyCode::Left | KeyCode::Char('h') => {
+                if let Some(parent) = browser.parent_prefix() {
+                    let (bucket, options) = (browser.bucket.clone(), browser.options.clone());
+                    self.spawn_s3_listing(bucket, parent, options);
+                }
+            }
+            KeyCode::Char('H') => {
+                // The whole prefix as one (hive-partitioned) dataset
+                let browser = self.s3_browser.take()?;
+                let mut options = browser.options.clone();
+                options.hive = true;
+                return Some(AppEvent::Open(
+                    vec![PathBuf::from(browser.location())],
+                    options,
+                ));
+            }
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => {
+                if let Some(prefix) = browser.selected_prefix() {
+                    let (bucket, options) = (browser.bucket.clone(), browser.options.clone());
+                    self.spawn_s3_listing(bucket, prefix, options);
+                    return None;
+                }
+                let urls = browser.chosen_urls();
+                if urls.is_empty() {
+                    return None;
+                }
+                let browser = self.s3_browser.take()?;
+                if urls.len() == 1 {
+                    return Some(AppEvent::Open(urls, browser.options));
+                }
+                return self.open_files_in_tabs(urls);
+            }
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(AppEvent::Exit);
+            }
+            _ => {}
         }
-        if let Some(k) = k {
-            builder = builder.with_access_key_id(k);
+        None
+    }
+
+    /// Close the current tab and show its neighbour. Without a file list the last tab stays
+    /// open; in directory mode closing it returns to the file list.
+    fn close_workspace_tab(&mut self) -> Option<AppEvent> {
+        if self.loading_state.is_loading() {
+            return None;
         }
-        if let Some(s) = s {
-            builder = builder.with_secret_access_key(s);
+        let ws = self.workspace.as_ref()?;
+        let index = ws.current?;
+        if !ws.has_file_list() && ws.tabs.len() == 1 {
+            return None;
         }
-        if let Some(r) = r {
-            builder = builder.with_region(r);
+        drop(self.take_tab_dataset());
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.len_count_inflight = None;
+        self.len_count_failed = None;
+        self.parquet_metadata_cache = None;
+        let ws = self.workspace.as_mut()?;
+        match ws.remove_tab(index) {
+            Some(next) => self.switch_workspace_tab(next),
+            None => {
+                ws.list_visible = true;
+                None
+            }
         }
-        let store = builder
-            .build()
-            .map_err(|e| color_eyre::eyre::eyre!("S3 config failed: {}", e))?;
-        Ok(Arc::new(store))
     }
 
-    #[cfg(feature = "cloud")]
-    fn build_gcs_object_store(gs_url: &str) -> Result<Arc<dyn object_store::ObjectStore>> {
-        let (path_part, _ext) = source::url_path_extension(gs_url);
-        let (bucket, _key) = path_part
-            .split_once('/')
-            .ok_or_else(|| color_eyre::eyre::eyre!("GCS URL must be gs://bucket/key"))?;
-        let store = object_store::gcp::GoogleCloudStorageBuilder::from_env()
-            .with_url(gs_url)
-            .with_bucket_name(bucket)
-            .build()
-            .map_err(|e| color_eyre::eyre::eyre!("GCS config failed: {}", e))?;
-        Ok(Arc::new(store))
+    /// Open the file selected in the workspace list in a new tab, or switch to its tab.
+    fn open_workspace_selection(&mut self) -> Option<AppEvent> {
+        let path = self.workspace.as_ref()?.selected_entry()?.path.clone();
+        self.open_workspace_tab(path)
     }
 
-    /// Human-readable byte size for download confirmation modal.
-    fn format_bytes(n: u64) -> String {
-        const KB: u64 = 1024;
-        const MB: u64 = KB * 1024;
-        const GB: u64 = MB * 1024;
-        const TB: u64 = GB * 1024;
-        if n >= TB {
-            format!("{:.2} TB", n as f64 / TB as f64)
-        } else if n >= GB {
-            format!("{:.2} GB", n as f64 / GB as f64)
-        } else if n >= MB {
-            format!("{:.2} MB", n as f64 / MB as f64)
-        } else if n >= KB {
-            format!("{:.2} KB", n as f64 / KB as f64)
-        } else {
-            format!("{} bytes", n)
-        }
+    /// Open every file with the selected file's extension (`dir/*.csv`) as one table, in a tab.
+    fn open_workspace_extension_group(&mut self) -> Option<AppEvent> {
+        let pattern = self
+            .workspace
+            .as_ref()?
+            .selected_entry()?
+            .extension_pattern()?;
+        self.open_workspace_tab(pattern)
     }
 
-    #[cfg(feature = "http")]
-    fn fetch_remote_size_http(url: &str) -> Result<Option<u64>> {
-        let response = ureq::request("HEAD", url)
-            .timeout(std::time::Duration::from_secs(15))
-            .call();
-        match response {
-            Ok(r) => Ok(r
-                .header("Content-Length")
-                .and_then(|s| s.parse::<u64>().ok())),
-            Err(_) => Ok(None),
-        }
+    /// Switch to the tab for `path`, adding it when it isn't open yet.
+    fn open_workspace_tab(&mut self, path: PathBuf) -> Option<AppEvent> {
+        let ws = self.workspace.as_mut()?;
+        let index = match ws.tab_index(&path) {
+            Some(index) => index,
+            None => {
+                ws.tabs.push(workspace::WorkspaceTab { path, saved: None });
+                ws.tabs.len() - 1
+            }
+        };
+        self.switch_workspace_tab(index)
     }
 
-    #[cfg(feature = "cloud")]
-    fn fetch_remote_size_s3(
-        s3_url: &str,
-        cloud: &crate::config::CloudConfig,
-        options: &OpenOptions,
-        runtime: &tokio::runtime::Handle,
-    ) -> Result<Option<u64>> {
-        use object_store::path::Path as OsPath;
-        use object_store::ObjectStore;
-
-        let (path_part, _ext) = source::url_path_extension(s3_url);
-        let (bucket, key) = path_part
-            .split_once('/')
-            .ok_or_else(|| color_eyre::eyre::eyre!("S3 URL must be s3://bucket/key"))?;
-        if key.is_empty() {
-            return Ok(None);
-        }
-        let mut builder = object_store::aws::AmazonS3Builder::from_env()
-            .with_url(s3_url)
-            .with_bucket_name(bucket);
-        let e = options
-            .s3_endpoint_url_override
-            .as_ref()
-            .or(cloud.s3_endpoint_url.as_ref());
-        let k = options
-            .s3_access_key_id_override
-            .as_ref()
-            .or(cloud.s3_access_key_id.as_ref());
-        let s = options
-            .s3_secret_access_key_override
-            .as_ref()
-            .or(cloud.s3_secret_access_key.as_ref());
-        let r = options
-            .s3_region_override
+    /// Open the export controls; `selection` (rows within the view) starts on the selected
+    /// rows scope.
+    fn open_export_modal(&mut self, selection: Option<(usize, usize)>) {
+        // Load config to get delimiter preference
+        let config_delimiter = AppConfig::load(APP_NAME)
+            .ok()
+            .and_then(|config| config.file_loading.delimiter);
+        self.export_modal.open(
+            self.original_file_format,
+            self.history_limit,
+            &self.theme,
+            self.original_file_delimiter,
+            config_delimiter,
+        );
+        let view_rows = self
+            .data_table_state
             .as_ref()
-            .or(cloud.s3_region.as_ref());
-        if let Some(e) = e {
-            builder = builder.with_endpoint(e);
-        }
-        if let Some(k) = k {
-            builder = builder.with_access_key_id(k);
-        }
-        if let Some(s) = s {
-            builder = builder.with_secret_access_key(s);
-        }
-        if let Some(r) = r {
-            builder = builder.with_region(r);
-        }
-        let store = builder
-            .build()
-            .map_err(|e| color_eyre::eyre::eyre!("S3 config failed: {}", e))?;
-        let path = OsPath::from(key);
-        match runtime.block_on(store.head(&path)) {
-            Ok(meta) => Ok(Some(meta.size)),
-            Err(_) => Ok(None),
+            .and_then(|state| state.is_num_rows_valid().then_some(state.num_rows));
+        let page_range = self.page_range();
+        self.export_modal
+            .set_ranges(view_rows, page_range, selection);
+        if let Some(state) = &self.data_table_state {
+            self.export_modal.columns = state.schema.iter_names().map(|n| n.to_string()).collect();
         }
+        self.input_mode = InputMode::Export;
     }
 
-    #[cfg(feature = "cloud")]
-    fn fetch_remote_size_gcs(
-        gs_url: &str,
-        _options: &OpenOptions,
-        runtime: &tokio::runtime::Handle,
-    ) -> Result<Option<u64>> {
-        use object_store::path::Path as OsPath;
-        use object_store::ObjectStore;
-
-        let (path_part, _ext) = source::url_path_extension(gs_url);
-        let (bucket, key) = path_part
-            .split_once('/')
-            .ok_or_else(|| color_eyre::eyre::eyre!("GCS URL must be gs://bucket/key"))?;
-        if key.is_empty() {
-            return Ok(None);
-        }
-        let store = object_store::gcp::GoogleCloudStorageBuilder::from_env()
-            .with_url(gs_url)
-            .with_bucket_name(bucket)
-            .build()
-            .map_err(|e| color_eyre::eyre::eyre!("GCS config failed: {}", e))?;
-        let path = OsPath::from(key);
-        match runtime.block_on(store.head(&path)) {
-            Ok(meta) => Ok(Some(meta.size)),
-            Err(_) => Ok(None),
-        }
+    /// (offset, len) of the rows on screen, in the current view's order. None when the page is
+    /// empty.
+    fn page_range(&self) -> Option<(usize, usize)> {
+        let state = self.data_table_state.as_ref()?;
+        let page_rows = match state.num_rows_if_valid() {
+            Some(n) => state.visible_rows.min(n.saturating_sub(state.start_row)),
+            None => state.visible_rows,
+        };
+        (page_rows > 0).then_some((state.start_row, page_rows))
     }
 
-    #[cfg(feature = "http")]
-    fn download_http_to_temp(
-        url: &str,
-        temp_dir: Option<&Path>,
-        extension: Option<&str>,
-    ) -> Result<PathBuf> {
-        let dir = temp_dir
-            .map(Path::to_path_buf)
-            .unwrap_or_else(std::env::temp_dir);
-        let suffix = extension
-            .map(|e| format!(".{e}"))
-            .unwrap_or_else(|| ".tmp".to_string());
-        let mut temp = tempfile::Builder::new()
-            .suffix(&suffix)
-            .tempfile_in(&dir)
-            .map_err(|_| color_eyre::eyre::eyre!("Could not create a temporary file."))?;
-        let response = ureq::get(url)
-            .timeout(std::time::Duration::from_secs(300))
-            .call()
-            .map_err(|e| {
-                color_eyre::eyre::eyre!("Download failed. Check the URL and your connection: {}", e)
-            })?;
-        let status = response.status();
-        if status >= 400 {
-            return Err(color_eyre::eyre::eyre!(
-                "Server returned {} {}. Check the URL.",
-                status,
-                response.status_text()
-            ));
+    /// (offset, len) of the selected row plus `export.context_rows` rows on each side, in the
+    /// current view's order. Clamped to the view when its row count is known.
+    fn selected_row_context(&self) -> Option<(usize, usize)> {
+        let state = self.data_table_state.as_ref()?;
+        let row = state.start_row + state.table_state.selected()?;
+        let context = self.app_config.export.context_rows;
+        let offset = row.saturating_sub(context);
+        let mut end = row.saturating_add(context).saturating_add(1);
+        if let Some(total) = state.num_rows_if_valid() {
+            end = end.min(total.max(row + 1));
         }
-        std::io::copy(&mut response.into_reader(), &mut temp)
-            .map_err(|_| color_eyre::eyre::eyre!("Download failed while saving the file."))?;
-        let (_file, path) = temp
-            .keep()
-            .map_err(|_| color_eyre::eyre::eyre!("Could not save the downloaded file."))?;
-        Ok(path)
+        Some((offset, end - offset))
     }
 
-    #[cfg(feature = "cloud")]
-    fn download_s3_to_temp(
-        s3_url: &str,
-        cloud: &crate::config::CloudConfig,
-        options: &OpenOptions,
-        runtime: &tokio::runtime::Handle,
-    ) -> Result<PathBuf> {
-        use object_store::path::Path as OsPath;
-        use object_store::ObjectStore;
+    /// Collect the selected row and its context rows as CSV in the background, then copy them.
+    fn spawn_copy_row_context(&mut self) -> Option<AppEvent> {
+        let (offset, len) = self.selected_row_context()?;
+        let state = self.data_table_state.as_ref()?;
+        let lf = state.lf.clone().slice(offset as i64, len as IdxSize);
+        let streaming = state.polars_streaming;
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Copying rows...", move |gen, tx| {
+            let result = crate::statistics::collect_lazy(lf, streaming).and_then(|mut df| {
+                let mut bytes = Vec::new();
+                CsvWriter::new(&mut bytes).finish(&mut df)?;
+                Ok((String::from_utf8_lossy(&bytes).into_owned(), df.height()))
+            });
+            let _ = tx.send(match result {
+                Ok((csv, rows)) => AppEvent::BackgroundRowContextReady {
+                    generation: gen,
+                    csv,
+                    offset,
+                    rows,
+                },
+                Err(e) => AppEvent::BackgroundError {
+                    generation: gen,
+                    message: format!(
+                        "Copy failed: {}",
+                        crate::error_display::user_message_from_polars(&e)
+                    ),
+                },
+            });
+        });
+        None
+    }
 
-        let (path_part, ext) = source::url_path_extension(s3_url);
-        let (bucket, key) = path_part
-            .split_once('/')
-            .ok_or_else(|| color_eyre::eyre::eyre!("S3 URL must be s3://bucket/key"))?;
-        if key.is_empty() {
-            return Err(color_eyre::eyre::eyre!(
-                "S3 URL must point to an object (e.g. s3://bucket/path/file.csv)"
-            ));
-        }
+    /// Collect the selected row as a JSON object in the background, then copy it.
+    fn spawn_copy_row_json(&mut self) -> Option<AppEvent> {
+        let state = self.data_table_state.as_ref()?;
+        let row = state.start_row + state.table_state.selected()?;
+        let lf = state.lf.clone().slice(row as i64, 1);
+        let streaming = state.polars_streaming;
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Copying row...", move |gen, tx| {
+            let result = crate::statistics::collect_lazy(lf, streaming)
+                .and_then(|mut df| record_json::row_to_json(&mut df));
+            let _ = tx.send(match result {
+                Ok(json) => AppEvent::BackgroundRowJsonReady {
+                    generation: gen,
+                    json,
+                    row,
+                },
+                Err(e) => AppEvent::BackgroundError {
+                    generation: gen,
+                    message: format!(
+                        "Copy failed: {}",
+                        crate::error_display::user_message_from_polars(&e)
+                    ),
+                },
+            });
+        });
+        None
+    }
 
-        let mut builder = object_store::aws::AmazonS3Builder::from_env()
-            .with_url(s3_url)
-            .with_bucket_name(bucket);
-        let e = options
-            .s3_endpoint_url_override
-            .as_ref()
-            .or(cloud.s3_endpoint_url.as_ref());
-        let k = options
-            .s3_access_key_id_override
-            .as_ref()
-            .or(cloud.s3_access_key_id.as_ref());
-        let s = options
-            .s3_secret_access_key_override
-            .as_ref()
-            .or(cloud.s3_secret_access_key.as_ref());
-        let r = options
-            .s3_region_override
-            .as_ref()
-            .or(cloud.s3_region.as_ref());
-        if let Some(e) = e {
-            builder = builder.with_endpoint(e);
-        }
-        if let Some(k) = k {
-            builder = builder.with_access_key_id(k);
-        }
-        if let Some(s) = s {
-            builder = builder.with_secret_access_key(s);
-        }
-        if let Some(r) = r {
-            builder = builder.with_region(r);
-        }
-        let store = builder
-            .build()
-            .map_err(|e| color_eyre::eyre::eyre!("S3 config failed: {}", e))?;
+    /// Collect the selected row in the background, then show it in the record view.
+    fn spawn_record_view(&mut self) -> Option<AppEvent> {
+        let state = self.data_table_state.as_ref()?;
+        let row = state.start_row + state.table_state.selected()?;
+        let lf = state.lf.clone().slice(row as i64, 1);
+        let streaming = state.polars_streaming;
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Loading row...", move |gen, tx| {
+            let record = crate::statistics::collect_lazy(lf, streaming)
+                .and_then(|df| record_view::RecordView::from_frame(&df, row))
+                .map(Box::new)
+                .map_err(|e| crate::error_display::user_message_from_polars(&e));
+            let _ = tx.send(AppEvent::BackgroundRecordReady {
+                generation: gen,
+                record,
+            });
+        });
+        None
+    }
 
-        let path = OsPath::from(key);
-        let get_result = runtime.block_on(store.get(&path)).map_err(|e| {
-            color_eyre::eyre::eyre!("Could not read from S3. Check credentials and URL: {}", e)
-        })?;
-        let bytes = runtime
-            .block_on(get_result.bytes())
-            .map_err(|e| color_eyre::eyre::eyre!("Could not read S3 object body: {}", e))?;
+    /// Collect the cells of `block` as tab-separated text in the background, then copy them.
+    fn spawn_copy_cells(
+        &mut self,
+        block: widgets::datatable::VisualBlock,
+        description: String,
+    ) -> Option<AppEvent> {
+        let state = self.data_table_state.as_ref()?;
+        let lf = state
+            .lf
+            .clone()
+            .slice(block.first_row as i64, block.rows as IdxSize)
+            .select(block.columns.iter().map(col).collect::<Vec<_>>());
+        let streaming = state.polars_streaming;
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Copying cells...", move |gen, tx| {
+            let result = crate::statistics::collect_lazy(lf, streaming).map(|df| yank::to_tsv(&df));
+            let _ = tx.send(match result {
+                Ok(text) => AppEvent::BackgroundCellsReady {
+                    generation: gen,
+                    text,
+                    description,
+                },
+                Err(e) => AppEvent::BackgroundError {
+                    generation: gen,
+                    message: format!(
+                        "Copy failed: {}",
+                        crate::error_display::user_message_from_polars(&e)
+                    ),
+                },
+            });
+        });
+        None
+    }
 
-        let dir = options.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
-        let suffix = ext
-            .as_ref()
-            .map(|e| format!(".{e}"))
-            .unwrap_or_else(|| ".tmp".to_string());
-        let mut temp = tempfile::Builder::new()
-            .suffix(&suffix)
-            .tempfile_in(&dir)
-            .map_err(|_| color_eyre::eyre::eyre!("Could not create a temporary file."))?;
-        std::io::copy(&mut std::io::Cursor::new(bytes.as_ref()), &mut temp)
-            .map_err(|_| color_eyre::eyre::eyre!("Could not write downloaded file."))?;
-        let (_file, path_buf) = temp
-            .keep()
-            .map_err(|_| color_eyre::eyre::eyre!("Could not save the downloaded file."))?;
-        Ok(path_buf)
+    /// Collect the visible page (all columns of the view) in the background and copy it as a
+    /// Markdown table (`zm`) or CSV with a header (`zc`).
+    fn spawn_copy_page(&mut self, markdown: bool) -> Option<AppEvent> {
+        let (offset, len) = self.page_range()?;
+        let state = self.data_table_state.as_ref()?;
+        let lf = state.lf.clone().slice(offset as i64, len as IdxSize);
+        let streaming = state.polars_streaming;
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Copying page...", move |gen, tx| {
+            let result = crate::statistics::collect_lazy(lf, streaming).and_then(|mut df| {
+                if markdown {
+                    return Ok((yank::to_markdown(&df), df.height()));
+                }
+                let mut bytes = Vec::new();
+                CsvWriter::new(&mut bytes).finish(&mut df)?;
+                Ok((String::from_utf8_lossy(&bytes).into_owned(), df.height()))
+            });
+            let _ = tx.send(match result {
+                Ok((text, rows)) => AppEvent::BackgroundCellsReady {
+                    generation: gen,
+                    text,
+                    description: format!(
+                        "rows {}–{} as {}",
+                        offset + 1,
+                        offset + rows,
+                        if markdown { "Markdown" } else { "CSV" }
+                    ),
+                },
+                Err(e) => AppEvent::BackgroundError {
+                    generation: gen,
+                    message: format!(
+                        "Copy failed: {}",
+                        crate::error_display::user_message_from_polars(&e)
+                    ),
+                },
+            });
+        });
+        None
     }
 
-    #[cfg(feature = "cloud")]
-    fn download_gcs_to_temp(
-        gs_url: &str,
-        options: &OpenOptions,
-        runtime: &tokio::runtime::Handle,
-    ) -> Result<PathBuf> {
-        use object_store::path::Path as OsPath;
-        use object_store::ObjectStore;
+    /// Copy the selected cell (`y`) or row (`Y`), or the visual block or selected rows when
+    /// one is active.
+    fn copy_selection(&mut self, whole_row: bool) -> Option<AppEvent> {
+        let state = self.data_table_state.as_mut()?;
+        let (block, description) = if let Some(block) = state.visual_block() {
+            let description = if state.selected_rows().is_some() {
+                format!(
+                    "rows {}–{}",
+                    block.first_row + 1,
+                    block.first_row + block.rows
+                )
+            } else {
+                format!("{} × {} cells", block.rows, block.columns.len())
+            };
+            state.clear_visual_block();
+            (block, description)
+        } else {
+            let block = state.selected_row_block(whole_row)?;
+            let description = if whole_row {
+                format!("row {}", block.first_row + 1)
+            } else {
+                format!("\"{}\" of row {}", block.columns[0], block.first_row + 1)
+            };
+            (block, description)
+        };
+        self.spawn_copy_cells(block, description)
+    }
 
-        let (path_part, ext) = source::url_path_extension(gs_url);
-        let (bucket, key) = path_part
-            .split_once('/')
-            .ok_or_else(|| color_eyre::eyre::eyre!("GCS URL must be gs://bucket/key"))?;
-        if key.is_empty() {
-            return Err(color_eyre::eyre::eyre!(
-                "GCS URL must point to an object (e.g. gs://bucket/path/file.csv)"
-            ));
+    /// Compute quick aggregates for the selected column of the current view in the background,
+    /// over the selected rows only when rows are selected (which ends the selection).
+    fn spawn_aggregate_peek(&mut self) -> Option<AppEvent> {
+        let state = self.data_table_state.as_mut()?;
+        let column = state.selected_column()?.to_string();
+        let rows = state.selected_rows();
+        let (lf, cached_rows) = match rows {
+            Some((offset, len)) => {
+                state.clear_visual_block();
+                (
+                    state.lf.clone().slice(offset as i64, len as IdxSize),
+                    Some(len),
+                )
+            }
+            None => (state.lf.clone(), state.num_rows_if_valid()),
+        };
+        let streaming = state.polars_streaming;
+        let threshold = self.sampling_threshold;
+        let sampling = self.sampling();
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Aggregating...", move |gen, tx| {
+            let result = count_rows(&lf, cached_rows, streaming).and_then(|total_rows| {
+                aggregate_peek::AggregatePeek::compute(
+                    &lf, &column, total_rows, threshold, &sampling, streaming,
+                )
+                .map(|peek| peek.for_rows(rows))
+            });
+            let _ = tx.send(match result {
+                Ok(peek) => AppEvent::BackgroundAggregatePeekReady {
+                    generation: gen,
+                    peek: Box::new(peek),
+                },
+                Err(e) => AppEvent::BackgroundError {
+                    generation: gen,
+                    message: crate::error_display::user_message_from_report(&e, None),
+                },
+            });
+        });
+        None
+    }
+
+    /// Compute statistics of the selected column for the column statistics sidebar, unless they
+    /// are cached for the current view or another column's are still being computed. Runs beside
+    /// other work: it neither sets `busy` nor uses `task_generation`, so scrolling stays free.
+    fn request_column_stats(&mut self) {
+        let Some(state) = self.data_table_state.as_ref() else {
+            return;
+        };
+        let Some(column) = state.selected_column() else {
+            return;
+        };
+        let generation = state.len_generation();
+        if !self.column_stats.should_compute(generation, column) {
+            return;
         }
+        let column = column.to_string();
+        let lf = state.lf.clone();
+        let total_rows = state.num_rows_if_valid();
+        let streaming = state.polars_streaming;
+        let threshold = self.sampling_threshold;
+        let sampling = self.sampling();
+        self.column_stats.start(generation, &column);
+        let tx = self.events.clone();
+        self.runtime.spawn_blocking(move || {
+            let stats = column_stats::ColumnStats::compute(
+                &lf, &column, total_rows, threshold, &sampling, streaming,
+            )
+            .map(Box::new)
+            .map_err(|e| crate::error_display::user_message_from_report(&e, None));
+            let _ = tx.send(AppEvent::BackgroundColumnStatsReady {
+                len_generation: generation,
+                column,
+                stats,
+            });
+        });
+    }
 
-        let store = object_store::gcp::GoogleCloudStorageBuilder::from_env()
-            .with_url(gs_url)
-            .with_bucket_name(bucket)
-            .build()
-            .map_err(|e| color_eyre::eyre::eyre!("GCS config failed: {}", e))?;
+    /// Compute the numeric column ranges of the current view in the background when heatmap mode
+    /// is on and they aren't known yet.
+    fn request_heatmap_ranges(&mut self) {
+        let Some(state) = self.data_table_state.as_ref() else {
+            return;
+        };
+        let generation = state.len_generation();
+        if !self.heatmap.should_compute(generation) {
+            return;
+        }
+        let lf = state.lf.clone();
+        let total_rows = state.num_rows_if_valid();
+        let streaming = state.polars_streaming;
+        let threshold = self.sampling_threshold;
+        let sampling = self.sampling();
+        self.heatmap.start(generation);
+        let tx = self.events.clone();
+        self.runtime.spawn_blocking(move || {
+            let ranges = heatmap::column_ranges(&lf, total_rows, threshold, &sampling, streaming)
+                .map_err(|e| crate::error_display::user_message_from_report(&e, None));
+            let _ = tx.send(AppEvent::BackgroundHeatmapReady {
+                len_generation: generation,
+                ranges,
+            });
+        });
+    }
 
-        let path = OsPath::from(key);
-        let get_result = runtime.block_on(store.get(&path)).map_err(|e| {
-            color_eyre::eyre::eyre!("Could not read from GCS. Check credentials and URL: {}", e)
-        })?;
-        let bytes = runtime
-            .block_on(get_result.bytes())
-            .map_err(|e| color_eyre::eyre::eyre!("Could not read GCS object body: {}", e))?;
+    /// Open the value counts popup for the selected column and count its values in the
+    /// background, reporting each phase to the popup.
+    fn spawn_value_counts(&mut self) -> Option<AppEvent> {
+        let state = self.data_table_state.as_ref()?;
+        let column = state.selected_column()?.to_string();
+        let lf = state.lf.clone();
+        let cached_rows = state.num_rows_if_valid();
+        let streaming = state.polars_streaming;
+        let threshold = self.sampling_threshold;
+        let sampling = self.sampling();
+        self.value_counts = Some(value_counts::ValueCountsPopup::new(column.clone()));
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Counting values...", move |gen, tx| {
+            let counts = value_counts::ValueCounts::compute(
+                &lf,
+                &column,
+                cached_rows,
+                threshold,
+                &sampling,
+                streaming,
+                |phase| {
+                    let _ = tx.send(AppEvent::BackgroundValueCountsPhase {
+                        generation: gen,
+                        phase,
+                    });
+                },
+            )
+            .map(Box::new)
+            .map_err(|e| crate::error_display::user_message_from_report(&e, None));
+            let _ = tx.send(AppEvent::BackgroundValueCountsReady {
+                generation: gen,
+                counts,
+            });
+        });
+        None
+    }
 
-        let dir = options.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
-        let suffix = ext
-            .as_ref()
-            .map(|e| format!(".{e}"))
-            .unwrap_or_else(|| ".tmp".to_string());
-        let mut temp = tempfile::Builder::new()
-            .suffix(&suffix)
-            .tempfile_in(&dir)
-            .map_err(|_| color_eyre::eyre::eyre!("Could not create a temporary file."))?;
-        std::io::copy(&mut std::io::Cursor::new(bytes.as_ref()), &mut temp)
-            .map_err(|_| color_eyre::eyre::eyre!("Could not write downloaded file."))?;
-        let (_file, path_buf) = temp
-            .keep()
-            .map_err(|_| color_eyre::eyre::eyre!("Could not save the downloaded file."))?;
-        Ok(path_buf)
+    /// Open the value picker of the Filter tab on its column and count the column's values in
+    /// the source (filters replace each other, so values they hide are listed too). Date and
+    /// datetime columns get the date range instead.
+    fn spawn_filter_values(&mut self) -> Option<AppEvent> {
+        let state = self.data_table_state.as_ref()?;
+        let filter = &self.sort_filter_modal.filter;
+        let column = filter.available_columns.get(filter.new_column_idx)?.clone();
+        let lf = state.source_lf_clone();
+        let streaming = state.polars_streaming;
+        if matches!(
+            state.schema.get(&column),
+            Some(DataType::Date | DataType::Datetime(..))
+        ) {
+            self.sort_filter_modal.filter.date_range =
+                Some(filter_modal::DateRange::new(column.clone()));
+            self.task_generation = self.task_generation.wrapping_add(1);
+            self.spawn_bg("Finding dates...", move |gen, tx| {
+                let bounds = filter_modal::DateRange::column_bounds(&lf, &column, streaming)
+                    .map_err(|e| crate::error_display::user_message_from_polars(&e));
+                let _ = tx.send(AppEvent::BackgroundFilterDateRangeReady {
+                    generation: gen,
+                    bounds,
+                });
+            });
+            return None;
+        }
+        let threshold = self.sampling_threshold;
+        let sampling = self.sampling();
+        self.sort_filter_modal.filter.value_picker =
+            Some(filter_modal::ValuePicker::new(column.clone()));
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Counting values...", move |gen, tx| {
+            let counts = value_counts::ValueCounts::compute(
+                &lf,
+                &column,
+                None,
+                threshold,
+                &sampling,
+                streaming,
+                |_| {},
+            )
+            .map(Box::new)
+            .map_err(|e| crate::error_display::user_message_from_report(&e, None));
+            let _ = tx.send(AppEvent::BackgroundFilterValuesReady {
+                generation: gen,
+                counts,
+            });
+        });
+        None
     }
 
-    /// Build LazyFrame from paths for phased loading (non-compressed only). Caller must not use for compressed CSV.
-    fn build_lazyframe_from_paths(
-        &mut self,
-        paths: &[PathBuf],
-        options: &OpenOptions,
-    ) -> Result<LazyFrame> {
-        let path = &paths[0];
-        match source::input_source(path) {
-            source::InputSource::Http(_url) => {
-                #[cfg(feature = "http")]
-                {
-                    return Err(color_eyre::eyre::eyre!(
-                        "HTTP/HTTPS load is handled in the event loop; this path should not be reached."
-                    ));
-                }
-                #[cfg(not(feature = "http"))]
-                {
-                    return Err(color_eyre::eyre::eyre!(
-                        "HTTP/HTTPS URLs are not supported in this build. Rebuild with default features."
-                    ));
-                }
-            }
-            source::InputSource::S3(url) => {
-                #[cfg(feature = "cloud")]
-                {
-                    let full = format!("s3://{url}");
-                    let cloud_opts = Self::build_s3_cloud_options(&self.app_config.cloud, options);
-                    let pl_path = PlPathRef::new(&full).into_owned();
-                    let is_glob = full.contains('*') || full.ends_with('/');
-                    let hive_options = if is_glob {
-                        polars::io::HiveOptions::new_enabled()
-                    } else {
-                        polars::io::HiveOptions::default()
-                    };
-                    let args = ScanArgsParquet {
-                        cloud_options: Some(cloud_opts),
-                        hive_options,
-                        glob: is_glob,
-                        ..Default::default()
-                    };
-                    let lf = LazyFrame::scan_parquet(pl_path, args).map_err(|e| {
-                        color_eyre::eyre::eyre!(
-                            "Could not read from S3. Check credentials and URL: {}",
-                            e
-                        )
-                    })?;
-                    let state = DataTableState::from_lazyframe(lf, options)?;
-                    return Ok(state.lf);
-                }
-                #[cfg(not(feature = "cloud"))]
-                {
-                    return Err(color_eyre::eyre::eyre!(
-                        "S3 is not supported in this build. Rebuild with default features and set AWS credentials (e.g. AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY, AWS_REGION)."
-                    ));
-                }
-            }
-            source::InputSource::Gcs(url) => {
-                #[cfg(feature = "cloud")]
-                {
-                    let full = format!("gs://{url}");
-                    let pl_path = PlPathRef::new(&full).into_owned();
-                    let is_glob = full.contains('*') || full.ends_with('/');
-                    let hive_options = if is_glob {
-                        polars::io::HiveOptions::new_enabled()
-                    } else {
-                        polars::io::HiveOptions::default()
-                    };
-                    let args = ScanArgsParquet {
-                        cloud_options: Some(CloudOptions::default()),
-                        hive_options,
-                        glob: is_glob,
-                        ..Default::default()
-                    };
-                    let lf = LazyFrame::scan_parquet(pl_path, args).map_err(|e| {
-                        color_eyre::eyre::eyre!(
-                            "Could not read from GCS. Check credentials and URL: {}",
-                            e
-                        )
-                    })?;
-                    let state = DataTableState::from_lazyframe(lf, options)?;
-                    return Ok(state.lf);
-                }
-                #[cfg(not(feature = "cloud"))]
-                {
-                    return Err(color_eyre::eyre::eyre!(
-                        "GCS (gs://) is not supported in this build. Rebuild with default features."
-                    ));
-                }
-            }
-            source::InputSource::Local(_) => {}
+    /// Move the cursor to the next (or previous) row matching the regex search, wrapping around
+    /// the view. When the view's matching rows aren't known yet they are found first with a
+    /// background scan. Returns GoToLine when the row isn't buffered.
+    fn jump_to_regex_match(&mut self, forward: bool) -> Option<AppEvent> {
+        let state = self.data_table_state.as_mut()?;
+        let plan = regex_search::view_plan(&state.lf);
+        let row = state.start_row + state.table_state.selected().unwrap_or(0);
+        let search = state.regex_search.as_mut()?;
+        let Some(rows) = search.rows_for(&plan) else {
+            return self.spawn_regex_scan(plan, forward);
+        };
+        let (pos, target) = regex_search::next_match(rows, row, forward)?;
+        search.current = Some(pos);
+        if state.scroll_would_trigger_collect(target as i64 - state.start_row as i64) {
+            self.busy = true;
+            return Some(AppEvent::GoToLine(target));
         }
+        self.handle_scroll(|s| s.scroll_to_row_centered(target))
+    }
 
-        if paths.len() == 1 && options.hive {
-            let path_str = path.as_os_str().to_string_lossy();
-            let is_single_file = path.exists()
-                && path.is_file()
-                && !path_str.contains('*')
-                && !path_str.contains("**");
-            if !is_single_file {
-                let use_parquet_hive = path.is_dir()
-                    || path_str.contains(".parquet")
-                    || path_str.contains("*.parquet");
-                if use_parquet_hive {
-                    // Only build LazyFrame here; schema + partition discovery happen in DoLoadSchema ("Caching schema")
-                    return DataTableState::scan_parquet_hive(path);
-                }
-                return Err(color_eyre::eyre::eyre!(
-                    "With --hive use a directory or a glob pattern for Parquet (e.g. path/to/dir or path/**/*.parquet)"
+    /// Find the rows of the view (whose plan is `plan`) matching the regex search in the
+    /// background, then jump to the next (or previous) one.
+    fn spawn_regex_scan(&mut self, plan: String, forward: bool) -> Option<AppEvent> {
+        let state = self.data_table_state.as_ref()?;
+        let search = state.regex_search.as_ref()?;
+        let pattern = search.pattern.clone();
+        let column = search.column.clone();
+        let lf = state.lf.clone();
+        let total_rows = state.num_rows_if_valid();
+        let streaming = state.polars_streaming;
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg(
+            &regex_scan_status(column.as_deref(), None),
+            move |gen, tx| {
+                let rows = regex_search::find_matching_rows(
+                    &lf,
+                    &pattern,
+                    column.as_deref(),
+                    total_rows,
+                    streaming,
+                    |progress| {
+                        let _ = tx.send(AppEvent::BackgroundRegexScanProgress {
+                            generation: gen,
+                            status: regex_scan_status(column.as_deref(), Some(progress)),
+                        });
+                    },
+                )
+                .map_err(|e| crate::error_display::user_message_from_polars(&e));
+                let _ = tx.send(AppEvent::BackgroundRegexMatchesReady {
+                    generation: gen,
+                    pattern,
+                    plan,
+                    rows,
+                    forward,
+                });
+            },
+        );
+        None
+    }
+
+    /// Copy standard input to a temp file in the background, then open that file. The format
+    /// must be given since piped data has no extension.
+    fn spawn_buffer_stdin(&mut self, options: &OpenOptions) -> Option<AppEvent> {
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.loading_state = LoadingState::Loading {
+            file_path: Some(PathBuf::from(source::STDIN_PATH)),
+            file_size: 0,
+            current_phase: "Reading standard input".to_string(),
+            progress_percent: 5,
+        };
+        let options = options.clone();
+        self.spawn_bg("Reading standard input...", move |gen, tx| {
+            let temp_path = source::buffer_stdin_to_temp(
+                std::io::stdin().lock(),
+                options.format,
+                options.compression,
+                options.temp_dir.as_deref(),
+            )
+            .map_err(|e| crate::error_display::user_message_from_report(&e, None));
+            let _ = tx.send(AppEvent::BackgroundStdinReady {
+                generation: gen,
+                temp_path,
+                options,
+            });
+        });
+        None
+    }
+
+    /// Date/datetime columns of the current view, candidates for the gaps tool's time axis.
+    fn time_columns(&self) -> Vec<String> {
+        self.data_table_state
+            .as_ref()
+            .map(|state| {
+                state
+                    .schema
+                    .iter()
+                    .filter(|(_, dtype)| time_gaps::is_time_dtype(dtype))
+                    .map(|(name, _)| name.to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Find time series gaps and null runs in the current view in the background.
+    /// Uses the chosen time column if the view still has it, else the first date/datetime column.
+    fn spawn_time_gaps(&mut self) -> Option<AppEvent> {
+        let columns = self.time_columns();
+        let column = match self
+            .analysis_modal
+            .gap_time_column
+            .clone()
+            .filter(|c| columns.contains(c))
+            .or_else(|| columns.first().cloned())
+        {
+            Some(column) => column,
+            None => {
+                self.analysis_modal.gap_results = Some(Err(
+                    "No date or datetime column in the current view. Parse or cast a column to a date type first."
+                        .to_string(),
                 ));
+                return None;
             }
+        };
+        self.analysis_modal.gap_time_column = Some(column.clone());
+        self.analysis_modal.gap_results = None;
+        self.analysis_modal.gap_table_state.select(Some(0));
+        let lf = self.data_table_state.as_ref()?.lf.clone();
+        let frequency = self.analysis_modal.gap_frequency;
+        let streaming = self.app_config.performance.polars_streaming;
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Finding gaps...", move |gen, tx| {
+            let report = time_gaps::compute(&lf, &column, frequency, streaming)
+                .map(Box::new)
+                .map_err(|e| crate::error_display::user_message_from_report(&e, None));
+            let _ = tx.send(AppEvent::BackgroundTimeGapsReady {
+                generation: gen,
+                report,
+            });
+        });
+        None
+    }
+
+    /// Profiling of the current view as work that can run off the UI thread: binary columns
+    /// stubbed, sampled like the analysis tools. None without data.
+    fn profile_job(
+        &self,
+    ) -> Option<impl FnOnce() -> Result<profile_report::Profile> + Send + 'static> {
+        let state = self.data_table_state.as_ref()?;
+        let lf = state.lf.clone().select(state.binary_stub_exprs());
+        let streaming = state.polars_streaming;
+        let threshold = self.sampling_threshold;
+        let sampling = self.sampling();
+        let title = self
+            .path
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .map_or_else(|| "data".to_string(), |n| n.to_string_lossy().into_owned());
+        Some(move || profile_report::compute(&lf, &title, threshold, &sampling, streaming))
+    }
+
+    /// Prompt for the path of a profiling report, `<file>_profile.html` to start.
+    fn open_profile_prompt(&mut self) {
+        if self.data_table_state.is_none() {
+            return;
         }
+        let stem = self
+            .path
+            .as_ref()
+            .and_then(|p| p.file_stem())
+            .and_then(|s| s.to_str())
+            .unwrap_or("data");
+        let path = format!("{stem}_profile.html");
+        self.input_mode = InputMode::Editing;
+        self.input_type = Some(InputType::Profile);
+        // Cursor before the extension so typing renames the file
+        let cursor = stem.chars().count() + "_profile".len();
+        self.query_input.set_value(path);
+        self.query_input.set_cursor(cursor);
+        self.query_input.set_focused(true);
+    }
 
-        let effective_format = options.format.or_else(|| FileFormat::from_path(path));
+    /// Write the profiling report of the current view to `path` (HTML or Markdown by its
+    /// extension) in the background.
+    fn spawn_profile(&mut self, path: PathBuf) -> Option<AppEvent> {
+        let Some(format) = profile_report::ProfileFormat::from_path(&path) else {
+            self.error_modal.show(format!(
+                "Can't tell the report format from {}: use .html or .md",
+                path.display()
+            ));
+            return None;
+        };
+        let job = self.profile_job()?;
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Profiling...", move |gen, tx| {
+            let result = job()
+                .and_then(|profile| profile_report::write(&profile, &path, format))
+                .map_err(|e| crate::error_display::user_message_from_report(&e, Some(&path)));
+            let _ = tx.send(AppEvent::BackgroundProfileWritten {
+                generation: gen,
+                path,
+                result,
+            });
+        });
+        None
+    }
 
-        let lf = if paths.len() > 1 {
-            match effective_format {
-                Some(FileFormat::Parquet) => DataTableState::from_parquet_paths(
-                    paths,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Csv) => DataTableState::from_csv_paths(paths, options)?,
-                Some(FileFormat::Json) => DataTableState::from_json_paths(
-                    paths,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Jsonl) => DataTableState::from_json_lines_paths(
-                    paths,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Arrow) => DataTableState::from_ipc_paths(
-                    paths,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Avro) => DataTableState::from_avro_paths(
-                    paths,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Orc) => DataTableState::from_orc_paths(
-                    paths,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Tsv) | Some(FileFormat::Psv) | Some(FileFormat::Excel) | None => {
-                    if !paths.is_empty() && !path.exists() {
-                        return Err(std::io::Error::new(
-                            std::io::ErrorKind::NotFound,
-                            format!("File not found: {}", path.display()),
-                        )
-                        .into());
-                    }
-                    return Err(color_eyre::eyre::eyre!(
-                        "Unsupported file type for multiple files (parquet, csv, json, jsonl, ndjson, arrow/ipc/feather, avro, orc only)"
-                    ));
-                }
+    /// Rank the columns of the current view by cardinality in the background.
+    fn spawn_cardinality(&mut self) -> Option<AppEvent> {
+        let state = self.data_table_state.as_ref()?;
+        // Stub binary columns so their blobs are never materialized (see AnalysisChunk).
+        let lf = state.lf.clone().select(state.binary_stub_exprs());
+        let cached_rows = state.num_rows_if_valid();
+        let streaming = state.polars_streaming;
+        let threshold = self.sampling_threshold;
+        let sampling = self.sampling();
+        self.analysis_modal.cardinality_results = None;
+        self.analysis_modal.cardinality_table_state.select(Some(0));
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Ranking columns...", move |gen, tx| {
+            let report = count_rows(&lf, cached_rows, streaming)
+                .and_then(|total_rows| {
+                    cardinality::compute(&lf, total_rows, threshold, &sampling, streaming)
+                })
+                .map(Box::new)
+                .map_err(|e| crate::error_display::user_message_from_report(&e, None));
+            let _ = tx.send(AppEvent::BackgroundCardinalityReady {
+                generation: gen,
+                report,
+            });
+        });
+        None
+    }
+
+    /// Count the nulls of the current view per column, with their pattern, in the background.
+    fn spawn_missing_data(&mut self) -> Option<AppEvent> {
+        let state = self.data_table_state.as_ref()?;
+        // Stub binary columns so their blobs are never materialized (see AnalysisChunk).
+        let lf = state.lf.clone().select(state.binary_stub_exprs());
+        let cached_rows = state.num_rows_if_valid();
+        let streaming = state.polars_streaming;
+        let threshold = self.sampling_threshold;
+        let sampling = self.sampling();
+        self.analysis_modal.missing_results = None;
+        self.analysis_modal.missing_table_state.select(Some(0));
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Counting nulls...", move |gen, tx| {
+            let report = count_rows(&lf, cached_rows, streaming)
+                .and_then(|total_rows| {
+                    missing_data::compute(&lf, total_rows, threshold, &sampling, streaming)
+                })
+                .map(Box::new)
+                .map_err(|e| crate::error_display::user_message_from_report(&e, None));
+            let _ = tx.send(AppEvent::BackgroundMissingDataReady {
+                generation: gen,
+                report,
+            });
+        });
+        None
+    }
+
+    /// Find duplicated rows of the current view, compared on the tool's key columns, in the
+    /// background.
+    fn spawn_duplicates(&mut self) -> Option<AppEvent> {
+        let state = self.data_table_state.as_ref()?;
+        let lf = state.lf.clone();
+        let streaming = state.polars_streaming;
+        let keys = self.analysis_modal.duplicate_keys.clone();
+        self.analysis_modal.duplicate_results = None;
+        self.analysis_modal.duplicate_table_state.select(Some(0));
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Finding duplicates...", move |gen, tx| {
+            let report = duplicates::compute(&lf, &keys, streaming)
+                .map(Box::new)
+                .map_err(|e| crate::error_display::user_message_from_report(&e, None));
+            let _ = tx.send(AppEvent::BackgroundDuplicatesReady {
+                generation: gen,
+                report,
+            });
+        });
+        None
+    }
+
+    /// Build the crosstab of the current view on the tool's columns in the background.
+    fn spawn_crosstab(&mut self) -> Option<AppEvent> {
+        let state = self.data_table_state.as_ref()?;
+        let Some(spec) = self.analysis_modal.crosstab_spec.clone() else {
+            self.analysis_modal.crosstab_results = Some(Err(
+                "Press c to choose the columns: rows, columns[, values]".to_string(),
+            ));
+            return None;
+        };
+        let lf = state.lf.clone();
+        let streaming = state.polars_streaming;
+        self.analysis_modal.crosstab_results = None;
+        self.analysis_modal.crosstab_table_state.select(Some(0));
+        self.analysis_modal.crosstab_column_offset = 0;
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Building crosstab...", move |gen, tx| {
+            let table = crosstab::compute(&lf, &spec, streaming)
+                .map(Box::new)
+                .map_err(|e| crate::error_display::user_message_from_report(&e, None));
+            let _ = tx.send(AppEvent::BackgroundCrosstabReady {
+                generation: gen,
+                table,
+            });
+        });
+        None
+    }
+
+    /// Run the analysis plugin at `index` on the current view in the background.
+    fn spawn_plugin_tool(&mut self, index: usize) -> Option<AppEvent> {
+        let state = self.data_table_state.as_ref()?;
+        let tool = self.analysis_modal.plugins.get(index)?.clone();
+        let lf = state.lf.clone();
+        if let Some(result) = self.analysis_modal.plugin_results.get_mut(index) {
+            *result = None;
+        }
+        self.analysis_modal.plugin_table_state.select(Some(0));
+        self.task_generation = self.task_generation.wrapping_add(1);
+        let status = format!("Running {}...", tool.name());
+        self.spawn_bg(&status, move |gen, tx| {
+            let result = tool.compute(lf);
+            let _ = tx.send(AppEvent::BackgroundPluginToolReady {
+                generation: gen,
+                index,
+                result,
+            });
+        });
+        None
+    }
+
+    /// Close analysis and narrow the view to the duplicated rows found by the Duplicate Rows
+    /// tool, then open the export dialog on it when `export` is set. Does nothing when no
+    /// duplicates were found.
+    fn filter_view_to_duplicates(&mut self, export: bool) {
+        let keys = match &self.analysis_modal.duplicate_results {
+            Some(Ok(report)) if report.group_count > 0 => report.key_columns.clone(),
+            _ => return,
+        };
+        self.analysis_modal.close();
+        if let Some(state) = &mut self.data_table_state {
+            state.defer_collect = true;
+            state.filter_duplicates(&keys);
+            state.defer_collect = false;
+        }
+        if export {
+            self.open_export_modal(None);
+        }
+        self.spawn_async_collect("Filtering...");
+    }
+
+    /// Close analysis and add null filters from the Missing Data tool to the view's filters
+    /// (and the Sort & Filter statements, so they can be edited or cleared there).
+    fn apply_missing_data_filters(
+        &mut self,
+        columns: Vec<String>,
+        operator: FilterOperator,
+    ) -> Option<AppEvent> {
+        if columns.is_empty() {
+            return None;
+        }
+        self.analysis_modal.close();
+        let statements = &mut self.sort_filter_modal.filter.statements;
+        for column in columns {
+            statements.push(FilterStatement {
+                column,
+                operator,
+                value: String::new(),
+                logical_op: LogicalOperator::And,
+            });
+        }
+        Some(AppEvent::Filter(statements.clone()))
+    }
+
+    /// Compare the schemas of all files in the workspace list in the background.
+    fn spawn_workspace_schema_diff(&mut self) -> Option<AppEvent> {
+        let ws = self.workspace.as_ref()?;
+        let files: Vec<PathBuf> = ws.entries.iter().map(|e| e.path.clone()).collect();
+        let options = ws.options.clone();
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Comparing schemas...", move |gen, tx| {
+            let diff = schema_diff::SchemaDiff::compute(files, &options);
+            let _ = tx.send(AppEvent::BackgroundSchemaDiffReady {
+                generation: gen,
+                diff: Box::new(diff),
+            });
+        });
+        None
+    }
+
+    /// A file opened in a tab failed to load. Instead of exiting, drop its tab and return to
+    /// the file list (or, without one, to another tab) with the error shown. Returns false
+    /// when no tabs are in use.
+    fn recover_workspace_load_error(&mut self, message: &str) -> bool {
+        let Some(ws) = self.workspace.as_mut() else {
+            return false;
+        };
+        let mut next = None;
+        if self.data_table_state.is_none() {
+            if let Some(index) = ws.current {
+                next = ws.remove_tab(index);
             }
-        } else {
-            match effective_format {
-                Some(FileFormat::Parquet) => DataTableState::from_parquet(
-                    path,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Csv) => DataTableState::from_csv(path, options)?,
-                Some(FileFormat::Tsv) => DataTableState::from_delimited(path, b'\t', options)?,
-                Some(FileFormat::Psv) => DataTableState::from_delimited(path, b'|', options)?,
-                Some(FileFormat::Json) => DataTableState::from_json(
-                    path,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Jsonl) => DataTableState::from_json_lines(
-                    path,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Arrow) => DataTableState::from_ipc(
-                    path,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Avro) => DataTableState::from_avro(
-                    path,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                Some(FileFormat::Excel) => DataTableState::from_excel(
-                    path,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                    options.excel_sheet.as_deref(),
-                )?,
-                Some(FileFormat::Orc) => DataTableState::from_orc(
-                    path,
-                    options.pages_lookahead,
-                    options.pages_lookback,
-                    options.max_buffered_rows,
-                    options.max_buffered_mb,
-                    options.row_numbers,
-                    options.row_start_index,
-                )?,
-                None => {
-                    if paths.len() == 1 && !path.exists() {
-                        return Err(std::io::Error::new(
-                            std::io::ErrorKind::NotFound,
-                            format!("File not found: {}", path.display()),
-                        )
-                        .into());
-                    }
-                    return Err(color_eyre::eyre::eyre!("Unsupported file type"));
-                }
+        }
+        if ws.has_file_list() {
+            ws.list_visible = true;
+            next = None;
+        }
+        self.loading_state = LoadingState::Idle;
+        self.status_message = None;
+        self.busy = false;
+        self.drain_keys_on_next_loop = true;
+        self.error_modal.show(message.to_string());
+        if let Some(event) = next.and_then(|index| self.switch_workspace_tab(index)) {
+            let _ = self.events.send(event);
+        }
+        true
+    }
+
+    /// Load profile for opening `paths` with `options`: the one chosen explicitly, else one
+    /// from the input size when automatic profiles are on.
+    fn choose_load_profile(
+        &self,
+        paths: &[PathBuf],
+        options: &OpenOptions,
+    ) -> Option<load_profile::LoadProfileInfo> {
+        let input_bytes = load_profile::input_size(paths);
+        let (profile, automatic) = match options.load_profile {
+            Some(profile) => (profile, false),
+            None if options.auto_load_profile => {
+                let perf = &self.app_config.performance;
+                (
+                    load_profile::LoadProfile::for_size(
+                        input_bytes,
+                        perf.small_file_mb,
+                        perf.large_file_mb,
+                    ),
+                    true,
+                )
             }
+            None => return None,
         };
-        Ok(lf.lf)
+        Some(load_profile::LoadProfileInfo {
+            profile,
+            automatic,
+            input_bytes,
+        })
     }
 
-    /// Set the appropriate help overlay visible (main, template, or analysis). No-op if already visible.
-    fn open_help_overlay(&mut self) {
-        let already = self.show_help
-            || (self.template_modal.active && self.template_modal.show_help)
-            || (self.analysis_modal.active && self.analysis_modal.show_help);
-        if already {
-            return;
+    /// Reopen the current dataset with the next load profile (Info panel `p`).
+    fn cycle_load_profile(&mut self) -> Option<AppEvent> {
+        let profile = self.load_profile.as_ref()?.profile.next();
+        let (paths, options) = self.last_open.as_ref()?;
+        let mut options = options.clone();
+        options.load_profile = Some(profile);
+        Some(AppEvent::Open(paths.clone(), options))
+    }
+
+    /// For a Small-profile view not yet in memory, read the source into memory in the
+    /// background; the first buffer is loaded once that finishes. Returns false when the
+    /// view should be buffered directly.
+    fn spawn_in_memory_load(&mut self) -> bool {
+        if !self
+            .load_profile
+            .as_ref()
+            .is_some_and(|info| info.profile.eager())
+        {
+            return false;
         }
-        if self.analysis_modal.active {
-            self.analysis_modal.show_help = true;
-        } else if self.template_modal.active {
-            self.template_modal.show_help = true;
-        } else {
-            self.show_help = true;
+        let Some(state) = self.data_table_state.as_ref() else {
+            return false;
+        };
+        if state.is_in_memory() {
+            return false;
         }
+        let lf = state.source_lf_clone();
+        let streaming = state.polars_streaming_enabled();
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.spawn_bg("Reading into memory...", move |gen, tx| {
+            let df = crate::statistics::collect_lazy(lf, streaming)
+                .map(Box::new)
+                .map_err(|e| crate::error_display::user_message_from_polars(&e));
+            let _ = tx.send(AppEvent::BackgroundInMemoryReady {
+                generation: gen,
+                df,
+            });
+        });
+        true
     }
 
-    fn key(&mut self, event: &KeyEvent) -> Option<AppEvent> {
-        self.debug.on_key(event);
-
-        // F1 opens help first so no other branch (e.g. Editing) can consume it.
-        if event.code == KeyCode::F(1) {
-            self.open_help_overlay();
-            return None;
+    /// Collect the first buffer of a freshly loaded view, or finish loading when there is
+    /// nothing to collect.
+    fn load_first_buffer(&mut self) {
+        if !self.spawn_async_collect("Loading buffer...") {
+            self.loading_state = LoadingState::Idle;
+            self.busy = false;
+            self.drain_keys_on_next_loop = true;
         }
+    }
 
-        // Handle modals first - they have highest priority
-        // Confirmation modal (for overwrite)
-        if self.confirmation_modal.active {
-            match event.code {
-                KeyCode::Left | KeyCode::Char('h') => {
-                    self.confirmation_modal.focus_yes = true;
-                }
-                KeyCode::Right | KeyCode::Char('l') => {
-                    self.confirmation_modal.focus_yes = false;
-                }
-                KeyCode::Tab => {
-                    // Toggle between Yes and No
-                    self.confirmation_modal.focus_yes = !self.confirmation_modal.focus_yes;
-                }
-                KeyCode::Enter => {
-                    if self.confirmation_modal.focus_yes {
-                        // User confirmed overwrite: chart export first, then dataframe export
-                        if let Some((path, format, title, width, height)) =
-                            self.pending_chart_export.take()
-                        {
-                            self.confirmation_modal.hide();
-                            return Some(AppEvent::ChartExport(path, format, title, width, height));
-                        }
-                        if let Some((path, format, options)) = self.pending_export.take() {
-                            self.confirmation_modal.hide();
-                            return Some(AppEvent::Export(path, format, options));
+    /// Ensures file path has an extension when user did not provide one; only adds
+    /// compression suffix (e.g. .gz) when compression is selected. If the user
+    /// provided a path with an extension (e.g. foo.feather), that extension is kept.
+    /// Spawn an async buffer collect if needed. Returns true if a background task was spawned.
+    /// Increments task_generation to invalidate any in-flight collect from a prior call.
+    ///
+    /// When the LazyFrame's row count is unknown (e.g. fresh load, or just after a
+    /// filter/sort/pivot/melt that invalidates the cache), the exact `len()` is computed
+    /// in the background and applied later via `BackgroundLenReady` — it never gates the
+    /// buffer paint. `prepare_async_collect` plans a top-of-data window when the count is
+    /// still unknown, so the first screen renders immediately. For large/partitioned/remote
+    /// datasets the count can take a long time; it runs silently and concurrently.
+    pub fn spawn_async_collect(&mut self, status: &str) -> bool {
+        let Some(state) = self.data_table_state.as_mut() else {
+            return false;
+        };
+
+        // Kick off the exact row count off-thread when it isn't known, unless one is
+        // already running for this data version. This task is independent of
+        // `task_generation` (a scroll must not restart it) and does not set `busy`.
+        if !state.is_num_rows_valid() {
+            let len_gen = state.len_generation();
+            if self.len_count_inflight != Some(len_gen) {
+                // Prefer the cheap footer-sum count for a pristine local Parquet hive
+                // directory; otherwise fall back to a `len()` data scan.
+                let count_dir = state.parquet_count_dir();
+                let lf = state.lf_clone();
+                let streaming = state.polars_streaming_enabled();
+                self.len_count_inflight = Some(len_gen);
+                // A fresh attempt for this generation clears any prior failure marker.
+                if self.len_count_failed == Some(len_gen) {
+                    self.len_count_failed = None;
+                }
+                let tx = self.events.clone();
+                self.runtime.spawn_blocking(move || {
+                    let counted = match count_dir {
+                        Some(dir) => {
+                            crate::widgets::datatable::DataTableState::count_rows_from_parquet_dir(
+                                &dir,
+                            )
+                            .map_err(|_| ())
                         }
-                        #[cfg(any(feature = "http", feature = "cloud"))]
-                        if let Some(pending) = self.pending_download.take() {
-                            self.confirmation_modal.hide();
-                            if let LoadingState::Loading {
-                                file_path,
-                                file_size,
-                                ..
-                            } = &self.loading_state
-                            {
-                                self.loading_state = LoadingState::Loading {
-                                    file_path: file_path.clone(),
-                                    file_size: *file_size,
-                                    current_phase: "Downloading".to_string(),
-                                    progress_percent: 20,
-                                };
+                        None => {
+                            match crate::statistics::collect_lazy(lf.select([len()]), streaming) {
+                                Ok(df) => Ok(match df.get(0) {
+                                    Some(col) => match col.first() {
+                                        Some(AnyValue::UInt32(n)) => *n as usize,
+                                        _ => 0,
+                                    },
+                                    None => 0,
+                                }),
+                                Err(_) => Err(()),
                             }
-                            return Some(match pending {
-                                #[cfg(feature = "http")]
-                                PendingDownload::Http { url, options, .. } => {
-                                    AppEvent::DoDownloadHttp(url, options)
-                                }
-                                #[cfg(feature = "cloud")]
-                                PendingDownload::S3 { url, options, .. } => {
-                                    AppEvent::DoDownloadS3ToTemp(url, options)
-                                }
-                                #[cfg(feature = "cloud")]
-                                PendingDownload::Gcs { url, options, .. } => {
-                                    AppEvent::DoDownloadGcsToTemp(url, options)
-                                }
-                            });
                         }
-                    } else {
-                        // User cancelled: if chart export overwrite, reopen chart export modal with path pre-filled
-                        if let Some((path, format, _, _, _)) = self.pending_chart_export.take() {
-                            self.chart_export_modal.reopen_with_path(&path, format);
+                    };
+                    match counted {
+                        Ok(num_rows) => {
+                            let _ = tx.send(AppEvent::BackgroundLenReady {
+                                len_generation: len_gen,
+                                num_rows,
+                            });
                         }
-                        self.pending_export = None;
-                        #[cfg(any(feature = "http", feature = "cloud"))]
-                        if self.pending_download.take().is_some() {
-                            self.confirmation_modal.hide();
-                            return Some(AppEvent::Exit);
+                        // Count failed: leave the total provisional and allow a retry on a
+                        // later interaction. The buffer paint below is unaffected.
+                        Err(()) => {
+                            let _ = tx.send(AppEvent::BackgroundLenFailed {
+                                len_generation: len_gen,
+                            });
                         }
-                        self.confirmation_modal.hide();
-                    }
-                }
-                KeyCode::Esc => {
-                    // Cancel: if chart export overwrite, reopen chart export modal with path pre-filled
-                    if let Some((path, format, _, _, _)) = self.pending_chart_export.take() {
-                        self.chart_export_modal.reopen_with_path(&path, format);
-                    }
-                    self.pending_export = None;
-                    #[cfg(any(feature = "http", feature = "cloud"))]
-                    if self.pending_download.take().is_some() {
-                        self.confirmation_modal.hide();
-                        return Some(AppEvent::Exit);
                     }
-                    self.confirmation_modal.hide();
-                }
-                _ => {}
+                });
             }
-            return None;
         }
-        // Success modal
-        if self.success_modal.active {
-            match event.code {
-                KeyCode::Esc | KeyCode::Enter => {
-                    self.success_modal.hide();
+
+        // Plan and spawn the buffer collect. With the count unknown this is a top-of-data
+        // window (`slice(0, N)`) that touches only the first file(s) of a partitioned set.
+        let Some(state) = self.data_table_state.as_mut() else {
+            return false;
+        };
+        if state.visible_rows == 0 {
+            state.visible_rows = self.viewport_rows_hint;
+        }
+        let Some(request) = state.prepare_async_collect(None) else {
+            return false;
+        };
+        self.task_generation = self.task_generation.wrapping_add(1);
+        let collect_slot = self.pending_collect_result.clone();
+        self.spawn_bg(status, move |gen, tx| {
+            match crate::statistics::collect_lazy(request.lf, request.polars_streaming) {
+                Ok(df) => {
+                    let mut slot = collect_slot.lock().unwrap_or_else(|e| e.into_inner());
+                    // Only write if no newer result is already stored.
+                    let dominated = slot.as_ref().is_some_and(|(g, _)| *g > gen);
+                    if !dominated {
+                        *slot = Some((
+                            gen,
+                            crate::widgets::datatable::CollectResult {
+                                df,
+                                buffer_start: request.buffer_start,
+                                buffer_end: request.buffer_end,
+                                num_rows: request.num_rows,
+                                count_known: request.count_known,
+                            },
+                        ));
+                    }
+                    drop(slot);
+                    let _ = tx.send(AppEvent::BackgroundCollectReady { generation: gen });
+                }
+                Err(e) => {
+                    let _ = tx.send(AppEvent::BackgroundError {
+                        generation: gen,
+                        message: crate::error_display::user_message_from_polars(&e),
+                    });
                 }
-                _ => {}
             }
-            return None;
+        });
+        true
+    }
+
+    /// Spawn a background task. Captures the current generation and event sender
+    /// for the closure, sets `busy` and `status_message`. The closure is responsible
+    /// for sending a follow-up event (typically `Background*Ready` or `BackgroundError`)
+    /// using the captured generation so stale results can be filtered out.
+    ///
+    /// Does not bump `task_generation`. Callers that need to invalidate prior in-flight
+    /// work should bump it explicitly before calling.
+    fn spawn_bg<F>(&mut self, status: &str, work: F)
+    where
+        F: FnOnce(u64, Sender<AppEvent>) + Send + 'static,
+    {
+        use std::sync::atomic::Ordering;
+        let gen = self.task_generation;
+        let tx = self.events.clone();
+        self.busy = true;
+        self.status_message = Some(status.to_string());
+        let workers = std::sync::Arc::clone(&self.bg_workers);
+        workers.fetch_add(1, Ordering::SeqCst);
+        self.runtime.spawn_blocking(move || {
+            work(gen, tx);
+            workers.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+
+    /// Run a scroll on `data_table_state` and resolve the busy/spawn cycle.
+    /// `scroll` returns true when its movement leaves the buffered window (caller must collect).
+    /// We clear `busy` ourselves when no collect is needed or the spawn no-ops, otherwise
+    /// the busy flag set by the key handler would gate further input forever.
+    fn handle_scroll<F>(&mut self, scroll: F) -> Option<AppEvent>
+    where
+        F: FnOnce(&mut crate::widgets::datatable::DataTableState) -> bool,
+    {
+        let needs = self.data_table_state.as_mut().is_some_and(scroll);
+        if !needs || !self.spawn_async_collect("Loading buffer...") {
+            self.busy = false;
+            self.status_message = None;
         }
-        // Error modal
-        if self.error_modal.active {
-            match event.code {
-                KeyCode::Esc | KeyCode::Enter => {
-                    self.error_modal.hide();
-                }
-                _ => {}
+        self.drain_keys_on_next_loop = true;
+        None
+    }
+
+    /// Mouse input. A left click presses the modal button under it or, in the main table,
+    /// moves the cursor to the clicked cell or cycles the sort on the clicked header. The wheel
+    /// and dragging with the left button scroll the table.
+    fn mouse(&mut self, event: &MouseEvent) -> Option<AppEvent> {
+        let (column, row) = (event.column, event.row);
+        if event.kind == MouseEventKind::Down(MouseButton::Left) {
+            if let Some(next) = self.click_button(column, row) {
+                return next;
             }
+        }
+        if !self.main_table_has_focus() {
+            self.mouse_drag_row = None;
             return None;
         }
-
-        // Main table: left/right scroll columns (before help/mode blocks so column scroll always works in Normal).
-        // No is_press()/is_release() check: some terminals do not report key kind correctly.
-        // Exclude template/analysis modals so they can handle Left/Right themselves.
-        let in_main_table = !(self.input_mode != InputMode::Normal
-            || self.show_help
-            || self.template_modal.active
-            || self.analysis_modal.active);
-        if in_main_table {
-            let did_scroll = match event.code {
-                KeyCode::Right | KeyCode::Char('l') => {
-                    if let Some(ref mut state) = self.data_table_state {
-                        state.scroll_right();
-                        if self.debug.enabled {
-                            self.debug.last_action = "scroll_right".to_string();
-                        }
-                        true
-                    } else {
-                        false
-                    }
-                }
-                KeyCode::Left | KeyCode::Char('h') => {
-                    if let Some(ref mut state) = self.data_table_state {
-                        state.scroll_left();
-                        if self.debug.enabled {
-                            self.debug.last_action = "scroll_left".to_string();
-                        }
-                        true
-                    } else {
-                        false
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.mouse_drag_row = Some(row);
+                match self.data_table_state.as_ref()?.hit(column, row)? {
+                    TableHit::Header(span) => self.sort_by_header(&span.name),
+                    TableHit::Cell(index, span) => {
+                        self.data_table_state.as_mut()?.select_cell(index, &span);
+                        None
                     }
                 }
-                _ => false,
-            };
-            if did_scroll {
-                return None;
             }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                // The rows follow the pointer: dragging up shows later rows.
+                let last = self.mouse_drag_row.replace(row)?;
+                self.scroll_rows(last as i64 - row as i64)
+            }
+            MouseEventKind::Up(_) => {
+                self.mouse_drag_row = None;
+                None
+            }
+            MouseEventKind::ScrollDown => self.scroll_rows(mouse::WHEEL_ROWS),
+            MouseEventKind::ScrollUp => self.scroll_rows(-mouse::WHEEL_ROWS),
+            MouseEventKind::ScrollRight => {
+                self.data_table_state.as_mut()?.scroll_right();
+                None
+            }
+            MouseEventKind::ScrollLeft => {
+                self.data_table_state.as_mut()?.scroll_left();
+                None
+            }
+            _ => None,
         }
+    }
 
-        if self.show_help
-            || (self.template_modal.active && self.template_modal.show_help)
-            || (self.analysis_modal.active && self.analysis_modal.show_help)
-        {
-            match event.code {
-                KeyCode::Esc => {
-                    if self.analysis_modal.active && self.analysis_modal.show_help {
-                        self.analysis_modal.show_help = false;
-                    } else if self.template_modal.active && self.template_modal.show_help {
-                        self.template_modal.show_help = false;
-                    } else {
-                        self.show_help = false;
-                    }
-                    self.help_scroll = 0;
-                }
-                KeyCode::Char('?') => {
-                    if self.analysis_modal.active && self.analysis_modal.show_help {
-                        self.analysis_modal.show_help = false;
-                    } else if self.template_modal.active && self.template_modal.show_help {
-                        self.template_modal.show_help = false;
-                    } else {
-                        self.show_help = false;
+    /// Keys of the chart export modal, open over the chart view or the correlation matrix.
+    fn chart_export_modal_key(&mut self, event: &KeyEvent) -> Option<AppEvent> {
+        match event.code {
+            KeyCode::Esc if event.is_press() => {
+                self.chart_export_modal.close();
+            }
+            KeyCode::Tab if event.is_press() => {
+                self.chart_export_modal.next_focus();
+            }
+            KeyCode::BackTab if event.is_press() => {
+                self.chart_export_modal.prev_focus();
+            }
+            KeyCode::Up | KeyCode::Char('k')
+                if event.is_press()
+                    && self.chart_export_modal.focus == ChartExportFocus::FormatSelector =>
+            {
+                let idx = ChartExportFormat::ALL
+                    .iter()
+                    .position(|&f| f == self.chart_export_modal.selected_format)
+                    .unwrap_or(0);
+                let prev = if idx == 0 {
+                    ChartExportFormat::ALL.len() - 1
+                } else {
+                    idx - 1
+                };
+                self.chart_export_modal.selected_format = ChartExportFormat::ALL[prev];
+            }
+            KeyCode::Down | KeyCode::Char('j')
+                if event.is_press()
+                    && self.chart_export_modal.focus == ChartExportFocus::FormatSelector =>
+            {
+                let idx = ChartExportFormat::ALL
+                    .iter()
+                    .position(|&f| f == self.chart_export_modal.selected_format)
+                    .unwrap_or(0);
+                let next = (idx + 1) % ChartExportFormat::ALL.len();
+                self.chart_export_modal.selected_format = ChartExportFormat::ALL[next];
+            }
+            KeyCode::Left | KeyCode::Char('h')
+                if event.is_press()
+                    && self.chart_export_modal.focus == ChartExportFocus::FormatSelector =>
+            {
+                let idx = ChartExportFormat::ALL
+                    .iter()
+                    .position(|&f| f == self.chart_export_modal.selected_format)
+                    .unwrap_or(0);
+                let prev = if idx == 0 {
+                    ChartExportFormat::ALL.len() - 1
+                } else {
+                    idx - 1
+                };
+                self.chart_export_modal.selected_format = ChartExportFormat::ALL[prev];
+            }
+            KeyCode::Right | KeyCode::Char('l')
+                if event.is_press()
+                    && self.chart_export_modal.focus == ChartExportFocus::FormatSelector =>
+            {
+                let idx = ChartExportFormat::ALL
+                    .iter()
+                    .position(|&f| f == self.chart_export_modal.selected_format)
+                    .unwrap_or(0);
+                let next = (idx + 1) % ChartExportFormat::ALL.len();
+                self.chart_export_modal.selected_format = ChartExportFormat::ALL[next];
+            }
+            KeyCode::Enter if event.is_press() => match self.chart_export_modal.focus {
+                ChartExportFocus::PathInput | ChartExportFocus::ExportButton => {
+                    let path_str = self.chart_export_modal.path_input.value.trim();
+                    if !path_str.is_empty() {
+                        let title = self.chart_export_modal.title_input.value.trim().to_string();
+                        let (width, height) = self.chart_export_modal.export_dimensions();
+                        let mut path = PathBuf::from(path_str);
+                        let format = self.chart_export_modal.selected_format;
+                        // Only add default extension when user did not provide one
+                        if path.extension().is_none() {
+                            path.set_extension(format.extension());
+                        }
+                        let path_display = path.display().to_string();
+                        if path.exists() {
+                            self.pending_chart_export = Some((path, format, title, width, height));
+                            self.chart_export_modal.close();
+                            self.confirmation_modal.show(format!(
+                                "File already exists:\n{}\n\nDo you wish to overwrite this file?",
+                                path_display
+                            ));
+                        } else {
+                            self.chart_export_modal.close();
+                            return Some(AppEvent::ChartExport(path, format, title, width, height));
+                        }
                     }
-                    self.help_scroll = 0;
-                }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    self.help_scroll = self.help_scroll.saturating_add(1);
-                }
-                KeyCode::Up | KeyCode::Char('k') => {
-                    self.help_scroll = self.help_scroll.saturating_sub(1);
-                }
-                KeyCode::PageDown => {
-                    self.help_scroll = self.help_scroll.saturating_add(10);
                 }
-                KeyCode::PageUp => {
-                    self.help_scroll = self.help_scroll.saturating_sub(10);
-                }
-                KeyCode::Home => {
-                    self.help_scroll = 0;
+                ChartExportFocus::ReportCardToggle => {
+                    self.chart_export_modal.report_card = !self.chart_export_modal.report_card;
                 }
-                KeyCode::End => {
-                    // Will be set based on content height in render
+                ChartExportFocus::CancelButton => {
+                    self.chart_export_modal.close();
                 }
                 _ => {}
+            },
+            KeyCode::Char(' ')
+                if event.is_press()
+                    && self.chart_export_modal.focus == ChartExportFocus::ReportCardToggle =>
+            {
+                self.chart_export_modal.report_card = !self.chart_export_modal.report_card;
+            }
+            _ => {
+                if event.is_press() {
+                    if self.chart_export_modal.focus == ChartExportFocus::TitleInput {
+                        let _ = self.chart_export_modal.title_input.handle_key(event, None);
+                    } else if self.chart_export_modal.focus == ChartExportFocus::PathInput {
+                        let _ = self.chart_export_modal.path_input.handle_key(event, None);
+                    } else if self.chart_export_modal.focus == ChartExportFocus::WidthInput {
+                        let allow = match event.code {
+                            KeyCode::Char(c) if c.is_ascii_digit() => true,
+                            KeyCode::Backspace
+                            | KeyCode::Delete
+                            | KeyCode::Left
+                            | KeyCode::Right
+                            | KeyCode::Home
+                            | KeyCode::End => true,
+                            _ => false,
+                        };
+                        if allow {
+                            let _ = self.chart_export_modal.width_input.handle_key(event, None);
+                        }
+                    } else if self.chart_export_modal.focus == ChartExportFocus::HeightInput {
+                        let allow = match event.code {
+                            KeyCode::Char(c) if c.is_ascii_digit() => true,
+                            KeyCode::Backspace
+                            | KeyCode::Delete
+                            | KeyCode::Left
+                            | KeyCode::Right
+                            | KeyCode::Home
+                            | KeyCode::End => true,
+                            _ => false,
+                        };
+                        if allow {
+                            let _ = self.chart_export_modal.height_input.handle_key(event, None);
+                        }
+                    }
+                }
             }
+        }
+        None
+    }
+
+    /// Press the modal button at (`column`, `row`) by focusing it and sending Enter. None when
+    /// there is no button there; modals are checked in the order `key` handles them.
+    fn click_button(&mut self, column: u16, row: u16) -> Option<Option<AppEvent>> {
+        if self.confirmation_modal.active {
+            self.confirmation_modal.focus_yes = *self.confirmation_modal.buttons.at(column, row)?;
+        } else if self.success_modal.active {
+            self.success_modal.buttons.at(column, row)?;
+        } else if self.error_modal.active {
+            self.error_modal.buttons.at(column, row)?;
+        } else if self.input_mode == InputMode::SortFilter {
+            self.sort_filter_modal.focus = *self.sort_filter_modal.buttons.at(column, row)?;
+        } else if self.input_mode == InputMode::PivotMelt {
+            self.pivot_melt_modal.focus = *self.pivot_melt_modal.buttons.at(column, row)?;
+        } else if self.input_mode == InputMode::Export {
+            self.export_modal.focus = *self.export_modal.buttons.at(column, row)?;
+        } else if self.chart_export_modal.active {
+            self.chart_export_modal.focus = *self.chart_export_modal.buttons.at(column, row)?;
+        } else {
             return None;
         }
+        Some(self.key(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)))
+    }
 
-        if event.code == KeyCode::Char('?') {
-            let ctrl_help = event.modifiers.contains(KeyModifiers::CONTROL);
-            let in_text_input = match self.input_mode {
-                InputMode::Editing => true,
-                InputMode::Export => matches!(
-                    self.export_modal.focus,
-                    ExportFocus::PathInput | ExportFocus::CsvDelimiter
-                ),
-                InputMode::SortFilter => {
-                    let on_body = self.sort_filter_modal.focus == SortFilterFocus::Body;
-                    let filter_tab = self.sort_filter_modal.active_tab == SortFilterTab::Filter;
-                    on_body
-                        && filter_tab
-                        && self.sort_filter_modal.filter.focus == FilterFocus::Value
+    /// Open the table picker on the sheets or tables of the loaded file, to switch to another.
+    fn open_table_switcher(&mut self) {
+        let Some(tables) = self.source_tables.as_ref() else {
+            self.error_modal.show(
+                "Only Excel workbooks and SQLite databases have sheets or tables to switch between"
+                    .to_string(),
+            );
+            return;
+        };
+        let Some((_, options)) = self.last_open.as_ref() else {
+            return;
+        };
+        match table_picker::TablePicker::open(
+            tables.path.clone(),
+            tables.source,
+            options.clone(),
+            Some(tables.names[tables.current].clone()),
+        ) {
+            Ok(picker) => self.table_picker = Some(picker),
+            Err(e) => self.error_modal.show(e.to_string()),
+        }
+    }
+
+    /// Keys of the sheet or table picker. Enter loads the highlighted one; Esc closes the picker
+    /// without loading anything.
+    fn table_picker_key(&mut self, event: &KeyEvent) -> Option<AppEvent> {
+        let picker = self.table_picker.as_mut()?;
+        let page = picker.visible.max(1) as isize;
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.table_picker = None,
+            KeyCode::Down | KeyCode::Char('j') => picker.move_by(1),
+            KeyCode::Up | KeyCode::Char('k') => picker.move_by(-1),
+            KeyCode::PageDown => picker.move_by(page),
+            KeyCode::PageUp => picker.move_by(-page),
+            KeyCode::Home => picker.move_by(isize::MIN),
+            KeyCode::End => picker.move_by(isize::MAX),
+            KeyCode::Enter => {
+                let (paths, options) = picker.open_selected()?;
+                self.table_picker = None;
+                return Some(AppEvent::Open(paths, options));
+            }
+            KeyCode::Char('p') if picker.source == table_picker::TableSource::Excel => {
+                let (mut paths, options) = picker.open_selected()?;
+                match excel_range::SheetPreview::open(paths.remove(0), options) {
+                    Ok(preview) => {
+                        self.table_picker = None;
+                        self.sheet_preview = Some(preview);
+                    }
+                    Err(e) => self.error_modal.show(e.to_string()),
                 }
-                InputMode::PivotMelt => matches!(
-                    self.pivot_melt_modal.focus,
-                    PivotMeltFocus::PivotFilter
-                        | PivotMeltFocus::MeltFilter
-                        | PivotMeltFocus::MeltPattern
-                        | PivotMeltFocus::MeltVarName
-                        | PivotMeltFocus::MeltValName
-                ),
-                InputMode::Info | InputMode::Chart => false,
-                InputMode::Normal => {
-                    if self.template_modal.active
-                        && self.template_modal.mode != TemplateModalMode::List
-                    {
-                        matches!(
-                            self.template_modal.create_focus,
-                            CreateFocus::Name
-                                | CreateFocus::Description
-                                | CreateFocus::ExactPath
-                                | CreateFocus::RelativePath
-                                | CreateFocus::PathPattern
-                                | CreateFocus::FilenamePattern
-                        )
+            }
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(AppEvent::Exit);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Keys of the sheet preview: move the header row, Enter loads the sheet with it, Esc
+    /// closes the preview without loading anything.
+    fn sheet_preview_key(&mut self, event: &KeyEvent) -> Option<AppEvent> {
+        let preview = self.sheet_preview.as_mut()?;
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.sheet_preview = None,
+            KeyCode::Down | KeyCode::Char('j') => preview.move_by(1),
+            KeyCode::Up | KeyCode::Char('k') => preview.move_by(-1),
+            KeyCode::Home => preview.move_by(isize::MIN),
+            KeyCode::End => preview.move_by(isize::MAX),
+            KeyCode::Enter => {
+                let (paths, options) = preview.open_selected();
+                self.sheet_preview = None;
+                return Some(AppEvent::Open(paths, options));
+            }
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(AppEvent::Exit);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Open the dialect modal on the loaded CSV, TSV or PSV file.
+    fn open_csv_dialect(&mut self) {
+        let target = self
+            .last_open
+            .as_ref()
+            .and_then(|(paths, options)| Some((csv_dialect::target(paths, options)?, options)));
+        let Some((path, options)) = target else {
+            self.error_modal.show(
+                "Only a single local CSV, TSV or PSV file has a dialect to adjust".to_string(),
+            );
+            return;
+        };
+        match csv_dialect::CsvDialectModal::open(path, options.clone()) {
+            Ok(modal) => self.csv_dialect = Some(modal),
+            Err(e) => self.error_modal.show(e.to_string()),
+        }
+    }
+
+    /// Offer the dialect modal for the CSV being loaded, with a sniffed dialect, because of
+    /// `reason`. False when no CSV is loading or the file can't be read.
+    fn offer_csv_dialect(&mut self, reason: &str) -> bool {
+        let Some((path, options)) = self.loading_csv.take() else {
+            return false;
+        };
+        match csv_dialect::CsvDialectModal::sniffed(path, options, reason.to_string()) {
+            Ok(modal) => {
+                self.csv_dialect = Some(modal);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// A load error while a CSV was loading: show the dialect modal in place of the error.
+    /// Returns false (nothing done) for other errors.
+    fn recover_csv_load_error(&mut self, message: &str) -> bool {
+        if !self.offer_csv_dialect(message) {
+            return false;
+        }
+        self.loading_state = LoadingState::Idle;
+        self.status_message = None;
+        self.busy = false;
+        self.drain_keys_on_next_loop = true;
+        true
+    }
+
+    /// Keys of the dialect modal: Up/Down (Tab) pick a setting, Left/Right step it, typing sets
+    /// it, Enter reloads the file with the dialect and Esc closes the modal.
+    fn csv_dialect_key(&mut self, event: &KeyEvent) -> Option<AppEvent> {
+        let modal = self.csv_dialect.as_mut()?;
+        match event.code {
+            KeyCode::Esc => self.csv_dialect = None,
+            KeyCode::Down | KeyCode::Tab => modal.move_field(1),
+            KeyCode::Up | KeyCode::BackTab => modal.move_field(-1),
+            KeyCode::Right => modal.cycle(1),
+            KeyCode::Left => modal.cycle(-1),
+            KeyCode::Backspace | KeyCode::Delete => modal.backspace(),
+            KeyCode::Enter => {
+                let (paths, options) = modal.open_selected();
+                self.csv_dialect = None;
+                return Some(AppEvent::Open(paths, options));
+            }
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(AppEvent::Exit);
+            }
+            KeyCode::Char(c) => modal.type_char(c),
+            _ => {}
+        }
+        None
+    }
+
+    /// Open the Load report panel on the rows the current file's load left out.
+    fn open_load_report(&mut self) {
+        let report = self
+            .data_table_state
+            .as_ref()
+            .and_then(|state| state.load_report.clone());
+        match report {
+            Some(report) => self.load_report = Some(load_report::LoadReportPanel::new(report)),
+            None => self.error_modal.show(
+                "No load report: open a CSV or JSON Lines file with --skip-bad-rows to leave out rows that don't parse"
+                    .to_string(),
+            ),
+        }
+    }
+
+    /// Keys of the Load report panel: move through the rejected rows, Esc closes it.
+    fn load_report_key(&mut self, event: &KeyEvent) -> Option<AppEvent> {
+        let panel = self.load_report.as_mut()?;
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('B') => self.load_report = None,
+            KeyCode::Down | KeyCode::Char('j') => panel.move_by(1),
+            KeyCode::Up | KeyCode::Char('k') => panel.move_by(-1),
+            KeyCode::PageDown => panel.move_by(10),
+            KeyCode::PageUp => panel.move_by(-10),
+            KeyCode::Home => panel.move_by(isize::MIN),
+            KeyCode::End => panel.move_by(isize::MAX),
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(AppEvent::Exit);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Open the schema step on the loaded CSV, TSV or PSV file.
+    fn open_schema_modal(&mut self) {
+        let target = self
+            .last_open
+            .as_ref()
+            .and_then(|(paths, options)| Some((csv_dialect::target(paths, options)?, options)));
+        let Some((path, options)) = target else {
+            self.error_modal.show(
+                "Only a single local CSV, TSV or PSV file has column types to adjust".to_string(),
+            );
+            return;
+        };
+        match schema_override::SchemaModal::open(path, options.clone(), false) {
+            Ok(modal) => self.schema_modal = Some(modal),
+            Err(e) => self.error_modal.show(e.to_string()),
+        }
+    }
+
+    /// Keys of the schema step: Up/Down pick a column, Left/Right step its type, `r` resets it
+    /// to the inferred type and Enter loads the file with the types. Esc closes the step, or
+    /// loads the file as inferred when the step came before reading it.
+    fn schema_modal_key(&mut self, event: &KeyEvent) -> Option<AppEvent> {
+        let modal = self.schema_modal.as_mut()?;
+        match event.code {
+            KeyCode::Esc if modal.before_load => {
+                let (paths, options) = modal.open_inferred();
+                self.schema_modal = None;
+                return Some(AppEvent::Open(paths, options));
+            }
+            KeyCode::Esc | KeyCode::Char('q') => self.schema_modal = None,
+            KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => modal.move_by(1),
+            KeyCode::Up | KeyCode::Char('k') | KeyCode::BackTab => modal.move_by(-1),
+            KeyCode::Right | KeyCode::Char('l') => modal.cycle(1),
+            KeyCode::Left | KeyCode::Char('h') => modal.cycle(-1),
+            KeyCode::Char('r') => modal.reset(),
+            KeyCode::Home => modal.move_by(isize::MIN),
+            KeyCode::End => modal.move_by(isize::MAX),
+            KeyCode::Enter => {
+                let (paths, options) = modal.open_selected();
+                self.schema_modal = None;
+                return Some(AppEvent::Open(paths, options));
+            }
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(AppEvent::Exit);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Candidates listed under the SQL-Like query input, with the one filled in.
+    pub fn query_completion(&self) -> Option<(&query::Completion, Option<usize>)> {
+        self.query_completion
+            .as_ref()
+            .map(|(completion, selected)| (completion, *selected))
+    }
+
+    /// Tab in the SQL-Like input: fill in the only candidate for the word at the cursor, or the
+    /// start they share and list them; with the list shown, Tab and Shift+Tab cycle through it.
+    /// False when there's nothing to complete, so Tab moves focus to the tab bar instead.
+    fn complete_query(&mut self, backward: bool) -> bool {
+        let cursor = self.query_input.cursor();
+        let (start, replacement) = match &mut self.query_completion {
+            Some((completion, selected)) if completion.candidates.len() > 1 => {
+                let count = completion.candidates.len();
+                let next = match (*selected, backward) {
+                    (None, false) => 0,
+                    (None, true) => count - 1,
+                    (Some(i), false) => (i + 1) % count,
+                    (Some(i), true) => (i + count - 1) % count,
+                };
+                *selected = Some(next);
+                (completion.start, completion.candidates[next].clone())
+            }
+            _ => {
+                if backward {
+                    return false;
+                }
+                let columns = self
+                    .data_table_state
+                    .as_ref()
+                    .map(|state| state.query_columns())
+                    .unwrap_or_default();
+                let Some(completion) = query::complete(&self.query_input.value, cursor, &columns)
+                else {
+                    return false;
+                };
+                let start = completion.start;
+                let replacement = if completion.candidates.len() == 1 {
+                    self.query_completion = None;
+                    completion.candidates[0].clone()
+                } else {
+                    let typed: String = self
+                        .query_input
+                        .value
+                        .chars()
+                        .skip(start)
+                        .take(cursor - start)
+                        .collect();
+                    let prefix = completion.common_prefix();
+                    // Keep what was typed when the shared start differs only in case
+                    let replacement = if prefix.chars().count() > typed.chars().count() {
+                        prefix.to_string()
                     } else {
-                        false
+                        typed
+                    };
+                    self.query_completion = Some((completion, None));
+                    replacement
+                };
+                (start, replacement)
+            }
+        };
+        let value: String = self
+            .query_input
+            .value
+            .chars()
+            .take(start)
+            .chain(replacement.chars())
+            .chain(self.query_input.value.chars().skip(cursor))
+            .collect();
+        self.query_input.set_value(value);
+        self.query_input
+            .set_cursor(start + replacement.chars().count());
+        true
+    }
+
+    /// Names of the columns matching what's typed after `|`, best first: the column it names
+    /// exactly, by number or letters, then fuzzy matches on names.
+    pub fn go_to_column_matches(&self) -> Vec<String> {
+        let Some(state) = self.data_table_state.as_ref() else {
+            return Vec::new();
+        };
+        let columns = state.get_column_order();
+        let query = self.query_input.value.as_str();
+        let mut matches = column_ruler::fuzzy_matches(columns, query);
+        if let Some(index) = column_ruler::find_column(columns, query) {
+            matches.retain(|&i| i != index);
+            matches.insert(0, index);
+        }
+        matches.into_iter().map(|i| columns[i].clone()).collect()
+    }
+
+    /// Keys of the command palette: typing filters the entries, Up/Down pick one, Enter scrolls to
+    /// the column or runs the action, Esc closes.
+    fn command_palette_key(&mut self, event: &KeyEvent) -> Option<AppEvent> {
+        if !event.is_press() {
+            return None;
+        }
+        let palette = self.command_palette.as_mut()?;
+        match event.code {
+            KeyCode::Esc => self.command_palette = None,
+            KeyCode::Down => palette.move_by(1),
+            KeyCode::Up => palette.move_by(-1),
+            KeyCode::PageDown => palette.move_by(10),
+            KeyCode::PageUp => palette.move_by(-10),
+            KeyCode::Char('n') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                palette.move_by(1)
+            }
+            KeyCode::Char('p') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                palette.move_by(-1)
+            }
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(AppEvent::Exit);
+            }
+            KeyCode::Enter => {
+                let item = palette.selected();
+                self.command_palette = None;
+                match item {
+                    Some(command_palette::PaletteItem::Column(index)) => {
+                        if let Some(state) = self.data_table_state.as_mut() {
+                            state.scroll_to_column(index);
+                        }
+                    }
+                    // Run the action through its active key, as if it had been pressed
+                    Some(command_palette::PaletteItem::Action(action)) => {
+                        match self.key_bindings.keys(action).first() {
+                            Some(key) => {
+                                return self.key(&KeyEvent::new(key.code, key.modifiers));
+                            }
+                            None => self.error_modal.show(format!(
+                                "{} has no key (see [keys] in the config)",
+                                action.name()
+                            )),
+                        }
                     }
+                    None => {}
                 }
-            };
-            // Ctrl-? always opens help; bare ? only when not in a text field
-            if ctrl_help || !in_text_input {
-                self.open_help_overlay();
-                return None;
+            }
+            _ => {
+                let _ = palette.input.handle_key(event, None);
+                palette.refresh();
             }
         }
+        None
+    }
 
-        if self.input_mode == InputMode::SortFilter {
-            let on_tab_bar = self.sort_filter_modal.focus == SortFilterFocus::TabBar;
-            let on_body = self.sort_filter_modal.focus == SortFilterFocus::Body;
-            let on_apply = self.sort_filter_modal.focus == SortFilterFocus::Apply;
-            let on_cancel = self.sort_filter_modal.focus == SortFilterFocus::Cancel;
-            let on_clear = self.sort_filter_modal.focus == SortFilterFocus::Clear;
-            let sort_tab = self.sort_filter_modal.active_tab == SortFilterTab::Sort;
-            let filter_tab = self.sort_filter_modal.active_tab == SortFilterTab::Filter;
+    /// Open the Columns modal on the columns of the current view.
+    fn open_columns_modal(&mut self) {
+        let Some(state) = self.data_table_state.as_ref() else {
+            return;
+        };
+        let columns: Vec<String> = state.schema.iter_names().map(|s| s.to_string()).collect();
+        self.columns_modal = Some(columns_modal::ColumnsModal::new(
+            &columns,
+            state.get_column_order(),
+            state.computed_columns(),
+        ));
+    }
 
-            match event.code {
-                KeyCode::Esc => {
-                    for col in &mut self.sort_filter_modal.sort.columns {
-                        col.is_to_be_locked = false;
+    /// Apply the Columns modal: renames, then computed columns (removed, renamed or duplicated),
+    /// then the order of the visible columns. On error the modal stays open.
+    fn apply_columns_modal(&mut self) {
+        let Some(modal) = self.columns_modal.as_ref() else {
+            return;
+        };
+        let changes = match modal.changes() {
+            Ok(changes) => changes,
+            Err(message) => {
+                self.error_modal.show(message);
+                return;
+            }
+        };
+        let Some(state) = self.data_table_state.as_mut() else {
+            return;
+        };
+        state.defer_collect = true;
+        let mut result = Ok(());
+        if !changes.renames.is_empty() {
+            result = state.rename_columns(&changes.renames);
+        }
+        if result.is_ok() {
+            result = state.set_computed_columns(changes.computed_columns);
+        }
+        if result.is_ok() {
+            state.set_column_order(changes.order);
+        }
+        state.defer_collect = false;
+        match result {
+            Ok(()) => {
+                self.columns_modal = None;
+                self.spawn_async_collect("Updating columns...");
+            }
+            Err(message) => self.error_modal.show(message),
+        }
+    }
+
+    /// Keys of the Columns modal: Up/Down pick a column, Shift+Up/Down (or `K`/`J`) move it,
+    /// Space shows or hides it, `r` renames it, `d` duplicates it and `x` removes a computed
+    /// column. Enter applies, Esc closes. While renaming, keys edit the name; Enter keeps it.
+    fn columns_modal_key(&mut self, event: &KeyEvent) -> Option<AppEvent> {
+        if !event.is_press() {
+            return None;
+        }
+        let modal = self.columns_modal.as_mut()?;
+        if let Some(input) = modal.rename_input.as_mut() {
+            match event.code {
+                KeyCode::Esc => modal.rename_input = None,
+                KeyCode::Enter => {
+                    if let Err(message) = modal.finish_rename() {
+                        self.error_modal.show(message);
                     }
-                    self.sort_filter_modal.sort.has_unapplied_changes = false;
-                    self.sort_filter_modal.close();
-                    self.input_mode = InputMode::Normal;
                 }
-                KeyCode::Tab => self.sort_filter_modal.next_focus(),
-                KeyCode::BackTab => self.sort_filter_modal.prev_focus(),
-                KeyCode::Left | KeyCode::Char('h') if on_tab_bar => {
-                    self.sort_filter_modal.switch_tab();
+                _ => {
+                    let _ = input.handle_key(event, None);
                 }
-                KeyCode::Right | KeyCode::Char('l') if on_tab_bar => {
-                    self.sort_filter_modal.switch_tab();
+            }
+            return None;
+        }
+        let shift = event.modifiers.contains(KeyModifiers::SHIFT);
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.columns_modal = None,
+            KeyCode::Down if shift => modal.shift(1),
+            KeyCode::Up if shift => modal.shift(-1),
+            KeyCode::Char('J') => modal.shift(1),
+            KeyCode::Char('K') => modal.shift(-1),
+            KeyCode::Down | KeyCode::Char('j') => modal.move_by(1),
+            KeyCode::Up | KeyCode::Char('k') => modal.move_by(-1),
+            KeyCode::Home => modal.move_by(isize::MIN),
+            KeyCode::End => modal.move_by(isize::MAX),
+            KeyCode::Char(' ') => modal.toggle_visible(),
+            KeyCode::Char('r') => modal.start_rename(&self.theme),
+            KeyCode::Char('d') => modal.duplicate(),
+            KeyCode::Char('x') | KeyCode::Delete if !modal.remove() => {
+                self.error_modal
+                    .show("Only computed columns can be removed: Space hides a column".to_string());
+            }
+            KeyCode::Enter => self.apply_columns_modal(),
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(AppEvent::Exit);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Open the Scripts menu on the scripts directory of the config directory.
+    fn open_scripts_modal(&mut self) {
+        if self.data_table_state.is_none() {
+            return;
+        }
+        self.scripts_modal = Some(script::ScriptsModal::new(
+            self.config_manager().config_path(script::SCRIPTS_DIR),
+        ));
+    }
+
+    /// Run the script at `path` on the source of the view in the background;
+    /// `BackgroundScriptReady` applies its result. On error the Scripts menu stays open.
+    pub fn run_script(&mut self, path: &Path) {
+        let Some(state) = self.data_table_state.as_ref() else {
+            return;
+        };
+        let source = match state.script_source() {
+            Ok(lf) => lf,
+            Err(message) => {
+                self.error_modal.show(message);
+                return;
+            }
+        };
+        let path = path.to_path_buf();
+        self.spawn_bg("Running script...", move |gen, tx| {
+            let result = script::run_script_file(&path, source).map(Box::new);
+            let _ = tx.send(AppEvent::BackgroundScriptReady {
+                generation: gen,
+                result,
+            });
+        });
+    }
+
+    /// Keys of the Scripts menu: Up/Down pick a script, Enter runs it, `o` types the path of
+    /// another one, Esc closes. While typing a path, Enter runs it and Esc goes back.
+    fn scripts_modal_key(&mut self, event: &KeyEvent) -> Option<AppEvent> {
+        if !event.is_press() {
+            return None;
+        }
+        let modal = self.scripts_modal.as_mut()?;
+        if let Some(input) = modal.path_input.as_mut() {
+            match event.code {
+                KeyCode::Esc => modal.path_input = None,
+                KeyCode::Enter => {
+                    let path = PathBuf::from(input.value().trim());
+                    self.run_script(&path);
                 }
-                KeyCode::Enter if event.modifiers.contains(KeyModifiers::CONTROL) && sort_tab => {
-                    let columns = self.sort_filter_modal.sort.get_sorted_columns();
-                    let column_order = self.sort_filter_modal.sort.get_column_order();
-                    let locked_count = self.sort_filter_modal.sort.get_locked_columns_count();
-                    let ascending = self.sort_filter_modal.sort.ascending;
-                    self.sort_filter_modal.sort.has_unapplied_changes = false;
-                    self.sort_filter_modal.close();
-                    self.input_mode = InputMode::Normal;
-                    let _ = self.send_event(AppEvent::ColumnOrder(column_order, locked_count));
-                    return Some(AppEvent::Sort(columns, ascending));
+                _ => {
+                    let _ = input.handle_key(event, None);
                 }
-                KeyCode::Enter if on_apply => {
-                    if sort_tab {
-                        let columns = self.sort_filter_modal.sort.get_sorted_columns();
-                        let column_order = self.sort_filter_modal.sort.get_column_order();
-                        let locked_count = self.sort_filter_modal.sort.get_locked_columns_count();
-                        let ascending = self.sort_filter_modal.sort.ascending;
-                        self.sort_filter_modal.sort.has_unapplied_changes = false;
-                        self.sort_filter_modal.close();
-                        self.input_mode = InputMode::Normal;
-                        let _ = self.send_event(AppEvent::ColumnOrder(column_order, locked_count));
-                        return Some(AppEvent::Sort(columns, ascending));
-                    } else {
-                        let statements = self.sort_filter_modal.filter.statements.clone();
-                        self.sort_filter_modal.close();
-                        self.input_mode = InputMode::Normal;
-                        return Some(AppEvent::Filter(statements));
-                    }
+            }
+            return None;
+        }
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.scripts_modal = None,
+            KeyCode::Down | KeyCode::Char('j') => modal.move_by(1),
+            KeyCode::Up | KeyCode::Char('k') => modal.move_by(-1),
+            KeyCode::Home => modal.move_by(isize::MIN),
+            KeyCode::End => modal.move_by(isize::MAX),
+            KeyCode::Char('o') => modal.start_path_input(&self.theme),
+            KeyCode::Enter => {
+                if let Some(path) = modal.selected().cloned() {
+                    self.run_script(&path);
                 }
-                KeyCode::Enter if on_cancel => {
-                    for col in &mut self.sort_filter_modal.sort.columns {
-                        col.is_to_be_locked = false;
-                    }
-                    self.sort_filter_modal.sort.has_unapplied_changes = false;
-                    self.sort_filter_modal.close();
-                    self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(AppEvent::Exit);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Open the Saved Queries menu: the queries of the config directory, then those of the
+    /// applied template.
+    fn open_saved_queries_modal(&mut self) {
+        let Some(state) = self.data_table_state.as_ref() else {
+            return;
+        };
+        let saved = match saved_queries::SavedQueries::load(&self.config_manager()) {
+            Ok(saved) => saved,
+            Err(e) => {
+                self.error_modal
+                    .show(format!("Could not read saved queries: {}", e));
+                return;
+            }
+        };
+        let template = self
+            .active_template_id
+            .as_ref()
+            .and_then(|id| self.template_manager.get_template_by_id(id))
+            .map(|t| (t.name.clone(), t.settings.saved_queries.clone()));
+        let current = state
+            .query_engine()
+            .map(|engine| saved_queries::SavedQuery {
+                name: String::new(),
+                query: match engine {
+                    QueryEngine::Sql => state.get_active_sql_query().to_string(),
+                    QueryEngine::DatuiExpr => state.get_active_query().to_string(),
+                },
+                sql: engine == QueryEngine::Sql,
+            });
+        self.saved_queries_modal = Some(saved_queries::SavedQueriesModal::new(
+            saved, template, current,
+        ));
+    }
+
+    /// Run `query` on the view as its SQL-Like or SQL query. On error the Saved Queries menu
+    /// stays open.
+    pub fn apply_saved_query(&mut self, query: &saved_queries::SavedQuery) {
+        let Some(state) = self.data_table_state.as_mut() else {
+            return;
+        };
+        state.defer_collect = true;
+        match query.engine() {
+            QueryEngine::Sql => state.sql_query(query.query.clone()),
+            QueryEngine::DatuiExpr => state.query(query.query.clone()),
+        }
+        state.defer_collect = false;
+        match state.error.take() {
+            None => {
+                state.suppress_error_display = false;
+                self.saved_queries_modal = None;
+                self.spawn_async_collect("Applying query...");
+            }
+            Some(e) => self.error_modal.show(format!(
+                "Query '{}' failed: {}",
+                query.name,
+                crate::error_display::user_message_from_polars(&e)
+            )),
+        }
+    }
+
+    /// Save the Saved Queries menu's queries, or those of the applied template when `template`
+    /// holds its new list.
+    fn store_saved_queries(&mut self, template: Option<Vec<saved_queries::SavedQuery>>) {
+        let Some(modal) = self.saved_queries_modal.as_mut() else {
+            return;
+        };
+        let result = match template {
+            None => modal.saved.save(),
+            Some(queries) => {
+                let Some(mut active) = self
+                    .active_template_id
+                    .as_ref()
+                    .and_then(|id| self.template_manager.get_template_by_id(id))
+                    .cloned()
+                else {
+                    self.error_modal
+                        .show("Apply a template (T) to keep queries in it".to_string());
+                    return;
+                };
+                active.settings.saved_queries = queries.clone();
+                let result = self.template_manager.update_template(&active);
+                if result.is_ok() {
+                    modal.template = Some((active.name, queries));
                 }
-                KeyCode::Enter if on_clear => {
-                    if sort_tab {
-                        self.sort_filter_modal.sort.clear_selection();
-                        let columns = self.sort_filter_modal.sort.get_sorted_columns();
-                        let column_order = self.sort_filter_modal.sort.get_column_order();
-                        let locked_count = self.sort_filter_modal.sort.get_locked_columns_count();
-                        let ascending = self.sort_filter_modal.sort.ascending;
-                        self.sort_filter_modal.sort.has_unapplied_changes = false;
-                        self.sort_filter_modal.close();
-                        self.input_mode = InputMode::Normal;
-                        let _ = self.send_event(AppEvent::ColumnOrder(column_order, locked_count));
-                        return Some(AppEvent::Sort(columns, ascending));
-                    } else {
-                        self.sort_filter_modal.filter.statements.clear();
-                        self.sort_filter_modal.filter.list_state.select(None);
-                        self.sort_filter_modal.close();
-                        self.input_mode = InputMode::Normal;
-                        return Some(AppEvent::Filter(vec![]));
+                result
+            }
+        };
+        match result {
+            Ok(()) => modal.move_by(0),
+            Err(e) => self
+                .error_modal
+                .show(format!("Could not save queries: {}", e)),
+        }
+    }
+
+    /// Keys of the Saved Queries menu: Up/Down pick a query, Enter applies it, `s` saves the
+    /// view's query under a name, `t` keeps the selected query in the applied template, `d`
+    /// deletes it, Esc closes. While typing a name, Enter saves and Esc goes back.
+    fn saved_queries_modal_key(&mut self, event: &KeyEvent) -> Option<AppEvent> {
+        if !event.is_press() {
+            return None;
+        }
+        let modal = self.saved_queries_modal.as_mut()?;
+        if let Some(input) = modal.name_input.as_mut() {
+            match event.code {
+                KeyCode::Esc => modal.name_input = None,
+                KeyCode::Enter => {
+                    let name = input.value().trim().to_string();
+                    if let (false, Some(current)) = (name.is_empty(), modal.current.clone()) {
+                        modal
+                            .saved
+                            .upsert(saved_queries::SavedQuery { name, ..current });
+                        modal.name_input = None;
+                        self.store_saved_queries(None);
                     }
                 }
-                KeyCode::Char(' ')
-                    if on_body
-                        && sort_tab
-                        && self.sort_filter_modal.sort.focus == SortFocus::ColumnList =>
-                {
-                    self.sort_filter_modal.sort.toggle_selection();
-                }
-                KeyCode::Char(' ')
-                    if on_body
-                        && sort_tab
-                        && self.sort_filter_modal.sort.focus == SortFocus::Order =>
-                {
-                    self.sort_filter_modal.sort.ascending = !self.sort_filter_modal.sort.ascending;
-                    self.sort_filter_modal.sort.has_unapplied_changes = true;
+                _ => {
+                    let _ = input.handle_key(event, None);
                 }
-                KeyCode::Char(' ') if on_apply && sort_tab => {
-                    let columns = self.sort_filter_modal.sort.get_sorted_columns();
-                    let column_order = self.sort_filter_modal.sort.get_column_order();
-                    let locked_count = self.sort_filter_modal.sort.get_locked_columns_count();
-                    let ascending = self.sort_filter_modal.sort.ascending;
-                    self.sort_filter_modal.sort.has_unapplied_changes = false;
-                    let _ = self.send_event(AppEvent::ColumnOrder(column_order, locked_count));
-                    return Some(AppEvent::Sort(columns, ascending));
+            }
+            return None;
+        }
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.saved_queries_modal = None,
+            KeyCode::Down | KeyCode::Char('j') => modal.move_by(1),
+            KeyCode::Up | KeyCode::Char('k') => modal.move_by(-1),
+            KeyCode::Home => modal.move_by(isize::MIN),
+            KeyCode::End => modal.move_by(isize::MAX),
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(AppEvent::Exit);
+            }
+            KeyCode::Char('s') => {
+                if modal.current.is_some() {
+                    modal.start_name_input(&self.theme);
+                } else {
+                    self.error_modal
+                        .show("The view has no query to save; run one with /".to_string());
                 }
-                KeyCode::Enter if on_body && filter_tab => {
-                    match self.sort_filter_modal.filter.focus {
-                        FilterFocus::Add => {
-                            self.sort_filter_modal.filter.add_statement();
-                        }
-                        FilterFocus::Statements => {
-                            let m = &mut self.sort_filter_modal.filter;
-                            if let Some(idx) = m.list_state.selected() {
-                                if idx < m.statements.len() {
-                                    m.statements.remove(idx);
-                                    if m.statements.is_empty() {
-                                        m.list_state.select(None);
-                                        m.focus = FilterFocus::Column;
-                                    } else {
-                                        m.list_state
-                                            .select(Some(m.statements.len().saturating_sub(1)));
-                                    }
-                                }
-                            }
-                        }
-                        _ => {}
-                    }
+            }
+            KeyCode::Enter => {
+                if let Some((query, _)) = modal.selected() {
+                    let query = query.clone();
+                    self.apply_saved_query(&query);
                 }
-                KeyCode::Enter if on_body && sort_tab => match self.sort_filter_modal.sort.focus {
-                    SortFocus::Filter => {
-                        self.sort_filter_modal.sort.focus = SortFocus::ColumnList;
-                    }
-                    SortFocus::ColumnList => {
-                        self.sort_filter_modal.sort.toggle_selection();
-                        let columns = self.sort_filter_modal.sort.get_sorted_columns();
-                        let column_order = self.sort_filter_modal.sort.get_column_order();
-                        let locked_count = self.sort_filter_modal.sort.get_locked_columns_count();
-                        let ascending = self.sort_filter_modal.sort.ascending;
-                        self.sort_filter_modal.sort.has_unapplied_changes = false;
-                        let _ = self.send_event(AppEvent::ColumnOrder(column_order, locked_count));
-                        return Some(AppEvent::Sort(columns, ascending));
-                    }
-                    SortFocus::Order => {
-                        self.sort_filter_modal.sort.ascending =
-                            !self.sort_filter_modal.sort.ascending;
-                        self.sort_filter_modal.sort.has_unapplied_changes = true;
-                    }
-                    _ => {}
-                },
-                KeyCode::Left
-                | KeyCode::Right
-                | KeyCode::Char('h')
-                | KeyCode::Char('l')
-                | KeyCode::Up
-                | KeyCode::Down
-                | KeyCode::Char('j')
-                | KeyCode::Char('k')
-                    if on_body
-                        && sort_tab
-                        && self.sort_filter_modal.sort.focus == SortFocus::Order =>
-                {
-                    let s = &mut self.sort_filter_modal.sort;
-                    match event.code {
-                        KeyCode::Left | KeyCode::Char('h') | KeyCode::Up | KeyCode::Char('k') => {
-                            s.ascending = true;
-                        }
-                        KeyCode::Right
-                        | KeyCode::Char('l')
-                        | KeyCode::Down
-                        | KeyCode::Char('j') => {
-                            s.ascending = false;
-                        }
-                        _ => {}
+            }
+            KeyCode::Char('t') => {
+                if let Some((query, false)) = modal.selected() {
+                    let mut queries = modal
+                        .template
+                        .as_ref()
+                        .map(|(_, queries)| queries.clone())
+                        .unwrap_or_default();
+                    saved_queries::upsert(&mut queries, query.clone());
+                    self.store_saved_queries(Some(queries));
+                }
+            }
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some((query, from_template)) = modal.selected() {
+                    let name = query.name.clone();
+                    if from_template {
+                        let mut queries = modal
+                            .template
+                            .as_ref()
+                            .map(|(_, queries)| queries.clone())
+                            .unwrap_or_default();
+                        queries.retain(|q| q.name != name);
+                        self.store_saved_queries(Some(queries));
+                    } else {
+                        modal.saved.remove(&name);
+                        self.store_saved_queries(None);
                     }
-                    s.has_unapplied_changes = true;
                 }
-                KeyCode::Down
-                    if on_body
-                        && filter_tab
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Whether the main table takes mouse input: Normal mode with nothing drawn over it.
+    fn main_table_has_focus(&self) -> bool {
+        self.input_mode == InputMode::Normal
+            && !self.show_help
+            && !self.template_modal.active
+            && !self.analysis_modal.active
+            && !self.workspace_list_visible()
+            && !self.confirmation_modal.active
+            && !self.success_modal.active
+            && !self.error_modal.active
+            && self.s3_browser.is_none()
+            && self.file_browser.is_none()
+            && self.table_picker.is_none()
+            && self.sheet_preview.is_none()
+            && self.csv_dialect.is_none()
+            && self.load_report.is_none()
+            && self.schema_modal.is_none()
+            && self.columns_modal.is_none()
+            && self.scripts_modal.is_none()
+            && self.saved_queries_modal.is_none()
+            && self.command_palette.is_none()
+            && self.aggregate_peek.is_none()
+            && self.cell_peek.is_none()
+            && self.value_counts.is_none()
+            && self.record_view.is_none()
+            && self.data_diff.is_none()
+            && !self.format_modal.active
+            && !self.sampling_modal.active
+            && !self.computed_column_modal.active
+            && !self.time_zone_modal.active
+            && !self.cast_modal.active
+            && !self.rolling_modal.active
+            && !self.resample_modal.active
+            && !self.group_by_modal.active
+    }
+
+    /// Move the view by `rows`, deferring to a collect when it leaves the buffer.
+    fn scroll_rows(&mut self, rows: i64) -> Option<AppEvent> {
+        let state = self.data_table_state.as_mut()?;
+        if rows == 0 {
+            None
+        } else if state.scroll_would_trigger_collect(rows) {
+            self.busy = true;
+            Some(AppEvent::DoScrollRows(rows))
+        } else {
+            state.slide_table(rows);
+            None
+        }
+    }
+
+    /// Cycle the sort on `column` (ascending, descending, none) and keep the Sort & Filter
+    /// modal's sort tab in step.
+    fn sort_by_header(&mut self, column: &str) -> Option<AppEvent> {
+        let state = self.data_table_state.as_ref()?;
+        let (columns, ascending) =
+            mouse::header_click_sort(state.get_sort_columns(), state.get_sort_ascending(), column);
+        let sort = &mut self.sort_filter_modal.sort;
+        for c in &mut sort.columns {
+            c.sort_order = columns
+                .iter()
+                .position(|name| *name == c.name)
+                .map(|i| i + 1);
+        }
+        sort.ascending = ascending;
+        Some(AppEvent::Sort(columns, ascending))
+    }
+
+    fn ensure_file_extension(
+        path: &Path,
+        format: ExportFormat,
+        compression: Option<CompressionFormat>,
+    ) -> PathBuf {
+        let current_ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let mut new_path = path.to_path_buf();
+
+        if current_ext.is_empty() {
+            // No extension: use default for format (and add compression if selected)
+            let desired_ext = if let Some(comp) = compression {
+                format!("{}.{}", format.extension(), comp.extension())
+            } else {
+                format.extension().to_string()
+            };
+            new_path.set_extension(&desired_ext);
+        } else {
+            // User provided an extension: keep it. Only add compression suffix when compression is selected.
+            let is_compression_only = matches!(
+                current_ext.to_lowercase().as_str(),
+                "gz" | "zst" | "bz2" | "xz"
+            ) && ExportFormat::from_extension(current_ext).is_none();
+
+            if is_compression_only {
+                // Path has only compression ext (e.g. file.gz); stem may have format (file.csv.gz)
+                let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+                let stem_has_format = stem
+                    .split('.')
+                    .next_back()
+                    .and_then(ExportFormat::from_extension)
+                    .is_some();
+                if stem_has_format {
+                    if let Some(comp) = compression {
+                        if let Some(format_ext) = stem
+                            .split('.')
+                            .next_back()
+                            .and_then(ExportFormat::from_extension)
+                            .map(|f| f.extension())
+                        {
+                            new_path =
+                                PathBuf::from(stem.rsplit_once('.').map(|x| x.0).unwrap_or(stem));
+                            new_path.set_extension(format!("{}.{}", format_ext, comp.extension()));
+                        }
+                    }
+                } else if let Some(comp) = compression {
+                    new_path.set_extension(format!("{}.{}", format.extension(), comp.extension()));
+                } else {
+                    new_path.set_extension(format.extension());
+                }
+            } else if let Some(comp) = compression {
+                if format.supports_compression() {
+                    new_path.set_extension(format!("{}.{}", current_ext, comp.extension()));
+                }
+                // else: path stays as-is (e.g. foo.feather stays foo.feather)
+            }
+            // else: path with format extension stays as-is
+        }
+
+        new_path
+    }
+
+    pub fn new(events: Sender<AppEvent>, runtime: tokio::runtime::Handle) -> App {
+        // Create default theme for backward compatibility
+        let theme = Theme::from_config(&AppConfig::default().theme).unwrap_or_else(|_| {
+            // Create a minimal fallback theme
+            Theme {
+                colors: std::collections::HashMap::new(),
+            }
+        });
+
+        Self::new_with_config(events, runtime, theme, AppConfig::default())
+    }
+
+    pub fn new_with_theme(
+        events: Sender<AppEvent>,
+        runtime: tokio::runtime::Handle,
+        theme: Theme,
+    ) -> App {
+        Self::new_with_config(events, runtime, theme, AppConfig::default())
+    }
+
+    pub fn new_with_config(
+        events: Sender<AppEvent>,
+        runtime: tokio::runtime::Handle,
+        theme: Theme,
+        app_config: AppConfig,
+    ) -> App {
+        let cache = CacheManager::new(APP_NAME).unwrap_or_else(|_| CacheManager {
+            cache_dir: std::env::temp_dir().join(APP_NAME),
+        });
+        let template_manager = Self::load_template_manager();
+        Self::with_managers(events, runtime, theme, app_config, cache, template_manager)
+    }
+
+    /// Creates an app for `--safe-mode`: templates, scripts, saved queries and query history
+    /// use an empty scratch directory that is deleted on exit, so nothing from the user's config
+    /// or previous sessions is read.
+    pub fn new_safe_mode(
+        events: Sender<AppEvent>,
+        runtime: tokio::runtime::Handle,
+        theme: Theme,
+        app_config: AppConfig,
+    ) -> App {
+        let scratch = tempfile::Builder::new()
+            .prefix(&format!("{}-safe-mode-", APP_NAME))
+            .tempdir()
+            .ok();
+        let root = scratch.as_ref().map_or_else(
+            || std::env::temp_dir().join(format!("{}-safe-mode-{}", APP_NAME, std::process::id())),
+            |dir| dir.path().to_path_buf(),
+        );
+        let cache = CacheManager {
+            cache_dir: root.join("cache"),
+        };
+        let template_manager =
+            TemplateManager::empty(&ConfigManager::with_dir(root.join("config")));
+        let mut app =
+            Self::with_managers(events, runtime, theme, app_config, cache, template_manager);
+        app.safe_mode_dir = scratch;
+        app
+    }
+
+    /// Config directory for scripts and saved queries: the user's, or in `--safe-mode` the empty
+    /// scratch one.
+    fn config_manager(&self) -> ConfigManager {
+        match &self.safe_mode_dir {
+            Some(dir) => ConfigManager::with_dir(dir.path().join("config")),
+            None => ConfigManager::new(APP_NAME).unwrap_or_else(|_| ConfigManager {
+                config_dir: std::env::temp_dir().join(APP_NAME).join("config"),
+            }),
+        }
+    }
+
+    /// Templates are only needed once a template modal opens (`t` / `T`), so they are read
+    /// from disk then rather than at startup.
+    fn load_template_manager() -> TemplateManager {
+        let config_manager = ConfigManager::new(APP_NAME).unwrap_or_else(|_| ConfigManager {
+            config_dir: std::env::temp_dir().join(APP_NAME).join("config"),
+        });
+        TemplateManager::deferred(&config_manager)
+    }
+
+    fn with_managers(
+        events: Sender<AppEvent>,
+        runtime: tokio::runtime::Handle,
+        theme: Theme,
+        app_config: AppConfig,
+        cache: CacheManager,
+        template_manager: TemplateManager,
+    ) -> App {
+        App {
+            path: None,
+            data_table_state: None,
+            original_file_format: None,
+            original_file_delimiter: None,
+            events,
+            focus: 0,
+            debug: DebugState::default(),
+            info_modal: InfoModal::new(),
+            parquet_metadata_cache: None,
+            query_input: TextInput::new()
+                .with_history_limit(app_config.query.history_limit)
+                .with_theme(&theme)
+                .with_history("query".to_string()),
+            sql_input: TextInput::new()
+                .with_history_limit(app_config.query.history_limit)
+                .with_theme(&theme)
+                .with_history("sql".to_string()),
+            fuzzy_input: TextInput::new()
+                .with_history_limit(app_config.query.history_limit)
+                .with_theme(&theme)
+                .with_history("fuzzy".to_string()),
+            regex_input: TextInput::new()
+                .with_history_limit(app_config.query.history_limit)
+                .with_theme(&theme)
+                .with_history("regex".to_string()),
+            input_mode: InputMode::Normal,
+            input_type: None,
+            query_tab: QueryTab::SqlLike,
+            query_focus: QueryFocus::Input,
+            query_completion: None,
+            sort_filter_modal: SortFilterModal::new(),
+            pivot_melt_modal: PivotMeltModal::new(),
+            template_modal: TemplateModal::new(),
+            analysis_modal: AnalysisModal::new(),
+            chart_modal: ChartModal::new(),
+            chart_export_modal: ChartExportModal::new(),
+            chart_labels_modal: ChartLabelsModal::new(),
+            format_modal: FormatModal::new(),
+            sampling_modal: SamplingModal::new(),
+            computed_column_modal: ComputedColumnModal::new(),
+            group_by_modal: GroupByModal::new(),
+            resample_modal: ResampleModal::new(),
+            rolling_modal: RollingModal::new(),
+            time_zone_modal: TimeZoneModal::new(),
+            cast_modal: CastModal::new(),
+            export_modal: ExportModal::new(),
+            chart_cache: ChartCache::default(),
+            error_modal: ErrorModal::new(),
+            success_modal: SuccessModal::new(),
+            confirmation_modal: ConfirmationModal::new(),
+            pending_export: None,
+            export_df: None,
+            pending_chart_export: None,
+            #[cfg(any(feature = "http", feature = "cloud"))]
+            pending_download: None,
+            #[cfg(feature = "cloud")]
+            s3_browser: None,
+            show_help: false,
+            help_scroll: 0,
+            cache,
+            template_manager,
+            active_template_id: None,
+            pending_template: None,
+            workspace: None,
+            aggregate_peek: None,
+            cell_peek: None,
+            value_counts: None,
+            record_view: None,
+            data_diff: None,
+            file_browser: None,
+            table_picker: None,
+            sheet_preview: None,
+            csv_dialect: None,
+            load_report: None,
+            schema_modal: None,
+            columns_modal: None,
+            pending_script: None,
+            pending_view: None,
+            scripts_modal: None,
+            saved_queries_modal: None,
+            command_palette: None,
+            loading_csv: None,
+            loading_source_files: SourceFiles::default(),
+            column_stats: column_stats::ColumnStatsSidebar::default(),
+            heatmap: heatmap::Heatmap::default(),
+            pending_z: false,
+            pending_f: false,
+            column_ruler: app_config.display.column_ruler,
+            // The config is validated at load; an invalid `[keys]` section falls back to defaults.
+            key_bindings: keymap::KeyBindings::from_config(&app_config.keys).unwrap_or_default(),
+            mouse_drag_row: None,
+            external_command: String::new(),
+            loading_state: LoadingState::Idle,
+            theme,
+            sampling_threshold: app_config.performance.sampling_threshold,
+            sampling_strategy: sampling::SamplingStrategy::default(),
+            load_profile: None,
+            concat_sources: None,
+            source_tables: None,
+            last_open: None,
+            history_limit: app_config.query.history_limit,
+            table_cell_padding: app_config.display.table_cell_padding.min(u16::MAX as usize) as u16,
+            column_colors: app_config.display.column_colors,
+            display_locale: app_config
+                .display
+                .locale
+                .as_deref()
+                .and_then(locale::DisplayLocale::from_name)
+                .unwrap_or_default(),
+            runtime,
+            task_generation: 0,
+            pending_schema_result: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            pending_scan_result: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            bg_workers: std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+            len_count_inflight: None,
+            len_count_failed: None,
+            pending_collect_result: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            busy: false,
+            viewport_rows_hint: 0,
+            throbber_frame: 0,
+            drain_keys_on_next_loop: false,
+            status_message: None,
+            analysis_computation: None,
+            app_config,
+            #[cfg(feature = "http")]
+            http_temp_path: None,
+            stdin_temp_path: None,
+            safe_mode_dir: None,
+        }
+    }
+
+    pub fn enable_debug(&mut self) {
+        self.debug.enabled = true;
+    }
+
+    /// Get a color from the theme by name
+    fn color(&self, name: &str) -> Color {
+        self.theme.get(name)
+    }
+
+    fn load(&mut self, paths: &[PathBuf], options: &OpenOptions) -> Result<()> {
+        self.parquet_metadata_cache = None;
+        self.export_df = None;
+        let path = &paths[0]; // Primary path for format detection and single-path logic
+                              // Check for compressed CSV files (e.g., file.csv.gz, file.csv.zst, etc.) — only single-file
+        let compression = options
+            .compression
+            .or_else(|| CompressionFormat::from_extension(path));
+        let is_csv = options.format == Some(FileFormat::Csv)
+            || path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .map(|stem| {
+                    stem.ends_with(".csv")
+                        || path
+                            .extension()
+                            .and_then(|e| e.to_str())
+                            .map(|e| e.eq_ignore_ascii_case("csv"))
+                            .unwrap_or(false)
+                })
+                .unwrap_or(false);
+        let is_compressed_csv = paths.len() == 1 && compression.is_some() && is_csv;
+
+        // For compressed files, decompression phase is already set in DoLoad handler
+        // Now actually perform decompression and CSV reading (this is the slow part)
+        if is_compressed_csv {
+            // Phase: Reading data or Scanning string columns (decompressing + parsing CSV; user may see "Decompressing" until we return)
+            if let LoadingState::Loading {
+                file_path,
+                file_size,
+                ..
+            } = &self.loading_state
+            {
+                self.loading_state = LoadingState::Loading {
+                    file_path: file_path.clone(),
+                    file_size: *file_size,
+                    current_phase: if options.parse_strings.is_some() {
+                        "Scanning string columns".to_string()
+                    } else {
+                        "Reading data".to_string()
+                    },
+                    progress_percent: if options.parse_strings.is_some() {
+                        55
+                    } else {
+                        50
+                    },
+                };
+            }
+            let lf = DataTableState::from_csv(path, options)?; // Already passes pages_lookahead/lookback via options
+
+            // Phase: Building lazyframe (after decompression, before rendering)
+            if let LoadingState::Loading {
+                file_path,
+                file_size,
+                ..
+            } = &self.loading_state
+            {
+                self.loading_state = LoadingState::Loading {
+                    file_path: file_path.clone(),
+                    file_size: *file_size,
+                    current_phase: "Building lazyframe".to_string(),
+                    progress_percent: 60,
+                };
+            }
+
+            // Phased loading: set "Loading buffer" so UI can show progress; caller (DoDecompress) will send DoLoadBuffer
+            if let LoadingState::Loading {
+                file_path,
+                file_size,
+                ..
+            } = &self.loading_state
+            {
+                self.loading_state = LoadingState::Loading {
+                    file_path: file_path.clone(),
+                    file_size: *file_size,
+                    current_phase: "Loading buffer".to_string(),
+                    progress_percent: 70,
+                };
+            }
+
+            self.data_table_state = Some(lf);
+            self.path = Some(path.clone());
+            let original_format =
+                path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| {
+                        if stem.ends_with(".csv") {
+                            Some(ExportFormat::Csv)
+                        } else {
+                            None
+                        }
+                    });
+            self.original_file_format = original_format;
+            self.original_file_delimiter = Some(options.delimiter.unwrap_or(b','));
+            self.sort_filter_modal = SortFilterModal::new();
+            self.pivot_melt_modal = PivotMeltModal::new();
+            return Ok(());
+        }
+
+        // Hive path: when --hive and single path is directory or glob (not a single file), use hive load.
+        // Multiple paths or single file with --hive use the normal path below.
+        if paths.len() == 1 && options.hive {
+            let path_str = path.as_os_str().to_string_lossy();
+            let is_single_file = path.exists()
+                && path.is_file()
+                && !path_str.contains('*')
+                && !path_str.contains("**");
+            if !is_single_file {
+                // Directory or glob of Parquet, CSV, NDJSON or Arrow IPC files
+                if let Some(format) = DataTableState::hive_format(path, options.format) {
+                    if let LoadingState::Loading {
+                        file_path,
+                        file_size,
+                        ..
+                    } = &self.loading_state
+                    {
+                        self.loading_state = LoadingState::Loading {
+                            file_path: file_path.clone(),
+                            file_size: *file_size,
+                            current_phase: "Scanning partitioned dataset".to_string(),
+                            progress_percent: 60,
+                        };
+                    }
+                    let lf = DataTableState::from_hive(path, format, options)?;
+                    if let LoadingState::Loading {
+                        file_path,
+                        file_size,
+                        ..
+                    } = &self.loading_state
+                    {
+                        self.loading_state = LoadingState::Loading {
+                            file_path: file_path.clone(),
+                            file_size: *file_size,
+                            current_phase: "Rendering data".to_string(),
+                            progress_percent: 90,
+                        };
+                    }
+                    self.loading_state = LoadingState::Idle;
+                    self.data_table_state = Some(lf);
+                    self.path = Some(path.clone());
+                    self.original_file_format = file_format_to_export_format(format);
+                    self.original_file_delimiter = None;
+                    // Enable the cheap footer-sum row count for a local Parquet hive directory
+                    // (globs go through the same constructor but aren't a single dir).
+                    if path.is_dir() && format == FileFormat::Parquet {
+                        if let Some(state) = self.data_table_state.as_mut() {
+                            state.set_parquet_count_dir(path.clone());
+                        }
+                    }
+                    self.sort_filter_modal = SortFilterModal::new();
+                    self.pivot_melt_modal = PivotMeltModal::new();
+                    return Ok(());
+                }
+                self.loading_state = LoadingState::Idle;
+                return Err(color_eyre::eyre::eyre!(
+                    "With --hive use a directory or a glob pattern of Parquet, CSV, NDJSON or Arrow IPC files (e.g. path/to/dir or path/**/*.csv)"
+                ));
+            }
+        }
+
+        // For non-gzipped files, proceed with normal loading
+        // Phase 2: Building lazyframe (or Scanning string columns for CSV when --parse-strings)
+        let effective_format = options.format.or_else(|| FileFormat::from_path(path));
+        let csv_parse_strings =
+            effective_format == Some(FileFormat::Csv) && options.parse_strings.is_some();
+        if let LoadingState::Loading {
+            file_path,
+            file_size,
+            ..
+        } = &self.loading_state
+        {
+            self.loading_state = LoadingState::Loading {
+                file_path: file_path.clone(),
+                file_size: *file_size,
+                current_phase: if csv_parse_strings {
+                    "Scanning string columns".to_string()
+                } else {
+                    "Building lazyframe".to_string()
+                },
+                progress_percent: if csv_parse_strings { 55 } else { 60 },
+            };
+        }
+
+        // Determine and store original file format (from explicit format or first path)
+        let original_format = effective_format
+            .and_then(file_format_to_export_format)
+            .or_else(|| {
+                path.extension().and_then(|e| e.to_str()).and_then(|ext| {
+                    if ext.eq_ignore_ascii_case("parquet") {
+                        Some(ExportFormat::Parquet)
+                    } else if ext.eq_ignore_ascii_case("csv") {
+                        Some(ExportFormat::Csv)
+                    } else if ext.eq_ignore_ascii_case("json") {
+                        Some(ExportFormat::Json)
+                    } else if ext.eq_ignore_ascii_case("jsonl")
+                        || ext.eq_ignore_ascii_case("ndjson")
+                    {
+                        Some(ExportFormat::Ndjson)
+                    } else if ext.eq_ignore_ascii_case("arrow")
+                        || ext.eq_ignore_ascii_case("ipc")
+                        || ext.eq_ignore_ascii_case("feather")
+                    {
+                        Some(ExportFormat::Ipc)
+                    } else if ext.eq_ignore_ascii_case("avro") {
+                        Some(ExportFormat::Avro)
+                    } else if ext.eq_ignore_ascii_case("xlsx") {
+                        Some(ExportFormat::Xlsx)
+                    } else {
+                        None
+                    }
+                })
+            });
+
+        self.concat_sources = None;
+        let lf = if paths.len() > 1 && options.concat == ConcatMode::Diagonal {
+            // Diagonal: each file keeps its own format; columns are unioned
+            let (state, sources) = concat::open_diagonal(paths, options)?;
+            self.concat_sources = Some(sources);
+            state
+        } else if paths.len() > 1 && options.source_column {
+            DataTableState::from_lazyframe(concat::concat_vertical(paths, options)?, options)?
+        } else if paths.len() > 1 {
+            // Multiple files: same format assumed (from first path or --format), concatenated into one LazyFrame
+            match effective_format {
+                Some(FileFormat::Parquet) => DataTableState::from_parquet_paths(
+                    paths,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Csv) => DataTableState::from_csv_paths(paths, options)?,
+                Some(FileFormat::Json) => DataTableState::from_json_paths(
+                    paths,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Jsonl) => DataTableState::from_json_lines_paths(
+                    paths,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Arrow) => DataTableState::from_ipc_paths(
+                    paths,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Avro) => DataTableState::from_avro_paths(
+                    paths,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Orc) => DataTableState::from_orc_paths(
+                    paths,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Tsv)
+                | Some(FileFormat::Psv)
+                | Some(FileFormat::Excel)
+                | Some(FileFormat::Sqlite)
+                | Some(FileFormat::Protobuf)
+                | None => {
+                    self.loading_state = LoadingState::Idle;
+                    if !paths.is_empty() && !path.exists() {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::NotFound,
+                            format!("File not found: {}", path.display()),
+                        )
+                        .into());
+                    }
+                    return Err(color_eyre::eyre::eyre!(
+                        "Unsupported file type for multiple files (parquet, csv, json, jsonl, ndjson, arrow/ipc/feather, avro, orc only)"
+                    ));
+                }
+            }
+        } else {
+            match effective_format {
+                Some(FileFormat::Parquet) => DataTableState::from_parquet(
+                    path,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Csv) => DataTableState::from_csv(path, options)?,
+                Some(FileFormat::Tsv) => DataTableState::from_delimited(path, b'\t', options)?,
+                Some(FileFormat::Psv) => DataTableState::from_delimited(path, b'|', options)?,
+                Some(FileFormat::Json) => DataTableState::from_json(
+                    path,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Jsonl) if options.skip_bad_rows => {
+                    DataTableState::from_json_lines_skipping_bad_rows(path, options)?
+                }
+                Some(FileFormat::Jsonl) => DataTableState::from_json_lines(
+                    path,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Arrow) => DataTableState::from_ipc(
+                    path,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Avro) => DataTableState::from_avro(
+                    path,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Excel) => DataTableState::from_excel(path, options)?,
+                Some(FileFormat::Sqlite) => DataTableState::from_sqlite(path, options)?,
+                Some(FileFormat::Protobuf) => DataTableState::from_protobuf(path, options)?,
+                Some(FileFormat::Orc) => DataTableState::from_orc(
+                    path,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                None => {
+                    self.loading_state = LoadingState::Idle;
+                    if paths.len() == 1 && !path.exists() {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::NotFound,
+                            format!("File not found: {}", path.display()),
+                        )
+                        .into());
+                    }
+                    return Err(color_eyre::eyre::eyre!("Unsupported file type"));
+                }
+            }
+        };
+
+        // Phase 3: Rendering data
+        if let LoadingState::Loading {
+            file_path,
+            file_size,
+            ..
+        } = &self.loading_state
+        {
+            self.loading_state = LoadingState::Loading {
+                file_path: file_path.clone(),
+                file_size: *file_size,
+                current_phase: "Rendering data".to_string(),
+                progress_percent: 90,
+            };
+        }
+
+        // Clear loading state after successful load
+        self.loading_state = LoadingState::Idle;
+        self.data_table_state = Some(lf);
+        self.path = Some(path.clone());
+        self.original_file_format = original_format;
+        // Store delimiter based on file type (use effective format when set)
+        self.original_file_delimiter = match effective_format {
+            Some(FileFormat::Csv) => Some(options.delimiter.unwrap_or(b',')),
+            Some(FileFormat::Tsv) => Some(b'\t'),
+            Some(FileFormat::Psv) => Some(b'|'),
+            _ => path.extension().and_then(|e| e.to_str()).and_then(|ext| {
+                if ext.eq_ignore_ascii_case("csv") {
+                    Some(options.delimiter.unwrap_or(b','))
+                } else if ext.eq_ignore_ascii_case("tsv") {
+                    Some(b'\t')
+                } else if ext.eq_ignore_ascii_case("psv") {
+                    Some(b'|')
+                } else {
+                    None
+                }
+            }),
+        };
+        self.sort_filter_modal = SortFilterModal::new();
+        self.pivot_melt_modal = PivotMeltModal::new();
+        Ok(())
+    }
+
+    #[cfg(feature = "cloud")]
+    fn build_s3_cloud_options(
+        cloud: &crate::config::CloudConfig,
+        options: &OpenOptions,
+    ) -> CloudOptions {
+        let mut opts = CloudOptions::default();
+        let mut configs: Vec<(AmazonS3ConfigKey, String)> = Vec::new();
+        let e = options
+            .s3_endpoint_url_override
+            .as_ref()
+            .or(cloud.s3_endpoint_url.as_ref());
+        let k = options
+            .s3_access_key_id_override
+            .as_ref()
+            .or(cloud.s3_access_key_id.as_ref());
+        let s = options
+            .s3_secret_access_key_override
+            .as_ref()
+            .or(cloud.s3_secret_access_key.as_ref());
+        let r = options
+            .s3_region_override
+            .as_ref()
+            .or(cloud.s3_region.as_ref());
+        if let Some(e) = e {
+            configs.push((AmazonS3ConfigKey::Endpoint, e.clone()));
+        }
+        if let Some(k) = k {
+            configs.push((AmazonS3ConfigKey::AccessKeyId, k.clone()));
+        }
+        if let Some(s) = s {
+            configs.push((AmazonS3ConfigKey::SecretAccessKey, s.clone()));
+        }
+        if let Some(r) = r {
+            configs.push((AmazonS3ConfigKey::Region, r.clone()));
+        }
+        if !configs.is_empty() {
+            opts = opts.with_aws(configs);
+        }
+        opts
+    }
+
+    #[cfg(feature = "cloud")]
+    fn build_s3_object_store(
+        s3_url: &str,
+        cloud: &crate::config::CloudConfig,
+        options: &OpenOptions,
+    ) -> Result<Arc<dyn object_store::ObjectStore>> {
+        let (path_part, _ext) = source::url_path_extension(s3_url);
+        let (bucket, _key) = path_part
+            .split_once('/')
+            .ok_or_else(|| color_eyre::eyre::eyre!("S3 URL must be s3://bucket/key"))?;
+        let mut builder = object_store::aws::AmazonS3Builder::from_env()
+            .with_url(s3_url)
+            .with_bucket_name(bucket);
+        let e = options
+            .s3_endpoint_url_override
+            .as_ref()
+            .or(cloud.s3_endpoint_url.as_ref());
+        let k = options
+            .s3_access_key_id_override
+            .as_ref()
+            .or(cloud.s3_access_key_id.as_ref());
+        let s = options
+            .s3_secret_access_key_override
+            .as_ref()
+            .or(cloud.s3_secret_access_key.as_ref());
+        let r = options
+            .s3_region_override
+            .as_ref()
+            .or(cloud.s3_region.as_ref());
+        if let Some(e) = e {
+            builder = builder.with_endpoint(e);
+        }
+        if let Some(k) = k {
+            builder = builder.with_access_key_id(k);
+        }
+        if let Some(s) = s {
+            builder = builder.with_secret_access_key(s);
+        }
+        if let Some(r) = r {
+            builder = builder.with_region(r);
+        }
+        let store = builder
+            .build()
+            .map_err(|e| color_eyre::eyre::eyre!("S3 config failed: {}", e))?;
+        Ok(Arc::new(store))
+    }
+
+    #[cfg(feature = "cloud")]
+    fn build_gcs_object_store(gs_url: &str) -> Result<Arc<dyn object_store::ObjectStore>> {
+        let (path_part, _ext) = source::url_path_extension(gs_url);
+        let (bucket, _key) = path_part
+            .split_once('/')
+            .ok_or_else(|| color_eyre::eyre::eyre!("GCS URL must be gs://bucket/key"))?;
+        let store = object_store::gcp::GoogleCloudStorageBuilder::from_env()
+            .with_url(gs_url)
+            .with_bucket_name(bucket)
+            .build()
+            .map_err(|e| color_eyre::eyre::eyre!("GCS config failed: {}", e))?;
+        Ok(Arc::new(store))
+    }
+
+    /// Azure account and key: CLI / env overrides, then config. None leaves the value to the
+    /// AZURE_* environment.
+    #[cfg(feature = "cloud")]
+    fn azure_credentials(
+        cloud: &crate::config::CloudConfig,
+        options: &OpenOptions,
+    ) -> (Option<String>, Option<String>) {
+        let account = options
+            .azure_account_override
+            .as_ref()
+            .or(cloud.azure_account.as_ref());
+        let key = options
+            .azure_key_override
+            .as_ref()
+            .or(cloud.azure_key.as_ref());
+        (account.cloned(), key.cloned())
+    }
+
+    #[cfg(feature = "cloud")]
+    fn build_azure_cloud_options(
+        cloud: &crate::config::CloudConfig,
+        options: &OpenOptions,
+    ) -> CloudOptions {
+        let (account, key) = Self::azure_credentials(cloud, options);
+        let configs: Vec<(AzureConfigKey, String)> = account
+            .map(|a| (AzureConfigKey::AccountName, a))
+            .into_iter()
+            .chain(key.map(|k| (AzureConfigKey::AccessKey, k)))
+            .collect();
+        let opts = CloudOptions::default();
+        if configs.is_empty() {
+            opts
+        } else {
+            opts.with_azure(configs)
+        }
+    }
+
+    #[cfg(feature = "cloud")]
+    fn build_azure_object_store(
+        az_url: &str,
+        cloud: &crate::config::CloudConfig,
+        options: &OpenOptions,
+    ) -> Result<Arc<dyn object_store::ObjectStore>> {
+        let mut builder = object_store::azure::MicrosoftAzureBuilder::from_env().with_url(az_url);
+        let (account, key) = Self::azure_credentials(cloud, options);
+        if let Some(account) = account {
+            builder = builder.with_account(account);
+        }
+        if let Some(key) = key {
+            builder = builder.with_access_key(key);
+        }
+        let store = builder
+            .build()
+            .map_err(|e| color_eyre::eyre::eyre!("Azure config failed: {}", e))?;
+        Ok(Arc::new(store))
+    }
+
+    /// Object key of an Azure URL: the path after the container (or `container@account...` host).
+    #[cfg(feature = "cloud")]
+    fn azure_object_key(az_url: &str) -> Result<String> {
+        let (path_part, _ext) = source::url_path_extension(az_url);
+        let (_container, key) = path_part.split_once('/').ok_or_else(|| {
+            color_eyre::eyre::eyre!("Azure URL must be az://container/key or abfss://container@account.dfs.core.windows.net/key")
+        })?;
+        Ok(key.to_string())
+    }
+
+    /// Human-readable byte size for download confirmation modal.
+    fn format_bytes(n: u64) -> String {
+        const KB: u64 = 1024;
+        const MB: u64 = KB * 1024;
+        const GB: u64 = MB * 1024;
+        const TB: u64 = GB * 1024;
+        if n >= TB {
+            format!("{:.2} TB", n as f64 / TB as f64)
+        } else if n >= GB {
+            format!("{:.2} GB", n as f64 / GB as f64)
+        } else if n >= MB {
+            format!("{:.2} MB", n as f64 / MB as f64)
+        } else if n >= KB {
+            format!("{:.2} KB", n as f64 / KB as f64)
+        } else {
+            format!("{} bytes", n)
+        }
+    }
+
+    #[cfg(feature = "http")]
+    fn fetch_remote_size_http(url: &str) -> Result<Option<u64>> {
+        let response = ureq::request("HEAD", url)
+            .timeout(std::time::Duration::from_secs(15))
+            .call();
+        match response {
+            Ok(r) => Ok(r
+                .header("Content-Length")
+                .and_then(|s| s.parse::<u64>().ok())),
+            Err(_) => Ok(None),
+        }
+    }
+
+    #[cfg(feature = "cloud")]
+    fn fetch_remote_size_s3(
+        s3_url: &str,
+        cloud: &crate::config::CloudConfig,
+        options: &OpenOptions,
+        runtime: &tokio::runtime::Handle,
+    ) -> Result<Option<u64>> {
+        use object_store::path::Path as OsPath;
+        use object_store::ObjectStore;
+
+        let (path_part, _ext) = source::url_path_extension(s3_url);
+        let (bucket, key) = path_part
+            .split_once('/')
+            .ok_or_else(|| color_eyre::eyre::eyre!("S3 URL must be s3://bucket/key"))?;
+        if key.is_empty() {
+            return Ok(None);
+        }
+        let mut builder = object_store::aws::AmazonS3Builder::from_env()
+            .with_url(s3_url)
+            .with_bucket_name(bucket);
+        let e = options
+            .s3_endpoint_url_override
+            .as_ref()
+            .or(cloud.s3_endpoint_url.as_ref());
+        let k = options
+            .s3_access_key_id_override
+            .as_ref()
+            .or(cloud.s3_access_key_id.as_ref());
+        let s = options
+            .s3_secret_access_key_override
+            .as_ref()
+            .or(cloud.s3_secret_access_key.as_ref());
+        let r = options
+            .s3_region_override
+            .as_ref()
+            .or(cloud.s3_region.as_ref());
+        if let Some(e) = e {
+            builder = builder.with_endpoint(e);
+        }
+        if let Some(k) = k {
+            builder = builder.with_access_key_id(k);
+        }
+        if let Some(s) = s {
+            builder = builder.with_secret_access_key(s);
+        }
+        if let Some(r) = r {
+            builder = builder.with_region(r);
+        }
+        let store = builder
+            .build()
+            .map_err(|e| color_eyre::eyre::eyre!("S3 config failed: {}", e))?;
+        let path = OsPath::from(key);
+        match runtime.block_on(store.head(&path)) {
+            Ok(meta) => Ok(Some(meta.size)),
+            Err(_) => Ok(None),
+        }
+    }
+
+    #[cfg(feature = "cloud")]
+    fn fetch_remote_size_gcs(
+        gs_url: &str,
+        _options: &OpenOptions,
+        runtime: &tokio::runtime::Handle,
+    ) -> Result<Option<u64>> {
+        use object_store::path::Path as OsPath;
+        use object_store::ObjectStore;
+
+        let (path_part, _ext) = source::url_path_extension(gs_url);
+        let (bucket, key) = path_part
+            .split_once('/')
+            .ok_or_else(|| color_eyre::eyre::eyre!("GCS URL must be gs://bucket/key"))?;
+        if key.is_empty() {
+            return Ok(None);
+        }
+        let store = object_store::gcp::GoogleCloudStorageBuilder::from_env()
+            .with_url(gs_url)
+            .with_bucket_name(bucket)
+            .build()
+            .map_err(|e| color_eyre::eyre::eyre!("GCS config failed: {}", e))?;
+        let path = OsPath::from(key);
+        match runtime.block_on(store.head(&path)) {
+            Ok(meta) => Ok(Some(meta.size)),
+            Err(_) => Ok(None),
+        }
+    }
+
+    #[cfg(feature = "cloud")]
+    fn fetch_remote_size_azure(
+        az_url: &str,
+        cloud: &crate::config::CloudConfig,
+        options: &OpenOptions,
+        runtime: &tokio::runtime::Handle,
+    ) -> Result<Option<u64>> {
+        use object_store::path::Path as OsPath;
+        use object_store::ObjectStore;
+
+        let key = Self::azure_object_key(az_url)?;
+        if key.is_empty() {
+            return Ok(None);
+        }
+        let store = Self::build_azure_object_store(az_url, cloud, options)?;
+        match runtime.block_on(store.head(&OsPath::from(key))) {
+            Ok(meta) => Ok(Some(meta.size)),
+            Err(_) => Ok(None),
+        }
+    }
+
+    #[cfg(feature = "http")]
+    fn download_http_to_temp(
+        url: &str,
+        temp_dir: Option<&Path>,
+        extension: Option<&str>,
+    ) -> Result<PathBuf> {
+        let dir = temp_dir
+            .map(Path::to_path_buf)
+            .unwrap_or_else(std::env::temp_dir);
+        let suffix = extension
+            .map(|e| format!(".{e}"))
+            .unwrap_or_else(|| ".tmp".to_string());
+        let mut temp = tempfile::Builder::new()
+            .suffix(&suffix)
+            .tempfile_in(&dir)
+            .map_err(|_| color_eyre::eyre::eyre!("Could not create a temporary file."))?;
+        let response = ureq::get(url)
+            .timeout(std::time::Duration::from_secs(300))
+            .call()
+            .map_err(|e| {
+                color_eyre::eyre::eyre!("Download failed. Check the URL and your connection: {}", e)
+            })?;
+        let status = response.status();
+        if status >= 400 {
+            return Err(color_eyre::eyre::eyre!(
+                "Server returned {} {}. Check the URL.",
+                status,
+                response.status_text()
+            ));
+        }
+        std::io::copy(&mut response.into_reader(), &mut temp)
+            .map_err(|_| color_eyre::eyre::eyre!("Download failed while saving the file."))?;
+        let (_file, path) = temp
+            .keep()
+            .map_err(|_| color_eyre::eyre::eyre!("Could not save the downloaded file."))?;
+        Ok(path)
+    }
+
+    #[cfg(feature = "cloud")]
+    fn download_s3_to_temp(
+        s3_url: &str,
+        cloud: &crate::config::CloudConfig,
+        options: &OpenOptions,
+        runtime: &tokio::runtime::Handle,
+    ) -> Result<PathBuf> {
+        use object_store::path::Path as OsPath;
+        use object_store::ObjectStore;
+
+        let (path_part, ext) = source::url_path_extension(s3_url);
+        let (bucket, key) = path_part
+            .split_once('/')
+            .ok_or_else(|| color_eyre::eyre::eyre!("S3 URL must be s3://bucket/key"))?;
+        if key.is_empty() {
+            return Err(color_eyre::eyre::eyre!(
+                "S3 URL must point to an object (e.g. s3://bucket/path/file.csv)"
+            ));
+        }
+
+        let mut builder = object_store::aws::AmazonS3Builder::from_env()
+            .with_url(s3_url)
+            .with_bucket_name(bucket);
+        let e = options
+            .s3_endpoint_url_override
+            .as_ref()
+            .or(cloud.s3_endpoint_url.as_ref());
+        let k = options
+            .s3_access_key_id_override
+            .as_ref()
+            .or(cloud.s3_access_key_id.as_ref());
+        let s = options
+            .s3_secret_access_key_override
+            .as_ref()
+            .or(cloud.s3_secret_access_key.as_ref());
+        let r = options
+            .s3_region_override
+            .as_ref()
+            .or(cloud.s3_region.as_ref());
+        if let Some(e) = e {
+            builder = builder.with_endpoint(e);
+        }
+        if let Some(k) = k {
+            builder = builder.with_access_key_id(k);
+        }
+        if let Some(s) = s {
+            builder = builder.with_secret_access_key(s);
+        }
+        if let Some(r) = r {
+            builder = builder.with_region(r);
+        }
+        let store = builder
+            .build()
+            .map_err(|e| color_eyre::eyre::eyre!("S3 config failed: {}", e))?;
+
+        let path = OsPath::from(key);
+        let get_result = runtime.block_on(store.get(&path)).map_err(|e| {
+            color_eyre::eyre::eyre!("Could not read from S3. Check credentials and URL: {}", e)
+        })?;
+        let bytes = runtime
+            .block_on(get_result.bytes())
+            .map_err(|e| color_eyre::eyre::eyre!("Could not read S3 object body: {}", e))?;
+
+        let dir = options.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
+        let suffix = ext
+            .as_ref()
+            .map(|e| format!(".{e}"))
+            .unwrap_or_else(|| ".tmp".to_string());
+        let mut temp = tempfile::Builder::new()
+            .suffix(&suffix)
+            .tempfile_in(&dir)
+            .map_err(|_| color_eyre::eyre::eyre!("Could not create a temporary file."))?;
+        std::io::copy(&mut std::io::Cursor::new(bytes.as_ref()), &mut temp)
+            .map_err(|_| color_eyre::eyre::eyre!("Could not write downloaded file."))?;
+        let (_file, path_buf) = temp
+            .keep()
+            .map_err(|_| color_eyre::eyre::eyre!("Could not save the downloaded file."))?;
+        Ok(path_buf)
+    }
+
+    #[cfg(feature = "cloud")]
+    fn download_gcs_to_temp(
+        gs_url: &str,
+        options: &OpenOptions,
+        runtime: &tokio::runtime::Handle,
+    ) -> Result<PathBuf> {
+        use object_store::path::Path as OsPath;
+        use object_store::ObjectStore;
+
+        let (path_part, ext) = source::url_path_extension(gs_url);
+        let (bucket, key) = path_part
+            .split_once('/')
+            .ok_or_else(|| color_eyre::eyre::eyre!("GCS URL must be gs://bucket/key"))?;
+        if key.is_empty() {
+            return Err(color_eyre::eyre::eyre!(
+                "GCS URL must point to an object (e.g. gs://bucket/path/file.csv)"
+            ));
+        }
+
+        let store = object_store::gcp::GoogleCloudStorageBuilder::from_env()
+            .with_url(gs_url)
+            .with_bucket_name(bucket)
+            .build()
+            .map_err(|e| color_eyre::eyre::eyre!("GCS config failed: {}", e))?;
+
+        let path = OsPath::from(key);
+        let get_result = runtime.block_on(store.get(&path)).map_err(|e| {
+            color_eyre::eyre::eyre!("Could not read from GCS. Check credentials and URL: {}", e)
+        })?;
+        let bytes = runtime
+            .block_on(get_result.bytes())
+            .map_err(|e| color_eyre::eyre::eyre!("Could not read GCS object body: {}", e))?;
+
+        let dir = options.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
+        let suffix = ext
+            .as_ref()
+            .map(|e| format!(".{e}"))
+            .unwrap_or_else(|| ".tmp".to_string());
+        let mut temp = tempfile::Builder::new()
+            .suffix(&suffix)
+            .tempfile_in(&dir)
+            .map_err(|_| color_eyre::eyre::eyre!("Could not create a temporary file."))?;
+        std::io::copy(&mut std::io::Cursor::new(bytes.as_ref()), &mut temp)
+            .map_err(|_| color_eyre::eyre::eyre!("Could not write downloaded file."))?;
+        let (_file, path_buf) = temp
+            .keep()
+            .map_err(|_| color_eyre::eyre::eyre!("Could not save the downloaded file."))?;
+        Ok(path_buf)
+    }
+
+    #[cfg(feature = "cloud")]
+    fn download_azure_to_temp(
+        az_url: &str,
+        cloud: &crate::config::CloudConfig,
+        options: &OpenOptions,
+        runtime: &tokio::runtime::Handle,
+    ) -> Result<PathBuf> {
+        use object_store::path::Path as OsPath;
+        use object_store::ObjectStore;
+
+        let key = Self::azure_object_key(az_url)?;
+        if key.is_empty() {
+            return Err(color_eyre::eyre::eyre!(
+                "Azure URL must point to a blob (e.g. az://container/path/file.csv)"
+            ));
+        }
+        let store = Self::build_azure_object_store(az_url, cloud, options)?;
+
+        let get_result = runtime
+            .block_on(store.get(&OsPath::from(key)))
+            .map_err(|e| {
+                color_eyre::eyre::eyre!(
+                    "Could not read from Azure. Check credentials and URL: {}",
+                    e
+                )
+            })?;
+        let bytes = runtime
+            .block_on(get_result.bytes())
+            .map_err(|e| color_eyre::eyre::eyre!("Could not read Azure blob body: {}", e))?;
+
+        let (_, ext) = source::url_path_extension(az_url);
+        let dir = options.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
+        let suffix = ext
+            .as_ref()
+            .map(|e| format!(".{e}"))
+            .unwrap_or_else(|| ".tmp".to_string());
+        let mut temp = tempfile::Builder::new()
+            .suffix(&suffix)
+            .tempfile_in(&dir)
+            .map_err(|_| color_eyre::eyre::eyre!("Could not create a temporary file."))?;
+        std::io::copy(&mut std::io::Cursor::new(bytes.as_ref()), &mut temp)
+            .map_err(|_| color_eyre::eyre::eyre!("Could not write downloaded file."))?;
+        let (_file, path_buf) = temp
+            .keep()
+            .map_err(|_| color_eyre::eyre::eyre!("Could not save the downloaded file."))?;
+        Ok(path_buf)
+    }
+
+    /// Build LazyFrame from paths for phased loading (non-compressed only). Caller must not use for compressed CSV.
+    /// Runs on a worker (see `spawn_scan`), so what the load needs besides the LazyFrame goes in `extras`,
+    /// and `progress` reports the phase (and percent) it is in.
+    fn build_lazyframe_from_paths(
+        paths: &[PathBuf],
+        options: &OpenOptions,
+        cloud: &crate::config::CloudConfig,
+        extras: &mut ScanExtras,
+        progress: &dyn Fn(&str, u16),
+    ) -> Result<LazyFrame> {
+        let path = &paths[0];
+        let source = source::input_source(path);
+        match &source {
+            source::InputSource::S3(_) => progress("Connecting to S3", 20),
+            source::InputSource::Gcs(_) => progress("Connecting to Google Cloud Storage", 20),
+            source::InputSource::Azure(_) => progress("Connecting to Azure Blob Storage", 20),
+            source::InputSource::Database(_) => progress("Querying the database", 20),
+            source::InputSource::Http(_)
+            | source::InputSource::Stdin
+            | source::InputSource::Local(_) => {}
+        }
+        match source {
+            source::InputSource::Http(_url) => {
+                #[cfg(feature = "http")]
+                {
+                    return Err(color_eyre::eyre::eyre!(
+                        "HTTP/HTTPS load is handled in the event loop; this path should not be reached."
+                    ));
+                }
+                #[cfg(not(feature = "http"))]
+                {
+                    return Err(color_eyre::eyre::eyre!(
+                        "HTTP/HTTPS URLs are not supported in this build. Rebuild with default features."
+                    ));
+                }
+            }
+            source::InputSource::S3(url) => {
+                #[cfg(feature = "cloud")]
+                {
+                    let full = format!("s3://{url}");
+                    let cloud_opts = Self::build_s3_cloud_options(cloud, options);
+                    let pl_path = PlPathRef::new(&full).into_owned();
+                    let is_glob = full.contains('*') || full.ends_with('/');
+                    let hive_options = if is_glob {
+                        polars::io::HiveOptions::new_enabled()
+                    } else {
+                        polars::io::HiveOptions::default()
+                    };
+                    let args = ScanArgsParquet {
+                        cloud_options: Some(cloud_opts),
+                        hive_options,
+                        glob: is_glob,
+                        include_file_paths: options
+                            .source_column
+                            .then(|| concat::SOURCE_FILE_COLUMN.into()),
+                        ..Default::default()
+                    };
+                    let lf = LazyFrame::scan_parquet(pl_path, args).map_err(|e| {
+                        color_eyre::eyre::eyre!(
+                            "Could not read from S3. Check credentials and URL: {}",
+                            e
+                        )
+                    })?;
+                    let state = DataTableState::from_lazyframe(lf, options)?;
+                    return Ok(state.lf);
+                }
+                #[cfg(not(feature = "cloud"))]
+                {
+                    return Err(color_eyre::eyre::eyre!(
+                        "S3 is not supported in this build. Rebuild with default features and set AWS credentials (e.g. AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY, AWS_REGION)."
+                    ));
+                }
+            }
+            source::InputSource::Gcs(url) => {
+                #[cfg(feature = "cloud")]
+                {
+                    let full = format!("gs://{url}");
+                    let pl_path = PlPathRef::new(&full).into_owned();
+                    let is_glob = full.contains('*') || full.ends_with('/');
+                    let hive_options = if is_glob {
+                        polars::io::HiveOptions::new_enabled()
+                    } else {
+                        polars::io::HiveOptions::default()
+                    };
+                    let args = ScanArgsParquet {
+                        cloud_options: Some(CloudOptions::default()),
+                        hive_options,
+                        glob: is_glob,
+                        include_file_paths: options
+                            .source_column
+                            .then(|| concat::SOURCE_FILE_COLUMN.into()),
+                        ..Default::default()
+                    };
+                    let lf = LazyFrame::scan_parquet(pl_path, args).map_err(|e| {
+                        color_eyre::eyre::eyre!(
+                            "Could not read from GCS. Check credentials and URL: {}",
+                            e
+                        )
+                    })?;
+                    let state = DataTableState::from_lazyframe(lf, options)?;
+                    return Ok(state.lf);
+                }
+                #[cfg(not(feature = "cloud"))]
+                {
+                    return Err(color_eyre::eyre::eyre!(
+                        "GCS (gs://) is not supported in this build. Rebuild with default features."
+                    ));
+                }
+            }
+            source::InputSource::Azure(full) => {
+                #[cfg(feature = "cloud")]
+                {
+                    let cloud_opts = Self::build_azure_cloud_options(cloud, options);
+                    let pl_path = PlPathRef::new(&full).into_owned();
+                    let is_glob = full.contains('*') || full.ends_with('/');
+                    let hive_options = if is_glob {
+                        polars::io::HiveOptions::new_enabled()
+                    } else {
+                        polars::io::HiveOptions::default()
+                    };
+                    let args = ScanArgsParquet {
+                        cloud_options: Some(cloud_opts),
+                        hive_options,
+                        glob: is_glob,
+                        include_file_paths: options
+                            .source_column
+                            .then(|| concat::SOURCE_FILE_COLUMN.into()),
+                        ..Default::default()
+                    };
+                    let lf = LazyFrame::scan_parquet(pl_path, args).map_err(|e| {
+                        color_eyre::eyre::eyre!(
+                            "Could not read from Azure. Check credentials and URL: {}",
+                            e
+                        )
+                    })?;
+                    let state = DataTableState::from_lazyframe(lf, options)?;
+                    return Ok(state.lf);
+                }
+                #[cfg(not(feature = "cloud"))]
+                {
+                    let _ = full;
+                    return Err(color_eyre::eyre::eyre!(
+                        "Azure Blob (az://, abfss://) is not supported in this build. Rebuild with default features."
+                    ));
+                }
+            }
+            source::InputSource::Database(url) => {
+                #[cfg(feature = "database")]
+                {
+                    let state = DataTableState::from_database(&url, options)?;
+                    return Ok(state.lf);
+                }
+                #[cfg(not(feature = "database"))]
+                {
+                    let _ = url;
+                    return Err(color_eyre::eyre::eyre!(
+                        "Database URLs (postgres://, mysql://) are not supported in this build. Rebuild with default features."
+                    ));
+                }
+            }
+            source::InputSource::Stdin => {
+                return Err(color_eyre::eyre::eyre!(
+                    "Standard input is read into a temporary file first; this path should not be reached."
+                ));
+            }
+            source::InputSource::Local(_) => {}
+        }
+
+        if paths.len() > 1 {
+            progress(&format!("Scanning {} files", paths.len()), 20);
+        }
+        if paths.len() == 1 && options.hive {
+            let path_str = path.as_os_str().to_string_lossy();
+            let is_single_file = path.exists()
+                && path.is_file()
+                && !path_str.contains('*')
+                && !path_str.contains("**");
+            if !is_single_file {
+                if let Some(format) = DataTableState::hive_format(path, options.format) {
+                    progress("Scanning partitions", 20);
+                    // Only build LazyFrame here; schema + partition discovery happen in DoLoadSchema ("Caching schema")
+                    return DataTableState::scan_hive(path, format, options);
+                }
+                return Err(color_eyre::eyre::eyre!(
+                    "With --hive use a directory or a glob pattern of Parquet, CSV, NDJSON or Arrow IPC files (e.g. path/to/dir or path/**/*.csv)"
+                ));
+            }
+        }
+
+        if paths.len() > 1 && options.concat == ConcatMode::Diagonal {
+            let (lf, sources) = concat::concat_diagonal(paths, options)?;
+            extras.concat_sources = Some(sources);
+            return Ok(lf);
+        }
+        if paths.len() > 1 && options.source_column {
+            return concat::concat_vertical(paths, options);
+        }
+
+        let effective_format = options.format.or_else(|| FileFormat::from_path(path));
+
+        let mut lf = if paths.len() > 1 {
+            match effective_format {
+                Some(FileFormat::Parquet) => DataTableState::from_parquet_paths(
+                    paths,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Csv) => DataTableState::from_csv_paths(paths, options)?,
+                Some(FileFormat::Json) => DataTableState::from_json_paths(
+                    paths,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Jsonl) => DataTableState::from_json_lines_paths(
+                    paths,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Arrow) => DataTableState::from_ipc_paths(
+                    paths,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Avro) => DataTableState::from_avro_paths(
+                    paths,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Orc) => DataTableState::from_orc_paths(
+                    paths,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Tsv)
+                | Some(FileFormat::Psv)
+                | Some(FileFormat::Excel)
+                | Some(FileFormat::Sqlite)
+                | Some(FileFormat::Protobuf)
+                | None => {
+                    if !paths.is_empty() && !path.exists() {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::NotFound,
+                            format!("File not found: {}", path.display()),
+                        )
+                        .into());
+                    }
+                    return Err(color_eyre::eyre::eyre!(
+                        "Unsupported file type for multiple files (parquet, csv, json, jsonl, ndjson, arrow/ipc/feather, avro, orc only)"
+                    ));
+                }
+            }
+        } else {
+            // Formats read whole here take longest; the others are scanned lazily.
+            match effective_format {
+                Some(FileFormat::Excel) => progress("Reading workbook", 20),
+                Some(FileFormat::Sqlite) => progress("Reading database", 20),
+                Some(FileFormat::Protobuf) => progress("Decoding records", 20),
+                _ => {}
+            }
+            match effective_format {
+                Some(FileFormat::Parquet) => DataTableState::from_parquet(
+                    path,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Csv) => DataTableState::from_csv(path, options)?,
+                Some(FileFormat::Tsv) => DataTableState::from_delimited(path, b'\t', options)?,
+                Some(FileFormat::Psv) => DataTableState::from_delimited(path, b'|', options)?,
+                Some(FileFormat::Json) => DataTableState::from_json(
+                    path,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Jsonl) if options.skip_bad_rows => {
+                    DataTableState::from_json_lines_skipping_bad_rows(path, options)?
+                }
+                Some(FileFormat::Jsonl) => DataTableState::from_json_lines(
+                    path,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Arrow) => DataTableState::from_ipc(
+                    path,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Avro) => DataTableState::from_avro(
+                    path,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                Some(FileFormat::Excel) => DataTableState::from_excel(path, options)?,
+                Some(FileFormat::Sqlite) => DataTableState::from_sqlite(path, options)?,
+                Some(FileFormat::Protobuf) => DataTableState::from_protobuf(path, options)?,
+                Some(FileFormat::Orc) => DataTableState::from_orc(
+                    path,
+                    options.pages_lookahead,
+                    options.pages_lookback,
+                    options.max_buffered_rows,
+                    options.max_buffered_mb,
+                    options.row_numbers,
+                    options.row_start_index,
+                )?,
+                None => {
+                    if paths.len() == 1 && !path.exists() {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::NotFound,
+                            format!("File not found: {}", path.display()),
+                        )
+                        .into());
+                    }
+                    return Err(color_eyre::eyre::eyre!("Unsupported file type"));
+                }
+            }
+        };
+        extras.source_files = lf.take_source_files();
+        Ok(lf.lf)
+    }
+
+    /// Scan `paths` on a worker so the UI keeps drawing while a large input is read: the help
+    /// scrolls, the terminal can be resized and Esc cancels. `BackgroundScanReady` carries on
+    /// with the schema; `path` is the one shown for the load.
+    fn spawn_scan(
+        &mut self,
+        paths: Vec<PathBuf>,
+        path: Option<PathBuf>,
+        options: OpenOptions,
+    ) -> Option<AppEvent> {
+        let cloud = self.app_config.cloud.clone();
+        let slot = self.pending_scan_result.clone();
+        self.spawn_bg("Scanning input...", move |gen, tx| {
+            let mut extras = ScanExtras::default();
+            let progress = |phase: &str, progress_percent: u16| {
+                let _ = tx.send(AppEvent::BackgroundLoadProgress {
+                    generation: gen,
+                    phase: phase.to_string(),
+                    progress_percent,
+                });
+            };
+            let result =
+                Self::build_lazyframe_from_paths(&paths, &options, &cloud, &mut extras, &progress)
+                    .map(|lf| (lf, extras))
+                    .map_err(|e| {
+                        crate::error_display::user_message_from_report(
+                            &e,
+                            paths.first().map(|p| p.as_path()),
+                        )
+                    });
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some((gen, result));
+            }
+            let _ = tx.send(AppEvent::BackgroundScanReady {
+                generation: gen,
+                path,
+                options,
+            });
+        });
+        None
+    }
+
+    /// Stop the load in progress (Esc while loading). Work already running on a worker finishes
+    /// there, but its result is dropped; the data shown before, if any, stays. A cancelled first
+    /// load leaves an empty view to open another file from.
+    fn cancel_load(&mut self) {
+        self.task_generation = self.task_generation.wrapping_add(1);
+        self.loading_state = LoadingState::Idle;
+        self.busy = false;
+        self.drain_keys_on_next_loop = true;
+        self.status_message = None;
+        if let Ok(mut slot) = self.pending_scan_result.lock() {
+            *slot = None;
+        }
+        if self.data_table_state.is_none()
+            && !self.recover_workspace_load_error("Loading cancelled")
+        {
+            self.status_message = Some("Loading cancelled: o opens a file, q quits".to_string());
+        }
+    }
+
+    /// Set the appropriate help overlay visible (main, template, or analysis). No-op if already visible.
+    fn open_help_overlay(&mut self) {
+        let already = self.show_help
+            || (self.template_modal.active && self.template_modal.show_help)
+            || (self.analysis_modal.active && self.analysis_modal.show_help);
+        if already {
+            return;
+        }
+        if self.analysis_modal.active {
+            self.analysis_modal.show_help = true;
+        } else if self.template_modal.active {
+            self.template_modal.show_help = true;
+        } else {
+            self.show_help = true;
+        }
+    }
+
+    fn key(&mut self, event: &KeyEvent) -> Option<AppEvent> {
+        self.debug.on_key(event);
+
+        // F1 opens help first so no other branch (e.g. Editing) can consume it.
+        if event.code == KeyCode::F(1) {
+            self.open_help_overlay();
+            return None;
+        }
+
+        // Handle modals first - they have highest priority
+        // Confirmation modal (for overwrite)
+        if self.confirmation_modal.active {
+            match event.code {
+                KeyCode::Left | KeyCode::Char('h') => {
+                    self.confirmation_modal.focus_yes = true;
+                }
+                KeyCode::Right | KeyCode::Char('l') => {
+                    self.confirmation_modal.focus_yes = false;
+                }
+                KeyCode::Tab => {
+                    // Toggle between Yes and No
+                    self.confirmation_modal.focus_yes = !self.confirmation_modal.focus_yes;
+                }
+                KeyCode::Enter => {
+                    if self.confirmation_modal.focus_yes {
+                        // User confirmed overwrite: chart export first, then dataframe export
+                        if let Some((path, format, title, width, height)) =
+                            self.pending_chart_export.take()
+                        {
+                            self.confirmation_modal.hide();
+                            return Some(AppEvent::ChartExport(path, format, title, width, height));
+                        }
+                        if let Some((path, format, options)) = self.pending_export.take() {
+                            self.confirmation_modal.hide();
+                            return Some(AppEvent::Export(path, format, options));
+                        }
+                        #[cfg(any(feature = "http", feature = "cloud"))]
+                        if let Some(pending) = self.pending_download.take() {
+                            self.confirmation_modal.hide();
+                            if let LoadingState::Loading {
+                                file_path,
+                                file_size,
+                                ..
+                            } = &self.loading_state
+                            {
+                                self.loading_state = LoadingState::Loading {
+                                    file_path: file_path.clone(),
+                                    file_size: *file_size,
+                                    current_phase: "Downloading".to_string(),
+                                    progress_percent: 20,
+                                };
+                            }
+                            return Some(match pending {
+                                #[cfg(feature = "http")]
+                                PendingDownload::Http { url, options, .. } => {
+                                    AppEvent::DoDownloadHttp(url, options)
+                                }
+                                #[cfg(feature = "cloud")]
+                                PendingDownload::S3 { url, options, .. } => {
+                                    AppEvent::DoDownloadS3ToTemp(url, options)
+                                }
+                                #[cfg(feature = "cloud")]
+                                PendingDownload::Gcs { url, options, .. } => {
+                                    AppEvent::DoDownloadGcsToTemp(url, options)
+                                }
+                                #[cfg(feature = "cloud")]
+                                PendingDownload::Azure { url, options, .. } => {
+                                    AppEvent::DoDownloadAzureToTemp(url, options)
+                                }
+                            });
+                        }
+                    } else {
+                        // User cancelled: if chart export overwrite, reopen chart export modal with path pre-filled
+                        if let Some((path, format, _, _, _)) = self.pending_chart_export.take() {
+                            self.chart_export_modal.reopen_with_path(&path, format);
+                        }
+                        self.pending_export = None;
+                        #[cfg(any(feature = "http", feature = "cloud"))]
+                        if self.pending_download.take().is_some() {
+                            self.confirmation_modal.hide();
+                            return Some(AppEvent::Exit);
+                        }
+                        self.confirmation_modal.hide();
+                    }
+                }
+                KeyCode::Esc => {
+                    // Cancel: if chart export overwrite, reopen chart export modal with path pre-filled
+                    if let Some((path, format, _, _, _)) = self.pending_chart_export.take() {
+                        self.chart_export_modal.reopen_with_path(&path, format);
+                    }
+                    self.pending_export = None;
+                    #[cfg(any(feature = "http", feature = "cloud"))]
+                    if self.pending_download.take().is_some() {
+                        self.confirmation_modal.hide();
+                        return Some(AppEvent::Exit);
+                    }
+                    self.confirmation_modal.hide();
+                }
+                _ => {}
+            }
+            return None;
+        }
+        // Success modal
+        if self.success_modal.active {
+            match event.code {
+                KeyCode::Esc | KeyCode::Enter => {
+                    self.success_modal.hide();
+                }
+                _ => {}
+            }
+            return None;
+        }
+        // Error modal
+        if self.error_modal.active {
+            match event.code {
+                KeyCode::Esc | KeyCode::Enter => {
+                    self.error_modal.hide();
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        #[cfg(feature = "cloud")]
+        if self.s3_browser.is_some() {
+            return self.s3_browser_key(event);
+        }
+        if self.file_browser.is_some() {
+            return self.file_browser_key(event);
+        }
+        if self.table_picker.is_some() {
+            return self.table_picker_key(event);
+        }
+        if self.sheet_preview.is_some() {
+            return self.sheet_preview_key(event);
+        }
+        if self.csv_dialect.is_some() {
+            return self.csv_dialect_key(event);
+        }
+        if self.load_report.is_some() {
+            return self.load_report_key(event);
+        }
+        if self.schema_modal.is_some() {
+            return self.schema_modal_key(event);
+        }
+        if self.columns_modal.is_some() {
+            return self.columns_modal_key(event);
+        }
+        if self.scripts_modal.is_some() {
+            return self.scripts_modal_key(event);
+        }
+        if self.saved_queries_modal.is_some() {
+            return self.saved_queries_modal_key(event);
+        }
+        if self.command_palette.is_some() {
+            return self.command_palette_key(event);
+        }
+
+        // Aggregate peek popup: any key closes it; Esc/Enter/A only close, other keys also act.
+        if self.aggregate_peek.take().is_some()
+            && matches!(
+                event.code,
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('A')
+            )
+        {
+            return None;
+        }
+        // Value counts popup: scrolls with the navigation keys; Esc, Enter, q or F closes it.
+        if let Some(popup) = self.value_counts.as_mut() {
+            let page = popup.visible.max(1) as isize;
+            match event.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('F') => {
+                    self.value_counts = None
+                }
+                KeyCode::Down | KeyCode::Char('j') => popup.scroll_by(1),
+                KeyCode::Up | KeyCode::Char('k') => popup.scroll_by(-1),
+                KeyCode::PageDown => popup.scroll_by(page),
+                KeyCode::PageUp => popup.scroll_by(-page),
+                KeyCode::Home => popup.scroll = 0,
+                KeyCode::End => popup.scroll_by(isize::MAX),
+                KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Some(AppEvent::Exit);
+                }
+                _ => {}
+            }
+            return None;
+        }
+        // Data diff: the navigation keys move through the rows, Left / Right scroll the compared
+        // columns and f cycles the change shown; Esc or q closes it.
+        if let Some(view) = self.data_diff.as_mut() {
+            let page = view.visible.max(1) as isize;
+            match event.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.data_diff = None,
+                KeyCode::Down | KeyCode::Char('j') => view.select_by(1),
+                KeyCode::Up | KeyCode::Char('k') => view.select_by(-1),
+                KeyCode::PageDown => view.select_by(page),
+                KeyCode::PageUp => view.select_by(-page),
+                KeyCode::Home => view.select_by(isize::MIN),
+                KeyCode::End => view.select_by(isize::MAX),
+                KeyCode::Right | KeyCode::Char('l') => view.scroll_columns_right(),
+                KeyCode::Left | KeyCode::Char('h') => view.scroll_columns_left(),
+                KeyCode::Char('f') => view.cycle_filter(),
+                KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Some(AppEvent::Exit);
+                }
+                _ => {}
+            }
+            return None;
+        }
+        // Record view: scrolls with the navigation keys; Esc, Enter or q closes it.
+        if let Some(view) = self.record_view.as_mut() {
+            let page = view.visible.max(1) as isize;
+            match event.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => self.record_view = None,
+                KeyCode::Down | KeyCode::Char('j') => view.scroll_by(1),
+                KeyCode::Up | KeyCode::Char('k') => view.scroll_by(-1),
+                KeyCode::PageDown => view.scroll_by(page),
+                KeyCode::PageUp => view.scroll_by(-page),
+                KeyCode::Home => view.scroll = 0,
+                KeyCode::End => view.scroll_by(isize::MAX),
+                KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Some(AppEvent::Exit);
+                }
+                _ => {}
+            }
+            return None;
+        }
+        // Cell peek popup: same as above (Esc/Enter only close, other keys also act).
+        if self.cell_peek.take().is_some() && matches!(event.code, KeyCode::Esc | KeyCode::Enter) {
+            return None;
+        }
+        // `z` prefix from the main table: za shows the full cell value, zm / zc copy the visible
+        // page as Markdown / CSV, zr cycles the column ruler, anything else cancels.
+        if std::mem::take(&mut self.pending_z) {
+            return match event.code {
+                KeyCode::Char('a') => {
+                    self.open_cell_peek();
+                    None
+                }
+                KeyCode::Char('m') => self.spawn_copy_page(true),
+                KeyCode::Char('c') => self.spawn_copy_page(false),
+                KeyCode::Char('r') => {
+                    self.column_ruler = self.column_ruler.next();
+                    None
+                }
+                _ => None,
+            };
+        }
+        // `f` prefix from the main table: `=` `!` `>` `<` filter on the selected cell; any other
+        // key cancels the prefix and does what it normally does.
+        if self.pending_f && event.is_press() {
+            self.pending_f = false;
+            let operator = match event.code {
+                KeyCode::Char('=') => Some(FilterOperator::Eq),
+                KeyCode::Char('!') => Some(FilterOperator::NotEq),
+                KeyCode::Char('>') => Some(FilterOperator::Gt),
+                KeyCode::Char('<') => Some(FilterOperator::Lt),
+                _ => None,
+            };
+            if let Some(operator) = operator {
+                return self.quick_filter(operator);
+            }
+        }
+        // Format modal: Tab moves between fields, ←/→ change the selectors, Enter applies.
+        if self.format_modal.active {
+            match event.code {
+                KeyCode::Esc if event.is_press() => self.format_modal.close(),
+                KeyCode::Enter if event.is_press() => self.apply_format_modal(),
+                KeyCode::Tab if event.is_press() => self.format_modal.next_focus(),
+                KeyCode::BackTab if event.is_press() => self.format_modal.prev_focus(),
+                _ if !event.is_press() => {}
+                _ => match self.format_modal.focused_input() {
+                    Some(input) => {
+                        let _ = input.handle_key(event, None);
+                    }
+                    None => match event.code {
+                        KeyCode::Left | KeyCode::Char('h') => self.format_modal.cycle(-1),
+                        KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => {
+                            self.format_modal.cycle(1)
+                        }
+                        _ => {}
+                    },
+                },
+            }
+            return None;
+        }
+        // Sampling modal: same keys as the Format modal.
+        if self.sampling_modal.active {
+            match event.code {
+                KeyCode::Esc if event.is_press() => self.sampling_modal.close(),
+                KeyCode::Enter if event.is_press() => self.apply_sampling_modal(),
+                KeyCode::Tab if event.is_press() => self.sampling_modal.next_focus(),
+                KeyCode::BackTab if event.is_press() => self.sampling_modal.prev_focus(),
+                _ if !event.is_press() => {}
+                _ => match self.sampling_modal.focused_input() {
+                    Some(input) => {
+                        let _ = input.handle_key(event, None);
+                    }
+                    None => match event.code {
+                        KeyCode::Left | KeyCode::Char('h') => self.sampling_modal.cycle(-1),
+                        KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => {
+                            self.sampling_modal.cycle(1)
+                        }
+                        _ => {}
+                    },
+                },
+            }
+            return None;
+        }
+
+        // New Column modal: Tab switches between name and expression; the preview follows edits.
+        if self.computed_column_modal.active {
+            match event.code {
+                KeyCode::Esc if event.is_press() => self.computed_column_modal.close(),
+                KeyCode::Enter if event.is_press() => self.apply_computed_column_modal(),
+                KeyCode::Tab if event.is_press() => self.computed_column_modal.next_focus(),
+                KeyCode::BackTab if event.is_press() => self.computed_column_modal.prev_focus(),
+                _ if !event.is_press() => {}
+                _ => {
+                    let _ = self
+                        .computed_column_modal
+                        .focused_input()
+                        .handle_key(event, None);
+                    self.computed_column_modal.refresh_preview();
+                }
+            }
+            return None;
+        }
+
+        // Time Zone modal: ←/→ change the action, Tab moves to the zone; the preview follows edits.
+        if self.time_zone_modal.active {
+            match event.code {
+                KeyCode::Esc if event.is_press() => self.time_zone_modal.close(),
+                KeyCode::Enter if event.is_press() => self.apply_time_zone_modal(),
+                KeyCode::Tab if event.is_press() => self.time_zone_modal.next_focus(),
+                KeyCode::BackTab if event.is_press() => self.time_zone_modal.prev_focus(),
+                _ if !event.is_press() => {}
+                _ => match self.time_zone_modal.focused_input() {
+                    Some(input) => {
+                        let _ = input.handle_key(event, None);
+                    }
+                    None => match event.code {
+                        KeyCode::Left | KeyCode::Char('h') => self.time_zone_modal.cycle(-1),
+                        KeyCode::Right | KeyCode::Char('l') => self.time_zone_modal.cycle(1),
+                        _ => {}
+                    },
+                },
+            }
+            if self.time_zone_modal.active {
+                self.time_zone_modal.refresh_preview();
+            }
+            return None;
+        }
+
+        // Cast modal: ←/→ change the type or error handling, Tab moves between fields; the preview
+        // follows edits.
+        if self.cast_modal.active {
+            match event.code {
+                KeyCode::Esc if event.is_press() => self.cast_modal.close(),
+                KeyCode::Enter if event.is_press() => self.apply_cast_modal(),
+                KeyCode::Tab if event.is_press() => self.cast_modal.next_focus(),
+                KeyCode::BackTab if event.is_press() => self.cast_modal.prev_focus(),
+                _ if !event.is_press() => {}
+                _ => match self.cast_modal.focused_input() {
+                    Some(input) => {
+                        let _ = input.handle_key(event, None);
+                    }
+                    None => match event.code {
+                        KeyCode::Left | KeyCode::Char('h') => self.cast_modal.cycle(-1),
+                        KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => {
+                            self.cast_modal.cycle(1)
+                        }
+                        _ => {}
+                    },
+                },
+            }
+            if self.cast_modal.active {
+                self.cast_modal.refresh_preview();
+            }
+            return None;
+        }
+
+        // Rolling modal: Tab moves between fields, ←/→ change a selector, ↑/↓ move in the column
+        // list and Space checks a column; the preview follows each change.
+        if self.rolling_modal.active {
+            match event.code {
+                KeyCode::Esc if event.is_press() => self.rolling_modal.close(),
+                KeyCode::Enter if event.is_press() => self.apply_rolling_modal(),
+                KeyCode::Tab if event.is_press() => self.rolling_modal.next_focus(),
+                KeyCode::BackTab if event.is_press() => self.rolling_modal.prev_focus(),
+                _ if !event.is_press() => {}
+                _ => match self.rolling_modal.focused_input() {
+                    Some(input) => {
+                        let _ = input.handle_key(event, None);
+                    }
+                    None => match event.code {
+                        KeyCode::Left | KeyCode::Char('h') => self.rolling_modal.cycle(-1),
+                        KeyCode::Right | KeyCode::Char('l') => self.rolling_modal.cycle(1),
+                        KeyCode::Down | KeyCode::Char('j') => self.rolling_modal.move_cursor(1),
+                        KeyCode::Up | KeyCode::Char('k') => self.rolling_modal.move_cursor(-1),
+                        KeyCode::Char(' ') => self.rolling_modal.toggle(),
+                        _ => {}
+                    },
+                },
+            }
+            if self.rolling_modal.active {
+                self.rolling_modal.refresh_preview();
+            }
+            return None;
+        }
+
+        // Resample modal: Tab moves between fields, ←/→ change a selector or the aggregation of
+        // the column under the cursor, ↑/↓ move in the column list, Space keeps or drops it.
+        if self.resample_modal.active {
+            match event.code {
+                KeyCode::Esc if event.is_press() => self.resample_modal.close(),
+                KeyCode::Enter if event.is_press() => self.apply_resample_modal(),
+                KeyCode::Tab if event.is_press() => self.resample_modal.next_focus(),
+                KeyCode::BackTab if event.is_press() => self.resample_modal.prev_focus(),
+                _ if !event.is_press() => {}
+                _ => match self.resample_modal.focused_input() {
+                    Some(input) => {
+                        let _ = input.handle_key(event, None);
+                    }
+                    None => match event.code {
+                        KeyCode::Left | KeyCode::Char('h') => self.resample_modal.cycle(-1),
+                        KeyCode::Right | KeyCode::Char('l') => self.resample_modal.cycle(1),
+                        KeyCode::Down | KeyCode::Char('j') => self.resample_modal.move_cursor(1),
+                        KeyCode::Up | KeyCode::Char('k') => self.resample_modal.move_cursor(-1),
+                        KeyCode::Char(' ') => self.resample_modal.toggle(),
+                        _ => {}
+                    },
+                },
+            }
+            return None;
+        }
+
+        // GroupBy modal: Tab moves between the lists, ↑/↓ move in a list, Space toggles.
+        if self.group_by_modal.active {
+            match event.code {
+                KeyCode::Esc if event.is_press() => self.group_by_modal.close(),
+                KeyCode::Enter if event.is_press() => self.apply_group_by_modal(),
+                KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') if event.is_press() => {
+                    self.group_by_modal.next_focus()
+                }
+                KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') if event.is_press() => {
+                    self.group_by_modal.prev_focus()
+                }
+                KeyCode::Down | KeyCode::Char('j') if event.is_press() => {
+                    self.group_by_modal.move_cursor(1)
+                }
+                KeyCode::Up | KeyCode::Char('k') if event.is_press() => {
+                    self.group_by_modal.move_cursor(-1)
+                }
+                KeyCode::Char(' ') if event.is_press() => self.group_by_modal.toggle(),
+                _ => {}
+            }
+            return None;
+        }
+
+        // Main table: left/right scroll columns (before help/mode blocks so column scroll always works in Normal).
+        // No is_press()/is_release() check: some terminals do not report key kind correctly.
+        // Exclude template/analysis modals so they can handle Left/Right themselves.
+        let in_main_table = !(self.input_mode != InputMode::Normal
+            || self.show_help
+            || self.template_modal.active
+            || self.analysis_modal.active
+            || self.workspace_list_visible());
+        if in_main_table {
+            let did_scroll = match self.key_bindings.action(event) {
+                Some(keymap::Action::ScrollRight) => {
+                    if let Some(ref mut state) = self.data_table_state {
+                        state.scroll_right();
+                        if self.debug.enabled {
+                            self.debug.last_action = "scroll_right".to_string();
+                        }
+                        true
+                    } else {
+                        false
+                    }
+                }
+                Some(keymap::Action::ScrollLeft) => {
+                    if let Some(ref mut state) = self.data_table_state {
+                        state.scroll_left();
+                        if self.debug.enabled {
+                            self.debug.last_action = "scroll_left".to_string();
+                        }
+                        true
+                    } else {
+                        false
+                    }
+                }
+                _ => false,
+            };
+            if did_scroll {
+                return None;
+            }
+        }
+
+        if self.show_help
+            || (self.template_modal.active && self.template_modal.show_help)
+            || (self.analysis_modal.active && self.analysis_modal.show_help)
+        {
+            match event.code {
+                KeyCode::Esc => {
+                    if self.analysis_modal.active && self.analysis_modal.show_help {
+                        self.analysis_modal.show_help = false;
+                    } else if self.template_modal.active && self.template_modal.show_help {
+                        self.template_modal.show_help = false;
+                    } else {
+                        self.show_help = false;
+                    }
+                    self.help_scroll = 0;
+                }
+                KeyCode::Char('?') => {
+                    if self.analysis_modal.active && self.analysis_modal.show_help {
+                        self.analysis_modal.show_help = false;
+                    } else if self.template_modal.active && self.template_modal.show_help {
+                        self.template_modal.show_help = false;
+                    } else {
+                        self.show_help = false;
+                    }
+                    self.help_scroll = 0;
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.help_scroll = self.help_scroll.saturating_add(1);
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.help_scroll = self.help_scroll.saturating_sub(1);
+                }
+                KeyCode::PageDown => {
+                    self.help_scroll = self.help_scroll.saturating_add(10);
+                }
+                KeyCode::PageUp => {
+                    self.help_scroll = self.help_scroll.saturating_sub(10);
+                }
+                KeyCode::Home => {
+                    self.help_scroll = 0;
+                }
+                KeyCode::End => {
+                    // Will be set based on content height in render
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        if event.code == KeyCode::Char('?') {
+            let ctrl_help = event.modifiers.contains(KeyModifiers::CONTROL);
+            let in_text_input = match self.input_mode {
+                InputMode::Editing => true,
+                InputMode::Export => matches!(
+                    self.export_modal.focus,
+                    ExportFocus::PathInput
+                        | ExportFocus::CsvDelimiter
+                        | ExportFocus::XlsxSheetName
+                        | ExportFocus::SqliteTable
+                ),
+                InputMode::SortFilter => {
+                    let on_body = self.sort_filter_modal.focus == SortFilterFocus::Body;
+                    let filter_tab = self.sort_filter_modal.active_tab == SortFilterTab::Filter;
+                    on_body
+                        && filter_tab
+                        && self.sort_filter_modal.filter.focus == FilterFocus::Value
+                }
+                InputMode::PivotMelt => matches!(
+                    self.pivot_melt_modal.focus,
+                    PivotMeltFocus::PivotFilter
+                        | PivotMeltFocus::MeltFilter
+                        | PivotMeltFocus::MeltPattern
+                        | PivotMeltFocus::MeltVarName
+                        | PivotMeltFocus::MeltValName
+                ),
+                InputMode::Info | InputMode::Chart => false,
+                InputMode::Normal => {
+                    if self.template_modal.active
+                        && self.template_modal.mode != TemplateModalMode::List
+                    {
+                        matches!(
+                            self.template_modal.create_focus,
+                            CreateFocus::Name
+                                | CreateFocus::Description
+                                | CreateFocus::ExactPath
+                                | CreateFocus::RelativePath
+                                | CreateFocus::PathPattern
+                                | CreateFocus::FilenamePattern
+                        )
+                    } else {
+                        false
+                    }
+                }
+            };
+            // Ctrl-? always opens help; bare ? only when not in a text field
+            if ctrl_help || !in_text_input {
+                self.open_help_overlay();
+                return None;
+            }
+        }
+
+        if self.input_mode == InputMode::Normal && self.workspace_list_visible() {
+            if let Some(view) = self.workspace.as_mut().and_then(|w| w.schema_diff.as_mut()) {
+                match event.code {
+                    KeyCode::Char('q') | KeyCode::Char('Q') => return Some(AppEvent::Exit),
+                    KeyCode::Esc | KeyCode::Char('D') => {
+                        if let Some(ws) = self.workspace.as_mut() {
+                            ws.schema_diff = None;
+                        }
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => view.select_next(),
+                    KeyCode::Up | KeyCode::Char('k') => view.select_previous(),
+                    KeyCode::Right | KeyCode::Char('l') => view.scroll_files_right(),
+                    KeyCode::Left | KeyCode::Char('h') => view.scroll_files_left(),
+                    KeyCode::Char('f') => view.toggle_drift_only(),
+                    _ => {}
+                }
+                return None;
+            }
+            let has_tab = self.workspace.as_ref().is_some_and(|w| w.current.is_some());
+            match event.code {
+                KeyCode::Char('q') | KeyCode::Char('Q') => return Some(AppEvent::Exit),
+                KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Some(AppEvent::Exit);
+                }
+                KeyCode::Enter => return self.open_workspace_selection(),
+                KeyCode::Char('A') => return self.open_workspace_extension_group(),
+                KeyCode::Char('D') => return self.spawn_workspace_schema_diff(),
+                KeyCode::Esc | KeyCode::Char('w') if has_tab => {
+                    if let Some(ws) = self.workspace.as_mut() {
+                        ws.list_visible = false;
+                    }
+                }
+                _ => {
+                    if let Some(ws) = self.workspace.as_mut() {
+                        match event.code {
+                            KeyCode::Down | KeyCode::Char('j') => ws.select_next(),
+                            KeyCode::Up | KeyCode::Char('k') => ws.select_previous(),
+                            KeyCode::Home => ws.select_first(),
+                            KeyCode::End | KeyCode::Char('G') => ws.select_last(),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            return None;
+        }
+
+        if self.input_mode == InputMode::SortFilter
+            && self.sort_filter_modal.filter.date_range.is_some()
+        {
+            let filter = &mut self.sort_filter_modal.filter;
+            let range = filter.date_range.as_mut()?;
+            match event.code {
+                KeyCode::Esc => filter.date_range = None,
+                KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                    range.editing_end = !range.editing_end;
+                }
+                KeyCode::Char(c) => range.field_mut().push(c),
+                KeyCode::Backspace => {
+                    range.field_mut().pop();
+                }
+                KeyCode::Enter => filter.use_date_range(),
+                _ => {}
+            }
+            return None;
+        }
+        if self.input_mode == InputMode::SortFilter
+            && self.sort_filter_modal.filter.value_picker.is_some()
+        {
+            let filter = &mut self.sort_filter_modal.filter;
+            let picker = filter.value_picker.as_mut()?;
+            match event.code {
+                KeyCode::Esc => filter.value_picker = None,
+                KeyCode::Down | KeyCode::Char('j') => picker.move_by(1),
+                KeyCode::Up | KeyCode::Char('k') => picker.move_by(-1),
+                KeyCode::PageDown => picker.move_by(10),
+                KeyCode::PageUp => picker.move_by(-10),
+                KeyCode::Home => picker.move_by(isize::MIN),
+                KeyCode::End => picker.move_by(isize::MAX),
+                KeyCode::Char(' ') => picker.toggle(),
+                KeyCode::Enter => filter.pick_values(),
+                _ => {}
+            }
+            return None;
+        }
+
+        if self.input_mode == InputMode::SortFilter {
+            let on_tab_bar = self.sort_filter_modal.focus == SortFilterFocus::TabBar;
+            let on_body = self.sort_filter_modal.focus == SortFilterFocus::Body;
+            let on_apply = self.sort_filter_modal.focus == SortFilterFocus::Apply;
+            let on_cancel = self.sort_filter_modal.focus == SortFilterFocus::Cancel;
+            let on_clear = self.sort_filter_modal.focus == SortFilterFocus::Clear;
+            let sort_tab = self.sort_filter_modal.active_tab == SortFilterTab::Sort;
+            let filter_tab = self.sort_filter_modal.active_tab == SortFilterTab::Filter;
+
+            match event.code {
+                KeyCode::Esc => {
+                    for col in &mut self.sort_filter_modal.sort.columns {
+                        col.is_to_be_locked = false;
+                    }
+                    self.sort_filter_modal.sort.has_unapplied_changes = false;
+                    self.sort_filter_modal.close();
+                    self.input_mode = InputMode::Normal;
+                }
+                KeyCode::Tab => self.sort_filter_modal.next_focus(),
+                KeyCode::BackTab => self.sort_filter_modal.prev_focus(),
+                KeyCode::Left | KeyCode::Char('h') if on_tab_bar => {
+                    self.sort_filter_modal.switch_tab();
+                }
+                KeyCode::Right | KeyCode::Char('l') if on_tab_bar => {
+                    self.sort_filter_modal.switch_tab();
+                }
+                KeyCode::Enter if event.modifiers.contains(KeyModifiers::CONTROL) && sort_tab => {
+                    let columns = self.sort_filter_modal.sort.get_sorted_columns();
+                    let column_order = self.sort_filter_modal.sort.get_column_order();
+                    let locked_count = self.sort_filter_modal.sort.get_locked_columns_count();
+                    let ascending = self.sort_filter_modal.sort.ascending;
+                    self.sort_filter_modal.sort.has_unapplied_changes = false;
+                    self.sort_filter_modal.close();
+                    self.input_mode = InputMode::Normal;
+                    let _ = self.send_event(AppEvent::ColumnOrder(column_order, locked_count));
+                    return Some(AppEvent::Sort(columns, ascending));
+                }
+                KeyCode::Enter if on_apply => {
+                    if sort_tab {
+                        let columns = self.sort_filter_modal.sort.get_sorted_columns();
+                        let column_order = self.sort_filter_modal.sort.get_column_order();
+                        let locked_count = self.sort_filter_modal.sort.get_locked_columns_count();
+                        let ascending = self.sort_filter_modal.sort.ascending;
+                        self.sort_filter_modal.sort.has_unapplied_changes = false;
+                        self.sort_filter_modal.close();
+                        self.input_mode = InputMode::Normal;
+                        let _ = self.send_event(AppEvent::ColumnOrder(column_order, locked_count));
+                        return Some(AppEvent::Sort(columns, ascending));
+                    } else {
+                        let statements = self.sort_filter_modal.filter.statements.clone();
+                        self.sort_filter_modal.close();
+                        self.input_mode = InputMode::Normal;
+                        return Some(AppEvent::Filter(statements));
+                    }
+                }
+                KeyCode::Enter if on_cancel => {
+                    for col in &mut self.sort_filter_modal.sort.columns {
+                        col.is_to_be_locked = false;
+                    }
+                    self.sort_filter_modal.sort.has_unapplied_changes = false;
+                    self.sort_filter_modal.close();
+                    self.input_mode = InputMode::Normal;
+                }
+                KeyCode::Enter if on_clear => {
+                    if sort_tab {
+                        self.sort_filter_modal.sort.clear_selection();
+                        let columns = self.sort_filter_modal.sort.get_sorted_columns();
+                        let column_order = self.sort_filter_modal.sort.get_column_order();
+                        let locked_count = self.sort_filter_modal.sort.get_locked_columns_count();
+                        let ascending = self.sort_filter_modal.sort.ascending;
+                        self.sort_filter_modal.sort.has_unapplied_changes = false;
+                        self.sort_filter_modal.close();
+                        self.input_mode = InputMode::Normal;
+                        let _ = self.send_event(AppEvent::ColumnOrder(column_order, locked_count));
+                        return Some(AppEvent::Sort(columns, ascending));
+                    } else {
+                        self.sort_filter_modal.filter.statements.clear();
+                        self.sort_filter_modal.filter.list_state.select(None);
+                        self.sort_filter_modal.close();
+                        self.input_mode = InputMode::Normal;
+                        return Some(AppEvent::Filter(vec![]));
+                    }
+                }
+                KeyCode::Char(' ')
+                    if on_body
+                        && sort_tab
+                        && self.sort_filter_modal.sort.focus == SortFocus::ColumnList =>
+                {
+                    self.sort_filter_modal.sort.toggle_selection();
+                }
+                KeyCode::Char(' ')
+                    if on_body
+                        && sort_tab
+                        && self.sort_filter_modal.sort.focus == SortFocus::Order =>
+                {
+                    self.sort_filter_modal.sort.ascending = !self.sort_filter_modal.sort.ascending;
+                    self.sort_filter_modal.sort.has_unapplied_changes = true;
+                }
+                KeyCode::Char(' ') if on_apply && sort_tab => {
+                    let columns = self.sort_filter_modal.sort.get_sorted_columns();
+                    let column_order = self.sort_filter_modal.sort.get_column_order();
+                    let locked_count = self.sort_filter_modal.sort.get_locked_columns_count();
+                    let ascending = self.sort_filter_modal.sort.ascending;
+                    self.sort_filter_modal.sort.has_unapplied_changes = false;
+                    let _ = self.send_event(AppEvent::ColumnOrder(column_order, locked_count));
+                    return Some(AppEvent::Sort(columns, ascending));
+                }
+                KeyCode::Enter if on_body && filter_tab => {
+                    match self.sort_filter_modal.filter.focus {
+                        FilterFocus::Value => return self.spawn_filter_values(),
+                        FilterFocus::Add => {
+                            self.sort_filter_modal.filter.add_statement();
+                        }
+                        FilterFocus::Statements => {
+                            let m = &mut self.sort_filter_modal.filter;
+                            if let Some(idx) = m.list_state.selected() {
+                                if idx < m.statements.len() {
+                                    m.statements.remove(idx);
+                                    if m.statements.is_empty() {
+                                        m.list_state.select(None);
+                                        m.focus = FilterFocus::Column;
+                                    } else {
+                                        m.list_state
+                                            .select(Some(m.statements.len().saturating_sub(1)));
+                                    }
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                KeyCode::Enter if on_body && sort_tab => match self.sort_filter_modal.sort.focus {
+                    SortFocus::Filter => {
+                        self.sort_filter_modal.sort.focus = SortFocus::ColumnList;
+                    }
+                    SortFocus::ColumnList => {
+                        self.sort_filter_modal.sort.toggle_selection();
+                        let columns = self.sort_filter_modal.sort.get_sorted_columns();
+                        let column_order = self.sort_filter_modal.sort.get_column_order();
+                        let locked_count = self.sort_filter_modal.sort.get_locked_columns_count();
+                        let ascending = self.sort_filter_modal.sort.ascending;
+                        self.sort_filter_modal.sort.has_unapplied_changes = false;
+                        let _ = self.send_event(AppEvent::ColumnOrder(column_order, locked_count));
+                        return Some(AppEvent::Sort(columns, ascending));
+                    }
+                    SortFocus::Order => {
+                        self.sort_filter_modal.sort.ascending =
+                            !self.sort_filter_modal.sort.ascending;
+                        self.sort_filter_modal.sort.has_unapplied_changes = true;
+                    }
+                    _ => {}
+                },
+                KeyCode::Left
+                | KeyCode::Right
+                | KeyCode::Char('h')
+                | KeyCode::Char('l')
+                | KeyCode::Up
+                | KeyCode::Down
+                | KeyCode::Char('j')
+                | KeyCode::Char('k')
+                    if on_body
+                        && sort_tab
+                        && self.sort_filter_modal.sort.focus == SortFocus::Order =>
+                {
+                    let s = &mut self.sort_filter_modal.sort;
+                    match event.code {
+                        KeyCode::Left | KeyCode::Char('h') | KeyCode::Up | KeyCode::Char('k') => {
+                            s.ascending = true;
+                        }
+                        KeyCode::Right
+                        | KeyCode::Char('l')
+                        | KeyCode::Down
+                        | KeyCode::Char('j') => {
+                            s.ascending = false;
+                        }
+                        _ => {}
+                    }
+                    s.has_unapplied_changes = true;
+                }
+                KeyCode::Down
+                    if on_body
+                        && filter_tab
                         && self.sort_filter_modal.filter.focus == FilterFocus::Statements =>
                 {
                     let m = &mut self.sort_filter_modal.filter;
@@ -3227,17 +7645,11 @@ impl App {
                 KeyCode::Up | KeyCode::Char('k') => {
                     match self.export_modal.focus {
                         ExportFocus::FormatSelector => {
-                            // Cycle through formats
-                            let current_idx = ExportFormat::ALL
-                                .iter()
-                                .position(|&f| f == self.export_modal.selected_format)
-                                .unwrap_or(0);
-                            let prev_idx = if current_idx == 0 {
-                                ExportFormat::ALL.len() - 1
-                            } else {
-                                current_idx - 1
-                            };
-                            self.export_modal.selected_format = ExportFormat::ALL[prev_idx];
+                            self.export_modal.cycle_format(-1);
+                        }
+                        ExportFocus::ScopeSelector => self.export_modal.cycle_scope(-1),
+                        ExportFocus::ParquetPartitions => {
+                            self.export_modal.move_partition_cursor(-1)
                         }
                         ExportFocus::PathInput => {
                             // Pass to text input widget (for history navigation)
@@ -3249,6 +7661,14 @@ impl App {
                                 .csv_delimiter_input
                                 .handle_key(event, None);
                         }
+                        ExportFocus::XlsxSheetName => {
+                            self.export_modal
+                                .xlsx_sheet_name_input
+                                .handle_key(event, None);
+                        }
+                        ExportFocus::SqliteTable => {
+                            self.export_modal.sqlite_table_input.handle_key(event, None);
+                        }
                         ExportFocus::CsvCompression
                         | ExportFocus::JsonCompression
                         | ExportFocus::NdjsonCompression => {
@@ -3263,13 +7683,11 @@ impl App {
                 KeyCode::Down | KeyCode::Char('j') => {
                     match self.export_modal.focus {
                         ExportFocus::FormatSelector => {
-                            // Cycle through formats
-                            let current_idx = ExportFormat::ALL
-                                .iter()
-                                .position(|&f| f == self.export_modal.selected_format)
-                                .unwrap_or(0);
-                            let next_idx = (current_idx + 1) % ExportFormat::ALL.len();
-                            self.export_modal.selected_format = ExportFormat::ALL[next_idx];
+                            self.export_modal.cycle_format(1);
+                        }
+                        ExportFocus::ScopeSelector => self.export_modal.cycle_scope(1),
+                        ExportFocus::ParquetPartitions => {
+                            self.export_modal.move_partition_cursor(1)
                         }
                         ExportFocus::PathInput => {
                             // Pass to text input widget (for history navigation)
@@ -3281,6 +7699,14 @@ impl App {
                                 .csv_delimiter_input
                                 .handle_key(event, None);
                         }
+                        ExportFocus::XlsxSheetName => {
+                            self.export_modal
+                                .xlsx_sheet_name_input
+                                .handle_key(event, None);
+                        }
+                        ExportFocus::SqliteTable => {
+                            self.export_modal.sqlite_table_input.handle_key(event, None);
+                        }
                         ExportFocus::CsvCompression
                         | ExportFocus::JsonCompression
                         | ExportFocus::NdjsonCompression => {
@@ -3302,8 +7728,16 @@ impl App {
                                 .csv_delimiter_input
                                 .handle_key(event, None);
                         }
-                        ExportFocus::FormatSelector => {
-                            // Don't change focus in format selector
+                        ExportFocus::XlsxSheetName => {
+                            self.export_modal
+                                .xlsx_sheet_name_input
+                                .handle_key(event, None);
+                        }
+                        ExportFocus::SqliteTable => {
+                            self.export_modal.sqlite_table_input.handle_key(event, None);
+                        }
+                        ExportFocus::FormatSelector | ExportFocus::ScopeSelector => {
+                            // Don't change focus in format or scope selector
                         }
                         ExportFocus::CsvCompression
                         | ExportFocus::JsonCompression
@@ -3311,6 +7745,7 @@ impl App {
                             // Move to previous compression option
                             self.export_modal.cycle_compression_backward();
                         }
+                        ExportFocus::SqliteIfExists => self.export_modal.cycle_if_exists(-1),
                         _ => self.export_modal.prev_focus(),
                     }
                 }
@@ -3324,8 +7759,16 @@ impl App {
                                 .csv_delimiter_input
                                 .handle_key(event, None);
                         }
-                        ExportFocus::FormatSelector => {
-                            // Don't change focus in format selector
+                        ExportFocus::XlsxSheetName => {
+                            self.export_modal
+                                .xlsx_sheet_name_input
+                                .handle_key(event, None);
+                        }
+                        ExportFocus::SqliteTable => {
+                            self.export_modal.sqlite_table_input.handle_key(event, None);
+                        }
+                        ExportFocus::FormatSelector | ExportFocus::ScopeSelector => {
+                            // Don't change focus in format or scope selector
                         }
                         ExportFocus::CsvCompression
                         | ExportFocus::JsonCompression
@@ -3333,6 +7776,7 @@ impl App {
                             // Move to next compression option
                             self.export_modal.cycle_compression();
                         }
+                        ExportFocus::SqliteIfExists => self.export_modal.cycle_if_exists(1),
                         _ => self.export_modal.next_focus(),
                     }
                 }
@@ -3351,11 +7795,18 @@ impl App {
                                     ExportFormat::Ndjson => self.export_modal.ndjson_compression,
                                     ExportFormat::Parquet
                                     | ExportFormat::Ipc
-                                    | ExportFormat::Avro => None,
+                                    | ExportFormat::Avro
+                                    | ExportFormat::Xlsx
+                                    | ExportFormat::Sqlite
+                                    | ExportFormat::Markdown => None,
+                                };
+                                // Ensure file extension is present (including compression extension if needed);
+                                // a partitioned export is a directory
+                                let path_with_ext = if self.export_modal.is_partitioned() {
+                                    path.clone()
+                                } else {
+                                    Self::ensure_file_extension(&path, format, compression)
                                 };
-                                // Ensure file extension is present (including compression extension if needed)
-                                let path_with_ext =
-                                    Self::ensure_file_extension(&path, format, compression);
                                 // Update the path input to show the extension
                                 if path_with_ext != path {
                                     self.export_modal
@@ -3377,9 +7828,32 @@ impl App {
                                     json_compression: self.export_modal.json_compression,
                                     ndjson_compression: self.export_modal.ndjson_compression,
                                     parquet_compression: None,
+                                    parquet_partition_by: self
+                                        .export_modal
+                                        .parquet_partition_by
+                                        .clone(),
+                                    row_range: self.export_modal.row_range,
+                                    scope: self.export_modal.scope,
+                                    xlsx_sheet_name: self
+                                        .export_modal
+                                        .xlsx_sheet_name_input
+                                        .value
+                                        .clone(),
+                                    xlsx_autofit: self.export_modal.xlsx_autofit,
+                                    sqlite_table: self
+                                        .export_modal
+                                        .sqlite_table_input
+                                        .value
+                                        .clone(),
+                                    sqlite_if_exists: self.export_modal.sqlite_if_exists,
                                 };
-                                // Check if file exists and show confirmation
-                                if path.exists() {
+                                // Check if file exists and show confirmation (a database
+                                // gains a table instead; the if-exists option covers that, and
+                                // a partitioned export needs a new or empty directory)
+                                if path.exists()
+                                    && format != ExportFormat::Sqlite
+                                    && options.parquet_partition_by.is_empty()
+                                {
                                     let path_display = path.display().to_string();
                                     self.pending_export = Some((path, format, options));
                                     self.confirmation_modal.show(format!(
@@ -3406,13 +7880,20 @@ impl App {
                                 ExportFormat::Csv => self.export_modal.csv_compression,
                                 ExportFormat::Json => self.export_modal.json_compression,
                                 ExportFormat::Ndjson => self.export_modal.ndjson_compression,
-                                ExportFormat::Parquet | ExportFormat::Ipc | ExportFormat::Avro => {
-                                    None
-                                }
+                                ExportFormat::Parquet
+                                | ExportFormat::Ipc
+                                | ExportFormat::Avro
+                                | ExportFormat::Xlsx
+                                | ExportFormat::Sqlite
+                                | ExportFormat::Markdown => None,
+                            };
+                            // Ensure file extension is present (including compression extension if needed);
+                            // a partitioned export is a directory
+                            let path_with_ext = if self.export_modal.is_partitioned() {
+                                path.clone()
+                            } else {
+                                Self::ensure_file_extension(&path, format, compression)
                             };
-                            // Ensure file extension is present (including compression extension if needed)
-                            let path_with_ext =
-                                Self::ensure_file_extension(&path, format, compression);
                             // Update the path input to show the extension
                             if path_with_ext != path {
                                 self.export_modal
@@ -3434,9 +7915,28 @@ impl App {
                                 json_compression: self.export_modal.json_compression,
                                 ndjson_compression: self.export_modal.ndjson_compression,
                                 parquet_compression: None,
+                                parquet_partition_by: self
+                                    .export_modal
+                                    .parquet_partition_by
+                                    .clone(),
+                                row_range: self.export_modal.row_range,
+                                scope: self.export_modal.scope,
+                                xlsx_sheet_name: self
+                                    .export_modal
+                                    .xlsx_sheet_name_input
+                                    .value
+                                    .clone(),
+                                xlsx_autofit: self.export_modal.xlsx_autofit,
+                                sqlite_table: self.export_modal.sqlite_table_input.value.clone(),
+                                sqlite_if_exists: self.export_modal.sqlite_if_exists,
                             };
-                            // Check if file exists and show confirmation
-                            if path.exists() {
+                            // Check if file exists and show confirmation (a database
+                            // gains a table instead; the if-exists option covers that, and
+                            // a partitioned export needs a new or empty directory)
+                            if path.exists()
+                                && format != ExportFormat::Sqlite
+                                && options.parquet_partition_by.is_empty()
+                            {
                                 let path_display = path.display().to_string();
                                 self.pending_export = Some((path, format, options));
                                 self.confirmation_modal.show(format!(
@@ -3460,6 +7960,11 @@ impl App {
                             self.export_modal.csv_include_header =
                                 !self.export_modal.csv_include_header;
                         }
+                        ExportFocus::XlsxAutofit => {
+                            self.export_modal.xlsx_autofit = !self.export_modal.xlsx_autofit;
+                        }
+                        ExportFocus::SqliteIfExists => self.export_modal.cycle_if_exists(1),
+                        ExportFocus::ParquetPartitions => self.export_modal.toggle_partition(),
                         ExportFocus::CsvCompression
                         | ExportFocus::JsonCompression
                         | ExportFocus::NdjsonCompression => {
@@ -3482,11 +7987,24 @@ impl App {
                                 .csv_delimiter_input
                                 .handle_key(event, None);
                         }
+                        ExportFocus::XlsxSheetName => {
+                            self.export_modal
+                                .xlsx_sheet_name_input
+                                .handle_key(event, None);
+                        }
+                        ExportFocus::SqliteTable => {
+                            self.export_modal.sqlite_table_input.handle_key(event, None);
+                        }
                         ExportFocus::CsvIncludeHeader => {
                             // Toggle checkbox
                             self.export_modal.csv_include_header =
                                 !self.export_modal.csv_include_header;
                         }
+                        ExportFocus::XlsxAutofit => {
+                            self.export_modal.xlsx_autofit = !self.export_modal.xlsx_autofit;
+                        }
+                        ExportFocus::SqliteIfExists => self.export_modal.cycle_if_exists(1),
+                        ExportFocus::ParquetPartitions => self.export_modal.toggle_partition(),
                         _ => {}
                     }
                 }
@@ -3504,6 +8022,14 @@ impl App {
                                 .csv_delimiter_input
                                 .handle_key(event, None);
                         }
+                        ExportFocus::XlsxSheetName => {
+                            self.export_modal
+                                .xlsx_sheet_name_input
+                                .handle_key(event, None);
+                        }
+                        ExportFocus::SqliteTable => {
+                            self.export_modal.sqlite_table_input.handle_key(event, None);
+                        }
                         ExportFocus::FormatSelector => {
                             // Don't input text in format selector
                         }
@@ -3925,6 +8451,9 @@ impl App {
                     self.info_modal.close();
                     self.input_mode = InputMode::Normal;
                 }
+                KeyCode::Char('p') if event.is_press() => {
+                    return self.cycle_load_profile();
+                }
                 KeyCode::Tab if event.is_press() && schema_tab => {
                     self.info_modal.next_focus();
                 }
@@ -3962,148 +8491,43 @@ impl App {
 
         if self.input_mode == InputMode::Chart {
             // Chart export modal (sub-dialog within Chart mode)
-            if self.chart_export_modal.active {
+            if self.chart_labels_modal.active {
                 match event.code {
                     KeyCode::Esc if event.is_press() => {
-                        self.chart_export_modal.close();
+                        self.chart_labels_modal.close();
+                    }
+                    KeyCode::Enter if event.is_press() => {
+                        self.chart_modal.labels = self.chart_labels_modal.labels();
+                        self.chart_labels_modal.close();
                     }
                     KeyCode::Tab if event.is_press() => {
-                        self.chart_export_modal.next_focus();
+                        self.chart_labels_modal.next_focus();
                     }
                     KeyCode::BackTab if event.is_press() => {
-                        self.chart_export_modal.prev_focus();
-                    }
-                    KeyCode::Up | KeyCode::Char('k')
-                        if event.is_press()
-                            && self.chart_export_modal.focus
-                                == ChartExportFocus::FormatSelector =>
-                    {
-                        let idx = ChartExportFormat::ALL
-                            .iter()
-                            .position(|&f| f == self.chart_export_modal.selected_format)
-                            .unwrap_or(0);
-                        let prev = if idx == 0 {
-                            ChartExportFormat::ALL.len() - 1
-                        } else {
-                            idx - 1
-                        };
-                        self.chart_export_modal.selected_format = ChartExportFormat::ALL[prev];
+                        self.chart_labels_modal.prev_focus();
                     }
-                    KeyCode::Down | KeyCode::Char('j')
-                        if event.is_press()
-                            && self.chart_export_modal.focus
-                                == ChartExportFocus::FormatSelector =>
-                    {
-                        let idx = ChartExportFormat::ALL
-                            .iter()
-                            .position(|&f| f == self.chart_export_modal.selected_format)
-                            .unwrap_or(0);
-                        let next = (idx + 1) % ChartExportFormat::ALL.len();
-                        self.chart_export_modal.selected_format = ChartExportFormat::ALL[next];
-                    }
-                    KeyCode::Left | KeyCode::Char('h')
-                        if event.is_press()
-                            && self.chart_export_modal.focus
-                                == ChartExportFocus::FormatSelector =>
+                    KeyCode::Left | KeyCode::Right
+                        if event.is_press() && !self.chart_labels_modal.is_text_input_focused() =>
                     {
-                        let idx = ChartExportFormat::ALL
-                            .iter()
-                            .position(|&f| f == self.chart_export_modal.selected_format)
-                            .unwrap_or(0);
-                        let prev = if idx == 0 {
-                            ChartExportFormat::ALL.len() - 1
-                        } else {
-                            idx - 1
-                        };
-                        self.chart_export_modal.selected_format = ChartExportFormat::ALL[prev];
+                        let delta = if event.code == KeyCode::Left { -1 } else { 1 };
+                        self.chart_labels_modal.cycle_tick_format(delta);
                     }
-                    KeyCode::Right | KeyCode::Char('l')
-                        if event.is_press()
-                            && self.chart_export_modal.focus
-                                == ChartExportFocus::FormatSelector =>
-                    {
-                        let idx = ChartExportFormat::ALL
-                            .iter()
-                            .position(|&f| f == self.chart_export_modal.selected_format)
-                            .unwrap_or(0);
-                        let next = (idx + 1) % ChartExportFormat::ALL.len();
-                        self.chart_export_modal.selected_format = ChartExportFormat::ALL[next];
-                    }
-                    KeyCode::Enter if event.is_press() => match self.chart_export_modal.focus {
-                        ChartExportFocus::PathInput | ChartExportFocus::ExportButton => {
-                            let path_str = self.chart_export_modal.path_input.value.trim();
-                            if !path_str.is_empty() {
-                                let title =
-                                    self.chart_export_modal.title_input.value.trim().to_string();
-                                let (width, height) = self.chart_export_modal.export_dimensions();
-                                let mut path = PathBuf::from(path_str);
-                                let format = self.chart_export_modal.selected_format;
-                                // Only add default extension when user did not provide one
-                                if path.extension().is_none() {
-                                    path.set_extension(format.extension());
-                                }
-                                let path_display = path.display().to_string();
-                                if path.exists() {
-                                    self.pending_chart_export =
-                                        Some((path, format, title, width, height));
-                                    self.chart_export_modal.close();
-                                    self.confirmation_modal.show(format!(
-                                            "File already exists:\n{}\n\nDo you wish to overwrite this file?",
-                                            path_display
-                                        ));
-                                } else {
-                                    self.chart_export_modal.close();
-                                    return Some(AppEvent::ChartExport(
-                                        path, format, title, width, height,
-                                    ));
-                                }
-                            }
-                        }
-                        ChartExportFocus::CancelButton => {
-                            self.chart_export_modal.close();
-                        }
-                        _ => {}
-                    },
                     _ => {
                         if event.is_press() {
-                            if self.chart_export_modal.focus == ChartExportFocus::TitleInput {
-                                let _ = self.chart_export_modal.title_input.handle_key(event, None);
-                            } else if self.chart_export_modal.focus == ChartExportFocus::PathInput {
-                                let _ = self.chart_export_modal.path_input.handle_key(event, None);
-                            } else if self.chart_export_modal.focus == ChartExportFocus::WidthInput
-                            {
-                                let allow = match event.code {
-                                    KeyCode::Char(c) if c.is_ascii_digit() => true,
-                                    KeyCode::Backspace
-                                    | KeyCode::Delete
-                                    | KeyCode::Left
-                                    | KeyCode::Right
-                                    | KeyCode::Home
-                                    | KeyCode::End => true,
-                                    _ => false,
-                                };
-                                if allow {
-                                    let _ =
-                                        self.chart_export_modal.width_input.handle_key(event, None);
+                            let input = match self.chart_labels_modal.focus {
+                                ChartLabelsFocus::TitleInput => {
+                                    Some(&mut self.chart_labels_modal.title_input)
                                 }
-                            } else if self.chart_export_modal.focus == ChartExportFocus::HeightInput
-                            {
-                                let allow = match event.code {
-                                    KeyCode::Char(c) if c.is_ascii_digit() => true,
-                                    KeyCode::Backspace
-                                    | KeyCode::Delete
-                                    | KeyCode::Left
-                                    | KeyCode::Right
-                                    | KeyCode::Home
-                                    | KeyCode::End => true,
-                                    _ => false,
-                                };
-                                if allow {
-                                    let _ = self
-                                        .chart_export_modal
-                                        .height_input
-                                        .handle_key(event, None);
+                                ChartLabelsFocus::XTitleInput => {
+                                    Some(&mut self.chart_labels_modal.x_title_input)
+                                }
+                                ChartLabelsFocus::YTitleInput => {
+                                    Some(&mut self.chart_labels_modal.y_title_input)
                                 }
+                                ChartLabelsFocus::XTicks | ChartLabelsFocus::YTicks => None,
+                            };
+                            if let Some(input) = input {
+                                let _ = input.handle_key(event, None);
                             }
                         }
                     }
@@ -4111,6 +8535,10 @@ impl App {
                 return None;
             }
 
+            if self.chart_export_modal.active {
+                return self.chart_export_modal_key(event);
+            }
+
             match event.code {
                 KeyCode::Char('e')
                     if event.is_press() && !self.chart_modal.is_text_input_focused() =>
@@ -4119,8 +8547,28 @@ impl App {
                     if self.data_table_state.is_some() && self.chart_modal.can_export() {
                         self.chart_export_modal
                             .open(&self.theme, self.history_limit);
+                        self.chart_export_modal
+                            .title_input
+                            .set_value(self.chart_modal.labels.title.clone());
                     }
                 }
+                KeyCode::Char('t')
+                    if event.is_press() && !self.chart_modal.is_text_input_focused() =>
+                {
+                    self.chart_labels_modal
+                        .open(&self.chart_modal.labels, &self.theme);
+                }
+                // Each tab keeps its own chart; switching tabs leaves this one open
+                KeyCode::Char('[') | KeyCode::Char(']')
+                    if event.is_press() && !self.chart_modal.is_text_input_focused() =>
+                {
+                    let offset = if event.code == KeyCode::Char(']') {
+                        1
+                    } else {
+                        -1
+                    };
+                    return self.cycle_workspace_tab(offset);
+                }
                 // q/Q do nothing in chart view (no exit)
                 KeyCode::Char('?') if event.is_press() => {
                     self.show_help = true;
@@ -4141,6 +8589,18 @@ impl App {
                         ChartFocus::YStartsAtZero => self.chart_modal.toggle_y_starts_at_zero(),
                         ChartFocus::LogScale => self.chart_modal.toggle_log_scale(),
                         ChartFocus::ShowLegend => self.chart_modal.toggle_show_legend(),
+                        ChartFocus::GroupBy => self.chart_modal.cycle_group_column(1),
+                        ChartFocus::RightAxis => self.chart_modal.cycle_right_axis(1),
+                        ChartFocus::BandMode => self.chart_modal.cycle_band_mode(1),
+                        ChartFocus::BandColumn => self.chart_modal.cycle_band_column(1),
+                        ChartFocus::BandUpper => self.chart_modal.cycle_band_upper(1),
+                        ChartFocus::BandStyle => self.chart_modal.toggle_band_style(),
+                        ChartFocus::CategoryColumn => self.chart_modal.cycle_category_column(1),
+                        ChartFocus::CategoryAgg => self.chart_modal.cycle_category_agg(1),
+                        ChartFocus::CategoryValue => self.chart_modal.cycle_category_value(1),
+                        ChartFocus::ScatterX => self.chart_modal.cycle_scatter_x_column(1),
+                        ChartFocus::ScatterY => self.chart_modal.cycle_scatter_y_column(1),
+                        ChartFocus::ScatterTrend => self.chart_modal.toggle_scatter_trend(),
                         ChartFocus::XList => self.chart_modal.x_list_toggle(),
                         ChartFocus::YList => self.chart_modal.y_list_toggle(),
                         ChartFocus::ChartType => self.chart_modal.next_chart_type(),
@@ -4149,6 +8609,10 @@ impl App {
                         ChartFocus::KdeList => self.chart_modal.kde_list_toggle(),
                         ChartFocus::HeatmapXList => self.chart_modal.heatmap_x_list_toggle(),
                         ChartFocus::HeatmapYList => self.chart_modal.heatmap_y_list_toggle(),
+                        ChartFocus::Plot => {
+                            let series = self.chart_cache.plot_series(&self.chart_modal);
+                            self.chart_modal.toggle_crosshair(&series);
+                        }
                         _ => {}
                     }
                 }
@@ -4158,10 +8622,12 @@ impl App {
                     match self.chart_modal.focus {
                         ChartFocus::HistBins => self.chart_modal.adjust_hist_bins(1),
                         ChartFocus::HeatmapBins => self.chart_modal.adjust_heatmap_bins(1),
+                        ChartFocus::CategoryTop => self.chart_modal.adjust_category_top(1),
                         ChartFocus::KdeBandwidth => self
                             .chart_modal
                             .adjust_kde_bandwidth_factor(chart_modal::KDE_BANDWIDTH_STEP),
                         ChartFocus::LimitRows => self.chart_modal.adjust_row_limit(1),
+                        ChartFocus::Plot => self.chart_modal.zoom_x(2.0),
                         _ => {}
                     }
                 }
@@ -4171,25 +8637,50 @@ impl App {
                     match self.chart_modal.focus {
                         ChartFocus::HistBins => self.chart_modal.adjust_hist_bins(-1),
                         ChartFocus::HeatmapBins => self.chart_modal.adjust_heatmap_bins(-1),
+                        ChartFocus::CategoryTop => self.chart_modal.adjust_category_top(-1),
                         ChartFocus::KdeBandwidth => self
                             .chart_modal
                             .adjust_kde_bandwidth_factor(-chart_modal::KDE_BANDWIDTH_STEP),
                         ChartFocus::LimitRows => self.chart_modal.adjust_row_limit(-1),
+                        ChartFocus::Plot => self.chart_modal.zoom_x(0.5),
                         _ => {}
                     }
                 }
+                KeyCode::Char('0')
+                    if event.is_press() && self.chart_modal.focus == ChartFocus::Plot =>
+                {
+                    self.chart_modal.reset_zoom();
+                }
                 KeyCode::Left | KeyCode::Char('h')
                     if event.is_press() && !self.chart_modal.is_text_input_focused() =>
                 {
                     match self.chart_modal.focus {
                         ChartFocus::TabBar => self.chart_modal.prev_chart_kind(),
                         ChartFocus::ChartType => self.chart_modal.prev_chart_type(),
+                        ChartFocus::GroupBy => self.chart_modal.cycle_group_column(-1),
+                        ChartFocus::RightAxis => self.chart_modal.cycle_right_axis(-1),
+                        ChartFocus::BandMode => self.chart_modal.cycle_band_mode(-1),
+                        ChartFocus::BandColumn => self.chart_modal.cycle_band_column(-1),
+                        ChartFocus::BandUpper => self.chart_modal.cycle_band_upper(-1),
+                        ChartFocus::BandStyle => self.chart_modal.toggle_band_style(),
+                        ChartFocus::CategoryColumn => self.chart_modal.cycle_category_column(-1),
+                        ChartFocus::CategoryAgg => self.chart_modal.cycle_category_agg(-1),
+                        ChartFocus::CategoryValue => self.chart_modal.cycle_category_value(-1),
+                        ChartFocus::ScatterX => self.chart_modal.cycle_scatter_x_column(-1),
+                        ChartFocus::ScatterY => self.chart_modal.cycle_scatter_y_column(-1),
+                        ChartFocus::ScatterTrend => self.chart_modal.toggle_scatter_trend(),
                         ChartFocus::HistBins => self.chart_modal.adjust_hist_bins(-1),
                         ChartFocus::HeatmapBins => self.chart_modal.adjust_heatmap_bins(-1),
+                        ChartFocus::CategoryTop => self.chart_modal.adjust_category_top(-1),
                         ChartFocus::KdeBandwidth => self
                             .chart_modal
                             .adjust_kde_bandwidth_factor(-chart_modal::KDE_BANDWIDTH_STEP),
                         ChartFocus::LimitRows => self.chart_modal.adjust_row_limit(-1),
+                        ChartFocus::Plot if self.chart_modal.crosshair.is_some() => {
+                            let series = self.chart_cache.plot_series(&self.chart_modal);
+                            self.chart_modal.step_crosshair(&series, -1);
+                        }
+                        ChartFocus::Plot => self.chart_modal.pan_x(-chart_modal::PAN_STEP),
                         _ => {}
                     }
                 }
@@ -4199,12 +8690,30 @@ impl App {
                     match self.chart_modal.focus {
                         ChartFocus::TabBar => self.chart_modal.next_chart_kind(),
                         ChartFocus::ChartType => self.chart_modal.next_chart_type(),
+                        ChartFocus::GroupBy => self.chart_modal.cycle_group_column(1),
+                        ChartFocus::RightAxis => self.chart_modal.cycle_right_axis(1),
+                        ChartFocus::BandMode => self.chart_modal.cycle_band_mode(1),
+                        ChartFocus::BandColumn => self.chart_modal.cycle_band_column(1),
+                        ChartFocus::BandUpper => self.chart_modal.cycle_band_upper(1),
+                        ChartFocus::BandStyle => self.chart_modal.toggle_band_style(),
+                        ChartFocus::CategoryColumn => self.chart_modal.cycle_category_column(1),
+                        ChartFocus::CategoryAgg => self.chart_modal.cycle_category_agg(1),
+                        ChartFocus::CategoryValue => self.chart_modal.cycle_category_value(1),
+                        ChartFocus::ScatterX => self.chart_modal.cycle_scatter_x_column(1),
+                        ChartFocus::ScatterY => self.chart_modal.cycle_scatter_y_column(1),
+                        ChartFocus::ScatterTrend => self.chart_modal.toggle_scatter_trend(),
                         ChartFocus::HistBins => self.chart_modal.adjust_hist_bins(1),
                         ChartFocus::HeatmapBins => self.chart_modal.adjust_heatmap_bins(1),
+                        ChartFocus::CategoryTop => self.chart_modal.adjust_category_top(1),
                         ChartFocus::KdeBandwidth => self
                             .chart_modal
                             .adjust_kde_bandwidth_factor(chart_modal::KDE_BANDWIDTH_STEP),
                         ChartFocus::LimitRows => self.chart_modal.adjust_row_limit(1),
+                        ChartFocus::Plot if self.chart_modal.crosshair.is_some() => {
+                            let series = self.chart_cache.plot_series(&self.chart_modal);
+                            self.chart_modal.step_crosshair(&series, 1);
+                        }
+                        ChartFocus::Plot => self.chart_modal.pan_x(chart_modal::PAN_STEP),
                         _ => {}
                     }
                 }
@@ -4234,6 +8743,10 @@ impl App {
                         ChartFocus::KdeList => self.chart_modal.kde_list_up(),
                         ChartFocus::HeatmapXList => self.chart_modal.heatmap_x_list_up(),
                         ChartFocus::HeatmapYList => self.chart_modal.heatmap_y_list_up(),
+                        ChartFocus::Plot => {
+                            let series = self.chart_cache.plot_series(&self.chart_modal);
+                            self.chart_modal.cycle_crosshair_series(&series, -1);
+                        }
                         _ => {}
                     }
                 }
@@ -4249,6 +8762,10 @@ impl App {
                         ChartFocus::KdeList => self.chart_modal.kde_list_down(),
                         ChartFocus::HeatmapXList => self.chart_modal.heatmap_x_list_down(),
                         ChartFocus::HeatmapYList => self.chart_modal.heatmap_y_list_down(),
+                        ChartFocus::Plot => {
+                            let series = self.chart_cache.plot_series(&self.chart_modal);
+                            self.chart_modal.cycle_crosshair_series(&series, 1);
+                        }
                         _ => {}
                     }
                 }
@@ -4277,6 +8794,101 @@ impl App {
         }
 
         if self.analysis_modal.active {
+            if self.chart_export_modal.active {
+                return self.chart_export_modal_key(event);
+            }
+            // Duplicate Rows key columns input: Enter applies (empty = whole row), Esc cancels
+            if self.analysis_modal.duplicate_keys_editing {
+                match self
+                    .analysis_modal
+                    .duplicate_keys_input
+                    .handle_key(event, None)
+                {
+                    TextInputEvent::Submit => {
+                        self.analysis_modal.duplicate_keys_editing = false;
+                        self.analysis_modal.duplicate_keys_input.set_focused(false);
+                        self.analysis_modal.duplicate_keys =
+                            duplicates::parse_keys(&self.analysis_modal.duplicate_keys_input.value);
+                        return self.spawn_duplicates();
+                    }
+                    TextInputEvent::Cancel => {
+                        self.analysis_modal.duplicate_keys_editing = false;
+                        self.analysis_modal.duplicate_keys_input.set_focused(false);
+                    }
+                    TextInputEvent::HistoryChanged | TextInputEvent::None => {}
+                }
+                return None;
+            }
+            // Crosstab columns input: Enter applies, Esc cancels
+            if self.analysis_modal.crosstab_editing {
+                match self.analysis_modal.crosstab_input.handle_key(event, None) {
+                    TextInputEvent::Submit => {
+                        let agg = self
+                            .analysis_modal
+                            .crosstab_spec
+                            .as_ref()
+                            .map(|spec| spec.agg)
+                            .unwrap_or_default();
+                        match crosstab::CrosstabSpec::parse(
+                            &self.analysis_modal.crosstab_input.value,
+                            agg,
+                        ) {
+                            Ok(spec) => {
+                                self.analysis_modal.crosstab_editing = false;
+                                self.analysis_modal.crosstab_input.set_focused(false);
+                                self.analysis_modal.crosstab_spec = Some(spec);
+                                return self.spawn_crosstab();
+                            }
+                            Err(e) => self.error_modal.show(e.to_string()),
+                        }
+                    }
+                    TextInputEvent::Cancel => {
+                        self.analysis_modal.crosstab_editing = false;
+                        self.analysis_modal.crosstab_input.set_focused(false);
+                    }
+                    TextInputEvent::HistoryChanged | TextInputEvent::None => {}
+                }
+                return None;
+            }
+            // Gaps tool frequency input: Enter applies (empty = infer), Esc cancels
+            if self.analysis_modal.gap_frequency_editing {
+                match self
+                    .analysis_modal
+                    .gap_frequency_input
+                    .handle_key(event, None)
+                {
+                    TextInputEvent::Submit => {
+                        let text = self
+                            .analysis_modal
+                            .gap_frequency_input
+                            .value
+                            .trim()
+                            .to_string();
+                        let frequency = if text.is_empty() {
+                            Ok(None)
+                        } else {
+                            time_gaps::parse_frequency(&text).map(Some)
+                        };
+                        match frequency {
+                            Ok(frequency) => {
+                                self.analysis_modal.gap_frequency_editing = false;
+                                self.analysis_modal.gap_frequency_input.set_focused(false);
+                                self.analysis_modal.gap_frequency = frequency;
+                                return self.spawn_time_gaps();
+                            }
+                            Err(e) => {
+                                self.error_modal.show(e.to_string());
+                            }
+                        }
+                    }
+                    TextInputEvent::Cancel => {
+                        self.analysis_modal.gap_frequency_editing = false;
+                        self.analysis_modal.gap_frequency_input.set_focused(false);
+                    }
+                    TextInputEvent::HistoryChanged | TextInputEvent::None => {}
+                }
+                return None;
+            }
             match event.code {
                 KeyCode::Esc => {
                     if self.analysis_modal.show_help {
@@ -4334,7 +8946,19 @@ impl App {
                             self.busy = true;
                             return Some(AppEvent::AnalysisCorrelationCompute);
                         }
-                        None => {}
+                        Some(analysis_modal::AnalysisTool::ColumnCardinality) => {
+                            return self.spawn_cardinality();
+                        }
+                        Some(analysis_modal::AnalysisTool::MissingData) => {
+                            return self.spawn_missing_data();
+                        }
+                        Some(analysis_modal::AnalysisTool::Plugin(index)) => {
+                            return self.spawn_plugin_tool(index);
+                        }
+                        Some(analysis_modal::AnalysisTool::TimeSeriesGaps)
+                        | Some(analysis_modal::AnalysisTool::DuplicateRows)
+                        | Some(analysis_modal::AnalysisTool::Crosstab)
+                        | None => {}
                     }
                 }
                 KeyCode::Tab => {
@@ -4402,6 +9026,36 @@ impl App {
                                 self.busy = true;
                                 return Some(AppEvent::AnalysisCorrelationCompute);
                             }
+                            Some(analysis_modal::AnalysisTool::TimeSeriesGaps)
+                                if self.analysis_modal.gap_results.is_none() =>
+                            {
+                                return self.spawn_time_gaps();
+                            }
+                            Some(analysis_modal::AnalysisTool::ColumnCardinality)
+                                if self.analysis_modal.cardinality_results.is_none() =>
+                            {
+                                return self.spawn_cardinality();
+                            }
+                            Some(analysis_modal::AnalysisTool::MissingData)
+                                if self.analysis_modal.missing_results.is_none() =>
+                            {
+                                return self.spawn_missing_data();
+                            }
+                            Some(analysis_modal::AnalysisTool::DuplicateRows)
+                                if self.analysis_modal.duplicate_results.is_none() =>
+                            {
+                                return self.spawn_duplicates();
+                            }
+                            Some(analysis_modal::AnalysisTool::Crosstab)
+                                if self.analysis_modal.crosstab_results.is_none() =>
+                            {
+                                return self.spawn_crosstab();
+                            }
+                            Some(analysis_modal::AnalysisTool::Plugin(index))
+                                if self.analysis_modal.plugin_result(index).is_none() =>
+                            {
+                                return self.spawn_plugin_tool(index);
+                            }
                             _ => {}
                         }
                     } else {
@@ -4413,10 +9067,248 @@ impl App {
                             Some(analysis_modal::AnalysisTool::CorrelationMatrix) => {
                                 self.analysis_modal.open_correlation_detail();
                             }
+                            Some(analysis_modal::AnalysisTool::TimeSeriesGaps) => {
+                                // Close analysis and jump to the row after the gap
+                                if let Some(row) = self.analysis_modal.selected_gap_row() {
+                                    self.analysis_modal.close();
+                                    if let Some(state) = &mut self.data_table_state {
+                                        let would_collect = state.scroll_would_trigger_collect(
+                                            row as i64 - state.start_row as i64,
+                                        );
+                                        if would_collect {
+                                            self.busy = true;
+                                            return Some(AppEvent::GoToLine(row));
+                                        }
+                                        state.scroll_to_row_centered(row);
+                                    }
+                                }
+                            }
+                            Some(analysis_modal::AnalysisTool::MissingData) => {
+                                // Filter to the rows where the highlighted column is null
+                                let column = self.analysis_modal.selected_missing_column();
+                                let columns = column.map(str::to_string).into_iter().collect();
+                                return self
+                                    .apply_missing_data_filters(columns, FilterOperator::IsNull);
+                            }
+                            Some(analysis_modal::AnalysisTool::DuplicateRows) => {
+                                // Close analysis and jump to the group's first row
+                                if let Some(row) = self.analysis_modal.selected_duplicate_row() {
+                                    self.analysis_modal.close();
+                                    if let Some(state) = &mut self.data_table_state {
+                                        let would_collect = state.scroll_would_trigger_collect(
+                                            row as i64 - state.start_row as i64,
+                                        );
+                                        if would_collect {
+                                            self.busy = true;
+                                            return Some(AppEvent::GoToLine(row));
+                                        }
+                                        state.scroll_to_row_centered(row);
+                                    }
+                                }
+                            }
                             _ => {}
                         }
                     }
                 }
+                KeyCode::Char('d') | KeyCode::Char('D')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && self.analysis_modal.focus == analysis_modal::AnalysisFocus::Main
+                        && self.analysis_modal.selected_tool
+                            == Some(analysis_modal::AnalysisTool::MissingData) =>
+                {
+                    // d: drop rows where the highlighted column is null; D: rows with any null
+                    let columns: Vec<String> = if event.code == KeyCode::Char('d') {
+                        self.analysis_modal
+                            .selected_missing_column()
+                            .map(str::to_string)
+                            .into_iter()
+                            .collect()
+                    } else {
+                        match &self.analysis_modal.missing_results {
+                            Some(Ok(report)) => report
+                                .columns_with_nulls()
+                                .map(|c| c.column.clone())
+                                .collect(),
+                            _ => Vec::new(),
+                        }
+                    };
+                    return self.apply_missing_data_filters(columns, FilterOperator::IsNotNull);
+                }
+                KeyCode::Char('c')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && self.analysis_modal.selected_tool
+                            == Some(analysis_modal::AnalysisTool::DuplicateRows) =>
+                {
+                    let current = self.analysis_modal.duplicate_keys.join(", ");
+                    self.analysis_modal.duplicate_keys_input.set_value(current);
+                    self.analysis_modal.duplicate_keys_input.set_focused(true);
+                    self.analysis_modal.duplicate_keys_editing = true;
+                }
+                KeyCode::Char('f')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && self.analysis_modal.selected_tool
+                            == Some(analysis_modal::AnalysisTool::DuplicateRows) =>
+                {
+                    self.filter_view_to_duplicates(false);
+                }
+                KeyCode::Char('c')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && self.analysis_modal.selected_tool
+                            == Some(analysis_modal::AnalysisTool::Crosstab) =>
+                {
+                    let current = self
+                        .analysis_modal
+                        .crosstab_spec
+                        .as_ref()
+                        .map(crosstab::CrosstabSpec::to_text)
+                        .unwrap_or_default();
+                    let cursor = current.chars().count();
+                    self.analysis_modal.crosstab_input.set_value(current);
+                    self.analysis_modal.crosstab_input.set_cursor(cursor);
+                    self.analysis_modal.crosstab_input.set_focused(true);
+                    self.analysis_modal.crosstab_editing = true;
+                }
+                KeyCode::Char('a')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && self.analysis_modal.selected_tool
+                            == Some(analysis_modal::AnalysisTool::Crosstab) =>
+                {
+                    // Next aggregation of the value column
+                    if let Some(spec) = self
+                        .analysis_modal
+                        .crosstab_spec
+                        .as_mut()
+                        .filter(|spec| spec.value.is_some())
+                    {
+                        spec.cycle_agg();
+                        return self.spawn_crosstab();
+                    }
+                }
+                KeyCode::Char('e')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && self.analysis_modal.selected_tool
+                            == Some(analysis_modal::AnalysisTool::DuplicateRows) =>
+                {
+                    self.filter_view_to_duplicates(true);
+                }
+                KeyCode::Char('t')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && self.analysis_modal.selected_tool
+                            == Some(analysis_modal::AnalysisTool::TimeSeriesGaps) =>
+                {
+                    // Next date/datetime column as the time axis
+                    let columns = self.time_columns();
+                    if !columns.is_empty() {
+                        let next = self
+                            .analysis_modal
+                            .gap_time_column
+                            .as_ref()
+                            .and_then(|c| columns.iter().position(|n| n == c))
+                            .map_or(0, |i| (i + 1) % columns.len());
+                        self.analysis_modal.gap_time_column = Some(columns[next].clone());
+                        return self.spawn_time_gaps();
+                    }
+                }
+                KeyCode::Char('f')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && self.analysis_modal.selected_tool
+                            == Some(analysis_modal::AnalysisTool::TimeSeriesGaps) =>
+                {
+                    let current = self
+                        .analysis_modal
+                        .gap_frequency
+                        .map(time_gaps::format_frequency)
+                        .unwrap_or_default();
+                    self.analysis_modal.gap_frequency_input.set_value(current);
+                    self.analysis_modal.gap_frequency_input.set_focused(true);
+                    self.analysis_modal.gap_frequency_editing = true;
+                }
+                KeyCode::Char('H')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && self.analysis_modal.selected_tool
+                            == Some(analysis_modal::AnalysisTool::CorrelationMatrix) =>
+                {
+                    self.analysis_modal.correlation_heatmap =
+                        !self.analysis_modal.correlation_heatmap;
+                }
+                KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Char('-')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && self.analysis_modal.selected_tool
+                            == Some(analysis_modal::AnalysisTool::CorrelationMatrix) =>
+                {
+                    let delta = if event.code == KeyCode::Char('-') {
+                        -0.05
+                    } else {
+                        0.05
+                    };
+                    self.analysis_modal.adjust_correlation_threshold(delta);
+                }
+                KeyCode::Char('e')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && self.analysis_modal.selected_tool
+                            == Some(analysis_modal::AnalysisTool::CorrelationMatrix)
+                        && self.correlation_matrix().is_some() =>
+                {
+                    // Export the matrix as a heatmap through the chart export modal
+                    self.chart_export_modal
+                        .open(&self.theme, self.history_limit);
+                    self.chart_export_modal
+                        .title_input
+                        .set_value("Correlation Matrix".to_string());
+                }
+                KeyCode::Char('E')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && (self.analysis_modal.current_results().is_some()
+                            || self.analysis_crosstab().is_some()) =>
+                {
+                    // Write the results table through the data export modal
+                    let tool = match self.analysis_modal.selected_tool {
+                        Some(analysis_modal::AnalysisTool::DistributionAnalysis) => "Distribution",
+                        Some(analysis_modal::AnalysisTool::CorrelationMatrix) => "Correlation",
+                        Some(analysis_modal::AnalysisTool::Crosstab) => "Crosstab",
+                        _ => "Describe",
+                    };
+                    self.open_export_modal(None);
+                    self.export_modal.set_analysis(tool);
+                }
+                KeyCode::Char('b')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && self.analysis_modal.selected_tool
+                            == Some(analysis_modal::AnalysisTool::Describe) =>
+                {
+                    // Save the current statistics as the baseline to compare against
+                    if let (Some(results), Some(state)) = (
+                        &self.analysis_modal.describe_results,
+                        &self.data_table_state,
+                    ) {
+                        self.analysis_modal.describe_baseline = Some(stats_compare::StatsBaseline {
+                            label: stats_compare::context_label(&state.get_analysis_context()),
+                            results: results.clone(),
+                        });
+                        self.analysis_modal.describe_compare = false;
+                        self.analysis_modal.describe_column_offset = 0;
+                    }
+                }
+                KeyCode::Char('c')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && self.analysis_modal.selected_tool
+                            == Some(analysis_modal::AnalysisTool::Describe)
+                        && self.analysis_modal.describe_baseline.is_some() =>
+                {
+                    self.analysis_modal.describe_compare = !self.analysis_modal.describe_compare;
+                    self.analysis_modal.describe_column_offset = 0;
+                    self.analysis_modal.table_state.select(Some(0));
+                }
+                KeyCode::Char('x')
+                    if self.analysis_modal.view == analysis_modal::AnalysisView::Main
+                        && self.analysis_modal.selected_tool
+                            == Some(analysis_modal::AnalysisTool::Describe) =>
+                {
+                    self.analysis_modal.describe_baseline = None;
+                    self.analysis_modal.describe_compare = false;
+                    self.analysis_modal.describe_column_offset = 0;
+                    self.analysis_modal.table_state.select(Some(0));
+                }
                 KeyCode::Down | KeyCode::Char('j') => {
                     match self.analysis_modal.view {
                         analysis_modal::AnalysisView::Main => {
@@ -4430,7 +9322,9 @@ impl App {
                                     match self.analysis_modal.selected_tool {
                                         Some(analysis_modal::AnalysisTool::Describe) => {
                                             if let Some(state) = &self.data_table_state {
-                                                let max_rows = state.schema.len();
+                                                let max_rows = self
+                                    .analysis_modal
+                                    .describe_row_count(state.schema.len());
                                                 self.analysis_modal.next_row(max_rows);
                                             }
                                         }
@@ -4486,6 +9380,53 @@ impl App {
                                                 }
                                             }
                                         }
+                                        Some(analysis_modal::AnalysisTool::TimeSeriesGaps) => {
+                                            if let Some(Ok(report)) =
+                                                &self.analysis_modal.gap_results
+                                            {
+                                                let max_rows = report.gaps.len();
+                                                self.analysis_modal.next_row(max_rows);
+                                            }
+                                        }
+                                        Some(
+                                            analysis_modal::AnalysisTool::ColumnCardinality,
+                                        ) => {
+                                            if let Some(Ok(report)) =
+                                                &self.analysis_modal.cardinality_results
+                                            {
+                                                let max_rows = report.columns.len();
+                                                self.analysis_modal.next_row(max_rows);
+                                            }
+                                        }
+                                        Some(analysis_modal::AnalysisTool::MissingData) => {
+                                            if let Some(Ok(report)) =
+                                                &self.analysis_modal.missing_results
+                                            {
+                                                let max_rows = report.columns.len();
+                                                self.analysis_modal.next_row(max_rows);
+                                            }
+                                        }
+                                        Some(analysis_modal::AnalysisTool::DuplicateRows) => {
+                                            if let Some(Ok(report)) =
+                                                &self.analysis_modal.duplicate_results
+                                            {
+                                                let max_rows = report.groups.len();
+                                                self.analysis_modal.next_row(max_rows);
+                                            }
+                                        }
+                                        Some(analysis_modal::AnalysisTool::Crosstab) => {
+                                            if let Some(Ok(table)) =
+                                                &self.analysis_modal.crosstab_results
+                                            {
+                                                // Value rows and the totals row
+                                                let max_rows = table.row_labels.len() + 1;
+                                                self.analysis_modal.next_row(max_rows);
+                                            }
+                                        }
+                                        Some(analysis_modal::AnalysisTool::Plugin(_)) => {
+                                            let max_rows = self.analysis_modal.plugin_rows();
+                                            self.analysis_modal.next_row(max_rows);
+                                        }
                                         None => {}
                                     }
                                 }
@@ -4511,6 +9452,13 @@ impl App {
                         analysis_modal::HistogramScale::Log => analysis_modal::HistogramScale::Linear,
                     };
                 }
+                KeyCode::Char('o')
+                    // Toggle the best-fit curve on the histogram in distribution detail view
+                    if self.analysis_modal.view
+                        == analysis_modal::AnalysisView::DistributionDetail =>
+                {
+                    self.analysis_modal.histogram_best_fit = !self.analysis_modal.histogram_best_fit;
+                }
                 KeyCode::Up | KeyCode::Char('k') => {
                     if self.analysis_modal.view == analysis_modal::AnalysisView::Main {
                         self.analysis_modal.previous_row();
@@ -4538,7 +9486,8 @@ impl App {
                                 Some(analysis_modal::AnalysisTool::Describe) => {
                                     self.analysis_modal.scroll_left();
                                 }
-                                Some(analysis_modal::AnalysisTool::DistributionAnalysis) => {
+                                Some(analysis_modal::AnalysisTool::DistributionAnalysis)
+                                | Some(analysis_modal::AnalysisTool::Crosstab) => {
                                     self.analysis_modal.scroll_left();
                                 }
                                 Some(analysis_modal::AnalysisTool::CorrelationMatrix) => {
@@ -4585,7 +9534,12 @@ impl App {
                                         }
                                     }
                                 }
-                                None => {}
+                                Some(analysis_modal::AnalysisTool::TimeSeriesGaps)
+                                | Some(analysis_modal::AnalysisTool::ColumnCardinality)
+                                | Some(analysis_modal::AnalysisTool::MissingData)
+                                | Some(analysis_modal::AnalysisTool::DuplicateRows)
+                                | Some(analysis_modal::AnalysisTool::Plugin(_))
+                                | None => {}
                             }
                         }
                     }
@@ -4602,6 +9556,14 @@ impl App {
                         }
                         analysis_modal::AnalysisFocus::Main => {
                             match self.analysis_modal.selected_tool {
+                                Some(analysis_modal::AnalysisTool::Describe)
+                                    if self.analysis_modal.describe_compare =>
+                                {
+                                    // Baseline, current and change for each compared statistic
+                                    let max_stats = 3 * stats_compare::COMPARE_STATS.len();
+                                    let visible_stats = 6;
+                                    self.analysis_modal.scroll_right(max_stats, visible_stats);
+                                }
                                 Some(analysis_modal::AnalysisTool::Describe) => {
                                     // Number of statistics: count, null_count, mean, std, min, 25%, 50%, 75%, max, skewness, kurtosis, distribution
                                     let max_stats = 12;
@@ -4616,6 +9578,13 @@ impl App {
                                     let visible_stats = 6; // Will be calculated more accurately in widget
                                     self.analysis_modal.scroll_right(max_stats, visible_stats);
                                 }
+                                Some(analysis_modal::AnalysisTool::Crosstab) => {
+                                    // Scroll until the last value column is the first shown
+                                    if let Some(Ok(table)) = &self.analysis_modal.crosstab_results {
+                                        let max_columns = table.column_labels.len();
+                                        self.analysis_modal.scroll_right(max_columns, 1);
+                                    }
+                                }
                                 Some(analysis_modal::AnalysisTool::CorrelationMatrix) => {
                                     if let Some(results) = self.analysis_modal.current_results() {
                                         if let Some(corr) = &results.correlation_matrix {
@@ -4655,7 +9624,12 @@ impl App {
                                         }
                                     }
                                 }
-                                None => {}
+                                Some(analysis_modal::AnalysisTool::TimeSeriesGaps)
+                                | Some(analysis_modal::AnalysisTool::ColumnCardinality)
+                                | Some(analysis_modal::AnalysisTool::MissingData)
+                                | Some(analysis_modal::AnalysisTool::DuplicateRows)
+                                | Some(analysis_modal::AnalysisTool::Plugin(_))
+                                | None => {}
                             }
                         }
                     }
@@ -4667,7 +9641,9 @@ impl App {
                     match self.analysis_modal.selected_tool {
                         Some(analysis_modal::AnalysisTool::Describe) => {
                             if let Some(state) = &self.data_table_state {
-                                let max_rows = state.schema.len();
+                                let max_rows = self
+                                    .analysis_modal
+                                    .describe_row_count(state.schema.len());
                                 let page_size = 10;
                                 self.analysis_modal.page_down(max_rows, page_size);
                             }
@@ -4688,6 +9664,46 @@ impl App {
                                 }
                             }
                         }
+                        Some(analysis_modal::AnalysisTool::TimeSeriesGaps) => {
+                            if let Some(Ok(report)) = &self.analysis_modal.gap_results {
+                                let max_rows = report.gaps.len();
+                                let page_size = 10;
+                                self.analysis_modal.page_down(max_rows, page_size);
+                            }
+                        }
+                        Some(analysis_modal::AnalysisTool::ColumnCardinality) => {
+                            if let Some(Ok(report)) = &self.analysis_modal.cardinality_results {
+                                let max_rows = report.columns.len();
+                                let page_size = 10;
+                                self.analysis_modal.page_down(max_rows, page_size);
+                            }
+                        }
+                        Some(analysis_modal::AnalysisTool::MissingData) => {
+                            if let Some(Ok(report)) = &self.analysis_modal.missing_results {
+                                let max_rows = report.columns.len();
+                                let page_size = 10;
+                                self.analysis_modal.page_down(max_rows, page_size);
+                            }
+                        }
+                        Some(analysis_modal::AnalysisTool::DuplicateRows) => {
+                            if let Some(Ok(report)) = &self.analysis_modal.duplicate_results {
+                                let max_rows = report.groups.len();
+                                let page_size = 10;
+                                self.analysis_modal.page_down(max_rows, page_size);
+                            }
+                        }
+                        Some(analysis_modal::AnalysisTool::Crosstab) => {
+                            if let Some(Ok(table)) = &self.analysis_modal.crosstab_results {
+                                let max_rows = table.row_labels.len() + 1;
+                                let page_size = 10;
+                                self.analysis_modal.page_down(max_rows, page_size);
+                            }
+                        }
+                        Some(analysis_modal::AnalysisTool::Plugin(_)) => {
+                            let max_rows = self.analysis_modal.plugin_rows();
+                            let page_size = 10;
+                            self.analysis_modal.page_down(max_rows, page_size);
+                        }
                         None => {}
                     }
                 }
@@ -4724,6 +9740,24 @@ impl App {
                                     self.analysis_modal.correlation_table_state.select(Some(0));
                                     self.analysis_modal.selected_correlation = Some((0, 0));
                                 }
+                                Some(analysis_modal::AnalysisTool::TimeSeriesGaps) => {
+                                    self.analysis_modal.gap_table_state.select(Some(0));
+                                }
+                                Some(analysis_modal::AnalysisTool::ColumnCardinality) => {
+                                    self.analysis_modal.cardinality_table_state.select(Some(0));
+                                }
+                                Some(analysis_modal::AnalysisTool::MissingData) => {
+                                    self.analysis_modal.missing_table_state.select(Some(0));
+                                }
+                                Some(analysis_modal::AnalysisTool::DuplicateRows) => {
+                                    self.analysis_modal.duplicate_table_state.select(Some(0));
+                                }
+                                Some(analysis_modal::AnalysisTool::Crosstab) => {
+                                    self.analysis_modal.crosstab_table_state.select(Some(0));
+                                }
+                                Some(analysis_modal::AnalysisTool::Plugin(_)) => {
+                                    self.analysis_modal.plugin_table_state.select(Some(0));
+                                }
                                 None => {}
                             }
                         }
@@ -4734,7 +9768,9 @@ impl App {
                 {
                     match self.analysis_modal.focus {
                         analysis_modal::AnalysisFocus::Sidebar => {
-                            self.analysis_modal.sidebar_state.select(Some(2));
+                            self.analysis_modal
+                                .sidebar_state
+                                .select(Some(self.analysis_modal.tool_count() - 1));
                             // Last tool
                         }
                         analysis_modal::AnalysisFocus::DistributionSelector => {
@@ -4746,7 +9782,9 @@ impl App {
                             match self.analysis_modal.selected_tool {
                                 Some(analysis_modal::AnalysisTool::Describe) => {
                                     if let Some(state) = &self.data_table_state {
-                                        let max_rows = state.schema.len();
+                                        let max_rows = self
+                                    .analysis_modal
+                                    .describe_row_count(state.schema.len());
                                         if max_rows > 0 {
                                             self.analysis_modal
                                                 .table_state
@@ -4778,6 +9816,63 @@ impl App {
                                         }
                                     }
                                 }
+                                Some(analysis_modal::AnalysisTool::TimeSeriesGaps) => {
+                                    if let Some(Ok(report)) = &self.analysis_modal.gap_results {
+                                        if !report.gaps.is_empty() {
+                                            self.analysis_modal
+                                                .gap_table_state
+                                                .select(Some(report.gaps.len() - 1));
+                                        }
+                                    }
+                                }
+                                Some(analysis_modal::AnalysisTool::ColumnCardinality) => {
+                                    if let Some(Ok(report)) =
+                                        &self.analysis_modal.cardinality_results
+                                    {
+                                        if !report.columns.is_empty() {
+                                            self.analysis_modal
+                                                .cardinality_table_state
+                                                .select(Some(report.columns.len() - 1));
+                                        }
+                                    }
+                                }
+                                Some(analysis_modal::AnalysisTool::MissingData) => {
+                                    if let Some(Ok(report)) = &self.analysis_modal.missing_results
+                                    {
+                                        if !report.columns.is_empty() {
+                                            self.analysis_modal
+                                                .missing_table_state
+                                                .select(Some(report.columns.len() - 1));
+                                        }
+                                    }
+                                }
+                                Some(analysis_modal::AnalysisTool::DuplicateRows) => {
+                                    if let Some(Ok(report)) =
+                                        &self.analysis_modal.duplicate_results
+                                    {
+                                        if !report.groups.is_empty() {
+                                            self.analysis_modal
+                                                .duplicate_table_state
+                                                .select(Some(report.groups.len() - 1));
+                                        }
+                                    }
+                                }
+                                Some(analysis_modal::AnalysisTool::Crosstab) => {
+                                    if let Some(Ok(table)) = &self.analysis_modal.crosstab_results
+                                    {
+                                        self.analysis_modal
+                                            .crosstab_table_state
+                                            .select(Some(table.row_labels.len()));
+                                    }
+                                }
+                                Some(analysis_modal::AnalysisTool::Plugin(_)) => {
+                                    let rows = self.analysis_modal.plugin_rows();
+                                    if rows > 0 {
+                                        self.analysis_modal
+                                            .plugin_table_state
+                                            .select(Some(rows - 1));
+                                    }
+                                }
                                 None => {}
                             }
                         }
@@ -5084,7 +10179,7 @@ impl App {
                                             self.template_modal.templates.get(idx)
                                         {
                                             let template_clone = template.clone();
-                                            if let Err(e) = self.apply_template(&template_clone) {
+                                            if let Err(e) = self.start_template(&template_clone) {
                                                 // Show error modal instead of just printing
                                                 self.error_modal.show(format!(
                                                     "Error applying template: {}",
@@ -5271,6 +10366,7 @@ impl App {
                                                         state.get_active_sql_query(),
                                                         state.get_active_fuzzy_query(),
                                                     );
+                                                let previous_settings = template.settings.clone();
                                                 template.settings = template::TemplateSettings {
                                                     query,
                                                     sql_query,
@@ -5283,7 +10379,30 @@ impl App {
                                                         .locked_columns_count(),
                                                     pivot: state.last_pivot_spec().cloned(),
                                                     melt: state.last_melt_spec().cloned(),
+                                                    group_by: state
+                                                        .last_group_by_spec()
+                                                        .cloned(),
+                                                    parameters: Vec::new(),
+                                                    column_formats: state.column_formats.clone(),
+                                                    computed_columns: state
+                                                        .computed_columns()
+                                                        .to_vec(),
+                                                    column_casts: state.column_casts().to_vec(),
+                                                    column_renames: state
+                                                        .column_renames()
+                                                        .to_vec(),
+                                                    saved_queries: previous_settings
+                                                        .saved_queries
+                                                        .clone(),
                                                 };
+                                                // Parameterized reshapes keep their placeholders;
+                                                // the current state only holds resolved values.
+                                                if !previous_settings.parameters.is_empty() {
+                                                    template.settings.pivot = previous_settings.pivot;
+                                                    template.settings.melt = previous_settings.melt;
+                                                    template.settings.parameters =
+                                                        previous_settings.parameters;
+                                                }
                                             }
 
                                             match self.template_manager.update_template(&template) {
@@ -5710,6 +10829,13 @@ impl App {
                                 self.query_input.set_focused(false);
                                 self.fuzzy_input.set_focused(false);
                                 self.sql_input.set_focused(true);
+                            } else if self.query_tab.is_regex() {
+                                self.regex_input
+                                    .set_value(state.regex_search_pattern().to_string());
+                                self.query_input.set_focused(false);
+                                self.sql_input.set_focused(false);
+                                self.fuzzy_input.set_focused(false);
+                                self.regex_input.set_focused(true);
                             }
                         }
                         return None;
@@ -5726,11 +10852,15 @@ impl App {
                             } else if self.query_tab == QueryTab::Sql {
                                 self.sql_input.value = state.get_active_sql_query().to_string();
                                 self.sql_input.cursor = self.sql_input.value.chars().count();
+                            } else if self.query_tab.is_regex() {
+                                self.regex_input
+                                    .set_value(state.regex_search_pattern().to_string());
                             }
                         }
                         self.query_input.set_focused(false);
                         self.sql_input.set_focused(false);
                         self.fuzzy_input.set_focused(false);
+                        self.regex_input.set_focused(false);
                         return None;
                     }
                     if LEFT_KEYS.contains(&event.code) {
@@ -5745,20 +10875,26 @@ impl App {
                             } else if self.query_tab == QueryTab::Sql {
                                 self.sql_input.value = state.get_active_sql_query().to_string();
                                 self.sql_input.cursor = self.sql_input.value.chars().count();
+                            } else if self.query_tab.is_regex() {
+                                self.regex_input
+                                    .set_value(state.regex_search_pattern().to_string());
                             }
                         }
                         self.query_input.set_focused(false);
                         self.sql_input.set_focused(false);
                         self.fuzzy_input.set_focused(false);
+                        self.regex_input.set_focused(false);
                         return None;
                     }
                     if event.code == KeyCode::Esc {
                         self.query_input.clear();
                         self.sql_input.clear();
                         self.fuzzy_input.clear();
+                        self.regex_input.clear();
                         self.query_input.set_focused(false);
                         self.sql_input.set_focused(false);
                         self.fuzzy_input.set_focused(false);
+                        self.regex_input.set_focused(false);
                         self.input_mode = InputMode::Normal;
                         self.input_type = None;
                         if let Some(state) = &mut self.data_table_state {
@@ -5770,6 +10906,29 @@ impl App {
                     return None;
                 }
 
+                // SQL-Like input: Tab completes names, Shift+Tab goes back through the list
+                if self.query_tab == QueryTab::SqlLike
+                    && self.query_focus == QueryFocus::Input
+                    && event.is_press()
+                {
+                    match event.code {
+                        KeyCode::Tab if !event.modifiers.contains(KeyModifiers::SHIFT) => {
+                            if self.complete_query(false) {
+                                return None;
+                            }
+                        }
+                        KeyCode::BackTab if self.query_completion.is_some() => {
+                            self.complete_query(true);
+                            return None;
+                        }
+                        KeyCode::Esc if self.query_completion.is_some() => {
+                            self.query_completion = None;
+                            return None;
+                        }
+                        _ => self.query_completion = None,
+                    }
+                }
+
                 if event.is_press()
                     && event.code == KeyCode::Tab
                     && !event.modifiers.contains(KeyModifiers::SHIFT)
@@ -5778,6 +10937,7 @@ impl App {
                     self.query_input.set_focused(false);
                     self.sql_input.set_focused(false);
                     self.fuzzy_input.set_focused(false);
+                    self.regex_input.set_focused(false);
                     return None;
                 }
 
@@ -5834,80 +10994,256 @@ impl App {
                                 state.suppress_error_display = false;
                             }
                         }
-                        TextInputEvent::HistoryChanged | TextInputEvent::None => {}
+                        TextInputEvent::HistoryChanged | TextInputEvent::None => {}
+                    }
+                    return None;
+                }
+
+                if self.query_tab.is_regex() {
+                    self.query_input.set_focused(false);
+                    self.sql_input.set_focused(false);
+                    self.fuzzy_input.set_focused(false);
+                    self.regex_input.set_focused(true);
+                    let result = self.regex_input.handle_key(event, Some(&self.cache));
+                    match result {
+                        TextInputEvent::Submit => {
+                            let _ = self.regex_input.save_to_history(&self.cache);
+                            let pattern = self.regex_input.value.clone();
+                            let column = match self.query_tab {
+                                QueryTab::Column => self
+                                    .data_table_state
+                                    .as_ref()
+                                    .and_then(|s| s.selected_column())
+                                    .map(str::to_string),
+                                _ => None,
+                            };
+                            return Some(AppEvent::RegexSearch(pattern, column));
+                        }
+                        TextInputEvent::Cancel => {
+                            self.regex_input.clear();
+                            self.regex_input.set_focused(false);
+                            self.input_mode = InputMode::Normal;
+                            self.input_type = None;
+                            if let Some(state) = &mut self.data_table_state {
+                                state.error = None;
+                                state.suppress_error_display = false;
+                            }
+                        }
+                        TextInputEvent::HistoryChanged | TextInputEvent::None => {}
+                    }
+                    return None;
+                }
+
+                if self.query_tab != QueryTab::SqlLike {
+                    return None;
+                }
+
+                self.sql_input.set_focused(false);
+                self.fuzzy_input.set_focused(false);
+                self.query_input.set_focused(true);
+                let result = self.query_input.handle_key(event, Some(&self.cache));
+
+                match result {
+                    TextInputEvent::Submit => {
+                        // Save to history and execute query
+                        let _ = self.query_input.save_to_history(&self.cache);
+                        let query = self.query_input.value.clone();
+                        self.query_input.set_focused(false);
+                        return Some(AppEvent::Search(query));
+                    }
+                    TextInputEvent::Cancel => {
+                        // Clear and exit input mode
+                        self.query_input.clear();
+                        self.query_input.set_focused(false);
+                        self.input_mode = InputMode::Normal;
+                        if let Some(state) = &mut self.data_table_state {
+                            // Clear error and re-enable error display in main view
+                            state.error = None;
+                            state.suppress_error_display = false;
+                        }
+                    }
+                    TextInputEvent::HistoryChanged => {
+                        // History navigation occurred, nothing special needed
+                    }
+                    TextInputEvent::None => {
+                        // Regular input, nothing special needed
+                    }
+                }
+                return None;
+            }
+
+            // Line number input (GoToLine): ":" then type line number, Enter to jump, Esc to cancel
+            if self.input_type == Some(InputType::GoToLine) {
+                self.query_input.set_focused(true);
+                let result = self.query_input.handle_key(event, None);
+                match result {
+                    TextInputEvent::Submit => {
+                        let value = self.query_input.value.trim().to_string();
+                        self.query_input.clear();
+                        self.query_input.set_focused(false);
+                        self.input_mode = InputMode::Normal;
+                        self.input_type = None;
+                        if let Some(state) = &mut self.data_table_state {
+                            if let Ok(display_line) = value.parse::<usize>() {
+                                let row_index =
+                                    display_line.saturating_sub(state.row_start_index());
+                                let would_collect = state.scroll_would_trigger_collect(
+                                    row_index as i64 - state.start_row as i64,
+                                );
+                                if would_collect {
+                                    self.busy = true;
+                                    return Some(AppEvent::GoToLine(row_index));
+                                }
+                                state.scroll_to_row_centered(row_index);
+                            }
+                        }
+                    }
+                    TextInputEvent::Cancel => {
+                        self.query_input.clear();
+                        self.query_input.set_focused(false);
+                        self.input_mode = InputMode::Normal;
+                        self.input_type = None;
+                    }
+                    TextInputEvent::HistoryChanged | TextInputEvent::None => {}
+                }
+                return None;
+            }
+
+            // Go to column: "|" then a name, number or letters; Tab completes the best match,
+            // Enter scrolls to it, Esc cancels
+            if self.input_type == Some(InputType::GoToColumn) {
+                if event.code == KeyCode::Tab {
+                    if !event.is_press() {
+                        return None;
+                    }
+                    if let Some(name) = self.go_to_column_matches().into_iter().next() {
+                        self.query_input.set_value(name.clone());
+                        self.query_input.set_cursor(name.chars().count());
+                    }
+                    return None;
+                }
+                self.query_input.set_focused(true);
+                match self.query_input.handle_key(event, None) {
+                    TextInputEvent::Submit => {
+                        let value = self.query_input.value.trim().to_string();
+                        self.query_input.clear();
+                        self.query_input.set_focused(false);
+                        self.input_mode = InputMode::Normal;
+                        self.input_type = None;
+                        if let Some(state) = &mut self.data_table_state {
+                            match column_ruler::find_column(state.get_column_order(), &value) {
+                                Some(index) => state.scroll_to_column(index),
+                                None if value.is_empty() => {}
+                                None => self
+                                    .error_modal
+                                    .show(format!("No column matches \"{}\"", value)),
+                            }
+                        }
+                    }
+                    TextInputEvent::Cancel => {
+                        self.query_input.clear();
+                        self.query_input.set_focused(false);
+                        self.input_mode = InputMode::Normal;
+                        self.input_type = None;
+                    }
+                    TextInputEvent::HistoryChanged | TextInputEvent::None => {}
+                }
+                return None;
+            }
+
+            // Template parameter prompt: Enter stores the value and moves to the next parameter
+            if self.input_type == Some(InputType::TemplateParameter) {
+                self.query_input.set_focused(true);
+                let result = self.query_input.handle_key(event, None);
+                match result {
+                    TextInputEvent::Submit => {
+                        let value = self.query_input.value.trim().to_string();
+                        self.query_input.clear();
+                        if let Some(pending) = &mut self.pending_template {
+                            if let Some(param) =
+                                pending.template.settings.parameters.get(pending.next)
+                            {
+                                let value = if value.is_empty() {
+                                    param.default.clone().unwrap_or_default()
+                                } else {
+                                    value
+                                };
+                                pending.values.insert(param.name.clone(), value);
+                                pending.next += 1;
+                            }
+                        }
+                        self.prompt_next_template_parameter();
                     }
-                    return None;
-                }
-
-                if self.query_tab != QueryTab::SqlLike {
-                    return None;
+                    TextInputEvent::Cancel => {
+                        self.query_input.clear();
+                        self.query_input.set_focused(false);
+                        self.input_mode = InputMode::Normal;
+                        self.input_type = None;
+                        self.pending_template = None;
+                    }
+                    TextInputEvent::HistoryChanged | TextInputEvent::None => {}
                 }
+                return None;
+            }
 
-                self.sql_input.set_focused(false);
-                self.fuzzy_input.set_focused(false);
+            // External command prompt: "!" then a shell command, Enter runs it with the TUI suspended
+            if self.input_type == Some(InputType::ExternalCommand) {
                 self.query_input.set_focused(true);
-                let result = self.query_input.handle_key(event, Some(&self.cache));
-
+                let result = self.query_input.handle_key(event, None);
                 match result {
-                    TextInputEvent::Submit => {
-                        // Save to history and execute query
-                        let _ = self.query_input.save_to_history(&self.cache);
-                        let query = self.query_input.value.clone();
-                        self.query_input.set_focused(false);
-                        return Some(AppEvent::Search(query));
-                    }
-                    TextInputEvent::Cancel => {
-                        // Clear and exit input mode
+                    TextInputEvent::Submit | TextInputEvent::Cancel => {
+                        let command = self.query_input.value.trim().to_string();
                         self.query_input.clear();
                         self.query_input.set_focused(false);
                         self.input_mode = InputMode::Normal;
-                        if let Some(state) = &mut self.data_table_state {
-                            // Clear error and re-enable error display in main view
-                            state.error = None;
-                            state.suppress_error_display = false;
+                        self.input_type = None;
+                        if result == TextInputEvent::Submit && !command.is_empty() {
+                            self.external_command = command.clone();
+                            match external_command::expand(&command, self.path.as_deref()) {
+                                Ok(command) => return Some(AppEvent::RunExternalCommand(command)),
+                                Err(e) => self.error_modal.show(e),
+                            }
                         }
                     }
-                    TextInputEvent::HistoryChanged => {
-                        // History navigation occurred, nothing special needed
-                    }
-                    TextInputEvent::None => {
-                        // Regular input, nothing special needed
-                    }
+                    TextInputEvent::HistoryChanged | TextInputEvent::None => {}
                 }
                 return None;
             }
 
-            // Line number input (GoToLine): ":" then type line number, Enter to jump, Esc to cancel
-            if self.input_type == Some(InputType::GoToLine) {
+            // Open file prompt: "o" then a path, Enter opens it in a new tab
+            if self.input_type == Some(InputType::OpenFile) {
                 self.query_input.set_focused(true);
                 let result = self.query_input.handle_key(event, None);
                 match result {
-                    TextInputEvent::Submit => {
-                        let value = self.query_input.value.trim().to_string();
+                    TextInputEvent::Submit | TextInputEvent::Cancel => {
+                        let path = self.query_input.value.trim().to_string();
                         self.query_input.clear();
                         self.query_input.set_focused(false);
                         self.input_mode = InputMode::Normal;
                         self.input_type = None;
-                        if let Some(state) = &mut self.data_table_state {
-                            if let Ok(display_line) = value.parse::<usize>() {
-                                let row_index =
-                                    display_line.saturating_sub(state.row_start_index());
-                                let would_collect = state.scroll_would_trigger_collect(
-                                    row_index as i64 - state.start_row as i64,
-                                );
-                                if would_collect {
-                                    self.busy = true;
-                                    return Some(AppEvent::GoToLine(row_index));
-                                }
-                                state.scroll_to_row_centered(row_index);
-                            }
+                        if result == TextInputEvent::Submit && !path.is_empty() {
+                            return self.open_file_in_tab(PathBuf::from(path));
                         }
                     }
-                    TextInputEvent::Cancel => {
+                    TextInputEvent::HistoryChanged | TextInputEvent::None => {}
+                }
+                return None;
+            }
+
+            // Profile prompt: "U" then a report path, Enter writes the report
+            if self.input_type == Some(InputType::Profile) {
+                self.query_input.set_focused(true);
+                let result = self.query_input.handle_key(event, None);
+                match result {
+                    TextInputEvent::Submit | TextInputEvent::Cancel => {
+                        let path = self.query_input.value.trim().to_string();
                         self.query_input.clear();
                         self.query_input.set_focused(false);
                         self.input_mode = InputMode::Normal;
                         self.input_type = None;
+                        if result == TextInputEvent::Submit && !path.is_empty() {
+                            return self.spawn_profile(PathBuf::from(path));
+                        }
                     }
                     TextInputEvent::HistoryChanged | TextInputEvent::None => {}
                 }
@@ -5919,6 +11255,11 @@ impl App {
             return None;
         }
 
+        // `[keys]` config: a rebound action runs as its built-in key below, and the default keys
+        // it no longer uses do nothing.
+        let remapped = self.key_bindings.remap(event)?;
+        let event = &remapped;
+
         const RIGHT_KEYS: [KeyCode; 2] = [KeyCode::Right, KeyCode::Char('l')];
 
         const LEFT_KEYS: [KeyCode; 2] = [KeyCode::Left, KeyCode::Char('h')];
@@ -5933,6 +11274,114 @@ impl App {
                 Some(AppEvent::Exit)
             }
             KeyCode::Char('R') => Some(AppEvent::Reset),
+            KeyCode::Char('v') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Some(state) = &mut self.data_table_state {
+                    state.toggle_visual_block();
+                }
+                None
+            }
+            KeyCode::Esc
+                if self
+                    .data_table_state
+                    .as_ref()
+                    .is_some_and(|s| s.visual_block().is_some()) =>
+            {
+                if let Some(state) = &mut self.data_table_state {
+                    state.clear_visual_block();
+                }
+                None
+            }
+            KeyCode::Esc
+                if self
+                    .data_table_state
+                    .as_ref()
+                    .is_some_and(|s| s.regex_search.is_some()) =>
+            {
+                if let Some(state) = &mut self.data_table_state {
+                    state.regex_search = None;
+                }
+                None
+            }
+            KeyCode::Char('n')
+                if event.modifiers.contains(KeyModifiers::ALT) && event.is_press() =>
+            {
+                self.jump_to_regex_match(false)
+            }
+            KeyCode::Char('n') if event.is_press() => self.jump_to_regex_match(true),
+            KeyCode::Char('w') if self.workspace.as_ref().is_some_and(|w| w.has_file_list()) => {
+                if let Some(ws) = self.workspace.as_mut() {
+                    ws.list_visible = true;
+                }
+                None
+            }
+            KeyCode::Char('[') => self.cycle_workspace_tab(-1),
+            KeyCode::Char(']') => self.cycle_workspace_tab(1),
+            KeyCode::Char('O') if event.is_press() => {
+                self.open_table_switcher();
+                None
+            }
+            KeyCode::Char('D') if event.is_press() => {
+                self.open_csv_dialect();
+                None
+            }
+            KeyCode::Char('B') if event.is_press() => {
+                self.open_load_report();
+                None
+            }
+            KeyCode::Char('K') if event.is_press() => {
+                self.open_schema_modal();
+                None
+            }
+            KeyCode::Char('#') if event.is_press() => {
+                self.open_columns_modal();
+                None
+            }
+            KeyCode::Char('$') if event.is_press() => {
+                self.open_scripts_modal();
+                None
+            }
+            KeyCode::Char('b')
+                if !event.modifiers.contains(KeyModifiers::CONTROL) && event.is_press() =>
+            {
+                self.open_saved_queries_modal();
+                None
+            }
+            KeyCode::Char('U') if event.is_press() => {
+                self.open_profile_prompt();
+                None
+            }
+            KeyCode::Char('p')
+                if event.modifiers.contains(KeyModifiers::CONTROL) && event.is_press() =>
+            {
+                if let Some(state) = self.data_table_state.as_ref() {
+                    let columns = state.get_column_order().to_vec();
+                    self.command_palette =
+                        Some(command_palette::CommandPalette::new(columns, &self.theme));
+                }
+                None
+            }
+            KeyCode::Char('o') if event.is_press() => {
+                // Start from the current file's directory
+                let dir = self
+                    .path
+                    .as_deref()
+                    .filter(|p| matches!(source::input_source(p), source::InputSource::Local(_)))
+                    .and_then(|p| p.parent())
+                    .filter(|d| d.is_dir())
+                    .map(Path::to_path_buf)
+                    .or_else(|| std::env::current_dir().ok())
+                    .unwrap_or_else(|| PathBuf::from("."));
+                // Absolute, so Backspace can walk up past the directory given on the command line
+                let dir = dir.canonicalize().unwrap_or(dir);
+                match file_browser::FileBrowser::open(dir) {
+                    Ok(browser) => self.file_browser = Some(browser),
+                    Err(e) => self
+                        .error_modal
+                        .show(format!("Could not list the directory: {e}")),
+                }
+                None
+            }
+            KeyCode::Char('X') if event.is_press() => self.close_workspace_tab(),
             KeyCode::Char('N') => {
                 if let Some(ref mut state) = self.data_table_state {
                     state.toggle_row_numbers();
@@ -6151,7 +11600,14 @@ impl App {
                 }
             }
             KeyCode::Enter if event.is_press() => {
-                // Only drill down if not in a modal and viewing grouped data
+                // Drill down into a group of grouped data; on any other row show the record view
+                let ungrouped = self
+                    .data_table_state
+                    .as_ref()
+                    .is_some_and(|s| !s.is_grouped() || s.is_drilled_down());
+                if self.input_mode == InputMode::Normal && ungrouped {
+                    return self.spawn_record_view();
+                }
                 let drilled = if self.input_mode == InputMode::Normal {
                     if let Some(ref mut state) = self.data_table_state {
                         if state.is_grouped() && !state.is_drilled_down() {
@@ -6202,26 +11658,24 @@ impl App {
                 None
             }
             KeyCode::Char('/') => {
-                self.input_mode = InputMode::Editing;
-                self.input_type = Some(InputType::Search);
-                self.query_tab = QueryTab::SqlLike;
-                self.query_focus = QueryFocus::Input;
-                if let Some(state) = &mut self.data_table_state {
-                    self.query_input.value = state.active_query.clone();
-                    self.query_input.cursor = self.query_input.value.chars().count();
-                    self.sql_input.value = state.get_active_sql_query().to_string();
-                    self.fuzzy_input.value = state.get_active_fuzzy_query().to_string();
-                    self.fuzzy_input.cursor = self.fuzzy_input.value.chars().count();
-                    self.sql_input.cursor = self.sql_input.value.chars().count();
-                    state.suppress_error_display = true;
-                } else {
-                    self.query_input.clear();
-                    self.sql_input.clear();
-                    self.fuzzy_input.clear();
-                }
-                self.sql_input.set_focused(false);
-                self.fuzzy_input.set_focused(false);
-                self.query_input.set_focused(true);
+                // Reopen on the SQL tab when the view comes from SQL, and on the Column tab while
+                // a column search is active, so they can be edited
+                let state = self.data_table_state.as_ref();
+                let tab = match state.and_then(|s| s.query_engine()) {
+                    Some(QueryEngine::Sql) => QueryTab::Sql,
+                    _ if state
+                        .and_then(|s| s.regex_search.as_ref())
+                        .is_some_and(|search| search.column.is_some()) =>
+                    {
+                        QueryTab::Column
+                    }
+                    _ => QueryTab::SqlLike,
+                };
+                self.open_query_dialog(tab);
+                None
+            }
+            KeyCode::Char('S') if event.is_press() => {
+                self.open_query_dialog(QueryTab::Sql);
                 None
             }
             KeyCode::Char(':') if event.is_press() => {
@@ -6234,15 +11688,34 @@ impl App {
                 }
                 None
             }
+            KeyCode::Char('|') if event.is_press() => {
+                if self.data_table_state.is_some() {
+                    self.input_mode = InputMode::Editing;
+                    self.input_type = Some(InputType::GoToColumn);
+                    self.query_input.clear();
+                    self.query_input.set_focused(true);
+                }
+                None
+            }
+            KeyCode::Char('!') if event.is_press() => {
+                self.input_mode = InputMode::Editing;
+                self.input_type = Some(InputType::ExternalCommand);
+                self.query_input.set_value(self.external_command.clone());
+                self.query_input
+                    .set_cursor(self.external_command.chars().count());
+                self.query_input.set_focused(true);
+                None
+            }
             KeyCode::Char('T') => {
                 // Apply most relevant template immediately (no modal)
+                self.template_manager.ensure_loaded();
                 if let Some(ref state) = self.data_table_state {
                     if let Some(ref path) = self.path {
                         if let Some(template) =
                             self.template_manager.get_most_relevant(path, &state.schema)
                         {
                             // Apply template settings
-                            if let Err(e) = self.apply_template(&template) {
+                            if let Err(e) = self.start_template(&template) {
                                 // Show error modal instead of just printing
                                 self.error_modal
                                     .show(format!("Error applying template: {}", e));
@@ -6254,6 +11727,7 @@ impl App {
             }
             KeyCode::Char('t') => {
                 // Open template modal
+                self.template_manager.ensure_loaded();
                 if let Some(ref state) = self.data_table_state {
                     if let Some(ref path) = self.path {
                         // Load relevant templates
@@ -6340,61 +11814,129 @@ impl App {
                 }
                 None
             }
-            KeyCode::Char('a') => {
-                // Open analysis modal; no computation until user selects a tool from the sidebar (Enter)
-                if self.data_table_state.is_some() && self.input_mode == InputMode::Normal {
-                    self.analysis_modal.open();
+            KeyCode::Char('A') => self.spawn_aggregate_peek(),
+            KeyCode::Char('F') => self.spawn_value_counts(),
+            KeyCode::Char('I') => {
+                self.column_stats.active = !self.column_stats.active;
+                None
+            }
+            KeyCode::Char('H') => {
+                self.heatmap.active = !self.heatmap.active;
+                None
+            }
+            KeyCode::Char('f') => {
+                self.pending_f = self.data_table_state.is_some();
+                None
+            }
+            KeyCode::Char('%') => {
+                self.open_format_modal();
+                None
+            }
+            KeyCode::Char('>') | KeyCode::Char('<') => {
+                if let Some(state) = &mut self.data_table_state {
+                    let step = if event.code == KeyCode::Char('>') {
+                        COLUMN_WIDTH_STEP
+                    } else {
+                        -COLUMN_WIDTH_STEP
+                    };
+                    state.resize_selected_column(step);
                 }
                 None
             }
-            KeyCode::Char('c') => {
+            KeyCode::Char('=') => {
+                if let Some(state) = &mut self.data_table_state {
+                    state.autofit_selected_column();
+                }
+                None
+            }
+            KeyCode::Char('+') => {
+                self.open_computed_column_modal();
+                None
+            }
+            KeyCode::Char('L') => {
+                self.open_rolling_modal();
+                None
+            }
+            KeyCode::Char('Z') => {
+                self.open_time_zone_modal();
+                None
+            }
+            KeyCode::Char('~') => {
+                self.open_cast_modal();
+                None
+            }
+            KeyCode::Char('u') => {
+                self.expand_selected_column();
+                None
+            }
+            KeyCode::Char('P') => {
+                if let Some(state) = self.data_table_state.as_mut() {
+                    if let Err(message) = state.toggle_pinned_row() {
+                        self.error_modal.show(message);
+                    }
+                }
+                None
+            }
+            KeyCode::Char('g') => {
+                if let Some(state) = self.data_table_state.as_ref() {
+                    let columns = state.schema.iter_names().map(|n| n.to_string()).collect();
+                    self.group_by_modal
+                        .open(columns, state.last_group_by_spec());
+                }
+                None
+            }
+            KeyCode::Char('W') => {
+                if let Some(state) = self.data_table_state.as_ref() {
+                    if state
+                        .schema
+                        .iter()
+                        .any(|(_, d)| time_gaps::is_time_dtype(d))
+                    {
+                        self.resample_modal.open(
+                            &state.schema,
+                            state.last_resample_spec(),
+                            &self.theme,
+                        );
+                    } else {
+                        self.error_modal
+                            .show("Resampling needs a date or datetime column".to_string());
+                    }
+                }
+                None
+            }
+            KeyCode::Char('M') => {
+                if let Some(state) = self.data_table_state.as_ref() {
+                    let columns = state.schema.iter_names().map(|n| n.to_string()).collect();
+                    self.sampling_modal.open(
+                        self.sampling_threshold,
+                        &self.sampling_strategy,
+                        columns,
+                        &self.theme,
+                    );
+                }
+                None
+            }
+            KeyCode::Char('a') => {
+                // Open analysis modal; no computation until user selects a tool from the sidebar (Enter)
                 if let Some(state) = &self.data_table_state {
                     if self.input_mode == InputMode::Normal {
-                        let numeric_columns: Vec<String> = state
-                            .schema
-                            .iter()
-                            .filter(|(_, dtype)| dtype.is_numeric())
-                            .map(|(name, _)| name.to_string())
-                            .collect();
-                        let datetime_columns: Vec<String> = state
-                            .schema
-                            .iter()
-                            .filter(|(_, dtype)| {
-                                matches!(
-                                    dtype,
-                                    DataType::Datetime(_, _) | DataType::Date | DataType::Time
-                                )
-                            })
-                            .map(|(name, _)| name.to_string())
-                            .collect();
-                        self.chart_modal.open(
-                            &numeric_columns,
-                            &datetime_columns,
-                            self.app_config.chart.row_limit,
-                        );
-                        self.chart_modal.x_input =
-                            std::mem::take(&mut self.chart_modal.x_input).with_theme(&self.theme);
-                        self.chart_modal.y_input =
-                            std::mem::take(&mut self.chart_modal.y_input).with_theme(&self.theme);
-                        self.chart_modal.hist_input =
-                            std::mem::take(&mut self.chart_modal.hist_input)
-                                .with_theme(&self.theme);
-                        self.chart_modal.box_input =
-                            std::mem::take(&mut self.chart_modal.box_input).with_theme(&self.theme);
-                        self.chart_modal.kde_input =
-                            std::mem::take(&mut self.chart_modal.kde_input).with_theme(&self.theme);
-                        self.chart_modal.heatmap_x_input =
-                            std::mem::take(&mut self.chart_modal.heatmap_x_input)
-                                .with_theme(&self.theme);
-                        self.chart_modal.heatmap_y_input =
-                            std::mem::take(&mut self.chart_modal.heatmap_y_input)
-                                .with_theme(&self.theme);
-                        self.chart_cache.clear();
-                        self.input_mode = InputMode::Chart;
+                        self.analysis_modal.open();
+                        // Marked columns are the Duplicate Rows tool's keys and the Crosstab's
+                        // rows, columns and values
+                        let marked = state.selected_columns();
+                        self.analysis_modal.crosstab_spec =
+                            crosstab::default_spec(&state.schema, &marked);
+                        self.analysis_modal.duplicate_keys = marked;
                     }
                 }
                 None
             }
+            KeyCode::Char('c') => {
+                if self.data_table_state.is_some() && self.input_mode == InputMode::Normal {
+                    self.open_chart();
+                }
+                None
+            }
             KeyCode::Char('p') => {
                 if let Some(state) = &self.data_table_state {
                     if self.input_mode == InputMode::Normal {
@@ -6413,21 +11955,49 @@ impl App {
             }
             KeyCode::Char('e') => {
                 if self.data_table_state.is_some() && self.input_mode == InputMode::Normal {
-                    // Load config to get delimiter preference
-                    let config_delimiter = AppConfig::load(APP_NAME)
-                        .ok()
-                        .and_then(|config| config.file_loading.delimiter);
-                    self.export_modal.open(
-                        self.original_file_format,
-                        self.history_limit,
-                        &self.theme,
-                        self.original_file_delimiter,
-                        config_delimiter,
-                    );
-                    self.input_mode = InputMode::Export;
+                    self.open_export_modal(None);
+                }
+                None
+            }
+            KeyCode::Char('E') => {
+                let selected = self.data_table_state.as_mut().and_then(|state| {
+                    let rows = state.selected_rows();
+                    if rows.is_some() {
+                        state.clear_visual_block();
+                    }
+                    rows
+                });
+                if let Some(range) = selected.or_else(|| self.selected_row_context()) {
+                    self.open_export_modal(Some(range));
                 }
                 None
             }
+            KeyCode::Char('y') => self.copy_selection(false),
+            KeyCode::Char('Y') => self.copy_selection(true),
+            KeyCode::Char('C') => self.spawn_copy_row_context(),
+            KeyCode::Char('J') => self.spawn_copy_row_json(),
+            KeyCode::Char('v') => {
+                if let Some(state) = &mut self.data_table_state {
+                    state.toggle_column_selection();
+                }
+                None
+            }
+            KeyCode::Char('V') => {
+                if let Some(state) = &mut self.data_table_state {
+                    state.clear_column_selection();
+                }
+                None
+            }
+            KeyCode::Char('m') => {
+                if let Some(state) = &mut self.data_table_state {
+                    state.toggle_row_selection();
+                }
+                None
+            }
+            KeyCode::Char('z') => {
+                self.pending_z = self.data_table_state.is_some();
+                None
+            }
             _ => None,
         }
     }
@@ -6440,32 +12010,167 @@ impl App {
                 let is_column_scroll = matches!(
                     key.code,
                     KeyCode::Left | KeyCode::Right | KeyCode::Char('h') | KeyCode::Char('l')
+                ) || matches!(
+                    self.key_bindings.action(key),
+                    Some(keymap::Action::ScrollLeft | keymap::Action::ScrollRight)
                 );
                 let is_help_key = key.code == KeyCode::F(1) || key.code == KeyCode::Char('?');
                 // Quit must always work, even mid-load — otherwise a slow collect leaves the user
                 // stuck with only Ctrl-C (which kills via SIGINT rather than quitting cleanly).
                 let is_quit_key = matches!(key.code, KeyCode::Char('q') | KeyCode::Char('Q'))
                     || (key.code == KeyCode::Char('c')
-                        && key.modifiers.contains(KeyModifiers::CONTROL));
+                        && key.modifiers.contains(KeyModifiers::CONTROL))
+                    || self.key_bindings.action(key) == Some(keymap::Action::Quit);
+                let loading = matches!(self.loading_state, LoadingState::Loading { .. });
+                if loading && self.busy && key.code == KeyCode::Esc && !self.show_help {
+                    self.cancel_load();
+                    return None;
+                }
+                // Help opened mid-load keeps scrolling while the scan runs on its worker.
+                let is_help_open = loading && self.show_help;
                 // When busy (e.g. loading), still process quit, column scroll, help, and confirmation modal keys.
                 if self.busy
                     && !is_column_scroll
                     && !is_help_key
+                    && !is_help_open
                     && !is_quit_key
                     && !self.confirmation_modal.active
                 {
-                    return None;
+                    return None;
+                }
+                let next = self.key(key);
+                self.request_column_stats();
+                self.request_heatmap_ranges();
+                next
+            }
+            AppEvent::Mouse(mouse) => {
+                if self.busy {
+                    return None;
+                }
+                let next = self.mouse(mouse);
+                self.request_column_stats();
+                self.request_heatmap_ranges();
+                next
+            }
+            AppEvent::Open(paths, options) => {
+                if paths.is_empty() {
+                    return Some(AppEvent::Crash("No paths provided".to_string()));
+                }
+                // A SQLite URL opens the file it names, with the table from `?table=`.
+                if let [path] = paths.as_slice() {
+                    if let Some((file, table)) = source::sqlite_url(path) {
+                        let mut options = options.clone();
+                        options.format.get_or_insert(FileFormat::Sqlite);
+                        if options.sql_query.is_none() {
+                            options.table = table.or(options.table);
+                        }
+                        return Some(AppEvent::Open(vec![file], options));
+                    }
+                }
+                // Wildcards in local file names (`data/2024-*.csv`) open the matching files, and a
+                // directory with --format opens its files of that format; with --hive the path
+                // goes to Polars as is.
+                let expanded;
+                let format_dir = options
+                    .format
+                    .filter(|_| paths.len() == 1 && !options.hive && paths[0].is_dir());
+                let paths = if let Some(format) = format_dir {
+                    match workspace::files_of_format(&paths[0], format) {
+                        Ok(files) => {
+                            expanded = files;
+                            &expanded
+                        }
+                        Err(e) => return Some(AppEvent::Crash(e.to_string())),
+                    }
+                } else if !options.hive && paths.iter().any(|p| source::has_wildcard(p)) {
+                    match source::expand_local_globs(
+                        paths,
+                        options.format,
+                        options.concat == ConcatMode::Diagonal,
+                    ) {
+                        Ok(files) => {
+                            expanded = files;
+                            &expanded
+                        }
+                        Err(e) => return Some(AppEvent::Crash(e.to_string())),
+                    }
+                } else {
+                    paths
+                };
+                // A plain directory (no --hive, no --format) opens the workspace file list.
+                if paths.len() == 1
+                    && !options.hive
+                    && options.format.is_none()
+                    && self.workspace.is_none()
+                    && paths[0].is_dir()
+                {
+                    return self.open_workspace(&paths[0], options);
                 }
-                self.key(key)
-            }
-            AppEvent::Open(paths, options) => {
-                if paths.is_empty() {
-                    return Some(AppEvent::Crash("No paths provided".to_string()));
+                if paths
+                    .iter()
+                    .any(|p| source::input_source(p) == source::InputSource::Stdin)
+                {
+                    if paths.len() > 1 {
+                        return Some(AppEvent::Crash(
+                            "Standard input (-) can't be combined with other paths.".to_string(),
+                        ));
+                    }
+                    return self.spawn_buffer_stdin(options);
+                }
+                // With --adjust-schema, a CSV first lists its inferred column types to change.
+                if options.adjust_schema {
+                    if let Some(path) = csv_dialect::target(paths, options) {
+                        match schema_override::SchemaModal::open(path, options.clone(), true) {
+                            Ok(modal) => {
+                                self.schema_modal = Some(modal);
+                                return None;
+                            }
+                            Err(e) => return Some(AppEvent::Crash(e.to_string())),
+                        }
+                    }
+                }
+                // A SQLite database with several tables and no --table or --sql asks which one to load.
+                if paths.len() == 1
+                    && options.table.is_none()
+                    && options.sql_query.is_none()
+                    && table_picker::TableSource::of(&paths[0], options)
+                        == Some(table_picker::TableSource::Sqlite)
+                {
+                    match table_picker::TablePicker::open(
+                        paths[0].clone(),
+                        table_picker::TableSource::Sqlite,
+                        options.clone(),
+                        None,
+                    ) {
+                        Ok(picker) if picker.tables.len() > 1 => {
+                            self.table_picker = Some(picker);
+                            return None;
+                        }
+                        Ok(_) => {}
+                        Err(e) => return Some(AppEvent::Crash(e.to_string())),
+                    }
                 }
                 #[cfg(feature = "http")]
                 if let Some(ref p) = self.http_temp_path.take() {
                     let _ = std::fs::remove_file(p);
                 }
+                self.last_open = Some((paths.clone(), options.clone()));
+                self.concat_sources = None;
+                self.loading_csv =
+                    csv_dialect::target(paths, options).map(|path| (path, options.clone()));
+                self.loading_source_files = SourceFiles::default();
+                self.source_tables = table_picker::SourceTables::for_open(paths, options);
+                self.load_profile = self.choose_load_profile(paths, options);
+                let mut options = options.clone();
+                if let Some(ref info) = self.load_profile {
+                    info.profile.apply(&mut options);
+                }
+                let configured_sampling = self.app_config.performance.sampling_threshold;
+                self.sampling_threshold = match self.load_profile {
+                    Some(ref info) => info.profile.sampling_threshold(configured_sampling),
+                    None => configured_sampling,
+                };
+                self.sampling_strategy = sampling::SamplingStrategy::default();
                 self.task_generation = self.task_generation.wrapping_add(1);
                 self.busy = true;
                 let first = &paths[0];
@@ -6475,7 +12180,10 @@ impl App {
                     }
                     source::InputSource::S3(_)
                     | source::InputSource::Gcs(_)
-                    | source::InputSource::Http(_) => 0,
+                    | source::InputSource::Azure(_)
+                    | source::InputSource::Http(_)
+                    | source::InputSource::Database(_)
+                    | source::InputSource::Stdin => 0,
                 };
                 let path_str = first.as_os_str().to_string_lossy();
                 let _is_partitioned_path = paths.len() == 1
@@ -6490,9 +12198,17 @@ impl App {
                     progress_percent: 10,
                 };
 
-                Some(AppEvent::DoLoadScanPaths(paths.clone(), options.clone()))
+                Some(AppEvent::DoLoadScanPaths(paths.clone(), options))
             }
             AppEvent::OpenLazyFrame(lf, options) => {
+                self.last_open = None;
+                self.loading_csv = None;
+                self.loading_source_files = SourceFiles::default();
+                self.load_profile = None;
+                self.concat_sources = None;
+                self.source_tables = None;
+                self.sampling_threshold = self.app_config.performance.sampling_threshold;
+                self.sampling_strategy = sampling::SamplingStrategy::default();
                 self.task_generation = self.task_generation.wrapping_add(1);
                 self.busy = true;
                 self.loading_state = LoadingState::Loading {
@@ -6518,12 +12234,24 @@ impl App {
                                 "Only one GCS URL at a time. Open a single gs:// path.".to_string(),
                             ));
                         }
+                        source::InputSource::Azure(_) => {
+                            return Some(AppEvent::Crash(
+                                "Only one Azure URL at a time. Open a single az:// path."
+                                    .to_string(),
+                            ));
+                        }
                         source::InputSource::Http(_) => {
                             return Some(AppEvent::Crash(
                                 "Only one HTTP/HTTPS URL at a time. Open a single URL.".to_string(),
                             ));
                         }
-                        source::InputSource::Local(_) => {}
+                        source::InputSource::Database(_) => {
+                            return Some(AppEvent::Crash(
+                                "Only one database URL at a time. Open a single postgres:// or mysql:// URL."
+                                    .to_string(),
+                            ));
+                        }
+                        source::InputSource::Local(_) | source::InputSource::Stdin => {}
                     }
                 }
                 let compression = options
@@ -6587,6 +12315,26 @@ impl App {
                     }
                     #[cfg(feature = "cloud")]
                     if let source::InputSource::S3(ref url) = src {
+                        // A bucket or prefix without --hive: browse its objects instead
+                        if let Some((bucket, prefix)) =
+                            source::s3_listing_prefix(url).filter(|_| !options.hive)
+                        {
+                            if let LoadingState::Loading {
+                                file_path,
+                                file_size,
+                                ..
+                            } = &self.loading_state
+                            {
+                                self.loading_state = LoadingState::Loading {
+                                    file_path: file_path.clone(),
+                                    file_size: *file_size,
+                                    current_phase: "Listing objects".to_string(),
+                                    progress_percent: 20,
+                                };
+                            }
+                            self.spawn_s3_listing(bucket, prefix, options.clone());
+                            return None;
+                        }
                         let full = format!("s3://{url}");
                         let (_, ext) = source::url_path_extension(&full);
                         let is_glob = full.contains('*') || full.ends_with('/');
@@ -6615,172 +12363,662 @@ impl App {
                                 size,
                                 options: options.clone(),
                             });
-                            self.confirmation_modal.show(message);
-                            return None;
+                            self.confirmation_modal.show(message);
+                            return None;
+                        }
+                    }
+                    #[cfg(feature = "cloud")]
+                    if let source::InputSource::Gcs(ref url) = src {
+                        let full = format!("gs://{url}");
+                        let (_, ext) = source::url_path_extension(&full);
+                        let is_glob = full.contains('*') || full.ends_with('/');
+                        if source::cloud_path_should_download(ext.as_deref(), is_glob) {
+                            let size = Self::fetch_remote_size_gcs(&full, options, &self.runtime)
+                                .unwrap_or(None);
+                            let size_str = size
+                                .map(Self::format_bytes)
+                                .unwrap_or_else(|| "unknown".to_string());
+                            let dest_dir = options
+                                .temp_dir
+                                .as_deref()
+                                .map(|p| p.display().to_string())
+                                .unwrap_or_else(|| std::env::temp_dir().display().to_string());
+                            let message = format!(
+                                "URL: {}\nFile size: {}\nDestination: {} (temporary file)\n\nContinue with download?",
+                                full, size_str, dest_dir
+                            );
+                            self.pending_download = Some(PendingDownload::Gcs {
+                                url: full,
+                                size,
+                                options: options.clone(),
+                            });
+                            self.confirmation_modal.show(message);
+                            return None;
+                        }
+                    }
+                    #[cfg(feature = "cloud")]
+                    if let source::InputSource::Azure(ref full) = src {
+                        let (_, ext) = source::url_path_extension(full);
+                        let is_glob = full.contains('*') || full.ends_with('/');
+                        if source::cloud_path_should_download(ext.as_deref(), is_glob) {
+                            let size = Self::fetch_remote_size_azure(
+                                full,
+                                &self.app_config.cloud,
+                                options,
+                                &self.runtime,
+                            )
+                            .unwrap_or(None);
+                            let size_str = size
+                                .map(Self::format_bytes)
+                                .unwrap_or_else(|| "unknown".to_string());
+                            let dest_dir = options
+                                .temp_dir
+                                .as_deref()
+                                .map(|p| p.display().to_string())
+                                .unwrap_or_else(|| std::env::temp_dir().display().to_string());
+                            let message = format!(
+                                "URL: {}\nFile size: {}\nDestination: {} (temporary file)\n\nContinue with download?",
+                                full, size_str, dest_dir
+                            );
+                            self.pending_download = Some(PendingDownload::Azure {
+                                url: full.clone(),
+                                size,
+                                options: options.clone(),
+                            });
+                            self.confirmation_modal.show(message);
+                            return None;
+                        }
+                    }
+                    // With --parse-strings the scan reads the string columns of the CSV
+                    if paths.len() == 1 && is_csv && options.parse_strings.is_some() {
+                        if let LoadingState::Loading {
+                            file_path,
+                            file_size,
+                            ..
+                        } = &self.loading_state
+                        {
+                            self.loading_state = LoadingState::Loading {
+                                file_path: file_path.clone(),
+                                file_size: *file_size,
+                                current_phase: "Scanning string columns".to_string(),
+                                progress_percent: 55,
+                            };
+                        }
+                    }
+                    self.spawn_scan(paths.clone(), Some(paths[0].clone()), options.clone())
+                }
+            }
+            AppEvent::BackgroundLoadProgress {
+                generation,
+                phase,
+                progress_percent,
+            } => {
+                if *generation == self.task_generation {
+                    if let LoadingState::Loading {
+                        file_path,
+                        file_size,
+                        ..
+                    } = &self.loading_state
+                    {
+                        self.loading_state = LoadingState::Loading {
+                            file_path: file_path.clone(),
+                            file_size: *file_size,
+                            current_phase: phase.clone(),
+                            progress_percent: *progress_percent,
+                        };
+                    }
+                }
+                None
+            }
+            AppEvent::BackgroundScanReady {
+                generation,
+                path,
+                options,
+            } => {
+                let slot = self.pending_scan_result.lock().ok().and_then(|mut slot| {
+                    // A cancelled scan that finished late: drop its result (and temp files) unless
+                    // a newer scan has replaced it.
+                    if slot.as_ref().is_some_and(|(gen, _)| gen == generation) {
+                        slot.take()
+                    } else {
+                        None
+                    }
+                });
+                if *generation != self.task_generation {
+                    return None;
+                }
+                let result = slot.map(|(_, result)| result)?;
+                let (lf, extras) = match result {
+                    Ok(scanned) => scanned,
+                    Err(msg) => {
+                        self.loading_state = LoadingState::Idle;
+                        self.busy = false;
+                        self.status_message = None;
+                        self.drain_keys_on_next_loop = true;
+                        return Some(AppEvent::Crash(msg));
+                    }
+                };
+                self.concat_sources = extras.concat_sources;
+                self.loading_source_files = extras.source_files;
+                if let LoadingState::Loading {
+                    file_path,
+                    file_size,
+                    ..
+                } = &self.loading_state
+                {
+                    self.loading_state = LoadingState::Loading {
+                        file_path: file_path.clone(),
+                        file_size: *file_size,
+                        current_phase: "Caching schema".to_string(),
+                        progress_percent: 40,
+                    };
+                }
+                Some(AppEvent::DoLoadSchema(
+                    Box::new(lf),
+                    path.clone(),
+                    options.clone(),
+                ))
+            }
+            #[cfg(feature = "http")]
+            AppEvent::DoDownloadHttp(url, options) => {
+                let url = url.clone();
+                let options = options.clone();
+                self.spawn_bg("Downloading...", move |gen, tx| {
+                    let (_, ext) = source::url_path_extension(url.as_str());
+                    match Self::download_http_to_temp(
+                        url.as_str(),
+                        options.temp_dir.as_deref(),
+                        ext.as_deref(),
+                    ) {
+                        Ok(temp_path) => {
+                            let _ = tx.send(AppEvent::BackgroundDownloadReady {
+                                generation: gen,
+                                temp_path,
+                                options,
+                            });
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::BackgroundError {
+                                generation: gen,
+                                message: crate::error_display::user_message_from_report(&e, None),
+                            });
+                        }
+                    }
+                });
+                None
+            }
+            #[cfg(feature = "cloud")]
+            AppEvent::DoDownloadS3ToTemp(s3_url, options) => {
+                let s3_url = s3_url.clone();
+                let cloud_config = self.app_config.cloud.clone();
+                let options = options.clone();
+                let rt = self.runtime.clone();
+                self.spawn_bg("Downloading from S3...", move |gen, tx| {
+                    match Self::download_s3_to_temp(&s3_url, &cloud_config, &options, &rt) {
+                        Ok(temp_path) => {
+                            let _ = tx.send(AppEvent::BackgroundDownloadReady {
+                                generation: gen,
+                                temp_path,
+                                options,
+                            });
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::BackgroundError {
+                                generation: gen,
+                                message: crate::error_display::user_message_from_report(&e, None),
+                            });
+                        }
+                    }
+                });
+                None
+            }
+            #[cfg(feature = "cloud")]
+            AppEvent::DoDownloadGcsToTemp(gs_url, options) => {
+                let gs_url = gs_url.clone();
+                let options = options.clone();
+                let rt = self.runtime.clone();
+                self.spawn_bg("Downloading from GCS...", move |gen, tx| {
+                    match Self::download_gcs_to_temp(&gs_url, &options, &rt) {
+                        Ok(temp_path) => {
+                            let _ = tx.send(AppEvent::BackgroundDownloadReady {
+                                generation: gen,
+                                temp_path,
+                                options,
+                            });
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::BackgroundError {
+                                generation: gen,
+                                message: crate::error_display::user_message_from_report(&e, None),
+                            });
+                        }
+                    }
+                });
+                None
+            }
+            #[cfg(feature = "cloud")]
+            AppEvent::DoDownloadAzureToTemp(az_url, options) => {
+                let az_url = az_url.clone();
+                let cloud_config = self.app_config.cloud.clone();
+                let options = options.clone();
+                let rt = self.runtime.clone();
+                self.spawn_bg("Downloading from Azure...", move |gen, tx| {
+                    match Self::download_azure_to_temp(&az_url, &cloud_config, &options, &rt) {
+                        Ok(temp_path) => {
+                            let _ = tx.send(AppEvent::BackgroundDownloadReady {
+                                generation: gen,
+                                temp_path,
+                                options,
+                            });
                         }
-                    }
-                    #[cfg(feature = "cloud")]
-                    if let source::InputSource::Gcs(ref url) = src {
-                        let full = format!("gs://{url}");
-                        let (_, ext) = source::url_path_extension(&full);
-                        let is_glob = full.contains('*') || full.ends_with('/');
-                        if source::cloud_path_should_download(ext.as_deref(), is_glob) {
-                            let size = Self::fetch_remote_size_gcs(&full, options, &self.runtime)
-                                .unwrap_or(None);
-                            let size_str = size
-                                .map(Self::format_bytes)
-                                .unwrap_or_else(|| "unknown".to_string());
-                            let dest_dir = options
-                                .temp_dir
-                                .as_deref()
-                                .map(|p| p.display().to_string())
-                                .unwrap_or_else(|| std::env::temp_dir().display().to_string());
-                            let message = format!(
-                                "URL: {}\nFile size: {}\nDestination: {} (temporary file)\n\nContinue with download?",
-                                full, size_str, dest_dir
-                            );
-                            self.pending_download = Some(PendingDownload::Gcs {
-                                url: full,
-                                size,
-                                options: options.clone(),
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::BackgroundError {
+                                generation: gen,
+                                message: crate::error_display::user_message_from_report(&e, None),
                             });
-                            self.confirmation_modal.show(message);
-                            return None;
                         }
                     }
-                    let first = paths[0].clone();
-                    // When CSV with --parse-strings, set "Scanning string columns" and defer build so UI can show it before blocking.
-                    if paths.len() == 1 && is_csv && options.parse_strings.is_some() {
-                        if let LoadingState::Loading {
-                            file_path,
-                            file_size,
-                            ..
-                        } = &self.loading_state
+                });
+                None
+            }
+            AppEvent::BackgroundStdinReady {
+                generation,
+                temp_path,
+                options,
+            } => {
+                if *generation != self.task_generation {
+                    return None;
+                }
+                match temp_path {
+                    Ok(path) => {
+                        self.stdin_temp_path = Some(tempfile::TempPath::from_path(path));
+                        Some(AppEvent::Open(vec![path.clone()], options.clone()))
+                    }
+                    Err(message) => {
+                        self.loading_state = LoadingState::Idle;
+                        self.busy = false;
+                        self.drain_keys_on_next_loop = true;
+                        Some(AppEvent::Crash(message.clone()))
+                    }
+                }
+            }
+            #[cfg(any(feature = "http", feature = "cloud"))]
+            AppEvent::BackgroundDownloadReady {
+                generation,
+                temp_path,
+                options,
+            } => {
+                if *generation == self.task_generation {
+                    self.http_temp_path = Some(temp_path.clone());
+                    if let LoadingState::Loading {
+                        file_path,
+                        file_size,
+                        ..
+                    } = &self.loading_state
+                    {
+                        self.loading_state = LoadingState::Loading {
+                            file_path: file_path.clone(),
+                            file_size: *file_size,
+                            current_phase: "Scanning".to_string(),
+                            progress_percent: 30,
+                        };
+                    }
+                    self.status_message = Some("Scanning...".to_string());
+                    return Some(AppEvent::DoLoadFromHttpTemp(
+                        temp_path.clone(),
+                        options.clone(),
+                    ));
+                }
+                // Stale download result — ignore.
+                None
+            }
+            #[cfg(any(feature = "http", feature = "cloud"))]
+            AppEvent::DoLoadFromHttpTemp(temp_path, options) => {
+                self.http_temp_path = Some(temp_path.clone());
+                let display_path = match &self.loading_state {
+                    LoadingState::Loading { file_path, .. } => file_path.clone(),
+                    _ => None,
+                };
+                if let LoadingState::Loading {
+                    file_path,
+                    file_size,
+                    ..
+                } = &self.loading_state
+                {
+                    self.loading_state = LoadingState::Loading {
+                        file_path: file_path.clone(),
+                        file_size: *file_size,
+                        current_phase: "Scanning".to_string(),
+                        progress_percent: 30,
+                    };
+                }
+                self.spawn_scan(vec![temp_path.clone()], display_path, options.clone())
+            }
+            AppEvent::DoLoadSchema(lf, path, options) => {
+                // Set "Caching schema" and return so the UI draws this phase before we block in DoLoadSchemaBlocking
+                if let LoadingState::Loading {
+                    file_path,
+                    file_size,
+                    ..
+                } = &self.loading_state
+                {
+                    self.loading_state = LoadingState::Loading {
+                        file_path: file_path.clone(),
+                        file_size: *file_size,
+                        current_phase: "Caching schema".to_string(),
+                        progress_percent: 40,
+                    };
+                }
+                Some(AppEvent::DoLoadSchemaBlocking(
+                    lf.clone(),
+                    path.clone(),
+                    options.clone(),
+                ))
+            }
+            AppEvent::DoLoadSchemaBlocking(lf, path, options) => {
+                self.debug.schema_load = None;
+                // Fast path for hive directory: infer schema from one parquet file instead of collect_schema() over all files.
+                // The one-file schema has no source column, so --source-column takes the full scan.
+                if options.single_spine_schema
+                    && !options.source_column
+                    && path.as_ref().is_some_and(|p| p.is_dir() && options.hive)
+                {
+                    let p = path.as_ref().expect("path set by caller");
+                    if let Ok((merged_schema, partition_columns)) =
+                        DataTableState::schema_from_one_hive_parquet(p)
+                    {
+                        if let Ok(lf_owned) =
+                            DataTableState::scan_parquet_hive_with_schema(p, merged_schema.clone())
                         {
-                            self.loading_state = LoadingState::Loading {
-                                file_path: file_path.clone(),
-                                file_size: *file_size,
-                                current_phase: "Scanning string columns".to_string(),
-                                progress_percent: 55,
-                            };
+                            match DataTableState::from_schema_and_lazyframe(
+                                merged_schema,
+                                lf_owned,
+                                options,
+                                Some(partition_columns),
+                            ) {
+                                Ok(state) => {
+                                    self.apply_schema_ready(
+                                        state,
+                                        path.clone(),
+                                        options,
+                                        Some("one-file (local)".to_string()),
+                                    );
+                                    return Some(AppEvent::DoLoadBuffer);
+                                }
+                                Err(e) => {
+                                    self.loading_state = LoadingState::Idle;
+                                    self.busy = false;
+                                    self.drain_keys_on_next_loop = true;
+                                    let msg =
+                                        crate::error_display::user_message_from_report(&e, None);
+                                    return Some(AppEvent::Crash(msg));
+                                }
+                            }
                         }
-                        return Some(AppEvent::DoLoadCsvWithParseStrings(
-                            paths.clone(),
-                            options.clone(),
-                        ));
                     }
-                    #[allow(clippy::needless_borrow)]
-                    match self.build_lazyframe_from_paths(&paths, options) {
-                        Ok(lf) => {
-                            if let LoadingState::Loading {
-                                file_path,
-                                file_size,
-                                ..
-                            } = &self.loading_state
-                            {
-                                self.loading_state = LoadingState::Loading {
-                                    file_path: file_path.clone(),
-                                    file_size: *file_size,
-                                    current_phase: "Caching schema".to_string(),
-                                    progress_percent: 40,
-                                };
+                }
+
+                #[cfg(feature = "cloud")]
+                {
+                    // Use fast path for directory/glob cloud URLs (same as build_lazyframe_from_paths).
+                    // Don't require --hive: path shape already implies hive scan.
+                    if options.single_spine_schema
+                        && !options.source_column
+                        && path.as_ref().is_some_and(|p| {
+                            let s = p.as_os_str().to_string_lossy();
+                            let is_cloud = s.starts_with("s3://")
+                                || s.starts_with("gs://")
+                                || matches!(source::input_source(p), source::InputSource::Azure(_));
+                            let looks_like_hive = s.ends_with('/') || s.contains('*');
+                            is_cloud && (options.hive || looks_like_hive)
+                        })
+                    {
+                        self.debug.schema_load = Some("trying one-file (cloud)".to_string());
+                        let src = source::input_source(path.as_ref().expect("path set by caller"));
+                        let try_cloud = match &src {
+                            source::InputSource::S3(url) => {
+                                let full = format!("s3://{url}");
+                                let (path_part, _) = source::url_path_extension(&full);
+                                let key = path_part
+                                    .split_once('/')
+                                    .map(|(_, k)| k.trim_end_matches('/'))
+                                    .unwrap_or("");
+                                let cloud_opts =
+                                    Self::build_s3_cloud_options(&self.app_config.cloud, options);
+                                Self::build_s3_object_store(&full, &self.app_config.cloud, options)
+                                    .ok()
+                                    .and_then(|store| {
+                                        let (merged_schema, partition_columns) = self
+                                            .runtime
+                                            .block_on(cloud_hive::schema_from_one_cloud_hive(
+                                                store, key,
+                                            ))
+                                            .ok()?;
+                                        let pl_path = PlPathRef::new(&full).into_owned();
+                                        let args = ScanArgsParquet {
+                                            schema: Some(merged_schema.clone()),
+                                            cloud_options: Some(cloud_opts),
+                                            hive_options: polars::io::HiveOptions::new_enabled(),
+                                            glob: true,
+                                            ..Default::default()
+                                        };
+                                        let mut lf_owned =
+                                            LazyFrame::scan_parquet(pl_path, args).ok()?;
+                                        if !partition_columns.is_empty() {
+                                            let exprs: Vec<_> = partition_columns
+                                                .iter()
+                                                .map(|s| col(s.as_str()))
+                                                .chain(
+                                                    merged_schema
+                                                        .iter_names()
+                                                        .map(|s| s.to_string())
+                                                        .filter(|c| !partition_columns.contains(c))
+                                                        .map(|s| col(s.as_str())),
+                                                )
+                                                .collect();
+                                            lf_owned = lf_owned.select(exprs);
+                                        }
+                                        DataTableState::from_schema_and_lazyframe(
+                                            merged_schema,
+                                            lf_owned,
+                                            options,
+                                            Some(partition_columns),
+                                        )
+                                        .ok()
+                                    })
+                            }
+                            source::InputSource::Gcs(url) => {
+                                let full = format!("gs://{url}");
+                                let (path_part, _) = source::url_path_extension(&full);
+                                let key = path_part
+                                    .split_once('/')
+                                    .map(|(_, k)| k.trim_end_matches('/'))
+                                    .unwrap_or("");
+                                Self::build_gcs_object_store(&full).ok().and_then(|store| {
+                                    let (merged_schema, partition_columns) = self
+                                        .runtime
+                                        .block_on(cloud_hive::schema_from_one_cloud_hive(
+                                            store, key,
+                                        ))
+                                        .ok()?;
+                                    let pl_path = PlPathRef::new(&full).into_owned();
+                                    let args = ScanArgsParquet {
+                                        schema: Some(merged_schema.clone()),
+                                        cloud_options: Some(CloudOptions::default()),
+                                        hive_options: polars::io::HiveOptions::new_enabled(),
+                                        glob: true,
+                                        ..Default::default()
+                                    };
+                                    let mut lf_owned =
+                                        LazyFrame::scan_parquet(pl_path, args).ok()?;
+                                    if !partition_columns.is_empty() {
+                                        let exprs: Vec<_> = partition_columns
+                                            .iter()
+                                            .map(|s| col(s.as_str()))
+                                            .chain(
+                                                merged_schema
+                                                    .iter_names()
+                                                    .map(|s| s.to_string())
+                                                    .filter(|c| !partition_columns.contains(c))
+                                                    .map(|s| col(s.as_str())),
+                                            )
+                                            .collect();
+                                        lf_owned = lf_owned.select(exprs);
+                                    }
+                                    DataTableState::from_schema_and_lazyframe(
+                                        merged_schema,
+                                        lf_owned,
+                                        options,
+                                        Some(partition_columns),
+                                    )
+                                    .ok()
+                                })
                             }
-                            Some(AppEvent::DoLoadSchema(
-                                Box::new(lf),
-                                Some(first),
-                                options.clone(),
-                            ))
-                        }
-                        Err(e) => {
-                            self.loading_state = LoadingState::Idle;
-                            self.busy = false;
-                            self.drain_keys_on_next_loop = true;
-                            let msg = crate::error_display::user_message_from_report(
-                                &e,
-                                paths.first().map(|p| p.as_path()),
+                            source::InputSource::Azure(full) => {
+                                let key = Self::azure_object_key(full).unwrap_or_default();
+                                let key = key.trim_end_matches('/');
+                                let cloud_opts = Self::build_azure_cloud_options(
+                                    &self.app_config.cloud,
+                                    options,
+                                );
+                                Self::build_azure_object_store(
+                                    full,
+                                    &self.app_config.cloud,
+                                    options,
+                                )
+                                .ok()
+                                .and_then(|store| {
+                                    let (merged_schema, partition_columns) = self
+                                        .runtime
+                                        .block_on(cloud_hive::schema_from_one_cloud_hive(
+                                            store, key,
+                                        ))
+                                        .ok()?;
+                                    let pl_path = PlPathRef::new(full).into_owned();
+                                    let args = ScanArgsParquet {
+                                        schema: Some(merged_schema.clone()),
+                                        cloud_options: Some(cloud_opts),
+                                        hive_options: polars::io::HiveOptions::new_enabled(),
+                                        glob: true,
+                                        ..Default::default()
+                                    };
+                                    let mut lf_owned =
+                                        LazyFrame::scan_parquet(pl_path, args).ok()?;
+                                    if !partition_columns.is_empty() {
+                                        let exprs: Vec<_> = partition_columns
+                                            .iter()
+                                            .map(|s| col(s.as_str()))
+                                            .chain(
+                                                merged_schema
+                                                    .iter_names()
+                                                    .map(|s| s.to_string())
+                                                    .filter(|c| !partition_columns.contains(c))
+                                                    .map(|s| col(s.as_str())),
+                                            )
+                                            .collect();
+                                        lf_owned = lf_owned.select(exprs);
+                                    }
+                                    DataTableState::from_schema_and_lazyframe(
+                                        merged_schema,
+                                        lf_owned,
+                                        options,
+                                        Some(partition_columns),
+                                    )
+                                    .ok()
+                                })
+                            }
+                            _ => None,
+                        };
+                        if let Some(state) = try_cloud {
+                            self.apply_schema_ready(
+                                state,
+                                path.clone(),
+                                options,
+                                Some("one-file (cloud)".to_string()),
                             );
-                            Some(AppEvent::Crash(msg))
-                        }
-                    }
-                }
-            }
-            AppEvent::DoLoadCsvWithParseStrings(paths, options) => {
-                let first = paths[0].clone();
-                #[allow(clippy::needless_borrow)]
-                match self.build_lazyframe_from_paths(&paths, options) {
-                    Ok(lf) => {
-                        if let LoadingState::Loading {
-                            file_path,
-                            file_size,
-                            ..
-                        } = &self.loading_state
-                        {
-                            self.loading_state = LoadingState::Loading {
-                                file_path: file_path.clone(),
-                                file_size: *file_size,
-                                current_phase: "Caching schema".to_string(),
-                                progress_percent: 40,
-                            };
+                            return Some(AppEvent::DoLoadBuffer);
+                        } else {
+                            self.debug.schema_load = Some("fallback (cloud)".to_string());
                         }
-                        Some(AppEvent::DoLoadSchema(
-                            Box::new(lf),
-                            Some(first),
-                            options.clone(),
-                        ))
-                    }
-                    Err(e) => {
-                        self.loading_state = LoadingState::Idle;
-                        self.busy = false;
-                        self.drain_keys_on_next_loop = true;
-                        let msg = crate::error_display::user_message_from_report(
-                            &e,
-                            paths.first().map(|p| p.as_path()),
-                        );
-                        Some(AppEvent::Crash(msg))
                     }
                 }
-            }
-            #[cfg(feature = "http")]
-            AppEvent::DoDownloadHttp(url, options) => {
-                let url = url.clone();
-                let options = options.clone();
-                self.spawn_bg("Downloading...", move |gen, tx| {
-                    let (_, ext) = source::url_path_extension(url.as_str());
-                    match Self::download_http_to_temp(
-                        url.as_str(),
-                        options.temp_dir.as_deref(),
-                        ext.as_deref(),
-                    ) {
-                        Ok(temp_path) => {
-                            let _ = tx.send(AppEvent::BackgroundDownloadReady {
-                                generation: gen,
-                                temp_path,
-                                options,
-                            });
-                        }
+
+                // General path: collect_schema() may be slow. Spawn to background.
+                let debug_label = if self.debug.schema_load.is_none() {
+                    Some("full scan".to_string())
+                } else {
+                    self.debug.schema_load.clone()
+                };
+                let lf_clone = (**lf).clone();
+                let path_clone = path.clone();
+                let options_clone = options.clone();
+                let schema_slot = self.pending_schema_result.clone();
+                self.spawn_bg("Caching schema...", move |gen, tx| {
+                    let mut lf_owned = lf_clone;
+                    let schema = match lf_owned.collect_schema() {
+                        Ok(s) => s,
                         Err(e) => {
+                            let report = color_eyre::eyre::Report::from(e);
                             let _ = tx.send(AppEvent::BackgroundError {
                                 generation: gen,
-                                message: crate::error_display::user_message_from_report(&e, None),
+                                message: crate::error_display::user_message_from_report(
+                                    &report, None,
+                                ),
                             });
+                            return;
                         }
+                    };
+                    let partition_columns = if path_clone.as_ref().is_some_and(|p| {
+                        options_clone.hive
+                            && (p.is_dir() || p.as_os_str().to_string_lossy().contains('*'))
+                    }) {
+                        let discovered = DataTableState::discover_hive_partition_columns(
+                            path_clone.as_ref().expect("path set by caller"),
+                        );
+                        discovered
+                            .into_iter()
+                            .filter(|c| schema.contains(c.as_str()))
+                            .collect::<Vec<_>>()
+                    } else {
+                        Vec::new()
+                    };
+                    if !partition_columns.is_empty() {
+                        let exprs: Vec<_> = partition_columns
+                            .iter()
+                            .map(|s| col(s.as_str()))
+                            .chain(
+                                schema
+                                    .iter_names()
+                                    .map(|s| s.to_string())
+                                    .filter(|c| !partition_columns.contains(c))
+                                    .map(|s| col(s.as_str())),
+                            )
+                            .collect();
+                        lf_owned = lf_owned.select(exprs);
                     }
-                });
-                None
-            }
-            #[cfg(feature = "cloud")]
-            AppEvent::DoDownloadS3ToTemp(s3_url, options) => {
-                let s3_url = s3_url.clone();
-                let cloud_config = self.app_config.cloud.clone();
-                let options = options.clone();
-                let rt = self.runtime.clone();
-                self.spawn_bg("Downloading from S3...", move |gen, tx| {
-                    match Self::download_s3_to_temp(&s3_url, &cloud_config, &options, &rt) {
-                        Ok(temp_path) => {
-                            let _ = tx.send(AppEvent::BackgroundDownloadReady {
+                    let part_cols_opt = if partition_columns.is_empty() {
+                        None
+                    } else {
+                        Some(partition_columns)
+                    };
+                    match DataTableState::from_schema_and_lazyframe(
+                        schema,
+                        lf_owned,
+                        &options_clone,
+                        part_cols_opt,
+                    ) {
+                        Ok(state) => {
+                            let mut slot = schema_slot.lock().unwrap_or_else(|e| e.into_inner());
+                            let dominated = slot.as_ref().is_some_and(|(g, _)| *g > gen);
+                            if !dominated {
+                                *slot = Some((gen, state));
+                            }
+                            drop(slot);
+                            let _ = tx.send(AppEvent::BackgroundSchemaReady {
                                 generation: gen,
-                                temp_path,
-                                options,
+                                path: path_clone,
+                                options: options_clone,
+                                debug_label,
                             });
                         }
                         Err(e) => {
@@ -6792,39 +13030,36 @@ impl App {
                     }
                 });
                 None
-            }
-            #[cfg(feature = "cloud")]
-            AppEvent::DoDownloadGcsToTemp(gs_url, options) => {
-                let gs_url = gs_url.clone();
-                let options = options.clone();
-                let rt = self.runtime.clone();
-                self.spawn_bg("Downloading from GCS...", move |gen, tx| {
-                    match Self::download_gcs_to_temp(&gs_url, &options, &rt) {
-                        Ok(temp_path) => {
-                            let _ = tx.send(AppEvent::BackgroundDownloadReady {
-                                generation: gen,
-                                temp_path,
-                                options,
-                            });
-                        }
-                        Err(e) => {
-                            let _ = tx.send(AppEvent::BackgroundError {
-                                generation: gen,
-                                message: crate::error_display::user_message_from_report(&e, None),
-                            });
-                        }
-                    }
-                });
+            }
+            AppEvent::DoLoadBuffer => {
+                if self.spawn_in_memory_load() {
+                    return None;
+                }
+                self.load_first_buffer();
                 None
             }
-            #[cfg(any(feature = "http", feature = "cloud"))]
-            AppEvent::BackgroundDownloadReady {
-                generation,
-                temp_path,
-                options,
-            } => {
-                if *generation == self.task_generation {
-                    self.http_temp_path = Some(temp_path.clone());
+            AppEvent::DoLoad(paths, options) => {
+                let first = &paths[0];
+                // Check if file is compressed (only single-file compressed CSV supported for now)
+                let compression = options
+                    .compression
+                    .or_else(|| CompressionFormat::from_extension(first));
+                let is_csv = first
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .map(|stem| {
+                        stem.ends_with(".csv")
+                            || first
+                                .extension()
+                                .and_then(|e| e.to_str())
+                                .map(|e| e.eq_ignore_ascii_case("csv"))
+                                .unwrap_or(false)
+                    })
+                    .unwrap_or(false);
+                let is_compressed_csv = paths.len() == 1 && compression.is_some() && is_csv;
+
+                if is_compressed_csv {
+                    // Set "Decompressing" phase and return event to trigger render
                     if let LoadingState::Loading {
                         file_path,
                         file_size,
@@ -6834,775 +13069,789 @@ impl App {
                         self.loading_state = LoadingState::Loading {
                             file_path: file_path.clone(),
                             file_size: *file_size,
-                            current_phase: "Scanning".to_string(),
+                            current_phase: "Decompressing".to_string(),
                             progress_percent: 30,
                         };
                     }
-                    self.status_message = Some("Scanning...".to_string());
-                    return Some(AppEvent::DoLoadFromHttpTemp(
-                        temp_path.clone(),
-                        options.clone(),
-                    ));
+                    // Return DoDecompress to allow UI to render "Decompressing" before blocking
+                    Some(AppEvent::DoDecompress(paths.clone(), options.clone()))
+                } else {
+                    // For non-compressed files, proceed with normal loading
+                    match self.load(paths, options) {
+                        Ok(_) => {
+                            self.busy = false;
+                            self.drain_keys_on_next_loop = true;
+                            Some(AppEvent::Collect)
+                        }
+                        Err(e) => {
+                            self.loading_state = LoadingState::Idle;
+                            self.busy = false;
+                            self.drain_keys_on_next_loop = true;
+                            let msg = crate::error_display::user_message_from_report(
+                                &e,
+                                paths.first().map(|p| p.as_path()),
+                            );
+                            Some(AppEvent::Crash(msg))
+                        }
+                    }
+                }
+            }
+            AppEvent::DoDecompress(paths, options) => {
+                // Actually perform decompression now (after UI has rendered "Decompressing")
+                match self.load(paths, options) {
+                    Ok(_) => Some(AppEvent::DoLoadBuffer),
+                    Err(e) => {
+                        self.loading_state = LoadingState::Idle;
+                        self.busy = false;
+                        self.drain_keys_on_next_loop = true;
+                        let msg = crate::error_display::user_message_from_report(
+                            &e,
+                            paths.first().map(|p| p.as_path()),
+                        );
+                        Some(AppEvent::Crash(msg))
+                    }
+                }
+            }
+            AppEvent::Resize(_cols, _rows) => {
+                // No work here: the next render sets visible_rows and flips needs_recollect,
+                // which the main loop turns into an async collect against the correct size.
+                None
+            }
+            AppEvent::Collect => {
+                self.spawn_async_collect("Loading buffer...");
+                None
+            }
+            AppEvent::DoScrollDown => self.handle_scroll(|s| s.page_down()),
+            AppEvent::DoScrollUp => self.handle_scroll(|s| s.page_up()),
+            AppEvent::DoScrollNext => self.handle_scroll(|s| s.select_next()),
+            AppEvent::DoScrollPrev => self.handle_scroll(|s| s.select_previous()),
+            AppEvent::DoScrollEnd => self.handle_scroll(|s| s.scroll_to_end()),
+            AppEvent::DoScrollHalfDown => self.handle_scroll(|s| s.half_page_down()),
+            AppEvent::DoScrollHalfUp => self.handle_scroll(|s| s.half_page_up()),
+            AppEvent::DoScrollRows(rows) => {
+                let rows = *rows;
+                self.handle_scroll(|s| s.slide_table(rows))
+            }
+            AppEvent::GoToLine(n) => {
+                let n = *n;
+                self.handle_scroll(|s| s.scroll_to_row_centered(n))
+            }
+            AppEvent::AnalysisChunk => {
+                // Stub out binary columns: their blobs are never read for analysis (reading
+                // multi-GB blobs across partitions can exhaust memory and freeze the process).
+                let lf = match &self.data_table_state {
+                    Some(state) => state.lf.clone().select(state.binary_stub_exprs()),
+                    None => {
+                        self.analysis_computation = None;
+                        self.analysis_modal.computing = None;
+                        self.busy = false;
+                        return None;
+                    }
+                };
+                let comp = self.analysis_computation.take()?;
+                if comp.df.is_none() {
+                    let cached_rows = self
+                        .data_table_state
+                        .as_ref()
+                        .and_then(|s| s.num_rows_if_valid());
+                    let threshold = self.sampling_threshold;
+                    let sampling = self.sampling_strategy.with_fallback_seed(comp.sample_seed);
+                    let streaming = self.app_config.performance.polars_streaming;
+                    self.spawn_bg("Computing statistics...", move |gen, tx| {
+                        let total_rows = match cached_rows {
+                            Some(n) => n,
+                            None => match crate::statistics::collect_lazy(
+                                lf.clone().select([len()]),
+                                streaming,
+                            ) {
+                                Ok(count_df) => match count_df.get(0) {
+                                    Some(col) => match col.first() {
+                                        Some(AnyValue::UInt32(n)) => *n as usize,
+                                        _ => 0,
+                                    },
+                                    None => 0,
+                                },
+                                Err(e) => {
+                                    let _ = tx.send(AppEvent::BackgroundError {
+                                        generation: gen,
+                                        message: format!("{e}"),
+                                    });
+                                    return;
+                                }
+                            },
+                        };
+                        match crate::statistics::compute_describe_from_lazy(
+                            &lf, total_rows, threshold, &sampling, streaming,
+                        ) {
+                            Ok(results) => {
+                                let _ = tx.send(AppEvent::BackgroundDescribeReady {
+                                    generation: gen,
+                                    results,
+                                });
+                            }
+                            Err(e) => {
+                                let _ = tx.send(AppEvent::BackgroundError {
+                                    generation: gen,
+                                    message: format!("{e}"),
+                                });
+                            }
+                        }
+                    });
+                }
+                None
+            }
+            AppEvent::AnalysisDistributionCompute => {
+                if let Some(state) = &self.data_table_state {
+                    // Stub binary columns so their blobs are never materialized (see AnalysisChunk).
+                    let lf = state.lf.clone().select(state.binary_stub_exprs());
+                    let threshold = self.sampling_threshold;
+                    let sampling = self.sampling();
+                    let streaming = self.app_config.performance.polars_streaming;
+                    self.spawn_bg("Analyzing distributions...", move |gen, tx| {
+                        let options = crate::statistics::ComputeOptions {
+                            include_distribution_info: true,
+                            include_distribution_analyses: true,
+                            include_correlation_matrix: false,
+                            include_skewness_kurtosis_outliers: true,
+                            polars_streaming: streaming,
+                        };
+                        match crate::statistics::compute_statistics_with_options(
+                            &lf, threshold, &sampling, options,
+                        ) {
+                            Ok(results) => {
+                                let _ = tx.send(AppEvent::BackgroundDistributionReady {
+                                    generation: gen,
+                                    results,
+                                });
+                            }
+                            Err(e) => {
+                                let _ = tx.send(AppEvent::BackgroundError {
+                                    generation: gen,
+                                    message: format!("{e}"),
+                                });
+                            }
+                        }
+                    });
+                } else {
+                    self.analysis_modal.computing = None;
+                    self.busy = false;
+                    self.drain_keys_on_next_loop = true;
+                }
+                None
+            }
+            AppEvent::AnalysisCorrelationCompute => {
+                if let Some(state) = &self.data_table_state {
+                    // Stub binary columns so their blobs are never materialized (see AnalysisChunk).
+                    let lf = state.lf.clone().select(state.binary_stub_exprs());
+                    let streaming = state.polars_streaming;
+                    let seed = self.analysis_modal.random_seed;
+                    self.spawn_bg("Computing correlation matrix...", move |gen, tx| {
+                        let result = crate::statistics::collect_lazy(lf, streaming).map(|df| {
+                            let matrix = crate::statistics::compute_correlation_matrix(&df).ok();
+                            let height = df.height();
+                            crate::statistics::AnalysisResults {
+                                column_statistics: vec![],
+                                total_rows: height,
+                                sample_size: None,
+                                sample_seed: seed,
+                                correlation_matrix: matrix,
+                                distribution_analyses: vec![],
+                            }
+                        });
+                        match result {
+                            Ok(results) => {
+                                let _ = tx.send(AppEvent::BackgroundCorrelationReady {
+                                    generation: gen,
+                                    results,
+                                });
+                            }
+                            Err(e) => {
+                                let _ = tx.send(AppEvent::BackgroundError {
+                                    generation: gen,
+                                    message: format!("{e}"),
+                                });
+                            }
+                        }
+                    });
+                } else {
+                    self.analysis_modal.computing = None;
+                    self.busy = false;
+                    self.drain_keys_on_next_loop = true;
+                }
+                None
+            }
+            AppEvent::BackgroundLenReady {
+                len_generation,
+                num_rows,
+            } => {
+                if self.len_count_inflight == Some(*len_generation) {
+                    self.len_count_inflight = None;
+                }
+                if self.len_count_failed == Some(*len_generation) {
+                    self.len_count_failed = None;
+                }
+                // Apply the exact total only if the data hasn't changed since the count
+                // was spawned. This runs independently of the buffer paint (which has
+                // usually already rendered), so it just corrects the scrollbar/total —
+                // no busy state, no re-collect.
+                if let Some(state) = self.data_table_state.as_mut() {
+                    if state.len_generation() == *len_generation {
+                        state.set_num_rows(*num_rows);
+                    }
                 }
-                // Stale download result — ignore.
                 None
             }
-            #[cfg(any(feature = "http", feature = "cloud"))]
-            AppEvent::DoLoadFromHttpTemp(temp_path, options) => {
-                self.http_temp_path = Some(temp_path.clone());
-                let display_path = match &self.loading_state {
-                    LoadingState::Loading { file_path, .. } => file_path.clone(),
-                    _ => None,
-                };
-                if let LoadingState::Loading {
-                    file_path,
-                    file_size,
-                    ..
-                } = &self.loading_state
-                {
-                    self.loading_state = LoadingState::Loading {
-                        file_path: file_path.clone(),
-                        file_size: *file_size,
-                        current_phase: "Scanning".to_string(),
-                        progress_percent: 30,
-                    };
+            AppEvent::BackgroundLenFailed { len_generation } => {
+                if self.len_count_inflight == Some(*len_generation) {
+                    self.len_count_inflight = None;
                 }
-                #[allow(clippy::cloned_ref_to_slice_refs)]
-                match self.build_lazyframe_from_paths(&[temp_path.clone()], options) {
-                    Ok(lf) => {
-                        if let LoadingState::Loading {
-                            file_path,
-                            file_size,
-                            ..
-                        } = &self.loading_state
-                        {
-                            self.loading_state = LoadingState::Loading {
-                                file_path: file_path.clone(),
-                                file_size: *file_size,
-                                current_phase: "Caching schema".to_string(),
-                                progress_percent: 40,
-                            };
+                // Mark this generation's count as failed so the row count renders as "?"
+                // instead of a misleading provisional total.
+                self.len_count_failed = Some(*len_generation);
+                None
+            }
+            AppEvent::BackgroundCollectReady { generation } => {
+                if *generation == self.task_generation {
+                    let taken = self
+                        .pending_collect_result
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .take();
+                    if let Some((slot_gen, result)) = taken {
+                        if slot_gen == self.task_generation {
+                            if let Some(state) = &mut self.data_table_state {
+                                state.apply_async_collect(result);
+                            }
                         }
-                        Some(AppEvent::DoLoadSchema(
-                            Box::new(lf),
-                            display_path,
-                            options.clone(),
-                        ))
                     }
-                    Err(e) => {
-                        self.loading_state = LoadingState::Idle;
-                        self.busy = false;
-                        self.drain_keys_on_next_loop = true;
-                        let msg = crate::error_display::user_message_from_report(
-                            &e,
-                            Some(temp_path.as_path()),
-                        );
-                        Some(AppEvent::Crash(msg))
+                    self.loading_state = LoadingState::Idle;
+                    self.status_message = None;
+                    self.busy = false;
+                    self.drain_keys_on_next_loop = true;
+                    self.loading_csv = None;
+                    self.request_column_stats();
+                    self.request_heatmap_ranges();
+                    if let Some(view) = self.pending_view.take() {
+                        if let Err(e) = self.apply_initial_view(&view) {
+                            self.error_modal.show(e.to_string());
+                        }
+                    }
+                    if let Some(path) = self.pending_script.take() {
+                        self.run_script(&path);
                     }
                 }
+                // Stale results (generation mismatch) are silently ignored —
+                // busy stays true until the current generation's result arrives.
+                None
             }
-            AppEvent::DoLoadSchema(lf, path, options) => {
-                // Set "Caching schema" and return so the UI draws this phase before we block in DoLoadSchemaBlocking
-                if let LoadingState::Loading {
-                    file_path,
-                    file_size,
-                    ..
-                } = &self.loading_state
-                {
-                    self.loading_state = LoadingState::Loading {
-                        file_path: file_path.clone(),
-                        file_size: *file_size,
-                        current_phase: "Caching schema".to_string(),
-                        progress_percent: 40,
-                    };
-                }
-                Some(AppEvent::DoLoadSchemaBlocking(
-                    lf.clone(),
-                    path.clone(),
-                    options.clone(),
-                ))
+            AppEvent::BackgroundColumnStatsReady {
+                len_generation,
+                column,
+                stats,
+            } => {
+                let stats = stats
+                    .as_ref()
+                    .map(|s| s.as_ref().clone())
+                    .map_err(Clone::clone);
+                self.column_stats.finish(*len_generation, column, stats);
+                // The cursor may have moved on while this column was computed
+                self.request_column_stats();
+                None
             }
-            AppEvent::DoLoadSchemaBlocking(lf, path, options) => {
-                self.debug.schema_load = None;
-                // Fast path for hive directory: infer schema from one parquet file instead of collect_schema() over all files.
-                if options.single_spine_schema
-                    && path.as_ref().is_some_and(|p| p.is_dir() && options.hive)
-                {
-                    let p = path.as_ref().expect("path set by caller");
-                    if let Ok((merged_schema, partition_columns)) =
-                        DataTableState::schema_from_one_hive_parquet(p)
-                    {
-                        if let Ok(lf_owned) =
-                            DataTableState::scan_parquet_hive_with_schema(p, merged_schema.clone())
-                        {
-                            match DataTableState::from_schema_and_lazyframe(
-                                merged_schema,
-                                lf_owned,
-                                options,
-                                Some(partition_columns),
-                            ) {
-                                Ok(state) => {
-                                    self.apply_schema_ready(
-                                        state,
-                                        path.clone(),
-                                        options,
-                                        Some("one-file (local)".to_string()),
-                                    );
-                                    return Some(AppEvent::DoLoadBuffer);
-                                }
-                                Err(e) => {
-                                    self.loading_state = LoadingState::Idle;
-                                    self.busy = false;
-                                    self.drain_keys_on_next_loop = true;
-                                    let msg =
-                                        crate::error_display::user_message_from_report(&e, None);
-                                    return Some(AppEvent::Crash(msg));
-                                }
-                            }
-                        }
+            AppEvent::BackgroundHeatmapReady {
+                len_generation,
+                ranges,
+            } => {
+                self.heatmap.finish(*len_generation, ranges.clone());
+                // The view may have changed while the ranges were computed
+                self.request_heatmap_ranges();
+                None
+            }
+            AppEvent::BackgroundInMemoryReady { generation, df } => {
+                if *generation == self.task_generation {
+                    // On failure the view stays lazy: it still works, just without the speedup.
+                    if let (Ok(df), Some(state)) = (df, self.data_table_state.as_mut()) {
+                        state.set_in_memory_source((**df).clone());
                     }
+                    self.load_first_buffer();
                 }
-
-                #[cfg(feature = "cloud")]
-                {
-                    // Use fast path for directory/glob cloud URLs (same as build_lazyframe_from_paths).
-                    // Don't require --hive: path shape already implies hive scan.
-                    if options.single_spine_schema
-                        && path.as_ref().is_some_and(|p| {
-                            let s = p.as_os_str().to_string_lossy();
-                            let is_cloud = s.starts_with("s3://") || s.starts_with("gs://");
-                            let looks_like_hive = s.ends_with('/') || s.contains('*');
-                            is_cloud && (options.hive || looks_like_hive)
-                        })
-                    {
-                        self.debug.schema_load = Some("trying one-file (cloud)".to_string());
-                        let src = source::input_source(path.as_ref().expect("path set by caller"));
-                        let try_cloud = match &src {
-                            source::InputSource::S3(url) => {
-                                let full = format!("s3://{url}");
-                                let (path_part, _) = source::url_path_extension(&full);
-                                let key = path_part
-                                    .split_once('/')
-                                    .map(|(_, k)| k.trim_end_matches('/'))
-                                    .unwrap_or("");
-                                let cloud_opts =
-                                    Self::build_s3_cloud_options(&self.app_config.cloud, options);
-                                Self::build_s3_object_store(&full, &self.app_config.cloud, options)
-                                    .ok()
-                                    .and_then(|store| {
-                                        let (merged_schema, partition_columns) = self
-                                            .runtime
-                                            .block_on(cloud_hive::schema_from_one_cloud_hive(
-                                                store, key,
-                                            ))
-                                            .ok()?;
-                                        let pl_path = PlPathRef::new(&full).into_owned();
-                                        let args = ScanArgsParquet {
-                                            schema: Some(merged_schema.clone()),
-                                            cloud_options: Some(cloud_opts),
-                                            hive_options: polars::io::HiveOptions::new_enabled(),
-                                            glob: true,
-                                            ..Default::default()
-                                        };
-                                        let mut lf_owned =
-                                            LazyFrame::scan_parquet(pl_path, args).ok()?;
-                                        if !partition_columns.is_empty() {
-                                            let exprs: Vec<_> = partition_columns
-                                                .iter()
-                                                .map(|s| col(s.as_str()))
-                                                .chain(
-                                                    merged_schema
-                                                        .iter_names()
-                                                        .map(|s| s.to_string())
-                                                        .filter(|c| !partition_columns.contains(c))
-                                                        .map(|s| col(s.as_str())),
-                                                )
-                                                .collect();
-                                            lf_owned = lf_owned.select(exprs);
-                                        }
-                                        DataTableState::from_schema_and_lazyframe(
-                                            merged_schema,
-                                            lf_owned,
-                                            options,
-                                            Some(partition_columns),
-                                        )
-                                        .ok()
-                                    })
-                            }
-                            source::InputSource::Gcs(url) => {
-                                let full = format!("gs://{url}");
-                                let (path_part, _) = source::url_path_extension(&full);
-                                let key = path_part
-                                    .split_once('/')
-                                    .map(|(_, k)| k.trim_end_matches('/'))
-                                    .unwrap_or("");
-                                Self::build_gcs_object_store(&full).ok().and_then(|store| {
-                                    let (merged_schema, partition_columns) = self
-                                        .runtime
-                                        .block_on(cloud_hive::schema_from_one_cloud_hive(
-                                            store, key,
-                                        ))
-                                        .ok()?;
-                                    let pl_path = PlPathRef::new(&full).into_owned();
-                                    let args = ScanArgsParquet {
-                                        schema: Some(merged_schema.clone()),
-                                        cloud_options: Some(CloudOptions::default()),
-                                        hive_options: polars::io::HiveOptions::new_enabled(),
-                                        glob: true,
-                                        ..Default::default()
-                                    };
-                                    let mut lf_owned =
-                                        LazyFrame::scan_parquet(pl_path, args).ok()?;
-                                    if !partition_columns.is_empty() {
-                                        let exprs: Vec<_> = partition_columns
-                                            .iter()
-                                            .map(|s| col(s.as_str()))
-                                            .chain(
-                                                merged_schema
-                                                    .iter_names()
-                                                    .map(|s| s.to_string())
-                                                    .filter(|c| !partition_columns.contains(c))
-                                                    .map(|s| col(s.as_str())),
-                                            )
-                                            .collect();
-                                        lf_owned = lf_owned.select(exprs);
-                                    }
-                                    DataTableState::from_schema_and_lazyframe(
-                                        merged_schema,
-                                        lf_owned,
-                                        options,
-                                        Some(partition_columns),
-                                    )
-                                    .ok()
-                                })
-                            }
-                            _ => None,
-                        };
-                        if let Some(state) = try_cloud {
+                None
+            }
+            AppEvent::BackgroundSchemaReady {
+                generation,
+                path,
+                options,
+                debug_label,
+            } => {
+                if *generation == self.task_generation {
+                    let taken = self
+                        .pending_schema_result
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .take();
+                    if let Some((slot_gen, state)) = taken {
+                        if slot_gen == self.task_generation {
                             self.apply_schema_ready(
                                 state,
                                 path.clone(),
                                 options,
-                                Some("one-file (cloud)".to_string()),
+                                debug_label.clone(),
                             );
                             return Some(AppEvent::DoLoadBuffer);
-                        } else {
-                            self.debug.schema_load = Some("fallback (cloud)".to_string());
                         }
                     }
+                    // Generation matched but slot was empty or stale — loading failed silently.
+                    self.loading_state = LoadingState::Idle;
+                    self.status_message = None;
+                    self.busy = false;
+                    self.drain_keys_on_next_loop = true;
                 }
-
-                // General path: collect_schema() may be slow. Spawn to background.
-                let debug_label = if self.debug.schema_load.is_none() {
-                    Some("full scan".to_string())
-                } else {
-                    self.debug.schema_load.clone()
-                };
-                let lf_clone = (**lf).clone();
-                let path_clone = path.clone();
-                let options_clone = options.clone();
-                let schema_slot = self.pending_schema_result.clone();
-                self.spawn_bg("Caching schema...", move |gen, tx| {
-                    let mut lf_owned = lf_clone;
-                    let schema = match lf_owned.collect_schema() {
-                        Ok(s) => s,
-                        Err(e) => {
-                            let report = color_eyre::eyre::Report::from(e);
-                            let _ = tx.send(AppEvent::BackgroundError {
-                                generation: gen,
-                                message: crate::error_display::user_message_from_report(
-                                    &report, None,
-                                ),
-                            });
-                            return;
-                        }
+                // Stale message (generation mismatch) — ignore entirely.
+                None
+            }
+            AppEvent::BackgroundDescribeReady {
+                generation,
+                results,
+            } => {
+                if *generation == self.task_generation {
+                    self.analysis_modal.describe_results = Some(results.clone());
+                    self.analysis_modal.computing = None;
+                    self.status_message = None;
+                    self.busy = false;
+                    self.drain_keys_on_next_loop = true;
+                }
+                None
+            }
+            AppEvent::BackgroundDistributionReady {
+                generation,
+                results,
+            } => {
+                if *generation == self.task_generation {
+                    self.analysis_modal.distribution_results = Some(results.clone());
+                    self.analysis_modal.computing = None;
+                    self.status_message = None;
+                    self.busy = false;
+                    self.drain_keys_on_next_loop = true;
+                }
+                None
+            }
+            AppEvent::BackgroundCorrelationReady {
+                generation,
+                results,
+            } => {
+                if *generation == self.task_generation {
+                    self.analysis_modal.correlation_results = Some(results.clone());
+                    self.analysis_modal.computing = None;
+                    self.status_message = None;
+                    self.busy = false;
+                    self.drain_keys_on_next_loop = true;
+                }
+                None
+            }
+            AppEvent::BackgroundExportCollected {
+                generation,
+                df,
+                path,
+                format,
+                options,
+            } => {
+                if *generation == self.task_generation {
+                    self.export_df = Some(df.clone());
+                    let has_compression = match format {
+                        ExportFormat::Csv => options.csv_compression.is_some(),
+                        ExportFormat::Json => options.json_compression.is_some(),
+                        ExportFormat::Ndjson => options.ndjson_compression.is_some(),
+                        ExportFormat::Parquet
+                        | ExportFormat::Ipc
+                        | ExportFormat::Avro
+                        | ExportFormat::Xlsx
+                        | ExportFormat::Sqlite
+                        | ExportFormat::Markdown => false,
                     };
-                    let partition_columns = if path_clone.as_ref().is_some_and(|p| {
-                        options_clone.hive
-                            && (p.is_dir() || p.as_os_str().to_string_lossy().contains('*'))
-                    }) {
-                        let discovered = DataTableState::discover_hive_partition_columns(
-                            path_clone.as_ref().expect("path set by caller"),
-                        );
-                        discovered
-                            .into_iter()
-                            .filter(|c| schema.contains(c.as_str()))
-                            .collect::<Vec<_>>()
+                    let phase = if has_compression {
+                        "Writing and compressing file"
                     } else {
-                        Vec::new()
+                        "Writing file"
                     };
-                    if !partition_columns.is_empty() {
-                        let exprs: Vec<_> = partition_columns
-                            .iter()
-                            .map(|s| col(s.as_str()))
-                            .chain(
-                                schema
-                                    .iter_names()
-                                    .map(|s| s.to_string())
-                                    .filter(|c| !partition_columns.contains(c))
-                                    .map(|s| col(s.as_str())),
-                            )
-                            .collect();
-                        lf_owned = lf_owned.select(exprs);
-                    }
-                    let part_cols_opt = if partition_columns.is_empty() {
-                        None
-                    } else {
-                        Some(partition_columns)
+                    self.loading_state = LoadingState::Exporting {
+                        file_path: path.clone(),
+                        current_phase: phase.to_string(),
+                        progress_percent: 50,
                     };
-                    match DataTableState::from_schema_and_lazyframe(
-                        schema,
-                        lf_owned,
-                        &options_clone,
-                        part_cols_opt,
-                    ) {
-                        Ok(state) => {
-                            let mut slot = schema_slot.lock().unwrap_or_else(|e| e.into_inner());
-                            let dominated = slot.as_ref().is_some_and(|(g, _)| *g > gen);
-                            if !dominated {
-                                *slot = Some((gen, state));
-                            }
-                            drop(slot);
-                            let _ = tx.send(AppEvent::BackgroundSchemaReady {
-                                generation: gen,
-                                path: path_clone,
-                                options: options_clone,
-                                debug_label,
-                            });
+                    self.status_message = Some(format!("{}...", phase));
+                    return Some(AppEvent::DoExportWrite(
+                        path.clone(),
+                        *format,
+                        options.clone(),
+                    ));
+                }
+                // Stale export collect — ignore.
+                None
+            }
+            AppEvent::BackgroundExportWritten {
+                generation,
+                path,
+                result,
+            } => {
+                if *generation == self.task_generation {
+                    self.loading_state = LoadingState::Idle;
+                    self.status_message = None;
+                    self.busy = false;
+                    self.drain_keys_on_next_loop = true;
+                    match result {
+                        Ok(()) => {
+                            self.success_modal
+                                .show(format!("Data exported successfully to\n{}", path.display()));
                         }
                         Err(e) => {
-                            let _ = tx.send(AppEvent::BackgroundError {
-                                generation: gen,
-                                message: crate::error_display::user_message_from_report(&e, None),
-                            });
+                            self.error_modal.show(e.clone());
                         }
                     }
-                });
+                }
                 None
             }
-            AppEvent::DoLoadBuffer => {
-                if !self.spawn_async_collect("Loading buffer...") {
-                    self.loading_state = LoadingState::Idle;
+            AppEvent::BackgroundProfileWritten {
+                generation,
+                path,
+                result,
+            } => {
+                if *generation == self.task_generation {
+                    self.status_message = None;
                     self.busy = false;
                     self.drain_keys_on_next_loop = true;
+                    match result {
+                        Ok(()) => self
+                            .success_modal
+                            .show(format!("Profiling report written to\n{}", path.display())),
+                        Err(e) => self.error_modal.show(e.clone()),
+                    }
                 }
                 None
             }
-            AppEvent::DoLoad(paths, options) => {
-                let first = &paths[0];
-                // Check if file is compressed (only single-file compressed CSV supported for now)
-                let compression = options
-                    .compression
-                    .or_else(|| CompressionFormat::from_extension(first));
-                let is_csv = first
-                    .file_stem()
-                    .and_then(|stem| stem.to_str())
-                    .map(|stem| {
-                        stem.ends_with(".csv")
-                            || first
-                                .extension()
-                                .and_then(|e| e.to_str())
-                                .map(|e| e.eq_ignore_ascii_case("csv"))
-                                .unwrap_or(false)
-                    })
-                    .unwrap_or(false);
-                let is_compressed_csv = paths.len() == 1 && compression.is_some() && is_csv;
-
-                if is_compressed_csv {
-                    // Set "Decompressing" phase and return event to trigger render
-                    if let LoadingState::Loading {
-                        file_path,
-                        file_size,
-                        ..
-                    } = &self.loading_state
-                    {
-                        self.loading_state = LoadingState::Loading {
-                            file_path: file_path.clone(),
-                            file_size: *file_size,
-                            current_phase: "Decompressing".to_string(),
-                            progress_percent: 30,
-                        };
-                    }
-                    // Return DoDecompress to allow UI to render "Decompressing" before blocking
-                    Some(AppEvent::DoDecompress(paths.clone(), options.clone()))
-                } else {
-                    // For non-compressed files, proceed with normal loading
-                    match self.load(paths, options) {
-                        Ok(_) => {
-                            self.busy = false;
-                            self.drain_keys_on_next_loop = true;
-                            Some(AppEvent::Collect)
-                        }
-                        Err(e) => {
-                            self.loading_state = LoadingState::Idle;
-                            self.busy = false;
-                            self.drain_keys_on_next_loop = true;
-                            let msg = crate::error_display::user_message_from_report(
-                                &e,
-                                paths.first().map(|p| p.as_path()),
-                            );
-                            Some(AppEvent::Crash(msg))
-                        }
+            AppEvent::BackgroundRowContextReady {
+                generation,
+                csv,
+                offset,
+                rows,
+            } => {
+                if *generation == self.task_generation {
+                    self.busy = false;
+                    self.status_message = None;
+                    self.drain_keys_on_next_loop = true;
+                    match clipboard::copy_to_clipboard(csv) {
+                        Ok(()) => self.success_modal.show(format!(
+                            "Copied rows {}-{} ({} rows) to the clipboard as CSV.",
+                            offset + 1,
+                            offset + rows,
+                            rows
+                        )),
+                        Err(e) => self.error_modal.show(format!("Copy failed: {}", e)),
                     }
                 }
+                None
             }
-            AppEvent::DoDecompress(paths, options) => {
-                // Actually perform decompression now (after UI has rendered "Decompressing")
-                match self.load(paths, options) {
-                    Ok(_) => Some(AppEvent::DoLoadBuffer),
-                    Err(e) => {
-                        self.loading_state = LoadingState::Idle;
-                        self.busy = false;
-                        self.drain_keys_on_next_loop = true;
-                        let msg = crate::error_display::user_message_from_report(
-                            &e,
-                            paths.first().map(|p| p.as_path()),
-                        );
-                        Some(AppEvent::Crash(msg))
+            AppEvent::BackgroundRowJsonReady {
+                generation,
+                json,
+                row,
+            } => {
+                if *generation == self.task_generation {
+                    self.busy = false;
+                    self.status_message = None;
+                    self.drain_keys_on_next_loop = true;
+                    match clipboard::copy_to_clipboard(json) {
+                        Ok(()) => self
+                            .success_modal
+                            .show(format!("Copied row {} to the clipboard as JSON.", row + 1)),
+                        Err(e) => self.error_modal.show(format!("Copy failed: {}", e)),
                     }
                 }
-            }
-            AppEvent::Resize(_cols, _rows) => {
-                // No work here: the next render sets visible_rows and flips needs_recollect,
-                // which the main loop turns into an async collect against the correct size.
                 None
             }
-            AppEvent::Collect => {
-                self.spawn_async_collect("Loading buffer...");
+            AppEvent::OpenDiff(request, options) => {
+                self.data_diff = Some(data_diff::DataDiffView::new(request.as_ref().clone()));
+                let (request, options) = (request.as_ref().clone(), options.clone());
+                let tx = self.events.clone();
+                self.runtime.spawn_blocking(move || {
+                    let diff = data_diff::DataDiff::compute_files(&request, &options)
+                        .map(Box::new)
+                        .map_err(|e| crate::error_display::user_message_from_report(&e, None));
+                    let _ = tx.send(AppEvent::BackgroundDataDiffReady { diff });
+                });
                 None
             }
-            AppEvent::DoScrollDown => self.handle_scroll(|s| s.page_down()),
-            AppEvent::DoScrollUp => self.handle_scroll(|s| s.page_up()),
-            AppEvent::DoScrollNext => self.handle_scroll(|s| s.select_next()),
-            AppEvent::DoScrollPrev => self.handle_scroll(|s| s.select_previous()),
-            AppEvent::DoScrollEnd => self.handle_scroll(|s| s.scroll_to_end()),
-            AppEvent::DoScrollHalfDown => self.handle_scroll(|s| s.half_page_down()),
-            AppEvent::DoScrollHalfUp => self.handle_scroll(|s| s.half_page_up()),
-            AppEvent::GoToLine(n) => {
-                let n = *n;
-                self.handle_scroll(|s| s.scroll_to_row_centered(n))
+            AppEvent::BackgroundDataDiffReady { diff } => {
+                // Ignored when the view was closed while comparing
+                if let Some(view) = self.data_diff.as_mut() {
+                    view.set_result(
+                        diff.as_ref()
+                            .map(|d| d.as_ref().clone())
+                            .map_err(Clone::clone),
+                    );
+                }
+                None
             }
-            AppEvent::AnalysisChunk => {
-                // Stub out binary columns: their blobs are never read for analysis (reading
-                // multi-GB blobs across partitions can exhaust memory and freeze the process).
-                let lf = match &self.data_table_state {
-                    Some(state) => state.lf.clone().select(state.binary_stub_exprs()),
-                    None => {
-                        self.analysis_computation = None;
-                        self.analysis_modal.computing = None;
-                        self.busy = false;
-                        return None;
-                    }
-                };
-                let comp = self.analysis_computation.take()?;
-                if comp.df.is_none() {
-                    let cached_rows = self
-                        .data_table_state
-                        .as_ref()
-                        .and_then(|s| s.num_rows_if_valid());
-                    let sampling = self.sampling_threshold;
-                    let seed = comp.sample_seed;
-                    let streaming = self.app_config.performance.polars_streaming;
-                    self.spawn_bg("Computing statistics...", move |gen, tx| {
-                        let total_rows = match cached_rows {
-                            Some(n) => n,
-                            None => match crate::statistics::collect_lazy(
-                                lf.clone().select([len()]),
-                                streaming,
-                            ) {
-                                Ok(count_df) => match count_df.get(0) {
-                                    Some(col) => match col.first() {
-                                        Some(AnyValue::UInt32(n)) => *n as usize,
-                                        _ => 0,
-                                    },
-                                    None => 0,
-                                },
-                                Err(e) => {
-                                    let _ = tx.send(AppEvent::BackgroundError {
-                                        generation: gen,
-                                        message: format!("{e}"),
-                                    });
-                                    return;
-                                }
-                            },
-                        };
-                        match crate::statistics::compute_describe_from_lazy(
-                            &lf, total_rows, sampling, seed, streaming,
-                        ) {
-                            Ok(results) => {
-                                let _ = tx.send(AppEvent::BackgroundDescribeReady {
-                                    generation: gen,
-                                    results,
-                                });
-                            }
-                            Err(e) => {
-                                let _ = tx.send(AppEvent::BackgroundError {
-                                    generation: gen,
-                                    message: format!("{e}"),
-                                });
-                            }
+            AppEvent::BackgroundScriptReady { generation, result } => {
+                if *generation == self.task_generation {
+                    self.busy = false;
+                    self.status_message = None;
+                    self.drain_keys_on_next_loop = true;
+                    let state = self.data_table_state.as_mut()?;
+                    state.defer_collect = true;
+                    let applied = result.clone().and_then(|lf| state.replace_source(*lf));
+                    state.defer_collect = false;
+                    match applied {
+                        Ok(()) => {
+                            self.scripts_modal = None;
+                            self.spawn_async_collect("Running script...");
                         }
-                    });
+                        Err(message) => self.error_modal.show(message),
+                    }
                 }
                 None
             }
-            AppEvent::AnalysisDistributionCompute => {
-                if let Some(state) = &self.data_table_state {
-                    // Stub binary columns so their blobs are never materialized (see AnalysisChunk).
-                    let lf = state.lf.clone().select(state.binary_stub_exprs());
-                    let sampling = self.sampling_threshold;
-                    let seed = self.analysis_modal.random_seed;
-                    let streaming = self.app_config.performance.polars_streaming;
-                    self.spawn_bg("Analyzing distributions...", move |gen, tx| {
-                        let options = crate::statistics::ComputeOptions {
-                            include_distribution_info: true,
-                            include_distribution_analyses: true,
-                            include_correlation_matrix: false,
-                            include_skewness_kurtosis_outliers: true,
-                            polars_streaming: streaming,
-                        };
-                        match crate::statistics::compute_statistics_with_options(
-                            &lf, sampling, seed, options,
-                        ) {
-                            Ok(results) => {
-                                let _ = tx.send(AppEvent::BackgroundDistributionReady {
-                                    generation: gen,
-                                    results,
-                                });
-                            }
-                            Err(e) => {
-                                let _ = tx.send(AppEvent::BackgroundError {
-                                    generation: gen,
-                                    message: format!("{e}"),
-                                });
-                            }
-                        }
-                    });
-                } else {
-                    self.analysis_modal.computing = None;
+            AppEvent::BackgroundRecordReady { generation, record } => {
+                if *generation == self.task_generation {
                     self.busy = false;
+                    self.status_message = None;
                     self.drain_keys_on_next_loop = true;
+                    match record {
+                        Ok(record) => self.record_view = Some(record.as_ref().clone()),
+                        Err(message) => self.error_modal.show(message.clone()),
+                    }
                 }
                 None
             }
-            AppEvent::AnalysisCorrelationCompute => {
-                if let Some(state) = &self.data_table_state {
-                    // Stub binary columns so their blobs are never materialized (see AnalysisChunk).
-                    let lf = state.lf.clone().select(state.binary_stub_exprs());
-                    let streaming = state.polars_streaming;
-                    let seed = self.analysis_modal.random_seed;
-                    self.spawn_bg("Computing correlation matrix...", move |gen, tx| {
-                        let result = crate::statistics::collect_lazy(lf, streaming).map(|df| {
-                            let matrix = crate::statistics::compute_correlation_matrix(&df).ok();
-                            let height = df.height();
-                            crate::statistics::AnalysisResults {
-                                column_statistics: vec![],
-                                total_rows: height,
-                                sample_size: None,
-                                sample_seed: seed,
-                                correlation_matrix: matrix,
-                                distribution_analyses: vec![],
-                            }
-                        });
-                        match result {
-                            Ok(results) => {
-                                let _ = tx.send(AppEvent::BackgroundCorrelationReady {
-                                    generation: gen,
-                                    results,
-                                });
-                            }
-                            Err(e) => {
-                                let _ = tx.send(AppEvent::BackgroundError {
-                                    generation: gen,
-                                    message: format!("{e}"),
-                                });
-                            }
-                        }
-                    });
-                } else {
-                    self.analysis_modal.computing = None;
+            AppEvent::BackgroundCellsReady {
+                generation,
+                text,
+                description,
+            } => {
+                if *generation == self.task_generation {
                     self.busy = false;
+                    self.status_message = None;
                     self.drain_keys_on_next_loop = true;
+                    match clipboard::copy_to_clipboard(text) {
+                        Ok(()) => self
+                            .success_modal
+                            .show(format!("Copied {} to the clipboard.", description)),
+                        Err(e) => self.error_modal.show(format!("Copy failed: {}", e)),
+                    }
                 }
                 None
             }
-            AppEvent::BackgroundLenReady {
-                len_generation,
-                num_rows,
+            #[cfg(feature = "cloud")]
+            AppEvent::BackgroundS3ListingReady {
+                generation,
+                browser,
             } => {
-                if self.len_count_inflight == Some(*len_generation) {
-                    self.len_count_inflight = None;
+                if *generation == self.task_generation {
+                    self.loading_state = LoadingState::Idle;
+                    self.busy = false;
+                    self.status_message = None;
+                    self.drain_keys_on_next_loop = true;
+                    self.s3_browser = Some(browser.as_ref().clone());
                 }
-                if self.len_count_failed == Some(*len_generation) {
-                    self.len_count_failed = None;
+                None
+            }
+            AppEvent::BackgroundAggregatePeekReady { generation, peek } => {
+                if *generation == self.task_generation {
+                    self.busy = false;
+                    self.status_message = None;
+                    self.drain_keys_on_next_loop = true;
+                    self.aggregate_peek = Some(peek.as_ref().clone());
                 }
-                // Apply the exact total only if the data hasn't changed since the count
-                // was spawned. This runs independently of the buffer paint (which has
-                // usually already rendered), so it just corrects the scrollbar/total —
-                // no busy state, no re-collect.
-                if let Some(state) = self.data_table_state.as_mut() {
-                    if state.len_generation() == *len_generation {
-                        state.set_num_rows(*num_rows);
+                None
+            }
+            AppEvent::BackgroundValueCountsPhase { generation, phase } => {
+                if *generation == self.task_generation {
+                    if let Some(popup) = self.value_counts.as_mut() {
+                        popup.phase = Some(*phase);
                     }
                 }
                 None
             }
-            AppEvent::BackgroundLenFailed { len_generation } => {
-                if self.len_count_inflight == Some(*len_generation) {
-                    self.len_count_inflight = None;
+            AppEvent::BackgroundValueCountsReady { generation, counts } => {
+                if *generation == self.task_generation {
+                    self.busy = false;
+                    self.status_message = None;
+                    self.drain_keys_on_next_loop = true;
+                    match counts {
+                        Ok(counts) => {
+                            if let Some(popup) = self.value_counts.as_mut() {
+                                popup.phase = None;
+                                popup.counts = Some(counts.as_ref().clone());
+                            }
+                        }
+                        Err(message) => {
+                            self.value_counts = None;
+                            self.error_modal.show(message.clone());
+                        }
+                    }
                 }
-                // Mark this generation's count as failed so the row count renders as "?"
-                // instead of a misleading provisional total.
-                self.len_count_failed = Some(*len_generation);
                 None
             }
-            AppEvent::BackgroundCollectReady { generation } => {
+            AppEvent::BackgroundFilterValuesReady { generation, counts } => {
                 if *generation == self.task_generation {
-                    let taken = self
-                        .pending_collect_result
-                        .lock()
-                        .unwrap_or_else(|e| e.into_inner())
-                        .take();
-                    if let Some((slot_gen, result)) = taken {
-                        if slot_gen == self.task_generation {
-                            if let Some(state) = &mut self.data_table_state {
-                                state.apply_async_collect(result);
+                    self.busy = false;
+                    self.status_message = None;
+                    self.drain_keys_on_next_loop = true;
+                    let filter = &mut self.sort_filter_modal.filter;
+                    match counts {
+                        Ok(counts) => {
+                            if let Some(picker) = filter.value_picker.as_mut() {
+                                picker.set_counts(counts);
                             }
                         }
+                        Err(message) => {
+                            filter.value_picker = None;
+                            self.error_modal.show(message.clone());
+                        }
                     }
-                    self.loading_state = LoadingState::Idle;
-                    self.status_message = None;
+                }
+                None
+            }
+            AppEvent::BackgroundFilterDateRangeReady { generation, bounds } => {
+                if *generation == self.task_generation {
                     self.busy = false;
+                    self.status_message = None;
                     self.drain_keys_on_next_loop = true;
+                    let filter = &mut self.sort_filter_modal.filter;
+                    match bounds {
+                        Ok((min, max)) => {
+                            if let Some(range) = filter.date_range.as_mut() {
+                                range.set_bounds(min.clone(), max.clone());
+                            }
+                        }
+                        Err(message) => {
+                            filter.date_range = None;
+                            self.error_modal.show(message.clone());
+                        }
+                    }
                 }
-                // Stale results (generation mismatch) are silently ignored —
-                // busy stays true until the current generation's result arrives.
                 None
             }
-            AppEvent::BackgroundSchemaReady {
+            AppEvent::BackgroundRegexScanProgress { generation, status } => {
+                if *generation == self.task_generation {
+                    self.status_message = Some(status.clone());
+                }
+                None
+            }
+            AppEvent::BackgroundRegexMatchesReady {
                 generation,
-                path,
-                options,
-                debug_label,
+                pattern,
+                plan,
+                rows,
+                forward,
             } => {
-                if *generation == self.task_generation {
-                    let taken = self
-                        .pending_schema_result
-                        .lock()
-                        .unwrap_or_else(|e| e.into_inner())
-                        .take();
-                    if let Some((slot_gen, state)) = taken {
-                        if slot_gen == self.task_generation {
-                            self.apply_schema_ready(
-                                state,
-                                path.clone(),
-                                options,
-                                debug_label.clone(),
-                            );
-                            return Some(AppEvent::DoLoadBuffer);
+                if *generation != self.task_generation {
+                    return None;
+                }
+                self.busy = false;
+                self.status_message = None;
+                self.drain_keys_on_next_loop = true;
+                match rows {
+                    Ok(rows) => {
+                        let search = self
+                            .data_table_state
+                            .as_mut()
+                            .and_then(|state| state.regex_search.as_mut())
+                            .filter(|search| search.pattern == *pattern)?;
+                        search.set_matches(plan.clone(), rows.clone());
+                        // Only jump if the view is still the one that was scanned
+                        if search.rows_for(plan).is_some() {
+                            return self.jump_to_regex_match(*forward);
                         }
                     }
-                    // Generation matched but slot was empty or stale — loading failed silently.
-                    self.loading_state = LoadingState::Idle;
-                    self.status_message = None;
+                    Err(message) => self.error_modal.show(message.clone()),
+                }
+                None
+            }
+            AppEvent::BackgroundTimeGapsReady { generation, report } => {
+                if *generation == self.task_generation {
                     self.busy = false;
+                    self.status_message = None;
                     self.drain_keys_on_next_loop = true;
+                    if self.analysis_modal.active {
+                        self.analysis_modal.gap_results =
+                            Some(report.clone().map(|report| *report));
+                    }
                 }
-                // Stale message (generation mismatch) — ignore entirely.
                 None
             }
-            AppEvent::BackgroundDescribeReady {
-                generation,
-                results,
-            } => {
+            AppEvent::BackgroundCardinalityReady { generation, report } => {
                 if *generation == self.task_generation {
-                    self.analysis_modal.describe_results = Some(results.clone());
-                    self.analysis_modal.computing = None;
-                    self.status_message = None;
                     self.busy = false;
+                    self.status_message = None;
                     self.drain_keys_on_next_loop = true;
+                    if self.analysis_modal.active {
+                        self.analysis_modal.cardinality_results =
+                            Some(report.clone().map(|report| *report));
+                    }
                 }
                 None
             }
-            AppEvent::BackgroundDistributionReady {
-                generation,
-                results,
-            } => {
+            AppEvent::BackgroundDuplicatesReady { generation, report } => {
                 if *generation == self.task_generation {
-                    self.analysis_modal.distribution_results = Some(results.clone());
-                    self.analysis_modal.computing = None;
-                    self.status_message = None;
                     self.busy = false;
+                    self.status_message = None;
                     self.drain_keys_on_next_loop = true;
+                    if self.analysis_modal.active {
+                        self.analysis_modal.duplicate_results =
+                            Some(report.clone().map(|report| *report));
+                    }
                 }
                 None
             }
-            AppEvent::BackgroundCorrelationReady {
-                generation,
-                results,
-            } => {
+            AppEvent::BackgroundCrosstabReady { generation, table } => {
                 if *generation == self.task_generation {
-                    self.analysis_modal.correlation_results = Some(results.clone());
-                    self.analysis_modal.computing = None;
-                    self.status_message = None;
                     self.busy = false;
+                    self.status_message = None;
                     self.drain_keys_on_next_loop = true;
+                    if self.analysis_modal.active {
+                        self.analysis_modal.crosstab_results =
+                            Some(table.clone().map(|table| *table));
+                    }
                 }
                 None
             }
-            AppEvent::BackgroundExportCollected {
+            AppEvent::BackgroundPluginToolReady {
                 generation,
-                df,
-                path,
-                format,
-                options,
+                index,
+                result,
             } => {
                 if *generation == self.task_generation {
-                    self.export_df = Some(df.clone());
-                    let has_compression = match format {
-                        ExportFormat::Csv => options.csv_compression.is_some(),
-                        ExportFormat::Json => options.json_compression.is_some(),
-                        ExportFormat::Ndjson => options.ndjson_compression.is_some(),
-                        ExportFormat::Parquet | ExportFormat::Ipc | ExportFormat::Avro => false,
-                    };
-                    let phase = if has_compression {
-                        "Writing and compressing file"
-                    } else {
-                        "Writing file"
-                    };
-                    self.loading_state = LoadingState::Exporting {
-                        file_path: path.clone(),
-                        current_phase: phase.to_string(),
-                        progress_percent: 50,
-                    };
-                    self.status_message = Some(format!("{}...", phase));
-                    return Some(AppEvent::DoExportWrite(
-                        path.clone(),
-                        *format,
-                        options.clone(),
-                    ));
+                    self.busy = false;
+                    self.status_message = None;
+                    self.drain_keys_on_next_loop = true;
+                    if self.analysis_modal.active {
+                        if let Some(slot) = self.analysis_modal.plugin_results.get_mut(*index) {
+                            *slot = Some(result.clone());
+                        }
+                    }
                 }
-                // Stale export collect — ignore.
                 None
             }
-            AppEvent::BackgroundExportWritten {
-                generation,
-                path,
-                result,
-            } => {
+            AppEvent::BackgroundMissingDataReady { generation, report } => {
                 if *generation == self.task_generation {
-                    self.loading_state = LoadingState::Idle;
+                    self.busy = false;
                     self.status_message = None;
+                    self.drain_keys_on_next_loop = true;
+                    if self.analysis_modal.active {
+                        self.analysis_modal.missing_results =
+                            Some(report.clone().map(|report| *report));
+                    }
+                }
+                None
+            }
+            AppEvent::BackgroundSchemaDiffReady { generation, diff } => {
+                if *generation == self.task_generation {
                     self.busy = false;
+                    self.status_message = None;
                     self.drain_keys_on_next_loop = true;
-                    match result {
-                        Ok(()) => {
-                            self.success_modal
-                                .show(format!("Data exported successfully to\n{}", path.display()));
-                        }
-                        Err(e) => {
-                            self.error_modal.show(e.clone());
-                        }
+                    if let Some(ws) = self.workspace.as_mut() {
+                        ws.schema_diff =
+                            Some(workspace::SchemaDiffView::new(diff.as_ref().clone()));
                     }
                 }
                 None
@@ -7617,7 +13866,9 @@ impl App {
                     self.status_message = None;
                     self.busy = false;
                     self.drain_keys_on_next_loop = true;
-                    self.error_modal.show(message.clone());
+                    if !self.offer_csv_dialect(message) {
+                        self.error_modal.show(message.clone());
+                    }
                 }
                 None
             }
@@ -7677,6 +13928,42 @@ impl App {
                 }
                 None
             }
+            AppEvent::RegexSearch(pattern, column) => {
+                let state = self.data_table_state.as_mut()?;
+                let unsearchable = column.as_ref().and_then(|column| {
+                    let dtype = state.schema.get(column)?;
+                    (!regex_search::is_searchable(dtype))
+                        .then(|| format!("Column {} ({}) can't be searched", column, dtype))
+                });
+                let search = if pattern.trim().is_empty() {
+                    None
+                } else {
+                    match unsearchable.map_or_else(
+                        || regex_search::RegexSearch::new(pattern, column.clone()),
+                        Err,
+                    ) {
+                        Ok(search) => Some(search),
+                        Err(message) => {
+                            // Stay in the dialog with the error shown under the input
+                            state.error =
+                                Some(polars::prelude::PolarsError::ComputeError(message.into()));
+                            return None;
+                        }
+                    }
+                };
+                let searching = search.is_some();
+                state.regex_search = search;
+                state.error = None;
+                state.suppress_error_display = false;
+                self.regex_input.set_focused(false);
+                self.input_mode = InputMode::Normal;
+                self.input_type = None;
+                if searching {
+                    self.jump_to_regex_match(true)
+                } else {
+                    None
+                }
+            }
             AppEvent::Filter(statements) => {
                 if let Some(state) = &mut self.data_table_state {
                     state.defer_collect = true;
@@ -7835,9 +14122,45 @@ impl App {
                     None
                 }
             }
+            AppEvent::DoExportCollect(path, format, options)
+                if options.scope == ExportScope::Analysis =>
+            {
+                // Analysis results are already in memory: go straight to writing
+                match self.analysis_results_frame() {
+                    Some(Ok(df)) => {
+                        self.export_df = Some(df);
+                        self.loading_state = LoadingState::Exporting {
+                            file_path: path.clone(),
+                            current_phase: "Writing file".to_string(),
+                            progress_percent: 50,
+                        };
+                        Some(AppEvent::DoExportWrite(
+                            path.clone(),
+                            *format,
+                            options.clone(),
+                        ))
+                    }
+                    result => {
+                        self.loading_state = LoadingState::Idle;
+                        self.busy = false;
+                        let message = match result {
+                            Some(Err(e)) => crate::error_display::user_message_from_polars(&e),
+                            _ => "No analysis results to export".to_string(),
+                        };
+                        self.error_modal.show(format!("Export failed: {}", message));
+                        None
+                    }
+                }
+            }
             AppEvent::DoExportCollect(path, format, options) => {
                 if let Some(state) = &self.data_table_state {
-                    let lf = state.lf.clone();
+                    let lf = match (options.scope, options.row_range) {
+                        (ExportScope::Dataset, _) => state.source_lf_clone(),
+                        (_, Some((offset, len))) => {
+                            state.lf.clone().slice(offset as i64, len as IdxSize)
+                        }
+                        (_, None) => state.lf.clone(),
+                    };
                     let streaming = state.polars_streaming;
                     let path = path.clone();
                     let format = *format;
@@ -7926,6 +14249,30 @@ impl App {
         } else {
             Some(chart_title.to_string())
         };
+        let labels = &self.chart_modal.labels;
+
+        if self.analysis_modal.active
+            && self.analysis_modal.selected_tool
+                == Some(analysis_modal::AnalysisTool::CorrelationMatrix)
+        {
+            let matrix = self
+                .correlation_matrix()
+                .ok_or_else(|| color_eyre::eyre::eyre!("No correlation matrix to export"))?;
+            let bounds = CorrelationExportBounds {
+                threshold: self.analysis_modal.correlation_threshold,
+                chart_title,
+            };
+            let plot = ExportPlot::Correlation {
+                matrix,
+                bounds: &bounds,
+            };
+            let summary = self.chart_export_summary(
+                state,
+                &matrix.columns,
+                chart_modal::CHART_ROW_LIMIT_MAX,
+            )?;
+            return write_chart_export(path, format, &plot, summary.as_deref(), (width, height));
+        }
 
         match self.chart_modal.chart_kind {
             ChartKind::XY => {
@@ -7940,13 +14287,15 @@ impl App {
 
                 let row_limit_opt = self.chart_modal.row_limit;
                 let row_limit = self.chart_modal.effective_row_limit();
+                let group_column = self.chart_modal.group_column.as_deref();
                 let cache_matches = self.chart_cache.xy.as_ref().is_some_and(|c| {
                     c.x_column == *x_column
                         && c.y_columns == y_columns
+                        && c.group_column.as_deref() == group_column
                         && c.row_limit == row_limit_opt
                 });
 
-                let (series_vec, x_axis_kind_export, from_cache) = if cache_matches {
+                let (series_vec, names, x_axis_kind_export, from_cache) = if cache_matches {
                     if let Some(cache) = self.chart_cache.xy.as_ref() {
                         let pts = if self.chart_modal.log_scale {
                             cache.series_log.as_ref().cloned().unwrap_or_else(|| {
@@ -7961,34 +14310,45 @@ impl App {
                         } else {
                             cache.series.clone()
                         };
-                        (pts, cache.x_axis_kind, true)
+                        (pts, cache.names.clone(), cache.x_axis_kind, true)
                     } else {
-                        let r = chart_data::prepare_chart_data(
-                            &state.lf,
+                        let r = chart_data::prepare_xy_chart_data(
+                            self.cached_chart_source(&state.lf, row_limit),
                             &state.schema,
                             x_column,
                             &y_columns,
+                            group_column,
                             row_limit,
                         )?;
-                        (r.series, r.x_axis_kind, false)
+                        (r.series, r.names, r.x_axis_kind, false)
                     }
                 } else {
-                    let r = chart_data::prepare_chart_data(
-                        &state.lf,
+                    let r = chart_data::prepare_xy_chart_data(
+                        self.cached_chart_source(&state.lf, row_limit),
                         &state.schema,
                         x_column,
                         &y_columns,
+                        group_column,
                         row_limit,
                     )?;
-                    (r.series, r.x_axis_kind, false)
+                    (r.series, r.names, r.x_axis_kind, false)
                 };
 
                 let log_scale = self.chart_modal.log_scale;
-                let series: Vec<ChartExportSeries> = series_vec
+                // The last `right` series go on the right-hand axis (after the left ones, so
+                // they stay last once empty series are dropped).
+                let right = self.chart_modal.right_axis_count(series_vec.len());
+                let split = series_vec.len() - right;
+                let right_len = series_vec[split..]
                     .iter()
-                    .zip(y_columns.iter())
-                    .filter(|(points, _)| !points.is_empty())
-                    .map(|(points, name)| {
+                    .filter(|points| !points.is_empty())
+                    .count();
+                let mut series: Vec<ChartExportSeries> = series_vec
+                    .iter()
+                    .zip(names.iter())
+                    .enumerate()
+                    .filter(|(_, (points, _))| !points.is_empty())
+                    .map(|(i, (points, name))| {
                         let pts = if log_scale && !from_cache {
                             points
                                 .iter()
@@ -7998,43 +14358,119 @@ impl App {
                             points.clone()
                         };
                         ChartExportSeries {
-                            name: name.clone(),
+                            name: if i < split {
+                                name.clone()
+                            } else {
+                                format!("{name} (right)")
+                            },
                             points: pts,
+                            band: None,
                         }
                     })
                     .collect();
+                let split = series.len() - right_len;
 
                 if series.is_empty() {
                     return Err(color_eyre::eyre::eyre!("No valid data points to export"));
                 }
 
+                if let (Some(source), Some(first)) =
+                    (self.chart_modal.band_source(), series[..split].first_mut())
+                {
+                    // The band belongs to the first Y column; skip it when that series is empty.
+                    if first.name == y_columns[0] {
+                        let cached = self.chart_cache.band.as_ref().filter(|c| {
+                            c.x_column == *x_column
+                                && c.y_column == first.name
+                                && c.source == source
+                                && c.row_limit == row_limit_opt
+                        });
+                        let points = match cached {
+                            Some(c) => c.points.clone(),
+                            None => chart_data::prepare_band_data(
+                                self.cached_chart_source(&state.lf, row_limit),
+                                &state.schema,
+                                x_column,
+                                &first.name,
+                                &source,
+                                row_limit,
+                            )?,
+                        };
+                        let points = if log_scale {
+                            points
+                                .into_iter()
+                                .map(|(x, lower, upper)| {
+                                    (x, lower.max(0.0).ln_1p(), upper.max(0.0).ln_1p())
+                                })
+                                .collect()
+                        } else {
+                            points
+                        };
+                        first.band = Some(ChartExportBand {
+                            points,
+                            style: self.chart_modal.band_style,
+                        });
+                    }
+                }
+
                 let mut all_x_min = f64::INFINITY;
                 let mut all_x_max = f64::NEG_INFINITY;
                 let mut all_y_min = f64::INFINITY;
                 let mut all_y_max = f64::NEG_INFINITY;
-                for s in &series {
+                let mut right_y_min = f64::INFINITY;
+                let mut right_y_max = f64::NEG_INFINITY;
+                for (i, s) in series.iter().enumerate() {
                     for &(x, y) in &s.points {
                         all_x_min = all_x_min.min(x);
                         all_x_max = all_x_max.max(x);
-                        all_y_min = all_y_min.min(y);
-                        all_y_max = all_y_max.max(y);
+                        if i < split {
+                            all_y_min = all_y_min.min(y);
+                            all_y_max = all_y_max.max(y);
+                        } else {
+                            right_y_min = right_y_min.min(y);
+                            right_y_max = right_y_max.max(y);
+                        }
+                    }
+                }
+                for band in series.iter().filter_map(|s| s.band.as_ref()) {
+                    for &(x, lower, upper) in &band.points {
+                        all_x_min = all_x_min.min(x);
+                        all_x_max = all_x_max.max(x);
+                        all_y_min = all_y_min.min(lower);
+                        all_y_max = all_y_max.max(upper);
                     }
                 }
 
                 let chart_type = self.chart_modal.chart_type;
                 let y_starts_at_zero = self.chart_modal.y_starts_at_zero;
-                let y_min_bounds = if chart_type == ChartType::Bar {
-                    0.0_f64.min(all_y_min)
-                } else if y_starts_at_zero {
-                    0.0
-                } else {
-                    all_y_min
+                let fit_y = |min: f64, max: f64| {
+                    let lo = if chart_type == ChartType::Bar {
+                        0.0_f64.min(min)
+                    } else if y_starts_at_zero {
+                        0.0
+                    } else {
+                        min
+                    };
+                    (lo, if max > lo { max } else { lo + 1.0 })
                 };
-                let y_max_bounds = if all_y_max > y_min_bounds {
-                    all_y_max
-                } else {
-                    y_min_bounds + 1.0
+                let right_bounds = (split < series.len()).then(|| fit_y(right_y_min, right_y_max));
+                let (y_min_bounds, y_max_bounds) = match right_bounds {
+                    // Only right-axis series have points: the left axis takes their range.
+                    Some(bounds) if split == 0 => bounds,
+                    _ => fit_y(all_y_min, all_y_max),
                 };
+                let right_axis = right_bounds.map(|(y_min, y_max)| ChartExportRightAxis {
+                    y_min,
+                    y_max,
+                    label: y_columns[y_columns.len().saturating_sub(right)..].join(", "),
+                });
+                if let Some(axis) = &right_axis {
+                    for s in &mut series[split..] {
+                        for point in &mut s.points {
+                            point.1 = axis.to_left(point.1, y_min_bounds, y_max_bounds);
+                        }
+                    }
+                }
                 let x_min_bounds = if all_x_max > all_x_min {
                     all_x_min
                 } else {
@@ -8046,8 +14482,12 @@ impl App {
                     all_x_min + 0.5
                 };
 
-                let x_label = x_column.to_string();
-                let y_label = y_columns.join(", ");
+                let labels = &self.chart_modal.labels;
+                let x_label = labels.x_title(x_column).to_string();
+                let left_columns = &y_columns[..y_columns.len().saturating_sub(right)];
+                let y_label = labels
+                    .y_title(&self.chart_modal.y_axis_name(left_columns))
+                    .to_string();
                 let bounds = ChartExportBounds {
                     x_min: x_min_bounds,
                     x_max: x_max_bounds,
@@ -8058,14 +14498,18 @@ impl App {
                     x_axis_kind: x_axis_kind_export,
                     log_scale: self.chart_modal.log_scale,
                     chart_title,
+                    x_ticks: labels.x_ticks,
+                    y_ticks: labels.y_ticks,
+                    right_axis,
                 };
 
-                match format {
-                    ChartExportFormat::Png => {
-                        write_chart_png(path, &series, chart_type, &bounds, (width, height))
-                    }
-                    ChartExportFormat::Eps => write_chart_eps(path, &series, chart_type, &bounds),
-                }
+                let plot = ExportPlot::Xy {
+                    series: &series,
+                    chart_type,
+                    bounds: &bounds,
+                };
+                let summary = self.chart_export_summary(state, &y_columns, row_limit)?;
+                write_chart_export(path, format, &plot, summary.as_deref(), (width, height))
             }
             ChartKind::Histogram => {
                 let column = self
@@ -8081,7 +14525,7 @@ impl App {
                     c.data.clone()
                 } else {
                     chart_data::prepare_histogram_data(
-                        &state.lf,
+                        self.cached_chart_source(&state.lf, row_limit),
                         &column,
                         self.chart_modal.hist_bins,
                         row_limit,
@@ -8095,6 +14539,7 @@ impl App {
                 let series = vec![ChartExportSeries {
                     name: column.clone(),
                     points,
+                    band: None,
                 }];
                 let x_max = if data.x_max > data.x_min {
                     data.x_max
@@ -8111,20 +14556,22 @@ impl App {
                     x_max,
                     y_min: 0.0,
                     y_max,
-                    x_label: column.clone(),
-                    y_label: "Count".to_string(),
+                    x_label: labels.x_title(&column).to_string(),
+                    y_label: labels.y_title("Count").to_string(),
                     x_axis_kind: chart_data::XAxisTemporalKind::Numeric,
                     log_scale: false,
                     chart_title,
+                    x_ticks: labels.x_ticks,
+                    y_ticks: labels.y_ticks,
+                    right_axis: None,
                 };
-                match format {
-                    ChartExportFormat::Png => {
-                        write_chart_png(path, &series, ChartType::Bar, &bounds, (width, height))
-                    }
-                    ChartExportFormat::Eps => {
-                        write_chart_eps(path, &series, ChartType::Bar, &bounds)
-                    }
-                }
+                let plot = ExportPlot::Xy {
+                    series: &series,
+                    chart_type: ChartType::Bar,
+                    bounds: &bounds,
+                };
+                let summary = self.chart_export_summary(state, &[column], row_limit)?;
+                write_chart_export(path, format, &plot, summary.as_deref(), (width, height))
             }
             ChartKind::BoxPlot => {
                 let column = self
@@ -8141,7 +14588,7 @@ impl App {
                     c.data.clone()
                 } else {
                     chart_data::prepare_box_plot_data(
-                        &state.lf,
+                        self.cached_chart_source(&state.lf, row_limit),
                         std::slice::from_ref(&column),
                         row_limit,
                     )?
@@ -8153,16 +14600,17 @@ impl App {
                     y_min: data.y_min,
                     y_max: data.y_max,
                     x_labels: vec![column.clone()],
-                    x_label: "Columns".to_string(),
-                    y_label: "Value".to_string(),
+                    x_label: labels.x_title("Columns").to_string(),
+                    y_label: labels.y_title("Value").to_string(),
                     chart_title,
+                    y_ticks: labels.y_ticks,
                 };
-                match format {
-                    ChartExportFormat::Png => {
-                        write_box_plot_png(path, &data, &bounds, (width, height))
-                    }
-                    ChartExportFormat::Eps => write_box_plot_eps(path, &data, &bounds),
-                }
+                let plot = ExportPlot::BoxPlot {
+                    data: &data,
+                    bounds: &bounds,
+                };
+                let summary = self.chart_export_summary(state, &[column], row_limit)?;
+                write_chart_export(path, format, &plot, summary.as_deref(), (width, height))
             }
             ChartKind::Kde => {
                 let column = self
@@ -8178,7 +14626,7 @@ impl App {
                     c.data.clone()
                 } else {
                     chart_data::prepare_kde_data(
-                        &state.lf,
+                        self.cached_chart_source(&state.lf, row_limit),
                         std::slice::from_ref(&column),
                         self.chart_modal.kde_bandwidth_factor,
                         row_limit,
@@ -8193,6 +14641,7 @@ impl App {
                     .map(|s| ChartExportSeries {
                         name: s.name.clone(),
                         points: s.points.clone(),
+                        band: None,
                     })
                     .collect();
                 let bounds = ChartExportBounds {
@@ -8200,20 +14649,22 @@ impl App {
                     x_max: data.x_max,
                     y_min: 0.0,
                     y_max: data.y_max,
-                    x_label: column.clone(),
-                    y_label: "Density".to_string(),
+                    x_label: labels.x_title(&column).to_string(),
+                    y_label: labels.y_title("Density").to_string(),
                     x_axis_kind: chart_data::XAxisTemporalKind::Numeric,
                     log_scale: false,
                     chart_title,
+                    x_ticks: labels.x_ticks,
+                    y_ticks: labels.y_ticks,
+                    right_axis: None,
                 };
-                match format {
-                    ChartExportFormat::Png => {
-                        write_chart_png(path, &series, ChartType::Line, &bounds, (width, height))
-                    }
-                    ChartExportFormat::Eps => {
-                        write_chart_eps(path, &series, ChartType::Line, &bounds)
-                    }
-                }
+                let plot = ExportPlot::Xy {
+                    series: &series,
+                    chart_type: ChartType::Line,
+                    bounds: &bounds,
+                };
+                let summary = self.chart_export_summary(state, &[column], row_limit)?;
+                write_chart_export(path, format, &plot, summary.as_deref(), (width, height))
             }
             ChartKind::Heatmap => {
                 let x_column = self
@@ -8225,46 +14676,259 @@ impl App {
                     .effective_heatmap_y_column()
                     .ok_or_else(|| color_eyre::eyre::eyre!("No heatmap Y column selected"))?;
                 let row_limit = self.chart_modal.effective_row_limit();
-                let data = if let Some(c) = self.chart_cache.heatmap.as_ref().filter(|c| {
-                    c.x_column == *x_column
-                        && c.y_column == *y_column
-                        && c.bins == self.chart_modal.heatmap_bins
+                let data = if let Some(c) = self.chart_cache.heatmap.as_ref().filter(|c| {
+                    c.x_column == *x_column
+                        && c.y_column == *y_column
+                        && c.bins == self.chart_modal.heatmap_bins
+                        && c.row_limit == self.chart_modal.row_limit
+                }) {
+                    c.data.clone()
+                } else {
+                    chart_data::prepare_heatmap_data(
+                        self.cached_chart_source(&state.lf, row_limit),
+                        &x_column,
+                        &y_column,
+                        self.chart_modal.heatmap_bins,
+                        row_limit,
+                    )?
+                };
+                if data.counts.is_empty() || data.max_count <= 0.0 {
+                    return Err(color_eyre::eyre::eyre!("No valid data points to export"));
+                }
+                let bounds = ChartExportBounds {
+                    x_min: data.x_min,
+                    x_max: data.x_max,
+                    y_min: data.y_min,
+                    y_max: data.y_max,
+                    x_label: labels.x_title(&x_column).to_string(),
+                    y_label: labels.y_title(&y_column).to_string(),
+                    x_axis_kind: chart_data::XAxisTemporalKind::Numeric,
+                    log_scale: false,
+                    chart_title,
+                    x_ticks: labels.x_ticks,
+                    y_ticks: labels.y_ticks,
+                    right_axis: None,
+                };
+                let plot = ExportPlot::Heatmap {
+                    data: &data,
+                    bounds: &bounds,
+                };
+                let summary = self.chart_export_summary(state, &[x_column, y_column], row_limit)?;
+                write_chart_export(path, format, &plot, summary.as_deref(), (width, height))
+            }
+            ChartKind::Category => {
+                let column = self
+                    .chart_modal
+                    .category_column
+                    .clone()
+                    .ok_or_else(|| color_eyre::eyre::eyre!("No category column selected"))?;
+                let agg = self.chart_modal.category_agg;
+                let value = self
+                    .chart_modal
+                    .effective_category_value()
+                    .map(str::to_string);
+                let top = self.chart_modal.category_top;
+                let row_limit = self.chart_modal.effective_row_limit();
+                let data = if let Some(c) = self.chart_cache.category.as_ref().filter(|c| {
+                    c.column == column
+                        && c.agg == agg
+                        && c.value == value
+                        && c.top == top
+                        && c.row_limit == self.chart_modal.row_limit
+                }) {
+                    c.data.clone()
+                } else {
+                    chart_data::prepare_category_bar_data(
+                        self.cached_chart_source(&state.lf, row_limit),
+                        &column,
+                        agg,
+                        value.as_deref(),
+                        top,
+                        row_limit,
+                    )?
+                };
+                if data.bars.is_empty() {
+                    return Err(color_eyre::eyre::eyre!("No valid data points to export"));
+                }
+                let bounds = BoxPlotExportBounds {
+                    y_min: data.y_min,
+                    y_max: data.y_max,
+                    x_labels: data.bars.iter().map(|(name, _)| name.clone()).collect(),
+                    x_label: labels.x_title(&column).to_string(),
+                    y_label: labels
+                        .y_title(&self.chart_modal.category_axis_name())
+                        .to_string(),
+                    chart_title,
+                    y_ticks: labels.y_ticks,
+                };
+                let plot = ExportPlot::CategoryBars {
+                    data: &data,
+                    bounds: &bounds,
+                };
+                let summary = self.chart_export_summary(state, value.as_slice(), row_limit)?;
+                write_chart_export(path, format, &plot, summary.as_deref(), (width, height))
+            }
+            ChartKind::Scatter => {
+                let x_column = self
+                    .chart_modal
+                    .scatter_x_column
+                    .clone()
+                    .ok_or_else(|| color_eyre::eyre::eyre!("No scatter X column selected"))?;
+                let y_column = self
+                    .chart_modal
+                    .scatter_y_column
+                    .clone()
+                    .ok_or_else(|| color_eyre::eyre::eyre!("No scatter Y column selected"))?;
+                let row_limit = self.chart_modal.effective_row_limit();
+                let data = if let Some(c) = self.chart_cache.scatter.as_ref().filter(|c| {
+                    c.x_column == x_column
+                        && c.y_column == y_column
                         && c.row_limit == self.chart_modal.row_limit
                 }) {
                     c.data.clone()
                 } else {
-                    chart_data::prepare_heatmap_data(
-                        &state.lf,
+                    chart_data::prepare_scatter_data(
+                        self.cached_chart_source(&state.lf, row_limit),
                         &x_column,
                         &y_column,
-                        self.chart_modal.heatmap_bins,
                         row_limit,
                     )?
                 };
-                if data.counts.is_empty() || data.max_count <= 0.0 {
+                if data.points.is_empty() {
                     return Err(color_eyre::eyre::eyre!("No valid data points to export"));
                 }
+                // Files draw every point in one color; density shading is for the terminal.
+                let series = [ChartExportSeries {
+                    name: y_column.clone(),
+                    points: data.points.clone(),
+                    band: None,
+                }];
                 let bounds = ChartExportBounds {
                     x_min: data.x_min,
                     x_max: data.x_max,
                     y_min: data.y_min,
                     y_max: data.y_max,
-                    x_label: x_column.clone(),
-                    y_label: y_column.clone(),
+                    x_label: labels.x_title(&x_column).to_string(),
+                    y_label: labels.y_title(&y_column).to_string(),
                     x_axis_kind: chart_data::XAxisTemporalKind::Numeric,
                     log_scale: false,
                     chart_title,
+                    x_ticks: labels.x_ticks,
+                    y_ticks: labels.y_ticks,
+                    right_axis: None,
                 };
-                match format {
-                    ChartExportFormat::Png => {
-                        write_heatmap_png(path, &data, &bounds, (width, height))
-                    }
-                    ChartExportFormat::Eps => write_heatmap_eps(path, &data, &bounds),
-                }
+                let plot = ExportPlot::Xy {
+                    series: &series,
+                    chart_type: ChartType::Scatter,
+                    bounds: &bounds,
+                };
+                let summary = self.chart_export_summary(state, &[x_column, y_column], row_limit)?;
+                write_chart_export(path, format, &plot, summary.as_deref(), (width, height))
             }
         }
     }
 
+    /// Summary statistics of `columns` for a report-card export, within the chart's row limit.
+    /// None when the export modal's report card option is off.
+    /// The Correlation Matrix tool's result, once computed.
+    /// Results table of the selected analysis tool, for exporting with `E`.
+    fn analysis_results_frame(&self) -> Option<polars::prelude::PolarsResult<DataFrame>> {
+        if let Some(table) = self.analysis_crosstab() {
+            return Some(table.to_frame());
+        }
+        let tool = self.analysis_modal.selected_tool?;
+        analysis_export::results_frame(tool, self.analysis_modal.current_results()?)
+    }
+
+    /// The Crosstab tool's table, when it is the selected tool and was built.
+    fn analysis_crosstab(&self) -> Option<&crosstab::Crosstab> {
+        if self.analysis_modal.selected_tool != Some(analysis_modal::AnalysisTool::Crosstab) {
+            return None;
+        }
+        self.analysis_modal.crosstab_results.as_ref()?.as_ref().ok()
+    }
+
+    fn correlation_matrix(&self) -> Option<&statistics::CorrelationMatrix> {
+        self.analysis_modal
+            .correlation_results
+            .as_ref()?
+            .correlation_matrix
+            .as_ref()
+            .filter(|m| !m.columns.is_empty())
+    }
+
+    fn chart_export_summary<T: AsRef<str>>(
+        &self,
+        state: &DataTableState,
+        columns: &[T],
+        row_limit: usize,
+    ) -> color_eyre::Result<Option<Vec<chart_data::ColumnSummary>>> {
+        if !self.chart_export_modal.report_card {
+            return Ok(None);
+        }
+        let summary = chart_data::prepare_chart_summary(
+            self.cached_chart_source(&state.lf, row_limit),
+            columns,
+            row_limit,
+        )?;
+        if summary.is_empty() {
+            return Err(color_eyre::eyre::eyre!("No values to summarize"));
+        }
+        Ok(Some(summary))
+    }
+
+    /// Applies a template, first prompting for its parameters (if any) in the input strip.
+    fn start_template(&mut self, template: &Template) -> Result<()> {
+        if template.settings.parameters.is_empty() {
+            return self.apply_template(template);
+        }
+        self.pending_template = Some(PendingTemplateParameters {
+            template: template.clone(),
+            values: std::collections::HashMap::new(),
+            next: 0,
+        });
+        self.prompt_next_template_parameter();
+        Ok(())
+    }
+
+    /// Shows the prompt for the next pending template parameter, or applies the template once
+    /// all values are known.
+    fn prompt_next_template_parameter(&mut self) {
+        let Some(pending) = &self.pending_template else {
+            return;
+        };
+        if let Some(param) = pending.template.settings.parameters.get(pending.next) {
+            self.input_mode = InputMode::Editing;
+            self.input_type = Some(InputType::TemplateParameter);
+            self.query_input.value = param.default.clone().unwrap_or_default();
+            self.query_input.cursor = self.query_input.value.chars().count();
+            self.query_input.set_focused(true);
+            return;
+        }
+        let Some(pending) = self.pending_template.take() else {
+            return;
+        };
+        self.query_input.set_focused(false);
+        self.input_mode = InputMode::Normal;
+        self.input_type = None;
+        let template = pending.template.with_parameters(&pending.values);
+        if let Err(e) = self.apply_template(&template) {
+            self.error_modal
+                .show(format!("Error applying template: {}", e));
+        }
+    }
+
+    /// Text for the parameter prompt currently shown in the input strip.
+    pub(crate) fn template_parameter_prompt(&self) -> Option<&str> {
+        let pending = self.pending_template.as_ref()?;
+        pending
+            .template
+            .settings
+            .parameters
+            .get(pending.next)
+            .map(|p| p.prompt_text())
+    }
+
     fn apply_template(&mut self, template: &Template) -> Result<()> {
         // Save state before applying template so we can restore on failure
         let saved_state = self
@@ -8281,12 +14945,57 @@ impl App {
                 sort_ascending: state.get_sort_ascending(),
                 column_order: state.get_column_order().to_vec(),
                 locked_columns_count: state.locked_columns_count(),
+                computed_columns: state.computed_columns().to_vec(),
+                source: state.source_lf_clone(),
+                column_casts: state.column_casts().to_vec(),
+                column_renames: state.column_renames().to_vec(),
             });
         let saved_active_template_id = self.active_template_id.clone();
 
         if let Some(state) = &mut self.data_table_state {
             state.error = None;
 
+            // Renames first: casts, computed columns and everything after use the new names
+            if !template.settings.column_renames.is_empty() {
+                if let Err(message) = state.rename_columns(&template.settings.column_renames) {
+                    if let Some(saved) = saved_state {
+                        self.restore_state(saved);
+                    }
+                    self.active_template_id = saved_active_template_id;
+                    return Err(color_eyre::eyre::eyre!("{}", message));
+                }
+            }
+
+            // Casts next, so computed columns and the query see the columns' new types
+            if !template.settings.column_casts.is_empty() {
+                if let Err(message) = state.cast_columns(&template.settings.column_casts) {
+                    if let Some(saved) = saved_state {
+                        self.restore_state(saved);
+                    }
+                    self.active_template_id = saved_active_template_id;
+                    return Err(color_eyre::eyre::eyre!("{}", message));
+                }
+            }
+
+            // Then computed columns, so the query and everything after it can use them. They
+            // replace computed columns of the same name and keep the others.
+            if !template.settings.computed_columns.is_empty() {
+                let mut columns = state.computed_columns().to_vec();
+                for column in &template.settings.computed_columns {
+                    match columns.iter_mut().find(|c| c.name == column.name) {
+                        Some(existing) => *existing = column.clone(),
+                        None => columns.push(column.clone()),
+                    }
+                }
+                if let Err(message) = state.set_computed_columns(columns) {
+                    if let Some(saved) = saved_state {
+                        self.restore_state(saved);
+                    }
+                    self.active_template_id = saved_active_template_id;
+                    return Err(color_eyre::eyre::eyre!("{}", message));
+                }
+            }
+
             // At most one of SQL or DSL query is stored per template; then fuzzy. Apply in that order.
             let sql_trimmed = template.settings.sql_query.as_deref().unwrap_or("").trim();
             let query_opt = template.settings.query.as_deref().filter(|s| !s.is_empty());
@@ -8360,7 +15069,7 @@ impl App {
                 }
             }
 
-            // Apply pivot or melt (reshape) if present. Order: query → filters → sort → reshape → column_order.
+            // Apply pivot, melt or group-by (reshape) if present. Order: query → filters → sort → reshape → column_order.
             if let Some(ref spec) = template.settings.pivot {
                 if let Err(e) = state.pivot(spec) {
                     if let Some(saved) = saved_state {
@@ -8383,6 +15092,17 @@ impl App {
                         crate::error_display::user_message_from_report(&e, None)
                     ));
                 }
+            } else if let Some(ref spec) = template.settings.group_by {
+                if let Err(e) = state.group_by(spec) {
+                    if let Some(saved) = saved_state {
+                        self.restore_state(saved);
+                    }
+                    self.active_template_id = saved_active_template_id;
+                    return Err(color_eyre::eyre::eyre!(
+                        "{}",
+                        crate::error_display::user_message_from_report(&e, None)
+                    ));
+                }
             }
 
             // Apply column order and locks
@@ -8410,6 +15130,10 @@ impl App {
                     return Err(color_eyre::eyre::eyre!("{}", error));
                 }
             }
+
+            state
+                .column_formats
+                .extend(template.settings.column_formats.clone());
         }
 
         // Update template usage statistics
@@ -8432,6 +15156,56 @@ impl App {
         Ok(())
     }
 
+    /// Apply a view given when opening the data: its template (loaded from disk if need be),
+    /// then its query, filters, sort and column order in place of the template's.
+    fn apply_initial_view(&mut self, view: &initial_view::InitialView) -> Result<()> {
+        if let Some(name) = &view.template {
+            self.template_manager.ensure_loaded();
+            let template = initial_view::find_template(&self.template_manager, name)
+                .cloned()
+                .ok_or_else(|| color_eyre::eyre::eyre!("Template not found: {}", name))?;
+            self.start_template(&template)?;
+        }
+        let Some(state) = self.data_table_state.as_mut() else {
+            return Ok(());
+        };
+        state.error = None;
+        if let Some(query) = &view.query {
+            state.query(query.clone());
+        }
+        // Filters and sort of the data itself would drop a query's computed columns, so under
+        // a query they narrow its result instead
+        if state.error.is_none() && !state.active_query.is_empty() {
+            if !view.filters.is_empty() || !view.sort.is_empty() {
+                state.narrow_view(&view.filters, &view.sort, !view.descending);
+            }
+        } else {
+            if state.error.is_none() && !view.filters.is_empty() {
+                state.filter(view.filters.clone());
+            }
+            if state.error.is_none() && !view.sort.is_empty() {
+                state.sort(view.sort.clone(), !view.descending);
+            }
+        }
+        if state.error.is_none() && !view.columns.is_empty() {
+            let mut order = view.columns.clone();
+            order.extend(
+                state
+                    .headers()
+                    .into_iter()
+                    .filter(|name| !view.columns.contains(name)),
+            );
+            state.set_column_order(order);
+        }
+        match state.error.take() {
+            Some(error) => Err(color_eyre::eyre::eyre!(
+                "{}",
+                crate::error_display::user_message_from_polars(&error)
+            )),
+            None => Ok(()),
+        }
+    }
+
     /// Format export error messages to be more user-friendly using type-based handling.
     fn format_export_error(error: &color_eyre::eyre::Report, path: &Path) -> String {
         use std::io;
@@ -8499,6 +15273,9 @@ impl App {
                         .finish(df)?;
                 }
             }
+            ExportFormat::Parquet if !options.parquet_partition_by.is_empty() => {
+                crate::hive_export::write_hive_parquet(df, path, &options.parquet_partition_by)?;
+            }
             ExportFormat::Parquet => {
                 use polars::prelude::ParquetWriter;
                 let file = File::create(path)?;
@@ -8585,6 +15362,25 @@ impl App {
                 let mut writer = BufWriter::new(file);
                 AvroWriter::new(&mut writer).finish(df)?;
             }
+            ExportFormat::Xlsx => {
+                crate::xlsx_export::write_xlsx(
+                    df,
+                    path,
+                    &options.xlsx_sheet_name,
+                    options.xlsx_autofit,
+                )?;
+            }
+            ExportFormat::Sqlite => {
+                crate::sqlite_export::write_sqlite(
+                    df,
+                    path,
+                    &options.sqlite_table,
+                    options.sqlite_if_exists,
+                )?;
+            }
+            ExportFormat::Markdown => {
+                std::fs::write(path, crate::yank::to_markdown(df) + "\n")?;
+            }
         }
 
         Ok(())
@@ -8606,6 +15402,19 @@ impl App {
             // Clone saved lf and schema so we can restore them after applying methods
             let saved_lf = saved.lf.clone();
             let saved_schema = saved.schema.clone();
+            // The saved source and columns were valid before the template was applied
+            if state.column_casts() != saved.column_casts.as_slice()
+                || state.column_renames() != saved.column_renames.as_slice()
+            {
+                state.restore_source_changes(
+                    saved.source,
+                    saved.computed_columns,
+                    saved.column_casts,
+                    saved.column_renames,
+                );
+            } else {
+                let _ = state.set_computed_columns(saved.computed_columns);
+            }
 
             // Restore lf and schema directly (these are public fields)
             // This preserves the exact LazyFrame state from before template application
@@ -8659,6 +15468,13 @@ impl App {
                 locked_columns_count: state.locked_columns_count(),
                 pivot: state.last_pivot_spec().cloned(),
                 melt: state.last_melt_spec().cloned(),
+                group_by: state.last_group_by_spec().cloned(),
+                parameters: Vec::new(),
+                column_formats: state.column_formats.clone(),
+                computed_columns: state.computed_columns().to_vec(),
+                column_casts: state.column_casts().to_vec(),
+                column_renames: state.column_renames().to_vec(),
+                saved_queries: Vec::new(),
             }
         } else {
             template::TemplateSettings {
@@ -8672,6 +15488,13 @@ impl App {
                 locked_columns_count: 0,
                 pivot: None,
                 melt: None,
+                group_by: None,
+                parameters: Vec::new(),
+                column_formats: Default::default(),
+                computed_columns: Vec::new(),
+                column_casts: Vec::new(),
+                column_renames: Vec::new(),
+                saved_queries: Vec::new(),
             }
         };
 
@@ -8681,7 +15504,13 @@ impl App {
 
     fn get_help_info(&self) -> (String, String) {
         let (title, content) = match self.input_mode {
-            InputMode::Normal => ("Main View Help", help_strings::main_view()),
+            InputMode::Normal if self.workspace_list_visible() => {
+                ("Workspace Help", help_strings::workspace())
+            }
+            InputMode::Normal => {
+                let content = self.key_bindings.render_help(help_strings::main_view());
+                return ("Main View Help".to_string(), content);
+            }
             InputMode::Editing => match self.input_type {
                 Some(InputType::Search) => ("Query Help", help_strings::query()),
                 _ => ("Editing Help", help_strings::editing()),
@@ -8711,11 +15540,13 @@ impl Widget for &mut App {
             &self.theme,
             self.table_cell_padding,
             self.column_colors,
+            self.display_locale,
         );
 
         let main_view_content = MainViewContent::from_app_state(
             self.analysis_modal.active,
             self.input_mode == InputMode::Chart,
+            self.workspace_list_visible(),
         );
 
         Clear.render(area, buf);
@@ -8727,24 +15558,125 @@ impl Widget for &mut App {
         let app_layout = app_layout(area, self.debug.enabled);
         let main_area = app_layout.main_view;
         Clear.render(main_area, buf);
+        // Upper bound for the table: the main view less the header row.
+        self.viewport_rows_hint = main_area.height.saturating_sub(1) as usize;
 
         crate::render::main_view_render::render_main_view(area, main_area, buf, self, &ctx);
 
         // Status messages are shown inline in the control bar (no overlay popups).
 
+        if let Some(ref peek) = self.aggregate_peek {
+            crate::render::overlays::render_aggregate_peek(area, buf, peek, &ctx);
+        }
+        if let Some(ref mut popup) = self.value_counts {
+            crate::render::overlays::render_value_counts(area, buf, popup, &ctx);
+        }
+        if let Some(ref mut view) = self.record_view {
+            crate::render::overlays::render_record_view(area, buf, view, &ctx);
+        }
+        if let Some(ref mut view) = self.data_diff {
+            crate::render::overlays::render_data_diff(area, buf, view, &ctx);
+        }
+        #[cfg(feature = "cloud")]
+        if let Some(ref mut browser) = self.s3_browser {
+            crate::render::overlays::render_s3_browser(area, buf, browser, &ctx);
+        }
+        if let Some(ref mut browser) = self.file_browser {
+            crate::render::overlays::render_file_browser(area, buf, browser, &ctx);
+        }
+        if let Some(ref mut picker) = self.table_picker {
+            crate::render::overlays::render_table_picker(area, buf, picker, &ctx);
+        }
+        if let Some(ref mut preview) = self.sheet_preview {
+            crate::render::overlays::render_sheet_preview(area, buf, preview, &ctx);
+        }
+        if let Some(ref modal) = self.csv_dialect {
+            crate::render::overlays::render_csv_dialect(area, buf, modal, &ctx);
+        }
+        if let Some(ref mut panel) = self.load_report {
+            crate::render::overlays::render_load_report(area, buf, panel, &ctx);
+        }
+        if let Some(ref mut modal) = self.schema_modal {
+            crate::render::overlays::render_schema_modal(area, buf, modal, &ctx);
+        }
+        if let Some(ref mut modal) = self.columns_modal {
+            crate::render::overlays::render_columns_modal(area, buf, modal, &ctx);
+        }
+        if let Some(ref mut modal) = self.scripts_modal {
+            crate::render::overlays::render_scripts_modal(area, buf, modal, &ctx);
+        }
+        if let Some(ref mut modal) = self.saved_queries_modal {
+            crate::render::overlays::render_saved_queries_modal(area, buf, modal, &ctx);
+        }
+        if let Some(ref mut palette) = self.command_palette {
+            crate::render::overlays::render_command_palette(
+                area,
+                buf,
+                palette,
+                &self.key_bindings,
+                &ctx,
+            );
+        }
+        if let Some((ref column, ref value)) = self.cell_peek {
+            crate::render::overlays::render_cell_peek(area, buf, column, value, &ctx);
+        }
+        if self.format_modal.active {
+            crate::render::overlays::render_format_modal(area, buf, &mut self.format_modal, &ctx);
+        }
+        if self.sampling_modal.active {
+            crate::render::overlays::render_sampling_modal(
+                area,
+                buf,
+                &mut self.sampling_modal,
+                &ctx,
+            );
+        }
+        if self.group_by_modal.active {
+            crate::render::overlays::render_group_by_modal(area, buf, &self.group_by_modal, &ctx);
+        }
+        if self.resample_modal.active {
+            crate::render::overlays::render_resample_modal(
+                area,
+                buf,
+                &mut self.resample_modal,
+                &ctx,
+            );
+        }
+        if self.time_zone_modal.active {
+            crate::render::overlays::render_time_zone_modal(
+                area,
+                buf,
+                &mut self.time_zone_modal,
+                &ctx,
+            );
+        }
+        if self.cast_modal.active {
+            crate::render::overlays::render_cast_modal(area, buf, &mut self.cast_modal, &ctx);
+        }
+        if self.rolling_modal.active {
+            crate::render::overlays::render_rolling_modal(area, buf, &mut self.rolling_modal, &ctx);
+        }
+        if self.computed_column_modal.active {
+            crate::render::overlays::render_computed_column_modal(
+                area,
+                buf,
+                &mut self.computed_column_modal,
+                &ctx,
+            );
+        }
         if self.confirmation_modal.active {
             crate::render::overlays::render_confirmation_modal(
                 area,
                 buf,
-                &self.confirmation_modal,
+                &mut self.confirmation_modal,
                 &ctx,
             );
         }
         if self.success_modal.active {
-            crate::render::overlays::render_success_modal(area, buf, &self.success_modal, &ctx);
+            crate::render::overlays::render_success_modal(area, buf, &mut self.success_modal, &ctx);
         }
         if self.error_modal.active {
-            crate::render::overlays::render_error_modal(area, buf, &self.error_modal, &ctx);
+            crate::render::overlays::render_error_modal(area, buf, &mut self.error_modal, &ctx);
         }
         if self.show_help
             || (self.template_modal.active && self.template_modal.show_help)
@@ -8780,6 +15712,12 @@ impl Widget for &mut App {
             .with_unicode_throbber(use_unicode_throbber);
 
         // Derive status message from loading_state or explicit status_message.
+        // Esc is only read between events, so the cancel hint waits for a worker to be running.
+        let cancel_hint = if self.bg_workers.load(std::sync::atomic::Ordering::SeqCst) > 0 {
+            "  Esc: cancel"
+        } else {
+            ""
+        };
         let status_msg = match &self.loading_state {
             LoadingState::Loading {
                 current_phase,
@@ -8787,9 +15725,12 @@ impl Widget for &mut App {
                 ..
             } => {
                 if *progress_percent > 0 {
-                    Some(format!("{}... ({}%)", current_phase, progress_percent))
+                    Some(format!(
+                        "{}... ({}%){}",
+                        current_phase, progress_percent, cancel_hint
+                    ))
                 } else {
-                    Some(format!("{}...", current_phase))
+                    Some(format!("{}...{}", current_phase, cancel_hint))
                 }
             }
             LoadingState::Exporting {
@@ -8808,7 +15749,8 @@ impl Widget for &mut App {
                 }
             }
             LoadingState::Idle => {
-                if self.busy {
+                // Without data (a cancelled first load) the status says what happened.
+                if self.busy || self.data_table_state.is_none() {
                     self.status_message.clone()
                 } else {
                     None
@@ -8817,12 +15759,23 @@ impl Widget for &mut App {
         };
         controls = controls.with_status_message(status_msg);
 
+        controls = controls.with_sampling_status(
+            self.sampling_threshold
+                .map(|_| format!("sample: {}", self.sampling_strategy.label())),
+        );
         match crate::render::main_view::control_bar_spec(self, main_view_content) {
             crate::render::main_view::ControlBarSpec::Datatable {
                 dimmed,
-                query_active,
+                query_engine,
             } => {
-                controls = controls.with_dimmed(dimmed).with_query_active(query_active);
+                let search_status = self.data_table_state.as_ref().and_then(|state| {
+                    let search = state.regex_search.as_ref()?;
+                    Some(search.status(&regex_search::view_plan(&state.lf)))
+                });
+                controls = controls
+                    .with_dimmed(dimmed)
+                    .with_query_engine(query_engine)
+                    .with_search_status(search_status);
             }
             crate::render::main_view::ControlBarSpec::Custom(pairs) => {
                 controls = controls.with_custom_controls(pairs);
@@ -8849,6 +15802,46 @@ impl Widget for &mut App {
     }
 }
 
+/// Added to the panic report so users can tell whether their setup is involved.
+const SAFE_MODE_HINT: &str = "If this keeps happening, run datui again with --safe-mode. \
+     It skips your config, templates, cache and theme; if the problem goes away, one of those is the cause.";
+
+/// Leave the TUI: stop focus and mouse reports, then restore the terminal's normal mode and
+/// screen.
+fn restore_terminal() {
+    let _ = crossterm::execute!(
+        std::io::stdout(),
+        crossterm::event::DisableFocusChange,
+        crossterm::event::DisableMouseCapture
+    );
+    ratatui::restore();
+}
+
+/// Start the reports the TUI listens to: focus changes, and mouse events when `mouse` is on.
+fn enable_reports(mouse: bool) -> std::io::Result<()> {
+    crossterm::execute!(std::io::stdout(), crossterm::event::EnableFocusChange)?;
+    if mouse {
+        crossterm::execute!(std::io::stdout(), crossterm::event::EnableMouseCapture)?;
+    }
+    Ok(())
+}
+
+/// Hand the terminal back to the shell while `f` runs (suspended process, external command),
+/// then restore raw mode and the alternate screen and clear so the next draw repaints fully.
+fn release_terminal<T>(
+    terminal: &mut ratatui::DefaultTerminal,
+    mouse: bool,
+    f: impl FnOnce() -> T,
+) -> Result<T> {
+    restore_terminal();
+    let out = f();
+    crossterm::terminal::enable_raw_mode()?;
+    crossterm::execute!(std::io::stdout(), crossterm::terminal::EnterAlternateScreen)?;
+    enable_reports(mouse)?;
+    terminal.clear()?;
+    Ok(out)
+}
+
 /// Run the TUI with either file paths or an existing LazyFrame. Single event loop used by CLI and Python binding.
 pub fn run(input: RunInput, config: Option<AppConfig>) -> Result<()> {
     use std::io::Write;
@@ -8859,20 +15852,31 @@ pub fn run(input: RunInput, config: Option<AppConfig>) -> Result<()> {
         None => AppConfig::load(APP_NAME)?,
     };
 
-    let opts = match &input {
-        RunInput::Paths(_, o) => o.clone(),
-        RunInput::LazyFrame(_, o) => o.clone(),
-    };
+    let opts = input.options().clone();
 
-    let theme = Theme::from_config(&config.theme)
-        .or_else(|e| Theme::from_config(&AppConfig::default().theme).map_err(|_| e))?;
+    // Query the terminal before the TUI takes over stdin; only needed in "auto" mode.
+    let background = if config.theme.mode == "auto" {
+        terminal_background::detect()
+    } else {
+        None
+    };
+    let theme = Theme::from_config_with_background(&config.theme, background).or_else(|e| {
+        Theme::from_config_with_background(&AppConfig::default().theme, background).map_err(|_| e)
+    })?;
 
     // Install color_eyre at most once per process (e.g. first datui.view() in Python).
     // Subsequent run() calls skip install and reuse the result; no error-message detection.
     static COLOR_EYRE_INIT: Once = Once::new();
     static INSTALL_RESULT: Mutex<Option<Result<(), color_eyre::Report>>> = Mutex::new(None);
+    let safe_mode = opts.safe_mode;
     COLOR_EYRE_INIT.call_once(|| {
-        *INSTALL_RESULT.lock().unwrap_or_else(|e| e.into_inner()) = Some(color_eyre::install());
+        let hook = color_eyre::config::HookBuilder::default();
+        let hook = if safe_mode {
+            hook
+        } else {
+            hook.panic_section(SAFE_MODE_HINT)
+        };
+        *INSTALL_RESULT.lock().unwrap_or_else(|e| e.into_inner()) = Some(hook.install());
     });
     if let Some(Err(e)) = INSTALL_RESULT
         .lock()
@@ -8881,19 +15885,45 @@ pub fn run(input: RunInput, config: Option<AppConfig>) -> Result<()> {
     {
         return Err(color_eyre::eyre::eyre!(e.to_string()));
     }
+    // A frame's view is checked before the terminal is taken over, so the caller gets the error
+    if let (RunInput::LazyFrame(lf, _), Some(view)) = (&input, &opts.initial_view) {
+        let templates = if opts.safe_mode {
+            None
+        } else {
+            ConfigManager::new(APP_NAME)
+                .ok()
+                .and_then(|config| TemplateManager::new(&config).ok())
+        };
+        let templates = templates.unwrap_or_else(|| {
+            TemplateManager::empty(&ConfigManager::with_dir(
+                std::env::temp_dir().join(APP_NAME).join("config"),
+            ))
+        });
+        view.validate(lf.as_ref().clone(), &templates)?;
+    }
     // Require at least one path so event handlers can safely use paths[0].
-    if let RunInput::Paths(ref paths, _) = input {
+    let paths = match &input {
+        RunInput::Paths(paths, _) => Some(paths.clone()),
+        RunInput::Diff(request, _) => Some(vec![request.old.clone(), request.new.clone()]),
+        RunInput::LazyFrame(..) => None,
+    };
+    if let Some(paths) = paths {
         if paths.is_empty() {
             return Err(color_eyre::eyre::eyre!("At least one path is required"));
         }
-        for path in paths {
+        for path in &paths {
+            // A SQLite URL must name an existing file.
+            let sqlite_file = source::sqlite_url(path).map(|(file, _)| file);
+            let path = sqlite_file.as_ref().unwrap_or(path);
             let s = path.to_string_lossy();
             let is_remote = s.starts_with("s3://")
                 || s.starts_with("gs://")
                 || s.starts_with("http://")
-                || s.starts_with("https://");
-            let is_glob = s.contains('*');
-            if !is_remote && !is_glob && !path.exists() {
+                || s.starts_with("https://")
+                || matches!(source::input_source(path), source::InputSource::Database(_));
+            let is_glob = s.contains('*') || source::has_wildcard(path);
+            let is_stdin = source::input_source(path) == source::InputSource::Stdin;
+            if !is_remote && !is_glob && !is_stdin && !path.exists() {
                 return Err(std::io::Error::new(
                     std::io::ErrorKind::NotFound,
                     format!("File not found: {}", path.display()),
@@ -8937,11 +15967,30 @@ pub fn run(input: RunInput, config: Option<AppConfig>) -> Result<()> {
             e
         )
     })?;
+    // Focus reports tell completion notifications whether the user is looking. The panic hook
+    // installed by ratatui restores the screen; stop the reports too so the shell doesn't get them.
+    let mouse = config.ui.mouse;
+    let _ = enable_reports(mouse);
+    let panic_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = crossterm::execute!(
+            std::io::stdout(),
+            crossterm::event::DisableFocusChange,
+            crossterm::event::DisableMouseCapture
+        );
+        panic_hook(info);
+    }));
     let (tx, rx) = mpsc::channel::<AppEvent>();
-    let mut app = App::new_with_config(tx.clone(), rt_handle, theme, config.clone());
+    let mut app = if opts.safe_mode {
+        App::new_safe_mode(tx.clone(), rt_handle, theme, config.clone())
+    } else {
+        App::new_with_config(tx.clone(), rt_handle, theme, config.clone())
+    };
     if opts.debug {
         app.enable_debug();
     }
+    app.pending_script = opts.script.clone();
+    app.pending_view = opts.initial_view.clone();
 
     // Send initial event and show the first frame immediately.
     match input {
@@ -8953,29 +16002,64 @@ pub fn run(input: RunInput, config: Option<AppConfig>) -> Result<()> {
             app.set_loading_phase("Scanning input", 10);
             tx.send(AppEvent::OpenLazyFrame(lf, opts))?;
         }
+        RunInput::Diff(request, opts) => {
+            app.set_loading_phase("Scanning input", 10);
+            tx.send(AppEvent::Open(vec![request.old.clone()], opts.clone()))?;
+            tx.send(AppEvent::OpenDiff(request, opts))?;
+        }
     }
     app.busy = true;
     terminal.draw(|frame| frame.render_widget(&mut app, frame.area()))?;
     let _ = std::io::stdout().flush();
 
     // Main event loop: poll for input, drain queued events, redraw.
+    // While busy, poll briefly so results from background tasks (which arrive on the channel,
+    // not through crossterm) are picked up within a few ms: a cold open chains several of
+    // them. The throbber advances on its own ~30fps clock so short polls don't speed it up
+    // or multiply redraws.
+    const BUSY_POLL_MS: u64 = 4;
+    const THROBBER_INTERVAL: std::time::Duration = std::time::Duration::from_millis(33);
+    let mut last_throbber_tick = std::time::Instant::now();
+    let mut long_operation = notifications::LongOperationWatch::default();
     loop {
-        // Poll with a shorter timeout when busy so the throbber animates (~30fps).
-        // 33ms is plenty for a spinner and halves redraw load vs. 60fps.
         let poll_ms = if app.busy || app.len_count_inflight.is_some() {
-            33
+            BUSY_POLL_MS
         } else {
             config.performance.event_poll_interval_ms
         };
 
         if crossterm::event::poll(std::time::Duration::from_millis(poll_ms))? {
             match crossterm::event::read()? {
+                // Raw mode delivers Ctrl-Z as a key, so stop the process here as the shell would.
+                #[cfg(unix)]
+                crossterm::event::Event::Key(key)
+                    if key.is_press()
+                        && key.code == KeyCode::Char('z')
+                        && key.modifiers.contains(KeyModifiers::CONTROL) =>
+                {
+                    release_terminal(&mut terminal, mouse, external_command::suspend)?;
+                    let (cols, rows) = crossterm::terminal::size()?;
+                    tx.send(AppEvent::Resize(cols, rows))?;
+                }
                 crossterm::event::Event::Key(key) if key.is_press() => {
+                    long_operation.acknowledge();
                     tx.send(AppEvent::Key(key))?;
                 }
+                // Pointer motion without a button is not used; skip it so it doesn't redraw.
+                crossterm::event::Event::Mouse(event)
+                    if event.kind != crossterm::event::MouseEventKind::Moved =>
+                {
+                    long_operation.acknowledge();
+                    tx.send(AppEvent::Mouse(event))?;
+                }
                 crossterm::event::Event::Resize(cols, rows) => {
                     tx.send(AppEvent::Resize(cols, rows))?;
                 }
+                crossterm::event::Event::FocusGained => {
+                    long_operation.set_focused(true);
+                    long_operation.acknowledge();
+                }
+                crossterm::event::Event::FocusLost => long_operation.set_focused(false),
                 _ => {}
             }
         }
@@ -8985,12 +16069,27 @@ pub fn run(input: RunInput, config: Option<AppConfig>) -> Result<()> {
         loop {
             match rx.try_recv() {
                 Ok(AppEvent::Exit) => {
-                    ratatui::restore();
+                    restore_terminal();
                     return Ok(());
                 }
+                Ok(AppEvent::RunExternalCommand(command)) => {
+                    let result =
+                        release_terminal(&mut terminal, mouse, || external_command::run(&command))?;
+                    if let Err(e) = result {
+                        app.error_modal
+                            .show(format!("Failed to run command \"{}\": {}", command, e));
+                    }
+                    let (cols, rows) = crossterm::terminal::size()?;
+                    tx.send(AppEvent::Resize(cols, rows))?;
+                    updated = true;
+                }
                 Ok(AppEvent::Crash(msg)) => {
-                    ratatui::restore();
-                    return Err(color_eyre::eyre::eyre!(msg));
+                    if !app.recover_csv_load_error(&msg) && !app.recover_workspace_load_error(&msg)
+                    {
+                        restore_terminal();
+                        return Err(color_eyre::eyre::eyre!(msg));
+                    }
+                    updated = true;
                 }
                 Ok(event) => {
                     if let Some(next) = app.event(&event) {
@@ -9000,16 +16099,27 @@ pub fn run(input: RunInput, config: Option<AppConfig>) -> Result<()> {
                 }
                 Err(mpsc::TryRecvError::Empty) => break,
                 Err(mpsc::TryRecvError::Disconnected) => {
-                    ratatui::restore();
+                    restore_terminal();
                     return Ok(());
                 }
             }
         }
 
+        if let Some((label, elapsed)) = long_operation.update(app.busy, app.busy_label()) {
+            if long_operation.should_notify(&config.notifications, elapsed) {
+                let message =
+                    notifications::completion_message(&label, elapsed, app.error_modal.active);
+                long_operation.notify(&config.notifications, &message);
+            }
+        }
+
         // Animate throbber when busy or while the background row count is still resolving
         // (that count doesn't set `busy` but drives the row-count spinner).
-        if app.busy || app.len_count_inflight.is_some() {
+        if (app.busy || app.len_count_inflight.is_some())
+            && last_throbber_tick.elapsed() >= THROBBER_INTERVAL
+        {
             app.throbber_frame = app.throbber_frame.wrapping_add(1);
+            last_throbber_tick = std::time::Instant::now();
             updated = true;
         }
 
@@ -9031,3 +16141,221 @@ pub fn run(input: RunInput, config: Option<AppConfig>) -> Result<()> {
         }
     }
 }
+
+/// Load `input`, apply its initial view and script, write the result to `export` and return,
+/// without a terminal (`--headless`). The export's extension picks the format (see
+/// [`ExportFormat::from_extension`]); `.gz`, `.zst`, `.bz2` or `.xz` after `csv`, `json` or
+/// `ndjson` compresses it. Whatever would be shown in an error dialog is returned as the error.
+pub fn run_headless(input: RunInput, config: Option<AppConfig>, export: &Path) -> Result<()> {
+    let compression = CompressionFormat::from_extension(export);
+    let format_path = if compression.is_some() {
+        export.with_extension("")
+    } else {
+        export.to_path_buf()
+    };
+    let format = format_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .and_then(ExportFormat::from_extension)
+        .filter(|f| compression.is_none() || f.supports_compression())
+        .ok_or_else(|| {
+            color_eyre::eyre::eyre!(
+                "Can't tell the export format from {}: use .csv, .parquet, .json, .ndjson, \
+                 .arrow, .avro, .xlsx or .db (csv, json and ndjson may add .gz, .zst, .bz2 or .xz)",
+                export.display()
+            )
+        })?;
+
+    let mut headless = Headless::open(input, config)?;
+
+    let options = ExportOptions {
+        csv_delimiter: b',',
+        csv_include_header: true,
+        csv_compression: compression.filter(|_| format == ExportFormat::Csv),
+        json_compression: compression.filter(|_| format == ExportFormat::Json),
+        ndjson_compression: compression.filter(|_| format == ExportFormat::Ndjson),
+        parquet_compression: None,
+        parquet_partition_by: Vec::new(),
+        row_range: None,
+        scope: ExportScope::View,
+        xlsx_sheet_name: crate::xlsx_export::DEFAULT_SHEET_NAME.to_string(),
+        xlsx_autofit: true,
+        sqlite_table: crate::sqlite_export::DEFAULT_TABLE_NAME.to_string(),
+        sqlite_if_exists: IfExists::Replace,
+    };
+    headless.app.busy = true;
+    headless.pump(AppEvent::Export(export.to_path_buf(), format, options))?;
+    headless.rt.shutdown_background();
+    Ok(())
+}
+
+/// Render the loaded view, or with `chart` a chart of those columns (x first), to `output` as
+/// text, SVG or PNG by its extension, without a terminal (`--render`). The view is drawn as in
+/// a terminal `width` columns wide with room for `rows` rows of data.
+pub fn run_render(
+    input: RunInput,
+    config: Option<AppConfig>,
+    output: &Path,
+    (width, rows): (u16, u16),
+    chart: &[String],
+) -> Result<()> {
+    use ratatui::backend::TestBackend;
+
+    let format = snapshot::SnapshotFormat::from_path(output).ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "Can't tell the render format from {}: use .txt, .svg or .png",
+            output.display()
+        )
+    })?;
+    // The header row above the data and the control bar below it
+    let mut terminal = ratatui::Terminal::new(TestBackend::new(width, rows.saturating_add(2)))?;
+    let mut draw = |app: &mut App| -> Result<Buffer> {
+        let frame = terminal.draw(|frame| frame.render_widget(app, frame.area()))?;
+        Ok(frame.buffer.clone())
+    };
+    let mut headless = Headless::new(config, input.options().safe_mode)?;
+    // A first frame sizes the buffer the data is read into
+    draw(&mut headless.app)?;
+    headless.load(input)?;
+    if let Some((x, ys)) = chart.split_first() {
+        headless.app.open_chart();
+        let modal = &mut headless.app.chart_modal;
+        if !modal.x_candidates.contains(x) {
+            return Err(color_eyre::eyre::eyre!(
+                "Can't chart over {x}: the x column must be numeric or a date or time"
+            ));
+        }
+        if ys.is_empty() || ys.len() > chart_modal::Y_SERIES_MAX {
+            return Err(color_eyre::eyre::eyre!(
+                "--chart needs an x column, then 1 to {} numeric y columns, e.g. date,price",
+                chart_modal::Y_SERIES_MAX
+            ));
+        }
+        if let Some(y) = ys.iter().find(|y| !modal.y_candidates.contains(y)) {
+            return Err(color_eyre::eyre::eyre!("Can't chart {y}: it isn't numeric"));
+        }
+        modal.x_column = Some(x.clone());
+        modal.y_columns = ys.to_vec();
+    }
+    draw(&mut headless.app)?;
+    let recollect = headless
+        .app
+        .data_table_state
+        .as_mut()
+        .is_some_and(|state| std::mem::take(&mut state.needs_recollect));
+    if recollect {
+        headless.app.busy = true;
+        headless.pump(AppEvent::Collect)?;
+    }
+    let buffer = draw(&mut headless.app)?;
+    snapshot::write_snapshot(&buffer, output, format)?;
+    headless.rt.shutdown_background();
+    Ok(())
+}
+
+/// Write a profiling report of the loaded view to `output`, HTML or Markdown by its extension,
+/// without a terminal (`--profile`).
+pub fn run_profile(input: RunInput, config: Option<AppConfig>, output: &Path) -> Result<()> {
+    let format = profile_report::ProfileFormat::from_path(output).ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "Can't tell the report format from {}: use .html or .md",
+            output.display()
+        )
+    })?;
+    let headless = Headless::open(input, config)?;
+    let job = headless
+        .app
+        .profile_job()
+        .ok_or_else(|| color_eyre::eyre::eyre!("No data to profile"))?;
+    profile_report::write(&job()?, output, format)?;
+    headless.rt.shutdown_background();
+    Ok(())
+}
+
+/// An app without a terminal, for `--headless`, `--render` and `--profile`: events are handled
+/// as they arrive until no background work is left.
+struct Headless {
+    app: App,
+    rt: tokio::runtime::Runtime,
+    rx: std::sync::mpsc::Receiver<AppEvent>,
+}
+
+impl Headless {
+    fn new(config: Option<AppConfig>, safe_mode: bool) -> Result<Self> {
+        let config = match config {
+            Some(c) => c,
+            None => AppConfig::load(APP_NAME)?,
+        };
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to create tokio runtime: {}", e))?;
+        let theme = Theme::from_config(&config.theme)
+            .or_else(|_| Theme::from_config(&AppConfig::default().theme))?;
+        let (tx, rx) = std::sync::mpsc::channel::<AppEvent>();
+        let app = if safe_mode {
+            App::new_safe_mode(tx, rt.handle().clone(), theme, config)
+        } else {
+            App::new_with_config(tx, rt.handle().clone(), theme, config)
+        };
+        Ok(Self { app, rt, rx })
+    }
+
+    fn open(input: RunInput, config: Option<AppConfig>) -> Result<Self> {
+        let mut headless = Self::new(config, input.options().safe_mode)?;
+        headless.load(input)?;
+        Ok(headless)
+    }
+
+    /// Open `input` with its initial view and script applied.
+    fn load(&mut self, input: RunInput) -> Result<()> {
+        let opts = input.options();
+        self.app.pending_script = opts.script.clone();
+        self.app.pending_view = opts.initial_view.clone();
+        let open = match input {
+            RunInput::Paths(paths, opts) => AppEvent::Open(paths, opts),
+            RunInput::LazyFrame(lf, opts) => AppEvent::OpenLazyFrame(lf, opts),
+            RunInput::Diff(..) => {
+                return Err(color_eyre::eyre::eyre!(
+                    "--diff shows the differences in the app; it can't be used without it"
+                ))
+            }
+        };
+        self.app.busy = true;
+        self.pump(open)?;
+        if self.app.data_table_state.is_none() {
+            return Err(color_eyre::eyre::eyre!(
+                "The data needs a choice made in the app before it can be read (e.g. which table \
+                 or sheet to open); give it on the command line"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Handle `first` and everything it leads to. Whatever would be shown in an error dialog
+    /// is returned as the error.
+    fn pump(&mut self, first: AppEvent) -> Result<()> {
+        let mut next = Some(first);
+        loop {
+            while let Some(event) = next.take() {
+                match event {
+                    AppEvent::Exit => return Ok(()),
+                    AppEvent::Crash(msg) => return Err(color_eyre::eyre::eyre!(msg)),
+                    event => next = self.app.event(&event),
+                }
+            }
+            if self.app.error_modal.active {
+                return Err(color_eyre::eyre::eyre!(self
+                    .app
+                    .error_modal
+                    .message
+                    .clone()));
+            }
+            if !self.app.busy {
+                return Ok(());
+            }
+            next = Some(self.rx.recv()?);
+        }
+    }
+}
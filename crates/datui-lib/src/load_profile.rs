@@ -0,0 +1,168 @@
+//! File-size-aware defaults ("load profiles") chosen when a dataset is opened.
+//!
+//! Small files are read into memory once and analyzed in full. Large files keep the streaming
+//! engine, sample for analysis and buffer fewer pages around the view. Everything in between
+//! uses the configured settings unchanged. The profile is shown in the Info panel, where it can
+//! be changed (which reopens the dataset).
+
+use crate::OpenOptions;
+use std::path::{Path, PathBuf};
+
+/// Rows sampled for analysis under the Large profile when no `sampling_threshold` is configured.
+pub const LARGE_SAMPLING_THRESHOLD: usize = 100_000;
+
+/// Pages buffered ahead of and behind the view under the Large profile.
+const LARGE_PAGES: usize = 1;
+
+const MIB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadProfile {
+    /// Read the whole file into memory; analysis never samples.
+    Small,
+    /// The configured settings.
+    Standard,
+    /// Streaming engine, sampled analysis and a small scroll buffer.
+    Large,
+}
+
+impl LoadProfile {
+    /// Profile for an input of `bytes` on disk. Unknown sizes (remote, directories, globs)
+    /// get Standard.
+    pub fn for_size(bytes: Option<u64>, small_file_mb: u64, large_file_mb: u64) -> Self {
+        match bytes {
+            Some(b) if b <= small_file_mb.saturating_mul(MIB) => LoadProfile::Small,
+            Some(b) if b >= large_file_mb.saturating_mul(MIB) => LoadProfile::Large,
+            _ => LoadProfile::Standard,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LoadProfile::Small => "Small",
+            LoadProfile::Standard => "Standard",
+            LoadProfile::Large => "Large",
+        }
+    }
+
+    /// One-line summary of what the profile changes, for the Info panel.
+    pub fn summary(self) -> &'static str {
+        match self {
+            LoadProfile::Small => "in memory, no sampling",
+            LoadProfile::Standard => "configured settings",
+            LoadProfile::Large => "streaming, sampled analysis, small buffer",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            LoadProfile::Small => LoadProfile::Standard,
+            LoadProfile::Standard => LoadProfile::Large,
+            LoadProfile::Large => LoadProfile::Small,
+        }
+    }
+
+    /// True when the dataset should be read into memory once after its schema is known.
+    pub fn eager(self) -> bool {
+        self == LoadProfile::Small
+    }
+
+    /// Adjust `options` (already holding the configured values) for this profile.
+    pub fn apply(self, options: &mut OpenOptions) {
+        if self == LoadProfile::Large {
+            options.polars_streaming = true;
+            options.pages_lookahead = Some(LARGE_PAGES);
+            options.pages_lookback = Some(LARGE_PAGES);
+        }
+    }
+
+    /// Sampling threshold for analysis under this profile, given the configured one.
+    pub fn sampling_threshold(self, configured: Option<usize>) -> Option<usize> {
+        match self {
+            LoadProfile::Small => None,
+            LoadProfile::Standard => configured,
+            LoadProfile::Large => configured.or(Some(LARGE_SAMPLING_THRESHOLD)),
+        }
+    }
+}
+
+/// The profile of the current view and how it was chosen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadProfileInfo {
+    pub profile: LoadProfile,
+    /// False when the profile was picked in the Info panel rather than from the file size.
+    pub automatic: bool,
+    /// Total size of the input on disk, when known.
+    pub input_bytes: Option<u64>,
+}
+
+/// Total size on disk of local input files. None when any input is remote, a directory or a
+/// glob, since sizing those would mean listing (possibly many) files.
+pub fn input_size(paths: &[PathBuf]) -> Option<u64> {
+    paths.iter().try_fold(0u64, |total, path| {
+        let size = local_file_size(path)?;
+        Some(total.saturating_add(size))
+    })
+}
+
+fn local_file_size(path: &Path) -> Option<u64> {
+    match crate::source::input_source(path) {
+        crate::source::InputSource::Local(_) => {}
+        _ => return None,
+    }
+    let meta = std::fs::metadata(path).ok()?;
+    meta.is_file().then_some(meta.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_size() {
+        assert_eq!(
+            LoadProfile::for_size(Some(3 * MIB), 16, 1024),
+            LoadProfile::Small
+        );
+        assert_eq!(
+            LoadProfile::for_size(Some(100 * MIB), 16, 1024),
+            LoadProfile::Standard
+        );
+        assert_eq!(
+            LoadProfile::for_size(Some(2048 * MIB), 16, 1024),
+            LoadProfile::Large
+        );
+        assert_eq!(LoadProfile::for_size(None, 16, 1024), LoadProfile::Standard);
+    }
+
+    #[test]
+    fn test_apply_and_sampling() {
+        let mut options = OpenOptions::new();
+        options.polars_streaming = false;
+        options.pages_lookahead = Some(3);
+        LoadProfile::Standard.apply(&mut options);
+        assert_eq!(options.pages_lookahead, Some(3));
+        LoadProfile::Large.apply(&mut options);
+        assert!(options.polars_streaming);
+        assert_eq!(options.pages_lookahead, Some(1));
+        assert_eq!(options.pages_lookback, Some(1));
+
+        assert_eq!(LoadProfile::Small.sampling_threshold(Some(10)), None);
+        assert_eq!(LoadProfile::Standard.sampling_threshold(Some(10)), Some(10));
+        assert_eq!(
+            LoadProfile::Large.sampling_threshold(None),
+            Some(LARGE_SAMPLING_THRESHOLD)
+        );
+    }
+
+    #[test]
+    fn test_input_size_sums_local_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.csv");
+        let b = dir.path().join("b.csv");
+        std::fs::write(&a, "x\n1\n").unwrap();
+        std::fs::write(&b, "x\n22\n").unwrap();
+        assert_eq!(input_size(&[a.clone(), b]), Some(9));
+        assert_eq!(input_size(&[a, dir.path().to_path_buf()]), None);
+    }
+}
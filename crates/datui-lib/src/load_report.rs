@@ -0,0 +1,466 @@
+//! Rows of a CSV or JSON Lines file that don't parse. With `--skip-bad-rows` the file is copied
+//! without them before it loads, so one malformed row no longer fails the whole load, and the
+//! rows left out are kept in a [`LoadReport`]: the "Load report" panel (`B`, or right after a
+//! load that skipped rows) lists their line numbers, raw text and why they were rejected.
+//!
+//! A CSV row is rejected when it has a different number of fields than the header (or the first
+//! row), leaves a quoted field open, or has a value that doesn't parse as the type its column
+//! was inferred as from the first rows. A JSON Lines row is rejected when it isn't a JSON
+//! object, or has a value of a different type than its key was inferred as.
+
+use crate::OpenOptions;
+use color_eyre::Result;
+use ratatui::widgets::TableState;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Rejected rows kept for the panel; the rest are only counted.
+const MAX_REJECTED_ROWS: usize = 1000;
+/// Characters of a rejected row's text kept.
+const MAX_ROW_TEXT: usize = 1000;
+/// Rows used to infer column types when `infer_schema_length` is unset (the Polars default).
+const DEFAULT_INFER_ROWS: usize = 100;
+
+/// A row left out of the load.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedRow {
+    /// Line of the file (from 1) the row starts on.
+    pub line: usize,
+    pub text: String,
+    pub error: String,
+}
+
+/// Rows loaded from a file and rows left out of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadReport {
+    pub path: PathBuf,
+    /// Data rows kept.
+    pub rows: usize,
+    /// Data rows left out; more than `rejected.len()` when there were too many to keep.
+    pub skipped: usize,
+    pub rejected: Vec<RejectedRow>,
+}
+
+impl LoadReport {
+    fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            rows: 0,
+            skipped: 0,
+            rejected: Vec::new(),
+        }
+    }
+
+    fn reject(&mut self, line: usize, text: &[u8], error: String) {
+        self.skipped += 1;
+        if self.rejected.len() < MAX_REJECTED_ROWS {
+            let text = String::from_utf8_lossy(text);
+            let text = text.trim_end_matches(['\r', '\n']);
+            self.rejected.push(RejectedRow {
+                line,
+                text: text.chars().take(MAX_ROW_TEXT).collect(),
+                error,
+            });
+        }
+    }
+}
+
+/// Type of a value, or of a column from the values seen in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Null,
+    Bool,
+    Int,
+    Float,
+    Text,
+    Nested,
+    /// Values of different types: anything goes.
+    Mixed,
+}
+
+impl Kind {
+    fn merge(self, other: Kind) -> Kind {
+        match (self, other) {
+            (Kind::Null, k) | (k, Kind::Null) => k,
+            (a, b) if a == b => a,
+            (Kind::Int, Kind::Float) | (Kind::Float, Kind::Int) => Kind::Float,
+            _ => Kind::Mixed,
+        }
+    }
+
+    fn accepts(self, value: Kind) -> bool {
+        matches!(self, Kind::Null | Kind::Mixed)
+            || value == Kind::Null
+            || value == self
+            || (self == Kind::Float && value == Kind::Int)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Kind::Null => "null",
+            Kind::Bool => "boolean",
+            Kind::Int => "integer",
+            Kind::Float => "number",
+            Kind::Text => "string",
+            Kind::Nested => "list or object",
+            Kind::Mixed => "value",
+        }
+    }
+}
+
+/// Column types inferred from the first rows, then checked against every later row.
+struct Columns {
+    names: Vec<String>,
+    kinds: Vec<Kind>,
+    infer_rows: usize,
+    seen: usize,
+}
+
+impl Columns {
+    fn new(infer_rows: usize) -> Self {
+        Self {
+            names: Vec::new(),
+            kinds: Vec::new(),
+            infer_rows,
+            seen: 0,
+        }
+    }
+
+    fn set_names(&mut self, names: Vec<String>) {
+        self.kinds = vec![Kind::Null; names.len()];
+        self.names = names;
+    }
+
+    fn index(&mut self, name: &str) -> usize {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .unwrap_or_else(|| {
+                self.names.push(name.to_string());
+                self.kinds.push(Kind::Null);
+                self.names.len() - 1
+            })
+    }
+
+    /// Infer from a row while in the first rows, check it after: the first value whose type
+    /// its column doesn't take, as an error message.
+    fn observe(&mut self, values: &[(usize, Kind, String)]) -> Option<String> {
+        if self.seen < self.infer_rows {
+            self.seen += 1;
+            for (col, kind, _) in values {
+                self.kinds[*col] = self.kinds[*col].merge(*kind);
+            }
+            return None;
+        }
+        values.iter().find_map(|(col, kind, text)| {
+            let column = self.kinds[*col];
+            (!column.accepts(*kind)).then(|| {
+                format!(
+                    "`{}` doesn't match column `{}` ({})",
+                    text,
+                    self.names[*col],
+                    column.label()
+                )
+            })
+        })
+    }
+}
+
+/// Copy the CSV from `reader` to `writer` without the rows that don't parse with `options`
+/// (delimiter, quote, comment prefix, header, skipped lines and null values), and report them.
+pub fn filter_csv(
+    path: &Path,
+    reader: impl Read,
+    mut writer: impl Write,
+    options: &OpenOptions,
+) -> Result<LoadReport> {
+    let delimiter = options.delimiter.unwrap_or(b',');
+    let quote = options.quote_char;
+    let comment = options.comment_prefix.as_deref().map(str::as_bytes);
+    let null_values = options.null_values.as_deref().unwrap_or(&[]);
+    let mut report = LoadReport::new(path);
+    let mut columns = Columns::new(options.infer_schema_length.unwrap_or(DEFAULT_INFER_ROWS));
+    let mut header = options.has_header.unwrap_or(true);
+    let mut skip_lines = options.skip_lines.unwrap_or(0);
+    let mut skip_rows = options.skip_rows.unwrap_or(0);
+    let mut fields: Option<usize> = None;
+    let mut reader = BufReader::new(reader);
+    let mut line = 0;
+    let mut record = Vec::new();
+    loop {
+        record.clear();
+        if reader.read_until(b'\n', &mut record)? == 0 {
+            break;
+        }
+        line += 1;
+        let start = line;
+        if skip_lines > 0 {
+            skip_lines -= 1;
+            writer.write_all(&record)?;
+            continue;
+        }
+        let content = trim_newline(&record);
+        if content.is_empty() || comment.is_some_and(|c| content.starts_with(c)) {
+            writer.write_all(&record)?;
+            continue;
+        }
+        // A quoted field may run over several lines
+        while quote.is_some_and(|q| open_quote(&record, q)) {
+            if reader.read_until(b'\n', &mut record)? == 0 {
+                break;
+            }
+            line += 1;
+        }
+        if quote.is_some_and(|q| open_quote(&record, q)) {
+            report.reject(start, &record, "quoted field is never closed".to_string());
+            continue;
+        }
+        if skip_rows > 0 {
+            skip_rows -= 1;
+            writer.write_all(&record)?;
+            continue;
+        }
+        let values = split_fields(trim_newline(&record), delimiter, quote);
+        if header {
+            header = false;
+            fields = Some(values.len());
+            columns.set_names(values);
+            writer.write_all(&record)?;
+            continue;
+        }
+        let expected = *fields.get_or_insert_with(|| {
+            columns.set_names(
+                (1..=values.len())
+                    .map(|i| format!("column_{}", i))
+                    .collect(),
+            );
+            values.len()
+        });
+        if values.len() != expected {
+            let error = format!("expected {} fields, found {}", expected, values.len());
+            report.reject(start, &record, error);
+            continue;
+        }
+        let kinds: Vec<(usize, Kind, String)> = values
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                let null = is_null(&value, &columns.names[i], null_values);
+                let kind = if null { Kind::Null } else { csv_kind(&value) };
+                (i, kind, value)
+            })
+            .collect();
+        if let Some(error) = columns.observe(&kinds) {
+            report.reject(start, &record, error);
+            continue;
+        }
+        writer.write_all(&record)?;
+        report.rows += 1;
+    }
+    writer.flush()?;
+    Ok(report)
+}
+
+/// Copy JSON Lines from `reader` to `writer` without the lines that aren't JSON objects or
+/// hold a value of another type than the first lines gave its key, and report them.
+pub fn filter_json_lines(
+    path: &Path,
+    reader: impl Read,
+    mut writer: impl Write,
+    options: &OpenOptions,
+) -> Result<LoadReport> {
+    let mut report = LoadReport::new(path);
+    let mut columns = Columns::new(options.infer_schema_length.unwrap_or(DEFAULT_INFER_ROWS));
+    let mut reader = BufReader::new(reader);
+    let mut line = 0;
+    let mut record = Vec::new();
+    loop {
+        record.clear();
+        if reader.read_until(b'\n', &mut record)? == 0 {
+            break;
+        }
+        line += 1;
+        if trim_newline(&record).iter().all(u8::is_ascii_whitespace) {
+            writer.write_all(&record)?;
+            continue;
+        }
+        let object = match serde_json::from_slice::<serde_json::Value>(&record) {
+            Ok(serde_json::Value::Object(object)) => object,
+            Ok(_) => {
+                let error = "expected a JSON object".to_string();
+                report.reject(line, &record, error);
+                continue;
+            }
+            Err(e) => {
+                report.reject(line, &record, e.to_string());
+                continue;
+            }
+        };
+        let kinds: Vec<(usize, Kind, String)> = object
+            .iter()
+            .map(|(key, value)| (columns.index(key), json_kind(value), value.to_string()))
+            .collect();
+        if let Some(error) = columns.observe(&kinds) {
+            report.reject(line, &record, error);
+            continue;
+        }
+        writer.write_all(&record)?;
+        report.rows += 1;
+    }
+    writer.flush()?;
+    Ok(report)
+}
+
+fn trim_newline(record: &[u8]) -> &[u8] {
+    let record = record.strip_suffix(b"\n").unwrap_or(record);
+    record.strip_suffix(b"\r").unwrap_or(record)
+}
+
+/// Whether `record` ends inside a quoted field.
+fn open_quote(record: &[u8], quote: u8) -> bool {
+    record.iter().filter(|&&b| b == quote).count() % 2 == 1
+}
+
+/// Fields of a record split at `delimiter` outside quotes, with the quotes removed.
+fn split_fields(record: &[u8], delimiter: u8, quote: Option<u8>) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = Vec::new();
+    let mut in_quotes = false;
+    for &b in record {
+        if Some(b) == quote {
+            in_quotes = !in_quotes;
+        } else if b == delimiter && !in_quotes {
+            fields.push(String::from_utf8_lossy(&field).into_owned());
+            field.clear();
+        } else {
+            field.push(b);
+        }
+    }
+    fields.push(String::from_utf8_lossy(&field).into_owned());
+    fields
+}
+
+/// Whether `value` reads as null in `column`: empty, or one of the `--null-value` specs.
+fn is_null(value: &str, column: &str, specs: &[String]) -> bool {
+    value.is_empty()
+        || specs.iter().any(|spec| match spec.split_once('=') {
+            Some((col, null)) => col == column && null == value,
+            None => spec == value,
+        })
+}
+
+fn csv_kind(value: &str) -> Kind {
+    if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+        Kind::Bool
+    } else if value.parse::<i64>().is_ok() {
+        Kind::Int
+    } else if value.parse::<f64>().is_ok() && value.bytes().any(|b| b.is_ascii_digit()) {
+        Kind::Float
+    } else {
+        // Any CSV column can hold text, so text says nothing about the column's type
+        Kind::Mixed
+    }
+}
+
+fn json_kind(value: &serde_json::Value) -> Kind {
+    use serde_json::Value;
+    match value {
+        Value::Null => Kind::Null,
+        Value::Bool(_) => Kind::Bool,
+        Value::Number(n) if n.is_f64() => Kind::Float,
+        Value::Number(_) => Kind::Int,
+        Value::String(_) => Kind::Text,
+        Value::Array(_) | Value::Object(_) => Kind::Nested,
+    }
+}
+
+/// State of the "Load report" panel.
+pub struct LoadReportPanel {
+    pub report: LoadReport,
+    pub table_state: TableState,
+}
+
+impl LoadReportPanel {
+    pub fn new(report: LoadReport) -> Self {
+        let mut table_state = TableState::default();
+        table_state.select((!report.rejected.is_empty()).then_some(0));
+        Self {
+            report,
+            table_state,
+        }
+    }
+
+    /// Move the selected row by `delta`, clamped to the rejected rows.
+    pub fn move_by(&mut self, delta: isize) {
+        let last = self.report.rejected.len().saturating_sub(1);
+        let selected = self.table_state.selected().unwrap_or(0);
+        self.table_state
+            .select(Some(selected.saturating_add_signed(delta).min(last)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(text: &str, options: &OpenOptions) -> (String, LoadReport) {
+        let mut out = Vec::new();
+        let report = filter_csv(Path::new("t.csv"), text.as_bytes(), &mut out, options).unwrap();
+        (String::from_utf8(out).unwrap(), report)
+    }
+
+    #[test]
+    fn csv_rows_with_wrong_field_counts_or_types_are_left_out() {
+        let options = OpenOptions {
+            infer_schema_length: Some(2),
+            ..OpenOptions::new()
+        };
+        let (kept, report) = filter(
+            "id,name\n1,\"a,\nb\"\n2,b\n3,c,extra\nx,d\n4,\"open\n",
+            &options,
+        );
+        assert_eq!(kept, "id,name\n1,\"a,\nb\"\n2,b\n");
+        assert_eq!(report.rows, 2);
+        assert_eq!(report.skipped, 3);
+        let lines: Vec<usize> = report.rejected.iter().map(|r| r.line).collect();
+        assert_eq!(lines, [5, 6, 7]);
+        assert_eq!(report.rejected[0].text, "3,c,extra");
+        assert_eq!(report.rejected[0].error, "expected 2 fields, found 3");
+        assert!(report.rejected[1].error.contains("column `id`"));
+        assert_eq!(report.rejected[2].error, "quoted field is never closed");
+    }
+
+    #[test]
+    fn csv_null_values_and_comments_pass() {
+        let options = OpenOptions {
+            delimiter: Some(b';'),
+            infer_schema_length: Some(1),
+            null_values: Some(vec!["NA".to_string()]),
+            comment_prefix: Some("#".to_string()),
+            ..OpenOptions::new()
+        };
+        let (kept, report) = filter("a;b\n1;2.5\n# note\nNA;3\n;\n", &options);
+        assert_eq!(kept, "a;b\n1;2.5\n# note\nNA;3\n;\n");
+        assert_eq!(report.skipped, 0);
+    }
+
+    #[test]
+    fn json_lines_that_are_not_objects_or_change_type_are_left_out() {
+        let options = OpenOptions {
+            infer_schema_length: Some(1),
+            ..OpenOptions::new()
+        };
+        let text = "{\"a\": 1, \"b\": \"x\"}\n{\"a\": 2\n[1]\n\n{\"a\": \"two\"}\n{\"a\": 3, \"c\": true}\n";
+        let mut out = Vec::new();
+        let report =
+            filter_json_lines(Path::new("t.jsonl"), text.as_bytes(), &mut out, &options).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"a\": 1, \"b\": \"x\"}\n\n{\"a\": 3, \"c\": true}\n"
+        );
+        let lines: Vec<usize> = report.rejected.iter().map(|r| r.line).collect();
+        assert_eq!(lines, [2, 3, 5]);
+        assert_eq!(report.rejected[1].error, "expected a JSON object");
+        assert!(report.rejected[2].error.contains("column `a`"));
+    }
+}
@@ -0,0 +1,244 @@
+//! Locale-aware display of dates and numbers in the main table.
+//!
+//! Only the rendered text changes: the underlying data, queries, filters and exports keep
+//! Polars' own representation. The default ("iso") reproduces that representation exactly.
+
+use polars::prelude::AnyValue;
+use std::borrow::Cow;
+
+/// Locale names accepted by `display.locale` and `--locale`.
+pub const SUPPORTED_LOCALES: &[&str] = &[
+    "iso", "en-US", "en-GB", "de-DE", "fr-FR", "es-ES", "it-IT", "nl-NL", "pt-BR", "sv-SE", "ja-JP",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateOrder {
+    /// 2024-01-31
+    Ymd,
+    /// 31.01.2024
+    Dmy,
+    /// 01/31/2024
+    Mdy,
+}
+
+/// How dates and numbers are written in table cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayLocale {
+    pub date_order: DateOrder,
+    pub date_separator: char,
+    pub decimal_separator: char,
+    /// Thousands separator for the integer part of numbers; None = no grouping.
+    pub group_separator: Option<char>,
+}
+
+impl Default for DisplayLocale {
+    fn default() -> Self {
+        Self::ISO
+    }
+}
+
+impl DisplayLocale {
+    /// Polars' own formatting: ISO dates, "." decimals, no grouping.
+    pub const ISO: DisplayLocale = DisplayLocale {
+        date_order: DateOrder::Ymd,
+        date_separator: '-',
+        decimal_separator: '.',
+        group_separator: None,
+    };
+
+    const fn new(
+        date_order: DateOrder,
+        date_separator: char,
+        decimal_separator: char,
+        group_separator: char,
+    ) -> Self {
+        Self {
+            date_order,
+            date_separator,
+            decimal_separator,
+            group_separator: Some(group_separator),
+        }
+    }
+
+    /// Look up a locale by name (case-insensitive; "_" and "-" are interchangeable).
+    /// Returns None for names not in [`SUPPORTED_LOCALES`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalized = name.trim().replace('_', "-").to_ascii_lowercase();
+        let locale = match normalized.as_str() {
+            "iso" => Self::ISO,
+            "en-us" => Self::new(DateOrder::Mdy, '/', '.', ','),
+            "en-gb" => Self::new(DateOrder::Dmy, '/', '.', ','),
+            "de-de" => Self::new(DateOrder::Dmy, '.', ',', '.'),
+            "fr-fr" => Self::new(DateOrder::Dmy, '/', ',', ' '),
+            "es-es" | "it-it" | "pt-br" => Self::new(DateOrder::Dmy, '/', ',', '.'),
+            "nl-nl" => Self::new(DateOrder::Dmy, '-', ',', '.'),
+            "sv-se" => Self::new(DateOrder::Ymd, '-', ',', ' '),
+            "ja-jp" => Self::new(DateOrder::Ymd, '/', '.', ','),
+            _ => return None,
+        };
+        Some(locale)
+    }
+
+    /// Cell text for `value`. Values other than numbers, dates and datetimes are unchanged.
+    pub fn format_value<'a>(&self, value: &AnyValue<'a>) -> Cow<'a, str> {
+        let text = value.str_value();
+        if *self == Self::ISO {
+            return text;
+        }
+        match value {
+            AnyValue::Int8(_)
+            | AnyValue::Int16(_)
+            | AnyValue::Int32(_)
+            | AnyValue::Int64(_)
+            | AnyValue::UInt8(_)
+            | AnyValue::UInt16(_)
+            | AnyValue::UInt32(_)
+            | AnyValue::UInt64(_)
+            | AnyValue::Float32(_)
+            | AnyValue::Float64(_) => self.format_number(&text).map(Cow::Owned).unwrap_or(text),
+            AnyValue::Date(_) | AnyValue::Datetime(..) | AnyValue::DatetimeOwned(..) => {
+                self.format_date(&text).map(Cow::Owned).unwrap_or(text)
+            }
+            _ => text,
+        }
+    }
+
+    /// Rewrite a plain decimal number ("-1234.5"). Returns None for anything else
+    /// (scientific notation, NaN, inf), which is shown as-is.
+    pub fn format_number(&self, text: &str) -> Option<String> {
+        let (sign, unsigned) = match text.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", text),
+        };
+        let (int_part, frac_part) = match unsigned.split_once('.') {
+            Some((i, f)) => (i, Some(f)),
+            None => (unsigned, None),
+        };
+        let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        if !all_digits(int_part) || frac_part.is_some_and(|f| !all_digits(f)) {
+            return None;
+        }
+        let mut out = String::with_capacity(text.len() + int_part.len() / 3);
+        out.push_str(sign);
+        match self.group_separator {
+            Some(sep) => out.push_str(&group_digits(int_part, sep)),
+            None => out.push_str(int_part),
+        }
+        if let Some(frac) = frac_part {
+            out.push(self.decimal_separator);
+            out.push_str(frac);
+        }
+        Some(out)
+    }
+
+    /// Reorder the leading "YYYY-MM-DD" of a date or datetime; the time part is kept as-is.
+    pub fn format_date(&self, text: &str) -> Option<String> {
+        let date = text.get(..10)?;
+        let rest = &text[10..];
+        let bytes = date.as_bytes();
+        if bytes[4] != b'-' || bytes[7] != b'-' {
+            return None;
+        }
+        let (y, m, d) = (&date[..4], &date[5..7], &date[8..10]);
+        if ![y, m, d]
+            .iter()
+            .all(|p| p.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return None;
+        }
+        let sep = self.date_separator;
+        let reordered = match self.date_order {
+            DateOrder::Ymd => format!("{y}{sep}{m}{sep}{d}"),
+            DateOrder::Dmy => format!("{d}{sep}{m}{sep}{y}"),
+            DateOrder::Mdy => format!("{m}{sep}{d}{sep}{y}"),
+        };
+        Some(reordered + rest)
+    }
+}
+
+/// `n` with comma thousands separators ("1,234,567"), for counts in status text and reports.
+pub fn format_count(n: usize) -> String {
+    group_digits(&n.to_string(), ',')
+}
+
+/// Insert `sep` between each group of three digits, counting from the right.
+fn group_digits(digits: &str, sep: char) -> String {
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(sep);
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(DisplayLocale::from_name("iso"), Some(DisplayLocale::ISO));
+        assert_eq!(
+            DisplayLocale::from_name("de_DE").map(|l| l.decimal_separator),
+            Some(',')
+        );
+        assert_eq!(
+            DisplayLocale::from_name("EN-us").map(|l| l.date_order),
+            Some(DateOrder::Mdy)
+        );
+        assert_eq!(DisplayLocale::from_name("xx-XX"), None);
+        for name in SUPPORTED_LOCALES {
+            assert!(DisplayLocale::from_name(name).is_some(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_format_number() {
+        let us = DisplayLocale::from_name("en-US").unwrap();
+        let de = DisplayLocale::from_name("de-DE").unwrap();
+        assert_eq!(
+            us.format_number("1234567.25").as_deref(),
+            Some("1,234,567.25")
+        );
+        assert_eq!(
+            de.format_number("1234567.25").as_deref(),
+            Some("1.234.567,25")
+        );
+        assert_eq!(de.format_number("-1000").as_deref(), Some("-1.000"));
+        assert_eq!(de.format_number("999").as_deref(), Some("999"));
+        assert_eq!(de.format_number("1.5e10"), None);
+        assert_eq!(de.format_number("NaN"), None);
+    }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1000), "1,000");
+        assert_eq!(format_count(1234567), "1,234,567");
+    }
+
+    #[test]
+    fn test_format_date() {
+        let us = DisplayLocale::from_name("en-US").unwrap();
+        let de = DisplayLocale::from_name("de-DE").unwrap();
+        assert_eq!(us.format_date("2024-01-31").as_deref(), Some("01/31/2024"));
+        assert_eq!(de.format_date("2024-01-31").as_deref(), Some("31.01.2024"));
+        assert_eq!(
+            de.format_date("2024-01-31 08:15:00").as_deref(),
+            Some("31.01.2024 08:15:00")
+        );
+        assert_eq!(de.format_date("not a date"), None);
+    }
+
+    #[test]
+    fn test_format_value_iso_is_unchanged() {
+        let value = AnyValue::Float64(1234.5);
+        assert_eq!(DisplayLocale::ISO.format_value(&value), value.str_value());
+        let de = DisplayLocale::from_name("de-DE").unwrap();
+        assert_eq!(de.format_value(&AnyValue::Int64(1234567)), "1.234.567");
+        assert_eq!(de.format_value(&AnyValue::String("1234")), "1234");
+    }
+}
@@ -0,0 +1,90 @@
+//! Versioned file formats: upgrade saved config and template files on load.
+//!
+//! Each format lists its upgrade steps (`from` version → `to` version). Loading runs the steps
+//! from the file's version until none applies, then writes the upgraded file back after copying
+//! the original to `<file>.v<version>.bak`.
+
+use color_eyre::Result;
+use std::path::{Path, PathBuf};
+
+/// One upgrade step of a file format.
+pub struct Migration<V, T> {
+    pub from: V,
+    pub to: V,
+    /// Rewrites the parsed document from the `from` layout to the `to` layout.
+    pub apply: fn(&mut T),
+}
+
+/// Run the steps that chain from `version`, in order. Returns the version reached, which is
+/// `version` itself when no step starts there.
+pub fn migrate<V: PartialEq + Copy, T>(doc: &mut T, version: V, steps: &[Migration<V, T>]) -> V {
+    let mut version = version;
+    while let Some(step) = steps.iter().find(|s| s.from == version) {
+        (step.apply)(doc);
+        version = step.to;
+    }
+    version
+}
+
+/// Path of the pre-migration backup of `path`, e.g. `config.toml.v0.2.bak`.
+pub fn backup_path(path: &Path, version: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".v{}.bak", version));
+    path.with_file_name(name)
+}
+
+/// Copy `path` to its backup for `version`, then replace it with `contents`. An existing backup
+/// is kept: it holds the oldest copy of the file. Nothing is written if the backup fails.
+pub fn write_migrated(path: &Path, version: &str, contents: &str) -> Result<PathBuf> {
+    let backup = backup_path(path, version);
+    if !backup.exists() {
+        std::fs::copy(path, &backup)?;
+    }
+    std::fs::write(path, contents)?;
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_chain_from_the_file_version() {
+        let steps: &[Migration<u32, Vec<&str>>] = &[
+            Migration {
+                from: 1,
+                to: 2,
+                apply: |doc| doc.push("1->2"),
+            },
+            Migration {
+                from: 0,
+                to: 1,
+                apply: |doc| doc.push("0->1"),
+            },
+        ];
+        let mut doc = Vec::new();
+        assert_eq!(migrate(&mut doc, 0, steps), 2);
+        assert_eq!(doc, ["0->1", "1->2"]);
+
+        let mut doc = Vec::new();
+        assert_eq!(migrate(&mut doc, 2, steps), 2, "current version");
+        assert_eq!(migrate(&mut doc, 7, steps), 7, "newer version");
+        assert!(doc.is_empty());
+    }
+
+    #[test]
+    fn write_migrated_keeps_the_first_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "original").unwrap();
+
+        let backup = write_migrated(&path, "0.2", "upgraded").unwrap();
+        assert_eq!(backup, dir.path().join("config.toml.v0.2.bak"));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "original");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "upgraded");
+
+        write_migrated(&path, "0.2", "again").unwrap();
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "original");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "again");
+    }
+}
@@ -0,0 +1,166 @@
+//! Null inspection (the "Missing Data" analysis tool).
+//!
+//! For every column of the current view: its null count and share, plus a null-pattern heatmap
+//! that splits the rows into bands and shows the share of nulls in each (band, column) cell, so
+//! columns that are missing together (or only in part of the data) stand out. Sampled like the
+//! other tools when a sampling threshold is configured.
+
+use crate::sampling::SamplingStrategy;
+use crate::statistics::{collect_lazy, sample_dataframe};
+use color_eyre::Result;
+use polars::prelude::*;
+
+/// Row bands in the null-pattern heatmap; the view drawn may merge them further.
+pub const PATTERN_BANDS: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct ColumnMissing {
+    pub column: String,
+    pub dtype: DataType,
+    pub null_count: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct MissingDataReport {
+    /// Rows the report was computed from (the sample size when sampled).
+    pub rows: usize,
+    /// Rows in the view, when the report was computed on a sample.
+    pub sampled_from: Option<usize>,
+    /// Most nulls first; columns without nulls keep the view's order at the end.
+    pub columns: Vec<ColumnMissing>,
+    /// Rows with a null in at least one column.
+    pub rows_with_nulls: usize,
+    /// Share of nulls per band of consecutive rows (outer) and column (inner, in `columns`
+    /// order), each in [0, 1]. At most `PATTERN_BANDS` bands; fewer when there are fewer rows.
+    pub pattern: Vec<Vec<f64>>,
+}
+
+impl MissingDataReport {
+    /// Null share of `column`, in [0, 1].
+    pub fn null_fraction(&self, column: &ColumnMissing) -> f64 {
+        if self.rows == 0 {
+            0.0
+        } else {
+            column.null_count as f64 / self.rows as f64
+        }
+    }
+
+    /// Columns with at least one null.
+    pub fn columns_with_nulls(&self) -> impl Iterator<Item = &ColumnMissing> {
+        self.columns.iter().filter(|c| c.null_count > 0)
+    }
+}
+
+/// Inspect the nulls of `lf` (`total_rows` rows). Samples when `sampling_threshold` is set and
+/// `total_rows` reaches it.
+pub fn compute(
+    lf: &LazyFrame,
+    total_rows: usize,
+    sampling_threshold: Option<usize>,
+    sampling: &SamplingStrategy,
+    polars_streaming: bool,
+) -> Result<MissingDataReport> {
+    let (df, sampled_from) = match sampling_threshold {
+        Some(threshold) if total_rows >= threshold => (
+            sample_dataframe(lf, total_rows, threshold, sampling, polars_streaming)?,
+            Some(total_rows),
+        ),
+        _ => (collect_lazy(lf.clone(), polars_streaming)?, None),
+    };
+
+    let rows = df.height();
+    let mut columns: Vec<(ColumnMissing, BooleanChunked)> = df
+        .get_columns()
+        .iter()
+        .map(|c| {
+            let series = c.as_materialized_series();
+            (
+                ColumnMissing {
+                    column: series.name().to_string(),
+                    dtype: series.dtype().clone(),
+                    null_count: series.null_count(),
+                },
+                series.is_null(),
+            )
+        })
+        .collect();
+    // Stable, so columns with equal counts keep the view's order
+    columns.sort_by_key(|(c, _)| std::cmp::Reverse(c.null_count));
+
+    let bands = rows.clamp(1, PATTERN_BANDS);
+    let band_of = |row: usize| row * bands / rows.max(1);
+    let mut band_rows = vec![0usize; bands];
+    for row in 0..rows {
+        band_rows[band_of(row)] += 1;
+    }
+    let mut pattern = vec![vec![0.0; columns.len()]; bands];
+    let mut row_has_null = vec![false; rows];
+    for (index, (column, mask)) in columns.iter().enumerate() {
+        if column.null_count == 0 {
+            continue;
+        }
+        for (row, is_null) in mask.into_no_null_iter().enumerate() {
+            if is_null {
+                pattern[band_of(row)][index] += 1.0;
+                row_has_null[row] = true;
+            }
+        }
+    }
+    for (band, cells) in pattern.iter_mut().enumerate() {
+        if band_rows[band] > 0 {
+            for cell in cells.iter_mut() {
+                *cell /= band_rows[band] as f64;
+            }
+        }
+    }
+
+    Ok(MissingDataReport {
+        rows,
+        sampled_from,
+        columns: columns.into_iter().map(|(column, _)| column).collect(),
+        rows_with_nulls: row_has_null.into_iter().filter(|&n| n).count(),
+        pattern,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_and_pattern() {
+        let lf = df!(
+            "id" => [1i64, 2, 3, 4],
+            "late" => [Some(1.0), Some(2.0), None, None],
+            "early" => [None, Some("b"), Some("c"), Some("d")],
+        )
+        .unwrap()
+        .lazy();
+        let report = compute(&lf, 4, None, &SamplingStrategy::default(), false).unwrap();
+        let names: Vec<&str> = report.columns.iter().map(|c| c.column.as_str()).collect();
+        assert_eq!(names, ["late", "early", "id"]);
+        assert_eq!(report.columns[0].null_count, 2);
+        assert_eq!(report.null_fraction(&report.columns[0]), 0.5);
+        assert_eq!(report.rows_with_nulls, 3);
+        assert_eq!(report.columns_with_nulls().count(), 2);
+
+        // One band per row when there are few rows
+        assert_eq!(report.pattern.len(), 4);
+        assert_eq!(report.pattern[0], [0.0, 1.0, 0.0]);
+        assert_eq!(report.pattern[3], [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_bands_and_sampling() {
+        let values: Vec<Option<i64>> = (0..1_000).map(|i| (i < 500).then_some(i)).collect();
+        let lf = df!("x" => values).unwrap().lazy();
+        let report = compute(&lf, 1_000, None, &SamplingStrategy::default(), false).unwrap();
+        assert_eq!(report.pattern.len(), PATTERN_BANDS);
+        assert_eq!(report.pattern[0][0], 0.0);
+        assert_eq!(report.pattern[PATTERN_BANDS - 1][0], 1.0);
+
+        let sampled = compute(&lf, 1_000, Some(100), &SamplingStrategy::default(), false).unwrap();
+        assert_eq!(sampled.rows, 100);
+        assert_eq!(sampled.sampled_from, Some(1_000));
+    }
+}
@@ -0,0 +1,87 @@
+//! Mouse support: modal buttons record where they were drawn so a click can press them, and a
+//! click on a column header cycles the sort on that column. The run loop only captures the mouse
+//! when `ui.mouse` is on, since capturing it takes text selection away from the terminal.
+
+use ratatui::layout::{Position, Rect};
+
+/// Rows the main table moves per scroll wheel step.
+pub const WHEEL_ROWS: i64 = 3;
+
+/// Where a modal's buttons were drawn in the last frame, each with the value it stands for
+/// (usually the focus that selects it).
+#[derive(Debug, Clone)]
+pub struct ButtonAreas<T> {
+    areas: Vec<(Rect, T)>,
+}
+
+impl<T> Default for ButtonAreas<T> {
+    fn default() -> Self {
+        Self { areas: Vec::new() }
+    }
+}
+
+impl<T> ButtonAreas<T> {
+    /// Forget the buttons; call at the start of the modal's render.
+    pub fn clear(&mut self) {
+        self.areas.clear();
+    }
+
+    pub fn add(&mut self, area: Rect, value: T) {
+        self.areas.push((area, value));
+    }
+
+    /// The button at terminal position (`column`, `row`), if any.
+    pub fn at(&self, column: u16, row: u16) -> Option<&T> {
+        self.areas
+            .iter()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .map(|(_, value)| value)
+    }
+}
+
+/// The sort after clicking the header of `column`, given the current sort columns and direction:
+/// another column sorts ascending by it alone, a second click sorts descending and a third clears
+/// the sort.
+pub fn header_click_sort(
+    sort_columns: &[String],
+    ascending: bool,
+    column: &str,
+) -> (Vec<String>, bool) {
+    match sort_columns {
+        [only] if only == column && ascending => (vec![column.to_string()], false),
+        [only] if only == column => (Vec::new(), true),
+        _ => (vec![column.to_string()], true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn button_areas_find_clicked_button() {
+        let mut buttons = ButtonAreas::default();
+        buttons.add(Rect::new(10, 5, 12, 3), true);
+        buttons.add(Rect::new(24, 5, 12, 3), false);
+        assert_eq!(buttons.at(10, 5), Some(&true));
+        assert_eq!(buttons.at(35, 7), Some(&false));
+        assert_eq!(buttons.at(22, 6), None, "gap between the buttons");
+        assert_eq!(buttons.at(24, 8), None, "below the buttons");
+        buttons.clear();
+        assert_eq!(buttons.at(10, 5), None);
+    }
+
+    #[test]
+    fn header_clicks_cycle_sort() {
+        let by = |c: &str| vec![c.to_string()];
+        assert_eq!(header_click_sort(&[], true, "a"), (by("a"), true));
+        assert_eq!(header_click_sort(&by("a"), true, "a"), (by("a"), false));
+        assert_eq!(header_click_sort(&by("a"), false, "a"), (vec![], true));
+        assert_eq!(header_click_sort(&by("a"), false, "b"), (by("b"), true));
+        assert_eq!(
+            header_click_sort(&[String::from("a"), String::from("b")], true, "a"),
+            (by("a"), true),
+            "a multi-column sort is replaced"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! Completion notices for long operations (load, export, analysis): a desktop notification,
+//! the terminal bell and a terminal title update, sent when the operation outlasts the
+//! configured threshold while the terminal is not focused.
+//!
+//! Focus comes from the terminal's focus reports. Terminals that never report focus are treated
+//! as unfocused, so they still get notified.
+
+use crate::config::NotificationConfig;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+/// Follows the app's busy periods and the terminal focus between loop iterations.
+#[derive(Default)]
+pub struct LongOperationWatch {
+    started: Option<Instant>,
+    label: Option<String>,
+    /// Last focus report; None until the terminal sends one.
+    focused: Option<bool>,
+    /// The original title was pushed onto the terminal's title stack and must be restored.
+    title_pushed: bool,
+}
+
+impl LongOperationWatch {
+    /// Record whether the app is busy and what it is doing. When a busy period ends, returns
+    /// the operation's label (the first one seen) and how long it took.
+    pub fn update(&mut self, busy: bool, label: Option<String>) -> Option<(String, Duration)> {
+        match self.started {
+            None if busy => {
+                self.started = Some(Instant::now());
+                self.label = label;
+                None
+            }
+            Some(_) if busy => {
+                if self.label.is_none() {
+                    self.label = label;
+                }
+                None
+            }
+            Some(started) => {
+                self.started = None;
+                let label = self.label.take().unwrap_or_else(|| "Operation".to_string());
+                Some((label, started.elapsed()))
+            }
+            None => None,
+        }
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = Some(focused);
+    }
+
+    /// Whether an operation that took `elapsed` should be announced.
+    pub fn should_notify(&self, config: &NotificationConfig, elapsed: Duration) -> bool {
+        config.enabled
+            && elapsed >= Duration::from_secs(config.threshold_secs)
+            && self.focused != Some(true)
+    }
+
+    /// Announce `message` through the channels enabled in `config`.
+    pub fn notify(&mut self, config: &NotificationConfig, message: &str) {
+        let mut out = std::io::stdout();
+        if config.bell {
+            let _ = out.write_all(b"\x07");
+        }
+        if config.title {
+            if !self.title_pushed {
+                // Save the current title (xterm title stack) so it can be put back
+                let _ = out.write_all(b"\x1b[22;0t");
+                self.title_pushed = true;
+            }
+            let _ = crossterm::execute!(
+                out,
+                crossterm::terminal::SetTitle(format!("datui: {}", message))
+            );
+        }
+        let _ = out.flush();
+        if config.desktop {
+            desktop_notification(message);
+        }
+    }
+
+    /// Put back the title saved by [`notify`](Self::notify), once the user is back.
+    pub fn acknowledge(&mut self) {
+        if self.title_pushed {
+            let mut out = std::io::stdout();
+            let _ = out.write_all(b"\x1b[23;0t");
+            let _ = out.flush();
+            self.title_pushed = false;
+        }
+    }
+}
+
+/// Notice text, e.g. "Loading data.csv finished in 1m 05s" or "... failed after 12s".
+pub fn completion_message(label: &str, elapsed: Duration, failed: bool) -> String {
+    let secs = elapsed.as_secs();
+    let duration = if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    };
+    if failed {
+        format!("{} failed after {}", label, duration)
+    } else {
+        format!("{} finished in {}", label, duration)
+    }
+}
+
+#[cfg(feature = "notify")]
+fn desktop_notification(message: &str) {
+    let message = message.to_string();
+    // Sending can block on the notification service; never stall the UI for it
+    std::thread::spawn(move || {
+        let _ = notify_rust::Notification::new()
+            .appname("datui")
+            .summary("datui")
+            .body(&message)
+            .show();
+    });
+}
+
+#[cfg(not(feature = "notify"))]
+fn desktop_notification(_message: &str) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn busy_period_reports_first_label_and_respects_focus() {
+        let mut watch = LongOperationWatch::default();
+        assert_eq!(watch.update(false, None), None);
+        assert_eq!(
+            watch.update(true, Some("Exporting out.csv".to_string())),
+            None
+        );
+        assert_eq!(watch.update(true, Some("Writing file".to_string())), None);
+        let (label, _) = watch.update(false, None).unwrap();
+        assert_eq!(label, "Exporting out.csv");
+        assert_eq!(watch.update(false, None), None);
+
+        let config = NotificationConfig {
+            threshold_secs: 10,
+            ..Default::default()
+        };
+        let long = Duration::from_secs(65);
+        assert!(watch.should_notify(&config, long), "focus unknown");
+        assert!(!watch.should_notify(&config, Duration::from_secs(3)));
+        watch.set_focused(true);
+        assert!(!watch.should_notify(&config, long));
+        watch.set_focused(false);
+        assert!(watch.should_notify(&config, long));
+
+        assert_eq!(
+            completion_message("Loading data.csv", long, false),
+            "Loading data.csv finished in 1m 05s"
+        );
+        assert_eq!(
+            completion_message("Describing data", Duration::from_secs(12), true),
+            "Describing data failed after 12s"
+        );
+    }
+}
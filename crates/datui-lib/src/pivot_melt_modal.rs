@@ -2,6 +2,7 @@
 //!
 //! Phase 4: Pivot tab UI. Phase 5: Melt tab UI.
 
+use crate::mouse::ButtonAreas;
 use crate::widgets::text_input::TextInput;
 use polars::datatypes::DataType;
 use ratatui::widgets::TableState;
@@ -153,6 +154,8 @@ pub struct PivotMeltModal {
     pub active: bool,
     pub active_tab: PivotMeltTab,
     pub focus: PivotMeltFocus,
+    /// Where the buttons were drawn in the last frame, for mouse clicks.
+    pub buttons: ButtonAreas<PivotMeltFocus>,
 
     /// Column names from current schema. Set when opening modal.
     pub available_columns: Vec<String>,
@@ -193,6 +196,7 @@ impl Default for PivotMeltModal {
             active: false,
             active_tab: PivotMeltTab::default(),
             focus: PivotMeltFocus::default(),
+            buttons: ButtonAreas::default(),
             available_columns: Vec::new(),
             column_dtypes: HashMap::new(),
             pivot_filter_input: TextInput::new(),
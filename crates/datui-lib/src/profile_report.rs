@@ -0,0 +1,596 @@
+//! Profiling report (`U` in the app, `--profile out.html|md` on the command line): one
+//! document with the schema, null counts, per-column statistics with a histogram or the top
+//! values, the correlation matrix, and warnings about constant, all-null, unique,
+//! high-cardinality and skewed columns. HTML is a single self-contained page; Markdown reads
+//! well in a pager or a code review.
+
+use crate::analysis_modal::CORRELATION_THRESHOLD_DEFAULT;
+use crate::cardinality::{self, CardinalityFlag, CardinalityReport};
+use crate::locale::format_count;
+use crate::sampling::SamplingStrategy;
+use crate::statistics::{self, AnalysisResults, ColumnStatistics, ComputeOptions};
+use color_eyre::Result;
+use polars::prelude::*;
+use std::path::Path;
+
+/// Distinct values above which a text column is flagged as high-cardinality.
+pub const HIGH_CARDINALITY: usize = 50;
+/// |skewness| at or above which a numeric column is flagged as skewed.
+pub const SKEW_THRESHOLD: f64 = 1.0;
+/// Bars in a numeric column's histogram.
+const HISTOGRAM_BINS: usize = 24;
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// File format of a report, picked by the extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileFormat {
+    Html,
+    Markdown,
+}
+
+impl ProfileFormat {
+    /// `.html`/`.htm` or `.md`/`.markdown`; None for anything else.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "html" | "htm" => Some(Self::Html),
+            "md" | "markdown" => Some(Self::Markdown),
+            _ => None,
+        }
+    }
+}
+
+/// Something about a column worth a second look.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+    AllNull(String),
+    Constant(String),
+    /// A distinct value in every row: likely an identifier.
+    Unique(String),
+    HighCardinality {
+        column: String,
+        distinct: usize,
+    },
+    Skewed {
+        column: String,
+        skewness: f64,
+    },
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::AllNull(column) => write!(f, "{column} is entirely null"),
+            Warning::Constant(column) => write!(f, "{column} has a single value"),
+            Warning::Unique(column) => {
+                write!(
+                    f,
+                    "{column} has a distinct value in every row (likely a key)"
+                )
+            }
+            Warning::HighCardinality { column, distinct } => {
+                write!(
+                    f,
+                    "{column} has high cardinality ({distinct} distinct values)"
+                )
+            }
+            Warning::Skewed { column, skewness } => {
+                write!(f, "{column} is highly skewed (skewness {})", num(*skewness))
+            }
+        }
+    }
+}
+
+/// Everything a report shows.
+pub struct Profile {
+    /// Usually the file name.
+    pub title: String,
+    pub results: AnalysisResults,
+    pub cardinality: CardinalityReport,
+    pub warnings: Vec<Warning>,
+}
+
+/// Profile `lf`, sampling like the analysis tools when `sampling_threshold` is set and the
+/// data reaches it.
+pub fn compute(
+    lf: &LazyFrame,
+    title: &str,
+    sampling_threshold: Option<usize>,
+    sampling: &SamplingStrategy,
+    polars_streaming: bool,
+) -> Result<Profile> {
+    let options = ComputeOptions {
+        include_distribution_info: true,
+        include_distribution_analyses: true,
+        include_correlation_matrix: true,
+        include_skewness_kurtosis_outliers: true,
+        polars_streaming,
+    };
+    let results =
+        statistics::compute_statistics_with_options(lf, sampling_threshold, sampling, options)?;
+    let cardinality = cardinality::compute(
+        lf,
+        results.total_rows,
+        sampling_threshold,
+        sampling,
+        polars_streaming,
+    )?;
+    let warnings = warnings(&results, &cardinality);
+    Ok(Profile {
+        title: title.to_string(),
+        results,
+        cardinality,
+        warnings,
+    })
+}
+
+/// Warnings in column order. Float columns are never flagged unique: continuous values
+/// usually are.
+pub fn warnings(results: &AnalysisResults, cardinality: &CardinalityReport) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    for stats in &results.column_statistics {
+        let column = stats.name.clone();
+        let card = cardinality.columns.iter().find(|c| c.column == stats.name);
+        match card.and_then(|c| c.flag) {
+            Some(CardinalityFlag::AllNull) => warnings.push(Warning::AllNull(column.clone())),
+            Some(CardinalityFlag::Constant) => warnings.push(Warning::Constant(column.clone())),
+            Some(CardinalityFlag::LikelyKey) if !stats.dtype.is_float() => {
+                warnings.push(Warning::Unique(column.clone()))
+            }
+            _ => {
+                if let Some(distinct) = card
+                    .and_then(|c| c.distinct)
+                    .filter(|&d| stats.numeric_stats.is_none() && d > HIGH_CARDINALITY)
+                {
+                    warnings.push(Warning::HighCardinality {
+                        column: column.clone(),
+                        distinct,
+                    });
+                }
+            }
+        }
+        if let Some(skewness) = stats
+            .numeric_stats
+            .as_ref()
+            .map(|n| n.skewness)
+            .filter(|s| s.abs() >= SKEW_THRESHOLD)
+        {
+            warnings.push(Warning::Skewed { column, skewness });
+        }
+    }
+    warnings
+}
+
+/// Write `profile` to `path` in `format`.
+pub fn write(profile: &Profile, path: &Path, format: ProfileFormat) -> Result<()> {
+    let text = match format {
+        ProfileFormat::Html => to_html(profile),
+        ProfileFormat::Markdown => to_markdown(profile),
+    };
+    std::fs::write(path, text)?;
+    Ok(())
+}
+
+/// A piece of the report, rendered as Markdown or HTML.
+enum Block {
+    Heading(u8, String),
+    Text(String),
+    /// Preformatted, e.g. a histogram.
+    Code(String),
+    List(Vec<String>),
+    Table {
+        header: Vec<String>,
+        rows: Vec<Vec<String>>,
+        /// Right-align these columns.
+        numeric: Vec<bool>,
+    },
+}
+
+fn table(header: &[&str], rows: Vec<Vec<String>>, numeric: &[bool]) -> Block {
+    Block::Table {
+        header: header.iter().map(|h| h.to_string()).collect(),
+        rows,
+        numeric: numeric.to_vec(),
+    }
+}
+
+fn blocks(profile: &Profile) -> Vec<Block> {
+    let results = &profile.results;
+    let stats = &results.column_statistics;
+    let mut out = vec![Block::Heading(1, format!("Profile: {}", profile.title))];
+
+    let mut overview = format!(
+        "{} rows × {} columns.",
+        format_count(results.total_rows),
+        stats.len()
+    );
+    if let Some(sample) = results.sample_size {
+        overview.push_str(&format!(
+            " Statistics are computed from a sample of {} rows.",
+            format_count(sample)
+        ));
+    }
+    out.push(Block::Text(overview));
+
+    out.push(Block::Heading(2, "Warnings".to_string()));
+    if profile.warnings.is_empty() {
+        out.push(Block::Text("None.".to_string()));
+    } else {
+        out.push(Block::List(
+            profile.warnings.iter().map(|w| w.to_string()).collect(),
+        ));
+    }
+
+    out.push(Block::Heading(2, "Schema".to_string()));
+    let schema_rows = stats
+        .iter()
+        .map(|s| {
+            let distinct = profile
+                .cardinality
+                .columns
+                .iter()
+                .find(|c| c.column == s.name)
+                .and_then(|c| c.distinct)
+                .map_or_else(|| "-".to_string(), format_count);
+            vec![
+                s.name.clone(),
+                s.dtype.to_string(),
+                format_count(s.count - s.null_count),
+                format_count(s.null_count),
+                percent(s.null_count, s.count),
+                distinct,
+            ]
+        })
+        .collect();
+    out.push(table(
+        &["Column", "Type", "Non-null", "Nulls", "Null %", "Distinct"],
+        schema_rows,
+        &[false, false, true, true, true, true],
+    ));
+
+    out.push(Block::Heading(2, "Columns".to_string()));
+    for s in stats {
+        out.extend(column_blocks(profile, s));
+    }
+
+    if let Some(matrix) = results
+        .correlation_matrix
+        .as_ref()
+        .filter(|m| m.columns.len() > 1)
+    {
+        out.push(Block::Heading(2, "Correlations".to_string()));
+        let mut header = vec!["".to_string()];
+        header.extend(matrix.columns.iter().cloned());
+        let rows = matrix
+            .columns
+            .iter()
+            .zip(&matrix.correlations)
+            .map(|(name, row)| {
+                let mut cells = vec![name.clone()];
+                cells.extend(row.iter().map(|r| format!("{r:.2}")));
+                cells
+            })
+            .collect();
+        let mut numeric = vec![false];
+        numeric.extend(matrix.columns.iter().map(|_| true));
+        out.push(Block::Table {
+            header,
+            rows,
+            numeric,
+        });
+        let mut strong = Vec::new();
+        for i in 0..matrix.columns.len() {
+            for j in (i + 1)..matrix.columns.len() {
+                let r = matrix.correlations[i][j];
+                if r.abs() >= CORRELATION_THRESHOLD_DEFAULT {
+                    strong.push(format!(
+                        "{} and {}: {r:.2}",
+                        matrix.columns[i], matrix.columns[j]
+                    ));
+                }
+            }
+        }
+        if !strong.is_empty() {
+            out.push(Block::Text(format!(
+                "Pairs with |r| ≥ {CORRELATION_THRESHOLD_DEFAULT:.2}:"
+            )));
+            out.push(Block::List(strong));
+        }
+    }
+    out
+}
+
+/// Statistics of one column, then its histogram (numbers) or top values (text).
+fn column_blocks(profile: &Profile, s: &ColumnStatistics) -> Vec<Block> {
+    let mut out = vec![Block::Heading(3, format!("{} ({})", s.name, s.dtype))];
+    let mut rows = vec![
+        ("Count", format_count(s.count)),
+        (
+            "Nulls",
+            format!(
+                "{} ({})",
+                format_count(s.null_count),
+                percent(s.null_count, s.count)
+            ),
+        ),
+    ];
+    let analysis = profile
+        .results
+        .distribution_analyses
+        .iter()
+        .find(|a| a.column_name == s.name);
+    if let Some(n) = &s.numeric_stats {
+        rows.extend([
+            ("Mean", num(n.mean)),
+            ("Std", num(n.std)),
+            ("Min", num(n.min)),
+            ("25%", num(n.q25)),
+            ("50%", num(n.median)),
+            ("75%", num(n.q75)),
+            ("Max", num(n.max)),
+            ("Skewness", num(n.skewness)),
+            ("Kurtosis", num(n.kurtosis)),
+            ("Outliers (IQR)", format_count(n.outliers_iqr)),
+        ]);
+        if let Some(a) = analysis {
+            rows.push((
+                "Distribution",
+                format!("{} (p = {})", a.distribution_type, num(a.confidence)),
+            ));
+        }
+    }
+    if let Some(c) = &s.categorical_stats {
+        rows.push(("Distinct", format_count(c.unique_count)));
+        if let Some(mode) = &c.mode {
+            rows.push(("Mode", mode.clone()));
+        }
+        if let (Some(min), Some(max)) = (&c.min, &c.max) {
+            rows.push(("Min", min.clone()));
+            rows.push(("Max", max.clone()));
+        }
+    }
+    out.push(table(
+        &["Statistic", "Value"],
+        rows.into_iter()
+            .map(|(name, value)| vec![name.to_string(), value])
+            .collect(),
+        &[false, true],
+    ));
+
+    if let Some(bars) = analysis.and_then(|a| histogram(&a.sorted_sample_values)) {
+        let (min, max) = (
+            analysis.map_or(0.0, |a| a.sorted_sample_values[0]),
+            analysis.map_or(0.0, |a| *a.sorted_sample_values.last().unwrap_or(&0.0)),
+        );
+        out.push(Block::Code(format!("{} {bars} {}", num(min), num(max))));
+    }
+    if let Some(c) = s
+        .categorical_stats
+        .as_ref()
+        .filter(|c| !c.top_values.is_empty())
+    {
+        let non_null = s.count - s.null_count;
+        let rows = c
+            .top_values
+            .iter()
+            .map(|(value, n)| vec![value.clone(), format_count(*n), percent(*n, non_null)])
+            .collect();
+        out.push(table(
+            &["Top value", "Count", "%"],
+            rows,
+            &[false, true, true],
+        ));
+    }
+    out
+}
+
+/// Bars for `sorted` values in equal-width bins, tallest = most values. None when there is
+/// nothing to draw.
+fn histogram(sorted: &[f64]) -> Option<String> {
+    let (&min, &max) = (sorted.first()?, sorted.last()?);
+    if !min.is_finite() || !max.is_finite() {
+        return None;
+    }
+    let mut bins = [0usize; HISTOGRAM_BINS];
+    let width = (max - min) / HISTOGRAM_BINS as f64;
+    for &v in sorted {
+        let i = if width > 0.0 {
+            (((v - min) / width) as usize).min(HISTOGRAM_BINS - 1)
+        } else {
+            0
+        };
+        bins[i] += 1;
+    }
+    let tallest = *bins.iter().max()?;
+    Some(
+        bins.iter()
+            .map(|&n| {
+                if n == 0 {
+                    ' '
+                } else {
+                    BARS[((n * (BARS.len() - 1)) as f64 / tallest as f64).round() as usize]
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Integers in full, others with up to 4 decimals (scientific when very large or small).
+fn num(v: f64) -> String {
+    if !v.is_finite() {
+        return "-".to_string();
+    }
+    if v == v.trunc() && v.abs() < 1e15 {
+        return format!("{}", v as i64);
+    }
+    if v.abs() >= 1e9 || v.abs() < 1e-4 {
+        return format!("{v:.3e}");
+    }
+    let text = format!("{v:.4}");
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn percent(part: usize, whole: usize) -> String {
+    if whole == 0 {
+        return "-".to_string();
+    }
+    format!("{:.1}%", part as f64 * 100.0 / whole as f64)
+}
+
+/// The report as Markdown.
+pub fn to_markdown(profile: &Profile) -> String {
+    let cell = |text: &str| text.replace('|', "\\|").replace(['\n', '\r'], " ");
+    let row_line = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+    let mut parts = Vec::new();
+    for block in blocks(profile) {
+        parts.push(match block {
+            Block::Heading(level, text) => format!("{} {}", "#".repeat(level as usize), text),
+            Block::Text(text) => text,
+            Block::Code(text) => format!("```\n{text}\n```"),
+            Block::List(items) => items
+                .iter()
+                .map(|item| format!("- {item}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Block::Table {
+                header,
+                rows,
+                numeric,
+            } => {
+                let mut lines = vec![
+                    row_line(header.iter().map(|h| cell(h)).collect()),
+                    format!(
+                        "|{}|",
+                        numeric
+                            .iter()
+                            .map(|&n| if n { " ---: " } else { " --- " })
+                            .collect::<Vec<_>>()
+                            .join("|")
+                    ),
+                ];
+                lines.extend(
+                    rows.iter()
+                        .map(|r| row_line(r.iter().map(|c| cell(c)).collect())),
+                );
+                lines.join("\n")
+            }
+        });
+    }
+    parts.join("\n\n") + "\n"
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2em auto;max-width:60em;\
+padding:0 1em;color:#222}h3{margin-top:2em}table{border-collapse:collapse;margin:.5em 0}\
+th,td{border:1px solid #ccc;padding:.25em .6em}th{background:#f3f3f3}td.n{text-align:right;\
+font-variant-numeric:tabular-nums}pre{background:#f6f6f6;padding:.5em;display:inline-block}";
+
+/// The report as a self-contained HTML page.
+pub fn to_html(profile: &Profile) -> String {
+    let mut body = String::new();
+    for block in blocks(profile) {
+        match block {
+            Block::Heading(level, text) => {
+                body.push_str(&format!("<h{level}>{}</h{level}>\n", escape(&text)))
+            }
+            Block::Text(text) => body.push_str(&format!("<p>{}</p>\n", escape(&text))),
+            Block::Code(text) => body.push_str(&format!("<pre>{}</pre>\n", escape(&text))),
+            Block::List(items) => {
+                body.push_str("<ul>\n");
+                for item in items {
+                    body.push_str(&format!("<li>{}</li>\n", escape(&item)));
+                }
+                body.push_str("</ul>\n");
+            }
+            Block::Table {
+                header,
+                rows,
+                numeric,
+            } => {
+                body.push_str("<table>\n<tr>");
+                for h in &header {
+                    body.push_str(&format!("<th>{}</th>", escape(h)));
+                }
+                body.push_str("</tr>\n");
+                for row in &rows {
+                    body.push_str("<tr>");
+                    for (c, n) in row.iter().zip(&numeric) {
+                        let class = if *n { " class=\"n\"" } else { "" };
+                        body.push_str(&format!("<td{class}>{}</td>", escape(c)));
+                    }
+                    body.push_str("</tr>\n");
+                }
+                body.push_str("</table>\n");
+            }
+        }
+    }
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Profile: {}</title>\n\
+         <style>{STYLE}</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape(&profile.title)
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> Profile {
+        let lf = df!(
+            "id" => (0..100i64).collect::<Vec<_>>(),
+            "flag" => vec!["on"; 100],
+            "code" => (0..100).map(|i| format!("c{}", i % 60)).collect::<Vec<_>>(),
+            "wait" => (0..100).map(|i| if i < 90 { 1.0 } else { 100.0 + i as f64 }).collect::<Vec<_>>(),
+            "empty" => vec![None::<f64>; 100],
+        )
+        .unwrap()
+        .lazy();
+        compute(&lf, "t.csv", None, &SamplingStrategy::default(), false).unwrap()
+    }
+
+    #[test]
+    fn warnings_flag_constant_unique_cardinality_and_skew() {
+        let profile = profile();
+        let warnings = &profile.warnings;
+        assert!(warnings.contains(&Warning::Unique("id".to_string())));
+        assert!(warnings.contains(&Warning::Constant("flag".to_string())));
+        assert!(warnings.contains(&Warning::HighCardinality {
+            column: "code".to_string(),
+            distinct: 60
+        }));
+        assert!(warnings.contains(&Warning::AllNull("empty".to_string())));
+        assert!(warnings
+            .iter()
+            .any(|w| matches!(w, Warning::Skewed { column, .. } if column == "wait")));
+    }
+
+    #[test]
+    fn markdown_and_html_show_every_section() {
+        let profile = profile();
+        let markdown = to_markdown(&profile);
+        assert!(markdown.starts_with("# Profile: t.csv\n\n100 rows × 5 columns."));
+        for heading in ["## Warnings", "## Schema", "## Columns", "### wait (f64)"] {
+            assert!(markdown.contains(heading), "{heading}");
+        }
+        assert!(markdown.contains("| Top value | Count | % |"));
+        assert!(markdown.contains("| flag | str | 100 | 0 | 0.0% | 1 |"));
+        let html = to_html(&profile);
+        assert!(html.contains("<h2>Schema</h2>"));
+        assert!(html.contains("<td class=\"n\">100</td>"));
+    }
+
+    #[test]
+    fn histogram_scales_bars_to_the_tallest_bin() {
+        let values: Vec<f64> = vec![0.0, 0.0, 0.0, 0.0, 1.0, 2.0];
+        let bars = histogram(&values).unwrap();
+        assert_eq!(bars.chars().count(), HISTOGRAM_BINS);
+        assert_eq!(bars.chars().next(), Some('█'));
+        assert_eq!(bars.chars().last(), Some('▃'));
+        assert!(histogram(&[]).is_none());
+    }
+}
@@ -0,0 +1,1092 @@
+//! Protobuf record files (`--proto schema.proto --message Name`): a file of length-delimited
+//! messages, each preceded by its size as a varint (what `writeDelimitedTo` and
+//! `parseDelimitedFrom` produce). The `.proto` schema is parsed here, without code generation,
+//! and each message becomes one row:
+//!
+//! - Scalar and enum fields become columns (enums show their value names).
+//! - Nested messages are flattened into `parent.child` columns. They are null when the nested
+//!   message is absent.
+//! - Repeated scalars become list columns. Repeated messages, maps and recursive messages are
+//!   shown as JSON text.
+//!
+//! Fields without presence (proto3 fields not marked `optional`) read as their default value when
+//! absent, as protobuf defines; other absent fields are null. Imports are not followed, and
+//! groups are not supported.
+
+use crate::OpenOptions;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use polars::prelude::*;
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scalar {
+    Double,
+    Float,
+    Int32,
+    Int64,
+    Uint32,
+    Uint64,
+    Sint32,
+    Sint64,
+    Fixed32,
+    Fixed64,
+    Sfixed32,
+    Sfixed64,
+    Bool,
+    String,
+    Bytes,
+}
+
+impl Scalar {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "double" => Self::Double,
+            "float" => Self::Float,
+            "int32" => Self::Int32,
+            "int64" => Self::Int64,
+            "uint32" => Self::Uint32,
+            "uint64" => Self::Uint64,
+            "sint32" => Self::Sint32,
+            "sint64" => Self::Sint64,
+            "fixed32" => Self::Fixed32,
+            "fixed64" => Self::Fixed64,
+            "sfixed32" => Self::Sfixed32,
+            "sfixed64" => Self::Sfixed64,
+            "bool" => Self::Bool,
+            "string" => Self::String,
+            "bytes" => Self::Bytes,
+            _ => return None,
+        })
+    }
+
+    fn dtype(self) -> DataType {
+        match self {
+            Self::Double => DataType::Float64,
+            Self::Float => DataType::Float32,
+            Self::Int32 | Self::Sint32 | Self::Sfixed32 => DataType::Int32,
+            Self::Int64 | Self::Sint64 | Self::Sfixed64 => DataType::Int64,
+            Self::Uint32 | Self::Fixed32 => DataType::UInt32,
+            Self::Uint64 | Self::Fixed64 => DataType::UInt64,
+            Self::Bool => DataType::Boolean,
+            Self::String => DataType::String,
+            Self::Bytes => DataType::Binary,
+        }
+    }
+
+    /// Whether repeated values of this type may be packed into one length-delimited field.
+    fn packable(self) -> bool {
+        !matches!(self, Self::String | Self::Bytes)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FieldType {
+    Scalar(Scalar),
+    /// Full name of an enum.
+    Enum(String),
+    /// Full name of a message.
+    Message(String),
+    Map(Box<FieldType>, Box<FieldType>),
+    /// Type name as written, before resolution.
+    Named(String),
+}
+
+#[derive(Debug, Clone)]
+struct FieldDef {
+    name: String,
+    number: u32,
+    repeated: bool,
+    /// Whether an absent field is null (true) or its default value (proto3 fields without
+    /// `optional`).
+    presence: bool,
+    ty: FieldType,
+}
+
+#[derive(Debug, Clone, Default)]
+struct MessageDef {
+    fields: Vec<FieldDef>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct EnumDef {
+    /// Value names by number; the first listed is the default.
+    values: Vec<(i32, String)>,
+}
+
+impl EnumDef {
+    fn name_of(&self, number: i32) -> String {
+        self.values
+            .iter()
+            .find(|(n, _)| *n == number)
+            .map(|(_, name)| name.clone())
+            .unwrap_or_else(|| number.to_string())
+    }
+}
+
+/// Messages and enums of a `.proto` file, by full name (nested types as `Outer.Inner`, without
+/// the package).
+#[derive(Debug, Clone, Default)]
+pub struct ProtoSchema {
+    package: String,
+    proto3: bool,
+    messages: HashMap<String, MessageDef>,
+    enums: HashMap<String, EnumDef>,
+    /// Top-level messages, in file order.
+    top_level: Vec<String>,
+}
+
+/// Split `.proto` source into tokens: identifiers (with dots), numbers, quoted strings and
+/// single punctuation characters. Comments are dropped.
+fn tokenize(source: &str) -> Result<Vec<String>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+        } else if c == '"' || c == '\'' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err(eyre!("Unterminated string in .proto file"));
+            }
+            i += 1;
+            tokens.push(chars[start..i].iter().collect());
+        } else if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' || c == '+' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.' | '-' | '+'))
+            {
+                i += 1;
+            }
+            tokens.push(chars[start..i].iter().collect());
+        } else {
+            tokens.push(c.to_string());
+            i += 1;
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+    schema: ProtoSchema,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Result<String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| eyre!("Unexpected end of .proto file"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<()> {
+        let token = self.next()?;
+        if token != expected {
+            return Err(eyre!(
+                "Expected '{}' in .proto file, found '{}'",
+                expected,
+                token
+            ));
+        }
+        Ok(())
+    }
+
+    /// Skip to just past the next `;` at this nesting level.
+    fn skip_statement(&mut self) -> Result<()> {
+        let mut depth = 0usize;
+        loop {
+            match self.next()?.as_str() {
+                "{" | "[" | "(" => depth += 1,
+                "}" | "]" | ")" => depth = depth.saturating_sub(1),
+                ";" if depth == 0 => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+
+    /// Skip a `{ ... }` block, the name before it included.
+    fn skip_block(&mut self) -> Result<()> {
+        while self.next()? != "{" {}
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next()?.as_str() {
+                "{" => depth += 1,
+                "}" => depth -= 1,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_file(&mut self) -> Result<()> {
+        while let Some(token) = self.peek() {
+            match token {
+                "syntax" | "edition" => {
+                    self.next()?;
+                    self.expect("=")?;
+                    self.schema.proto3 = self.next()?.trim_matches('"') == "proto3";
+                    self.expect(";")?;
+                }
+                "package" => {
+                    self.next()?;
+                    self.schema.package = self.next()?;
+                    self.expect(";")?;
+                }
+                "message" => {
+                    let name = self.parse_message("")?;
+                    self.schema.top_level.push(name);
+                }
+                "enum" => self.parse_enum("")?,
+                "service" | "extend" => self.skip_block()?,
+                ";" => self.pos += 1,
+                _ => self.skip_statement()?,
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_message(&mut self, scope: &str) -> Result<String> {
+        self.expect("message")?;
+        let name = qualify(scope, &self.next()?);
+        self.expect("{")?;
+        let mut message = MessageDef::default();
+        loop {
+            match self.peek() {
+                Some("}") => {
+                    self.pos += 1;
+                    break;
+                }
+                Some("message") => {
+                    self.parse_message(&name)?;
+                }
+                Some("enum") => self.parse_enum(&name)?,
+                Some("oneof") => {
+                    self.next()?;
+                    self.next()?;
+                    self.expect("{")?;
+                    while self.peek() != Some("}") {
+                        if self.peek() == Some("option") {
+                            self.skip_statement()?;
+                        } else {
+                            message.fields.push(self.parse_field(true)?);
+                        }
+                    }
+                    self.pos += 1;
+                }
+                Some("option" | "reserved" | "extensions") => self.skip_statement()?,
+                Some("extend") => self.skip_block()?,
+                Some(";") => self.pos += 1,
+                Some(_) => message.fields.push(self.parse_field(false)?),
+                None => return Err(eyre!("Unexpected end of .proto file in message {}", name)),
+            }
+        }
+        self.schema.messages.insert(name.clone(), message);
+        Ok(name)
+    }
+
+    /// A field, `map<K, V>` field or oneof member (`in_oneof`, which always has presence).
+    fn parse_field(&mut self, in_oneof: bool) -> Result<FieldDef> {
+        let mut token = self.next()?;
+        let mut repeated = false;
+        let mut optional = in_oneof;
+        match token.as_str() {
+            "repeated" => repeated = true,
+            "optional" | "required" => optional = true,
+            "group" => return Err(eyre!("Protobuf groups are not supported")),
+            _ => {}
+        }
+        if repeated || (optional && !in_oneof) {
+            token = self.next()?;
+        }
+        let ty = if token == "map" {
+            self.expect("<")?;
+            let key = self.next()?;
+            self.expect(",")?;
+            let value = self.next()?;
+            self.expect(">")?;
+            repeated = true;
+            FieldType::Map(Box::new(named(&key)), Box::new(named(&value)))
+        } else {
+            named(&token)
+        };
+        let name = self.next()?;
+        self.expect("=")?;
+        let number_token = self.next()?;
+        let number = number_token
+            .parse()
+            .map_err(|_| eyre!("Bad field number '{}' for {}", number_token, name))?;
+        if self.peek() == Some("[") {
+            while self.next()? != "]" {}
+        }
+        self.expect(";")?;
+        Ok(FieldDef {
+            name,
+            number,
+            repeated,
+            presence: repeated || optional || !self.schema.proto3,
+            ty,
+        })
+    }
+
+    fn parse_enum(&mut self, scope: &str) -> Result<()> {
+        self.expect("enum")?;
+        let name = qualify(scope, &self.next()?);
+        self.expect("{")?;
+        let mut def = EnumDef::default();
+        loop {
+            match self.peek() {
+                Some("}") => {
+                    self.pos += 1;
+                    break;
+                }
+                Some("option" | "reserved") => self.skip_statement()?,
+                Some(";") => self.pos += 1,
+                Some(_) => {
+                    let value_name = self.next()?;
+                    self.expect("=")?;
+                    let number_token = self.next()?;
+                    let number = number_token.parse().map_err(|_| {
+                        eyre!("Bad enum value '{}' for {}", number_token, value_name)
+                    })?;
+                    if self.peek() == Some("[") {
+                        while self.next()? != "]" {}
+                    }
+                    self.expect(";")?;
+                    def.values.push((number, value_name));
+                }
+                None => return Err(eyre!("Unexpected end of .proto file in enum {}", name)),
+            }
+        }
+        self.schema.enums.insert(name, def);
+        Ok(())
+    }
+}
+
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", scope, name)
+    }
+}
+
+fn named(name: &str) -> FieldType {
+    match Scalar::parse(name) {
+        Some(scalar) => FieldType::Scalar(scalar),
+        None => FieldType::Named(name.to_string()),
+    }
+}
+
+impl ProtoSchema {
+    /// Parse `.proto` source.
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            pos: 0,
+            schema: ProtoSchema::default(),
+        };
+        parser.parse_file()?;
+        let mut schema = parser.schema;
+        schema.resolve()?;
+        Ok(schema)
+    }
+
+    /// Read and parse the `.proto` file at `path`.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| eyre!("Could not read {}: {}", path.display(), e))?;
+        Self::parse(&source).map_err(|e| eyre!("{}: {}", path.display(), e))
+    }
+
+    /// Replace type names in fields with the message or enum they refer to, looked up from the
+    /// field's message outwards as protobuf scoping does.
+    fn resolve(&mut self) -> Result<()> {
+        let names: Vec<String> = self.messages.keys().cloned().collect();
+        for scope in names {
+            let mut fields = std::mem::take(&mut self.messages.get_mut(&scope).unwrap().fields);
+            for field in &mut fields {
+                field.ty = self.resolve_type(&scope, &field.ty)?;
+            }
+            self.messages.get_mut(&scope).unwrap().fields = fields;
+        }
+        Ok(())
+    }
+
+    fn resolve_type(&self, scope: &str, ty: &FieldType) -> Result<FieldType> {
+        Ok(match ty {
+            FieldType::Named(name) => self.lookup(scope, name)?,
+            FieldType::Map(key, value) => FieldType::Map(
+                Box::new(self.resolve_type(scope, key)?),
+                Box::new(self.resolve_type(scope, value)?),
+            ),
+            other => other.clone(),
+        })
+    }
+
+    fn lookup(&self, scope: &str, name: &str) -> Result<FieldType> {
+        let found = |full: &str| {
+            if self.messages.contains_key(full) {
+                Some(FieldType::Message(full.to_string()))
+            } else if self.enums.contains_key(full) {
+                Some(FieldType::Enum(full.to_string()))
+            } else {
+                None
+            }
+        };
+        let package_prefix = format!("{}.", self.package);
+        let unqualified = name
+            .trim_start_matches('.')
+            .strip_prefix(package_prefix.as_str())
+            .unwrap_or(name.trim_start_matches('.'));
+        if !name.starts_with('.') {
+            let mut scope = scope;
+            loop {
+                if let Some(ty) = found(&qualify(scope, name)) {
+                    return Ok(ty);
+                }
+                if scope.is_empty() {
+                    break;
+                }
+                scope = scope.rsplit_once('.').map_or("", |(parent, _)| parent);
+            }
+        }
+        found(unqualified)
+            .ok_or_else(|| eyre!("Unknown type '{}' (imports are not followed)", name))
+    }
+
+    /// The message to read: `name` when given (a full or unqualified name), else the only
+    /// top-level message.
+    pub fn choose_message(&self, name: Option<&str>) -> Result<String> {
+        if let Some(name) = name {
+            return match self.lookup("", name) {
+                Ok(FieldType::Message(full)) => Ok(full),
+                _ => Err(eyre!(
+                    "No message '{}' in the schema (messages: {})",
+                    name,
+                    self.top_level.join(", ")
+                )),
+            };
+        }
+        match self.top_level.as_slice() {
+            [] => Err(eyre!("The schema has no messages")),
+            [only] => Ok(only.clone()),
+            _ => Err(eyre!(
+                "The schema has several messages ({}). Choose one with --message.",
+                self.top_level.join(", ")
+            )),
+        }
+    }
+}
+
+/// One decoded field value.
+#[derive(Debug, Clone, PartialEq)]
+enum ProtoValue {
+    Int(i64),
+    UInt(u64),
+    F32(f32),
+    F64(f64),
+    Bool(bool),
+    Str(String),
+    Bytes(Vec<u8>),
+    Enum(i32),
+    Message(Decoded),
+}
+
+/// A decoded message: every value read for each field, by field index.
+#[derive(Debug, Clone, PartialEq)]
+struct Decoded(Vec<Vec<ProtoValue>>);
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn at_end(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self
+                .data
+                .get(self.pos)
+                .ok_or_else(|| eyre!("Truncated varint"))?;
+            self.pos += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(eyre!("Varint longer than 10 bytes"))
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(|| eyre!("Truncated field"))?;
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn fixed32(&mut self) -> Result<[u8; 4]> {
+        Ok(self.bytes(4)?.try_into().unwrap())
+    }
+
+    fn fixed64(&mut self) -> Result<[u8; 8]> {
+        Ok(self.bytes(8)?.try_into().unwrap())
+    }
+
+    fn length_delimited(&mut self) -> Result<&'a [u8]> {
+        let len = self.varint()? as usize;
+        self.bytes(len)
+    }
+}
+
+const WIRE_VARINT: u64 = 0;
+const WIRE_FIXED64: u64 = 1;
+const WIRE_LEN: u64 = 2;
+const WIRE_FIXED32: u64 = 5;
+
+fn zigzag(n: u64) -> i64 {
+    ((n >> 1) as i64) ^ -((n & 1) as i64)
+}
+
+impl ProtoSchema {
+    fn decode(&self, def: &MessageDef, data: &[u8]) -> Result<Decoded> {
+        let mut values = vec![Vec::new(); def.fields.len()];
+        let mut reader = Reader { data, pos: 0 };
+        while !reader.at_end() {
+            let key = reader.varint()?;
+            let (number, wire) = ((key >> 3) as u32, key & 7);
+            let Some(index) = def.fields.iter().position(|f| f.number == number) else {
+                skip_field(&mut reader, wire)?;
+                continue;
+            };
+            let field = &def.fields[index];
+            match (&field.ty, wire) {
+                (FieldType::Scalar(scalar), WIRE_LEN) if field.repeated && scalar.packable() => {
+                    let mut packed = Reader {
+                        data: reader.length_delimited()?,
+                        pos: 0,
+                    };
+                    while !packed.at_end() {
+                        values[index].push(read_scalar(&mut packed, *scalar)?);
+                    }
+                }
+                (FieldType::Enum(_), WIRE_LEN) if field.repeated => {
+                    let mut packed = Reader {
+                        data: reader.length_delimited()?,
+                        pos: 0,
+                    };
+                    while !packed.at_end() {
+                        values[index].push(ProtoValue::Enum(packed.varint()? as i32));
+                    }
+                }
+                (FieldType::Scalar(scalar), _) => {
+                    values[index].push(read_scalar(&mut reader, *scalar)?)
+                }
+                (FieldType::Enum(_), _) => {
+                    values[index].push(ProtoValue::Enum(reader.varint()? as i32))
+                }
+                (FieldType::Message(name), _) => {
+                    let nested = self.decode(&self.messages[name], reader.length_delimited()?)?;
+                    values[index].push(ProtoValue::Message(nested));
+                }
+                (FieldType::Map(key, value), _) => {
+                    let entry = reader.length_delimited()?;
+                    values[index].push(ProtoValue::Message(
+                        self.decode_map_entry(key, value, entry)?,
+                    ));
+                }
+                (FieldType::Named(_), _) => unreachable!("types are resolved when parsing"),
+            }
+        }
+        Ok(Decoded(values))
+    }
+
+    /// A map entry, decoded as a message with `key` (1) and `value` (2).
+    fn decode_map_entry(&self, key: &FieldType, value: &FieldType, data: &[u8]) -> Result<Decoded> {
+        let entry = MessageDef {
+            fields: [("key", 1, key), ("value", 2, value)]
+                .into_iter()
+                .map(|(name, number, ty)| FieldDef {
+                    name: name.to_string(),
+                    number,
+                    repeated: false,
+                    presence: false,
+                    ty: ty.clone(),
+                })
+                .collect(),
+        };
+        self.decode(&entry, data)
+    }
+}
+
+fn skip_field(reader: &mut Reader, wire: u64) -> Result<()> {
+    match wire {
+        WIRE_VARINT => {
+            reader.varint()?;
+        }
+        WIRE_FIXED64 => {
+            reader.fixed64()?;
+        }
+        WIRE_LEN => {
+            reader.length_delimited()?;
+        }
+        WIRE_FIXED32 => {
+            reader.fixed32()?;
+        }
+        _ => return Err(eyre!("Unsupported wire type {} (groups?)", wire)),
+    }
+    Ok(())
+}
+
+fn read_scalar(reader: &mut Reader, scalar: Scalar) -> Result<ProtoValue> {
+    Ok(match scalar {
+        Scalar::Double => ProtoValue::F64(f64::from_le_bytes(reader.fixed64()?)),
+        Scalar::Float => ProtoValue::F32(f32::from_le_bytes(reader.fixed32()?)),
+        Scalar::Int32 => ProtoValue::Int(reader.varint()? as i32 as i64),
+        Scalar::Int64 => ProtoValue::Int(reader.varint()? as i64),
+        Scalar::Uint32 | Scalar::Uint64 => ProtoValue::UInt(reader.varint()?),
+        Scalar::Sint32 | Scalar::Sint64 => ProtoValue::Int(zigzag(reader.varint()?)),
+        Scalar::Fixed32 => ProtoValue::UInt(u32::from_le_bytes(reader.fixed32()?) as u64),
+        Scalar::Fixed64 => ProtoValue::UInt(u64::from_le_bytes(reader.fixed64()?)),
+        Scalar::Sfixed32 => ProtoValue::Int(i32::from_le_bytes(reader.fixed32()?) as i64),
+        Scalar::Sfixed64 => ProtoValue::Int(i64::from_le_bytes(reader.fixed64()?)),
+        Scalar::Bool => ProtoValue::Bool(reader.varint()? != 0),
+        Scalar::String => {
+            ProtoValue::Str(String::from_utf8_lossy(reader.length_delimited()?).into_owned())
+        }
+        Scalar::Bytes => ProtoValue::Bytes(reader.length_delimited()?.to_vec()),
+    })
+}
+
+/// How a column's cells are read from a record.
+#[derive(Debug, Clone)]
+struct ColumnDef {
+    name: String,
+    /// Field indexes from the record to the field, through nested messages.
+    path: Vec<usize>,
+    field: FieldDef,
+    /// Shown as JSON text: repeated messages, maps and recursive messages.
+    json: bool,
+    dtype: DataType,
+}
+
+impl ProtoSchema {
+    fn columns(&self, message: &str) -> Vec<ColumnDef> {
+        let mut columns = Vec::new();
+        self.collect_columns(
+            message,
+            "",
+            &[],
+            &mut vec![message.to_string()],
+            &mut columns,
+        );
+        columns
+    }
+
+    fn collect_columns(
+        &self,
+        message: &str,
+        prefix: &str,
+        path: &[usize],
+        ancestors: &mut Vec<String>,
+        columns: &mut Vec<ColumnDef>,
+    ) {
+        for (index, field) in self.messages[message].fields.iter().enumerate() {
+            let name = format!("{}{}", prefix, field.name);
+            let mut field_path = path.to_vec();
+            field_path.push(index);
+            let scalar_dtype = match &field.ty {
+                FieldType::Scalar(scalar) => Some(scalar.dtype()),
+                FieldType::Enum(_) => Some(DataType::String),
+                _ => None,
+            };
+            let (dtype, json) = match (&field.ty, scalar_dtype) {
+                (_, Some(dtype)) if field.repeated => (DataType::List(Box::new(dtype)), false),
+                (_, Some(dtype)) => (dtype, false),
+                (FieldType::Message(nested), _)
+                    if !field.repeated && !ancestors.contains(nested) =>
+                {
+                    ancestors.push(nested.clone());
+                    self.collect_columns(
+                        nested,
+                        &format!("{}.", name),
+                        &field_path,
+                        ancestors,
+                        columns,
+                    );
+                    ancestors.pop();
+                    continue;
+                }
+                _ => (DataType::String, true),
+            };
+            columns.push(ColumnDef {
+                name,
+                path: field_path,
+                field: field.clone(),
+                json,
+                dtype,
+            });
+        }
+    }
+
+    fn default_value(&self, ty: &FieldType) -> ProtoValue {
+        match ty {
+            FieldType::Scalar(Scalar::Double) => ProtoValue::F64(0.0),
+            FieldType::Scalar(Scalar::Float) => ProtoValue::F32(0.0),
+            FieldType::Scalar(
+                Scalar::Uint32 | Scalar::Uint64 | Scalar::Fixed32 | Scalar::Fixed64,
+            ) => ProtoValue::UInt(0),
+            FieldType::Scalar(Scalar::Bool) => ProtoValue::Bool(false),
+            FieldType::Scalar(Scalar::String) => ProtoValue::Str(String::new()),
+            FieldType::Scalar(Scalar::Bytes) => ProtoValue::Bytes(Vec::new()),
+            FieldType::Scalar(_) => ProtoValue::Int(0),
+            FieldType::Enum(name) => {
+                ProtoValue::Enum(self.enums[name].values.first().map_or(0, |(n, _)| *n))
+            }
+            _ => ProtoValue::Message(Decoded(Vec::new())),
+        }
+    }
+
+    fn any_value(&self, ty: &FieldType, value: &ProtoValue) -> AnyValue<'static> {
+        match (ty, value) {
+            (FieldType::Enum(name), ProtoValue::Enum(n)) => {
+                AnyValue::StringOwned(self.enums[name].name_of(*n).into())
+            }
+            (FieldType::Scalar(scalar), value) => match (scalar.dtype(), value) {
+                (DataType::Int32, ProtoValue::Int(n)) => AnyValue::Int32(*n as i32),
+                (DataType::UInt32, ProtoValue::UInt(n)) => AnyValue::UInt32(*n as u32),
+                (_, ProtoValue::Int(n)) => AnyValue::Int64(*n),
+                (_, ProtoValue::UInt(n)) => AnyValue::UInt64(*n),
+                (_, ProtoValue::F32(f)) => AnyValue::Float32(*f),
+                (_, ProtoValue::F64(f)) => AnyValue::Float64(*f),
+                (_, ProtoValue::Bool(b)) => AnyValue::Boolean(*b),
+                (_, ProtoValue::Str(s)) => AnyValue::StringOwned(s.as_str().into()),
+                (_, ProtoValue::Bytes(b)) => AnyValue::BinaryOwned(b.clone()),
+                _ => AnyValue::Null,
+            },
+            _ => AnyValue::Null,
+        }
+    }
+
+    fn json_value(&self, ty: &FieldType, value: &ProtoValue) -> serde_json::Value {
+        use serde_json::Value as Json;
+        match (ty, value) {
+            (FieldType::Enum(name), ProtoValue::Enum(n)) => {
+                Json::from(self.enums[name].name_of(*n))
+            }
+            (FieldType::Message(name), ProtoValue::Message(decoded)) => {
+                self.json_message(&self.messages[name].fields, decoded)
+            }
+            (_, ProtoValue::Int(n)) => Json::from(*n),
+            (_, ProtoValue::UInt(n)) => Json::from(*n),
+            (_, ProtoValue::F32(f)) => Json::from(*f),
+            (_, ProtoValue::F64(f)) => Json::from(*f),
+            (_, ProtoValue::Bool(b)) => Json::from(*b),
+            (_, ProtoValue::Str(s)) => Json::from(s.as_str()),
+            (_, ProtoValue::Bytes(b)) => Json::from(String::from_utf8_lossy(b).into_owned()),
+            _ => Json::Null,
+        }
+    }
+
+    /// A message as a JSON object of the fields it has.
+    fn json_message(&self, fields: &[FieldDef], decoded: &Decoded) -> serde_json::Value {
+        let mut object = serde_json::Map::new();
+        for (field, values) in fields.iter().zip(&decoded.0) {
+            if !values.is_empty() {
+                object.insert(field.name.clone(), self.json_field(field, values));
+            }
+        }
+        serde_json::Value::Object(object)
+    }
+
+    /// All values of a field as JSON: an object for maps, an array for repeated fields, else
+    /// the last value.
+    fn json_field(&self, field: &FieldDef, values: &[ProtoValue]) -> serde_json::Value {
+        match &field.ty {
+            FieldType::Map(key, value) => {
+                let mut object = serde_json::Map::new();
+                for entry in values {
+                    let ProtoValue::Message(Decoded(entry)) = entry else {
+                        continue;
+                    };
+                    let key_text = match entry.first().and_then(|k| k.last()) {
+                        Some(ProtoValue::Str(s)) => s.clone(),
+                        Some(k) => self.json_value(key, k).to_string(),
+                        None => String::new(),
+                    };
+                    let entry_value = entry
+                        .get(1)
+                        .and_then(|v| v.last())
+                        .map_or(serde_json::Value::Null, |v| self.json_value(value, v));
+                    object.insert(key_text, entry_value);
+                }
+                serde_json::Value::Object(object)
+            }
+            ty if field.repeated => {
+                serde_json::Value::Array(values.iter().map(|v| self.json_value(ty, v)).collect())
+            }
+            ty => values
+                .last()
+                .map_or(serde_json::Value::Null, |v| self.json_value(ty, v)),
+        }
+    }
+
+    /// The cell of `column` in `record`.
+    fn cell(&self, column: &ColumnDef, record: &Decoded) -> AnyValue<'static> {
+        let (last, parents) = column.path.split_last().unwrap();
+        let mut message = record;
+        for &index in parents {
+            match message.0.get(index).and_then(|v| v.last()) {
+                Some(ProtoValue::Message(nested)) => message = nested,
+                // An absent nested message makes its fields null.
+                _ => return AnyValue::Null,
+            }
+        }
+        let values = message.0.get(*last).map(Vec::as_slice).unwrap_or_default();
+        let field = &column.field;
+        if column.json {
+            if values.is_empty() && !field.repeated {
+                return AnyValue::Null;
+            }
+            return AnyValue::StringOwned(self.json_field(field, values).to_string().into());
+        }
+        if field.repeated {
+            let DataType::List(inner) = &column.dtype else {
+                unreachable!("repeated scalars are list columns");
+            };
+            let items: Vec<AnyValue> = values
+                .iter()
+                .map(|v| self.any_value(&field.ty, v))
+                .collect();
+            return Series::from_any_values_and_dtype("".into(), &items, inner, false)
+                .map_or(AnyValue::Null, AnyValue::List);
+        }
+        match values.last() {
+            Some(value) => self.any_value(&field.ty, value),
+            None if field.presence => AnyValue::Null,
+            None => self.any_value(&field.ty, &self.default_value(&field.ty)),
+        }
+    }
+
+    /// Decode length-delimited `message` records from `data` into a frame, one row per record.
+    pub fn read_records(&self, message: &str, data: &[u8]) -> Result<DataFrame> {
+        let mut reader = Reader { data, pos: 0 };
+        let mut records = Vec::new();
+        while !reader.at_end() {
+            let bytes = reader
+                .length_delimited()
+                .map_err(|_| eyre!("Record {} is truncated", records.len() + 1))?;
+            let record = self
+                .decode(&self.messages[message], bytes)
+                .map_err(|e| eyre!("Record {}: {}", records.len() + 1, e))?;
+            records.push(record);
+        }
+        let columns = self
+            .columns(message)
+            .into_iter()
+            .map(|column| {
+                let cells: Vec<AnyValue> = records.iter().map(|r| self.cell(&column, r)).collect();
+                Series::from_any_values_and_dtype(
+                    column.name.as_str().into(),
+                    &cells,
+                    &column.dtype,
+                    false,
+                )
+                .map(Column::from)
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        Ok(DataFrame::new(columns)?)
+    }
+}
+
+/// Read the protobuf record file at `path` with the schema and message from `options`.
+pub fn read_file(path: &Path, options: &OpenOptions) -> Result<DataFrame> {
+    let schema_path = options
+        .proto_schema
+        .as_deref()
+        .ok_or_else(|| eyre!("Protobuf files need a schema: --proto schema.proto"))?;
+    let schema = ProtoSchema::from_file(schema_path)?;
+    let message = schema.choose_message(options.proto_message.as_deref())?;
+    let data =
+        std::fs::read(path).map_err(|e| eyre!("Could not read {}: {}", path.display(), e))?;
+    schema
+        .read_records(&message, &data)
+        .map_err(|e| eyre!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"
+        syntax = "proto3";
+        package logs;
+
+        // A request log line.
+        message Request {
+          enum Method { GET = 0; POST = 1; }
+          message Client { string ip = 1; uint32 port = 2; }
+
+          int64 id = 1;
+          Method method = 2;
+          Client client = 3;
+          repeated string tags = 4;
+          optional double latency = 5;
+          map<string, int32> headers = 6;
+          repeated Event events = 7;
+        }
+
+        message Event { string name = 1; sint32 delta = 2; }
+    "#;
+
+    fn varint(mut n: u64, out: &mut Vec<u8>) {
+        while n >= 0x80 {
+            out.push((n as u8) | 0x80);
+            n >>= 7;
+        }
+        out.push(n as u8);
+    }
+
+    fn field(number: u32, wire: u64, out: &mut Vec<u8>) {
+        varint(((number as u64) << 3) | wire, out);
+    }
+
+    fn bytes_field(number: u32, bytes: &[u8], out: &mut Vec<u8>) {
+        field(number, WIRE_LEN, out);
+        varint(bytes.len() as u64, out);
+        out.extend_from_slice(bytes);
+    }
+
+    fn delimited(records: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for record in records {
+            varint(record.len() as u64, &mut out);
+            out.extend_from_slice(record);
+        }
+        out
+    }
+
+    #[test]
+    fn reads_delimited_records_into_flat_columns() {
+        let schema = ProtoSchema::parse(SCHEMA).unwrap();
+        assert_eq!(
+            schema.choose_message(None).unwrap_err().to_string(),
+            "The schema has several messages (Request, Event). Choose one with --message."
+        );
+        let message = schema.choose_message(Some("logs.Request")).unwrap();
+
+        let mut client = Vec::new();
+        bytes_field(1, b"10.0.0.1", &mut client);
+        field(2, WIRE_VARINT, &mut client);
+        varint(8080, &mut client);
+        let mut header = Vec::new();
+        bytes_field(1, b"host", &mut header);
+        field(2, WIRE_VARINT, &mut header);
+        varint(7, &mut header);
+        let mut event = Vec::new();
+        bytes_field(1, b"retry", &mut event);
+        field(2, WIRE_VARINT, &mut event);
+        varint(3, &mut event); // zigzag for -2
+
+        let mut first = Vec::new();
+        field(1, WIRE_VARINT, &mut first);
+        varint(42, &mut first);
+        field(2, WIRE_VARINT, &mut first);
+        varint(1, &mut first);
+        bytes_field(3, &client, &mut first);
+        bytes_field(4, b"a", &mut first);
+        bytes_field(4, b"b", &mut first);
+        field(5, WIRE_FIXED64, &mut first);
+        first.extend_from_slice(&1.5f64.to_le_bytes());
+        bytes_field(6, &header, &mut first);
+        bytes_field(7, &event, &mut first);
+        field(99, WIRE_VARINT, &mut first); // unknown field, skipped
+        varint(5, &mut first);
+        // Second record: everything absent
+        let data = delimited(&[first, Vec::new()]);
+
+        let df = schema.read_records(&message, &data).unwrap();
+        let names: Vec<&str> = df.get_column_names().iter().map(|c| c.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "id",
+                "method",
+                "client.ip",
+                "client.port",
+                "tags",
+                "latency",
+                "headers",
+                "events"
+            ]
+        );
+        assert_eq!(df.height(), 2);
+        let row = |name: &str, i| df.column(name).unwrap().get(i).unwrap().to_string();
+        assert_eq!(row("id", 0), "42");
+        assert_eq!(row("method", 0), "\"POST\"");
+        assert_eq!(row("client.ip", 0), "\"10.0.0.1\"");
+        assert_eq!(row("client.port", 0), "8080");
+        assert_eq!(row("latency", 0), "1.5");
+        assert_eq!(row("headers", 0), r#""{"host":7}""#);
+        assert_eq!(row("events", 0), r#""[{"name":"retry","delta":-2}]""#);
+        assert_eq!(
+            df.column("tags").unwrap().dtype(),
+            &DataType::List(Box::new(DataType::String))
+        );
+        // proto3 defaults for absent fields without presence; null otherwise
+        assert_eq!(row("id", 1), "0");
+        assert_eq!(row("method", 1), "\"GET\"");
+        assert_eq!(row("client.ip", 1), "null");
+        assert_eq!(row("latency", 1), "null");
+    }
+
+    #[test]
+    fn reports_unknown_types_and_truncated_records() {
+        let err = ProtoSchema::parse("syntax = \"proto3\"; message A { other.B b = 1; }")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown type 'other.B'"), "{err}");
+
+        let schema = ProtoSchema::parse("message A { optional int32 x = 1; }").unwrap();
+        assert_eq!(schema.choose_message(None).unwrap(), "A");
+        let err = schema.read_records("A", &[5, 8]).unwrap_err().to_string();
+        assert_eq!(err, "Record 1 is truncated");
+    }
+}
@@ -2,6 +2,25 @@ use polars::prelude::StrptimeOptions;
 use polars::prelude::*;
 use std::ops::{Add, Div, Mul, Sub};
 
+/// Language of the query that produced the current view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryEngine {
+    /// datui's own `select ... where ... by ...` syntax (SQL-Like tab).
+    DatuiExpr,
+    /// Full SQL run through the Polars SQL context, with the data registered as table `df`.
+    Sql,
+}
+
+impl QueryEngine {
+    /// Short name shown in the control bar.
+    pub fn label(self) -> &'static str {
+        match self {
+            QueryEngine::DatuiExpr => "Query",
+            QueryEngine::Sql => "SQL",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 enum Token {
     Identifier(String),
@@ -189,8 +208,8 @@ fn tokenize(input: &str) -> Result<Vec<Token>, String> {
                 }
                 tokens.push(Token::String(string_val));
             }
-            '^' => {
-                tokens.push(Token::Op("^".to_string()));
+            '^' | '/' => {
+                tokens.push(Token::Op(c.to_string()));
                 chars.next();
             }
             '+' | '-' | '*' | '%' | '=' | '<' | '>' | '!' => {
@@ -275,6 +294,16 @@ fn tokenize(input: &str) -> Result<Vec<Token>, String> {
                 match ident.as_str() {
                     "select" => tokens.push(Token::Select),
                     "where" => tokens.push(Token::Where),
+                    // SQL's `group by` is `by`; `group` after `select`, `,` or `:` is a column
+                    "by" if matches!(tokens.last(), Some(Token::Identifier(g)) if g == "group")
+                        && !matches!(
+                            tokens.iter().rev().nth(1),
+                            Some(Token::Select | Token::Comma | Token::Colon)
+                        ) =>
+                    {
+                        tokens.pop();
+                        tokens.push(Token::By);
+                    }
                     "by" => tokens.push(Token::By),
                     _ => tokens.push(Token::Identifier(ident)),
                 }
@@ -316,7 +345,7 @@ fn apply_op(left: Expr, op: &str, right: Expr) -> Result<Expr, String> {
         "+" => Ok(left.add(right)),
         "-" => Ok(left.sub(right)),
         "*" => Ok(left.mul(right)),
-        "%" => Ok(left.div(right)),
+        "%" | "/" => Ok(left.div(right)),
         "^" => Ok(coalesce(&[left, right])),
         "=" => Ok(left.eq(right)),
         "<" => Ok(left.lt(right)),
@@ -328,8 +357,75 @@ fn apply_op(left: Expr, op: &str, right: Expr) -> Result<Expr, String> {
     }
 }
 
+/// `x between a and b`, `x in (a, b, ...)`, `x is null` or `x is not null`, split at the first
+/// top-level `between`, `in` or `is` after the operand. None when `tokens` has none of them.
+fn parse_predicate(tokens: &[Token]) -> Result<Option<Expr>, String> {
+    let mut depth = 0;
+    let mut keyword = None;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::LParen | Token::LBracket => depth += 1,
+            Token::RParen | Token::RBracket => depth -= 1,
+            Token::Identifier(name)
+                if depth == 0 && i > 0 && matches!(name.as_str(), "between" | "in" | "is") =>
+            {
+                keyword = Some((i, name.as_str()));
+                break;
+            }
+            _ => {}
+        }
+    }
+    let Some((pos, keyword)) = keyword else {
+        return Ok(None);
+    };
+    let left = parse_expr(&tokens[..pos])?;
+    let rest = &tokens[pos + 1..];
+    let word = |token: &Token, w: &str| matches!(token, Token::Identifier(name) if name == w);
+    match keyword {
+        "is" => match rest {
+            [null] if word(null, "null") => Ok(Some(left.is_null())),
+            [not, null] if word(not, "not") && word(null, "null") => Ok(Some(left.is_not_null())),
+            _ => Err("Expected 'is null' or 'is not null'".to_string()),
+        },
+        "in" => {
+            let inner = match rest {
+                [Token::LParen, inner @ .., Token::RParen] => inner,
+                _ => return Err("Expected a list after 'in', e.g. x in (1, 2)".to_string()),
+            };
+            let mut any: Option<Expr> = None;
+            for value in split_tokens(inner, &Token::Comma) {
+                if value.is_empty() {
+                    continue;
+                }
+                let eq = left.clone().eq(parse_expr(&value)?);
+                any = Some(match any {
+                    Some(curr) => curr.or(eq),
+                    None => eq,
+                });
+            }
+            any.map(Some)
+                .ok_or_else(|| "The list after 'in' is empty".to_string())
+        }
+        _ => {
+            let and = split_tokens(rest, &Token::Identifier("and".to_string()));
+            match and.as_slice() {
+                [low, high] if !low.is_empty() && !high.is_empty() => {
+                    let low = parse_expr(low)?;
+                    let high = parse_expr(high)?;
+                    Ok(Some(left.clone().gt_eq(low).and(left.lt_eq(high))))
+                }
+                _ => Err("Expected 'between low and high'".to_string()),
+            }
+        }
+    }
+}
+
 // Parse aggregation function like avg[a], min[b], etc.
 fn parse_agg_function(name: &str, args: &[Token]) -> Result<Expr, String> {
+    // count[] counts rows, nulls included
+    if name.eq_ignore_ascii_case("count") && args.is_empty() {
+        return Ok(len().alias("count"));
+    }
     if args.is_empty() {
         return Err(format!(
             "Aggregation function {} requires an argument",
@@ -345,6 +441,7 @@ fn parse_agg_function(name: &str, args: &[Token]) -> Result<Expr, String> {
         "std" | "stddev" => Ok(expr.std(1)),
         "med" | "median" => Ok(expr.median()),
         "sum" => Ok(expr.sum()),
+        "n_unique" | "nunique" => Ok(expr.n_unique()),
         "first" => Ok(expr.first()),
         "last" => Ok(expr.last()),
         _ => Err(format!("Unknown aggregation function: {}", name)),
@@ -380,6 +477,9 @@ fn apply_dt_accessor(expr: Expr, accessor: &str, _arg: Option<&str>) -> Result<E
         "month" => Ok(dt.month()),
         "week" => Ok(dt.week()),
         "day" => Ok(dt.day()),
+        "hour" => Ok(dt.hour()),
+        "minute" => Ok(dt.minute()),
+        "second" => Ok(dt.second()),
         "dow" => Ok(dt.weekday()),
         "weekday" => Ok(dt.weekday()),
         "month_start" => Ok(dt.month_start()),
@@ -389,7 +489,7 @@ fn apply_dt_accessor(expr: Expr, accessor: &str, _arg: Option<&str>) -> Result<E
             Ok(dt.to_string(fmt))
         }
         _ => Err(format!(
-            "Unknown date/time accessor: '{}'. Valid: date, time, year, month, week, day, dow, month_start, month_end, format",
+            "Unknown date/time accessor: '{}'. Valid: date, time, year, month, week, day, hour, minute, second, dow, month_start, month_end, format",
             accessor
         )),
     }
@@ -430,14 +530,94 @@ fn apply_accessor(expr: Expr, accessor: &str, arg: Option<&str>) -> Result<Expr,
         return Ok(e);
     }
     Err(format!(
-        "Unknown accessor: '{}'. Valid date: date, time, year, month, week, day, dow, month_start, month_end, format. Valid string: len, upper, lower, starts_with, ends_with, contains",
+        "Unknown accessor: '{}'. Valid date: date, time, year, month, week, day, hour, minute, second, dow, month_start, month_end, format. Valid string: len, upper, lower, starts_with, ends_with, contains",
         accessor
     ))
 }
 
+/// Rolling window functions, as `.rolling_<function>(...)` accessors.
+pub const ROLLING_FUNCTIONS: [&str; 5] = ["mean", "sum", "std", "min", "max"];
+
+fn is_rolling_accessor(accessor: &str) -> bool {
+    accessor
+        .to_lowercase()
+        .strip_prefix("rolling_")
+        .is_some_and(|f| ROLLING_FUNCTIONS.contains(&f))
+}
+
+/// Apply a rolling window accessor given its arguments: `.rolling_mean(7)` over the last 7 rows,
+/// or `.rolling_mean("1h", ts)` over the last hour of the `ts` column. The first rows, before a
+/// window is full, use the rows available. Returns the expression and its alias suffix.
+fn apply_rolling_accessor(
+    expr: Expr,
+    accessor: &str,
+    args: &[Token],
+) -> Result<(Expr, String), String> {
+    let accessor = accessor.to_lowercase();
+    let function = accessor.trim_start_matches("rolling_");
+    match args {
+        [Token::Number(n)] => {
+            if *n < 1.0 || n.fract() != 0.0 {
+                return Err(format!(
+                    "{} window must be a whole number of rows, e.g. {}(7)",
+                    accessor, accessor
+                ));
+            }
+            let window_size = *n as usize;
+            let options = RollingOptionsFixedWindow {
+                window_size,
+                min_periods: 1,
+                ..Default::default()
+            };
+            let expr = match function {
+                "mean" => expr.rolling_mean(options),
+                "sum" => expr.rolling_sum(options),
+                "std" => expr.rolling_std(options),
+                "min" => expr.rolling_min(options),
+                _ => expr.rolling_max(options),
+            };
+            Ok((expr, format!("{}_{}", accessor, window_size)))
+        }
+        [Token::String(period), Token::Comma, Token::String(by) | Token::Identifier(by)] => {
+            let window_size = Duration::try_parse(period)
+                .ok()
+                .filter(|d| !d.is_zero() && !d.negative())
+                .ok_or_else(|| {
+                    format!(
+                        "Invalid {} window \"{}\": expected e.g. 30m, 1h, 7d",
+                        accessor, period
+                    )
+                })?;
+            let options = RollingOptionsDynamicWindow {
+                window_size,
+                min_periods: 1,
+                closed_window: ClosedWindow::Right,
+                fn_params: None,
+            };
+            let by = col(by.as_str());
+            let expr = match function {
+                "mean" => expr.rolling_mean_by(by, options),
+                "sum" => expr.rolling_sum_by(by, options),
+                "std" => expr.rolling_std_by(by, options),
+                "min" => expr.rolling_min_by(by, options),
+                _ => expr.rolling_max_by(by, options),
+            };
+            Ok((expr, format!("{}_{}", accessor, period)))
+        }
+        _ => Err(format!(
+            "{} expects a number of rows or a duration and a time column, e.g. {}(7) or {}(\"1h\", ts)",
+            accessor, accessor, accessor
+        )),
+    }
+}
+
 /// Parse optional dot accessors from remaining tokens. Returns (expr_with_accessors, remaining).
 /// When base_name is Some, each accessor result is aliased to {base}_{accessor} (or {base}_{acc1}_{acc2} for chained)
 /// to avoid duplicate column names.
+///
+/// Polars-style spellings are accepted too: a `.dt` / `.str` namespace before the accessor is
+/// skipped, and arguments can be given in parentheses (`ts.dt.hour()`, `name.str.contains("x")`).
+/// Rolling windows take one or two arguments: `x.rolling_mean(7)`, `x.rolling_sum("1h", ts)`.
 fn parse_accessors<'a>(
     mut expr: Expr,
     mut tokens: &'a [Token],
@@ -446,25 +626,50 @@ fn parse_accessors<'a>(
     let mut alias_suffix = String::new();
     while tokens.len() >= 2 {
         if let (Token::Dot, Token::Identifier(accessor)) = (&tokens[0], &tokens[1]) {
-            let (arg, consumed) = if tokens.len() >= 5
-                && tokens[2] == Token::LBracket
-                && matches!(tokens[3], Token::String(_) | Token::Identifier(_))
-                && tokens[4] == Token::RBracket
-            {
-                // accessor["arg"] or accessor[arg]
-                let arg = match &tokens[3] {
-                    Token::String(s) => s.clone(),
-                    Token::Identifier(id) => id.clone(),
-                    _ => {
-                        return Err(
-                            "Bracket accessor requires string or identifier argument".to_string()
-                        )
-                    }
-                };
-                (Some(arg), 5)
-            } else {
-                (None, 2)
+            if (accessor == "dt" || accessor == "str") && tokens.get(2) == Some(&Token::Dot) {
+                tokens = &tokens[2..];
+                continue;
+            }
+            if is_rolling_accessor(accessor) && tokens.get(2) == Some(&Token::LParen) {
+                let end = tokens
+                    .iter()
+                    .position(|t| *t == Token::RParen)
+                    .ok_or_else(|| format!("Missing ')' after {}(", accessor))?;
+                let (rolled, suffix) = apply_rolling_accessor(expr, accessor, &tokens[3..end])?;
+                expr = rolled;
+                if !alias_suffix.is_empty() {
+                    alias_suffix.push('_');
+                }
+                alias_suffix.push_str(&suffix);
+                tokens = &tokens[end + 1..];
+                continue;
+            }
+            let (open, close) = match tokens.get(2) {
+                Some(Token::LParen) => (Token::LParen, Token::RParen),
+                _ => (Token::LBracket, Token::RBracket),
             };
+            let (arg, consumed) =
+                if tokens.len() >= 4 && open == Token::LParen && tokens[3] == close {
+                    // accessor()
+                    (None, 4)
+                } else if tokens.len() >= 5
+                    && tokens[2] == open
+                    && matches!(tokens[3], Token::String(_) | Token::Identifier(_))
+                    && tokens[4] == close
+                {
+                    // accessor["arg"] or accessor[arg] (or with parentheses)
+                    let arg = match &tokens[3] {
+                        Token::String(s) => s.clone(),
+                        Token::Identifier(id) => id.clone(),
+                        _ => {
+                            return Err("Bracket accessor requires string or identifier argument"
+                                .to_string())
+                        }
+                    };
+                    (Some(arg), 5)
+                } else {
+                    (None, 2)
+                };
             expr = apply_accessor(expr, accessor, arg.as_deref())?;
             if !alias_suffix.is_empty() {
                 alias_suffix.push('_');
@@ -489,34 +694,127 @@ fn parse_accessors<'a>(
     Ok((expr, tokens))
 }
 
+/// Functions of the query language: aggregations, then the rest.
+const FUNCTION_NAMES: [&str; 24] = [
+    "avg", "mean", "min", "max", "count", "std", "stddev", "med", "median", "sum", "n_unique",
+    "nunique", "first", "last", "len", "length", "not", "null", "upper", "lower", "abs", "floor",
+    "ceil", "ceiling",
+];
+
+/// Accessors written after `.`: date and time, then string.
+const ACCESSOR_NAMES: [&str; 19] = [
+    "date",
+    "time",
+    "year",
+    "month",
+    "week",
+    "day",
+    "hour",
+    "minute",
+    "second",
+    "dow",
+    "month_start",
+    "month_end",
+    "format",
+    "len",
+    "upper",
+    "lower",
+    "starts_with",
+    "ends_with",
+    "contains",
+];
+
+/// Keywords and word operators.
+const KEYWORDS: [&str; 10] = [
+    "select", "where", "by", "group", "between", "and", "in", "is", "not", "null",
+];
+
 // Check if an identifier is a known function name
 fn is_function_name(name: &str) -> bool {
     let name_lower = name.to_lowercase();
-    matches!(
-        name_lower.as_str(),
-        "avg"
-            | "mean"
-            | "min"
-            | "max"
-            | "count"
-            | "std"
-            | "stddev"
-            | "med"
-            | "median"
-            | "sum"
-            | "first"
-            | "last"
-            | "len"
-            | "length"
-            | "not"
-            | "null"
-            | "upper"
-            | "lower"
-            | "abs"
-            | "floor"
-            | "ceil"
-            | "ceiling"
-    )
+    FUNCTION_NAMES.contains(&name_lower.as_str())
+}
+
+/// Tab completion in the query input: where the word being typed starts and what it can become.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// Char index of the word's start; completing replaces the text from here to the cursor.
+    pub start: usize,
+    pub candidates: Vec<String>,
+}
+
+impl Completion {
+    /// The longest start shared by every candidate.
+    pub fn common_prefix(&self) -> &str {
+        let Some(first) = self.candidates.first() else {
+            return "";
+        };
+        self.candidates[1..]
+            .iter()
+            .fold(first.as_str(), |prefix, c| {
+                let len = prefix
+                    .char_indices()
+                    .zip(c.chars())
+                    .find(|((_, a), b)| a != b)
+                    .map_or(prefix.len().min(c.len()), |((i, _), _)| i);
+                &prefix[..len]
+            })
+    }
+}
+
+/// Completions of the word before `cursor` (a char index) in `text`: column names (those that
+/// aren't plain words as `col["name"]`), functions and keywords, or accessors after a `.`.
+/// Matching ignores case. None when there's no word there, it's inside a string, or nothing
+/// matches.
+pub fn complete(text: &str, cursor: usize, columns: &[String]) -> Option<Completion> {
+    let chars: Vec<char> = text.chars().collect();
+    let cursor = cursor.min(chars.len());
+    let is_word = |c: &char| c.is_alphanumeric() || *c == '_';
+    let start = chars[..cursor]
+        .iter()
+        .rposition(|c| !is_word(c))
+        .map_or(0, |i| i + 1);
+    if start == cursor || chars[..start].iter().filter(|c| **c == '"').count() % 2 == 1 {
+        return None;
+    }
+    let word: String = chars[start..cursor]
+        .iter()
+        .collect::<String>()
+        .to_lowercase();
+    let matches = |name: &str| name.to_lowercase().starts_with(&word);
+    let mut candidates: Vec<String> = Vec::new();
+    let mut push = |candidate: String| {
+        if !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    };
+    if start > 0 && chars[start - 1] == '.' {
+        let rolling = ROLLING_FUNCTIONS.iter().map(|f| format!("rolling_{f}"));
+        for name in ACCESSOR_NAMES.iter().map(|a| a.to_string()).chain(rolling) {
+            if matches(&name) {
+                push(name);
+            }
+        }
+    } else {
+        for name in columns.iter().filter(|name| matches(name)) {
+            let plain = name
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '_')
+                && name.chars().all(|c| is_word(&c));
+            if plain {
+                push(name.clone());
+            } else {
+                push(format!("col[\"{}\"]", name.replace('"', "\\\"")));
+            }
+        }
+        for name in FUNCTION_NAMES.iter().chain(KEYWORDS.iter()) {
+            if matches(name) {
+                push(name.to_string());
+            }
+        }
+    }
+    (!candidates.is_empty()).then_some(Completion { start, candidates })
 }
 
 fn parse_term(tokens: &[Token]) -> Result<(Expr, &[Token]), String> {
@@ -525,16 +823,24 @@ fn parse_term(tokens: &[Token]) -> Result<(Expr, &[Token]), String> {
     }
     match &tokens[0] {
         Token::Identifier(name) => {
-            // Check if it's col[...] syntax for column names with spaces
-            if name == "col" && tokens.len() > 1 && tokens[1] == Token::LBracket {
+            // Check if it's col[...] syntax for column names with spaces (or Polars-style col("..."))
+            if name == "col"
+                && tokens.len() > 1
+                && (tokens[1] == Token::LBracket || tokens[1] == Token::LParen)
+            {
+                let (open, close) = if tokens[1] == Token::LParen {
+                    (Token::LParen, Token::RParen)
+                } else {
+                    (Token::LBracket, Token::RBracket)
+                };
                 // Find matching closing bracket
                 let mut depth = 1;
                 let mut i = 2;
                 while i < tokens.len() && depth > 0 {
-                    match tokens[i] {
-                        Token::LBracket => depth += 1,
-                        Token::RBracket => depth -= 1,
-                        _ => {}
+                    if tokens[i] == open {
+                        depth += 1;
+                    } else if tokens[i] == close {
+                        depth -= 1;
                     }
                     i += 1;
                 }
@@ -683,6 +989,11 @@ fn parse_expr(tokens: &[Token]) -> Result<Expr, String> {
         }
     }
 
+    // Predicates bind loosest: `a + 1 between 2 and 5` tests a + 1
+    if let Some(expr) = parse_predicate(tokens)? {
+        return Ok(expr);
+    }
+
     // Find the leftmost operator for right-to-left evaluation
     let mut op_pos = None;
     let mut depth = 0;
@@ -783,6 +1094,72 @@ pub fn sanitize_query_error(msg: &str) -> String {
     msg.to_string()
 }
 
+/// Parse a single expression, as used for a computed column: `price * qty`,
+/// `col("ts").dt.hour()`, `upper[name]`, ... Query keywords (`select`, `where`, `by`) aren't
+/// allowed.
+pub fn parse_column_expression(text: &str) -> Result<Expr, String> {
+    let tokens = tokenize(text)?;
+    if tokens.is_empty() {
+        return Err("Expression is empty".to_string());
+    }
+    if tokens
+        .iter()
+        .any(|t| matches!(t, Token::Select | Token::Where | Token::By))
+    {
+        return Err("Expected an expression, not a query".to_string());
+    }
+    parse_expr(&tokens)
+}
+
+/// `lf` with a parsed query applied: the `where` filter, then the `by` groups aggregating the
+/// selected columns (every other column when none are selected) sorted by their keys, or else
+/// the selection. Returns the frame with its schema.
+pub fn apply_query(
+    lf: LazyFrame,
+    cols: Vec<Expr>,
+    filter: Option<Expr>,
+    group_by_cols: Vec<Expr>,
+    group_by_col_names: &[String],
+) -> PolarsResult<(LazyFrame, Arc<Schema>)> {
+    let mut lf = lf;
+    // Apply filter first (where clause)
+    if let Some(f) = filter {
+        lf = lf.filter(f);
+    }
+
+    if !group_by_cols.is_empty() {
+        if !cols.is_empty() {
+            lf = lf.group_by(group_by_cols.clone()).agg(cols);
+        } else {
+            let schema = lf.clone().collect_schema()?;
+            // In Polars, when you group_by and aggregate columns without explicit aggregation functions,
+            // Polars automatically collects the values as lists. We need to aggregate all columns
+            // except the group columns to avoid duplicates.
+            let agg_exprs: Vec<Expr> = schema
+                .iter_names()
+                .filter(|name| !group_by_col_names.iter().any(|g| g == name.as_str()))
+                .map(|name| col(name.as_str()))
+                .collect();
+            lf = lf.group_by(group_by_cols.clone()).agg(agg_exprs);
+        }
+        // Sort by the result's group-key column names (first N columns after agg).
+        // Works for aliased or plain names without relying on parser-derived names.
+        let schema = lf.collect_schema()?;
+        let sort_exprs: Vec<Expr> = schema
+            .iter_names()
+            .take(group_by_cols.len())
+            .map(|n| col(n.as_str()))
+            .collect();
+        lf = lf.sort_by_exprs(sort_exprs, Default::default());
+        return Ok((lf, schema));
+    }
+    if !cols.is_empty() {
+        lf = lf.select(cols);
+    }
+    let schema = lf.collect_schema()?;
+    Ok((lf, schema))
+}
+
 pub fn parse_query(query: &str) -> ParseQueryResult {
     // Empty query is equivalent to "select" - return all columns with no filter or grouping
     let trimmed = query.trim();
@@ -797,12 +1174,25 @@ pub fn parse_query(query: &str) -> ParseQueryResult {
 
     // Split by "where" first
     let mut parts = split_tokens(&tokens[1..], &Token::Where);
-    let select_by_tokens = parts.remove(0);
-    let where_tokens = if !parts.is_empty() {
+    let mut select_by_tokens = parts.remove(0);
+    let mut where_tokens = if !parts.is_empty() {
         Some(parts.remove(0))
     } else {
         None
     };
+    // SQL order: `select ... where ... group by ...`
+    if let Some(wt) = where_tokens.take() {
+        let mut where_by = split_tokens(&wt, &Token::By);
+        let conditions = where_by.remove(0);
+        if let Some(by) = where_by.into_iter().next() {
+            if split_tokens(&select_by_tokens, &Token::By).len() > 1 {
+                return Err("Query has two 'by' clauses".to_string());
+            }
+            select_by_tokens.push(Token::By);
+            select_by_tokens.extend(by);
+        }
+        where_tokens = Some(conditions);
+    }
 
     // Split select/by part
     let mut select_by_parts = split_tokens(&select_by_tokens, &Token::By);
@@ -1586,4 +1976,170 @@ mod tests {
             col("order_date").dt().year().alias("order_date_year")
         );
     }
+
+    #[test]
+    fn test_parse_column_expression_accepts_polars_style() {
+        assert_eq!(
+            parse_column_expression("price * qty").unwrap(),
+            col("price").mul(col("qty"))
+        );
+        assert_eq!(
+            parse_column_expression("col(\"ts\").dt.hour()").unwrap(),
+            col("ts").dt().hour().alias("ts_hour")
+        );
+        assert_eq!(
+            parse_column_expression("col[\"unit price\"] / 2").unwrap(),
+            col("unit price").div(lit(2.0))
+        );
+        assert!(parse_column_expression("name.str.contains(\"x\")").is_ok());
+        assert!(parse_column_expression("").is_err());
+        assert!(parse_column_expression("select a where b > 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_column_expression_rolling_windows() {
+        let df = df!(
+            "ts" => [0i64, 1, 2, 10],
+            "x" => [1.0f64, 2.0, 3.0, 4.0],
+        )
+        .unwrap();
+        let eval = |text: &str| -> Vec<Option<f64>> {
+            let out = df
+                .clone()
+                .lazy()
+                .select([parse_column_expression(text).unwrap().alias("out")])
+                .collect()
+                .unwrap();
+            out.column("out")
+                .unwrap()
+                .f64()
+                .unwrap()
+                .into_iter()
+                .collect()
+        };
+        assert_eq!(
+            eval("x.rolling_sum(2)"),
+            [Some(1.0), Some(3.0), Some(5.0), Some(7.0)]
+        );
+        assert_eq!(
+            eval("col(\"x\").rolling_max(3)"),
+            [Some(1.0), Some(2.0), Some(3.0), Some(4.0)]
+        );
+        let df = df
+            .lazy()
+            .with_column(col("ts").cast(DataType::Datetime(TimeUnit::Milliseconds, None)))
+            .collect()
+            .unwrap();
+        let out = df
+            .lazy()
+            .select([parse_column_expression("x.rolling_mean(\"5ms\", ts)")
+                .unwrap()
+                .alias("out")])
+            .collect()
+            .unwrap();
+        let values: Vec<Option<f64>> = out
+            .column("out")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(values, [Some(1.0), Some(1.5), Some(2.0), Some(4.0)]);
+        assert!(parse_column_expression("x.rolling_mean(0)").is_err());
+        assert!(parse_column_expression("x.rolling_mean(\"soon\", ts)").is_err());
+        assert!(parse_column_expression("x.rolling_mean()").is_err());
+    }
+
+    #[test]
+    fn test_group_by_aggregates() {
+        let df = df!(
+            "region" => ["n", "s", "n", "s", "n"],
+            "product" => ["a", "a", "b", "a", "a"],
+            "amount" => [10.0, 20.0, 30.0, 40.0, 50.0],
+        )
+        .unwrap();
+        let run = |query: &str| {
+            let (cols, filter, by, by_names) = parse_query(query).unwrap();
+            let (lf, _) = apply_query(df.clone().lazy(), cols, filter, by, &by_names).unwrap();
+            lf.collect().unwrap()
+        };
+        let out = run(
+            "select rows: count[], total: sum[amount], products: n_unique[product] group by region",
+        );
+        assert_eq!(
+            out.get_column_names(),
+            ["region", "rows", "total", "products"]
+        );
+        assert_eq!(out.column("rows").unwrap().u32().unwrap().get(0), Some(3));
+        assert_eq!(
+            out.column("total").unwrap().f64().unwrap().get(1),
+            Some(60.0)
+        );
+        assert_eq!(
+            out.column("products").unwrap().u32().unwrap().get(0),
+            Some(2)
+        );
+
+        // SQL order, with the filter before the grouping
+        let out = run("select count[], med: median[amount] where amount > 10 group by region");
+        assert_eq!(out.column("count").unwrap().u32().unwrap().get(0), Some(2));
+        assert_eq!(out.column("med").unwrap().f64().unwrap().get(0), Some(40.0));
+        assert!(parse_query("select count[] by region where amount > 1 group by product").is_err());
+
+        // `group` is still a column name where a column is expected
+        let tokens = tokenize("select group by region").unwrap();
+        assert_eq!(tokens[1], Token::Identifier("group".to_string()));
+    }
+
+    #[test]
+    fn test_between_in_and_null_predicates() {
+        let df = df!(
+            "x" => [Some(1i64), Some(5), None, Some(10)],
+            "city" => ["a", "b", "c", "a"],
+        )
+        .unwrap();
+        let rows = |query: &str| {
+            let (_, filter, _, _) = parse_query(query).unwrap();
+            df.clone()
+                .lazy()
+                .filter(filter.unwrap())
+                .collect()
+                .unwrap()
+                .height()
+        };
+        assert_eq!(rows("select where x between 2 and 10"), 2);
+        assert_eq!(rows("select where x + 1 between 2 and 6"), 2);
+        assert_eq!(rows("select where city in (\"a\", \"c\")"), 3);
+        assert_eq!(rows("select where not city in (\"a\"), x is not null"), 1);
+        assert_eq!(rows("select where x is null"), 1);
+        assert_eq!(rows("select where x is null | x in (10)"), 2);
+        assert!(parse_query("select where x between 2").is_err());
+        assert!(parse_query("select where x in ()").is_err());
+        assert!(parse_query("select where x is empty").is_err());
+    }
+
+    #[test]
+    fn test_complete_columns_functions_and_accessors() {
+        let columns = vec![
+            "price".to_string(),
+            "Product".to_string(),
+            "unit price".to_string(),
+        ];
+        let c = complete("select pr", 9, &columns).unwrap();
+        assert_eq!(c.start, 7);
+        assert_eq!(c.candidates, ["price", "Product"]);
+        assert_eq!(c.common_prefix(), "");
+        let c = complete("select price, av where", 16, &columns).unwrap();
+        assert_eq!(c.candidates, ["avg"]);
+        let c = complete("select un", 9, &columns).unwrap();
+        assert_eq!(c.candidates, ["col[\"unit price\"]"]);
+        let c = complete("select ts.mon", 13, &columns).unwrap();
+        assert_eq!(c.candidates, ["month", "month_start", "month_end"]);
+        assert_eq!(c.common_prefix(), "month");
+        let c = complete("select x where x be", 19, &columns).unwrap();
+        assert_eq!(c.candidates, ["between"]);
+        assert!(complete("select ", 7, &columns).is_none());
+        assert!(complete("select where name = \"pr", 23, &columns).is_none());
+        assert!(complete("select zz", 9, &columns).is_none());
+    }
 }
@@ -0,0 +1,116 @@
+//! One row as a pretty-printed JSON object, for pasting a single record into a bug report.
+//!
+//! Values are encoded by polars' JSON writer (the same encoding as JSON export), so structs
+//! become nested objects, lists become arrays and temporal values are strings. Columns keep
+//! their table order.
+
+use polars::prelude::*;
+
+/// The first row of `df` as an indented JSON object (`{}` when `df` is empty).
+pub fn row_to_json(df: &mut DataFrame) -> PolarsResult<String> {
+    let mut row = df.head(Some(1));
+    if row.height() == 0 {
+        return Ok("{}".to_string());
+    }
+    let mut bytes = Vec::new();
+    JsonWriter::new(&mut bytes)
+        .with_json_format(JsonFormat::JsonLines)
+        .finish(&mut row)?;
+    Ok(pretty(String::from_utf8_lossy(&bytes).trim()))
+}
+
+/// Re-indent compact JSON text with two spaces per level. Key order and values are kept as
+/// written; empty objects and arrays stay on one line.
+pub fn pretty(json: &str) -> String {
+    let mut out = String::with_capacity(json.len() * 2);
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = json.chars().peekable();
+    let newline = |out: &mut String, depth: usize| {
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    };
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '{' | '[' => {
+                out.push(c);
+                let close = if c == '{' { '}' } else { ']' };
+                if chars.peek() == Some(&close) {
+                    out.push(close);
+                    chars.next();
+                } else {
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut out, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pretty_keeps_order_and_strings() {
+        assert_eq!(
+            pretty(r#"{"b":1,"a":{"x":[1,2],"e":[],"s":"{a, b}: \"q\""}}"#),
+            "{\n  \"b\": 1,\n  \"a\": {\n    \"x\": [\n      1,\n      2\n    ],\n    \"e\": [],\n    \"s\": \"{a, b}: \\\"q\\\"\"\n  }\n}"
+        );
+    }
+
+    #[test]
+    fn row_to_json_nests_structs_and_lists() {
+        let point = StructChunked::from_series(
+            "point".into(),
+            1,
+            [
+                Series::new("x".into(), [1i64]),
+                Series::new("y".into(), [2i64]),
+            ]
+            .iter(),
+        )
+        .unwrap()
+        .into_series();
+        let mut df = DataFrame::new(vec![
+            Series::new("name".into(), ["a"]).into(),
+            point.into(),
+            Series::new("tags".into(), [Series::new("".into(), ["p", "q"])]).into(),
+        ])
+        .unwrap();
+        assert_eq!(
+            row_to_json(&mut df).unwrap(),
+            "{\n  \"name\": \"a\",\n  \"point\": {\n    \"x\": 1,\n    \"y\": 2\n  },\n  \"tags\": [\n    \"p\",\n    \"q\"\n  ]\n}"
+        );
+        assert_eq!(row_to_json(&mut df.head(Some(0))).unwrap(), "{}");
+    }
+}
@@ -0,0 +1,260 @@
+//! Record view: one row shown vertically as `field: value` lines, for tables too wide to read
+//! across and for cells holding long text or nested data. Struct fields and list items are
+//! listed beneath their column, indented one level per nesting depth.
+
+use polars::prelude::*;
+
+/// Items listed per list value; the rest are summarised on one line.
+pub const MAX_LIST_ITEMS: usize = 100;
+
+/// What a record line shows after its label.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordValue {
+    /// A plain value (null included), formatted when drawn.
+    Scalar(AnyValue<'static>),
+    /// A struct with this many fields, listed on the following lines.
+    Struct(usize),
+    /// A list with this many items, listed on the following lines.
+    List(usize),
+    /// This many list items were left out.
+    More(usize),
+}
+
+impl RecordValue {
+    /// What a struct, list or left-out items line shows; None for a plain value.
+    pub fn summary(&self) -> Option<String> {
+        match self {
+            Self::Scalar(_) => None,
+            Self::Struct(n) => Some(format!("{{{n} field{}}}", if *n == 1 { "" } else { "s" })),
+            Self::List(n) => Some(format!("[{n} item{}]", if *n == 1 { "" } else { "s" })),
+            Self::More(n) => Some(format!("… {n} more")),
+        }
+    }
+}
+
+/// One line of the record view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordEntry {
+    /// Nesting level: 0 for the row's columns.
+    pub depth: usize,
+    /// Column or struct field name, or `[i]` for a list item.
+    pub label: String,
+    pub value: RecordValue,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordView {
+    /// Row number in the view (0-based).
+    pub row: usize,
+    pub entries: Vec<RecordEntry>,
+    /// First wrapped line shown.
+    pub scroll: usize,
+    /// Wrapped lines that fit in the popup, as of the last render.
+    pub visible: usize,
+    /// Wrapped lines in total, as of the last render.
+    pub lines: usize,
+}
+
+impl RecordView {
+    /// The record view of the first row of `df` (row `row` of the view), columns in `df` order.
+    pub fn from_frame(df: &DataFrame, row: usize) -> PolarsResult<Self> {
+        let mut entries = Vec::new();
+        if df.height() > 0 {
+            for column in df.get_columns() {
+                push_entries(
+                    &mut entries,
+                    0,
+                    column.name().to_string(),
+                    column.as_materialized_series(),
+                    0,
+                )?;
+            }
+        }
+        Ok(Self {
+            row,
+            entries,
+            scroll: 0,
+            visible: 1,
+            lines: 0,
+        })
+    }
+
+    /// Scroll by `delta` lines, stopping when the last line is on screen.
+    pub fn scroll_by(&mut self, delta: isize) {
+        let max = self.lines.saturating_sub(self.visible.max(1));
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+}
+
+/// The fields or items of the first value of `series`, one `label: value` line each and
+/// indented by nesting depth, for showing a nested cell in full. None when the value isn't a
+/// struct or list.
+pub fn nested_text(series: &Series) -> PolarsResult<Option<String>> {
+    let mut entries = Vec::new();
+    push_entries(&mut entries, 0, String::new(), series, 0)?;
+    if !matches!(
+        entries.first().map(|e| &e.value),
+        Some(RecordValue::Struct(_) | RecordValue::List(_))
+    ) {
+        return Ok(None);
+    }
+    let lines: Vec<String> = entries[1..]
+        .iter()
+        .map(|e| {
+            let value = match &e.value {
+                RecordValue::Scalar(v) if v.is_null() => "null".to_string(),
+                RecordValue::Scalar(v) => v.str_value().into_owned(),
+                other => other.summary().unwrap_or_default(),
+            };
+            let indent = "  ".repeat(e.depth - 1);
+            if e.label.is_empty() {
+                format!("{indent}{value}")
+            } else {
+                format!("{indent}{}: {value}", e.label)
+            }
+        })
+        .collect();
+    Ok(Some(lines.join("\n")))
+}
+
+/// Add the line for value `index` of `series`, then the lines of its fields or items.
+fn push_entries(
+    entries: &mut Vec<RecordEntry>,
+    depth: usize,
+    label: String,
+    series: &Series,
+    index: usize,
+) -> PolarsResult<()> {
+    let value = series.get(index)?;
+    if value.is_null() {
+        entries.push(RecordEntry {
+            depth,
+            label,
+            value: RecordValue::Scalar(AnyValue::Null),
+        });
+        return Ok(());
+    }
+    match series.dtype() {
+        DataType::Struct(_) => {
+            let fields = series.struct_()?.fields_as_series();
+            entries.push(RecordEntry {
+                depth,
+                label,
+                value: RecordValue::Struct(fields.len()),
+            });
+            for field in &fields {
+                push_entries(entries, depth + 1, field.name().to_string(), field, index)?;
+            }
+        }
+        DataType::List(_) => {
+            let items = series
+                .list()?
+                .get_as_series(index)
+                .unwrap_or_else(|| Series::new_empty(PlSmallStr::EMPTY, &DataType::Null));
+            entries.push(RecordEntry {
+                depth,
+                label,
+                value: RecordValue::List(items.len()),
+            });
+            for item in 0..items.len().min(MAX_LIST_ITEMS) {
+                push_entries(entries, depth + 1, format!("[{item}]"), &items, item)?;
+            }
+            if items.len() > MAX_LIST_ITEMS {
+                entries.push(RecordEntry {
+                    depth: depth + 1,
+                    label: String::new(),
+                    value: RecordValue::More(items.len() - MAX_LIST_ITEMS),
+                });
+            }
+        }
+        _ => entries.push(RecordEntry {
+            depth,
+            label,
+            value: RecordValue::Scalar(value.into_static()),
+        }),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_values_are_listed_beneath_their_column() {
+        let point = StructChunked::from_series(
+            "point".into(),
+            1,
+            [
+                Series::new("x".into(), [1i64]),
+                Series::new("y".into(), [2i64]),
+            ]
+            .iter(),
+        )
+        .unwrap()
+        .into_series();
+        let tags = Series::new("tags".into(), [Series::new(PlSmallStr::EMPTY, ["a", "b"])]);
+        let df = DataFrame::new(vec![
+            Series::new("name".into(), ["n"]).into(),
+            point.into(),
+            tags.into(),
+            Series::new("note".into(), [None::<&str>]).into(),
+        ])
+        .unwrap();
+        let view = RecordView::from_frame(&df, 7).unwrap();
+        assert_eq!(view.row, 7);
+        let lines: Vec<(usize, &str, &RecordValue)> = view
+            .entries
+            .iter()
+            .map(|e| (e.depth, e.label.as_str(), &e.value))
+            .collect();
+        let text = |s: &'static str| RecordValue::Scalar(AnyValue::String(s));
+        assert_eq!(
+            lines,
+            vec![
+                (0, "name", &text("n")),
+                (0, "point", &RecordValue::Struct(2)),
+                (1, "x", &RecordValue::Scalar(AnyValue::Int64(1))),
+                (1, "y", &RecordValue::Scalar(AnyValue::Int64(2))),
+                (0, "tags", &RecordValue::List(2)),
+                (1, "[0]", &text("a")),
+                (1, "[1]", &text("b")),
+                (0, "note", &RecordValue::Scalar(AnyValue::Null)),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_cells_are_shown_field_by_field() {
+        let point = StructChunked::from_series(
+            "point".into(),
+            1,
+            [
+                Series::new("x".into(), [1i64]),
+                Series::new("tags".into(), [Series::new(PlSmallStr::EMPTY, ["a"])]),
+            ]
+            .iter(),
+        )
+        .unwrap()
+        .into_series();
+        assert_eq!(
+            nested_text(&point).unwrap().as_deref(),
+            Some("x: 1\ntags: [1 item]\n  [0]: a")
+        );
+        assert_eq!(nested_text(&Series::new("n".into(), [1i64])).unwrap(), None);
+    }
+
+    #[test]
+    fn long_lists_are_cut_short() {
+        let items: Vec<i32> = (0..MAX_LIST_ITEMS as i32 + 5).collect();
+        let df = DataFrame::new(vec![Series::new(
+            "ids".into(),
+            [Series::new(PlSmallStr::EMPTY, items)],
+        )
+        .into()])
+        .unwrap();
+        let view = RecordView::from_frame(&df, 0).unwrap();
+        assert_eq!(view.entries.len(), MAX_LIST_ITEMS + 2);
+        assert_eq!(view.entries.last().unwrap().value, RecordValue::More(5));
+    }
+}
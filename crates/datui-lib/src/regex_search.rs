@@ -0,0 +1,345 @@
+//! Regex search: highlights the cells of the current view that match a regular expression and
+//! jumps between matching rows with `n` / `N`. The search covers every column (Regex tab) or
+//! only the selected one (Column tab).
+//!
+//! Unlike the Fuzzy tab, the search doesn't filter the view. Matching rows are found with a
+//! lazy scan over the whole view (not just the buffered rows), so jumps can land on any page;
+//! the scan is redone when the view changes (query, filter, sort, ...). Large views, and views
+//! whose row count isn't known yet, are scanned in slices so progress and the matches found so
+//! far can be reported.
+
+use crate::statistics::collect_lazy;
+use polars::prelude::*;
+use regex::Regex;
+
+/// Row index column added for the scan; removed from the result.
+const ROW_INDEX_COLUMN: &str = "__datui_search_row";
+
+/// Views with more rows than this are scanned in slices, reporting progress after each.
+const SCAN_SLICE_ROWS: usize = 100_000;
+
+/// Most slices a scan is split into (larger views get larger slices).
+const MAX_SCAN_SLICES: usize = 20;
+
+/// Progress of a scan, reported after each slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanProgress {
+    /// Rows of the view scanned so far.
+    pub scanned: usize,
+    /// Rows of the view, when known.
+    pub total: Option<usize>,
+    /// Matching rows found so far.
+    pub matches: usize,
+}
+
+impl ScanProgress {
+    /// Share of the view scanned, when its row count is known.
+    pub fn percent(&self) -> Option<u16> {
+        self.total
+            .filter(|&total| total > 0)
+            .map(|total| (self.scanned.min(total) * 100 / total) as u16)
+    }
+}
+
+/// Rows of one view that match the search.
+#[derive(Debug, Clone)]
+pub struct SearchMatches {
+    /// Plan of the view that was scanned; the rows only apply while the view has this plan.
+    plan: String,
+    /// Matching rows (0-based, in the view), ascending.
+    pub rows: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RegexSearch {
+    pub pattern: String,
+    pub regex: Regex,
+    /// Column the search is limited to; None searches every column.
+    pub column: Option<String>,
+    pub matches: Option<SearchMatches>,
+    /// Position in the matching rows of the last jump.
+    pub current: Option<usize>,
+}
+
+impl RegexSearch {
+    /// Compile `pattern`, returning a message when it isn't a valid regular expression.
+    pub fn new(pattern: &str, column: Option<String>) -> Result<Self, String> {
+        let regex =
+            Regex::new(pattern).map_err(|e| format!("Invalid regular expression: {}", e))?;
+        Ok(Self {
+            pattern: pattern.to_string(),
+            regex,
+            column,
+            matches: None,
+            current: None,
+        })
+    }
+
+    /// Whether a cell matches. Values are matched as text, as the scan casts them; null never
+    /// matches.
+    pub fn is_match(&self, value: &AnyValue) -> bool {
+        !value.is_null() && self.regex.is_match(&value.str_value())
+    }
+
+    /// Matching rows of the view whose plan is `plan`, or None when it hasn't been scanned.
+    pub fn rows_for(&self, plan: &str) -> Option<&[usize]> {
+        self.matches
+            .as_ref()
+            .filter(|m| m.plan == plan)
+            .map(|m| m.rows.as_slice())
+    }
+
+    pub fn set_matches(&mut self, plan: String, rows: Vec<usize>) {
+        self.matches = Some(SearchMatches { plan, rows });
+        self.current = None;
+    }
+
+    /// Short status for the control bar, e.g. "/ab+/ 3 of 12" or "/ab+/ in name 3 of 12".
+    pub fn status(&self, plan: &str) -> String {
+        let search = match &self.column {
+            Some(column) => format!("/{}/ in {}", self.pattern, column),
+            None => format!("/{}/", self.pattern),
+        };
+        match (self.rows_for(plan), self.current) {
+            (None, _) => search,
+            (Some([]), _) => format!("{} no matches", search),
+            (Some(rows), Some(pos)) => format!("{} {} of {}", search, pos + 1, rows.len()),
+            (Some(rows), None) => format!("{} {} matches", search, rows.len()),
+        }
+    }
+}
+
+/// Plan of `lf`, used to tell whether scanned matches still apply to the view.
+pub fn view_plan(lf: &LazyFrame) -> String {
+    lf.describe_plan().unwrap_or_default()
+}
+
+/// Whether cells of `dtype` are searched: text, numbers, booleans and dates/times (as text).
+pub fn is_searchable(dtype: &DataType) -> bool {
+    dtype.is_string()
+        || dtype.is_primitive_numeric()
+        || dtype.is_bool()
+        || dtype.is_categorical()
+        || matches!(
+            dtype,
+            DataType::Date | DataType::Datetime(_, _) | DataType::Time | DataType::Duration(_)
+        )
+}
+
+/// Rows (0-based, ascending) of `lf` where a searchable column (or just `column`) matches
+/// `pattern`. When the view's row count is large or not known, it is scanned in slices and
+/// `progress` is called after each.
+pub fn find_matching_rows(
+    lf: &LazyFrame,
+    pattern: &str,
+    column: Option<&str>,
+    total_rows: Option<usize>,
+    polars_streaming: bool,
+    mut progress: impl FnMut(ScanProgress),
+) -> PolarsResult<Vec<usize>> {
+    let schema = lf.clone().collect_schema()?;
+    if let Some(column) = column {
+        let dtype = schema.try_get(column)?;
+        if !is_searchable(dtype) {
+            polars_bail!(InvalidOperation: "Column {} ({}) can't be searched", column, dtype);
+        }
+    }
+    let condition = schema
+        .iter()
+        .filter(|(name, dtype)| column.is_none_or(|c| c == name.as_str()) && is_searchable(dtype))
+        .map(|(name, dtype)| {
+            let text = if dtype.is_string() {
+                col(name.clone())
+            } else {
+                col(name.clone()).cast(DataType::String)
+            };
+            text.str().contains(lit(pattern), true)
+        })
+        .reduce(|a, b| a.or(b));
+    let Some(condition) = condition else {
+        return Ok(Vec::new());
+    };
+    let indexed = lf.clone().with_row_index(ROW_INDEX_COLUMN, None);
+    let scan = |slice: LazyFrame| -> PolarsResult<Vec<usize>> {
+        let df = collect_lazy(
+            slice
+                .filter(condition.clone())
+                .select([col(ROW_INDEX_COLUMN)]),
+            polars_streaming,
+        )?;
+        Ok(df
+            .column(ROW_INDEX_COLUMN)?
+            .idx()?
+            .into_no_null_iter()
+            .map(|i| i as usize)
+            .collect())
+    };
+    let total = match total_rows {
+        Some(total) if total > SCAN_SLICE_ROWS => total,
+        Some(_) => return scan(indexed),
+        None => return scan_unknown_length(&indexed, &condition, polars_streaming, progress),
+    };
+    let slice_rows = SCAN_SLICE_ROWS.max(total.div_ceil(MAX_SCAN_SLICES));
+    let mut rows = Vec::new();
+    for offset in (0..total).step_by(slice_rows) {
+        rows.extend(scan(
+            indexed.clone().slice(offset as i64, slice_rows as IdxSize),
+        )?);
+        progress(ScanProgress {
+            scanned: (offset + slice_rows).min(total),
+            total: Some(total),
+            matches: rows.len(),
+        });
+    }
+    Ok(rows)
+}
+
+/// Scan `indexed` (the view with its row index) slice by slice until a slice comes back short,
+/// for views whose row count isn't known. Each slice returns its matching rows and its length.
+fn scan_unknown_length(
+    indexed: &LazyFrame,
+    condition: &Expr,
+    polars_streaming: bool,
+    mut progress: impl FnMut(ScanProgress),
+) -> PolarsResult<Vec<usize>> {
+    const SLICE_LENGTH_COLUMN: &str = "__datui_search_slice_rows";
+    let mut rows = Vec::new();
+    let mut offset = 0;
+    loop {
+        let df = collect_lazy(
+            indexed
+                .clone()
+                .slice(offset as i64, SCAN_SLICE_ROWS as IdxSize)
+                .select([
+                    col(ROW_INDEX_COLUMN)
+                        .filter(condition.clone())
+                        .implode()
+                        .alias(ROW_INDEX_COLUMN),
+                    len().alias(SLICE_LENGTH_COLUMN),
+                ]),
+            polars_streaming,
+        )?;
+        let matched = df.column(ROW_INDEX_COLUMN)?.list()?.get_as_series(0);
+        if let Some(matched) = matched {
+            rows.extend(matched.idx()?.into_no_null_iter().map(|i| i as usize));
+        }
+        let slice_len = df
+            .column(SLICE_LENGTH_COLUMN)?
+            .get(0)?
+            .extract::<usize>()
+            .unwrap_or(0);
+        offset += slice_len;
+        progress(ScanProgress {
+            scanned: offset,
+            total: None,
+            matches: rows.len(),
+        });
+        if slice_len < SCAN_SLICE_ROWS {
+            return Ok(rows);
+        }
+    }
+}
+
+/// The match to jump to from `row`: the first matching row after it (`forward`) or the last one
+/// before it, wrapping around the view. Returns its position in `rows` and the row.
+pub fn next_match(rows: &[usize], row: usize, forward: bool) -> Option<(usize, usize)> {
+    if rows.is_empty() {
+        return None;
+    }
+    let pos = if forward {
+        let after = rows.partition_point(|&r| r <= row);
+        if after == rows.len() {
+            0
+        } else {
+            after
+        }
+    } else {
+        let before = rows.partition_point(|&r| r < row);
+        if before == 0 {
+            rows.len() - 1
+        } else {
+            before - 1
+        }
+    };
+    Some((pos, rows[pos]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_finds_rows_matching_in_any_column() {
+        let lf = df!(
+            "name" => ["alice", "bob", "carol", "dave"],
+            "code" => [10i64, 205, 33, 420],
+        )
+        .unwrap()
+        .lazy();
+        let find = |pattern| find_matching_rows(&lf, pattern, None, None, false, |_| {}).unwrap();
+        assert_eq!(find("^b|^c"), [1, 2]);
+        assert_eq!(find("20"), [1, 3]);
+        assert!(find("zzz").is_empty());
+
+        let mut search = RegexSearch::new("o", None).unwrap();
+        assert!(search.is_match(&AnyValue::String("bob")));
+        assert!(!search.is_match(&AnyValue::Null));
+        assert!(RegexSearch::new("(", None).is_err());
+
+        let plan = view_plan(&lf);
+        assert_eq!(search.status(&plan), "/o/");
+        search.set_matches(plan.clone(), find("o"));
+        assert_eq!(search.status(&plan), "/o/ 2 matches");
+        search.current = Some(1);
+        assert_eq!(search.status(&plan), "/o/ 2 of 2");
+        // Matches of another view don't apply
+        assert_eq!(search.status(&view_plan(&lf.clone().limit(2))), "/o/");
+    }
+
+    #[test]
+    fn column_scan_only_searches_that_column_in_slices() {
+        let n = 250_000;
+        let lf = df!(
+            "id" => (0..n as i64).collect::<Vec<_>>(),
+            "tag" => (0..n).map(|i| if i % 100_000 == 7 { "x7" } else { "-" }).collect::<Vec<_>>(),
+        )
+        .unwrap()
+        .lazy();
+        let mut reported = Vec::new();
+        let rows = find_matching_rows(&lf, "7", Some("tag"), Some(n), false, |p| {
+            reported.push((p.percent(), p.matches))
+        })
+        .unwrap();
+        assert_eq!(rows, [7, 100_007, 200_007]);
+        assert_eq!(reported, [(Some(40), 1), (Some(80), 2), (Some(100), 3)]);
+
+        // Without a row count the view is still scanned in slices, to its end
+        let mut reported = Vec::new();
+        let rows = find_matching_rows(&lf, "7", Some("tag"), None, false, |p| {
+            reported.push((p.scanned, p.percent(), p.matches))
+        })
+        .unwrap();
+        assert_eq!(rows, [7, 100_007, 200_007]);
+        assert_eq!(
+            reported,
+            [(100_000, None, 1), (200_000, None, 2), (250_000, None, 3)]
+        );
+
+        let err = find_matching_rows(&lf, "7", Some("missing"), None, false, |_| {});
+        assert!(err.is_err());
+
+        let search = RegexSearch::new("7", Some("tag".into())).unwrap();
+        assert_eq!(search.status(&view_plan(&lf)), "/7/ in tag");
+    }
+
+    #[test]
+    fn next_match_wraps_around() {
+        let rows = [2, 5, 9];
+        assert_eq!(next_match(&rows, 0, true), Some((0, 2)));
+        assert_eq!(next_match(&rows, 5, true), Some((2, 9)));
+        assert_eq!(next_match(&rows, 9, true), Some((0, 2)));
+        assert_eq!(next_match(&rows, 5, false), Some((0, 2)));
+        assert_eq!(next_match(&rows, 2, false), Some((2, 9)));
+        assert_eq!(next_match(&[], 2, false), None);
+    }
+}
@@ -68,7 +68,15 @@ pub fn render(
             Some(analysis_modal::AnalysisTool::CorrelationMatrix) => {
                 app.analysis_modal.correlation_column_offset
             }
-            None => 0,
+            Some(analysis_modal::AnalysisTool::Crosstab) => {
+                app.analysis_modal.crosstab_column_offset
+            }
+            Some(analysis_modal::AnalysisTool::TimeSeriesGaps)
+            | Some(analysis_modal::AnalysisTool::ColumnCardinality)
+            | Some(analysis_modal::AnalysisTool::MissingData)
+            | Some(analysis_modal::AnalysisTool::DuplicateRows)
+            | Some(analysis_modal::AnalysisTool::Plugin(_))
+            | None => 0,
         };
 
         let results_for_widget = app.analysis_modal.current_results().cloned();
@@ -83,8 +91,61 @@ pub fn render(
             focus: app.analysis_modal.focus,
             selected_theoretical_distribution: app.analysis_modal.selected_theoretical_distribution,
             histogram_scale: app.analysis_modal.histogram_scale,
+            show_best_fit: app.analysis_modal.histogram_best_fit,
             theme: &app.theme,
             table_cell_padding: app.table_cell_padding,
+            correlation: analysis::CorrelationView {
+                heatmap: app.analysis_modal.correlation_heatmap,
+                threshold: app.analysis_modal.correlation_threshold,
+            },
+            gaps: analysis::TimeGapsView {
+                results: app.analysis_modal.gap_results.as_ref(),
+                time_column: app.analysis_modal.gap_time_column.as_deref(),
+                frequency_input: &app.analysis_modal.gap_frequency_input,
+                frequency_editing: app.analysis_modal.gap_frequency_editing,
+            },
+            cardinality: app.analysis_modal.cardinality_results.as_ref(),
+            missing: app.analysis_modal.missing_results.as_ref(),
+            duplicates: analysis::DuplicatesView {
+                results: app.analysis_modal.duplicate_results.as_ref(),
+                keys: &app.analysis_modal.duplicate_keys,
+                keys_input: &app.analysis_modal.duplicate_keys_input,
+                keys_editing: app.analysis_modal.duplicate_keys_editing,
+            },
+            crosstab: analysis::CrosstabView {
+                results: app.analysis_modal.crosstab_results.as_ref(),
+                spec: app.analysis_modal.crosstab_spec.as_ref(),
+                input: &app.analysis_modal.crosstab_input,
+                editing: app.analysis_modal.crosstab_editing,
+            },
+            plugins: analysis::PluginsView {
+                tools: &app.analysis_modal.plugins,
+                results: &app.analysis_modal.plugin_results,
+            },
+            describe_baseline: app.analysis_modal.describe_baseline.as_ref().map(|b| {
+                analysis::DescribeBaselineView {
+                    label: &b.label,
+                    rows: app.analysis_modal.compare_rows(),
+                }
+            }),
+        };
+        let list_table_state = match app.analysis_modal.selected_tool {
+            Some(analysis_modal::AnalysisTool::ColumnCardinality) => {
+                &mut app.analysis_modal.cardinality_table_state
+            }
+            Some(analysis_modal::AnalysisTool::MissingData) => {
+                &mut app.analysis_modal.missing_table_state
+            }
+            Some(analysis_modal::AnalysisTool::DuplicateRows) => {
+                &mut app.analysis_modal.duplicate_table_state
+            }
+            Some(analysis_modal::AnalysisTool::Crosstab) => {
+                &mut app.analysis_modal.crosstab_table_state
+            }
+            Some(analysis_modal::AnalysisTool::Plugin(_)) => {
+                &mut app.analysis_modal.plugin_table_state
+            }
+            _ => &mut app.analysis_modal.gap_table_state,
         };
         let widget = analysis::AnalysisWidget::new(
             config,
@@ -93,8 +154,15 @@ pub fn render(
             &mut app.analysis_modal.correlation_table_state,
             &mut app.analysis_modal.sidebar_state,
             &mut app.analysis_modal.distribution_selector_state,
+            list_table_state,
         );
         widget.render(area, buf);
+        if app.chart_export_modal.active {
+            crate::render::chart_view::render_export_modal(area, buf, app, ctx);
+        }
+        if app.export_modal.active {
+            crate::render::datatable_main::render_export_modal(area, buf, app, ctx);
+        }
     } else {
         Clear.render(area, buf);
         Paragraph::new("No data available for analysis")
@@ -129,6 +197,30 @@ pub fn help_title_and_text(modal: &AnalysisModal) -> (String, String) {
                 "Correlation Matrix Help".to_string(),
                 crate::help_strings::analysis_correlation_matrix().to_string(),
             ),
+            Some(analysis_modal::AnalysisTool::TimeSeriesGaps) => (
+                "Time Series Gaps Help".to_string(),
+                crate::help_strings::analysis_time_gaps().to_string(),
+            ),
+            Some(analysis_modal::AnalysisTool::ColumnCardinality) => (
+                "Column Cardinality Help".to_string(),
+                crate::help_strings::analysis_cardinality().to_string(),
+            ),
+            Some(analysis_modal::AnalysisTool::MissingData) => (
+                "Missing Data Help".to_string(),
+                crate::help_strings::analysis_missing_data().to_string(),
+            ),
+            Some(analysis_modal::AnalysisTool::DuplicateRows) => (
+                "Duplicate Rows Help".to_string(),
+                crate::help_strings::analysis_duplicates().to_string(),
+            ),
+            Some(analysis_modal::AnalysisTool::Crosstab) => (
+                "Crosstab Help".to_string(),
+                crate::help_strings::analysis_crosstab().to_string(),
+            ),
+            Some(analysis_modal::AnalysisTool::Plugin(index)) => match modal.plugins.get(index) {
+                Some(tool) => (format!("{} Help", tool.name()), tool.help().to_string()),
+                None => ("Analysis Help".to_string(), String::new()),
+            },
             None => (
                 "Analysis Help".to_string(),
                 "Select an analysis tool from the sidebar.".to_string(),
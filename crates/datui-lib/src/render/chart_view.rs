@@ -16,40 +16,52 @@ pub fn render(
 ) {
     Clear.render(chart_area, buf);
     let mut xy_series: Option<&Vec<Vec<(f64, f64)>>> = None;
+    let mut xy_names: &[String] = &[];
     let mut x_axis_kind = chart_data::XAxisTemporalKind::Numeric;
     let mut x_bounds: Option<(f64, f64)> = None;
+    let mut band: Option<Vec<(f64, f64, f64)>> = None;
     let mut hist_data: Option<&chart_data::HistogramData> = None;
     let mut box_data: Option<&chart_data::BoxPlotData> = None;
     let mut kde_data: Option<&chart_data::KdeData> = None;
     let mut heatmap_data: Option<&chart_data::HeatmapData> = None;
+    let mut category_data: Option<&chart_data::CategoryBarData> = None;
+    let mut scatter_data: Option<&chart_data::ScatterData> = None;
 
     let row_limit_opt = app.chart_modal.row_limit;
     let row_limit = app.chart_modal.effective_row_limit();
+    let chart_lf = app.chart_source(row_limit);
     match app.chart_modal.chart_kind {
         ChartKind::XY => {
             if let Some(x_column) = app.chart_modal.effective_x_column() {
                 let x_key = x_column.to_string();
                 let y_columns = app.chart_modal.effective_y_columns();
+                let group_column = app.chart_modal.group_column.clone();
                 if !y_columns.is_empty() {
                     let use_cache = app.chart_cache.xy.as_ref().filter(|c| {
                         c.x_column == x_key
                             && c.y_columns == y_columns
+                            && c.group_column == group_column
                             && c.row_limit == row_limit_opt
                     });
                     if use_cache.is_none() {
-                        if let Some(state) = app.data_table_state.as_ref() {
-                            if let Ok(result) = chart_data::prepare_chart_data(
-                                &state.lf,
+                        if let (Some(state), Some(lf)) =
+                            (app.data_table_state.as_ref(), chart_lf.as_ref())
+                        {
+                            if let Ok(result) = chart_data::prepare_xy_chart_data(
+                                lf,
                                 &state.schema,
                                 x_column,
                                 &y_columns,
+                                group_column.as_deref(),
                                 row_limit,
                             ) {
                                 app.chart_cache.xy = Some(crate::ChartCacheXY {
                                     x_column: x_key.clone(),
                                     y_columns: y_columns.clone(),
+                                    group_column: group_column.clone(),
                                     row_limit: row_limit_opt,
                                     series: result.series,
+                                    names: result.names,
                                     series_log: None,
                                     x_axis_kind: result.x_axis_kind,
                                 });
@@ -60,6 +72,7 @@ pub fn render(
                         if let Some(cache) = app.chart_cache.xy.as_mut() {
                             if cache.x_column == x_key
                                 && cache.y_columns == y_columns
+                                && cache.group_column == group_column
                                 && cache.row_limit == row_limit_opt
                                 && cache.series_log.is_none()
                                 && cache.series.iter().any(|s| !s.is_empty())
@@ -81,9 +94,11 @@ pub fn render(
                     if let Some(cache) = app.chart_cache.xy.as_ref() {
                         if cache.x_column == x_key
                             && cache.y_columns == y_columns
+                            && cache.group_column == group_column
                             && cache.row_limit == row_limit_opt
                         {
                             x_axis_kind = cache.x_axis_kind;
+                            xy_names = &cache.names;
                             if app.chart_modal.log_scale {
                                 if let Some(ref log) = cache.series_log {
                                     if log.iter().any(|v| !v.is_empty()) {
@@ -95,6 +110,55 @@ pub fn render(
                             }
                         }
                     }
+                    if let (Some(source), Some(y_column)) =
+                        (app.chart_modal.band_source(), y_columns.first())
+                    {
+                        let matches = |c: &crate::ChartCacheBand| {
+                            c.x_column == x_key
+                                && &c.y_column == y_column
+                                && c.source == source
+                                && c.row_limit == row_limit_opt
+                        };
+                        if !app.chart_cache.band.as_ref().is_some_and(matches) {
+                            if let (Some(state), Some(lf)) =
+                                (app.data_table_state.as_ref(), chart_lf.as_ref())
+                            {
+                                if let Ok(points) = chart_data::prepare_band_data(
+                                    lf,
+                                    &state.schema,
+                                    x_column,
+                                    y_column,
+                                    &source,
+                                    row_limit,
+                                ) {
+                                    app.chart_cache.band = Some(crate::ChartCacheBand {
+                                        x_column: x_key.clone(),
+                                        y_column: y_column.clone(),
+                                        source: source.clone(),
+                                        row_limit: row_limit_opt,
+                                        points,
+                                    });
+                                }
+                            }
+                        }
+                        band = app
+                            .chart_cache
+                            .band
+                            .as_ref()
+                            .filter(|c| matches(c))
+                            .map(|c| {
+                                if app.chart_modal.log_scale {
+                                    c.points
+                                        .iter()
+                                        .map(|&(x, lower, upper)| {
+                                            (x, lower.max(0.0).ln_1p(), upper.max(0.0).ln_1p())
+                                        })
+                                        .collect()
+                                } else {
+                                    c.points.clone()
+                                }
+                            });
+                    }
                 } else {
                     let use_cache = app
                         .chart_cache
@@ -102,9 +166,11 @@ pub fn render(
                         .as_ref()
                         .filter(|c| c.x_column == x_key && c.row_limit == row_limit_opt);
                     if use_cache.is_none() {
-                        if let Some(state) = app.data_table_state.as_ref() {
+                        if let (Some(state), Some(lf)) =
+                            (app.data_table_state.as_ref(), chart_lf.as_ref())
+                        {
                             if let Ok(result) = chart_data::prepare_chart_x_range(
-                                &state.lf,
+                                lf,
                                 &state.schema,
                                 x_column,
                                 row_limit,
@@ -132,17 +198,16 @@ pub fn render(
             }
         }
         ChartKind::Histogram => {
-            if let (Some(state), Some(column)) = (
-                app.data_table_state.as_ref(),
-                app.chart_modal.effective_hist_column(),
-            ) {
+            if let (Some(lf), Some(column)) =
+                (chart_lf.as_ref(), app.chart_modal.effective_hist_column())
+            {
                 let bins = app.chart_modal.hist_bins;
                 let use_cache = app.chart_cache.histogram.as_ref().filter(|c| {
                     c.column == column && c.bins == bins && c.row_limit == row_limit_opt
                 });
                 if use_cache.is_none() {
                     if let Ok(data) =
-                        chart_data::prepare_histogram_data(&state.lf, &column, bins, row_limit)
+                        chart_data::prepare_histogram_data(lf, &column, bins, row_limit)
                     {
                         app.chart_cache.histogram = Some(crate::ChartCacheHistogram {
                             column: column.clone(),
@@ -163,10 +228,9 @@ pub fn render(
             }
         }
         ChartKind::BoxPlot => {
-            if let (Some(state), Some(column)) = (
-                app.data_table_state.as_ref(),
-                app.chart_modal.effective_box_column(),
-            ) {
+            if let (Some(lf), Some(column)) =
+                (chart_lf.as_ref(), app.chart_modal.effective_box_column())
+            {
                 let use_cache = app
                     .chart_cache
                     .box_plot
@@ -174,7 +238,7 @@ pub fn render(
                     .filter(|c| c.column == column && c.row_limit == row_limit_opt);
                 if use_cache.is_none() {
                     if let Ok(data) = chart_data::prepare_box_plot_data(
-                        &state.lf,
+                        lf,
                         std::slice::from_ref(&column),
                         row_limit,
                     ) {
@@ -194,10 +258,9 @@ pub fn render(
             }
         }
         ChartKind::Kde => {
-            if let (Some(state), Some(column)) = (
-                app.data_table_state.as_ref(),
-                app.chart_modal.effective_kde_column(),
-            ) {
+            if let (Some(lf), Some(column)) =
+                (chart_lf.as_ref(), app.chart_modal.effective_kde_column())
+            {
                 let bandwidth = app.chart_modal.kde_bandwidth_factor;
                 let use_cache = app.chart_cache.kde.as_ref().filter(|c| {
                     c.column == column
@@ -206,7 +269,7 @@ pub fn render(
                 });
                 if use_cache.is_none() {
                     if let Ok(data) = chart_data::prepare_kde_data(
-                        &state.lf,
+                        lf,
                         std::slice::from_ref(&column),
                         bandwidth,
                         row_limit,
@@ -232,8 +295,8 @@ pub fn render(
             }
         }
         ChartKind::Heatmap => {
-            if let (Some(state), Some(x_column), Some(y_column)) = (
-                app.data_table_state.as_ref(),
+            if let (Some(lf), Some(x_column), Some(y_column)) = (
+                chart_lf.as_ref(),
                 app.chart_modal.effective_heatmap_x_column(),
                 app.chart_modal.effective_heatmap_y_column(),
             ) {
@@ -245,9 +308,9 @@ pub fn render(
                         && c.row_limit == row_limit_opt
                 });
                 if use_cache.is_none() {
-                    if let Ok(data) = chart_data::prepare_heatmap_data(
-                        &state.lf, &x_column, &y_column, bins, row_limit,
-                    ) {
+                    if let Ok(data) =
+                        chart_data::prepare_heatmap_data(lf, &x_column, &y_column, bins, row_limit)
+                    {
                         app.chart_cache.heatmap = Some(crate::ChartCacheHeatmap {
                             x_column: x_column.clone(),
                             y_column: y_column.clone(),
@@ -270,18 +333,99 @@ pub fn render(
                     .map(|c| &c.data);
             }
         }
+        ChartKind::Category => {
+            if let (Some(lf), Some(column), true) = (
+                chart_lf.as_ref(),
+                app.chart_modal.category_column.clone(),
+                app.chart_modal.category_ready(),
+            ) {
+                let agg = app.chart_modal.category_agg;
+                let value = app
+                    .chart_modal
+                    .effective_category_value()
+                    .map(str::to_string);
+                let top = app.chart_modal.category_top;
+                let matches = |c: &crate::ChartCacheCategory| {
+                    c.column == column
+                        && c.agg == agg
+                        && c.value == value
+                        && c.top == top
+                        && c.row_limit == row_limit_opt
+                };
+                if !app.chart_cache.category.as_ref().is_some_and(matches) {
+                    if let Ok(data) = chart_data::prepare_category_bar_data(
+                        lf,
+                        &column,
+                        agg,
+                        value.as_deref(),
+                        top,
+                        row_limit,
+                    ) {
+                        app.chart_cache.category = Some(crate::ChartCacheCategory {
+                            column: column.clone(),
+                            agg,
+                            value: value.clone(),
+                            top,
+                            row_limit: row_limit_opt,
+                            data,
+                        });
+                    }
+                }
+                category_data = app
+                    .chart_cache
+                    .category
+                    .as_ref()
+                    .filter(|c| matches(c))
+                    .map(|c| &c.data);
+            }
+        }
+        ChartKind::Scatter => {
+            if let (Some(lf), Some(x_column), Some(y_column)) = (
+                chart_lf.as_ref(),
+                app.chart_modal.scatter_x_column.clone(),
+                app.chart_modal.scatter_y_column.clone(),
+            ) {
+                let matches = |c: &crate::ChartCacheScatter| {
+                    c.x_column == x_column && c.y_column == y_column && c.row_limit == row_limit_opt
+                };
+                if !app.chart_cache.scatter.as_ref().is_some_and(matches) {
+                    if let Ok(data) =
+                        chart_data::prepare_scatter_data(lf, &x_column, &y_column, row_limit)
+                    {
+                        app.chart_cache.scatter = Some(crate::ChartCacheScatter {
+                            x_column: x_column.clone(),
+                            y_column: y_column.clone(),
+                            row_limit: row_limit_opt,
+                            data,
+                        });
+                    }
+                }
+                scatter_data = app
+                    .chart_cache
+                    .scatter
+                    .as_ref()
+                    .filter(|c| matches(c))
+                    .map(|c| &c.data);
+            }
+        }
     }
 
     let render_data = match app.chart_modal.chart_kind {
         ChartKind::XY => widgets::chart::ChartRenderData::XY {
             series: xy_series,
+            names: xy_names,
             x_axis_kind,
             x_bounds,
+            band: band.as_deref(),
         },
         ChartKind::Histogram => widgets::chart::ChartRenderData::Histogram { data: hist_data },
         ChartKind::BoxPlot => widgets::chart::ChartRenderData::BoxPlot { data: box_data },
         ChartKind::Kde => widgets::chart::ChartRenderData::Kde { data: kde_data },
         ChartKind::Heatmap => widgets::chart::ChartRenderData::Heatmap { data: heatmap_data },
+        ChartKind::Category => widgets::chart::ChartRenderData::Category {
+            data: category_data,
+        },
+        ChartKind::Scatter => widgets::chart::ChartRenderData::Scatter { data: scatter_data },
     };
 
     widgets::chart::render_chart_view(
@@ -293,25 +437,56 @@ pub fn render(
     );
 
     if app.chart_export_modal.active {
-        const CHART_EXPORT_MODAL_HEIGHT: u16 = 20;
-        let modal_width = (chart_area.width * 3 / 4).clamp(80, 108);
-        let modal_height = CHART_EXPORT_MODAL_HEIGHT
-            .min(chart_area.height)
-            .max(CHART_EXPORT_MODAL_HEIGHT);
-        let modal_x = chart_area.x + chart_area.width.saturating_sub(modal_width) / 2;
-        let modal_y = chart_area.y + chart_area.height.saturating_sub(modal_height) / 2;
+        render_export_modal(chart_area, buf, app, _ctx);
+    }
+
+    if app.chart_labels_modal.active {
+        let height = widgets::chart_labels_modal::CHART_LABELS_MODAL_HEIGHT.min(chart_area.height);
+        let width = (chart_area.width * 3 / 4)
+            .clamp(60, 80)
+            .min(chart_area.width);
         let modal_area = Rect {
-            x: modal_x,
-            y: modal_y,
-            width: modal_width,
-            height: modal_height,
+            x: chart_area.x + chart_area.width.saturating_sub(width) / 2,
+            y: chart_area.y + chart_area.height.saturating_sub(height) / 2,
+            width,
+            height,
         };
-        widgets::chart_export_modal::render_chart_export_modal(
+        widgets::chart_labels_modal::render_chart_labels_modal(
             modal_area,
             buf,
-            &mut app.chart_export_modal,
+            &mut app.chart_labels_modal,
             _ctx.modal_border,
             _ctx.modal_border_active,
         );
     }
 }
+
+/// Renders the chart export modal centered over `area`. Also used by the analysis view, whose
+/// correlation matrix exports through the same modal.
+pub fn render_export_modal(
+    area: Rect,
+    buf: &mut ratatui::buffer::Buffer,
+    app: &mut crate::App,
+    ctx: &RenderContext,
+) {
+    const CHART_EXPORT_MODAL_HEIGHT: u16 = 20;
+    let modal_width = (area.width * 3 / 4).clamp(80, 108);
+    let modal_height = CHART_EXPORT_MODAL_HEIGHT
+        .min(area.height)
+        .max(CHART_EXPORT_MODAL_HEIGHT);
+    let modal_x = area.x + area.width.saturating_sub(modal_width) / 2;
+    let modal_y = area.y + area.height.saturating_sub(modal_height) / 2;
+    let modal_area = Rect {
+        x: modal_x,
+        y: modal_y,
+        width: modal_width,
+        height: modal_height,
+    };
+    widgets::chart_export_modal::render_chart_export_modal(
+        modal_area,
+        buf,
+        &mut app.chart_export_modal,
+        ctx.modal_border,
+        ctx.modal_border_active,
+    );
+}
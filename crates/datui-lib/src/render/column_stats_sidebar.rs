@@ -0,0 +1,69 @@
+//! Column statistics sidebar rendering.
+
+use crate::column_stats::ColumnStatsSidebar;
+use crate::render::context::RenderContext;
+use crate::widgets::datatable::DataTableState;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::Widget;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap};
+
+/// Render the statistics of the selected column: one line per statistic once computed, else
+/// "Computing…" (or the error). Sampled statistics say so at the bottom.
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    sidebar: &ColumnStatsSidebar,
+    state: &DataTableState,
+    ctx: &RenderContext,
+) {
+    Clear.render(area, buf);
+    let column = state.selected_column().unwrap_or_default();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ctx.modal_border))
+        .title(format!(" {column} "));
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let generation = state.len_generation();
+    let label_style = Style::default().fg(ctx.label).add_modifier(Modifier::BOLD);
+    let value_style = Style::default().fg(ctx.text_primary);
+    let lines: Vec<Line> = match sidebar.get(generation, column) {
+        Some(Ok(stats)) => {
+            let mut lines: Vec<Line> = stats
+                .lines(&ctx.locale)
+                .into_iter()
+                .map(|(label, value)| {
+                    Line::from(vec![
+                        Span::styled(format!("{label:<9}"), label_style),
+                        Span::styled(value, value_style),
+                    ])
+                })
+                .collect();
+            if let Some(total) = stats.sampled_from {
+                let total = ctx
+                    .locale
+                    .format_number(&total.to_string())
+                    .unwrap_or_else(|| total.to_string());
+                lines.push(Line::default());
+                lines.push(Line::styled(
+                    format!("Sample of {total} rows"),
+                    Style::default().fg(ctx.dimmed),
+                ));
+            }
+            lines
+        }
+        Some(Err(message)) => vec![Line::styled(
+            message.clone(),
+            Style::default().fg(ctx.error),
+        )],
+        None => vec![Line::styled("Computing…", Style::default().fg(ctx.dimmed))],
+    };
+    Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .render(inner, buf);
+}
@@ -1,4 +1,5 @@
 use crate::config::Theme;
+use crate::locale::DisplayLocale;
 use ratatui::style::Color;
 
 /// Snapshot of theme colors and display configuration for rendering.
@@ -38,9 +39,13 @@ pub struct RenderContext {
     /// Placeholder color for binary-column cells. Applied regardless of `column_colors` since the
     /// `‹binary›` stub is a placeholder, not data.
     pub binary_col: Color,
+    /// Heatmap mode: cell backgrounds at a column's minimum and maximum.
+    pub heatmap_low: Color,
+    pub heatmap_high: Color,
 
     pub table_cell_padding: u16,
     pub column_colors: bool,
+    pub locale: DisplayLocale,
 }
 
 impl RenderContext {
@@ -50,6 +55,7 @@ impl RenderContext {
         theme: &Theme,
         table_cell_padding: u16,
         column_colors: bool,
+        locale: DisplayLocale,
     ) -> Self {
         Self {
             keybind_hints: theme.get("keybind_hints"),
@@ -103,9 +109,12 @@ impl RenderContext {
                 Color::Reset
             },
             binary_col: theme.get("binary_col"),
+            heatmap_low: theme.get("heatmap_low"),
+            heatmap_high: theme.get("heatmap_high"),
 
             table_cell_padding,
             column_colors,
+            locale,
         }
     }
 }
@@ -1,4 +1,5 @@
-//! Datatable main view: table content, input strip, sidebars (sort/filter, template, pivot/melt), export modal.
+//! Datatable main view: table content, input strip, sidebars (sort/filter, template, pivot/melt,
+//! column statistics), export modal.
 
 use crate::render::context::RenderContext;
 use crate::render::datatable_view::{ActiveSidebar, DatatableLayout};
@@ -22,6 +23,7 @@ pub fn render(
     let main_view_content = MainViewContent::from_app_state(
         app.analysis_modal.active,
         app.input_mode == crate::InputMode::Chart,
+        app.workspace_list_visible(),
     );
     let input_strip_visible = main_view_content == MainViewContent::Datatable
         && app.input_mode == crate::InputMode::Editing;
@@ -53,6 +55,7 @@ pub fn render(
         app.sort_filter_modal.active,
         app.template_modal.active,
         app.pivot_melt_modal.active,
+        app.column_stats.active && app.data_table_state.is_some(),
     );
 
     let datatable_layout = DatatableLayout::compute(
@@ -65,6 +68,7 @@ pub fn render(
     let data_area = datatable_layout.content_area;
     let sort_area = datatable_layout.sidebar_area.unwrap_or_default();
 
+    let column_formats = app.display_column_formats();
     match &mut app.data_table_state {
         Some(state) => {
             let mut table_area = data_area;
@@ -122,7 +126,28 @@ pub fn render(
                 .with_cell_padding(ctx.table_cell_padding)
                 .with_alternate_row_bg(ctx.alternate_row_color)
                 .with_binary_col(ctx.binary_col)
-                .with_binary_columns(state.binary_column_names());
+                .with_binary_columns(state.binary_column_names())
+                .with_marked_columns(state.selected_columns().into_iter().collect())
+                .with_locale(ctx.locale)
+                .with_column_formats(column_formats)
+                .with_column_widths(
+                    state.column_widths.clone(),
+                    app.app_config.display.max_column_width,
+                )
+                .with_search_highlight(
+                    state
+                        .regex_search
+                        .as_ref()
+                        .map(|search| search.regex.clone()),
+                    state
+                        .regex_search
+                        .as_ref()
+                        .and_then(|search| search.column.clone()),
+                    Style::default().fg(ctx.text_inverse).bg(ctx.warning),
+                );
+            if let Some(ranges) = app.heatmap.ranges(state.len_generation()) {
+                dt = dt.with_heatmap(ranges.clone(), ctx.heatmap_low, ctx.heatmap_high);
+            }
             if ctx.column_colors {
                 dt = dt.with_column_type_colors(
                     ctx.str_col,
@@ -138,6 +163,14 @@ pub fn render(
                     path: app.path.as_deref(),
                     format: app.original_file_format,
                     parquet_metadata: app.parquet_metadata_cache.as_ref(),
+                    load_profile: app.load_profile.as_ref(),
+                    concat_sources: app.concat_sources.as_ref(),
+                    source_tables: app.source_tables.as_ref().map(|tables| {
+                        (
+                            tables,
+                            app.key_bindings.label(crate::keymap::Action::SwitchTable),
+                        )
+                    }),
                 };
                 let mut info_widget = DataTableInfo::new(
                     state,
@@ -149,6 +182,15 @@ pub fn render(
                 );
                 info_widget.render(sort_area, buf);
             }
+            if active_sidebar == ActiveSidebar::ColumnStats {
+                crate::render::column_stats_sidebar::render(
+                    sort_area,
+                    buf,
+                    &app.column_stats,
+                    state,
+                    ctx,
+                );
+            }
         }
         None => {
             // Show nothing while loading — the status bar spinner communicates progress.
@@ -191,24 +233,34 @@ pub fn render(
     }
 
     if app.export_modal.active {
-        let modal_width = (area.width * 3 / 4).min(80);
-        let modal_height = 20;
-        let modal_x = (area.width.saturating_sub(modal_width)) / 2;
-        let modal_y = (area.height.saturating_sub(modal_height)) / 2;
-        let modal_area = Rect {
-            x: modal_x,
-            y: modal_y,
-            width: modal_width,
-            height: modal_height,
-        };
-        export::render_export_modal(
-            modal_area,
-            buf,
-            &mut app.export_modal,
-            ctx.modal_border,
-            ctx.modal_border_active,
-            ctx.text_primary,
-            ctx.text_inverse,
-        );
+        render_export_modal(area, buf, app, ctx);
     }
 }
+
+/// Renders the export modal centered in `area`; the analysis view uses it for results tables.
+pub fn render_export_modal(
+    area: Rect,
+    buf: &mut ratatui::buffer::Buffer,
+    app: &mut crate::App,
+    ctx: &RenderContext,
+) {
+    let modal_width = (area.width * 3 / 4).min(80);
+    let modal_height = 20;
+    let modal_x = area.x + (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = area.y + (area.height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect {
+        x: modal_x,
+        y: modal_y,
+        width: modal_width,
+        height: modal_height.min(area.height),
+    };
+    export::render_export_modal(
+        modal_area,
+        buf,
+        &mut app.export_modal,
+        ctx.modal_border,
+        ctx.modal_border_active,
+        ctx.text_primary,
+        ctx.text_inverse,
+    );
+}
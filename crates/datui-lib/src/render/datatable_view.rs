@@ -8,6 +8,7 @@ pub enum ActiveSidebar {
     SortFilter,
     Template,
     PivotMelt,
+    ColumnStats,
 }
 
 impl ActiveSidebar {
@@ -17,6 +18,7 @@ impl ActiveSidebar {
         sort_filter_active: bool,
         template_active: bool,
         pivot_melt_active: bool,
+        column_stats_active: bool,
     ) -> Self {
         if info_active {
             ActiveSidebar::Info
@@ -26,6 +28,8 @@ impl ActiveSidebar {
             ActiveSidebar::Template
         } else if pivot_melt_active {
             ActiveSidebar::PivotMelt
+        } else if column_stats_active {
+            ActiveSidebar::ColumnStats
         } else {
             ActiveSidebar::None
         }
@@ -43,6 +47,7 @@ impl ActiveSidebar {
             ActiveSidebar::SortFilter => 50,
             ActiveSidebar::Template => 80,
             ActiveSidebar::PivotMelt => 50,
+            ActiveSidebar::ColumnStats => 40,
         }
     }
 }
@@ -105,7 +110,7 @@ mod tests {
     #[test]
     fn test_active_sidebar_from_modals_none() {
         assert_eq!(
-            ActiveSidebar::from_modals(false, false, false, false),
+            ActiveSidebar::from_modals(false, false, false, false, false),
             ActiveSidebar::None
         );
     }
@@ -113,7 +118,7 @@ mod tests {
     #[test]
     fn test_active_sidebar_from_modals_info() {
         assert_eq!(
-            ActiveSidebar::from_modals(true, false, false, false),
+            ActiveSidebar::from_modals(true, false, false, false, false),
             ActiveSidebar::Info
         );
     }
@@ -121,21 +126,25 @@ mod tests {
     #[test]
     fn test_active_sidebar_from_modals_priority() {
         assert_eq!(
-            ActiveSidebar::from_modals(true, true, true, true),
+            ActiveSidebar::from_modals(true, true, true, true, true),
             ActiveSidebar::Info
         );
         assert_eq!(
-            ActiveSidebar::from_modals(false, true, true, true),
+            ActiveSidebar::from_modals(false, true, true, true, true),
             ActiveSidebar::SortFilter
         );
         assert_eq!(
-            ActiveSidebar::from_modals(false, false, true, true),
+            ActiveSidebar::from_modals(false, false, true, true, true),
             ActiveSidebar::Template
         );
         assert_eq!(
-            ActiveSidebar::from_modals(false, false, false, true),
+            ActiveSidebar::from_modals(false, false, false, true, true),
             ActiveSidebar::PivotMelt
         );
+        assert_eq!(
+            ActiveSidebar::from_modals(false, false, false, false, true),
+            ActiveSidebar::ColumnStats
+        );
     }
 
     #[test]
@@ -145,6 +154,7 @@ mod tests {
         assert_eq!(ActiveSidebar::SortFilter.width(None), 50);
         assert_eq!(ActiveSidebar::Template.width(None), 80);
         assert_eq!(ActiveSidebar::PivotMelt.width(None), 50);
+        assert_eq!(ActiveSidebar::ColumnStats.width(None), 40);
         assert_eq!(ActiveSidebar::Info.width(Some(70)), 70);
         assert_eq!(ActiveSidebar::SortFilter.width(Some(60)), 60);
     }
@@ -1,9 +1,59 @@
-//! Query / Filter / Go-to-line input strip rendering.
+//! Query (SQL-Like / Fuzzy / SQL / Regex / Column) / Filter / Go-to-line / Go-to-column / template parameter / external command / open file input strip rendering.
 
 use crate::render::context::RenderContext;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
-use ratatui::widgets::{Block, BorderType, Borders, Paragraph, Tabs, Widget};
+use ratatui::widgets::{
+    Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, StatefulWidget, Tabs,
+    Widget,
+};
+
+/// Most candidates listed at once under Tab completion; the list scrolls past them.
+const MAX_COMPLETIONS: usize = 8;
+
+/// The query completion candidates in a small box just above the input strip, under the word
+/// being completed, the one filled in highlighted.
+fn render_completions(
+    input_area: Rect,
+    text_x: u16,
+    completion: &crate::query::Completion,
+    selected: Option<usize>,
+    buf: &mut ratatui::buffer::Buffer,
+    ctx: &RenderContext,
+) {
+    let rows = completion.candidates.len().min(MAX_COMPLETIONS) as u16;
+    let widest = completion
+        .candidates
+        .iter()
+        .map(|c| c.chars().count())
+        .max()
+        .unwrap_or(0) as u16;
+    let height = (rows + 2).min(input_area.y);
+    let width = (widest + 4).min(input_area.width);
+    if height < 3 || width < 5 {
+        return;
+    }
+    let x = text_x
+        .saturating_add(completion.start as u16)
+        .min(input_area.right().saturating_sub(width));
+    let area = Rect::new(x, input_area.y - height, width, height);
+    Clear.render(area, buf);
+    let items: Vec<ListItem> = completion
+        .candidates
+        .iter()
+        .map(|c| ListItem::new(c.as_str()))
+        .collect();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(ctx.modal_border_active)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(selected);
+    StatefulWidget::render(list, area, buf, &mut state);
+}
 
 /// Renders the input strip (query/fuzzy/SQL tabs, inputs, error) when in Editing mode.
 pub fn render(
@@ -15,10 +65,30 @@ pub fn render(
     ctx: &RenderContext,
 ) {
     let title = match app.input_type {
-        Some(crate::InputType::Search) => "Query",
-        Some(crate::InputType::Filter) => "Filter",
-        Some(crate::InputType::GoToLine) => "Go to line",
-        None => "Input",
+        Some(crate::InputType::Search) => "Query".to_string(),
+        Some(crate::InputType::Filter) => "Filter".to_string(),
+        Some(crate::InputType::GoToLine) => "Go to line".to_string(),
+        Some(crate::InputType::GoToColumn) => {
+            // The best matches so far; Tab completes the first
+            let matches = app.go_to_column_matches();
+            if matches.is_empty() {
+                "Go to column (name, number or letters)".to_string()
+            } else {
+                let shown: Vec<&str> = matches.iter().take(5).map(String::as_str).collect();
+                let more = if matches.len() > 5 { ", ..." } else { "" };
+                format!("Go to column: {}{} (Tab completes)", shown.join(", "), more)
+            }
+        }
+        Some(crate::InputType::TemplateParameter) => format!(
+            "Template parameter: {}",
+            app.template_parameter_prompt().unwrap_or("value")
+        ),
+        Some(crate::InputType::ExternalCommand) => {
+            "Run command ({file} = current file)".to_string()
+        }
+        Some(crate::InputType::OpenFile) => "Open file in a new tab".to_string(),
+        Some(crate::InputType::Profile) => "Write profiling report to (.html or .md)".to_string(),
+        None => "Input".to_string(),
     };
 
     let mut border_style = Style::default();
@@ -52,7 +122,7 @@ pub fn render(
             .direction(Direction::Horizontal)
             .constraints([Constraint::Min(0), Constraint::Max(40)])
             .split(tab_line_chunks[0]);
-        let tab_titles = vec!["SQL-Like", "Fuzzy", "SQL"];
+        let tab_titles = vec!["SQL-Like", "Fuzzy", "SQL", "Regex", "Column"];
         let tabs = Tabs::new(tab_titles)
             .style(Style::default().fg(border_c))
             .highlight_style(
@@ -62,9 +132,18 @@ pub fn render(
             )
             .select(app.query_tab.index());
         tabs.render(tab_row_chunks[0], buf);
+        let column_desc = format!(
+            "Search {}; n / N to jump",
+            app.data_table_state
+                .as_ref()
+                .and_then(|s| s.selected_column())
+                .unwrap_or("")
+        );
         let desc_text = match app.query_tab {
             crate::QueryTab::SqlLike => "select [cols] [by ...] [where ...]",
-            crate::QueryTab::Fuzzy => "Search text to find matching rows",
+            crate::QueryTab::Fuzzy => "Search text to find matching rows; \"quote\" exact text",
+            crate::QueryTab::Regex => "Highlight matches; n / N to jump",
+            crate::QueryTab::Column => column_desc.as_str(),
             crate::QueryTab::Sql => {
                 #[cfg(feature = "sql")]
                 {
@@ -112,6 +191,27 @@ pub fn render(
                         .set_focused(app.query_focus == crate::QueryFocus::Input);
                     (&app.query_input).render(chunks[1], buf);
                 }
+                if let Some((completion, selected)) = app.query_completion() {
+                    render_completions(input_area, chunks[1].x, completion, selected, buf, ctx);
+                }
+            }
+            crate::QueryTab::Regex | crate::QueryTab::Column => {
+                app.query_input.set_focused(false);
+                app.regex_input
+                    .set_focused(app.query_focus == crate::QueryFocus::Input);
+                if has_error {
+                    let body_chunks = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([Constraint::Length(1), Constraint::Min(1)])
+                        .split(chunks[1]);
+                    (&app.regex_input).render(body_chunks[0], buf);
+                    Paragraph::new(err_msg)
+                        .style(Style::default().fg(ctx.error))
+                        .wrap(ratatui::widgets::Wrap { trim: true })
+                        .render(body_chunks[1], buf);
+                } else {
+                    (&app.regex_input).render(chunks[1], buf);
+                }
             }
             crate::QueryTab::Fuzzy => {
                 app.query_input.set_focused(false);
@@ -7,15 +7,23 @@ pub enum MainViewContent {
     Analysis,
     /// Full-screen chart view.
     Chart,
+    /// Workspace file list (directory opened without --hive).
+    Workspace,
 }
 
 impl MainViewContent {
     /// Determine active main-view content from app state.
-    pub fn from_app_state(analysis_active: bool, input_mode_chart: bool) -> Self {
+    pub fn from_app_state(
+        analysis_active: bool,
+        input_mode_chart: bool,
+        workspace_list_visible: bool,
+    ) -> Self {
         if analysis_active {
             MainViewContent::Analysis
         } else if input_mode_chart {
             MainViewContent::Chart
+        } else if workspace_list_visible {
+            MainViewContent::Workspace
         } else {
             MainViewContent::Datatable
         }
@@ -26,8 +34,12 @@ impl MainViewContent {
 /// The main render loop uses this to build the Controls widget.
 #[derive(Debug, Clone)]
 pub enum ControlBarSpec {
-    /// Default datatable controls (row count, etc.) with optional dimmed and query-active state.
-    Datatable { dimmed: bool, query_active: bool },
+    /// Default datatable controls (row count, etc.) with optional dimmed state and the engine of
+    /// the active query.
+    Datatable {
+        dimmed: bool,
+        query_engine: Option<crate::QueryEngine>,
+    },
     /// Custom keybinding list for this view (e.g. analysis or chart).
     Custom(Vec<(&'static str, &'static str)>),
 }
@@ -36,12 +48,36 @@ pub enum ControlBarSpec {
 /// The main render loop calls this and applies the result to the Controls widget.
 pub fn control_bar_spec(app: &crate::App, content: MainViewContent) -> ControlBarSpec {
     match content {
+        MainViewContent::Datatable
+            if app.input_mode == crate::InputMode::Normal
+                && app
+                    .data_table_state
+                    .as_ref()
+                    .is_some_and(|s| s.selected_rows().is_some()) =>
+        {
+            ControlBarSpec::Custom(vec![
+                ("↑↓", "Extend Rows"),
+                ("y", "Copy"),
+                ("E", "Export"),
+                ("A", "Stats"),
+                ("Esc", "Cancel"),
+            ])
+        }
+        MainViewContent::Datatable
+            if app.input_mode == crate::InputMode::Normal
+                && app
+                    .data_table_state
+                    .as_ref()
+                    .is_some_and(|s| s.visual_block().is_some()) =>
+        {
+            ControlBarSpec::Custom(vec![
+                ("↑↓←→", "Extend Block"),
+                ("y", "Copy Block"),
+                ("Esc", "Cancel"),
+            ])
+        }
         MainViewContent::Datatable => {
-            let query_active = app
-                .data_table_state
-                .as_ref()
-                .map(|s| !s.active_query.trim().is_empty())
-                .unwrap_or(false);
+            let query_engine = app.data_table_state.as_ref().and_then(|s| s.query_engine());
             let dimmed = app.show_help
                 || app.input_mode == crate::InputMode::Editing
                 || app.input_mode == crate::InputMode::SortFilter
@@ -50,7 +86,7 @@ pub fn control_bar_spec(app: &crate::App, content: MainViewContent) -> ControlBa
                 || app.sort_filter_modal.active;
             ControlBarSpec::Datatable {
                 dimmed,
-                query_active,
+                query_engine,
             }
         }
         MainViewContent::Analysis => {
@@ -61,6 +97,40 @@ pub fn control_bar_spec(app: &crate::App, content: MainViewContent) -> ControlBa
                 ("Tab", "Sidebar"),
                 ("Enter", "Select"),
             ];
+            if app.analysis_modal.selected_tool
+                == Some(crate::analysis_modal::AnalysisTool::Describe)
+            {
+                pairs.push(("b", "Baseline"));
+                if app.analysis_modal.describe_baseline.is_some() {
+                    pairs.push(("c", "Compare"));
+                }
+            }
+            if app.analysis_modal.selected_tool
+                == Some(crate::analysis_modal::AnalysisTool::MissingData)
+            {
+                pairs.push(("d", "Drop Nulls"));
+                pairs.push(("D", "Drop Null Rows"));
+            }
+            if app.analysis_modal.selected_tool
+                == Some(crate::analysis_modal::AnalysisTool::DuplicateRows)
+            {
+                pairs.push(("c", "Keys"));
+                pairs.push(("f", "Filter"));
+                pairs.push(("e", "Export"));
+            }
+            if app.analysis_modal.selected_tool
+                == Some(crate::analysis_modal::AnalysisTool::Crosstab)
+            {
+                pairs.push(("c", "Columns"));
+                if app
+                    .analysis_modal
+                    .crosstab_spec
+                    .as_ref()
+                    .is_some_and(|spec| spec.value.is_some())
+                {
+                    pairs.push(("a", "Aggregation"));
+                }
+            }
             if app.sampling_threshold.is_some() {
                 if let Some(results) = app.analysis_modal.current_results() {
                     if results.sample_size.is_some() {
@@ -71,5 +141,26 @@ pub fn control_bar_spec(app: &crate::App, content: MainViewContent) -> ControlBa
             ControlBarSpec::Custom(pairs)
         }
         MainViewContent::Chart => ControlBarSpec::Custom(vec![("Esc", "Back"), ("e", "Export")]),
+        MainViewContent::Workspace
+            if app
+                .workspace
+                .as_ref()
+                .is_some_and(|w| w.schema_diff.is_some()) =>
+        {
+            ControlBarSpec::Custom(vec![
+                ("Esc", "Back"),
+                ("↑↓", "Navigate"),
+                ("←→", "Scroll Files"),
+                ("f", "Drift Only"),
+            ])
+        }
+        MainViewContent::Workspace => {
+            let mut pairs = vec![("↑↓", "Navigate"), ("Enter", "Open"), ("D", "Schema Diff")];
+            if app.workspace.as_ref().is_some_and(|w| w.current.is_some()) {
+                pairs.push(("Esc", "Back"));
+            }
+            pairs.extend([("?", "Help"), ("q", "Quit")]);
+            ControlBarSpec::Custom(pairs)
+        }
     }
 }
@@ -1,8 +1,9 @@
-//! Main view dispatcher: datatable, analysis, or chart.
+//! Main view dispatcher: datatable, analysis, chart, or workspace file list.
 
 use crate::render::main_view::MainViewContent;
 
-/// Renders the main view based on content mode: datatable (table + sidebars), analysis, or chart.
+/// Renders the main view based on content mode: datatable (table + sidebars), analysis, chart,
+/// or the workspace file list.
 pub fn render_main_view(
     area: ratatui::layout::Rect,
     main_area: ratatui::layout::Rect,
@@ -13,16 +14,22 @@ pub fn render_main_view(
     let content = MainViewContent::from_app_state(
         app.analysis_modal.active,
         app.input_mode == crate::InputMode::Chart,
+        app.workspace_list_visible(),
     );
     match content {
         MainViewContent::Datatable => {
+            let main_area = crate::render::workspace_view::render_tab_bar(main_area, buf, app, ctx);
             crate::render::datatable_main::render(area, main_area, buf, app, ctx);
         }
         MainViewContent::Analysis => {
             crate::render::analysis_view::render(main_area, buf, app, ctx);
         }
         MainViewContent::Chart => {
+            let main_area = crate::render::workspace_view::render_tab_bar(main_area, buf, app, ctx);
             crate::render::chart_view::render(main_area, buf, app, ctx);
         }
+        MainViewContent::Workspace => {
+            crate::render::workspace_view::render(main_area, buf, app, ctx);
+        }
     }
 }
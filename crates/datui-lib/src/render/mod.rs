@@ -1,5 +1,6 @@
 pub mod analysis_view;
 pub mod chart_view;
+pub mod column_stats_sidebar;
 pub mod context;
 pub mod datatable_main;
 pub mod datatable_view;
@@ -10,3 +11,4 @@ pub mod main_view_render;
 pub mod overlays;
 pub mod sort_filter_sidebar;
 pub mod template_sidebar;
+pub mod workspace_view;
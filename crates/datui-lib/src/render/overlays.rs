@@ -1,18 +1,20 @@
-//! Overlay rendering (confirmation/success/error modals, help).
+//! Overlay rendering (confirmation/success/error modals, aggregate and cell peeks, value counts,
+//! record view, data diff, S3 object browser, file picker, Format modal, help).
 
 use crate::render::context::RenderContext;
-use crate::render::layout::{centered_rect, centered_rect_with_min};
+use crate::render::layout::{centered_rect, centered_rect_fixed, centered_rect_with_min};
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::prelude::Widget;
-use ratatui::style::Style;
-use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Gauge, Paragraph};
 
 /// Renders the confirmation modal (Yes/No).
 pub fn render_confirmation_modal(
     area: Rect,
     buf: &mut Buffer,
-    modal: &crate::ConfirmationModal,
+    modal: &mut crate::ConfirmationModal,
     ctx: &RenderContext,
 ) {
     let popup_area = centered_rect_with_min(area, 64, 26, 50, 12);
@@ -51,6 +53,9 @@ pub fn render_confirmation_modal(
             Constraint::Fill(1),
         ])
         .split(chunks[1]);
+    modal.buttons.clear();
+    modal.buttons.add(button_chunks[1], true);
+    modal.buttons.add(button_chunks[3], false);
 
     let yes_style = if modal.focus_yes {
         Style::default().fg(ctx.modal_border_active)
@@ -88,7 +93,7 @@ pub fn render_confirmation_modal(
 pub fn render_success_modal(
     area: Rect,
     buf: &mut Buffer,
-    modal: &crate::SuccessModal,
+    modal: &mut crate::SuccessModal,
     ctx: &RenderContext,
 ) {
     let popup_area = centered_rect(area, 70, 40);
@@ -110,6 +115,8 @@ pub fn render_success_modal(
         .wrap(ratatui::widgets::Wrap { trim: true })
         .render(chunks[0], buf);
 
+    modal.buttons.clear();
+    modal.buttons.add(chunks[1], ());
     let ok_style = Style::default().fg(ctx.modal_border_active);
     Paragraph::new("OK")
         .centered()
@@ -126,7 +133,7 @@ pub fn render_success_modal(
 pub fn render_error_modal(
     area: Rect,
     buf: &mut Buffer,
-    modal: &crate::ErrorModal,
+    modal: &mut crate::ErrorModal,
     ctx: &RenderContext,
 ) {
     let popup_area = centered_rect(area, 70, 40);
@@ -149,6 +156,8 @@ pub fn render_error_modal(
         .wrap(ratatui::widgets::Wrap { trim: true })
         .render(chunks[0], buf);
 
+    modal.buttons.clear();
+    modal.buttons.add(chunks[1], ());
     let ok_style = Style::default().fg(ctx.modal_border_active);
     Paragraph::new("OK")
         .centered()
@@ -161,6 +170,2535 @@ pub fn render_error_modal(
         .render(chunks[1], buf);
 }
 
+/// Renders the aggregate peek popup: one line per aggregate, with what it covers in the footer.
+pub fn render_aggregate_peek(
+    area: Rect,
+    buf: &mut Buffer,
+    peek: &crate::aggregate_peek::AggregatePeek,
+    ctx: &RenderContext,
+) {
+    let lines = peek.lines(&ctx.locale);
+    let coverage = peek.coverage(&ctx.locale);
+    let title = format!(" {} ({}) ", peek.column, peek.dtype);
+    let value_width = lines
+        .iter()
+        .map(|(_, v)| v.chars().count())
+        .max()
+        .unwrap_or(0);
+    let width = (value_width + 8)
+        .max(coverage.chars().count())
+        .max(title.chars().count())
+        + 4;
+    let popup_area = centered_rect_fixed(area, width as u16, lines.len() as u16 + 2);
+    Clear.render(popup_area, buf);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(title)
+        .title_bottom(Line::from(format!(" {coverage} ")).right_aligned())
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .style(Style::default().bg(ctx.background));
+    let inner_area = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let label_style = Style::default().fg(ctx.label).add_modifier(Modifier::BOLD);
+    let text: Vec<Line> = lines
+        .into_iter()
+        .map(|(label, value)| {
+            Line::from(vec![
+                Span::styled(format!(" {label:<6}"), label_style),
+                Span::styled(value, Style::default().fg(ctx.text_primary)),
+            ])
+        })
+        .collect();
+    Paragraph::new(text)
+        .style(Style::default().bg(ctx.background))
+        .render(inner_area, buf);
+}
+
+/// Widest value shown in the value counts popup; longer values are cut with "…".
+const VALUE_COUNTS_VALUE_WIDTH: usize = 40;
+/// Width of the share bar after each count.
+const VALUE_COUNTS_BAR_WIDTH: usize = 10;
+
+/// Renders the value counts popup: the running phase with a gauge while computing, then one
+/// line per value (value, count, share and a bar) with what is listed in the footer. Records how
+/// many values fit so the popup scrolls by pages.
+pub fn render_value_counts(
+    area: Rect,
+    buf: &mut Buffer,
+    popup: &mut crate::value_counts::ValueCountsPopup,
+    ctx: &RenderContext,
+) {
+    let phases = crate::value_counts::PHASES;
+    let Some(counts) = popup.counts.as_ref() else {
+        let phase = popup.phase.unwrap_or(0).min(phases.len() - 1);
+        let popup_area = centered_rect_fixed(area, 50, 4);
+        Clear.render(popup_area, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(format!(" {} ", popup.column))
+            .border_style(Style::default().fg(ctx.modal_border_active))
+            .style(Style::default().bg(ctx.background));
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Length(1)])
+            .split(inner);
+        Paragraph::new(format!(
+            " {}: {} / {}",
+            phases[phase],
+            phase + 1,
+            phases.len()
+        ))
+        .style(Style::default().fg(ctx.text_primary))
+        .render(rows[0], buf);
+        Gauge::default()
+            .gauge_style(Style::default().fg(ctx.label))
+            .ratio(phase as f64 / phases.len() as f64)
+            .render(rows[1], buf);
+        return;
+    };
+
+    let lines = counts.lines(&ctx.locale);
+    let coverage = counts.coverage(&ctx.locale);
+    let title = format!(" {} ({}) ", counts.column, counts.dtype);
+    let width_of = |f: fn(&(String, String, String)) -> &String| {
+        lines
+            .iter()
+            .map(|l| f(l).chars().count())
+            .max()
+            .unwrap_or(0)
+    };
+    let value_width = width_of(|l| &l.0).min(VALUE_COUNTS_VALUE_WIDTH);
+    let count_width = width_of(|l| &l.1);
+    let percent_width = width_of(|l| &l.2);
+    let width = (value_width + count_width + percent_width + VALUE_COUNTS_BAR_WIDTH + 5)
+        .max(coverage.chars().count())
+        .max(title.chars().count())
+        + 4;
+    let max_lines = area.height.saturating_sub(6).max(1) as usize;
+    let visible = lines.len().clamp(1, max_lines);
+    popup.visible = visible;
+    popup.scroll = popup.scroll.min(lines.len().saturating_sub(visible));
+    let popup_area = centered_rect_fixed(area, width as u16, visible as u16 + 2);
+    Clear.render(popup_area, buf);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(title)
+        .title_bottom(Line::from(format!(" {coverage} ")).right_aligned())
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .style(Style::default().bg(ctx.background));
+    let inner_area = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let value_style = Style::default().fg(ctx.text_primary);
+    let count_style = Style::default().fg(ctx.label).add_modifier(Modifier::BOLD);
+    let bar_style = Style::default().fg(ctx.primary_chart_series_color);
+    let text: Vec<Line> = lines
+        .iter()
+        .zip(&counts.values)
+        .skip(popup.scroll)
+        .take(visible)
+        .map(|((value, count, percent), (_, n))| {
+            let value = if value.chars().count() > value_width {
+                let cut: String = value.chars().take(value_width.saturating_sub(1)).collect();
+                format!("{cut}…")
+            } else {
+                value.clone()
+            };
+            let share = if counts.rows > 0 {
+                *n as f64 / counts.rows as f64
+            } else {
+                0.0
+            };
+            let bar = "█".repeat((share * VALUE_COUNTS_BAR_WIDTH as f64).ceil() as usize);
+            Line::from(vec![
+                Span::styled(format!(" {value:<value_width$} "), value_style),
+                Span::styled(format!("{count:>count_width$} "), count_style),
+                Span::styled(format!("{percent:>percent_width$} "), value_style),
+                Span::styled(bar, bar_style),
+            ])
+        })
+        .collect();
+    Paragraph::new(text)
+        .style(Style::default().bg(ctx.background))
+        .render(inner_area, buf);
+}
+
+/// Renders the record view: one line per column, struct field and list item with the label
+/// column indented by nesting depth and values wrapped beneath themselves. Records how many
+/// lines there are and how many fit so the view scrolls by pages.
+pub fn render_record_view(
+    area: Rect,
+    buf: &mut Buffer,
+    view: &mut crate::record_view::RecordView,
+    ctx: &RenderContext,
+) {
+    use crate::record_view::RecordValue;
+
+    let width = (area.width as usize * 4 / 5).max(30);
+    let label_width = view
+        .entries
+        .iter()
+        .map(|e| e.depth * 2 + e.label.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(width / 3);
+    let value_width = width.saturating_sub(label_width + 6).max(1);
+    let label_style = Style::default().fg(ctx.label).add_modifier(Modifier::BOLD);
+    let value_style = Style::default().fg(ctx.text_primary);
+    let dimmed_style = Style::default().fg(ctx.dimmed);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for entry in &view.entries {
+        let (text, style) = match &entry.value {
+            RecordValue::Scalar(v) if v.is_null() => ("null".to_string(), dimmed_style),
+            RecordValue::Scalar(v) => (ctx.locale.format_value(v).into_owned(), value_style),
+            other => (other.summary().unwrap_or_default(), dimmed_style),
+        };
+        let mut label: String = " ".repeat(entry.depth * 2) + entry.label.as_str();
+        if label.chars().count() > label_width {
+            label = label.chars().take(label_width.saturating_sub(1)).collect();
+            label.push('…');
+        }
+        let mut first = true;
+        for part in text.split('\n') {
+            let chars: Vec<char> = part.chars().collect();
+            for chunk in chars
+                .chunks(value_width)
+                .map(|c| c.iter().collect::<String>())
+            {
+                let shown = if first { label.as_str() } else { "" };
+                lines.push(Line::from(vec![
+                    Span::styled(format!(" {shown:<label_width$}  "), label_style),
+                    Span::styled(chunk, style),
+                ]));
+                first = false;
+            }
+            if chars.is_empty() {
+                let shown = if first { label.as_str() } else { "" };
+                lines.push(Line::from(Span::styled(
+                    format!(" {shown:<label_width$}  "),
+                    label_style,
+                )));
+                first = false;
+            }
+        }
+    }
+
+    let max_lines = area.height.saturating_sub(6).max(1) as usize;
+    let visible = lines.len().clamp(1, max_lines);
+    view.lines = lines.len();
+    view.visible = visible;
+    view.scroll = view.scroll.min(lines.len().saturating_sub(visible));
+    let popup_area = centered_rect_fixed(area, width as u16, visible as u16 + 2);
+    Clear.render(popup_area, buf);
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(format!(" Row {} ", view.row + 1))
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .style(Style::default().bg(ctx.background));
+    if lines.len() > visible {
+        block = block.title_bottom(
+            Line::from(format!(
+                " {}-{} of {} lines ",
+                view.scroll + 1,
+                view.scroll + visible,
+                lines.len()
+            ))
+            .right_aligned(),
+        );
+    }
+    let inner_area = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let shown: Vec<Line> = lines.into_iter().skip(view.scroll).take(visible).collect();
+    Paragraph::new(shown)
+        .style(Style::default().bg(ctx.background))
+        .render(inner_area, buf);
+}
+
+/// Renders the data comparison of `--diff`: a summary of added, removed, changed and unchanged
+/// rows and schema changes, then one row per difference with its key and the compared columns
+/// (changed cells as `old → new`). Records how many rows fit so the view pages by screens.
+pub fn render_data_diff(
+    area: Rect,
+    buf: &mut Buffer,
+    view: &mut crate::data_diff::DataDiffView,
+    ctx: &RenderContext,
+) {
+    use crate::data_diff::RowChange;
+    use ratatui::widgets::{Cell, Row, StatefulWidget, Table};
+
+    /// Widest a compared column is drawn.
+    const MAX_COLUMN_WIDTH: usize = 40;
+
+    let popup_area = centered_rect(area, 95, 90);
+    Clear.render(popup_area, buf);
+    let name = |path: &std::path::Path| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string())
+    };
+    let filter = view.filter.map(RowChange::label).unwrap_or("all");
+    let key_style = Style::default()
+        .fg(ctx.keybind_hints)
+        .add_modifier(Modifier::BOLD);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(format!(
+            " Diff: {} → {} ",
+            name(&view.request.old),
+            name(&view.request.new)
+        ))
+        .title_bottom(Line::from(vec![
+            Span::raw(" "),
+            Span::styled("f", key_style),
+            Span::raw(format!(" Show: {filter}  ")),
+            Span::styled("←/→", key_style),
+            Span::raw(" Columns  "),
+            Span::styled("Esc", key_style),
+            Span::raw(" Close "),
+        ]))
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let diff = match &view.diff {
+        None => {
+            Paragraph::new(format!(
+                "Comparing {} and {}…",
+                name(&view.request.old),
+                name(&view.request.new)
+            ))
+            .style(Style::default().fg(ctx.text_secondary))
+            .render(inner, buf);
+            return;
+        }
+        Some(Err(message)) => {
+            Paragraph::new(message.as_str())
+                .style(Style::default().fg(ctx.error))
+                .wrap(ratatui::widgets::Wrap { trim: true })
+                .render(inner, buf);
+            return;
+        }
+        Some(Ok(diff)) => diff,
+    };
+    let change_style = |change: RowChange| {
+        Style::default().fg(match change {
+            RowChange::Added => ctx.success,
+            RowChange::Removed => ctx.error,
+            RowChange::Changed => ctx.warning,
+        })
+    };
+
+    // Summary: counts, then schema changes
+    let mut summary = Line::default();
+    for change in RowChange::ALL {
+        summary.spans.push(Span::styled(
+            format!(
+                "{}{} {}",
+                change.marker(),
+                diff.count(change),
+                change.label()
+            ),
+            change_style(change),
+        ));
+        summary.spans.push(Span::raw("  "));
+    }
+    summary.spans.push(Span::styled(
+        format!("{} unchanged", diff.unchanged),
+        Style::default().fg(ctx.dimmed),
+    ));
+    if diff.rows.len() < diff.differing() {
+        summary.spans.push(Span::styled(
+            format!("  (listing the first {})", diff.rows.len()),
+            Style::default().fg(ctx.dimmed),
+        ));
+    }
+    let mut lines = vec![summary];
+    const MAX_SCHEMA_LINES: usize = 3;
+    let schema_changes = diff.schema_changes();
+    lines.extend(
+        schema_changes
+            .iter()
+            .take(MAX_SCHEMA_LINES)
+            .map(|l| Line::styled(l.clone(), Style::default().fg(ctx.label))),
+    );
+    if schema_changes.len() > MAX_SCHEMA_LINES {
+        lines.push(Line::styled(
+            format!(
+                "… {} more schema changes",
+                schema_changes.len() - MAX_SCHEMA_LINES
+            ),
+            Style::default().fg(ctx.dimmed),
+        ));
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(lines.len() as u16 + 1),
+            Constraint::Fill(1),
+        ])
+        .split(inner);
+    Paragraph::new(lines).render(chunks[0], buf);
+
+    let visible = view.visible_rows();
+    // Width of a column with `header` whose cells are `cell(row)` for each visible row
+    let width_of = |header: &str, cell: &dyn Fn(&crate::data_diff::DiffRow) -> usize| {
+        visible
+            .iter()
+            .map(|&i| cell(&diff.rows[i]))
+            .chain(std::iter::once(header.chars().count()))
+            .max()
+            .unwrap_or(0)
+            .min(MAX_COLUMN_WIDTH) as u16
+    };
+    let mut widths = vec![1u16];
+    let mut headers = vec![String::new()];
+    for (k, key) in diff.keys.iter().enumerate() {
+        widths.push(width_of(key, &|row| row.key[k].chars().count()));
+        headers.push(key.clone());
+    }
+    // Compared columns that fit after the keys, starting at column_offset
+    let mut remaining = chunks[1]
+        .width
+        .saturating_sub(widths.iter().map(|w| w + 1).sum::<u16>());
+    let mut columns = Vec::new();
+    for c in view.column_offset..diff.columns.len() {
+        let width = width_of(&diff.columns[c], &|row| row.values[c].chars().count()).max(1);
+        if width + 1 > remaining && !columns.is_empty() {
+            break;
+        }
+        remaining = remaining.saturating_sub(width + 1);
+        columns.push(c);
+        widths.push(width);
+        headers.push(diff.columns[c].clone());
+    }
+
+    let rows: Vec<Row> = visible
+        .iter()
+        .map(|&i| {
+            let row = &diff.rows[i];
+            let style = change_style(row.change);
+            let mut cells = vec![Cell::from(row.change.marker()).style(style)];
+            cells.extend(row.key.iter().map(|k| {
+                Cell::from(k.clone()).style(Style::default().add_modifier(Modifier::BOLD))
+            }));
+            cells.extend(columns.iter().map(|&c| {
+                let cell = Cell::from(row.values[c].clone());
+                match row.change {
+                    RowChange::Changed if row.changed[c] => {
+                        cell.style(style.add_modifier(Modifier::BOLD))
+                    }
+                    RowChange::Changed => cell,
+                    _ => cell.style(style),
+                }
+            }));
+            Row::new(cells)
+        })
+        .collect();
+    let header = Row::new(headers).style(
+        Style::default()
+            .fg(ctx.table_header)
+            .add_modifier(Modifier::UNDERLINED),
+    );
+    view.visible = chunks[1].height.saturating_sub(1).max(1) as usize;
+    let table = Table::new(rows, widths.into_iter().map(Constraint::Length))
+        .header(header)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(table, chunks[1], buf, &mut view.table_state);
+}
+
+/// Renders the S3 object browser: one row per sub-prefix or object under the listed prefix
+/// (name, size, modified), marked objects flagged with "●" and the keys in the footer. Records
+/// how many rows fit so the browser pages by screens.
+#[cfg(feature = "cloud")]
+pub fn render_s3_browser(
+    area: Rect,
+    buf: &mut Buffer,
+    browser: &mut crate::s3_browser::S3Browser,
+    ctx: &RenderContext,
+) {
+    use crate::s3_browser::S3EntryKind;
+    use ratatui::widgets::{Cell, Row, StatefulWidget, Table};
+
+    let popup_area = centered_rect(area, 80, 70);
+    Clear.render(popup_area, buf);
+    browser.visible = popup_area.height.saturating_sub(3).max(1) as usize;
+
+    let rows: Vec<Row> = browser
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let marker = if browser.marked.contains(&i) {
+                Cell::from("●").style(Style::default().fg(ctx.success))
+            } else {
+                Cell::from(" ")
+            };
+            let (size, modified) = match &entry.kind {
+                S3EntryKind::Prefix => (String::new(), String::new()),
+                S3EntryKind::Object { size, modified } => (
+                    crate::widgets::info::format_bytes(*size),
+                    modified.format("%Y-%m-%d %H:%M").to_string(),
+                ),
+            };
+            let name_style = if entry.is_prefix() {
+                Style::default().fg(ctx.label).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(ctx.text_primary)
+            };
+            Row::new(vec![
+                marker,
+                Cell::from(entry.name.clone()).style(name_style),
+                Cell::from(Line::from(size).right_aligned()),
+                Cell::from(modified),
+            ])
+        })
+        .collect();
+    let header = Row::new(vec![
+        Cell::from(" "),
+        Cell::from("Name"),
+        Cell::from(Line::from("Size").right_aligned()),
+        Cell::from("Modified"),
+    ])
+    .style(
+        Style::default()
+            .fg(ctx.table_header)
+            .add_modifier(Modifier::UNDERLINED),
+    );
+    let title = if browser.marked.is_empty() {
+        format!(
+            " {} ({} entries) ",
+            browser.location(),
+            browser.entries.len()
+        )
+    } else {
+        format!(
+            " {} ({} entries, {} marked) ",
+            browser.location(),
+            browser.entries.len(),
+            browser.marked.len()
+        )
+    };
+    let hints = " Enter open · Space mark · Backspace up · H load prefix (hive) · Esc close ";
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(11),
+            Constraint::Length(16),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ctx.modal_border_active))
+            .title(title)
+            .title_bottom(Line::from(hints).right_aligned())
+            .style(Style::default().bg(ctx.background)),
+    )
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(table, popup_area, buf, &mut browser.table_state);
+}
+
+/// Renders the file picker: subdirectories and files of the current directory (name, format,
+/// size), the filter in the title, and a preview line for the highlighted file with its
+/// estimated rows. Records how many rows fit so the picker pages by screens.
+pub fn render_file_browser(
+    area: Rect,
+    buf: &mut Buffer,
+    browser: &mut crate::file_browser::FileBrowser,
+    ctx: &RenderContext,
+) {
+    use crate::widgets::info::format_bytes;
+    use ratatui::widgets::{Cell, Row, StatefulWidget, Table};
+
+    let popup_area = centered_rect(area, 80, 70);
+    Clear.render(popup_area, buf);
+    let hidden = if browser.show_hidden {
+        ", hidden shown"
+    } else {
+        ""
+    };
+    let title = format!(
+        " {} ({}{}) ",
+        browser.dir.display(),
+        browser.filter.label(),
+        hidden
+    );
+    let hints = " Enter open · Backspace up · f filter · . hidden · e type path · Esc close ";
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .title(title)
+        .title_bottom(Line::from(hints).right_aligned())
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Fill(1), Constraint::Length(1)])
+        .split(inner);
+    browser.visible = chunks[0].height.saturating_sub(1).max(1) as usize;
+
+    let rows: Vec<Row> = browser
+        .entries
+        .iter()
+        .map(|entry| {
+            if entry.is_dir {
+                Row::new(vec![
+                    Cell::from(format!("{}/", entry.name))
+                        .style(Style::default().fg(ctx.label).add_modifier(Modifier::BOLD)),
+                    Cell::from(""),
+                    Cell::from(""),
+                ])
+            } else {
+                let style = if entry.format.is_some() {
+                    Style::default().fg(ctx.text_primary)
+                } else {
+                    Style::default().fg(ctx.dimmed)
+                };
+                Row::new(vec![
+                    Cell::from(entry.name.clone()).style(style),
+                    Cell::from(entry.format_label()),
+                    Cell::from(Line::from(format_bytes(entry.size)).right_aligned()),
+                ])
+            }
+        })
+        .collect();
+    let header = Row::new(vec![
+        Cell::from("Name"),
+        Cell::from("Format"),
+        Cell::from(Line::from("Size").right_aligned()),
+    ])
+    .style(
+        Style::default()
+            .fg(ctx.table_header)
+            .add_modifier(Modifier::UNDERLINED),
+    );
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(12),
+            Constraint::Length(11),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(table, chunks[0], buf, &mut browser.table_state);
+
+    let preview = match browser.selected_entry() {
+        None => " No matching files".to_string(),
+        Some(entry) if entry.is_dir => format!(" {}/", entry.name),
+        Some(entry) if entry.format.is_none() => {
+            format!(
+                " {} · {} · not a supported format",
+                entry.name,
+                format_bytes(entry.size)
+            )
+        }
+        Some(entry) => {
+            let rows = browser
+                .preview_rows
+                .map(|r| r.label())
+                .unwrap_or_else(|| "?".to_string());
+            format!(
+                " {} · {} · {} · {} rows",
+                entry.name,
+                entry.format_label(),
+                format_bytes(entry.size),
+                rows
+            )
+        }
+    };
+    Paragraph::new(preview)
+        .style(Style::default().fg(ctx.dimmed))
+        .render(chunks[1], buf);
+}
+
+/// Renders the sheet or table picker: one row per sheet, table or view (name, kind, columns,
+/// rows), the one shown now marked with "●". Records how many rows fit so the picker pages by
+/// screens.
+pub fn render_table_picker(
+    area: Rect,
+    buf: &mut Buffer,
+    picker: &mut crate::table_picker::TablePicker,
+    ctx: &RenderContext,
+) {
+    use ratatui::widgets::{Cell, Row, StatefulWidget, Table};
+
+    let popup_area = centered_rect(area, 60, 60);
+    Clear.render(popup_area, buf);
+    picker.visible = popup_area.height.saturating_sub(3).max(1) as usize;
+
+    let rows: Vec<Row> = picker
+        .tables
+        .iter()
+        .map(|table| {
+            let marker = if picker.current.as_deref() == Some(table.name.as_str()) {
+                Cell::from("●").style(Style::default().fg(ctx.success))
+            } else {
+                Cell::from(" ")
+            };
+            let rows = table.rows.map(|n| n.to_string()).unwrap_or_default();
+            Row::new(vec![
+                marker,
+                Cell::from(table.name.clone()).style(Style::default().fg(ctx.text_primary)),
+                Cell::from(table.kind).style(Style::default().fg(ctx.dimmed)),
+                Cell::from(Line::from(table.columns.to_string()).right_aligned()),
+                Cell::from(Line::from(rows).right_aligned()),
+            ])
+        })
+        .collect();
+    let header = Row::new(vec![
+        Cell::from(" "),
+        Cell::from("Name"),
+        Cell::from("Kind"),
+        Cell::from(Line::from("Columns").right_aligned()),
+        Cell::from(Line::from("Rows").right_aligned()),
+    ])
+    .style(
+        Style::default()
+            .fg(ctx.table_header)
+            .add_modifier(Modifier::UNDERLINED),
+    );
+    let file_name = picker
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| picker.path.display().to_string());
+    let title = format!(
+        " {} ({} {}s) ",
+        file_name,
+        picker.tables.len(),
+        picker.source.noun()
+    );
+    let hints = if picker.source == crate::table_picker::TableSource::Excel {
+        " p preview · Enter load · Esc close "
+    } else {
+        " Enter load · Esc close "
+    };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(11),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ctx.modal_border_active))
+            .title(title)
+            .title_bottom(Line::from(hints).right_aligned())
+            .style(Style::default().bg(ctx.background)),
+    )
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(table, popup_area, buf, &mut picker.table_state);
+}
+
+/// Renders the sheet preview: the sheet's first rows under their row numbers and column
+/// letters, the chosen header row highlighted and the rows above it dimmed, as they are skipped.
+pub fn render_sheet_preview(
+    area: Rect,
+    buf: &mut Buffer,
+    preview: &mut crate::excel_range::SheetPreview,
+    ctx: &RenderContext,
+) {
+    use ratatui::widgets::{Cell, Row, StatefulWidget, Table};
+
+    let popup_area = centered_rect(area, 80, 70);
+    Clear.render(popup_area, buf);
+
+    let width = preview.rows.iter().map(Vec::len).max().unwrap_or(0);
+    let last_row = preview.first_row as usize + preview.rows.len();
+    let number_width = last_row.to_string().len() as u16;
+    let mut widths = vec![Constraint::Length(number_width)];
+    widths.extend((0..width).map(|col| {
+        let longest = preview
+            .rows
+            .iter()
+            .filter_map(|row| row.get(col))
+            .map(|cell| cell.chars().count())
+            .max()
+            .unwrap_or(0);
+        Constraint::Length(longest.clamp(3, 20) as u16)
+    }));
+    let rows: Vec<Row> = preview
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let style = if i < preview.header {
+                Style::default().fg(ctx.dimmed)
+            } else if i == preview.header {
+                Style::default()
+                    .fg(ctx.table_header)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(ctx.text_primary)
+            };
+            let number = preview.first_row as usize + i + 1;
+            let mut cells = vec![Cell::from(Line::from(number.to_string()).right_aligned())
+                .style(Style::default().fg(ctx.dimmed))];
+            cells.extend(row.iter().map(|cell| Cell::from(cell.clone())));
+            Row::new(cells).style(style)
+        })
+        .collect();
+    let mut letters = vec![Cell::from(" ")];
+    letters.extend((0..width).map(|col| {
+        Cell::from(crate::excel_range::column_name(
+            preview.first_column + col as u32,
+        ))
+    }));
+    let header = Row::new(letters).style(
+        Style::default()
+            .fg(ctx.dimmed)
+            .add_modifier(Modifier::UNDERLINED),
+    );
+    let title = format!(" {} {} ", preview.sheet, preview.range);
+    let hints = format!(
+        " Header row {} · ↑↓ move · Enter load · Esc close ",
+        preview.header_row()
+    );
+    preview.table_state.select(Some(preview.header));
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(ctx.modal_border_active))
+                .title(title)
+                .title_bottom(Line::from(hints).right_aligned())
+                .style(Style::default().bg(ctx.background)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(table, popup_area, buf, &mut preview.table_state);
+}
+
+/// Renders the CSV dialect modal: why it opened, the settings (the focused one highlighted),
+/// the file's first lines with the header line highlighted and the lines above it dimmed, and
+/// the rows the settings parse (or why they don't parse).
+pub fn render_csv_dialect(
+    area: Rect,
+    buf: &mut Buffer,
+    modal: &crate::csv_dialect::CsvDialectModal,
+    ctx: &RenderContext,
+) {
+    use crate::csv_dialect::DialectField;
+    use ratatui::widgets::{Cell, Row, Table};
+
+    let popup_area = centered_rect(area, 80, 80);
+    Clear.render(popup_area, buf);
+    let file_name = modal
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| modal.path.display().to_string());
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .title(format!(" CSV dialect: {file_name} "))
+        .title_bottom(
+            Line::from(" ↑↓ setting · ←→ change · type to set · Enter load · Esc close ")
+                .right_aligned(),
+        )
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let raw_height = modal.lines.len().min(10) as u16 + 1;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(u16::from(modal.reason.is_some())),
+            Constraint::Length(DialectField::ALL.len() as u16 + 1),
+            Constraint::Length(raw_height + 1),
+            Constraint::Fill(1),
+        ])
+        .split(inner);
+
+    if let Some(reason) = &modal.reason {
+        Paragraph::new(format!(
+            "{reason}. Adjust the dialect and press Enter to reload."
+        ))
+        .style(Style::default().fg(ctx.warning))
+        .render(chunks[0], buf);
+    }
+
+    let settings: Vec<Line> = DialectField::ALL
+        .iter()
+        .map(|&field| {
+            let style = if field == modal.field {
+                Style::default()
+                    .fg(ctx.text_primary)
+                    .add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default().fg(ctx.text_primary)
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<12}", field.label()),
+                    Style::default().fg(ctx.dimmed),
+                ),
+                Span::styled(format!(" {} ", modal.value(field)), style),
+            ])
+        })
+        .collect();
+    Paragraph::new(settings).render(chunks[1], buf);
+
+    let header_row = modal.dialect.header_row;
+    let number_width = modal.lines.len().to_string().len();
+    let mut raw = vec![Line::from(Span::styled(
+        "File",
+        Style::default()
+            .fg(ctx.table_header)
+            .add_modifier(Modifier::UNDERLINED),
+    ))];
+    raw.extend(modal.lines.iter().take(10).enumerate().map(|(i, line)| {
+        let style = if i + 1 < header_row {
+            Style::default().fg(ctx.dimmed)
+        } else if i + 1 == header_row {
+            Style::default()
+                .fg(ctx.table_header)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(ctx.text_primary)
+        };
+        Line::from(vec![
+            Span::styled(
+                format!("{:>number_width$} ", i + 1),
+                Style::default().fg(ctx.dimmed),
+            ),
+            Span::styled(line.replace('\t', "→"), style),
+        ])
+    }));
+    Paragraph::new(raw).render(chunks[2], buf);
+
+    match &modal.preview {
+        Ok(df) => {
+            let columns = df.get_columns();
+            let heading = Row::new(
+                columns
+                    .iter()
+                    .map(|c| Cell::from(c.name().to_string()))
+                    .collect::<Vec<_>>(),
+            )
+            .style(
+                Style::default()
+                    .fg(ctx.table_header)
+                    .add_modifier(Modifier::UNDERLINED),
+            );
+            let rows: Vec<Row> = (0..df.height())
+                .map(|i| {
+                    Row::new(
+                        columns
+                            .iter()
+                            .map(|c| {
+                                let value = c.get(i).map(|v| v.str_value().to_string());
+                                Cell::from(value.unwrap_or_default())
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                    .style(Style::default().fg(ctx.text_primary))
+                })
+                .collect();
+            let widths = vec![Constraint::Min(6); columns.len().max(1)];
+            let title = format!(
+                "Parsed: {} column{}",
+                columns.len(),
+                if columns.len() == 1 { "" } else { "s" }
+            );
+            let table_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Fill(1)])
+                .split(chunks[3]);
+            Paragraph::new(title)
+                .style(Style::default().fg(ctx.dimmed))
+                .render(table_chunks[0], buf);
+            Widget::render(
+                Table::new(rows, widths).header(heading).column_spacing(2),
+                table_chunks[1],
+                buf,
+            );
+        }
+        Err(message) => {
+            Paragraph::new(format!("Does not parse: {message}"))
+                .style(Style::default().fg(ctx.error))
+                .wrap(ratatui::widgets::Wrap { trim: true })
+                .render(chunks[3], buf);
+        }
+    }
+}
+
+/// Renders the Load report panel: how many rows loaded and were left out, the rejected rows
+/// (line number, error, raw text) and the selected row's full text and error, wrapped.
+pub fn render_load_report(
+    area: Rect,
+    buf: &mut Buffer,
+    panel: &mut crate::load_report::LoadReportPanel,
+    ctx: &RenderContext,
+) {
+    use ratatui::widgets::{Cell, Row, StatefulWidget, Table, Wrap};
+
+    let popup_area = centered_rect(area, 80, 70);
+    Clear.render(popup_area, buf);
+    let report = &panel.report;
+    let name = report
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| report.path.display().to_string());
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .title(format!(" Load report: {name} "))
+        .title_bottom(Line::from(" ↑↓ move · Esc close ").right_aligned())
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(5),
+        ])
+        .split(inner);
+
+    let mut summary = format!(
+        "{} rows loaded, {} left out because they don't parse",
+        report.rows, report.skipped
+    );
+    if report.rejected.len() < report.skipped {
+        summary.push_str(&format!(" (first {} listed)", report.rejected.len()));
+    }
+    Paragraph::new(summary)
+        .style(Style::default().fg(ctx.warning))
+        .render(chunks[0], buf);
+
+    let line_width = report
+        .rejected
+        .last()
+        .map_or(4, |row| row.line.to_string().len().max(4)) as u16;
+    let error_width = report
+        .rejected
+        .iter()
+        .map(|row| row.error.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(5, 40) as u16;
+    let rows: Vec<Row> = report
+        .rejected
+        .iter()
+        .map(|row| {
+            Row::new(vec![
+                Cell::from(Line::from(row.line.to_string()).right_aligned())
+                    .style(Style::default().fg(ctx.dimmed)),
+                Cell::from(row.error.clone()).style(Style::default().fg(ctx.error)),
+                Cell::from(row.text.clone()).style(Style::default().fg(ctx.text_primary)),
+            ])
+        })
+        .collect();
+    let header = Row::new(vec!["Line", "Error", "Row"]).style(
+        Style::default()
+            .fg(ctx.table_header)
+            .add_modifier(Modifier::UNDERLINED),
+    );
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(line_width),
+            Constraint::Length(error_width),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .column_spacing(2)
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(table, chunks[1], buf, &mut panel.table_state);
+
+    if let Some(row) = panel
+        .table_state
+        .selected()
+        .and_then(|i| panel.report.rejected.get(i))
+    {
+        let detail = vec![
+            Line::from(Span::styled(
+                format!("Line {}: {}", row.line, row.error),
+                Style::default().fg(ctx.error),
+            )),
+            Line::from(Span::styled(
+                row.text.clone(),
+                Style::default().fg(ctx.text_primary),
+            )),
+        ];
+        Paragraph::new(detail)
+            .block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .border_style(Style::default().fg(ctx.dimmed)),
+            )
+            .wrap(Wrap { trim: false })
+            .render(chunks[2], buf);
+    }
+}
+
+/// Renders the schema step: each CSV column with its inferred type, the type it will load as
+/// (changed ones highlighted) and its first values.
+pub fn render_schema_modal(
+    area: Rect,
+    buf: &mut Buffer,
+    modal: &mut crate::schema_override::SchemaModal,
+    ctx: &RenderContext,
+) {
+    use crate::schema_override::dtype_name;
+    use ratatui::widgets::{Cell, Row, StatefulWidget, Table};
+
+    let popup_area = centered_rect(area, 80, 70);
+    Clear.render(popup_area, buf);
+    let name = modal
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| modal.path.display().to_string());
+    let enter = if modal.before_load { "load" } else { "reload" };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .title(format!(" Adjust schema: {name} "))
+        .title_bottom(
+            Line::from(format!(" ←→ type · r reset · Enter {enter} · Esc close ")).right_aligned(),
+        )
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Fill(1)])
+        .split(inner);
+
+    let changed = modal.changed();
+    let summary = match changed {
+        0 => format!("{} columns, types as inferred", modal.columns.len()),
+        1 => format!("{} columns, 1 type changed", modal.columns.len()),
+        n => format!("{} columns, {n} types changed", modal.columns.len()),
+    };
+    Paragraph::new(summary)
+        .style(Style::default().fg(ctx.dimmed))
+        .render(chunks[0], buf);
+
+    let name_width = modal
+        .columns
+        .iter()
+        .map(|c| c.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(6, 30) as u16;
+    let rows: Vec<Row> = modal
+        .columns
+        .iter()
+        .map(|column| {
+            let chosen_style = if column.changed() {
+                Style::default()
+                    .fg(ctx.warning)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(ctx.text_primary)
+            };
+            Row::new(vec![
+                Cell::from(column.name.clone()).style(Style::default().fg(ctx.text_primary)),
+                Cell::from(dtype_name(&column.inferred)).style(Style::default().fg(ctx.dimmed)),
+                Cell::from(dtype_name(&column.chosen)).style(chosen_style),
+                Cell::from(column.samples.join(", ")).style(Style::default().fg(ctx.dimmed)),
+            ])
+        })
+        .collect();
+    let header = Row::new(vec!["Column", "Inferred", "Type", "First values"]).style(
+        Style::default()
+            .fg(ctx.table_header)
+            .add_modifier(Modifier::UNDERLINED),
+    );
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(name_width),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .column_spacing(2)
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(table, chunks[1], buf, &mut modal.table_state);
+}
+
+/// Renders the Columns modal: columns in display order with whether they're shown, their source
+/// name when renamed and a marker for computed ones; the name being typed replaces the list's
+/// footer while renaming.
+pub fn render_columns_modal(
+    area: Rect,
+    buf: &mut Buffer,
+    modal: &mut crate::columns_modal::ColumnsModal,
+    ctx: &RenderContext,
+) {
+    use ratatui::widgets::{Cell, Row, StatefulWidget, Table};
+
+    let popup_area = centered_rect(area, 60, 70);
+    Clear.render(popup_area, buf);
+    let hints = if modal.rename_input.is_some() {
+        " Enter keep name · Esc cancel "
+    } else {
+        " Space show/hide · r rename · d duplicate · x remove · Shift+↑↓ move · Enter apply · Esc close "
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .title(" Columns ")
+        .title_bottom(Line::from(hints).right_aligned())
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+    let input_height = if modal.rename_input.is_some() { 3 } else { 0 };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(input_height),
+        ])
+        .split(inner);
+
+    let shown = modal.entries.iter().filter(|e| e.visible).count();
+    Paragraph::new(format!(
+        "{} columns, {} shown, {} hidden",
+        modal.entries.len(),
+        shown,
+        modal.entries.len() - shown
+    ))
+    .style(Style::default().fg(ctx.dimmed))
+    .render(chunks[0], buf);
+
+    let name_width = modal
+        .entries
+        .iter()
+        .map(|e| e.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(6, 40) as u16;
+    let rows: Vec<Row> = modal
+        .entries
+        .iter()
+        .map(|entry| {
+            let name_style = if !entry.visible {
+                Style::default().fg(ctx.dimmed)
+            } else if entry.renamed() || entry.source.is_none() {
+                Style::default()
+                    .fg(ctx.warning)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(ctx.text_primary)
+            };
+            let note = match (&entry.source, &entry.duplicate_of) {
+                (_, Some(original)) => format!("copy of {original}"),
+                (Some(source), _) if entry.renamed() => format!("was {source}"),
+                _ if entry.computed => "computed".to_string(),
+                _ => String::new(),
+            };
+            Row::new(vec![
+                Cell::from(if entry.visible { "[x]" } else { "[ ]" }),
+                Cell::from(entry.name.clone()).style(name_style),
+                Cell::from(note).style(Style::default().fg(ctx.dimmed)),
+            ])
+        })
+        .collect();
+    let header = Row::new(vec!["Shown", "Column", ""]).style(
+        Style::default()
+            .fg(ctx.table_header)
+            .add_modifier(Modifier::UNDERLINED),
+    );
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(5),
+            Constraint::Length(name_width),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header)
+    .column_spacing(2)
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(table, chunks[1], buf, &mut modal.table_state);
+
+    if let Some(input) = modal.rename_input.as_ref() {
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ctx.modal_border_active))
+            .title(" New name ");
+        let input_inner = input_block.inner(chunks[2]);
+        input_block.render(chunks[2], buf);
+        input.render(input_inner, buf);
+    }
+}
+
+/// Renders the command palette: the typed text over the matching columns and actions, each
+/// action with its key.
+pub fn render_command_palette(
+    area: Rect,
+    buf: &mut Buffer,
+    palette: &mut crate::command_palette::CommandPalette,
+    key_bindings: &crate::keymap::KeyBindings,
+    ctx: &RenderContext,
+) {
+    use crate::command_palette::PaletteItem;
+    use ratatui::widgets::{Cell, Row, StatefulWidget, Table};
+
+    let popup_area = centered_rect(area, 60, 60);
+    Clear.render(popup_area, buf);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .title(" Go to column or run action ")
+        .title_bottom(Line::from(" ↑↓ select · Enter go/run · Esc close ").right_aligned())
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Fill(1)])
+        .split(inner);
+
+    let input_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ctx.modal_border_active));
+    let input_inner = input_block.inner(chunks[0]);
+    input_block.render(chunks[0], buf);
+    (&palette.input).render(input_inner, buf);
+
+    let rows: Vec<Row> = palette
+        .items
+        .iter()
+        .map(|&item| {
+            let (kind, key) = match item {
+                PaletteItem::Column(i) => (
+                    format!("column {}", crate::column_ruler::column_letters(i)),
+                    String::new(),
+                ),
+                PaletteItem::Action(action) => ("action".to_string(), key_bindings.label(action)),
+            };
+            Row::new(vec![
+                Cell::from(palette.label(item)).style(Style::default().fg(ctx.text_primary)),
+                Cell::from(kind).style(Style::default().fg(ctx.dimmed)),
+                Cell::from(key).style(Style::default().fg(ctx.keybind_hints)),
+            ])
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(10),
+            Constraint::Length(12),
+        ],
+    )
+    .column_spacing(2)
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(table, chunks[1], buf, &mut palette.table_state);
+}
+
+/// Renders the Scripts menu: the scripts of the scripts directory, and the path being typed.
+pub fn render_scripts_modal(
+    area: Rect,
+    buf: &mut Buffer,
+    modal: &mut crate::script::ScriptsModal,
+    ctx: &RenderContext,
+) {
+    use ratatui::widgets::{Cell, Row, StatefulWidget, Table};
+
+    let popup_area = centered_rect(area, 60, 60);
+    Clear.render(popup_area, buf);
+    let hints = if modal.path_input.is_some() {
+        " Enter run · Esc cancel "
+    } else {
+        " ↑↓ select · Enter run · o open path · Esc close "
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .title(" Scripts ")
+        .title_bottom(Line::from(hints).right_aligned())
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+    let input_height = if modal.path_input.is_some() { 3 } else { 0 };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(input_height),
+        ])
+        .split(inner);
+
+    Paragraph::new(modal.dir.display().to_string())
+        .style(Style::default().fg(ctx.dimmed))
+        .render(chunks[0], buf);
+
+    if modal.scripts.is_empty() {
+        Paragraph::new("No *.rhai scripts here yet: add some, or press o to open one by path")
+            .style(Style::default().fg(ctx.dimmed))
+            .wrap(ratatui::widgets::Wrap { trim: true })
+            .render(chunks[1], buf);
+    } else {
+        let rows: Vec<Row> = modal
+            .scripts
+            .iter()
+            .map(|path| {
+                let name = path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                Row::new(vec![
+                    Cell::from(name).style(Style::default().fg(ctx.text_primary))
+                ])
+            })
+            .collect();
+        let table = Table::new(rows, [Constraint::Fill(1)])
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        StatefulWidget::render(table, chunks[1], buf, &mut modal.table_state);
+    }
+
+    if let Some(input) = modal.path_input.as_ref() {
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ctx.modal_border_active))
+            .title(" Script path ");
+        let input_inner = input_block.inner(chunks[2]);
+        input_block.render(chunks[2], buf);
+        input.render(input_inner, buf);
+    }
+}
+
+/// Renders the Saved Queries menu: the saved queries, those of the applied template, and the
+/// name being typed for the view's query.
+pub fn render_saved_queries_modal(
+    area: Rect,
+    buf: &mut Buffer,
+    modal: &mut crate::saved_queries::SavedQueriesModal,
+    ctx: &RenderContext,
+) {
+    use ratatui::widgets::{Cell, Row, StatefulWidget, Table};
+
+    let popup_area = centered_rect(area, 70, 60);
+    Clear.render(popup_area, buf);
+    let hints = if modal.name_input.is_some() {
+        " Enter save · Esc cancel "
+    } else {
+        " ↑↓ select · Enter apply · s save view's query · t keep in template · d delete · Esc close "
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .title(" Saved Queries ")
+        .title_bottom(Line::from(hints).right_aligned())
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+    let input_height = if modal.name_input.is_some() { 3 } else { 0 };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(input_height),
+        ])
+        .split(inner);
+
+    let current = match modal.current.as_ref() {
+        Some(query) if query.sql => format!("View (SQL): {}", query.query),
+        Some(query) => format!("View: {}", query.query),
+        None => "The view has no query".to_string(),
+    };
+    Paragraph::new(current)
+        .style(Style::default().fg(ctx.dimmed))
+        .render(chunks[0], buf);
+
+    let template_name = modal.template.as_ref().map(|(name, _)| name.as_str());
+    let entries = modal.entries();
+    if entries.is_empty() {
+        Paragraph::new("No saved queries yet: run a query with /, then press s here to name it")
+            .style(Style::default().fg(ctx.dimmed))
+            .wrap(ratatui::widgets::Wrap { trim: true })
+            .render(chunks[1], buf);
+    } else {
+        let rows: Vec<Row> = entries
+            .iter()
+            .map(|(query, from_template)| {
+                let origin = match (*from_template, template_name) {
+                    (true, Some(name)) => name.to_string(),
+                    _ if query.sql => "SQL".to_string(),
+                    _ => String::new(),
+                };
+                Row::new(vec![
+                    Cell::from(query.name.clone()).style(Style::default().fg(ctx.text_primary)),
+                    Cell::from(query.query.clone()).style(Style::default().fg(ctx.dimmed)),
+                    Cell::from(origin).style(Style::default().fg(ctx.dimmed)),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(25),
+                Constraint::Fill(1),
+                Constraint::Length(12),
+            ],
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        StatefulWidget::render(table, chunks[1], buf, &mut modal.table_state);
+    }
+
+    if let Some(input) = modal.name_input.as_ref() {
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ctx.modal_border_active))
+            .title(" Name ");
+        let input_inner = input_block.inner(chunks[2]);
+        input_block.render(chunks[2], buf);
+        input.render(input_inner, buf);
+    }
+}
+
+/// Renders the full value of a (usually truncated) cell in a floating box, wrapped to fit.
+pub fn render_cell_peek(
+    area: Rect,
+    buf: &mut Buffer,
+    column: &str,
+    value: &str,
+    ctx: &RenderContext,
+) {
+    let title = format!(" {column} ");
+    let max_width = (area.width as usize * 4 / 5).max(20);
+    let longest = value.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+    let width = (longest.max(title.chars().count()) + 4).min(max_width);
+    let inner_width = width.saturating_sub(4).max(1);
+    // Wrapped line count, so short values get a small box and long ones a tall one.
+    let lines: usize = value
+        .lines()
+        .map(|l| l.chars().count().div_ceil(inner_width).max(1))
+        .sum::<usize>()
+        .max(1);
+    let popup_area = centered_rect_fixed(area, width as u16, lines as u16 + 2);
+    Clear.render(popup_area, buf);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(title)
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .style(Style::default().bg(ctx.background));
+    let inner_area = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    Paragraph::new(value)
+        .style(Style::default().fg(ctx.text_primary).bg(ctx.background))
+        .wrap(ratatui::widgets::Wrap { trim: false })
+        .render(
+            Rect {
+                x: inner_area.x + 1,
+                width: inner_area.width.saturating_sub(2),
+                ..inner_area
+            },
+            buf,
+        );
+}
+
+/// Renders the Format modal: number style, decimals, thousands and currency symbol for numeric
+/// columns, or the date pattern for temporal ones, with a preview of the selected cell.
+pub fn render_format_modal(
+    area: Rect,
+    buf: &mut Buffer,
+    modal: &mut crate::format_modal::FormatModal,
+    ctx: &RenderContext,
+) {
+    use crate::format_modal::FormatFocus;
+
+    let height = if modal.numeric { 13 } else { 9 };
+    let popup_area = centered_rect_fixed(area, 56, height);
+    Clear.render(popup_area, buf);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(format!(" Format: {} ", modal.column))
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let preview = match (modal.format(), modal.sample.as_ref()) {
+        (Err(e), _) => e,
+        (Ok(_), None) => String::new(),
+        (Ok(_), Some(v)) if v.is_null() => String::new(),
+        (Ok(format), Some(v)) => format
+            .format_value(v, &ctx.locale)
+            .unwrap_or_else(|| ctx.locale.format_value(v).into_owned()),
+    };
+    let focus = modal.focus;
+    let color = |f: FormatFocus| {
+        if focus == f {
+            ctx.modal_border_active
+        } else {
+            ctx.modal_border
+        }
+    };
+    let selector = |label: &str, value: &str, f: FormatFocus, area: Rect, buf: &mut Buffer| {
+        Paragraph::new(format!(" {:<11}‹ {} ›", label, value))
+            .style(Style::default().fg(color(f)))
+            .render(area, buf);
+    };
+    let input = |title: &str,
+                 input: &mut crate::widgets::text_input::TextInput,
+                 f: FormatFocus,
+                 area: Rect,
+                 buf: &mut Buffer| {
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color(f)))
+            .title(title.to_string());
+        let input_inner = input_block.inner(area);
+        input_block.render(area, buf);
+        input.set_focused(focus == f);
+        (&*input).render(input_inner, buf);
+    };
+    let label_line = |label: &str, text: &str| {
+        Line::from(vec![
+            Span::styled(format!(" {:<11}", label), Style::default().fg(ctx.label)),
+            Span::styled(text.to_string(), Style::default().fg(ctx.text_primary)),
+        ])
+    };
+
+    if modal.numeric {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Style
+                Constraint::Length(3), // Decimals
+                Constraint::Length(1), // Thousands
+                Constraint::Length(3), // Currency symbol
+                Constraint::Length(1), // Preview
+                Constraint::Length(1), // Spacer
+                Constraint::Length(1), // Key hints
+            ])
+            .split(inner);
+        selector(
+            "Style:",
+            modal.style.as_str(),
+            FormatFocus::Style,
+            rows[0],
+            buf,
+        );
+        input(
+            " Decimals (empty: as is) ",
+            &mut modal.decimals_input,
+            FormatFocus::Decimals,
+            rows[1],
+            buf,
+        );
+        selector(
+            "Thousands:",
+            if modal.thousands { "on" } else { "off" },
+            FormatFocus::Thousands,
+            rows[2],
+            buf,
+        );
+        input(
+            &format!(
+                " Currency Symbol (empty: {}) ",
+                crate::column_format::DEFAULT_CURRENCY_SYMBOL
+            ),
+            &mut modal.currency_input,
+            FormatFocus::CurrencySymbol,
+            rows[3],
+            buf,
+        );
+        Paragraph::new(label_line("Preview:", &preview)).render(rows[4], buf);
+        Paragraph::new(" Tab: next field  ←/→: change  Enter: apply  Esc: cancel")
+            .style(Style::default().fg(ctx.modal_border))
+            .render(rows[6], buf);
+    } else {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Date format
+                Constraint::Length(1), // Example patterns
+                Constraint::Length(1), // Preview
+                Constraint::Length(1), // Spacer
+                Constraint::Length(1), // Key hints
+            ])
+            .split(inner);
+        input(
+            " Date Format (strftime; empty: default) ",
+            &mut modal.date_format_input,
+            FormatFocus::DateFormat,
+            rows[0],
+            buf,
+        );
+        Paragraph::new(label_line("e.g.", "%d %b %Y   %m/%d/%Y %H:%M   %A")).render(rows[1], buf);
+        Paragraph::new(label_line("Preview:", &preview)).render(rows[2], buf);
+        Paragraph::new(" Enter: apply  Esc: cancel")
+            .style(Style::default().fg(ctx.modal_border))
+            .render(rows[4], buf);
+    }
+}
+
+/// Renders the Sampling modal: method, strata column (stratified only), sample size and seed.
+pub fn render_sampling_modal(
+    area: Rect,
+    buf: &mut Buffer,
+    modal: &mut crate::sampling_modal::SamplingModal,
+    ctx: &RenderContext,
+) {
+    use crate::sampling::SamplingMethod;
+    use crate::sampling_modal::SamplingFocus;
+
+    let stratified = modal.method == SamplingMethod::Stratified;
+    let height = if stratified { 13 } else { 12 };
+    let popup_area = centered_rect_fixed(area, 60, height);
+    Clear.render(popup_area, buf);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(" Sampling ")
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let focus = modal.focus;
+    let color = |f: SamplingFocus| {
+        if focus == f {
+            ctx.modal_border_active
+        } else {
+            ctx.modal_border
+        }
+    };
+    let selector = |label: &str, value: &str, f: SamplingFocus, area: Rect, buf: &mut Buffer| {
+        Paragraph::new(format!(" {:<9}‹ {} ›", label, value))
+            .style(Style::default().fg(color(f)))
+            .render(area, buf);
+    };
+    let input = |title: &str,
+                 input: &mut crate::widgets::text_input::TextInput,
+                 f: SamplingFocus,
+                 area: Rect,
+                 buf: &mut Buffer| {
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color(f)))
+            .title(title.to_string());
+        let input_inner = input_block.inner(area);
+        input_block.render(area, buf);
+        input.set_focused(focus == f);
+        (&*input).render(input_inner, buf);
+    };
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),                              // Method
+            Constraint::Length(if stratified { 1 } else { 0 }), // Column
+            Constraint::Length(1),                              // Method description
+            Constraint::Length(3),                              // Sample size
+            Constraint::Length(3),                              // Seed
+            Constraint::Length(1),                              // Spacer
+            Constraint::Length(1),                              // Key hints
+        ])
+        .split(inner);
+    selector(
+        "Method:",
+        modal.method.as_str(),
+        SamplingFocus::Method,
+        rows[0],
+        buf,
+    );
+    if stratified {
+        let column = modal.columns.get(modal.column).map_or("", String::as_str);
+        selector("Column:", column, SamplingFocus::Column, rows[1], buf);
+    }
+    let description = match modal.method {
+        SamplingMethod::Random => "Rows drawn at random from the whole view",
+        SamplingMethod::Head => "The first rows of the view",
+        SamplingMethod::Stratified => "Random rows from each value, in proportion to its count",
+        SamplingMethod::Systematic => "Every k-th row, from an offset set by the seed",
+    };
+    Paragraph::new(Line::from(Span::styled(
+        format!(" {}", description),
+        Style::default().fg(ctx.label),
+    )))
+    .render(rows[2], buf);
+    input(
+        " Sample Size (larger views are sampled; empty: off) ",
+        &mut modal.sample_size_input,
+        SamplingFocus::SampleSize,
+        rows[3],
+        buf,
+    );
+    input(
+        " Seed (empty: new sample each time) ",
+        &mut modal.seed_input,
+        SamplingFocus::Seed,
+        rows[4],
+        buf,
+    );
+    Paragraph::new(" Tab: next field  ←/→: change  Enter: apply  Esc: cancel")
+        .style(Style::default().fg(ctx.modal_border))
+        .render(rows[6], buf);
+}
+
+/// Renders the GroupBy modal: group columns, value columns and aggregations side by side, with
+/// the chosen items checked.
+pub fn render_group_by_modal(
+    area: Rect,
+    buf: &mut Buffer,
+    modal: &crate::group_by_modal::GroupByModal,
+    ctx: &RenderContext,
+) {
+    use crate::group_by_modal::{GroupAggregation, GroupByFocus};
+    use ratatui::widgets::{List, ListItem, ListState, StatefulWidget};
+
+    let list_rows = modal.columns.len().max(GroupAggregation::ALL.len()) as u16;
+    let height = (list_rows + 5).min(area.height.saturating_sub(2)).max(8);
+    let popup_area = centered_rect_fixed(area, 78, height);
+    Clear.render(popup_area, buf);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(" Group By ")
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(inner);
+    let panels = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(37),
+            Constraint::Percentage(37),
+            Constraint::Percentage(26),
+        ])
+        .split(rows[0]);
+
+    let mut panel =
+        |title: &str, items: Vec<(String, bool)>, cursor: usize, f: GroupByFocus, area: Rect| {
+            let focused = modal.focus == f;
+            let list_block = Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(if focused {
+                    ctx.modal_border_active
+                } else {
+                    ctx.modal_border
+                }))
+                .title(title.to_string());
+            let items: Vec<ListItem> = items
+                .into_iter()
+                .map(|(name, checked)| {
+                    let style = if checked {
+                        Style::default().fg(ctx.modal_border_active)
+                    } else {
+                        Style::default().fg(ctx.text_primary)
+                    };
+                    let mark = if checked { "[x]" } else { "[ ]" };
+                    ListItem::new(Line::from(Span::styled(
+                        format!("{} {}", mark, name),
+                        style,
+                    )))
+                })
+                .collect();
+            let list = List::new(items)
+                .block(list_block)
+                .highlight_style(if focused {
+                    Style::default().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default()
+                });
+            let mut state = ListState::default().with_selected(Some(cursor));
+            StatefulWidget::render(list, area, buf, &mut state);
+        };
+
+    panel(
+        " Group by ",
+        modal
+            .columns
+            .iter()
+            .map(|c| (c.clone(), modal.group_columns.contains(c)))
+            .collect(),
+        modal.group_cursor,
+        GroupByFocus::GroupColumns,
+        panels[0],
+    );
+    panel(
+        " Aggregate ",
+        modal
+            .value_pool()
+            .into_iter()
+            .map(|c| (c.clone(), modal.value_columns.contains(c)))
+            .collect(),
+        modal.value_cursor,
+        GroupByFocus::ValueColumns,
+        panels[1],
+    );
+    panel(
+        " With ",
+        GroupAggregation::ALL
+            .iter()
+            .map(|a| (a.as_str().to_string(), modal.aggregations.contains(a)))
+            .collect(),
+        modal.aggregation_cursor,
+        GroupByFocus::Aggregations,
+        panels[2],
+    );
+    Paragraph::new(" Tab/←/→: list  ↑/↓: move  Space: toggle  Enter: apply  Esc: cancel")
+        .style(Style::default().fg(ctx.modal_border))
+        .render(rows[1], buf);
+}
+
+/// Renders the Resample modal: time column, interval and empty-window selectors above the
+/// numeric columns, each with its aggregation.
+pub fn render_resample_modal(
+    area: Rect,
+    buf: &mut Buffer,
+    modal: &mut crate::resample_modal::ResampleModal,
+    ctx: &RenderContext,
+) {
+    use crate::resample_modal::{ResampleFocus, INTERVAL_EXAMPLES};
+    use ratatui::widgets::{List, ListItem, ListState, StatefulWidget};
+
+    let list_rows = modal.columns.len().max(1) as u16;
+    let height = (list_rows + 11).min(area.height.saturating_sub(2)).max(14);
+    let popup_area = centered_rect_fixed(area, 64, height);
+    Clear.render(popup_area, buf);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(" Resample ")
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let focus = modal.focus;
+    let color = |f: ResampleFocus| {
+        if focus == f {
+            ctx.modal_border_active
+        } else {
+            ctx.modal_border
+        }
+    };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Time column
+            Constraint::Length(3), // Interval
+            Constraint::Length(1), // Empty windows
+            Constraint::Min(3),    // Columns
+            Constraint::Length(1), // Key hints
+        ])
+        .split(inner);
+
+    let time_column = modal
+        .time_columns
+        .get(modal.time_column)
+        .map_or("", String::as_str);
+    Paragraph::new(format!(" {:<15}‹ {} ›", "Time column:", time_column))
+        .style(Style::default().fg(color(ResampleFocus::TimeColumn)))
+        .render(rows[0], buf);
+
+    let input_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(color(ResampleFocus::Interval)))
+        .title(format!(" Interval (e.g. {}) ", INTERVAL_EXAMPLES));
+    let input_inner = input_block.inner(rows[1]);
+    input_block.render(rows[1], buf);
+    modal
+        .interval_input
+        .set_focused(focus == ResampleFocus::Interval);
+    (&modal.interval_input).render(input_inner, buf);
+
+    Paragraph::new(format!(
+        " {:<15}‹ {} ›",
+        "Empty windows:",
+        modal.empty_windows.as_str()
+    ))
+    .style(Style::default().fg(color(ResampleFocus::EmptyWindows)))
+    .render(rows[2], buf);
+
+    let focused = focus == ResampleFocus::Columns;
+    let list_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(color(ResampleFocus::Columns)))
+        .title(" Columns ");
+    let items: Vec<ListItem> = modal
+        .columns
+        .iter()
+        .map(|c| {
+            let (mark, style) = if c.included {
+                ("[x]", Style::default().fg(ctx.modal_border_active))
+            } else {
+                ("[ ]", Style::default().fg(ctx.text_primary))
+            };
+            ListItem::new(Line::from(Span::styled(
+                format!("{} {} ‹ {} ›", mark, c.column, c.aggregation.as_str()),
+                style,
+            )))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(list_block)
+        .highlight_style(if focused {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        });
+    let mut state = ListState::default().with_selected(Some(modal.column_cursor));
+    StatefulWidget::render(list, rows[3], buf, &mut state);
+
+    Paragraph::new(" Tab: next field  ←/→: change  ↑/↓: column  Space: keep  Enter: apply")
+        .style(Style::default().fg(ctx.modal_border))
+        .render(rows[4], buf);
+}
+
+/// Renders the Time Zone modal: action, target zone and the first values before and after.
+pub fn render_time_zone_modal(
+    area: Rect,
+    buf: &mut Buffer,
+    modal: &mut crate::time_zone_modal::TimeZoneModal,
+    ctx: &RenderContext,
+) {
+    use crate::time_zone_modal::{TimeZoneFocus, TIME_ZONE_EXAMPLES};
+
+    let preview_rows = match &modal.preview {
+        Some(Ok(values)) => values.len().max(1) as u16,
+        _ => 1,
+    };
+    let height = (preview_rows + 10).min(area.height.saturating_sub(2));
+    let popup_area = centered_rect_fixed(area, 72, height);
+    Clear.render(popup_area, buf);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(format!(" Time Zone: {} ", modal.column))
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let focus = modal.focus;
+    let color = |f: TimeZoneFocus| {
+        if focus == f {
+            ctx.modal_border_active
+        } else {
+            ctx.modal_border
+        }
+    };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),            // Current zone
+            Constraint::Length(1),            // Action
+            Constraint::Length(3),            // Zone
+            Constraint::Length(1),            // Spacer
+            Constraint::Length(preview_rows), // Preview
+            Constraint::Min(0),               // Spacer
+            Constraint::Length(1),            // Key hints
+        ])
+        .split(inner);
+
+    Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!(" {:<10}", "Current:"),
+            Style::default().fg(ctx.label),
+        ),
+        Span::styled(
+            modal
+                .current
+                .as_deref()
+                .unwrap_or("naive (no zone)")
+                .to_string(),
+            Style::default().fg(ctx.text_primary),
+        ),
+    ]))
+    .render(rows[0], buf);
+    Paragraph::new(format!(
+        " {:<10}‹ {} ›",
+        "Action:",
+        modal.action.as_str(modal.aware())
+    ))
+    .style(Style::default().fg(color(TimeZoneFocus::Action)))
+    .render(rows[1], buf);
+
+    if modal.action.needs_zone() {
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color(TimeZoneFocus::Zone)))
+            .title(format!(" Zone (e.g. {}) ", TIME_ZONE_EXAMPLES));
+        let input_inner = input_block.inner(rows[2]);
+        input_block.render(rows[2], buf);
+        modal.zone_input.set_focused(focus == TimeZoneFocus::Zone);
+        (&modal.zone_input).render(input_inner, buf);
+    }
+
+    let preview_lines: Vec<Line> = match &modal.preview {
+        None => vec![],
+        Some(Ok(values)) => values
+            .iter()
+            .map(|(before, after)| {
+                Line::from(vec![
+                    Span::styled(format!(" {}", before), Style::default().fg(ctx.label)),
+                    Span::styled("  →  ", Style::default().fg(ctx.modal_border)),
+                    Span::styled(after.clone(), Style::default().fg(ctx.text_primary)),
+                ])
+            })
+            .collect(),
+        Some(Err(message)) => vec![Line::from(vec![
+            Span::styled(" Error: ", Style::default().fg(ctx.label)),
+            Span::styled(message.clone(), Style::default().fg(ctx.error)),
+        ])],
+    };
+    Paragraph::new(preview_lines).render(rows[4], buf);
+
+    Paragraph::new(" Tab: next field  ←/→: change action  Enter: apply  Esc: cancel")
+        .style(Style::default().fg(ctx.modal_border))
+        .render(rows[6], buf);
+}
+
+/// Renders the Cast modal: target type, date format, error handling and the first values before
+/// and after.
+pub fn render_cast_modal(
+    area: Rect,
+    buf: &mut Buffer,
+    modal: &mut crate::cast_modal::CastModal,
+    ctx: &RenderContext,
+) {
+    use crate::cast_modal::{CastFocus, CAST_FORMAT_EXAMPLES};
+    use crate::column_cast::CAST_TYPES;
+
+    let preview_rows = match &modal.preview {
+        Some(Ok(preview)) => preview.values.len().max(1) as u16 + 1,
+        _ => 1,
+    };
+    let height = (preview_rows + 11).min(area.height.saturating_sub(2));
+    let popup_area = centered_rect_fixed(area, 72, height);
+    Clear.render(popup_area, buf);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(format!(" Cast: {} ", modal.column))
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let focus = modal.focus;
+    let color = |f: CastFocus| {
+        if focus == f {
+            ctx.modal_border_active
+        } else {
+            ctx.modal_border
+        }
+    };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),            // Current type
+            Constraint::Length(1),            // Target type
+            Constraint::Length(3),            // Format
+            Constraint::Length(1),            // On error
+            Constraint::Length(1),            // Spacer
+            Constraint::Length(preview_rows), // Preview
+            Constraint::Min(0),               // Spacer
+            Constraint::Length(1),            // Key hints
+        ])
+        .split(inner);
+
+    Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!(" {:<10}", "Current:"),
+            Style::default().fg(ctx.label),
+        ),
+        Span::styled(
+            modal
+                .from
+                .as_ref()
+                .map(crate::schema_override::dtype_name)
+                .unwrap_or_default(),
+            Style::default().fg(ctx.text_primary),
+        ),
+    ]))
+    .render(rows[0], buf);
+    Paragraph::new(format!(" {:<10}‹ {} ›", "Type:", CAST_TYPES[modal.target]))
+        .style(Style::default().fg(color(CastFocus::Type)))
+        .render(rows[1], buf);
+
+    if modal.takes_format() {
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color(CastFocus::Format)))
+            .title(format!(
+                " Format, empty to infer (e.g. {}) ",
+                CAST_FORMAT_EXAMPLES
+            ));
+        let input_inner = input_block.inner(rows[2]);
+        input_block.render(rows[2], buf);
+        modal.format_input.set_focused(focus == CastFocus::Format);
+        (&modal.format_input).render(input_inner, buf);
+    }
+    Paragraph::new(format!(
+        " {:<10}‹ {} ›",
+        "On error:",
+        modal.strategy.as_str()
+    ))
+    .style(Style::default().fg(color(CastFocus::Strategy)))
+    .render(rows[3], buf);
+
+    let preview_lines: Vec<Line> = match &modal.preview {
+        None => vec![],
+        Some(Ok(preview)) => {
+            let mut lines: Vec<Line> = preview
+                .values
+                .iter()
+                .map(|(before, after)| {
+                    Line::from(vec![
+                        Span::styled(format!(" {}", before), Style::default().fg(ctx.label)),
+                        Span::styled("  →  ", Style::default().fg(ctx.modal_border)),
+                        Span::styled(after.clone(), Style::default().fg(ctx.text_primary)),
+                    ])
+                })
+                .collect();
+            if preview.failed > 0 {
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        " {} of {} values shown can't be converted",
+                        preview.failed,
+                        preview.values.len()
+                    ),
+                    Style::default().fg(ctx.warning),
+                )));
+            }
+            lines
+        }
+        Some(Err(message)) => vec![Line::from(vec![
+            Span::styled(" Error: ", Style::default().fg(ctx.label)),
+            Span::styled(message.clone(), Style::default().fg(ctx.error)),
+        ])],
+    };
+    Paragraph::new(preview_lines).render(rows[5], buf);
+
+    Paragraph::new(" Tab: next field  ←/→: change  Enter: cast  Esc: cancel")
+        .style(Style::default().fg(ctx.modal_border))
+        .render(rows[7], buf);
+}
+
+/// Renders the Rolling modal: function, window, time column, numeric columns and a preview of
+/// the new columns' first values.
+pub fn render_rolling_modal(
+    area: Rect,
+    buf: &mut Buffer,
+    modal: &mut crate::rolling_modal::RollingModal,
+    ctx: &RenderContext,
+) {
+    use crate::rolling_modal::{RollingFocus, RollingWindowKind};
+    use ratatui::widgets::{List, ListItem, ListState, StatefulWidget};
+
+    let preview_rows = match &modal.preview {
+        Some(Ok(previews)) => previews.len().max(1) as u16,
+        _ => 1,
+    };
+    let list_rows = modal.columns.len().max(1) as u16;
+    let height = (list_rows + preview_rows + 11)
+        .min(area.height.saturating_sub(2))
+        .max(15);
+    let popup_area = centered_rect_fixed(area, 72, height);
+    Clear.render(popup_area, buf);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(" Rolling ")
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let focus = modal.focus;
+    let color = |f: RollingFocus| {
+        if focus == f {
+            ctx.modal_border_active
+        } else {
+            ctx.modal_border
+        }
+    };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),            // Function
+            Constraint::Length(1),            // Window kind
+            Constraint::Length(3),            // Window
+            Constraint::Length(1),            // Time column
+            Constraint::Min(3),               // Columns
+            Constraint::Length(preview_rows), // Preview
+            Constraint::Length(1),            // Key hints
+        ])
+        .split(inner);
+
+    let selector = |label: &str, value: &str, f: RollingFocus, area: Rect, buf: &mut Buffer| {
+        Paragraph::new(format!(" {:<13}‹ {} ›", label, value))
+            .style(Style::default().fg(color(f)))
+            .render(area, buf);
+    };
+    selector(
+        "Function:",
+        modal.function(),
+        RollingFocus::Function,
+        rows[0],
+        buf,
+    );
+    selector(
+        "Window over:",
+        modal.kind.as_str(),
+        RollingFocus::Kind,
+        rows[1],
+        buf,
+    );
+
+    let input_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(color(RollingFocus::Window)))
+        .title(format!(" Window ({}) ", modal.kind.examples()));
+    let input_inner = input_block.inner(rows[2]);
+    input_block.render(rows[2], buf);
+    modal
+        .window_input
+        .set_focused(focus == RollingFocus::Window);
+    (&modal.window_input).render(input_inner, buf);
+
+    if modal.kind == RollingWindowKind::Time {
+        let time_column = modal
+            .time_columns
+            .get(modal.time_column)
+            .map_or("(none)", String::as_str);
+        selector(
+            "Time column:",
+            time_column,
+            RollingFocus::TimeColumn,
+            rows[3],
+            buf,
+        );
+    }
+
+    let focused = focus == RollingFocus::Columns;
+    let list_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(color(RollingFocus::Columns)))
+        .title(" Columns ");
+    let items: Vec<ListItem> = modal
+        .columns
+        .iter()
+        .map(|c| {
+            let (mark, style) = if c.included {
+                ("[x]", Style::default().fg(ctx.modal_border_active))
+            } else {
+                ("[ ]", Style::default().fg(ctx.text_primary))
+            };
+            ListItem::new(Line::from(Span::styled(
+                format!("{} {}", mark, c.column),
+                style,
+            )))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(list_block)
+        .highlight_style(if focused {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        });
+    let mut state = ListState::default().with_selected(Some(modal.column_cursor));
+    StatefulWidget::render(list, rows[4], buf, &mut state);
+
+    let preview_lines: Vec<Line> = match &modal.preview {
+        None => vec![Line::from(Span::styled(
+            " Check a column to preview it",
+            Style::default().fg(ctx.modal_border),
+        ))],
+        Some(Ok(previews)) => previews
+            .iter()
+            .map(|(name, preview)| {
+                Line::from(vec![
+                    Span::styled(format!(" {}: ", name), Style::default().fg(ctx.label)),
+                    Span::styled(
+                        preview.values.join(", "),
+                        Style::default().fg(ctx.text_primary),
+                    ),
+                ])
+            })
+            .collect(),
+        Some(Err(message)) => vec![Line::from(vec![
+            Span::styled(" Error: ", Style::default().fg(ctx.label)),
+            Span::styled(message.clone(), Style::default().fg(ctx.error)),
+        ])],
+    };
+    Paragraph::new(preview_lines).render(rows[5], buf);
+
+    Paragraph::new(" Tab: next field  ←/→: change  ↑/↓: column  Space: check  Enter: add")
+        .style(Style::default().fg(ctx.modal_border))
+        .render(rows[6], buf);
+}
+
+/// Renders the New Column modal: name, expression and a preview of the first values.
+pub fn render_computed_column_modal(
+    area: Rect,
+    buf: &mut Buffer,
+    modal: &mut crate::computed_column_modal::ComputedColumnModal,
+    ctx: &RenderContext,
+) {
+    use crate::computed_column_modal::ComputedColumnFocus;
+
+    let popup_area = centered_rect_fixed(area, 64, 13);
+    Clear.render(popup_area, buf);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(" New Column ")
+        .border_style(Style::default().fg(ctx.modal_border_active))
+        .style(Style::default().bg(ctx.background));
+    let inner = block.inner(popup_area);
+    block.render(popup_area, buf);
+
+    let focus = modal.focus;
+    let input = |title: &str,
+                 input: &mut crate::widgets::text_input::TextInput,
+                 f: ComputedColumnFocus,
+                 area: Rect,
+                 buf: &mut Buffer| {
+        let color = if focus == f {
+            ctx.modal_border_active
+        } else {
+            ctx.modal_border
+        };
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color))
+            .title(title.to_string());
+        let input_inner = input_block.inner(area);
+        input_block.render(area, buf);
+        input.set_focused(focus == f);
+        (&*input).render(input_inner, buf);
+    };
+    let label_line = |label: &str, text: &str, fg: Color| {
+        Line::from(vec![
+            Span::styled(format!(" {:<8}", label), Style::default().fg(ctx.label)),
+            Span::styled(text.to_string(), Style::default().fg(fg)),
+        ])
+    };
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Name
+            Constraint::Length(3), // Expression
+            Constraint::Length(1), // Type
+            Constraint::Length(1), // Values
+            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Key hints
+        ])
+        .split(inner);
+    input(
+        " Name ",
+        &mut modal.name_input,
+        ComputedColumnFocus::Name,
+        rows[0],
+        buf,
+    );
+    input(
+        " Expression (e.g. price * qty, col(\"ts\").dt.hour()) ",
+        &mut modal.expression_input,
+        ComputedColumnFocus::Expression,
+        rows[1],
+        buf,
+    );
+    match &modal.preview {
+        None => {}
+        Some(Ok(preview)) => {
+            Paragraph::new(label_line(
+                "Type:",
+                &preview.dtype.to_string(),
+                ctx.text_primary,
+            ))
+            .render(rows[2], buf);
+            Paragraph::new(label_line(
+                "Values:",
+                &preview.values.join(", "),
+                ctx.text_primary,
+            ))
+            .render(rows[3], buf);
+        }
+        Some(Err(message)) => {
+            Paragraph::new(label_line("Error:", message, ctx.error)).render(rows[2], buf);
+        }
+    }
+    Paragraph::new(" Tab: next field  Enter: add (empty expression: remove)  Esc: cancel")
+        .style(Style::default().fg(ctx.modal_border))
+        .render(rows[5], buf);
+}
+
 /// Renders the help overlay with wrapped text and scrollbar. Clamps and updates `scroll` so the caller can persist it.
 pub fn render_help_overlay(
     area: Rect,
@@ -8,6 +8,7 @@ use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::prelude::{StatefulWidget, Widget};
 use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{
     Block, BorderType, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Table, Tabs,
 };
@@ -76,6 +77,10 @@ pub fn render(area: Rect, buf: &mut Buffer, modal: &mut SortFilterModal, ctx: &R
             Constraint::Percentage(34),
         ])
         .split(chunks[2]);
+    modal.buttons.clear();
+    modal.buttons.add(footer_chunks[0], SortFilterFocus::Apply);
+    modal.buttons.add(footer_chunks[1], SortFilterFocus::Cancel);
+    modal.buttons.add(footer_chunks[2], SortFilterFocus::Clear);
 
     let mut apply_text_style = Style::default();
     let mut apply_border_style = Style::default();
@@ -138,7 +143,7 @@ fn render_filter_tab(
     buf: &mut Buffer,
     border_c: ratatui::style::Color,
     active_c: ratatui::style::Color,
-    _ctx: &RenderContext,
+    ctx: &RenderContext,
 ) {
     let fchunks = Layout::default()
         .direction(Direction::Vertical)
@@ -179,10 +184,10 @@ fn render_filter_tab(
         )
         .render(row_layout[0], buf);
 
-    let op_name = FilterOperator::iterator()
+    let op = FilterOperator::iterator()
         .nth(filter.new_operator_idx)
-        .unwrap_or(FilterOperator::Eq)
-        .as_str();
+        .unwrap_or(FilterOperator::Eq);
+    let op_name = op.as_str();
     let op_style = if filter.focus == FilterFocus::Operator {
         Style::default().fg(active_c)
     } else {
@@ -208,7 +213,11 @@ fn render_filter_tab(
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .title("Val")
+                .title(match op {
+                    FilterOperator::Between => "Val (low, high)",
+                    FilterOperator::In => "Val (a, b, ...)",
+                    _ => "Val",
+                })
                 .border_style(val_style),
         )
         .render(row_layout[2], buf);
@@ -257,6 +266,18 @@ fn render_filter_tab(
             } else {
                 "".to_string()
             };
+            if !s.operator.takes_value() {
+                return ListItem::new(format!("{}{} {}", prefix, s.column, s.operator.as_str()));
+            }
+            if matches!(s.operator, FilterOperator::Between | FilterOperator::In) {
+                return ListItem::new(format!(
+                    "{}{} {} ({})",
+                    prefix,
+                    s.column,
+                    s.operator.as_str(),
+                    s.value
+                ));
+            }
             ListItem::new(format!(
                 "{}{}{}{}",
                 prefix,
@@ -281,6 +302,121 @@ fn render_filter_tab(
         )
         .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
     StatefulWidget::render(list, fchunks[2], buf, &mut filter.list_state);
+
+    if let Some(picker) = filter.value_picker.as_mut() {
+        let picker_area = Rect {
+            height: fchunks[1].height + fchunks[2].height,
+            ..fchunks[1]
+        };
+        render_value_picker(picker, picker_area, buf, active_c, ctx);
+    }
+    if let Some(range) = filter.date_range.as_ref() {
+        let range_area = Rect {
+            height: fchunks[1].height + fchunks[2].height,
+            ..fchunks[1]
+        };
+        render_date_range(range, range_area, buf, border_c, active_c, ctx);
+    }
+}
+
+/// The date range over the filter list: start and end inputs and the column's span.
+fn render_date_range(
+    range: &crate::filter_modal::DateRange,
+    area: Rect,
+    buf: &mut Buffer,
+    border_c: ratatui::style::Color,
+    active_c: ratatui::style::Color,
+    ctx: &RenderContext,
+) {
+    Clear.render(area, buf);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(format!(" Dates of {} ", range.column))
+        .title_bottom(Line::from(" Tab switch · Enter use · Esc cancel ").right_aligned())
+        .border_style(Style::default().fg(active_c));
+    let inner = block.inner(area);
+    block.render(area, buf);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+    for (i, (title, value, editing)) in [
+        ("From", &range.start, !range.editing_end),
+        ("To", &range.end, range.editing_end),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let style = Style::default().fg(if editing { active_c } else { border_c });
+        Paragraph::new(value.as_str())
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(title)
+                    .border_style(style),
+            )
+            .render(chunks[i], buf);
+    }
+    let span = match range.bounds.as_ref() {
+        Some((min, max)) => format!("Values run from {} to {}", min, max),
+        None => "Finding dates...".to_string(),
+    };
+    Paragraph::new(span)
+        .style(Style::default().fg(ctx.dimmed))
+        .render(chunks[2], buf);
+}
+
+/// The value picker over the filter list: the column's most frequent values with their counts,
+/// ticked ones marked.
+fn render_value_picker(
+    picker: &mut crate::filter_modal::ValuePicker,
+    area: Rect,
+    buf: &mut Buffer,
+    active_c: ratatui::style::Color,
+    ctx: &RenderContext,
+) {
+    Clear.render(area, buf);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(format!(" Values of {} ", picker.column))
+        .title_bottom(Line::from(" Space tick · Enter use · Esc cancel ").right_aligned())
+        .border_style(Style::default().fg(active_c));
+    let Some(values) = picker.values.as_ref() else {
+        Paragraph::new("Counting values...")
+            .style(Style::default().fg(ctx.dimmed))
+            .block(block)
+            .render(area, buf);
+        return;
+    };
+    if values.is_empty() {
+        Paragraph::new("No values to pick")
+            .style(Style::default().fg(ctx.dimmed))
+            .block(block)
+            .render(area, buf);
+        return;
+    }
+    let items: Vec<ListItem> = values
+        .iter()
+        .zip(&picker.picked)
+        .map(|((value, count), picked)| {
+            let mark = if *picked { "[x]" } else { "[ ]" };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{mark} {value} ")),
+                Span::styled(format!("({count})"), Style::default().fg(ctx.dimmed)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(list, area, buf, &mut picker.list_state);
 }
 
 fn render_sort_tab(
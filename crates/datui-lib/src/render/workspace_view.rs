@@ -0,0 +1,284 @@
+//! Workspace directory mode: file list, schema diff matrix, and the tab bar above the table.
+
+use crate::render::context::RenderContext;
+use crate::widgets::info::format_bytes;
+use crate::workspace::SchemaDiffView;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, BorderType, Borders, Cell, Clear, Paragraph, Row, StatefulWidget, Table, Widget,
+};
+
+/// Widest a file column of the schema diff gets; longer names are truncated.
+const DIFF_FILE_COL_MAX: usize = 24;
+
+/// Renders the workspace file list: name, format, size and estimated rows per file, with the
+/// file count per format in the title. Files that are open in a tab are marked with "●". When
+/// a schema diff is open it is shown instead.
+pub fn render(
+    area: Rect,
+    buf: &mut ratatui::buffer::Buffer,
+    app: &mut crate::App,
+    ctx: &RenderContext,
+) {
+    Clear.render(area, buf);
+    let Some(ws) = app.workspace.as_mut() else {
+        return;
+    };
+    if let Some(view) = ws.schema_diff.as_mut() {
+        render_schema_diff(area, buf, view, ctx);
+        return;
+    }
+
+    let rows: Vec<Row> = ws
+        .entries
+        .iter()
+        .map(|entry| {
+            let marker = match ws.tab_index(&entry.path) {
+                Some(i) if ws.current == Some(i) => {
+                    Cell::from("●").style(Style::default().fg(ctx.success))
+                }
+                Some(_) => Cell::from("●").style(Style::default().fg(ctx.dimmed)),
+                None => Cell::from(" "),
+            };
+            Row::new(vec![
+                marker,
+                Cell::from(entry.name.clone()),
+                Cell::from(entry.format_label()),
+                Cell::from(Line::from(format_bytes(entry.size)).right_aligned()),
+                Cell::from(Line::from(entry.rows.label()).right_aligned()),
+            ])
+        })
+        .collect();
+
+    let header = Row::new(vec![
+        Cell::from(" "),
+        Cell::from("Name"),
+        Cell::from("Format"),
+        Cell::from(Line::from("Size").right_aligned()),
+        Cell::from(Line::from("Rows").right_aligned()),
+    ])
+    .style(
+        Style::default()
+            .fg(ctx.table_header)
+            .add_modifier(Modifier::UNDERLINED),
+    );
+
+    let dir = ws
+        .dir
+        .as_ref()
+        .map(|d| d.display().to_string())
+        .unwrap_or_default();
+    let groups: Vec<String> = crate::workspace::format_groups(&ws.entries)
+        .into_iter()
+        .map(|(label, n)| format!("{n} {label}"))
+        .collect();
+    let title = format!(
+        " {} ({} files: {}) ",
+        dir,
+        ws.entries.len(),
+        groups.join(", ")
+    );
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(12),
+            Constraint::Length(11),
+            Constraint::Length(14),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(ctx.modal_border))
+            .title(title),
+    )
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    StatefulWidget::render(table, area, buf, &mut ws.table_state);
+}
+
+/// Columns × files matrix: dtype per cell, "—" where a column is missing. Drifting columns
+/// are highlighted, and cells that differ from the column's most common dtype stand out.
+/// Columns out of order are marked "~" and their cells show each file's position.
+fn render_schema_diff(
+    area: Rect,
+    buf: &mut ratatui::buffer::Buffer,
+    view: &mut SchemaDiffView,
+    ctx: &RenderContext,
+) {
+    let diff = &view.diff;
+    let visible = view.visible_columns();
+    let name_width = visible
+        .iter()
+        .map(|&i| diff.columns[i].name.chars().count())
+        .chain(std::iter::once("Column".len()))
+        .max()
+        .unwrap_or(0)
+        .min(32) as u16;
+
+    // File columns that fit after the marker and name columns, starting at file_offset.
+    let mut remaining = area.width.saturating_sub(2 + 2 + name_width);
+    let mut files = Vec::new();
+    for f in view.file_offset..diff.files.len() {
+        let width = diff
+            .file_name(f)
+            .chars()
+            .count()
+            .clamp(8, DIFF_FILE_COL_MAX) as u16;
+        if width + 1 > remaining && !files.is_empty() {
+            break;
+        }
+        remaining = remaining.saturating_sub(width + 1);
+        files.push((f, width));
+    }
+
+    let header = Row::new(
+        [Cell::from(" "), Cell::from("Column")]
+            .into_iter()
+            .chain(files.iter().map(|&(f, _)| Cell::from(diff.file_name(f))))
+            .collect::<Vec<_>>(),
+    )
+    .style(
+        Style::default()
+            .fg(ctx.table_header)
+            .add_modifier(Modifier::UNDERLINED),
+    );
+
+    let rows: Vec<Row> =
+        visible
+            .iter()
+            .map(|&i| {
+                let column = &diff.columns[i];
+                let drift = diff.has_drift(column);
+                let marker = if drift {
+                    "!"
+                } else if diff.is_out_of_order(column) {
+                    "~"
+                } else {
+                    " "
+                };
+                let majority = diff.majority_dtype(column);
+                let name_style = if drift {
+                    Style::default()
+                        .fg(ctx.warning)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                let mut cells = vec![
+                    Cell::from(marker).style(Style::default().fg(ctx.warning)),
+                    Cell::from(column.name.clone()).style(name_style),
+                ];
+                cells.extend(files.iter().map(|&(f, _)| {
+                    match (&column.dtypes[f], &diff.errors[f]) {
+                        (_, Some(_)) => Cell::from("error").style(Style::default().fg(ctx.dimmed)),
+                        (None, None) => Cell::from("—").style(Style::default().fg(ctx.error)),
+                        (Some(dtype), None) => {
+                            let style = if Some(dtype) != majority {
+                                Style::default().fg(ctx.warning)
+                            } else {
+                                Style::default()
+                            };
+                            Cell::from(diff.cell_text(column, f).unwrap_or_default()).style(style)
+                        }
+                    }
+                }));
+                Row::new(cells)
+            })
+            .collect();
+
+    let mut widths = vec![Constraint::Length(1), Constraint::Length(name_width)];
+    widths.extend(files.iter().map(|&(_, w)| Constraint::Length(w)));
+
+    let shown = match (files.first(), files.last()) {
+        (Some(&(first, _)), Some(&(last, _))) if files.len() < diff.files.len() => {
+            format!(", showing {}-{}", first + 1, last + 1)
+        }
+        _ => String::new(),
+    };
+    let title = format!(
+        " Schema diff: {} files{}, {} columns, {} with drift, {} out of order{} ",
+        diff.files.len(),
+        shown,
+        diff.columns.len(),
+        diff.drift_count(),
+        diff.out_of_order_count(),
+        if view.drift_only {
+            " (differences only)"
+        } else {
+            ""
+        }
+    );
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(ctx.modal_border))
+                .title(title),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    StatefulWidget::render(table, area, buf, &mut view.table_state);
+}
+
+/// When workspace tabs are open, draws a one-line tab bar at the top of `area` and returns
+/// the remaining area for the table. Otherwise returns `area` unchanged.
+pub fn render_tab_bar(
+    area: Rect,
+    buf: &mut ratatui::buffer::Buffer,
+    app: &crate::App,
+    ctx: &RenderContext,
+) -> Rect {
+    let Some(ws) = app.workspace.as_ref().filter(|w| !w.tabs.is_empty()) else {
+        return area;
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Fill(1)])
+        .split(area);
+
+    let mut line = Line::default();
+    for (i, tab) in ws.tabs.iter().enumerate() {
+        let style = if ws.current == Some(i) {
+            Style::default()
+                .fg(ctx.text_inverse)
+                .bg(ctx.modal_border_active)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(ctx.text_secondary)
+        };
+        // The current tab's file is still loading (or being exported)
+        let busy = if ws.current == Some(i) && app.loading_state.is_loading() {
+            " …"
+        } else {
+            ""
+        };
+        line.spans
+            .push(Span::styled(format!(" {}{} ", tab.title(), busy), style));
+        line.spans.push(Span::raw(" "));
+    }
+    let key_style = Style::default()
+        .fg(ctx.keybind_hints)
+        .add_modifier(Modifier::BOLD);
+    line.spans.push(Span::styled("[ ]", key_style));
+    line.spans.push(Span::raw(" Tabs "));
+    line.spans.push(Span::styled("o", key_style));
+    line.spans.push(Span::raw(" Open "));
+    line.spans.push(Span::styled("X", key_style));
+    line.spans.push(Span::raw(" Close"));
+    if ws.has_file_list() {
+        line.spans.push(Span::raw(" "));
+        line.spans.push(Span::styled("w", key_style));
+        line.spans.push(Span::raw(" Files"));
+    }
+    Paragraph::new(line).render(chunks[0], buf);
+    chunks[1]
+}
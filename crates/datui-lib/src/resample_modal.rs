@@ -0,0 +1,421 @@
+//! Resample modal: put the current view on a regular time grid with Polars' `group_by_dynamic`.
+//! Rows are grouped into windows of a fixed interval (1m, 1h, 1d, 1w, ...) on a temporal column
+//! and each numeric column is aggregated its own way. A coarser interval downsamples; a finer one
+//! upsamples, with empty windows kept as nulls or filled forward. The resampled frame replaces the
+//! view; `R` returns to the raw data. Opened with `W`; Enter applies, Esc discards.
+
+use crate::aggregate_peek::has_sum;
+use crate::group_by_modal::GroupAggregation;
+use crate::time_gaps::is_time_dtype;
+use crate::widgets::text_input::TextInput;
+use polars::prelude::*;
+
+/// Intervals offered as examples in the modal.
+pub const INTERVAL_EXAMPLES: &str = "1m, 15m, 1h, 1d, 1w, 1mo";
+
+/// What happens to windows without rows.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyWindows {
+    /// Leave them out (plain downsampling).
+    #[default]
+    Drop,
+    /// Keep them, with null values.
+    Null,
+    /// Keep them, with the previous window's values.
+    FillForward,
+}
+
+impl EmptyWindows {
+    pub const ALL: [EmptyWindows; 3] = [Self::Drop, Self::Null, Self::FillForward];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Drop => "drop",
+            Self::Null => "keep as null",
+            Self::FillForward => "fill forward",
+        }
+    }
+}
+
+/// A numeric column and how it is aggregated in each window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResampleColumn {
+    pub column: String,
+    pub aggregation: GroupAggregation,
+}
+
+/// Spec for a resample. Each value column keeps its name in the result, after the time column
+/// (the start of each window).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResampleSpec {
+    pub time_column: String,
+    /// Window size in Polars' duration language, e.g. "15m", "1h", "1d", "1mo".
+    pub every: String,
+    pub columns: Vec<ResampleColumn>,
+    pub empty_windows: EmptyWindows,
+}
+
+impl ResampleSpec {
+    /// Parsed interval, or a message when it isn't a positive duration.
+    fn interval(&self) -> Result<Duration, String> {
+        let text = self.every.trim();
+        let invalid = || {
+            format!(
+                "Invalid interval \"{}\": expected e.g. {}",
+                text, INTERVAL_EXAMPLES
+            )
+        };
+        let every = Duration::try_parse(text).map_err(|_| invalid())?;
+        if every.is_zero() || every.negative() {
+            return Err(invalid());
+        }
+        Ok(every)
+    }
+
+    /// Check the spec, returning a message for the first problem.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.time_column.is_empty() {
+            return Err("Resampling needs a date or datetime column".to_string());
+        }
+        self.interval()?;
+        if self.columns.is_empty() {
+            return Err("Choose at least one column to aggregate".to_string());
+        }
+        Ok(())
+    }
+
+    /// `lf` sorted by the time column and aggregated into windows. Keeping empty windows needs
+    /// the windows collected, so the result is then an in-memory frame.
+    pub fn apply(&self, lf: LazyFrame) -> color_eyre::Result<LazyFrame> {
+        self.validate().map_err(|e| color_eyre::eyre::eyre!(e))?;
+        let every = self.interval().map_err(|e| color_eyre::eyre::eyre!(e))?;
+        let time = self.time_column.as_str();
+        let aggs: Vec<Expr> = self
+            .columns
+            .iter()
+            .map(|c| c.aggregation.of(&c.column))
+            .collect();
+        let windows = lf
+            .sort([time], Default::default())
+            .group_by_dynamic(
+                col(time),
+                [],
+                DynamicGroupOptions {
+                    every,
+                    period: every,
+                    offset: Duration::parse("0ns"),
+                    ..Default::default()
+                },
+            )
+            .agg(aggs);
+        if self.empty_windows == EmptyWindows::Drop {
+            return Ok(windows);
+        }
+        let filled = windows
+            .collect()?
+            .upsample::<[PlSmallStr; 0]>([], time, every)?
+            .lazy();
+        Ok(match self.empty_windows {
+            EmptyWindows::FillForward => filled.with_columns(
+                self.columns
+                    .iter()
+                    .map(|c| {
+                        col(c.column.as_str())
+                            .fill_null_with_strategy(FillNullStrategy::Forward(None))
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            EmptyWindows::Drop | EmptyWindows::Null => filled,
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ResampleFocus {
+    #[default]
+    TimeColumn,
+    Interval,
+    EmptyWindows,
+    Columns,
+}
+
+/// A numeric column in the modal: whether it is kept and its aggregation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResampleColumnChoice {
+    pub column: String,
+    pub included: bool,
+    pub aggregation: GroupAggregation,
+}
+
+#[derive(Default)]
+pub struct ResampleModal {
+    pub active: bool,
+    pub focus: ResampleFocus,
+    /// Date and datetime columns of the view.
+    pub time_columns: Vec<String>,
+    /// Index into `time_columns`.
+    pub time_column: usize,
+    pub interval_input: TextInput,
+    pub empty_windows: EmptyWindows,
+    /// Numeric columns of the view, all kept (mean) at first.
+    pub columns: Vec<ResampleColumnChoice>,
+    pub column_cursor: usize,
+}
+
+impl ResampleModal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open for a view with `schema`, starting from `spec` (the last resample) where its columns
+    /// are still there.
+    pub fn open(
+        &mut self,
+        schema: &Schema,
+        spec: Option<&ResampleSpec>,
+        theme: &crate::config::Theme,
+    ) {
+        self.active = true;
+        self.focus = ResampleFocus::TimeColumn;
+        self.column_cursor = 0;
+        self.time_columns = schema
+            .iter()
+            .filter(|(_, dtype)| is_time_dtype(dtype))
+            .map(|(name, _)| name.to_string())
+            .collect();
+        self.time_column = spec
+            .and_then(|s| self.time_columns.iter().position(|c| *c == s.time_column))
+            .unwrap_or(0);
+        self.interval_input = TextInput::new().with_theme(theme);
+        self.interval_input
+            .set_value(spec.map_or_else(|| "1h".to_string(), |s| s.every.clone()));
+        self.empty_windows = spec.map(|s| s.empty_windows).unwrap_or_default();
+        self.columns = schema
+            .iter()
+            .filter(|(_, dtype)| has_sum(dtype))
+            .map(|(name, _)| {
+                let previous = spec.map(|s| s.columns.iter().find(|c| c.column == name.as_str()));
+                ResampleColumnChoice {
+                    column: name.to_string(),
+                    included: previous.is_none_or(|c| c.is_some()),
+                    aggregation: previous
+                        .flatten()
+                        .map_or(GroupAggregation::Mean, |c| c.aggregation),
+                }
+            })
+            .collect();
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+    }
+
+    /// The spec as edited, or a message when it isn't complete.
+    pub fn spec(&self) -> Result<ResampleSpec, String> {
+        let spec = ResampleSpec {
+            time_column: self
+                .time_columns
+                .get(self.time_column)
+                .cloned()
+                .unwrap_or_default(),
+            every: self.interval_input.value().trim().to_string(),
+            columns: self
+                .columns
+                .iter()
+                .filter(|c| c.included)
+                .map(|c| ResampleColumn {
+                    column: c.column.clone(),
+                    aggregation: c.aggregation,
+                })
+                .collect(),
+            empty_windows: self.empty_windows,
+        };
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    const ORDER: [ResampleFocus; 4] = [
+        ResampleFocus::TimeColumn,
+        ResampleFocus::Interval,
+        ResampleFocus::EmptyWindows,
+        ResampleFocus::Columns,
+    ];
+
+    pub fn next_focus(&mut self) {
+        self.move_focus(1);
+    }
+
+    pub fn prev_focus(&mut self) {
+        self.move_focus(Self::ORDER.len() - 1);
+    }
+
+    fn move_focus(&mut self, step: usize) {
+        let pos = Self::ORDER
+            .iter()
+            .position(|&f| f == self.focus)
+            .unwrap_or(0);
+        self.focus = Self::ORDER[(pos + step) % Self::ORDER.len()];
+    }
+
+    /// Change the focused selector forwards (`delta` > 0) or backwards: the time column, what
+    /// happens to empty windows, or the aggregation of the column under the cursor.
+    pub fn cycle(&mut self, delta: i32) {
+        let step = |idx: usize, len: usize| {
+            if delta < 0 {
+                (idx + len - 1) % len
+            } else {
+                (idx + 1) % len
+            }
+        };
+        match self.focus {
+            ResampleFocus::TimeColumn if !self.time_columns.is_empty() => {
+                self.time_column = step(self.time_column, self.time_columns.len());
+            }
+            ResampleFocus::EmptyWindows => {
+                let all = EmptyWindows::ALL;
+                let idx = all
+                    .iter()
+                    .position(|e| *e == self.empty_windows)
+                    .unwrap_or(0);
+                self.empty_windows = all[step(idx, all.len())];
+            }
+            ResampleFocus::Columns => {
+                if let Some(choice) = self.columns.get_mut(self.column_cursor) {
+                    let all = GroupAggregation::ALL;
+                    let idx = all
+                        .iter()
+                        .position(|a| *a == choice.aggregation)
+                        .unwrap_or(0);
+                    choice.aggregation = all[step(idx, all.len())];
+                    choice.included = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Move the column cursor down (`delta` > 0) or up, wrapping around.
+    pub fn move_cursor(&mut self, delta: i32) {
+        let len = self.columns.len();
+        if len == 0 {
+            self.column_cursor = 0;
+        } else if delta < 0 {
+            self.column_cursor = (self.column_cursor + len - 1) % len;
+        } else {
+            self.column_cursor = (self.column_cursor + 1) % len;
+        }
+    }
+
+    /// Keep or leave out the column under the cursor.
+    pub fn toggle(&mut self) {
+        if let Some(choice) = self.columns.get_mut(self.column_cursor) {
+            choice.included = !choice.included;
+        }
+    }
+
+    pub fn focused_input(&mut self) -> Option<&mut TextInput> {
+        match self.focus {
+            ResampleFocus::Interval => Some(&mut self.interval_input),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hourly() -> LazyFrame {
+        // 00:00, 00:30, 01:00, 03:00 on 2024-01-01
+        let minutes = [0i64, 30, 60, 180];
+        let base = 1_704_067_200_000i64;
+        df!(
+            "ts" => minutes.iter().map(|m| base + m * 60_000).collect::<Vec<_>>(),
+            "value" => [1.0f64, 3.0, 5.0, 7.0],
+            "label" => ["a", "b", "c", "d"],
+        )
+        .unwrap()
+        .lazy()
+        .with_column(col("ts").cast(DataType::Datetime(TimeUnit::Milliseconds, None)))
+    }
+
+    fn values(df: &DataFrame) -> Vec<Option<f64>> {
+        df.column("value")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn spec_downsamples_and_fills_empty_windows() {
+        let mut spec = ResampleSpec {
+            time_column: "ts".into(),
+            every: "1h".into(),
+            columns: vec![ResampleColumn {
+                column: "value".into(),
+                aggregation: GroupAggregation::Mean,
+            }],
+            empty_windows: EmptyWindows::Drop,
+        };
+        let df = spec.apply(hourly()).unwrap().collect().unwrap();
+        let names: Vec<&str> = df.get_column_names().iter().map(|n| n.as_str()).collect();
+        assert_eq!(names, ["ts", "value"]);
+        assert_eq!(values(&df), [Some(2.0), Some(5.0), Some(7.0)]);
+
+        spec.empty_windows = EmptyWindows::Null;
+        let df = spec.apply(hourly()).unwrap().collect().unwrap();
+        assert_eq!(values(&df), [Some(2.0), Some(5.0), None, Some(7.0)]);
+
+        spec.empty_windows = EmptyWindows::FillForward;
+        let df = spec.apply(hourly()).unwrap().collect().unwrap();
+        assert_eq!(values(&df), [Some(2.0), Some(5.0), Some(5.0), Some(7.0)]);
+
+        spec.every = "soon".into();
+        assert!(spec.validate().unwrap_err().contains("soon"));
+        spec.every = "0h".into();
+        assert!(spec.validate().is_err());
+    }
+
+    #[test]
+    fn modal_builds_spec_from_choices() {
+        let config = crate::config::AppConfig::default();
+        let theme = crate::config::Theme::from_config(&config.theme).unwrap();
+        let schema = hourly().collect_schema().unwrap();
+        let mut modal = ResampleModal::new();
+        modal.open(&schema, None, &theme);
+        assert_eq!(modal.time_columns, ["ts"]);
+        let spec = modal.spec().unwrap();
+        assert_eq!(spec.every, "1h");
+        assert_eq!(
+            spec.columns,
+            [ResampleColumn {
+                column: "value".into(),
+                aggregation: GroupAggregation::Mean,
+            }],
+            "only numeric columns, averaged"
+        );
+
+        modal.focus = ResampleFocus::Columns;
+        modal.cycle(1);
+        assert_eq!(
+            modal.spec().unwrap().columns[0].aggregation,
+            GroupAggregation::Count
+        );
+        modal.toggle();
+        assert!(modal.spec().unwrap_err().contains("at least one column"));
+        modal.toggle();
+
+        modal.prev_focus();
+        modal.cycle(1);
+        modal.prev_focus();
+        modal.focused_input().unwrap().set_value("1d".into());
+        let spec = modal.spec().unwrap();
+        assert_eq!(spec.empty_windows, EmptyWindows::Null);
+
+        // Reopening starts from the last spec
+        modal.open(&schema, Some(&spec), &theme);
+        assert_eq!(modal.spec(), Ok(spec));
+    }
+}
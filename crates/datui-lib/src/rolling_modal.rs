@@ -0,0 +1,376 @@
+//! Rolling modal: add rolling mean/sum/std/min/max columns over a window of rows (the last 7
+//! rows) or of time (the last hour of a date/datetime column). Each chosen numeric column gets a
+//! computed column such as `price_rolling_mean_7` = `col("price").rolling_mean(7)`, so the
+//! columns are saved in templates and can be edited with `+`. Opened with `L`; Enter adds the
+//! columns, Esc discards.
+
+use crate::aggregate_peek::has_sum;
+use crate::computed_column::ComputedColumn;
+use crate::computed_column_modal::{preview, ColumnPreview};
+use crate::query::ROLLING_FUNCTIONS;
+use crate::time_gaps::is_time_dtype;
+use crate::widgets::text_input::TextInput;
+use polars::prelude::*;
+
+/// Whether the window counts rows or spans a duration of a time column.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RollingWindowKind {
+    #[default]
+    Rows,
+    Time,
+}
+
+impl RollingWindowKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Rows => "rows",
+            Self::Time => "time",
+        }
+    }
+
+    /// Window the input starts with.
+    fn default_window(self) -> &'static str {
+        match self {
+            Self::Rows => "7",
+            Self::Time => "1d",
+        }
+    }
+
+    /// Examples shown in the window field's title.
+    pub fn examples(self) -> &'static str {
+        match self {
+            Self::Rows => "number of rows, e.g. 7",
+            Self::Time => "e.g. 30m, 1h, 7d",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RollingFocus {
+    #[default]
+    Function,
+    Kind,
+    Window,
+    TimeColumn,
+    Columns,
+}
+
+/// A numeric column in the modal and whether it gets a rolling column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollingColumnChoice {
+    pub column: String,
+    pub included: bool,
+}
+
+#[derive(Default)]
+pub struct RollingModal {
+    pub active: bool,
+    pub focus: RollingFocus,
+    /// Index into `ROLLING_FUNCTIONS`.
+    pub function: usize,
+    pub kind: RollingWindowKind,
+    pub window_input: TextInput,
+    /// Date and datetime columns of the view, for time windows.
+    pub time_columns: Vec<String>,
+    /// Index into `time_columns`.
+    pub time_column: usize,
+    /// Numeric columns of the view.
+    pub columns: Vec<RollingColumnChoice>,
+    pub column_cursor: usize,
+    /// Source the preview is evaluated on (the view's source, with its computed columns).
+    source: Option<LazyFrame>,
+    /// Name and first values of each new column, or the first error; None without columns.
+    pub preview: Option<Result<Vec<(String, ColumnPreview)>, String>>,
+}
+
+impl RollingModal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open on `source` with its numeric columns listed; those in `preselected` (the marked
+    /// columns, or the selected one) start checked.
+    pub fn open(
+        &mut self,
+        source: LazyFrame,
+        preselected: &[String],
+        theme: &crate::config::Theme,
+    ) {
+        self.active = true;
+        self.focus = RollingFocus::Function;
+        self.column_cursor = 0;
+        let schema = source.clone().collect_schema().unwrap_or_default();
+        self.time_columns = schema
+            .iter()
+            .filter(|(_, dtype)| is_time_dtype(dtype))
+            .map(|(name, _)| name.to_string())
+            .collect();
+        self.time_column = 0;
+        self.window_input = TextInput::new().with_theme(theme);
+        self.reset_window();
+        self.columns = schema
+            .iter()
+            .filter(|(_, dtype)| has_sum(dtype))
+            .map(|(name, _)| RollingColumnChoice {
+                column: name.to_string(),
+                included: preselected.iter().any(|c| c == name.as_str()),
+            })
+            .collect();
+        if let Some(first) = self.columns.iter().position(|c| c.included) {
+            self.column_cursor = first;
+        }
+        self.source = Some(source);
+        self.refresh_preview();
+    }
+
+    /// Put the default window for the kind in the input, with the cursor after it.
+    fn reset_window(&mut self) {
+        let window = self.kind.default_window();
+        self.window_input.set_value(window.to_string());
+        self.window_input.set_cursor(window.len());
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+        self.source = None;
+        self.preview = None;
+    }
+
+    pub fn function(&self) -> &'static str {
+        ROLLING_FUNCTIONS[self.function % ROLLING_FUNCTIONS.len()]
+    }
+
+    /// The window as typed, checked for its kind.
+    fn window(&self) -> Result<String, String> {
+        let window = self.window_input.value().trim().to_string();
+        let valid = match self.kind {
+            RollingWindowKind::Rows => window.parse::<usize>().is_ok_and(|n| n > 0),
+            RollingWindowKind::Time => {
+                Duration::try_parse(&window).is_ok_and(|d| !d.is_zero() && !d.negative())
+            }
+        };
+        if valid {
+            Ok(window)
+        } else {
+            Err(format!(
+                "Invalid window \"{}\": expected a {}",
+                window,
+                self.kind.examples()
+            ))
+        }
+    }
+
+    /// The computed columns to add, or a message when the choices aren't complete.
+    pub fn computed_columns(&self) -> Result<Vec<ComputedColumn>, String> {
+        let window = self.window()?;
+        let args = match self.kind {
+            RollingWindowKind::Rows => window.clone(),
+            RollingWindowKind::Time => {
+                let by = self
+                    .time_columns
+                    .get(self.time_column)
+                    .ok_or("Time windows need a date or datetime column")?;
+                format!("\"{}\", \"{}\"", window, by)
+            }
+        };
+        let function = self.function();
+        let columns: Vec<ComputedColumn> = self
+            .columns
+            .iter()
+            .filter(|c| c.included)
+            .map(|c| {
+                ComputedColumn::new(
+                    format!("{}_rolling_{}_{}", c.column, function, window),
+                    format!("col(\"{}\").rolling_{}({})", c.column, function, args),
+                )
+            })
+            .collect();
+        if columns.is_empty() {
+            return Err("Choose at least one column".to_string());
+        }
+        Ok(columns)
+    }
+
+    /// Evaluate the new columns on the first rows of the source. Call after each change.
+    pub fn refresh_preview(&mut self) {
+        let Some(source) = self.source.as_ref() else {
+            self.preview = None;
+            return;
+        };
+        self.preview = match self.computed_columns() {
+            Ok(columns) => Some(
+                columns
+                    .iter()
+                    .map(|c| preview(source, c).map(|p| (c.name.clone(), p)))
+                    .collect(),
+            ),
+            Err(_) if !self.columns.iter().any(|c| c.included) => None,
+            Err(message) => Some(Err(message)),
+        };
+    }
+
+    fn order(&self) -> &'static [RollingFocus] {
+        match self.kind {
+            RollingWindowKind::Rows => &[
+                RollingFocus::Function,
+                RollingFocus::Kind,
+                RollingFocus::Window,
+                RollingFocus::Columns,
+            ],
+            RollingWindowKind::Time => &[
+                RollingFocus::Function,
+                RollingFocus::Kind,
+                RollingFocus::Window,
+                RollingFocus::TimeColumn,
+                RollingFocus::Columns,
+            ],
+        }
+    }
+
+    pub fn next_focus(&mut self) {
+        self.move_focus(1);
+    }
+
+    pub fn prev_focus(&mut self) {
+        self.move_focus(self.order().len() - 1);
+    }
+
+    fn move_focus(&mut self, step: usize) {
+        let order = self.order();
+        let pos = order.iter().position(|&f| f == self.focus).unwrap_or(0);
+        self.focus = order[(pos + step) % order.len()];
+    }
+
+    /// Change the focused selector forwards (`delta` > 0) or backwards: the function, the kind
+    /// of window (resetting the window to that kind's default) or the time column.
+    pub fn cycle(&mut self, delta: i32) {
+        let step = |idx: usize, len: usize| {
+            if delta < 0 {
+                (idx + len - 1) % len
+            } else {
+                (idx + 1) % len
+            }
+        };
+        match self.focus {
+            RollingFocus::Function => {
+                self.function = step(self.function, ROLLING_FUNCTIONS.len());
+            }
+            RollingFocus::Kind => {
+                self.kind = match self.kind {
+                    RollingWindowKind::Rows => RollingWindowKind::Time,
+                    RollingWindowKind::Time => RollingWindowKind::Rows,
+                };
+                self.reset_window();
+            }
+            RollingFocus::TimeColumn if !self.time_columns.is_empty() => {
+                self.time_column = step(self.time_column, self.time_columns.len());
+            }
+            _ => {}
+        }
+    }
+
+    /// Move the column cursor down (`delta` > 0) or up, wrapping around.
+    pub fn move_cursor(&mut self, delta: i32) {
+        let len = self.columns.len();
+        if len == 0 {
+            self.column_cursor = 0;
+        } else if delta < 0 {
+            self.column_cursor = (self.column_cursor + len - 1) % len;
+        } else {
+            self.column_cursor = (self.column_cursor + 1) % len;
+        }
+    }
+
+    /// Check or uncheck the column under the cursor.
+    pub fn toggle(&mut self) {
+        if let Some(choice) = self.columns.get_mut(self.column_cursor) {
+            choice.included = !choice.included;
+        }
+    }
+
+    pub fn focused_input(&mut self) -> Option<&mut TextInput> {
+        match self.focus {
+            RollingFocus::Window => Some(&mut self.window_input),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme() -> crate::config::Theme {
+        let config = crate::config::AppConfig::default();
+        crate::config::Theme::from_config(&config.theme).unwrap()
+    }
+
+    fn source() -> LazyFrame {
+        let base = 1_704_067_200_000i64;
+        df!(
+            "ts" => [0i64, 1, 2, 4].iter().map(|d| base + d * 86_400_000).collect::<Vec<_>>(),
+            "price" => [1.0f64, 3.0, 5.0, 7.0],
+            "label" => ["a", "b", "c", "d"],
+        )
+        .unwrap()
+        .lazy()
+        .with_column(col("ts").cast(DataType::Datetime(TimeUnit::Milliseconds, None)))
+    }
+
+    #[test]
+    fn row_window_columns_preview_first_values() {
+        let mut modal = RollingModal::new();
+        modal.open(source(), &[], &theme());
+        assert_eq!(modal.time_columns, ["ts"]);
+        assert_eq!(
+            modal.columns,
+            [RollingColumnChoice {
+                column: "price".into(),
+                included: false,
+            }],
+            "only numeric columns, none marked"
+        );
+        assert!(modal.preview.is_none());
+        assert!(modal.computed_columns().is_err());
+
+        modal.toggle();
+        modal.focus = RollingFocus::Window;
+        modal.focused_input().unwrap().set_value("2".into());
+        modal.refresh_preview();
+        assert_eq!(
+            modal.computed_columns().unwrap(),
+            [ComputedColumn::new(
+                "price_rolling_mean_2",
+                "col(\"price\").rolling_mean(2)"
+            )]
+        );
+        let preview = modal.preview.clone().unwrap().unwrap();
+        assert_eq!(preview[0].0, "price_rolling_mean_2");
+        assert_eq!(preview[0].1.values, ["1.0", "2.0", "4.0", "6.0"]);
+
+        modal.focused_input().unwrap().set_value("two".into());
+        modal.refresh_preview();
+        assert!(modal.preview.clone().unwrap().is_err());
+    }
+
+    #[test]
+    fn time_window_uses_time_column() {
+        let mut modal = RollingModal::new();
+        modal.open(source(), &["price".to_string()], &theme());
+        modal.focus = RollingFocus::Function;
+        modal.cycle(1);
+        assert_eq!(modal.function(), "sum");
+        modal.next_focus();
+        modal.cycle(1);
+        assert_eq!(modal.kind, RollingWindowKind::Time);
+        assert_eq!(modal.window_input.value(), "1d");
+        modal.next_focus();
+        modal.focused_input().unwrap().set_value("2d".into());
+        modal.next_focus();
+        assert_eq!(modal.focus, RollingFocus::TimeColumn);
+        modal.refresh_preview();
+        let preview = modal.preview.clone().unwrap().unwrap();
+        assert_eq!(preview[0].0, "price_rolling_sum_2d");
+        assert_eq!(preview[0].1.values, ["1.0", "4.0", "8.0", "7.0"]);
+    }
+}
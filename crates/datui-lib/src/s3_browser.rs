@@ -0,0 +1,268 @@
+//! Object browser for S3 prefixes: `datui s3://bucket/prefix/` lists the sub-prefixes and
+//! objects under the prefix (name, size, modified) so one or several objects can be picked and
+//! loaded, instead of requiring a URL that points to an object.
+//!
+//! Listing uses `list_with_delimiter`, so only one level is fetched at a time; sub-prefixes are
+//! listed when entered.
+
+use crate::OpenOptions;
+use chrono::{DateTime, Utc};
+use color_eyre::Result;
+use object_store::path::Path as OsPath;
+use object_store::ObjectStore;
+use ratatui::widgets::TableState;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum S3EntryKind {
+    /// Keys sharing this prefix, shown like a directory.
+    Prefix,
+    Object {
+        size: u64,
+        modified: DateTime<Utc>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct S3Entry {
+    /// Name relative to the listed prefix; sub-prefixes end with '/'.
+    pub name: String,
+    pub kind: S3EntryKind,
+}
+
+impl S3Entry {
+    pub fn is_prefix(&self) -> bool {
+        self.kind == S3EntryKind::Prefix
+    }
+}
+
+/// List the sub-prefixes (first, by name) and objects (by name) directly under `prefix`
+/// (empty for the bucket root, else ending with '/').
+pub async fn list_prefix(store: Arc<dyn ObjectStore>, prefix: &str) -> Result<Vec<S3Entry>> {
+    let location = OsPath::from(prefix);
+    let result = store
+        .list_with_delimiter((!prefix.is_empty()).then_some(&location))
+        .await
+        .map_err(|e| color_eyre::eyre::eyre!("Could not list S3 prefix: {}", e))?;
+    let mut prefixes: Vec<S3Entry> = result
+        .common_prefixes
+        .iter()
+        .filter_map(|p| p.parts().last())
+        .map(|part| S3Entry {
+            name: format!("{}/", part.as_ref()),
+            kind: S3EntryKind::Prefix,
+        })
+        .collect();
+    let mut objects: Vec<S3Entry> = result
+        .objects
+        .iter()
+        .filter_map(|meta| {
+            let name = meta.location.filename()?;
+            Some(S3Entry {
+                name: name.to_string(),
+                kind: S3EntryKind::Object {
+                    size: meta.size,
+                    modified: meta.last_modified,
+                },
+            })
+        })
+        .collect();
+    prefixes.sort_by(|a, b| a.name.cmp(&b.name));
+    objects.sort_by(|a, b| a.name.cmp(&b.name));
+    prefixes.extend(objects);
+    Ok(prefixes)
+}
+
+/// The object browser popup for one listed prefix.
+#[derive(Clone)]
+pub struct S3Browser {
+    pub bucket: String,
+    /// Listed key prefix: empty for the bucket root, else ending with '/'.
+    pub prefix: String,
+    pub entries: Vec<S3Entry>,
+    pub table_state: TableState,
+    /// Objects marked with Space, by index into `entries`.
+    pub marked: BTreeSet<usize>,
+    /// Options the prefix was opened with; objects loaded from the browser use them too.
+    pub options: OpenOptions,
+    /// Rows that fit in the popup, as of the last render.
+    pub visible: usize,
+}
+
+impl S3Browser {
+    pub fn new(
+        bucket: String,
+        prefix: String,
+        entries: Vec<S3Entry>,
+        options: OpenOptions,
+    ) -> Self {
+        let mut table_state = TableState::default();
+        if !entries.is_empty() {
+            table_state.select(Some(0));
+        }
+        Self {
+            bucket,
+            prefix,
+            entries,
+            table_state,
+            marked: BTreeSet::new(),
+            options,
+            visible: 1,
+        }
+    }
+
+    /// `s3://bucket/prefix/`, as shown in the title.
+    pub fn location(&self) -> String {
+        format!("s3://{}/{}", self.bucket, self.prefix)
+    }
+
+    pub fn url_of(&self, entry: &S3Entry) -> String {
+        format!("{}{}", self.location(), entry.name)
+    }
+
+    pub fn selected_entry(&self) -> Option<&S3Entry> {
+        self.table_state
+            .selected()
+            .and_then(|i| self.entries.get(i))
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let current = self.table_state.selected().unwrap_or(0);
+        let last = self.entries.len() - 1;
+        self.table_state
+            .select(Some(current.saturating_add_signed(delta).min(last)));
+    }
+
+    /// Mark or unmark the highlighted object and move to the next entry. Prefixes can't be marked.
+    pub fn toggle_mark(&mut self) {
+        let Some(index) = self.table_state.selected() else {
+            return;
+        };
+        if self.entries[index].is_prefix() {
+            return;
+        }
+        if !self.marked.remove(&index) {
+            self.marked.insert(index);
+        }
+        self.move_by(1);
+    }
+
+    /// The highlighted sub-prefix's key, to list it.
+    pub fn selected_prefix(&self) -> Option<String> {
+        self.selected_entry()
+            .filter(|e| e.is_prefix())
+            .map(|e| format!("{}{}", self.prefix, e.name))
+    }
+
+    /// Key of the prefix one level up; None at the bucket root.
+    pub fn parent_prefix(&self) -> Option<String> {
+        let trimmed = self.prefix.strip_suffix('/')?;
+        Some(match trimmed.rfind('/') {
+            Some(i) => trimmed[..=i].to_string(),
+            None => String::new(),
+        })
+    }
+
+    /// URLs of the marked objects, or of the highlighted object when none are marked.
+    pub fn chosen_urls(&self) -> Vec<PathBuf> {
+        let chosen: Vec<&S3Entry> = if self.marked.is_empty() {
+            self.selected_entry()
+                .filter(|e| !e.is_prefix())
+                .into_iter()
+                .collect()
+        } else {
+            self.marked.iter().map(|&i| &self.entries[i]).collect()
+        };
+        chosen
+            .into_iter()
+            .map(|e| PathBuf::from(self.url_of(e)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use object_store::memory::InMemory;
+    use object_store::PutPayload;
+
+    fn object(name: &str) -> S3Entry {
+        S3Entry {
+            name: name.to_string(),
+            kind: S3EntryKind::Object {
+                size: 1,
+                modified: DateTime::<Utc>::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_list_prefix_lists_one_level() {
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        for key in ["data/b.csv", "data/a.parquet", "data/2024/x.csv", "top.csv"] {
+            rt.block_on(store.put(&OsPath::from(key), PutPayload::from_static(b"abc")))
+                .unwrap();
+        }
+        let entries = rt.block_on(list_prefix(store.clone(), "data/")).unwrap();
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["2024/", "a.parquet", "b.csv"]);
+        assert!(matches!(
+            entries[1].kind,
+            S3EntryKind::Object { size: 3, .. }
+        ));
+        let root = rt.block_on(list_prefix(store, "")).unwrap();
+        let names: Vec<&str> = root.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["data/", "top.csv"]);
+    }
+
+    #[test]
+    fn test_browser_marks_objects_and_navigates() {
+        let entries = vec![
+            S3Entry {
+                name: "2024/".to_string(),
+                kind: S3EntryKind::Prefix,
+            },
+            object("a.csv"),
+            object("b.csv"),
+        ];
+        let mut browser = S3Browser::new(
+            "bucket".to_string(),
+            "data/raw/".to_string(),
+            entries,
+            OpenOptions::default(),
+        );
+        assert_eq!(browser.selected_prefix().as_deref(), Some("data/raw/2024/"));
+        assert!(browser.chosen_urls().is_empty(), "a prefix is not loadable");
+        browser.toggle_mark();
+        assert!(browser.marked.is_empty(), "prefixes can't be marked");
+
+        browser.move_by(1);
+        assert_eq!(
+            browser.chosen_urls(),
+            [PathBuf::from("s3://bucket/data/raw/a.csv")]
+        );
+        browser.toggle_mark();
+        browser.toggle_mark();
+        assert_eq!(
+            browser.chosen_urls(),
+            [
+                PathBuf::from("s3://bucket/data/raw/a.csv"),
+                PathBuf::from("s3://bucket/data/raw/b.csv")
+            ]
+        );
+
+        assert_eq!(browser.parent_prefix().as_deref(), Some("data/"));
+        browser.prefix = "data/".to_string();
+        assert_eq!(browser.parent_prefix().as_deref(), Some(""));
+        browser.prefix = String::new();
+        assert_eq!(browser.parent_prefix(), None);
+    }
+}
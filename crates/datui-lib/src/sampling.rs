@@ -0,0 +1,337 @@
+//! Sampling strategies for views with at least `sampling_threshold` rows. Analysis, value counts,
+//! quick aggregates and charts work on a sample of the view drawn with the active strategy:
+//!
+//! - random: rows drawn uniformly from the whole view
+//! - head: the first rows
+//! - stratified: rows drawn at random within each value of a column, in proportion to how often
+//!   the value occurs (rare values keep their share of the sample)
+//! - systematic: every k-th row, starting at an offset picked by the seed
+//!
+//! The strategy is chosen in the Sampling modal (`M`). Sampled rows keep their order in the view.
+
+use crate::statistics::collect_lazy;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Row index column added to pick sampled rows; removed from the sample.
+const SAMPLE_ROW_COLUMN: &str = "__datui_sample_row";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SamplingMethod {
+    #[default]
+    Random,
+    Head,
+    Stratified,
+    Systematic,
+}
+
+impl SamplingMethod {
+    pub const ALL: [SamplingMethod; 4] =
+        [Self::Random, Self::Head, Self::Stratified, Self::Systematic];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Random => "Random",
+            Self::Head => "Head",
+            Self::Stratified => "Stratified",
+            Self::Systematic => "Systematic",
+        }
+    }
+
+    /// Whether the seed changes which rows are sampled.
+    pub fn uses_seed(self) -> bool {
+        self != Self::Head
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SamplingStrategy {
+    pub method: SamplingMethod,
+    /// Column whose values are the strata (stratified only).
+    pub column: Option<String>,
+    /// Fixed seed; None draws a new sample each time analysis is opened or resampled.
+    pub seed: Option<u64>,
+}
+
+impl SamplingStrategy {
+    /// Random sampling with a fixed seed.
+    pub fn random(seed: u64) -> Self {
+        Self {
+            seed: Some(seed),
+            ..Self::default()
+        }
+    }
+
+    /// This strategy with `seed` filled in when no fixed seed is set.
+    pub fn with_fallback_seed(&self, seed: u64) -> Self {
+        Self {
+            seed: Some(self.seed.unwrap_or(seed)),
+            ..self.clone()
+        }
+    }
+
+    /// Short description for the control bar, e.g. "stratified by region, seed 7".
+    pub fn label(&self) -> String {
+        let method = self.method.as_str().to_lowercase();
+        let method = match (self.method, &self.column) {
+            (SamplingMethod::Stratified, Some(column)) => format!("{} by {}", method, column),
+            _ => method,
+        };
+        match self.seed {
+            Some(seed) if self.method.uses_seed() => format!("{}, seed {}", method, seed),
+            _ => method,
+        }
+    }
+}
+
+/// Seeded pseudo-random numbers (SplitMix64); enough to pick rows reproducibly.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound` (`bound` > 0).
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+/// `n` distinct positions in `0..total` (`n` <= `total`), ascending (Floyd's algorithm).
+fn random_positions(total: usize, n: usize, rng: &mut SplitMix64) -> Vec<usize> {
+    let mut picked = HashSet::with_capacity(n);
+    for j in total - n..total {
+        let t = rng.below(j + 1);
+        if !picked.insert(t) {
+            picked.insert(j);
+        }
+    }
+    let mut positions: Vec<usize> = picked.into_iter().collect();
+    positions.sort_unstable();
+    positions
+}
+
+/// How many of `n` sampled rows each stratum gets: proportional to its size, with the rows
+/// left over after rounding down going to the largest remainders.
+fn stratum_quotas(sizes: &[usize], n: usize) -> Vec<usize> {
+    let total: usize = sizes.iter().sum();
+    if total == 0 {
+        return vec![0; sizes.len()];
+    }
+    let mut quotas: Vec<usize> = sizes.iter().map(|&s| s * n / total).collect();
+    let mut by_remainder: Vec<usize> = (0..sizes.len()).collect();
+    by_remainder.sort_by_key(|&i| std::cmp::Reverse(sizes[i] * n % total));
+    let assigned: usize = quotas.iter().sum();
+    for &i in by_remainder.iter().take(n.saturating_sub(assigned)) {
+        quotas[i] += 1;
+    }
+    quotas
+}
+
+/// Rows of `lf` to sample for the stratified strategy, ascending.
+fn stratified_rows(
+    lf: &LazyFrame,
+    column: &str,
+    n: usize,
+    rng: &mut SplitMix64,
+    polars_streaming: bool,
+) -> PolarsResult<Vec<usize>> {
+    let df = collect_lazy(
+        lf.clone().select([col(column).cast(DataType::String)]),
+        polars_streaming,
+    )?;
+    let values = df.column(column)?.str()?.clone();
+    let mut strata: HashMap<Option<&str>, Vec<usize>> = HashMap::new();
+    for (row, value) in values.iter().enumerate() {
+        strata.entry(value).or_default().push(row);
+    }
+    // Fixed stratum order so a seed always picks the same rows
+    let mut strata: Vec<Vec<usize>> = {
+        let mut keyed: Vec<_> = strata.into_iter().collect();
+        keyed.sort_by(|a, b| a.0.cmp(&b.0));
+        keyed.into_iter().map(|(_, rows)| rows).collect()
+    };
+    let sizes: Vec<usize> = strata.iter().map(Vec::len).collect();
+    let mut rows = Vec::with_capacity(n);
+    for (stratum, quota) in strata.iter_mut().zip(stratum_quotas(&sizes, n)) {
+        let positions = random_positions(stratum.len(), quota, rng);
+        rows.extend(positions.into_iter().map(|p| stratum[p]));
+    }
+    rows.sort_unstable();
+    Ok(rows)
+}
+
+/// The rows of `lf` at `rows` (ascending), in view order.
+fn take_rows(lf: &LazyFrame, rows: Vec<usize>) -> PolarsResult<LazyFrame> {
+    let columns: Vec<Expr> = lf
+        .clone()
+        .collect_schema()?
+        .iter_names()
+        .map(|name| col(name.clone()))
+        .collect();
+    let rows: Vec<IdxSize> = rows.into_iter().map(|r| r as IdxSize).collect();
+    let picked = DataFrame::new(vec![Column::new(SAMPLE_ROW_COLUMN.into(), rows)])?.lazy();
+    Ok(lf
+        .clone()
+        .with_row_index(SAMPLE_ROW_COLUMN, None)
+        .join(
+            picked,
+            [col(SAMPLE_ROW_COLUMN)],
+            [col(SAMPLE_ROW_COLUMN)],
+            JoinArgs::new(JoinType::Inner),
+        )
+        .sort([SAMPLE_ROW_COLUMN], SortMultipleOptions::default())
+        .select(columns))
+}
+
+/// A sample of (at most) `sample_size` rows of `lf`, which has `total_rows` rows, drawn with
+/// `strategy`. Views no larger than the sample are returned whole.
+pub fn sample_lazy(
+    lf: &LazyFrame,
+    total_rows: usize,
+    sample_size: usize,
+    strategy: &SamplingStrategy,
+    polars_streaming: bool,
+) -> PolarsResult<LazyFrame> {
+    if total_rows <= sample_size {
+        return Ok(lf.clone());
+    }
+    let seed = strategy.seed.unwrap_or_default();
+    let mut rng = SplitMix64(seed);
+    match strategy.method {
+        SamplingMethod::Head => Ok(lf.clone().limit(sample_size as IdxSize)),
+        SamplingMethod::Systematic => {
+            let step = (total_rows / sample_size.max(1)).max(1);
+            let offset = (seed % step as u64) as IdxSize;
+            let columns: Vec<Expr> = lf
+                .clone()
+                .collect_schema()?
+                .iter_names()
+                .map(|name| col(name.clone()))
+                .collect();
+            Ok(lf
+                .clone()
+                .with_row_index(SAMPLE_ROW_COLUMN, None)
+                .filter((col(SAMPLE_ROW_COLUMN) % lit(step as IdxSize)).eq(lit(offset)))
+                .limit(sample_size as IdxSize)
+                .select(columns))
+        }
+        SamplingMethod::Random => {
+            take_rows(lf, random_positions(total_rows, sample_size, &mut rng))
+        }
+        SamplingMethod::Stratified => {
+            let Some(column) = strategy.column.as_deref() else {
+                polars_bail!(InvalidOperation: "Stratified sampling needs a column");
+            };
+            let rows = stratified_rows(lf, column, sample_size, &mut rng, polars_streaming)?;
+            take_rows(lf, rows)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(n: usize) -> LazyFrame {
+        df!(
+            "id" => (0..n as i64).collect::<Vec<_>>(),
+            // One row in ten is "rare"
+            "kind" => (0..n).map(|i| if i % 10 == 0 { "rare" } else { "common" }).collect::<Vec<_>>(),
+        )
+        .unwrap()
+        .lazy()
+    }
+
+    fn ids(lf: LazyFrame) -> Vec<i64> {
+        let df = lf.collect().unwrap();
+        df.column("id")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect()
+    }
+
+    fn sample(strategy: &SamplingStrategy, n: usize) -> Vec<i64> {
+        ids(sample_lazy(&frame(1_000), 1_000, n, strategy, false).unwrap())
+    }
+
+    #[test]
+    fn head_and_systematic_are_ordered() {
+        let head = SamplingStrategy {
+            method: SamplingMethod::Head,
+            ..Default::default()
+        };
+        assert_eq!(sample(&head, 3), [0, 1, 2]);
+
+        let systematic = SamplingStrategy {
+            method: SamplingMethod::Systematic,
+            seed: Some(3),
+            ..Default::default()
+        };
+        let rows = sample(&systematic, 100);
+        assert_eq!(rows.len(), 100);
+        assert_eq!(&rows[..3], [3, 13, 23]);
+        // Small views aren't sampled
+        assert_eq!(
+            ids(sample_lazy(&frame(5), 5, 10, &systematic, false).unwrap()).len(),
+            5
+        );
+    }
+
+    #[test]
+    fn random_is_reproducible_for_a_seed() {
+        let rows = sample(&SamplingStrategy::random(42), 50);
+        assert_eq!(rows.len(), 50);
+        assert!(rows.windows(2).all(|w| w[0] < w[1]), "in view order");
+        assert_eq!(rows, sample(&SamplingStrategy::random(42), 50));
+        assert_ne!(rows, sample(&SamplingStrategy::random(43), 50));
+    }
+
+    #[test]
+    fn stratified_keeps_each_value_share() {
+        let strategy = SamplingStrategy {
+            method: SamplingMethod::Stratified,
+            column: Some("kind".into()),
+            seed: Some(1),
+        };
+        let rows = sample(&strategy, 100);
+        assert_eq!(rows.len(), 100);
+        assert_eq!(rows.iter().filter(|&&id| id % 10 == 0).count(), 10);
+
+        let missing = SamplingStrategy {
+            column: None,
+            ..strategy
+        };
+        assert!(sample_lazy(&frame(1_000), 1_000, 10, &missing, false).is_err());
+        assert_eq!(stratum_quotas(&[5, 3, 2], 5), [3, 1, 1]);
+    }
+
+    #[test]
+    fn label_names_the_strategy() {
+        let strategy = SamplingStrategy {
+            method: SamplingMethod::Stratified,
+            column: Some("region".into()),
+            seed: Some(7),
+        };
+        assert_eq!(strategy.label(), "stratified by region, seed 7");
+        let head = SamplingStrategy {
+            method: SamplingMethod::Head,
+            seed: Some(7),
+            ..Default::default()
+        };
+        assert_eq!(head.label(), "head");
+        assert_eq!(
+            SamplingStrategy::default().with_fallback_seed(9).seed,
+            Some(9)
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Sampling modal: how large views are sampled for analysis and charts (method, strata column,
+//! sample size and seed). Opened with `M`; Enter applies, Esc discards.
+
+use crate::sampling::{SamplingMethod, SamplingStrategy};
+use crate::widgets::text_input::TextInput;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SamplingFocus {
+    #[default]
+    Method,
+    Column,
+    SampleSize,
+    Seed,
+}
+
+#[derive(Default)]
+pub struct SamplingModal {
+    pub active: bool,
+    pub focus: SamplingFocus,
+    pub method: SamplingMethod,
+    /// Columns of the view, for the stratified method.
+    pub columns: Vec<String>,
+    /// Index into `columns` of the strata column.
+    pub column: usize,
+    pub sample_size_input: TextInput,
+    pub seed_input: TextInput,
+}
+
+impl SamplingModal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open with the current threshold and strategy. `columns` are the view's columns.
+    pub fn open(
+        &mut self,
+        threshold: Option<usize>,
+        strategy: &SamplingStrategy,
+        columns: Vec<String>,
+        theme: &crate::config::Theme,
+    ) {
+        self.active = true;
+        self.focus = SamplingFocus::Method;
+        self.method = strategy.method;
+        self.column = strategy
+            .column
+            .as_ref()
+            .and_then(|c| columns.iter().position(|name| name == c))
+            .unwrap_or(0);
+        self.columns = columns;
+        self.sample_size_input = TextInput::new().with_theme(theme);
+        self.sample_size_input
+            .set_value(threshold.map(|t| t.to_string()).unwrap_or_default());
+        self.seed_input = TextInput::new().with_theme(theme);
+        self.seed_input
+            .set_value(strategy.seed.map(|s| s.to_string()).unwrap_or_default());
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+        self.columns.clear();
+    }
+
+    /// Sample size (the sampling threshold; None turns sampling off) and strategy as edited, or a
+    /// message when a field is invalid.
+    pub fn settings(&self) -> Result<(Option<usize>, SamplingStrategy), String> {
+        let threshold = match self.sample_size_input.value().trim() {
+            "" => None,
+            text => match text.parse::<usize>() {
+                Ok(n) if n > 0 => Some(n),
+                _ => {
+                    return Err(format!(
+                        "Sample size must be a positive whole number: {}",
+                        text
+                    ))
+                }
+            },
+        };
+        let seed = match self.seed_input.value().trim() {
+            "" => None,
+            text => Some(
+                text.parse::<u64>()
+                    .map_err(|_| format!("Seed must be a whole number: {}", text))?,
+            ),
+        };
+        let column = if self.method == SamplingMethod::Stratified {
+            Some(
+                self.columns
+                    .get(self.column)
+                    .cloned()
+                    .ok_or("Stratified sampling needs a column")?,
+            )
+        } else {
+            None
+        };
+        Ok((
+            threshold,
+            SamplingStrategy {
+                method: self.method,
+                column,
+                seed,
+            },
+        ))
+    }
+
+    fn order(&self) -> Vec<SamplingFocus> {
+        let mut order = vec![SamplingFocus::Method];
+        if self.method == SamplingMethod::Stratified {
+            order.push(SamplingFocus::Column);
+        }
+        order.extend([SamplingFocus::SampleSize, SamplingFocus::Seed]);
+        order
+    }
+
+    pub fn next_focus(&mut self) {
+        self.move_focus(true);
+    }
+
+    pub fn prev_focus(&mut self) {
+        self.move_focus(false);
+    }
+
+    fn move_focus(&mut self, forward: bool) {
+        let order = self.order();
+        let pos = order.iter().position(|&f| f == self.focus).unwrap_or(0);
+        let next = if forward {
+            (pos + 1) % order.len()
+        } else {
+            (pos + order.len() - 1) % order.len()
+        };
+        self.focus = order[next];
+    }
+
+    /// Change the focused selector (method or column) forwards (`delta` > 0) or backwards.
+    /// No-op on text inputs.
+    pub fn cycle(&mut self, delta: i32) {
+        let step = |idx: usize, len: usize| {
+            if delta < 0 {
+                (idx + len - 1) % len
+            } else {
+                (idx + 1) % len
+            }
+        };
+        match self.focus {
+            SamplingFocus::Method => {
+                let all = SamplingMethod::ALL;
+                let idx = all.iter().position(|m| *m == self.method).unwrap_or(0);
+                self.method = all[step(idx, all.len())];
+            }
+            SamplingFocus::Column if !self.columns.is_empty() => {
+                self.column = step(self.column, self.columns.len());
+            }
+            _ => {}
+        }
+    }
+
+    pub fn focused_input(&mut self) -> Option<&mut TextInput> {
+        match self.focus {
+            SamplingFocus::SampleSize => Some(&mut self.sample_size_input),
+            SamplingFocus::Seed => Some(&mut self.seed_input),
+            SamplingFocus::Method | SamplingFocus::Column => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme() -> crate::config::Theme {
+        let config = crate::config::AppConfig::default();
+        crate::config::Theme::from_config(&config.theme).unwrap()
+    }
+
+    #[test]
+    fn edits_round_trip() {
+        let strategy = SamplingStrategy {
+            method: SamplingMethod::Stratified,
+            column: Some("region".into()),
+            seed: Some(7),
+        };
+        let mut modal = SamplingModal::new();
+        let columns = vec!["id".to_string(), "region".to_string()];
+        modal.open(Some(1_000), &strategy, columns, &theme());
+        assert_eq!(modal.settings(), Ok((Some(1_000), strategy)));
+
+        // The column selector is only in the tab order for stratified sampling
+        modal.next_focus();
+        assert_eq!(modal.focus, SamplingFocus::Column);
+        modal.cycle(1);
+        assert_eq!(modal.settings().unwrap().1.column.as_deref(), Some("id"));
+        modal.prev_focus();
+        modal.cycle(1);
+        assert_eq!(modal.method, SamplingMethod::Systematic);
+        modal.next_focus();
+        assert_eq!(modal.focus, SamplingFocus::SampleSize);
+        modal.focused_input().unwrap().set_value(String::new());
+        modal.next_focus();
+        modal.focused_input().unwrap().set_value("x".into());
+        assert!(modal.settings().unwrap_err().contains("Seed"));
+        modal.focused_input().unwrap().set_value(String::new());
+        let (threshold, edited) = modal.settings().unwrap();
+        assert_eq!(threshold, None);
+        assert_eq!(edited.column, None);
+        assert_eq!(edited.seed, None);
+    }
+}
@@ -0,0 +1,194 @@
+//! Saved queries: named queries kept in `queries.json` in the config directory, to apply again
+//! from the Saved Queries menu (`b`) on any data. A template can carry queries of its own, listed
+//! in the menu while the template is applied.
+
+use crate::config::ConfigManager;
+use crate::query::QueryEngine;
+use crate::widgets::text_input::TextInput;
+use color_eyre::Result;
+use ratatui::widgets::TableState;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// File of the config directory the saved queries are kept in.
+pub const SAVED_QUERIES_FILE: &str = "queries.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedQuery {
+    pub name: String,
+    pub query: String,
+    /// Run as SQL (the SQL tab) instead of datui's query syntax.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sql: bool,
+}
+
+impl SavedQuery {
+    pub fn engine(&self) -> QueryEngine {
+        if self.sql {
+            QueryEngine::Sql
+        } else {
+            QueryEngine::DatuiExpr
+        }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct SavedQueriesFile {
+    #[serde(default)]
+    queries: Vec<SavedQuery>,
+}
+
+/// The saved queries and the file they're kept in.
+pub struct SavedQueries {
+    path: PathBuf,
+    pub queries: Vec<SavedQuery>,
+}
+
+impl SavedQueries {
+    /// Read the saved queries of `config`'s directory; without a file there are none.
+    pub fn load(config: &ConfigManager) -> Result<Self> {
+        let path = config.config_path(SAVED_QUERIES_FILE);
+        let queries = if path.exists() {
+            let file: SavedQueriesFile = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+            file.queries
+        } else {
+            Vec::new()
+        };
+        Ok(Self { path, queries })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = SavedQueriesFile {
+            queries: self.queries.clone(),
+        };
+        std::fs::write(&self.path, serde_json::to_string_pretty(&file)?)?;
+        Ok(())
+    }
+
+    /// Add `query`, in place of the one with the same name if there is one.
+    pub fn upsert(&mut self, query: SavedQuery) {
+        upsert(&mut self.queries, query);
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<SavedQuery> {
+        let index = self.queries.iter().position(|q| q.name == name)?;
+        Some(self.queries.remove(index))
+    }
+}
+
+/// Add `query` to `queries`, in place of the one with the same name if there is one.
+pub fn upsert(queries: &mut Vec<SavedQuery>, query: SavedQuery) {
+    match queries.iter_mut().find(|q| q.name == query.name) {
+        Some(existing) => *existing = query,
+        None => queries.push(query),
+    }
+}
+
+/// Saved Queries menu: the saved queries, then those of the applied template.
+pub struct SavedQueriesModal {
+    pub saved: SavedQueries,
+    /// Name and queries of the applied template.
+    pub template: Option<(String, Vec<SavedQuery>)>,
+    /// Query of the view, offered by `s`.
+    pub current: Option<SavedQuery>,
+    pub table_state: TableState,
+    /// Name being typed for the view's query (`s`); None when picking from the list.
+    pub name_input: Option<TextInput>,
+}
+
+impl SavedQueriesModal {
+    pub fn new(
+        saved: SavedQueries,
+        template: Option<(String, Vec<SavedQuery>)>,
+        current: Option<SavedQuery>,
+    ) -> Self {
+        let mut modal = Self {
+            saved,
+            template,
+            current,
+            table_state: TableState::default(),
+            name_input: None,
+        };
+        modal.move_by(0);
+        modal
+    }
+
+    /// Saved queries then the template's, each with whether it comes from the template.
+    pub fn entries(&self) -> Vec<(&SavedQuery, bool)> {
+        let template = self.template.iter().flat_map(|(_, queries)| queries);
+        self.saved
+            .queries
+            .iter()
+            .map(|q| (q, false))
+            .chain(template.map(|q| (q, true)))
+            .collect()
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        let count = self.entries().len();
+        if count == 0 {
+            self.table_state.select(None);
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0);
+        self.table_state
+            .select(Some(selected.saturating_add_signed(delta).min(count - 1)));
+    }
+
+    pub fn selected(&self) -> Option<(&SavedQuery, bool)> {
+        self.entries().get(self.table_state.selected()?).copied()
+    }
+
+    /// Start typing the name to save the view's query under.
+    pub fn start_name_input(&mut self, theme: &crate::config::Theme) {
+        let mut input = TextInput::new().with_theme(theme);
+        input.set_focused(true);
+        self.name_input = Some(input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(name: &str, query: &str) -> SavedQuery {
+        SavedQuery {
+            name: name.to_string(),
+            query: query.to_string(),
+            sql: false,
+        }
+    }
+
+    #[test]
+    fn saved_queries_round_trip_and_replace_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ConfigManager::with_dir(dir.path().join("datui"));
+        let mut saved = SavedQueries::load(&config).unwrap();
+        assert!(saved.queries.is_empty());
+        saved.upsert(query("big", "select where price > 100"));
+        saved.upsert(query("by region", "select sum[price] by region"));
+        saved.upsert(query("big", "select where price > 1000"));
+        saved.save().unwrap();
+
+        let mut saved = SavedQueries::load(&config).unwrap();
+        assert_eq!(saved.queries.len(), 2);
+        assert_eq!(saved.queries[0].query, "select where price > 1000");
+        assert_eq!(saved.remove("by region").unwrap().name, "by region");
+        assert!(saved.remove("by region").is_none());
+    }
+
+    #[test]
+    fn menu_lists_template_queries_after_saved_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut saved = SavedQueries::load(&ConfigManager::with_dir(dir.path().into())).unwrap();
+        saved.upsert(query("a", "select a"));
+        let template = Some(("monthly".to_string(), vec![query("b", "select b")]));
+        let mut modal = SavedQueriesModal::new(saved, template, None);
+        assert_eq!(modal.selected().unwrap().0.name, "a");
+        modal.move_by(5);
+        assert_eq!(modal.selected(), Some((&query("b", "select b"), true)));
+    }
+}
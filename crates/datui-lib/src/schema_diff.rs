@@ -0,0 +1,508 @@
+//! Schema comparison across files: a columns × files matrix of presence and dtype that
+//! highlights drift (a column missing from some files, or stored with different types) and
+//! columns stored in a different order (which misaligns files concatenated by position).
+//!
+//! Used by the workspace file list and by `datui --schema-diff <PATHS>...`.
+
+use crate::source;
+use crate::widgets::datatable::DataTableState;
+use crate::workspace;
+use crate::{FileFormat, OpenOptions};
+use color_eyre::Result;
+use polars::prelude::{DataType, Schema};
+use std::path::{Path, PathBuf};
+
+/// One column of the union schema and its dtype in each compared file.
+#[derive(Debug, Clone)]
+pub struct ColumnPresence {
+    pub name: String,
+    /// Per file, in the order of [`SchemaDiff::files`]; None = column missing from that file.
+    pub dtypes: Vec<Option<DataType>>,
+    /// Per file: the column's position (0-based) in that file's schema.
+    pub positions: Vec<Option<usize>>,
+}
+
+/// Columns × files matrix of presence and dtype.
+#[derive(Debug, Clone)]
+pub struct SchemaDiff {
+    pub files: Vec<PathBuf>,
+    /// Per file: the error message when its schema could not be read.
+    pub errors: Vec<Option<String>>,
+    /// Union of all columns, in first-seen order.
+    pub columns: Vec<ColumnPresence>,
+}
+
+impl SchemaDiff {
+    /// Build the matrix from per-file schema results (same order as `files`).
+    pub fn from_schemas(
+        files: Vec<PathBuf>,
+        schemas: Vec<std::result::Result<Schema, String>>,
+    ) -> Self {
+        let mut columns: Vec<ColumnPresence> = Vec::new();
+        let mut errors = Vec::with_capacity(files.len());
+        for (file_idx, schema) in schemas.iter().enumerate() {
+            let schema = match schema {
+                Ok(schema) => {
+                    errors.push(None);
+                    schema
+                }
+                Err(e) => {
+                    errors.push(Some(e.clone()));
+                    continue;
+                }
+            };
+            for (position, (name, dtype)) in schema.iter().enumerate() {
+                let column = match columns.iter().position(|c| c.name == name.as_str()) {
+                    Some(i) => &mut columns[i],
+                    None => {
+                        columns.push(ColumnPresence {
+                            name: name.to_string(),
+                            dtypes: vec![None; files.len()],
+                            positions: vec![None; files.len()],
+                        });
+                        columns.last_mut().expect("just pushed")
+                    }
+                };
+                column.dtypes[file_idx] = Some(dtype.clone());
+                column.positions[file_idx] = Some(position);
+            }
+        }
+        Self {
+            files,
+            errors,
+            columns,
+        }
+    }
+
+    /// Read the schema of every file. A file that fails to read is kept in the matrix with
+    /// its error instead of aborting the comparison.
+    pub fn compute(files: Vec<PathBuf>, options: &OpenOptions) -> Self {
+        let schemas = files
+            .iter()
+            .map(|path| {
+                read_schema(path, options).map_err(|e| {
+                    crate::error_display::user_message_from_report(&e, Some(path.as_path()))
+                })
+            })
+            .collect();
+        Self::from_schemas(files, schemas)
+    }
+
+    /// Whether `column` drifts: missing from a readable file, or not the same dtype everywhere.
+    pub fn has_drift(&self, column: &ColumnPresence) -> bool {
+        let mut first: Option<&DataType> = None;
+        for (dtype, error) in column.dtypes.iter().zip(&self.errors) {
+            if error.is_some() {
+                continue;
+            }
+            match (dtype, first) {
+                (None, _) => return true,
+                (Some(d), None) => first = Some(d),
+                (Some(d), Some(f)) if d != f => return true,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    pub fn drift_count(&self) -> usize {
+        self.columns.iter().filter(|c| self.has_drift(c)).count()
+    }
+
+    /// Whether `column` is in every readable file.
+    fn is_shared(&self, column: &ColumnPresence) -> bool {
+        column
+            .dtypes
+            .iter()
+            .zip(&self.errors)
+            .all(|(dtype, error)| error.is_some() || dtype.is_some())
+    }
+
+    /// Whether `column` comes in a different place among the columns every readable file has,
+    /// so files concatenated by position would misalign it. Columns missing somewhere don't
+    /// count (they drift instead).
+    pub fn is_out_of_order(&self, column: &ColumnPresence) -> bool {
+        if !self.is_shared(column) {
+            return false;
+        }
+        // Place among the shared columns, per readable file
+        let mut ranks = (0..self.files.len())
+            .filter(|&f| self.errors[f].is_none())
+            .map(|f| {
+                self.columns
+                    .iter()
+                    .filter(|other| {
+                        self.is_shared(other) && other.positions[f] < column.positions[f]
+                    })
+                    .count()
+            });
+        let first = ranks.next();
+        ranks.any(|rank| Some(rank) != first)
+    }
+
+    pub fn out_of_order_count(&self) -> usize {
+        self.columns
+            .iter()
+            .filter(|c| self.is_out_of_order(c))
+            .count()
+    }
+
+    /// Whether `column` drifts or is out of order.
+    pub fn differs(&self, column: &ColumnPresence) -> bool {
+        self.has_drift(column) || self.is_out_of_order(column)
+    }
+
+    /// Text of `column`'s cell for file `index`: its dtype, followed by its position (1-based)
+    /// when the column is out of order.
+    pub fn cell_text(&self, column: &ColumnPresence, index: usize) -> Option<String> {
+        let dtype = column.dtypes[index].as_ref()?;
+        Some(match column.positions[index] {
+            Some(position) if self.is_out_of_order(column) => {
+                format!("{dtype} #{}", position + 1)
+            }
+            _ => dtype.to_string(),
+        })
+    }
+
+    /// Most common dtype of `column` across files; cells with another dtype are the drift.
+    pub fn majority_dtype<'a>(&self, column: &'a ColumnPresence) -> Option<&'a DataType> {
+        let present: Vec<&DataType> = column.dtypes.iter().flatten().collect();
+        present
+            .iter()
+            .max_by_key(|d| present.iter().filter(|o| o == d).count())
+            .copied()
+    }
+
+    pub fn file_name(&self, index: usize) -> String {
+        let path = &self.files[index];
+        path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string())
+    }
+
+    /// Plain-text matrix for the command line. Drifting columns are marked with `*` and columns
+    /// out of order with `~` (their cells show each file's position); missing columns show `-`.
+    pub fn to_text(&self) -> String {
+        let headers: Vec<String> = (0..self.files.len()).map(|i| self.file_name(i)).collect();
+        let rows: Vec<(&str, &str, Vec<String>)> = self
+            .columns
+            .iter()
+            .map(|c| {
+                let cells = (0..self.files.len())
+                    .map(|f| match &self.errors[f] {
+                        Some(_) => "error".to_string(),
+                        None => self.cell_text(c, f).unwrap_or_else(|| "-".to_string()),
+                    })
+                    .collect();
+                let marker = if self.has_drift(c) {
+                    "*"
+                } else if self.is_out_of_order(c) {
+                    "~"
+                } else {
+                    " "
+                };
+                (marker, c.name.as_str(), cells)
+            })
+            .collect();
+
+        let name_width = rows
+            .iter()
+            .map(|(_, name, _)| name.chars().count())
+            .chain(std::iter::once("column".len()))
+            .max()
+            .unwrap_or(0);
+        let widths: Vec<usize> = headers
+            .iter()
+            .enumerate()
+            .map(|(i, h)| {
+                rows.iter()
+                    .map(|(_, _, cells)| cells[i].chars().count())
+                    .chain(std::iter::once(h.chars().count()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let mut out = String::new();
+        let mut push_line = |marker: &str, name: &str, cells: &[String]| {
+            let mut line = format!("{} {:<name_width$}", marker, name);
+            for (cell, width) in cells.iter().zip(&widths) {
+                line.push_str(&format!("  {:<width$}", cell));
+            }
+            out.push_str(line.trim_end());
+            out.push('\n');
+        };
+        push_line(" ", "column", &headers);
+        for (marker, name, cells) in &rows {
+            push_line(marker, name, cells);
+        }
+        for (i, error) in self.errors.iter().enumerate() {
+            if let Some(e) = error {
+                out.push_str(&format!("\nerror: {}: {}\n", self.file_name(i), e));
+            }
+        }
+        out.push_str(&format!(
+            "\n{} files, {} columns, {} with drift, {} out of order\n",
+            self.files.len(),
+            self.columns.len(),
+            self.drift_count(),
+            self.out_of_order_count()
+        ));
+        out
+    }
+}
+
+/// Schema of a single file, read the same way the table loads it (honoring delimiter,
+/// header, and other options).
+pub fn read_schema(path: &Path, options: &OpenOptions) -> Result<Schema> {
+    Ok(open_file(path, options)?.schema.as_ref().clone())
+}
+
+/// A single file opened the same way the table loads it (honoring delimiter, header, and
+/// other options). Only the schema is read; rows are scanned when the state's `lf` is collected.
+pub fn open_file(path: &Path, options: &OpenOptions) -> Result<DataTableState> {
+    let format = options
+        .format
+        .or_else(|| workspace::detect_format(path).map(|(f, _)| f))
+        .ok_or_else(|| color_eyre::eyre::eyre!("Unsupported file type: {}", path.display()))?;
+    let (lookahead, lookback) = (options.pages_lookahead, options.pages_lookback);
+    let (max_rows, max_mb) = (options.max_buffered_rows, options.max_buffered_mb);
+    let (row_numbers, start) = (options.row_numbers, options.row_start_index);
+    let state = match format {
+        FileFormat::Parquet => DataTableState::from_parquet(
+            path,
+            lookahead,
+            lookback,
+            max_rows,
+            max_mb,
+            row_numbers,
+            start,
+        )?,
+        FileFormat::Csv => DataTableState::from_csv(path, options)?,
+        FileFormat::Tsv => DataTableState::from_delimited(path, b'\t', options)?,
+        FileFormat::Psv => DataTableState::from_delimited(path, b'|', options)?,
+        FileFormat::Json => DataTableState::from_json(
+            path,
+            lookahead,
+            lookback,
+            max_rows,
+            max_mb,
+            row_numbers,
+            start,
+        )?,
+        FileFormat::Jsonl => DataTableState::from_json_lines(
+            path,
+            lookahead,
+            lookback,
+            max_rows,
+            max_mb,
+            row_numbers,
+            start,
+        )?,
+        FileFormat::Arrow => DataTableState::from_ipc(
+            path,
+            lookahead,
+            lookback,
+            max_rows,
+            max_mb,
+            row_numbers,
+            start,
+        )?,
+        FileFormat::Avro => DataTableState::from_avro(
+            path,
+            lookahead,
+            lookback,
+            max_rows,
+            max_mb,
+            row_numbers,
+            start,
+        )?,
+        FileFormat::Excel => DataTableState::from_excel(path, options)?,
+        FileFormat::Sqlite => DataTableState::from_sqlite(path, options)?,
+        FileFormat::Protobuf => DataTableState::from_protobuf(path, options)?,
+        FileFormat::Orc => DataTableState::from_orc(
+            path,
+            lookahead,
+            lookback,
+            max_rows,
+            max_mb,
+            row_numbers,
+            start,
+        )?,
+    };
+    Ok(state)
+}
+
+/// Expand command-line paths into the files to compare: a directory contributes its
+/// supported files (as in workspace mode), and a `*` / `?` pattern in the file name
+/// (e.g. `'data/*.parquet'`, quoted so the shell leaves it alone) matches files in its
+/// directory. Other paths are used as given.
+pub fn expand_paths(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            files.extend(
+                workspace::scan_directory(path)?
+                    .into_iter()
+                    .map(|entry| entry.path),
+            );
+        } else if source::has_wildcard(path) {
+            files.extend(source::expand_wildcard(path)?);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    if files.len() < 2 {
+        return Err(color_eyre::eyre::eyre!(
+            "Schema diff needs at least two files to compare (found {})",
+            files.len()
+        ));
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::Field;
+
+    fn schema(fields: &[(&str, DataType)]) -> Schema {
+        Schema::from_iter(
+            fields
+                .iter()
+                .map(|(name, dtype)| Field::new((*name).into(), dtype.clone())),
+        )
+    }
+
+    #[test]
+    fn test_from_schemas_detects_drift() {
+        let diff = SchemaDiff::from_schemas(
+            vec!["jan.parquet".into(), "feb.parquet".into()],
+            vec![
+                Ok(schema(&[
+                    ("id", DataType::Int64),
+                    ("amount", DataType::Float64),
+                ])),
+                Ok(schema(&[
+                    ("id", DataType::Int64),
+                    ("amount", DataType::String),
+                    ("note", DataType::String),
+                ])),
+            ],
+        );
+        let names: Vec<&str> = diff.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "amount", "note"]);
+        let drift: Vec<bool> = diff.columns.iter().map(|c| diff.has_drift(c)).collect();
+        assert_eq!(drift, vec![false, true, true]);
+        assert_eq!(diff.drift_count(), 2);
+        assert_eq!(diff.columns[2].dtypes[0], None);
+    }
+
+    #[test]
+    fn test_columns_out_of_order() {
+        let diff = SchemaDiff::from_schemas(
+            vec!["a.csv".into(), "b.csv".into(), "c.csv".into()],
+            vec![
+                Ok(schema(&[
+                    ("id", DataType::Int64),
+                    ("x", DataType::Int64),
+                    ("y", DataType::Int64),
+                ])),
+                // A column missing from one file doesn't shift the others out of order
+                Ok(schema(&[
+                    ("id", DataType::Int64),
+                    ("extra", DataType::Int64),
+                    ("x", DataType::Int64),
+                    ("y", DataType::Int64),
+                ])),
+                Ok(schema(&[
+                    ("id", DataType::Int64),
+                    ("y", DataType::Int64),
+                    ("x", DataType::Int64),
+                ])),
+            ],
+        );
+        let moved: Vec<&str> = diff
+            .columns
+            .iter()
+            .filter(|c| diff.is_out_of_order(c))
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(moved, vec!["x", "y"]);
+        assert_eq!(diff.out_of_order_count(), 2);
+        assert_eq!(
+            diff.cell_text(&diff.columns[1], 1).as_deref(),
+            Some("i64 #3")
+        );
+        assert_eq!(diff.cell_text(&diff.columns[0], 2).as_deref(), Some("i64"));
+        let text = diff.to_text();
+        assert!(text.lines().any(|l| l.starts_with("~ x")), "{text}");
+        assert!(
+            text.contains("3 files, 4 columns, 1 with drift, 2 out of order"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn test_unreadable_file_does_not_count_as_drift() {
+        let diff = SchemaDiff::from_schemas(
+            vec!["a.csv".into(), "b.csv".into()],
+            vec![
+                Ok(schema(&[("x", DataType::Int64)])),
+                Err("bad file".to_string()),
+            ],
+        );
+        assert_eq!(diff.drift_count(), 0);
+        let text = diff.to_text();
+        assert!(text.contains("error: b.csv: bad file"), "{text}");
+        assert!(text.contains("2 files, 1 columns, 0 with drift"), "{text}");
+    }
+
+    #[test]
+    fn test_to_text_marks_drift() {
+        let diff = SchemaDiff::from_schemas(
+            vec!["a.csv".into(), "b.csv".into()],
+            vec![
+                Ok(schema(&[("x", DataType::Int64), ("y", DataType::Int64)])),
+                Ok(schema(&[("x", DataType::Int64)])),
+            ],
+        );
+        let text = diff.to_text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].contains("a.csv") && lines[0].contains("b.csv"));
+        assert!(lines[1].starts_with("  x"));
+        assert!(lines[2].starts_with("* y"));
+        assert!(lines[2].ends_with('-'));
+    }
+
+    #[test]
+    fn test_expand_paths_glob_and_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["a.csv", "b.csv", "c.parquet", "notes.txt"] {
+            std::fs::write(dir.path().join(name), "x\n1\n").unwrap();
+        }
+        let csvs = expand_paths(&[dir.path().join("*.csv")]).unwrap();
+        assert_eq!(
+            csvs,
+            vec![dir.path().join("a.csv"), dir.path().join("b.csv")]
+        );
+        let all = expand_paths(&[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(all.len(), 3);
+        assert!(expand_paths(&[dir.path().join("*.json")]).is_err());
+        assert!(expand_paths(&[dir.path().join("a.csv")]).is_err());
+    }
+
+    #[test]
+    fn test_compute_reads_csv_schemas() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.csv");
+        let b = dir.path().join("b.csv");
+        std::fs::write(&a, "id,amount\n1,2.5\n").unwrap();
+        std::fs::write(&b, "id,amount\n1,abc\n").unwrap();
+        let diff = SchemaDiff::compute(vec![a, b], &OpenOptions::default());
+        assert!(diff.errors.iter().all(Option::is_none));
+        assert_eq!(diff.drift_count(), 1);
+        assert_eq!(diff.columns[1].dtypes[0], Some(DataType::Float64));
+        assert_eq!(diff.columns[1].dtypes[1], Some(DataType::String));
+    }
+}
@@ -0,0 +1,302 @@
+//! Column types given at load time. `--dtypes zip:str,amount:f64` (or `dtypes` under
+//! `[file_loading]`) reads those columns of a CSV, TSV or PSV file as the given types instead of
+//! inferring them, so ZIP codes and IDs keep their leading zeros. The [schema step](SchemaModal)
+//! (`K`, or `--adjust-schema` before the file is read) lists the columns with their inferred
+//! types and first values, and Enter loads the file with the types chosen there.
+
+use crate::csv_dialect::{self, CsvDialect};
+use crate::widgets::datatable::DataTableState;
+use crate::OpenOptions;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use polars::prelude::*;
+use ratatui::widgets::TableState;
+use std::path::PathBuf;
+
+/// Types a column can be read as, in the order the schema step cycles through them.
+pub const DTYPE_NAMES: [&str; 11] = [
+    "str", "i64", "i32", "u64", "u32", "f64", "f32", "bool", "date", "datetime", "time",
+];
+/// First values of each column shown in the schema step.
+const SAMPLE_VALUES: usize = 5;
+
+/// The type named `name`: one of [`DTYPE_NAMES`] or a longer alias (`string`, `int`, `float`...).
+pub fn parse_dtype(name: &str) -> Option<DataType> {
+    Some(match name.trim().to_ascii_lowercase().as_str() {
+        "str" | "string" | "utf8" | "text" => DataType::String,
+        "i8" => DataType::Int8,
+        "i16" => DataType::Int16,
+        "i32" => DataType::Int32,
+        "i64" | "int" | "integer" => DataType::Int64,
+        "u8" => DataType::UInt8,
+        "u16" => DataType::UInt16,
+        "u32" => DataType::UInt32,
+        "u64" => DataType::UInt64,
+        "f32" => DataType::Float32,
+        "f64" | "float" | "double" => DataType::Float64,
+        "bool" | "boolean" => DataType::Boolean,
+        "date" => DataType::Date,
+        "datetime" => DataType::Datetime(TimeUnit::Microseconds, None),
+        "time" => DataType::Time,
+        _ => return None,
+    })
+}
+
+/// Short name of `dtype` as `--dtypes` takes it, or Polars' name for other types.
+pub fn dtype_name(dtype: &DataType) -> String {
+    match dtype {
+        DataType::String => "str".to_string(),
+        DataType::Int8 => "i8".to_string(),
+        DataType::Int16 => "i16".to_string(),
+        DataType::Int32 => "i32".to_string(),
+        DataType::Int64 => "i64".to_string(),
+        DataType::UInt8 => "u8".to_string(),
+        DataType::UInt16 => "u16".to_string(),
+        DataType::UInt32 => "u32".to_string(),
+        DataType::UInt64 => "u64".to_string(),
+        DataType::Float32 => "f32".to_string(),
+        DataType::Float64 => "f64".to_string(),
+        DataType::Boolean => "bool".to_string(),
+        DataType::Date => "date".to_string(),
+        DataType::Datetime(_, _) => "datetime".to_string(),
+        DataType::Time => "time".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Parse `COLUMN:TYPE` specs. The last `:` separates the type, so column names may hold one.
+pub fn parse_specs(specs: &[String]) -> Result<Vec<(String, DataType)>> {
+    specs
+        .iter()
+        .map(|spec| {
+            let (column, name) = spec
+                .rsplit_once(':')
+                .filter(|(column, _)| !column.trim().is_empty())
+                .ok_or_else(|| eyre!("--dtypes: expected COLUMN:TYPE, got \"{}\"", spec))?;
+            let dtype = parse_dtype(name).ok_or_else(|| {
+                eyre!(
+                    "--dtypes: unknown type \"{}\" for column \"{}\" (use one of {})",
+                    name,
+                    column.trim(),
+                    DTYPE_NAMES.join(", ")
+                )
+            })?;
+            Ok((column.trim().to_string(), dtype))
+        })
+        .collect()
+}
+
+/// Schema of the columns `options.dtypes` gives types, for the CSV reader to read them with.
+pub fn schema(options: &OpenOptions) -> Result<Option<SchemaRef>> {
+    let specs = match &options.dtypes {
+        Some(specs) if !specs.is_empty() => specs,
+        _ => return Ok(None),
+    };
+    let schema: Schema = parse_specs(specs)?
+        .into_iter()
+        .map(|(column, dtype)| Field::new(column.into(), dtype))
+        .collect();
+    Ok(Some(Arc::new(schema)))
+}
+
+/// Columns `options.dtypes` gives types (ignoring specs that don't parse).
+pub fn columns(options: &OpenOptions) -> Vec<String> {
+    let specs = options.dtypes.as_deref().unwrap_or(&[]);
+    parse_specs(specs)
+        .map(|specs| specs.into_iter().map(|(column, _)| column).collect())
+        .unwrap_or_default()
+}
+
+/// A column in the schema step.
+pub struct SchemaColumn {
+    pub name: String,
+    /// Type the column loads as without an override.
+    pub inferred: DataType,
+    pub chosen: DataType,
+    /// First values as written in the file.
+    pub samples: Vec<String>,
+}
+
+impl SchemaColumn {
+    pub fn changed(&self) -> bool {
+        self.chosen != self.inferred
+    }
+}
+
+/// The schema step: a CSV's columns with their inferred types, to override before it is read.
+pub struct SchemaModal {
+    pub path: PathBuf,
+    /// Options the file is opened with; Enter loads it with these and the chosen types.
+    pub options: OpenOptions,
+    /// Opened by `--adjust-schema` before the file was read: Esc loads it as inferred.
+    pub before_load: bool,
+    pub columns: Vec<SchemaColumn>,
+    pub table_state: TableState,
+}
+
+impl SchemaModal {
+    /// Infer the column types of `path` from its first rows as loading it with `options` would
+    /// (without its `dtypes`), and start from the types `dtypes` gives.
+    pub fn open(path: PathBuf, options: OpenOptions, before_load: bool) -> Result<Self> {
+        let sample = csv_dialect::read_sample(&path, &options)?;
+        let dialect = CsvDialect::from_options(&path, &options);
+        let mut inferred_options = options.clone();
+        inferred_options.dtypes = None;
+        let inferred = dialect
+            .read(
+                &sample,
+                None,
+                options.infer_schema_length,
+                options.csv_try_parse_dates(),
+            )
+            .map_err(|e| eyre!(e))?;
+        let inferred = DataTableState::parse_strings_in_sample(inferred, &inferred_options)?;
+        let raw = dialect
+            .read(&sample, Some(SAMPLE_VALUES), Some(0), false)
+            .map_err(|e| eyre!(e))?;
+        let overrides = parse_specs(options.dtypes.as_deref().unwrap_or(&[]))?;
+        let columns = inferred
+            .schema()
+            .iter()
+            .map(|(name, dtype)| {
+                let chosen = overrides
+                    .iter()
+                    .rev()
+                    .find(|(column, _)| column == name.trim())
+                    .map_or_else(|| dtype.clone(), |(_, dtype)| dtype.clone());
+                let samples = raw
+                    .column(name)
+                    .ok()
+                    .and_then(|c| c.str().ok().cloned())
+                    .map(|values| {
+                        values
+                            .into_iter()
+                            .map(|v| v.unwrap_or("").to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                SchemaColumn {
+                    name: name.trim().to_string(),
+                    inferred: dtype.clone(),
+                    chosen,
+                    samples,
+                }
+            })
+            .collect();
+        let mut table_state = TableState::default();
+        table_state.select(Some(0));
+        Ok(Self {
+            path,
+            options,
+            before_load,
+            columns,
+            table_state,
+        })
+    }
+
+    fn selected(&self) -> usize {
+        self.table_state.selected().unwrap_or(0)
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        let last = self.columns.len().saturating_sub(1);
+        let selected = self.selected().saturating_add_signed(delta).min(last);
+        self.table_state.select(Some(selected));
+    }
+
+    /// Step the selected column's type through [`DTYPE_NAMES`].
+    pub fn cycle(&mut self, delta: isize) {
+        let selected = self.selected();
+        let Some(column) = self.columns.get_mut(selected) else {
+            return;
+        };
+        let current = dtype_name(&column.chosen);
+        let len = DTYPE_NAMES.len() as isize;
+        let next = match DTYPE_NAMES.iter().position(|n| *n == current) {
+            Some(i) => (i as isize + delta).rem_euclid(len),
+            None if delta < 0 => len - 1,
+            None => 0,
+        };
+        column.chosen = parse_dtype(DTYPE_NAMES[next as usize]).unwrap_or(DataType::String);
+    }
+
+    /// Back to the inferred type for the selected column.
+    pub fn reset(&mut self) {
+        let selected = self.selected();
+        if let Some(column) = self.columns.get_mut(selected) {
+            column.chosen = column.inferred.clone();
+        }
+    }
+
+    pub fn changed(&self) -> usize {
+        self.columns.iter().filter(|c| c.changed()).count()
+    }
+
+    /// Paths and options that load the file with the chosen types.
+    pub fn open_selected(&self) -> (Vec<PathBuf>, OpenOptions) {
+        let mut options = self.options.clone();
+        let specs: Vec<String> = self
+            .columns
+            .iter()
+            .filter(|c| c.changed())
+            .map(|c| format!("{}:{}", c.name, dtype_name(&c.chosen)))
+            .collect();
+        options.dtypes = (!specs.is_empty()).then_some(specs);
+        options.adjust_schema = false;
+        (vec![self.path.clone()], options)
+    }
+
+    /// Paths and options that load the file as it is inferred (Esc before the file was read).
+    pub fn open_inferred(&self) -> (Vec<PathBuf>, OpenOptions) {
+        let mut options = self.options.clone();
+        options.adjust_schema = false;
+        (vec![self.path.clone()], options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_specs_with_aliases_and_colons_in_names() {
+        let specs = ["zip:str".to_string(), "at: 12:00:Datetime".to_string()];
+        let parsed = parse_specs(&specs).unwrap();
+        assert_eq!(parsed[0], ("zip".to_string(), DataType::String));
+        assert_eq!(
+            parsed[1],
+            (
+                "at: 12:00".to_string(),
+                DataType::Datetime(TimeUnit::Microseconds, None)
+            )
+        );
+        let err = parse_specs(&["amount:money".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("unknown type \"money\""));
+        assert!(parse_specs(&["amount".to_string()]).is_err());
+    }
+
+    #[test]
+    fn schema_step_keeps_changed_types_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("people.csv");
+        std::fs::write(&path, "zip,amount,name\n00123,1.5,ann\n04567,2,bob\n").unwrap();
+        let options = OpenOptions {
+            dtypes: Some(vec!["amount:f32".to_string()]),
+            ..OpenOptions::new()
+        };
+        let mut modal = SchemaModal::open(path, options, false).unwrap();
+        assert_eq!(modal.columns[0].inferred, DataType::Int64);
+        assert_eq!(modal.columns[0].samples, ["00123", "04567"]);
+        assert_eq!(modal.columns[1].chosen, DataType::Float32);
+        modal.cycle(-1);
+        assert_eq!(modal.columns[0].chosen, DataType::String);
+        modal.move_by(2);
+        modal.cycle(1);
+        modal.reset();
+        let (_, options) = modal.open_selected();
+        assert_eq!(
+            options.dtypes,
+            Some(vec!["zip:str".to_string(), "amount:f32".to_string()])
+        );
+    }
+}
@@ -0,0 +1,349 @@
+//! Scripts: [Rhai](https://rhai.rs) programs that transform the view's source. A script gets the
+//! source as `df` and returns a new frame, either as its last expression or by assigning `df`:
+//!
+//! ```text
+//! df.filter("price > 10").with_column("total", "price * qty").sort("total", true)
+//! ```
+//!
+//! Scripts run from the Scripts menu, which lists the `*.rhai` files in the `scripts` directory
+//! of the config directory, or with `--script`. The query, filters and sort of the view are
+//! applied again on the result.
+
+use crate::widgets::text_input::TextInput;
+use ratatui::widgets::TableState;
+use std::path::{Path, PathBuf};
+
+/// Directory of the config directory the Scripts menu lists.
+pub const SCRIPTS_DIR: &str = "scripts";
+
+/// The `*.rhai` files of `dir`, sorted by name. A missing directory has none.
+pub fn list_scripts(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut scripts: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "rhai"))
+        .collect();
+    scripts.sort();
+    scripts
+}
+
+/// Read the script at `path` and run it on `lf`.
+pub fn run_script_file(
+    path: &Path,
+    lf: polars::prelude::LazyFrame,
+) -> Result<polars::prelude::LazyFrame, String> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("Could not read script {}: {}", path.display(), e))?;
+    run_script(&source, lf)
+}
+
+#[cfg(not(feature = "scripting"))]
+pub fn run_script(
+    _source: &str,
+    _lf: polars::prelude::LazyFrame,
+) -> Result<polars::prelude::LazyFrame, String> {
+    Err("Scripting support not compiled in (build with --features scripting)".to_string())
+}
+
+#[cfg(feature = "scripting")]
+pub use engine::run_script;
+
+#[cfg(feature = "scripting")]
+mod engine {
+    use crate::column_cast::{apply_casts, CastStrategy, ColumnCast};
+    use crate::error_display::user_message_from_polars;
+    use crate::query::{apply_query, parse_column_expression, parse_query};
+    use polars::prelude::*;
+    use rhai::{Array, Dynamic, Engine, EvalAltResult, Scope};
+
+    type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+    /// A frame as scripts see it: every method returns a new frame.
+    #[derive(Clone)]
+    struct Frame(LazyFrame);
+
+    fn checked(lf: LazyFrame) -> ScriptResult<Frame> {
+        lf.clone()
+            .collect_schema()
+            .map_err(|e| user_message_from_polars(&e))?;
+        Ok(Frame(lf))
+    }
+
+    fn expression(text: &str) -> ScriptResult<Expr> {
+        Ok(parse_column_expression(text)?)
+    }
+
+    fn names(array: Array) -> ScriptResult<Vec<String>> {
+        array
+            .into_iter()
+            .map(|item| {
+                item.into_string()
+                    .map_err(|t| format!("Expected column names, found {}", t).into())
+            })
+            .collect()
+    }
+
+    fn columns(frame: &mut Frame) -> ScriptResult<Array> {
+        let schema = frame
+            .0
+            .collect_schema()
+            .map_err(|e| user_message_from_polars(&e))?;
+        Ok(schema
+            .iter_names()
+            .map(|name| Dynamic::from(name.to_string()))
+            .collect())
+    }
+
+    fn height(frame: &mut Frame) -> ScriptResult<i64> {
+        let df = frame
+            .0
+            .clone()
+            .select([len()])
+            .collect()
+            .map_err(|e| user_message_from_polars(&e))?;
+        let height = df
+            .column("len")
+            .ok()
+            .and_then(|c| c.u32().ok()?.get(0))
+            .unwrap_or(0);
+        Ok(height as i64)
+    }
+
+    fn filter(frame: Frame, predicate: &str) -> ScriptResult<Frame> {
+        checked(frame.0.filter(expression(predicate)?))
+    }
+
+    fn with_column(frame: Frame, name: &str, expr: &str) -> ScriptResult<Frame> {
+        checked(frame.0.with_column(expression(expr)?.alias(name)))
+    }
+
+    fn select(frame: Frame, columns: Array) -> ScriptResult<Frame> {
+        checked(
+            frame.0.select(
+                names(columns)?
+                    .iter()
+                    .map(|c| col(c.as_str()))
+                    .collect::<Vec<_>>(),
+            ),
+        )
+    }
+
+    fn drop(frame: Frame, columns: Array) -> ScriptResult<Frame> {
+        checked(frame.0.drop(by_name(names(columns)?, true)))
+    }
+
+    fn rename(frame: Frame, from: &str, to: &str) -> ScriptResult<Frame> {
+        checked(frame.0.rename([from], [to], true))
+    }
+
+    fn sort(frame: Frame, column: &str, descending: bool) -> ScriptResult<Frame> {
+        let options = SortMultipleOptions::default()
+            .with_order_descending(descending)
+            .with_maintain_order(true);
+        checked(frame.0.sort([column], options))
+    }
+
+    fn head(frame: Frame, n: i64) -> Frame {
+        Frame(frame.0.limit(n.max(0) as IdxSize))
+    }
+
+    fn tail(frame: Frame, n: i64) -> Frame {
+        Frame(frame.0.tail(n.max(0) as IdxSize))
+    }
+
+    fn unique(frame: Frame) -> Frame {
+        Frame(frame.0.unique_stable(None, UniqueKeepStrategy::First))
+    }
+
+    fn cast(frame: Frame, column: &str, to: &str) -> ScriptResult<Frame> {
+        let cast = ColumnCast {
+            column: column.to_string(),
+            to: to.to_string(),
+            format: None,
+            strategy: CastStrategy::NullOnError,
+        };
+        Ok(Frame(apply_casts(frame.0, &[cast])?))
+    }
+
+    fn query(frame: Frame, text: &str) -> ScriptResult<Frame> {
+        let (cols, filter, group_by_cols, group_by_col_names) = parse_query(text)?;
+        let (lf, _) = apply_query(frame.0, cols, filter, group_by_cols, &group_by_col_names)
+            .map_err(|e| user_message_from_polars(&e))?;
+        Ok(Frame(lf))
+    }
+
+    #[cfg(feature = "sql")]
+    fn sql(frame: Frame, text: &str) -> ScriptResult<Frame> {
+        let mut ctx = polars_sql::SQLContext::new();
+        ctx.register("df", frame.0);
+        let lf = ctx
+            .execute(text)
+            .map_err(|e| user_message_from_polars(&e))?;
+        checked(lf)
+    }
+
+    /// Rhai operations a script may take before it is stopped, so a runaway loop ends with an
+    /// error instead of running forever. Frame methods count as one operation each.
+    const MAX_OPERATIONS: u64 = 1_000_000;
+
+    fn engine() -> Engine {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        // The terminal belongs to the UI: printing would garble it.
+        engine.on_print(|_| {});
+        engine.on_debug(|_, _, _| {});
+        engine
+            .register_type_with_name::<Frame>("Frame")
+            .register_fn("columns", columns)
+            .register_fn("height", height)
+            .register_fn("filter", filter)
+            .register_fn("with_column", with_column)
+            .register_fn("select", select)
+            .register_fn("drop", drop)
+            .register_fn("rename", rename)
+            .register_fn("sort", |frame: Frame, column: &str| {
+                sort(frame, column, false)
+            })
+            .register_fn("sort", sort)
+            .register_fn("head", head)
+            .register_fn("tail", tail)
+            .register_fn("unique", unique)
+            .register_fn("cast", cast)
+            .register_fn("query", query);
+        #[cfg(feature = "sql")]
+        engine.register_fn("sql", sql);
+        engine
+    }
+
+    /// Run the Rhai `source` with `lf` as `df`. The result is the script's value when that's
+    /// a frame, otherwise `df` as the script left it.
+    pub fn run_script(source: &str, lf: LazyFrame) -> Result<LazyFrame, String> {
+        let mut scope = Scope::new();
+        scope.push("df", Frame(lf));
+        let value = engine()
+            .eval_with_scope::<Dynamic>(&mut scope, source)
+            .map_err(|e| format!("Script error: {}", e))?;
+        let frame = match value.try_cast::<Frame>() {
+            Some(frame) => frame,
+            None => scope
+                .get_value::<Frame>("df")
+                .ok_or_else(|| "The script must return a frame or leave one in df".to_string())?,
+        };
+        frame
+            .0
+            .clone()
+            .collect_schema()
+            .map_err(|e| user_message_from_polars(&e))?;
+        Ok(frame.0)
+    }
+}
+
+/// Scripts menu: the scripts of the config directory's `scripts` directory, or a path typed in.
+pub struct ScriptsModal {
+    pub dir: PathBuf,
+    pub scripts: Vec<PathBuf>,
+    pub table_state: TableState,
+    /// Path being typed (`o`); None when picking from the list.
+    pub path_input: Option<TextInput>,
+}
+
+impl ScriptsModal {
+    pub fn new(dir: PathBuf) -> Self {
+        let scripts = list_scripts(&dir);
+        let mut table_state = TableState::default();
+        table_state.select((!scripts.is_empty()).then_some(0));
+        Self {
+            dir,
+            scripts,
+            table_state,
+            path_input: None,
+        }
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        if self.scripts.is_empty() {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0);
+        let last = self.scripts.len() - 1;
+        self.table_state
+            .select(Some(selected.saturating_add_signed(delta).min(last)));
+    }
+
+    pub fn selected(&self) -> Option<&PathBuf> {
+        self.scripts.get(self.table_state.selected()?)
+    }
+
+    /// Start typing the path of a script outside the scripts directory.
+    pub fn start_path_input(&mut self, theme: &crate::config::Theme) {
+        let mut input = TextInput::new().with_theme(theme);
+        input.set_focused(true);
+        self.path_input = Some(input);
+    }
+}
+
+#[cfg(all(test, feature = "scripting"))]
+mod tests {
+    use super::*;
+    use polars::prelude::*;
+
+    fn frame() -> LazyFrame {
+        df!(
+            "name" => ["a", "b", "c", "d"],
+            "price" => [5i64, 20, 15, 30],
+            "qty" => [1i64, 2, 3, 4],
+        )
+        .unwrap()
+        .lazy()
+    }
+
+    #[test]
+    fn scripts_transform_the_frame() {
+        let lf = run_script(
+            r#"df.filter("price > 10").with_column("total", "price * qty").sort("total", true)"#,
+            frame(),
+        )
+        .unwrap();
+        let df = lf.collect().unwrap();
+        assert_eq!(df.get_column_names(), ["name", "price", "qty", "total"]);
+        let totals: Vec<i64> = df
+            .column("total")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(totals, [120, 45, 40]);
+
+        let lf = run_script(
+            r#"
+            if df.height() > 2 { df = df.head(2); }
+            df = df.drop(["qty"]).rename("price", "cost");
+            "#,
+            frame(),
+        )
+        .unwrap();
+        let df = lf.collect().unwrap();
+        assert_eq!(df.get_column_names(), ["name", "cost"]);
+        assert_eq!(df.height(), 2);
+
+        let lf = run_script(r#"df.query("select name where qty >= 3")"#, frame()).unwrap();
+        assert_eq!(lf.collect().unwrap().shape(), (2, 1));
+    }
+
+    #[test]
+    fn script_errors_are_reported() {
+        let err = run_script(r#"df.filter("missing > 1")"#, frame())
+            .err()
+            .unwrap();
+        assert!(err.contains("missing"), "{err}");
+        let err = run_script("df.nope()", frame()).err().unwrap();
+        assert!(err.starts_with("Script error"), "{err}");
+        let err = run_script("df = 1; 2", frame()).err().unwrap();
+        assert!(err.contains("must return a frame"), "{err}");
+        let err = run_script("loop {}", frame()).err().unwrap();
+        assert!(err.starts_with("Script error"), "{err}");
+    }
+}
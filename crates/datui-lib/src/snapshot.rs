@@ -0,0 +1,262 @@
+//! Snapshots of the app's screen for `--render`: the cells of a drawn frame written as plain
+//! text, or as an SVG or PNG image in the theme's colors.
+
+use color_eyre::Result;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use plotters::style::Color as _;
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+use ratatui::text::Span;
+use std::path::Path;
+
+/// Size of a cell in an image, in pixels, and the font size that fills it.
+const CELL_WIDTH: i32 = 9;
+const CELL_HEIGHT: i32 = 18;
+const FONT_SIZE: f64 = 15.0;
+/// What a terminal draws for `Color::Reset`.
+const DEFAULT_FG: RGBColor = RGBColor(204, 204, 204);
+const DEFAULT_BG: RGBColor = RGBColor(24, 24, 24);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    Text,
+    Svg,
+    Png,
+}
+
+impl SnapshotFormat {
+    /// The format for `path`'s extension: `.txt`, `.svg` or `.png`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "txt" => Some(Self::Text),
+            "svg" => Some(Self::Svg),
+            "png" => Some(Self::Png),
+            _ => None,
+        }
+    }
+}
+
+/// Write `buf` to `path` as `format`.
+pub fn write_snapshot(buf: &Buffer, path: &Path, format: SnapshotFormat) -> Result<()> {
+    let size = (
+        (buf.area.width as i32 * CELL_WIDTH) as u32,
+        (buf.area.height as i32 * CELL_HEIGHT) as u32,
+    );
+    match format {
+        SnapshotFormat::Text => std::fs::write(path, buffer_text(buf))?,
+        SnapshotFormat::Svg => {
+            let root = SVGBackend::new(path, size).into_drawing_area();
+            draw_buffer(&root, buf)?;
+            root.present()?;
+        }
+        SnapshotFormat::Png => {
+            let root = BitMapBackend::new(path, size).into_drawing_area();
+            draw_buffer(&root, buf)?;
+            root.present()?;
+        }
+    }
+    Ok(())
+}
+
+/// The lines of `buf`, without trailing spaces.
+pub fn buffer_text(buf: &Buffer) -> String {
+    let mut text = String::new();
+    for y in 0..buf.area.height {
+        let line: String = visible_cells(buf, y)
+            .into_iter()
+            .map(|(_, cell)| cell.symbol())
+            .collect();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// The cells of row `y` with their columns, leaving out those hidden by a wide symbol before
+/// them.
+fn visible_cells(buf: &Buffer, y: u16) -> Vec<(u16, &Cell)> {
+    let mut cells = Vec::new();
+    let mut x = 0;
+    while x < buf.area.width {
+        let cell = &buf[(buf.area.x + x, buf.area.y + y)];
+        cells.push((x, cell));
+        x += (Span::raw(cell.symbol()).width() as u16).max(1);
+    }
+    cells
+}
+
+fn draw_buffer<DB: DrawingBackend>(root: &DrawingArea<DB, Shift>, buf: &Buffer) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    root.fill(&DEFAULT_BG)?;
+    for y in 0..buf.area.height {
+        let top = y as i32 * CELL_HEIGHT;
+        for (x, cell) in visible_cells(buf, y) {
+            let left = x as i32 * CELL_WIDTH;
+            let right = left + CELL_WIDTH * (Span::raw(cell.symbol()).width() as i32).max(1);
+            let mut fg = rgb(cell.fg, DEFAULT_FG);
+            let mut bg = rgb(cell.bg, DEFAULT_BG);
+            if cell.modifier.contains(Modifier::REVERSED) {
+                std::mem::swap(&mut fg, &mut bg);
+            }
+            if bg != DEFAULT_BG {
+                root.draw(&Rectangle::new(
+                    [(left, top), (right, top + CELL_HEIGHT)],
+                    bg.filled(),
+                ))?;
+            }
+            if let Some(dots) = braille_dots(cell.symbol()) {
+                draw_braille(root, (left, top), dots, fg)?;
+            } else if !cell.symbol().trim().is_empty() {
+                let font = if cell.modifier.contains(Modifier::BOLD) {
+                    ("monospace", FONT_SIZE, FontStyle::Bold).into_font()
+                } else {
+                    ("monospace", FONT_SIZE).into_font()
+                };
+                root.draw(&Text::new(
+                    cell.symbol().to_string(),
+                    (left, top + 1),
+                    font.color(&fg),
+                ))?;
+            }
+            if cell.modifier.contains(Modifier::UNDERLINED) {
+                let base = top + CELL_HEIGHT - 2;
+                root.draw(&PathElement::new(vec![(left, base), (right, base)], fg))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The dots of a braille symbol (charts draw with them), as its bits: 0-2 and 6 down the left,
+/// 3-5 and 7 down the right.
+fn braille_dots(symbol: &str) -> Option<u8> {
+    let mut chars = symbol.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c @ '\u{2800}'..='\u{28FF}'), None) => Some((c as u32 - 0x2800) as u8),
+        _ => None,
+    }
+}
+
+/// Draw braille `dots` in the cell at `(left, top)`. Drawn as squares, as fonts often lack the
+/// braille symbols.
+fn draw_braille<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    (left, top): (i32, i32),
+    dots: u8,
+    color: RGBColor,
+) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    const POSITIONS: [(i32, i32); 8] = [
+        (0, 0),
+        (0, 1),
+        (0, 2),
+        (1, 0),
+        (1, 1),
+        (1, 2),
+        (0, 3),
+        (1, 3),
+    ];
+    for (bit, (column, row)) in POSITIONS.iter().enumerate() {
+        if dots & (1 << bit) != 0 {
+            let x = left + 1 + column * CELL_WIDTH / 2;
+            let y = top + 1 + row * CELL_HEIGHT / 4;
+            root.draw(&Rectangle::new([(x, y), (x + 3, y + 3)], color.filled()))?;
+        }
+    }
+    Ok(())
+}
+
+/// The color a terminal with the xterm palette draws for `color`.
+fn rgb(color: Color, default: RGBColor) -> RGBColor {
+    const BASIC: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    let index = match color {
+        Color::Reset => return default,
+        Color::Rgb(r, g, b) => return RGBColor(r, g, b),
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        Color::Indexed(i) => i,
+    };
+    match index {
+        0..=15 => {
+            let (r, g, b) = BASIC[index as usize];
+            RGBColor(r, g, b)
+        }
+        16..=231 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let i = index - 16;
+            RGBColor(level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        _ => {
+            let v = 8 + (index - 232) * 10;
+            RGBColor(v, v, v)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn test_buffer_text_skips_cells_under_wide_symbols() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 2));
+        buf.set_string(0, 0, "表 ab", ratatui::style::Style::default());
+        buf.set_string(1, 1, "x", ratatui::style::Style::default());
+        assert_eq!(buffer_text(&buf), "表 ab\n x\n");
+        assert_eq!(
+            SnapshotFormat::from_path(Path::new("out.SVG")),
+            Some(SnapshotFormat::Svg)
+        );
+        assert_eq!(SnapshotFormat::from_path(Path::new("out.csv")), None);
+        assert_eq!(braille_dots("⡁"), Some(0b0100_0001));
+        assert_eq!(braille_dots("a"), None);
+    }
+
+    #[test]
+    fn test_rgb_follows_the_xterm_palette() {
+        assert_eq!(rgb(Color::Reset, DEFAULT_BG), DEFAULT_BG);
+        assert_eq!(rgb(Color::Indexed(9), DEFAULT_FG), RGBColor(255, 0, 0));
+        assert_eq!(rgb(Color::Indexed(196), DEFAULT_FG), RGBColor(255, 0, 0));
+        assert_eq!(
+            rgb(Color::Indexed(244), DEFAULT_FG),
+            RGBColor(128, 128, 128)
+        );
+    }
+}
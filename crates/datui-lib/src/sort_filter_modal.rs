@@ -1,6 +1,7 @@
 //! Combined Sort & Filter modal with tabs.
 
 use crate::filter_modal::{FilterFocus, FilterModal};
+use crate::mouse::ButtonAreas;
 use crate::sort_modal::{SortFocus, SortModal};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -25,6 +26,8 @@ pub struct SortFilterModal {
     pub active: bool,
     pub active_tab: SortFilterTab,
     pub focus: SortFilterFocus,
+    /// Where the buttons were drawn in the last frame, for mouse clicks.
+    pub buttons: ButtonAreas<SortFilterFocus>,
     pub sort: SortModal,
     pub filter: FilterModal,
 }
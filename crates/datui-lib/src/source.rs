@@ -1,17 +1,33 @@
-//! Input source detection for local paths vs remote URLs (S3, GCS, HTTP/HTTPS).
+//! Input source detection for local paths vs remote URLs (S3, GCS, Azure Blob, HTTP/HTTPS) and
+//! standard input (`-`).
 
+use crate::cli::{CompressionFormat, FileFormat};
+use color_eyre::Result;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
+/// Path that stands for standard input, as in `curl ... | datui - --format csv`.
+pub const STDIN_PATH: &str = "-";
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) enum InputSource {
     Local(PathBuf),
     S3(String),
     Gcs(String),
+    /// Full URL: the scheme tells object_store how to read the container and account.
+    Azure(String),
     Http(String),
+    /// Postgres or MySQL / MariaDB server: the full connection URL.
+    Database(String),
+    Stdin,
 }
 
-/// Classifies the path as local, S3, GCS, or HTTP/HTTPS using string parsing only (no filesystem calls).
+/// Classifies the path as local, S3, GCS, Azure, HTTP/HTTPS or standard input using string parsing only
+/// (no filesystem calls).
 pub(crate) fn input_source(path: &Path) -> InputSource {
+    if path.as_os_str() == STDIN_PATH {
+        return InputSource::Stdin;
+    }
     let s = path.as_os_str().to_string_lossy();
     if let Some(after_scheme) = s.find("://") {
         let prefix = s[..after_scheme].to_lowercase();
@@ -22,9 +38,18 @@ pub(crate) fn input_source(path: &Path) -> InputSource {
         if prefix == "gs" || prefix == "gcs" {
             return InputSource::Gcs(rest);
         }
+        if matches!(prefix.as_str(), "az" | "azure" | "abfs" | "abfss") {
+            return InputSource::Azure(s.to_string());
+        }
         if prefix == "http" || prefix == "https" {
             return InputSource::Http(s.to_string());
         }
+        if matches!(
+            prefix.as_str(),
+            "postgres" | "postgresql" | "mysql" | "mariadb"
+        ) {
+            return InputSource::Database(s.to_string());
+        }
     }
     InputSource::Local(path.to_path_buf())
 }
@@ -54,7 +79,65 @@ pub(crate) fn url_path_extension(url: &str) -> (String, Option<String>) {
     (path_part, ext)
 }
 
-/// For S3/GCS: Polars can only scan Parquet directly. So we pass through only when the path is
+/// For a SQLite URL: the file of `sqlite:///path/to/data.db?table=orders` (or
+/// `sqlite://relative.db`) and the table it names, if any. None for anything else; server URLs
+/// (`postgres://`, `mysql://`) are [`InputSource::Database`].
+pub(crate) fn sqlite_url(path: &Path) -> Option<(PathBuf, Option<String>)> {
+    let s = path.as_os_str().to_string_lossy();
+    let (scheme, rest) = s.split_once("://")?;
+    if !matches!(scheme.to_lowercase().as_str(), "sqlite" | "sqlite3") {
+        return None;
+    }
+    let (file, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let table = query
+        .split('&')
+        .find_map(|param| param.strip_prefix("table="))
+        .filter(|table| !table.is_empty())
+        .map(String::from);
+    Some((PathBuf::from(file), table))
+}
+
+/// Copy `input` (standard input) to a temporary file in `temp_dir` (default: the system temp
+/// directory) so it can be scanned like any local file. Piped data has no extension, so
+/// `format` is required; the file is named after it (and `compression`) so the usual
+/// extension-based loading applies. The caller owns the returned file and deletes it.
+pub(crate) fn buffer_stdin_to_temp(
+    mut input: impl Read,
+    format: Option<FileFormat>,
+    compression: Option<CompressionFormat>,
+    temp_dir: Option<&Path>,
+) -> Result<PathBuf> {
+    let format = format.ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "Reading from standard input needs --format, e.g. `datui - --format csv`."
+        )
+    })?;
+    let dir = temp_dir
+        .map(Path::to_path_buf)
+        .unwrap_or_else(std::env::temp_dir);
+    let suffix = match compression {
+        Some(c) => format!(".{}.{}", format.extension(), c.extension()),
+        None => format!(".{}", format.extension()),
+    };
+    let mut temp = tempfile::Builder::new()
+        .prefix("datui-stdin-")
+        .suffix(&suffix)
+        .tempfile_in(&dir)
+        .map_err(|_| color_eyre::eyre::eyre!("Could not create a temporary file."))?;
+    let bytes = std::io::copy(&mut input, &mut temp)
+        .map_err(|e| color_eyre::eyre::eyre!("Could not read standard input: {}", e))?;
+    if bytes == 0 {
+        return Err(color_eyre::eyre::eyre!(
+            "Standard input is empty. Pipe data into datui, e.g. `cat data.csv | datui - --format csv`."
+        ));
+    }
+    let (_file, path) = temp
+        .keep()
+        .map_err(|_| color_eyre::eyre::eyre!("Could not save standard input."))?;
+    Ok(path)
+}
+
+/// For S3/GCS/Azure: Polars can only scan Parquet directly. So we pass through only when the path is
 /// Parquet or looks like a directory/glob (no extension, trailing slash, or *). All other paths
 /// (e.g. .csv, .json, .gz, .csv.gz) must be downloaded first.
 /// Returns true when the path should be downloaded to temp instead of passed to Polars.
@@ -68,10 +151,173 @@ pub(crate) fn cloud_path_should_download(ext: Option<&str>, is_glob: bool) -> bo
     }
 }
 
+/// For the part of an `s3://` URL after the scheme: when it names a bucket or a prefix
+/// (`bucket`, `bucket/`, `bucket/dir/`) rather than an object or a glob, the bucket and the key
+/// prefix (empty, or ending with '/').
+pub(crate) fn s3_listing_prefix(rest: &str) -> Option<(String, String)> {
+    if rest.is_empty() || rest.contains('*') {
+        return None;
+    }
+    match rest.split_once('/') {
+        None => Some((rest.to_string(), String::new())),
+        Some((bucket, key)) if key.is_empty() || key.ends_with('/') => {
+            Some((bucket.to_string(), key.to_string()))
+        }
+        Some(_) => None,
+    }
+}
+
+/// Whether the file name of a local path holds a `*` or `?` wildcard.
+pub(crate) fn has_wildcard(path: &Path) -> bool {
+    matches!(input_source(path), InputSource::Local(_))
+        && path
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().contains(['*', '?']))
+}
+
+/// Files in the pattern's directory whose names match its `*` / `?` file name, sorted.
+/// Errors when nothing matches or the directory part holds a wildcard.
+pub(crate) fn expand_wildcard(path: &Path) -> Result<Vec<PathBuf>> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    if dir.to_string_lossy().contains(['*', '?']) {
+        return Err(color_eyre::eyre::eyre!(
+            "Wildcards are only supported in the file name: {}",
+            path.display()
+        ));
+    }
+    let mut matched: Vec<PathBuf> = std::fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+        .filter(|entry| wildcard_match(&name, &entry.file_name().to_string_lossy()))
+        .map(|entry| entry.path())
+        .collect();
+    matched.sort();
+    if matched.is_empty() {
+        return Err(color_eyre::eyre::eyre!("No files match {}", path.display()));
+    }
+    Ok(matched)
+}
+
+/// Expand wildcard paths (`data/2024-*.csv`) into the files they match; other paths are kept.
+/// Without a forced `format`, the matches must share one format unless `mixed_formats` is set
+/// (diagonal concat reads each file with its own format).
+pub(crate) fn expand_local_globs(
+    paths: &[PathBuf],
+    format: Option<FileFormat>,
+    mixed_formats: bool,
+) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if !has_wildcard(path) {
+            files.push(path.clone());
+            continue;
+        }
+        let matched = expand_wildcard(path)?;
+        if format.is_none() && !mixed_formats {
+            // Count matches per format, in order of first appearance
+            let mut groups: Vec<(&str, usize)> = Vec::new();
+            for file in &matched {
+                let label = crate::workspace::detect_format(file)
+                    .map(|(f, _)| f.extension())
+                    .unwrap_or("unknown");
+                match groups.iter_mut().find(|(l, _)| *l == label) {
+                    Some((_, n)) => *n += 1,
+                    None => groups.push((label, 1)),
+                }
+            }
+            if groups.len() > 1 {
+                let counts: Vec<String> = groups.iter().map(|(l, n)| format!("{n} {l}")).collect();
+                return Err(color_eyre::eyre::eyre!(
+                    "{} matches files of different formats ({}). Narrow the pattern, or combine them with --concat diagonal.",
+                    path.display(),
+                    counts.join(", ")
+                ));
+            }
+        }
+        files.extend(matched);
+    }
+    Ok(files)
+}
+
+/// Match `text` against a pattern where `*` is any run of characters and `?` is one character.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn wildcard_match_patterns() {
+        assert!(wildcard_match("*.parquet", "2024-01.parquet"));
+        assert!(wildcard_match("sales_??.csv", "sales_01.csv"));
+        assert!(!wildcard_match("*.parquet", "notes.txt"));
+        assert!(!wildcard_match("sales_??.csv", "sales_1.csv"));
+        assert!(wildcard_match("*", ""));
+    }
+
+    #[test]
+    fn expand_local_globs_checks_formats() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "2024-01.csv",
+            "2024-02.csv",
+            "2024-03.parquet",
+            "2023-12.csv",
+        ] {
+            std::fs::write(dir.path().join(name), "a\n1\n").unwrap();
+        }
+        let csvs = expand_local_globs(&[dir.path().join("2024-*.csv")], None, false).unwrap();
+        assert_eq!(
+            csvs,
+            vec![
+                dir.path().join("2024-01.csv"),
+                dir.path().join("2024-02.csv")
+            ]
+        );
+        let err = expand_local_globs(&[dir.path().join("2024-*")], None, false).unwrap_err();
+        assert!(err.to_string().contains("2 csv, 1 parquet"), "{err}");
+        assert_eq!(
+            expand_local_globs(&[dir.path().join("2024-*")], None, true)
+                .unwrap()
+                .len(),
+            3
+        );
+        let plain = dir.path().join("2023-12.csv");
+        assert_eq!(
+            expand_local_globs(std::slice::from_ref(&plain), None, false).unwrap(),
+            vec![plain]
+        );
+        assert!(!has_wildcard(Path::new("s3://bucket/*.parquet")));
+    }
+
     #[test]
     fn input_source_local_path() {
         let p = PathBuf::from("/tmp/file.parquet");
@@ -124,12 +370,94 @@ mod tests {
         }
     }
 
+    #[test]
+    fn input_source_azure() {
+        let p = PathBuf::from("az://container/path/file.parquet");
+        assert_eq!(
+            input_source(&p),
+            InputSource::Azure("az://container/path/file.parquet".to_string())
+        );
+        let url = "abfss://fs@account.dfs.core.windows.net/dir/file.csv";
+        assert_eq!(
+            input_source(Path::new(url)),
+            InputSource::Azure(url.to_string())
+        );
+        let (path, ext) = url_path_extension(url);
+        assert_eq!(path.split_once('/').unwrap().1, "dir/file.csv");
+        assert_eq!(ext.as_deref(), Some("csv"));
+    }
+
+    #[test]
+    fn s3_listing_prefix_only_for_buckets_and_prefixes() {
+        assert_eq!(
+            s3_listing_prefix("bucket"),
+            Some(("bucket".to_string(), String::new()))
+        );
+        assert_eq!(
+            s3_listing_prefix("bucket/"),
+            Some(("bucket".to_string(), String::new()))
+        );
+        assert_eq!(
+            s3_listing_prefix("bucket/data/2024/"),
+            Some(("bucket".to_string(), "data/2024/".to_string()))
+        );
+        assert_eq!(s3_listing_prefix("bucket/data/file.csv"), None);
+        assert_eq!(s3_listing_prefix("bucket/data/**/*.parquet"), None);
+    }
+
     #[test]
     fn input_source_unknown_scheme_stays_local() {
         let p = PathBuf::from("file:///tmp/foo.parquet");
         assert!(matches!(input_source(&p), InputSource::Local(_)));
     }
 
+    #[test]
+    fn sqlite_url_names_file_and_table() {
+        let (file, table) = sqlite_url(Path::new("sqlite:///data/shop.db?table=orders")).unwrap();
+        assert_eq!(file, PathBuf::from("/data/shop.db"));
+        assert_eq!(table.as_deref(), Some("orders"));
+        let (file, table) = sqlite_url(Path::new("SQLITE://shop.db")).unwrap();
+        assert_eq!((file, table), (PathBuf::from("shop.db"), None));
+
+        let server = "postgres://me@host/db?table=orders";
+        assert!(sqlite_url(Path::new(server)).is_none());
+        assert_eq!(
+            input_source(Path::new(server)),
+            InputSource::Database(server.to_string())
+        );
+        assert!(sqlite_url(Path::new("s3://bucket/shop.db")).is_none());
+        assert!(sqlite_url(Path::new("shop.db")).is_none());
+    }
+
+    #[test]
+    fn input_source_stdin() {
+        assert_eq!(input_source(Path::new("-")), InputSource::Stdin);
+        assert!(matches!(
+            input_source(Path::new("./-")),
+            InputSource::Local(_)
+        ));
+    }
+
+    #[test]
+    fn buffer_stdin_names_file_after_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = buffer_stdin_to_temp(
+            "a,b\n1,2\n".as_bytes(),
+            Some(FileFormat::Csv),
+            Some(CompressionFormat::Gzip),
+            Some(dir.path()),
+        )
+        .unwrap();
+        assert!(path.to_string_lossy().ends_with(".csv.gz"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a,b\n1,2\n");
+
+        let err = buffer_stdin_to_temp("a\n".as_bytes(), None, None, Some(dir.path()));
+        assert!(err.unwrap_err().to_string().contains("--format"));
+        let err =
+            buffer_stdin_to_temp("".as_bytes(), Some(FileFormat::Csv), None, Some(dir.path()));
+        assert!(err.unwrap_err().to_string().contains("empty"));
+    }
+
     #[test]
     fn url_path_extension_s3() {
         let (path, ext) = url_path_extension("s3://bucket/key.parquet");
@@ -0,0 +1,179 @@
+//! SQLite export: the frame is written to a table of a SQLite database file, created when
+//! missing. Integers and booleans become INTEGER columns, floats REAL, binary BLOB and everything
+//! else (strings, dates, lists, ...) TEXT, as displayed. What happens when the table already
+//! exists is chosen with [`IfExists`].
+
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use polars::prelude::*;
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection};
+use std::path::Path;
+
+/// Table name used when none is given.
+pub const DEFAULT_TABLE_NAME: &str = "data";
+
+/// What to do when the table is already in the database.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IfExists {
+    /// Stop with an error.
+    #[default]
+    Fail,
+    /// Drop the table and write it again.
+    Replace,
+    /// Add the rows to the table.
+    Append,
+}
+
+impl IfExists {
+    pub const ALL: [Self; 3] = [Self::Fail, Self::Replace, Self::Append];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fail => "Fail",
+            Self::Replace => "Replace",
+            Self::Append => "Append",
+        }
+    }
+}
+
+/// `name` as a quoted SQL identifier.
+pub(crate) fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn column_type(dtype: &DataType) -> &'static str {
+    match dtype {
+        DataType::Boolean => "INTEGER",
+        d if d.is_integer() => "INTEGER",
+        d if d.is_float() => "REAL",
+        DataType::Binary => "BLOB",
+        _ => "TEXT",
+    }
+}
+
+fn sql_value(value: AnyValue) -> Value {
+    match value {
+        AnyValue::Null => Value::Null,
+        AnyValue::Boolean(b) => Value::Integer(b as i64),
+        AnyValue::Int8(n) => Value::Integer(n as i64),
+        AnyValue::Int16(n) => Value::Integer(n as i64),
+        AnyValue::Int32(n) => Value::Integer(n as i64),
+        AnyValue::Int64(n) => Value::Integer(n),
+        AnyValue::UInt8(n) => Value::Integer(n as i64),
+        AnyValue::UInt16(n) => Value::Integer(n as i64),
+        AnyValue::UInt32(n) => Value::Integer(n as i64),
+        // Past i64::MAX only a REAL can hold it
+        AnyValue::UInt64(n) => match i64::try_from(n) {
+            Ok(n) => Value::Integer(n),
+            Err(_) => Value::Real(n as f64),
+        },
+        AnyValue::Float32(f) => Value::Real(f as f64),
+        AnyValue::Float64(f) => Value::Real(f),
+        AnyValue::Binary(b) => Value::Blob(b.to_vec()),
+        AnyValue::BinaryOwned(b) => Value::Blob(b),
+        v => Value::Text(v.str_value().into_owned()),
+    }
+}
+
+/// Write `df` to table `table` (the default when blank) of the database at `path`.
+pub fn write_sqlite(df: &DataFrame, path: &Path, table: &str, if_exists: IfExists) -> Result<()> {
+    let table = match table.trim() {
+        "" => DEFAULT_TABLE_NAME,
+        name => name,
+    };
+    let mut conn = Connection::open(path)?;
+    let tx = conn.transaction()?;
+    let exists: bool = tx.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+        [table],
+        |row| row.get(0),
+    )?;
+    let create = !exists || if_exists == IfExists::Replace;
+    match if_exists {
+        IfExists::Fail if exists => {
+            return Err(eyre!(
+                "Table '{}' already exists in {}",
+                table,
+                path.display()
+            ))
+        }
+        IfExists::Replace if exists => {
+            tx.execute(&format!("DROP TABLE {}", quote(table)), [])?;
+        }
+        _ => {}
+    }
+    let columns = df.get_columns();
+    if create {
+        let definitions: Vec<String> = columns
+            .iter()
+            .map(|c| format!("{} {}", quote(c.name()), column_type(c.dtype())))
+            .collect();
+        tx.execute(
+            &format!("CREATE TABLE {} ({})", quote(table), definitions.join(", ")),
+            [],
+        )?;
+    }
+    {
+        let names: Vec<String> = columns.iter().map(|c| quote(c.name())).collect();
+        let placeholders: Vec<String> = (1..=columns.len()).map(|i| format!("?{}", i)).collect();
+        let mut insert = tx.prepare(&format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            quote(table),
+            names.join(", "),
+            placeholders.join(", ")
+        ))?;
+        let series: Vec<&Series> = columns.iter().map(|c| c.as_materialized_series()).collect();
+        for row in 0..df.height() {
+            let values = series
+                .iter()
+                .map(|s| s.get(row).map(sql_value))
+                .collect::<PolarsResult<Vec<Value>>>()?;
+            insert.execute(params_from_iter(values))?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(path: &Path, table: &str) -> i64 {
+        Connection::open(path)
+            .unwrap()
+            .query_row(&format!("SELECT COUNT(*) FROM {}", quote(table)), [], |r| {
+                r.get(0)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn if_exists_decides_between_fail_replace_and_append() {
+        let df = df!(
+            "id" => [1i64, 2],
+            "name" => [Some("a"), None],
+            "score" => [0.5, 1.5],
+        )
+        .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.db");
+        write_sqlite(&df, &path, "my table", IfExists::Fail).unwrap();
+        assert_eq!(count(&path, "my table"), 2);
+        let name: Option<String> = Connection::open(&path)
+            .unwrap()
+            .query_row("SELECT name FROM \"my table\" WHERE id = 2", [], |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert_eq!(name, None);
+
+        let err = write_sqlite(&df, &path, "my table", IfExists::Fail).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+        write_sqlite(&df, &path, "my table", IfExists::Append).unwrap();
+        assert_eq!(count(&path, "my table"), 4);
+        write_sqlite(&df, &path, "my table", IfExists::Replace).unwrap();
+        assert_eq!(count(&path, "my table"), 2);
+    }
+}
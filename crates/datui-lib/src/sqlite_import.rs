@@ -0,0 +1,300 @@
+//! SQLite import: list the tables and views of a SQLite database file (`.db`, `.sqlite`,
+//! `.sqlite3`) and read one into a frame. SQLite columns are not typed, so each column's type
+//! comes from its stored values: whole numbers become Int64, numbers with any REAL Float64,
+//! BLOBs Binary and everything else (including columns mixing text and numbers) String.
+//! A database with several tables opens the [table picker](crate::table_picker) unless
+//! `--table` names one, or `--sql` gives a query to load instead.
+
+use crate::sqlite_export::quote;
+use crate::table_picker::TableEntry;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use polars::prelude::*;
+use rusqlite::types::Value;
+use rusqlite::{Connection, OpenFlags};
+use std::path::Path;
+
+/// Tables and views by name, leaving out SQLite's internal tables.
+const TABLES_QUERY: &str = "SELECT name, type FROM sqlite_master \
+     WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite_%' ORDER BY name";
+
+fn open(path: &Path) -> Result<Connection> {
+    Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| eyre!("SQLite: {}: {}", path.display(), e))
+}
+
+/// Tables and views of the database at `path`, by name, with their sizes. Views have no row count, since counting runs their query.
+pub fn list_tables(path: &Path) -> Result<Vec<TableEntry>> {
+    let conn = open(path)?;
+    let mut stmt = conn.prepare(TABLES_QUERY)?;
+    let entries = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)? == "view"))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| eyre!("SQLite: {}: {}", path.display(), e))?;
+    entries
+        .into_iter()
+        .map(|(name, is_view)| {
+            let columns: usize = conn.query_row(
+                "SELECT COUNT(*) FROM pragma_table_info(?1)",
+                [&name],
+                |row| row.get(0),
+            )?;
+            let rows = if is_view {
+                None
+            } else {
+                Some(conn.query_row(
+                    &format!("SELECT COUNT(*) FROM {}", quote(&name)),
+                    [],
+                    |row| row.get(0),
+                )?)
+            };
+            Ok(TableEntry {
+                name,
+                kind: if is_view { "view" } else { "table" },
+                columns,
+                rows,
+            })
+        })
+        .collect()
+}
+
+/// Names of the tables and views of the database at `path`, without counting their rows.
+pub fn table_names(path: &Path) -> Result<Vec<String>> {
+    let conn = open(path)?;
+    let mut stmt = conn.prepare(TABLES_QUERY)?;
+    let names = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| eyre!("SQLite: {}: {}", path.display(), e))?;
+    Ok(names)
+}
+
+/// The table to load from `path`: `table` when given (it must exist), else the only one.
+pub fn choose_table(path: &Path, table: Option<&str>) -> Result<String> {
+    let tables = list_tables(path)?;
+    if let Some(table) = table {
+        return match tables.iter().find(|t| t.name == table) {
+            Some(t) => Ok(t.name.clone()),
+            None => Err(eyre!(
+                "No table '{}' in {} (tables: {})",
+                table,
+                path.display(),
+                joined_names(&tables)
+            )),
+        };
+    }
+    match tables.as_slice() {
+        [] => Err(eyre!("{} has no tables", path.display())),
+        [only] => Ok(only.name.clone()),
+        _ => Err(eyre!(
+            "{} has several tables ({}). Choose one with --table.",
+            path.display(),
+            joined_names(&tables)
+        )),
+    }
+}
+
+fn joined_names(tables: &[TableEntry]) -> String {
+    tables
+        .iter()
+        .map(|t| t.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Storage class seen in a column so far; decides the column's type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Null,
+    Integer,
+    Real,
+    Blob,
+    Text,
+}
+
+impl ColumnKind {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Integer(_) => Self::Integer,
+            Value::Real(_) => Self::Real,
+            Value::Blob(_) => Self::Blob,
+            Value::Text(_) => Self::Text,
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Null, k) | (k, Self::Null) => k,
+            (a, b) if a == b => a,
+            (Self::Integer, Self::Real) | (Self::Real, Self::Integer) => Self::Real,
+            _ => Self::Text,
+        }
+    }
+}
+
+fn column_series(name: &str, values: &[Value]) -> Series {
+    let kind = values
+        .iter()
+        .fold(ColumnKind::Null, |k, v| k.merge(ColumnKind::of(v)));
+    let name: PlSmallStr = name.into();
+    match kind {
+        ColumnKind::Integer => Series::new(
+            name,
+            values
+                .iter()
+                .map(|v| match v {
+                    Value::Integer(n) => Some(*n),
+                    _ => None,
+                })
+                .collect::<Vec<_>>(),
+        ),
+        ColumnKind::Real => Series::new(
+            name,
+            values
+                .iter()
+                .map(|v| match v {
+                    Value::Integer(n) => Some(*n as f64),
+                    Value::Real(f) => Some(*f),
+                    _ => None,
+                })
+                .collect::<Vec<_>>(),
+        ),
+        ColumnKind::Blob => BinaryChunked::from_iter_options(
+            name,
+            values.iter().map(|v| match v {
+                Value::Blob(b) => Some(b.as_slice()),
+                _ => None,
+            }),
+        )
+        .into_series(),
+        ColumnKind::Null | ColumnKind::Text => Series::new(
+            name,
+            values
+                .iter()
+                .map(|v| match v {
+                    Value::Null => None,
+                    Value::Integer(n) => Some(n.to_string()),
+                    Value::Real(f) => Some(f.to_string()),
+                    Value::Text(s) => Some(s.clone()),
+                    Value::Blob(b) => Some(String::from_utf8_lossy(b).into_owned()),
+                })
+                .collect::<Vec<_>>(),
+        ),
+    }
+}
+
+/// Read all rows of `table` in the database at `path`.
+pub fn read_table(path: &Path, table: &str) -> Result<DataFrame> {
+    read_query(path, &format!("SELECT * FROM {}", quote(table)))
+}
+
+/// Read the rows `sql` returns from the database at `path`, which is opened read-only.
+pub fn read_query(path: &Path, sql: &str) -> Result<DataFrame> {
+    let conn = open(path)?;
+    let mut stmt = conn
+        .prepare(sql)
+        .map_err(|e| eyre!("SQLite: {}: {}", path.display(), e))?;
+    let names: Vec<String> = stmt.column_names().iter().map(|n| n.to_string()).collect();
+    let mut values: Vec<Vec<Value>> = vec![Vec::new(); names.len()];
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        for (i, column) in values.iter_mut().enumerate() {
+            column.push(row.get::<_, Value>(i)?);
+        }
+    }
+    let columns = names
+        .iter()
+        .zip(&values)
+        .map(|(name, values)| column_series(name, values).into())
+        .collect();
+    Ok(DataFrame::new(columns)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn database(path: &Path) {
+        let conn = Connection::open(path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE people (id INTEGER, name TEXT, score REAL, mixed, photo BLOB);
+             INSERT INTO people VALUES (1, 'ann', 1, 'x', x'0102');
+             INSERT INTO people VALUES (2, NULL, 2.5, 3, NULL);
+             CREATE TABLE empty (a INTEGER);
+             CREATE VIEW named AS SELECT name FROM people WHERE name IS NOT NULL;",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn lists_tables_and_reads_typed_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.db");
+        database(&path);
+
+        let tables = list_tables(&path).unwrap();
+        let summary: Vec<(&str, &str, usize, Option<usize>)> = tables
+            .iter()
+            .map(|t| (t.name.as_str(), t.kind, t.columns, t.rows))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("empty", "table", 1, Some(0)),
+                ("named", "view", 1, None),
+                ("people", "table", 5, Some(2)),
+            ]
+        );
+
+        let df = read_table(&path, "people").unwrap();
+        let dtypes: Vec<DataType> = df.dtypes();
+        assert_eq!(
+            dtypes,
+            vec![
+                DataType::Int64,
+                DataType::String,
+                DataType::Float64,
+                DataType::String,
+                DataType::Binary,
+            ]
+        );
+        assert_eq!(df.height(), 2);
+        assert_eq!(df.column("name").unwrap().null_count(), 1);
+        assert_eq!(read_table(&path, "named").unwrap().height(), 1);
+
+        assert_eq!(choose_table(&path, Some("people")).unwrap(), "people");
+        let err = choose_table(&path, None).unwrap_err().to_string();
+        assert!(
+            err.contains("several tables (empty, named, people)"),
+            "{err}"
+        );
+        let err = choose_table(&path, Some("nope")).unwrap_err().to_string();
+        assert!(err.contains("No table 'nope'"), "{err}");
+    }
+
+    #[test]
+    fn reads_query_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.db");
+        database(&path);
+
+        let df = read_query(
+            &path,
+            "SELECT id, score * 2 AS doubled FROM people ORDER BY id",
+        )
+        .unwrap();
+        assert_eq!(df.get_column_names(), ["id", "doubled"]);
+        assert_eq!(
+            df.column("doubled").unwrap().f64().unwrap().get(1),
+            Some(5.0)
+        );
+        let err = read_query(&path, "SELECT * FROM nope")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("no such table: nope"), "{err}");
+        assert!(read_query(&path, "DELETE FROM people").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::sampling::{sample_lazy, SamplingStrategy};
 use color_eyre::eyre::Report;
 use color_eyre::Result;
 use polars::polars_compute::rolling::QuantileMethod;
@@ -271,9 +272,9 @@ impl Default for ComputeOptions {
 pub fn compute_statistics(
     lf: &LazyFrame,
     sample_size: Option<usize>,
-    seed: u64,
+    sampling: &SamplingStrategy,
 ) -> Result<AnalysisResults> {
-    compute_statistics_with_options(lf, sample_size, seed, ComputeOptions::default())
+    compute_statistics_with_options(lf, sample_size, sampling, ComputeOptions::default())
 }
 
 /// Computes comprehensive statistics for a LazyFrame.
@@ -289,7 +290,7 @@ pub fn compute_statistics(
 pub fn compute_statistics_with_options(
     lf: &LazyFrame,
     sample_size: Option<usize>,
-    seed: u64,
+    sampling: &SamplingStrategy,
     options: ComputeOptions,
 ) -> Result<AnalysisResults> {
     let schema = lf.clone().collect_schema()?;
@@ -319,7 +320,7 @@ pub fn compute_statistics_with_options(
     };
 
     let df = if should_sample {
-        sample_dataframe(lf, sampling_threshold, seed, use_streaming)?
+        sample_dataframe(lf, total_rows, sampling_threshold, sampling, use_streaming)?
     } else {
         collect_lazy(lf.clone(), use_streaming).map_err(Report::from)?
     };
@@ -410,7 +411,7 @@ pub fn compute_statistics_with_options(
         column_statistics,
         total_rows,
         sample_size: actual_sample_size,
-        sample_seed: seed,
+        sample_seed: sampling.seed.unwrap_or_default(),
         correlation_matrix,
         distribution_analyses,
     })
@@ -621,20 +622,20 @@ pub fn compute_describe_from_lazy(
     lf: &LazyFrame,
     total_rows: usize,
     sample_size: Option<usize>,
-    seed: u64,
+    sampling: &SamplingStrategy,
     polars_streaming: bool,
 ) -> Result<AnalysisResults> {
     let schema = lf.clone().collect_schema()?;
     let should_sample = sample_size.is_some_and(|t| total_rows >= t);
     if should_sample {
         let threshold = sample_size.unwrap();
-        let df = sample_dataframe(lf, threshold, seed, polars_streaming)?;
+        let df = sample_dataframe(lf, total_rows, threshold, sampling, polars_streaming)?;
         return compute_describe_single_aggregation(
             &df,
             &schema,
             total_rows,
             Some(threshold),
-            seed,
+            sampling.seed.unwrap_or_default(),
             polars_streaming,
         );
     }
@@ -645,7 +646,7 @@ pub fn compute_describe_from_lazy(
         column_statistics,
         total_rows,
         None,
-        seed,
+        sampling.seed.unwrap_or_default(),
     ))
 }
 
@@ -704,7 +705,7 @@ pub fn compute_distribution_statistics(
     results: &mut AnalysisResults,
     lf: &LazyFrame,
     sample_size: Option<usize>,
-    seed: u64,
+    sampling: &SamplingStrategy,
     polars_streaming: bool,
 ) -> Result<()> {
     // sample_size: None = never sample; Some(threshold) = sample when total_rows >= threshold
@@ -717,7 +718,13 @@ pub fn compute_distribution_statistics(
     };
 
     let df = if should_sample {
-        sample_dataframe(lf, sampling_threshold, seed, polars_streaming)?
+        sample_dataframe(
+            lf,
+            results.total_rows,
+            sampling_threshold,
+            sampling,
+            polars_streaming,
+        )?
     } else {
         collect_lazy(lf.clone(), polars_streaming).map_err(Report::from)?
     };
@@ -813,42 +820,17 @@ fn is_categorical_type(dtype: &DataType) -> bool {
     matches!(dtype, DataType::String | DataType::Categorical(..))
 }
 
-/// Samples a LazyFrame for analysis when row count exceeds threshold. Used by chunked describe.
+/// Samples `sample_size` rows of a LazyFrame with `total_rows` rows for analysis, using the
+/// sampling strategy (see [`crate::sampling`]).
 pub fn sample_dataframe(
     lf: &LazyFrame,
+    total_rows: usize,
     sample_size: usize,
-    seed: u64,
+    sampling: &SamplingStrategy,
     polars_streaming: bool,
 ) -> Result<DataFrame> {
-    let collect_multiplier = if sample_size <= 1000 {
-        5
-    } else if sample_size <= 5000 {
-        3
-    } else {
-        2
-    };
-
-    let collect_limit = (sample_size * collect_multiplier).min(50_000);
-    let df = collect_lazy(lf.clone().limit(collect_limit as u32), polars_streaming)
-        .map_err(Report::from)?;
-    let total_collected = df.height();
-
-    if total_collected <= sample_size {
-        return Ok(df);
-    }
-
-    let step = total_collected / sample_size;
-    let start_offset = (seed as usize) % step;
-
-    let indices: Vec<u32> = (0..sample_size)
-        .map(|i| {
-            let idx = start_offset + i * step;
-            (idx.min(total_collected - 1)) as u32
-        })
-        .collect();
-
-    let indices_ca = UInt32Chunked::new("indices".into(), indices);
-    df.take(&indices_ca)
+    let sample = sample_lazy(lf, total_rows, sample_size, sampling, polars_streaming)?;
+    collect_lazy(sample, polars_streaming)
         .map_err(|e| color_eyre::eyre::eyre!("Sampling error: {}", e))
 }
 
@@ -2943,7 +2925,7 @@ pub(crate) fn gamma_quantile(p: f64, shape: f64, scale: f64) -> f64 {
 }
 
 // Chi-squared distribution CDF (special case of Gamma with shape = df/2, scale = 2)
-fn chi_squared_cdf(x: f64, df: f64) -> f64 {
+pub(crate) fn chi_squared_cdf(x: f64, df: f64) -> f64 {
     if x <= 0.0 {
         return 0.0;
     }
@@ -0,0 +1,148 @@
+//! Describe statistics compared against a saved baseline: snapshot the statistics under one
+//! filter, change the filter, and view both side by side with the change per column.
+//!
+//! Columns are matched by name. Rows follow the current column order; columns that exist only in
+//! the baseline come last.
+
+use crate::statistics::{AnalysisContext, AnalysisResults, ColumnStatistics};
+
+/// Statistics shown in the comparison, in display order.
+pub const COMPARE_STATS: [&str; 7] = ["Count", "Nulls", "Mean", "Std", "Min", "Median", "Max"];
+
+/// Describe results saved for comparison, with a label for the data they were computed on.
+#[derive(Clone)]
+pub struct StatsBaseline {
+    pub label: String,
+    pub results: AnalysisResults,
+}
+
+/// One column's statistics in the baseline and the current results (`None` = not available,
+/// e.g. the column is missing or not numeric).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompareRow {
+    pub column: String,
+    pub baseline: [Option<f64>; COMPARE_STATS.len()],
+    pub current: [Option<f64>; COMPARE_STATS.len()],
+}
+
+impl CompareRow {
+    /// Change of statistic `stat` from the baseline to the current results.
+    pub fn delta(&self, stat: usize) -> Option<f64> {
+        Some(self.current[stat]? - self.baseline[stat]?)
+    }
+
+    /// Change of statistic `stat` relative to the baseline, in percent (None when the baseline
+    /// value is zero).
+    pub fn delta_percent(&self, stat: usize) -> Option<f64> {
+        let base = self.baseline[stat]?;
+        if base == 0.0 {
+            return None;
+        }
+        Some(self.delta(stat)? / base.abs() * 100.0)
+    }
+}
+
+/// Short description of the rows statistics were computed on, e.g. `query "region = 'EU'"`.
+pub fn context_label(context: &AnalysisContext) -> String {
+    let mut parts = Vec::new();
+    if context.has_query {
+        parts.push(format!("query \"{}\"", context.query.trim()));
+    }
+    if context.has_filters {
+        let plural = if context.filter_count == 1 { "" } else { "s" };
+        parts.push(format!("{} filter{}", context.filter_count, plural));
+    }
+    if context.is_drilled_down {
+        if let (Some(key), Some(columns)) = (&context.group_key, &context.group_columns) {
+            let group: Vec<String> = columns
+                .iter()
+                .zip(key)
+                .map(|(c, v)| format!("{}={}", c, v))
+                .collect();
+            parts.push(format!("group {}", group.join(", ")));
+        }
+    }
+    if parts.is_empty() {
+        "all rows".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+fn stat_values(stats: &ColumnStatistics) -> [Option<f64>; COMPARE_STATS.len()] {
+    let numeric = stats.numeric_stats.as_ref();
+    let finite = |v: f64| if v.is_nan() { None } else { Some(v) };
+    [
+        Some(stats.count as f64),
+        Some(stats.null_count as f64),
+        numeric.and_then(|n| finite(n.mean)),
+        numeric.and_then(|n| finite(n.std)),
+        numeric.and_then(|n| finite(n.min)),
+        numeric.and_then(|n| finite(n.median)),
+        numeric.and_then(|n| finite(n.max)),
+    ]
+}
+
+/// Pair up the columns of `baseline` and `current` by name.
+pub fn compare(baseline: &AnalysisResults, current: &AnalysisResults) -> Vec<CompareRow> {
+    let find = |results: &AnalysisResults, name: &str| {
+        results
+            .column_statistics
+            .iter()
+            .find(|c| c.name == name)
+            .map(stat_values)
+            .unwrap_or_default()
+    };
+    let mut rows: Vec<CompareRow> = current
+        .column_statistics
+        .iter()
+        .map(|c| CompareRow {
+            column: c.name.clone(),
+            baseline: find(baseline, &c.name),
+            current: stat_values(c),
+        })
+        .collect();
+    rows.extend(
+        baseline
+            .column_statistics
+            .iter()
+            .filter(|c| !current.column_statistics.iter().any(|n| n.name == c.name))
+            .map(|c| CompareRow {
+                column: c.name.clone(),
+                baseline: stat_values(c),
+                current: Default::default(),
+            }),
+    );
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::compute_statistics;
+    use polars::prelude::*;
+
+    fn describe(df: DataFrame) -> AnalysisResults {
+        compute_statistics(&df.lazy(), None, &Default::default()).unwrap()
+    }
+
+    #[test]
+    fn compare_matches_columns_by_name() {
+        let global = describe(df!("region" => ["EU", "US", "EU", "US"], "sales" => [10.0, 30.0, 20.0, 40.0], "old" => [1, 2, 3, 4]).unwrap());
+        let eu = describe(df!("sales" => [10.0, 20.0], "region" => ["EU", "EU"]).unwrap());
+        let rows = compare(&global, &eu);
+        let names: Vec<&str> = rows.iter().map(|r| r.column.as_str()).collect();
+        assert_eq!(names, ["sales", "region", "old"]);
+
+        let sales = &rows[0];
+        assert_eq!(sales.baseline[0], Some(4.0));
+        assert_eq!(sales.current[0], Some(2.0));
+        assert_eq!(sales.delta(0), Some(-2.0));
+        assert_eq!(sales.delta(2), Some(15.0 - 25.0));
+        assert_eq!(sales.delta_percent(2), Some(-40.0));
+
+        assert_eq!(rows[1].delta(2), None, "string column has no mean");
+        assert_eq!(rows[2].current, [None; COMPARE_STATS.len()]);
+        assert_eq!(rows[2].delta(0), None);
+    }
+}
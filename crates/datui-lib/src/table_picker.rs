@@ -0,0 +1,271 @@
+//! Picker for files holding several tables: the sheets of an Excel workbook or the tables and
+//! views of a SQLite database. It opens on its own for a database with several tables opened
+//! without `--table`, and with `O` to switch to another sheet or table of the current file.
+//! Enter reloads the file with the highlighted one.
+
+use crate::{FileFormat, OpenOptions};
+use calamine::{open_workbook_auto, Reader};
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use ratatui::widgets::TableState;
+use std::path::{Path, PathBuf};
+
+/// What holds the tables of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableSource {
+    /// Sheets of an Excel workbook (`--sheet`).
+    Excel,
+    /// Tables and views of a SQLite database (`--table`).
+    Sqlite,
+}
+
+impl TableSource {
+    /// The source of `path` opened with `options`: local Excel and SQLite files only.
+    pub fn of(path: &Path, options: &OpenOptions) -> Option<Self> {
+        if !matches!(
+            crate::source::input_source(path),
+            crate::source::InputSource::Local(_)
+        ) {
+            return None;
+        }
+        match options.format.or_else(|| FileFormat::from_path(path))? {
+            FileFormat::Excel => Some(Self::Excel),
+            FileFormat::Sqlite => Some(Self::Sqlite),
+            _ => None,
+        }
+    }
+
+    /// What one table is called, e.g. "sheet".
+    pub fn noun(self) -> &'static str {
+        match self {
+            Self::Excel => "sheet",
+            Self::Sqlite => "table",
+        }
+    }
+
+    /// The tables of `path` with their sizes.
+    pub fn list(self, path: &Path) -> Result<Vec<TableEntry>> {
+        match self {
+            Self::Excel => excel_sheets(path),
+            Self::Sqlite => crate::sqlite_import::list_tables(path),
+        }
+    }
+
+    /// Names of the tables of `path`, without opening each one.
+    fn names(self, path: &Path) -> Result<Vec<String>> {
+        match self {
+            Self::Excel => Ok(open_workbook_auto(path)
+                .map_err(|e| eyre!("Excel: {}", e))?
+                .sheet_names()),
+            Self::Sqlite => crate::sqlite_import::table_names(path),
+        }
+    }
+
+    /// The table `options` selects: its position in `names`. None when it is not there.
+    fn selected(self, options: &OpenOptions, names: &[String]) -> Option<usize> {
+        match self {
+            Self::Excel => match options.excel_sheet.as_deref() {
+                None => Some(0),
+                Some(sheet) => match sheet.parse::<usize>() {
+                    Ok(index) => (index < names.len()).then_some(index),
+                    Err(_) => names.iter().position(|n| n == sheet),
+                },
+            },
+            Self::Sqlite => match options.table.as_deref() {
+                None => (names.len() == 1).then_some(0),
+                Some(table) => names.iter().position(|n| n == table),
+            },
+        }
+    }
+
+    /// `options` loading the table `name` at `index`.
+    fn select(self, options: &mut OpenOptions, index: usize, name: &str) {
+        match self {
+            // By index, since a sheet named like a number would be read as one.
+            Self::Excel => options.excel_sheet = Some(index.to_string()),
+            Self::Sqlite => {
+                options.table = Some(name.to_string());
+                options.sql_query = None;
+            }
+        }
+    }
+}
+
+/// A sheet, table or view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableEntry {
+    pub name: String,
+    /// "sheet", "table" or "view".
+    pub kind: &'static str,
+    pub columns: usize,
+    /// Data rows (the header row of a sheet not counted); None when not counted.
+    pub rows: Option<usize>,
+}
+
+/// Sheets of the workbook at `path` with the size of their used range.
+fn excel_sheets(path: &Path) -> Result<Vec<TableEntry>> {
+    let mut workbook = open_workbook_auto(path).map_err(|e| eyre!("Excel: {}", e))?;
+    workbook
+        .sheet_names()
+        .into_iter()
+        .map(|name| {
+            let range = workbook
+                .worksheet_range(&name)
+                .map_err(|e| eyre!("Excel: {}", e))?;
+            let (height, width) = range.get_size();
+            Ok(TableEntry {
+                name,
+                kind: "sheet",
+                columns: width,
+                rows: Some(height.saturating_sub(1)),
+            })
+        })
+        .collect()
+}
+
+/// The tables of the loaded file and which one is shown, for the info panel and `O`.
+#[derive(Debug, Clone)]
+pub struct SourceTables {
+    pub path: PathBuf,
+    pub source: TableSource,
+    pub names: Vec<String>,
+    /// Position of the shown table in `names`.
+    pub current: usize,
+}
+
+impl SourceTables {
+    /// The tables behind opening `paths` with `options`: a single Excel or SQLite file.
+    pub fn for_open(paths: &[PathBuf], options: &OpenOptions) -> Option<Self> {
+        let [path] = paths else {
+            return None;
+        };
+        let source = TableSource::of(path, options)?;
+        let names = source.names(path).ok()?;
+        let current = source.selected(options, &names)?;
+        Some(Self {
+            path: path.clone(),
+            source,
+            names,
+            current,
+        })
+    }
+
+    /// Info panel line, e.g. "Sheet: Sales (2 of 3)".
+    pub fn label(&self) -> String {
+        let noun = self.source.noun();
+        format!(
+            "{}{}: {} ({} of {})",
+            noun[..1].to_uppercase(),
+            &noun[1..],
+            self.names[self.current],
+            self.current + 1,
+            self.names.len()
+        )
+    }
+}
+
+/// The picker popup.
+pub struct TablePicker {
+    pub path: PathBuf,
+    pub source: TableSource,
+    /// Options the file was opened with; Enter reloads it with these and the chosen table.
+    pub options: OpenOptions,
+    pub tables: Vec<TableEntry>,
+    pub table_state: TableState,
+    /// Name of the table shown now, marked in the list.
+    pub current: Option<String>,
+    /// Rows that fit in the popup, as of the last render.
+    pub visible: usize,
+}
+
+impl TablePicker {
+    /// List the tables of `path`, highlighting `current` when given.
+    pub fn open(
+        path: PathBuf,
+        source: TableSource,
+        options: OpenOptions,
+        current: Option<String>,
+    ) -> Result<Self> {
+        let tables = source.list(&path)?;
+        let index = current
+            .as_deref()
+            .and_then(|c| tables.iter().position(|t| t.name == c))
+            .unwrap_or(0);
+        let mut table_state = TableState::default();
+        table_state.select((!tables.is_empty()).then_some(index));
+        Ok(Self {
+            path,
+            source,
+            options,
+            tables,
+            table_state,
+            current,
+            visible: 1,
+        })
+    }
+
+    pub fn selected(&self) -> Option<&TableEntry> {
+        self.table_state.selected().and_then(|i| self.tables.get(i))
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        if self.tables.is_empty() {
+            return;
+        }
+        let current = self.table_state.selected().unwrap_or(0);
+        let last = self.tables.len() - 1;
+        self.table_state
+            .select(Some(current.saturating_add_signed(delta).min(last)));
+    }
+
+    /// Paths and options that load the highlighted table.
+    pub fn open_selected(&self) -> Option<(Vec<PathBuf>, OpenOptions)> {
+        let index = self.table_state.selected()?;
+        let table = self.tables.get(index)?;
+        let mut options = self.options.clone();
+        self.source.select(&mut options, index, &table.name);
+        Some((vec![self.path.clone()], options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_tables_follow_the_selected_sheet() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("book.xlsx");
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        for (name, rows) in [("Jan", 2), ("Feb", 3)] {
+            let sheet = workbook.add_worksheet();
+            sheet.set_name(name).unwrap();
+            sheet.write(0, 0, "id").unwrap();
+            sheet.write(0, 1, "amount").unwrap();
+            for row in 1..=rows {
+                sheet.write(row, 0, row).unwrap();
+                sheet.write(row, 1, row * 10).unwrap();
+            }
+        }
+        workbook.save(&path).unwrap();
+
+        let mut options = OpenOptions::default();
+        let tables = SourceTables::for_open(std::slice::from_ref(&path), &options).unwrap();
+        assert_eq!(tables.names, vec!["Jan", "Feb"]);
+        assert_eq!(tables.label(), "Sheet: Jan (1 of 2)");
+
+        let mut picker =
+            TablePicker::open(path.clone(), tables.source, options.clone(), None).unwrap();
+        let sizes: Vec<(usize, Option<usize>)> =
+            picker.tables.iter().map(|t| (t.columns, t.rows)).collect();
+        assert_eq!(sizes, vec![(2, Some(2)), (2, Some(3))]);
+        picker.move_by(1);
+        let (paths, chosen) = picker.open_selected().unwrap();
+        assert_eq!(paths, vec![path.clone()]);
+        assert_eq!(chosen.excel_sheet.as_deref(), Some("1"));
+
+        options.excel_sheet = Some("Feb".to_string());
+        let tables = SourceTables::for_open(&[path], &options).unwrap();
+        assert_eq!(tables.label(), "Sheet: Feb (2 of 2)");
+    }
+}
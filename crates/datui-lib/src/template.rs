@@ -1,7 +1,7 @@
 use color_eyre::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::hash::{Hash, Hasher};
 use std::io::Write;
@@ -10,9 +10,16 @@ use std::time::SystemTime;
 
 use polars::prelude::Schema;
 
+use crate::column_cast::ColumnCast;
+use crate::column_format::ColumnFormats;
+use crate::columns_modal::ColumnRename;
+use crate::computed_column::ComputedColumn;
 use crate::config::ConfigManager;
 use crate::filter_modal::FilterStatement;
+use crate::group_by_modal::GroupBySpec;
+use crate::migration::{self, Migration};
 use crate::pivot_melt_modal::{MeltSpec, PivotSpec};
+use crate::saved_queries::SavedQuery;
 
 // Custom serialization for SystemTime (convert to/from seconds since epoch)
 mod time_serde {
@@ -61,6 +68,45 @@ mod time_serde {
     }
 }
 
+/// Format of saved template files, stored in them as `schema_version`. Bump it and add a step to
+/// `TEMPLATE_MIGRATIONS` when a change to `Template` would stop older files from loading.
+pub const TEMPLATE_SCHEMA_VERSION: u32 = 1;
+
+/// Upgrade steps for template files. Files without a `schema_version` count as version 1, the
+/// format before upgrades existed.
+const TEMPLATE_MIGRATIONS: &[Migration<u32, serde_json::Value>] = &[];
+
+/// Parse a template file, upgrading older formats. Returns the template and, when the file was
+/// upgraded, its original version.
+fn parse_template(content: &str) -> std::result::Result<(Template, Option<u32>), String> {
+    let mut doc: serde_json::Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let version = doc
+        .get("schema_version")
+        .and_then(|v| v.as_u64())
+        .map_or(TEMPLATE_SCHEMA_VERSION, |v| v as u32);
+    if version > TEMPLATE_SCHEMA_VERSION {
+        return Err(format!(
+            "saved by a newer version of datui (template format {}, this version reads up to {})",
+            version, TEMPLATE_SCHEMA_VERSION
+        ));
+    }
+    let reached = migration::migrate(&mut doc, version, TEMPLATE_MIGRATIONS);
+    if reached != TEMPLATE_SCHEMA_VERSION {
+        return Err(format!("no upgrade from template format {}", reached));
+    }
+    let template = serde_json::from_value(doc).map_err(|e| e.to_string())?;
+    Ok((template, (version != reached).then_some(version)))
+}
+
+/// Template as written to disk: the fields plus `schema_version`.
+fn template_json(template: &Template) -> Result<String> {
+    let mut doc = serde_json::to_value(template)?;
+    if let Some(fields) = doc.as_object_mut() {
+        fields.insert("schema_version".into(), TEMPLATE_SCHEMA_VERSION.into());
+    }
+    Ok(serde_json::to_string_pretty(&doc)?)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Template {
     pub id: String,
@@ -116,6 +162,106 @@ pub struct TemplateSettings {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub melt: Option<MeltSpec>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub group_by: Option<GroupBySpec>,
+    /// Values prompted for when the template is applied. Pivot/melt specs and column order
+    /// reference them as `${name}`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub parameters: Vec<TemplateParameter>,
+    /// Display formats by column name (see [`crate::column_format`]).
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default)]
+    pub column_formats: ColumnFormats,
+    /// Computed columns, added before the query is applied (see [`crate::computed_column`]).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub computed_columns: Vec<ComputedColumn>,
+    /// Columns read as other types, applied before computed columns (see
+    /// [`crate::column_cast`]).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub column_casts: Vec<ColumnCast>,
+    /// Columns of the data renamed in the Columns modal, applied first (see
+    /// [`crate::columns_modal`]).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub column_renames: Vec<ColumnRename>,
+    /// Named queries listed in the Saved Queries menu while the template is applied (see
+    /// [`crate::saved_queries`]).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub saved_queries: Vec<SavedQuery>,
+}
+
+/// A value supplied at apply time, e.g. the measure column of a monthly file whose name varies.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateParameter {
+    pub name: String,
+    /// Text shown when asking for the value; the name is used when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Used when the user submits an empty value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+impl TemplateParameter {
+    pub fn prompt_text(&self) -> &str {
+        self.prompt.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Replace `${name}` placeholders with the given values. Unknown placeholders are left as-is.
+pub fn substitute_parameters(text: &str, values: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find('}') {
+            Some(end) => {
+                let name = &after[..end];
+                match values.get(name) {
+                    Some(value) => out.push_str(value),
+                    None => out.push_str(&rest[start..start + 2 + end + 1]),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+impl Template {
+    /// Returns a copy with parameter placeholders in the pivot/melt specs and column order
+    /// replaced by `values`.
+    pub fn with_parameters(&self, values: &HashMap<String, String>) -> Template {
+        let sub = |s: &String| substitute_parameters(s, values);
+        let sub_all = |v: &Vec<String>| v.iter().map(sub).collect::<Vec<_>>();
+        let mut template = self.clone();
+        if let Some(pivot) = template.settings.pivot.as_mut() {
+            pivot.index = sub_all(&pivot.index);
+            pivot.pivot_column = sub(&pivot.pivot_column);
+            pivot.value_column = sub(&pivot.value_column);
+        }
+        if let Some(melt) = template.settings.melt.as_mut() {
+            melt.index = sub_all(&melt.index);
+            melt.value_columns = sub_all(&melt.value_columns);
+            melt.variable_name = sub(&melt.variable_name);
+            melt.value_name = sub(&melt.value_name);
+        }
+        template.settings.column_order = sub_all(&template.settings.column_order);
+        template
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -129,6 +275,8 @@ pub struct TemplateManager {
     templates: Vec<Template>,
     pub(crate) templates_dir: PathBuf,
     pub broken_templates: Vec<BrokenTemplate>,
+    /// False until templates have been read from disk (see `deferred` and `ensure_loaded`).
+    loaded: bool,
 }
 
 impl TemplateManager {
@@ -144,6 +292,7 @@ impl TemplateManager {
             templates: Vec::new(),
             templates_dir,
             broken_templates: Vec::new(),
+            loaded: false,
         };
 
         // Only try to load templates if the directory exists
@@ -160,12 +309,31 @@ impl TemplateManager {
             templates: Vec::new(),
             templates_dir: config.config_dir().join("templates"),
             broken_templates: Vec::new(),
+            loaded: true,
+        }
+    }
+
+    /// Creates a template manager that reads templates from disk on the first
+    /// `ensure_loaded()`, so startup doesn't pay for parsing every template file.
+    pub fn deferred(config: &ConfigManager) -> Self {
+        Self {
+            loaded: false,
+            ..Self::empty(config)
+        }
+    }
+
+    /// Load templates from disk if that hasn't happened yet. A directory that can't be read
+    /// leaves the list empty, as `empty()` would.
+    pub fn ensure_loaded(&mut self) {
+        if !self.loaded && self.load_templates().is_err() {
+            self.templates.clear();
         }
     }
 
     pub fn load_templates(&mut self) -> Result<()> {
         self.templates.clear();
         self.broken_templates.clear();
+        self.loaded = true;
 
         // Load all template files
         if !self.templates_dir.exists() {
@@ -179,8 +347,19 @@ impl TemplateManager {
 
             if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("json") {
                 if let Ok(content) = fs::read_to_string(&path) {
-                    match serde_json::from_str::<Template>(&content) {
-                        Ok(template) => {
+                    match parse_template(&content) {
+                        Ok((template, migrated_from)) => {
+                            // Best effort: an unwritable file still loads, and is upgraded
+                            // again next time.
+                            if let Some(version) = migrated_from {
+                                if let Ok(json) = template_json(&template) {
+                                    let _ = migration::write_migrated(
+                                        &path,
+                                        &version.to_string(),
+                                        &json,
+                                    );
+                                }
+                            }
                             self.templates.push(template);
                         }
                         Err(e) => {
@@ -189,10 +368,8 @@ impl TemplateManager {
                                 .and_then(|s| s.to_str())
                                 .unwrap_or("unknown")
                                 .to_string();
-                            self.broken_templates.push(BrokenTemplate {
-                                filename,
-                                error: e.to_string(),
-                            });
+                            self.broken_templates
+                                .push(BrokenTemplate { filename, error: e });
                         }
                     }
                 }
@@ -221,7 +398,7 @@ impl TemplateManager {
             fs::create_dir_all(parent)?;
         }
 
-        let json = serde_json::to_string_pretty(template)?;
+        let json = template_json(template)?;
 
         // Use file locking to prevent race conditions
         use fs2::FileExt;
@@ -602,6 +779,19 @@ mod tests {
         assert_eq!(settings.fuzzy_query, None);
     }
 
+    #[test]
+    fn test_substitute_parameters() {
+        let values = HashMap::from([("measure".to_string(), "revenue".to_string())]);
+        assert_eq!(substitute_parameters("${measure}", &values), "revenue");
+        assert_eq!(
+            substitute_parameters("total_${measure}_usd", &values),
+            "total_revenue_usd"
+        );
+        assert_eq!(substitute_parameters("${other}", &values), "${other}");
+        assert_eq!(substitute_parameters("${measure", &values), "${measure");
+        assert_eq!(substitute_parameters("plain", &values), "plain");
+    }
+
     #[test]
     fn test_matches_pattern() {
         assert!(matches_pattern("test.csv", "test.csv"));
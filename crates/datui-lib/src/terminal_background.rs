@@ -0,0 +1,196 @@
+//! Terminal background detection (dark vs light) used to pick the theme variant.
+//!
+//! Detection first asks the terminal for its background color with an OSC 11 query and
+//! falls back to the `COLORFGBG` environment variable set by some terminals (rxvt, Konsole).
+//! When neither gives an answer the caller keeps the dark variant.
+
+use std::time::Duration;
+
+/// How long to wait for the terminal to answer the OSC 11 query. The query is followed by a
+/// Primary Device Attributes request (DA1), which virtually every terminal answers, so reading
+/// stops after one round trip and the timeout only matters for terminals that answer neither.
+const OSC11_TIMEOUT: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalBackground {
+    Dark,
+    Light,
+}
+
+impl TerminalBackground {
+    /// Classify an RGB background by relative luminance (ITU-R BT.709).
+    pub fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        let luminance = 0.2126 * r as f64 + 0.7152 * g as f64 + 0.0722 * b as f64;
+        if luminance > 127.5 {
+            TerminalBackground::Light
+        } else {
+            TerminalBackground::Dark
+        }
+    }
+}
+
+/// Detect the terminal background. Returns None when the terminal does not answer the query
+/// and no heuristic applies.
+pub fn detect() -> Option<TerminalBackground> {
+    query_osc11(OSC11_TIMEOUT)
+        .map(|(r, g, b)| TerminalBackground::from_rgb(r, g, b))
+        .or_else(|| {
+            std::env::var("COLORFGBG")
+                .ok()
+                .and_then(|v| from_colorfgbg(&v))
+        })
+}
+
+/// Parse `COLORFGBG` ("fg;bg" or "fg;default;bg"). The last field is the background palette
+/// index: 0-6 and 8 are dark, 7 and 9-15 are light.
+pub fn from_colorfgbg(value: &str) -> Option<TerminalBackground> {
+    let bg: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+    match bg {
+        0..=6 | 8 => Some(TerminalBackground::Dark),
+        7 | 9..=15 => Some(TerminalBackground::Light),
+        _ => None,
+    }
+}
+
+/// True once `response` holds a complete DA1 reply (`ESC [ ? ... c`). Terminals answer queries
+/// in order, so any OSC 11 reply has arrived by then.
+fn has_da1_reply(response: &[u8]) -> bool {
+    response
+        .windows(3)
+        .position(|w| w == b"\x1b[?")
+        .is_some_and(|start| response[start + 3..].contains(&b'c'))
+}
+
+/// Parse an OSC 11 reply such as `ESC ] 11 ; rgb:ffff/ffff/ffff BEL` (or ST-terminated).
+/// Components may have 1-4 hex digits; each is scaled to 8 bits.
+pub fn parse_osc11_response(response: &[u8]) -> Option<(u8, u8, u8)> {
+    let text = std::str::from_utf8(response).ok()?;
+    let start = text.find("rgb:")? + 4;
+    let body: String = text[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_hexdigit() || *c == '/')
+        .collect();
+    let mut parts = body.split('/').map(scale_hex_component);
+    let r = parts.next()??;
+    let g = parts.next()??;
+    let b = parts.next()??;
+    Some((r, g, b))
+}
+
+fn scale_hex_component(s: &str) -> Option<u8> {
+    if s.is_empty() || s.len() > 4 {
+        return None;
+    }
+    let value = u32::from_str_radix(s, 16).ok()?;
+    let max = (1u32 << (4 * s.len())) - 1;
+    Some((value * 255 / max) as u8)
+}
+
+#[cfg(unix)]
+fn query_osc11(timeout: Duration) -> Option<(u8, u8, u8)> {
+    use std::io::{IsTerminal, Read, Write};
+    use std::os::unix::fs::OpenOptionsExt;
+
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    let mut tty = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open("/dev/tty")
+        .ok()?;
+    crossterm::terminal::enable_raw_mode().ok()?;
+
+    let mut response = Vec::new();
+    if tty
+        .write_all(b"\x1b]11;?\x1b\\\x1b[c")
+        .and_then(|_| tty.flush())
+        .is_ok()
+    {
+        let deadline = std::time::Instant::now() + timeout;
+        let mut chunk = [0u8; 64];
+        while std::time::Instant::now() < deadline {
+            match tty.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(n) => {
+                    response.extend_from_slice(&chunk[..n]);
+                    // Wait for the DA1 reply even after the OSC 11 one so it isn't left
+                    // in stdin for the TUI to read as keys.
+                    if has_da1_reply(&response) {
+                        break;
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                Err(_) => break,
+            }
+        }
+    }
+    let _ = crossterm::terminal::disable_raw_mode();
+    parse_osc11_response(&response)
+}
+
+#[cfg(not(unix))]
+fn query_osc11(_timeout: Duration) -> Option<(u8, u8, u8)> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_osc11_response() {
+        assert_eq!(
+            parse_osc11_response(b"\x1b]11;rgb:ffff/ffff/ffff\x07"),
+            Some((255, 255, 255))
+        );
+        assert_eq!(
+            parse_osc11_response(b"\x1b]11;rgb:1e1e/1e1e/2e2e\x1b\\"),
+            Some((30, 30, 46))
+        );
+        assert_eq!(
+            parse_osc11_response(b"\x1b]11;rgb:f/0/8\x07"),
+            Some((255, 0, 136))
+        );
+        assert_eq!(parse_osc11_response(b""), None);
+        assert_eq!(parse_osc11_response(b"\x1b]11;rgb:ffff/ffff\x07"), None);
+    }
+
+    #[test]
+    fn test_has_da1_reply() {
+        assert!(!has_da1_reply(b"\x1b]11;rgb:ffff/ffff/ffff\x07"));
+        assert!(!has_da1_reply(b"\x1b]11;rgb:ffff/ffff/ffff\x07\x1b[?62;"));
+        assert!(has_da1_reply(b"\x1b]11;rgb:ffff/ffff/ffff\x07\x1b[?62;22c"));
+        assert!(has_da1_reply(b"\x1b[?1;2c"));
+    }
+
+    #[test]
+    fn test_from_rgb() {
+        assert_eq!(
+            TerminalBackground::from_rgb(255, 255, 255),
+            TerminalBackground::Light
+        );
+        assert_eq!(
+            TerminalBackground::from_rgb(253, 246, 227),
+            TerminalBackground::Light
+        );
+        assert_eq!(
+            TerminalBackground::from_rgb(0, 43, 54),
+            TerminalBackground::Dark
+        );
+    }
+
+    #[test]
+    fn test_from_colorfgbg() {
+        assert_eq!(from_colorfgbg("15;0"), Some(TerminalBackground::Dark));
+        assert_eq!(from_colorfgbg("0;15"), Some(TerminalBackground::Light));
+        assert_eq!(
+            from_colorfgbg("0;default;7"),
+            Some(TerminalBackground::Light)
+        );
+        assert_eq!(from_colorfgbg("default"), None);
+    }
+}
@@ -0,0 +1,331 @@
+//! Gap and null-run detection for time series (the "Time Series Gaps" analysis tool).
+//!
+//! Rows are read in the current view's order. Consecutive timestamps further apart than the
+//! expected frequency are reported as gaps, with the number of missing timestamps. For every
+//! column the longest run of consecutive nulls is reported. Row numbers are positions in the
+//! view, so the table can jump to them.
+
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use polars::prelude::*;
+
+/// Consecutive timestamps further apart than the expected frequency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gap {
+    /// View row of the first timestamp after the gap.
+    pub row: usize,
+    pub from: String,
+    pub to: String,
+    /// Expected timestamps missing between `from` and `to`.
+    pub missing: u64,
+}
+
+/// Longest run of consecutive nulls in one column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NullRun {
+    pub column: String,
+    pub longest: usize,
+    /// View row where the longest run starts; None when the column has no nulls.
+    pub start_row: Option<usize>,
+    pub null_count: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct GapReport {
+    pub time_column: String,
+    /// Expected spacing between timestamps, in nanoseconds.
+    pub frequency_ns: i64,
+    /// True when the frequency was inferred (most common spacing) rather than given.
+    pub frequency_inferred: bool,
+    pub rows: usize,
+    pub gaps: Vec<Gap>,
+    pub missing_total: u64,
+    /// Timestamps equal to the previous one.
+    pub duplicates: usize,
+    /// Timestamps earlier than the previous one (the view is not sorted by time).
+    pub out_of_order: usize,
+    /// One entry per column, longest run first.
+    pub null_runs: Vec<NullRun>,
+}
+
+/// Columns that can serve as the time axis.
+pub fn is_time_dtype(dtype: &DataType) -> bool {
+    matches!(dtype, DataType::Date | DataType::Datetime(_, _))
+}
+
+/// Nanoseconds per unit of the column's physical (integer) representation.
+fn ns_per_unit(dtype: &DataType) -> Option<i64> {
+    match dtype {
+        DataType::Date => Some(86_400_000_000_000),
+        DataType::Datetime(TimeUnit::Nanoseconds, _) => Some(1),
+        DataType::Datetime(TimeUnit::Microseconds, _) => Some(1_000),
+        DataType::Datetime(TimeUnit::Milliseconds, _) => Some(1_000_000),
+        _ => None,
+    }
+}
+
+const UNITS: &[(&str, i64)] = &[
+    ("w", 7 * 86_400_000_000_000),
+    ("d", 86_400_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("ns", 1),
+];
+
+/// Parse a frequency such as "15m", "1h", "500ms" or "1d" into nanoseconds.
+/// Units: ns, us, ms, s, m (or min), h, d, w.
+pub fn parse_frequency(text: &str) -> Result<i64> {
+    let text = text.trim().to_ascii_lowercase();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| eyre!("Frequency needs a unit, e.g. 15m, 1h or 1d"))?;
+    let (number, unit) = text.split_at(split);
+    let n: i64 = number.parse().map_err(|_| {
+        eyre!(
+            "Invalid frequency \"{}\": expected e.g. 15m, 1h or 1d",
+            text
+        )
+    })?;
+    let unit = if unit.trim() == "min" {
+        "m"
+    } else {
+        unit.trim()
+    };
+    let ns = UNITS
+        .iter()
+        .find(|(u, _)| *u == unit)
+        .map(|(_, ns)| *ns)
+        .ok_or_else(|| {
+            eyre!(
+                "Unknown frequency unit \"{}\": use ns, us, ms, s, m, h, d or w",
+                unit
+            )
+        })?;
+    match n.checked_mul(ns) {
+        Some(total) if total > 0 => Ok(total),
+        _ => Err(eyre!("Frequency must be greater than zero")),
+    }
+}
+
+/// Shortest exact representation of a frequency, e.g. 900_000_000_000 -> "15m".
+pub fn format_frequency(ns: i64) -> String {
+    UNITS
+        .iter()
+        .find(|(_, unit)| ns % unit == 0)
+        .map(|(name, unit)| format!("{}{}", ns / unit, name))
+        .unwrap_or_else(|| format!("{ns}ns"))
+}
+
+/// Find gaps in `time_column` and the longest null run of every column of `lf`.
+/// `frequency_ns` of None infers the frequency as the most common spacing.
+pub fn compute(
+    lf: &LazyFrame,
+    time_column: &str,
+    frequency_ns: Option<i64>,
+    polars_streaming: bool,
+) -> Result<GapReport> {
+    let schema = lf.clone().collect_schema()?;
+    let dtype = schema
+        .get(time_column)
+        .ok_or_else(|| eyre!("Column \"{}\" not found", time_column))?;
+    let unit_ns = ns_per_unit(dtype).ok_or_else(|| {
+        eyre!(
+            "\"{}\" is {}, not a date or datetime column",
+            time_column,
+            dtype
+        )
+    })?;
+
+    const TIME: &str = "__datui_time";
+    let mut exprs = vec![col(time_column).alias(TIME)];
+    exprs.extend(
+        schema
+            .iter_names()
+            .map(|name| col(name.as_str()).is_null().alias(name.as_str())),
+    );
+    let df = crate::statistics::collect_lazy(lf.clone().select(exprs), polars_streaming)?;
+
+    let time = df.column(TIME)?.as_materialized_series();
+    let physical = time.to_physical_repr().cast(&DataType::Int64)?;
+    let stamps: Vec<Option<i64>> = physical
+        .i64()?
+        .into_iter()
+        .map(|v| v.map(|v| v.saturating_mul(unit_ns)))
+        .collect();
+
+    let (frequency_ns, frequency_inferred) = match frequency_ns {
+        Some(f) => (f, false),
+        None => (
+            infer_frequency(&stamps)
+                .ok_or_else(|| eyre!("Not enough distinct timestamps to infer a frequency"))?,
+            true,
+        ),
+    };
+
+    let mut gaps = Vec::new();
+    let mut duplicates = 0;
+    let mut out_of_order = 0;
+    let mut previous: Option<(usize, i64)> = None;
+    for (row, stamp) in stamps.iter().enumerate() {
+        let Some(stamp) = *stamp else {
+            continue;
+        };
+        if let Some((prev_row, prev)) = previous {
+            let diff = stamp.saturating_sub(prev);
+            if diff == 0 {
+                duplicates += 1;
+            } else if diff < 0 {
+                out_of_order += 1;
+            } else {
+                // Round to whole periods so small jitter is not reported as a gap.
+                let periods = diff.saturating_add(frequency_ns / 2) / frequency_ns;
+                if periods > 1 {
+                    gaps.push(Gap {
+                        row,
+                        from: time.get(prev_row)?.str_value().into_owned(),
+                        to: time.get(row)?.str_value().into_owned(),
+                        missing: (periods - 1) as u64,
+                    });
+                }
+            }
+        }
+        previous = Some((row, stamp));
+    }
+
+    let mut null_runs: Vec<NullRun> = schema
+        .iter_names()
+        .map(|name| longest_null_run(&df, name.as_str()))
+        .collect::<Result<_>>()?;
+    null_runs.sort_by_key(|run| std::cmp::Reverse(run.longest));
+
+    Ok(GapReport {
+        time_column: time_column.to_string(),
+        frequency_ns,
+        frequency_inferred,
+        rows: df.height(),
+        missing_total: gaps.iter().map(|g| g.missing).sum(),
+        gaps,
+        duplicates,
+        out_of_order,
+        null_runs,
+    })
+}
+
+/// Most common positive spacing between consecutive timestamps.
+fn infer_frequency(stamps: &[Option<i64>]) -> Option<i64> {
+    let mut counts: std::collections::HashMap<i64, usize> = std::collections::HashMap::new();
+    let mut previous = None;
+    for stamp in stamps.iter().flatten() {
+        if let Some(prev) = previous {
+            let diff: i64 = stamp - prev;
+            if diff > 0 {
+                *counts.entry(diff).or_default() += 1;
+            }
+        }
+        previous = Some(*stamp);
+    }
+    // Ties go to the smaller spacing so the result does not depend on hash order.
+    counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(diff, _)| diff)
+}
+
+fn longest_null_run(df: &DataFrame, column: &str) -> Result<NullRun> {
+    let is_null = df.column(column)?.as_materialized_series().bool()?.clone();
+    let mut run = NullRun {
+        column: column.to_string(),
+        longest: 0,
+        start_row: None,
+        null_count: 0,
+    };
+    let mut current = 0;
+    for (row, null) in is_null.into_iter().enumerate() {
+        if null == Some(true) {
+            current += 1;
+            run.null_count += 1;
+            if current > run.longest {
+                run.longest = current;
+                run.start_row = Some(row + 1 - current);
+            }
+        } else {
+            current = 0;
+        }
+    }
+    Ok(run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hourly_with_gap() -> LazyFrame {
+        // 00:00, 01:00, 02:00, 05:00 (03:00 and 04:00 missing), 06:00 (±10 min jitter), 06:00 again
+        let hours: [i64; 6] = [0, 60, 120, 300, 370, 370];
+        let ms: Vec<i64> = hours.iter().map(|m| m * 60_000).collect();
+        df!(
+            "ts" => ms,
+            "v" => [Some(1.0), None, None, Some(4.0), None, Some(6.0)],
+        )
+        .unwrap()
+        .lazy()
+        .with_column(col("ts").cast(DataType::Datetime(TimeUnit::Milliseconds, None)))
+    }
+
+    #[test]
+    fn test_parse_and_format_frequency() {
+        assert_eq!(parse_frequency("15m").unwrap(), 900_000_000_000);
+        assert_eq!(parse_frequency("15min").unwrap(), 900_000_000_000);
+        assert_eq!(parse_frequency(" 1D ").unwrap(), 86_400_000_000_000);
+        assert_eq!(parse_frequency("500ms").unwrap(), 500_000_000);
+        assert!(parse_frequency("15").is_err());
+        assert!(parse_frequency("0h").is_err());
+        assert!(parse_frequency("3 fortnights").is_err());
+        assert_eq!(format_frequency(900_000_000_000), "15m");
+        assert_eq!(format_frequency(86_400_000_000_000), "1d");
+        assert_eq!(format_frequency(1_500_000_000), "1500ms");
+    }
+
+    #[test]
+    fn test_inferred_frequency_finds_gap_and_duplicates() {
+        let report = compute(&hourly_with_gap(), "ts", None, false).unwrap();
+        assert!(report.frequency_inferred);
+        assert_eq!(format_frequency(report.frequency_ns), "1h");
+        assert_eq!(report.gaps.len(), 1);
+        let gap = &report.gaps[0];
+        assert_eq!(gap.row, 3);
+        assert_eq!(gap.missing, 2);
+        assert!(gap.from.contains("02:00"), "{}", gap.from);
+        assert!(gap.to.contains("05:00"), "{}", gap.to);
+        assert_eq!(report.duplicates, 1);
+        assert_eq!(report.out_of_order, 0);
+    }
+
+    #[test]
+    fn test_explicit_frequency_and_null_runs() {
+        let report = compute(
+            &hourly_with_gap(),
+            "ts",
+            Some(parse_frequency("30m").unwrap()),
+            false,
+        )
+        .unwrap();
+        assert!(!report.frequency_inferred);
+        // Every hourly step now misses one half-hour timestamp.
+        assert_eq!(report.missing_total, 1 + 1 + 5 + 1);
+        assert_eq!(report.null_runs[0].column, "v");
+        assert_eq!(report.null_runs[0].longest, 2);
+        assert_eq!(report.null_runs[0].start_row, Some(1));
+        assert_eq!(report.null_runs[0].null_count, 3);
+        assert_eq!(report.null_runs[1].start_row, None);
+    }
+
+    #[test]
+    fn test_rejects_non_time_column() {
+        let err = compute(&hourly_with_gap(), "v", None, false).unwrap_err();
+        assert!(err.to_string().contains("not a date or datetime"));
+    }
+}
@@ -0,0 +1,363 @@
+//! Time Zone modal: change the time zone of the leftmost scrolled datetime column, e.g. when a
+//! warehouse export mixes UTC and local timestamps. Zone-aware columns can be converted to
+//! another zone (same instants), given another zone (same clock times) or made naive; naive
+//! columns can be given a zone or read as UTC and converted. The column is replaced in the
+//! view's source. Opened with `Z`; Enter applies, Esc discards.
+
+use crate::computed_column_modal::PREVIEW_ROWS;
+use crate::widgets::text_input::TextInput;
+use polars::prelude::*;
+
+/// Zones offered as examples in the modal.
+pub const TIME_ZONE_EXAMPLES: &str = "UTC, Europe/London, America/New_York, +02:00";
+
+/// What happens to the column's zone.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TimeZoneAction {
+    /// Show the same instants in another zone. Naive values are read as UTC first.
+    #[default]
+    Convert,
+    /// Keep the clock times and attach another zone (localizes naive values).
+    Replace,
+    /// Drop the zone, keeping the clock times.
+    MakeNaive,
+}
+
+impl TimeZoneAction {
+    /// Actions that apply to a column with (`aware`) or without a zone.
+    pub fn available(aware: bool) -> &'static [TimeZoneAction] {
+        if aware {
+            &[Self::Convert, Self::Replace, Self::MakeNaive]
+        } else {
+            &[Self::Convert, Self::Replace]
+        }
+    }
+
+    pub fn as_str(self, aware: bool) -> &'static str {
+        match (self, aware) {
+            (Self::Convert, true) => "convert to zone (same instants)",
+            (Self::Convert, false) => "read as UTC, convert to zone",
+            (Self::Replace, true) => "replace zone (same clock times)",
+            (Self::Replace, false) => "set zone (same clock times)",
+            (Self::MakeNaive, _) => "make naive (drop zone)",
+        }
+    }
+
+    pub fn needs_zone(self) -> bool {
+        self != Self::MakeNaive
+    }
+}
+
+/// Name of the zone of a datetime type: the zone, "naive", or None for other types.
+pub fn time_zone_label(dtype: &DataType) -> Option<String> {
+    match dtype {
+        DataType::Datetime(_, Some(tz)) => Some(tz.to_string()),
+        DataType::Datetime(_, None) => Some("naive".to_string()),
+        _ => None,
+    }
+}
+
+/// A change of zone for one datetime column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeZoneChange {
+    pub column: String,
+    pub action: TimeZoneAction,
+    /// Target zone; unused when making the column naive.
+    pub time_zone: String,
+}
+
+impl TimeZoneChange {
+    fn target(&self) -> Result<TimeZone, String> {
+        let text = self.time_zone.trim();
+        if text.is_empty() {
+            return Err(format!("Enter a time zone, e.g. {}", TIME_ZONE_EXAMPLES));
+        }
+        TimeZone::opt_try_new(Some(text))
+            .ok()
+            .flatten()
+            .ok_or_else(|| {
+                format!(
+                    "Unknown time zone \"{}\": expected e.g. {}",
+                    text, TIME_ZONE_EXAMPLES
+                )
+            })
+    }
+
+    /// The column with its new zone, keeping its name. `aware` is whether the column has a zone.
+    pub fn expr(&self, aware: bool) -> Result<Expr, String> {
+        let column = col(self.column.as_str());
+        // Clock times that don't exist or repeat on DST changes become the earliest time or null.
+        let replace = |expr: Expr, zone: Option<TimeZone>| {
+            expr.dt()
+                .replace_time_zone(zone, lit("earliest"), NonExistent::Null)
+        };
+        Ok(match self.action {
+            TimeZoneAction::Convert if aware => column.dt().convert_time_zone(self.target()?),
+            TimeZoneAction::Convert => replace(column, Some(TimeZone::UTC))
+                .dt()
+                .convert_time_zone(self.target()?),
+            TimeZoneAction::Replace => replace(column, Some(self.target()?)),
+            TimeZoneAction::MakeNaive if aware => replace(column, None),
+            TimeZoneAction::MakeNaive => return Err("The column has no time zone".to_string()),
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TimeZoneFocus {
+    #[default]
+    Action,
+    Zone,
+}
+
+#[derive(Default)]
+pub struct TimeZoneModal {
+    pub active: bool,
+    pub focus: TimeZoneFocus,
+    pub column: String,
+    /// Current zone of the column, None when naive.
+    pub current: Option<String>,
+    pub action: TimeZoneAction,
+    pub zone_input: TextInput,
+    /// Source the preview is evaluated on.
+    source: Option<LazyFrame>,
+    /// First values before and after the change, or a message.
+    pub preview: Option<Result<Vec<(String, String)>, String>>,
+}
+
+impl TimeZoneModal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open for `column` of `source`, which must be a datetime column of `dtype`.
+    pub fn open(
+        &mut self,
+        column: &str,
+        dtype: &DataType,
+        source: LazyFrame,
+        theme: &crate::config::Theme,
+    ) {
+        self.active = true;
+        self.focus = TimeZoneFocus::Action;
+        self.column = column.to_string();
+        self.current = match dtype {
+            DataType::Datetime(_, tz) => tz.as_ref().map(|tz| tz.to_string()),
+            _ => None,
+        };
+        self.action = TimeZoneAction::Convert;
+        self.zone_input = TextInput::new().with_theme(theme);
+        let zone = if self.current.as_deref() == Some("UTC") {
+            ""
+        } else {
+            "UTC"
+        };
+        self.zone_input.set_value(zone.to_string());
+        self.zone_input.set_cursor(zone.len());
+        self.source = Some(source);
+        self.refresh_preview();
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+        self.source = None;
+        self.preview = None;
+    }
+
+    pub fn aware(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// The change as edited.
+    pub fn change(&self) -> TimeZoneChange {
+        TimeZoneChange {
+            column: self.column.clone(),
+            action: self.action,
+            time_zone: self.zone_input.value().trim().to_string(),
+        }
+    }
+
+    /// Evaluate the change on the first rows of the source. Call after each edit.
+    pub fn refresh_preview(&mut self) {
+        let Some(source) = self.source.as_ref() else {
+            self.preview = None;
+            return;
+        };
+        if self.action.needs_zone() && self.zone_input.value().trim().is_empty() {
+            self.preview = None;
+            return;
+        }
+        self.preview = Some(preview(source, &self.change(), self.aware()));
+    }
+
+    pub fn next_focus(&mut self) {
+        self.focus = match self.focus {
+            TimeZoneFocus::Action if self.action.needs_zone() => TimeZoneFocus::Zone,
+            _ => TimeZoneFocus::Action,
+        };
+    }
+
+    pub fn prev_focus(&mut self) {
+        self.next_focus();
+    }
+
+    /// Change the action forwards (`delta` > 0) or backwards.
+    pub fn cycle(&mut self, delta: i32) {
+        let all = TimeZoneAction::available(self.aware());
+        let idx = all.iter().position(|a| *a == self.action).unwrap_or(0);
+        let len = all.len();
+        self.action = if delta < 0 {
+            all[(idx + len - 1) % len]
+        } else {
+            all[(idx + 1) % len]
+        };
+    }
+
+    pub fn focused_input(&mut self) -> Option<&mut TextInput> {
+        match self.focus {
+            TimeZoneFocus::Zone => Some(&mut self.zone_input),
+            TimeZoneFocus::Action => None,
+        }
+    }
+}
+
+fn preview(
+    source: &LazyFrame,
+    change: &TimeZoneChange,
+    aware: bool,
+) -> Result<Vec<(String, String)>, String> {
+    let df = source
+        .clone()
+        .limit(PREVIEW_ROWS as IdxSize)
+        .select([
+            col(change.column.as_str()).alias("before"),
+            change.expr(aware)?.alias("after"),
+        ])
+        .collect()
+        .map_err(|e| crate::error_display::user_message_from_polars(&e))?;
+    let text = |name: &str| -> Vec<String> {
+        df.column(name)
+            .map(|c| {
+                c.as_materialized_series()
+                    .iter()
+                    .map(|v| {
+                        if v.is_null() {
+                            "null".to_string()
+                        } else {
+                            v.str_value().into_owned()
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    Ok(text("before").into_iter().zip(text("after")).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(tz: Option<&str>) -> LazyFrame {
+        // 2024-01-01 12:00 and 2024-07-01 12:00
+        df!("ts" => [1_704_110_400_000i64, 1_719_835_200_000])
+            .unwrap()
+            .lazy()
+            .with_column(col("ts").cast(DataType::Datetime(
+                TimeUnit::Milliseconds,
+                TimeZone::opt_try_new(tz).unwrap(),
+            )))
+    }
+
+    fn apply(tz: Option<&str>, action: TimeZoneAction, zone: &str) -> DataFrame {
+        let change = TimeZoneChange {
+            column: "ts".into(),
+            action,
+            time_zone: zone.into(),
+        };
+        source(tz)
+            .with_column(change.expr(tz.is_some()).unwrap())
+            .collect()
+            .unwrap()
+    }
+
+    fn hours(df: &DataFrame) -> Vec<Option<i8>> {
+        df.clone()
+            .lazy()
+            .select([col("ts").dt().hour()])
+            .collect()
+            .unwrap()
+            .column("ts")
+            .unwrap()
+            .i8()
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn changes_zone_of_aware_and_naive_columns() {
+        let df = apply(Some("UTC"), TimeZoneAction::Convert, "Europe/London");
+        assert_eq!(
+            time_zone_label(df.column("ts").unwrap().dtype()).unwrap(),
+            "Europe/London"
+        );
+        assert_eq!(hours(&df), [Some(12), Some(13)], "winter and summer time");
+
+        let df = apply(Some("UTC"), TimeZoneAction::Replace, "America/New_York");
+        assert_eq!(hours(&df), [Some(12), Some(12)]);
+
+        let df = apply(Some("Europe/London"), TimeZoneAction::MakeNaive, "");
+        assert_eq!(
+            time_zone_label(df.column("ts").unwrap().dtype()).unwrap(),
+            "naive"
+        );
+
+        let df = apply(None, TimeZoneAction::Convert, "Europe/London");
+        assert_eq!(hours(&df), [Some(12), Some(13)], "naive values read as UTC");
+
+        let df = apply(None, TimeZoneAction::Replace, "+02:00");
+        assert_eq!(hours(&df), [Some(12), Some(12)]);
+
+        let change = TimeZoneChange {
+            column: "ts".into(),
+            action: TimeZoneAction::Convert,
+            time_zone: "Mars/Olympus".into(),
+        };
+        assert!(change.expr(true).unwrap_err().contains("Mars/Olympus"));
+        assert!(TimeZoneChange {
+            action: TimeZoneAction::MakeNaive,
+            ..change
+        }
+        .expr(false)
+        .is_err());
+    }
+
+    #[test]
+    fn modal_previews_before_and_after() {
+        let config = crate::config::AppConfig::default();
+        let theme = crate::config::Theme::from_config(&config.theme).unwrap();
+        let dtype = DataType::Datetime(TimeUnit::Milliseconds, None);
+        let mut modal = TimeZoneModal::new();
+        modal.open("ts", &dtype, source(None), &theme);
+        assert!(!modal.aware());
+        assert_eq!(modal.zone_input.value(), "UTC");
+        modal.cycle(1);
+        assert_eq!(modal.action, TimeZoneAction::Replace);
+        modal.cycle(1);
+        assert_eq!(
+            modal.action,
+            TimeZoneAction::Convert,
+            "naive columns can't be made naive"
+        );
+        modal.next_focus();
+        modal
+            .focused_input()
+            .unwrap()
+            .set_value("Asia/Tokyo".into());
+        modal.refresh_preview();
+        let preview = modal.preview.clone().unwrap().unwrap();
+        assert_eq!(preview[0].0, "2024-01-01 12:00:00");
+        assert_eq!(preview[0].1, "2024-01-01 21:00:00 JST");
+    }
+}
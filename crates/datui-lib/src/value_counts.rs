@@ -0,0 +1,284 @@
+//! Value counts (frequencies) for a single column of the current view: the most frequent
+//! distinct values with their counts and shares of the rows, for exploring categorical data.
+//!
+//! Computed lazily in phases (row count, distinct values, top values) so the popup can show
+//! progress on large views. When the view has at least `sampling_threshold` rows, the values are
+//! counted on a sample instead and the result says so.
+
+use crate::cardinality::is_countable;
+use crate::locale::DisplayLocale;
+use crate::sampling::{sample_lazy, SamplingStrategy};
+use crate::statistics::collect_lazy;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use polars::prelude::*;
+
+/// How many of the most frequent values are listed.
+pub const TOP_VALUES: usize = 50;
+
+/// Phases reported while computing, in order.
+pub const PHASES: [&str; 3] = [
+    "Counting rows",
+    "Counting distinct values",
+    "Ranking values",
+];
+
+#[derive(Debug, Clone)]
+pub struct ValueCounts {
+    pub column: String,
+    pub dtype: DataType,
+    /// Most frequent values first (ties in value order), null included, with their counts.
+    pub values: Vec<(AnyValue<'static>, usize)>,
+    /// Distinct values, null counted as one.
+    pub distinct: usize,
+    /// Rows counted (the sample size when sampled).
+    pub rows: usize,
+    /// Rows in the current view.
+    pub total_rows: usize,
+    pub sampled: bool,
+}
+
+/// The value counts popup: progress while computing, then the counts.
+#[derive(Debug, Clone)]
+pub struct ValueCountsPopup {
+    pub column: String,
+    /// Index into [`PHASES`] of the running phase; None once done.
+    pub phase: Option<usize>,
+    pub counts: Option<ValueCounts>,
+    /// First listed value shown.
+    pub scroll: usize,
+    /// Values that fit in the popup, as of the last render.
+    pub visible: usize,
+}
+
+impl ValueCountsPopup {
+    pub fn new(column: String) -> Self {
+        Self {
+            column,
+            phase: Some(0),
+            counts: None,
+            scroll: 0,
+            visible: 1,
+        }
+    }
+
+    /// Scroll by `delta` lines, stopping when the last value is on screen.
+    pub fn scroll_by(&mut self, delta: isize) {
+        let len = self.counts.as_ref().map_or(0, |c| c.values.len());
+        let max = len.saturating_sub(self.visible.max(1));
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+}
+
+impl ValueCounts {
+    /// Count the values of `column` in `lf`. `total_rows` is the view's row count when known.
+    /// `on_phase` is called with the index into [`PHASES`] as each phase starts.
+    pub fn compute(
+        lf: &LazyFrame,
+        column: &str,
+        total_rows: Option<usize>,
+        sampling_threshold: Option<usize>,
+        sampling: &SamplingStrategy,
+        polars_streaming: bool,
+        mut on_phase: impl FnMut(usize),
+    ) -> Result<Self> {
+        let selected = lf.clone().select([col(column)]);
+        let dtype = selected
+            .clone()
+            .collect_schema()?
+            .get(column)
+            .cloned()
+            .unwrap_or(DataType::Null);
+        if !is_countable(&dtype) {
+            return Err(eyre!("Value counts aren't available for {} columns", dtype));
+        }
+
+        on_phase(0);
+        let total_rows = match total_rows {
+            Some(n) => n,
+            None => collect_lazy(selected.clone().select([len()]), polars_streaming)?
+                .get_columns()
+                .first()
+                .and_then(|c| c.get(0).ok())
+                .and_then(|v| v.extract::<usize>())
+                .unwrap_or(0),
+        };
+        let (source, rows, sampled) = match sampling_threshold {
+            Some(threshold) if total_rows >= threshold => {
+                // Sample the whole view: the strata column may not be the counted one
+                let sample = sample_lazy(lf, total_rows, threshold, sampling, polars_streaming)?;
+                let df = collect_lazy(sample.select([col(column)]), polars_streaming)?;
+                let n = df.height();
+                (df.lazy(), n, true)
+            }
+            _ => (selected, total_rows, false),
+        };
+
+        on_phase(1);
+        let grouped = source.group_by([col(column)]).agg([len().alias("count")]);
+        let distinct = collect_lazy(grouped.clone().select([len()]), polars_streaming)?
+            .get_columns()
+            .first()
+            .and_then(|c| c.get(0).ok())
+            .and_then(|v| v.extract::<usize>())
+            .unwrap_or(0);
+
+        on_phase(2);
+        let top = collect_lazy(
+            grouped
+                .sort_by_exprs(
+                    [col("count"), col(column)],
+                    SortMultipleOptions::default()
+                        .with_order_descending_multi([true, false])
+                        .with_nulls_last(true),
+                )
+                .limit(TOP_VALUES as IdxSize),
+            polars_streaming,
+        )?;
+        let value_col = top.column(column)?;
+        let count_col = top.column("count")?;
+        let values = (0..top.height())
+            .map(|i| {
+                let value = value_col.get(i)?.into_static();
+                let count = count_col.get(i)?.extract::<usize>().unwrap_or(0);
+                Ok((value, count))
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        Ok(Self {
+            column: column.to_string(),
+            dtype,
+            values,
+            distinct,
+            rows,
+            total_rows,
+            sampled,
+        })
+    }
+
+    /// (value, count, percent of rows) lines for the popup, formatted with `locale`. Null is
+    /// shown as "(null)".
+    pub fn lines(&self, locale: &DisplayLocale) -> Vec<(String, String, String)> {
+        self.values
+            .iter()
+            .map(|(value, count)| {
+                let text = if value.is_null() {
+                    "(null)".to_string()
+                } else {
+                    locale.format_value(value).into_owned()
+                };
+                let percent = if self.rows > 0 {
+                    *count as f64 * 100.0 / self.rows as f64
+                } else {
+                    0.0
+                };
+                let percent = format!("{percent:.1}");
+                let percent = locale.format_number(&percent).unwrap_or(percent) + "%";
+                (text, integer(locale, *count), percent)
+            })
+            .collect()
+    }
+
+    /// Footer describing what is listed, e.g. "top 50 of 1,204 distinct · 10,000 rows" or
+    /// "3 distinct · sample of 1,000 / 2,000,000 rows".
+    pub fn coverage(&self, locale: &DisplayLocale) -> String {
+        let listed = if self.values.len() < self.distinct {
+            format!(
+                "top {} of {} distinct",
+                integer(locale, self.values.len()),
+                integer(locale, self.distinct)
+            )
+        } else {
+            format!("{} distinct", integer(locale, self.distinct))
+        };
+        let rows = if self.sampled {
+            format!(
+                "sample of {} / {} rows",
+                integer(locale, self.rows),
+                integer(locale, self.total_rows)
+            )
+        } else {
+            format!("{} rows", integer(locale, self.total_rows))
+        };
+        format!("{listed} · {rows}")
+    }
+}
+
+fn integer(locale: &DisplayLocale, n: usize) -> String {
+    locale
+        .format_number(&n.to_string())
+        .unwrap_or_else(|| n.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_most_frequent_first_with_null() {
+        let lf = df!("s" => [Some("b"), Some("a"), None, Some("b"), Some("a"), Some("b")])
+            .unwrap()
+            .lazy();
+        let mut phases = Vec::new();
+        let counts = ValueCounts::compute(
+            &lf,
+            "s",
+            None,
+            None,
+            &SamplingStrategy::default(),
+            false,
+            |p| phases.push(p),
+        )
+        .unwrap();
+        assert_eq!(phases, vec![0, 1, 2]);
+        assert_eq!(counts.distinct, 3);
+        assert_eq!(counts.total_rows, 6);
+        let lines = counts.lines(&DisplayLocale::ISO);
+        assert_eq!(
+            lines,
+            vec![
+                ("b".to_string(), "3".to_string(), "50.0%".to_string()),
+                ("a".to_string(), "2".to_string(), "33.3%".to_string()),
+                ("(null)".to_string(), "1".to_string(), "16.7%".to_string()),
+            ]
+        );
+        assert_eq!(counts.coverage(&DisplayLocale::ISO), "3 distinct · 6 rows");
+    }
+
+    #[test]
+    fn test_lists_top_values_of_many() {
+        let lf = df!("x" => (0..1_000i64).map(|i| i % 200).collect::<Vec<_>>())
+            .unwrap()
+            .lazy();
+        let counts = ValueCounts::compute(
+            &lf,
+            "x",
+            Some(1_000),
+            None,
+            &SamplingStrategy::default(),
+            false,
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(counts.values.len(), TOP_VALUES);
+        assert_eq!(counts.values[0], (AnyValue::Int64(0), 5));
+        let locale = DisplayLocale::from_name("en-US").unwrap();
+        assert_eq!(
+            counts.coverage(&locale),
+            "top 50 of 200 distinct · 1,000 rows"
+        );
+
+        let sampled = ValueCounts::compute(
+            &lf,
+            "x",
+            Some(1_000),
+            Some(100),
+            &SamplingStrategy::default(),
+            false,
+            |_| {},
+        )
+        .unwrap();
+        assert!(sampled.sampled);
+        assert_eq!(sampled.rows, 100);
+    }
+}
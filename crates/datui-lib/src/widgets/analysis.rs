@@ -11,13 +11,24 @@ use ratatui::{
 };
 
 use crate::analysis_modal::{AnalysisFocus, AnalysisTool, AnalysisView, HistogramScale};
+use crate::analysis_plugin;
+use crate::cardinality::{CardinalityFlag, CardinalityReport};
 use crate::config::Theme;
+use crate::crosstab::{Crosstab, CrosstabSpec};
+use crate::duplicates::DuplicateReport;
+use crate::heatmap;
+use crate::missing_data::MissingDataReport;
 use crate::statistics::{
     beta_pdf, chi_squared_pdf, gamma_pdf, gamma_quantile, geometric_pmf, geometric_quantile,
-    students_t_pdf, weibull_pdf, AnalysisContext, AnalysisResults, DistributionAnalysis,
-    DistributionType,
+    students_t_pdf, weibull_pdf, AnalysisContext, AnalysisResults, ColumnStatistics,
+    DistributionAnalysis, DistributionType,
 };
+use crate::stats_compare::{context_label, CompareRow, COMPARE_STATS};
+use crate::time_gaps::{format_frequency, GapReport};
 use crate::widgets::datatable::DataTableState;
+use crate::widgets::text_input::TextInput;
+use polars::prelude::DataFrame;
+use std::sync::Arc;
 
 pub struct AnalysisWidgetConfig<'a> {
     pub state: &'a DataTableState,
@@ -30,14 +41,70 @@ pub struct AnalysisWidgetConfig<'a> {
     pub focus: AnalysisFocus,
     pub selected_theoretical_distribution: DistributionType,
     pub histogram_scale: HistogramScale,
+    /// Draw the best-fit curve on the detail histogram alongside the selected distribution's.
+    pub show_best_fit: bool,
     pub theme: &'a Theme,
     pub table_cell_padding: u16,
+    pub correlation: CorrelationView,
+    pub gaps: TimeGapsView<'a>,
+    pub cardinality: Option<&'a Result<CardinalityReport, String>>,
+    pub missing: Option<&'a Result<MissingDataReport, String>>,
+    pub duplicates: DuplicatesView<'a>,
+    pub crosstab: CrosstabView<'a>,
+    pub plugins: PluginsView<'a>,
+    /// Saved Describe baseline, if any.
+    pub describe_baseline: Option<DescribeBaselineView<'a>>,
+}
+
+/// Saved Describe baseline: its label and, when the compare view is on, the paired rows.
+pub struct DescribeBaselineView<'a> {
+    pub label: &'a str,
+    pub rows: Option<Vec<CompareRow>>,
+}
+
+/// Display settings of the Correlation Matrix tool.
+pub struct CorrelationView {
+    /// Shade cells on a diverging palette instead of coloring only the text.
+    pub heatmap: bool,
+    /// Off-diagonal cells with |r| at or above this are highlighted.
+    pub threshold: f64,
+}
+
+/// Inputs for the Time Series Gaps tool: its result and current settings.
+pub struct TimeGapsView<'a> {
+    pub results: Option<&'a Result<GapReport, String>>,
+    pub time_column: Option<&'a str>,
+    pub frequency_input: &'a TextInput,
+    pub frequency_editing: bool,
+}
+
+/// Inputs for the Duplicate Rows tool: its result and key columns.
+pub struct DuplicatesView<'a> {
+    pub results: Option<&'a Result<DuplicateReport, String>>,
+    /// Key columns the next search uses; empty = the whole row.
+    pub keys: &'a [String],
+    pub keys_input: &'a TextInput,
+    pub keys_editing: bool,
+}
+
+/// Inputs for the Crosstab tool: its table and columns.
+pub struct CrosstabView<'a> {
+    pub results: Option<&'a Result<Crosstab, String>>,
+    pub spec: Option<&'a CrosstabSpec>,
+    pub input: &'a TextInput,
+    pub editing: bool,
+}
+
+/// Registered analysis plugins and their results, by the same index.
+pub struct PluginsView<'a> {
+    pub tools: &'a [Arc<dyn analysis_plugin::AnalysisTool>],
+    pub results: &'a [Option<Result<DataFrame, String>>],
 }
 
 pub struct AnalysisWidget<'a> {
     _state: &'a DataTableState,
     results: Option<&'a AnalysisResults>,
-    _context: &'a AnalysisContext,
+    context: &'a AnalysisContext,
     view: AnalysisView,
     selected_tool: Option<AnalysisTool>,
     table_state: &'a mut TableState,
@@ -50,8 +117,20 @@ pub struct AnalysisWidget<'a> {
     selected_theoretical_distribution: DistributionType,
     distribution_selector_state: &'a mut TableState,
     histogram_scale: HistogramScale,
+    show_best_fit: bool,
     theme: &'a Theme,
     table_cell_padding: u16,
+    correlation: CorrelationView,
+    gaps: TimeGapsView<'a>,
+    cardinality: Option<&'a Result<CardinalityReport, String>>,
+    missing: Option<&'a Result<MissingDataReport, String>>,
+    duplicates: DuplicatesView<'a>,
+    crosstab: CrosstabView<'a>,
+    plugins: PluginsView<'a>,
+    describe_baseline: Option<DescribeBaselineView<'a>>,
+    /// Selection for the list-style tools (gaps, cardinality, missing data, duplicates); the
+    /// caller passes the selected tool's state.
+    list_table_state: &'a mut TableState,
 }
 
 impl<'a> AnalysisWidget<'a> {
@@ -62,11 +141,12 @@ impl<'a> AnalysisWidget<'a> {
         correlation_table_state: &'a mut TableState,
         sidebar_state: &'a mut TableState,
         distribution_selector_state: &'a mut TableState,
+        list_table_state: &'a mut TableState,
     ) -> Self {
         Self {
             _state: config.state,
             results: config.results,
-            _context: config.context,
+            context: config.context,
             view: config.view,
             selected_tool: config.selected_tool,
             table_state,
@@ -79,8 +159,18 @@ impl<'a> AnalysisWidget<'a> {
             selected_theoretical_distribution: config.selected_theoretical_distribution,
             distribution_selector_state,
             histogram_scale: config.histogram_scale,
+            show_best_fit: config.show_best_fit,
             theme: config.theme,
             table_cell_padding: config.table_cell_padding,
+            correlation: config.correlation,
+            gaps: config.gaps,
+            cardinality: config.cardinality,
+            missing: config.missing,
+            duplicates: config.duplicates,
+            crosstab: config.crosstab,
+            plugins: config.plugins,
+            describe_baseline: config.describe_baseline,
+            list_table_state,
         }
     }
 }
@@ -114,10 +204,43 @@ impl<'a> AnalysisWidget<'a> {
             Some(AnalysisTool::Describe) => "Describe",
             Some(AnalysisTool::DistributionAnalysis) => "Distribution Analysis",
             Some(AnalysisTool::CorrelationMatrix) => "Correlation Matrix",
+            Some(AnalysisTool::TimeSeriesGaps) => "Time Series Gaps",
+            Some(AnalysisTool::ColumnCardinality) => "Column Cardinality",
+            Some(AnalysisTool::MissingData) => "Missing Data",
+            Some(AnalysisTool::DuplicateRows) => "Duplicate Rows",
+            Some(AnalysisTool::Crosstab) => "Crosstab",
+            Some(AnalysisTool::Plugin(index)) => self
+                .plugins
+                .tools
+                .get(index)
+                .map_or("Analysis", |tool| tool.name()),
             None => "Analysis",
         };
 
-        let breadcrumb_text = if let Some(results) = self.results {
+        let cardinality_sampled = match self.selected_tool {
+            Some(AnalysisTool::ColumnCardinality) => {
+                matches!(self.cardinality, Some(Ok(report)) if report.sampled_from.is_some())
+            }
+            Some(AnalysisTool::MissingData) => {
+                matches!(self.missing, Some(Ok(report)) if report.sampled_from.is_some())
+            }
+            _ => false,
+        };
+        let breadcrumb_text = if let (Some(AnalysisTool::Describe), Some(baseline)) =
+            (self.selected_tool, &self.describe_baseline)
+        {
+            if baseline.rows.is_some() {
+                format!(
+                    "Describe: {} vs baseline {}",
+                    context_label(self.context),
+                    baseline.label
+                )
+            } else {
+                format!("Describe (baseline: {})", baseline.label)
+            }
+        } else if cardinality_sampled {
+            format!("{} (sampled)", tool_name)
+        } else if let Some(results) = self.results {
             if results.sample_size.is_some() {
                 format!("{} (sampled)", tool_name)
             } else {
@@ -158,19 +281,93 @@ impl<'a> AnalysisWidget<'a> {
                     .style(Style::default().fg(self.theme.get("text_primary")))
                     .render(inner[1], buf);
             }
+            Some(AnalysisTool::TimeSeriesGaps) => {
+                render_time_gaps(
+                    &self.gaps,
+                    self._state.row_start_index(),
+                    self.list_table_state,
+                    main_layout[0],
+                    buf,
+                    self.theme,
+                );
+            }
+            Some(AnalysisTool::ColumnCardinality) => {
+                render_cardinality(
+                    self.cardinality,
+                    self.list_table_state,
+                    main_layout[0],
+                    buf,
+                    self.theme,
+                );
+            }
+            Some(AnalysisTool::MissingData) => {
+                render_missing_data(
+                    self.missing,
+                    self.list_table_state,
+                    main_layout[0],
+                    buf,
+                    self.theme,
+                );
+            }
+            Some(AnalysisTool::DuplicateRows) => {
+                render_duplicates(
+                    &self.duplicates,
+                    self._state.row_start_index(),
+                    self.list_table_state,
+                    main_layout[0],
+                    buf,
+                    self.theme,
+                );
+            }
+            Some(AnalysisTool::Crosstab) => {
+                render_crosstab(
+                    &self.crosstab,
+                    self.list_table_state,
+                    self.column_offset,
+                    main_layout[0],
+                    buf,
+                    self.theme,
+                );
+            }
+            Some(AnalysisTool::Plugin(index)) => {
+                render_plugin(
+                    &self.plugins,
+                    index,
+                    self.list_table_state,
+                    main_layout[0],
+                    buf,
+                    self.theme,
+                );
+            }
             Some(tool) => {
                 if let Some(results) = self.results {
                     match tool {
                         AnalysisTool::Describe => {
-                            render_statistics_table(
-                                results,
-                                self.table_state,
-                                self.column_offset,
-                                main_layout[0],
-                                buf,
-                                self.theme,
-                                self.table_cell_padding,
-                            );
+                            if let Some(rows) = self
+                                .describe_baseline
+                                .as_ref()
+                                .and_then(|b| b.rows.as_ref())
+                            {
+                                render_compare_table(
+                                    rows,
+                                    self.table_state,
+                                    self.column_offset,
+                                    main_layout[0],
+                                    buf,
+                                    self.theme,
+                                    self.table_cell_padding,
+                                );
+                            } else {
+                                render_statistics_table(
+                                    results,
+                                    self.table_state,
+                                    self.column_offset,
+                                    main_layout[0],
+                                    buf,
+                                    self.theme,
+                                    self.table_cell_padding,
+                                );
+                            }
                         }
                         AnalysisTool::DistributionAnalysis => {
                             render_distribution_table(
@@ -188,11 +385,18 @@ impl<'a> AnalysisWidget<'a> {
                                 self.correlation_table_state,
                                 &self.selected_correlation,
                                 self.column_offset,
+                                &self.correlation,
                                 main_layout[0],
                                 buf,
                                 self.theme,
                             );
                         }
+                        AnalysisTool::TimeSeriesGaps
+                        | AnalysisTool::ColumnCardinality
+                        | AnalysisTool::MissingData
+                        | AnalysisTool::DuplicateRows
+                        | AnalysisTool::Crosstab
+                        | AnalysisTool::Plugin(_) => {}
                     }
                 } else {
                     Paragraph::new("Computing statistics...")
@@ -207,6 +411,7 @@ impl<'a> AnalysisWidget<'a> {
             main_layout[1],
             buf,
             self.sidebar_state,
+            self.plugins.tools,
             self.selected_tool,
             self.focus,
             self.theme,
@@ -403,9 +608,13 @@ impl<'a> AnalysisWidget<'a> {
             let log_scale_requested_but_unavailable =
                 matches!(self.histogram_scale, HistogramScale::Log) && !can_use_log_scale;
 
+            let best_fit = best_fit_distribution(dist).filter(|&best| {
+                self.show_best_fit && best != self.selected_theoretical_distribution
+            });
             let histogram_config = HistogramRenderConfig {
                 dist,
                 dist_type: self.selected_theoretical_distribution,
+                best_fit,
                 area: histogram_area,
                 shared_y_axis_label_width,
                 theme: self.theme,
@@ -429,6 +638,7 @@ impl<'a> AnalysisWidget<'a> {
             render_distribution_settings(
                 self.histogram_scale,
                 log_scale_requested_but_unavailable,
+                self.show_best_fit,
                 right_layout[1],
                 buf,
                 self.theme,
@@ -457,8 +667,7 @@ fn render_statistics_table(
     theme: &Theme,
     table_cell_padding: u16,
 ) {
-    let num_columns = results.column_statistics.len();
-    if num_columns == 0 {
+    if results.column_statistics.is_empty() {
         Paragraph::new("No columns to display")
             .centered()
             .render(area, buf);
@@ -466,7 +675,7 @@ fn render_statistics_table(
     }
 
     // Statistics to display (in order) - internal names for matching data
-    let stat_names = vec![
+    let stat_names = [
         "count",
         "null_count",
         "mean",
@@ -478,13 +687,178 @@ fn render_statistics_table(
         "max",
     ];
     // Display names in Title case for headers
-    let stat_display_names = vec![
+    let stat_display_names = [
         "Count", "Nulls", "Mean", "Std", "Min", "25%", "50%", "75%", "Max",
     ];
-    let num_stats = stat_names.len();
+
+    let rows = results
+        .column_statistics
+        .iter()
+        .map(|col_stat| {
+            let values = stat_names
+                .iter()
+                .map(|stat_name| describe_value(col_stat, stat_name))
+                .collect();
+            (col_stat.name.clone(), values)
+        })
+        .collect();
+    render_stat_columns(
+        &stat_display_names.map(String::from),
+        rows,
+        table_state,
+        column_offset,
+        area,
+        buf,
+        theme,
+        table_cell_padding,
+    );
+}
+
+/// Value of one describe statistic for a column ("-" when it does not apply).
+fn describe_value(col_stat: &ColumnStatistics, stat_name: &str) -> String {
+    match stat_name {
+        "count" => col_stat.count.to_string(),
+        "null_count" => col_stat.null_count.to_string(),
+        "mean" => col_stat
+            .numeric_stats
+            .as_ref()
+            .map(|n| format_num(n.mean))
+            .unwrap_or_else(|| "-".to_string()),
+        "std" => col_stat
+            .numeric_stats
+            .as_ref()
+            .map(|n| format_num(n.std))
+            .unwrap_or_else(|| "-".to_string()),
+        "min" => {
+            if let Some(ref num_stats) = col_stat.numeric_stats {
+                format_num(num_stats.min)
+            } else if let Some(ref cat_stats) = col_stat.categorical_stats {
+                cat_stats.min.clone().unwrap_or_else(|| "-".to_string())
+            } else {
+                "-".to_string()
+            }
+        }
+        "25%" => col_stat
+            .numeric_stats
+            .as_ref()
+            .map(|n| format_num(n.q25))
+            .unwrap_or_else(|| "-".to_string()),
+        "50%" => col_stat
+            .numeric_stats
+            .as_ref()
+            .map(|n| format_num(n.median))
+            .unwrap_or_else(|| "-".to_string()),
+        "75%" => col_stat
+            .numeric_stats
+            .as_ref()
+            .map(|n| format_num(n.q75))
+            .unwrap_or_else(|| "-".to_string()),
+        "max" => {
+            if let Some(ref num_stats) = col_stat.numeric_stats {
+                format_num(num_stats.max)
+            } else if let Some(ref cat_stats) = col_stat.categorical_stats {
+                cat_stats.max.clone().unwrap_or_else(|| "-".to_string())
+            } else {
+                "-".to_string()
+            }
+        }
+        _ => "-".to_string(),
+    }
+}
+
+/// Describe statistics beside the baseline's: for each statistic, the baseline value, the
+/// current value and the change.
+fn render_compare_table(
+    compare_rows: &[CompareRow],
+    table_state: &mut TableState,
+    column_offset: usize,
+    area: Rect,
+    buf: &mut Buffer,
+    theme: &Theme,
+    table_cell_padding: u16,
+) {
+    if compare_rows.is_empty() {
+        Paragraph::new("No columns to display")
+            .centered()
+            .render(area, buf);
+        return;
+    }
+
+    let headers: Vec<String> = COMPARE_STATS
+        .iter()
+        .flat_map(|stat| {
+            [
+                format!("{} (base)", stat),
+                stat.to_string(),
+                format!("{} Δ", stat),
+            ]
+        })
+        .collect();
+    let rows = compare_rows
+        .iter()
+        .map(|row| {
+            let values = (0..COMPARE_STATS.len())
+                .flat_map(|stat| {
+                    // Count and Nulls are whole numbers
+                    let format = |v: f64| {
+                        if stat < 2 {
+                            format!("{}", v as i64)
+                        } else {
+                            format_num(v)
+                        }
+                    };
+                    let delta = match (row.delta(stat), row.delta_percent(stat)) {
+                        (Some(d), Some(p)) => format!("{}{} ({:+.1}%)", sign(d), format(d), p),
+                        (Some(d), None) => format!("{}{}", sign(d), format(d)),
+                        _ => "-".to_string(),
+                    };
+                    [
+                        row.baseline[stat].map_or_else(|| "-".to_string(), format),
+                        row.current[stat].map_or_else(|| "-".to_string(), format),
+                        delta,
+                    ]
+                })
+                .collect();
+            (row.column.clone(), values)
+        })
+        .collect();
+    render_stat_columns(
+        &headers,
+        rows,
+        table_state,
+        column_offset,
+        area,
+        buf,
+        theme,
+        table_cell_padding,
+    );
+}
+
+/// "+" for positive changes; negative numbers already carry their sign.
+fn sign(v: f64) -> &'static str {
+    if v > 0.0 {
+        "+"
+    } else {
+        ""
+    }
+}
+
+/// Table with a locked "Column" name column and statistic columns that scroll horizontally
+/// from `column_offset`. Each row is a column name and one value per header.
+#[allow(clippy::too_many_arguments)]
+fn render_stat_columns(
+    stat_display_names: &[String],
+    rows: Vec<(String, Vec<String>)>,
+    table_state: &mut TableState,
+    column_offset: usize,
+    area: Rect,
+    buf: &mut Buffer,
+    theme: &Theme,
+    table_cell_padding: u16,
+) {
+    let num_stats = stat_display_names.len();
 
     // Calculate column widths based on header names and content (minimal spacing)
-    // First, determine minimum width for each column based on header length
     // Note: ratatui Table adds 1 space between columns by default, so we don't add extra padding
     let mut min_col_widths: Vec<u16> = stat_display_names
         .iter()
@@ -492,72 +866,20 @@ fn render_statistics_table(
         .collect();
 
     // Scan all data to find maximum width needed for each column
-    for col_stat in &results.column_statistics {
-        for (stat_idx, stat_name) in stat_names.iter().enumerate() {
-            let value_str = match *stat_name {
-                "count" => col_stat.count.to_string(),
-                "null_count" => col_stat.null_count.to_string(),
-                "mean" => col_stat
-                    .numeric_stats
-                    .as_ref()
-                    .map(|n| format_num(n.mean))
-                    .unwrap_or_else(|| "-".to_string()),
-                "std" => col_stat
-                    .numeric_stats
-                    .as_ref()
-                    .map(|n| format_num(n.std))
-                    .unwrap_or_else(|| "-".to_string()),
-                "min" => {
-                    if let Some(ref num_stats) = col_stat.numeric_stats {
-                        format_num(num_stats.min)
-                    } else if let Some(ref cat_stats) = col_stat.categorical_stats {
-                        cat_stats.min.clone().unwrap_or_else(|| "-".to_string())
-                    } else {
-                        "-".to_string()
-                    }
-                }
-                "25%" => col_stat
-                    .numeric_stats
-                    .as_ref()
-                    .map(|n| format_num(n.q25))
-                    .unwrap_or_else(|| "-".to_string()),
-                "50%" => col_stat
-                    .numeric_stats
-                    .as_ref()
-                    .map(|n| format_num(n.median))
-                    .unwrap_or_else(|| "-".to_string()),
-                "75%" => col_stat
-                    .numeric_stats
-                    .as_ref()
-                    .map(|n| format_num(n.q75))
-                    .unwrap_or_else(|| "-".to_string()),
-                "max" => {
-                    if let Some(ref num_stats) = col_stat.numeric_stats {
-                        format_num(num_stats.max)
-                    } else if let Some(ref cat_stats) = col_stat.categorical_stats {
-                        cat_stats.max.clone().unwrap_or_else(|| "-".to_string())
-                    } else {
-                        "-".to_string()
-                    }
-                }
-                _ => "-".to_string(),
-            };
+    for (_, values) in &rows {
+        for (stat_idx, value_str) in values.iter().enumerate() {
             let value_len = value_str.chars().count() as u16;
-            // Ensure width is at least the header length (already initialized) AND value length
-            // This preserves header widths even if all data values are shorter
-            let header_len = stat_display_names[stat_idx].chars().count() as u16;
-            min_col_widths[stat_idx] = min_col_widths[stat_idx].max(value_len).max(header_len);
-            // must fit both header and content (no padding - table handles spacing)
+            // Width must fit both header (already initialized) and value
+            min_col_widths[stat_idx] = min_col_widths[stat_idx].max(value_len);
         }
     }
 
     // Locked column width (column name) - calculate from header text AND actual column names
     let header_text = "Column";
     let header_len = header_text.chars().count() as u16;
-    let max_col_name_len = results
-        .column_statistics
+    let max_col_name_len = rows
         .iter()
-        .map(|cs| cs.name.chars().count() as u16)
+        .map(|(name, _)| name.chars().count() as u16)
         .max()
         .unwrap_or(header_len);
     let locked_col_width = max_col_name_len.max(header_len).max(10); // min 10, must fit both header and data (no padding - table handles spacing)
@@ -632,74 +954,25 @@ fn render_statistics_table(
         return;
     }
 
-    let mut rows = Vec::new();
-
     let mut header_cells = vec![Cell::from("Column").style(Style::default())];
     for &stat_idx in &visible_stats {
-        header_cells.push(Cell::from(stat_display_names[stat_idx]).style(Style::default()));
+        header_cells
+            .push(Cell::from(stat_display_names[stat_idx].as_str()).style(Style::default()));
     }
     let header_row_style = header_style(theme, "controls_bg", "table_header");
-    let header_row = Row::new(header_cells.clone()).style(header_row_style);
-
-    for col_stat in &results.column_statistics {
-        let mut cells = vec![Cell::from(col_stat.name.as_str())
-            .style(Style::default().fg(theme.get("text_primary")))];
-        for &stat_idx in &visible_stats {
-            let stat_name = stat_names[stat_idx];
-            let value = match stat_name {
-                "count" => col_stat.count.to_string(),
-                "null_count" => col_stat.null_count.to_string(),
-                "mean" => col_stat
-                    .numeric_stats
-                    .as_ref()
-                    .map(|n| format_num(n.mean))
-                    .unwrap_or_else(|| "-".to_string()),
-                "std" => col_stat
-                    .numeric_stats
-                    .as_ref()
-                    .map(|n| format_num(n.std))
-                    .unwrap_or_else(|| "-".to_string()),
-                "min" => {
-                    if let Some(ref num_stats) = col_stat.numeric_stats {
-                        format_num(num_stats.min)
-                    } else if let Some(ref cat_stats) = col_stat.categorical_stats {
-                        cat_stats.min.clone().unwrap_or_else(|| "-".to_string())
-                    } else {
-                        "-".to_string()
-                    }
-                }
-                "25%" => col_stat
-                    .numeric_stats
-                    .as_ref()
-                    .map(|n| format_num(n.q25))
-                    .unwrap_or_else(|| "-".to_string()),
-                "50%" => col_stat
-                    .numeric_stats
-                    .as_ref()
-                    .map(|n| format_num(n.median))
-                    .unwrap_or_else(|| "-".to_string()),
-                "75%" => col_stat
-                    .numeric_stats
-                    .as_ref()
-                    .map(|n| format_num(n.q75))
-                    .unwrap_or_else(|| "-".to_string()),
-                "max" => {
-                    if let Some(ref num_stats) = col_stat.numeric_stats {
-                        format_num(num_stats.max)
-                    } else if let Some(ref cat_stats) = col_stat.categorical_stats {
-                        cat_stats.max.clone().unwrap_or_else(|| "-".to_string())
-                    } else {
-                        "-".to_string()
-                    }
-                }
-                _ => "-".to_string(),
-            };
-
-            cells.push(Cell::from(value));
-        }
+    let header_row = Row::new(header_cells).style(header_row_style);
 
-        rows.push(Row::new(cells));
-    }
+    let table_rows: Vec<Row> = rows
+        .into_iter()
+        .map(|(name, mut values)| {
+            let mut cells =
+                vec![Cell::from(name).style(Style::default().fg(theme.get("text_primary")))];
+            for &stat_idx in &visible_stats {
+                cells.push(Cell::from(std::mem::take(&mut values[stat_idx])));
+            }
+            Row::new(cells)
+        })
+        .collect();
 
     let mut constraints = vec![Constraint::Length(locked_col_width)];
     for &stat_idx in &visible_stats {
@@ -707,7 +980,7 @@ fn render_statistics_table(
         constraints.push(Constraint::Length(min_col_widths[stat_idx]));
     }
 
-    let table = Table::new(rows, constraints)
+    let table = Table::new(table_rows, constraints)
         .header(header_row)
         .column_spacing(table_cell_padding)
         .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
@@ -1040,11 +1313,13 @@ fn render_distribution_table(
     StatefulWidget::render(table, area, buf, table_state);
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_correlation_matrix(
     results: &AnalysisResults,
     table_state: &mut TableState,
     selected_cell: &Option<(usize, usize)>,
     column_offset: usize,
+    view: &CorrelationView,
     area: Rect,
     buf: &mut Buffer,
     theme: &Theme,
@@ -1068,6 +1343,16 @@ fn render_correlation_matrix(
 
     let n = correlation_matrix.columns.len();
 
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Fill(1),   // Matrix
+            Constraint::Length(1), // Legend + hint
+        ])
+        .split(area);
+    render_correlation_legend(view, layout[1], buf, theme);
+    let area = layout[0];
+
     // Calculate column widths - ensure they're wide enough for content
     let row_header_width = 20u16;
     let cell_width = 12u16; // Wide enough for "-1.00" format
@@ -1152,6 +1437,11 @@ fn render_correlation_matrix(
                 Style::default()
                     .fg(theme.get("text_inverse"))
                     .bg(theme.get("modal_border_active"))
+            } else if view.heatmap {
+                // Heatmap: the shade carries the value; the selection shows in the headers
+                Style::default()
+                    .fg(theme.get("text_primary"))
+                    .bg(correlation_shade(correlation, theme))
             } else if is_selected_row || is_in_selected_col {
                 // Selected row or column: dim background with colored text
                 Style::default().fg(text_color).bg(theme.get("surface"))
@@ -1159,6 +1449,11 @@ fn render_correlation_matrix(
                 // Normal cell: just text color
                 Style::default().fg(text_color)
             };
+            let cell_style = if i != col_idx && correlation.abs() >= view.threshold {
+                cell_style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+            } else {
+                cell_style
+            };
 
             cells.push(Cell::from(cell_text).style(cell_style));
         }
@@ -1190,6 +1485,47 @@ fn render_correlation_matrix(
     StatefulWidget::render(table, area, buf, table_state);
 }
 
+/// Heatmap background of a correlation: the theme's `heatmap_low` at -1 and `heatmap_high` at
+/// +1, fading to the neutral `controls_bg` at 0.
+fn correlation_shade(correlation: f64, theme: &Theme) -> Color {
+    let neutral = theme.get("controls_bg");
+    if correlation.is_nan() {
+        neutral
+    } else if correlation < 0.0 {
+        heatmap::shade(neutral, theme.get("heatmap_low"), (-correlation).min(1.0))
+    } else {
+        heatmap::shade(neutral, theme.get("heatmap_high"), correlation.min(1.0))
+    }
+}
+
+/// One line below the matrix: the palette (in heatmap mode), the highlight threshold and the
+/// tool's keys.
+fn render_correlation_legend(view: &CorrelationView, area: Rect, buf: &mut Buffer, theme: &Theme) {
+    let hint_style = Style::default().fg(theme.get("dimmed"));
+    let text_style = Style::default().fg(theme.get("text_primary"));
+    let mut spans = Vec::new();
+    if view.heatmap {
+        spans.push(Span::styled("-1 ", text_style));
+        for r in [-1.0, -0.5, 0.0, 0.5, 1.0] {
+            spans.push(Span::styled(
+                "  ",
+                Style::default().bg(correlation_shade(r, theme)),
+            ));
+        }
+        spans.push(Span::styled(" +1   ", text_style));
+    }
+    spans.push(Span::styled(
+        format!("|r| ≥ {:.2}", view.threshold),
+        text_style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+    ));
+    let mode = if view.heatmap { "numbers" } else { "heatmap" };
+    spans.push(Span::styled(
+        format!("  (+/-: threshold  H: {mode}  e: export)"),
+        hint_style,
+    ));
+    Paragraph::new(Line::from(spans)).render(area, buf);
+}
+
 fn get_correlation_color(correlation: f64, theme: &Theme) -> Color {
     let abs_corr = correlation.abs();
 
@@ -1340,6 +1676,8 @@ fn render_distribution_selector(
 struct HistogramRenderConfig<'a> {
     dist: &'a DistributionAnalysis,
     dist_type: DistributionType,
+    /// Best-fit distribution drawn alongside `dist_type`; None draws only `dist_type`.
+    best_fit: Option<DistributionType>,
     area: Rect,
     shared_y_axis_label_width: u16,
     theme: &'a Theme,
@@ -1350,6 +1688,7 @@ struct HistogramRenderConfig<'a> {
 fn render_distribution_settings(
     histogram_scale: HistogramScale,
     log_scale_unavailable: bool,
+    show_best_fit: bool,
     area: Rect,
     buf: &mut Buffer,
     theme: &Theme,
@@ -1379,6 +1718,7 @@ fn render_distribution_settings(
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(1), // Scale setting line
+            Constraint::Length(1), // Best fit setting line
             Constraint::Fill(1),   // Remaining space
         ])
         .split(inner_area);
@@ -1402,68 +1742,1269 @@ fn render_distribution_settings(
         .style(scale_value_style)
         .render(scale_layout[1], buf);
 
+    let best_fit_label = "Best fit:";
+    let best_fit_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(best_fit_label.chars().count() as u16 + 1),
+            Constraint::Fill(1),
+        ])
+        .split(settings_layout[1]);
+    Paragraph::new(best_fit_label)
+        .style(scale_label_style)
+        .render(best_fit_layout[0], buf);
+    Paragraph::new(if show_best_fit { "Shown" } else { "Hidden" })
+        .style(Style::default().fg(theme.get("text_primary")))
+        .render(best_fit_layout[1], buf);
+
     block.render(area, buf);
 }
 
-fn render_sidebar(
+/// Settings and summary on top, then the gap list (selectable, Enter jumps to the row) and the
+/// longest null run per column.
+fn render_time_gaps(
+    view: &TimeGapsView,
+    row_start_index: usize,
+    table_state: &mut TableState,
     area: Rect,
     buf: &mut Buffer,
-    sidebar_state: &mut TableState,
-    selected_tool: Option<AnalysisTool>,
-    focus: AnalysisFocus,
     theme: &Theme,
 ) {
-    let tools = [
-        ("Describe", AnalysisTool::Describe),
-        ("Distribution Analysis", AnalysisTool::DistributionAnalysis),
-        ("Correlation Matrix", AnalysisTool::CorrelationMatrix),
-    ];
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Settings + summary
+            Constraint::Fill(2),   // Gaps
+            Constraint::Fill(1),   // Null runs
+        ])
+        .split(area);
 
     let text_primary = theme.get("text_primary");
-    // Use REVERSED for focused row (like main table) so selection is always visible,
-    // even when controls_bg is "default"/none.
-    let focused_style = Style::default().add_modifier(Modifier::REVERSED);
+    let label_style = Style::default()
+        .fg(theme.get("label"))
+        .add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(theme.get("dimmed"));
+    let report = view.results.and_then(|r| r.as_ref().ok());
+
+    let time_column = report
+        .map(|r| r.time_column.as_str())
+        .or(view.time_column)
+        .unwrap_or("-");
+    Paragraph::new(Line::from(vec![
+        Span::styled("Time column: ", label_style),
+        Span::styled(time_column.to_string(), Style::default().fg(text_primary)),
+        Span::styled("  (t: next)", hint_style),
+    ]))
+    .render(
+        Rect {
+            height: 1,
+            ..layout[0]
+        },
+        buf,
+    );
 
-    let items: Vec<ListItem> = tools
+    let frequency_row = Rect {
+        y: layout[0].y + 1,
+        height: 1,
+        ..layout[0]
+    };
+    let label = "Frequency: ";
+    Paragraph::new(Span::styled(label, label_style)).render(frequency_row, buf);
+    let value_area = Rect {
+        x: frequency_row.x + label.len() as u16,
+        width: frequency_row.width.saturating_sub(label.len() as u16),
+        ..frequency_row
+    };
+    if view.frequency_editing {
+        let input_area = Rect {
+            width: value_area.width.min(24),
+            ..value_area
+        };
+        view.frequency_input.render(input_area, buf);
+        Paragraph::new(Span::styled(
+            "  e.g. 15m, 1h, 1d; empty = infer; Enter to apply",
+            hint_style,
+        ))
+        .render(
+            Rect {
+                x: input_area.right(),
+                width: value_area.width.saturating_sub(input_area.width),
+                ..value_area
+            },
+            buf,
+        );
+    } else {
+        let (value, source) = match report {
+            Some(r) if r.frequency_inferred => (format_frequency(r.frequency_ns), " (inferred)"),
+            Some(r) => (format_frequency(r.frequency_ns), ""),
+            None => ("-".to_string(), ""),
+        };
+        Paragraph::new(Line::from(vec![
+            Span::styled(value, Style::default().fg(text_primary)),
+            Span::styled(source, hint_style),
+            Span::styled("  (f: set)", hint_style),
+        ]))
+        .render(value_area, buf);
+    }
+
+    let summary_row = Rect {
+        y: layout[0].y + 2,
+        height: 1,
+        ..layout[0]
+    };
+    let report = match view.results {
+        None => {
+            Paragraph::new("Finding gaps...")
+                .centered()
+                .render(layout[1], buf);
+            return;
+        }
+        Some(Err(message)) => {
+            Paragraph::new(message.as_str())
+                .style(Style::default().fg(theme.get("warning")))
+                .render(summary_row, buf);
+            return;
+        }
+        Some(Ok(report)) => report,
+    };
+    let mut summary = format!(
+        "{} rows, {} gaps, {} missing timestamps",
+        report.rows,
+        report.gaps.len(),
+        report.missing_total
+    );
+    if report.duplicates > 0 {
+        summary.push_str(&format!(", {} duplicate timestamps", report.duplicates));
+    }
+    if report.out_of_order > 0 {
+        summary.push_str(&format!(
+            ", {} out of order (sort by {} for accurate gaps)",
+            report.out_of_order, report.time_column
+        ));
+    }
+    Paragraph::new(summary)
+        .style(Style::default().fg(text_primary))
+        .render(summary_row, buf);
+
+    let header_row_style = header_style(theme, "controls_bg", "table_header");
+    let border_style = Style::default().fg(theme.get("modal_border"));
+
+    let gap_rows: Vec<Row> = report
+        .gaps
         .iter()
-        .enumerate()
-        .map(|(idx, (name, tool))| {
-            let is_selected = selected_tool == Some(*tool);
-            let is_focused =
-                focus == AnalysisFocus::Sidebar && sidebar_state.selected() == Some(idx);
-            let prefix = if is_selected { "> " } else { "  " };
-            let style = if is_focused {
-                focused_style
-            } else {
-                Style::default().fg(text_primary)
-            };
-            ListItem::new(format!("{}{}", prefix, name)).style(style)
+        .map(|gap| {
+            Row::new(vec![
+                Cell::from(Line::from((gap.row + row_start_index).to_string()).right_aligned()),
+                Cell::from(gap.from.clone()),
+                Cell::from(gap.to.clone()),
+                Cell::from(Line::from(gap.missing.to_string()).right_aligned()),
+            ])
         })
         .collect();
+    let gaps_table = Table::new(
+        gap_rows,
+        [
+            Constraint::Length(10),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Length(10),
+        ],
+    )
+    .header(Row::new(vec!["Row", "From", "To", "Missing"]).style(header_row_style))
+    .block(
+        Block::default()
+            .title(" Gaps (Enter: jump to row) ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(border_style),
+    )
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(gaps_table, layout[1], buf, table_state);
 
-    let border_color = if focus == AnalysisFocus::Sidebar {
-        theme.get("modal_border_active")
-    } else {
-        theme.get("modal_border")
-    };
-    let block = Block::default()
-        .title("Analysis Tools")
-        .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(border_color));
+    let null_rows: Vec<Row> = report
+        .null_runs
+        .iter()
+        .map(|run| {
+            let start = run
+                .start_row
+                .map(|r| (r + row_start_index).to_string())
+                .unwrap_or_else(|| "-".to_string());
+            Row::new(vec![
+                Cell::from(run.column.clone()),
+                Cell::from(Line::from(run.longest.to_string()).right_aligned()),
+                Cell::from(Line::from(start).right_aligned()),
+                Cell::from(Line::from(run.null_count.to_string()).right_aligned()),
+            ])
+        })
+        .collect();
+    let null_table = Table::new(
+        null_rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(10),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            Cell::from("Column"),
+            Cell::from(Line::from("Longest run").right_aligned()),
+            Cell::from(Line::from("Starts at").right_aligned()),
+            Cell::from(Line::from("Nulls").right_aligned()),
+        ])
+        .style(header_row_style),
+    )
+    .block(
+        Block::default()
+            .title(" Null runs ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(border_style),
+    );
+    Widget::render(null_table, layout[2], buf);
+}
 
-    let list = List::new(items).block(block);
+/// Columns ranked by distinct ratio, with entropy and a flag for constant columns and likely keys.
+fn render_cardinality(
+    results: Option<&Result<CardinalityReport, String>>,
+    table_state: &mut TableState,
+    area: Rect,
+    buf: &mut Buffer,
+    theme: &Theme,
+) {
+    let report = match results {
+        None => {
+            Paragraph::new("Ranking columns...")
+                .centered()
+                .render(area, buf);
+            return;
+        }
+        Some(Err(message)) => {
+            Paragraph::new(message.as_str())
+                .style(Style::default().fg(theme.get("warning")))
+                .render(area, buf);
+            return;
+        }
+        Some(Ok(report)) => report,
+    };
 
-    Widget::render(list, area, buf);
-}
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Fill(1)])
+        .split(area);
 
-fn render_distribution_histogram(config: HistogramRenderConfig, buf: &mut Buffer) {
-    // Use BarChart widget to show histogram comparing data vs theoretical distribution
-    // Use fixed-width bins that span both data range and theoretical distribution range
-    let HistogramRenderConfig {
-        dist,
-        dist_type,
-        area,
+    let keys = report
+        .columns
+        .iter()
+        .filter(|c| c.flag == Some(CardinalityFlag::LikelyKey))
+        .count();
+    let constant = report
+        .columns
+        .iter()
+        .filter(|c| {
+            matches!(
+                c.flag,
+                Some(CardinalityFlag::Constant) | Some(CardinalityFlag::AllNull)
+            )
+        })
+        .count();
+    let rows = match report.sampled_from {
+        Some(total) => format!("sample of {} / {} rows", report.rows, total),
+        None => format!("{} rows", report.rows),
+    };
+    Paragraph::new(format!(
+        "{} columns, {}: {} likely keys, {} constant or empty",
+        report.columns.len(),
+        rows,
+        keys,
+        constant
+    ))
+    .style(Style::default().fg(theme.get("text_primary")))
+    .render(layout[0], buf);
+
+    let dimmed = Style::default().fg(theme.get("dimmed"));
+    let right = |text: String| Cell::from(Line::from(text).right_aligned());
+    let rows: Vec<Row> = report
+        .columns
+        .iter()
+        .map(|c| {
+            let (distinct, ratio, entropy) = match (c.distinct, c.distinct_ratio, c.entropy) {
+                (Some(d), Some(r), Some(e)) => (
+                    d.to_string(),
+                    format!("{:.1}%", r * 100.0),
+                    format!("{:.2}", e),
+                ),
+                _ => ("-".to_string(), "-".to_string(), "-".to_string()),
+            };
+            let flag = match c.flag {
+                Some(CardinalityFlag::LikelyKey) => Cell::from(Span::styled(
+                    CardinalityFlag::LikelyKey.label(),
+                    Style::default()
+                        .fg(theme.get("success"))
+                        .add_modifier(Modifier::BOLD),
+                )),
+                Some(flag) => Cell::from(Span::styled(flag.label(), dimmed)),
+                None => Cell::from(""),
+            };
+            let row = Row::new(vec![
+                Cell::from(c.column.clone()),
+                Cell::from(format!("{}", c.dtype)),
+                right(distinct),
+                right(ratio),
+                right(entropy),
+                right(c.null_count.to_string()),
+                flag,
+            ]);
+            if matches!(
+                c.flag,
+                Some(CardinalityFlag::Constant) | Some(CardinalityFlag::AllNull)
+            ) {
+                row.style(dimmed)
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let header = Row::new(vec![
+        Cell::from("Column"),
+        Cell::from("Type"),
+        Cell::from(Line::from("Distinct").right_aligned()),
+        Cell::from(Line::from("Distinct %").right_aligned()),
+        Cell::from(Line::from("Entropy").right_aligned()),
+        Cell::from(Line::from("Nulls").right_aligned()),
+        Cell::from("Flag"),
+    ])
+    .style(header_style(theme, "controls_bg", "table_header"));
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Length(10),
+            Constraint::Length(11),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(11),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(table, layout[1], buf, table_state);
+}
+
+/// Shade for a heatmap cell holding `fraction` nulls: a dot when there are none, then lighter
+/// to darker blocks.
+fn null_shade(fraction: f64) -> &'static str {
+    match fraction {
+        f if f <= 0.0 => "·",
+        f if f < 0.25 => "░",
+        f if f < 0.5 => "▒",
+        f if f < 0.75 => "▓",
+        _ => "█",
+    }
+}
+
+/// Null counts per column with a share bar, beside a heatmap of where the nulls are: row bands
+/// down, columns (in list order) across, the highlighted column marked.
+fn render_missing_data(
+    results: Option<&Result<MissingDataReport, String>>,
+    table_state: &mut TableState,
+    area: Rect,
+    buf: &mut Buffer,
+    theme: &Theme,
+) {
+    let report = match results {
+        None => {
+            Paragraph::new("Counting nulls...")
+                .centered()
+                .render(area, buf);
+            return;
+        }
+        Some(Err(message)) => {
+            Paragraph::new(message.as_str())
+                .style(Style::default().fg(theme.get("warning")))
+                .render(area, buf);
+            return;
+        }
+        Some(Ok(report)) => report,
+    };
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .split(area);
+
+    let rows = match report.sampled_from {
+        Some(total) => format!("sample of {} / {} rows", report.rows, total),
+        None => format!("{} rows", report.rows),
+    };
+    let row_share = if report.rows > 0 {
+        report.rows_with_nulls as f64 * 100.0 / report.rows as f64
+    } else {
+        0.0
+    };
+    Paragraph::new(format!(
+        "{} columns, {}: {} columns with nulls, {} rows ({:.1}%) with a null",
+        report.columns.len(),
+        rows,
+        report.columns_with_nulls().count(),
+        report.rows_with_nulls,
+        row_share
+    ))
+    .style(Style::default().fg(theme.get("text_primary")))
+    .render(layout[0], buf);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Fill(3), Constraint::Fill(2)])
+        .split(layout[1]);
+
+    const BAR_WIDTH: usize = 12;
+    let dimmed = Style::default().fg(theme.get("dimmed"));
+    let bar_style = Style::default().fg(theme.get("warning"));
+    let right = |text: String| Cell::from(Line::from(text).right_aligned());
+    let table_rows: Vec<Row> = report
+        .columns
+        .iter()
+        .map(|c| {
+            let fraction = report.null_fraction(c);
+            let bar = "█".repeat((fraction * BAR_WIDTH as f64).ceil() as usize);
+            let row = Row::new(vec![
+                Cell::from(c.column.clone()),
+                Cell::from(format!("{}", c.dtype)),
+                right(c.null_count.to_string()),
+                right(format!("{:.1}%", fraction * 100.0)),
+                Cell::from(Span::styled(bar, bar_style)),
+            ]);
+            if c.null_count == 0 {
+                row.style(dimmed)
+            } else {
+                row
+            }
+        })
+        .collect();
+    let header = Row::new(vec![
+        Cell::from("Column"),
+        Cell::from("Type"),
+        Cell::from(Line::from("Nulls").right_aligned()),
+        Cell::from(Line::from("Null %").right_aligned()),
+        Cell::from(""),
+    ])
+    .style(header_style(theme, "controls_bg", "table_header"));
+    let table = Table::new(
+        table_rows,
+        [
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Length(10),
+            Constraint::Length(7),
+            Constraint::Length(BAR_WIDTH as u16),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(table, body[0], buf, table_state);
+
+    let block = Block::default()
+        .title(" Null pattern ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.get("modal_border")));
+    let inner = block.inner(body[1]);
+    block.render(body[1], buf);
+    let bands = report.pattern.len();
+    if inner.height > 0 && bands > 0 {
+        // Merge bands into the lines available, averaging their shares
+        let lines = bands.min(inner.height as usize);
+        let selected = table_state.selected();
+        let text: Vec<Line> = (0..lines)
+            .map(|line| {
+                let merged = &report.pattern[line * bands / lines..(line + 1) * bands / lines];
+                let spans: Vec<Span> = (0..report.columns.len().min(inner.width as usize))
+                    .map(|column| {
+                        let fraction = merged.iter().map(|band| band[column]).sum::<f64>()
+                            / merged.len() as f64;
+                        let style = if selected == Some(column) {
+                            bar_style.add_modifier(Modifier::REVERSED)
+                        } else if fraction > 0.0 {
+                            bar_style
+                        } else {
+                            dimmed
+                        };
+                        Span::styled(null_shade(fraction), style)
+                    })
+                    .collect();
+                Line::from(spans)
+            })
+            .collect();
+        Paragraph::new(text).render(inner, buf);
+    }
+
+    Paragraph::new("Enter filters to the rows where the highlighted column is null")
+        .style(dimmed)
+        .render(layout[2], buf);
+}
+
+/// Key columns (editable with `c`), a summary and the duplicated groups, largest first.
+fn render_duplicates(
+    view: &DuplicatesView,
+    row_start_index: usize,
+    table_state: &mut TableState,
+    area: Rect,
+    buf: &mut Buffer,
+    theme: &Theme,
+) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Keys + summary
+            Constraint::Fill(1),   // Groups
+            Constraint::Length(1), // Hint
+        ])
+        .split(area);
+
+    let text_primary = theme.get("text_primary");
+    let label_style = Style::default()
+        .fg(theme.get("label"))
+        .add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(theme.get("dimmed"));
+
+    let keys_row = Rect {
+        height: 1,
+        ..layout[0]
+    };
+    let label = "Keys: ";
+    Paragraph::new(Span::styled(label, label_style)).render(keys_row, buf);
+    let value_area = Rect {
+        x: keys_row.x + label.len() as u16,
+        width: keys_row.width.saturating_sub(label.len() as u16),
+        ..keys_row
+    };
+    if view.keys_editing {
+        let input_area = Rect {
+            width: value_area.width.min(40),
+            ..value_area
+        };
+        view.keys_input.render(input_area, buf);
+        Paragraph::new(Span::styled(
+            "  comma-separated; empty = whole row; Enter to apply",
+            hint_style,
+        ))
+        .render(
+            Rect {
+                x: input_area.right(),
+                width: value_area.width.saturating_sub(input_area.width),
+                ..value_area
+            },
+            buf,
+        );
+    } else {
+        let keys = if view.keys.is_empty() {
+            "whole row".to_string()
+        } else {
+            view.keys.join(", ")
+        };
+        Paragraph::new(Line::from(vec![
+            Span::styled(keys, Style::default().fg(text_primary)),
+            Span::styled("  (c: set)", hint_style),
+        ]))
+        .render(value_area, buf);
+    }
+
+    let summary_row = Rect {
+        y: layout[0].y + 1,
+        height: 1,
+        ..layout[0]
+    };
+    let report = match view.results {
+        None => {
+            Paragraph::new("Finding duplicates...")
+                .centered()
+                .render(layout[1], buf);
+            return;
+        }
+        Some(Err(message)) => {
+            Paragraph::new(message.as_str())
+                .style(Style::default().fg(theme.get("warning")))
+                .render(summary_row, buf);
+            return;
+        }
+        Some(Ok(report)) => report,
+    };
+    let mut summary = format!(
+        "{} rows: {} duplicated groups, {} rows in them, {} extra",
+        report.rows,
+        report.group_count,
+        report.duplicate_rows,
+        report.extra_rows()
+    );
+    if !report.skipped.is_empty() {
+        summary.push_str(&format!(" (not compared: {})", report.skipped.join(", ")));
+    }
+    Paragraph::new(summary)
+        .style(Style::default().fg(text_primary))
+        .render(summary_row, buf);
+
+    let title = if report.groups.len() < report.group_count {
+        format!(
+            " Groups (largest {} of {}) ",
+            report.groups.len(),
+            report.group_count
+        )
+    } else {
+        " Groups ".to_string()
+    };
+    let group_rows: Vec<Row> = report
+        .groups
+        .iter()
+        .map(|group| {
+            Row::new(vec![
+                Cell::from(group.values.join(", ")),
+                Cell::from(Line::from(group.count.to_string()).right_aligned()),
+                Cell::from(
+                    Line::from((group.first_row + row_start_index).to_string()).right_aligned(),
+                ),
+            ])
+        })
+        .collect();
+    let header = Row::new(vec![
+        Cell::from(report.key_columns.join(", ")),
+        Cell::from(Line::from("Count").right_aligned()),
+        Cell::from(Line::from("First row").right_aligned()),
+    ])
+    .style(header_style(theme, "controls_bg", "table_header"));
+    let table = Table::new(
+        group_rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(8),
+            Constraint::Length(10),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.get("modal_border"))),
+    )
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(table, layout[1], buf, table_state);
+
+    Paragraph::new(
+        "Enter jumps to the group's first row; f filters the view to duplicates, e exports them",
+    )
+    .style(hint_style)
+    .render(layout[2], buf);
+}
+
+/// Crosstab cell text: whole numbers as such (counts), others like the other tables.
+fn crosstab_value(value: Option<f64>) -> String {
+    match value {
+        None => "-".to_string(),
+        Some(v) if v.fract() == 0.0 && v.abs() < 1e12 => format!("{}", v as i64),
+        Some(v) => format_num(v),
+    }
+}
+
+fn render_crosstab(
+    view: &CrosstabView,
+    table_state: &mut TableState,
+    column_offset: usize,
+    area: Rect,
+    buf: &mut Buffer,
+    theme: &Theme,
+) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Columns
+            Constraint::Fill(1),   // Table
+            Constraint::Length(1), // Chi-square
+        ])
+        .split(area);
+
+    let text_primary = theme.get("text_primary");
+    let label_style = Style::default()
+        .fg(theme.get("label"))
+        .add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(theme.get("dimmed"));
+
+    let label = "Columns: ";
+    Paragraph::new(Span::styled(label, label_style)).render(layout[0], buf);
+    let value_area = Rect {
+        x: layout[0].x + label.len() as u16,
+        width: layout[0].width.saturating_sub(label.len() as u16),
+        ..layout[0]
+    };
+    if view.editing {
+        let input_area = Rect {
+            width: value_area.width.min(40),
+            ..value_area
+        };
+        view.input.render(input_area, buf);
+        Paragraph::new(Span::styled(
+            "  rows, columns[, values]; Enter to apply",
+            hint_style,
+        ))
+        .render(
+            Rect {
+                x: input_area.right(),
+                width: value_area.width.saturating_sub(input_area.width),
+                ..value_area
+            },
+            buf,
+        );
+    } else {
+        let mut spans = match view.spec {
+            Some(spec) => vec![
+                Span::styled(
+                    format!("{} × {}", spec.rows, spec.columns),
+                    Style::default().fg(text_primary),
+                ),
+                Span::styled(
+                    format!("  cells: {}", spec.cells_label()),
+                    Style::default().fg(text_primary),
+                ),
+            ],
+            None => vec![Span::styled("none", Style::default().fg(text_primary))],
+        };
+        spans.push(Span::styled("  (c: set", hint_style));
+        if view.spec.is_some_and(|spec| spec.value.is_some()) {
+            spans.push(Span::styled(", a: aggregation", hint_style));
+        }
+        spans.push(Span::styled(")", hint_style));
+        Paragraph::new(Line::from(spans)).render(value_area, buf);
+    }
+
+    let table = match view.results {
+        None => {
+            Paragraph::new("Building crosstab...")
+                .centered()
+                .render(layout[1], buf);
+            return;
+        }
+        Some(Err(message)) => {
+            Paragraph::new(message.as_str())
+                .style(Style::default().fg(theme.get("warning")))
+                .render(layout[1], buf);
+            return;
+        }
+        Some(Ok(table)) => table,
+    };
+
+    // Every value column is as wide as its widest entry; the row labels and totals stay put
+    // while the value columns scroll.
+    let width = |text: &str| text.chars().count() as u16;
+    let row_header_width = table
+        .row_labels
+        .iter()
+        .map(|l| width(l))
+        .chain([width(&table.spec.rows), width("Total")])
+        .max()
+        .unwrap_or(0)
+        .min(24);
+    let columns: Vec<(String, Vec<String>)> = table
+        .column_labels
+        .iter()
+        .enumerate()
+        .map(|(j, name)| {
+            let mut values: Vec<String> = table
+                .cells
+                .iter()
+                .map(|row| crosstab_value(row[j]))
+                .collect();
+            values.push(crosstab_value(table.column_totals[j]));
+            (name.clone(), values)
+        })
+        .collect();
+    let mut totals: Vec<String> = table
+        .row_totals
+        .iter()
+        .map(|&v| crosstab_value(v))
+        .collect();
+    totals.push(crosstab_value(table.total));
+    let column_width = |(name, values): &(String, Vec<String>)| {
+        values
+            .iter()
+            .map(|v| width(v))
+            .chain([width(name)])
+            .max()
+            .unwrap_or(0)
+            .clamp(4, 16)
+    };
+    let total_width = column_width(&("Total".to_string(), totals.clone()));
+
+    let block = Block::default()
+        .title(format!(
+            " {} × {}: {} ",
+            table.spec.rows,
+            table.spec.columns,
+            table.spec.cells_label()
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.get("modal_border")));
+    let inner_width = block.inner(layout[1]).width;
+    let start = column_offset.min(columns.len().saturating_sub(1));
+    let mut available = inner_width.saturating_sub(row_header_width + 1 + total_width);
+    let mut visible = Vec::new();
+    for column in columns.iter().skip(start) {
+        let needed = column_width(column) + 1;
+        if needed > available && !visible.is_empty() {
+            break;
+        }
+        available = available.saturating_sub(needed);
+        visible.push(column);
+    }
+
+    let header_row_style = header_style(theme, "controls_bg", "table_header");
+    let right = |text: String| Cell::from(Line::from(text).right_aligned());
+    let mut header = vec![Cell::from(table.spec.rows.clone())];
+    header.extend(visible.iter().map(|(name, _)| right(name.clone())));
+    header.push(right("Total".to_string()));
+
+    let mut labels = table.row_labels.clone();
+    labels.push("Total".to_string());
+    let last = labels.len() - 1;
+    let rows: Vec<Row> = labels
+        .into_iter()
+        .enumerate()
+        .map(|(i, label)| {
+            let mut cells = vec![Cell::from(label)];
+            cells.extend(visible.iter().map(|(_, values)| right(values[i].clone())));
+            cells.push(right(totals[i].clone()));
+            let row = Row::new(cells);
+            if i == last {
+                row.style(Style::default().add_modifier(Modifier::BOLD))
+            } else {
+                row
+            }
+        })
+        .collect();
+    let mut widths = vec![Constraint::Length(row_header_width)];
+    widths.extend(visible.iter().map(|c| Constraint::Length(column_width(c))));
+    widths.push(Constraint::Length(total_width));
+    let hidden = columns.len() - visible.len();
+    let block = if hidden > 0 {
+        block.title_bottom(
+            Line::from(format!(
+                " columns {}-{} of {} (←→ scroll) ",
+                start + 1,
+                start + visible.len(),
+                columns.len()
+            ))
+            .right_aligned(),
+        )
+    } else {
+        block
+    };
+    let widget = Table::new(rows, widths)
+        .header(Row::new(header).style(header_row_style))
+        .column_spacing(1)
+        .block(block)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    StatefulWidget::render(widget, layout[1], buf, table_state);
+
+    let footer = match &table.chi_square {
+        Some(chi) => {
+            let mut text = format!(
+                "χ² = {}, df = {}, p = {}, Cramér's V = {:.2} (N = {})",
+                format_num(chi.statistic),
+                chi.dof,
+                format_pvalue(chi.p_value),
+                chi.cramers_v,
+                table.rows
+            );
+            if chi.low_expected > 0.2 {
+                text.push_str(&format!(
+                    "; {:.0}% of cells expect fewer than 5 rows, so p is unreliable",
+                    chi.low_expected * 100.0
+                ));
+            }
+            Span::styled(text, Style::default().fg(text_primary))
+        }
+        None => Span::styled(
+            "Chi-square needs at least 2 values in each column",
+            hint_style,
+        ),
+    };
+    Paragraph::new(footer).render(layout[2], buf);
+}
+
+/// A plugin's result drawn by the plugin, "Computing..." until it arrives, or its error.
+fn render_plugin(
+    plugins: &PluginsView,
+    index: usize,
+    table_state: &mut TableState,
+    area: Rect,
+    buf: &mut Buffer,
+    theme: &Theme,
+) {
+    let Some(tool) = plugins.tools.get(index) else {
+        return;
+    };
+    match plugins.results.get(index).and_then(Option::as_ref) {
+        None => Paragraph::new("Computing...").centered().render(area, buf),
+        Some(Err(message)) => Paragraph::new(message.as_str())
+            .style(Style::default().fg(theme.get("warning")))
+            .render(area, buf),
+        Some(Ok(result)) => tool.render(result, area, buf, table_state, theme),
+    }
+}
+
+fn render_sidebar(
+    area: Rect,
+    buf: &mut Buffer,
+    sidebar_state: &mut TableState,
+    plugins: &[Arc<dyn analysis_plugin::AnalysisTool>],
+    selected_tool: Option<AnalysisTool>,
+    focus: AnalysisFocus,
+    theme: &Theme,
+) {
+    let built_in = [
+        ("Describe", AnalysisTool::Describe),
+        ("Distribution Analysis", AnalysisTool::DistributionAnalysis),
+        ("Correlation Matrix", AnalysisTool::CorrelationMatrix),
+        ("Time Series Gaps", AnalysisTool::TimeSeriesGaps),
+        ("Column Cardinality", AnalysisTool::ColumnCardinality),
+        ("Missing Data", AnalysisTool::MissingData),
+        ("Duplicate Rows", AnalysisTool::DuplicateRows),
+        ("Crosstab", AnalysisTool::Crosstab),
+    ];
+    let tools = built_in.into_iter().chain(
+        plugins
+            .iter()
+            .enumerate()
+            .map(|(index, tool)| (tool.name(), AnalysisTool::Plugin(index))),
+    );
+
+    let text_primary = theme.get("text_primary");
+    // Use REVERSED for focused row (like main table) so selection is always visible,
+    // even when controls_bg is "default"/none.
+    let focused_style = Style::default().add_modifier(Modifier::REVERSED);
+
+    let items: Vec<ListItem> = tools
+        .enumerate()
+        .map(|(idx, (name, tool))| {
+            let is_selected = selected_tool == Some(tool);
+            let is_focused =
+                focus == AnalysisFocus::Sidebar && sidebar_state.selected() == Some(idx);
+            let prefix = if is_selected { "> " } else { "  " };
+            let style = if is_focused {
+                focused_style
+            } else {
+                Style::default().fg(text_primary)
+            };
+            ListItem::new(format!("{}{}", prefix, name)).style(style)
+        })
+        .collect();
+
+    let border_color = if focus == AnalysisFocus::Sidebar {
+        theme.get("modal_border_active")
+    } else {
+        theme.get("modal_border")
+    };
+    let block = Block::default()
+        .title("Analysis Tools")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(border_color));
+
+    let list = List::new(items).block(block);
+
+    Widget::render(list, area, buf);
+}
+
+/// Distribution that best fits `dist`: its detected type, or else the tested distribution with
+/// the highest p-value.
+fn best_fit_distribution(dist: &DistributionAnalysis) -> Option<DistributionType> {
+    if dist.distribution_type != DistributionType::Unknown {
+        return Some(dist.distribution_type);
+    }
+    dist.all_distribution_pvalues
+        .iter()
+        .filter(|(_, p)| p.is_finite())
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(&dist_type, _)| dist_type)
+}
+
+/// Bins of the distribution histogram, shared by the bars and the fitted curves.
+struct HistogramBins<'a> {
+    boundaries: &'a [f64],
+    hist_min: f64,
+    hist_max: f64,
+}
+
+/// `dist_type` fitted to `dist`, sampled across the histogram as (x, expected count) points: the
+/// PDF at x times the width of x's bin times the sample size, so the curve follows the bars on
+/// linear and log-width bins alike. Falls back to the expected count per bin center when the
+/// data has no spread.
+fn fitted_curve(
+    dist: &DistributionAnalysis,
+    dist_type: DistributionType,
+    bins: &HistogramBins,
+    num_samples: usize,
+) -> Vec<(f64, f64)> {
+    let sorted_data = &dist.sorted_sample_values;
+    let n = sorted_data.len();
+    let bin_boundaries = bins.boundaries;
+    let num_bins = bin_boundaries.len().saturating_sub(1);
+    if n == 0 || num_bins == 0 {
+        return Vec::new();
+    }
+    // Calculate theoretical bin probabilities using CDF, as expected counts
+    let theory_bin_counts: Vec<f64> =
+        crate::statistics::calculate_theoretical_bin_probabilities(dist, dist_type, bin_boundaries)
+            .iter()
+            .map(|&prob| prob * n as f64)
+            .collect();
+    if theory_bin_counts.len() < num_bins {
+        return Vec::new();
+    }
+    let (hist_min, hist_max) = (bins.hist_min, bins.hist_max);
+    let hist_range = hist_max - hist_min;
+    if num_samples <= 1 || hist_range <= 0.0 || dist.characteristics.std_dev <= 0.0 {
+        return bin_boundaries
+            .windows(2)
+            .zip(&theory_bin_counts)
+            .map(|(b, &count)| ((b[0] + b[1]) / 2.0, count))
+            .collect();
+    }
+
+    let data_min = sorted_data[0];
+    let data_max = sorted_data[n - 1];
+    let mean = dist.characteristics.mean;
+    let std = dist.characteristics.std_dev;
+
+    // Sample across the full range, but use a small epsilon to avoid exact boundary conditions
+    // that can cause issues with domain-restricted distributions (e.g., Gamma at x=0, Beta at x=0 or x=1)
+    // The epsilon is very small (0.1% of range) so the curve still extends nearly to the edges
+    let epsilon = hist_range * 0.001;
+    let effective_min = hist_min + epsilon;
+    let effective_range = hist_range - 2.0 * epsilon;
+
+    (0..num_samples)
+        .map(|i| {
+            let x = effective_min + (i as f64 / (num_samples - 1) as f64) * effective_range;
+            // Bin x falls in, and its width (log-scale bins widen with x)
+            let bin_idx = bin_boundaries
+                .partition_point(|&b| b <= x)
+                .saturating_sub(1)
+                .min(num_bins - 1);
+            let bin_width = bin_boundaries[bin_idx + 1] - bin_boundaries[bin_idx];
+
+            // PDF gives us density (probability per unit), convert to count: PDF(x) * bin_width * n
+            let count = match dist_type {
+                DistributionType::Normal => {
+                    // Normal PDF: (1 / (σ * sqrt(2π))) * exp(-0.5 * ((x - μ) / σ)²)
+                    let z = (x - mean) / std;
+                    let pdf =
+                        (1.0 / (std * (2.0 * std::f64::consts::PI).sqrt())) * (-0.5 * z * z).exp();
+                    pdf * bin_width * n as f64
+                }
+                DistributionType::LogNormal => {
+                    // LogNormal PDF: show theoretical distribution over [0, ∞) even if data is negative
+                    if x > 0.0 {
+                        let (mu, sigma) = if mean > 0.0 && std >= 0.0 {
+                            let variance = std * std;
+                            let sigma_sq = (1.0 + variance / (mean * mean)).ln();
+                            let mu_val = mean.ln() - sigma_sq / 2.0;
+                            let sigma_val = sigma_sq.sqrt();
+                            (mu_val, sigma_val)
+                        } else {
+                            // Data doesn't match LogNormal: use default parameters (mu=0, sigma=1)
+                            (0.0, 1.0)
+                        };
+                        let z = (x.ln() - mu) / sigma;
+                        let pdf = (1.0 / (x * sigma * (2.0 * std::f64::consts::PI).sqrt()))
+                            * (-0.5 * z * z).exp();
+                        pdf * bin_width * n as f64
+                    } else {
+                        // LogNormal is strictly positive, return 0 for x <= 0
+                        0.0
+                    }
+                }
+                DistributionType::Exponential => {
+                    // Exponential PDF: show theoretical distribution over [0, ∞) even if data is negative
+                    if x >= 0.0 {
+                        let lambda = if mean > 0.0 {
+                            1.0 / mean
+                        } else {
+                            // Data doesn't match Exponential: use default lambda=1
+                            1.0
+                        };
+                        let pdf = lambda * (-lambda * x).exp();
+                        pdf * bin_width * n as f64
+                    } else {
+                        // Exponential is strictly non-negative, return 0 for x < 0
+                        0.0
+                    }
+                }
+                DistributionType::Uniform => {
+                    if !sorted_data.is_empty() && x >= data_min && x <= data_max {
+                        let data_range = data_max - data_min;
+                        if data_range > 0.0 {
+                            let pdf = 1.0 / data_range;
+                            pdf * bin_width * n as f64
+                        } else {
+                            0.0
+                        }
+                    } else {
+                        0.0
+                    }
+                }
+                DistributionType::Gamma => {
+                    // Gamma PDF: evaluate directly for smooth curve
+                    // Show theoretical distribution over its valid domain [0, ∞) even if data is negative
+                    if x > 0.0 {
+                        let variance = std * std;
+                        let (shape, scale) = if mean > 0.0 && variance > 0.0 {
+                            let s = (mean * mean) / variance;
+                            let sc = variance / mean;
+                            if s > 0.0 && sc > 0.0 {
+                                (s, sc)
+                            } else {
+                                // Invalid parameters: use default (exponential with scale=1)
+                                (1.0, 1.0)
+                            }
+                        } else {
+                            // Data doesn't match Gamma (e.g., negative mean): use default parameters
+                            // This ensures we still show the theoretical distribution shape
+                            (1.0, 1.0)
+                        };
+                        let pdf = gamma_pdf(x, shape, scale);
+                        pdf * bin_width * n as f64
+                    } else {
+                        // Gamma is strictly non-negative, return 0 for x <= 0
+                        0.0
+                    }
+                }
+                DistributionType::Geometric => {
+                    // Geometric PMF: evaluate directly for smooth curve
+                    if x >= 0.0 && mean > 0.0 {
+                        let p_param = 1.0 / (mean + 1.0);
+                        if p_param > 0.0 && p_param < 1.0 {
+                            // Use PMF for continuous approximation
+                            let pmf = geometric_pmf(x, p_param);
+                            // Convert PMF to expected count: PMF * n
+                            // Note: For discrete distributions, we use PMF directly rather than PDF * bin_width
+                            pmf * n as f64
+                        } else {
+                            0.0
+                        }
+                    } else {
+                        0.0
+                    }
+                }
+                DistributionType::Weibull => {
+                    // Weibull PDF: evaluate directly for smooth curve
+                    if x > 0.0 && mean > 0.0 && std > 0.0 {
+                        // Approximate shape from CV
+                        let cv = std / mean;
+                        let shape = if cv < 1.0 { 1.0 / cv } else { 1.0 };
+                        // Scale from mean
+                        let gamma_1_over_shape = 1.0 + 1.0 / shape; // Approximation
+                        let scale = mean / gamma_1_over_shape;
+                        if shape > 0.0 && scale > 0.0 {
+                            let pdf = weibull_pdf(x, shape, scale);
+                            pdf * bin_width * n as f64
+                        } else {
+                            0.0
+                        }
+                    } else {
+                        0.0
+                    }
+                }
+                DistributionType::Beta => {
+                    // Beta PDF: evaluate directly for smooth curve
+                    if x > 0.0 && x < 1.0 {
+                        let variance = std * std;
+                        let mean_val = mean;
+                        if mean_val > 0.0 && mean_val < 1.0 && variance > 0.0 {
+                            let max_var = mean_val * (1.0 - mean_val);
+                            if variance < max_var {
+                                // Estimate alpha and beta using method of moments
+                                let sum = mean_val * (1.0 - mean_val) / variance - 1.0;
+                                let alpha = mean_val * sum;
+                                let beta = (1.0 - mean_val) * sum;
+                                if alpha > 0.0 && beta > 0.0 {
+                                    let pdf = beta_pdf(x, alpha, beta);
+                                    pdf * bin_width * n as f64
+                                } else {
+                                    0.0
+                                }
+                            } else {
+                                0.0
+                            }
+                        } else {
+                            0.0
+                        }
+                    } else {
+                        0.0
+                    }
+                }
+                DistributionType::ChiSquared => {
+                    // ChiSquared PDF: evaluate directly for smooth curve (uses gamma_pdf)
+                    if x > 0.0 {
+                        let df = mean; // For chi-squared, mean = df
+                        if df > 0.0 {
+                            let pdf = chi_squared_pdf(x, df);
+                            pdf * bin_width * n as f64
+                        } else {
+                            0.0
+                        }
+                    } else {
+                        0.0
+                    }
+                }
+                DistributionType::StudentsT => {
+                    // StudentsT PDF: evaluate directly for smooth curve
+                    let variance = std * std;
+                    let df = if variance > 1.0 {
+                        2.0 * variance / (variance - 1.0)
+                    } else {
+                        30.0
+                    };
+                    if df > 0.0 {
+                        // StudentsT is centered at mean, but PDF is typically for standard t (mean=0, std=1)
+                        // Adjust x to account for data mean and scale
+                        let x_standardized = if std > 0.0 { (x - mean) / std } else { 0.0 };
+                        let pdf_standard = students_t_pdf(x_standardized, df);
+                        // Convert back to data scale: PDF_standard / std
+                        let pdf = if std > 0.0 { pdf_standard / std } else { 0.0 };
+                        pdf * bin_width * n as f64
+                    } else {
+                        0.0
+                    }
+                }
+                DistributionType::PowerLaw => {
+                    // PowerLaw PDF depends on its x_min parameter; use the bin's expected count from the CDF
+                    theory_bin_counts[bin_idx]
+                }
+                // Distributions without a PDF here use the bin's expected count from the CDF
+                _ => theory_bin_counts[bin_idx],
+            };
+            (x, count)
+        })
+        .collect()
+}
+
+fn render_distribution_histogram(config: HistogramRenderConfig, buf: &mut Buffer) {
+    // Use BarChart widget to show histogram comparing data vs theoretical distribution
+    // Use fixed-width bins that span both data range and theoretical distribution range
+    let HistogramRenderConfig {
+        dist,
+        dist_type,
+        best_fit,
+        area,
         shared_y_axis_label_width,
         theme,
         unified_x_range,
@@ -1558,7 +3099,7 @@ fn render_distribution_histogram(config: HistogramRenderConfig, buf: &mut Buffer
         && log_hist_min > 0.0
         && log_hist_max > log_hist_min;
 
-    let (bin_boundaries, bin_width): (Vec<f64>, f64) = if use_log_scale {
+    let bin_boundaries: Vec<f64> = if use_log_scale {
         // Log-scale binning: bins with equal width in log space
         // This ensures each bin represents roughly equal multiplicative range
         // Use adjusted range based on actual data values
@@ -1567,25 +3108,18 @@ fn render_distribution_histogram(config: HistogramRenderConfig, buf: &mut Buffer
         let log_range = log_max - log_min;
         let log_bin_width = log_range / num_bins as f64;
 
-        let boundaries: Vec<f64> = (0..=num_bins)
+        (0..=num_bins)
             .map(|i| {
                 let log_value = log_min + (i as f64) * log_bin_width;
                 log_value.exp()
             })
-            .collect();
-
-        // For log scale, calculate average bin width for use in theoretical PDF calculations
-        // This is approximate but needed for compatibility
-        let log_range_linear = log_hist_max - log_hist_min;
-        let avg_bin_width = log_range_linear / num_bins as f64;
-        (boundaries, avg_bin_width)
+            .collect()
     } else {
         // Linear binning for all other distributions
         let bin_width = hist_range / num_bins as f64;
-        let boundaries: Vec<f64> = (0..=num_bins)
+        (0..=num_bins)
             .map(|i| hist_min + (i as f64) * bin_width)
-            .collect();
-        (boundaries, bin_width)
+            .collect()
     };
 
     // Count data points in each bin
@@ -1601,19 +3135,27 @@ fn render_distribution_histogram(config: HistogramRenderConfig, buf: &mut Buffer
         }
     }
 
-    // Calculate theoretical bin probabilities using CDF for the selected distribution
-    let theory_probs = crate::statistics::calculate_theoretical_bin_probabilities(
-        dist,
-        dist_type,
-        &bin_boundaries,
-    );
-
-    // Convert probabilities to expected counts
-    let theory_bin_counts: Vec<f64> = theory_probs.iter().map(|&prob| prob * n as f64).collect();
+    // Fitted curves: the selected distribution and, for comparison, the best fit
+    let bins = HistogramBins {
+        boundaries: &bin_boundaries,
+        hist_min: bin_boundaries[0],
+        hist_max: bin_boundaries[num_bins],
+    };
+    // Braille markers create 2x4 dot patterns per character, need high density
+    let num_samples = (available_width as usize * 15).clamp(1500, 10000);
+    let theory_counts = fitted_curve(dist, dist_type, &bins, num_samples);
+    let best_fit_counts = best_fit
+        .map(|best| fitted_curve(dist, best, &bins, num_samples))
+        .unwrap_or_default();
 
     // Normalize values for display (find the maximum for scaling)
     let max_data = data_bin_counts.iter().cloned().fold(0, usize::max);
-    let max_theory = theory_bin_counts.iter().cloned().fold(0.0, f64::max);
+    let max_theory = theory_counts
+        .iter()
+        .chain(&best_fit_counts)
+        .map(|&(_, count)| count)
+        .filter(|count| count.is_finite())
+        .fold(0.0, f64::max);
     let global_max = max_data.max(max_theory as usize).max(1) as f64;
 
     // Use the shared label width calculated in the caller
@@ -1727,314 +3269,58 @@ fn render_distribution_histogram(config: HistogramRenderConfig, buf: &mut Buffer
 
     // No border - chart renders without surrounding box
 
-    // Overlay theory distribution as dense scatter plot (dot plot) on top of bar chart
-    // Evaluate theoretical PDF directly at each x point for accurate smooth curve
-    // This ensures the theoretical distribution shows the correct shape (e.g., bell curve for normal)
-    // Use very dense sampling for smooth continuous appearance
-    // Braille markers create 2x4 dot patterns per character, need high density
-    let num_samples = (available_width as usize * 15).clamp(1500, 10000); // Very dense for smooth Braille lines
-
-    let theory_points: Vec<(f64, f64)> = if num_bins > 0
-        && !theory_bin_counts.is_empty()
-        && num_samples > 1
-        && hist_range > 0.0
-        && dist.characteristics.std_dev > 0.0
-    {
-        // Evaluate theoretical PDF directly at each x point for accurate smooth curve
-        // Get distribution parameters
-        let mean = dist.characteristics.mean;
-        let std = dist.characteristics.std_dev;
-
-        // Evaluate theoretical PDF directly at each x point for accurate smooth curve
-        // Sample across the full range, but use a small epsilon to avoid exact boundary conditions
-        // that can cause issues with domain-restricted distributions (e.g., Gamma at x=0, Beta at x=0 or x=1)
-        // The epsilon is very small (0.1% of range) so the curve still extends nearly to the edges
-        let epsilon = hist_range * 0.001; // 0.1% of range - small enough to be visually negligible
-        let effective_min = hist_min + epsilon;
-        let effective_max = hist_max - epsilon;
-        let effective_range = effective_max - effective_min;
-
-        (0..num_samples)
-            .map(|i| {
-                // Sample x values across the histogram range, avoiding exact boundaries
-                let x = if num_samples > 1 && effective_range > 0.0 {
-                    effective_min + (i as f64 / (num_samples - 1) as f64) * effective_range
-                } else if num_samples > 1 {
-                    // Fallback if range is too small
-                    hist_min + (i as f64 / (num_samples - 1) as f64) * hist_range
-                } else {
-                    (hist_min + hist_max) / 2.0
-                };
-
-                // Calculate theoretical PDF at x value, then convert to expected count
-                // PDF gives us density (probability per unit), convert to count: PDF(x) * bin_width * n
-                let theory_count = match dist_type {
-                    DistributionType::Normal => {
-                        // Normal PDF: (1 / (σ * sqrt(2π))) * exp(-0.5 * ((x - μ) / σ)²)
-                        let z = (x - mean) / std;
-                        let pdf = (1.0 / (std * (2.0 * std::f64::consts::PI).sqrt()))
-                            * (-0.5 * z * z).exp();
-                        pdf * bin_width * n as f64
-                    }
-                    DistributionType::LogNormal => {
-                        // LogNormal PDF: show theoretical distribution over [0, ∞) even if data is negative
-                        if x > 0.0 {
-                            let (mu, sigma) = if mean > 0.0 && std >= 0.0 {
-                                let variance = std * std;
-                                let sigma_sq = (1.0 + variance / (mean * mean)).ln();
-                                let mu_val = mean.ln() - sigma_sq / 2.0;
-                                let sigma_val = sigma_sq.sqrt();
-                                (mu_val, sigma_val)
-                            } else {
-                                // Data doesn't match LogNormal: use default parameters (mu=0, sigma=1)
-                                (0.0, 1.0)
-                            };
-                            let z = (x.ln() - mu) / sigma;
-                            let pdf = (1.0 / (x * sigma * (2.0 * std::f64::consts::PI).sqrt()))
-                                * (-0.5 * z * z).exp();
-                            pdf * bin_width * n as f64
-                        } else {
-                            // LogNormal is strictly positive, return 0 for x <= 0
-                            0.0
-                        }
-                    }
-                    DistributionType::Exponential => {
-                        // Exponential PDF: show theoretical distribution over [0, ∞) even if data is negative
-                        if x >= 0.0 {
-                            let lambda = if mean > 0.0 {
-                                1.0 / mean
-                            } else {
-                                // Data doesn't match Exponential: use default lambda=1
-                                1.0
-                            };
-                            let pdf = lambda * (-lambda * x).exp();
-                            pdf * bin_width * n as f64
-                        } else {
-                            // Exponential is strictly non-negative, return 0 for x < 0
-                            0.0
-                        }
-                    }
-                    DistributionType::Uniform => {
-                        if !sorted_data.is_empty() && x >= data_min && x <= data_max {
-                            let data_range = data_max - data_min;
-                            if data_range > 0.0 {
-                                let pdf = 1.0 / data_range;
-                                pdf * bin_width * n as f64
-                            } else {
-                                0.0
-                            }
-                        } else {
-                            0.0
-                        }
-                    }
-                    DistributionType::Gamma => {
-                        // Gamma PDF: evaluate directly for smooth curve
-                        // Show theoretical distribution over its valid domain [0, ∞) even if data is negative
-                        if x > 0.0 {
-                            let variance = std * std;
-                            let (shape, scale) = if mean > 0.0 && variance > 0.0 {
-                                let s = (mean * mean) / variance;
-                                let sc = variance / mean;
-                                if s > 0.0 && sc > 0.0 {
-                                    (s, sc)
-                                } else {
-                                    // Invalid parameters: use default (exponential with scale=1)
-                                    (1.0, 1.0)
-                                }
-                            } else {
-                                // Data doesn't match Gamma (e.g., negative mean): use default parameters
-                                // This ensures we still show the theoretical distribution shape
-                                (1.0, 1.0)
-                            };
-                            let pdf = gamma_pdf(x, shape, scale);
-                            pdf * bin_width * n as f64
-                        } else {
-                            // Gamma is strictly non-negative, return 0 for x <= 0
-                            0.0
-                        }
-                    }
-                    DistributionType::Geometric => {
-                        // Geometric PMF: evaluate directly for smooth curve
-                        if x >= 0.0 && mean > 0.0 {
-                            let p_param = 1.0 / (mean + 1.0);
-                            if p_param > 0.0 && p_param < 1.0 {
-                                // Use PMF for continuous approximation
-                                let pmf = geometric_pmf(x, p_param);
-                                // Convert PMF to expected count: PMF * n
-                                // Note: For discrete distributions, we use PMF directly rather than PDF * bin_width
-                                pmf * n as f64
-                            } else {
-                                0.0
-                            }
-                        } else {
-                            0.0
-                        }
-                    }
-                    DistributionType::Weibull => {
-                        // Weibull PDF: evaluate directly for smooth curve
-                        if x > 0.0 && mean > 0.0 && std > 0.0 {
-                            // Approximate shape from CV
-                            let cv = std / mean;
-                            let shape = if cv < 1.0 { 1.0 / cv } else { 1.0 };
-                            // Scale from mean
-                            let gamma_1_over_shape = 1.0 + 1.0 / shape; // Approximation
-                            let scale = mean / gamma_1_over_shape;
-                            if shape > 0.0 && scale > 0.0 {
-                                let pdf = weibull_pdf(x, shape, scale);
-                                pdf * bin_width * n as f64
-                            } else {
-                                0.0
-                            }
-                        } else {
-                            0.0
-                        }
-                    }
-                    DistributionType::Beta => {
-                        // Beta PDF: evaluate directly for smooth curve
-                        if x > 0.0 && x < 1.0 {
-                            let variance = std * std;
-                            let mean_val = mean;
-                            if mean_val > 0.0 && mean_val < 1.0 && variance > 0.0 {
-                                let max_var = mean_val * (1.0 - mean_val);
-                                if variance < max_var {
-                                    // Estimate alpha and beta using method of moments
-                                    let sum = mean_val * (1.0 - mean_val) / variance - 1.0;
-                                    let alpha = mean_val * sum;
-                                    let beta = (1.0 - mean_val) * sum;
-                                    if alpha > 0.0 && beta > 0.0 {
-                                        let pdf = beta_pdf(x, alpha, beta);
-                                        pdf * bin_width * n as f64
-                                    } else {
-                                        0.0
-                                    }
-                                } else {
-                                    0.0
-                                }
-                            } else {
-                                0.0
-                            }
-                        } else {
-                            0.0
-                        }
-                    }
-                    DistributionType::ChiSquared => {
-                        // ChiSquared PDF: evaluate directly for smooth curve (uses gamma_pdf)
-                        if x > 0.0 {
-                            let df = mean; // For chi-squared, mean = df
-                            if df > 0.0 {
-                                let pdf = chi_squared_pdf(x, df);
-                                pdf * bin_width * n as f64
-                            } else {
-                                0.0
-                            }
-                        } else {
-                            0.0
-                        }
-                    }
-                    DistributionType::StudentsT => {
-                        // StudentsT PDF: evaluate directly for smooth curve
-                        let variance = std * std;
-                        let df = if variance > 1.0 {
-                            2.0 * variance / (variance - 1.0)
-                        } else {
-                            30.0
-                        };
-                        if df > 0.0 {
-                            // StudentsT is centered at mean, but PDF is typically for standard t (mean=0, std=1)
-                            // Adjust x to account for data mean and scale
-                            let x_standardized = if std > 0.0 { (x - mean) / std } else { 0.0 };
-                            let pdf_standard = students_t_pdf(x_standardized, df);
-                            // Convert back to data scale: PDF_standard / std
-                            let pdf = if std > 0.0 { pdf_standard / std } else { 0.0 };
-                            pdf * bin_width * n as f64
-                        } else {
-                            0.0
-                        }
-                    }
-                    DistributionType::PowerLaw => {
-                        // PowerLaw: use bin-based values from CDF calculations
-                        // Power law PDF is complex and depends on x_min parameter
-                        // For log-scale binning, find which bin x belongs to using binary search
-                        if use_log_scale && x > 0.0 {
-                            // Binary search to find the correct bin for log-scale boundaries
-                            let mut left = 0;
-                            let mut right = num_bins;
-                            while left < right {
-                                let mid = (left + right) / 2;
-                                if x < bin_boundaries[mid] {
-                                    right = mid;
-                                } else {
-                                    left = mid + 1;
-                                }
-                            }
-                            let bin_idx = if left > 0 { left - 1 } else { 0 };
-                            if bin_idx < num_bins {
-                                theory_bin_counts[bin_idx]
-                            } else {
-                                theory_bin_counts[num_bins - 1]
-                            }
-                        } else {
-                            // Linear binning fallback
-                            let bin_idx = ((x - hist_min) / bin_width).floor() as usize;
-                            if bin_idx < num_bins {
-                                theory_bin_counts[bin_idx]
-                            } else if bin_idx == num_bins {
-                                theory_bin_counts[num_bins - 1]
-                            } else {
-                                0.0
-                            }
-                        }
-                    }
-                    // REMOVED: All individual PDF implementations below caused issues with plateaus
-                    // Keeping only the bin-based approach above which uses CDF-calculated values
-                    _ => {
-                        // Fallback: Use bin-based approach for distributions without PDF implementation
-                        let bin_idx = ((x - hist_min) / bin_width).floor() as usize;
-                        let bin_idx = bin_idx.min(num_bins - 1);
-                        if bin_idx < theory_bin_counts.len() {
-                            theory_bin_counts[bin_idx]
-                        } else {
-                            0.0
-                        }
-                    }
-                };
-                let normalized_height = if global_max > 0.0 {
-                    (theory_count / global_max) * 100.0
-                } else {
-                    0.0
-                };
-                (x, normalized_height)
-            })
-            .collect()
-    } else {
-        // Fallback: use bin centers with theory_bin_counts if PDF evaluation fails
-        let theory_normalized_heights: Vec<f64> = theory_bin_counts
-            .iter()
-            .map(|&theory_count| {
-                if global_max > 0.0 {
-                    (theory_count / global_max) * 100.0
-                } else {
-                    0.0
-                }
-            })
-            .collect();
-        bin_centers
-            .iter()
-            .zip(theory_normalized_heights.iter())
-            .map(|(&bin_center, &normalized_height)| (bin_center, normalized_height))
+    // Overlay the fitted curves as dense scatter plots (dot plots) on top of the bar chart,
+    // on the bars' 0-100 scale
+    let normalize = |counts: Vec<(f64, f64)>| -> Vec<(f64, f64)> {
+        counts
+            .into_iter()
+            .map(|(x, count)| (x, count / global_max * 100.0))
             .collect()
     };
+    let theory_points = normalize(theory_counts);
+    let best_fit_points = normalize(best_fit_counts);
 
     // Create scatter plot dataset for theoretical distribution
     // Use Braille marker for dense, continuous appearance
     let marker = symbols::Marker::Braille;
 
-    let theory_dataset = Dataset::default()
-        .name("") // Empty name to prevent legend from appearing
-        .marker(marker)
-        .graph_type(GraphType::Scatter)
-        .style(Style::default().fg(theme.get("secondary_chart_series_color")))
-        .data(&theory_points);
+    let best_fit_style = Style::default().fg(theme.get("secondary_chart_series_color"));
+    let selected_style = if best_fit.is_some() {
+        Style::default().fg(theme.get("chart_series_color_2"))
+    } else {
+        best_fit_style
+    };
+    let mut datasets = Vec::new();
+    if best_fit.is_some() {
+        datasets.push(
+            Dataset::default()
+                .name("") // Empty name to prevent legend from appearing
+                .marker(marker)
+                .graph_type(GraphType::Scatter)
+                .style(best_fit_style)
+                .data(&best_fit_points),
+        );
+    }
+    // The selected distribution is drawn last so it stays on top where the curves cross
+    datasets.push(
+        Dataset::default()
+            .name("")
+            .marker(marker)
+            .graph_type(GraphType::Scatter)
+            .style(selected_style)
+            .data(&theory_points),
+    );
+
+    // Title doubles as the legend when two curves are shown
+    let title = match best_fit {
+        Some(best) => Line::from(vec![
+            Span::raw("Histogram  "),
+            Span::styled(format!("─ {best} (best fit)"), best_fit_style),
+            Span::raw("  "),
+            Span::styled(format!("─ {dist_type}"), selected_style),
+        ]),
+        None => Line::from("Histogram"),
+    };
 
     // Create Chart widget with scatter plot overlay
     // Configure axes to match BarChart coordinate system exactly:
@@ -2061,10 +3347,10 @@ fn render_distribution_histogram(config: HistogramRenderConfig, buf: &mut Buffer
         ),
     ];
 
-    let theory_chart = Chart::new(vec![theory_dataset])
+    let theory_chart = Chart::new(datasets)
         .block(
             Block::default()
-                .title("Histogram")
+                .title(title)
                 .title_alignment(ratatui::layout::Alignment::Center)
                 .padding(ratatui::widgets::Padding::new(1, 0, 0, 0)), // Extra top padding to separate title from chart
         )
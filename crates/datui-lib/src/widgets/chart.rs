@@ -6,17 +6,21 @@ use ratatui::{
     symbols,
     text::{Line, Span},
     widgets::{
-        Axis, Block, BorderType, Borders, Chart, Dataset, GraphType, List, ListItem, Paragraph,
-        StatefulWidget, Tabs, Widget,
+        Axis, Block, BorderType, Borders, Chart, Clear, Dataset, GraphType, List, ListItem,
+        Paragraph, StatefulWidget, Tabs, Widget,
     },
 };
 
 use crate::chart_data::{
-    format_axis_label, format_x_axis_label, BoxPlotData, HeatmapData, HistogramData, KdeData,
-    XAxisTemporalKind,
+    format_axis_label, BoxPlotData, CategoryBarData, HeatmapData, HistogramData, KdeData,
+    ScatterData, TrendLine, XAxisTemporalKind,
+};
+use crate::chart_modal::{
+    BandMode, BandStyle, CategoryAgg, ChartFocus, ChartKind, ChartLabels, ChartModal, ChartType,
+    Crosshair,
 };
-use crate::chart_modal::{ChartFocus, ChartKind, ChartModal, ChartType};
 use crate::config::Theme;
+use crate::heatmap;
 use crate::widgets::radio_block::RadioBlock;
 use std::collections::HashSet;
 
@@ -25,12 +29,20 @@ const LABEL_WIDTH: u16 = 20;
 const TAB_HEIGHT: u16 = 3;
 const HEATMAP_TITLE_HEIGHT: u16 = 1;
 const HEATMAP_X_LABEL_HEIGHT: u16 = 2;
+/// Widest category label beside a bar; longer ones are cut with `…`.
+const CATEGORY_LABEL_MAX: usize = 20;
+/// Shades of a scatter chart's points, from the sparsest cells to the densest.
+const SCATTER_DENSITY_LEVELS: usize = 4;
 
 pub enum ChartRenderData<'a> {
     XY {
         series: Option<&'a Vec<Vec<(f64, f64)>>>,
+        /// Legend name of each series.
+        names: &'a [String],
         x_axis_kind: XAxisTemporalKind,
         x_bounds: Option<(f64, f64)>,
+        /// Band around the first series as (x, lower, upper), in display form like `series`.
+        band: Option<&'a [(f64, f64, f64)]>,
     },
     Histogram {
         data: Option<&'a HistogramData>,
@@ -44,6 +56,12 @@ pub enum ChartRenderData<'a> {
     Heatmap {
         data: Option<&'a HeatmapData>,
     },
+    Category {
+        data: Option<&'a CategoryBarData>,
+    },
+    Scatter {
+        data: Option<&'a ScatterData>,
+    },
 }
 
 /// Renders a single axis column list (shared by X and Y). Display order: selected (remembered) items first.
@@ -246,9 +264,15 @@ pub fn render_chart_view(
                     Constraint::Length(1), // Space between X and Y groups
                     Constraint::Length(1), // Y axis label
                     Constraint::Min(4),    // Y axis box (input + list)
+                    Constraint::Length(1), // Group by
+                    Constraint::Length(1), // Right axis
                     Constraint::Length(1), // Start y axis at 0
                     Constraint::Length(1), // Log Scale
                     Constraint::Length(1), // Legend
+                    Constraint::Length(1), // Band
+                    Constraint::Length(1), // Band lower bound / std dev column
+                    Constraint::Length(1), // Band upper bound
+                    Constraint::Length(1), // Band style
                     Constraint::Length(1), // Limit Rows
                 ])
                 .split(sidebar_inner);
@@ -304,6 +328,24 @@ pub fn render_chart_view(
                 " Filter Columns ",
             );
 
+            render_number_option(
+                sidebar_content[7],
+                buf,
+                "Group by:",
+                modal.group_column.as_deref().unwrap_or("(none)"),
+                focus == ChartFocus::GroupBy,
+                theme,
+            );
+
+            render_number_option(
+                sidebar_content[8],
+                buf,
+                "Right axis:",
+                &modal.right_axis_display(),
+                focus == ChartFocus::RightAxis,
+                theme,
+            );
+
             let y0_row = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints([
@@ -311,7 +353,7 @@ pub fn render_chart_view(
                     Constraint::Length(2),
                     Constraint::Min(1),
                 ])
-                .split(sidebar_content[7]);
+                .split(sidebar_content[9]);
             let is_y0_focused = focus == ChartFocus::YStartsAtZero;
             let y0_label_style = if is_y0_focused {
                 Style::default().fg(active_color)
@@ -337,7 +379,7 @@ pub fn render_chart_view(
                     Constraint::Length(2),
                     Constraint::Min(1),
                 ])
-                .split(sidebar_content[8]);
+                .split(sidebar_content[10]);
             let is_log_focused = focus == ChartFocus::LogScale;
             let log_label_style = if is_log_focused {
                 Style::default().fg(active_color)
@@ -363,7 +405,7 @@ pub fn render_chart_view(
                     Constraint::Length(2),
                     Constraint::Min(1),
                 ])
-                .split(sidebar_content[9]);
+                .split(sidebar_content[11]);
             let is_legend_focused = focus == ChartFocus::ShowLegend;
             let legend_label_style = if is_legend_focused {
                 Style::default().fg(active_color)
@@ -382,8 +424,42 @@ pub fn render_chart_view(
             Paragraph::new(Line::from(Span::styled(legend_marker, legend_check_style)))
                 .render(legend_row[1], buf);
 
+            let band_column_label = match modal.band_mode {
+                BandMode::StdDev1 | BandMode::StdDev2 => "  Std dev column:",
+                BandMode::Off | BandMode::Bounds => "  Lower bound:",
+            };
+            let upper_display = if modal.band_mode == BandMode::Bounds {
+                modal.band_upper.as_deref().unwrap_or("(none)")
+            } else {
+                "-"
+            };
+            let band_rows = [
+                ("Band:", modal.band_mode.as_str(), ChartFocus::BandMode),
+                (
+                    band_column_label,
+                    modal.band_column.as_deref().unwrap_or("(none)"),
+                    ChartFocus::BandColumn,
+                ),
+                ("  Upper bound:", upper_display, ChartFocus::BandUpper),
+                (
+                    "  Band style:",
+                    modal.band_style.as_str(),
+                    ChartFocus::BandStyle,
+                ),
+            ];
+            for (i, (label, value, row_focus)) in band_rows.into_iter().enumerate() {
+                render_number_option(
+                    sidebar_content[12 + i],
+                    buf,
+                    label,
+                    value,
+                    focus == row_focus,
+                    theme,
+                );
+            }
+
             render_number_option(
-                sidebar_content[10],
+                sidebar_content[16],
                 buf,
                 "Limit Rows:",
                 &modal.row_limit_display(),
@@ -578,35 +654,188 @@ pub fn render_chart_view(
                 theme,
             );
         }
+        ChartKind::Category => {
+            let sidebar_content = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(1), // Category
+                    Constraint::Length(1), // Aggregate
+                    Constraint::Length(1), // Value column
+                    Constraint::Length(1), // Top
+                    Constraint::Length(1), // Limit Rows
+                    Constraint::Fill(1),
+                ])
+                .split(sidebar_inner);
+            let value_display = if modal.category_agg == CategoryAgg::Count {
+                "-"
+            } else {
+                modal.category_value.as_deref().unwrap_or("(none)")
+            };
+            let rows = [
+                (
+                    "Category:",
+                    modal
+                        .category_column
+                        .as_deref()
+                        .unwrap_or("(none)")
+                        .to_string(),
+                    ChartFocus::CategoryColumn,
+                ),
+                (
+                    "Aggregate:",
+                    modal.category_agg.as_str().to_string(),
+                    ChartFocus::CategoryAgg,
+                ),
+                (
+                    "Value column:",
+                    value_display.to_string(),
+                    ChartFocus::CategoryValue,
+                ),
+                (
+                    "Top:",
+                    modal.category_top.to_string(),
+                    ChartFocus::CategoryTop,
+                ),
+                (
+                    "Limit Rows:",
+                    modal.row_limit_display(),
+                    ChartFocus::LimitRows,
+                ),
+            ];
+            for (i, (label, value, row_focus)) in rows.into_iter().enumerate() {
+                render_number_option(
+                    sidebar_content[i],
+                    buf,
+                    label,
+                    &value,
+                    focus == row_focus,
+                    theme,
+                );
+            }
+        }
+        ChartKind::Scatter => {
+            let sidebar_content = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(1), // X column
+                    Constraint::Length(1), // Y column
+                    Constraint::Length(1), // Trend line
+                    Constraint::Length(1), // Limit Rows
+                    Constraint::Fill(1),
+                ])
+                .split(sidebar_inner);
+            let rows = [
+                (
+                    "X axis:",
+                    modal.scatter_x_column.as_deref().unwrap_or("(none)"),
+                    ChartFocus::ScatterX,
+                ),
+                (
+                    "Y axis:",
+                    modal.scatter_y_column.as_deref().unwrap_or("(none)"),
+                    ChartFocus::ScatterY,
+                ),
+                (
+                    "Trend line:",
+                    if modal.scatter_trend { "☑" } else { "☐" },
+                    ChartFocus::ScatterTrend,
+                ),
+                (
+                    "Limit Rows:",
+                    &modal.row_limit_display(),
+                    ChartFocus::LimitRows,
+                ),
+            ];
+            for (i, (label, value, row_focus)) in rows.into_iter().enumerate() {
+                render_number_option(
+                    sidebar_content[i],
+                    buf,
+                    label,
+                    value,
+                    focus == row_focus,
+                    theme,
+                );
+            }
+        }
     }
 
-    let chart_inner = main_layout[1];
+    let mut chart_inner = main_layout[1];
+    if let Some(title) = modal.labels.title() {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Fill(1)])
+            .split(chart_inner);
+        Paragraph::new(title)
+            .style(
+                Style::default()
+                    .fg(text_primary)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .centered()
+            .render(rows[0], buf);
+        chart_inner = rows[1];
+    }
+    if matches!(modal.chart_kind, ChartKind::XY | ChartKind::Scatter)
+        && (focus == ChartFocus::Plot || modal.x_window.is_some())
+    {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Fill(1)])
+            .split(chart_inner);
+        let mut hint = format!("Zoom ×{}", modal.zoom_factor());
+        let style = if focus == ChartFocus::Plot {
+            if modal.crosshair.is_some() {
+                hint.push_str("   ←/→ point   ↑/↓ series   Enter hide crosshair");
+            } else {
+                hint.push_str("   ←/→ pan   +/- zoom   0 reset   Enter crosshair");
+            }
+            Style::default().fg(active_color)
+        } else {
+            Style::default().fg(text_secondary)
+        };
+        Paragraph::new(hint)
+            .style(style)
+            .right_aligned()
+            .render(rows[0], buf);
+        chart_inner = rows[1];
+    }
+    let labels = &modal.labels;
     match render_data {
         ChartRenderData::XY {
             series,
+            names,
             x_axis_kind,
             x_bounds,
+            band,
         } => render_xy_chart(
             chart_inner,
             buf,
             modal,
             theme,
             series,
+            names,
             x_axis_kind,
             x_bounds,
+            band,
             text_secondary,
         ),
         ChartRenderData::Histogram { data } => {
-            render_histogram_chart(chart_inner, buf, theme, data, text_secondary)
+            render_histogram_chart(chart_inner, buf, theme, labels, data, text_secondary)
         }
         ChartRenderData::BoxPlot { data } => {
-            render_box_plot_chart(chart_inner, buf, theme, data, text_secondary)
+            render_box_plot_chart(chart_inner, buf, theme, labels, data, text_secondary)
         }
         ChartRenderData::Kde { data } => {
             render_kde_chart(chart_inner, buf, modal, theme, data, text_secondary)
         }
         ChartRenderData::Heatmap { data } => {
-            render_heatmap_chart(chart_inner, buf, theme, data, text_secondary)
+            render_heatmap_chart(chart_inner, buf, theme, labels, data, text_secondary)
+        }
+        ChartRenderData::Category { data } => {
+            render_category_chart(chart_inner, buf, modal, theme, data, text_secondary)
+        }
+        ChartRenderData::Scatter { data } => {
+            render_scatter_chart(chart_inner, buf, modal, theme, data, text_secondary)
         }
     }
 }
@@ -618,8 +847,10 @@ fn render_xy_chart(
     modal: &ChartModal,
     theme: &Theme,
     chart_data: Option<&Vec<Vec<(f64, f64)>>>,
+    names: &[String],
     x_axis_kind: XAxisTemporalKind,
     x_bounds: Option<(f64, f64)>,
+    band: Option<&[(f64, f64, f64)]>,
     text_secondary: ratatui::style::Color,
 ) {
     let chart_type = modal.chart_type;
@@ -642,27 +873,28 @@ fn render_xy_chart(
         const PLACEHOLDER_MIN: f64 = 0.0;
         const PLACEHOLDER_MAX: f64 = 1.0;
         let (x_min, x_max) = x_bounds.unwrap_or((PLACEHOLDER_MIN, PLACEHOLDER_MAX));
-        let format_x = |v: f64| format_x_axis_label(v, x_axis_kind);
+        let labels = &modal.labels;
+        let format_x = |v: f64| labels.format_x(v, x_axis_kind);
         let x_labels = vec![
             Span::styled(format_x(x_min), axis_label_style),
             Span::styled(format_x((x_min + x_max) / 2.0), axis_label_style),
             Span::styled(format_x(x_max), axis_label_style),
         ];
         let y_labels = vec![
-            Span::styled(format_axis_label(PLACEHOLDER_MIN), axis_label_style),
+            Span::styled(labels.format_y(PLACEHOLDER_MIN), axis_label_style),
             Span::styled(
-                format_axis_label((PLACEHOLDER_MIN + PLACEHOLDER_MAX) / 2.0),
+                labels.format_y((PLACEHOLDER_MIN + PLACEHOLDER_MAX) / 2.0),
                 axis_label_style,
             ),
-            Span::styled(format_axis_label(PLACEHOLDER_MAX), axis_label_style),
+            Span::styled(labels.format_y(PLACEHOLDER_MAX), axis_label_style),
         ];
         let x_axis = Axis::default()
-            .title(x_name)
+            .title(labels.x_title(x_name))
             .bounds([x_min, x_max])
             .style(Style::default().fg(theme.get("text_primary")))
             .labels(x_labels);
         let y_axis = Axis::default()
-            .title(y_names)
+            .title(labels.y_title(&y_names))
             .bounds([PLACEHOLDER_MIN, PLACEHOLDER_MAX])
             .style(Style::default().fg(theme.get("text_primary")))
             .labels(y_labels);
@@ -688,6 +920,34 @@ fn render_xy_chart(
 
     if has_data {
         if let Some(data) = chart_data {
+            // Zoomed in: only the points in the visible x window are drawn, and y fits them.
+            let x_window = modal.x_window.map(|_| {
+                let (min, max) = data
+                    .iter()
+                    .flatten()
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), &(x, _)| {
+                        (a.min(x), b.max(x))
+                    });
+                modal.visible_x_range(min, max)
+            });
+            let in_window = |x: f64| x_window.is_none_or(|(lo, hi)| x >= lo && x <= hi);
+            let zoomed: Vec<Vec<(f64, f64)>>;
+            let zoomed_band: Vec<(f64, f64, f64)>;
+            let (data, band) = if x_window.is_some() {
+                zoomed = data
+                    .iter()
+                    .map(|s| s.iter().copied().filter(|p| in_window(p.0)).collect())
+                    .collect();
+                zoomed_band = band
+                    .unwrap_or_default()
+                    .iter()
+                    .copied()
+                    .filter(|b| in_window(b.0))
+                    .collect();
+                (&zoomed, band.map(|_| zoomed_band.as_slice()))
+            } else {
+                (data, band)
+            };
             let y_columns = modal.effective_y_columns();
             let graph_type = match chart_type {
                 ChartType::Line => GraphType::Line,
@@ -710,61 +970,43 @@ fn render_xy_chart(
                 "chart_series_color_7",
             ];
 
-            let mut all_x_min = f64::INFINITY;
-            let mut all_x_max = f64::NEG_INFINITY;
-            let mut all_y_min = f64::INFINITY;
-            let mut all_y_max = f64::NEG_INFINITY;
-
-            // Data is already in display form (log-scaled when log_scale) from cache; use as-is.
-            let names_and_points: Vec<(&str, &[(f64, f64)])> = data
-                .iter()
-                .zip(y_columns.iter())
-                .filter_map(|(points, name)| {
-                    if points.is_empty() {
-                        return None;
-                    }
-                    Some((name.as_str(), points.as_slice()))
-                })
-                .collect();
-
-            for (_, points) in &names_and_points {
-                let (x_min, x_max) = points
-                    .iter()
-                    .map(|&(x, _)| x)
-                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), x| {
-                        (a.min(x), b.max(x))
-                    });
-                let (y_min, y_max) = points
-                    .iter()
-                    .map(|&(_, y)| y)
-                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), y| {
-                        (a.min(y), b.max(y))
-                    });
-                all_x_min = all_x_min.min(x_min);
-                all_x_max = all_x_max.max(x_max);
-                all_y_min = all_y_min.min(y_min);
-                all_y_max = all_y_max.max(y_max);
+            // Data is already in display form (log-scaled when log_scale) from cache. The last
+            // `right` series belong to the right-hand axis: they get their own y range and are
+            // drawn scaled onto the left one.
+            let right = modal.right_axis_count(data.len());
+            let split = data.len() - right;
+            let extent = |series: &[Vec<(f64, f64)>]| {
+                series.iter().flatten().fold(
+                    [
+                        f64::INFINITY,
+                        f64::NEG_INFINITY,
+                        f64::INFINITY,
+                        f64::NEG_INFINITY,
+                    ],
+                    |[x0, x1, y0, y1], &(x, y)| [x0.min(x), x1.max(x), y0.min(y), y1.max(y)],
+                )
+            };
+            let [mut all_x_min, mut all_x_max, mut all_y_min, mut all_y_max] =
+                extent(&data[..split]);
+            let [right_x_min, right_x_max, right_y_min, right_y_max] = extent(&data[split..]);
+            all_x_min = all_x_min.min(right_x_min);
+            all_x_max = all_x_max.max(right_x_max);
+
+            // The band belongs to the first series, so it is only drawn with it.
+            let band = band.filter(|b| {
+                !b.is_empty()
+                    && split > 0
+                    && data.first().is_some_and(|points| !points.is_empty())
+                    && names.first() == y_columns.first()
+            });
+            for &(x, lower, upper) in band.unwrap_or_default() {
+                all_x_min = all_x_min.min(x);
+                all_x_max = all_x_max.max(x);
+                all_y_min = all_y_min.min(lower);
+                all_y_max = all_y_max.max(upper);
             }
 
-            let datasets: Vec<Dataset> = names_and_points
-                .iter()
-                .enumerate()
-                .map(|(i, (name, points))| {
-                    let color_key = series_colors
-                        .get(i)
-                        .copied()
-                        .unwrap_or("primary_chart_series_color");
-                    let style = Style::default().fg(theme.get(color_key));
-                    Dataset::default()
-                        .name(*name)
-                        .marker(marker)
-                        .graph_type(graph_type)
-                        .style(style)
-                        .data(points)
-                })
-                .collect();
-
-            if datasets.is_empty() {
+            if data.iter().all(|points| points.is_empty()) {
                 Paragraph::new("No valid data points")
                     .style(Style::default().fg(text_secondary))
                     .centered()
@@ -772,31 +1014,57 @@ fn render_xy_chart(
                 return;
             }
 
-            let y_min_bounds = if chart_type == ChartType::Bar {
-                0.0_f64.min(all_y_min)
-            } else if y_starts_at_zero {
-                0.0
-            } else {
-                all_y_min
+            let fit_y = |min: f64, max: f64| {
+                let lo = if chart_type == ChartType::Bar {
+                    0.0_f64.min(min)
+                } else if y_starts_at_zero {
+                    0.0
+                } else {
+                    min
+                };
+                [lo, if max > lo { max } else { lo + 1.0 }]
             };
-            let y_max_bounds = if all_y_max > y_min_bounds {
-                all_y_max
-            } else {
-                y_min_bounds + 1.0
+            let right_bounds =
+                (right_y_min <= right_y_max).then(|| fit_y(right_y_min, right_y_max));
+            let [y_min_bounds, y_max_bounds] = match right_bounds {
+                // Only right-axis series have points: the left axis takes their range.
+                Some(bounds) if all_y_min > all_y_max => bounds,
+                _ => fit_y(all_y_min, all_y_max),
             };
-            let x_min_bounds = if all_x_max > all_x_min {
-                all_x_min
-            } else {
-                all_x_min - 0.5
+            let (x_min_bounds, x_max_bounds) = match x_window {
+                Some(window) => window,
+                None if all_x_max > all_x_min => (all_x_min, all_x_max),
+                None => (all_x_min - 0.5, all_x_min + 0.5),
             };
-            let x_max_bounds = if all_x_max > all_x_min {
-                all_x_max
-            } else {
-                all_x_min + 0.5
+            let to_left = |y: f64| match right_bounds {
+                Some([lo, hi]) => {
+                    y_min_bounds + (y - lo) / (hi - lo) * (y_max_bounds - y_min_bounds)
+                }
+                None => y,
             };
+            let scaled: Vec<Vec<(f64, f64)>> = data[split..]
+                .iter()
+                .map(|points| points.iter().map(|&(x, y)| (x, to_left(y))).collect())
+                .collect();
+            let names_and_points: Vec<(String, &[(f64, f64)])> = data[..split]
+                .iter()
+                .chain(&scaled)
+                .zip(names.iter())
+                .enumerate()
+                .filter(|(_, (points, _))| !points.is_empty())
+                .map(|(i, (points, name))| {
+                    let name = if i < split {
+                        name.clone()
+                    } else {
+                        format!("{name} (right)")
+                    };
+                    (name, points.as_slice())
+                })
+                .collect();
 
             let axis_label_style = Style::default().fg(theme.get("text_primary"));
-            let format_x = |v: f64| format_x_axis_label(v, x_axis_kind);
+            let labels = &modal.labels;
+            let format_x = |v: f64| labels.format_x(v, x_axis_kind);
             let x_labels = vec![
                 Span::styled(format_x(x_min_bounds), axis_label_style),
                 Span::styled(
@@ -807,7 +1075,7 @@ fn render_xy_chart(
             ];
             let format_y_label = |log_v: f64| {
                 let v = if log_scale { log_v.exp_m1() } else { log_v };
-                format_axis_label(v)
+                labels.format_y(v)
             };
             let y_labels = vec![
                 Span::styled(format_y_label(y_min_bounds), axis_label_style),
@@ -817,9 +1085,32 @@ fn render_xy_chart(
                 ),
                 Span::styled(format_y_label(y_max_bounds), axis_label_style),
             ];
+            let y_label_width = y_labels.iter().map(|l| l.width() as u16).max().unwrap_or(0);
+            let right_labels = right_bounds.map(|[lo, hi]| {
+                [
+                    format_y_label(hi),
+                    format_y_label((lo + hi) / 2.0),
+                    format_y_label(lo),
+                ]
+            });
+            let (area, right_axis_area) = match &right_labels {
+                Some(right_labels) => {
+                    let width = right_labels.iter().map(|l| l.chars().count()).max();
+                    let [chart, axis] = Layout::horizontal([
+                        Constraint::Fill(1),
+                        Constraint::Length(width.unwrap_or(0) as u16 + 1),
+                    ])
+                    .areas(area);
+                    (chart, Some(axis))
+                }
+                None => (area, None),
+            };
 
-            let x_axis_title = modal.effective_x_column().map(|s| s.as_str()).unwrap_or("");
-            let y_axis_title = y_columns.join(", ");
+            let x_axis_title =
+                labels.x_title(modal.effective_x_column().map(|s| s.as_str()).unwrap_or(""));
+            let left_columns = &y_columns[..y_columns.len().saturating_sub(right)];
+            let y_names = modal.y_axis_name(left_columns);
+            let y_axis_title = labels.y_title(&y_names);
             let x_axis = Axis::default()
                 .title(x_axis_title)
                 .bounds([x_min_bounds, x_max_bounds])
@@ -831,6 +1122,69 @@ fn render_xy_chart(
                 .style(Style::default().fg(theme.get("text_primary")))
                 .labels(y_labels);
 
+            // Drawn first so the series lines stay on top of the band.
+            let band_dots = band
+                .map(|b| {
+                    band_points(
+                        b,
+                        modal.band_style,
+                        [x_min_bounds, x_max_bounds],
+                        [y_min_bounds, y_max_bounds],
+                        area,
+                    )
+                })
+                .unwrap_or_default();
+            let mut datasets: Vec<Dataset> = Vec::with_capacity(names_and_points.len() + 1);
+            if !band_dots.is_empty() {
+                datasets.push(
+                    Dataset::default()
+                        .marker(symbols::Marker::Braille)
+                        .graph_type(GraphType::Scatter)
+                        .style(
+                            Style::default()
+                                .fg(theme.get(series_colors[0]))
+                                .add_modifier(Modifier::DIM),
+                        )
+                        .data(&band_dots),
+                );
+            }
+            // The crosshair snaps to the nearest visible point of its series; its lines go under
+            // the series and its point over them.
+            let crosshair = modal.crosshair.and_then(|c| {
+                let point = Crosshair::snap(data.get(c.series)?, c.point)?;
+                Some((c.series, point))
+            });
+            let crosshair_parts = crosshair
+                .map(|(series, (x, y))| {
+                    let y = if series < split { y } else { to_left(y) };
+                    crosshair_lines(
+                        (x, y),
+                        [x_min_bounds, x_max_bounds],
+                        [y_min_bounds, y_max_bounds],
+                    )
+                })
+                .unwrap_or_default();
+            datasets.extend(crosshair_datasets(&crosshair_parts[..2], theme, false));
+            datasets.extend(
+                names_and_points
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (name, points))| {
+                        let color_key = series_colors
+                            .get(i)
+                            .copied()
+                            .unwrap_or("primary_chart_series_color");
+                        let style = Style::default().fg(theme.get(color_key));
+                        Dataset::default()
+                            .name(name.as_str())
+                            .marker(marker)
+                            .graph_type(graph_type)
+                            .style(style)
+                            .data(points)
+                    }),
+            );
+            datasets.extend(crosshair_datasets(&crosshair_parts[2..], theme, true));
+
             let mut chart = Chart::new(datasets).x_axis(x_axis).y_axis(y_axis);
             if show_legend {
                 chart = chart.legend_position(Some(ratatui::widgets::LegendPosition::TopRight));
@@ -838,6 +1192,25 @@ fn render_xy_chart(
                 chart = chart.legend_position(None);
             }
             chart.render(area, buf);
+            if let (Some(axis_area), Some(right_labels)) = (right_axis_area, right_labels) {
+                render_right_axis(axis_area, buf, right_labels, axis_label_style);
+            }
+            if let Some((series, (x, y))) = crosshair {
+                render_crosshair_tooltip(
+                    area,
+                    buf,
+                    theme,
+                    (x, if series < split { y } else { to_left(y) }),
+                    [x_min_bounds, x_max_bounds],
+                    [y_min_bounds, y_max_bounds],
+                    y_label_width,
+                    [
+                        names.get(series).cloned().unwrap_or_default(),
+                        format!("x: {}", format_x(x)),
+                        format!("y: {}", format_y_label(y)),
+                    ],
+                );
+            }
         }
     } else {
         Paragraph::new("Select X and Y columns in sidebar — Tab to change focus")
@@ -847,10 +1220,111 @@ fn render_xy_chart(
     }
 }
 
+/// Right-hand y axis of an XY chart drawn in `area`: the axis line down the left column, level
+/// with the chart's graph rows and x axis line, and `labels` (top, middle, bottom) beside it.
+fn render_right_axis(
+    area: Rect,
+    buf: &mut ratatui::buffer::Buffer,
+    labels: [String; 3],
+    style: Style,
+) {
+    // The chart keeps its last two rows for the x axis line and labels.
+    if area.height < 3 || area.width < 2 {
+        return;
+    }
+    let axis_row = area.bottom() - 2;
+    for y in area.y..axis_row {
+        buf[(area.x, y)]
+            .set_symbol(symbols::line::VERTICAL)
+            .set_style(style);
+    }
+    buf[(area.x, axis_row)]
+        .set_symbol(symbols::line::BOTTOM_RIGHT)
+        .set_style(style);
+    let rows = [area.y, area.y + (axis_row - 1 - area.y) / 2, axis_row - 1];
+    for (label, y) in labels.into_iter().zip(rows) {
+        buf.set_stringn(area.x + 1, y, label, usize::from(area.width - 1), style);
+    }
+}
+
+/// Points that draw a band on a braille chart of `area`: a stippled fill between the
+/// interpolated bounds (Shaded) or a capped vertical bar at each point (Error bars).
+/// `band` holds (x, lower, upper) in chart coordinates.
+fn band_points(
+    band: &[(f64, f64, f64)],
+    style: BandStyle,
+    x_bounds: [f64; 2],
+    y_bounds: [f64; 2],
+    area: Rect,
+) -> Vec<(f64, f64)> {
+    // Braille cells are 2 dots wide and 4 tall.
+    let dx = (x_bounds[1] - x_bounds[0]) / f64::from(area.width.max(1) * 2);
+    let dy = (y_bounds[1] - y_bounds[0]) / f64::from(area.height.max(1) * 4);
+    if band.is_empty() || !(dx > 0.0 && dy > 0.0) {
+        return Vec::new();
+    }
+    let mut out = Vec::new();
+    let mut vertical = |x: f64, lower: f64, upper: f64, step: f64| {
+        let lower = lower.max(y_bounds[0]);
+        let upper = upper.min(y_bounds[1]);
+        let mut y = lower;
+        while y < upper {
+            out.push((x, y));
+            y += step;
+        }
+        if upper >= lower {
+            out.push((x, upper));
+        }
+    };
+    match style {
+        BandStyle::Shaded => {
+            let mut sorted = band.to_vec();
+            sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let mut j = 0;
+            let mut x = x_bounds[0];
+            while x <= x_bounds[1] {
+                while j + 1 < sorted.len() && sorted[j + 1].0 < x {
+                    j += 1;
+                }
+                let (x0, l0, u0) = sorted[j];
+                let bounds = match sorted.get(j + 1) {
+                    _ if x < x0 => None,
+                    Some(&(x1, l1, u1)) if x1 > x0 => {
+                        let t = ((x - x0) / (x1 - x0)).clamp(0.0, 1.0);
+                        Some((l0 + t * (l1 - l0), u0 + t * (u1 - u0)))
+                    }
+                    Some(_) => Some((l0, u0)),
+                    None => (x - x0 <= dx).then_some((l0, u0)),
+                };
+                if let Some((lower, upper)) = bounds {
+                    // Every other dot row keeps the fill lighter than the series line.
+                    vertical(x, lower, upper, 2.0 * dy);
+                }
+                x += dx;
+            }
+        }
+        BandStyle::ErrorBars => {
+            let columns = usize::from(area.width.max(1)) * 2;
+            let stride = band.len().div_ceil(columns).max(1);
+            for &(x, lower, upper) in band.iter().step_by(stride) {
+                vertical(x, lower, upper, dy);
+                for cap in [lower, upper] {
+                    if cap >= y_bounds[0] && cap <= y_bounds[1] {
+                        vertical(x - dx, cap, cap, dy);
+                        vertical(x + dx, cap, cap, dy);
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
 fn render_histogram_chart(
     area: Rect,
     buf: &mut ratatui::buffer::Buffer,
     theme: &Theme,
+    labels: &ChartLabels,
     data: Option<&HistogramData>,
     text_secondary: ratatui::style::Color,
 ) {
@@ -886,30 +1360,31 @@ fn render_histogram_chart(
     };
 
     let axis_label_style = Style::default().fg(theme.get("text_primary"));
+    let format_x = |v: f64| labels.format_x(v, XAxisTemporalKind::Numeric);
     let x_labels = vec![
-        Span::styled(format_axis_label(x_min_bounds), axis_label_style),
+        Span::styled(format_x(x_min_bounds), axis_label_style),
         Span::styled(
-            format_axis_label((x_min_bounds + x_max_bounds) / 2.0),
+            format_x((x_min_bounds + x_max_bounds) / 2.0),
             axis_label_style,
         ),
-        Span::styled(format_axis_label(x_max_bounds), axis_label_style),
+        Span::styled(format_x(x_max_bounds), axis_label_style),
     ];
     let y_labels = vec![
-        Span::styled(format_axis_label(y_min_bounds), axis_label_style),
+        Span::styled(labels.format_y(y_min_bounds), axis_label_style),
         Span::styled(
-            format_axis_label((y_min_bounds + y_max_bounds) / 2.0),
+            labels.format_y((y_min_bounds + y_max_bounds) / 2.0),
             axis_label_style,
         ),
-        Span::styled(format_axis_label(y_max_bounds), axis_label_style),
+        Span::styled(labels.format_y(y_max_bounds), axis_label_style),
     ];
 
     let x_axis = Axis::default()
-        .title(data.column.as_str())
+        .title(labels.x_title(&data.column))
         .bounds([x_min_bounds, x_max_bounds])
         .style(Style::default().fg(theme.get("text_primary")))
         .labels(x_labels);
     let y_axis = Axis::default()
-        .title("Count")
+        .title(labels.y_title("Count"))
         .bounds([y_min_bounds, y_max_bounds])
         .style(Style::default().fg(theme.get("text_primary")))
         .labels(y_labels);
@@ -980,39 +1455,41 @@ fn render_kde_chart(
         })
         .collect();
 
+    let labels = &modal.labels;
+    let format_x = |v: f64| labels.format_x(v, XAxisTemporalKind::Numeric);
     let x_axis = Axis::default()
-        .title("Value")
+        .title(labels.x_title("Value"))
         .bounds([data.x_min, data.x_max])
         .style(Style::default().fg(theme.get("text_primary")))
         .labels(vec![
             Span::styled(
-                format_axis_label(data.x_min),
+                format_x(data.x_min),
                 Style::default().fg(theme.get("text_primary")),
             ),
             Span::styled(
-                format_axis_label((data.x_min + data.x_max) / 2.0),
+                format_x((data.x_min + data.x_max) / 2.0),
                 Style::default().fg(theme.get("text_primary")),
             ),
             Span::styled(
-                format_axis_label(data.x_max),
+                format_x(data.x_max),
                 Style::default().fg(theme.get("text_primary")),
             ),
         ]);
     let y_axis = Axis::default()
-        .title("Density")
+        .title(labels.y_title("Density"))
         .bounds([0.0, data.y_max])
         .style(Style::default().fg(theme.get("text_primary")))
         .labels(vec![
             Span::styled(
-                format_axis_label(0.0),
+                labels.format_y(0.0),
                 Style::default().fg(theme.get("text_primary")),
             ),
             Span::styled(
-                format_axis_label(data.y_max / 2.0),
+                labels.format_y(data.y_max / 2.0),
                 Style::default().fg(theme.get("text_primary")),
             ),
             Span::styled(
-                format_axis_label(data.y_max),
+                labels.format_y(data.y_max),
                 Style::default().fg(theme.get("text_primary")),
             ),
         ]);
@@ -1030,6 +1507,7 @@ fn render_box_plot_chart(
     area: Rect,
     buf: &mut ratatui::buffer::Buffer,
     theme: &Theme,
+    labels: &ChartLabels,
     data: Option<&BoxPlotData>,
     text_secondary: ratatui::style::Color,
 ) {
@@ -1112,21 +1590,21 @@ fn render_box_plot_chart(
         .map(|s| Span::styled(s.name.as_str(), axis_label_style))
         .collect();
     let y_labels = vec![
-        Span::styled(format_axis_label(data.y_min), axis_label_style),
+        Span::styled(labels.format_y(data.y_min), axis_label_style),
         Span::styled(
-            format_axis_label((data.y_min + data.y_max) / 2.0),
+            labels.format_y((data.y_min + data.y_max) / 2.0),
             axis_label_style,
         ),
-        Span::styled(format_axis_label(data.y_max), axis_label_style),
+        Span::styled(labels.format_y(data.y_max), axis_label_style),
     ];
 
     let x_axis = Axis::default()
-        .title("Columns")
+        .title(labels.x_title("Columns"))
         .bounds([x_min_bounds, x_max_bounds])
         .style(Style::default().fg(theme.get("text_primary")))
         .labels(x_labels);
     let y_axis = Axis::default()
-        .title("Value")
+        .title(labels.y_title("Value"))
         .bounds([data.y_min, data.y_max])
         .style(Style::default().fg(theme.get("text_primary")))
         .labels(y_labels);
@@ -1141,6 +1619,7 @@ fn render_heatmap_chart(
     area: Rect,
     buf: &mut ratatui::buffer::Buffer,
     theme: &Theme,
+    labels: &ChartLabels,
     data: Option<&HeatmapData>,
     text_secondary: ratatui::style::Color,
 ) {
@@ -1167,15 +1646,19 @@ fn render_heatmap_chart(
             Constraint::Length(HEATMAP_X_LABEL_HEIGHT),
         ])
         .split(area);
-    let title = format!("{} vs {}", data.x_column, data.y_column);
+    let title = format!(
+        "{} vs {}",
+        labels.x_title(&data.x_column),
+        labels.y_title(&data.y_column)
+    );
     Paragraph::new(title)
         .style(Style::default().fg(theme.get("text_primary")))
         .render(layout[0], buf);
 
     let y_labels = [
-        format_axis_label(data.y_max),
-        format_axis_label((data.y_min + data.y_max) / 2.0),
-        format_axis_label(data.y_min),
+        labels.format_y(data.y_max),
+        labels.format_y((data.y_min + data.y_max) / 2.0),
+        labels.format_y(data.y_min),
     ];
     let y_label_width = y_labels.iter().map(|s| s.len()).max().unwrap_or(1) as u16;
     let y_label_width = y_label_width.clamp(4, 12);
@@ -1223,10 +1706,11 @@ fn render_heatmap_chart(
         }
     }
 
+    let format_x = |v: f64| labels.format_x(v, XAxisTemporalKind::Numeric);
     let x_labels = [
-        format_axis_label(data.x_min),
-        format_axis_label((data.x_min + data.x_max) / 2.0),
-        format_axis_label(data.x_max),
+        format_x(data.x_min),
+        format_x((data.x_min + data.x_max) / 2.0),
+        format_x(data.x_max),
     ];
     let x_label_area = layout[2];
     let mid_x = x_label_area.x + x_label_area.width / 2;
@@ -1244,8 +1728,8 @@ fn render_heatmap_chart(
         &x_labels[2],
         label_style,
     );
-    let x_title = format!("X: {}", data.x_column);
-    let y_title = format!("Y: {}", data.y_column);
+    let x_title = format!("X: {}", labels.x_title(&data.x_column));
+    let y_title = format!("Y: {}", labels.y_title(&data.y_column));
     if x_label_area.height > 1 {
         buf.set_string(x_label_area.x, x_label_area.y + 1, &x_title, label_style);
         buf.set_string(
@@ -1256,3 +1740,432 @@ fn render_heatmap_chart(
         );
     }
 }
+
+/// Category bars drawn across, one row per category from the largest value down, with the
+/// value after each. Bars grow from 0, so negative sums and means extend to the left.
+fn render_category_chart(
+    area: Rect,
+    buf: &mut ratatui::buffer::Buffer,
+    modal: &ChartModal,
+    theme: &Theme,
+    data: Option<&CategoryBarData>,
+    text_secondary: ratatui::style::Color,
+) {
+    let Some(data) = data else {
+        let hint = if modal.category_column.is_none() {
+            "Select a category column"
+        } else {
+            "Select a value column to aggregate"
+        };
+        Paragraph::new(hint)
+            .style(Style::default().fg(text_secondary))
+            .centered()
+            .render(area, buf);
+        return;
+    };
+    if data.bars.is_empty() {
+        Paragraph::new("No data for category chart")
+            .style(Style::default().fg(text_secondary))
+            .centered()
+            .render(area, buf);
+        return;
+    }
+
+    let labels = &modal.labels;
+    let label_style = Style::default().fg(theme.get("text_primary"));
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(HEATMAP_TITLE_HEIGHT), Constraint::Min(1)])
+        .split(area);
+    let mut title = format!(
+        "{} by {}",
+        labels.y_title(&modal.category_axis_name()),
+        labels.x_title(&data.column)
+    );
+    if data.categories > data.bars.len() {
+        title.push_str(&format!(
+            " (top {} of {} categories)",
+            data.bars.len(),
+            data.categories
+        ));
+    }
+    Paragraph::new(title)
+        .style(label_style)
+        .render(layout[0], buf);
+
+    let names: Vec<String> = data
+        .bars
+        .iter()
+        .map(|(name, _)| {
+            if name.chars().count() > CATEGORY_LABEL_MAX {
+                let mut cut: String = name.chars().take(CATEGORY_LABEL_MAX - 1).collect();
+                cut.push('…');
+                cut
+            } else {
+                name.clone()
+            }
+        })
+        .collect();
+    let values: Vec<String> = data.bars.iter().map(|(_, v)| labels.format_y(*v)).collect();
+    let name_width = names.iter().map(|n| n.chars().count()).max().unwrap_or(1) as u16;
+    let value_width = values.iter().map(|v| v.chars().count()).max().unwrap_or(1) as u16;
+    let plot = layout[1];
+    let bar_left = plot.x + name_width + 1;
+    let bar_width = plot.width.saturating_sub(name_width + value_width + 2);
+    if bar_width == 0 || plot.height == 0 {
+        return;
+    }
+    // A blank row between bars when they all fit that way
+    let step = if data.bars.len() * 2 <= plot.height as usize {
+        2
+    } else {
+        1
+    };
+    let span = data.y_max - data.y_min;
+    let to_col = |v: f64| ((v - data.y_min) / span * bar_width as f64).round() as u16;
+    let zero = to_col(0.0);
+    let bar_style = Style::default().fg(theme.get("primary_chart_series_color"));
+    for (i, ((name, value), (_, v))) in names.iter().zip(&values).zip(&data.bars).enumerate() {
+        let y = plot.y + (i * step) as u16;
+        if y >= plot.y + plot.height {
+            break;
+        }
+        buf.set_string(plot.x, y, name, label_style);
+        let end = to_col(*v);
+        let (from, to) = if end < zero { (end, zero) } else { (zero, end) };
+        // Every bar shows at least a sliver, so small values aren't mistaken for missing ones
+        let to = to.max(from + 1).min(bar_width);
+        for col in from..to {
+            buf[(bar_left + col, y)]
+                .set_symbol(symbols::block::FULL)
+                .set_style(bar_style);
+        }
+        buf.set_string(bar_left + bar_width + 1, y, value, label_style);
+    }
+}
+
+fn render_scatter_chart(
+    area: Rect,
+    buf: &mut ratatui::buffer::Buffer,
+    modal: &ChartModal,
+    theme: &Theme,
+    data: Option<&ScatterData>,
+    text_secondary: ratatui::style::Color,
+) {
+    let Some(data) = data else {
+        Paragraph::new("Select X and Y columns in sidebar — Tab to change focus")
+            .style(Style::default().fg(text_secondary))
+            .centered()
+            .render(area, buf);
+        return;
+    };
+    if data.points.is_empty() {
+        Paragraph::new("No valid data points")
+            .style(Style::default().fg(text_secondary))
+            .centered()
+            .render(area, buf);
+        return;
+    }
+
+    let (x_min, x_max) = modal.visible_x_range(data.x_min, data.x_max);
+    let zoomed: Vec<(f64, f64)>;
+    let (points, [y_min, y_max]) = if modal.x_window.is_some() {
+        zoomed = data
+            .points
+            .iter()
+            .copied()
+            .filter(|&(x, _)| x >= x_min && x <= x_max)
+            .collect();
+        let (lo, hi) = zoomed
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), &(_, y)| {
+                (a.min(y), b.max(y))
+            });
+        let y_bounds = if zoomed.is_empty() {
+            [data.y_min, data.y_max]
+        } else if hi > lo {
+            [lo, hi]
+        } else {
+            [lo - 0.5, lo + 0.5]
+        };
+        (zoomed.as_slice(), y_bounds)
+    } else {
+        (data.points.as_slice(), [data.y_min, data.y_max])
+    };
+    let x_bounds = [x_min, x_max];
+    let y_bounds = [y_min, y_max];
+    let layers = density_layers(points, x_bounds, y_bounds, area);
+    let sparse = theme.get("dimmed");
+    let dense = theme.get("primary_chart_series_color");
+    let mut datasets: Vec<Dataset> = layers
+        .iter()
+        .enumerate()
+        .filter(|(_, layer)| !layer.is_empty())
+        .map(|(level, layer)| {
+            let t = level as f64 / (SCATTER_DENSITY_LEVELS - 1) as f64;
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Scatter)
+                .style(Style::default().fg(heatmap::shade(sparse, dense, t)))
+                .data(layer)
+        })
+        .collect();
+    let crosshair = modal
+        .crosshair
+        .and_then(|c| Crosshair::snap(points, c.point));
+    let crosshair_parts = crosshair
+        .map(|point| crosshair_lines(point, x_bounds, y_bounds))
+        .unwrap_or_default();
+    datasets.splice(
+        0..0,
+        crosshair_datasets(&crosshair_parts[..2], theme, false),
+    );
+    let trend = data.trend.filter(|_| modal.scatter_trend);
+    let trend_points = trend
+        .and_then(|t| trend_segment(t, x_bounds, y_bounds))
+        .unwrap_or_default();
+    if let Some(t) = trend.filter(|_| !trend_points.is_empty()) {
+        let sign = if t.intercept < 0.0 { '-' } else { '+' };
+        datasets.push(
+            Dataset::default()
+                .name(format!(
+                    "y = {}x {} {}  r² = {:.2}",
+                    format_axis_label(t.slope),
+                    sign,
+                    format_axis_label(t.intercept.abs()),
+                    t.r_squared
+                ))
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.get("secondary_chart_series_color")))
+                .data(&trend_points),
+        );
+    }
+    datasets.extend(crosshair_datasets(&crosshair_parts[2..], theme, true));
+
+    let labels = &modal.labels;
+    let axis_style = Style::default().fg(theme.get("text_primary"));
+    let format_x = |v: f64| labels.format_x(v, XAxisTemporalKind::Numeric);
+    let x_axis = Axis::default()
+        .title(labels.x_title(&data.x_column))
+        .bounds(x_bounds)
+        .style(axis_style)
+        .labels(vec![
+            Span::styled(format_x(x_min), axis_style),
+            Span::styled(format_x((x_min + x_max) / 2.0), axis_style),
+            Span::styled(format_x(x_max), axis_style),
+        ]);
+    let y_labels = vec![
+        Span::styled(labels.format_y(y_min), axis_style),
+        Span::styled(labels.format_y((y_min + y_max) / 2.0), axis_style),
+        Span::styled(labels.format_y(y_max), axis_style),
+    ];
+    let y_label_width = y_labels.iter().map(|l| l.width() as u16).max().unwrap_or(0);
+    let y_axis = Axis::default()
+        .title(labels.y_title(&data.y_column))
+        .bounds(y_bounds)
+        .style(axis_style)
+        .labels(y_labels);
+
+    // The trend equation is wider than the default legend allows (a quarter of the chart)
+    let mut chart = Chart::new(datasets)
+        .x_axis(x_axis)
+        .y_axis(y_axis)
+        .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 4)));
+    if modal.show_legend {
+        chart = chart.legend_position(Some(ratatui::widgets::LegendPosition::TopRight));
+    } else {
+        chart = chart.legend_position(None);
+    }
+    chart.render(area, buf);
+    if let Some((x, y)) = crosshair {
+        render_crosshair_tooltip(
+            area,
+            buf,
+            theme,
+            (x, y),
+            x_bounds,
+            y_bounds,
+            y_label_width,
+            [
+                data.y_column.clone(),
+                format!("x: {}", format_x(x)),
+                format!("y: {}", labels.format_y(y)),
+            ],
+        );
+    }
+}
+
+/// Crosshair through `point`: a vertical and a horizontal line across the bounds, then the point.
+fn crosshair_lines(
+    point: (f64, f64),
+    x_bounds: [f64; 2],
+    y_bounds: [f64; 2],
+) -> [Vec<(f64, f64)>; 3] {
+    [
+        vec![(point.0, y_bounds[0]), (point.0, y_bounds[1])],
+        vec![(x_bounds[0], point.1), (x_bounds[1], point.1)],
+        vec![point],
+    ]
+}
+
+/// Datasets for `crosshair_lines` output: lines in text_secondary, the point (`marker`) as a
+/// half block in modal_border_active. Unnamed, so they stay out of the legend.
+fn crosshair_datasets<'a>(
+    lines: &'a [Vec<(f64, f64)>],
+    theme: &Theme,
+    marker: bool,
+) -> impl Iterator<Item = Dataset<'a>> {
+    let (graph_type, symbol, color) = if marker {
+        (
+            GraphType::Scatter,
+            symbols::Marker::HalfBlock,
+            theme.get("modal_border_active"),
+        )
+    } else {
+        (
+            GraphType::Line,
+            symbols::Marker::Braille,
+            theme.get("text_secondary"),
+        )
+    };
+    lines.iter().filter(|l| !l.is_empty()).map(move |l| {
+        Dataset::default()
+            .marker(symbol)
+            .graph_type(graph_type)
+            .style(Style::default().fg(color))
+            .data(l)
+    })
+}
+
+/// Readout box next to the crosshair point. The point's cell is estimated from the chart area
+/// less the y labels (`y_label_width`, plus the axis line) and the x labels and axis line below;
+/// the box goes up and right of it, flipping to stay inside the area.
+#[allow(clippy::too_many_arguments)]
+fn render_crosshair_tooltip(
+    area: Rect,
+    buf: &mut ratatui::buffer::Buffer,
+    theme: &Theme,
+    point: (f64, f64),
+    x_bounds: [f64; 2],
+    y_bounds: [f64; 2],
+    y_label_width: u16,
+    lines: [String; 3],
+) {
+    let left = y_label_width.saturating_add(1).min(area.width);
+    let graph = Rect::new(
+        area.x + left,
+        area.y,
+        area.width - left,
+        area.height.saturating_sub(2),
+    );
+    let width = lines
+        .iter()
+        .map(|l| l.chars().count() as u16)
+        .max()
+        .unwrap_or(0)
+        .saturating_add(4)
+        .min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let fraction = |v: f64, [lo, hi]: [f64; 2]| {
+        if hi > lo {
+            ((v - lo) / (hi - lo)).clamp(0.0, 1.0)
+        } else {
+            0.5
+        }
+    };
+    let col =
+        graph.x + (fraction(point.0, x_bounds) * f64::from(graph.width.saturating_sub(1))) as u16;
+    let row = graph.bottom().saturating_sub(1).max(graph.y)
+        - (fraction(point.1, y_bounds) * f64::from(graph.height.saturating_sub(1))) as u16;
+    let x = if col + 2 + width <= area.right() {
+        col + 2
+    } else {
+        col.saturating_sub(width + 1).max(area.x)
+    };
+    let y = if row >= area.y + height {
+        row - height
+    } else {
+        (row + 1).min(area.bottom().saturating_sub(height))
+    };
+    let tooltip = Rect::new(x, y, width, height);
+    Clear.render(tooltip, buf);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.get("modal_border_active")));
+    let text_primary = theme.get("text_primary");
+    let [name, x_value, y_value] = lines;
+    Paragraph::new(vec![
+        Line::from(Span::styled(
+            name,
+            Style::default()
+                .fg(text_primary)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(x_value, Style::default().fg(text_primary))),
+        Line::from(Span::styled(y_value, Style::default().fg(text_primary))),
+    ])
+    .block(block)
+    .render(tooltip, buf);
+}
+
+/// Splits scatter points into `SCATTER_DENSITY_LEVELS` layers by how many points fall in their
+/// character cell of `area`, sparsest first so the dense layers draw on top. Levels follow the
+/// log of the count, so a few crowded cells don't push everything else into the lowest shade.
+fn density_layers(
+    points: &[(f64, f64)],
+    x_bounds: [f64; 2],
+    y_bounds: [f64; 2],
+    area: Rect,
+) -> Vec<Vec<(f64, f64)>> {
+    let columns = usize::from(area.width.max(1));
+    let rows = usize::from(area.height.max(1));
+    let cell = |&(x, y): &(f64, f64)| {
+        let col = ((x - x_bounds[0]) / (x_bounds[1] - x_bounds[0]) * columns as f64) as usize;
+        let row = ((y - y_bounds[0]) / (y_bounds[1] - y_bounds[0]) * rows as f64) as usize;
+        row.min(rows - 1) * columns + col.min(columns - 1)
+    };
+    let mut counts = vec![0_u32; columns * rows];
+    for p in points {
+        counts[cell(p)] += 1;
+    }
+    let max = counts.iter().copied().max().unwrap_or(0);
+    let top = SCATTER_DENSITY_LEVELS - 1;
+    let mut layers = vec![Vec::new(); SCATTER_DENSITY_LEVELS];
+    for p in points {
+        let level = if max > 1 {
+            let t = f64::from(counts[cell(p)]).ln() / f64::from(max).ln();
+            (t * top as f64).round() as usize
+        } else {
+            // No cell holds more than one point: nothing is denser than anything else
+            top
+        };
+        layers[level.min(top)].push(*p);
+    }
+    layers
+}
+
+/// The part of `trend` inside the chart bounds, as its two end points. The canvas skips lines
+/// with an end outside the bounds, so the line is clipped here.
+fn trend_segment(
+    trend: TrendLine,
+    x_bounds: [f64; 2],
+    y_bounds: [f64; 2],
+) -> Option<Vec<(f64, f64)>> {
+    let (mut x0, mut x1) = (x_bounds[0], x_bounds[1]);
+    if trend.slope != 0.0 {
+        let at_min = (y_bounds[0] - trend.intercept) / trend.slope;
+        let at_max = (y_bounds[1] - trend.intercept) / trend.slope;
+        x0 = x0.max(at_min.min(at_max));
+        x1 = x1.min(at_min.max(at_max));
+    } else if trend.intercept < y_bounds[0] || trend.intercept > y_bounds[1] {
+        return None;
+    }
+    if x0 > x1 {
+        return None;
+    }
+    // Clamped so rounding at the bounds doesn't put an end just outside them
+    let y = |x: f64| trend.at(x).clamp(y_bounds[0], y_bounds[1]);
+    Some(vec![(x0, y(x0)), (x1, y(x1))])
+}
@@ -1,4 +1,5 @@
-//! Chart export modal rendering: format (vertical radio), path, title, dimensions, buttons (right).
+//! Chart export modal rendering: format (vertical radio), path, title, dimensions, report card
+//! checkbox, buttons (right).
 
 use crate::chart_export::ChartExportFormat;
 use crate::chart_export_modal::{ChartExportFocus, ChartExportModal};
@@ -54,7 +55,7 @@ pub fn render_chart_export_modal(
             Constraint::Length(3), // File path
             Constraint::Length(3), // Chart title
             Constraint::Length(3), // Width x Height
-            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Report card checkbox
             Constraint::Length(3), // Buttons
         ])
         .split(right);
@@ -128,12 +129,30 @@ pub fn render_chart_export_modal(
     modal.height_input.set_focused(is_height_focused);
     (&modal.height_input).render(height_inner, buf);
 
+    // Report card checkbox
+    let is_report_focused = modal.focus == ChartExportFocus::ReportCardToggle;
+    let marker = if modal.report_card { "☑" } else { "☐" };
+    Paragraph::new(format!(" {} Append summary table (report card)", marker))
+        .style(Style::default().fg(if is_report_focused {
+            active_color
+        } else {
+            border_color
+        }))
+        .render(right_chunks[3], buf);
+
     // Buttons
     let btn_area = right_chunks[4];
     let btn_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(btn_area);
+    modal.buttons.clear();
+    modal
+        .buttons
+        .add(btn_chunks[0], ChartExportFocus::ExportButton);
+    modal
+        .buttons
+        .add(btn_chunks[1], ChartExportFocus::CancelButton);
 
     let is_export_focused = modal.focus == ChartExportFocus::ExportButton;
     Paragraph::new("Export")
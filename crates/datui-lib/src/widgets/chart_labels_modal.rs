@@ -0,0 +1,86 @@
+//! Chart labels modal rendering: title and axis title inputs, tick format selectors, key hints.
+
+use crate::chart_labels_modal::{ChartLabelsFocus, ChartLabelsModal};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget};
+
+/// Rows needed by the modal, borders included.
+pub const CHART_LABELS_MODAL_HEIGHT: u16 = 15;
+
+pub fn render_chart_labels_modal(
+    area: Rect,
+    buf: &mut ratatui::buffer::Buffer,
+    modal: &mut ChartLabelsModal,
+    border_color: ratatui::style::Color,
+    active_color: ratatui::style::Color,
+) {
+    Clear.render(area, buf);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(border_color))
+        .title(" Chart Labels ");
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Chart title
+            Constraint::Length(3), // X axis title
+            Constraint::Length(3), // Y axis title
+            Constraint::Length(1), // X tick format
+            Constraint::Length(1), // Y tick format
+            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Key hints
+        ])
+        .split(inner);
+
+    let color = |focused: bool| if focused { active_color } else { border_color };
+    let focus = modal.focus;
+    let inputs = [
+        (
+            " Chart Title ",
+            &mut modal.title_input,
+            ChartLabelsFocus::TitleInput,
+        ),
+        (
+            " X Axis Title (empty: column name) ",
+            &mut modal.x_title_input,
+            ChartLabelsFocus::XTitleInput,
+        ),
+        (
+            " Y Axis Title (empty: column names) ",
+            &mut modal.y_title_input,
+            ChartLabelsFocus::YTitleInput,
+        ),
+    ];
+    for (i, (title, input, input_focus)) in inputs.into_iter().enumerate() {
+        let focused = focus == input_focus;
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color(focused)))
+            .title(title);
+        let input_inner = input_block.inner(rows[i]);
+        input_block.render(rows[i], buf);
+        input.set_focused(focused);
+        (&*input).render(input_inner, buf);
+    }
+
+    let ticks = [
+        (" X ticks:", modal.x_ticks, ChartLabelsFocus::XTicks),
+        (" Y ticks:", modal.y_ticks, ChartLabelsFocus::YTicks),
+    ];
+    for (i, (label, format, row_focus)) in ticks.into_iter().enumerate() {
+        let focused = focus == row_focus;
+        Paragraph::new(format!("{:<12}‹ {} ›", label, format.as_str()))
+            .style(Style::default().fg(color(focused)))
+            .render(rows[3 + i], buf);
+    }
+
+    Paragraph::new(" Tab: next field  ←/→: tick format  Enter: apply  Esc: cancel")
+        .style(Style::default().fg(border_color))
+        .render(rows[6], buf);
+}
@@ -1,3 +1,4 @@
+use crate::query::QueryEngine;
 use crate::render::context::RenderContext;
 use ratatui::{
     buffer::Buffer,
@@ -9,7 +10,9 @@ use ratatui::{
 pub struct Controls {
     pub row_count: Option<usize>,
     pub dimmed: bool,
-    pub query_active: bool,
+    pub query_engine: Option<QueryEngine>, // When Some, the engine of the active query is shown before the row count
+    pub search_status: Option<String>, // When Some, the regex search and match position are shown before the engine
+    pub sampling_status: Option<String>, // When Some, the sampling strategy is shown between the search and the engine
     pub custom_controls: Option<Vec<(&'static str, &'static str)>>,
     pub bg_color: Color,
     pub key_color: Color,   // Color for keybind hints (keys in toolbar)
@@ -28,7 +31,9 @@ impl Default for Controls {
         Self {
             row_count: None,
             dimmed: false,
-            query_active: false,
+            query_engine: None,
+            search_status: None,
+            sampling_status: None,
             custom_controls: None,
             bg_color: Color::Indexed(236), // Default for backward compatibility
             key_color: Color::Cyan,        // Keys in cyan
@@ -53,7 +58,9 @@ impl Controls {
         Self {
             row_count: Some(row_count),
             dimmed: false,
-            query_active: false,
+            query_engine: None,
+            search_status: None,
+            sampling_status: None,
             custom_controls: None,
             bg_color: Color::Indexed(236), // Default
             key_color: Color::Cyan,        // Keys in cyan
@@ -79,8 +86,18 @@ impl Controls {
         self
     }
 
-    pub fn with_query_active(mut self, query_active: bool) -> Self {
-        self.query_active = query_active;
+    pub fn with_query_engine(mut self, query_engine: Option<QueryEngine>) -> Self {
+        self.query_engine = query_engine;
+        self
+    }
+
+    pub fn with_search_status(mut self, search_status: Option<String>) -> Self {
+        self.search_status = search_status;
+        self
+    }
+
+    pub fn with_sampling_status(mut self, sampling_status: Option<String>) -> Self {
+        self.sampling_status = sampling_status;
         self
     }
 
@@ -129,7 +146,9 @@ impl Controls {
         Self {
             row_count: Some(row_count),
             dimmed: false,
-            query_active: false,
+            query_engine: None,
+            search_status: None,
+            sampling_status: None,
             custom_controls: None,
             bg_color: ctx.controls_bg,
             key_color: ctx.keybind_hints,
@@ -154,7 +173,9 @@ impl Controls {
         Self {
             row_count: Some(row_count),
             dimmed: false,
-            query_active: false,
+            query_engine: None,
+            search_status: None,
+            sampling_status: None,
             custom_controls: None,
             bg_color,
             key_color,
@@ -287,8 +308,25 @@ impl Widget for &Controls {
             (key.chars().count() as u16 + 1) + (action.chars().count() as u16 + 1)
         };
 
-        // Reserve space for fill and row count (no right-side throbber in normal mode).
-        let right_reserved = if self.row_count.is_some() { 21 } else { 1 };
+        // Regex search, sampling and active query engine badges, e.g.
+        // "/ab+/ 3 of 12 [sample: random] [SQL]", shown just left of the row count.
+        let badges: Vec<String> = self
+            .search_status
+            .iter()
+            .cloned()
+            .chain(self.sampling_status.iter().map(|s| format!("[{}]", s)))
+            .chain(
+                self.query_engine
+                    .map(|engine| format!("[{}]", engine.label())),
+            )
+            .collect();
+        let engine_badge = (!badges.is_empty()).then(|| badges.join(" "));
+        let badge_width = engine_badge
+            .as_ref()
+            .map_or(0, |b| b.chars().count() as u16 + 1);
+
+        // Reserve space for fill, engine badge and row count (no right-side throbber in normal mode).
+        let right_reserved = if self.row_count.is_some() { 21 } else { 1 } + badge_width;
         let mut available = area.width.saturating_sub(right_reserved);
 
         let mut n_show = 0;
@@ -314,6 +352,9 @@ impl Widget for &Controls {
             .collect();
 
         constraints.push(Constraint::Fill(1));
+        if engine_badge.is_some() {
+            constraints.push(Constraint::Length(badge_width));
+        }
         if self.row_count.is_some() {
             constraints.push(Constraint::Length(20));
         }
@@ -335,11 +376,19 @@ impl Widget for &Controls {
         }
 
         let fill_idx = n_show * 2;
+        let mut right_idx = fill_idx + 1;
+        if let Some(ref badge) = engine_badge {
+            Paragraph::new(badge.as_str())
+                .style(key_style)
+                .right_aligned()
+                .render(layout[right_idx], buf);
+            right_idx += 1;
+        }
         if let Some(count) = self.row_count {
             Paragraph::new(row_count_text(count))
                 .style(label_style)
                 .right_aligned()
-                .render(layout[fill_idx + 1], buf);
+                .render(layout[right_idx], buf);
         }
 
         Paragraph::new("")
@@ -417,6 +466,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn shows_active_query_engine_before_row_count() {
+        let controls = Controls::with_row_count(10).with_query_engine(Some(QueryEngine::Sql));
+        let out = render_to_string(&controls, 80);
+        let badge = out.find("[SQL]").expect("engine badge");
+        assert!(badge < out.find("Rows: 10").unwrap(), "got: {out:?}");
+
+        let out = render_to_string(&Controls::with_row_count(10), 80);
+        assert!(!out.contains('['), "no badge without a query: {out:?}");
+    }
+
+    #[test]
+    fn shows_search_status_before_engine_badge() {
+        let controls = Controls::with_row_count(10)
+            .with_query_engine(Some(QueryEngine::Sql))
+            .with_search_status(Some("/ab+/ 3 of 12".to_string()));
+        let out = render_to_string(&controls, 100);
+        let search = out.find("/ab+/ 3 of 12").expect("search status");
+        assert!(search < out.find("[SQL]").unwrap(), "got: {out:?}");
+    }
+
+    #[test]
+    fn shows_sampling_strategy_badge() {
+        let controls = Controls::with_row_count(10)
+            .with_query_engine(Some(QueryEngine::Sql))
+            .with_sampling_status(Some("sample: stratified by region".to_string()));
+        let out = render_to_string(&controls, 100);
+        let sampling = out
+            .find("[sample: stratified by region]")
+            .expect("sampling badge");
+        assert!(sampling < out.find("[SQL]").unwrap(), "got: {out:?}");
+    }
+
     #[test]
     fn pending_spinner_shown_in_status_message_mode() {
         let controls = Controls::with_row_count(99)
@@ -1,6 +1,6 @@
 use color_eyre::Result;
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::{fs, fs::File, path::Path, path::PathBuf};
 
@@ -16,18 +16,29 @@ use ratatui::{
     },
 };
 
+use crate::column_cast::{apply_casts, ColumnCast};
+use crate::column_format::ColumnFormats;
+use crate::column_ruler::ColumnRuler;
+use crate::columns_modal::ColumnRename;
+use crate::computed_column::{add_computed_columns, ComputedColumn};
 use crate::error_display::user_message_from_polars;
 use crate::filter_modal::{FilterOperator, FilterStatement, LogicalOperator};
+use crate::group_by_modal::GroupBySpec;
+use crate::heatmap::{self, ColumnRanges};
+use crate::locale::DisplayLocale;
 use crate::pivot_melt_modal::{MeltSpec, PivotAggregation, PivotSpec};
-use crate::query::parse_query;
+use crate::query::{apply_query, parse_query, QueryEngine};
+use crate::regex_search::RegexSearch;
+use crate::resample_modal::ResampleSpec;
 use crate::statistics::collect_lazy;
-use crate::{CompressionFormat, OpenOptions, ParseStringsTarget};
+use crate::time_zone_modal::TimeZoneChange;
+use crate::{CompressionFormat, FileFormat, OpenOptions, ParseStringsTarget};
 use polars::io::csv::read::NullValues;
 use polars::lazy::frame::pivot::pivot_stable;
 use polars::prelude::StrptimeOptions;
 use std::io::{BufReader, Read};
 
-use calamine::{open_workbook_auto, Data, Reader};
+use calamine::Data;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use orc_rust::ArrowReaderBuilder;
 use tempfile::NamedTempFile;
@@ -39,6 +50,12 @@ use arrow::array::types::{
 use arrow::array::{Array, AsArray};
 use arrow::record_batch::RecordBatch;
 
+static NEXT_LEN_GENERATION: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+fn next_len_generation() -> u64 {
+    NEXT_LEN_GENERATION.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+}
+
 fn pivot_agg_expr(agg: PivotAggregation) -> Result<Expr> {
     let e = col(PlSmallStr::from_static(""));
     let expr = match agg {
@@ -54,6 +71,14 @@ fn pivot_agg_expr(agg: PivotAggregation) -> Result<Expr> {
     Ok(expr)
 }
 
+/// What a state read from a file holds besides its LazyFrame: the temp copy the LazyFrame
+/// scans (decompressed, unescaped or without bad rows) and the load report.
+#[derive(Default)]
+pub struct SourceFiles {
+    temp_file: Option<NamedTempFile>,
+    load_report: Option<crate::load_report::LoadReport>,
+}
+
 pub struct DataTableState {
     pub lf: LazyFrame,
     original_lf: LazyFrame,
@@ -64,6 +89,12 @@ pub struct DataTableState {
     pub visible_rows: usize,
     pub termcol_index: usize,
     pub visible_termcols: usize,
+    /// Column shown truncated (with `…`) in the last rendered frame, if any.
+    pub truncated_column: Option<String>,
+    /// Area of the last rendered frame (header row first) and where each column was drawn, so
+    /// mouse clicks can be mapped to cells and headers.
+    pub table_area: Rect,
+    pub column_spans: Vec<ColumnSpan>,
     pub error: Option<PolarsError>,
     pub suppress_error_display: bool, // When true, don't show errors in main view (e.g., when query input is active)
     pub schema: Arc<Schema>,
@@ -74,6 +105,8 @@ pub struct DataTableState {
     /// count carries the generation it was spawned under; a result whose generation no
     /// longer matches is stale (the data changed) and is dropped. Decoupled from
     /// `task_generation` so a mere scroll doesn't invalidate / restart an in-flight count.
+    /// Drawn from a process-wide counter so a count for one table (e.g. another workspace
+    /// tab) can never match a different table's generation.
     len_generation: u64,
     /// When set, the current `lf` is a pristine scan of this local Parquet hive directory,
     /// so the exact row count equals the sum of per-file footer counts — far cheaper than
@@ -90,6 +123,32 @@ pub struct DataTableState {
     pub active_fuzzy_query: String,
     column_order: Vec<String>,   // Order of columns for display
     locked_columns_count: usize, // Number of locked columns (from left)
+    /// Columns marked with `v` in the main view, in the order they were marked.
+    selected_columns: Vec<String>,
+    /// Display formats set for this view (Format modal or template), by column name. Kept
+    /// across reset since they don't change the data.
+    pub column_formats: ColumnFormats,
+    /// Widths set for this view with `<`, `>` and `=`, by column name. They replace the content
+    /// width and `display.max_column_width`, and are kept across reset like `column_formats`.
+    pub column_widths: HashMap<String, u16>,
+    /// Rows pinned to the top of the table with `P`, by row number in the view, ascending.
+    pinned_rows: Vec<usize>,
+    /// Values of the pinned rows (every column of the view), one row each in `pinned_rows` order.
+    pinned_df: Option<DataFrame>,
+    /// Columns computed from expressions (New Column modal or template), in the order added.
+    /// They're part of `original_lf`, so every view of the data includes them.
+    computed_columns: Vec<ComputedColumn>,
+    /// Columns read as another type (Cast modal or template), in the order cast. Like computed
+    /// columns, they're applied to `original_lf`.
+    column_casts: Vec<ColumnCast>,
+    /// Columns of the data renamed (Columns modal or template), in the order renamed. Applied to
+    /// `original_lf` like casts.
+    column_renames: Vec<ColumnRename>,
+    /// Regex search (Query dialog, Regex tab): matching cells are highlighted and `n` / `N`
+    /// jump between matching rows.
+    pub regex_search: Option<RegexSearch>,
+    /// Where the visual selection started: a block corner (`Ctrl-V`) or a row (`m`).
+    visual_anchor: Option<VisualAnchor>,
     grouped_lf: Option<LazyFrame>,
     drilled_down_group_index: Option<usize>, // Index of the group we're viewing
     pub drilled_down_group_key: Option<Vec<String>>, // Key values of the drilled down group
@@ -110,10 +169,18 @@ pub struct DataTableState {
     last_pivot_spec: Option<PivotSpec>,
     /// Last applied melt spec, if current lf is result of a melt. Used for templates.
     last_melt_spec: Option<MeltSpec>,
+    /// Last applied group-by spec, if current lf is result of a group-by. Used for templates.
+    last_group_by_spec: Option<GroupBySpec>,
+    /// Last applied resample spec, if current lf is result of a resample. Reopens the modal.
+    last_resample_spec: Option<ResampleSpec>,
     /// When set, dataset was loaded with hive partitioning; partition column names for Info panel and predicate pushdown.
     pub partition_columns: Option<Vec<String>>,
     /// When set, decompressed CSV was written to this temp file; kept alive so the file exists for lazy scan.
     decompress_temp_file: Option<NamedTempFile>,
+    /// When set, the file was loaded with `skip_bad_rows`: the rows left out of it.
+    pub load_report: Option<crate::load_report::LoadReport>,
+    /// True once the source has been read into memory (`set_in_memory_source`).
+    in_memory: bool,
     /// When true, use Polars streaming engine for LazyFrame collect when the streaming feature is enabled.
     pub polars_streaming: bool,
     /// When true, cast Date/Datetime pivot index columns to Int32 before pivot (workaround for Polars 0.52).
@@ -185,12 +252,15 @@ impl DataTableState {
             visible_rows: 0,
             termcol_index: 0,
             visible_termcols: 0,
+            truncated_column: None,
+            table_area: Rect::default(),
+            column_spans: Vec::new(),
             error: None,
             suppress_error_display: false,
             schema,
             num_rows: 0,
             num_rows_valid: false,
-            len_generation: 0,
+            len_generation: next_len_generation(),
             parquet_count_dir: None,
             filters: Vec::new(),
             sort_columns: Vec::new(),
@@ -200,6 +270,16 @@ impl DataTableState {
             active_fuzzy_query: String::new(),
             column_order,
             locked_columns_count: 0,
+            selected_columns: Vec::new(),
+            column_formats: ColumnFormats::new(),
+            column_widths: HashMap::new(),
+            pinned_rows: Vec::new(),
+            pinned_df: None,
+            computed_columns: Vec::new(),
+            column_casts: Vec::new(),
+            column_renames: Vec::new(),
+            regex_search: None,
+            visual_anchor: None,
             grouped_lf: None,
             drilled_down_group_index: None,
             drilled_down_group_key: None,
@@ -216,8 +296,12 @@ impl DataTableState {
             row_start_index: 1,     // Will be set from options
             last_pivot_spec: None,
             last_melt_spec: None,
+            last_group_by_spec: None,
+            last_resample_spec: None,
             partition_columns: None,
             decompress_temp_file: None,
+            load_report: None,
+            in_memory: false,
             polars_streaming,
             workaround_pivot_date_index: true,
             defer_collect: false,
@@ -271,12 +355,15 @@ impl DataTableState {
             visible_rows: 0,
             termcol_index: 0,
             visible_termcols: 0,
+            truncated_column: None,
+            table_area: Rect::default(),
+            column_spans: Vec::new(),
             error: None,
             suppress_error_display: false,
             schema,
             num_rows: 0,
             num_rows_valid: false,
-            len_generation: 0,
+            len_generation: next_len_generation(),
             parquet_count_dir: None,
             filters: Vec::new(),
             sort_columns: Vec::new(),
@@ -286,6 +373,16 @@ impl DataTableState {
             active_fuzzy_query: String::new(),
             column_order,
             locked_columns_count: 0,
+            selected_columns: Vec::new(),
+            column_formats: ColumnFormats::new(),
+            column_widths: HashMap::new(),
+            pinned_rows: Vec::new(),
+            pinned_df: None,
+            computed_columns: Vec::new(),
+            column_casts: Vec::new(),
+            column_renames: Vec::new(),
+            regex_search: None,
+            visual_anchor: None,
             grouped_lf: None,
             drilled_down_group_index: None,
             drilled_down_group_key: None,
@@ -302,8 +399,12 @@ impl DataTableState {
             row_start_index: options.row_start_index,
             last_pivot_spec: None,
             last_melt_spec: None,
+            last_group_by_spec: None,
+            last_resample_spec: None,
             partition_columns,
             decompress_temp_file: None,
+            load_report: None,
+            in_memory: false,
             polars_streaming: options.polars_streaming,
             workaround_pivot_date_index: options.workaround_pivot_date_index,
             defer_collect: false,
@@ -328,6 +429,8 @@ impl DataTableState {
         self.active_sql_query.clear();
         self.active_fuzzy_query.clear();
         self.locked_columns_count = 0;
+        self.selected_columns.clear();
+        self.visual_anchor = None;
         self.filters.clear();
         self.sort_columns.clear();
         self.sort_ascending = true;
@@ -345,10 +448,13 @@ impl DataTableState {
 
     pub fn reset(&mut self) {
         self.reset_lf_to_original();
+        self.regex_search = None;
         self.error = None;
         self.suppress_error_display = false;
         self.last_pivot_spec = None;
         self.last_melt_spec = None;
+        self.last_group_by_spec = None;
+        self.last_resample_spec = None;
         self.collect();
         if self.num_rows > 0 {
             self.start_row = 0;
@@ -569,55 +675,12 @@ impl DataTableState {
     }
 
     /// Load a single Excel file (xls, xlsx, xlsm, xlsb) using calamine (eager read, then lazy).
-    /// Sheet is selected by 0-based index or name via `excel_sheet`.
-    #[allow(clippy::too_many_arguments)]
-    pub fn from_excel(
-        path: &Path,
-        pages_lookahead: Option<usize>,
-        pages_lookback: Option<usize>,
-        max_buffered_rows: Option<usize>,
-        max_buffered_mb: Option<usize>,
-        row_numbers: bool,
-        row_start_index: usize,
-        excel_sheet: Option<&str>,
-    ) -> Result<Self> {
-        let mut workbook =
-            open_workbook_auto(path).map_err(|e| color_eyre::eyre::eyre!("Excel: {}", e))?;
-        let sheet_names = workbook.sheet_names().to_vec();
-        if sheet_names.is_empty() {
-            return Err(color_eyre::eyre::eyre!("Excel file has no worksheets"));
-        }
-        let range = if let Some(sheet_sel) = excel_sheet {
-            if let Ok(idx) = sheet_sel.parse::<usize>() {
-                workbook
-                    .worksheet_range_at(idx)
-                    .ok_or_else(|| color_eyre::eyre::eyre!("Excel: no sheet at index {}", idx))?
-                    .map_err(|e| color_eyre::eyre::eyre!("Excel: {}", e))?
-            } else {
-                workbook
-                    .worksheet_range(sheet_sel)
-                    .map_err(|e| color_eyre::eyre::eyre!("Excel: {}", e))?
-            }
-        } else {
-            workbook
-                .worksheet_range_at(0)
-                .ok_or_else(|| color_eyre::eyre::eyre!("Excel: no first sheet"))?
-                .map_err(|e| color_eyre::eyre::eyre!("Excel: {}", e))?
-        };
-        let rows: Vec<Vec<Data>> = range.rows().map(|r| r.to_vec()).collect();
+    /// Sheet is selected by 0-based index or name via `excel_sheet`; `excel_range` and
+    /// `excel_header_row` pick the cells within it (see [`crate::excel_range`]).
+    pub fn from_excel(path: &Path, options: &OpenOptions) -> Result<Self> {
+        let rows = crate::excel_range::read_rows(path, options)?.rows;
         if rows.is_empty() {
-            let empty_df = DataFrame::new(vec![])?;
-            let mut state = Self::new(
-                empty_df.lazy(),
-                pages_lookahead,
-                pages_lookback,
-                max_buffered_rows,
-                max_buffered_mb,
-                true,
-            )?;
-            state.row_numbers = row_numbers;
-            state.row_start_index = row_start_index;
-            return Ok(state);
+            return Self::from_lazyframe(DataFrame::new(vec![])?.lazy(), options);
         }
         let headers: Vec<String> = rows[0]
             .iter()
@@ -638,17 +701,39 @@ impl DataTableState {
             series_vec.push(series.into());
         }
         let df = DataFrame::new(series_vec)?;
-        let mut state = Self::new(
-            df.lazy(),
-            pages_lookahead,
-            pages_lookback,
-            max_buffered_rows,
-            max_buffered_mb,
-            true,
-        )?;
-        state.row_numbers = row_numbers;
-        state.row_start_index = row_start_index;
-        Ok(state)
+        Self::from_lazyframe(df.lazy(), options)
+    }
+
+    /// Load a table of a SQLite database: `options.table`, or the only table when the
+    /// database has one.
+    pub fn from_sqlite(path: &Path, options: &OpenOptions) -> Result<Self> {
+        let df = match &options.sql_query {
+            Some(sql) => crate::sqlite_import::read_query(path, sql)?,
+            None => {
+                let table = crate::sqlite_import::choose_table(path, options.table.as_deref())?;
+                crate::sqlite_import::read_table(path, &table)?
+            }
+        };
+        Self::from_lazyframe(df.lazy(), options)
+    }
+
+    /// Load a Postgres or MySQL table, or the rows of `options.sql_query`, from the server at
+    /// `url`. A `?table=` in the URL wins over `options.table`.
+    #[cfg(feature = "database")]
+    pub fn from_database(url: &str, options: &OpenOptions) -> Result<Self> {
+        let mut url = crate::db_import::ServerUrl::parse(url)
+            .ok_or_else(|| color_eyre::eyre::eyre!("Not a database URL: {}", url))?;
+        if url.table.is_none() {
+            url.table = options.table.clone();
+        }
+        let df = crate::db_import::read(&url, options.sql_query.as_deref())?;
+        Self::from_lazyframe(df.lazy(), options)
+    }
+
+    /// Load a file of length-delimited protobuf records with `options.proto_schema`.
+    pub fn from_protobuf(path: &Path, options: &OpenOptions) -> Result<Self> {
+        let df = crate::protobuf::read_file(path, options)?;
+        Self::from_lazyframe(df.lazy(), options)
     }
 
     /// Infers column type: prefers Int64 for whole-number floats; infers Date/Datetime for
@@ -1121,13 +1206,15 @@ impl DataTableState {
 
     /// Build a LazyFrame for hive-partitioned Parquet only (no schema collection, no partition discovery).
     /// Use this for phased loading so "Scanning input" is instant; schema and partition handling happen in DoLoadSchema.
-    pub fn scan_parquet_hive(path: &Path) -> Result<LazyFrame> {
+    /// `include_file_paths` names a column to fill with each row's file path.
+    pub fn scan_parquet_hive(path: &Path, include_file_paths: Option<&str>) -> Result<LazyFrame> {
         let path_str = path.as_os_str().to_string_lossy();
         let is_glob = path_str.contains('*');
         let pl_path = PlPath::Local(Arc::from(path));
         let args = ScanArgsParquet {
             hive_options: HiveOptions::new_enabled(),
             glob: is_glob,
+            include_file_paths: include_file_paths.map(Into::into),
             ..Default::default()
         };
         LazyFrame::scan_parquet(pl_path, args).map_err(Into::into)
@@ -1147,15 +1234,132 @@ impl DataTableState {
         LazyFrame::scan_parquet(pl_path, args).map_err(Into::into)
     }
 
+    /// Build a LazyFrame for a hive-partitioned dataset of `format` files (see [`Self::hive_format`]),
+    /// without collecting the schema. With `--source-column` each row gets its file's path.
+    pub fn scan_hive(path: &Path, format: FileFormat, options: &OpenOptions) -> Result<LazyFrame> {
+        let include_file_paths = options
+            .source_column
+            .then_some(crate::concat::SOURCE_FILE_COLUMN);
+        let is_glob = path.as_os_str().to_string_lossy().contains('*');
+        let pl_path = PlPath::Local(Arc::from(path));
+        let lf = match format {
+            FileFormat::Parquet => return Self::scan_parquet_hive(path, include_file_paths),
+            FileFormat::Csv | FileFormat::Tsv | FileFormat::Psv => {
+                let mut reader = LazyCsvReader::new(pl_path)
+                    .with_glob(is_glob)
+                    .with_include_file_paths(include_file_paths.map(Into::into));
+                if let Some(skip_lines) = options.skip_lines {
+                    reader = reader.with_skip_lines(skip_lines);
+                }
+                if let Some(skip_rows) = options.skip_rows {
+                    reader = reader.with_skip_rows(skip_rows);
+                }
+                if let Some(has_header) = options.has_header {
+                    reader = reader.with_has_header(has_header);
+                }
+                reader = Self::csv_dialect_scan(reader, options);
+                let separator = match format {
+                    FileFormat::Tsv => b'\t',
+                    FileFormat::Psv => b'|',
+                    _ => b',',
+                };
+                reader = reader.with_separator(options.delimiter.unwrap_or(separator));
+                if let Some(n) = options.infer_schema_length {
+                    reader = reader.with_infer_schema_length(Some(n));
+                }
+                let first_file = Self::first_data_file_in_hive_dir(path, format);
+                let nv = Self::build_null_values_for_csv(options, first_file.as_deref())?;
+                reader = reader
+                    .with_ignore_errors(options.ignore_errors)
+                    .with_dtype_overwrite(crate::schema_override::schema(options)?)
+                    .with_try_parse_dates(options.csv_try_parse_dates());
+                if let Some(nv) = nv {
+                    reader =
+                        reader.map_parse_options(|opts| opts.with_null_values(Some(nv.clone())));
+                }
+                Self::with_hive_partitioning(reader.finish()?)
+            }
+            FileFormat::Jsonl => {
+                let mut reader = LazyJsonLineReader::new(pl_path)
+                    .with_include_file_paths(include_file_paths.map(Into::into));
+                if let Some(n) = options.infer_schema_length {
+                    reader = reader.with_infer_schema_length(std::num::NonZeroUsize::new(n));
+                }
+                Self::with_hive_partitioning(reader.finish()?)
+            }
+            FileFormat::Arrow => {
+                let args = UnifiedScanArgs {
+                    hive_options: HiveOptions::new_enabled(),
+                    glob: is_glob,
+                    include_file_paths: include_file_paths.map(Into::into),
+                    ..Default::default()
+                };
+                LazyFrame::scan_ipc(pl_path, Default::default(), args)?
+            }
+            other => {
+                return Err(color_eyre::eyre::eyre!(
+                    "Hive partitioning is not supported for {:?} files",
+                    other
+                ))
+            }
+        };
+        Ok(lf)
+    }
+
+    /// Turn on hive partitioning for a scan from a reader that has no option for it (CSV, NDJSON).
+    fn with_hive_partitioning(mut lf: LazyFrame) -> LazyFrame {
+        if let DslPlan::Scan {
+            unified_scan_args, ..
+        } = &mut lf.logical_plan
+        {
+            unified_scan_args.hive_options = HiveOptions::new_enabled();
+        }
+        lf
+    }
+
+    /// Format of the files of a hive-partitioned dataset at `path` (a directory or glob):
+    /// `format` when given, else the extension of the glob (`**/*.csv`) or of the first data file
+    /// along a partition branch. None unless that is Parquet, CSV/TSV/PSV, NDJSON or Arrow IPC.
+    pub fn hive_format(path: &Path, format: Option<FileFormat>) -> Option<FileFormat> {
+        let format = format.or_else(|| FileFormat::from_path(path)).or_else(|| {
+            let dir = path.ancestors().find(|p| p.is_dir())?;
+            Self::first_file_spine(dir, &|f| FileFormat::from_path(f).is_some(), 0, 64)
+                .as_deref()
+                .and_then(FileFormat::from_path)
+        })?;
+        matches!(
+            format,
+            FileFormat::Parquet
+                | FileFormat::Csv
+                | FileFormat::Tsv
+                | FileFormat::Psv
+                | FileFormat::Jsonl
+                | FileFormat::Arrow
+        )
+        .then_some(format)
+    }
+
+    /// First `format` file along a single spine of the hive dataset at `path` (a directory, or
+    /// the directory a glob starts in).
+    fn first_data_file_in_hive_dir(path: &Path, format: FileFormat) -> Option<PathBuf> {
+        let dir = path.ancestors().find(|p| p.is_dir())?;
+        Self::first_file_spine(dir, &|f| FileFormat::from_path(f) == Some(format), 0, 64)
+    }
+
     /// Find the first parquet file along a single spine of a hive-partitioned directory (same walk as partition discovery).
     /// Returns `None` if the directory is empty or has no parquet files along that spine.
     fn first_parquet_file_in_hive_dir(path: &Path) -> Option<std::path::PathBuf> {
         const MAX_DEPTH: usize = 64;
-        Self::first_parquet_file_spine(path, 0, MAX_DEPTH)
+        let is_parquet = |f: &Path| {
+            f.extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case("parquet"))
+        };
+        Self::first_file_spine(path, &is_parquet, 0, MAX_DEPTH)
     }
 
-    fn first_parquet_file_spine(
+    fn first_file_spine(
         path: &Path,
+        is_data_file: &dyn Fn(&Path) -> bool,
         depth: usize,
         max_depth: usize,
     ) -> Option<std::path::PathBuf> {
@@ -1167,10 +1371,7 @@ impl DataTableState {
         for entry in entries.flatten() {
             let child = entry.path();
             if child.is_file() {
-                if child
-                    .extension()
-                    .is_some_and(|e| e.eq_ignore_ascii_case("parquet"))
-                {
+                if is_data_file(&child) {
                     return Some(child);
                 }
             } else if child.is_dir() {
@@ -1181,7 +1382,8 @@ impl DataTableState {
                 }
             }
         }
-        first_partition_child.and_then(|p| Self::first_parquet_file_spine(&p, depth + 1, max_depth))
+        first_partition_child
+            .and_then(|p| Self::first_file_spine(&p, is_data_file, depth + 1, max_depth))
     }
 
     /// Recursively collect every `*.parquet` file under `dir`. Unlike the single-spine
@@ -1378,7 +1580,7 @@ impl DataTableState {
         columns
     }
 
-    /// Load Parquet with Hive partitioning from a directory or glob path.
+    /// Load a hive-partitioned dataset of `format` files from a directory or glob path.
     /// When path is a directory, partition columns are discovered from path structure.
     /// When path contains glob (e.g. `**/*.parquet`), partition columns are inferred from the pattern (e.g. `year=*/month=*`).
     /// Partition columns are moved to the left in the initial LazyFrame before state is created.
@@ -1386,24 +1588,8 @@ impl DataTableState {
     /// **Performance**: The slow part is Polars, not our code. `scan_parquet` + `collect_schema()` trigger
     /// path expansion (full directory tree or glob) and parquet metadata reads; we only do a single-spine
     /// walk for partition key discovery and cheap schema/select work.
-    pub fn from_parquet_hive(
-        path: &Path,
-        pages_lookahead: Option<usize>,
-        pages_lookback: Option<usize>,
-        max_buffered_rows: Option<usize>,
-        max_buffered_mb: Option<usize>,
-        row_numbers: bool,
-        row_start_index: usize,
-    ) -> Result<Self> {
-        let path_str = path.as_os_str().to_string_lossy();
-        let is_glob = path_str.contains('*');
-        let pl_path = PlPath::Local(Arc::from(path));
-        let args = ScanArgsParquet {
-            hive_options: HiveOptions::new_enabled(),
-            glob: is_glob,
-            ..Default::default()
-        };
-        let mut lf = LazyFrame::scan_parquet(pl_path, args)?;
+    pub fn from_hive(path: &Path, format: FileFormat, options: &OpenOptions) -> Result<Self> {
+        let mut lf = Self::scan_hive(path, format, options)?;
         let schema = lf.collect_schema()?;
 
         let mut discovered = if path.is_dir() {
@@ -1451,14 +1637,14 @@ impl DataTableState {
 
         let mut state = Self::new(
             lf,
-            pages_lookahead,
-            pages_lookback,
-            max_buffered_rows,
-            max_buffered_mb,
+            options.pages_lookahead,
+            options.pages_lookback,
+            options.max_buffered_rows,
+            options.max_buffered_mb,
             true,
         )?;
-        state.row_numbers = row_numbers;
-        state.row_start_index = row_start_index;
+        state.row_numbers = options.row_numbers;
+        state.row_start_index = options.row_start_index;
         state.partition_columns = if partition_columns.is_empty() {
             None
         } else {
@@ -1482,6 +1668,21 @@ impl DataTableState {
         self.row_start_index
     }
 
+    /// Reader over the bytes of `path`, decompressed when `compression` is set.
+    pub(crate) fn open_decompressed(
+        path: &Path,
+        compression: Option<CompressionFormat>,
+    ) -> Result<Box<dyn Read>> {
+        let f = BufReader::new(File::open(path)?);
+        Ok(match compression {
+            None => Box::new(f),
+            Some(CompressionFormat::Gzip) => Box::new(flate2::read::GzDecoder::new(f)),
+            Some(CompressionFormat::Zstd) => Box::new(zstd::Decoder::new(f)?),
+            Some(CompressionFormat::Bzip2) => Box::new(bzip2::read::BzDecoder::new(f)),
+            Some(CompressionFormat::Xz) => Box::new(xz2::read::XzDecoder::new(f)),
+        })
+    }
+
     /// Decompress a compressed file to a temp file for lazy CSV scan.
     fn decompress_compressed_csv_to_temp(
         path: &Path,
@@ -1490,29 +1691,33 @@ impl DataTableState {
     ) -> Result<NamedTempFile> {
         let mut temp = NamedTempFile::new_in(temp_dir)?;
         let out = temp.as_file_mut();
-        let mut reader: Box<dyn Read> = match compression {
-            CompressionFormat::Gzip => {
-                let f = File::open(path)?;
-                Box::new(flate2::read::GzDecoder::new(BufReader::new(f)))
-            }
-            CompressionFormat::Zstd => {
-                let f = File::open(path)?;
-                Box::new(zstd::Decoder::new(BufReader::new(f))?)
-            }
-            CompressionFormat::Bzip2 => {
-                let f = File::open(path)?;
-                Box::new(bzip2::read::BzDecoder::new(BufReader::new(f)))
-            }
-            CompressionFormat::Xz => {
-                let f = File::open(path)?;
-                Box::new(xz2::read::XzDecoder::new(BufReader::new(f)))
-            }
-        };
+        let mut reader = Self::open_decompressed(path, Some(compression))?;
         std::io::copy(&mut reader, out)?;
         out.sync_all()?;
         Ok(temp)
     }
 
+    /// Copy of the CSV at `path` (decompressed) with `escape` before a quote rewritten as a
+    /// doubled quote, the only escape Polars reads, for lazy CSV scan.
+    fn unescape_csv_to_temp(
+        path: &Path,
+        compression: Option<CompressionFormat>,
+        quote: u8,
+        escape: u8,
+        temp_dir: &Path,
+    ) -> Result<NamedTempFile> {
+        let mut temp = NamedTempFile::new_in(temp_dir)?;
+        let reader = Self::open_decompressed(path, compression)?;
+        crate::csv_dialect::unescape(
+            reader,
+            std::io::BufWriter::new(temp.as_file_mut()),
+            quote,
+            escape,
+        )?;
+        temp.as_file_mut().sync_all()?;
+        Ok(temp)
+    }
+
     /// Parse null value specs: "VAL" -> global, "COL=VAL" -> per-column (first '=' separates).
     fn parse_null_value_specs(specs: &[String]) -> (Vec<String>, Vec<(String, String)>) {
         let mut global = Vec::new();
@@ -1571,6 +1776,22 @@ impl DataTableState {
         Some(NullValues::Named(pairs))
     }
 
+    /// Separator, quote character and comment prefix of `options` on a CSV scan.
+    fn csv_dialect_scan(reader: LazyCsvReader, options: &OpenOptions) -> LazyCsvReader {
+        reader
+            .with_separator(options.delimiter.unwrap_or(b','))
+            .with_quote_char(options.quote_char)
+            .with_comment_prefix(options.comment_prefix.as_deref().map(PlSmallStr::from))
+    }
+
+    /// Separator, quote character and comment prefix of `options` for an eager CSV read.
+    fn csv_dialect_parse(parse: CsvParseOptions, options: &OpenOptions) -> CsvParseOptions {
+        parse
+            .with_separator(options.delimiter.unwrap_or(b','))
+            .with_quote_char(options.quote_char)
+            .with_comment_prefix(options.comment_prefix.as_deref())
+    }
+
     /// Infer CSV schema with minimal read (one row) for building null_values when both global and per-column are set.
     fn csv_schema_for_null_values(path: &Path, options: &OpenOptions) -> Result<Arc<Schema>> {
         let pl_path = PlPath::Local(Arc::from(path));
@@ -1584,6 +1805,7 @@ impl DataTableState {
         if let Some(has_header) = options.has_header {
             reader = reader.with_has_header(has_header);
         }
+        reader = Self::csv_dialect_scan(reader, options);
         reader = reader.with_try_parse_dates(options.csv_try_parse_dates());
         let mut lf = reader.finish()?;
         lf.collect_schema().map_err(color_eyre::eyre::Report::from)
@@ -1720,6 +1942,16 @@ impl DataTableState {
             .copied()
     }
 
+    /// `df`, rows read from the start of a CSV, with parse-strings run on it as loading the file
+    /// with `options` would.
+    pub(crate) fn parse_strings_in_sample(
+        df: DataFrame,
+        options: &OpenOptions,
+    ) -> Result<DataFrame> {
+        let lf = Self::apply_parse_strings_to_csv_lazyframe(df.lazy(), options)?;
+        Ok(lf.collect()?)
+    }
+
     /// Apply trim and type inference to CSV string columns when --parse-strings is enabled.
     /// Samples up to `options.parse_strings_sample_rows` rows to infer types, then overlays lazy exprs (trim then cast) on the LazyFrame.
     fn apply_parse_strings_to_csv_lazyframe(
@@ -1733,9 +1965,13 @@ impl DataTableState {
         let sample_rows = options.parse_strings_sample_rows;
         let sample_df = lf.clone().limit(sample_rows as u32).collect()?;
         let schema = sample_df.schema();
+        // Columns given a type with --dtypes keep it
+        let typed_cols = crate::schema_override::columns(options);
         let string_cols: Vec<String> = schema
             .iter()
-            .filter(|(_name, dtype)| **dtype == DataType::String)
+            .filter(|(name, dtype)| {
+                **dtype == DataType::String && !typed_cols.contains(&name.to_string())
+            })
             .map(|(name, _)| name.to_string())
             .collect();
         let target_cols: Vec<String> = match target {
@@ -1976,13 +2212,65 @@ impl DataTableState {
     }
 
     pub fn from_csv(path: &Path, options: &OpenOptions) -> Result<Self> {
-        let nv = Self::build_null_values_for_csv(options, Some(path))?;
-
         // Determine compression format: explicit option, or auto-detect from extension
         let compression = options
             .compression
             .or_else(|| CompressionFormat::from_extension(path));
 
+        // Skipping bad rows: read a copy without them (escapes rewritten first, if any)
+        if options.skip_bad_rows {
+            let temp_dir = options.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
+            let mut filtered_options = options.clone();
+            filtered_options.skip_bad_rows = false;
+            filtered_options.compression = None;
+            let unescaped = match (options.escape_char, options.quote_char) {
+                (Some(escape), Some(quote)) if escape != quote => {
+                    filtered_options.escape_char = None;
+                    Some(Self::unescape_csv_to_temp(
+                        path,
+                        compression,
+                        quote,
+                        escape,
+                        &temp_dir,
+                    )?)
+                }
+                _ => None,
+            };
+            let reader = match &unescaped {
+                Some(temp) => Self::open_decompressed(temp.path(), None)?,
+                None => Self::open_decompressed(path, compression)?,
+            };
+            let mut filtered = NamedTempFile::new_in(&temp_dir)?;
+            let report = crate::load_report::filter_csv(
+                path,
+                reader,
+                std::io::BufWriter::new(filtered.as_file_mut()),
+                &filtered_options,
+            )?;
+            filtered.as_file_mut().sync_all()?;
+            let mut state = Self::from_csv(filtered.path(), &filtered_options)?;
+            state.decompress_temp_file = Some(filtered);
+            state.load_report = Some(report);
+            return Ok(state);
+        }
+
+        // An escape character other than the quote: read a copy with the escapes rewritten
+        if let (Some(escape), Some(quote)) = (options.escape_char, options.quote_char) {
+            if escape != quote {
+                let temp_dir = options.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
+                let temp = Self::unescape_csv_to_temp(path, compression, quote, escape, &temp_dir)?;
+                let mut unescaped = options.clone();
+                unescaped.escape_char = None;
+                unescaped.compression = None;
+                let mut state = Self::from_csv(temp.path(), &unescaped)?;
+                state.decompress_temp_file = Some(temp);
+                return Ok(state);
+            }
+        }
+
+        let nv = Self::build_null_values_for_csv(options, Some(path))?;
+        let dtypes = crate::schema_override::schema(options)?;
+
         if let Some(compression) = compression {
             if options.decompress_in_memory {
                 // Eager read: decompress into memory, then CSV read
@@ -2002,8 +2290,10 @@ impl DataTableState {
                             read_options.infer_schema_length = Some(n);
                         }
                         read_options.ignore_errors = options.ignore_errors;
+                        read_options = read_options.with_schema_overwrite(dtypes.clone());
                         read_options = read_options.map_parse_options(|opts| {
-                            let o = opts.with_try_parse_dates(options.csv_try_parse_dates());
+                            let o = Self::csv_dialect_parse(opts, options)
+                                .with_try_parse_dates(options.csv_try_parse_dates());
                             match &nv {
                                 Some(n) => o.with_null_values(Some(n.clone())),
                                 None => o,
@@ -2046,8 +2336,10 @@ impl DataTableState {
                             read_options.infer_schema_length = Some(n);
                         }
                         read_options.ignore_errors = options.ignore_errors;
+                        read_options = read_options.with_schema_overwrite(dtypes.clone());
                         read_options = read_options.map_parse_options(|opts| {
-                            let o = opts.with_try_parse_dates(options.csv_try_parse_dates());
+                            let o = Self::csv_dialect_parse(opts, options)
+                                .with_try_parse_dates(options.csv_try_parse_dates());
                             match &nv {
                                 Some(n) => o.with_null_values(Some(n.clone())),
                                 None => o,
@@ -2090,8 +2382,10 @@ impl DataTableState {
                             read_options.infer_schema_length = Some(n);
                         }
                         read_options.ignore_errors = options.ignore_errors;
+                        read_options = read_options.with_schema_overwrite(dtypes.clone());
                         read_options = read_options.map_parse_options(|opts| {
-                            let o = opts.with_try_parse_dates(options.csv_try_parse_dates());
+                            let o = Self::csv_dialect_parse(opts, options)
+                                .with_try_parse_dates(options.csv_try_parse_dates());
                             match &nv {
                                 Some(n) => o.with_null_values(Some(n.clone())),
                                 None => o,
@@ -2137,10 +2431,12 @@ impl DataTableState {
                         if let Some(has_header) = options.has_header {
                             reader = reader.with_has_header(has_header);
                         }
+                        reader = Self::csv_dialect_scan(reader, options);
                         if let Some(n) = options.infer_schema_length {
                             reader = reader.with_infer_schema_length(Some(n));
                         }
                         reader = reader.with_ignore_errors(options.ignore_errors);
+                        reader = reader.with_dtype_overwrite(dtypes.clone());
                         reader = reader.with_try_parse_dates(options.csv_try_parse_dates());
                         reader = match &nv_temp {
                             Some(n) => reader
@@ -2187,10 +2483,12 @@ impl DataTableState {
                     if let Some(has_header) = options.has_header {
                         reader = reader.with_has_header(has_header);
                     }
+                    reader = Self::csv_dialect_scan(reader, options);
                     if let Some(n) = options.infer_schema_length {
                         reader = reader.with_infer_schema_length(Some(n));
                     }
                     reader = reader.with_ignore_errors(options.ignore_errors);
+                    reader = reader.with_dtype_overwrite(dtypes.clone());
                     reader = reader.with_try_parse_dates(options.csv_try_parse_dates());
                     reader = match &nv {
                         Some(n) => {
@@ -2253,6 +2551,7 @@ impl DataTableState {
             return Self::from_csv(paths[0].as_ref(), options);
         }
         let nv = Self::build_null_values_for_csv(options, Some(paths[0].as_ref()))?;
+        let dtypes = crate::schema_override::schema(options)?;
         let mut lazy_frames = Vec::with_capacity(paths.len());
         for p in paths {
             let pl_path = PlPath::Local(Arc::from(p.as_ref()));
@@ -2266,10 +2565,12 @@ impl DataTableState {
             if let Some(has_header) = options.has_header {
                 reader = reader.with_has_header(has_header);
             }
+            reader = Self::csv_dialect_scan(reader, options);
             if let Some(n) = options.infer_schema_length {
                 reader = reader.with_infer_schema_length(Some(n));
             }
             reader = reader.with_ignore_errors(options.ignore_errors);
+            reader = reader.with_dtype_overwrite(dtypes.clone());
             reader = reader.with_try_parse_dates(options.csv_try_parse_dates());
             reader = match &nv {
                 Some(n) => reader.map_parse_options(|opts| opts.with_null_values(Some(n.clone()))),
@@ -2436,6 +2737,34 @@ impl DataTableState {
         Ok(state)
     }
 
+    /// Load a JSON Lines file without the lines that don't parse, keeping them in `load_report`.
+    pub fn from_json_lines_skipping_bad_rows(path: &Path, options: &OpenOptions) -> Result<Self> {
+        let temp_dir = options.temp_dir.clone().unwrap_or_else(std::env::temp_dir);
+        let compression = options
+            .compression
+            .or_else(|| CompressionFormat::from_extension(path));
+        let reader = Self::open_decompressed(path, compression)?;
+        let mut filtered = NamedTempFile::new_in(&temp_dir)?;
+        let report = crate::load_report::filter_json_lines(
+            path,
+            reader,
+            std::io::BufWriter::new(filtered.as_file_mut()),
+            options,
+        )?;
+        filtered.as_file_mut().sync_all()?;
+        let mut state = Self::from_json_lines(
+            filtered.path(),
+            options.pages_lookahead,
+            options.pages_lookback,
+            options.max_buffered_rows,
+            options.max_buffered_mb,
+            options.row_numbers,
+            options.row_start_index,
+        )?;
+        state.load_report = Some(report);
+        Ok(state)
+    }
+
     /// Load multiple JSON (array) files and concatenate into one LazyFrame.
     pub fn from_json_paths(
         paths: &[impl AsRef<Path>],
@@ -2536,8 +2865,15 @@ impl DataTableState {
     }
 
     pub fn from_delimited(path: &Path, delimiter: u8, options: &OpenOptions) -> Result<Self> {
+        if options.escape_char.is_some() || options.skip_bad_rows {
+            let mut csv_options = options.clone();
+            csv_options.delimiter = Some(delimiter);
+            return Self::from_csv(path, &csv_options);
+        }
         let pl_path = PlPath::Local(Arc::from(path));
-        let mut reader = LazyCsvReader::new(pl_path).with_separator(delimiter);
+        let mut reader = Self::csv_dialect_scan(LazyCsvReader::new(pl_path), options)
+            .with_separator(delimiter)
+            .with_dtype_overwrite(crate::schema_override::schema(options)?);
         if let Some(skip_lines) = options.skip_lines {
             reader = reader.with_skip_lines(skip_lines);
         }
@@ -2595,6 +2931,17 @@ impl DataTableState {
         !within_buffer
     }
 
+    /// True when the buffered rows span the whole visible window.
+    fn buffer_covers_view(&self) -> bool {
+        let view_end = self.start_row
+            + self
+                .visible_rows
+                .min(self.num_rows_bound().saturating_sub(self.start_row));
+        self.buffered_df.is_some()
+            && self.start_row >= self.buffered_start_row
+            && view_end <= self.buffered_end_row
+    }
+
     /// Update scroll position. If the view is within the buffer, re-slices display.
     /// If outside the buffer, sets the position but the caller must trigger a collect
     /// (synchronous or async) to load the new buffer range.
@@ -3060,11 +3407,13 @@ impl DataTableState {
     /// Invalidate num_rows cache when lf is mutated. Bumps `len_generation` so any
     /// in-flight background count for the previous `lf` is recognized as stale. Also drops
     /// the cheap Parquet-footer count source: once `lf` carries a filter/query/group, the
-    /// row count no longer equals the sum of file footers.
+    /// row count no longer equals the sum of file footers. Pinned rows are unpinned, since
+    /// their row numbers belong to the previous `lf`.
     fn invalidate_num_rows(&mut self) {
         self.num_rows_valid = false;
-        self.len_generation = self.len_generation.wrapping_add(1);
+        self.len_generation = next_len_generation();
         self.parquet_count_dir = None;
+        self.clear_pinned_rows();
     }
 
     /// Record that the current `lf` is a pristine scan of `dir` (a local Parquet hive
@@ -3121,100 +3470,500 @@ impl DataTableState {
         self.num_rows_valid = true;
     }
 
-    /// Clone of the LazyFrame for off-thread queries (e.g. background len()).
-    pub fn lf_clone(&self) -> LazyFrame {
-        self.lf.clone()
+    /// Clone of the unmodified source LazyFrame (before queries, filters and sorts).
+    pub fn source_lf_clone(&self) -> LazyFrame {
+        self.original_lf.clone()
     }
 
-    /// Whether the current LazyFrame should use Polars streaming engine.
-    pub fn polars_streaming_enabled(&self) -> bool {
-        self.polars_streaming
+    /// Take what the state holds besides its LazyFrame, for the state rebuilt from that
+    /// LazyFrame during phased loading.
+    pub fn take_source_files(&mut self) -> SourceFiles {
+        SourceFiles {
+            temp_file: self.decompress_temp_file.take(),
+            load_report: self.load_report.take(),
+        }
     }
 
-    /// Start row of the currently buffered range.
-    pub fn buffered_start(&self) -> usize {
-        self.buffered_start_row
+    /// Keep the temp file and load report, if any, of the state this one's LazyFrame came from.
+    pub fn set_source_files(&mut self, files: SourceFiles) {
+        if files.temp_file.is_some() {
+            self.decompress_temp_file = files.temp_file;
+        }
+        if files.load_report.is_some() {
+            self.load_report = files.load_report;
+        }
     }
 
-    /// End row (exclusive) of the currently buffered range.
-    pub fn buffered_end(&self) -> usize {
-        self.buffered_end_row
+    /// Replace the source with `df`, the source read into memory, so buffering, queries and
+    /// analysis no longer re-read the input. Only call on a freshly loaded view; any query,
+    /// filter or sort is dropped.
+    pub fn set_in_memory_source(&mut self, df: DataFrame) {
+        self.num_rows = df.height();
+        self.num_rows_valid = true;
+        self.original_lf = df.lazy();
+        self.lf = self.original_lf.clone();
+        self.parquet_count_dir = None;
+        self.in_memory = true;
     }
 
-    /// Clamp buffer to max_buffered_rows; when at cap, slide window to keep view inside.
-    fn clamp_buffer_to_max_size(
-        &self,
-        view_start: usize,
-        view_end: usize,
-        buffer_start: &mut usize,
-        buffer_end: &mut usize,
-    ) {
-        if self.max_buffered_rows == 0 {
-            return;
-        }
-        let max_len = self.max_buffered_rows;
-        let requested_len = buffer_end.saturating_sub(*buffer_start);
-        if requested_len <= max_len {
-            return;
-        }
-        let bound = self.num_rows_bound();
-        let view_len = view_end.saturating_sub(view_start);
-        if view_len >= max_len {
-            *buffer_start = view_start;
-            *buffer_end = (view_start + max_len).min(bound);
-        } else {
-            let half = (max_len - view_len) / 2;
-            *buffer_end = (view_end + half).min(bound);
-            *buffer_start = (*buffer_end).saturating_sub(max_len);
-            if *buffer_start > view_start {
-                *buffer_start = view_start;
-            }
-            *buffer_end = (*buffer_start + max_len).min(bound);
-        }
+    pub fn computed_columns(&self) -> &[ComputedColumn] {
+        &self.computed_columns
     }
 
-    /// Trim a freshly collected buffer `df` (spanning `[buffer_start, buffer_start + df.height())`)
-    /// down to the `max_buffered_mb` byte budget. Crucially, the kept window is centered on the
-    /// current view rather than always taken from the buffer's head — otherwise a jump near the
-    /// END of the dataset drops exactly the rows the view needs, leaving the table blank.
-    /// Returns the (possibly sliced) df and its new `[start, end)` row range.
-    fn clamp_buffer_bytes(&self, df: DataFrame, buffer_start: usize) -> (DataFrame, usize, usize) {
-        let total = df.height();
-        let full_end = buffer_start + total;
-        if self.max_buffered_mb == 0 || total == 0 {
-            return (df, buffer_start, full_end);
-        }
-        let size = df.estimated_size();
-        let max_bytes = self.max_buffered_mb * 1024 * 1024;
-        if size <= max_bytes {
-            return (df, buffer_start, full_end);
+    /// Add `column` to the source, replacing the computed column of the same name. The current
+    /// query, search, filters and sort are applied again so the view shows it.
+    pub fn add_computed_column(&mut self, column: ComputedColumn) -> Result<(), String> {
+        let mut columns = self.computed_columns.clone();
+        match columns.iter_mut().find(|c| c.name == column.name) {
+            Some(existing) => *existing = column,
+            None => columns.push(column),
         }
-        let bytes_per_row = (size / total).max(1);
-        let max_rows = (max_bytes / bytes_per_row).clamp(1, total);
-        if max_rows >= total {
-            return (df, buffer_start, full_end);
+        self.set_computed_columns(columns)
+    }
+
+    /// Replace the computed columns of the source with `columns`. On error (an invalid
+    /// expression or a name already used by the data) nothing changes.
+    pub fn set_computed_columns(&mut self, columns: Vec<ComputedColumn>) -> Result<(), String> {
+        if columns == self.computed_columns {
+            return Ok(());
         }
-        // Keep `max_rows` rows centered on the view so the visible window survives the trim.
-        let view_off = self.start_row.saturating_sub(buffer_start).min(total);
-        let view_len = self.visible_rows.max(1).min(total);
-        let view_center = view_off + view_len / 2;
-        let mut keep_start = view_center.saturating_sub(max_rows / 2);
-        if keep_start + max_rows > total {
-            keep_start = total - max_rows;
+        if self.last_pivot_spec.is_some()
+            || self.last_melt_spec.is_some()
+            || self.last_group_by_spec.is_some()
+            || self.last_resample_spec.is_some()
+        {
+            return Err(
+                "Reset the pivot, melt, group-by or resample before changing computed columns"
+                    .to_string(),
+            );
         }
-        let kept = max_rows.min(total - keep_start);
-        let sliced = df.slice(keep_start as i64, kept);
-        (
-            sliced,
-            buffer_start + keep_start,
-            buffer_start + keep_start + kept,
-        )
+        let existing: Vec<String> = self
+            .computed_columns
+            .iter()
+            .map(|c| c.name.trim().to_string())
+            .collect();
+        let base = self.original_lf.clone().drop(by_name(existing, true));
+        self.original_lf = add_computed_columns(base, &columns)?;
+        self.computed_columns = columns;
+        self.reapply_view();
+        Ok(())
     }
 
-    fn load_buffer(&mut self, buffer_start: usize, buffer_end: usize) {
-        let buffer_size = buffer_end.saturating_sub(buffer_start);
-        if buffer_size == 0 {
-            return;
+    /// Replace a datetime column of the source with the same column in another time zone. The
+    /// current query, search, filters and sort are applied again. On error nothing changes.
+    pub fn change_time_zone(&mut self, change: &TimeZoneChange) -> Result<(), String> {
+        if self.last_pivot_spec.is_some()
+            || self.last_melt_spec.is_some()
+            || self.last_group_by_spec.is_some()
+            || self.last_resample_spec.is_some()
+        {
+            return Err(
+                "Reset the pivot, melt, group-by or resample before changing time zones"
+                    .to_string(),
+            );
+        }
+        if self
+            .computed_columns
+            .iter()
+            .any(|c| c.name == change.column)
+        {
+            return Err(format!(
+                "'{}' is a computed column: change its time zone in its expression",
+                change.column
+            ));
+        }
+        let schema = self
+            .original_lf
+            .clone()
+            .collect_schema()
+            .map_err(|e| user_message_from_polars(&e))?;
+        let aware = match schema.get(change.column.as_str()) {
+            Some(DataType::Datetime(_, tz)) => tz.is_some(),
+            _ => return Err(format!("'{}' is not a datetime column", change.column)),
+        };
+        let lf = self.original_lf.clone().with_column(change.expr(aware)?);
+        lf.clone()
+            .collect_schema()
+            .map_err(|e| user_message_from_polars(&e))?;
+        self.original_lf = lf;
+        self.reapply_view();
+        Ok(())
+    }
+
+    pub fn column_casts(&self) -> &[ColumnCast] {
+        &self.column_casts
+    }
+
+    /// Read columns of the source as other types, in order. The current query, search, filters
+    /// and sort are applied again. On error (an invalid cast, or a strict one meeting a value it
+    /// can't convert) nothing changes.
+    pub fn cast_columns(&mut self, casts: &[ColumnCast]) -> Result<(), String> {
+        if self.last_pivot_spec.is_some()
+            || self.last_melt_spec.is_some()
+            || self.last_group_by_spec.is_some()
+            || self.last_resample_spec.is_some()
+        {
+            return Err(
+                "Reset the pivot, melt, group-by or resample before casting columns".to_string(),
+            );
+        }
+        if let Some(cast) = casts
+            .iter()
+            .find(|cast| self.computed_columns.iter().any(|c| c.name == cast.column))
+        {
+            return Err(format!(
+                "'{}' is a computed column: cast it in its expression",
+                cast.column
+            ));
+        }
+        self.original_lf = apply_casts(self.original_lf.clone(), casts)?;
+        self.column_casts.extend(casts.iter().cloned());
+        self.reapply_view();
+        Ok(())
+    }
+
+    /// The source for a script to transform; refused while a pivot, melt, group-by or resample
+    /// is shown.
+    pub fn script_source(&self) -> Result<LazyFrame, String> {
+        if self.last_pivot_spec.is_some()
+            || self.last_melt_spec.is_some()
+            || self.last_group_by_spec.is_some()
+            || self.last_resample_spec.is_some()
+        {
+            return Err(
+                "Reset the pivot, melt, group-by or resample before running a script".to_string(),
+            );
+        }
+        Ok(self.original_lf.clone())
+    }
+
+    /// Replace the source with `lf`, e.g. a script's result. Filters, sort columns and computed
+    /// columns on columns the result no longer has are dropped, and the current query, search,
+    /// filters and sort are applied again. On error nothing changes.
+    pub fn replace_source(&mut self, lf: LazyFrame) -> Result<(), String> {
+        let schema = lf
+            .clone()
+            .collect_schema()
+            .map_err(|e| user_message_from_polars(&e))?;
+        self.filters.retain(|f| schema.contains(&f.column));
+        self.sort_columns.retain(|c| schema.contains(c));
+        self.computed_columns.retain(|c| schema.contains(&c.name));
+        self.original_lf = lf;
+        self.reapply_view();
+        Ok(())
+    }
+
+    pub fn column_renames(&self) -> &[ColumnRename] {
+        &self.column_renames
+    }
+
+    /// Give columns of the source other names, in order. Filters, sort, column order, formats,
+    /// widths and casts follow the new names, and the view is applied again. Renaming is refused
+    /// while a query is active (it names the columns) or when a computed column would lose a
+    /// column it uses. On error nothing changes.
+    pub fn rename_columns(&mut self, renames: &[ColumnRename]) -> Result<(), String> {
+        if self.last_pivot_spec.is_some()
+            || self.last_melt_spec.is_some()
+            || self.last_group_by_spec.is_some()
+            || self.last_resample_spec.is_some()
+        {
+            return Err(
+                "Reset the pivot, melt, group-by or resample before renaming columns".to_string(),
+            );
+        }
+        if !self.active_query.trim().is_empty() || !self.active_sql_query.trim().is_empty() {
+            return Err("Clear the query before renaming columns".to_string());
+        }
+        let computed: Vec<String> = self
+            .computed_columns
+            .iter()
+            .map(|c| c.name.trim().to_string())
+            .collect();
+        let mut base = self
+            .original_lf
+            .clone()
+            .drop(by_name(computed.clone(), true));
+        for rename in renames {
+            if computed.contains(&rename.from) {
+                return Err(format!(
+                    "'{}' is a computed column: rename it in the New Column modal",
+                    rename.from
+                ));
+            }
+            let to = rename.to.trim();
+            if to.is_empty() {
+                return Err(format!("New name of '{}' is empty", rename.from));
+            }
+            let schema = base
+                .clone()
+                .collect_schema()
+                .map_err(|e| user_message_from_polars(&e))?;
+            if !schema.contains(&rename.from) {
+                return Err(format!("Column '{}' not found", rename.from));
+            }
+            if schema.contains(to) || computed.iter().any(|c| c == to) {
+                return Err(format!("Column '{}' already exists", to));
+            }
+            base = base.rename([rename.from.as_str()], [to], true);
+        }
+        let lf = add_computed_columns(base, &self.computed_columns)
+            .map_err(|e| format!("A computed column uses a renamed column: {}", e))?;
+
+        let rename_name = |name: &mut String| {
+            if let Some(rename) = renames.iter().find(|r| r.from == *name) {
+                *name = rename.to.trim().to_string();
+            }
+        };
+        for name in self
+            .column_order
+            .iter_mut()
+            .chain(self.sort_columns.iter_mut())
+        {
+            rename_name(name);
+        }
+        for filter in self.filters.iter_mut() {
+            rename_name(&mut filter.column);
+        }
+        for cast in self.column_casts.iter_mut() {
+            rename_name(&mut cast.column);
+        }
+        for rename in renames {
+            let to = rename.to.trim().to_string();
+            if let Some(format) = self.column_formats.remove(&rename.from) {
+                self.column_formats.insert(to.clone(), format);
+            }
+            if let Some(width) = self.column_widths.remove(&rename.from) {
+                self.column_widths.insert(to.clone(), width);
+            }
+            self.column_renames.push(ColumnRename {
+                from: rename.from.clone(),
+                to,
+            });
+        }
+        self.original_lf = lf;
+        self.reapply_view();
+        Ok(())
+    }
+
+    /// Put back a source with the computed columns, casts and renames made on it, saved before
+    /// changes that are being undone. The view isn't applied again.
+    pub fn restore_source_changes(
+        &mut self,
+        source: LazyFrame,
+        computed_columns: Vec<ComputedColumn>,
+        casts: Vec<ColumnCast>,
+        renames: Vec<ColumnRename>,
+    ) {
+        self.original_lf = source;
+        self.computed_columns = computed_columns;
+        self.column_casts = casts;
+        self.column_renames = renames;
+    }
+
+    /// Expand a nested column of the source: a struct column is replaced by one column per
+    /// field, named `column_field`, in its place; a list column gets one row per item (an empty
+    /// or null list keeps one row with a null). The current query, search, filters and sort are
+    /// applied again. On error nothing changes.
+    pub fn expand_column(&mut self, column: &str) -> Result<(), String> {
+        if self.last_pivot_spec.is_some()
+            || self.last_melt_spec.is_some()
+            || self.last_group_by_spec.is_some()
+            || self.last_resample_spec.is_some()
+        {
+            return Err(
+                "Reset the pivot, melt, group-by or resample before expanding columns".to_string(),
+            );
+        }
+        if self.computed_columns.iter().any(|c| c.name == column) {
+            return Err(format!(
+                "'{column}' is a computed column: expand it in its expression"
+            ));
+        }
+        let schema = self
+            .original_lf
+            .clone()
+            .collect_schema()
+            .map_err(|e| user_message_from_polars(&e))?;
+        let (lf, fields) = match schema.get(column) {
+            Some(DataType::Struct(fields)) => (
+                self.original_lf
+                    .clone()
+                    .unnest(by_name([column], true), Some("_".into())),
+                fields
+                    .iter()
+                    .map(|f| format!("{column}_{}", f.name))
+                    .collect::<Vec<_>>(),
+            ),
+            Some(DataType::List(_)) => (
+                self.original_lf.clone().explode(by_name([column], true)),
+                vec![column.to_string()],
+            ),
+            _ => return Err(format!("'{column}' is not a struct or list column")),
+        };
+        lf.clone()
+            .collect_schema()
+            .map_err(|e| user_message_from_polars(&e))?;
+        self.original_lf = lf;
+        let position = self.column_order.iter().position(|c| c == column);
+        let mut order = Vec::with_capacity(self.column_order.len() + fields.len());
+        for name in &self.column_order {
+            if name == column {
+                order.extend(fields.iter().cloned());
+            } else {
+                order.push(name.clone());
+            }
+        }
+        self.column_order = order;
+        // A locked struct column stays locked as its fields.
+        if position.is_some_and(|p| p < self.locked_columns_count) {
+            self.locked_columns_count += fields.len().saturating_sub(1);
+        }
+        self.reapply_view();
+        Ok(())
+    }
+
+    /// Rebuild the view from `original_lf` with the current query (or SQL, or fuzzy search),
+    /// filters and sort, keeping the column order and locks. New columns go at the end.
+    fn reapply_view(&mut self) {
+        let query = self.active_query.clone();
+        let sql = self.active_sql_query.clone();
+        let fuzzy = self.active_fuzzy_query.clone();
+        let filters = self.filters.clone();
+        let sort_columns = self.sort_columns.clone();
+        let sort_ascending = self.sort_ascending;
+        let column_order = self.column_order.clone();
+        let locked_columns_count = self.locked_columns_count;
+        let defer_collect = self.defer_collect;
+        self.defer_collect = true;
+        self.reset_lf_to_original();
+        if !sql.trim().is_empty() {
+            self.sql_query(sql);
+        } else if !query.trim().is_empty() {
+            self.query(query);
+        } else if !fuzzy.trim().is_empty() {
+            self.fuzzy_search(fuzzy);
+        } else if !filters.is_empty() || !sort_columns.is_empty() || !sort_ascending {
+            self.filters = filters;
+            self.sort_columns = sort_columns;
+            self.sort_ascending = sort_ascending;
+            self.apply_transformations();
+        }
+        let mut order: Vec<String> = column_order
+            .into_iter()
+            .filter(|c| self.schema.contains(c))
+            .collect();
+        let added: Vec<String> = self
+            .schema
+            .iter_names()
+            .map(|s| s.to_string())
+            .filter(|c| !order.contains(c))
+            .collect();
+        order.extend(added);
+        self.column_order = order;
+        self.locked_columns_count = locked_columns_count.min(self.column_order.len());
+        self.defer_collect = defer_collect;
+        self.collect();
+    }
+
+    /// Whether the source has been read into memory.
+    pub fn is_in_memory(&self) -> bool {
+        self.in_memory
+    }
+
+    /// Clone of the LazyFrame for off-thread queries (e.g. background len()).
+    pub fn lf_clone(&self) -> LazyFrame {
+        self.lf.clone()
+    }
+
+    /// Whether the current LazyFrame should use Polars streaming engine.
+    pub fn polars_streaming_enabled(&self) -> bool {
+        self.polars_streaming
+    }
+
+    /// Start row of the currently buffered range.
+    pub fn buffered_start(&self) -> usize {
+        self.buffered_start_row
+    }
+
+    /// End row (exclusive) of the currently buffered range.
+    pub fn buffered_end(&self) -> usize {
+        self.buffered_end_row
+    }
+
+    /// Clamp buffer to max_buffered_rows; when at cap, slide window to keep view inside.
+    fn clamp_buffer_to_max_size(
+        &self,
+        view_start: usize,
+        view_end: usize,
+        buffer_start: &mut usize,
+        buffer_end: &mut usize,
+    ) {
+        if self.max_buffered_rows == 0 {
+            return;
+        }
+        let max_len = self.max_buffered_rows;
+        let requested_len = buffer_end.saturating_sub(*buffer_start);
+        if requested_len <= max_len {
+            return;
+        }
+        let bound = self.num_rows_bound();
+        let view_len = view_end.saturating_sub(view_start);
+        if view_len >= max_len {
+            *buffer_start = view_start;
+            *buffer_end = (view_start + max_len).min(bound);
+        } else {
+            let half = (max_len - view_len) / 2;
+            *buffer_end = (view_end + half).min(bound);
+            *buffer_start = (*buffer_end).saturating_sub(max_len);
+            if *buffer_start > view_start {
+                *buffer_start = view_start;
+            }
+            *buffer_end = (*buffer_start + max_len).min(bound);
+        }
+    }
+
+    /// Trim a freshly collected buffer `df` (spanning `[buffer_start, buffer_start + df.height())`)
+    /// down to the `max_buffered_mb` byte budget. Crucially, the kept window is centered on the
+    /// current view rather than always taken from the buffer's head — otherwise a jump near the
+    /// END of the dataset drops exactly the rows the view needs, leaving the table blank.
+    /// Returns the (possibly sliced) df and its new `[start, end)` row range.
+    fn clamp_buffer_bytes(&self, df: DataFrame, buffer_start: usize) -> (DataFrame, usize, usize) {
+        let total = df.height();
+        let full_end = buffer_start + total;
+        if self.max_buffered_mb == 0 || total == 0 {
+            return (df, buffer_start, full_end);
+        }
+        let size = df.estimated_size();
+        let max_bytes = self.max_buffered_mb * 1024 * 1024;
+        if size <= max_bytes {
+            return (df, buffer_start, full_end);
+        }
+        let bytes_per_row = (size / total).max(1);
+        let max_rows = (max_bytes / bytes_per_row).clamp(1, total);
+        if max_rows >= total {
+            return (df, buffer_start, full_end);
+        }
+        // Keep `max_rows` rows centered on the view so the visible window survives the trim.
+        let view_off = self.start_row.saturating_sub(buffer_start).min(total);
+        let view_len = self.visible_rows.max(1).min(total);
+        let view_center = view_off + view_len / 2;
+        let mut keep_start = view_center.saturating_sub(max_rows / 2);
+        if keep_start + max_rows > total {
+            keep_start = total - max_rows;
+        }
+        let kept = max_rows.min(total - keep_start);
+        let sliced = df.slice(keep_start as i64, kept);
+        (
+            sliced,
+            buffer_start + keep_start,
+            buffer_start + keep_start + kept,
+        )
+    }
+
+    fn load_buffer(&mut self, buffer_start: usize, buffer_end: usize) {
+        let buffer_size = buffer_end.saturating_sub(buffer_start);
+        if buffer_size == 0 {
+            return;
         }
 
         let all_columns = self.binary_stub_exprs();
@@ -3523,6 +4272,250 @@ impl DataTableState {
         }
     }
 
+    /// The header or cell drawn at terminal position (`column`, `row`) in the last frame.
+    pub fn hit(&self, column: u16, row: u16) -> Option<TableHit> {
+        let area = self.table_area;
+        if row < area.y || row >= area.y + area.height {
+            return None;
+        }
+        let span = self
+            .column_spans
+            .iter()
+            .find(|s| column >= s.x && column < s.x + s.width)?
+            .clone();
+        if row == area.y {
+            return Some(TableHit::Header(span));
+        }
+        let shown = self
+            .visible_rows
+            .min(self.num_rows.saturating_sub(self.start_row));
+        // Pinned rows (drawn under the header) can't be selected
+        let pinned = self
+            .pinned_rows
+            .len()
+            .min(area.height.saturating_sub(2) as usize);
+        let index = ((row - area.y - 1) as usize).checked_sub(pinned)?;
+        (index < shown).then_some(TableHit::Cell(index, span))
+    }
+
+    /// Move the cursor to a cell: select row `row` on screen and, for a scrollable column, scroll
+    /// so it is the leftmost one (the selected column).
+    pub fn select_cell(&mut self, row: usize, span: &ColumnSpan) {
+        self.table_state.select(Some(row));
+        if span.locked {
+            return;
+        }
+        let scrollable =
+            &self.column_order[self.locked_columns_count.min(self.column_order.len())..];
+        if let Some(index) = scrollable.iter().position(|c| c == &span.name) {
+            if index != self.termcol_index {
+                self.termcol_index = index;
+                self.collect();
+            }
+        }
+    }
+
+    /// Scroll so the column at `index` of the column order is the leftmost scrolled one (the
+    /// selected column). Locked columns are always on screen, so they don't scroll.
+    pub fn scroll_to_column(&mut self, index: usize) {
+        let Some(scroll) = index.checked_sub(self.locked_columns_count) else {
+            return;
+        };
+        if index < self.column_order.len() && scroll != self.termcol_index {
+            self.termcol_index = scroll;
+            self.collect();
+        }
+    }
+
+    /// Widen (`delta` > 0) or narrow the selected column from its set width, or the width it
+    /// was last drawn with, keeping at least `MIN_COLUMN_WIDTH` and at most the table's width.
+    pub fn resize_selected_column(&mut self, delta: i32) {
+        let Some(span) = self.selected_column_span() else {
+            return;
+        };
+        let current = self
+            .column_widths
+            .get(&span.name)
+            .copied()
+            .unwrap_or(span.width);
+        let width = (current as i32 + delta).clamp(
+            MIN_COLUMN_WIDTH as i32,
+            self.table_area.width.max(MIN_COLUMN_WIDTH) as i32,
+        );
+        self.column_widths.insert(span.name, width as u16);
+    }
+
+    /// Fit the selected column to its content (past `display.max_column_width`); when it
+    /// already has a set width, go back to the automatic width instead.
+    pub fn autofit_selected_column(&mut self) {
+        let Some(span) = self.selected_column_span() else {
+            return;
+        };
+        if self.column_widths.remove(&span.name).is_none() {
+            let width = span
+                .content_width
+                .min(self.table_area.width.max(MIN_COLUMN_WIDTH));
+            self.column_widths.insert(span.name, width);
+        }
+    }
+
+    fn selected_column_span(&self) -> Option<ColumnSpan> {
+        let name = self.selected_column()?;
+        self.column_spans.iter().find(|s| s.name == name).cloned()
+    }
+
+    /// Whether `column` was drawn narrower than its values in the last frame.
+    pub fn is_column_cut(&self, column: &str) -> bool {
+        self.column_spans
+            .iter()
+            .any(|s| s.name == column && s.width < s.content_width)
+    }
+
+    /// The first scrollable (non-locked) column on screen; horizontal scrolling moves it.
+    pub fn selected_column(&self) -> Option<&str> {
+        self.column_order
+            .get(self.locked_columns_count + self.termcol_index)
+            .map(|s| s.as_str())
+    }
+
+    /// Mark or unmark the current column (see [`Self::selected_column`]). Returns the column
+    /// and whether it is now marked.
+    pub fn toggle_column_selection(&mut self) -> Option<(String, bool)> {
+        let name = self.selected_column()?.to_string();
+        if let Some(pos) = self.selected_columns.iter().position(|c| c == &name) {
+            self.selected_columns.remove(pos);
+            Some((name, false))
+        } else {
+            self.selected_columns.push(name.clone());
+            Some((name, true))
+        }
+    }
+
+    pub fn clear_column_selection(&mut self) {
+        self.selected_columns.clear();
+    }
+
+    /// Start a visual block selection at the selected cell, or cancel the one in progress.
+    /// A row selection in progress becomes a block starting at the selected cell.
+    /// Returns whether visual block mode is now on.
+    pub fn toggle_visual_block(&mut self) -> bool {
+        if matches!(self.visual_anchor.take(), Some(VisualAnchor::Block(..))) {
+            return false;
+        }
+        let row = self.table_state.selected().map(|r| self.start_row + r);
+        self.visual_anchor = row
+            .zip(self.selected_column().map(str::to_string))
+            .map(|(row, column)| VisualAnchor::Block(row, column));
+        self.visual_anchor.is_some()
+    }
+
+    /// Start selecting whole rows at the selected row, or cancel the row selection in progress.
+    /// Returns whether row selection is now on.
+    pub fn toggle_row_selection(&mut self) -> bool {
+        if matches!(self.visual_anchor.take(), Some(VisualAnchor::Rows(_))) {
+            return false;
+        }
+        self.visual_anchor = self
+            .table_state
+            .selected()
+            .map(|r| VisualAnchor::Rows(self.start_row + r));
+        self.visual_anchor.is_some()
+    }
+
+    /// End visual block or row selection.
+    pub fn clear_visual_block(&mut self) {
+        self.visual_anchor = None;
+    }
+
+    /// The block between the visual anchor and the selected cell, or None outside visual mode
+    /// (or once the anchor column has left the view). A row selection is a block of all columns.
+    pub fn visual_block(&self) -> Option<VisualBlock> {
+        let row = self.start_row + self.table_state.selected()?;
+        let (anchor_row, columns) = match self.visual_anchor.as_ref()? {
+            VisualAnchor::Rows(anchor_row) => (*anchor_row, self.column_order.clone()),
+            VisualAnchor::Block(anchor_row, anchor_column) => {
+                let anchor = self.column_order.iter().position(|c| c == anchor_column)?;
+                let cursor = self.locked_columns_count + self.termcol_index;
+                if cursor >= self.column_order.len() {
+                    return None;
+                }
+                let columns = self.column_order[anchor.min(cursor)..=anchor.max(cursor)].to_vec();
+                (*anchor_row, columns)
+            }
+        };
+        let first_row = anchor_row.min(row);
+        Some(VisualBlock {
+            first_row,
+            rows: anchor_row.max(row) - first_row + 1,
+            columns,
+        })
+    }
+
+    /// (offset, len) of the rows selected with `m`, in the current view. None outside row
+    /// selection.
+    pub fn selected_rows(&self) -> Option<(usize, usize)> {
+        if !matches!(self.visual_anchor, Some(VisualAnchor::Rows(_))) {
+            return None;
+        }
+        self.visual_block().map(|b| (b.first_row, b.rows))
+    }
+
+    /// The selected cell, or the whole selected row with `whole_row`, as a one-row block.
+    pub fn selected_row_block(&self, whole_row: bool) -> Option<VisualBlock> {
+        let row = self.start_row + self.table_state.selected()?;
+        let columns = if whole_row {
+            self.column_order.clone()
+        } else {
+            vec![self.selected_column()?.to_string()]
+        };
+        Some(VisualBlock {
+            first_row: row,
+            rows: 1,
+            columns,
+        })
+    }
+
+    /// Marked columns still present in the view, in the order they were marked.
+    pub fn selected_columns(&self) -> Vec<String> {
+        self.selected_columns
+            .iter()
+            .filter(|c| self.schema.contains(c.as_str()))
+            .cloned()
+            .collect()
+    }
+
+    /// Full text of `column` in the selected row, from the display buffer. None when the row or
+    /// column isn't buffered; an empty string for null.
+    pub fn selected_cell_value(&self, column: &str) -> Option<String> {
+        let value = self.selected_cell_any_value(column)?;
+        Some(if value.is_null() {
+            String::new()
+        } else {
+            value.str_value().into_owned()
+        })
+    }
+
+    /// Value of `column` in the selected row, as stored (before any display formatting).
+    pub fn selected_cell_any_value(&self, column: &str) -> Option<AnyValue<'static>> {
+        Some(
+            self.selected_cell_series(column)?
+                .get(0)
+                .ok()?
+                .into_static(),
+        )
+    }
+
+    /// The selected row's cell of `column` as a one-value series.
+    pub fn selected_cell_series(&self, column: &str) -> Option<Series> {
+        let row =
+            self.start_row.checked_sub(self.buffered_start_row)? + self.table_state.selected()?;
+        let column = [self.df.as_ref(), self.locked_df.as_ref()]
+            .into_iter()
+            .flatten()
+            .find_map(|df| df.column(column).ok())?;
+        (row < column.len()).then(|| column.as_materialized_series().slice(row as i64, 1))
+    }
+
     pub fn headers(&self) -> Vec<String> {
         self.column_order.clone()
     }
@@ -3535,6 +4528,78 @@ impl DataTableState {
         self.collect();
     }
 
+    /// Rows pinned to the top of the table, by row number in the view.
+    pub fn pinned_rows(&self) -> &[usize] {
+        &self.pinned_rows
+    }
+
+    /// Pin the selected row to the top of the table, or unpin it when it is pinned. Returns
+    /// whether the row is pinned now. Pins belong to the view: a new query, filter or sort
+    /// clears them.
+    pub fn toggle_pinned_row(&mut self) -> Result<bool, String> {
+        let row = self.start_row + self.table_state.selected().ok_or("No row selected")?;
+        let index = self.pinned_rows.partition_point(|&r| r < row);
+        let pinned = self.pinned_df.clone().unwrap_or_default();
+        let (before, after) = (
+            pinned.slice(0, index),
+            pinned.slice(index as i64, usize::MAX),
+        );
+        if self.pinned_rows.get(index) == Some(&row) {
+            self.pinned_rows.remove(index);
+            self.pinned_df = before
+                .vstack(&after.slice(1, usize::MAX))
+                .ok()
+                .filter(|df| df.height() > 0);
+            return Ok(false);
+        }
+        if self.pinned_rows.len() >= MAX_PINNED_ROWS {
+            return Err(format!("At most {MAX_PINNED_ROWS} rows can be pinned"));
+        }
+        let values = self
+            .buffered_df
+            .as_ref()
+            .zip(row.checked_sub(self.buffered_start_row))
+            .filter(|(df, offset)| *offset < df.height())
+            .map(|(df, offset)| df.slice(offset as i64, 1))
+            .ok_or("The selected row isn't loaded yet")?;
+        let df = if pinned.width() == 0 {
+            values
+        } else {
+            before
+                .vstack(&values)
+                .and_then(|df| df.vstack(&after))
+                .map_err(|e| user_message_from_polars(&e))?
+        };
+        self.pinned_rows.insert(index, row);
+        self.pinned_df = Some(df);
+        Ok(true)
+    }
+
+    pub fn clear_pinned_rows(&mut self) {
+        self.pinned_rows.clear();
+        self.pinned_df = None;
+    }
+
+    /// `sliced` (visible rows of the buffer) with the first `count` pinned rows on top, in the
+    /// same columns and formatting. Unchanged when there are none or they can't be joined.
+    fn with_pinned_rows(&self, sliced: DataFrame, count: usize) -> DataFrame {
+        let Some(pinned) = self.pinned_df.as_ref().filter(|_| count > 0) else {
+            return sliced;
+        };
+        let pinned = pinned
+            .head(Some(count))
+            .select(sliced.get_column_names_owned());
+        let pinned = match pinned {
+            Ok(df) if self.is_grouped() => self.format_grouped_dataframe(df).ok(),
+            Ok(df) => Some(df),
+            Err(_) => None,
+        };
+        match pinned.map(|p| p.vstack(&sliced)) {
+            Some(Ok(df)) => df,
+            _ => sliced,
+        }
+    }
+
     pub fn set_locked_columns(&mut self, count: usize) {
         self.locked_columns_count = count.min(self.column_order.len());
         self.buffered_start_row = 0;
@@ -3572,10 +4637,29 @@ impl DataTableState {
         &self.active_sql_query
     }
 
+    /// Pattern of the regex search, empty when there is none.
+    pub fn regex_search_pattern(&self) -> &str {
+        self.regex_search
+            .as_ref()
+            .map_or("", |search| search.pattern.as_str())
+    }
+
     pub fn get_active_fuzzy_query(&self) -> &str {
         &self.active_fuzzy_query
     }
 
+    /// Engine of the query shaping the view, if any. SQL runs on top of the current view, so it
+    /// wins when both are set.
+    pub fn query_engine(&self) -> Option<QueryEngine> {
+        if !self.active_sql_query.trim().is_empty() {
+            Some(QueryEngine::Sql)
+        } else if !self.active_query.trim().is_empty() {
+            Some(QueryEngine::DatuiExpr)
+        } else {
+            None
+        }
+    }
+
     pub fn last_pivot_spec(&self) -> Option<&PivotSpec> {
         self.last_pivot_spec.as_ref()
     }
@@ -3584,6 +4668,14 @@ impl DataTableState {
         self.last_melt_spec.as_ref()
     }
 
+    pub fn last_group_by_spec(&self) -> Option<&GroupBySpec> {
+        self.last_group_by_spec.as_ref()
+    }
+
+    pub fn last_resample_spec(&self) -> Option<&ResampleSpec> {
+        self.last_resample_spec.as_ref()
+    }
+
     pub fn is_grouped(&self) -> bool {
         self.schema
             .iter()
@@ -3880,6 +4972,8 @@ impl DataTableState {
 
         self.last_pivot_spec = Some(spec.clone());
         self.last_melt_spec = None;
+        self.last_group_by_spec = None;
+        self.last_resample_spec = None;
         self.replace_lf_after_reshape(pivoted.lazy())?;
         Ok(())
     }
@@ -3897,10 +4991,39 @@ impl DataTableState {
         let lf = self.lf.clone().unpivot(args);
         self.last_melt_spec = Some(spec.clone());
         self.last_pivot_spec = None;
+        self.last_group_by_spec = None;
+        self.last_resample_spec = None;
         self.replace_lf_after_reshape(lf)?;
         Ok(())
     }
 
+    /// Group the current `LazyFrame` and aggregate it. Never uses `original_lf`. The group
+    /// columns come first and are locked.
+    pub fn group_by(&mut self, spec: &GroupBySpec) -> Result<()> {
+        spec.validate().map_err(|e| color_eyre::eyre::eyre!(e))?;
+        let grouped = spec.apply(self.lf.clone());
+        self.replace_lf_after_reshape(grouped)?;
+        self.last_group_by_spec = Some(spec.clone());
+        self.last_pivot_spec = None;
+        self.last_melt_spec = None;
+        self.last_resample_spec = None;
+        self.set_locked_columns(spec.group_columns.len());
+        Ok(())
+    }
+
+    /// Resample the current `LazyFrame` into time windows. Never uses `original_lf`. The time
+    /// column comes first and is locked.
+    pub fn resample(&mut self, spec: &ResampleSpec) -> Result<()> {
+        let resampled = spec.apply(self.lf.clone())?;
+        self.replace_lf_after_reshape(resampled)?;
+        self.last_resample_spec = Some(spec.clone());
+        self.last_pivot_spec = None;
+        self.last_melt_spec = None;
+        self.last_group_by_spec = None;
+        self.set_locked_columns(1);
+        Ok(())
+    }
+
     fn replace_lf_after_reshape(&mut self, lf: LazyFrame) -> Result<()> {
         self.invalidate_num_rows();
         self.lf = lf;
@@ -3929,45 +5052,51 @@ impl DataTableState {
         Ok(())
     }
 
+    /// Columns a query can use: those of the data before any query, with computed columns.
+    pub fn query_columns(&self) -> Vec<String> {
+        self.original_lf
+            .clone()
+            .collect_schema()
+            .map(|schema| schema.iter_names().map(|n| n.to_string()).collect())
+            .unwrap_or_else(|_| self.column_order.clone())
+    }
+
     pub fn is_drilled_down(&self) -> bool {
         self.drilled_down_group_index.is_some()
     }
 
-    fn apply_transformations(&mut self) {
-        let mut lf = self.original_lf.clone();
+    /// All of `filters` as one expression, values typed by the view's schema.
+    fn filters_expr(&self, filters: &[FilterStatement]) -> Option<Expr> {
         let mut final_expr: Option<Expr> = None;
 
-        for filter in &self.filters {
+        for filter in filters {
             let col_expr = col(&filter.column);
-            let val_lit = if let Some(dtype) = self.schema.get(&filter.column) {
-                match dtype {
-                    DataType::Float32 | DataType::Float64 => filter
-                        .value
-                        .parse::<f64>()
-                        .map(lit)
-                        .unwrap_or_else(|_| lit(filter.value.as_str())),
-                    DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => filter
-                        .value
-                        .parse::<i64>()
-                        .map(lit)
-                        .unwrap_or_else(|_| lit(filter.value.as_str())),
-                    DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
-                        filter
-                            .value
-                            .parse::<u64>()
-                            .map(lit)
-                            .unwrap_or_else(|_| lit(filter.value.as_str()))
-                    }
-                    DataType::Boolean => filter
-                        .value
-                        .parse::<bool>()
-                        .map(lit)
-                        .unwrap_or_else(|_| lit(filter.value.as_str())),
-                    _ => lit(filter.value.as_str()),
+            let typed_lit = |value: &str| match self.schema.get(&filter.column) {
+                Some(DataType::Float32 | DataType::Float64) => {
+                    value.parse::<f64>().map(lit).unwrap_or_else(|_| lit(value))
                 }
-            } else {
-                lit(filter.value.as_str())
+                Some(DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64) => {
+                    value.parse::<i64>().map(lit).unwrap_or_else(|_| lit(value))
+                }
+                Some(DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64) => {
+                    value.parse::<u64>().map(lit).unwrap_or_else(|_| lit(value))
+                }
+                Some(DataType::Boolean) => value
+                    .parse::<bool>()
+                    .map(lit)
+                    .unwrap_or_else(|_| lit(value)),
+                // Dates and times in any format Polars recognizes
+                Some(DataType::Date) => lit(value).str().to_date(StrptimeOptions::default()),
+                Some(DataType::Datetime(time_unit, time_zone)) => lit(value).str().to_datetime(
+                    Some(*time_unit),
+                    time_zone.clone(),
+                    StrptimeOptions::default(),
+                    lit("raise"),
+                ),
+                Some(DataType::Time) => lit(value).str().to_time(StrptimeOptions::default()),
+                _ => lit(value),
             };
+            let val_lit = typed_lit(filter.value.as_str());
 
             let op_expr = match filter.operator {
                 FilterOperator::Eq => col_expr.eq(val_lit),
@@ -3976,6 +5105,19 @@ impl DataTableState {
                 FilterOperator::Lt => col_expr.lt(val_lit),
                 FilterOperator::GtEq => col_expr.gt_eq(val_lit),
                 FilterOperator::LtEq => col_expr.lt_eq(val_lit),
+                FilterOperator::Between => {
+                    let values = filter.values();
+                    let low = typed_lit(values[0]);
+                    // A single value is both bounds
+                    let high = typed_lit(values.get(1).copied().unwrap_or(values[0]));
+                    col_expr.clone().gt_eq(low).and(col_expr.lt_eq(high))
+                }
+                FilterOperator::In => filter
+                    .values()
+                    .into_iter()
+                    .map(|value| col_expr.clone().eq(typed_lit(value)))
+                    .reduce(|any, eq| any.or(eq))
+                    .unwrap_or_else(|| lit(false)),
                 FilterOperator::Contains => {
                     let val = filter.value.clone();
                     col_expr.str().contains_literal(lit(val))
@@ -3984,6 +5126,8 @@ impl DataTableState {
                     let val = filter.value.clone();
                     col_expr.str().contains_literal(lit(val)).not()
                 }
+                FilterOperator::IsNull => col_expr.is_null(),
+                FilterOperator::IsNotNull => col_expr.is_not_null(),
             };
 
             if let Some(current) = final_expr {
@@ -3995,8 +5139,13 @@ impl DataTableState {
                 final_expr = Some(op_expr);
             }
         }
+        final_expr
+    }
+
+    fn apply_transformations(&mut self) {
+        let mut lf = self.original_lf.clone();
 
-        if let Some(e) = final_expr {
+        if let Some(e) = self.filters_expr(&self.filters) {
             lf = lf.filter(e);
         }
 
@@ -4060,6 +5209,58 @@ impl DataTableState {
         }
     }
 
+    /// Keep only the rows whose `keys` values occur more than once, with each group's rows
+    /// together (in order of appearance within a group). Like `reverse`, this applies to the
+    /// current view; changing filters or sort, or `R`, starts over from the data.
+    pub fn filter_duplicates(&mut self, keys: &[String]) {
+        let key_exprs: Vec<Expr> = keys.iter().map(|k| col(k.as_str())).collect();
+        self.buffered_start_row = 0;
+        self.buffered_end_row = 0;
+        self.buffered_df = None;
+        self.start_row = 0;
+        self.table_state.select(Some(0));
+        self.invalidate_num_rows();
+        self.lf = self
+            .lf
+            .clone()
+            .filter(crate::duplicates::duplicated_expr(keys))
+            .sort_by_exprs(
+                key_exprs,
+                SortMultipleOptions::default().with_maintain_order(true),
+            );
+        self.collect();
+    }
+
+    /// Keep the rows of the current view passing `filters`, sorted by `sort_columns` if any.
+    /// Unlike `filter` and `sort`, this applies on top of the view (e.g. a query's computed
+    /// columns); like `filter_duplicates`, changing filters or sort, or `R`, starts over.
+    pub fn narrow_view(
+        &mut self,
+        filters: &[FilterStatement],
+        sort_columns: &[String],
+        ascending: bool,
+    ) {
+        let mut lf = self.lf.clone();
+        if let Some(e) = self.filters_expr(filters) {
+            lf = lf.filter(e);
+        }
+        if !sort_columns.is_empty() {
+            let options = SortMultipleOptions {
+                descending: sort_columns.iter().map(|_| !ascending).collect(),
+                ..Default::default()
+            };
+            lf = lf.sort_by_exprs(sort_columns.iter().map(col).collect::<Vec<_>>(), options);
+        }
+        self.buffered_start_row = 0;
+        self.buffered_end_row = 0;
+        self.buffered_df = None;
+        self.start_row = 0;
+        self.table_state.select(Some(0));
+        self.invalidate_num_rows();
+        self.lf = lf;
+        self.collect();
+    }
+
     pub fn filter(&mut self, filters: Vec<FilterStatement>) {
         self.filters = filters;
         self.buffered_start_row = 0;
@@ -4080,69 +5281,19 @@ impl DataTableState {
 
         match parse_query(&query) {
             Ok((cols, filter, group_by_cols, group_by_col_names)) => {
-                let mut lf = self.original_lf.clone();
-                let mut schema_opt: Option<Arc<Schema>> = None;
-
-                // Apply filter first (where clause)
-                if let Some(f) = filter {
-                    lf = lf.filter(f);
-                }
-
-                if !group_by_cols.is_empty() {
-                    if !cols.is_empty() {
-                        lf = lf.group_by(group_by_cols.clone()).agg(cols);
-                    } else {
-                        let schema = match lf.clone().collect_schema() {
-                            Ok(s) => s,
-                            Err(e) => {
-                                self.error = Some(e);
-                                return; // Don't modify state on error
-                            }
-                        };
-                        let all_columns: Vec<String> =
-                            schema.iter_names().map(|s| s.to_string()).collect();
-
-                        // In Polars, when you group_by and aggregate columns without explicit aggregation functions,
-                        // Polars automatically collects the values as lists. We need to aggregate all columns
-                        // except the group columns to avoid duplicates.
-                        let mut agg_exprs = Vec::new();
-                        for col_name in &all_columns {
-                            if !group_by_col_names.contains(col_name) {
-                                agg_exprs.push(col(col_name));
-                            }
-                        }
-
-                        lf = lf.group_by(group_by_cols.clone()).agg(agg_exprs);
+                let applied = apply_query(
+                    self.original_lf.clone(),
+                    cols,
+                    filter,
+                    group_by_cols,
+                    &group_by_col_names,
+                );
+                let (lf, schema) = match applied {
+                    Ok(applied) => applied,
+                    Err(e) => {
+                        self.error = Some(e);
+                        return; // Don't modify state on error
                     }
-                    // Sort by the result's group-key column names (first N columns after agg).
-                    // Works for aliased or plain names without relying on parser-derived names.
-                    let schema = match lf.collect_schema() {
-                        Ok(s) => s,
-                        Err(e) => {
-                            self.error = Some(e);
-                            return;
-                        }
-                    };
-                    schema_opt = Some(schema.clone());
-                    let sort_exprs: Vec<Expr> = schema
-                        .iter_names()
-                        .take(group_by_cols.len())
-                        .map(|n| col(n.as_str()))
-                        .collect();
-                    lf = lf.sort_by_exprs(sort_exprs, Default::default());
-                } else if !cols.is_empty() {
-                    lf = lf.select(cols);
-                }
-
-                let schema = match schema_opt {
-                    Some(s) => s,
-                    None => match lf.collect_schema() {
-                        Ok(s) => s,
-                        Err(e) => {
-                            self.error = Some(e);
-                            return;
-                        }
-                    },
                 };
 
                 self.schema = schema;
@@ -4176,6 +5327,9 @@ impl DataTableState {
                 self.start_row = 0;
                 self.termcol_index = 0;
                 self.active_query = query;
+                // The query starts from the original data, replacing any SQL or fuzzy search
+                self.active_sql_query.clear();
+                self.active_fuzzy_query.clear();
                 self.buffered_start_row = 0;
                 self.buffered_end_row = 0;
                 self.buffered_df = None;
@@ -4262,6 +5416,7 @@ impl DataTableState {
 
     /// Fuzzy search: filter rows where any string column matches the query.
     /// Query is split on whitespace; each token must match (in order, case-insensitive) in some string column.
+    /// A "quoted phrase" must appear as typed (ignoring case) in some string column.
     /// Empty query resets to original_lf.
     pub fn fuzzy_search(&mut self, query: String) {
         self.error = None;
@@ -4283,14 +5438,9 @@ impl DataTableState {
             ));
             return;
         }
-        let tokens: Vec<&str> = trimmed
-            .split_whitespace()
-            .filter(|s| !s.is_empty())
-            .collect();
-        let token_exprs: Vec<Expr> = tokens
+        let token_exprs: Vec<Expr> = fuzzy_query_regexes(trimmed)
             .iter()
-            .map(|token| {
-                let pattern = fuzzy_token_regex(token);
+            .map(|pattern| {
                 string_cols
                     .iter()
                     .map(|c| col(c.as_str()).str().contains(lit(pattern.as_str()), false))
@@ -4298,7 +5448,11 @@ impl DataTableState {
                     .unwrap()
             })
             .collect();
-        let combined = token_exprs.into_iter().reduce(|a, b| a.and(b)).unwrap();
+        let Some(combined) = token_exprs.into_iter().reduce(|a, b| a.and(b)) else {
+            self.reset_lf_to_original();
+            self.collect();
+            return;
+        };
         self.lf = self.original_lf.clone().filter(combined);
         self.filters.clear();
         self.sort_columns.clear();
@@ -4322,6 +5476,22 @@ impl DataTableState {
     }
 }
 
+/// Case-insensitive regexes for a fuzzy search query, one per term: a "quoted phrase" (closing
+/// quote optional) matches as typed, other words fuzzily (see [`fuzzy_token_regex`]).
+pub(crate) fn fuzzy_query_regexes(query: &str) -> Vec<String> {
+    let mut regexes = Vec::new();
+    for (i, part) in query.split('"').enumerate() {
+        if i % 2 == 1 {
+            if !part.trim().is_empty() {
+                regexes.push(format!("(?i){}", regex::escape(part)));
+            }
+        } else {
+            regexes.extend(part.split_whitespace().map(fuzzy_token_regex));
+        }
+    }
+    regexes
+}
+
 /// Case-insensitive regex for one token: chars in order with `.*` between.
 pub(crate) fn fuzzy_token_regex(token: &str) -> String {
     let inner: String =
@@ -4358,6 +5528,27 @@ pub struct DataTable {
     /// Names of columns that are binary in the source schema. Their cells hold the `‹binary›`
     /// stub (see [`BINARY_STUB`]) and are styled with `binary_col` + italic.
     pub binary_cols: std::collections::HashSet<String>,
+    /// Columns marked in the main view; their headers are drawn reversed.
+    pub marked_cols: std::collections::HashSet<String>,
+    /// How dates and numbers are written in cells (display only).
+    pub locale: DisplayLocale,
+    /// Per-column display formats; take precedence over `locale` for the columns they name.
+    pub column_formats: ColumnFormats,
+    /// Widths set for columns by name, used instead of their content width.
+    pub column_widths: HashMap<String, u16>,
+    /// Widest a column without a set width is drawn; None fits the content.
+    pub max_column_width: Option<u16>,
+    /// Regex search: cells whose value matches are drawn with `search_match_style`.
+    pub search: Option<regex::Regex>,
+    /// Column the regex search is limited to; None highlights matches in every column.
+    pub search_column: Option<String>,
+    pub search_match_style: Style,
+    /// Heatmap mode: numeric columns with a range here get a cell background between
+    /// `heatmap_colors` (low, high) by where the value falls in its column's range.
+    pub heatmap: Option<ColumnRanges>,
+    pub heatmap_colors: (Color, Color),
+    /// Numbers or letters drawn above the column headers; Off draws no ruler row.
+    pub column_ruler: ColumnRuler,
 }
 
 impl Default for DataTable {
@@ -4377,10 +5568,65 @@ impl Default for DataTable {
             temporal_col: None,
             binary_col: None,
             binary_cols: std::collections::HashSet::new(),
+            marked_cols: std::collections::HashSet::new(),
+            locale: DisplayLocale::ISO,
+            column_formats: ColumnFormats::new(),
+            column_widths: HashMap::new(),
+            max_column_width: None,
+            search: None,
+            search_column: None,
+            search_match_style: Style::default().fg(Color::Black).bg(Color::Yellow),
+            heatmap: None,
+            heatmap_colors: (Color::Blue, Color::Red),
+            column_ruler: ColumnRuler::Off,
         }
     }
 }
 
+/// Start of a visual selection: the (absolute row, column) corner of a block, or the first row
+/// of a row selection.
+#[derive(Debug, Clone)]
+enum VisualAnchor {
+    Block(usize, String),
+    Rows(usize),
+}
+
+/// Rectangle of cells selected in visual block mode, or the cell/row copied with `y` / `Y`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VisualBlock {
+    /// First row of the block (0-based, in the current view).
+    pub first_row: usize,
+    pub rows: usize,
+    /// Columns of the block, in display order.
+    pub columns: Vec<String>,
+}
+
+impl VisualBlock {
+    pub fn row_range(&self) -> std::ops::Range<usize> {
+        self.first_row..self.first_row + self.rows
+    }
+}
+
+/// Where a column was drawn in the last frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSpan {
+    pub name: String,
+    pub x: u16,
+    pub width: u16,
+    /// Width the header and the values on screen need; more than `width` when they were cut.
+    pub content_width: u16,
+    /// Drawn in the locked (frozen) part on the left.
+    pub locked: bool,
+}
+
+/// What a screen position in the table points at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableHit {
+    Header(ColumnSpan),
+    /// A cell: the row on screen (0 = first visible row) and its column.
+    Cell(usize, ColumnSpan),
+}
+
 /// Parameters for rendering the row numbers column.
 struct RowNumbersParams {
     start_row: usize,
@@ -4388,8 +5634,30 @@ struct RowNumbersParams {
     num_rows: usize,
     row_start_index: usize,
     selected_row: Option<usize>,
+    /// Pinned rows drawn above the visible rows.
+    pinned_rows: Vec<usize>,
+}
+
+/// The view rows a rendered frame holds: the `pinned` rows, then the rows from `start_row` on.
+#[derive(Debug, Clone, Copy, Default)]
+struct FrameRows<'a> {
+    start_row: usize,
+    pinned: &'a [usize],
+}
+
+impl FrameRows<'_> {
+    /// View row number of row `index` of the frame.
+    fn row(&self, index: usize) -> usize {
+        match self.pinned.get(index) {
+            Some(&row) => row,
+            None => self.start_row + index - self.pinned.len(),
+        }
+    }
 }
 
+/// Rows that can be pinned to the top of the table at once.
+pub const MAX_PINNED_ROWS: usize = 5;
+
 /// Placeholder shown in the table for binary columns. Their values (often large blobs, e.g.
 /// raw document bytes) are never read into the display buffer — only this stub is — which keeps
 /// scrolling and jump-to-end fast. The real bytes remain in `lf` for export/analysis.
@@ -4406,6 +5674,26 @@ fn is_truncatable_dtype(dtype: &DataType) -> bool {
     }
 }
 
+/// Clip `text` to `width` characters, marking the cut with a trailing `…`.
+/// Narrowest width `<` leaves a column: room for a character or two and the `…`.
+pub const MIN_COLUMN_WIDTH: u16 = 3;
+
+/// Characters `<` and `>` take from or add to a column's width.
+pub const COLUMN_WIDTH_STEP: i32 = 2;
+
+fn ellipsize(text: &str, width: u16) -> Cow<'_, str> {
+    let width = width as usize;
+    if text.chars().count() <= width {
+        return Cow::Borrowed(text);
+    }
+    if width == 0 {
+        return Cow::Borrowed("");
+    }
+    let mut clipped: String = text.chars().take(width - 1).collect();
+    clipped.push('…');
+    Cow::Owned(clipped)
+}
+
 impl DataTable {
     pub fn new() -> Self {
         Self::default()
@@ -4453,6 +5741,43 @@ impl DataTable {
         self
     }
 
+    /// Set the locale used to format dates and numbers in cells.
+    pub fn with_locale(mut self, locale: DisplayLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Set the per-column display formats.
+    pub fn with_column_formats(mut self, formats: ColumnFormats) -> Self {
+        self.column_formats = formats;
+        self
+    }
+
+    /// Set the widths of columns (`<`, `>`, `=`) and the widest other columns may be drawn.
+    pub fn with_column_widths(
+        mut self,
+        widths: HashMap<String, u16>,
+        max_column_width: Option<u16>,
+    ) -> Self {
+        self.column_widths = widths;
+        self.max_column_width = max_column_width;
+        self
+    }
+
+    /// Highlight cells matching `search` (the regex search, if any), in every column or only
+    /// `column`, with `style`.
+    pub fn with_search_highlight(
+        mut self,
+        search: Option<regex::Regex>,
+        column: Option<String>,
+        style: Style,
+    ) -> Self {
+        self.search = search;
+        self.search_column = column;
+        self.search_match_style = style;
+        self
+    }
+
     /// Set the color used for binary-column placeholder cells.
     pub fn with_binary_col(mut self, color: Color) -> Self {
         self.binary_col = Some(color);
@@ -4465,6 +5790,50 @@ impl DataTable {
         self
     }
 
+    /// Shade numeric cells on a gradient from `low` at their column's minimum to `high` at its
+    /// maximum; columns without a range in `ranges` are drawn as usual.
+    pub fn with_heatmap(mut self, ranges: ColumnRanges, low: Color, high: Color) -> Self {
+        self.heatmap = Some(ranges);
+        self.heatmap_colors = (low, high);
+        self
+    }
+
+    pub fn with_marked_columns(mut self, names: std::collections::HashSet<String>) -> Self {
+        self.marked_cols = names;
+        self
+    }
+
+    /// Draw a ruler of column numbers or letters above the headers.
+    pub fn with_column_ruler(mut self, ruler: ColumnRuler) -> Self {
+        self.column_ruler = ruler;
+        self
+    }
+
+    /// Label each column drawn in the last frame with its number or letters in the view's
+    /// column order, on the row above its header.
+    fn render_column_ruler(&self, area: Rect, buf: &mut Buffer, state: &DataTableState) {
+        let style = Style::default().fg(self.row_numbers_fg);
+        for span in &state.column_spans {
+            let Some(index) = state.column_order.iter().position(|c| c == &span.name) else {
+                continue;
+            };
+            let label = self.column_ruler.label(index);
+            let x = span.x.max(area.x);
+            let width = span.width.min((area.x + area.width).saturating_sub(x));
+            Paragraph::new(ellipsize(&label, width).into_owned())
+                .style(style)
+                .render(
+                    Rect {
+                        x,
+                        y: area.y,
+                        width,
+                        height: 1,
+                    },
+                    buf,
+                );
+        }
+    }
+
     /// Return the color for a column dtype when column_colors is enabled.
     fn column_type_color(&self, dtype: &DataType) -> Option<Color> {
         if !self.column_colors {
@@ -4490,17 +5859,18 @@ impl DataTable {
     }
 
     /// Render the dataframe into `area`, returning the number of columns that were actually
-    /// shown (which may be fewer than `df`'s column count when they don't all fit). The caller
-    /// uses this to decide whether to draw an "more columns off-screen" indicator.
+    /// shown (which may be fewer than `df`'s column count when they don't all fit) and the
+    /// column shown truncated, if any. The caller uses the count to decide whether to draw an
+    /// "more columns off-screen" indicator, and the truncated column for the `za` full-value popup.
     fn render_dataframe(
         &self,
         df: &DataFrame,
         area: Rect,
         buf: &mut Buffer,
         state: &mut TableState,
-        _row_numbers: bool,
-        _start_row_offset: usize,
-    ) -> usize {
+        visual: Option<&VisualBlock>,
+        rows: FrameRows,
+    ) -> (usize, Option<String>, Vec<ColumnSpan>) {
         // make each column as wide as it needs to be to fit the content
         let (height, cols) = df.shape();
 
@@ -4512,9 +5882,11 @@ impl DataTable {
             .collect();
 
         let mut used_width = 0;
+        // Width each column's header and values need, before set and maximum widths
+        let mut content_widths = widths.clone();
 
-        // rows is a vector initialized to a vector of lenth "height" empty rows
-        let mut rows: Vec<Vec<Cell>> = vec![vec![]; height];
+        // cells is a vector initialized to a vector of lenth "height" empty rows
+        let mut cells: Vec<Vec<Cell>> = vec![vec![]; height];
         let mut visible_columns = 0;
 
         let max_rows = height.min(if area.height > 1 {
@@ -4524,6 +5896,7 @@ impl DataTable {
         });
 
         let col_names = df.get_column_names();
+        let mut truncated_column = None;
         for col_index in 0..cols {
             let mut max_len = widths[col_index];
             let col_data = &df[col_index];
@@ -4541,26 +5914,98 @@ impl DataTable {
                     .map(|c| Style::default().fg(c))
             };
 
-            for (row_index, row) in rows.iter_mut().take(max_rows).enumerate() {
-                let value = col_data.get(row_index).unwrap();
-                let val_str: Cow<str> = if matches!(value, AnyValue::Null) {
-                    Cow::Borrowed("")
-                } else {
-                    value.str_value()
-                };
-                let len = val_str.chars().count() as u16;
-                max_len = max_len.max(len);
-                let cell = match cell_style {
-                    Some(s) => Cell::from(Line::from(Span::styled(val_str.into_owned(), s))),
-                    None => Cell::from(Line::from(val_str)),
-                };
-                row.push(cell);
+            let format = self.column_formats.get(col_names[col_index].as_str());
+            // Regex search matches, tested on the stored value (as the search scan does)
+            let matched: Vec<bool> = match &self.search {
+                Some(search)
+                    if !is_binary
+                        && crate::regex_search::is_searchable(col_data.dtype())
+                        && self
+                            .search_column
+                            .as_deref()
+                            .is_none_or(|c| c == col_names[col_index].as_str()) =>
+                {
+                    (0..max_rows)
+                        .map(|row_index| {
+                            let value = col_data.get(row_index).unwrap();
+                            !value.is_null() && search.is_match(&value.str_value())
+                        })
+                        .collect()
+                }
+                _ => Vec::new(),
+            };
+            // Heatmap background of each cell, when the column is shaded
+            let shades: Vec<Option<Color>> = match self
+                .heatmap
+                .as_ref()
+                .and_then(|ranges| ranges.get(col_names[col_index].as_str()))
+            {
+                Some(&range) if !is_binary => {
+                    let (low, high) = self.heatmap_colors;
+                    (0..max_rows)
+                        .map(|row_index| {
+                            let value = col_data.get(row_index).unwrap().extract::<f64>()?;
+                            Some(heatmap::shade(low, high, heatmap::position(value, range)))
+                        })
+                        .collect()
+                }
+                _ => Vec::new(),
+            };
+            let values: Vec<Cow<str>> = (0..max_rows)
+                .map(|row_index| {
+                    let value = col_data.get(row_index).unwrap();
+                    if matches!(value, AnyValue::Null) {
+                        Cow::Borrowed("")
+                    } else if let Some(text) =
+                        format.and_then(|f| f.format_value(&value, &self.locale))
+                    {
+                        text.into()
+                    } else {
+                        self.locale.format_value(&value).into_owned().into()
+                    }
+                })
+                .collect();
+            for val_str in &values {
+                max_len = max_len.max(val_str.chars().count() as u16);
+            }
+            content_widths[col_index] = max_len;
+            if let Some(&width) = self.column_widths.get(col_names[col_index].as_str()) {
+                max_len = width;
+            } else if let Some(max) = self.max_column_width {
+                max_len = max_len.min(max);
             }
 
             // Use > not >= so the last column is shown when it fits exactly (no padding needed after it)
             let overflows = (used_width + max_len) > area.width;
 
+            // Rows of this column inside the visual block, if the column is part of it
+            let block_rows = visual
+                .filter(|v| v.columns.iter().any(|c| c == col_names[col_index].as_str()))
+                .map(VisualBlock::row_range);
+
+            let mut push_cells = |width: u16| {
+                for (row_index, (row, val_str)) in cells.iter_mut().zip(&values).enumerate() {
+                    let text = ellipsize(val_str, width);
+                    let mut cell = match cell_style {
+                        Some(s) => Cell::from(Line::from(Span::styled(text.into_owned(), s))),
+                        None => Cell::from(Line::from(text.into_owned())),
+                    };
+                    if let Some(&Some(bg)) = shades.get(row_index) {
+                        cell = cell.style(Style::default().bg(bg));
+                    }
+                    if block_rows
+                        .as_ref()
+                        .is_some_and(|r| r.contains(&rows.row(row_index)))
+                    {
+                        cell = cell.style(Style::default().add_modifier(Modifier::REVERSED));
+                    } else if matched.get(row_index).copied().unwrap_or(false) {
+                        cell = cell.style(self.search_match_style);
+                    }
+                    row.push(cell);
+                }
+            };
             if !overflows {
+                push_cells(max_len);
                 visible_columns += 1;
                 widths[col_index] = max_len;
                 used_width += max_len + self.table_cell_padding;
@@ -4574,21 +6019,32 @@ impl DataTable {
                 const MIN_PARTIAL_COLUMN_WIDTH: u16 = 3;
                 let remaining = area.width.saturating_sub(used_width);
                 if is_truncatable_dtype(col_data.dtype()) && remaining >= MIN_PARTIAL_COLUMN_WIDTH {
+                    push_cells(remaining);
                     visible_columns += 1;
                     widths[col_index] = remaining;
+                    truncated_column = Some(col_names[col_index].to_string());
                 }
                 break;
             }
         }
 
         widths.truncate(visible_columns);
-        // convert rows to a vector of Row, with optional alternate row background
-        let rows: Vec<Row> = rows
+        // convert cells to a vector of Row, with optional alternate row background; pinned rows
+        // are bold, the last one underlined to set them apart from the scrolling rows
+        let pinned = rows.pinned.len();
+        let table_rows: Vec<Row> = cells
             .into_iter()
             .enumerate()
             .map(|(row_index, mut row)| {
                 row.truncate(visible_columns);
-                let row_style = if row_index % 2 == 1 {
+                let row_style = if row_index < pinned {
+                    let style = Style::default().add_modifier(Modifier::BOLD);
+                    if row_index + 1 == pinned {
+                        style.add_modifier(Modifier::UNDERLINED)
+                    } else {
+                        style
+                    }
+                } else if (row_index - pinned) % 2 == 1 {
                     self.alternate_row_bg
                         .map(|c| Style::default().bg(c))
                         .unwrap_or_default()
@@ -4607,21 +6063,54 @@ impl DataTable {
         let headers: Vec<Span> = df
             .get_column_names()
             .iter()
-            .take(visible_columns)
-            .map(|name| Span::styled(name.to_string(), Style::default()))
+            .zip(&widths)
+            .map(|(name, &width)| {
+                let style = if self.marked_cols.contains(name.as_str()) {
+                    Style::default().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default()
+                };
+                Span::styled(ellipsize(name, width).into_owned(), style)
+            })
             .collect();
 
-        StatefulWidget::render(
-            Table::new(rows, widths)
-                .column_spacing(self.table_cell_padding)
-                .header(Row::new(headers).style(header_row_style))
-                .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
-            area,
-            buf,
-            state,
-        );
+        // In visual block mode the block is highlighted instead of the selected row
+        let row_highlight_style = if visual.is_some() {
+            Style::default()
+        } else {
+            Style::default().add_modifier(Modifier::REVERSED)
+        };
+        let mut x = area.x;
+        let spans = col_names
+            .iter()
+            .zip(&widths)
+            .zip(content_widths)
+            .map(|((name, &width), content_width)| {
+                let span = ColumnSpan {
+                    name: name.to_string(),
+                    x,
+                    width,
+                    content_width,
+                    locked: false,
+                };
+                x = x.saturating_add(width + self.table_cell_padding);
+                span
+            })
+            .collect();
+        let table = Table::new(table_rows, widths)
+            .column_spacing(self.table_cell_padding)
+            .header(Row::new(headers).style(header_row_style))
+            .row_highlight_style(row_highlight_style);
+        if pinned == 0 {
+            StatefulWidget::render(table, area, buf, state);
+        } else {
+            // The selection counts the scrolling rows only; skip the pinned ones above it
+            let mut shown =
+                TableState::default().with_selected(state.selected().map(|s| s + pinned));
+            StatefulWidget::render(table, area, buf, &mut shown);
+        }
 
-        visible_columns
+        (visible_columns, truncated_column, spans)
     }
 
     fn render_row_numbers(&self, area: Rect, buf: &mut Buffer, params: RowNumbersParams) {
@@ -4652,12 +6141,40 @@ impl DataTable {
         }
 
         // Calculate width needed for largest row number
-        let max_row_num =
-            params.start_row + rows_to_render.saturating_sub(1) + params.row_start_index;
+        let max_row_num = (params.start_row + rows_to_render.saturating_sub(1))
+            .max(params.pinned_rows.last().copied().unwrap_or(0))
+            + params.row_start_index;
         let max_width = max_row_num.to_string().len();
 
+        // Pinned rows first, styled like their table rows
+        let pinned = params.pinned_rows.len();
+        for (row_idx, row) in params.pinned_rows.iter().enumerate() {
+            let y = area.y + row_idx as u16 + 1;
+            if y >= area.y + area.height {
+                break;
+            }
+            let mut style = Style::default()
+                .fg(self.row_numbers_fg)
+                .add_modifier(Modifier::BOLD);
+            if row_idx + 1 == pinned {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            Paragraph::new(format!("{:>max_width$}", row + params.row_start_index))
+                .style(style)
+                .render(
+                    Rect {
+                        x: area.x,
+                        y,
+                        width: area.width,
+                        height: 1,
+                    },
+                    buf,
+                );
+        }
+
         // Render row numbers
-        for row_idx in 0..rows_to_render.min(area.height.saturating_sub(1) as usize) {
+        let data_height = (area.height.saturating_sub(1) as usize).saturating_sub(pinned);
+        for row_idx in 0..rows_to_render.min(data_height) {
             let row_num = params.start_row + row_idx + params.row_start_index;
             let row_num_text = row_num.to_string();
 
@@ -4685,7 +6202,7 @@ impl DataTable {
                 None => Style::default().fg(fg),
             };
 
-            let y = area.y + row_idx as u16 + 1; // +1 for header row
+            let y = area.y + (pinned + row_idx) as u16 + 1; // +1 for header row
             if y < area.y + area.height {
                 Paragraph::new(padded_text).style(row_num_style).render(
                     Rect {
@@ -4705,9 +6222,29 @@ impl StatefulWidget for DataTable {
     type State = DataTableState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        // The ruler takes the top row; the header and rows are drawn below it
+        let ruler_area = (self.column_ruler != ColumnRuler::Off && area.height > 2)
+            .then_some(Rect { height: 1, ..area });
+        let area = match ruler_area {
+            Some(_) => Rect {
+                y: area.y + 1,
+                height: area.height - 1,
+                ..area
+            },
+            None => area,
+        };
         state.visible_termcols = area.width as usize;
+        state.truncated_column = None;
+        state.table_area = area;
+        state.column_spans.clear();
+        // Pinned rows sit under the header, leaving at least one scrolling row
+        let pinned = state
+            .pinned_rows
+            .len()
+            .min(area.height.saturating_sub(2) as usize);
+        let pinned_rows = state.pinned_rows[..pinned].to_vec();
         let new_visible_rows = if area.height > 0 {
-            (area.height - 1) as usize
+            (area.height - 1) as usize - pinned
         } else {
             0
         };
@@ -4720,9 +6257,11 @@ impl StatefulWidget for DataTable {
             }
         }
 
-        if visible_rows_changed {
-            // Flag that the buffer needs re-collection for the new visible_rows.
-            // The App event loop checks this flag after each render and triggers an async collect.
+        if visible_rows_changed && !state.buffer_covers_view() {
+            // Flag that the buffer needs re-collection for the new visible_rows. A buffer that
+            // still covers the (smaller) view is kept, e.g. a first buffer sized from the
+            // App's viewport hint. The App event loop checks this flag after each render and
+            // triggers an async collect.
             state.needs_recollect = true;
         }
 
@@ -4744,6 +6283,8 @@ impl StatefulWidget for DataTable {
             // If suppress_error_display is true, continue rendering the table normally
         }
 
+        let visual = state.visual_block();
+
         // Captures the scrollable area plus whether columns exist off-screen to the left/right,
         // so a header-row indicator can be drawn after the table is rendered.
         // Tuple: (scrollable_area, more_columns_left, more_columns_right).
@@ -4806,6 +6347,7 @@ impl StatefulWidget for DataTable {
                         num_rows: state.num_rows,
                         row_start_index: state.row_start_index,
                         selected_row: state.table_state.selected(),
+                        pinned_rows: pinned_rows.clone(),
                     },
                 );
             }
@@ -4836,15 +6378,22 @@ impl StatefulWidget for DataTable {
                     .visible_rows
                     .min(locked_df.height().saturating_sub(offset));
                 if offset < locked_df.height() && slice_len > 0 {
-                    let sliced_df = locked_df.slice(offset as i64, slice_len);
-                    self.render_dataframe(
+                    let sliced_df =
+                        state.with_pinned_rows(locked_df.slice(offset as i64, slice_len), pinned);
+                    let (_, _, spans) = self.render_dataframe(
                         &sliced_df,
                         adjusted_locked_area,
                         buf,
                         &mut state.table_state,
-                        false,
-                        state.start_row,
+                        visual.as_ref(),
+                        FrameRows {
+                            start_row: state.start_row,
+                            pinned: &pinned_rows,
+                        },
                     );
+                    state
+                        .column_spans
+                        .extend(spans.into_iter().map(|s| ColumnSpan { locked: true, ..s }));
                 }
             }
 
@@ -4878,16 +6427,22 @@ impl StatefulWidget for DataTable {
                 let offset = state.start_row.saturating_sub(state.buffered_start_row);
                 let slice_len = state.visible_rows.min(df.height().saturating_sub(offset));
                 if offset < df.height() && slice_len > 0 {
-                    let sliced_df = df.slice(offset as i64, slice_len);
+                    let sliced_df =
+                        state.with_pinned_rows(df.slice(offset as i64, slice_len), pinned);
                     let total_cols = sliced_df.width();
-                    let shown = self.render_dataframe(
+                    let (shown, truncated, spans) = self.render_dataframe(
                         &sliced_df,
                         adjusted_scrollable_area,
                         buf,
                         &mut state.table_state,
-                        false,
-                        state.start_row,
+                        visual.as_ref(),
+                        FrameRows {
+                            start_row: state.start_row,
+                            pinned: &pinned_rows,
+                        },
                     );
+                    state.truncated_column = truncated;
+                    state.column_spans.extend(spans);
                     scroll_indicator = Some((
                         adjusted_scrollable_area,
                         state.termcol_index > 0,
@@ -4914,6 +6469,7 @@ impl StatefulWidget for DataTable {
                         num_rows: state.num_rows,
                         row_start_index: state.row_start_index,
                         selected_row: state.table_state.selected(),
+                        pinned_rows: pinned_rows.clone(),
                     },
                 );
 
@@ -4929,16 +6485,22 @@ impl StatefulWidget for DataTable {
                 let offset = state.start_row.saturating_sub(state.buffered_start_row);
                 let slice_len = state.visible_rows.min(df.height().saturating_sub(offset));
                 if offset < df.height() && slice_len > 0 {
-                    let sliced_df = df.slice(offset as i64, slice_len);
+                    let sliced_df =
+                        state.with_pinned_rows(df.slice(offset as i64, slice_len), pinned);
                     let total_cols = sliced_df.width();
-                    let shown = self.render_dataframe(
+                    let (shown, truncated, spans) = self.render_dataframe(
                         &sliced_df,
                         data_area,
                         buf,
                         &mut state.table_state,
-                        false,
-                        state.start_row,
+                        visual.as_ref(),
+                        FrameRows {
+                            start_row: state.start_row,
+                            pinned: &pinned_rows,
+                        },
                     );
+                    state.truncated_column = truncated;
+                    state.column_spans.extend(spans);
                     scroll_indicator =
                         Some((data_area, state.termcol_index > 0, shown < total_cols));
                 }
@@ -4947,16 +6509,22 @@ impl StatefulWidget for DataTable {
                 let offset = state.start_row.saturating_sub(state.buffered_start_row);
                 let slice_len = state.visible_rows.min(df.height().saturating_sub(offset));
                 if offset < df.height() && slice_len > 0 {
-                    let sliced_df = df.slice(offset as i64, slice_len);
+                    let sliced_df =
+                        state.with_pinned_rows(df.slice(offset as i64, slice_len), pinned);
                     let total_cols = sliced_df.width();
-                    let shown = self.render_dataframe(
+                    let (shown, truncated, spans) = self.render_dataframe(
                         &sliced_df,
                         area,
                         buf,
                         &mut state.table_state,
-                        false,
-                        state.start_row,
+                        visual.as_ref(),
+                        FrameRows {
+                            start_row: state.start_row,
+                            pinned: &pinned_rows,
+                        },
                     );
+                    state.truncated_column = truncated;
+                    state.column_spans.extend(spans);
                     scroll_indicator = Some((area, state.termcol_index > 0, shown < total_cols));
                 }
             }
@@ -4984,6 +6552,7 @@ impl StatefulWidget for DataTable {
                             num_rows: 0,
                             row_start_index: state.row_start_index,
                             selected_row: None,
+                            pinned_rows: Vec::new(),
                         },
                     );
                     let data_area = Rect {
@@ -4992,16 +6561,25 @@ impl StatefulWidget for DataTable {
                         width: area.width.saturating_sub(row_num_width),
                         height: area.height,
                     };
-                    self.render_dataframe(
+                    let (_, _, spans) = self.render_dataframe(
                         &empty_df,
                         data_area,
                         buf,
                         &mut state.table_state,
-                        false,
-                        0,
+                        visual.as_ref(),
+                        FrameRows::default(),
                     );
+                    state.column_spans = spans;
                 } else {
-                    self.render_dataframe(&empty_df, area, buf, &mut state.table_state, false, 0);
+                    let (_, _, spans) = self.render_dataframe(
+                        &empty_df,
+                        area,
+                        buf,
+                        &mut state.table_state,
+                        None,
+                        FrameRows::default(),
+                    );
+                    state.column_spans = spans;
                 }
             } else {
                 Paragraph::new("No data").render(area, buf);
@@ -5039,6 +6617,9 @@ impl StatefulWidget for DataTable {
                 }
             }
         }
+        if let Some(ruler_area) = ruler_area {
+            self.render_column_ruler(ruler_area, buf, state);
+        }
     }
 }
 
@@ -5350,6 +6931,58 @@ mod tests {
         assert_eq!(df.column("a").unwrap().get(0).unwrap(), AnyValue::Int32(3));
     }
 
+    #[test]
+    fn test_filter_between_and_in() {
+        let lf = create_test_lf();
+        let mut state = DataTableState::new(lf, None, None, None, None, true).unwrap();
+        let statement = |column: &str, operator, value: &str| FilterStatement {
+            column: column.to_string(),
+            operator,
+            value: value.to_string(),
+            logical_op: LogicalOperator::And,
+        };
+        state.filter(vec![statement("a", FilterOperator::Between, "2, 3")]);
+        assert_eq!(state.lf.clone().collect().unwrap().height(), 2);
+        state.filter(vec![statement("b", FilterOperator::In, "x, z")]);
+        let df = state.lf.clone().collect().unwrap();
+        assert_eq!(df.column("a").unwrap().get(1).unwrap(), AnyValue::Int32(3));
+    }
+
+    #[test]
+    fn test_filter_between_dates() {
+        let lf = df!(
+            "day" => ["2024-01-05", "2024-02-10", "2024-03-15"],
+            "at" => ["2024-01-05 08:00:00", "2024-02-10 12:30:00", "2024-03-15 23:00:00"]
+        )
+        .unwrap()
+        .lazy()
+        .with_columns([
+            col("day").cast(DataType::Date),
+            col("at").str().to_datetime(
+                Some(TimeUnit::Microseconds),
+                None,
+                StrptimeOptions::default(),
+                lit("raise"),
+            ),
+        ]);
+        let mut state = DataTableState::new(lf, None, None, None, None, true).unwrap();
+        let statement = |column: &str, value: &str| FilterStatement {
+            column: column.to_string(),
+            operator: FilterOperator::Between,
+            value: value.to_string(),
+            logical_op: LogicalOperator::And,
+        };
+        state.filter(vec![statement("day", "2024-01-01, 2024-02-10")]);
+        assert_eq!(state.lf.clone().collect().unwrap().height(), 2);
+        state.filter(vec![statement(
+            "at",
+            "2024-02-10 12:30:00, 2024-03-15 22:00:00",
+        )]);
+        assert_eq!(state.lf.clone().collect().unwrap().height(), 1);
+        state.filter(vec![statement("at", "2024-01-01, 2024-03-01")]);
+        assert_eq!(state.lf.clone().collect().unwrap().height(), 2);
+    }
+
     #[test]
     fn test_sort() {
         let lf = create_test_lf();
@@ -5372,6 +7005,26 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "sql")]
+    #[test]
+    fn test_query_engine_follows_latest_query() {
+        let lf = create_test_lf();
+        let mut state = DataTableState::new(lf, None, None, None, None, true).unwrap();
+        assert_eq!(state.query_engine(), None);
+        state.query("select a, b where a > 1".to_string());
+        assert_eq!(state.query_engine(), Some(QueryEngine::DatuiExpr));
+        state.sql_query(
+            "SELECT a, SUM(a) OVER (PARTITION BY b) AS s FROM df ORDER BY a".to_string(),
+        );
+        assert!(state.error.is_none(), "{:?}", state.error);
+        assert_eq!(state.query_engine(), Some(QueryEngine::Sql));
+        let df = state.lf.clone().collect().unwrap();
+        assert_eq!(df.height(), 2);
+        state.query("select b".to_string());
+        assert_eq!(state.query_engine(), Some(QueryEngine::DatuiExpr));
+        assert!(state.get_active_sql_query().is_empty());
+    }
+
     #[test]
     fn test_query_date_accessors() {
         use chrono::NaiveDate;
@@ -5841,6 +7494,60 @@ mod tests {
         assert!(pat.contains("\\["));
     }
 
+    #[test]
+    fn test_hive_format_from_glob_option_or_first_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let part = dir.path().join("day=1");
+        std::fs::create_dir_all(&part).unwrap();
+        std::fs::write(part.join("_SUCCESS"), "").unwrap();
+        std::fs::write(part.join("log.ndjson"), "{\"a\": 1}\n").unwrap();
+        assert_eq!(
+            DataTableState::hive_format(dir.path(), None),
+            Some(FileFormat::Jsonl)
+        );
+        assert_eq!(
+            DataTableState::hive_format(&dir.path().join("**/*"), None),
+            Some(FileFormat::Jsonl)
+        );
+        assert_eq!(
+            DataTableState::hive_format(&dir.path().join("**/*.tsv"), None),
+            Some(FileFormat::Tsv)
+        );
+        assert_eq!(
+            DataTableState::hive_format(dir.path(), Some(FileFormat::Csv)),
+            Some(FileFormat::Csv)
+        );
+        assert_eq!(
+            DataTableState::hive_format(&dir.path().join("**/*.avro"), None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_query_regexes_match_quoted_phrases_as_typed() {
+        assert_eq!(
+            fuzzy_query_regexes(r#"ab "connection refused" "x.y"#),
+            ["(?i).*a.*b.*", "(?i)connection refused", r"(?i)x\.y"]
+        );
+        assert!(fuzzy_query_regexes(r#""" "  ""#).is_empty());
+
+        let lf = df!(
+            "level" => &["ERROR", "info", "warn"],
+            "message" => &["Connection refused by host", "connected", "refused: connection"]
+        )
+        .unwrap()
+        .lazy();
+        let mut state = DataTableState::new(lf, None, None, None, None, true).unwrap();
+        state.fuzzy_search(r#""connection refused""#.to_string());
+        assert!(state.error.is_none(), "{:?}", state.error);
+        let df = state.lf.clone().collect().unwrap();
+        assert_eq!(df.height(), 1);
+        assert_eq!(
+            df.column("level").unwrap().get(0).unwrap(),
+            AnyValue::String("ERROR")
+        );
+    }
+
     #[test]
     fn test_fuzzy_search() {
         // Filter logic is covered by test_fuzzy_search_regex_direct. This test runs the full
@@ -6060,7 +7767,8 @@ mod tests {
         let area = Rect::new(0, 0, 8, 4);
         let mut buf = Buffer::empty(area);
         let mut ts = TableState::default();
-        let shown = table.render_dataframe(&df, area, &mut buf, &mut ts, false, 0);
+        let (shown, _, _) =
+            table.render_dataframe(&df, area, &mut buf, &mut ts, None, FrameRows::default());
         assert_eq!(
             shown, 2,
             "the overflowing trailing string column should be kept (truncated)"
@@ -6073,6 +7781,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn truncated_cells_end_with_ellipsis() {
+        let table = DataTable::default();
+        let df = df!(
+            "a" => &[1i32, 2],
+            "wide_text" => &["aaaaaaaaaa", "bb"],
+        )
+        .unwrap();
+        let area = Rect::new(0, 0, 8, 3);
+        let mut buf = Buffer::empty(area);
+        let mut ts = TableState::default();
+        let (shown, truncated, _) =
+            table.render_dataframe(&df, area, &mut buf, &mut ts, None, FrameRows::default());
+        assert_eq!(shown, 2);
+        assert_eq!(truncated.as_deref(), Some("wide_text"));
+        let row = |y: u16| -> String {
+            (area.x..area.x + area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect()
+        };
+        // Clipped values and heading end in `…`; values that fit are left alone.
+        assert_eq!(row(0).trim_end(), "a wide_…");
+        assert_eq!(row(1).trim_end(), "1 aaaaa…");
+        assert_eq!(row(2).trim_end(), "2 bb");
+    }
+
+    #[test]
+    fn set_and_maximum_column_widths_cut_values() {
+        let table = DataTable::default()
+            .with_column_widths(HashMap::from([("code".to_string(), 3)]), Some(6));
+        let df = df!(
+            "name" => &["abcdefghij", "ab"],
+            "code" => &["XYZW", "Q"],
+            "n" => &[1i32, 2],
+        )
+        .unwrap();
+        let area = Rect::new(0, 0, 20, 3);
+        let mut buf = Buffer::empty(area);
+        let mut ts = TableState::default();
+        let (shown, truncated, spans) =
+            table.render_dataframe(&df, area, &mut buf, &mut ts, None, FrameRows::default());
+        assert_eq!(shown, 3);
+        assert_eq!(truncated, None, "nothing runs off the right edge");
+        let row = |y: u16| -> String {
+            (area.x..area.x + area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect()
+        };
+        assert_eq!(row(0).trim_end(), "name   co… n");
+        assert_eq!(row(1).trim_end(), "abcde… XY… 1");
+        assert_eq!(row(2).trim_end(), "ab     Q   2");
+        let widths: Vec<(u16, u16)> = spans.iter().map(|s| (s.width, s.content_width)).collect();
+        assert_eq!(widths, [(6, 10), (3, 4), (1, 1)]);
+    }
+
     #[test]
     fn binary_stub_cells_are_styled_with_binary_color_and_italic() {
         // Binary columns render the `‹binary›` stub; those cells should be colored with
@@ -6091,7 +7854,7 @@ mod tests {
         let area = Rect::new(0, 0, 20, 4);
         let mut buf = Buffer::empty(area);
         let mut ts = TableState::default();
-        table.render_dataframe(&df, area, &mut buf, &mut ts, false, 0);
+        table.render_dataframe(&df, area, &mut buf, &mut ts, None, FrameRows::default());
 
         // A data row (y = 1; y = 0 is the header). The stub cells should be dark gray + italic.
         let stub_styled = (area.x..area.x + area.width).any(|x| {
@@ -6125,7 +7888,8 @@ mod tests {
         let area = Rect::new(0, 0, 8, 4);
         let mut buf = Buffer::empty(area);
         let mut ts = TableState::default();
-        let shown = table.render_dataframe(&df, area, &mut buf, &mut ts, false, 0);
+        let (shown, _, _) =
+            table.render_dataframe(&df, area, &mut buf, &mut ts, None, FrameRows::default());
         assert_eq!(
             shown, 1,
             "an overflowing numeric column should be dropped, not truncated"
@@ -6213,9 +7977,14 @@ mod tests {
         let state = DataTableState::new(lf, None, None, None, None, true).unwrap();
 
         let analysis_lf = state.lf.clone().select(state.binary_stub_exprs());
-        let results =
-            crate::statistics::compute_describe_from_lazy(&analysis_lf, 3, None, 0, false)
-                .expect("describe should not fail on binary columns");
+        let results = crate::statistics::compute_describe_from_lazy(
+            &analysis_lf,
+            3,
+            None,
+            &Default::default(),
+            false,
+        )
+        .expect("describe should not fail on binary columns");
 
         let blob_stat = results
             .column_statistics
@@ -6256,7 +8025,8 @@ mod tests {
         let area = Rect::new(0, 0, 8, 4);
         let mut buf = Buffer::empty(area);
         let mut ts = TableState::default();
-        let shown = table.render_dataframe(&df, area, &mut buf, &mut ts, false, 0);
+        let (shown, _, _) =
+            table.render_dataframe(&df, area, &mut buf, &mut ts, None, FrameRows::default());
         assert_eq!(
             shown, 2,
             "an overflowing binary column should be shown truncated"
@@ -6276,7 +8046,8 @@ mod tests {
         let area = Rect::new(0, 0, 5, 4);
         let mut buf = Buffer::empty(area);
         let mut ts = TableState::default();
-        let shown = table.render_dataframe(&df, area, &mut buf, &mut ts, false, 0);
+        let (shown, _, _) =
+            table.render_dataframe(&df, area, &mut buf, &mut ts, None, FrameRows::default());
         assert_eq!(shown, 1, "a sub-minimal sliver should not be shown");
     }
 
@@ -6312,4 +8083,139 @@ mod tests {
             "expected left indicator after scroll: {header2:?}"
         );
     }
+
+    #[test]
+    fn column_ruler_labels_columns_above_headers() {
+        let lf = df!("a" => &[1i32, 2], "bb" => &[3i32, 4], "c" => &[5i32, 6])
+            .unwrap()
+            .lazy();
+        let mut state = DataTableState::new(lf, None, None, None, None, true).unwrap();
+        state.visible_rows = 2;
+        state.collect();
+        state.scroll_to_column(1);
+        assert_eq!(state.selected_column(), Some("bb"));
+
+        let area = Rect::new(0, 0, 10, 4);
+        let row = |buf: &Buffer, y: u16| -> String {
+            (area.x..area.x + area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect()
+        };
+        let mut buf = Buffer::empty(area);
+        DataTable::default()
+            .with_column_ruler(ColumnRuler::Letters)
+            .render(area, &mut buf, &mut state);
+        // Labels follow the column order, not the position on screen
+        assert_eq!(row(&buf, 0).trim_end(), "B  C");
+        // The header is under it, `◀` marking the column scrolled past
+        assert_eq!(row(&buf, 1).trim_end(), "◀b c");
+        assert_eq!(
+            state.visible_rows, 2,
+            "the ruler takes a row from the table"
+        );
+
+        let mut buf = Buffer::empty(area);
+        DataTable::default()
+            .with_column_ruler(ColumnRuler::Index)
+            .render(area, &mut buf, &mut state);
+        assert_eq!(row(&buf, 0).trim_end(), "2  3");
+    }
+
+    #[test]
+    fn expand_column_unnests_structs_in_place_and_explodes_lists() {
+        let point = StructChunked::from_series(
+            "point".into(),
+            2,
+            [
+                Series::new("x".into(), [1i64, 2]),
+                Series::new("y".into(), [3i64, 4]),
+            ]
+            .iter(),
+        )
+        .unwrap()
+        .into_series();
+        let tags = Series::new(
+            "tags".into(),
+            [
+                Series::new(PlSmallStr::EMPTY, ["a", "b"]),
+                Series::new(PlSmallStr::EMPTY, ["c"]),
+            ],
+        );
+        let id = Series::new("id".into(), [1i32, 2]);
+        let lf = DataFrame::new(vec![id.into(), point.into(), tags.into()])
+            .unwrap()
+            .lazy();
+        let mut state = DataTableState::new(lf, None, None, None, None, true).unwrap();
+        state.visible_rows = 10;
+        state.collect();
+
+        state.expand_column("point").unwrap();
+        assert_eq!(state.headers(), ["id", "point_x", "point_y", "tags"]);
+        state.expand_column("tags").unwrap();
+        let df = state.df.as_ref().unwrap();
+        assert_eq!(df.height(), 3, "one row per list item");
+        let tags: Vec<_> = df
+            .column("tags")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(tags, [Some("a"), Some("b"), Some("c")]);
+
+        assert_eq!(
+            state.expand_column("id").unwrap_err(),
+            "'id' is not a struct or list column"
+        );
+    }
+
+    #[test]
+    fn pinned_rows_stay_under_the_header_while_scrolling() {
+        let ids: Vec<i32> = (0..20).collect();
+        let names: Vec<String> = (0..20).map(|i| format!("row{i}")).collect();
+        let lf = df!("id" => ids, "name" => names).unwrap().lazy();
+        let mut state = DataTableState::new(lf, None, None, None, None, true).unwrap();
+        state.visible_rows = 5;
+        state.collect();
+        state.table_state.select(Some(0));
+        assert_eq!(state.toggle_pinned_row(), Ok(true));
+        state.table_state.select(Some(2));
+        assert_eq!(state.toggle_pinned_row(), Ok(true));
+        assert_eq!(state.pinned_rows(), [0, 2]);
+
+        let area = Rect::new(0, 0, 20, 6);
+        let line = |buf: &Buffer, y: u16| -> String {
+            (0..area.width).map(|x| buf[(x, y)].symbol()).collect()
+        };
+        let mut buf = Buffer::empty(area);
+        DataTable::default().render(area, &mut buf, &mut state);
+        assert_eq!(state.visible_rows, 3, "two of five rows are pinned");
+
+        if state.scroll_to(10) {
+            state.collect();
+        }
+        let mut buf = Buffer::empty(area);
+        DataTable::default().render(area, &mut buf, &mut state);
+        assert!(line(&buf, 1).contains("row0"), "{:?}", line(&buf, 1));
+        assert!(line(&buf, 2).contains("row2"), "{:?}", line(&buf, 2));
+        assert!(line(&buf, 3).contains("row10"), "{:?}", line(&buf, 3));
+        assert!(buf[(0, 2)].modifier.contains(Modifier::UNDERLINED));
+        // The selection counts the scrolling rows: the first one is highlighted.
+        state.table_state.select(Some(0));
+        let mut buf = Buffer::empty(area);
+        DataTable::default().render(area, &mut buf, &mut state);
+        assert!(buf[(0, 3)].modifier.contains(Modifier::REVERSED));
+        assert!(matches!(state.hit(0, 3), Some(TableHit::Cell(0, _))));
+        assert!(state.hit(0, 1).is_none(), "pinned rows can't be selected");
+
+        // Pinning the row again unpins it; a new view clears the pins.
+        if state.scroll_to(2) {
+            state.collect();
+        }
+        state.table_state.select(Some(0));
+        assert_eq!(state.toggle_pinned_row(), Ok(false));
+        assert_eq!(state.pinned_rows(), [0]);
+        state.reverse();
+        assert!(state.pinned_rows().is_empty());
+    }
 }
@@ -1,11 +1,14 @@
 //! Export modal rendering.
 
-use crate::export_modal::{ExportFocus, ExportFormat, ExportModal};
+use crate::export_modal::{ExportFocus, ExportFormat, ExportModal, ExportScope};
+use crate::sqlite_export::IfExists;
 use crate::CompressionFormat;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Color, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Borders, Clear, List, ListItem, Paragraph, Widget};
+use ratatui::widgets::{
+    Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Widget,
+};
 
 /// Render the export modal with format selector on left, options on right.
 pub fn render_export_modal(
@@ -22,7 +25,7 @@ pub fn render_export_modal(
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(border_color))
-        .title("Export Data");
+        .title(modal.title());
     let inner = block.inner(area);
     block.render(area, buf);
 
@@ -35,8 +38,16 @@ pub fn render_export_modal(
         ])
         .split(inner);
 
-    // Left: Format selector (list)
-    render_format_list(chunks[0], buf, modal, border_color, active_color);
+    // Left: Format selector (list) above the scope selector
+    let left_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(modal.formats().len() as u16 + 2),
+            Constraint::Min(ExportScope::ALL.len() as u16 + 2),
+        ])
+        .split(chunks[0]);
+    render_format_list(left_chunks[0], buf, modal, border_color, active_color);
+    render_scope_list(left_chunks[1], buf, modal, border_color, active_color);
 
     // Right: Path input and format-specific options
     let right_chunks = Layout::default()
@@ -96,7 +107,8 @@ fn render_format_list(
     let inner = block.inner(area);
     block.render(area, buf);
 
-    let items: Vec<ListItem> = ExportFormat::ALL
+    let items: Vec<ListItem> = modal
+        .formats()
         .iter()
         .map(|format| {
             let marker = if modal.selected_format == *format {
@@ -124,6 +136,59 @@ fn render_format_list(
     list.render(inner, buf);
 }
 
+/// Scopes that can't be chosen (no drawn page, no selection) are dimmed; analysis results list
+/// only their own scope.
+fn render_scope_list(
+    area: Rect,
+    buf: &mut ratatui::buffer::Buffer,
+    modal: &mut ExportModal,
+    border_color: Color,
+    active_color: Color,
+) {
+    let is_focused = modal.focus == ExportFocus::ScopeSelector;
+    let border_style = if is_focused {
+        Style::default().fg(active_color)
+    } else {
+        Style::default().fg(border_color)
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(border_style)
+        .title("Rows");
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let available = modal.available_scopes();
+    let listed = if modal.analysis.is_some() {
+        available.clone()
+    } else {
+        ExportScope::ALL.to_vec()
+    };
+    let items: Vec<ListItem> = listed
+        .iter()
+        .map(|scope| {
+            let marker = if modal.scope == *scope { "●" } else { "○" };
+            let style = if modal.scope == *scope {
+                Style::default().fg(active_color)
+            } else if available.contains(scope) {
+                Style::default().fg(border_color)
+            } else {
+                Style::default()
+                    .fg(border_color)
+                    .add_modifier(Modifier::DIM)
+            };
+            ListItem::new(Line::from(vec![Span::styled(
+                format!("{} {}", marker, scope.as_str()),
+                style,
+            )]))
+        })
+        .collect();
+
+    List::new(items).render(inner, buf);
+}
+
 fn render_path_input(
     area: Rect,
     buf: &mut ratatui::buffer::Buffer,
@@ -184,7 +249,14 @@ fn render_format_options(
         ExportFormat::Ndjson => {
             render_ndjson_options(inner, buf, modal, border_color, active_color)
         }
-        ExportFormat::Parquet | ExportFormat::Ipc | ExportFormat::Avro => {
+        ExportFormat::Xlsx => render_xlsx_options(inner, buf, modal, border_color, active_color),
+        ExportFormat::Sqlite => {
+            render_sqlite_options(inner, buf, modal, border_color, active_color)
+        }
+        ExportFormat::Parquet => {
+            render_parquet_options(inner, buf, modal, border_color, active_color)
+        }
+        ExportFormat::Ipc | ExportFormat::Avro | ExportFormat::Markdown => {
             render_no_format_options(inner, buf, modal, border_color, active_color)
         }
     }
@@ -413,6 +485,190 @@ fn render_ndjson_options(
     }
 }
 
+/// Partition columns for a hive-style export; the order they're chosen in is the directory order.
+fn render_parquet_options(
+    area: Rect,
+    buf: &mut ratatui::buffer::Buffer,
+    modal: &mut ExportModal,
+    border_color: Color,
+    active_color: Color,
+) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Label row
+            Constraint::Min(1),    // Column list
+            Constraint::Length(1), // Path hint
+        ])
+        .split(area);
+
+    let is_focused = modal.focus == ExportFocus::ParquetPartitions;
+    let style = |focused: bool| {
+        if focused {
+            Style::default().fg(active_color)
+        } else {
+            Style::default().fg(border_color)
+        }
+    };
+    Paragraph::new("Partition By (Space toggles):")
+        .style(style(is_focused))
+        .render(rows[0], buf);
+
+    let items: Vec<ListItem> = modal
+        .columns
+        .iter()
+        .map(|column| {
+            let label = match modal.parquet_partition_by.iter().position(|c| c == column) {
+                Some(i) => format!("[{}] {}", i + 1, column),
+                None => format!("[ ] {}", column),
+            };
+            ListItem::new(Line::from(Span::styled(
+                label,
+                style(modal.parquet_partition_by.contains(column)),
+            )))
+        })
+        .collect();
+    let mut list_state = ListState::default();
+    if is_focused {
+        list_state.select(Some(modal.partition_cursor));
+    }
+    ratatui::widgets::StatefulWidget::render(
+        List::new(items).highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+        rows[1],
+        buf,
+        &mut list_state,
+    );
+
+    let hint = if modal.is_partitioned() {
+        "Path is a directory: col=value/ folders"
+    } else {
+        "None chosen: a single file"
+    };
+    Paragraph::new(hint)
+        .style(Style::default().fg(border_color))
+        .render(rows[2], buf);
+}
+
+fn render_xlsx_options(
+    area: Rect,
+    buf: &mut ratatui::buffer::Buffer,
+    modal: &mut ExportModal,
+    border_color: Color,
+    active_color: Color,
+) {
+    // Row 1: Sheet name label + input
+    // Row 2: Auto-width label + checkbox
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Sheet name row
+            Constraint::Length(1), // Auto-width row
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let label_row = |row: Rect| {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(15), // Fixed width for label alignment
+                Constraint::Length(2),  // Padding between label and widget
+                Constraint::Min(1),
+            ])
+            .split(row)
+    };
+    let label_style = |focused: bool| {
+        if focused {
+            Style::default().fg(active_color)
+        } else {
+            Style::default().fg(border_color)
+        }
+    };
+
+    let sheet_row = label_row(rows[0]);
+    let is_sheet_focused = modal.focus == ExportFocus::XlsxSheetName;
+    Paragraph::new("Sheet Name:")
+        .style(label_style(is_sheet_focused))
+        .render(sheet_row[0], buf);
+    modal.xlsx_sheet_name_input.set_focused(is_sheet_focused);
+    (&modal.xlsx_sheet_name_input).render(sheet_row[2], buf);
+
+    let autofit_row = label_row(rows[1]);
+    let is_autofit_focused = modal.focus == ExportFocus::XlsxAutofit;
+    Paragraph::new("Auto-width:")
+        .style(label_style(is_autofit_focused))
+        .render(autofit_row[0], buf);
+    let marker = if modal.xlsx_autofit { "☑" } else { "☐" };
+    Paragraph::new(Line::from(vec![Span::styled(
+        marker,
+        label_style(is_autofit_focused),
+    )]))
+    .render(autofit_row[2], buf);
+}
+
+fn render_sqlite_options(
+    area: Rect,
+    buf: &mut ratatui::buffer::Buffer,
+    modal: &mut ExportModal,
+    border_color: Color,
+    active_color: Color,
+) {
+    // Row 1: Table name label + input
+    // Row 2: If-exists label + horizontal radio buttons
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Table name row
+            Constraint::Length(1), // If exists row
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let label_row = |row: Rect| {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(15), // Fixed width for label alignment
+                Constraint::Length(2),  // Padding between label and widget
+                Constraint::Min(1),
+            ])
+            .split(row)
+    };
+    let label_style = |focused: bool| {
+        if focused {
+            Style::default().fg(active_color)
+        } else {
+            Style::default().fg(border_color)
+        }
+    };
+
+    let table_row = label_row(rows[0]);
+    let is_table_focused = modal.focus == ExportFocus::SqliteTable;
+    Paragraph::new("Table Name:")
+        .style(label_style(is_table_focused))
+        .render(table_row[0], buf);
+    modal.sqlite_table_input.set_focused(is_table_focused);
+    (&modal.sqlite_table_input).render(table_row[2], buf);
+
+    let exists_row = label_row(rows[1]);
+    let is_exists_focused = modal.focus == ExportFocus::SqliteIfExists;
+    Paragraph::new("If Exists:")
+        .style(label_style(is_exists_focused))
+        .render(exists_row[0], buf);
+    let spans: Vec<Span> = IfExists::ALL
+        .iter()
+        .map(|behavior| {
+            let selected = modal.sqlite_if_exists == *behavior;
+            let marker = if selected { "●" } else { "○" };
+            Span::styled(
+                format!("{} {}  ", marker, behavior.as_str()),
+                label_style(is_exists_focused && selected),
+            )
+        })
+        .collect();
+    Paragraph::new(Line::from(spans)).render(exists_row[2], buf);
+}
+
 fn render_no_format_options(
     area: Rect,
     buf: &mut ratatui::buffer::Buffer,
@@ -508,6 +764,9 @@ fn render_footer(
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(area);
+    modal.buttons.clear();
+    modal.buttons.add(chunks[0], ExportFocus::ExportButton);
+    modal.buttons.add(chunks[1], ExportFocus::CancelButton);
 
     // Export button
     let is_focused = modal.focus == ExportFocus::ExportButton;
@@ -20,7 +20,11 @@ use ratatui::widgets::{
 };
 
 use super::datatable::DataTableState;
+use crate::concat::ConcatSources;
 use crate::export_modal::ExportFormat;
+use crate::load_profile::LoadProfileInfo;
+use crate::table_picker::SourceTables;
+use crate::time_zone_modal::time_zone_label;
 
 /// Human-readable byte size (e.g. "1.2 MiB", "456 KiB").
 pub fn format_bytes(n: u64) -> String {
@@ -226,11 +230,16 @@ impl InfoModal {
     }
 }
 
-/// Context for the info panel: path, format, optional Parquet metadata.
+/// Context for the info panel: path, format, optional Parquet metadata, load profile, the
+/// files behind each column of a diagonal concatenation and the sheets or tables of the file.
 pub struct InfoContext<'a> {
     pub path: Option<&'a Path>,
     pub format: Option<ExportFormat>,
     pub parquet_metadata: Option<&'a ParquetMetadataCache>,
+    pub load_profile: Option<&'a LoadProfileInfo>,
+    pub concat_sources: Option<&'a ConcatSources>,
+    /// Sheets or tables of the loaded file and the key that switches between them.
+    pub source_tables: Option<(&'a SourceTables, String)>,
 }
 
 impl<'a> InfoContext<'a> {
@@ -356,6 +365,16 @@ impl<'a> DataTableInfo<'a> {
         if !by_type.is_empty() {
             lines.push(by_type);
         }
+        if let Some((tables, key)) = &self.ctx.source_tables {
+            lines.push(format!("{} · {} to switch", tables.label(), key));
+        }
+        if let Some(sources) = self.ctx.concat_sources {
+            lines.push(format!(
+                "Diagonal concat of {} files · {} columns not in every file",
+                sources.files.len(),
+                sources.partial_count()
+            ));
+        }
         for (i, s) in lines.iter().enumerate() {
             Paragraph::new(s.as_str()).render(
                 Rect {
@@ -377,11 +396,22 @@ impl<'a> DataTableInfo<'a> {
             .parquet_metadata
             .map(|m| parquet_column_compression(m.as_ref(), self.state.schema.as_ref()));
         let has_comp = compression.as_ref().is_some_and(|c| !c.is_empty());
-        let header = if has_comp {
-            Row::new(vec!["Column", "Type", "Source", "Compression"]).bold()
-        } else {
-            Row::new(vec!["Column", "Type", "Source"]).bold()
-        };
+        let has_zone = self
+            .state
+            .schema
+            .iter()
+            .any(|(_, dtype)| time_zone_label(dtype).is_some());
+        let mut header = vec!["Column", "Type"];
+        if has_zone {
+            header.push("Time zone");
+        }
+        // After a diagonal concat, the files that have each column replace the schema source
+        let files = self.ctx.concat_sources;
+        header.push(if files.is_some() { "Files" } else { "Source" });
+        if has_comp {
+            header.push("Compression");
+        }
+        let header = Row::new(header).bold();
 
         let total_rows = self.state.schema.len();
         let body_focused = self.modal.focus == InfoFocus::Body;
@@ -420,33 +450,36 @@ impl<'a> DataTableInfo<'a> {
                 .and_then(|c| c.get(name_str))
                 .map(|(codec, ratio)| format!("{} {:.1}×", codec, ratio))
                 .unwrap_or_else(|| "—".to_string());
-            let row = if has_comp {
-                Row::new(vec![
-                    name.to_string(),
-                    dtype.to_string(),
-                    src.to_string(),
-                    comp_str,
-                ])
-            } else {
-                Row::new(vec![name.to_string(), dtype.to_string(), src.to_string()])
-            };
-            rows.push(row);
+            let mut cells = vec![name.to_string(), dtype.to_string()];
+            if has_zone {
+                cells.push(time_zone_label(dtype).unwrap_or_else(|| "—".to_string()));
+            }
+            cells.push(match files {
+                Some(sources) => sources.label(name_str).unwrap_or_else(|| "—".to_string()),
+                None => src.to_string(),
+            });
+            if has_comp {
+                cells.push(comp_str);
+            }
+            rows.push(Row::new(cells));
         }
 
-        let widths: Vec<Constraint> = if has_comp {
-            vec![
-                Constraint::Percentage(25),
-                Constraint::Percentage(35),
-                Constraint::Percentage(15),
-                Constraint::Percentage(25),
-            ]
-        } else {
-            vec![
-                Constraint::Percentage(40),
-                Constraint::Percentage(40),
-                Constraint::Percentage(20),
-            ]
+        let mut percentages: Vec<u16> = match (has_comp, has_zone) {
+            (true, true) => vec![22, 25, 18, 13, 22],
+            (true, false) => vec![25, 35, 15, 25],
+            (false, true) => vec![30, 30, 22, 18],
+            (false, false) => vec![40, 40, 20],
         };
+        if files.is_some() {
+            // File names need more room than "Known"/"Inferred"
+            percentages[2 + usize::from(has_zone)] *= 3;
+            let total: u16 = percentages.iter().sum();
+            percentages.iter_mut().for_each(|p| *p = *p * 100 / total);
+        }
+        let widths: Vec<Constraint> = percentages
+            .into_iter()
+            .map(Constraint::Percentage)
+            .collect();
         let table = Table::new(rows, widths)
             .header(header)
             .column_spacing(1)
@@ -509,6 +542,42 @@ impl<'a> DataTableInfo<'a> {
         );
         y += 1;
 
+        if y >= area.y + h {
+            return;
+        }
+        let profile = match self.ctx.load_profile {
+            Some(info) => {
+                let how = if info.automatic {
+                    match info.input_bytes {
+                        Some(bytes) => format!("auto, {}", format_bytes(bytes)),
+                        None => "auto, size unknown".to_string(),
+                    }
+                } else {
+                    "chosen".to_string()
+                };
+                format!(
+                    "{} ({}): {} · p: change",
+                    info.profile.label(),
+                    how,
+                    info.profile.summary()
+                )
+            }
+            None => "—".to_string(),
+        };
+        label_value_row(
+            "Load profile:",
+            &profile,
+            Rect {
+                y,
+                width: w,
+                height: 1,
+                ..area
+            },
+            buf,
+            LABEL_WIDTH,
+        );
+        y += 1;
+
         if y >= area.y + h {
             return;
         }
@@ -1,6 +1,7 @@
 pub mod analysis;
 pub mod chart;
 pub mod chart_export_modal;
+pub mod chart_labels_modal;
 pub mod controls;
 pub mod datatable;
 pub mod debug;
@@ -100,6 +100,10 @@ pub fn render_shell(
             Constraint::Percentage(34),
         ])
         .split(chunks[2]);
+    modal.buttons.clear();
+    modal.buttons.add(footer_chunks[0], PivotMeltFocus::Apply);
+    modal.buttons.add(footer_chunks[1], PivotMeltFocus::Cancel);
+    modal.buttons.add(footer_chunks[2], PivotMeltFocus::Clear);
 
     let apply_style = if modal.focus == PivotMeltFocus::Apply {
         Style::default().fg(active_color)
@@ -0,0 +1,541 @@
+//! Workspace directory mode: `datui ./data/` lists the supported files in a directory
+//! (name, format, size, estimated rows) and opens them in tabs. Files opened from inside the
+//! TUI (`o`) use the same tabs, without a file list.
+//!
+//! Only the current tab's dataset lives in `App`; the others are parked here as
+//! [`SavedDataset`]s and swapped back in when their tab is selected.
+
+use crate::chart_modal::ChartModal;
+use crate::export_modal::ExportFormat;
+use crate::load_profile::LoadProfileInfo;
+use crate::locale::format_count;
+use crate::schema_diff::SchemaDiff;
+use crate::widgets::datatable::DataTableState;
+use crate::{CompressionFormat, FileFormat, OpenOptions};
+use polars::prelude::{ParquetReader, SerReader};
+use ratatui::widgets::TableState;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Bytes read from the start of a line-based file to estimate its row count.
+const ROW_ESTIMATE_SAMPLE_BYTES: usize = 64 * 1024;
+
+/// Row count shown in the file list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowEstimate {
+    /// Read from file metadata (Parquet footer) or a file small enough to count fully.
+    Exact(usize),
+    /// Extrapolated from the average line length of a sample.
+    Approx(usize),
+    /// Format or compression does not allow a cheap estimate.
+    Unknown,
+}
+
+impl RowEstimate {
+    pub fn label(&self) -> String {
+        match self {
+            RowEstimate::Exact(n) => format_count(*n),
+            RowEstimate::Approx(n) => format!("~{}", format_count(*n)),
+            RowEstimate::Unknown => "?".to_string(),
+        }
+    }
+}
+
+/// One supported data file in the workspace directory.
+#[derive(Debug, Clone)]
+pub struct WorkspaceEntry {
+    pub path: PathBuf,
+    pub name: String,
+    pub format: FileFormat,
+    pub compression: Option<CompressionFormat>,
+    pub size: u64,
+    pub rows: RowEstimate,
+}
+
+impl WorkspaceEntry {
+    /// Format column text, e.g. "Parquet" or "CSV (gz)".
+    pub fn format_label(&self) -> String {
+        let name = format_name(self.format);
+        match self.compression {
+            Some(c) => format!("{} ({})", name, c.extension()),
+            None => name.to_string(),
+        }
+    }
+
+    /// Pattern for this file and every other file in its directory with the same extension,
+    /// e.g. `dir/*.csv` or `dir/*.csv.gz`.
+    pub fn extension_pattern(&self) -> Option<PathBuf> {
+        let ext = self.path.extension()?.to_string_lossy();
+        let pattern = match self.compression {
+            Some(_) => {
+                let inner = Path::new(self.path.file_stem()?).extension()?;
+                format!("*.{}.{}", inner.to_string_lossy(), ext)
+            }
+            None => format!("*.{ext}"),
+        };
+        Some(self.path.with_file_name(pattern))
+    }
+}
+
+/// Files per format label (e.g. "CSV", "CSV (gz)"), most common first; ties keep list order.
+pub fn format_groups(entries: &[WorkspaceEntry]) -> Vec<(String, usize)> {
+    let mut groups: Vec<(String, usize)> = Vec::new();
+    for entry in entries {
+        let label = entry.format_label();
+        match groups.iter_mut().find(|(l, _)| *l == label) {
+            Some((_, n)) => *n += 1,
+            None => groups.push((label, 1)),
+        }
+    }
+    groups.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+    groups
+}
+
+/// The files of `format` directly inside `dir` (uncompressed only, as multiple paths are
+/// loaded), sorted by name.
+pub fn files_of_format(dir: &Path, format: FileFormat) -> color_eyre::Result<Vec<PathBuf>> {
+    let files: Vec<PathBuf> = scan_directory(dir)?
+        .into_iter()
+        .filter(|entry| entry.format == format && entry.compression.is_none())
+        .map(|entry| entry.path)
+        .collect();
+    if files.is_empty() {
+        return Err(color_eyre::eyre::eyre!(
+            "No {} files in {}",
+            format_name(format),
+            dir.display()
+        ));
+    }
+    Ok(files)
+}
+
+pub fn format_name(format: FileFormat) -> &'static str {
+    match format {
+        FileFormat::Parquet => "Parquet",
+        FileFormat::Csv => "CSV",
+        FileFormat::Tsv => "TSV",
+        FileFormat::Psv => "PSV",
+        FileFormat::Json => "JSON",
+        FileFormat::Jsonl => "JSONL",
+        FileFormat::Arrow => "Arrow",
+        FileFormat::Avro => "Avro",
+        FileFormat::Orc => "ORC",
+        FileFormat::Excel => "Excel",
+        FileFormat::Sqlite => "SQLite",
+        FileFormat::Protobuf => "Protobuf",
+    }
+}
+
+/// Format of a file datui can open on its own, by extension. Compressed files are only
+/// supported for CSV (e.g. `data.csv.gz`), matching the loader.
+pub fn detect_format(path: &Path) -> Option<(FileFormat, Option<CompressionFormat>)> {
+    if let Some(compression) = CompressionFormat::from_extension(path) {
+        let inner = Path::new(path.file_stem()?);
+        return match FileFormat::from_path(inner) {
+            Some(FileFormat::Csv) => Some((FileFormat::Csv, Some(compression))),
+            _ => None,
+        };
+    }
+    FileFormat::from_path(path).map(|f| (f, None))
+}
+
+/// List the supported files directly inside `dir` (not recursive), sorted by name.
+/// Hidden files and subdirectories are skipped.
+pub fn scan_directory(dir: &Path) -> std::io::Result<Vec<WorkspaceEntry>> {
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        let Some((format, compression)) = detect_format(&path) else {
+            continue;
+        };
+        let size = metadata.len();
+        let rows = estimate_rows(&path, format, compression, size);
+        entries.push(WorkspaceEntry {
+            path,
+            name,
+            format,
+            compression,
+            size,
+            rows,
+        });
+    }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entries)
+}
+
+/// Cheap row estimate: the Parquet footer, or a line count sampled from the start of
+/// delimited and JSON Lines files. Everything else is [`RowEstimate::Unknown`].
+pub fn estimate_rows(
+    path: &Path,
+    format: FileFormat,
+    compression: Option<CompressionFormat>,
+    size: u64,
+) -> RowEstimate {
+    if compression.is_some() {
+        return RowEstimate::Unknown;
+    }
+    match format {
+        FileFormat::Parquet => File::open(path)
+            .ok()
+            .and_then(|file| ParquetReader::new(file).num_rows().ok())
+            .map_or(RowEstimate::Unknown, RowEstimate::Exact),
+        FileFormat::Csv | FileFormat::Tsv | FileFormat::Psv => estimate_lines(path, size, true),
+        FileFormat::Jsonl => estimate_lines(path, size, false),
+        _ => RowEstimate::Unknown,
+    }
+}
+
+fn estimate_lines(path: &Path, size: u64, has_header: bool) -> RowEstimate {
+    let Ok(file) = File::open(path) else {
+        return RowEstimate::Unknown;
+    };
+    let mut sample = Vec::with_capacity(ROW_ESTIMATE_SAMPLE_BYTES);
+    if file
+        .take(ROW_ESTIMATE_SAMPLE_BYTES as u64)
+        .read_to_end(&mut sample)
+        .is_err()
+    {
+        return RowEstimate::Unknown;
+    }
+    let newlines = sample.iter().filter(|&&b| b == b'\n').count();
+    let header = usize::from(has_header);
+    if sample.len() as u64 >= size {
+        // Whole file read: count lines, including a last line without a trailing newline.
+        let unterminated = usize::from(sample.last().is_some_and(|&b| b != b'\n'));
+        return RowEstimate::Exact((newlines + unterminated).saturating_sub(header));
+    }
+    if newlines == 0 {
+        return RowEstimate::Unknown;
+    }
+    let avg_line_len = sample.len() as f64 / newlines as f64;
+    let lines = (size as f64 / avg_line_len).round() as usize;
+    RowEstimate::Approx(lines.saturating_sub(header))
+}
+
+/// Dataset of a tab that is not currently shown, with the view state that belongs to it.
+pub struct SavedDataset {
+    pub state: DataTableState,
+    pub path: Option<PathBuf>,
+    pub original_file_format: Option<ExportFormat>,
+    pub original_file_delimiter: Option<u8>,
+    pub active_template_id: Option<String>,
+    pub chart_modal: ChartModal,
+    pub(crate) chart_cache: crate::ChartCache,
+    /// The tab was showing its chart when it was parked.
+    pub chart_open: bool,
+    pub load_profile: Option<LoadProfileInfo>,
+    pub sampling_threshold: Option<usize>,
+    pub sampling_strategy: crate::sampling::SamplingStrategy,
+    pub last_open: Option<(Vec<PathBuf>, OpenOptions)>,
+}
+
+pub struct WorkspaceTab {
+    pub path: PathBuf,
+    /// None for the current tab, whose dataset is held by `App`.
+    pub saved: Option<SavedDataset>,
+}
+
+impl WorkspaceTab {
+    pub fn title(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+}
+
+/// Schema comparison of the workspace files, shown in place of the file list.
+pub struct SchemaDiffView {
+    pub diff: SchemaDiff,
+    pub table_state: TableState,
+    /// First file column shown (scrolled with Left/Right).
+    pub file_offset: usize,
+    /// Show only columns that drift or are out of order.
+    pub drift_only: bool,
+}
+
+impl SchemaDiffView {
+    pub fn new(diff: SchemaDiff) -> Self {
+        let mut table_state = TableState::default();
+        if !diff.columns.is_empty() {
+            table_state.select(Some(0));
+        }
+        Self {
+            diff,
+            table_state,
+            file_offset: 0,
+            drift_only: false,
+        }
+    }
+
+    /// Indices into `diff.columns` of the rows currently shown.
+    pub fn visible_columns(&self) -> Vec<usize> {
+        (0..self.diff.columns.len())
+            .filter(|&i| !self.drift_only || self.diff.differs(&self.diff.columns[i]))
+            .collect()
+    }
+
+    pub fn toggle_drift_only(&mut self) {
+        self.drift_only = !self.drift_only;
+        let any = !self.visible_columns().is_empty();
+        self.table_state.select(any.then_some(0));
+    }
+
+    pub fn select_next(&mut self) {
+        let len = self.visible_columns().len();
+        if let Some(i) = self.table_state.selected() {
+            if i + 1 < len {
+                self.table_state.select(Some(i + 1));
+            }
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if let Some(i) = self.table_state.selected() {
+            self.table_state.select(Some(i.saturating_sub(1)));
+        }
+    }
+
+    pub fn scroll_files_right(&mut self) {
+        if self.file_offset + 1 < self.diff.files.len() {
+            self.file_offset += 1;
+        }
+    }
+
+    pub fn scroll_files_left(&mut self) {
+        self.file_offset = self.file_offset.saturating_sub(1);
+    }
+}
+
+pub struct Workspace {
+    /// Directory whose files are listed; None when tabs were opened one file at a time.
+    pub dir: Option<PathBuf>,
+    pub entries: Vec<WorkspaceEntry>,
+    pub table_state: TableState,
+    pub tabs: Vec<WorkspaceTab>,
+    /// Index into `tabs` of the tab whose dataset is loaded in `App`.
+    pub current: Option<usize>,
+    /// File list pane shown instead of the current tab.
+    pub list_visible: bool,
+    /// Options the workspace was opened with; reused for every file opened from it.
+    pub options: OpenOptions,
+    /// Schema comparison shown instead of the file list, when open.
+    pub schema_diff: Option<SchemaDiffView>,
+}
+
+impl Workspace {
+    pub fn new(dir: PathBuf, entries: Vec<WorkspaceEntry>, options: OpenOptions) -> Self {
+        let mut table_state = TableState::default();
+        if !entries.is_empty() {
+            table_state.select(Some(0));
+        }
+        Self {
+            dir: Some(dir),
+            entries,
+            table_state,
+            tabs: Vec::new(),
+            current: None,
+            list_visible: true,
+            options,
+            schema_diff: None,
+        }
+    }
+
+    /// Tabs without a directory file list, for files opened from inside the TUI.
+    pub fn without_dir(options: OpenOptions) -> Self {
+        Self {
+            dir: None,
+            entries: Vec::new(),
+            table_state: TableState::default(),
+            tabs: Vec::new(),
+            current: None,
+            list_visible: false,
+            options,
+            schema_diff: None,
+        }
+    }
+
+    pub fn has_file_list(&self) -> bool {
+        self.dir.is_some()
+    }
+
+    pub fn selected_entry(&self) -> Option<&WorkspaceEntry> {
+        self.table_state
+            .selected()
+            .and_then(|i| self.entries.get(i))
+    }
+
+    pub fn select_next(&mut self) {
+        if let Some(i) = self.table_state.selected() {
+            if i + 1 < self.entries.len() {
+                self.table_state.select(Some(i + 1));
+            }
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if let Some(i) = self.table_state.selected() {
+            self.table_state.select(Some(i.saturating_sub(1)));
+        }
+    }
+
+    pub fn select_first(&mut self) {
+        if !self.entries.is_empty() {
+            self.table_state.select(Some(0));
+        }
+    }
+
+    pub fn select_last(&mut self) {
+        if !self.entries.is_empty() {
+            self.table_state.select(Some(self.entries.len() - 1));
+        }
+    }
+
+    /// Tab already showing `path`, if any.
+    pub fn tab_index(&self, path: &Path) -> Option<usize> {
+        self.tabs.iter().position(|t| t.path == path)
+    }
+
+    /// Tab `offset` positions from the current one, wrapping around.
+    pub fn relative_tab(&self, offset: isize) -> Option<usize> {
+        let current = self.current?;
+        let len = self.tabs.len() as isize;
+        Some((current as isize + offset).rem_euclid(len) as usize)
+    }
+
+    /// Remove tab `index`. Returns the tab to show in its place: the one that moved into its
+    /// position, else the new last tab; None when no tabs are left.
+    pub fn remove_tab(&mut self, index: usize) -> Option<usize> {
+        self.tabs.remove(index);
+        self.current = match self.current {
+            Some(c) if c == index => None,
+            Some(c) if c > index => Some(c - 1),
+            other => other,
+        };
+        if self.tabs.is_empty() {
+            None
+        } else {
+            Some(index.min(self.tabs.len() - 1))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            detect_format(Path::new("a.parquet")),
+            Some((FileFormat::Parquet, None))
+        );
+        assert_eq!(
+            detect_format(Path::new("a.csv.gz")),
+            Some((FileFormat::Csv, Some(CompressionFormat::Gzip)))
+        );
+        assert_eq!(detect_format(Path::new("a.json.gz")), None);
+        assert_eq!(detect_format(Path::new("notes.txt")), None);
+    }
+
+    #[test]
+    fn test_scan_directory_lists_supported_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("b.csv"), "x,y\n1,2\n3,4\n").unwrap();
+        std::fs::write(dir.path().join("a.jsonl"), "{\"x\":1}\n{\"x\":2}").unwrap();
+        std::fs::write(dir.path().join("readme.txt"), "ignored").unwrap();
+        std::fs::write(dir.path().join(".hidden.csv"), "x\n1\n").unwrap();
+        std::fs::create_dir(dir.path().join("sub.csv")).unwrap();
+
+        let entries = scan_directory(dir.path()).unwrap();
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["a.jsonl", "b.csv"]);
+        assert_eq!(entries[0].rows, RowEstimate::Exact(2));
+        assert_eq!(entries[1].rows, RowEstimate::Exact(2));
+        assert_eq!(entries[1].format_label(), "CSV");
+    }
+
+    #[test]
+    fn test_format_groups_and_extension_patterns() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["a.csv", "b.csv", "c.parquet", "d.csv.gz"] {
+            std::fs::write(dir.path().join(name), "x\n1\n").unwrap();
+        }
+        let entries = scan_directory(dir.path()).unwrap();
+        assert_eq!(
+            format_groups(&entries),
+            vec![
+                ("CSV".to_string(), 2),
+                ("Parquet".to_string(), 1),
+                ("CSV (gz)".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            entries[0].extension_pattern(),
+            Some(dir.path().join("*.csv"))
+        );
+        assert_eq!(
+            entries[3].extension_pattern(),
+            Some(dir.path().join("*.csv.gz"))
+        );
+        let csvs = files_of_format(dir.path(), FileFormat::Csv).unwrap();
+        assert_eq!(
+            csvs,
+            vec![dir.path().join("a.csv"), dir.path().join("b.csv")]
+        );
+        assert!(files_of_format(dir.path(), FileFormat::Avro).is_err());
+    }
+
+    #[test]
+    fn test_remove_tab_picks_neighbour() {
+        let mut ws = Workspace::without_dir(OpenOptions::default());
+        for name in ["a.csv", "b.csv", "c.csv"] {
+            ws.tabs.push(WorkspaceTab {
+                path: PathBuf::from(name),
+                saved: None,
+            });
+        }
+        ws.current = Some(2);
+        assert_eq!(ws.remove_tab(0), Some(0));
+        assert_eq!(ws.current, Some(1), "current tab shifts left");
+        assert_eq!(ws.remove_tab(1), Some(0), "last tab falls back to previous");
+        assert_eq!(ws.current, None);
+        assert_eq!(ws.remove_tab(0), None);
+        assert!(!ws.has_file_list());
+    }
+
+    #[test]
+    fn test_estimate_lines_extrapolates_large_files() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "id,value").unwrap();
+        for i in 0..20_000 {
+            writeln!(file, "{:05},{:05}", i, i).unwrap();
+        }
+        file.flush().unwrap();
+        let size = file.as_file().metadata().unwrap().len();
+        match estimate_lines(file.path(), size, true) {
+            RowEstimate::Approx(n) => assert!((19_900..=20_100).contains(&n), "got {n}"),
+            other => panic!("expected approximate count, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_row_estimate_label() {
+        assert_eq!(RowEstimate::Exact(1234567).label(), "1,234,567");
+        assert_eq!(RowEstimate::Approx(999).label(), "~999");
+        assert_eq!(RowEstimate::Unknown.label(), "?");
+    }
+}
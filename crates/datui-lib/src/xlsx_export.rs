@@ -0,0 +1,166 @@
+//! Excel (XLSX) export: one worksheet with a bold, frozen header row. Numbers and booleans keep
+//! their type, dates and times become Excel dates, and other values (lists, structs, durations,
+//! ...) are written as text. Nulls are left blank.
+
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use polars::prelude::*;
+use rust_xlsxwriter::{Format, Workbook, Worksheet};
+use std::path::Path;
+
+/// Rows in an Excel worksheet, including the header.
+const MAX_ROWS: usize = 1_048_576;
+/// Columns in an Excel worksheet.
+const MAX_COLUMNS: usize = 16_384;
+
+/// Sheet name used when none is given.
+pub const DEFAULT_SHEET_NAME: &str = "Sheet1";
+
+struct CellFormats {
+    date: Format,
+    datetime: Format,
+    time: Format,
+}
+
+/// Write `df` to `path` as a workbook with one sheet named `sheet_name` (the default when
+/// blank). With `autofit`, columns are sized to their contents.
+pub fn write_xlsx(df: &DataFrame, path: &Path, sheet_name: &str, autofit: bool) -> Result<()> {
+    if df.height() + 1 > MAX_ROWS {
+        return Err(eyre!(
+            "Excel sheets hold at most {} rows; export fewer rows or another format",
+            MAX_ROWS - 1
+        ));
+    }
+    if df.width() > MAX_COLUMNS {
+        return Err(eyre!("Excel sheets hold at most {} columns", MAX_COLUMNS));
+    }
+    let sheet_name = match sheet_name.trim() {
+        "" => DEFAULT_SHEET_NAME,
+        name => name,
+    };
+    let formats = CellFormats {
+        date: Format::new().set_num_format("yyyy-mm-dd"),
+        datetime: Format::new().set_num_format("yyyy-mm-dd hh:mm:ss"),
+        time: Format::new().set_num_format("hh:mm:ss"),
+    };
+    let header = Format::new().set_bold();
+
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    sheet
+        .set_name(sheet_name)
+        .map_err(|e| eyre!("Invalid sheet name '{}': {}", sheet_name, e))?;
+    for (c, column) in df.get_columns().iter().enumerate() {
+        let c = c as u16;
+        sheet.write_string_with_format(0, c, column.name().as_str(), &header)?;
+        let series = column.as_materialized_series();
+        for (r, value) in series.iter().enumerate() {
+            write_value(sheet, r as u32 + 1, c, &value, &formats)?;
+        }
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    if autofit {
+        sheet.autofit();
+    }
+    workbook.save(path)?;
+    Ok(())
+}
+
+fn write_value(
+    sheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    value: &AnyValue,
+    formats: &CellFormats,
+) -> Result<()> {
+    match value {
+        AnyValue::Null => {}
+        AnyValue::Boolean(b) => {
+            sheet.write_boolean(row, col, *b)?;
+        }
+        AnyValue::Date(days) => {
+            match chrono::NaiveDate::from_num_days_from_ce_opt(days + 719_163) {
+                Some(date) => {
+                    sheet.write_datetime_with_format(row, col, date, &formats.date)?;
+                }
+                None => {
+                    sheet.write_string(row, col, value.str_value())?;
+                }
+            }
+        }
+        AnyValue::Datetime(v, unit, _) | AnyValue::DatetimeOwned(v, unit, _) => {
+            // Excel has no time zones: the UTC time is written
+            let datetime = match unit {
+                TimeUnit::Nanoseconds => Some(chrono::DateTime::from_timestamp_nanos(*v)),
+                TimeUnit::Microseconds => chrono::DateTime::from_timestamp_micros(*v),
+                TimeUnit::Milliseconds => chrono::DateTime::from_timestamp_millis(*v),
+            };
+            match datetime {
+                Some(datetime) => {
+                    sheet.write_datetime_with_format(
+                        row,
+                        col,
+                        datetime.naive_utc(),
+                        &formats.datetime,
+                    )?;
+                }
+                None => {
+                    sheet.write_string(row, col, value.str_value())?;
+                }
+            }
+        }
+        AnyValue::Time(ns) => {
+            let secs = (ns / 1_000_000_000) as u32;
+            let nanos = (ns % 1_000_000_000) as u32;
+            match chrono::NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos) {
+                Some(time) => {
+                    sheet.write_datetime_with_format(row, col, time, &formats.time)?;
+                }
+                None => {
+                    sheet.write_string(row, col, value.str_value())?;
+                }
+            }
+        }
+        v if v.is_primitive_numeric() => match v.extract::<f64>() {
+            // Excel has no NaN or infinity
+            Some(n) if n.is_finite() => {
+                sheet.write_number(row, col, n)?;
+            }
+            _ => {
+                sheet.write_string(row, col, v.str_value())?;
+            }
+        },
+        v => {
+            sheet.write_string(row, col, v.str_value())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_keep_their_type() {
+        use calamine::{open_workbook, Data, Reader, Xlsx};
+        let df = df!(
+            "n" => [Some(1.5), None],
+            "s" => ["x", "y"],
+            "b" => [true, false],
+        )
+        .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.xlsx");
+        write_xlsx(&df, &path, "Sales", true).unwrap();
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        let range = workbook.worksheet_range("Sales").unwrap();
+        assert_eq!(range.get((0, 1)), Some(&Data::String("s".into())));
+        assert_eq!(range.get((1, 0)), Some(&Data::Float(1.5)));
+        assert_eq!(range.get((2, 0)), Some(&Data::Empty));
+        assert_eq!(range.get((1, 2)), Some(&Data::Bool(true)));
+
+        let err = write_xlsx(&df, &path, "bad/name", false).unwrap_err();
+        assert!(err.to_string().contains("Invalid sheet name"));
+    }
+}
@@ -0,0 +1,101 @@
+//! Tab-separated copies of table cells, for pasting into spreadsheets and chats: the selected
+//! cell (`y`), the selected row (`Y`), or a block picked in visual block mode (`Ctrl-V`). The
+//! visible page can also be copied as a Markdown table (`zm`), for issues and docs.
+//!
+//! Values are written without the display locale and nulls are empty. Tabs and line breaks
+//! inside a value become spaces so every row stays on one line.
+
+use polars::prelude::*;
+
+/// Plain text of one value.
+pub fn value_text(value: &AnyValue) -> String {
+    let text = match value {
+        AnyValue::Null => return String::new(),
+        v => v
+            .get_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| v.to_string()),
+    };
+    text.replace(['\t', '\n', '\r'], " ")
+}
+
+/// `df` as tab-separated lines, without a header or trailing newline.
+pub fn to_tsv(df: &DataFrame) -> String {
+    let columns = df.get_columns();
+    (0..df.height())
+        .map(|row| {
+            columns
+                .iter()
+                .map(|c| c.get(row).map(|v| value_text(&v)).unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join("\t")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `df` as a Markdown table with a header row; numeric columns are right-aligned and `|` in
+/// values is escaped.
+pub fn to_markdown(df: &DataFrame) -> String {
+    let columns = df.get_columns();
+    let cell = |text: String| text.replace('|', "\\|");
+    let row_line = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+    let mut lines = vec![
+        row_line(
+            columns
+                .iter()
+                .map(|c| cell(c.name().replace(['\t', '\n', '\r'], " ")))
+                .collect(),
+        ),
+        format!(
+            "|{}|",
+            columns
+                .iter()
+                .map(|c| if c.dtype().is_primitive_numeric() {
+                    " ---: "
+                } else {
+                    " --- "
+                })
+                .collect::<Vec<_>>()
+                .join("|")
+        ),
+    ];
+    lines.extend((0..df.height()).map(|row| {
+        row_line(
+            columns
+                .iter()
+                .map(|c| cell(c.get(row).map(|v| value_text(&v)).unwrap_or_default()))
+                .collect(),
+        )
+    }));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_tsv_cleans_values() {
+        let df = df!(
+            "name" => [Some("a\tb"), None, Some("line\nbreak")],
+            "n" => [1.5, 2.0, -3.25],
+        )
+        .unwrap();
+        assert_eq!(to_tsv(&df), "a b\t1.5\n\t2.0\nline break\t-3.25");
+        assert_eq!(to_tsv(&df.head(Some(0))), "");
+    }
+
+    #[test]
+    fn test_to_markdown_aligns_numbers_and_escapes_pipes() {
+        let df = df!(
+            "name" => [Some("a|b"), None],
+            "n" => [1i64, 20],
+        )
+        .unwrap();
+        assert_eq!(
+            to_markdown(&df),
+            "| name | n |\n| --- | ---: |\n| a\\|b | 1 |\n|  | 20 |"
+        );
+    }
+}
@@ -1,13 +1,15 @@
 //! Python bindings for datui. Exposes `view_from_bytes` (binary-serialized LazyFrame),
 //! `view_from_json` (JSON, deprecated by Polars), `view_paths` (open by path strings),
 //! `DatuiOptions`, `CompressionFormat`, and `run_cli`. The Python package provides
-//! `view()` which accepts LazyFrame/DataFrame or path string(s) and dispatches accordingly.
+//! `view()` which accepts LazyFrame/DataFrame or path string(s) and dispatches accordingly,
+//! and `show()`, which also opens the data with a template, query, filters, sort and column order.
 //!
 //! Error classification lives in datui-lib; the binding only maps lib result to Python exceptions.
 
 use std::panic;
 use std::path::{Path, PathBuf};
 
+use ::datui::initial_view::{parse_filter, InitialView};
 use ::datui::{
     error_for_python, CompressionFormat, ErrorKindForPython, FileFormat, OpenOptions, ParseStringsTarget,
     RunInput, run,
@@ -53,6 +55,8 @@ fn format_to_str(f: FileFormat) -> &'static str {
         FileFormat::Avro => "avro",
         FileFormat::Orc => "orc",
         FileFormat::Excel => "excel",
+        FileFormat::Sqlite => "sqlite",
+        FileFormat::Protobuf => "protobuf",
     }
 }
 
@@ -124,11 +128,28 @@ fn parse_strings_from_py(any: Option<&Bound<'_, pyo3::types::PyAny>>) -> PyResul
     ))
 }
 
+/// Convert a str or list of str to a list (None → empty). `what` names the argument in errors.
+fn str_list_from_py(any: Option<&Bound<'_, pyo3::types::PyAny>>, what: &str) -> PyResult<Vec<String>> {
+    let Some(any) = any else { return Ok(Vec::new()) };
+    if any.is_none() {
+        return Ok(Vec::new());
+    }
+    if let Ok(s) = any.extract::<String>() {
+        return Ok(vec![s]);
+    }
+    any.extract::<Vec<String>>().map_err(|_| {
+        PyTypeError::new_err(format!("{} must be a str or a list of str", what))
+    })
+}
+
 /// Options for loading and displaying data in the TUI (Python name for OpenOptions).
 ///
 /// **parse_strings**: Default is all CSV string columns (trim + type inference). Use `False` to
 /// disable; `True` or `[]` for all; or a list of column names to limit to those columns.
 /// **parse_strings_sample_rows**: Rows to sample for type inference when parse_strings is enabled (default 1000).
+/// **template**, **query**, **filters**, **sort**, **descending**, **columns**: the view to open
+/// the data with (see `datui.show`). Filters are strings like `"price > 10"`; an invalid one
+/// raises ValueError.
 #[pyclass(name = "DatuiOptions")]
 struct DatuiOptionsPy {
     inner: OpenOptions,
@@ -166,7 +187,13 @@ impl DatuiOptionsPy {
         null_values=None,
         debug=false,
         parse_strings=None,
-        parse_strings_sample_rows=1000
+        parse_strings_sample_rows=1000,
+        template=None,
+        query=None,
+        filters=None,
+        sort=None,
+        descending=false,
+        columns=None
     ))]
     #[allow(clippy::too_many_arguments)]
     fn new(
@@ -199,6 +226,12 @@ impl DatuiOptionsPy {
         debug: bool,
         parse_strings: Option<Bound<'_, pyo3::types::PyAny>>,
         parse_strings_sample_rows: usize,
+        template: Option<String>,
+        query: Option<String>,
+        filters: Option<Bound<'_, pyo3::types::PyAny>>,
+        sort: Option<Bound<'_, pyo3::types::PyAny>>,
+        descending: bool,
+        columns: Option<Bound<'_, pyo3::types::PyAny>>,
     ) -> PyResult<Self> {
         let mut opts = OpenOptions::new();
         opts.row_numbers = row_numbers;
@@ -305,6 +338,20 @@ impl DatuiOptionsPy {
         opts.parse_strings = parse_strings_from_py(parse_strings.as_ref().map(|b| b.as_ref()))?;
         opts.parse_strings_sample_rows = parse_strings_sample_rows;
         opts.debug = debug;
+        let view = InitialView {
+            template,
+            query,
+            filters: str_list_from_py(filters.as_ref(), "filters")?
+                .iter()
+                .map(|f| parse_filter(f).map_err(|e| PyValueError::new_err(e.to_string())))
+                .collect::<PyResult<_>>()?,
+            sort: str_list_from_py(sort.as_ref(), "sort")?,
+            descending,
+            columns: str_list_from_py(columns.as_ref(), "columns")?,
+        };
+        if !view.is_empty() {
+            opts.initial_view = Some(view);
+        }
         Ok(Self { inner: opts })
     }
 
@@ -388,6 +435,29 @@ impl DatuiOptionsPy {
             d.set_item("null_values", v.as_slice())?;
         }
         d.set_item("debug", o.debug)?;
+        if let Some(ref view) = o.initial_view {
+            if let Some(ref v) = view.template {
+                d.set_item("template", v.as_str())?;
+            }
+            if let Some(ref v) = view.query {
+                d.set_item("query", v.as_str())?;
+            }
+            if !view.filters.is_empty() {
+                let filters: Vec<String> = view
+                    .filters
+                    .iter()
+                    .map(|f| format!("{} {} {}", f.column, f.operator.as_str(), f.value).trim_end().to_string())
+                    .collect();
+                d.set_item("filters", filters)?;
+            }
+            if !view.sort.is_empty() {
+                d.set_item("sort", view.sort.clone())?;
+            }
+            d.set_item("descending", view.descending)?;
+            if !view.columns.is_empty() {
+                d.set_item("columns", view.columns.clone())?;
+            }
+        }
         Ok(d)
     }
 }
@@ -464,6 +534,7 @@ fn run_tui(plan: DslPlan, opts: OpenOptions) -> PyResult<()> {
             Err(match kind {
                 ErrorKindForPython::FileNotFound => PyFileNotFoundError::new_err(msg),
                 ErrorKindForPython::PermissionDenied => PyPermissionError::new_err(msg),
+                ErrorKindForPython::InvalidArgument => PyValueError::new_err(msg),
                 ErrorKindForPython::Other => PyRuntimeError::new_err(msg),
             }
             .into())
@@ -495,7 +566,8 @@ fn run_tui(plan: DslPlan, opts: OpenOptions) -> PyResult<()> {
 ///     options: Optional DatuiOptions (includes debug); default when None.
 ///
 /// Raises:
-///     ValueError: If the bytes are not valid LazyFrame binary.
+///     ValueError: If the bytes are not valid LazyFrame binary, or the view in options (template,
+///         query, filters, sort, columns) doesn't fit the data.
 ///     FileNotFoundError: If a path is used and the file is not found (internal).
 ///     PermissionError: If read access is denied (internal).
 ///     RuntimeError: If the TUI fails or panics.
@@ -529,7 +601,8 @@ fn view_from_bytes(
 ///     options: Optional DatuiOptions (includes debug); default when None.
 ///
 /// Raises:
-///     ValueError: If the string is not valid LazyFrame JSON.
+///     ValueError: If the string is not valid LazyFrame JSON, or the view in options (template,
+///         query, filters, sort, columns) doesn't fit the data.
 ///     FileNotFoundError: If a path is used and the file is not found (internal).
 ///     PermissionError: If read access is denied (internal).
 ///     RuntimeError: If the TUI fails or panics.
@@ -597,6 +670,7 @@ fn view_paths(
             Err(match kind {
                 ErrorKindForPython::FileNotFound => PyFileNotFoundError::new_err(msg),
                 ErrorKindForPython::PermissionDenied => PyPermissionError::new_err(msg),
+                ErrorKindForPython::InvalidArgument => PyValueError::new_err(msg),
                 ErrorKindForPython::Other => PyRuntimeError::new_err(msg),
             }
             .into())
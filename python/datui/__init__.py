@@ -46,6 +46,12 @@ _DATUI_OPTIONS_KEYS = frozenset({
     "workaround_pivot_date_index",
     "null_values",
     "debug",
+    "template",
+    "query",
+    "filters",
+    "sort",
+    "descending",
+    "columns",
 })
 
 
@@ -94,7 +100,11 @@ def _view_frame(lf: pl.LazyFrame, *, options: DatuiOptions | None) -> None:
         try:
             datui._datui.view_from_bytes(payload, options=options)
             return
-        except (ValueError, RuntimeError):
+        except ValueError as e:
+            # Only a plan this build can't read falls back to JSON; an invalid view is final
+            if "invalid LazyFrame" not in str(e):
+                raise
+        except RuntimeError:
             pass
         with warnings.catch_warnings():
             warnings.filterwarnings("ignore", message=".*json.*deprecated", category=UserWarning)
@@ -177,3 +187,56 @@ def view(
         _view_frame(lf, options=opts)
     except AttributeError as e:
         raise TypeError("data must be a LazyFrame or DataFrame") from e
+
+
+def show(
+    data: pl.LazyFrame | pl.DataFrame | PathLike | list[PathLike] | tuple[PathLike, ...],
+    *,
+    template: str | None = None,
+    query: str | None = None,
+    filters: str | list[str] | None = None,
+    sort: str | list[str] | None = None,
+    descending: bool = False,
+    columns: list[str] | None = None,
+    options: DatuiOptions | None = None,
+    **kwargs: object,
+) -> None:
+    """
+    View data in the terminal, opened with a template, query, filters, sort and column order.
+
+    The template is applied first; the other arguments replace its query, filters, sort and
+    column order. For a LazyFrame or DataFrame the view is checked against the data before the
+    terminal opens; for paths, problems are shown in the app.
+
+    Args:
+        data: Path(s), LazyFrame, or DataFrame (as for view()).
+        template: Name or id of a saved template.
+        query: Query in datui's query syntax, e.g. "select name, price where price > 10".
+        filters: Filter(s) as "column operator value", e.g. "price > 10" or "name contains Ann".
+            Operators: =, !=, >, <, >=, <=, between (low and high), in ((a, b)), contains,
+            !contains, is null, not null. Rows must pass all of them.
+        sort: Column(s) to sort by.
+        descending: Sort in descending order.
+        columns: Columns to show first, in this order.
+        options: Optional DatuiOptions; use default options when None.
+        **kwargs: Optional DatuiOptions fields (override options when both given).
+
+    Raises:
+        ValueError: A filter can't be parsed, the template doesn't exist, or the query, filters,
+            sort or columns don't fit the data (frames only); also as for view().
+        TypeError, FileNotFoundError, PermissionError, RuntimeError: As for view().
+    """
+    view_kwargs = {
+        "template": template,
+        "query": query,
+        "filters": filters,
+        "sort": sort,
+        "descending": descending,
+        "columns": columns,
+    }
+    view(
+        data,
+        options=options,
+        **{k: v for k, v in view_kwargs.items() if v is not None and v is not False},
+        **kwargs,
+    )
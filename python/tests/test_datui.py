@@ -10,6 +10,7 @@ def test_import_datui():
     import datui
 
     assert hasattr(datui, "view")
+    assert hasattr(datui, "show")
     assert hasattr(datui, "DatuiOptions")
     assert hasattr(datui, "CompressionFormat")
 
@@ -127,6 +128,39 @@ def test_datui_options_delimiter_single_char():
     assert d["delimiter"] == ord(";")
 
 
+def test_datui_options_view():
+    """DatuiOptions stores the view to open the data with; filters are parsed."""
+    import datui
+
+    opts = datui.DatuiOptions(
+        query="select name, price", filters=["price >= 10", "name is null"], sort="price", descending=True
+    )
+    d = opts._as_dict()
+    assert d["query"] == "select name, price"
+    assert d["filters"] == ["price >= 10", "name is null"]
+    assert d["sort"] == ["price"]
+    assert d["descending"] is True
+
+
+def test_datui_options_invalid_filter_raises():
+    """A filter without an operator should raise ValueError."""
+    import datui
+
+    with pytest.raises(ValueError, match="operator"):
+        datui.DatuiOptions(filters=["price"])
+
+
+def test_show_unknown_column_raises():
+    """show() checks a frame's view before the TUI starts, so no TTY is needed."""
+    import datui
+
+    lf = polars.DataFrame({"a": [1, 2, 3]}).lazy()
+    with pytest.raises(ValueError, match="Unknown column in filter: b"):
+        datui.show(lf, filters="b > 1")
+    with pytest.raises(ValueError, match="Template not found"):
+        datui.show(lf, template="no such template")
+
+
 def test_view_invalid_kwarg_raises():
     """view() with invalid option keyword should raise TypeError."""
     import datui
@@ -1,6 +1,8 @@
 use clap::Parser;
 use color_eyre::Result;
-use datui::{error_display, Args, OpenOptions, RunInput, APP_NAME};
+use datui::data_diff::DiffRequest;
+use datui::initial_view::InitialView;
+use datui::{error_display, Args, OpenOptions, RunInput, APP_NAME, STDIN_PATH};
 use datui::{AppConfig, ConfigManager, TemplateManager};
 
 fn handle_early_exit_flags(args: &Args) -> Result<Option<()>> {
@@ -84,21 +86,50 @@ fn handle_early_exit_flags(args: &Args) -> Result<Option<()>> {
     Ok(None)
 }
 
+/// Parse the command line. With no path and data piped in, reads standard input as if `-` was
+/// given, so `curl ... | datui --format csv` works.
+fn parse_args() -> Args {
+    use std::io::IsTerminal;
+
+    match Args::try_parse() {
+        Ok(args) => args,
+        Err(e)
+            if e.kind() == clap::error::ErrorKind::MissingRequiredArgument
+                && !std::io::stdin().is_terminal() =>
+        {
+            Args::parse_from(std::env::args_os().chain(["--".into(), STDIN_PATH.into()]))
+        }
+        Err(e) => e.exit(),
+    }
+}
+
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = parse_args();
 
     if let Some(()) = handle_early_exit_flags(&args)? {
         return Ok(());
     }
 
-    let mut config = match AppConfig::load(APP_NAME) {
+    if args.paths.iter().any(|p| p.as_os_str() == STDIN_PATH) && args.format.is_none() {
+        eprintln!(
+            "Error: Reading from standard input needs --format, e.g. `datui - --format csv`."
+        );
+        std::process::exit(1);
+    }
+
+    let config = if args.safe_mode {
+        Ok(AppConfig::safe_mode())
+    } else {
+        AppConfig::load(APP_NAME)
+    };
+    let mut config = match config {
         Ok(c) => c,
         Err(e) => {
             eprintln!(
                 "Error: {}",
                 error_display::user_message_from_report(&e, None)
             );
-            eprintln!("Fix the configuration and try again, or remove/rename the config file to use defaults.");
+            eprintln!("Fix the configuration and try again, or remove/rename the config file to use defaults (or start with --safe-mode).");
             std::process::exit(1);
         }
     };
@@ -115,8 +146,85 @@ fn main() -> Result<()> {
         config.performance.polars_streaming = ps;
     }
 
-    let opts = OpenOptions::from_args_and_config(&args, &config);
-    let input = RunInput::Paths(args.paths.clone(), opts);
+    if let Some(ref locale) = args.locale {
+        config.display.locale = Some(locale.clone());
+        if let Err(e) = config.validate() {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    let mut opts = OpenOptions::from_args_and_config(&args, &config);
+    match InitialView::from_args(&args) {
+        Ok(view) => opts.initial_view = view,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    if args.schema_diff {
+        match datui::schema_diff::expand_paths(&args.paths) {
+            Ok(files) => {
+                print!(
+                    "{}",
+                    datui::schema_diff::SchemaDiff::compute(files, &opts).to_text()
+                );
+                return Ok(());
+            }
+            Err(e) => {
+                eprintln!(
+                    "Error: {}",
+                    error_display::user_message_from_report(&e, None)
+                );
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let input = if args.diff {
+        let [old, new] = args.paths.as_slice() else {
+            eprintln!(
+                "Error: --diff compares two files, e.g. `datui --diff old.parquet new.parquet`."
+            );
+            std::process::exit(1);
+        };
+        RunInput::Diff(
+            Box::new(DiffRequest {
+                old: old.clone(),
+                new: new.clone(),
+                keys: args.diff_key.clone(),
+            }),
+            opts,
+        )
+    } else {
+        RunInput::Paths(args.paths.clone(), opts)
+    };
+
+    if let Some(export) = args.export.as_deref().filter(|_| args.headless) {
+        if let Err(e) = datui::run_headless(input, Some(config), export) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(output) = &args.profile {
+        if let Err(e) = datui::run_profile(input, Some(config), output) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(output) = &args.render {
+        let size = (args.width, args.rows);
+        if let Err(e) = datui::run_render(input, Some(config), output, size, &args.chart) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
 
     if let Err(e) = datui::run(input, Some(config)) {
         eprintln!("Error: {}", e);
@@ -139,14 +247,43 @@ mod tests {
             skip_tail_rows: None,
             no_header: Some(true),
             delimiter: Some(b','),
+            quote_char: None,
+            escape_char: None,
+            comment_prefix: None,
             null_value: vec![],
+            dtypes: vec![],
             compression: None,
             format: None,
             debug: false,
+            safe_mode: false,
+            adjust_schema: false,
             excel_sheet: None,
+            excel_range: None,
+            excel_header_row: None,
+            table: None,
+            sql_query: None,
+            proto: None,
+            proto_message: None,
             clear_cache: false,
             template: None,
+            script: None,
+            query: None,
+            filter: vec![],
+            sort: vec![],
+            descending: false,
+            headless: false,
+            export: None,
+            render: None,
+            rows: 25,
+            width: 120,
+            chart: vec![],
+            profile: None,
             remove_templates: false,
+            schema_diff: false,
+            diff: false,
+            diff_key: vec![],
+            concat: None,
+            source_column: false,
             sampling_threshold: None,
             pages_lookahead: None,
             pages_lookback: None,
@@ -157,6 +294,7 @@ mod tests {
             hive: false,
             single_spine_schema: None,
             column_colors: None,
+            locale: None,
             parse_dates: None,
             parse_strings: vec![],
             no_parse_strings: false,
@@ -166,10 +304,13 @@ mod tests {
             s3_access_key_id: None,
             s3_secret_access_key: None,
             s3_region: None,
+            azure_account: None,
+            azure_key: None,
             polars_streaming: None,
             workaround_pivot_date_index: None,
             infer_schema_length: None,
             ignore_errors: None,
+            skip_bad_rows: None,
         };
         let opts: OpenOptions = (&args).into();
         assert_eq!(opts.skip_lines, Some(1));
@@ -237,4 +378,65 @@ mod tests {
         assert_eq!(args.paths, vec![PathBuf::from("test.csv")]);
         assert!(args.generate_config);
     }
+
+    #[test]
+    fn test_safe_mode_flag_sets_open_option() {
+        use clap::Parser;
+
+        let args = Args::try_parse_from(vec!["datui", "--safe-mode", "test.csv"]).unwrap();
+        assert!(args.safe_mode);
+        let opts: OpenOptions = (&args).into();
+        assert!(opts.safe_mode);
+    }
+
+    #[test]
+    fn test_dash_reads_stdin() {
+        use clap::Parser;
+
+        let args = Args::try_parse_from(vec!["datui", "--format", "csv", "--", "-"]).unwrap();
+        assert_eq!(args.paths, vec![PathBuf::from(datui::STDIN_PATH)]);
+        let args = Args::try_parse_from(vec!["datui", "-", "--format", "csv"]).unwrap();
+        assert_eq!(args.paths, vec![PathBuf::from(datui::STDIN_PATH)]);
+    }
+
+    #[test]
+    fn test_headless_needs_export_and_reads_the_view() {
+        use clap::Parser;
+        use datui::initial_view::InitialView;
+
+        assert!(Args::try_parse_from(vec!["datui", "--headless", "a.csv"]).is_err());
+        assert!(Args::try_parse_from(vec!["datui", "--export", "b.csv", "a.csv"]).is_err());
+        let args = Args::try_parse_from(vec![
+            "datui",
+            "a.csv",
+            "--headless",
+            "--export",
+            "b.parquet",
+            "--filter",
+            "price > 10",
+            "--filter",
+            "name contains x",
+            "--sort",
+            "price,name",
+            "--descending",
+        ])
+        .unwrap();
+        let view = InitialView::from_args(&args).unwrap().unwrap();
+        assert_eq!(view.filters.len(), 2);
+        assert_eq!(view.sort, ["price", "name"]);
+        assert!(view.descending);
+
+        let args = Args::try_parse_from(vec!["datui", "a.csv", "--filter", "price"]).unwrap();
+        assert!(InitialView::from_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_schema_diff_requires_paths() {
+        use clap::Parser;
+
+        assert!(Args::try_parse_from(vec!["datui", "--schema-diff"]).is_err());
+        let args = Args::try_parse_from(vec!["datui", "--schema-diff", "data/*.parquet"]).unwrap();
+        assert!(args.schema_diff);
+        assert_eq!(args.paths, vec![PathBuf::from("data/*.parquet")]);
+    }
 }
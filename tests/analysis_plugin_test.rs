@@ -0,0 +1,79 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use datui::analysis_plugin::{register_analysis_tool, AnalysisTool};
+use datui::{App, AppEvent, OpenOptions};
+use polars::prelude::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::sync::mpsc;
+
+mod common;
+
+struct TotalQty;
+
+impl AnalysisTool for TotalQty {
+    fn name(&self) -> &str {
+        "Total Qty"
+    }
+
+    fn compute(&self, lf: LazyFrame) -> Result<DataFrame, String> {
+        lf.select([col("qty").sum().alias("total")])
+            .collect()
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Handles `ev` and the events it chains, then waits out any background work.
+fn pump(app: &mut App, rx: &mpsc::Receiver<AppEvent>, ev: AppEvent) {
+    let mut next = Some(ev);
+    loop {
+        while let Some(ev) = next.take() {
+            next = app.event(&ev);
+        }
+        match rx.recv_timeout(std::time::Duration::from_millis(2000)) {
+            Ok(ev) => next = Some(ev),
+            Err(_) => return,
+        }
+    }
+}
+
+#[test]
+fn test_plugin_tool_is_listed_and_computed() {
+    register_analysis_tool(TotalQty);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("orders.csv");
+    std::fs::write(&path, "id,qty\n1,4\n2,3\n3,5\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump(
+        &mut app,
+        &rx,
+        AppEvent::Open(vec![path], OpenOptions::default()),
+    );
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    pump(&mut app, &rx, key(KeyCode::Char('a')));
+    // Plugins come after the built-in tools
+    pump(&mut app, &rx, key(KeyCode::End));
+    pump(&mut app, &rx, key(KeyCode::Enter));
+
+    let result = app
+        .analysis_modal
+        .plugin_result(0)
+        .expect("plugin computed")
+        .as_ref()
+        .expect("plugin succeeded");
+    assert_eq!(
+        result.column("total").unwrap().get(0).unwrap(),
+        AnyValue::Int64(12)
+    );
+
+    let area = Rect::new(0, 0, 100, 30);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("Total Qty"));
+    assert!(screen.contains("12"));
+}
@@ -328,6 +328,8 @@ fn test_theme_with_custom_colors() {
         bool_col: "yellow".to_string(),
         temporal_col: "magenta".to_string(),
         binary_col: "dark_gray".to_string(),
+        heatmap_low: "#1e3a8a".to_string(),
+        heatmap_high: "#991b1b".to_string(),
         chart_series_color_1: "cyan".to_string(),
         chart_series_color_2: "magenta".to_string(),
         chart_series_color_3: "green".to_string(),
@@ -16,20 +16,50 @@ fn test_config_used_for_row_numbers() {
         skip_tail_rows: None,
         no_header: None,
         delimiter: None,
+        quote_char: None,
+        escape_char: None,
+        comment_prefix: None,
         null_value: vec![],
+        dtypes: vec![],
         compression: None,
         format: None,
         debug: false,
+        safe_mode: false,
+        adjust_schema: false,
         excel_sheet: None,
+        excel_range: None,
+        excel_header_row: None,
+        table: None,
+        sql_query: None,
+        proto: None,
+        proto_message: None,
         clear_cache: false,
         template: None,
+        script: None,
+        query: None,
+        filter: vec![],
+        sort: vec![],
+        descending: false,
+        headless: false,
+        export: None,
+        render: None,
+        rows: 25,
+        width: 120,
+        chart: vec![],
+        profile: None,
         remove_templates: false,
+        schema_diff: false,
+        diff: false,
+        diff_key: vec![],
+        concat: None,
+        source_column: false,
         sampling_threshold: None,
         pages_lookahead: None,
         pages_lookback: None,
         row_numbers: false, // Not set via CLI
         row_start_index: None,
         column_colors: None,
+        locale: None,
         generate_config: false,
         force: false,
         hive: false,
@@ -43,10 +73,13 @@ fn test_config_used_for_row_numbers() {
         s3_access_key_id: None,
         s3_secret_access_key: None,
         s3_region: None,
+        azure_account: None,
+        azure_key: None,
         polars_streaming: None,
         workaround_pivot_date_index: None,
         infer_schema_length: None,
         ignore_errors: None,
+        skip_bad_rows: None,
     };
 
     let opts = OpenOptions::from_args_and_config(&args, &config);
@@ -70,20 +103,50 @@ fn test_cli_args_override_config() {
         skip_tail_rows: None,
         no_header: None,
         delimiter: None,
+        quote_char: None,
+        escape_char: None,
+        comment_prefix: None,
         null_value: vec![],
+        dtypes: vec![],
         compression: None,
         format: None,
         debug: false,
+        safe_mode: false,
+        adjust_schema: false,
         excel_sheet: None,
+        excel_range: None,
+        excel_header_row: None,
+        table: None,
+        sql_query: None,
+        proto: None,
+        proto_message: None,
         clear_cache: false,
         template: None,
+        script: None,
+        query: None,
+        filter: vec![],
+        sort: vec![],
+        descending: false,
+        headless: false,
+        export: None,
+        render: None,
+        rows: 25,
+        width: 120,
+        chart: vec![],
+        profile: None,
         remove_templates: false,
+        schema_diff: false,
+        diff: false,
+        diff_key: vec![],
+        concat: None,
+        source_column: false,
         sampling_threshold: None,
         pages_lookahead: Some(5), // Override config
         pages_lookback: None,
         row_numbers: false,
         row_start_index: Some(1), // Override config
         column_colors: None,
+        locale: None,
         generate_config: false,
         force: false,
         hive: false,
@@ -97,10 +160,13 @@ fn test_cli_args_override_config() {
         s3_access_key_id: None,
         s3_secret_access_key: None,
         s3_region: None,
+        azure_account: None,
+        azure_key: None,
         polars_streaming: None,
         workaround_pivot_date_index: None,
         infer_schema_length: None,
         ignore_errors: None,
+        skip_bad_rows: None,
     };
 
     let opts = OpenOptions::from_args_and_config(&args, &config);
@@ -124,20 +190,50 @@ fn test_config_display_settings() {
         skip_tail_rows: None,
         no_header: None,
         delimiter: None,
+        quote_char: None,
+        escape_char: None,
+        comment_prefix: None,
         null_value: vec![],
+        dtypes: vec![],
         compression: None,
         format: None,
         debug: false,
+        safe_mode: false,
+        adjust_schema: false,
         excel_sheet: None,
+        excel_range: None,
+        excel_header_row: None,
+        table: None,
+        sql_query: None,
+        proto: None,
+        proto_message: None,
         clear_cache: false,
         template: None,
+        script: None,
+        query: None,
+        filter: vec![],
+        sort: vec![],
+        descending: false,
+        headless: false,
+        export: None,
+        render: None,
+        rows: 25,
+        width: 120,
+        chart: vec![],
+        profile: None,
         remove_templates: false,
+        schema_diff: false,
+        diff: false,
+        diff_key: vec![],
+        concat: None,
+        source_column: false,
         sampling_threshold: None,
         pages_lookahead: None,
         pages_lookback: None,
         row_numbers: false,
         row_start_index: None,
         column_colors: None,
+        locale: None,
         generate_config: false,
         force: false,
         hive: false,
@@ -151,10 +247,13 @@ fn test_config_display_settings() {
         s3_access_key_id: None,
         s3_secret_access_key: None,
         s3_region: None,
+        azure_account: None,
+        azure_key: None,
         polars_streaming: None,
         workaround_pivot_date_index: None,
         infer_schema_length: None,
         ignore_errors: None,
+        skip_bad_rows: None,
     };
 
     let opts = OpenOptions::from_args_and_config(&args, &config);
@@ -178,20 +277,50 @@ fn test_config_file_loading_settings() {
         skip_tail_rows: None,
         no_header: None,
         delimiter: None,
+        quote_char: None,
+        escape_char: None,
+        comment_prefix: None,
         null_value: vec![],
+        dtypes: vec![],
         compression: None,
         format: None,
         debug: false,
+        safe_mode: false,
+        adjust_schema: false,
         excel_sheet: None,
+        excel_range: None,
+        excel_header_row: None,
+        table: None,
+        sql_query: None,
+        proto: None,
+        proto_message: None,
         clear_cache: false,
         template: None,
+        script: None,
+        query: None,
+        filter: vec![],
+        sort: vec![],
+        descending: false,
+        headless: false,
+        export: None,
+        render: None,
+        rows: 25,
+        width: 120,
+        chart: vec![],
+        profile: None,
         remove_templates: false,
+        schema_diff: false,
+        diff: false,
+        diff_key: vec![],
+        concat: None,
+        source_column: false,
         sampling_threshold: None,
         pages_lookahead: None,
         pages_lookback: None,
         row_numbers: false,
         row_start_index: None,
         column_colors: None,
+        locale: None,
         generate_config: false,
         force: false,
         hive: false,
@@ -205,10 +334,13 @@ fn test_config_file_loading_settings() {
         s3_access_key_id: None,
         s3_secret_access_key: None,
         s3_region: None,
+        azure_account: None,
+        azure_key: None,
         polars_streaming: None,
         workaround_pivot_date_index: None,
         infer_schema_length: None,
         ignore_errors: None,
+        skip_bad_rows: None,
     };
 
     let opts = OpenOptions::from_args_and_config(&args, &config);
@@ -230,20 +362,50 @@ fn test_config_null_values_merge() {
         skip_tail_rows: None,
         no_header: None,
         delimiter: None,
+        quote_char: None,
+        escape_char: None,
+        comment_prefix: None,
         null_value: vec!["amount=".to_string()],
+        dtypes: vec![],
         compression: None,
         format: None,
         debug: false,
+        safe_mode: false,
+        adjust_schema: false,
         excel_sheet: None,
+        excel_range: None,
+        excel_header_row: None,
+        table: None,
+        sql_query: None,
+        proto: None,
+        proto_message: None,
         clear_cache: false,
         template: None,
+        script: None,
+        query: None,
+        filter: vec![],
+        sort: vec![],
+        descending: false,
+        headless: false,
+        export: None,
+        render: None,
+        rows: 25,
+        width: 120,
+        chart: vec![],
+        profile: None,
         remove_templates: false,
+        schema_diff: false,
+        diff: false,
+        diff_key: vec![],
+        concat: None,
+        source_column: false,
         sampling_threshold: None,
         pages_lookahead: None,
         pages_lookback: None,
         row_numbers: false,
         row_start_index: None,
         column_colors: None,
+        locale: None,
         generate_config: false,
         force: false,
         hive: false,
@@ -257,10 +419,13 @@ fn test_config_null_values_merge() {
         s3_access_key_id: None,
         s3_secret_access_key: None,
         s3_region: None,
+        azure_account: None,
+        azure_key: None,
         polars_streaming: None,
         workaround_pivot_date_index: None,
         infer_schema_length: None,
         ignore_errors: None,
+        skip_bad_rows: None,
     };
 
     let opts = OpenOptions::from_args_and_config(&args, &config);
@@ -331,20 +496,50 @@ fn test_parse_strings_default_and_no_parse_strings() {
         skip_tail_rows: None,
         no_header: None,
         delimiter: None,
+        quote_char: None,
+        escape_char: None,
+        comment_prefix: None,
         null_value: vec![],
+        dtypes: vec![],
         compression: None,
         format: None,
         debug: false,
+        safe_mode: false,
+        adjust_schema: false,
         excel_sheet: None,
+        excel_range: None,
+        excel_header_row: None,
+        table: None,
+        sql_query: None,
+        proto: None,
+        proto_message: None,
         clear_cache: false,
         template: None,
+        script: None,
+        query: None,
+        filter: vec![],
+        sort: vec![],
+        descending: false,
+        headless: false,
+        export: None,
+        render: None,
+        rows: 25,
+        width: 120,
+        chart: vec![],
+        profile: None,
         remove_templates: false,
+        schema_diff: false,
+        diff: false,
+        diff_key: vec![],
+        concat: None,
+        source_column: false,
         sampling_threshold: None,
         pages_lookahead: None,
         pages_lookback: None,
         row_numbers: false,
         row_start_index: None,
         column_colors: None,
+        locale: None,
         generate_config: false,
         force: false,
         hive: false,
@@ -358,10 +553,13 @@ fn test_parse_strings_default_and_no_parse_strings() {
         s3_access_key_id: None,
         s3_secret_access_key: None,
         s3_region: None,
+        azure_account: None,
+        azure_key: None,
         polars_streaming: None,
         workaround_pivot_date_index: None,
         infer_schema_length: None,
         ignore_errors: None,
+        skip_bad_rows: None,
     };
     let opts = OpenOptions::from_args_and_config(&args, &config);
     assert!(matches!(opts.parse_strings, Some(ParseStringsTarget::All)));
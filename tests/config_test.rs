@@ -56,6 +56,7 @@ fn test_generate_default_config() {
     assert!(template.contains("[performance]"));
     assert!(template.contains("[theme.colors]"));
     assert!(template.contains("[query]"));
+    assert!(template.contains("[export]"));
     assert!(template.contains("[templates]"));
     assert!(template.contains("[debug]"));
 
@@ -162,6 +163,29 @@ row_start_index = 0
     assert_eq!(config.performance.sampling_threshold, None); // Default: no sampling
 }
 
+#[test]
+fn test_load_from_config_dir() {
+    let (_temp_dir, config_manager) = setup_test_config_dir();
+    config_manager
+        .ensure_config_dir()
+        .expect("Failed to create config dir");
+    let config_path = config_manager.config_path("config.toml");
+
+    // No version: read as the current format, and left untouched.
+    let unversioned = "[display]\nrow_numbers = true\n";
+    fs::write(&config_path, unversioned).expect("Failed to write config");
+    let config = AppConfig::load_from(&config_manager).expect("Should load config");
+    assert!(config.display.row_numbers);
+    assert_eq!(config.version, "0.2");
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), unversioned);
+    assert!(!config_manager.config_path("config.toml.v0.2.bak").exists());
+
+    // A format this version can't upgrade from is reported, not ignored.
+    fs::write(&config_path, "version = \"9.0\"\n").expect("Failed to write config");
+    let err = AppConfig::load_from(&config_manager).unwrap_err();
+    assert!(err.to_string().contains("Unsupported config version: 9.0"));
+}
+
 #[test]
 fn test_merge_configs() {
     let mut base = AppConfig::default();
@@ -250,6 +274,31 @@ fn test_validate_config_zero_event_poll_interval() {
         .contains("event_poll_interval_ms must be greater than 0"));
 }
 
+#[test]
+fn test_load_profile_thresholds_default_and_validation() {
+    let config = AppConfig::default();
+    assert!(config.performance.load_profiles);
+    assert_eq!(config.performance.small_file_mb, 16);
+    assert_eq!(config.performance.large_file_mb, 1024);
+
+    let mut config = AppConfig::default();
+    config.performance.large_file_mb = 16;
+    let result = config.validate();
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("large_file_mb (16) must be greater than small_file_mb (16)"));
+
+    let mut base = AppConfig::default();
+    let mut override_config = AppConfig::default();
+    override_config.performance.load_profiles = false;
+    override_config.performance.small_file_mb = 4;
+    base.merge(override_config);
+    assert!(!base.performance.load_profiles);
+    assert_eq!(base.performance.small_file_mb, 4);
+    assert_eq!(base.performance.large_file_mb, 1024);
+}
+
 #[test]
 fn test_parse_full_config() {
     // Clear NO_COLOR for color validation
@@ -299,6 +348,9 @@ distribution_skewed = "yellow"
 distribution_other = "white"
 outlier_marker = "red"
 
+[ui]
+mouse = false
+
 [ui.controls]
 row_count_width = 25
 
@@ -326,6 +378,8 @@ show_transformations = true
     assert!(config.display.row_numbers);
     assert_eq!(config.performance.sampling_threshold, Some(50000));
     assert_eq!(config.theme.colors.keybind_hints, "blue");
+    assert!(!config.ui.mouse);
+    assert!(AppConfig::default().ui.mouse);
     assert_eq!(config.ui.controls.row_count_width, 25);
     assert_eq!(config.query.history_limit, 500);
     assert!(config.templates.auto_apply);
@@ -368,6 +422,10 @@ fn test_merge_does_not_override_with_defaults() {
         table_cell_padding: 1,
         column_colors: true,
         sidebar_width: None,
+        max_column_width: Some(40),
+        column_ruler: datui::column_ruler::ColumnRuler::Letters,
+        locale: Some("de-DE".to_string()),
+        column_formats: Default::default(),
     };
 
     let override_config = DisplayConfig::default();
@@ -379,6 +437,8 @@ fn test_merge_does_not_override_with_defaults() {
     assert_eq!(base.pages_lookback, 5);
     assert!(base.row_numbers);
     assert_eq!(base.row_start_index, 0);
+    assert_eq!(base.max_column_width, Some(40));
+    assert_eq!(base.column_ruler, datui::column_ruler::ColumnRuler::Letters);
 }
 
 #[test]
@@ -460,6 +520,147 @@ fn test_new_color_fields_custom_values() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_theme_mode_default_and_validation() {
+    let mut config = AppConfig::default();
+    assert_eq!(config.theme.mode, "auto");
+    assert!(config.validate().is_ok());
+
+    config.theme.mode = "light".to_string();
+    assert!(config.validate().is_ok());
+
+    config.theme.mode = "sepia".to_string();
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("theme.mode"));
+}
+
+#[test]
+fn test_theme_mode_resolves_background() {
+    use datui::terminal_background::TerminalBackground;
+
+    let mut config = AppConfig::default();
+    assert_eq!(
+        config
+            .theme
+            .resolve_background(Some(TerminalBackground::Light)),
+        TerminalBackground::Light
+    );
+    assert_eq!(
+        config.theme.resolve_background(None),
+        TerminalBackground::Dark
+    );
+
+    config.theme.mode = "dark".to_string();
+    assert_eq!(
+        config
+            .theme
+            .resolve_background(Some(TerminalBackground::Light)),
+        TerminalBackground::Dark
+    );
+}
+
+#[test]
+fn test_light_variant_keeps_user_colors() {
+    use datui::config::ColorConfig;
+    use datui::terminal_background::TerminalBackground;
+
+    let colors = ColorConfig {
+        controls_bg: "#202020".to_string(),
+        ..Default::default()
+    };
+
+    let light = colors.for_background(TerminalBackground::Light);
+    assert_eq!(light.controls_bg, "#202020");
+    assert_eq!(light.table_header, ColorConfig::light().table_header);
+    assert_eq!(light.str_col, "green");
+
+    let dark = colors.for_background(TerminalBackground::Dark);
+    assert_eq!(dark.table_header, "white");
+}
+
+#[test]
+fn test_theme_mode_merge() {
+    let mut base = AppConfig::default();
+    let mut user = AppConfig::default();
+    user.theme.mode = "light".to_string();
+    base.merge(user);
+    assert_eq!(base.theme.mode, "light");
+}
+
+#[test]
+fn test_display_locale_validation_and_merge() {
+    let mut config = AppConfig::default();
+    assert!(config.display.locale.is_none());
+    config.display.locale = Some("en-GB".to_string());
+    assert!(config.validate().is_ok());
+    config.display.locale = Some("xx-XX".to_string());
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("display.locale"), "{}", err);
+
+    let mut base = AppConfig::default();
+    let mut user = AppConfig::default();
+    user.display.locale = Some("fr-FR".to_string());
+    base.merge(user);
+    assert_eq!(base.display.locale.as_deref(), Some("fr-FR"));
+    base.merge(AppConfig::default());
+    assert_eq!(base.display.locale.as_deref(), Some("fr-FR"));
+}
+
+#[test]
+fn test_export_context_rows_default_and_merge() {
+    let mut base = AppConfig::default();
+    assert_eq!(base.export.context_rows, 5);
+    let user: AppConfig = toml::from_str("[export]\ncontext_rows = 20\n").unwrap();
+    base.merge(user);
+    assert_eq!(base.export.context_rows, 20);
+    base.merge(AppConfig::default());
+    assert_eq!(base.export.context_rows, 20);
+}
+
+#[test]
+fn test_notifications_default_and_merge() {
+    let mut base = AppConfig::default();
+    assert!(base.notifications.enabled);
+    assert_eq!(base.notifications.threshold_secs, 10);
+    let user: AppConfig =
+        toml::from_str("[notifications]\nthreshold_secs = 60\ndesktop = false\n").unwrap();
+    base.merge(user);
+    assert_eq!(base.notifications.threshold_secs, 60);
+    assert!(!base.notifications.desktop);
+    assert!(base.notifications.bell);
+}
+
+#[test]
+fn test_keys_parse_merge_and_conflicts() {
+    let mut base = AppConfig::default();
+    assert!(base.keys.is_empty());
+    let user: AppConfig =
+        toml::from_str("[keys]\nopen_filter = \"ctrl+s\"\npage_down = [\"space\", \"pagedown\"]\n")
+            .unwrap();
+    assert!(user.validate().is_ok());
+    base.merge(user);
+    assert_eq!(base.keys.len(), 2);
+    assert_eq!(base.keys["page_down"].keys(), ["space", "pagedown"]);
+    base.merge(AppConfig::default());
+    assert_eq!(base.keys.len(), 2);
+
+    let conflict: AppConfig = toml::from_str("[keys]\nopen_chart = \"s\"\n").unwrap();
+    let err = conflict.validate().unwrap_err().to_string();
+    assert!(err.starts_with("keys: "), "{}", err);
+    assert!(err.contains("open_filter"), "{}", err);
+
+    let unknown: AppConfig = toml::from_str("[keys]\nopen_filtr = \"x\"\n").unwrap();
+    assert!(unknown.validate().is_err());
+}
+
+#[test]
+fn test_safe_mode_config() {
+    let config = AppConfig::safe_mode();
+    assert_eq!(config.theme.mode, "dark");
+    assert!(!config.display.column_colors);
+    assert!(config.validate().is_ok());
+}
+
 #[test]
 fn test_validate_config_with_invalid_chart_series_color() {
     std::env::remove_var("NO_COLOR");
@@ -1,4 +1,5 @@
 use color_eyre::Result;
+use datui::sampling::SamplingStrategy;
 use datui::statistics::{compute_statistics_with_options, ComputeOptions, DistributionType};
 use polars::prelude::*;
 use std::path::Path;
@@ -30,7 +31,8 @@ fn test_distribution_detection(column_name: &str, expected_type: DistributionTyp
         polars_streaming: true,
     };
 
-    let results = compute_statistics_with_options(&lf, Some(10000), 42, options)?;
+    let results =
+        compute_statistics_with_options(&lf, Some(10000), &SamplingStrategy::random(42), options)?;
 
     // Find the column statistics for our test column
     let col_stat = results
@@ -1,4 +1,4 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use datui::{App, AppEvent, InputMode, OpenOptions};
 use polars::prelude::*;
 use ratatui::buffer::Buffer;
@@ -48,6 +48,19 @@ fn pump_open_until_loaded(
     }
 }
 
+/// Waits for a background scan's result, applying the load-progress events it sends first.
+fn recv_scan_result(app: &mut App, rx: &std::sync::mpsc::Receiver<AppEvent>) -> AppEvent {
+    loop {
+        let ev = rx
+            .recv_timeout(std::time::Duration::from_secs(60))
+            .expect("scan result");
+        if !matches!(ev, AppEvent::BackgroundLoadProgress { .. }) {
+            return ev;
+        }
+        app.event(&ev);
+    }
+}
+
 #[test]
 fn test_app_creation() {
     let (tx, _) = mpsc::channel();
@@ -267,15 +280,526 @@ fn test_chart_view_render_with_cache() {
     assert_eq!(app.input_mode, InputMode::Normal);
 }
 
+#[test]
+fn test_chart_group_by_splits_series_with_legend() {
+    use datui::chart_modal::ChartFocus;
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("prices.csv");
+    let body: String = (0..20)
+        .map(|i| format!("{},{},{}\n", i / 2, 10 + i, ["AAA", "BBB"][i % 2]))
+        .collect();
+    std::fs::write(&path, format!("day,price,ticker\n{body}")).unwrap();
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Char('c'),
+        KeyModifiers::NONE,
+    )));
+    assert_eq!(app.chart_modal.group_candidates, ["ticker"]);
+    app.chart_modal.x_column = Some("day".to_string());
+    app.chart_modal.y_columns = vec!["price".to_string()];
+    while app.chart_modal.focus != ChartFocus::GroupBy {
+        app.event(&AppEvent::Key(KeyEvent::new(
+            KeyCode::Tab,
+            KeyModifiers::NONE,
+        )));
+    }
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Right,
+        KeyModifiers::NONE,
+    )));
+    assert_eq!(app.chart_modal.group_column.as_deref(), Some("ticker"));
+
+    let area = Rect::new(0, 0, 120, 36);
+    let mut buf = Buffer::empty(area);
+    Widget::render(&mut app, area, &mut buf);
+    let text: String = (0..area.height)
+        .map(|y| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(text.contains("AAA"), "{text}");
+    assert!(text.contains("BBB"));
+    assert!(text.contains("price by ticker"));
+}
+
+#[test]
+fn test_category_chart_draws_the_top_categories() {
+    use datui::chart_modal::{CategoryAgg, ChartFocus, ChartKind};
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sales.csv");
+    let body: String = (0..30)
+        .map(|i| format!("{},{}\n", ["east", "west", "north", "south"][i % 7 % 4], i))
+        .collect();
+    std::fs::write(&path, format!("region,amount\n{body}")).unwrap();
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Char('c'),
+        KeyModifiers::NONE,
+    )));
+    while app.chart_modal.chart_kind != ChartKind::Category {
+        app.event(&AppEvent::Key(KeyEvent::new(
+            KeyCode::Right,
+            KeyModifiers::NONE,
+        )));
+    }
+    for (focus, key) in [
+        (ChartFocus::CategoryColumn, KeyCode::Right),
+        (ChartFocus::CategoryAgg, KeyCode::Right),
+        (ChartFocus::CategoryValue, KeyCode::Right),
+        (ChartFocus::CategoryTop, KeyCode::Char('-')),
+    ] {
+        while app.chart_modal.focus != focus {
+            app.event(&AppEvent::Key(KeyEvent::new(
+                KeyCode::Tab,
+                KeyModifiers::NONE,
+            )));
+        }
+        app.event(&AppEvent::Key(KeyEvent::new(key, KeyModifiers::NONE)));
+    }
+    assert_eq!(app.chart_modal.category_top, 9);
+    app.chart_modal.category_top = 3;
+    assert_eq!(app.chart_modal.category_column.as_deref(), Some("region"));
+    assert_eq!(app.chart_modal.category_agg, CategoryAgg::Sum);
+    assert_eq!(app.chart_modal.category_value.as_deref(), Some("amount"));
+
+    let area = Rect::new(0, 0, 120, 36);
+    let mut buf = Buffer::empty(area);
+    Widget::render(&mut app, area, &mut buf);
+    let text: String = (0..area.height)
+        .map(|y| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(
+        text.contains("Sum of amount by region (top 3 of 4 categories)"),
+        "{text}"
+    );
+    assert!(text.contains("█"));
+}
+
+#[test]
+fn test_scatter_chart_shades_dense_cells_and_draws_a_trend_line() {
+    use datui::chart_modal::{ChartFocus, ChartKind};
+    use std::collections::HashSet;
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("points.csv");
+    // A spread of points along y = 2x, plus a tight cluster off the line
+    let spread: String = (0..40).map(|i| format!("{i},{}\n", 2 * i)).collect();
+    let cluster: String = (0..200)
+        .map(|i| format!("{},{}\n", 30.0 + (i % 5) as f64 * 0.01, 10.0))
+        .collect();
+    std::fs::write(&path, format!("x,y\n{spread}{cluster}")).unwrap();
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Char('c'),
+        KeyModifiers::NONE,
+    )));
+    while app.chart_modal.chart_kind != ChartKind::Scatter {
+        app.event(&AppEvent::Key(KeyEvent::new(
+            KeyCode::Right,
+            KeyModifiers::NONE,
+        )));
+    }
+    for (focus, key) in [
+        (ChartFocus::ScatterX, KeyCode::Right),
+        (ChartFocus::ScatterY, KeyCode::Left),
+        (ChartFocus::ScatterTrend, KeyCode::Enter),
+    ] {
+        while app.chart_modal.focus != focus {
+            app.event(&AppEvent::Key(KeyEvent::new(
+                KeyCode::Tab,
+                KeyModifiers::NONE,
+            )));
+        }
+        app.event(&AppEvent::Key(KeyEvent::new(key, KeyModifiers::NONE)));
+    }
+    assert_eq!(app.chart_modal.scatter_x_column.as_deref(), Some("x"));
+    assert_eq!(app.chart_modal.scatter_y_column.as_deref(), Some("y"));
+    assert!(app.chart_modal.scatter_trend);
+
+    let area = Rect::new(0, 0, 120, 36);
+    let mut buf = Buffer::empty(area);
+    Widget::render(&mut app, area, &mut buf);
+    let text: String = (0..area.height)
+        .map(|y| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(text.contains("r² = "), "{text}");
+    // Dot colors: the sparse points, the dense cluster and the trend line
+    let dot_colors: HashSet<_> = buf
+        .content()
+        .iter()
+        .filter(|c| {
+            c.symbol()
+                .chars()
+                .any(|ch| ('\u{2801}'..='\u{28ff}').contains(&ch))
+        })
+        .map(|c| c.fg)
+        .collect();
+    assert!(dot_colors.len() >= 3, "{dot_colors:?}");
+}
+
+#[test]
+fn test_chart_zoom_and_pan_narrow_the_x_axis() {
+    use datui::chart_modal::ChartFocus;
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("line.csv");
+    let body: String = (0..=100).map(|i| format!("{i},{}\n", i * i)).collect();
+    std::fs::write(&path, format!("x,y\n{body}")).unwrap();
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Char('c'),
+        KeyModifiers::NONE,
+    )));
+    app.chart_modal.x_column = Some("x".into());
+    app.chart_modal.y_columns = vec!["y".into()];
+    while app.chart_modal.focus != ChartFocus::Plot {
+        app.event(&AppEvent::Key(KeyEvent::new(
+            KeyCode::Tab,
+            KeyModifiers::NONE,
+        )));
+    }
+    for key in [KeyCode::Char('+'), KeyCode::Char('+'), KeyCode::Right] {
+        app.event(&AppEvent::Key(KeyEvent::new(key, KeyModifiers::NONE)));
+    }
+    assert_eq!(app.chart_modal.x_window, Some((0.4375, 0.6875)));
+
+    let area = Rect::new(0, 0, 120, 36);
+    let mut buf = Buffer::empty(area);
+    Widget::render(&mut app, area, &mut buf);
+    let text: String = (0..area.height)
+        .map(|y| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(text.contains("Zoom ×4"), "{text}");
+    // The x axis spans the window and the y axis fits the visible points (44² to 68²)
+    assert!(text.contains("43.75") && text.contains("68.75"), "{text}");
+    assert!(text.contains("1936") && text.contains("4624"), "{text}");
+
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Char('0'),
+        KeyModifiers::NONE,
+    )));
+    assert_eq!(app.chart_modal.x_window, None);
+}
+
+#[test]
+fn test_chart_crosshair_shows_the_nearest_point() {
+    use datui::chart_modal::ChartFocus;
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("line.csv");
+    let body: String = (0..=10).map(|i| format!("{i},{}\n", i * i)).collect();
+    std::fs::write(&path, format!("x,y\n{body}")).unwrap();
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Char('c'),
+        KeyModifiers::NONE,
+    )));
+    app.chart_modal.x_column = Some("x".into());
+    app.chart_modal.y_columns = vec!["y".into()];
+    let area = Rect::new(0, 0, 120, 36);
+    let render = |app: &mut App| {
+        let mut buf = Buffer::empty(area);
+        Widget::render(app, area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol().to_string())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    // The crosshair moves over the points last drawn
+    render(&mut app);
+    while app.chart_modal.focus != ChartFocus::Plot {
+        app.event(&AppEvent::Key(KeyEvent::new(
+            KeyCode::Tab,
+            KeyModifiers::NONE,
+        )));
+    }
+    for key in [KeyCode::Enter, KeyCode::Right] {
+        app.event(&AppEvent::Key(KeyEvent::new(key, KeyModifiers::NONE)));
+    }
+    assert_eq!(
+        app.chart_modal.crosshair.map(|c| c.point),
+        Some((6.0, 36.0))
+    );
+
+    let text = render(&mut app);
+    assert!(text.contains("x: 6") && text.contains("y: 36"), "{text}");
+    assert!(text.contains("↑/↓ series"), "{text}");
+
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Enter,
+        KeyModifiers::NONE,
+    )));
+    assert!(app.chart_modal.crosshair.is_none());
+    assert!(!render(&mut app).contains("y: 36"));
+}
+
+#[test]
+fn test_chart_right_axis_scales_series_separately() {
+    use datui::chart_export::ChartExportFormat;
+    use datui::chart_modal::ChartFocus;
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("trades.csv");
+    let body: String = (0..=20)
+        .map(|i| format!("{i},{},{}\n", 10.0 + i as f64 * 0.5, 1000 + i * 200))
+        .collect();
+    std::fs::write(&path, format!("day,price,volume\n{body}")).unwrap();
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Char('c'),
+        KeyModifiers::NONE,
+    )));
+    app.chart_modal.x_column = Some("day".into());
+    app.chart_modal.y_columns = vec!["price".into(), "volume".into()];
+    while app.chart_modal.focus != ChartFocus::RightAxis {
+        app.event(&AppEvent::Key(KeyEvent::new(
+            KeyCode::Tab,
+            KeyModifiers::NONE,
+        )));
+    }
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Right,
+        KeyModifiers::NONE,
+    )));
+    assert_eq!(app.chart_modal.right_axis_display(), "volume");
+
+    let area = Rect::new(0, 0, 120, 36);
+    let mut buf = Buffer::empty(area);
+    Widget::render(&mut app, area, &mut buf);
+    let rows: Vec<String> = (0..area.height)
+        .map(|y| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect::<String>()
+        })
+        .collect();
+    let text = rows.join("\n");
+    assert!(text.contains("volume (right)"), "{text}");
+    // Each axis spans its own series: price up to 20 on the left, volume up to 5000 on the right
+    assert!(
+        rows.iter()
+            .any(|r| r.contains("20.00") && r.contains("5000")),
+        "{text}"
+    );
+
+    for (name, format) in [
+        ("chart.png", ChartExportFormat::Png),
+        ("chart.eps", ChartExportFormat::Eps),
+    ] {
+        let out = dir.path().join(name);
+        app.event(&AppEvent::DoChartExport(
+            out.clone(),
+            format,
+            String::new(),
+            640,
+            480,
+        ));
+        assert!(out.exists(), "{name}");
+    }
+    let eps = std::fs::read_to_string(dir.path().join("chart.eps")).unwrap();
+    assert!(eps.contains("(volume) show"), "{eps}");
+}
+
+/// The correlation matrix shades its cells, highlights |r| at the threshold and exports through
+/// the chart export modal.
+#[test]
+fn test_correlation_heatmap_highlights_and_exports() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pairs.csv");
+    let body: String = (0..20)
+        .map(|i| format!("{i},{},{}\n", 50 - 2 * i, (i * 7) % 5))
+        .collect();
+    std::fs::write(&path, format!("a,b,c\n{body}")).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let pump = |app: &mut App, first: Option<AppEvent>| {
+        let mut next = first;
+        while let Some(ev) = next.take() {
+            next = app.event(&ev);
+            if next.is_none() && app.is_busy() {
+                next = rx.recv_timeout(std::time::Duration::from_secs(10)).ok();
+            }
+        }
+    };
+    app.event(&key(KeyCode::Char('a')));
+    for code in [KeyCode::Down, KeyCode::Down] {
+        app.event(&key(code));
+    }
+    let next = app.event(&key(KeyCode::Enter));
+    pump(&mut app, next);
+    assert!(app.analysis_modal.correlation_results.is_some());
+
+    let area = Rect::new(0, 0, 120, 30);
+    let render = |app: &mut App| {
+        let mut buf = Buffer::empty(area);
+        Widget::render(app, area, &mut buf);
+        buf
+    };
+    let buf = render(&mut app);
+    let rows: Vec<Vec<&str>> = (0..area.height)
+        .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+        .collect();
+    let text = rows
+        .iter()
+        .map(|r| r.concat())
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(text.contains("|r| ≥ 0.70"), "{text}");
+    // a and b are perfectly anti-correlated: shaded and highlighted
+    let (x, y) = rows
+        .iter()
+        .enumerate()
+        .find_map(|(y, row)| {
+            let x = row.windows(5).position(|w| w.concat() == "-1.00")?;
+            Some((x as u16, y as u16))
+        })
+        .expect("a/b cell");
+    let style = buf[(x, y)].style();
+    assert!(style.add_modifier.contains(ratatui::style::Modifier::BOLD));
+    assert!(style.bg.is_some());
+
+    app.event(&key(KeyCode::Char('H')));
+    assert!(!app.analysis_modal.correlation_heatmap);
+    app.event(&key(KeyCode::Char('+')));
+    assert!((app.analysis_modal.correlation_threshold - 0.75).abs() < 1e-9);
+
+    app.event(&key(KeyCode::Char('e')));
+    assert!(app.chart_export_modal.active);
+    let out = dir.path().join("corr.eps");
+    app.chart_export_modal
+        .path_input
+        .set_value(out.display().to_string());
+    app.chart_export_modal.selected_format = datui::chart_export::ChartExportFormat::Eps;
+    let next = app.event(&key(KeyCode::Enter));
+    assert!(matches!(next, Some(AppEvent::ChartExport(..))));
+    pump(&mut app, next);
+    let eps = std::fs::read_to_string(&out).unwrap();
+    assert!(eps.contains("(Correlation Matrix) show"), "{eps}");
+    assert!(eps.contains("(-1.00) show"), "{eps}");
+    assert!(app.analysis_modal.active);
+}
+
+/// The distribution detail histogram keeps the best-fit curve next to the candidate picked in
+/// the selector, and `o` hides it.
+#[test]
+fn test_distribution_histogram_overlays_best_fit_and_candidate() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("values.csv");
+    let body: String = (1..=200)
+        .map(|i| format!("{}\n", (i as f64 * 0.37).sin() * 10.0 + 50.0))
+        .collect();
+    std::fs::write(&path, format!("value\n{body}")).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('a')));
+    app.event(&key(KeyCode::Down));
+    let mut next = app.event(&key(KeyCode::Enter));
+    while let Some(ev) = next.take() {
+        next = app.event(&ev);
+        if next.is_none() && app.is_busy() {
+            next = rx.recv_timeout(std::time::Duration::from_secs(10)).ok();
+        }
+    }
+    app.event(&key(KeyCode::Enter));
+    assert_eq!(
+        app.analysis_modal.view,
+        datui::analysis_modal::AnalysisView::DistributionDetail
+    );
+
+    let area = Rect::new(0, 0, 160, 44);
+    let render = |app: &mut App| {
+        let mut buf = Buffer::empty(area);
+        Widget::render(app, area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol().to_string())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    // The detail view opens on the best fit, so there is a single curve
+    let best = app.analysis_modal.selected_theoretical_distribution;
+    assert!(!render(&mut app).contains("(best fit)"));
+
+    // Picking another candidate draws both
+    while app.analysis_modal.selected_theoretical_distribution == best {
+        app.event(&key(KeyCode::Down));
+    }
+    let candidate = app.analysis_modal.selected_theoretical_distribution;
+    let text = render(&mut app);
+    assert!(text.contains(&format!("─ {best} (best fit)")), "{text}");
+    assert!(text.contains(&format!("─ {candidate}")), "{text}");
+    assert!(text.contains("Best fit: Shown"), "{text}");
+
+    app.event(&key(KeyCode::Char('o')));
+    let text = render(&mut app);
+    assert!(!text.contains("(best fit)"), "{text}");
+    assert!(text.contains("Best fit: Hidden"), "{text}");
+}
+
 #[test]
 fn test_open_s3_url_returns_crash_or_loads() {
-    let (tx, _) = mpsc::channel();
+    let (tx, rx) = mpsc::channel();
     let mut app = App::new(tx, common::test_runtime());
     let path = PathBuf::from("s3://my-bucket/path/to/file.parquet");
     let next = app.event(&AppEvent::Open(vec![path], OpenOptions::default()));
     let ev = next.expect("Open should emit DoLoadScanPaths");
     assert!(matches!(ev, AppEvent::DoLoadScanPaths(_, _)));
-    let next = app.event(&ev);
+    assert!(app.event(&ev).is_none(), "the scan runs on a worker");
+    let ready = recv_scan_result(&mut app, &rx);
+    let next = app.event(&ready);
     match next.as_ref() {
         Some(AppEvent::Crash(m)) => {
             assert!(m.contains("S3"), "error should mention S3: {}", m);
@@ -328,6 +852,30 @@ fn test_open_http_url_attempts_load_or_returns_friendly_error() {
     }
 }
 
+/// Standard input (`-`) needs `--format`; the error comes back before anything is read.
+#[test]
+fn test_stdin_without_format_returns_error() {
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let paths = vec![PathBuf::from(datui::STDIN_PATH)];
+    assert!(app
+        .event(&AppEvent::Open(paths, OpenOptions::default()))
+        .is_none());
+    let ev = rx
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .expect("stdin result");
+    match app.event(&ev) {
+        Some(AppEvent::Crash(m)) => assert!(m.contains("--format"), "{}", m),
+        _ => panic!("expected Crash when reading stdin without --format"),
+    }
+
+    let paths = vec![PathBuf::from(datui::STDIN_PATH), PathBuf::from("a.csv")];
+    match app.event(&AppEvent::Open(paths, OpenOptions::default())) {
+        Some(AppEvent::Crash(m)) => assert!(m.contains("other paths"), "{}", m),
+        _ => panic!("expected Crash when combining stdin with other paths"),
+    }
+}
+
 #[test]
 fn test_multiple_remote_paths_returns_error() {
     let (tx, _) = mpsc::channel();
@@ -369,13 +917,15 @@ fn test_multiple_remote_paths_returns_error() {
 
 #[test]
 fn test_open_gs_url_returns_friendly_error_or_attempts_load() {
-    let (tx, _) = mpsc::channel();
+    let (tx, rx) = mpsc::channel();
     let mut app = App::new(tx, common::test_runtime());
     let path = PathBuf::from("gs://my-bucket/path/file.parquet");
     let next = app.event(&AppEvent::Open(vec![path], OpenOptions::default()));
     let ev = next.expect("Open should emit DoLoadScanPaths");
     assert!(matches!(ev, AppEvent::DoLoadScanPaths(_, _)));
-    let next = app.event(&ev);
+    assert!(app.event(&ev).is_none(), "the scan runs on a worker");
+    let ready = recv_scan_result(&mut app, &rx);
+    let next = app.event(&ready);
     match next.as_ref() {
         Some(AppEvent::Crash(m)) => {
             assert!(
@@ -829,3 +1379,3731 @@ fn test_hive_dir_loads_and_counts_via_footers() {
         "first buffer should be populated"
     );
 }
+
+/// Opening a plain directory shows the workspace file list; files open in tabs and
+/// `[` / `]` switch between them without reloading.
+#[test]
+fn test_workspace_directory_opens_files_in_tabs() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.csv"), "x,y\n1,2\n3,4\n5,6\n").unwrap();
+    std::fs::write(dir.path().join("b.csv"), "x\n1\n2\n3\n4\n5\n").unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "not data").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let next = app.event(&AppEvent::Open(
+        vec![dir.path().to_path_buf()],
+        OpenOptions::default(),
+    ));
+    assert!(
+        next.is_none(),
+        "directory should open the file list, not load"
+    );
+    assert!(app.data_table_state.is_none());
+    assert!(!app.is_busy());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let open_selected = |app: &mut App| match app.event(&key(KeyCode::Enter)) {
+        Some(AppEvent::Open(paths, opts)) => pump_open_until_loaded(app, &rx, paths, opts),
+        _ => panic!("Enter in the file list should open the selected file"),
+    };
+
+    open_selected(&mut app);
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 3);
+
+    app.event(&key(KeyCode::Char('w')));
+    app.event(&key(KeyCode::Char('j')));
+    open_selected(&mut app);
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 5);
+
+    // Switching back restores the first tab's table without a new Open.
+    assert!(app.event(&key(KeyCode::Char('['))).is_none());
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 3);
+    assert!(app.event(&key(KeyCode::Char(']'))).is_none());
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 5);
+}
+
+/// `A` in the file list opens every file with the selected file's extension as one table, and
+/// `--format` with a directory loads its files of that format without the list.
+#[test]
+fn test_directory_opens_files_of_one_format_together() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.csv"), "x\n1\n2\n3\n").unwrap();
+    std::fs::write(dir.path().join("b.csv"), "x\n4\n5\n").unwrap();
+    std::fs::write(dir.path().join("c.jsonl"), "{\"x\": 6}\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    app.event(&AppEvent::Open(
+        vec![dir.path().to_path_buf()],
+        OpenOptions::default(),
+    ));
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    match app.event(&key(KeyCode::Char('A'))) {
+        Some(AppEvent::Open(paths, opts)) => {
+            assert_eq!(paths, vec![dir.path().join("*.csv")]);
+            pump_open_until_loaded(&mut app, &rx, paths, opts);
+        }
+        _ => panic!("A in the file list should open the selected file's extension group"),
+    }
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 5);
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let options = OpenOptions {
+        format: Some(datui::FileFormat::Csv),
+        ..Default::default()
+    };
+    pump_open_until_loaded(&mut app, &rx, vec![dir.path().to_path_buf()], options);
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 5);
+}
+
+/// A SQLite database with several tables opens the table picker and Enter loads the
+/// highlighted table; `--table` skips the picker.
+#[test]
+fn test_sqlite_table_picker_loads_chosen_table() {
+    use datui::sqlite_export::{write_sqlite, IfExists};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("shop.db");
+    let orders = df!("id" => [1i64, 2, 3], "total" => [9.5, 3.0, 12.25]).unwrap();
+    let users = df!("name" => ["ann", "bob"]).unwrap();
+    write_sqlite(&orders, &path, "orders", IfExists::Fail).unwrap();
+    write_sqlite(&users, &path, "users", IfExists::Fail).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    assert!(app
+        .event(&AppEvent::Open(vec![path.clone()], OpenOptions::default()))
+        .is_none());
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('j')));
+    match app.event(&key(KeyCode::Enter)) {
+        Some(AppEvent::Open(paths, opts)) => {
+            assert_eq!(opts.table.as_deref(), Some("users"));
+            pump_open_until_loaded(&mut app, &rx, paths, opts);
+        }
+        _ => panic!("Enter in the table picker should open the highlighted table"),
+    }
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.num_rows, 2);
+    assert_eq!(state.schema.get("name"), Some(&DataType::String));
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let options = OpenOptions {
+        table: Some("orders".to_string()),
+        ..Default::default()
+    };
+    pump_open_until_loaded(&mut app, &rx, vec![path], options);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.num_rows, 3);
+    assert_eq!(state.schema.get("total"), Some(&DataType::Float64));
+}
+
+/// A `sqlite://` URL opens the table named by `?table=`, and `--sql` loads a query's result
+/// without the table picker.
+#[test]
+fn test_sqlite_url_and_sql_query() {
+    use datui::sqlite_export::{write_sqlite, IfExists};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("shop.db");
+    let orders = df!("id" => [1i64, 2, 3], "total" => [9.5, 3.0, 12.25]).unwrap();
+    write_sqlite(&orders, &path, "orders", IfExists::Fail).unwrap();
+    write_sqlite(
+        &df!("name" => ["ann"]).unwrap(),
+        &path,
+        "users",
+        IfExists::Fail,
+    )
+    .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let url = PathBuf::from(format!("sqlite://{}?table=orders", path.display()));
+    pump_open_until_loaded(&mut app, &rx, vec![url], OpenOptions::default());
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 3);
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let options = OpenOptions {
+        sql_query: Some("SELECT id FROM orders WHERE total > 5 ORDER BY id".to_string()),
+        ..Default::default()
+    };
+    pump_open_until_loaded(&mut app, &rx, vec![path], options);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.num_rows, 2);
+    assert_eq!(state.schema.len(), 1);
+}
+
+/// A Postgres or MySQL URL opens the table named by `?table=`, and `--sql` loads a query's
+/// result. Runs against the servers in `DATUI_TEST_POSTGRES_URL` and `DATUI_TEST_MYSQL_URL`
+/// (e.g. `postgres://postgres@localhost/test`), at least one of which must be set; run with
+/// `--ignored`.
+#[test]
+#[ignore = "needs DATUI_TEST_POSTGRES_URL / DATUI_TEST_MYSQL_URL"]
+#[cfg(feature = "database")]
+fn test_postgres_and_mysql_urls_with_table_and_sql() {
+    let servers: Vec<(&str, String)> = ["DATUI_TEST_POSTGRES_URL", "DATUI_TEST_MYSQL_URL"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok().map(|url| (var, url)))
+        .collect();
+    assert!(
+        !servers.is_empty(),
+        "set DATUI_TEST_POSTGRES_URL or DATUI_TEST_MYSQL_URL"
+    );
+    for (var, base) in servers {
+        let table = format!("datui_test_{}", std::process::id());
+        let setup = format!(
+            "CREATE TABLE {table} (id INT, total DECIMAL(8,2), placed DATE, note VARCHAR(20)); \
+             INSERT INTO {table} VALUES (1, 9.50, '2024-03-01', 'a'), (2, 3.00, NULL, NULL), \
+             (3, 12.25, '2024-03-03', 'c')"
+        );
+        execute(&base, &setup);
+
+        let (tx, rx) = mpsc::channel();
+        let mut app = App::new(tx, common::test_runtime());
+        let sep = if base.contains('?') { '&' } else { '?' };
+        let url = PathBuf::from(format!("{base}{sep}table={table}"));
+        pump_open_until_loaded(&mut app, &rx, vec![url], OpenOptions::default());
+        let state = app.data_table_state.as_ref().unwrap();
+        assert_eq!(state.num_rows, 3, "{var}");
+        assert_eq!(state.schema.get("id"), Some(&DataType::Int64), "{var}");
+        assert_eq!(state.schema.get("total"), Some(&DataType::Float64), "{var}");
+        assert_eq!(state.schema.get("placed"), Some(&DataType::Date), "{var}");
+        assert_eq!(state.schema.get("note"), Some(&DataType::String), "{var}");
+
+        let (tx, rx) = mpsc::channel();
+        let mut app = App::new(tx, common::test_runtime());
+        let options = OpenOptions {
+            sql_query: Some(format!(
+                "SELECT id FROM {table} WHERE total > 5 ORDER BY id"
+            )),
+            ..Default::default()
+        };
+        pump_open_until_loaded(&mut app, &rx, vec![PathBuf::from(&base)], options);
+        let state = app.data_table_state.as_ref().unwrap();
+        assert_eq!(state.num_rows, 2, "{var}");
+        assert_eq!(state.schema.len(), 1, "{var}");
+
+        execute(&base, &format!("DROP TABLE {table}"));
+    }
+
+    fn execute(url: &str, sql: &str) {
+        if url.starts_with("postgres") {
+            let mut client = postgres::Client::connect(url, postgres::NoTls).unwrap();
+            client.batch_execute(sql).unwrap();
+        } else {
+            use mysql::prelude::Queryable;
+            let url = url.replacen("mariadb://", "mysql://", 1);
+            let mut conn = mysql::Conn::new(mysql::Opts::from_url(&url).unwrap()).unwrap();
+            for statement in sql.split("; ") {
+                conn.query_drop(statement).unwrap();
+            }
+        }
+    }
+}
+
+/// `O` lists the tables of the loaded database and switches to the highlighted one, and the info
+/// panel names the table shown.
+#[test]
+fn test_switch_table_reloads_file_with_chosen_table() {
+    use datui::sqlite_export::{write_sqlite, IfExists};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("shop.db");
+    write_sqlite(
+        &df!("id" => [1i64, 2, 3]).unwrap(),
+        &path,
+        "orders",
+        IfExists::Fail,
+    )
+    .unwrap();
+    write_sqlite(
+        &df!("name" => ["ann"]).unwrap(),
+        &path,
+        "users",
+        IfExists::Fail,
+    )
+    .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let options = OpenOptions {
+        table: Some("orders".to_string()),
+        ..Default::default()
+    };
+    pump_open_until_loaded(&mut app, &rx, vec![path], options);
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 3);
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    assert!(app.event(&key(KeyCode::Char('O'))).is_none());
+    app.event(&key(KeyCode::Char('j')));
+    match app.event(&key(KeyCode::Enter)) {
+        Some(AppEvent::Open(paths, opts)) => {
+            assert_eq!(opts.table.as_deref(), Some("users"));
+            pump_open_until_loaded(&mut app, &rx, paths, opts);
+        }
+        _ => panic!("Enter in the table switcher should reload the file with the table"),
+    }
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 1);
+
+    app.event(&key(KeyCode::Char('i')));
+    let area = Rect::new(0, 0, 120, 40);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("Table: users (2 of 2) · O to switch"));
+}
+
+/// `p` in the sheet picker previews the sheet; moving past its title rows and pressing Enter
+/// loads it with that header row, and `--excel-range` cuts the sheet to a block of cells.
+#[test]
+fn test_sheet_preview_loads_with_chosen_header_row() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("report.xlsx");
+    let sheet = df!(
+        "Report" => [None, Some("id"), Some("1"), Some("2"), Some("Total")],
+        "2024" => [None, Some("amount"), Some("10"), Some("20"), None]
+    )
+    .unwrap();
+    datui::xlsx_export::write_xlsx(&sheet, &path, "Sheet1", false).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path.clone()], OpenOptions::default());
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 5);
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('O')));
+    assert!(app.event(&key(KeyCode::Char('p'))).is_none());
+    let area = Rect::new(0, 0, 120, 40);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("Sheet1 A1:B6"), "{screen}");
+    assert!(screen.contains("Header row 1"));
+
+    app.event(&key(KeyCode::Char('j')));
+    app.event(&key(KeyCode::Char('j')));
+    match app.event(&key(KeyCode::Enter)) {
+        Some(AppEvent::Open(paths, opts)) => {
+            assert_eq!(opts.excel_header_row, Some(3));
+            pump_open_until_loaded(&mut app, &rx, paths, opts);
+        }
+        _ => panic!("Enter in the sheet preview should load the sheet"),
+    }
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.num_rows, 3);
+    assert_eq!(state.headers(), vec!["id", "amount"]);
+
+    let options = OpenOptions {
+        excel_range: Some("A3:B5".to_string()),
+        ..Default::default()
+    };
+    pump_open_until_loaded(&mut app, &rx, vec![path], options);
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 2);
+}
+
+/// A semicolon-separated `.csv` loads as one column, so the dialect modal opens with the
+/// sniffed delimiter and header row; Enter reloads the file with them.
+#[test]
+fn test_csv_dialect_modal_offered_for_single_column_load() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("export.csv");
+    std::fs::write(&path, "Sales export\nid;name\n1;ann\n2;bob\n3;cy\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+    let area = Rect::new(0, 0, 120, 40);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("Only one column was found"), "{screen}");
+    assert!(screen.contains("Parsed: 2 columns"));
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    match app.event(&key(KeyCode::Enter)) {
+        Some(AppEvent::Open(paths, opts)) => {
+            assert_eq!(opts.delimiter, Some(b';'));
+            assert_eq!(opts.skip_lines, Some(1));
+            pump_open_until_loaded(&mut app, &rx, paths, opts);
+        }
+        _ => panic!("Enter in the dialect modal should reload the file"),
+    }
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.headers(), vec!["id", "name"]);
+    assert_eq!(state.num_rows, 3);
+
+    // D reopens it with the dialect the file was loaded with
+    assert!(app.event(&key(KeyCode::Char('D'))).is_none());
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("CSV dialect: export.csv"));
+    assert!(!screen.contains("Only one column was found"));
+    app.event(&key(KeyCode::Esc));
+}
+
+/// With `skip_bad_rows`, malformed CSV and JSON Lines rows are left out of the load instead of
+/// failing it, and the Load report panel lists them with their line numbers and errors.
+#[test]
+fn test_skip_bad_rows_loads_the_rest_and_reports_rejected_rows() {
+    let dir = tempfile::tempdir().unwrap();
+    let csv = dir.path().join("orders.csv");
+    std::fs::write(&csv, "id,amount\n1,2.5\n2,3\n3,4,oops\nfour,5\n5,6\n").unwrap();
+    let jsonl = dir.path().join("events.jsonl");
+    std::fs::write(&jsonl, "{\"id\": 1}\n{\"id\": 2\n{\"id\": 3}\n").unwrap();
+    let options = OpenOptions {
+        skip_bad_rows: true,
+        infer_schema_length: Some(2),
+        ..OpenOptions::default()
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![csv], options.clone());
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 3);
+    let area = Rect::new(0, 0, 120, 40);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("Load report: orders.csv"), "{screen}");
+    assert!(screen.contains("3 rows loaded, 2 left out"));
+    assert!(screen.contains("expected 2 fields, found 3"));
+    assert!(screen.contains("3,4,oops"));
+    assert!(screen.contains("`four` doesn't match column `id`"));
+
+    // Esc closes the panel and B reopens it
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Esc));
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(!screen.contains("Load report"));
+    app.event(&key(KeyCode::Char('B')));
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("Load report: orders.csv"));
+    app.event(&key(KeyCode::Esc));
+
+    pump_open_until_loaded(&mut app, &rx, vec![jsonl], options);
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 2);
+    let report = app
+        .data_table_state
+        .as_ref()
+        .and_then(|state| state.load_report.clone())
+        .unwrap();
+    assert_eq!(report.skipped, 1);
+    assert_eq!(report.rejected[0].line, 2);
+    assert_eq!(report.rejected[0].text, "{\"id\": 2");
+}
+
+/// `dtypes` reads the named CSV columns as the given types, and `adjust_schema` lists the
+/// inferred types first so one can be changed before the file is read.
+#[test]
+fn test_dtypes_and_schema_step_override_inferred_types() {
+    let dir = tempfile::tempdir().unwrap();
+    let csv = dir.path().join("people.csv");
+    std::fs::write(&csv, "zip,amount\n00123,1\n04567,2\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let options = OpenOptions {
+        dtypes: Some(vec!["zip:str".to_string(), "amount:f64".to_string()]),
+        ..OpenOptions::default()
+    };
+    pump_open_until_loaded(&mut app, &rx, vec![csv.clone()], options);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.schema.get("zip"), Some(&DataType::String));
+    assert_eq!(state.schema.get("amount"), Some(&DataType::Float64));
+    let area = Rect::new(0, 0, 120, 40);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("00123"), "{screen}");
+
+    // The schema step opens before the file is read; Left on zip steps i64 back to str
+    let options = OpenOptions {
+        adjust_schema: true,
+        ..OpenOptions::default()
+    };
+    let mut app = App::new(mpsc::channel().0, common::test_runtime());
+    assert!(app
+        .event(&AppEvent::Open(vec![csv.clone()], options))
+        .is_none());
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("Adjust schema: people.csv"), "{screen}");
+    assert!(screen.contains("00123, 04567"));
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Left));
+    let Some(AppEvent::Open(paths, options)) = app.event(&key(KeyCode::Enter)) else {
+        panic!("Enter should load the file");
+    };
+    assert_eq!(options.dtypes, Some(vec!["zip:str".to_string()]));
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, paths, options);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.schema.get("zip"), Some(&DataType::String));
+    assert_eq!(state.schema.get("amount"), Some(&DataType::Int64));
+}
+
+/// A file of length-delimited protobuf records opens with its `.proto` schema, one row per
+/// record and nested messages flattened into `parent.child` columns.
+#[test]
+fn test_protobuf_records_open_with_schema() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema = dir.path().join("event.proto");
+    std::fs::write(
+        &schema,
+        "syntax = \"proto3\";\n\
+         message Point { int32 x = 1; int32 y = 2; }\n\
+         message Event { string name = 1; Point at = 2; }\n",
+    )
+    .unwrap();
+    // Event{name: "a", at: {x: 1, y: 2}} and Event{name: "b"}, each preceded by its length
+    let records: Vec<u8> = vec![
+        9, 0x0a, 1, b'a', 0x12, 4, 0x08, 1, 0x10, 2, //
+        3, 0x0a, 1, b'b',
+    ];
+    let path = dir.path().join("events.log");
+    std::fs::write(&path, records).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let options = OpenOptions {
+        format: Some(datui::FileFormat::Protobuf),
+        proto_schema: Some(schema),
+        proto_message: Some("Event".to_string()),
+        ..Default::default()
+    };
+    pump_open_until_loaded(&mut app, &rx, vec![path], options);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.num_rows, 2);
+    let names: Vec<&str> = state.schema.iter_names().map(|n| n.as_str()).collect();
+    assert_eq!(names, vec!["name", "at.x", "at.y"]);
+    let df = state.lf.clone().collect().unwrap();
+    assert_eq!(
+        df.column("at.y").unwrap().get(0).unwrap(),
+        AnyValue::Int32(2)
+    );
+    assert_eq!(df.column("at.x").unwrap().null_count(), 1);
+}
+
+/// `o` picks another file to open in a new tab next to the data already shown; `X` closes
+/// tabs but keeps the last one.
+#[test]
+fn test_open_file_in_new_tab_and_close_it() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("a.csv");
+    std::fs::write(&first, "x,y\n1,2\n3,4\n5,6\n").unwrap();
+    std::fs::write(dir.path().join("b.csv"), "x\n1\n2\n3\n4\n5\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![first], OpenOptions::default());
+    app.data_table_state
+        .as_mut()
+        .unwrap()
+        .query("select where x > 1".to_string());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    // The picker starts in the current file's directory, on a.csv
+    assert!(app.event(&key(KeyCode::Char('o'))).is_none());
+    app.event(&key(KeyCode::Char('j')));
+    match app.event(&key(KeyCode::Enter)) {
+        Some(AppEvent::Open(paths, opts)) => {
+            assert_eq!(paths, vec![dir.path().join("b.csv")]);
+            pump_open_until_loaded(&mut app, &rx, paths, opts);
+        }
+        _ => panic!("Enter in the file picker should open the highlighted file"),
+    }
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 5);
+
+    // The first tab kept its query
+    assert!(app.event(&key(KeyCode::Char('['))).is_none());
+    assert_eq!(
+        app.data_table_state.as_ref().unwrap().get_active_query(),
+        "select where x > 1"
+    );
+
+    assert!(app.event(&key(KeyCode::Char('X'))).is_none());
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 5);
+    app.event(&key(KeyCode::Char('X')));
+    assert!(
+        app.data_table_state.is_some(),
+        "the last tab should stay open"
+    );
+}
+
+/// `e` in the file picker switches to typing a path, for files the picker doesn't list.
+#[test]
+fn test_file_picker_type_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("a.csv");
+    std::fs::write(&first, "x\n1\n").unwrap();
+    std::fs::write(dir.path().join("other.dat"), "x\n1\n2\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![first], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('o')));
+    app.event(&key(KeyCode::Char('e')));
+    assert_eq!(app.input_mode, InputMode::Editing);
+    for c in "other.dat".chars() {
+        app.event(&key(KeyCode::Char(c)));
+    }
+    match app.event(&key(KeyCode::Enter)) {
+        Some(AppEvent::Open(paths, _)) => {
+            assert_eq!(paths, vec![dir.path().join("other.dat")]);
+        }
+        _ => panic!("Enter in the open file prompt should open the typed path"),
+    }
+}
+
+/// `y` copies the selected cell, `Y` the row, and `Ctrl-V` then `y` a block, as TSV.
+#[test]
+fn test_copy_cell_row_and_visual_block_as_tsv() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cells.csv");
+    std::fs::write(&path, "a,b,c\n1,x,true\n2,y,false\n3,z,true\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let copied = |app: &mut App, event: AppEvent| -> String {
+        app.event(&event);
+        loop {
+            let ev = rx
+                .recv_timeout(std::time::Duration::from_secs(10))
+                .expect("copy result");
+            app.event(&ev);
+            if let AppEvent::BackgroundCellsReady { text, .. } = ev {
+                // Dismiss the copy confirmation (or error, without a clipboard)
+                app.event(&AppEvent::Key(KeyEvent::new(
+                    KeyCode::Esc,
+                    KeyModifiers::NONE,
+                )));
+                return text;
+            }
+        }
+    };
+
+    assert_eq!(copied(&mut app, key(KeyCode::Char('y'))), "1");
+    assert_eq!(copied(&mut app, key(KeyCode::Char('Y'))), "1\tx\ttrue");
+
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Char('v'),
+        KeyModifiers::CONTROL,
+    )));
+    app.event(&key(KeyCode::Char('j')));
+    app.event(&key(KeyCode::Char('l')));
+    let block = app
+        .data_table_state
+        .as_ref()
+        .unwrap()
+        .visual_block()
+        .unwrap();
+    assert_eq!(block.columns, vec!["a", "b"]);
+    assert_eq!(copied(&mut app, key(KeyCode::Char('y'))), "1\tx\n2\ty");
+    assert!(
+        app.data_table_state
+            .as_ref()
+            .unwrap()
+            .visual_block()
+            .is_none(),
+        "copying ends visual block mode"
+    );
+}
+
+/// `zm` and `zc` copy the rows on screen, with every column of the view, as Markdown and CSV.
+#[test]
+fn test_copy_visible_page_as_markdown_and_csv() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("page.csv");
+    std::fs::write(&path, "city,sales\nOslo,120\nLima,85\nRiga,40\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+    app.data_table_state.as_mut().unwrap().visible_rows = 2;
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let copied = |app: &mut App, code: KeyCode| -> String {
+        app.event(&key(KeyCode::Char('z')));
+        app.event(&key(code));
+        loop {
+            let ev = rx
+                .recv_timeout(std::time::Duration::from_secs(10))
+                .expect("copy result");
+            app.event(&ev);
+            if let AppEvent::BackgroundCellsReady { text, .. } = ev {
+                app.event(&key(KeyCode::Esc));
+                return text;
+            }
+        }
+    };
+
+    assert_eq!(
+        copied(&mut app, KeyCode::Char('m')),
+        "| city | sales |\n| --- | ---: |\n| Oslo | 120 |\n| Lima | 85 |"
+    );
+    assert_eq!(
+        copied(&mut app, KeyCode::Char('c')),
+        "city,sales\nOslo,120\nLima,85\n"
+    );
+}
+
+#[test]
+fn test_aggregate_peek_respects_query_and_closes_on_key() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("peek.csv");
+    std::fs::write(&path, "x,y\n1,a\n2,b\n3,c\n10,d\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+    app.data_table_state
+        .as_mut()
+        .unwrap()
+        .query("select where x < 10".to_string());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    assert!(app.event(&key(KeyCode::Char('A'))).is_none());
+    while app.is_busy() {
+        let ev = rx
+            .recv_timeout(std::time::Duration::from_secs(10))
+            .expect("aggregate peek result");
+        app.event(&ev);
+    }
+
+    let peek = app.aggregate_peek().expect("popup shown");
+    assert_eq!(peek.column, "x");
+    assert_eq!(peek.count, 3);
+    assert_eq!(peek.sum, Some(AnyValue::Int64(6)));
+    assert_eq!(peek.max, Some(AnyValue::Int64(3)));
+
+    app.event(&key(KeyCode::Esc));
+    assert!(app.aggregate_peek().is_none());
+}
+
+/// `m` selects a range of rows; `E` exports exactly those rows and `A` aggregates over them.
+#[test]
+fn test_row_selection_exports_and_aggregates_selected_rows() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("rows.csv");
+    let body: String = (0..30).map(|i| format!("{i}\n")).collect();
+    std::fs::write(&path, format!("n\n{body}")).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.data_table_state
+        .as_mut()
+        .unwrap()
+        .table_state
+        .select(Some(10));
+    app.event(&key(KeyCode::Char('m')));
+    for _ in 0..3 {
+        app.event(&key(KeyCode::Char('j')));
+    }
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.selected_rows(), Some((10, 4)));
+    assert_eq!(state.visual_block().unwrap().columns, vec!["n"]);
+
+    app.event(&key(KeyCode::Char('A')));
+    while app.is_busy() {
+        let ev = rx
+            .recv_timeout(std::time::Duration::from_secs(10))
+            .expect("aggregate peek result");
+        app.event(&ev);
+    }
+    let peek = app.aggregate_peek().expect("popup shown");
+    assert_eq!(peek.count, 4);
+    assert_eq!(peek.sum, Some(AnyValue::Int64(10 + 11 + 12 + 13)));
+    assert_eq!(peek.rows, Some((10, 4)));
+    assert!(
+        app.data_table_state
+            .as_ref()
+            .unwrap()
+            .selected_rows()
+            .is_none(),
+        "aggregating ends the selection"
+    );
+    app.event(&key(KeyCode::Esc));
+
+    app.event(&key(KeyCode::Char('m')));
+    app.event(&key(KeyCode::Char('k')));
+    app.event(&key(KeyCode::Char('E')));
+    assert_eq!(app.input_mode, InputMode::Export);
+    assert_eq!(app.export_modal.row_range, Some((12, 2)));
+}
+
+/// `F` opens the value counts popup for the selected column; counts follow the current query.
+#[test]
+fn test_value_counts_popup_lists_most_frequent_values() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("colors.csv");
+    std::fs::write(
+        &path,
+        "color,n\nred,1\nblue,2\nred,3\ngreen,4\nred,5\nblue,6\n",
+    )
+    .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+    app.data_table_state
+        .as_mut()
+        .unwrap()
+        .query("select where n > 1".to_string());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    assert!(app.event(&key(KeyCode::Char('F'))).is_none());
+    assert!(app.value_counts().is_some(), "popup opens while counting");
+    while app.is_busy() {
+        let ev = rx
+            .recv_timeout(std::time::Duration::from_secs(10))
+            .expect("value counts result");
+        app.event(&ev);
+    }
+
+    let popup = app.value_counts().expect("popup shown");
+    assert_eq!(popup.phase, None);
+    let counts = popup.counts.as_ref().expect("counts ready");
+    assert_eq!(counts.column, "color");
+    assert_eq!(counts.distinct, 3);
+    let values: Vec<(String, usize)> = counts
+        .values
+        .iter()
+        .map(|(v, n)| (v.str_value().to_string(), *n))
+        .collect();
+    assert_eq!(
+        values,
+        vec![
+            ("blue".to_string(), 2),
+            ("red".to_string(), 2),
+            ("green".to_string(), 1)
+        ]
+    );
+
+    let area = Rect::new(0, 0, 80, 24);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("3 distinct · 5 rows"), "footer rendered");
+    assert!(screen.contains("40.0%"), "share of blue rendered");
+
+    app.event(&key(KeyCode::Esc));
+    assert!(app.value_counts().is_none());
+}
+
+/// `[keys]` in the config moves actions to other keys, frees their default keys and is shown in
+/// the main view help.
+/// Where `text` first appears on a rendered screen, as (column, row).
+fn find_on_screen(buf: &Buffer, text: &str) -> Option<(u16, u16)> {
+    let area = buf.area;
+    (area.y..area.bottom()).find_map(|y| {
+        let line: String = (area.x..area.right())
+            .map(|x| buf[(x, y)].symbol().to_string())
+            .collect();
+        line.find(text)
+            .map(|i| (area.x + line[..i].chars().count() as u16, y))
+    })
+}
+
+#[test]
+fn test_mouse_selects_cells_sorts_headers_and_presses_buttons() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("scores.csv");
+    std::fs::write(&path, "name,score\nc,2\na,3\nb,1\n").unwrap();
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let area = Rect::new(0, 0, 80, 20);
+    let render = |app: &mut App| {
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf
+    };
+    let click = |(column, row): (u16, u16)| {
+        AppEvent::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    };
+
+    let buf = render(&mut app);
+    let (x, header_y) = find_on_screen(&buf, "score").unwrap();
+    app.event(&click((x, header_y + 3)));
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.table_state.selected(), Some(2), "third row selected");
+    assert_eq!(state.selected_column(), Some("score"));
+
+    let buf = render(&mut app);
+    // The scrolled-left indicator covers the header's first letter.
+    let header = find_on_screen(&buf, "core").unwrap();
+    for (ascending, expected) in [(true, [1, 2, 3]), (false, [3, 2, 1])] {
+        let next = app.event(&click(header)).expect("a header click sorts");
+        app.event(&next);
+        drain_events(&mut app, &rx);
+        let state = app.data_table_state.as_ref().unwrap();
+        assert_eq!(state.get_sort_columns(), ["score"]);
+        assert_eq!(state.get_sort_ascending(), ascending);
+        let df = state.display_df().unwrap();
+        let scores: Vec<i64> = df
+            .column("score")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(scores, expected);
+        render(&mut app);
+    }
+    let next = app.event(&click(header)).unwrap();
+    app.event(&next);
+    drain_events(&mut app, &rx);
+    assert!(app
+        .data_table_state
+        .as_ref()
+        .unwrap()
+        .get_sort_columns()
+        .is_empty());
+
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Char('s'),
+        KeyModifiers::NONE,
+    )));
+    assert_eq!(app.input_mode, InputMode::SortFilter);
+    let buf = render(&mut app);
+    app.event(&click(find_on_screen(&buf, "Cancel").unwrap()));
+    assert_eq!(app.input_mode, InputMode::Normal, "Cancel clicked");
+}
+
+#[test]
+fn test_column_widths_resize_autofit_and_peek() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("notes.csv");
+    std::fs::write(&path, "note,id\nthe full text of a long note,1\n").unwrap();
+
+    let config: datui::config::AppConfig =
+        toml::from_str("[display]\nmax_column_width = 8\n").unwrap();
+    config.validate().unwrap();
+    let theme = datui::config::Theme::from_config(&config.theme).unwrap();
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new_with_config(tx, common::test_runtime(), theme, config);
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let area = Rect::new(0, 0, 100, 20);
+    let screen = |app: &mut App| {
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content().iter().map(|c| c.symbol()).collect::<String>()
+    };
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+
+    assert!(
+        screen(&mut app).contains("the ful…"),
+        "cut at max_column_width"
+    );
+    assert!(app.data_table_state.as_ref().unwrap().is_column_cut("note"));
+
+    app.event(&key(KeyCode::Char('z')));
+    app.event(&key(KeyCode::Char('a')));
+    assert!(
+        screen(&mut app).contains("the full text of a long note"),
+        "za shows the whole value"
+    );
+    app.event(&key(KeyCode::Esc));
+
+    app.event(&key(KeyCode::Char('>')));
+    assert!(screen(&mut app).contains("the full …"));
+    app.event(&key(KeyCode::Char('<')));
+    app.event(&key(KeyCode::Char('<')));
+    assert_eq!(
+        app.data_table_state.as_ref().unwrap().column_widths["note"],
+        6
+    );
+    assert!(screen(&mut app).contains("the f…"));
+
+    // The column has a set width, so = goes back to the automatic width; again fits the content.
+    app.event(&key(KeyCode::Char('=')));
+    assert!(app
+        .data_table_state
+        .as_ref()
+        .unwrap()
+        .column_widths
+        .is_empty());
+    assert!(screen(&mut app).contains("the ful…"));
+    app.event(&key(KeyCode::Char('=')));
+    assert!(screen(&mut app).contains("the full text of a long note"));
+    assert!(!app.data_table_state.as_ref().unwrap().is_column_cut("note"));
+}
+
+#[test]
+fn test_enter_shows_record_view_with_nested_values() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("events.parquet");
+    let point = StructChunked::from_series(
+        "point".into(),
+        1,
+        [
+            Series::new("lat".into(), [51.5f64]),
+            Series::new("lon".into(), [-0.12f64]),
+        ]
+        .iter(),
+    )
+    .unwrap()
+    .into_series();
+    let note = "word ".repeat(30);
+    let mut df = DataFrame::new(vec![
+        Series::new("id".into(), [7i64]).into(),
+        Series::new("note".into(), [note.trim()]).into(),
+        point.into(),
+    ])
+    .unwrap();
+    ParquetWriter::new(File::create(&path).unwrap())
+        .finish(&mut df)
+        .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let area = Rect::new(0, 0, 80, 24);
+    let screen = |app: &mut App| {
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+    };
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+
+    if let Some(next) = app.event(&key(KeyCode::Enter)) {
+        app.event(&next);
+    }
+    drain_events(&mut app, &rx);
+    let view = app.record_view().expect("Enter opens the record view");
+    assert_eq!(view.row, 0);
+
+    let lines = screen(&mut app);
+    let text = lines.join("\n");
+    assert!(text.contains(" Row 1 "));
+    assert!(lines.iter().any(|l| l.contains("id") && l.contains('7')));
+    assert!(text.contains("{2 fields}"));
+    assert!(lines
+        .iter()
+        .any(|l| l.contains("  lat") && l.contains("51.5")));
+    assert!(lines
+        .iter()
+        .any(|l| l.contains("  lon") && l.contains("-0.12")));
+    let note_lines = lines.iter().filter(|l| l.contains("word word")).count();
+    assert!(note_lines > 1, "the long note wraps onto several lines");
+
+    app.event(&key(KeyCode::Esc));
+    assert!(app.record_view().is_none());
+}
+
+#[test]
+fn test_expand_unnests_struct_and_explodes_list_columns() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("places.parquet");
+    let point = StructChunked::from_series(
+        "point".into(),
+        2,
+        [
+            Series::new("lat".into(), [51.5f64, 48.9]),
+            Series::new("lon".into(), [-0.12f64, 2.35]),
+        ]
+        .iter(),
+    )
+    .unwrap()
+    .into_series();
+    let tags = Series::new(
+        "tags".into(),
+        [
+            Series::new(PlSmallStr::EMPTY, ["uk", "capital"]),
+            Series::new(PlSmallStr::EMPTY, ["fr"]),
+        ],
+    );
+    let mut df = DataFrame::new(vec![point.into(), tags.into()]).unwrap();
+    ParquetWriter::new(File::create(&path).unwrap())
+        .finish(&mut df)
+        .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    // Expanding collects the view in the background.
+    let expand = |app: &mut App| {
+        if let Some(next) = app.event(&key(KeyCode::Char('u'))) {
+            app.event(&next);
+        }
+        drain_events(app, &rx);
+    };
+
+    // za shows a struct cell field by field.
+    app.event(&key(KeyCode::Char('z')));
+    app.event(&key(KeyCode::Char('a')));
+    assert_eq!(app.cell_peek(), Some(("point", "lat: 51.5\nlon: -0.12")));
+    app.event(&key(KeyCode::Esc));
+
+    expand(&mut app);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.headers(), ["point_lat", "point_lon", "tags"]);
+
+    app.event(&key(KeyCode::Char('l')));
+    app.event(&key(KeyCode::Char('l')));
+    expand(&mut app);
+    let state = app.data_table_state.as_ref().unwrap();
+    let df = state.display_df().unwrap();
+    assert_eq!(df.height(), 3, "one row per tag");
+
+    app.event(&key(KeyCode::Char('h')));
+    expand(&mut app);
+    let area = Rect::new(0, 0, 100, 24);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(
+        screen.contains("is not a struct or list column"),
+        "a plain column can't be expanded"
+    );
+}
+
+#[test]
+fn test_config_keys_rebind_actions_and_help() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("events.csv");
+    std::fs::write(&path, "ts,value\n2024-01-01T12:00:00,1\n").unwrap();
+
+    let config: datui::config::AppConfig = toml::from_str(
+        "[keys]\ntime_zone = \"alt+z\"\npage_down = \"space\"\nscroll_right = \"x\"\n",
+    )
+    .unwrap();
+    config.validate().unwrap();
+    let theme = datui::config::Theme::from_config(&config.theme).unwrap();
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new_with_config(tx, common::test_runtime(), theme, config);
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('Z')));
+    assert!(!app.time_zone_modal.active, "Z is no longer bound");
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Char('z'),
+        KeyModifiers::ALT,
+    )));
+    assert!(app.time_zone_modal.active);
+    app.event(&key(KeyCode::Esc));
+    assert!(!app.time_zone_modal.active);
+
+    // Column scrolling is handled ahead of the other main-view keys; it follows the bindings too.
+    app.event(&key(KeyCode::Char('l')));
+    assert_eq!(app.data_table_state.as_ref().unwrap().termcol_index, 0);
+    app.event(&key(KeyCode::Char('x')));
+    assert_eq!(app.data_table_state.as_ref().unwrap().termcol_index, 1);
+
+    app.event(&key(KeyCode::Char('?')));
+    let area = Rect::new(0, 0, 120, 40);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(
+        screen.contains("Space / PgUp/Ctrl+B:"),
+        "help shows the bindings"
+    );
+}
+
+/// The previous regex match is an action of its own: `N` toggles row numbers while a search is
+/// active, and `prev_match` can be bound like any other action.
+#[test]
+fn test_prev_match_is_its_own_action() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("log.csv");
+    std::fs::write(&path, "id,message\n0,hay\n1,needle\n2,hay\n3,needle\n").unwrap();
+
+    let config: datui::config::AppConfig = toml::from_str("[keys]\nprev_match = \",\"\n").unwrap();
+    config.validate().unwrap();
+    let theme = datui::config::Theme::from_config(&config.theme).unwrap();
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new_with_config(tx, common::test_runtime(), theme, config);
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let run = |app: &mut App, ev: AppEvent| {
+        let mut next = app.event(&ev);
+        loop {
+            while let Some(ev) = next.take() {
+                next = app.event(&ev);
+            }
+            if !app.is_busy() {
+                break;
+            }
+            next = Some(
+                rx.recv_timeout(std::time::Duration::from_secs(10))
+                    .expect("background result"),
+            );
+        }
+    };
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let selected_row = |app: &App| {
+        let state = app.data_table_state.as_ref().unwrap();
+        state.start_row + state.table_state.selected().unwrap()
+    };
+
+    let area = Rect::new(0, 0, 80, 24);
+    app.render(area, &mut Buffer::empty(area));
+    run(&mut app, AppEvent::Collect);
+
+    run(&mut app, key(KeyCode::Char('/')));
+    run(&mut app, key(KeyCode::Tab));
+    run(&mut app, key(KeyCode::Left));
+    run(&mut app, key(KeyCode::Left));
+    run(&mut app, key(KeyCode::Tab));
+    for c in "needle".chars() {
+        run(&mut app, key(KeyCode::Char(c)));
+    }
+    run(&mut app, key(KeyCode::Enter));
+    assert_eq!(selected_row(&app), 1);
+    run(&mut app, key(KeyCode::Char('n')));
+    assert_eq!(selected_row(&app), 3);
+    run(&mut app, key(KeyCode::Char('N')));
+    assert_eq!(
+        selected_row(&app),
+        3,
+        "N toggles row numbers, not the match"
+    );
+    run(&mut app, key(KeyCode::Char(',')));
+    assert_eq!(
+        selected_row(&app),
+        1,
+        "the bound key jumps back to the previous match"
+    );
+}
+
+/// `I` shows statistics of the selected column in a sidebar, computed in the background and
+/// following the column cursor.
+#[test]
+fn test_column_stats_sidebar_follows_selected_column() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("scores.csv");
+    std::fs::write(&path, "name,score\nann,3\nbob,\ncid,9\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let area = Rect::new(0, 0, 100, 24);
+    let screen = |app: &mut App| {
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content().iter().map(|c| c.symbol()).collect::<String>()
+    };
+    // Process background events until the sidebar shows `expected`
+    let wait_for = |app: &mut App, expected: &str| {
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        while !screen(app).contains(expected) {
+            let timeout = deadline.saturating_duration_since(std::time::Instant::now());
+            let ev = rx.recv_timeout(timeout).expect("column statistics");
+            app.event(&ev);
+        }
+    };
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('I')));
+    wait_for(&mut app, "~3");
+    assert!(screen(&mut app).contains("Rows"), "statistics rendered");
+
+    app.event(&key(KeyCode::Right));
+    wait_for(&mut app, "6.0000");
+    assert!(
+        screen(&mut app).contains("1 (33.3%)"),
+        "null share of score"
+    );
+
+    app.event(&key(KeyCode::Char('I')));
+    assert!(!screen(&mut app).contains("Distinct"), "sidebar hidden");
+}
+
+/// `--diff` compares two files by key over the old file's table: counts, schema changes, and
+/// one line per differing row with changed cells as `old → new`; `f` filters by change and Esc
+/// closes it.
+#[test]
+fn test_diff_shows_added_removed_and_changed_rows() {
+    let dir = tempfile::tempdir().unwrap();
+    let old = dir.path().join("old.csv");
+    let new = dir.path().join("new.csv");
+    std::fs::write(&old, "id,name,qty\n1,ann,10\n2,bob,20\n3,cid,30\n").unwrap();
+    std::fs::write(
+        &new,
+        "id,name,qty,note\n1,ann,10,x\n3,cid,31,y\n4,dan,40,z\n",
+    )
+    .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![old.clone()], OpenOptions::default());
+    let request = datui::data_diff::DiffRequest {
+        old,
+        new,
+        keys: vec!["id".to_string()],
+    };
+    app.event(&AppEvent::OpenDiff(
+        Box::new(request),
+        OpenOptions::default(),
+    ));
+    while app.data_diff().is_some_and(|view| view.diff.is_none()) {
+        let ev = rx
+            .recv_timeout(std::time::Duration::from_secs(10))
+            .expect("diff result");
+        app.event(&ev);
+    }
+
+    let area = Rect::new(0, 0, 100, 24);
+    let screen = |app: &mut App| {
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+    };
+    let lines = screen(&mut app);
+    let text = lines.join("\n");
+    assert!(text.contains("Diff: old.csv → new.csv"), "{text}");
+    assert!(text.contains("+1 added"), "{text}");
+    assert!(text.contains("-1 removed"), "{text}");
+    assert!(text.contains("~1 changed"), "{text}");
+    assert!(text.contains("1 unchanged"), "{text}");
+    assert!(text.contains("Only in new.csv: note"), "{text}");
+    assert!(lines
+        .iter()
+        .any(|l| l.contains("~ 3") && l.contains("30 → 31")));
+    assert!(lines.iter().any(|l| l.contains("- 2") && l.contains("bob")));
+    assert!(lines.iter().any(|l| l.contains("+ 4") && l.contains("dan")));
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('f')));
+    let text = screen(&mut app).join("\n");
+    assert!(text.contains("Show: added"));
+    assert!(
+        text.contains("dan") && !text.contains("bob"),
+        "only added rows"
+    );
+
+    app.event(&key(KeyCode::Esc));
+    assert!(app.data_diff().is_none());
+    assert!(!screen(&mut app).join("\n").contains("Diff:"));
+}
+
+/// `H` shades numeric cells from the column's minimum to its maximum once the column ranges are
+/// computed in the background; other columns keep their background.
+#[test]
+fn test_heatmap_shades_numeric_cells_by_column_range() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("scores.csv");
+    std::fs::write(&path, "name,score\nann,1\nbob,5\ncid,9\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let area = Rect::new(0, 0, 80, 24);
+    let render = |app: &mut App| {
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf
+    };
+    // Backgrounds of the first letter of the name and of the score, in the row of `name`
+    let backgrounds = |buf: &Buffer, name: &str| {
+        let y = (0..area.height)
+            .find(|&y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+                    .contains(name)
+            })
+            .expect("row shown");
+        let line: Vec<&str> = (0..area.width).map(|x| buf[(x, y)].symbol()).collect();
+        let name_x = line
+            .iter()
+            .position(|s| *s == &name[..1])
+            .expect("name shown");
+        let score_x = line.iter().rposition(|s| *s != " ").expect("score shown");
+        (buf[(name_x as u16, y)].bg, buf[(score_x as u16, y)].bg)
+    };
+    let before = render(&mut app);
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('H')));
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while backgrounds(&render(&mut app), "cid") == backgrounds(&before, "cid") {
+        let timeout = deadline.saturating_duration_since(std::time::Instant::now());
+        let ev = rx.recv_timeout(timeout).expect("heatmap ranges");
+        app.event(&ev);
+    }
+
+    let shaded = render(&mut app);
+    let (ann_name, ann_score) = backgrounds(&shaded, "ann");
+    let (cid_name, cid_score) = backgrounds(&shaded, "cid");
+    assert_eq!(
+        ann_name,
+        backgrounds(&before, "ann").0,
+        "strings aren't shaded"
+    );
+    assert_eq!(
+        cid_name,
+        backgrounds(&before, "cid").0,
+        "strings aren't shaded"
+    );
+    assert_ne!(
+        ann_score, cid_score,
+        "minimum and maximum get different shades"
+    );
+
+    app.event(&key(KeyCode::Char('H')));
+    assert_eq!(
+        backgrounds(&render(&mut app), "cid"),
+        backgrounds(&before, "cid"),
+        "heatmap off"
+    );
+}
+
+/// Regex tab: matching cells are highlighted and `n` / `N` jump between matching rows, loading
+/// rows beyond the buffer when needed.
+#[test]
+fn test_regex_search_jumps_between_matches() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("log.csv");
+    let mut csv = String::from("id,message\n");
+    for i in 0..2000 {
+        let message = match i {
+            5 => "needle here",
+            1500 => "another NEEDLE",
+            _ => "hay",
+        };
+        csv.push_str(&format!("{},{}\n", i, message));
+    }
+    std::fs::write(&path, csv).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+    let area = Rect::new(0, 0, 80, 24);
+
+    // Run an event and everything it leads to, including background results
+    let run = |app: &mut App, ev: AppEvent| {
+        let mut next = app.event(&ev);
+        loop {
+            while let Some(ev) = next.take() {
+                next = app.event(&ev);
+            }
+            if !app.is_busy() {
+                break;
+            }
+            next = Some(
+                rx.recv_timeout(std::time::Duration::from_secs(10))
+                    .expect("background result"),
+            );
+        }
+    };
+    // Size the buffer to the viewport, as the event loop does after the first draw
+    app.render(area, &mut Buffer::empty(area));
+    run(&mut app, AppEvent::Collect);
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let selected_row = |app: &App| {
+        let state = app.data_table_state.as_ref().unwrap();
+        state.start_row + state.table_state.selected().unwrap()
+    };
+
+    run(&mut app, key(KeyCode::Char('/')));
+    // Tab bar, then Left wraps from SQL-Like to Column, then Regex
+    run(&mut app, key(KeyCode::Tab));
+    run(&mut app, key(KeyCode::Left));
+    run(&mut app, key(KeyCode::Left));
+    run(&mut app, key(KeyCode::Tab));
+    for c in "(?i)need.e".chars() {
+        run(&mut app, key(KeyCode::Char(c)));
+    }
+    run(&mut app, key(KeyCode::Enter));
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(selected_row(&app), 5);
+
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(
+        screen.contains("/(?i)need.e/ 1 of 2"),
+        "search status shown"
+    );
+
+    run(&mut app, key(KeyCode::Char('n')));
+    assert_eq!(selected_row(&app), 1500);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(
+        screen.contains("another NEEDLE"),
+        "row beyond the first buffer loaded"
+    );
+    assert!(screen.contains("2 of 2"));
+
+    // Wraps around both ways
+    run(&mut app, key(KeyCode::Char('n')));
+    assert_eq!(selected_row(&app), 5);
+    run(
+        &mut app,
+        AppEvent::Key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::ALT)),
+    );
+    assert_eq!(selected_row(&app), 1500);
+
+    // Esc clears the search
+    run(&mut app, key(KeyCode::Esc));
+    assert!(app
+        .data_table_state
+        .as_ref()
+        .unwrap()
+        .regex_search
+        .is_none());
+    run(&mut app, key(KeyCode::Char('n')));
+    assert_eq!(selected_row(&app), 1500);
+
+    // Column tab: only the selected column (id) is searched
+    run(&mut app, key(KeyCode::Char('/')));
+    run(&mut app, key(KeyCode::Tab));
+    run(&mut app, key(KeyCode::Left));
+    run(&mut app, key(KeyCode::Tab));
+    for c in "^(5|hay)$".chars() {
+        run(&mut app, key(KeyCode::Char(c)));
+    }
+    run(&mut app, key(KeyCode::Enter));
+    assert_eq!(selected_row(&app), 5);
+    let search = app.data_table_state.as_ref().unwrap().regex_search.as_ref();
+    assert_eq!(search.unwrap().column.as_deref(), Some("id"));
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(
+        screen.contains("/^(5|hay)$/ in id 1 of 1"),
+        "column search status"
+    );
+    // `/` reopens on the Column tab while a column search is active
+    run(&mut app, key(KeyCode::Char('/')));
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("Search id; n / N to jump"));
+}
+
+/// `f` formats the selected column: the table shows the formatted text and the format is kept
+/// in the view (and so in templates).
+#[test]
+fn test_format_modal_formats_selected_column() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("prices.csv");
+    std::fs::write(&path, "price,name\n1234.5,a\n0.25,b\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('%')));
+    assert!(app.format_modal.active);
+    assert_eq!(app.format_modal.column, "price");
+    // Style: Number -> Currency, then decimals 2 and thousands on
+    app.event(&key(KeyCode::Left));
+    app.event(&key(KeyCode::Tab));
+    app.event(&key(KeyCode::Char('2')));
+    app.event(&key(KeyCode::Tab));
+    app.event(&key(KeyCode::Right));
+    app.event(&key(KeyCode::Enter));
+    assert!(!app.format_modal.active);
+
+    let format = &app.data_table_state.as_ref().unwrap().column_formats["price"];
+    assert_eq!(format.decimals, Some(2));
+    assert!(format.thousands);
+
+    let area = Rect::new(0, 0, 80, 24);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("$1,234.50"), "formatted price rendered");
+    assert!(screen.contains("$0.25"));
+
+    // String columns can't be formatted
+    app.event(&key(KeyCode::Right));
+    app.event(&key(KeyCode::Char('%')));
+    assert!(!app.format_modal.active);
+}
+
+/// `f=` / `f>` add a filter on the selected cell to the view's filters, without the modal.
+#[test]
+fn test_quick_filter_from_selected_cell() {
+    use datui::filter_modal::FilterOperator;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sales.csv");
+    std::fs::write(&path, "region,amount\nEast,5\nWest,20\nEast,15\nNorth,7\n").unwrap();
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let press = |app: &mut App, code| {
+        if let Some(next) = app.event(&key(code)) {
+            app.event(&next);
+        }
+        drain_events(app, &rx);
+    };
+    press(&mut app, KeyCode::Char('f'));
+    press(&mut app, KeyCode::Char('='));
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 2);
+
+    press(&mut app, KeyCode::Right);
+    press(&mut app, KeyCode::Char('f'));
+    press(&mut app, KeyCode::Char('>'));
+    assert!(!app.format_modal.active);
+    let state = app.data_table_state.as_ref().unwrap();
+    let filters = state.get_filters();
+    assert_eq!(filters.len(), 2);
+    assert_eq!(filters[1].operator, FilterOperator::Gt);
+    assert_eq!(filters[1].value, "5");
+    assert_eq!(state.num_rows, 1);
+    assert_eq!(app.sort_filter_modal.filter.statements.len(), 2);
+
+    // Any other key after `f` does its usual action and adds no filter
+    press(&mut app, KeyCode::Char('f'));
+    press(&mut app, KeyCode::Char('%'));
+    assert!(app.format_modal.active, "% still opens the Format modal");
+    assert_eq!(
+        app.data_table_state.as_ref().unwrap().get_filters().len(),
+        2
+    );
+}
+
+/// `+` adds a computed column: the preview follows the expression, the column joins the view
+/// (where queries can use it) and is saved in templates.
+#[test]
+fn test_new_column_modal_adds_computed_column() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("orders.csv");
+    std::fs::write(&path, "price,qty\n2.5,4\n3,1\n10,2\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let type_text = |app: &mut App, text: &str| {
+        for c in text.chars() {
+            app.event(&key(KeyCode::Char(c)));
+        }
+    };
+    app.event(&key(KeyCode::Char('+')));
+    assert!(app.computed_column_modal.active);
+    type_text(&mut app, "total");
+    app.event(&key(KeyCode::Tab));
+    type_text(&mut app, "price * qty");
+    let preview = app.computed_column_modal.preview.clone().unwrap().unwrap();
+    assert_eq!(preview.values, ["10.0", "3.0", "20.0"]);
+    app.event(&key(KeyCode::Enter));
+    assert!(!app.computed_column_modal.active);
+    drain_events(&mut app, &rx);
+
+    let state = app.data_table_state.as_mut().unwrap();
+    assert_eq!(state.headers(), ["price", "qty", "total"]);
+    state.query("select total where total > 5".to_string());
+    assert!(state.error.is_none());
+    assert_eq!(state.num_rows, 2);
+
+    let template = app
+        .create_template_from_current_state(
+            "orders".to_string(),
+            None,
+            datui::template::MatchCriteria {
+                exact_path: None,
+                relative_path: None,
+                path_pattern: None,
+                filename_pattern: Some("orders.csv".to_string()),
+                schema_columns: None,
+                schema_types: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(template.settings.computed_columns.len(), 1);
+    assert_eq!(
+        template.settings.computed_columns[0].expression,
+        "price * qty"
+    );
+
+    // On a computed column `+` edits its definition; an unknown column is reported in the
+    // preview and the edit isn't applied
+    app.event(&key(KeyCode::Char('+')));
+    assert_eq!(app.computed_column_modal.name_input.value(), "total");
+    type_text(&mut app, " + missing");
+    assert!(app.computed_column_modal.preview.clone().unwrap().is_err());
+    app.event(&key(KeyCode::Enter));
+    assert!(app.computed_column_modal.active);
+    app.event(&key(KeyCode::Esc));
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.computed_columns()[0].expression, "price * qty");
+}
+
+/// `g` groups the view: the aggregated result replaces the table and `R` brings back the raw
+/// data.
+#[test]
+fn test_group_by_modal_replaces_view_with_aggregates() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sales.csv");
+    std::fs::write(&path, "region,sales\nb,1\na,2\nb,3\na,4\nb,5\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('g')));
+    assert!(app.group_by_modal.active);
+    // Group by region, aggregate sales with sum (count is on by default)
+    app.event(&key(KeyCode::Char(' ')));
+    app.event(&key(KeyCode::Tab));
+    app.event(&key(KeyCode::Char(' ')));
+    app.event(&key(KeyCode::Tab));
+    app.event(&key(KeyCode::Char(' ')));
+    app.event(&key(KeyCode::Enter));
+    assert!(!app.group_by_modal.active);
+    drain_events(&mut app, &rx);
+
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.headers(), ["region", "sales_sum", "sales_count"]);
+    assert_eq!(state.num_rows, 2);
+    assert_eq!(state.locked_columns_count(), 1);
+    let df = state.lf.clone().collect().unwrap();
+    let sums: Vec<i64> = df
+        .column("sales_sum")
+        .unwrap()
+        .i64()
+        .unwrap()
+        .into_no_null_iter()
+        .collect();
+    assert_eq!(sums, [6, 9]);
+
+    let reset = app.event(&key(KeyCode::Char('R'))).unwrap();
+    app.event(&reset);
+    drain_events(&mut app, &rx);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.headers(), ["region", "sales"]);
+    assert_eq!(state.num_rows, 5);
+}
+
+/// `W` resamples the view into hourly windows; empty windows can be kept and `R` brings back
+/// the raw data.
+#[test]
+fn test_resample_modal_replaces_view_with_windows() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("readings.csv");
+    std::fs::write(
+        &path,
+        "ts,value,station\n\
+         2024-01-01T00:00:00,1.0,a\n\
+         2024-01-01T00:30:00,3.0,b\n\
+         2024-01-01T01:15:00,5.0,a\n\
+         2024-01-01T03:45:00,7.0,b\n",
+    )
+    .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('W')));
+    assert!(app.resample_modal.active);
+    // Defaults: ts, 1h, mean of value; keep empty windows as nulls
+    app.event(&key(KeyCode::Tab));
+    app.event(&key(KeyCode::Tab));
+    app.event(&key(KeyCode::Right));
+    app.event(&key(KeyCode::Enter));
+    assert!(!app.resample_modal.active);
+    drain_events(&mut app, &rx);
+
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.headers(), ["ts", "value"]);
+    assert_eq!(state.locked_columns_count(), 1);
+    let df = state.lf.clone().collect().unwrap();
+    let values: Vec<Option<f64>> = df
+        .column("value")
+        .unwrap()
+        .f64()
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(values, [Some(2.0), Some(5.0), None, Some(7.0)]);
+
+    let reset = app.event(&key(KeyCode::Char('R'))).unwrap();
+    app.event(&reset);
+    drain_events(&mut app, &rx);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.headers(), ["ts", "value", "station"]);
+    assert_eq!(state.num_rows, 4);
+}
+
+#[test]
+fn test_rolling_modal_adds_rolling_column() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("prices.csv");
+    std::fs::write(&path, "price,label\n1.0,a\n3.0,b\n5.0,c\n7.0,d\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('L')));
+    assert!(app.rolling_modal.active);
+    // The leftmost column starts checked; sum over the last 2 rows
+    app.event(&key(KeyCode::Right));
+    app.event(&key(KeyCode::Tab));
+    app.event(&key(KeyCode::Tab));
+    app.event(&key(KeyCode::Backspace));
+    app.event(&key(KeyCode::Char('2')));
+    let preview = app.rolling_modal.preview.clone().unwrap().unwrap();
+    assert_eq!(preview[0].0, "price_rolling_sum_2");
+    assert_eq!(preview[0].1.values, ["1.0", "4.0", "8.0", "12.0"]);
+    app.event(&key(KeyCode::Enter));
+    assert!(!app.rolling_modal.active);
+    drain_events(&mut app, &rx);
+
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.headers(), ["price", "label", "price_rolling_sum_2"]);
+    assert_eq!(
+        state.computed_columns()[0].expression,
+        "col(\"price\").rolling_sum(2)"
+    );
+    let df = state.lf.clone().collect().unwrap();
+    let values: Vec<Option<f64>> = df
+        .column("price_rolling_sum_2")
+        .unwrap()
+        .f64()
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(values, [Some(1.0), Some(4.0), Some(8.0), Some(12.0)]);
+}
+
+#[test]
+fn test_time_zone_modal_sets_and_converts_zone() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("events.csv");
+    std::fs::write(
+        &path,
+        "ts,value\n2024-01-01T12:00:00,1\n2024-07-01T12:00:00,2\n",
+    )
+    .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let zone = |app: &App| match app.data_table_state.as_ref().unwrap().schema.get("ts") {
+        Some(DataType::Datetime(_, tz)) => tz.as_ref().map(|tz| tz.to_string()),
+        other => panic!("ts is {:?}", other),
+    };
+    assert_eq!(zone(&app), None);
+
+    // Naive: set the zone to UTC, keeping the clock times
+    app.event(&key(KeyCode::Char('Z')));
+    assert!(app.time_zone_modal.active);
+    app.event(&key(KeyCode::Right));
+    app.event(&key(KeyCode::Enter));
+    assert!(!app.time_zone_modal.active);
+    drain_events(&mut app, &rx);
+    assert_eq!(zone(&app).as_deref(), Some("UTC"));
+
+    // Aware: convert to Tokyo time
+    app.event(&key(KeyCode::Char('Z')));
+    app.event(&key(KeyCode::Tab));
+    for c in "Asia/Tokyo".chars() {
+        app.event(&key(KeyCode::Char(c)));
+    }
+    app.event(&key(KeyCode::Enter));
+    drain_events(&mut app, &rx);
+    assert_eq!(zone(&app).as_deref(), Some("Asia/Tokyo"));
+    let state = app.data_table_state.as_ref().unwrap();
+    let hours: Vec<Option<i8>> = state
+        .lf
+        .clone()
+        .select([col("ts").dt().hour()])
+        .collect()
+        .unwrap()
+        .column("ts")
+        .unwrap()
+        .i8()
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(hours, [Some(21), Some(21)]);
+    assert_eq!(state.headers(), ["ts", "value"]);
+
+    // Only datetime columns have a zone
+    app.event(&key(KeyCode::Right));
+    app.event(&key(KeyCode::Char('Z')));
+    assert!(!app.time_zone_modal.active);
+}
+
+/// `~` casts the leftmost column: a strict cast meeting a bad value is refused, null on error
+/// nulls it, and templates save the cast.
+#[test]
+fn test_cast_modal_casts_column_and_saves_it_in_templates() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("visits.csv");
+    std::fs::write(
+        &path,
+        "day,count
+03/01/2024,1
+unknown,2
+15/02/2024,3
+",
+    )
+    .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let dtype = |app: &App| {
+        let state = app.data_table_state.as_ref().unwrap();
+        state.schema.get("day").cloned().unwrap()
+    };
+    app.event(&key(KeyCode::Char('~')));
+    assert!(app.cast_modal.active);
+    while datui::column_cast::CAST_TYPES[app.cast_modal.target] != "date" {
+        app.event(&key(KeyCode::Right));
+    }
+    app.event(&key(KeyCode::Tab));
+    for c in "%d/%m/%Y".chars() {
+        app.event(&key(KeyCode::Char(c)));
+    }
+    let preview = app.cast_modal.preview.clone().unwrap().unwrap();
+    assert_eq!(preview.values[0].1, "2024-01-03");
+    assert_eq!(preview.failed, 1);
+
+    // Strict: refused with an error, the modal stays open and the column keeps its type
+    app.event(&key(KeyCode::Enter));
+    app.event(&key(KeyCode::Esc));
+    assert!(app.cast_modal.active);
+    assert_eq!(dtype(&app), DataType::String);
+
+    // Null on error
+    app.event(&key(KeyCode::Tab));
+    app.event(&key(KeyCode::Right));
+    app.event(&key(KeyCode::Enter));
+    assert!(!app.cast_modal.active);
+    drain_events(&mut app, &rx);
+    assert_eq!(dtype(&app), DataType::Date);
+    let state = app.data_table_state.as_ref().unwrap();
+    let nulls = state
+        .lf
+        .clone()
+        .select([col("day").null_count()])
+        .collect()
+        .unwrap();
+    assert_eq!(
+        nulls.column("day").unwrap().get(0).unwrap(),
+        AnyValue::UInt32(1)
+    );
+
+    let template = app
+        .create_template_from_current_state(
+            "visits".to_string(),
+            None,
+            datui::template::MatchCriteria {
+                exact_path: None,
+                relative_path: None,
+                path_pattern: None,
+                filename_pattern: Some("visits.csv".to_string()),
+                schema_columns: None,
+                schema_types: None,
+            },
+        )
+        .unwrap();
+    let cast = &template.settings.column_casts[0];
+    assert_eq!(cast.to, "date");
+    assert_eq!(cast.format.as_deref(), Some("%d/%m/%Y"));
+    assert_eq!(cast.strategy, datui::column_cast::CastStrategy::NullOnError);
+}
+
+/// `#` lists the columns: renames, a duplicate, a hidden column and a new order apply together,
+/// and templates save them.
+#[test]
+fn test_columns_modal_renames_hides_duplicates_and_reorders() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("orders.csv");
+    std::fs::write(&path, "id,price,qty\n1,2.5,4\n2,1.0,3\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('#')));
+    assert!(app.columns_modal.is_some());
+
+    // Rename price, duplicate it, hide qty and move it first
+    app.event(&key(KeyCode::Down));
+    app.event(&key(KeyCode::Char('r')));
+    for _ in 0.."price".len() {
+        app.event(&key(KeyCode::Backspace));
+    }
+    for c in "unit_price".chars() {
+        app.event(&key(KeyCode::Char(c)));
+    }
+    app.event(&key(KeyCode::Enter));
+    app.event(&key(KeyCode::Char('d')));
+    app.event(&key(KeyCode::Down));
+    app.event(&key(KeyCode::Char(' ')));
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Up,
+        KeyModifiers::SHIFT,
+    )));
+    app.event(&key(KeyCode::Char('K')));
+    app.event(&key(KeyCode::Char('K')));
+
+    // Regular columns can't be removed, only hidden
+    app.event(&key(KeyCode::Char('x')));
+    app.event(&key(KeyCode::Esc));
+    assert!(app.columns_modal.is_some());
+
+    app.event(&key(KeyCode::Enter));
+    assert!(app.columns_modal.is_none());
+    drain_events(&mut app, &rx);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.headers(), ["id", "unit_price", "unit_price_copy"]);
+    let copies = state
+        .lf
+        .clone()
+        .select([col("unit_price_copy").sum()])
+        .collect()
+        .unwrap();
+    assert_eq!(
+        copies.column("unit_price_copy").unwrap().get(0).unwrap(),
+        AnyValue::Float64(3.5)
+    );
+
+    let template = app
+        .create_template_from_current_state(
+            "orders".to_string(),
+            None,
+            datui::template::MatchCriteria {
+                exact_path: None,
+                relative_path: None,
+                path_pattern: None,
+                filename_pattern: Some("orders.csv".to_string()),
+                schema_columns: None,
+                schema_types: None,
+            },
+        )
+        .unwrap();
+    let settings = &template.settings;
+    assert_eq!(settings.column_renames[0].from, "price");
+    assert_eq!(settings.column_renames[0].to, "unit_price");
+    assert_eq!(settings.computed_columns[0].name, "unit_price_copy");
+    assert_eq!(
+        settings.column_order,
+        ["id", "unit_price", "unit_price_copy"]
+    );
+}
+
+/// `|` jumps to a column by fuzzy name (Tab completes), number or letters; `zr` cycles the ruler.
+#[test]
+fn test_go_to_column_and_column_ruler() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wide.csv");
+    std::fs::write(
+        &path,
+        "id,unit_price,price,quantity,region\n1,2.5,2.5,4,north\n",
+    )
+    .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let type_text = |app: &mut App, text: &str| {
+        for c in text.chars() {
+            app.event(&key(KeyCode::Char(c)));
+        }
+    };
+    let selected = |app: &App| {
+        let state = app.data_table_state.as_ref().unwrap();
+        state.selected_column().map(str::to_string)
+    };
+
+    app.event(&key(KeyCode::Char('|')));
+    type_text(&mut app, "pri");
+    assert_eq!(app.go_to_column_matches(), ["price", "unit_price"]);
+    type_text(&mut app, "x");
+    assert!(app.go_to_column_matches().is_empty());
+    app.event(&key(KeyCode::Backspace));
+    app.event(&key(KeyCode::Tab));
+    app.event(&key(KeyCode::Enter));
+    drain_events(&mut app, &rx);
+    assert_eq!(selected(&app).as_deref(), Some("price"));
+
+    app.event(&key(KeyCode::Char('|')));
+    type_text(&mut app, "qty");
+    app.event(&key(KeyCode::Enter));
+    drain_events(&mut app, &rx);
+    assert_eq!(selected(&app).as_deref(), Some("quantity"));
+
+    app.event(&key(KeyCode::Char('|')));
+    type_text(&mut app, "2");
+    app.event(&key(KeyCode::Enter));
+    drain_events(&mut app, &rx);
+    assert_eq!(selected(&app).as_deref(), Some("unit_price"));
+
+    app.event(&key(KeyCode::Char('|')));
+    type_text(&mut app, "E");
+    app.event(&key(KeyCode::Enter));
+    drain_events(&mut app, &rx);
+    assert_eq!(selected(&app).as_deref(), Some("region"));
+
+    use datui::column_ruler::ColumnRuler;
+    assert_eq!(app.column_ruler, ColumnRuler::Off);
+    app.event(&key(KeyCode::Char('z')));
+    app.event(&key(KeyCode::Char('r')));
+    assert_eq!(app.column_ruler, ColumnRuler::Index);
+    let area = Rect::new(0, 0, 80, 24);
+    let mut buf = Buffer::empty(area);
+    Widget::render(&mut app, area, &mut buf);
+}
+
+/// Ctrl+P lists columns and actions: a column scrolls the table to it, an action runs.
+#[test]
+fn test_command_palette_jumps_to_columns_and_runs_actions() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("wide.csv");
+    std::fs::write(&path, "id,unit_price,quantity\n1,2.5,4\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let ctrl_p = AppEvent::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+    app.event(&ctrl_p);
+    assert!(app.command_palette.is_some());
+    for c in "qua".chars() {
+        app.event(&key(KeyCode::Char(c)));
+    }
+    app.event(&key(KeyCode::Enter));
+    assert!(app.command_palette.is_none());
+    drain_events(&mut app, &rx);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.selected_column(), Some("quantity"));
+
+    app.event(&ctrl_p);
+    for c in "columns".chars() {
+        app.event(&key(KeyCode::Char(c)));
+    }
+    let area = Rect::new(0, 0, 80, 24);
+    let mut buf = Buffer::empty(area);
+    Widget::render(&mut app, area, &mut buf);
+    app.event(&key(KeyCode::Enter));
+    drain_events(&mut app, &rx);
+    assert!(app.command_palette.is_none());
+    assert!(app.columns_modal.is_some(), "runs the action");
+}
+
+/// Enter on the filter's value field lists the column's most frequent values; ticking two with
+/// Space and pressing Enter makes an `in` filter of them.
+#[test]
+fn test_filter_value_picker_builds_in_filter() {
+    use datui::filter_modal::{FilterFocus, FilterOperator};
+    use datui::sort_filter_modal::SortFilterFocus;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sales.csv");
+    std::fs::write(
+        &path,
+        "region,amount\nEast,1\nEast,2\nEast,3\nWest,4\nWest,5\nNorth,6\n",
+    )
+    .unwrap();
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('s')));
+    app.sort_filter_modal.switch_tab();
+    app.sort_filter_modal.focus = SortFilterFocus::Body;
+    let filter = &mut app.sort_filter_modal.filter;
+    filter.available_columns = vec!["region".to_string(), "amount".to_string()];
+    filter.focus = FilterFocus::Value;
+    app.event(&key(KeyCode::Enter));
+    drain_events(&mut app, &rx);
+    let picker = app.sort_filter_modal.filter.value_picker.as_ref().unwrap();
+    let values: Vec<&str> = picker
+        .values
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|(v, _)| v.as_str())
+        .collect();
+    assert_eq!(values, ["East", "West", "North"]);
+
+    let area = Rect::new(0, 0, 100, 30);
+    let mut buf = Buffer::empty(area);
+    Widget::render(&mut app, area, &mut buf);
+    for code in [
+        KeyCode::Char(' '),
+        KeyCode::Down,
+        KeyCode::Char(' '),
+        KeyCode::Enter,
+    ] {
+        app.event(&key(code));
+    }
+    let filter = &mut app.sort_filter_modal.filter;
+    assert!(filter.value_picker.is_none());
+    assert_eq!(filter.new_value, "East, West");
+    assert_eq!(
+        FilterOperator::iterator().nth(filter.new_operator_idx),
+        Some(FilterOperator::In)
+    );
+    filter.add_statement();
+    let statements = filter.statements.clone();
+    app.event(&AppEvent::Filter(statements));
+    drain_events(&mut app, &rx);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.lf.clone().collect().unwrap().height(), 5);
+}
+
+/// Enter on the value field of a datetime column opens the date range, filled in with the
+/// column's first and last values; narrowing the end makes a `between` filter.
+#[test]
+fn test_filter_date_range_makes_between_filter() {
+    use datui::filter_modal::{FilterFocus, FilterOperator};
+    use datui::sort_filter_modal::SortFilterFocus;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("events.parquet");
+    let mut df = df!(
+        "at" => ["2024-01-05 08:00:00", "2024-02-10 12:30:00", "2024-03-15 23:00:00"],
+        "n" => [1, 2, 3]
+    )
+    .unwrap()
+    .lazy()
+    .with_column(col("at").str().to_datetime(
+        Some(TimeUnit::Microseconds),
+        None,
+        StrptimeOptions::default(),
+        lit("raise"),
+    ))
+    .collect()
+    .unwrap();
+    ParquetWriter::new(File::create(&path).unwrap())
+        .finish(&mut df)
+        .unwrap();
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('s')));
+    app.sort_filter_modal.switch_tab();
+    app.sort_filter_modal.focus = SortFilterFocus::Body;
+    let filter = &mut app.sort_filter_modal.filter;
+    filter.available_columns = vec!["at".to_string(), "n".to_string()];
+    filter.focus = FilterFocus::Value;
+    app.event(&key(KeyCode::Enter));
+    drain_events(&mut app, &rx);
+    let range = app.sort_filter_modal.filter.date_range.as_ref().unwrap();
+    assert!(range.start.starts_with("2024-01-05 08:00:00"));
+    assert!(range.end.starts_with("2024-03-15 23:00:00"));
+
+    let area = Rect::new(0, 0, 100, 30);
+    let mut buf = Buffer::empty(area);
+    Widget::render(&mut app, area, &mut buf);
+    app.event(&key(KeyCode::Tab));
+    let end_len = app
+        .sort_filter_modal
+        .filter
+        .date_range
+        .as_ref()
+        .unwrap()
+        .end
+        .len();
+    for _ in 0..end_len {
+        app.event(&key(KeyCode::Backspace));
+    }
+    for c in "2024-03-01".chars() {
+        app.event(&key(KeyCode::Char(c)));
+    }
+    app.event(&key(KeyCode::Enter));
+    let filter = &mut app.sort_filter_modal.filter;
+    assert!(filter.date_range.is_none());
+    assert_eq!(
+        FilterOperator::iterator().nth(filter.new_operator_idx),
+        Some(FilterOperator::Between)
+    );
+    filter.add_statement();
+    let statements = filter.statements.clone();
+    app.event(&AppEvent::Filter(statements));
+    drain_events(&mut app, &rx);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert!(state.error.is_none());
+    assert_eq!(state.lf.clone().collect().unwrap().height(), 2);
+}
+
+/// The Saved Queries menu (`b`) saves the view's query under a name and applies it again.
+#[test]
+fn test_saved_queries_save_and_apply() {
+    use datui::config::ConfigManager;
+    use datui::saved_queries::SavedQueries;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sales.csv");
+    std::fs::write(&path, "name,price\na,5\nb,20\nc,15\n").unwrap();
+    let config = ConfigManager::with_dir(dir.path().join("config"));
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+    app.event(&AppEvent::Search("select where price > 10".to_string()));
+    drain_events(&mut app, &rx);
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('b')));
+    let modal = app.saved_queries_modal.as_mut().unwrap();
+    assert_eq!(
+        modal.current.as_ref().unwrap().query,
+        "select where price > 10"
+    );
+    modal.saved = SavedQueries::load(&config).unwrap();
+    app.event(&key(KeyCode::Char('s')));
+    for c in "pricey".chars() {
+        app.event(&key(KeyCode::Char(c)));
+    }
+    app.event(&key(KeyCode::Enter));
+    let saved = SavedQueries::load(&config).unwrap();
+    assert_eq!(saved.queries.len(), 1);
+    assert_eq!(saved.queries[0].name, "pricey");
+    let area = Rect::new(0, 0, 100, 24);
+    let mut buf = Buffer::empty(area);
+    Widget::render(&mut app, area, &mut buf);
+    app.event(&key(KeyCode::Esc));
+    assert!(app.saved_queries_modal.is_none());
+
+    app.event(&AppEvent::Search(String::new()));
+    drain_events(&mut app, &rx);
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 3);
+    app.event(&key(KeyCode::Char('b')));
+    let modal = app.saved_queries_modal.as_mut().unwrap();
+    modal.saved = saved;
+    modal.move_by(0);
+    app.event(&key(KeyCode::Enter));
+    assert!(app.saved_queries_modal.is_none());
+    drain_events(&mut app, &rx);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.get_active_query(), "select where price > 10");
+    assert_eq!(state.num_rows, 2);
+}
+
+/// A script runs from the Scripts menu (`$`, then `o` for a path) or with --script once the
+/// data is loaded, and the view shows the frame it returns.
+#[test]
+fn test_scripts_transform_the_data() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sales.csv");
+    std::fs::write(&path, "name,price,qty\na,5,1\nb,20,2\nc,15,3\n").unwrap();
+    let script = dir.path().join("totals.rhai");
+    std::fs::write(
+        &script,
+        r#"df.filter("price > 10").with_column("total", "price * qty").sort("total", true)"#,
+    )
+    .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    app.pending_script = Some(script.clone());
+    pump_open_until_loaded(&mut app, &rx, vec![path.clone()], OpenOptions::default());
+    drain_events(&mut app, &rx);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.get_column_order(), ["name", "price", "qty", "total"]);
+    assert_eq!(state.num_rows, 2);
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('$')));
+    assert!(app.scripts_modal.is_some());
+    app.event(&key(KeyCode::Char('o')));
+    for c in script.to_string_lossy().chars() {
+        app.event(&key(KeyCode::Char(c)));
+    }
+    let area = Rect::new(0, 0, 80, 24);
+    let mut buf = Buffer::empty(area);
+    Widget::render(&mut app, area, &mut buf);
+    app.event(&key(KeyCode::Enter));
+    assert!(app.is_busy(), "the script runs in the background");
+    drain_events(&mut app, &rx);
+    assert!(app.scripts_modal.is_none());
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.get_column_order(), ["name", "price", "qty", "total"]);
+    let df = state.lf.clone().collect().unwrap();
+    let totals: Vec<i64> = df
+        .column("total")
+        .unwrap()
+        .i64()
+        .unwrap()
+        .into_no_null_iter()
+        .collect();
+    assert_eq!(totals, [45, 40]);
+}
+
+/// `--safe-mode` lists scripts from an empty scratch config directory, not the user's, and
+/// deletes it on exit.
+#[test]
+fn test_safe_mode_uses_a_scratch_config_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sales.csv");
+    std::fs::write(&path, "name,price\na,5\n").unwrap();
+
+    let config = datui::config::AppConfig::safe_mode();
+    let theme = datui::config::Theme::from_config(&config.theme).unwrap();
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new_safe_mode(tx, common::test_runtime(), theme, config);
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Char('$'),
+        KeyModifiers::NONE,
+    )));
+    let scripts_dir = app.scripts_modal.as_ref().unwrap().dir.clone();
+    if let Ok(config) = datui::config::ConfigManager::new("datui") {
+        assert!(
+            !scripts_dir.starts_with(config.config_dir()),
+            "{scripts_dir:?}"
+        );
+    }
+    let scratch = scripts_dir
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    assert!(scratch.is_dir());
+    drop(app);
+    assert!(
+        !scratch.exists(),
+        "the scratch directory is deleted on exit"
+    );
+}
+
+#[test]
+fn test_initial_view_opens_with_query_filters_sort_and_columns() {
+    use datui::initial_view::{parse_filter, InitialView};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sales.csv");
+    std::fs::write(&path, "name,price,qty\na,5,1\nb,20,2\nc,15,3\nd,30,\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    app.pending_view = Some(InitialView {
+        query: Some("select name, price, qty".into()),
+        filters: vec![
+            parse_filter("price > 10").unwrap(),
+            parse_filter("qty not null").unwrap(),
+        ],
+        sort: vec!["price".into()],
+        descending: true,
+        columns: vec!["qty".into()],
+        ..Default::default()
+    });
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+    drain_events(&mut app, &rx);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.get_column_order(), ["qty", "name", "price"]);
+    let names: Vec<String> = state
+        .lf
+        .clone()
+        .collect()
+        .unwrap()
+        .column("name")
+        .unwrap()
+        .str()
+        .unwrap()
+        .into_no_null_iter()
+        .map(String::from)
+        .collect();
+    assert_eq!(names, ["b", "c"]);
+}
+
+#[test]
+fn test_headless_exports_the_view() {
+    use datui::initial_view::{parse_filter, InitialView};
+    use datui::{AppConfig, RunInput};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sales.csv");
+    std::fs::write(&path, "name,price,qty\na,5,1\nb,20,2\nc,15,3\n").unwrap();
+    let out = dir.path().join("big.parquet");
+
+    let opts = OpenOptions {
+        initial_view: Some(InitialView {
+            query: Some("select name, total: price * qty".into()),
+            filters: vec![parse_filter("total > 10").unwrap()],
+            sort: vec!["total".into()],
+            descending: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    datui::run_headless(
+        RunInput::Paths(vec![path.clone()], opts),
+        Some(AppConfig::default()),
+        &out,
+    )
+    .unwrap();
+    let df = LazyFrame::scan_parquet(PlPath::Local(out.as_path().into()), Default::default())
+        .unwrap()
+        .collect()
+        .unwrap();
+    let names: Vec<&str> = df
+        .column("name")
+        .unwrap()
+        .str()
+        .unwrap()
+        .into_no_null_iter()
+        .collect();
+    assert_eq!(names, ["c", "b"]);
+
+    // A query that doesn't run is an error, and nothing is written
+    let opts = OpenOptions {
+        initial_view: Some(InitialView {
+            query: Some("select nope".into()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let out = dir.path().join("none.csv.gz");
+    assert!(datui::run_headless(
+        RunInput::Paths(vec![path], opts),
+        Some(AppConfig::default()),
+        &out
+    )
+    .is_err());
+    assert!(!out.exists());
+}
+
+#[test]
+fn test_render_draws_the_table_and_a_chart() {
+    use datui::{AppConfig, RunInput};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("prices.csv");
+    let body: String = (0..40).map(|i| format!("{i},{}\n", i * 3)).collect();
+    std::fs::write(&path, format!("day,price\n{body}")).unwrap();
+
+    let out = dir.path().join("table.txt");
+    datui::run_render(
+        RunInput::Paths(vec![path.clone()], OpenOptions::default()),
+        Some(AppConfig::default()),
+        &out,
+        (60, 10),
+        &[],
+    )
+    .unwrap();
+    let text = std::fs::read_to_string(&out).unwrap();
+    assert!(text.contains("day"), "{text}");
+    assert!(text.contains("price"));
+    // The first rows fit, the 40th doesn't
+    assert!(text.lines().any(|l| l.contains("27")));
+    assert!(!text.contains("117"));
+    assert!(text.lines().all(|l| l.chars().count() <= 60));
+
+    let out = dir.path().join("chart.svg");
+    datui::run_render(
+        RunInput::Paths(vec![path.clone()], OpenOptions::default()),
+        Some(AppConfig::default()),
+        &out,
+        (60, 20),
+        &["day".to_string(), "price".to_string()],
+    )
+    .unwrap();
+    assert!(std::fs::read_to_string(&out).unwrap().starts_with("<svg"));
+
+    let out = dir.path().join("chart.png");
+    assert!(datui::run_render(
+        RunInput::Paths(vec![path], OpenOptions::default()),
+        Some(AppConfig::default()),
+        &out,
+        (60, 20),
+        &["day".to_string(), "nope".to_string()],
+    )
+    .is_err());
+    assert!(!out.exists());
+}
+
+#[test]
+fn test_sampling_modal_sets_strategy_shown_in_control_bar() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("regions.csv");
+    let body: String = (0..50).map(|i| format!("{i},r{}\n", i % 3)).collect();
+    std::fs::write(&path, format!("id,region\n{body}")).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('M')));
+    assert!(app.sampling_modal.active);
+    // Method: Random -> Head -> Stratified, then the column (id -> region), size 10 and seed 7
+    app.event(&key(KeyCode::Right));
+    app.event(&key(KeyCode::Right));
+    app.event(&key(KeyCode::Tab));
+    app.event(&key(KeyCode::Right));
+    app.event(&key(KeyCode::Tab));
+    app.event(&key(KeyCode::Char('1')));
+    app.event(&key(KeyCode::Char('0')));
+    app.event(&key(KeyCode::Tab));
+    app.event(&key(KeyCode::Char('7')));
+    app.event(&key(KeyCode::Enter));
+    assert!(!app.sampling_modal.active);
+
+    let area = Rect::new(0, 0, 120, 24);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(
+        screen.contains("[sample: stratified by region, seed 7]"),
+        "sampling strategy in the control bar"
+    );
+
+    // Reopening shows the applied settings; an invalid seed is rejected
+    app.event(&key(KeyCode::Char('M')));
+    assert_eq!(
+        app.sampling_modal.columns[app.sampling_modal.column],
+        "region"
+    );
+    assert_eq!(app.sampling_modal.seed_input.value(), "7");
+    app.event(&key(KeyCode::Esc));
+    assert!(!app.sampling_modal.active);
+}
+
+/// The export scope picks the page or the whole dataset regardless of the view's query.
+#[test]
+fn test_export_scope_chooses_page_or_entire_dataset() {
+    use datui::export_modal::{ExportFocus, ExportScope};
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("rows.csv");
+    let body: String = (0..30).map(|i| format!("{i}\n")).collect();
+    std::fs::write(&path, format!("n\n{body}")).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+    let state = app.data_table_state.as_mut().unwrap();
+    state.query("select where n > 19".to_string());
+    state.visible_rows = 4;
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('e')));
+    assert_eq!(app.export_modal.scope, ExportScope::View);
+    assert_eq!(app.export_modal.row_range, None);
+    app.event(&key(KeyCode::BackTab));
+    assert_eq!(app.export_modal.focus, ExportFocus::ScopeSelector);
+    app.event(&key(KeyCode::Down));
+    assert_eq!(app.export_modal.scope, ExportScope::Page);
+    assert_eq!(app.export_modal.row_range, Some((0, 4)));
+    assert_eq!(app.export_modal.title(), "Export Data (rows 1-4)");
+    // No rows are selected, so the selection is skipped
+    app.event(&key(KeyCode::Down));
+    assert_eq!(app.export_modal.scope, ExportScope::Dataset);
+    assert_eq!(app.export_modal.title(), "Export Data (entire dataset)");
+
+    let out = dir.path().join("all.csv");
+    let options = datui::ExportOptions {
+        csv_delimiter: b',',
+        csv_include_header: true,
+        csv_compression: None,
+        json_compression: None,
+        ndjson_compression: None,
+        parquet_compression: None,
+        parquet_partition_by: Vec::new(),
+        row_range: app.export_modal.row_range,
+        scope: app.export_modal.scope,
+        xlsx_sheet_name: String::new(),
+        xlsx_autofit: false,
+        sqlite_table: String::new(),
+        sqlite_if_exists: Default::default(),
+    };
+    let mut next = Some(AppEvent::Export(
+        out.clone(),
+        datui::export_modal::ExportFormat::Csv,
+        options,
+    ));
+    while let Some(ev) = next.take() {
+        next = app.event(&ev);
+        if next.is_none() && app.is_busy() {
+            next = rx.recv_timeout(std::time::Duration::from_secs(10)).ok();
+        }
+    }
+    let written = std::fs::read_to_string(&out).unwrap();
+    assert_eq!(written.lines().count(), 31);
+}
+
+/// Choosing partition columns for Parquet writes a hive directory that `--hive` loads back.
+#[test]
+fn test_export_hive_partitioned_parquet_round_trips() {
+    use datui::export_modal::{ExportFocus, ExportFormat};
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sales.csv");
+    std::fs::write(&path, "region,sales\nwest,1\neast,2\nwest,3\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('e')));
+    app.export_modal.selected_format = ExportFormat::Parquet;
+    let out = dir.path().join("by_region");
+    app.export_modal
+        .path_input
+        .set_value(out.display().to_string());
+    app.event(&key(KeyCode::Tab));
+    assert_eq!(app.export_modal.focus, ExportFocus::ParquetPartitions);
+    app.event(&key(KeyCode::Char(' ')));
+    assert_eq!(app.export_modal.parquet_partition_by, ["region"]);
+    app.event(&key(KeyCode::BackTab));
+    let mut next = app.event(&key(KeyCode::Enter));
+    assert!(matches!(next, Some(AppEvent::Export(..))));
+    while let Some(ev) = next.take() {
+        next = app.event(&ev);
+        if next.is_none() && app.is_busy() {
+            next = rx.recv_timeout(std::time::Duration::from_secs(10)).ok();
+        }
+    }
+    assert!(out.join("region=west").join("part-0.parquet").is_file());
+    assert!(out.join("region=east").join("part-0.parquet").is_file());
+
+    let opts = OpenOptions {
+        hive: true,
+        ..OpenOptions::default()
+    };
+    let (tx, rx) = mpsc::channel();
+    let mut loaded = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut loaded, &rx, vec![out], opts);
+    let state = loaded.data_table_state.as_ref().unwrap();
+    assert_eq!(state.num_rows, 3);
+    assert!(state.schema.contains("region"));
+}
+
+/// `E` in the analysis modal writes the tool's results table, asking before overwriting.
+#[test]
+fn test_analysis_results_export_to_markdown_and_csv() {
+    use datui::export_modal::{ExportFocus, ExportFormat, ExportScope};
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("stats.csv");
+    std::fs::write(&path, "name,score\nann,1\nbob,2\ncy,3\ndee,4\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let pump = |app: &mut App, first: Option<AppEvent>| {
+        let mut next = first;
+        while let Some(ev) = next.take() {
+            next = app.event(&ev);
+            if next.is_none() && app.is_busy() {
+                next = rx.recv_timeout(std::time::Duration::from_secs(10)).ok();
+            }
+        }
+    };
+    app.event(&key(KeyCode::Char('a')));
+    let next = app.event(&key(KeyCode::Enter));
+    pump(&mut app, next);
+    assert!(app.analysis_modal.describe_results.is_some());
+
+    app.event(&key(KeyCode::Char('E')));
+    assert_eq!(app.input_mode, InputMode::Export);
+    assert_eq!(app.export_modal.title(), "Export Describe Results");
+    let area = Rect::new(0, 0, 120, 30);
+    let mut buf = Buffer::empty(area);
+    Widget::render(&mut app, area, &mut buf);
+    let text: String = (0..area.height)
+        .flat_map(|y| (0..area.width).map(move |x| (x, y)))
+        .map(|(x, y)| buf[(x, y)].symbol().to_string())
+        .collect();
+    assert!(text.contains("Export Describe Results"));
+    assert!(text.contains("Markdown"));
+    assert_eq!(app.export_modal.scope, ExportScope::Analysis);
+    assert_eq!(app.export_modal.formats(), ExportFormat::ANALYSIS);
+    assert_eq!(app.export_modal.focus, ExportFocus::PathInput);
+    app.export_modal.focus = ExportFocus::FormatSelector;
+    app.event(&key(KeyCode::Up));
+    assert_eq!(app.export_modal.selected_format, ExportFormat::Markdown);
+    let out = dir.path().join("describe");
+    app.export_modal
+        .path_input
+        .set_value(out.display().to_string());
+    app.export_modal.focus = ExportFocus::ExportButton;
+    let next = app.event(&key(KeyCode::Enter));
+    assert!(matches!(next, Some(AppEvent::Export(..))));
+    pump(&mut app, next);
+    let markdown = std::fs::read_to_string(dir.path().join("describe.md")).unwrap();
+    let lines: Vec<&str> = markdown.lines().collect();
+    assert_eq!(
+        lines[0],
+        "| Column | Type | Count | Nulls | Mean | Std | Min | 25% | 50% | 75% | Max |"
+    );
+    assert!(
+        lines[2].starts_with("| name | str | 4 | 0 |  |  | ann |"),
+        "{markdown}"
+    );
+    assert!(
+        lines[3].starts_with("| score | i64 | 4 | 0 | 2.5 |"),
+        "{markdown}"
+    );
+    assert!(app.analysis_modal.active);
+
+    // Writing over an existing file asks first
+    app.event(&key(KeyCode::Esc));
+    std::fs::write(dir.path().join("describe.csv"), "old\n").unwrap();
+    app.event(&key(KeyCode::Char('E')));
+    assert_eq!(app.input_mode, InputMode::Export);
+    app.export_modal.selected_format = ExportFormat::Csv;
+    app.export_modal
+        .path_input
+        .set_value(out.display().to_string());
+    app.export_modal.focus = ExportFocus::ExportButton;
+    assert!(app.event(&key(KeyCode::Enter)).is_none());
+    let next = app.event(&key(KeyCode::Enter));
+    assert!(matches!(next, Some(AppEvent::Export(..))));
+    pump(&mut app, next);
+    let csv = std::fs::read_to_string(dir.path().join("describe.csv")).unwrap();
+    assert!(csv.starts_with("Column,Type,Count,Nulls,Mean"), "{csv}");
+}
+
+/// `U` prompts for a report path and writes the profiling report in the format of its extension.
+#[test]
+fn test_profile_key_writes_markdown_report() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("people.csv");
+    let body: String = (0..80)
+        .map(|i| format!("{i},user{},eu,{}\n", i % 60, if i < 70 { 1 } else { 500 }))
+        .collect();
+    std::fs::write(&path, format!("id,name,region,spend\n{body}")).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('U')));
+    assert_eq!(app.input_mode, InputMode::Editing);
+    // The prompt starts at people_profile.html in the working directory: put it in `dir`, as .md
+    app.event(&key(KeyCode::Home));
+    for c in format!("{}{}", dir.path().display(), std::path::MAIN_SEPARATOR).chars() {
+        app.event(&key(KeyCode::Char(c)));
+    }
+    app.event(&key(KeyCode::End));
+    for _ in 0..4 {
+        app.event(&key(KeyCode::Backspace));
+    }
+    app.event(&key(KeyCode::Char('m')));
+    app.event(&key(KeyCode::Char('d')));
+    let mut next = app.event(&key(KeyCode::Enter));
+    assert!(app.is_busy());
+    while app.is_busy() {
+        if let Some(ev) = next
+            .take()
+            .or_else(|| rx.recv_timeout(std::time::Duration::from_secs(30)).ok())
+        {
+            next = app.event(&ev);
+        }
+    }
+    assert_eq!(app.input_mode, InputMode::Normal);
+    let report = std::fs::read_to_string(dir.path().join("people_profile.md")).unwrap();
+    assert!(report.starts_with("# Profile: people.csv\n"), "{report}");
+    for line in [
+        "- id has a distinct value in every row (likely a key)",
+        "- name has high cardinality (60 distinct values)",
+        "- region has a single value",
+        "| spend | i64 | 80 | 0 | 0.0% | 2 |",
+        "### spend (i64)",
+    ] {
+        assert!(report.contains(line), "{line} missing from:\n{report}");
+    }
+    assert!(report.contains("- spend is highly skewed"), "{report}");
+}
+
+#[test]
+fn test_export_row_context_writes_selected_row_and_neighbors() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("rows.csv");
+    let body: String = (0..30).map(|i| format!("{i}\n")).collect();
+    std::fs::write(&path, format!("n\n{body}")).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.data_table_state
+        .as_mut()
+        .unwrap()
+        .table_state
+        .select(Some(10));
+    app.event(&key(KeyCode::Char('E')));
+    assert_eq!(app.input_mode, InputMode::Export);
+    assert_eq!(app.export_modal.row_range, Some((5, 11)));
+    assert_eq!(app.export_modal.title(), "Export Data (rows 6-16)");
+
+    let out = dir.path().join("context.csv");
+    let options = datui::ExportOptions {
+        csv_delimiter: b',',
+        csv_include_header: true,
+        csv_compression: None,
+        json_compression: None,
+        ndjson_compression: None,
+        parquet_compression: None,
+        parquet_partition_by: Vec::new(),
+        row_range: app.export_modal.row_range,
+        scope: app.export_modal.scope,
+        xlsx_sheet_name: String::new(),
+        xlsx_autofit: false,
+        sqlite_table: String::new(),
+        sqlite_if_exists: Default::default(),
+    };
+    let mut next = Some(AppEvent::Export(
+        out.clone(),
+        datui::export_modal::ExportFormat::Csv,
+        options,
+    ));
+    while let Some(ev) = next.take() {
+        next = app.event(&ev);
+        if next.is_none() && app.is_busy() {
+            next = rx.recv_timeout(std::time::Duration::from_secs(10)).ok();
+        }
+    }
+    let written = std::fs::read_to_string(&out).unwrap();
+    let lines: Vec<&str> = written.lines().collect();
+    assert_eq!(lines.len(), 12);
+    assert_eq!(lines[0], "n");
+    assert_eq!(lines[1], "5");
+    assert_eq!(lines[11], "15");
+}
+
+#[test]
+fn test_time_series_gaps_finds_gap_and_jumps_to_row() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("daily.parquet");
+    // Days 0..40 with days 25-27 missing
+    let days: Vec<i32> = (0..40).filter(|d| !(25..28).contains(d)).collect();
+    let n = days.len();
+    let mut df = df!(
+        "day" => days,
+        "v" => (0..n as i64).collect::<Vec<_>>(),
+    )
+    .unwrap()
+    .lazy()
+    .with_column(col("day").cast(DataType::Date))
+    .collect()
+    .unwrap();
+    ParquetWriter::new(File::create(&path).unwrap())
+        .finish(&mut df)
+        .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+    // Headless: no render has sized the table yet
+    app.data_table_state.as_mut().unwrap().visible_rows = 10;
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let pump = |app: &mut App, code: KeyCode| {
+        let mut next = app.event(&key(code));
+        while let Some(ev) = next.take() {
+            next = app.event(&ev);
+        }
+        while app.is_busy() {
+            let ev = rx
+                .recv_timeout(std::time::Duration::from_secs(10))
+                .expect("background result");
+            let mut next = app.event(&ev);
+            while let Some(ev) = next.take() {
+                next = app.event(&ev);
+            }
+        }
+    };
+    // Analysis opens with the sidebar focused on the first tool
+    pump(&mut app, KeyCode::Char('a'));
+    for _ in 0..3 {
+        pump(&mut app, KeyCode::Down);
+    }
+    pump(&mut app, KeyCode::Enter);
+
+    let report = app
+        .analysis_modal
+        .gap_results
+        .as_ref()
+        .expect("gaps computed")
+        .as_ref()
+        .expect("no error");
+    assert_eq!(report.time_column, "day");
+    assert!(report.frequency_inferred);
+    assert_eq!(report.gaps.len(), 1);
+    assert_eq!(report.gaps[0].row, 25);
+    assert_eq!(report.gaps[0].missing, 3);
+
+    pump(&mut app, KeyCode::Enter);
+    assert!(!app.analysis_modal.active);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.start_row + state.table_state.selected().unwrap(), 25);
+}
+
+/// Missing Data lists nulls per column; Enter filters to a column's null rows and `D` drops
+/// every row with a null.
+#[test]
+fn test_missing_data_filters_null_rows() {
+    use datui::filter_modal::FilterOperator;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sparse.csv");
+    std::fs::write(&path, "id,a,b\n1,x,\n2,,\n3,z,5\n4,w,6\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let pump = |app: &mut App, code: KeyCode| {
+        let mut next = app.event(&key(code));
+        while let Some(ev) = next.take() {
+            next = app.event(&ev);
+        }
+        while app.is_busy() {
+            let ev = rx
+                .recv_timeout(std::time::Duration::from_secs(10))
+                .expect("background result");
+            let mut next = app.event(&ev);
+            while let Some(ev) = next.take() {
+                next = app.event(&ev);
+            }
+        }
+    };
+    let open_tool = |app: &mut App| {
+        pump(app, KeyCode::Char('a'));
+        pump(app, KeyCode::End);
+        pump(app, KeyCode::Up);
+        pump(app, KeyCode::Up);
+        pump(app, KeyCode::Enter);
+    };
+    open_tool(&mut app);
+
+    let report = app
+        .analysis_modal
+        .missing_results
+        .as_ref()
+        .expect("nulls counted")
+        .as_ref()
+        .expect("no error");
+    let counts: Vec<(&str, usize)> = report
+        .columns
+        .iter()
+        .map(|c| (c.column.as_str(), c.null_count))
+        .collect();
+    assert_eq!(counts, [("b", 2), ("a", 1), ("id", 0)]);
+    assert_eq!(report.rows_with_nulls, 2);
+
+    // Enter on "b": only its null rows remain
+    pump(&mut app, KeyCode::Enter);
+    assert!(!app.analysis_modal.active);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.lf.clone().collect().unwrap().height(), 2);
+    assert_eq!(state.get_filters()[0].operator, FilterOperator::IsNull);
+
+    // Clear that filter, then drop every row with a null
+    app.event(&AppEvent::Filter(vec![]));
+    app.sort_filter_modal.filter.statements.clear();
+    while app.is_busy() {
+        let ev = rx
+            .recv_timeout(std::time::Duration::from_secs(10))
+            .expect("background result");
+        app.event(&ev);
+    }
+    open_tool(&mut app);
+    pump(&mut app, KeyCode::Char('D'));
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(
+        state.get_filters().len(),
+        2,
+        "one filter per column with nulls"
+    );
+    assert_eq!(state.lf.clone().collect().unwrap().height(), 2);
+}
+
+#[test]
+fn test_duplicate_rows_jump_and_filter() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("dupes.csv");
+    std::fs::write(&path, "id,name\n1,a\n2,b\n1,a\n3,b\n4,c\n1,a\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+    // Size the table so jumps can place the cursor
+    let area = Rect::new(0, 0, 80, 20);
+    app.render(area, &mut Buffer::empty(area));
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let pump = |app: &mut App, code: KeyCode| {
+        let mut next = app.event(&key(code));
+        while let Some(ev) = next.take() {
+            next = app.event(&ev);
+        }
+        while app.is_busy() {
+            let ev = rx
+                .recv_timeout(std::time::Duration::from_secs(10))
+                .expect("background result");
+            let mut next = app.event(&ev);
+            while let Some(ev) = next.take() {
+                next = app.event(&ev);
+            }
+        }
+    };
+    pump(&mut app, KeyCode::Char('a'));
+    pump(&mut app, KeyCode::End);
+    pump(&mut app, KeyCode::Up);
+    pump(&mut app, KeyCode::Enter);
+
+    let report = app
+        .analysis_modal
+        .duplicate_results
+        .as_ref()
+        .expect("duplicates found")
+        .as_ref()
+        .expect("no error");
+    assert!(report.full_row);
+    assert_eq!(report.group_count, 1);
+    assert_eq!(report.duplicate_rows, 3);
+
+    // Compare on "name" only: groups a (3 rows) and b (2 rows)
+    pump(&mut app, KeyCode::Char('c'));
+    assert!(app.analysis_modal.duplicate_keys_editing);
+    for c in "name".chars() {
+        pump(&mut app, KeyCode::Char(c));
+    }
+    pump(&mut app, KeyCode::Enter);
+    let report = app
+        .analysis_modal
+        .duplicate_results
+        .as_ref()
+        .unwrap()
+        .as_ref()
+        .unwrap();
+    assert_eq!(app.analysis_modal.duplicate_keys, ["name"]);
+    assert_eq!(report.group_count, 2);
+    assert_eq!(report.groups[1].values, ["b"]);
+
+    // Enter on group "b" jumps to its first row
+    pump(&mut app, KeyCode::Down);
+    pump(&mut app, KeyCode::Enter);
+    assert!(!app.analysis_modal.active);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.start_row + state.table_state.selected().unwrap(), 1);
+
+    // Reopened without marked columns, whole rows are compared again; f keeps the three "1,a"
+    pump(&mut app, KeyCode::Char('a'));
+    pump(&mut app, KeyCode::End);
+    pump(&mut app, KeyCode::Up);
+    pump(&mut app, KeyCode::Enter);
+    pump(&mut app, KeyCode::Char('f'));
+    assert!(!app.analysis_modal.active);
+    let df = app
+        .data_table_state
+        .as_ref()
+        .unwrap()
+        .lf
+        .clone()
+        .collect()
+        .unwrap();
+    assert_eq!(df.height(), 3);
+}
+
+#[test]
+fn test_crosstab_counts_pairs_and_aggregates_values() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("orders.csv");
+    std::fs::write(
+        &path,
+        "region,product,amount\nnorth,a,10\nnorth,b,20\nsouth,a,30\nsouth,a,40\nnorth,a,50\n",
+    )
+    .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let pump = |app: &mut App, code: KeyCode| {
+        let mut next = app.event(&key(code));
+        while let Some(ev) = next.take() {
+            next = app.event(&ev);
+        }
+        while app.is_busy() {
+            let ev = rx
+                .recv_timeout(std::time::Duration::from_secs(10))
+                .expect("background result");
+            let mut next = app.event(&ev);
+            while let Some(ev) = next.take() {
+                next = app.event(&ev);
+            }
+        }
+    };
+    // Without marked columns the first two text columns are crossed
+    pump(&mut app, KeyCode::Char('a'));
+    pump(&mut app, KeyCode::End);
+    pump(&mut app, KeyCode::Enter);
+    let table = app
+        .analysis_modal
+        .crosstab_results
+        .as_ref()
+        .expect("crosstab built")
+        .as_ref()
+        .expect("no error");
+    assert_eq!(table.row_labels, ["north", "south"]);
+    assert_eq!(table.column_labels, ["a", "b"]);
+    assert_eq!(table.cells[0], [Some(2.0), Some(1.0)]);
+    assert_eq!(table.cells[1], [Some(2.0), None]);
+    assert_eq!(table.total, Some(5.0));
+    assert!(table.chi_square.is_some());
+
+    let area = Rect::new(0, 0, 120, 30);
+    let mut buf = Buffer::empty(area);
+    Widget::render(&mut app, area, &mut buf);
+    let text: String = (0..area.height)
+        .map(|y| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(text.contains("Total"), "{text}");
+    assert!(text.contains("Cramér's V"), "{text}");
+
+    // Sum of amount per pair, then the mean
+    pump(&mut app, KeyCode::Char('c'));
+    assert!(app.analysis_modal.crosstab_editing);
+    for c in ", amount".chars() {
+        pump(&mut app, KeyCode::Char(c));
+    }
+    pump(&mut app, KeyCode::Enter);
+    let table = app
+        .analysis_modal
+        .crosstab_results
+        .as_ref()
+        .unwrap()
+        .as_ref()
+        .unwrap();
+    assert_eq!(table.cells[0], [Some(60.0), Some(20.0)]);
+    assert_eq!(table.total, Some(150.0));
+    pump(&mut app, KeyCode::Char('a'));
+    let table = app
+        .analysis_modal
+        .crosstab_results
+        .as_ref()
+        .unwrap()
+        .as_ref()
+        .unwrap();
+    assert_eq!(table.cells[1], [Some(35.0), None]);
+}
+
+#[test]
+fn test_query_tab_completes_names() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("items.csv");
+    std::fs::write(&path, "price,product,qty\n1.5,pen,3\n2.0,ink,1\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let pump = |app: &mut App, code: KeyCode| {
+        let mut next = app.event(&key(code));
+        while let Some(ev) = next.take() {
+            next = app.event(&ev);
+        }
+        while app.is_busy() {
+            let ev = rx
+                .recv_timeout(std::time::Duration::from_secs(10))
+                .expect("background result");
+            let mut next = app.event(&ev);
+            while let Some(ev) = next.take() {
+                next = app.event(&ev);
+            }
+        }
+    };
+    let typed = |app: &App| app.query_completion().is_some();
+    pump(&mut app, KeyCode::Char('/'));
+    for c in "select q".chars() {
+        pump(&mut app, KeyCode::Char(c));
+    }
+    // One candidate is filled in
+    pump(&mut app, KeyCode::Tab);
+    assert!(!typed(&app));
+    for c in ", pr".chars() {
+        pump(&mut app, KeyCode::Char(c));
+    }
+    // Two are listed, then Tab cycles through them
+    pump(&mut app, KeyCode::Tab);
+    let (completion, selected) = app.query_completion().expect("candidates listed");
+    assert_eq!(completion.candidates, ["price", "product"]);
+    assert_eq!(selected, None);
+    pump(&mut app, KeyCode::Tab);
+    pump(&mut app, KeyCode::Tab);
+
+    let area = Rect::new(0, 0, 80, 24);
+    let mut buf = Buffer::empty(area);
+    Widget::render(&mut app, area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("price"), "{screen}");
+    assert!(screen.contains("select qty, product"), "{screen}");
+
+    pump(&mut app, KeyCode::Enter);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.active_query, "select qty, product");
+    assert_eq!(state.schema.len(), 2);
+}
+
+#[test]
+fn test_za_shows_full_value_of_truncated_cell() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("notes.csv");
+    let long = "word ".repeat(40);
+    std::fs::write(&path, format!("id,note\n1,short\n2,{}\n", long.trim())).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+    let area = Rect::new(0, 0, 60, 12);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    assert_eq!(
+        app.data_table_state
+            .as_ref()
+            .unwrap()
+            .truncated_column
+            .as_deref(),
+        Some("note")
+    );
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.data_table_state
+        .as_mut()
+        .unwrap()
+        .table_state
+        .select(Some(1));
+    app.event(&key(KeyCode::Char('z')));
+    app.event(&key(KeyCode::Char('a')));
+    assert_eq!(app.cell_peek(), Some(("note", long.trim())));
+
+    // Any key closes the popup; `z` followed by anything other than `a` does nothing.
+    app.event(&key(KeyCode::Esc));
+    assert!(app.cell_peek().is_none());
+    app.event(&key(KeyCode::Char('z')));
+    app.event(&key(KeyCode::Char('x')));
+    assert!(app.cell_peek().is_none());
+}
+
+#[test]
+fn test_bang_runs_command_on_current_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("people.csv");
+    std::fs::write(&path, "id,name\n1,a\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path.clone()], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    assert!(app.event(&key(KeyCode::Char('!'))).is_none());
+    assert_eq!(app.input_mode, InputMode::Editing);
+    for c in "wc -l {file}".chars() {
+        app.event(&key(KeyCode::Char(c)));
+    }
+    let Some(AppEvent::RunExternalCommand(command)) = app.event(&key(KeyCode::Enter)) else {
+        panic!("Enter should run the command");
+    };
+    assert!(command.starts_with("wc -l "));
+    assert!(command.contains(&*path.to_string_lossy()));
+    assert_eq!(app.input_mode, InputMode::Normal);
+
+    // The prompt comes back with the last command; Esc leaves without running it.
+    app.event(&key(KeyCode::Char('!')));
+    assert!(app.event(&key(KeyCode::Esc)).is_none());
+    assert_eq!(app.input_mode, InputMode::Normal);
+}
+
+#[test]
+fn test_small_file_loads_in_memory_and_profile_can_change() {
+    use datui::load_profile::LoadProfile;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("small.csv");
+    std::fs::write(&path, "id,name\n1,a\n2,b\n3,c\n").unwrap();
+    let options = OpenOptions {
+        auto_load_profile: true,
+        ..Default::default()
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], options);
+    let info = app.load_profile().expect("profile chosen on open");
+    assert_eq!(info.profile, LoadProfile::Small);
+    assert!(info.automatic);
+    let state = app.data_table_state.as_ref().unwrap();
+    assert!(state.is_in_memory());
+    assert_eq!(state.num_rows, 3);
+
+    // `p` in the Info panel reopens the file with the next profile.
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    app.event(&key(KeyCode::Char('i')));
+    assert_eq!(app.input_mode, InputMode::Info);
+    let Some(AppEvent::Open(paths, options)) = app.event(&key(KeyCode::Char('p'))) else {
+        panic!("p should reopen the dataset");
+    };
+    pump_open_until_loaded(&mut app, &rx, paths, options);
+    let info = app.load_profile().unwrap();
+    assert_eq!(info.profile, LoadProfile::Standard);
+    assert!(!info.automatic);
+    assert!(!app.data_table_state.as_ref().unwrap().is_in_memory());
+}
+
+#[test]
+fn test_marked_columns_prefill_chart() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("marked.csv");
+    std::fs::write(&path, "name,a,b,c\nx,1,2,3\ny,4,5,6\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![path], OpenOptions::default());
+
+    let key = |code| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+    let press = |app: &mut App, code| {
+        let mut next = app.event(&key(code));
+        while let Some(ev) = next {
+            next = app.event(&ev);
+        }
+        while app.is_busy() {
+            let ev = rx
+                .recv_timeout(std::time::Duration::from_secs(10))
+                .expect("background result");
+            app.event(&ev);
+        }
+    };
+    // Mark name (not chartable), a and c; b is scrolled past unmarked.
+    press(&mut app, KeyCode::Char('v'));
+    press(&mut app, KeyCode::Char('l'));
+    press(&mut app, KeyCode::Char('v'));
+    press(&mut app, KeyCode::Char('l'));
+    press(&mut app, KeyCode::Char('l'));
+    press(&mut app, KeyCode::Char('v'));
+    let state = app.data_table_state.as_ref().unwrap();
+    assert_eq!(state.selected_columns(), ["name", "a", "c"]);
+
+    press(&mut app, KeyCode::Char('c'));
+    assert_eq!(app.input_mode, InputMode::Chart);
+    assert_eq!(app.chart_modal.x_column.as_deref(), Some("a"));
+    assert_eq!(app.chart_modal.y_columns, ["c"]);
+    assert_eq!(app.chart_modal.hist_column.as_deref(), Some("a"));
+
+    press(&mut app, KeyCode::Esc);
+    press(&mut app, KeyCode::Char('V'));
+    press(&mut app, KeyCode::Char('c'));
+    assert_eq!(app.chart_modal.x_column, None);
+    assert!(app.chart_modal.y_columns.is_empty());
+}
+
+#[test]
+fn test_diagonal_concat_unions_columns_and_reports_sources() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("jan.csv");
+    let b = dir.path().join("feb.jsonl");
+    std::fs::write(&a, "id,x\n1,10\n2,20\n").unwrap();
+    std::fs::write(&b, "{\"id\": 3, \"y\": \"c\"}\n").unwrap();
+    let options = OpenOptions {
+        concat: datui::ConcatMode::Diagonal,
+        ..Default::default()
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![a, b], options);
+    let state = app.data_table_state.as_ref().expect("loaded");
+    let names: Vec<&str> = state.schema.iter_names().map(|n| n.as_str()).collect();
+    assert_eq!(names, vec!["id", "x", "y"]);
+    assert_eq!(state.num_rows, 3);
+
+    // The Info panel lists the files behind each column
+    app.event(&AppEvent::Key(KeyEvent::new(
+        KeyCode::Char('i'),
+        KeyModifiers::NONE,
+    )));
+    let area = Rect::new(0, 0, 160, 30);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let text: String = (0..area.height)
+        .map(|y| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(text.contains("Diagonal concat of 2 files"), "{text}");
+    assert!(text.contains("feb.jsonl"), "{text}");
+}
+
+#[test]
+fn test_source_column_names_files_of_hive_dataset() {
+    let dir = tempfile::tempdir().unwrap();
+    for (sub, n) in [("year=2020", 3i64), ("year=2021", 2)] {
+        let d = dir.path().join(sub);
+        std::fs::create_dir_all(&d).unwrap();
+        let mut df = df!("v" => (0..n).collect::<Vec<i64>>()).unwrap();
+        ParquetWriter::new(File::create(d.join("data.parquet")).unwrap())
+            .finish(&mut df)
+            .unwrap();
+    }
+    let opts = OpenOptions {
+        hive: true,
+        source_column: true,
+        ..OpenOptions::default()
+    };
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    pump_open_until_loaded(&mut app, &rx, vec![dir.path().to_path_buf()], opts);
+
+    let state = app.data_table_state.as_ref().expect("loaded");
+    assert!(state.schema.contains(datui::concat::SOURCE_FILE_COLUMN));
+    let df = state
+        .lf
+        .clone()
+        .group_by([col(datui::concat::SOURCE_FILE_COLUMN)])
+        .agg([len()])
+        .sort([datui::concat::SOURCE_FILE_COLUMN], Default::default())
+        .collect()
+        .unwrap();
+    let files: Vec<String> = df
+        .column(datui::concat::SOURCE_FILE_COLUMN)
+        .unwrap()
+        .str()
+        .unwrap()
+        .into_iter()
+        .map(|s| s.unwrap().to_string())
+        .collect();
+    assert_eq!(files.len(), 2);
+    assert!(files[0].contains("year=2020") && files[0].ends_with("data.parquet"));
+    let counts: Vec<u32> = df
+        .column("len")
+        .unwrap()
+        .u32()
+        .unwrap()
+        .into_no_null_iter()
+        .collect();
+    assert_eq!(counts, vec![3, 2]);
+}
+
+#[test]
+fn test_local_glob_opens_matching_files_and_rejects_mixed_formats() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("2024-01.csv"), "id\n1\n2\n").unwrap();
+    std::fs::write(dir.path().join("2024-02.csv"), "id\n3\n").unwrap();
+    std::fs::write(dir.path().join("2023-12.csv"), "id\n9\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let pattern = dir.path().join("2024-*.csv");
+    pump_open_until_loaded(&mut app, &rx, vec![pattern], OpenOptions::default());
+    assert_eq!(app.data_table_state.as_ref().expect("loaded").num_rows, 3);
+
+    let mut df = df!("id" => [4i64]).unwrap();
+    ParquetWriter::new(File::create(dir.path().join("2024-03.parquet")).unwrap())
+        .finish(&mut df)
+        .unwrap();
+    let paths = vec![dir.path().join("2024-*")];
+    match app.event(&AppEvent::Open(paths, OpenOptions::default())) {
+        Some(AppEvent::Crash(m)) => assert!(m.contains("2 csv, 1 parquet"), "{m}"),
+        _ => panic!("expected Crash for a pattern matching mixed formats"),
+    }
+}
+
+#[test]
+fn test_hive_datasets_of_csv_ndjson_and_ipc_files() {
+    let write_ipc = |path: &std::path::Path, n: i64| {
+        let mut df = df!("v" => (0..n).collect::<Vec<i64>>()).unwrap();
+        IpcWriter::new(File::create(path).unwrap())
+            .finish(&mut df)
+            .unwrap();
+    };
+    for ext in ["csv", "jsonl", "arrow"] {
+        let dir = tempfile::tempdir().unwrap();
+        for (sub, n) in [
+            ("region=east/year=2020", 3i64),
+            ("region=west/year=2021", 2),
+        ] {
+            let d = dir.path().join(sub);
+            std::fs::create_dir_all(&d).unwrap();
+            let file = d.join(format!("data.{ext}"));
+            match ext {
+                "csv" => {
+                    let rows: String = (0..n).map(|i| format!("{i}\n")).collect();
+                    std::fs::write(file, format!("v\n{rows}")).unwrap();
+                }
+                "jsonl" => {
+                    let rows: String = (0..n).map(|i| format!("{{\"v\": {i}}}\n")).collect();
+                    std::fs::write(file, rows).unwrap();
+                }
+                _ => write_ipc(&file, n),
+            }
+        }
+        let opts = OpenOptions {
+            hive: true,
+            ..OpenOptions::default()
+        };
+        let (tx, rx) = mpsc::channel();
+        let mut app = App::new(tx, common::test_runtime());
+        pump_open_until_loaded(&mut app, &rx, vec![dir.path().to_path_buf()], opts);
+
+        let state = app.data_table_state.as_ref().expect("loaded");
+        assert_eq!(
+            state.partition_columns.as_deref(),
+            Some(&["region".to_string(), "year".to_string()][..]),
+            "{ext}"
+        );
+        let df = state
+            .lf
+            .clone()
+            .group_by([col("region")])
+            .agg([len()])
+            .sort(["region"], Default::default())
+            .collect()
+            .unwrap();
+        let counts: Vec<u32> = df
+            .column("len")
+            .unwrap()
+            .u32()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(counts, vec![3, 2], "{ext}");
+    }
+}
+
+/// Esc while the input is scanned on its worker cancels the load without quitting, and the
+/// scan's late result is dropped.
+#[test]
+fn test_esc_cancels_a_load_in_progress() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("big.csv");
+    std::fs::write(&path, "a,b\n1,x\n2,y\n").unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let area = Rect::new(0, 0, 100, 10);
+    let screen = |app: &mut App| {
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content().iter().map(|c| c.symbol()).collect::<String>()
+    };
+    // Before a worker runs, Esc can't be read, so it isn't offered
+    app.set_loading_phase("Scanning input", 10);
+    assert!(!screen(&mut app).contains("Esc: cancel"));
+
+    let mut next = Some(AppEvent::Open(vec![path], OpenOptions::default()));
+    while let Some(ev) = next.take() {
+        next = app.event(&ev);
+    }
+    // The scan runs on a worker, so keys still reach the app before its result does.
+    let esc = AppEvent::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(app.event(&esc).is_none(), "cancelling doesn't quit");
+    assert!(!app.is_busy());
+    assert!(screen(&mut app).contains("Loading cancelled"));
+
+    let ready = rx.recv_timeout(std::time::Duration::from_secs(30)).unwrap();
+    assert!(matches!(ready, AppEvent::BackgroundScanReady { .. }));
+    assert!(app.event(&ready).is_none());
+    assert!(app.data_table_state.is_none());
+}
+
+/// The scan worker reports the phase it is in, and the status line shows it while the load runs.
+#[test]
+fn test_scan_worker_reports_load_phases() {
+    use datui::sqlite_export::{write_sqlite, IfExists};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("shop.db");
+    let orders = df!("id" => [1i64, 2, 3]).unwrap();
+    write_sqlite(&orders, &path, "orders", IfExists::Fail).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let mut app = App::new(tx, common::test_runtime());
+    let mut next = Some(AppEvent::Open(vec![path], OpenOptions::default()));
+    while let Some(ev) = next.take() {
+        next = app.event(&ev);
+    }
+    let progress = rx.recv_timeout(std::time::Duration::from_secs(30)).unwrap();
+    match &progress {
+        AppEvent::BackgroundLoadProgress { phase, .. } => assert_eq!(phase, "Reading database"),
+        _ => panic!("the worker reports its phase before its result"),
+    }
+    app.event(&progress);
+    let area = Rect::new(0, 0, 100, 10);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    let screen: String = buf.content().iter().map(|c| c.symbol()).collect();
+    assert!(screen.contains("Reading database... (20%)"), "{screen}");
+
+    drain_events(&mut app, &rx);
+    assert_eq!(app.data_table_state.as_ref().unwrap().num_rows, 3);
+}
@@ -1,4 +1,5 @@
 use color_eyre::Result;
+use datui::sampling::SamplingStrategy;
 use datui::statistics::{
     compute_correlation_matrix, compute_correlation_pair, compute_statistics_with_options,
     ComputeOptions,
@@ -29,7 +30,8 @@ fn test_distribution_detection_normal() -> Result<()> {
         include_skewness_kurtosis_outliers: true,
         polars_streaming: true,
     };
-    let results = compute_statistics_with_options(&lf, Some(1000), 42, options)?;
+    let results =
+        compute_statistics_with_options(&lf, Some(1000), &SamplingStrategy::random(42), options)?;
 
     // Check that we have distribution analysis
     assert!(!results.distribution_analyses.is_empty());
@@ -121,7 +123,8 @@ fn test_outlier_detection() -> Result<()> {
         include_skewness_kurtosis_outliers: true,
         polars_streaming: true,
     };
-    let results = compute_statistics_with_options(&lf, Some(102), 42, options)?;
+    let results =
+        compute_statistics_with_options(&lf, Some(102), &SamplingStrategy::random(42), options)?;
 
     // Check that outliers are detected
     if let Some(dist_analysis) = results.distribution_analyses.first() {
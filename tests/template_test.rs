@@ -1,7 +1,11 @@
 use color_eyre::Result;
 use datui::config::ConfigManager;
 use datui::filter_modal::{FilterOperator, FilterStatement, LogicalOperator};
-use datui::template::{MatchCriteria, TemplateManager, TemplateSettings};
+use datui::pivot_melt_modal::{PivotAggregation, PivotSpec};
+use datui::template::{
+    MatchCriteria, TemplateManager, TemplateParameter, TemplateSettings, TEMPLATE_SCHEMA_VERSION,
+};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::SystemTime;
 
@@ -53,6 +57,13 @@ fn test_template_creation() -> Result<()> {
         locked_columns_count: 1,
         pivot: None,
         melt: None,
+        group_by: None,
+        parameters: Vec::new(),
+        column_formats: Default::default(),
+        computed_columns: Vec::new(),
+        column_casts: Vec::new(),
+        column_renames: Vec::new(),
+        saved_queries: Vec::new(),
     };
 
     let template = manager.create_template(
@@ -106,6 +117,13 @@ fn test_template_serialization() -> Result<()> {
         locked_columns_count: 0,
         pivot: None,
         melt: None,
+        group_by: None,
+        parameters: Vec::new(),
+        column_formats: Default::default(),
+        computed_columns: Vec::new(),
+        column_casts: Vec::new(),
+        column_renames: Vec::new(),
+        saved_queries: Vec::new(),
     };
 
     let template = manager.create_template(
@@ -177,6 +195,13 @@ fn test_template_relevance_exact_path() -> Result<()> {
         locked_columns_count: 0,
         pivot: None,
         melt: None,
+        group_by: None,
+        parameters: Vec::new(),
+        column_formats: Default::default(),
+        computed_columns: Vec::new(),
+        column_casts: Vec::new(),
+        column_renames: Vec::new(),
+        saved_queries: Vec::new(),
     };
 
     let mut manager = manager;
@@ -227,6 +252,13 @@ fn test_template_serialization_with_sql_and_fuzzy() -> Result<()> {
         locked_columns_count: 0,
         pivot: None,
         melt: None,
+        group_by: None,
+        parameters: Vec::new(),
+        column_formats: Default::default(),
+        computed_columns: Vec::new(),
+        column_casts: Vec::new(),
+        column_renames: Vec::new(),
+        saved_queries: Vec::new(),
     };
 
     let template =
@@ -245,3 +277,172 @@ fn test_template_serialization_with_sql_and_fuzzy() -> Result<()> {
     let _ = std::fs::remove_dir_all(&temp_dir);
     Ok(())
 }
+
+/// Parameters round-trip and their placeholders are substituted into the pivot spec.
+#[test]
+fn test_template_parameters_substitution() -> Result<()> {
+    let temp_dir = create_test_temp_dir()?;
+    let config = ConfigManager::with_dir(temp_dir.clone());
+
+    let mut manager = TemplateManager::new(&config)?;
+    let match_criteria = MatchCriteria {
+        exact_path: None,
+        relative_path: None,
+        path_pattern: None,
+        filename_pattern: Some("sales_*.csv".to_string()),
+        schema_columns: None,
+        schema_types: None,
+    };
+
+    let settings = TemplateSettings {
+        query: None,
+        sql_query: None,
+        fuzzy_query: None,
+        filters: Vec::new(),
+        sort_columns: Vec::new(),
+        sort_ascending: true,
+        column_order: vec!["region".to_string(), "${measure}_total".to_string()],
+        locked_columns_count: 0,
+        pivot: Some(PivotSpec {
+            index: vec!["region".to_string()],
+            pivot_column: "product".to_string(),
+            value_column: "${measure}".to_string(),
+            aggregation: PivotAggregation::Max,
+            sort_columns: None,
+        }),
+        melt: None,
+        group_by: None,
+        parameters: vec![TemplateParameter {
+            name: "measure".to_string(),
+            prompt: Some("Measure column".to_string()),
+            default: Some("revenue".to_string()),
+        }],
+        column_formats: Default::default(),
+        computed_columns: Vec::new(),
+        column_casts: Vec::new(),
+        column_renames: Vec::new(),
+        saved_queries: Vec::new(),
+    };
+
+    let template =
+        manager.create_template("monthly_sales".to_string(), None, match_criteria, settings)?;
+    manager.save_template(&template)?;
+    manager.load_templates()?;
+
+    let loaded = manager.get_template_by_name("monthly_sales").unwrap();
+    assert_eq!(loaded.settings.parameters.len(), 1);
+    assert_eq!(
+        loaded.settings.parameters[0].prompt_text(),
+        "Measure column"
+    );
+
+    let values = HashMap::from([("measure".to_string(), "units".to_string())]);
+    let resolved = loaded.with_parameters(&values);
+    let pivot = resolved.settings.pivot.unwrap();
+    assert_eq!(pivot.value_column, "units");
+    assert_eq!(pivot.pivot_column, "product");
+    assert_eq!(resolved.settings.column_order[1], "units_total");
+    // The stored template keeps its placeholder.
+    assert_eq!(
+        loaded.settings.pivot.as_ref().unwrap().value_column,
+        "${measure}"
+    );
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    Ok(())
+}
+
+#[test]
+fn test_deferred_manager_loads_on_first_use() -> Result<()> {
+    let temp_dir = create_test_temp_dir()?;
+    let config = ConfigManager::with_dir(temp_dir.clone());
+
+    let mut manager = TemplateManager::new(&config)?;
+    let match_criteria = MatchCriteria {
+        exact_path: Some(PathBuf::from("/test/deferred.csv")),
+        relative_path: None,
+        path_pattern: None,
+        filename_pattern: None,
+        schema_columns: None,
+        schema_types: None,
+    };
+    let settings = TemplateSettings {
+        query: None,
+        sql_query: None,
+        fuzzy_query: None,
+        filters: Vec::new(),
+        sort_columns: Vec::new(),
+        sort_ascending: true,
+        column_order: Vec::new(),
+        locked_columns_count: 0,
+        pivot: None,
+        melt: None,
+        group_by: None,
+        parameters: Vec::new(),
+        column_formats: Default::default(),
+        computed_columns: Vec::new(),
+        column_casts: Vec::new(),
+        column_renames: Vec::new(),
+        saved_queries: Vec::new(),
+    };
+    manager.create_template("deferred".to_string(), None, match_criteria, settings)?;
+
+    // Nothing is read from disk until the first ensure_loaded().
+    let mut deferred = TemplateManager::deferred(&config);
+    assert!(deferred.all_templates().is_empty());
+    deferred.ensure_loaded();
+    assert!(deferred.template_exists("deferred"));
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    Ok(())
+}
+
+#[test]
+fn test_unversioned_template_loads_without_rewrite() -> Result<()> {
+    let temp_dir = create_test_temp_dir()?;
+    let config = ConfigManager::with_dir(temp_dir.clone());
+    let templates_dir = temp_dir.join("templates");
+    std::fs::create_dir_all(&templates_dir)?;
+
+    // Written before template files carried a schema_version.
+    let legacy = r#"{
+  "id": "old",
+  "name": "legacy",
+  "description": null,
+  "created": 1700000000,
+  "usage_count": 2,
+  "match_criteria": {},
+  "settings": {
+    "filters": [],
+    "sort_columns": ["a"],
+    "sort_ascending": true,
+    "column_order": [],
+    "locked_columns_count": 0
+  }
+}"#;
+    let legacy_path = templates_dir.join("template_old.json");
+    std::fs::write(&legacy_path, legacy)?;
+    let newer = legacy.replacen(
+        "{",
+        &format!("{{\n  \"schema_version\": {},", TEMPLATE_SCHEMA_VERSION + 1),
+        1,
+    );
+    std::fs::write(templates_dir.join("template_newer.json"), newer)?;
+
+    let manager = TemplateManager::new(&config)?;
+    let template = manager
+        .get_template_by_id("old")
+        .expect("unversioned template");
+    assert_eq!(template.settings.sort_columns, vec!["a".to_string()]);
+    assert_eq!(manager.all_templates().len(), 1);
+    assert_eq!(manager.broken_templates.len(), 1);
+    assert_eq!(manager.broken_templates[0].filename, "template_newer");
+    assert!(manager.broken_templates[0].error.contains("newer version"));
+
+    // Same format as the current one: the file is left alone and no backup is written.
+    assert_eq!(std::fs::read_to_string(&legacy_path)?, legacy);
+    assert_eq!(std::fs::read_dir(&templates_dir)?.count(), 2);
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    Ok(())
+}